@@ -14,7 +14,7 @@ fn main() {
     );
     print(s, &args[5]);
     // just to prevent the compiler from removing dead code
-    _ = float::sum3(1f64, 2f64, 3f64);
+    _ = (float::sum3(1f64, 2f64, 3f64), float::sum3_f32(1f32, 2f32, 3f32));
 }
 
 fn sum2(a: i64, b: i64) -> i64 {
@@ -43,4 +43,15 @@ pub mod float {
         let ab = sum2(a, b);
         sum2(ab, c)
     }
+
+    #[no_mangle]
+    pub fn sum2_f32(a: f32, b: f32) -> f32 {
+        a + b
+    }
+
+    #[no_mangle]
+    pub fn sum3_f32(a: f32, b: f32, c: f32) -> f32 {
+        let ab = sum2_f32(a, b);
+        sum2_f32(ab, c)
+    }
 }
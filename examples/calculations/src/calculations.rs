@@ -112,4 +112,36 @@ pub fn main() {
     calculation_local_value_mt();
     calculation_with_complex_types();
     calculate_from_arg(1);
+    calculation_through_pointer();
+}
+
+#[repr(C)]
+struct Counter {
+    id: u64,
+    generation: u64,
+    value: u32,
+}
+
+static mut ACTIVE_COUNTER: *mut Counter = std::ptr::null_mut();
+
+fn calculation_through_pointer() {
+    let mut first = Box::new(Counter {
+        id: 1,
+        generation: 1,
+        value: 1,
+    });
+    let mut second = Box::new(Counter {
+        id: 1,
+        generation: 2,
+        value: 10,
+    });
+    unsafe {
+        ACTIVE_COUNTER = &mut *first;
+        (*ACTIVE_COUNTER).value += 1;
+        ACTIVE_COUNTER = &mut *second;
+        (*ACTIVE_COUNTER).value += 1;
+        ACTIVE_COUNTER = std::ptr::null_mut();
+    }
+
+    println!("{} {}", first.value, second.value);
 }
@@ -0,0 +1,37 @@
+use std::sync::mpsc;
+
+fn main() {
+    let (tx, rx) = mpsc::channel::<i32>();
+    tx.send(1).unwrap();
+    tx.send(2).unwrap();
+    tx.send(3).unwrap();
+    rx.recv().unwrap();
+
+    let (long_tx, long_rx) = mpsc::channel::<u64>();
+    for i in 0..40 {
+        long_tx.send(i).unwrap();
+    }
+
+    let (sync_tx, sync_rx) = mpsc::sync_channel::<String>(4);
+    sync_tx.send("a".to_string()).unwrap();
+    sync_tx.send("b".to_string()).unwrap();
+
+    let (rendezvous_tx, rendezvous_rx) = mpsc::sync_channel::<u8>(0);
+    drop(rendezvous_rx);
+
+    let (closed_tx, closed_rx) = mpsc::channel::<i32>();
+    closed_tx.send(42).unwrap();
+    drop(closed_tx);
+
+    let (cb_tx, cb_rx) = crossbeam_channel::bounded::<i32>(2);
+    cb_tx.send(10).unwrap();
+
+    let (cb_list_tx, cb_list_rx) = crossbeam_channel::unbounded::<&str>();
+    cb_list_tx.send("x").unwrap();
+    drop(cb_list_tx);
+
+    println!("channels ready");
+
+    drop((tx, rx, long_tx, long_rx, sync_tx, sync_rx, rendezvous_tx, closed_rx));
+    drop((cb_tx, cb_rx, cb_list_rx));
+}
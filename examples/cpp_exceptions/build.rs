@@ -0,0 +1,9 @@
+fn main() {
+    println!("cargo:rerun-if-changed=src/parser.cpp");
+    cc::Build::new()
+        .cpp(true)
+        .file("src/parser.cpp")
+        .debug(true)
+        .opt_level(0)
+        .compile("parser");
+}
@@ -0,0 +1,10 @@
+extern "C" {
+    fn handle(n: i32) -> i32;
+}
+
+fn main() {
+    for n in [2, 3] {
+        let code = unsafe { handle(n) };
+        println!("handled: {code}");
+    }
+}
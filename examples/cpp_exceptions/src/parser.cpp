@@ -0,0 +1,28 @@
+#include <cstdio>
+#include <stdexcept>
+
+namespace app {
+struct ParseError {
+    int code;
+};
+}
+
+static void parse(int n) {
+    if (n % 2 == 0) {
+        throw std::runtime_error("even number");
+    }
+    throw app::ParseError{n};
+}
+
+extern "C" int handle(int n) {
+    try {
+        parse(n);
+    } catch (const std::runtime_error& e) {
+        std::printf("caught runtime_error: %s\n", e.what());
+        return 1;
+    } catch (const app::ParseError& e) {
+        std::printf("caught ParseError: %d\n", e.code);
+        return 2;
+    }
+    return 0;
+}
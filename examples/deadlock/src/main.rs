@@ -0,0 +1,59 @@
+use std::sync::{Arc, Barrier, Mutex};
+use std::{env, thread};
+
+fn transfer(from: &Mutex<i32>, to: &Mutex<i32>, barrier: &Barrier) {
+    let mut from_guard = from.lock().unwrap();
+    barrier.wait();
+    let mut to_guard = to.lock().unwrap();
+    *from_guard -= 1;
+    *to_guard += 1;
+}
+
+fn transfer_pl(
+    from: &parking_lot::Mutex<i32>,
+    to: &parking_lot::Mutex<i32>,
+    barrier: &Barrier,
+) {
+    let mut from_guard = from.lock();
+    barrier.wait();
+    let mut to_guard = to.lock();
+    *from_guard -= 1;
+    *to_guard += 1;
+}
+
+fn std_deadlock() {
+    let a = Arc::new(Mutex::new(10));
+    let b = Arc::new(Mutex::new(10));
+    let barrier = Arc::new(Barrier::new(2));
+
+    let t1 = {
+        let (a, b, barrier) = (a.clone(), b.clone(), barrier.clone());
+        thread::spawn(move || transfer(&a, &b, &barrier))
+    };
+    let t2 = thread::spawn(move || transfer(&b, &a, &barrier));
+
+    t1.join().unwrap();
+    t2.join().unwrap();
+}
+
+fn parking_lot_deadlock() {
+    let a = Arc::new(parking_lot::Mutex::new(10));
+    let b = Arc::new(parking_lot::Mutex::new(10));
+    let barrier = Arc::new(Barrier::new(2));
+
+    let t1 = {
+        let (a, b, barrier) = (a.clone(), b.clone(), barrier.clone());
+        thread::spawn(move || transfer_pl(&a, &b, &barrier))
+    };
+    let t2 = thread::spawn(move || transfer_pl(&b, &a, &barrier));
+
+    t1.join().unwrap();
+    t2.join().unwrap();
+}
+
+fn main() {
+    match env::args().nth(1).as_deref() {
+        Some("parking_lot") => parking_lot_deadlock(),
+        _ => std_deadlock(),
+    }
+}
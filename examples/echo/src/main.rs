@@ -0,0 +1,12 @@
+use std::io::BufRead;
+
+fn main() {
+    let mut count = 0;
+    for line in std::io::stdin().lock().lines() {
+        let line = line.unwrap();
+        count += 1;
+        println!("echo: {line}");
+    }
+
+    println!("lines read: {count}");
+}
@@ -0,0 +1,31 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Barrier};
+use std::thread;
+
+/// Number of writes of each writer thread.
+const WRITES: u64 = 100;
+
+static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+fn writer(start: &Barrier) {
+    start.wait();
+    for _ in 0..WRITES {
+        COUNTER.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+fn main() {
+    let start = Arc::new(Barrier::new(2));
+    let writers: Vec<_> = (0..2)
+        .map(|_| {
+            let start = start.clone();
+            thread::spawn(move || writer(&start))
+        })
+        .collect();
+
+    for writer in writers {
+        writer.join().unwrap();
+    }
+
+    println!("counter: {}", COUNTER.load(Ordering::Relaxed));
+}
@@ -13,5 +13,10 @@ fn infinite_inc(i: u64) -> u64 {
 
 fn main() {
     println!("{}", fibonacci(19));
+    println!("{}", apply(fibonacci, 5));
     println!("{}", infinite_inc(1));
 }
+
+fn apply<F: FnOnce(u64) -> u64>(f: F, v: u64) -> u64 {
+    f(v)
+}
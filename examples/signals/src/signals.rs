@@ -1,4 +1,5 @@
-use std::sync::atomic::{AtomicBool, Ordering};
+use nix::sys::signal::{raise, sigaction, SaFlags, SigAction, SigHandler, SigSet, Signal};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
@@ -62,6 +63,23 @@ fn multi_thread_signal_2() {
     println!("threads join");
 }
 
+static HANDLED: AtomicUsize = AtomicUsize::new(0);
+
+extern "C" fn on_sigusr1(_: nix::libc::c_int) {
+    HANDLED.fetch_add(1, Ordering::Relaxed);
+}
+
+fn custom_handler() {
+    let action = SigAction::new(
+        SigHandler::Handler(on_sigusr1),
+        SaFlags::empty(),
+        SigSet::empty(),
+    );
+    unsafe { sigaction(Signal::SIGUSR1, &action) }.unwrap();
+    raise(Signal::SIGUSR1).unwrap();
+    println!("handled: {}", HANDLED.load(Ordering::Relaxed));
+}
+
 fn main() {
     let args: Vec<String> = std::env::args().collect();
 
@@ -69,6 +87,7 @@ fn main() {
         "single_thread" => single_thread_signal(),
         "multi_thread" => multi_thread_signal(),
         "multi_thread_multi_signal" => multi_thread_signal_2(),
+        "custom_handler" => custom_handler(),
         _ => panic!("unknown opt"),
     }
 }
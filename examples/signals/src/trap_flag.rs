@@ -0,0 +1,28 @@
+use std::arch::asm;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Set the trap flag (TF) for a couple of instructions, CPU raises a single step trap
+/// (SIGTRAP with TRAP_TRACE code) after each of them, like a debugger does with PTRACE_SINGLESTEP.
+fn trap_flag() {
+    unsafe {
+        asm!(
+            "pushfq",
+            "or qword ptr [rsp], 0x100",
+            "popfq",
+            "nop",
+            "pushfq",
+            "and qword ptr [rsp], ~0x100",
+            "popfq",
+        );
+    }
+}
+
+pub fn main() {
+    let trapped = Arc::new(AtomicBool::new(false));
+    signal_hook::flag::register(signal_hook::consts::SIGTRAP, Arc::clone(&trapped)).unwrap();
+
+    trap_flag();
+
+    println!("trapped: {}", trapped.load(Ordering::Relaxed));
+}
@@ -0,0 +1,8 @@
+use std::process::Command;
+
+fn main() {
+    let mut child = Command::new("sleep").arg("600").spawn().unwrap();
+    println!("child spawned: {}", child.id());
+    let status = child.wait().unwrap();
+    println!("child exit: {status}");
+}
@@ -550,4 +550,64 @@ pub fn main() {
     inner_static();
     shadowing();
     uuid();
+    enum_discriminants();
+    iterators();
+    platform_strings();
+    called_closure();
+}
+
+fn enum_discriminants() {
+    #[allow(dead_code)]
+    #[repr(u8)]
+    enum HighDiscr {
+        Low(u32) = 1,
+        High(u32) = 200,
+    }
+
+    #[allow(dead_code)]
+    #[repr(i16)]
+    enum NegativeDiscr {
+        Neg(u8) = -3,
+        Pos(u8) = 3,
+    }
+
+    let high = HighDiscr::High(5);
+    let low = HighDiscr::Low(6);
+    let neg = NegativeDiscr::Neg(7);
+    let pos = NegativeDiscr::Pos(8);
+    let niche = Some(NegativeDiscr::Pos(9));
+    let none_niche: Option<NegativeDiscr> = None;
+
+    let nop: Option<u8> = None;
+}
+
+fn iterators() {
+    let v = vec![1, 2, 3, 4];
+    let arr = [10u8, 20, 30];
+    let mut map = v.clone().into_iter().map(|x| x * 2);
+    let filter = arr.iter().filter(|x| **x > 10);
+    let mut enumerate = v.iter().enumerate();
+    let chain = (0..3).chain(5..7);
+    map.next();
+    enumerate.next();
+
+    let nop: Option<u8> = None;
+}
+
+fn platform_strings() {
+    let path = std::path::PathBuf::from("/tmp/file.txt");
+    let os_string = std::ffi::OsString::from("os string");
+    let c_string = std::ffi::CString::new("c string").unwrap();
+    let c_str = c_string.as_c_str();
+    let path_ref = path.as_path();
+    let os_str = os_string.as_os_str();
+
+    let nop: Option<u8> = None;
+}
+
+fn called_closure() {
+    let inc = |a: i32| -> i32 { a + 1 };
+    let two = inc(1);
+
+    let nop: Option<u8> = None;
 }
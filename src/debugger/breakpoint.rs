@@ -1,17 +1,25 @@
-use crate::debugger::address::{Address, RelocatedAddress};
+use crate::debugger::address::{Address, GlobalAddress, RelocatedAddress};
 use crate::debugger::debugee::dwarf::unit::PlaceDescriptorOwned;
 use crate::debugger::debugee::dwarf::DebugInformation;
-use crate::debugger::debugee::Debugee;
+use crate::debugger::debugee::dwarf::DwarfUnwinder;
+use crate::debugger::debugee::{Debugee, Location};
 use crate::debugger::error::Error;
-use crate::debugger::error::Error::{NoDebugInformation, NoSuitablePlace, PlaceNotFound};
-use crate::debugger::Debugger;
-use nix::libc::c_void;
-use nix::sys;
-use nix::unistd::Pid;
+use crate::debugger::error::Error::{
+    AmbiguousFile, ColumnNotFound, NoCodeAtLine, NoDebugInformation, NoSuitablePlace, PlaceNotFound,
+};
+use crate::debugger::register::debug::{DebugRegisterNumber, HardwareDebugState};
+use crate::debugger::register::Register;
+use crate::debugger::variable::{SupportedScalar, VariableIR};
+use crate::debugger::Pid;
+use crate::debugger::{sys, Debugger, ReturnValue};
+use crate::{disable_when_not_stared, weak_error};
+use log::{error, warn};
+use regex::Regex;
 use std::borrow::Cow;
 use std::cell::Cell;
-use std::collections::HashMap;
-use std::fmt::{Debug, Formatter};
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Debug, Display, Formatter};
+use std::fs;
 use std::mem;
 use std::path::PathBuf;
 use std::rc::Rc;
@@ -22,10 +30,21 @@ enum BrkptsToAddRequest {
     Uninit(Vec<UninitBreakpoint>),
 }
 
+impl BrkptsToAddRequest {
+    fn numbers(&self) -> Vec<u32> {
+        match self {
+            BrkptsToAddRequest::Init(brkpts) => brkpts.iter().map(|b| b.number).collect(),
+            BrkptsToAddRequest::Uninit(brkpts) => brkpts.iter().map(|b| b.number).collect(),
+        }
+    }
+}
+
 /// Parameters for construct a transparent breakpoint.
 pub enum CreateTransparentBreakpointRequest {
     Line(String, u64, Rc<dyn Fn(&mut Debugger)>),
     Function(String, Rc<dyn Fn(&mut Debugger)>),
+    Symbol(String, Rc<dyn Fn(&mut Debugger)>),
+    Address(RelocatedAddress, Rc<dyn Fn(&mut Debugger)>),
 }
 
 impl CreateTransparentBreakpointRequest {
@@ -50,15 +69,134 @@ impl CreateTransparentBreakpointRequest {
         Self::Line(file.to_string(), line, Rc::new(cb))
     }
 
+    /// Create request for transparent breakpoint at the first instruction of a symbol.
+    /// Unlike [`CreateTransparentBreakpointRequest::function`] function prolog is not skipped
+    /// and debug information is not required, so a callback may read function arguments
+    /// directly from registers.
+    ///
+    /// # Arguments
+    ///
+    /// * `regex`: regular expression for a (demangled) symbol name
+    /// * `cb`: callback that invoked when breakpoint is heat
+    pub fn symbol(regex: impl ToString, cb: impl Fn(&mut Debugger) + 'static) -> Self {
+        Self::Symbol(regex.to_string(), Rc::new(cb))
+    }
+
+    /// Create request for transparent breakpoint at address (for example, at a return address
+    /// of a function).
+    ///
+    /// # Arguments
+    ///
+    /// * `addr`: instruction address
+    /// * `cb`: callback that invoked when breakpoint is heat
+    pub fn address(addr: RelocatedAddress, cb: impl Fn(&mut Debugger) + 'static) -> Self {
+        Self::Address(addr, Rc::new(cb))
+    }
+
     /// Return underline callback.
     fn callback(&self) -> Rc<dyn Fn(&mut Debugger)> {
         match self {
             CreateTransparentBreakpointRequest::Line(_, _, cb) => cb.clone(),
             CreateTransparentBreakpointRequest::Function(_, cb) => cb.clone(),
+            CreateTransparentBreakpointRequest::Symbol(_, cb) => cb.clone(),
+            CreateTransparentBreakpointRequest::Address(_, cb) => cb.clone(),
         }
     }
 }
 
+/// Condition of a breakpoint that stops a program when a function returns.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReturnCondition {
+    /// Stop on each return.
+    Any,
+    /// Stop if a returned value equals to the given one.
+    Equal(ReturnValue),
+}
+
+impl Display for ReturnCondition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReturnCondition::Any => Ok(()),
+            ReturnCondition::Equal(value) => write!(f, " == {value}"),
+        }
+    }
+}
+
+impl ReturnCondition {
+    /// Return true if a returned value satisfies the condition. Unknown value (`None`)
+    /// can't be checked, so it always satisfies the condition.
+    fn is_satisfied(&self, value: Option<&VariableIR>) -> bool {
+        const EPS: f64 = 0.0000001f64;
+
+        let ReturnCondition::Equal(expected) = *self else {
+            return true;
+        };
+        let Some(value) = value else {
+            return true;
+        };
+
+        match (value, expected) {
+            (VariableIR::Scalar(scalar), ReturnValue::Int(expected)) => match scalar.value {
+                Some(SupportedScalar::I128(v)) => v == expected as i128,
+                Some(SupportedScalar::U128(v)) => v == expected as u128,
+                Some(SupportedScalar::Bool(v)) => v as i64 == expected,
+                Some(SupportedScalar::Char(v)) => v as i64 == expected,
+                _ => scalar.try_as_number() == Some(expected),
+            },
+            (VariableIR::Scalar(scalar), ReturnValue::Float(expected)) => match scalar.value {
+                Some(SupportedScalar::F32(v)) => (v as f64 - expected).abs() < EPS,
+                Some(SupportedScalar::F64(v)) => (v - expected).abs() < EPS,
+                _ => false,
+            },
+            (VariableIR::Pointer(ptr), ReturnValue::Int(expected)) => {
+                ptr.value.map(|p| p as usize as i64) == Some(expected)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// C++ exception handling event that stops a program (see [`Debugger::set_catchpoint`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CatchEvent {
+    /// Exception is thrown (`__cxa_throw` is called).
+    Throw,
+    /// Exception is caught by a handler (`__cxa_begin_catch` is called).
+    Catch,
+}
+
+impl CatchEvent {
+    /// Return name of the C++ runtime function called on this event.
+    pub fn function_name(&self) -> &'static str {
+        match self {
+            CatchEvent::Throw => "__cxa_throw",
+            CatchEvent::Catch => "__cxa_begin_catch",
+        }
+    }
+}
+
+impl Display for CatchEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CatchEvent::Throw => f.write_str("throw"),
+            CatchEvent::Catch => f.write_str("catch"),
+        }
+    }
+}
+
+/// A function frame with an on-return breakpoint, frame is tracked until the function returns.
+struct PendingReturn {
+    /// Number of on-return breakpoint.
+    number: u32,
+    /// Location at function start.
+    callee: Location,
+    /// Address where the function returns.
+    ret_addr: RelocatedAddress,
+    /// Value of a stack pointer after return (CFA of a function frame), distinguishes
+    /// recursive calls of the same function.
+    sp: u64,
+}
+
 impl Debugger {
     /// Create and enable breakpoint at debugee address space
     ///
@@ -74,30 +212,31 @@ impl Debugger {
         &mut self,
         addr: RelocatedAddress,
     ) -> Result<BreakpointView, Error> {
-        if self.debugee.is_in_progress() {
-            let dwarf = self
+        let inferior = self.inferior_mut();
+        if inferior.debugee.is_in_progress() {
+            let dwarf = inferior
                 .debugee
                 .debug_info(addr)
                 .map_err(|_| NoDebugInformation("current place"))?;
-            let global_addr = addr.into_global(&self.debugee)?;
+            let global_addr = addr.into_global(&inferior.debugee)?;
 
             let place = dwarf
                 .find_place_from_pc(global_addr)?
                 .map(|p| p.to_owned())
                 .ok_or(PlaceNotFound(global_addr))?;
 
-            return self.breakpoints.add_and_enable(Breakpoint::new(
+            return inferior.breakpoints.add_and_enable(Breakpoint::new(
                 dwarf.pathname(),
                 addr,
-                self.process.pid(),
+                inferior.process.pid(),
                 Some(place),
             ));
         }
 
-        Ok(self.breakpoints.add_uninit(UninitBreakpoint::new(
+        Ok(inferior.breakpoints.add_uninit(UninitBreakpoint::new(
             None::<PathBuf>,
             Address::Relocated(addr),
-            self.process.pid(),
+            inferior.process.pid(),
             None,
         )))
     }
@@ -108,7 +247,7 @@ impl Debugger {
     ///
     /// * `addr`: breakpoint address
     pub fn remove_breakpoint(&mut self, addr: Address) -> Result<Option<BreakpointView>, Error> {
-        self.breakpoints.remove_by_addr(addr)
+        self.inferior_mut().breakpoints.remove_by_addr(addr)
     }
 
     /// Disable and remove a breakpoint by it number.
@@ -120,22 +259,24 @@ impl Debugger {
         &mut self,
         number: u32,
     ) -> Result<Option<BreakpointView>, Error> {
-        self.breakpoints.remove_by_num(number)
+        self.inferior_mut().breakpoints.remove_by_num(number)
     }
 
     fn create_breakpoint_at_places(
         &self,
         places: Vec<(&DebugInformation, Vec<PlaceDescriptorOwned>)>,
     ) -> Result<BrkptsToAddRequest, Error> {
-        let brkpts_to_add = if self.debugee.is_in_progress() {
+        let brkpts_to_add = if self.inferior().debugee.is_in_progress() {
             let mut to_add = Vec::new();
             for (dwarf, places) in places {
                 for place in places {
-                    let addr = place.address.relocate_to_segment(&self.debugee, dwarf)?;
+                    let addr = place
+                        .address
+                        .relocate_to_segment(&self.inferior().debugee, dwarf)?;
                     to_add.push(Breakpoint::new(
                         dwarf.pathname(),
                         addr,
-                        self.process.pid(),
+                        self.inferior().process.pid(),
                         Some(place),
                     ));
                 }
@@ -148,7 +289,7 @@ impl Debugger {
                     to_add.push(UninitBreakpoint::new(
                         Some(dwarf.pathname()),
                         Address::Global(place.address),
-                        self.process.pid(),
+                        self.inferior().process.pid(),
                         Some(place),
                     ));
                 }
@@ -167,14 +308,15 @@ impl Debugger {
                 let mut result_addrs = Vec::with_capacity(init_brkpts.len());
                 for brkpt in init_brkpts {
                     let addr = brkpt.addr;
-                    self.breakpoints.add_and_enable(brkpt)?;
+                    self.inferior_mut().breakpoints.add_and_enable(brkpt)?;
                     result_addrs.push(addr);
                 }
                 result_addrs
                     .iter()
                     .map(|addr| {
                         BreakpointView::from(
-                            self.breakpoints
+                            self.inferior()
+                                .breakpoints
                                 .get_enabled(*addr)
                                 .expect("breakpoint must exists"),
                         )
@@ -184,14 +326,15 @@ impl Debugger {
             BrkptsToAddRequest::Uninit(uninit_brkpts) => {
                 let mut result_addrs = Vec::with_capacity(uninit_brkpts.len());
                 for brkpt in uninit_brkpts {
-                    let addr = self.breakpoints.add_uninit(brkpt).addr;
+                    let addr = self.inferior_mut().breakpoints.add_uninit(brkpt).addr;
                     result_addrs.push(addr);
                 }
                 result_addrs
                     .iter()
                     .map(|addr| {
                         BreakpointView::from(
-                            self.breakpoints
+                            self.inferior()
+                                .breakpoints
                                 .get_disabled(*addr)
                                 .expect("breakpoint must exists"),
                         )
@@ -208,10 +351,12 @@ impl Debugger {
         places: &[(&DebugInformation, Vec<PlaceDescriptorOwned>)],
     ) -> Result<impl Iterator<Item = Address>, Error> {
         let mut init_addresses_to_remove: Vec<Address> = vec![];
-        if self.debugee.is_in_progress() {
+        if self.inferior().debugee.is_in_progress() {
             for (dwarf, places) in places.iter() {
                 for place in places {
-                    let addr = place.address.relocate_to_segment(&self.debugee, dwarf)?;
+                    let addr = place
+                        .address
+                        .relocate_to_segment(&self.inferior().debugee, dwarf)?;
                     init_addresses_to_remove.push(Address::Relocated(addr));
                 }
             }
@@ -238,7 +383,7 @@ impl Debugger {
     ) -> Result<Vec<BreakpointView>, Error> {
         let mut result = vec![];
         for to_rem in addresses {
-            if let Some(view) = self.breakpoints.remove_by_addr(to_rem)? {
+            if let Some(view) = self.inferior_mut().breakpoints.remove_by_addr(to_rem)? {
                 result.push(view)
             }
         }
@@ -249,7 +394,7 @@ impl Debugger {
         &self,
         tpl: &str,
     ) -> Result<Vec<(&DebugInformation, Vec<PlaceDescriptorOwned>)>, Error> {
-        let dwarfs = self.debugee.debug_info_all();
+        let dwarfs = self.inferior().debugee.debug_info_all();
 
         dwarfs
             .iter()
@@ -262,6 +407,8 @@ impl Debugger {
     }
 
     /// Create and enable breakpoint at debugee address space on the following function start.
+    /// If template matches several functions (for example, all monomorphizations
+    /// of a generic function) then created breakpoints are united into a group.
     ///
     /// # Arguments
     ///
@@ -278,9 +425,287 @@ impl Debugger {
         }
 
         let brkpts = self.create_breakpoint_at_places(places)?;
+        let numbers = brkpts.numbers();
+        if numbers.len() > 1 {
+            self.inferior_mut().breakpoints.add_group(template, numbers);
+        }
         self.add_breakpoints(brkpts)
     }
 
+    /// Create and enable breakpoints that stop a program when the following functions return
+    /// (and a returned value satisfies a condition). Breakpoints are set at functions start,
+    /// a frame is tracked since then and debugee stops at a return address in a caller.
+    ///
+    /// # Arguments
+    ///
+    /// * `template`: template for searching functions
+    /// * `condition`: condition for a returned value
+    pub fn set_breakpoint_on_return(
+        &mut self,
+        template: &str,
+        condition: ReturnCondition,
+    ) -> Result<Vec<BreakpointView<'_>>, Error> {
+        let numbers: Vec<_> = self
+            .set_breakpoint_at_fn(template)?
+            .iter()
+            .map(|view| view.number)
+            .collect();
+        for &number in &numbers {
+            self.inferior_mut()
+                .breakpoints
+                .on_return
+                .insert(number, condition);
+        }
+        Ok(self.inferior().breakpoints.snapshot_of(&numbers))
+    }
+
+    /// Return addresses of a function symbol in all loaded object files
+    /// (a symbol is searched in symbol tables, debug information is not required).
+    ///
+    /// # Arguments
+    ///
+    /// * `name`: exact symbol name
+    pub(super) fn search_function_symbol(
+        &self,
+        name: &str,
+    ) -> Vec<(&DebugInformation, GlobalAddress)> {
+        self.inferior()
+            .debugee
+            .debug_info_all()
+            .into_iter()
+            .filter_map(|dwarf| Some((dwarf, dwarf.find_function_symbol(name)?.addr)))
+            .collect()
+    }
+
+    /// Create and enable breakpoints that stop a program on C++ exception handling events.
+    /// Breakpoints are set at the first instruction of C++ runtime functions
+    /// (`__cxa_throw` or `__cxa_begin_catch`), so debug information for C++ runtime
+    /// is not required.
+    ///
+    /// # Arguments
+    ///
+    /// * `event`: exception handling event
+    ///
+    /// # Errors
+    ///
+    /// Return [`NoSuitablePlace`] if C++ runtime is not loaded yet.
+    pub fn set_catchpoint(&mut self, event: CatchEvent) -> Result<Vec<BreakpointView<'_>>, Error> {
+        let symbols = self.search_function_symbol(event.function_name());
+        if symbols.is_empty() {
+            return Err(NoSuitablePlace);
+        }
+
+        let brkpts = if self.inferior().debugee.is_in_progress() {
+            let mut to_add = Vec::with_capacity(symbols.len());
+            for (dwarf, addr) in symbols {
+                let addr = addr.relocate_to_segment(&self.inferior().debugee, dwarf)?;
+                to_add.push(Breakpoint::new(
+                    dwarf.pathname(),
+                    addr,
+                    self.inferior().process.pid(),
+                    None,
+                ));
+            }
+            BrkptsToAddRequest::Init(to_add)
+        } else {
+            let to_add = symbols
+                .into_iter()
+                .map(|(dwarf, addr)| {
+                    UninitBreakpoint::new(
+                        Some(dwarf.pathname()),
+                        Address::Global(addr),
+                        self.inferior().process.pid(),
+                        None,
+                    )
+                })
+                .collect();
+            BrkptsToAddRequest::Uninit(to_add)
+        };
+
+        let numbers: Vec<_> = self
+            .add_breakpoints(brkpts)?
+            .iter()
+            .map(|view| view.number)
+            .collect();
+        for &number in &numbers {
+            self.inferior_mut()
+                .breakpoints
+                .catchpoints
+                .insert(number, event);
+        }
+        Ok(self.inferior().breakpoints.snapshot_of(&numbers))
+    }
+
+    /// Remove all breakpoints that stop a program on C++ exception handling event.
+    ///
+    /// # Arguments
+    ///
+    /// * `event`: exception handling event
+    pub fn remove_catchpoints(
+        &mut self,
+        event: CatchEvent,
+    ) -> Result<Vec<BreakpointView<'_>>, Error> {
+        let numbers: Vec<_> = self
+            .inferior()
+            .breakpoints
+            .catchpoints
+            .iter()
+            .filter_map(|(&number, &ev)| (ev == event).then_some(number))
+            .collect();
+
+        let mut removed = Vec::with_capacity(numbers.len());
+        for number in numbers {
+            if let Some(view) = self.inferior_mut().breakpoints.remove_by_num(number)? {
+                removed.push(view);
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Start to track a frame of a function with on-return breakpoint, must be called
+    /// when the on-return breakpoint (at function start) is hit.
+    /// A companion breakpoint is set at the return address.
+    ///
+    /// # Arguments
+    ///
+    /// * `number`: on-return breakpoint number
+    pub(super) fn track_function_return(&mut self, number: u32) -> Result<(), Error> {
+        let ctx = self.exploration_ctx();
+        let callee = ctx.location();
+        let caller_registers =
+            DwarfUnwinder::new(&self.inferior().debugee).caller_registers(ctx)?;
+        let ret_addr = RelocatedAddress::from(caller_registers.value(gimli::Register(16))?);
+        let sp = caller_registers.value(gimli::Register(7))?;
+
+        // a breakpoint already set at the return address is used instead of a companion
+        let inferior = self.inferior_mut();
+        if inferior.breakpoints.get_enabled(ret_addr).is_none() {
+            let debug_info = inferior.debugee.debug_info(callee.pc)?;
+            inferior
+                .breakpoints
+                .add_and_enable(Breakpoint::new_function_return(
+                    debug_info.pathname(),
+                    ret_addr,
+                    callee.pid,
+                ))?;
+        }
+        self.inferior_mut()
+            .breakpoints
+            .pending_returns
+            .push(PendingReturn {
+                number,
+                callee,
+                ret_addr,
+                sp,
+            });
+        Ok(())
+    }
+
+    /// Check that a thread returns from a tracked function frame, must be called
+    /// when debugee stops at any breakpoint. Return on-return breakpoint number and
+    /// a returned value if a program must be stopped (a condition is satisfied).
+    ///
+    /// # Arguments
+    ///
+    /// * `pid`: stopped thread
+    /// * `pc`: address of a breakpoint
+    pub(super) fn take_function_return(
+        &mut self,
+        pid: Pid,
+        pc: RelocatedAddress,
+    ) -> Result<Option<(u32, Option<VariableIR>)>, Error> {
+        if !self
+            .inferior()
+            .breakpoints
+            .pending_returns
+            .iter()
+            .any(|r| r.ret_addr == pc)
+        {
+            return Ok(None);
+        }
+        let sp = self
+            .inferior()
+            .debugee
+            .tracee_ctl()
+            .registers(pid)?
+            .value(Register::Rsp);
+        let Some(idx) = self
+            .inferior()
+            .breakpoints
+            .pending_returns
+            .iter()
+            .position(|r| r.ret_addr == pc && r.callee.pid == pid && r.sp == sp)
+        else {
+            return Ok(None);
+        };
+        let pending = self.inferior_mut().breakpoints.pending_returns.remove(idx);
+
+        let companion_unused = !self
+            .inferior()
+            .breakpoints
+            .pending_returns
+            .iter()
+            .any(|r| r.ret_addr == pc);
+        let is_companion = self
+            .inferior()
+            .breakpoints
+            .get_enabled(pc)
+            .is_some_and(|brkpt| brkpt.r#type == BrkptType::FunctionReturn);
+        if companion_unused && is_companion {
+            self.inferior_mut()
+                .breakpoints
+                .remove_by_addr(Address::Relocated(pc))?;
+        }
+
+        // breakpoint may be removed or disabled while a function is executed
+        let Some(&condition) = self.inferior().breakpoints.on_return.get(&pending.number) else {
+            return Ok(None);
+        };
+        let active = self
+            .inferior()
+            .breakpoints
+            .breakpoints
+            .values()
+            .any(|b| b.number == pending.number);
+        if !active {
+            return Ok(None);
+        }
+
+        let value = weak_error!(self.read_return_value(pending.callee)).flatten();
+        if !condition.is_satisfied(value.as_ref()) {
+            return Ok(None);
+        }
+        Ok(Some((pending.number, value)))
+    }
+
+    /// Set a one-shot breakpoint at the program `main` function (see [`super::StopAt::Main`]),
+    /// it is removed when debugee stops at it.
+    pub(super) fn set_main_breakpoint(&mut self) -> Result<(), Error> {
+        let inferior = self.inferior_mut();
+        let dwarf = inferior.debugee.program_debug_info()?;
+        let place = dwarf
+            .search_places_for_main()?
+            .into_iter()
+            .next()
+            .ok_or(NoSuitablePlace)?;
+        let addr = place
+            .address
+            .relocate_to_segment(&inferior.debugee, dwarf)?;
+
+        // an existing breakpoint stops debugee anyway
+        if inferior.breakpoints.get_enabled(addr).is_none() {
+            inferior
+                .breakpoints
+                .add_and_enable(Breakpoint::new_temporary(
+                    dwarf.pathname(),
+                    addr,
+                    inferior.process.pid(),
+                ))?;
+            inferior.main_brkpt = Some(addr);
+        }
+        Ok(())
+    }
+
     /// Disable and remove breakpoint from function start.
     ///
     /// # Arguments
@@ -295,6 +720,97 @@ impl Debugger {
         self.remove_breakpoints_at_addresses(addresses)
     }
 
+    fn search_functions_by_regex(
+        &self,
+        regex: &Regex,
+    ) -> Result<Vec<(&DebugInformation, Vec<PlaceDescriptorOwned>)>, Error> {
+        let dwarfs = self.inferior().debugee.debug_info_all();
+
+        dwarfs
+            .iter()
+            .filter(|dwarf| dwarf.has_debug_info())
+            .map(|&dwarf| {
+                let places = dwarf.search_places_for_fn_regex(regex)?;
+                Ok((dwarf, places))
+            })
+            .collect()
+    }
+
+    /// Create and enable breakpoints at start of all functions which full names match
+    /// a regular expression. Created breakpoints are united into a group,
+    /// return group number and created breakpoints.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern`: regular expression for function full names (like `^myapp::parser::.*`)
+    pub fn set_breakpoints_at_fn_regex(
+        &mut self,
+        pattern: &str,
+    ) -> Result<(u32, Vec<BreakpointView>), Error> {
+        let regex = Regex::new(pattern)?;
+        let places = self.search_functions_by_regex(&regex)?;
+        if places.iter().all(|(_, places)| places.is_empty()) {
+            return Err(NoSuitablePlace);
+        }
+
+        let brkpts = self.create_breakpoint_at_places(places)?;
+        let group = self
+            .inferior_mut()
+            .breakpoints
+            .add_group(pattern, brkpts.numbers());
+        Ok((group, self.add_breakpoints(brkpts)?))
+    }
+
+    /// Enable all breakpoints of a group. Return breakpoints of a group
+    /// (empty list if there is no group with such number).
+    ///
+    /// # Arguments
+    ///
+    /// * `number`: group number
+    pub fn enable_breakpoint_group(&mut self, number: u32) -> Result<Vec<BreakpointView>, Error> {
+        let inferior = self.inferior_mut();
+        let numbers = inferior.breakpoints.group_members(number);
+        for &brkpt_num in &numbers {
+            inferior
+                .breakpoints
+                .resume_by_num(brkpt_num, &inferior.debugee)?;
+        }
+        Ok(inferior.breakpoints.snapshot_of(&numbers))
+    }
+
+    /// Disable all breakpoints of a group. Return breakpoints of a group
+    /// (empty list if there is no group with such number).
+    ///
+    /// # Arguments
+    ///
+    /// * `number`: group number
+    pub fn disable_breakpoint_group(&mut self, number: u32) -> Result<Vec<BreakpointView>, Error> {
+        let numbers = self.inferior().breakpoints.group_members(number);
+        for &brkpt_num in &numbers {
+            self.inferior_mut().breakpoints.suspend_by_num(brkpt_num)?;
+        }
+        Ok(self.inferior().breakpoints.snapshot_of(&numbers))
+    }
+
+    /// Return list of breakpoint groups.
+    pub fn breakpoint_groups(&self) -> &[BreakpointGroup] {
+        &self.inferior().breakpoints.groups
+    }
+
+    /// Return number of a group which contains a breakpoint, `None` if there is no such group.
+    ///
+    /// # Arguments
+    ///
+    /// * `number`: breakpoint number
+    pub fn breakpoint_group_of(&self, number: u32) -> Option<u32> {
+        self.inferior()
+            .breakpoints
+            .groups
+            .iter()
+            .find(|group| group.breakpoints.contains(&number))
+            .map(|group| group.number)
+    }
+
     fn search_lines_in_file(
         &self,
         debug_info: &DebugInformation,
@@ -305,12 +821,48 @@ impl Debugger {
         Ok(places.into_iter().map(|p| p.to_owned()).collect())
     }
 
+    /// Resolve a file name template into a template that matches exactly one file.
+    /// An empty template means the file of the current place,
+    /// if template matches files with different paths then all of them returned as
+    /// candidates in [`Error::AmbiguousFile`] error.
+    pub(super) fn resolve_file_tpl(&self, file_tpl: &str) -> Result<String, Error> {
+        if file_tpl.is_empty() {
+            disable_when_not_stared!(self);
+            let location = self.exploration_ctx().location();
+            let debug_info = self.inferior().debugee.debug_info(location.pc)?;
+            let place = debug_info
+                .find_place_from_pc(location.global_pc)?
+                .ok_or(PlaceNotFound(location.global_pc))?;
+            return Ok(place.file.to_string_lossy().to_string());
+        }
+
+        let mut candidates = vec![];
+        for dwarf in self.inferior().debugee.debug_info_all() {
+            if !dwarf.has_debug_info() {
+                continue;
+            }
+            for file in dwarf.find_files(file_tpl) {
+                let file = file.to_string_lossy().to_string();
+                if !candidates.contains(&file) {
+                    candidates.push(file);
+                }
+            }
+        }
+
+        if candidates.len() > 1 {
+            candidates.sort_unstable();
+            return Err(AmbiguousFile(file_tpl.to_string(), candidates));
+        }
+        Ok(file_tpl.to_string())
+    }
+
     fn search_lines(
         &self,
         fine_tpl: &str,
         line: u64,
     ) -> Result<Vec<(&DebugInformation, Vec<PlaceDescriptorOwned>)>, Error> {
-        let dwarfs = self.debugee.debug_info_all();
+        let fine_tpl = &self.resolve_file_tpl(fine_tpl)?;
+        let dwarfs = self.inferior().debugee.debug_info_all();
 
         dwarfs
             .iter()
@@ -322,16 +874,98 @@ impl Debugger {
             .collect()
     }
 
+    /// Return sorted lines of a file where a breakpoint can be set (lines with statements)
+    /// across all loaded object files.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_tpl`: file name template
+    pub fn breakpoint_lines(&self, file_tpl: &str) -> Result<Vec<u64>, Error> {
+        let file_tpl = &self.resolve_file_tpl(file_tpl)?;
+        let mut lines: Vec<_> = self
+            .inferior()
+            .debugee
+            .debug_info_all()
+            .into_iter()
+            .filter(|dwarf| dwarf.has_debug_info())
+            .flat_map(|dwarf| dwarf.find_stmt_lines(file_tpl))
+            .collect();
+        lines.sort_unstable();
+        lines.dedup();
+        Ok(lines)
+    }
+
+    /// Explain why there is no code for a line. Return `None` if there is no such file
+    /// in a debug information (a shared library with this file may be not loaded yet).
+    pub(super) fn diagnose_line(
+        &self,
+        file_tpl: &str,
+        line: u64,
+    ) -> Result<Option<LineDiagnostic>, Error> {
+        let file_tpl = &self.resolve_file_tpl(file_tpl)?;
+        let Some(debug_info) = self
+            .inferior()
+            .debugee
+            .debug_info_all()
+            .into_iter()
+            .filter(|dwarf| dwarf.has_debug_info())
+            .find(|dwarf| !dwarf.find_files(file_tpl).is_empty())
+        else {
+            return Ok(None);
+        };
+
+        let lines = debug_info.find_stmt_lines(file_tpl);
+        let mut nearby_lines: Vec<_> = lines
+            .iter()
+            .copied()
+            .filter(|&l| l.abs_diff(line) <= NEARBY_LINES_RADIUS)
+            .collect();
+        if nearby_lines.is_empty() {
+            // take the closest lines at least
+            nearby_lines.extend(lines.iter().rev().find(|&&l| l < line));
+            nearby_lines.extend(lines.iter().find(|&&l| l > line));
+        }
+
+        // a line is inside a function if the closest lines with code
+        // before and after it belong to the same function
+        let functions_at = |line: Option<&u64>| -> Result<Vec<String>, Error> {
+            let Some(&line) = line else {
+                return Ok(vec![]);
+            };
+            let mut functions = vec![];
+            for place in debug_info.find_closest_place(file_tpl, line)? {
+                if let Some(name) = debug_info
+                    .find_function_by_pc(place.address)?
+                    .and_then(|func| func.full_name())
+                {
+                    functions.push(name);
+                }
+            }
+            Ok(functions)
+        };
+        let before = functions_at(lines.iter().rev().find(|&&l| l < line))?;
+        let after = functions_at(lines.iter().find(|&&l| l > line))?;
+        let function = before.into_iter().find(|name| after.contains(name));
+
+        Ok(Some(LineDiagnostic {
+            nearby_lines,
+            function,
+        }))
+    }
+
     /// Create and enable breakpoint at the following file and line number.
     ///
     /// # Arguments
     ///
-    /// * `fine_name`: file name (ex: "main.rs")
+    /// * `fine_name`: file name (ex: "main.rs") or a path suffix (ex: "src/main.rs"),
+    ///   an empty name means the current file
     /// * `line`: line number
     ///
     /// # Errors
     ///
-    /// Return [`SetupError::PlaceNotFound`] if line or file not exist,
+    /// Return [`SetupError::PlaceNotFound`] if file not exist,
+    /// return [`Error::NoCodeAtLine`] with a diagnostic if there is no code at the line,
+    /// return [`Error::AmbiguousFile`] if file name matches more than one file,
     /// return [`BreakpointError::DebugInformation`] if errors occur while fetching debug information.
     pub fn set_breakpoint_at_line(
         &mut self,
@@ -340,13 +974,106 @@ impl Debugger {
     ) -> Result<Vec<BreakpointView>, Error> {
         let places = self.search_lines(fine_path_tpl, line)?;
         if places.iter().all(|(_, places)| places.is_empty()) {
-            return Err(NoSuitablePlace);
+            return match self.diagnose_line(fine_path_tpl, line)? {
+                Some(diagnostic) => Err(NoCodeAtLine(line, diagnostic)),
+                None => Err(NoSuitablePlace),
+            };
         }
 
         let brkpts = self.create_breakpoint_at_places(places)?;
         self.add_breakpoints(brkpts)
     }
 
+    fn search_column(
+        &self,
+        file_tpl: &str,
+        line: u64,
+        column: u64,
+    ) -> Result<Vec<(&DebugInformation, Vec<PlaceDescriptorOwned>)>, Error> {
+        let file_tpl = &self.resolve_file_tpl(file_tpl)?;
+        self.inferior()
+            .debugee
+            .debug_info_all()
+            .into_iter()
+            .filter(|dwarf| dwarf.has_debug_info())
+            .map(|dwarf| {
+                let places = dwarf.find_column_place(file_tpl, line, column)?;
+                Ok((dwarf, places.into_iter().map(|p| p.to_owned()).collect()))
+            })
+            .collect()
+    }
+
+    /// Return sorted columns of statements at the following file and line number,
+    /// more than one column means that there are several statements at the line.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_tpl`: file name (ex: "main.rs"), an empty name means the current file
+    /// * `line`: line number
+    pub fn line_columns(&self, file_tpl: &str, line: u64) -> Result<Vec<u64>, Error> {
+        let file_tpl = &self.resolve_file_tpl(file_tpl)?;
+        let mut columns: Vec<_> = self
+            .inferior()
+            .debugee
+            .debug_info_all()
+            .into_iter()
+            .filter(|dwarf| dwarf.has_debug_info())
+            .flat_map(|dwarf| dwarf.find_line_columns(file_tpl, line))
+            .collect();
+        columns.sort_unstable();
+        columns.dedup();
+        Ok(columns)
+    }
+
+    /// Create and enable breakpoint at a statement at the following file, line and column number.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_tpl`: file name (ex: "main.rs"), an empty name means the current file
+    /// * `line`: line number
+    /// * `column`: column number
+    ///
+    /// # Errors
+    ///
+    /// Return [`Error::ColumnNotFound`] with available columns if there is no statement
+    /// at the column, return [`Error::NoSuitablePlace`] if line or file not exist.
+    pub fn set_breakpoint_at_column(
+        &mut self,
+        file_tpl: &str,
+        line: u64,
+        column: u64,
+    ) -> Result<Vec<BreakpointView<'_>>, Error> {
+        let places = self.search_column(file_tpl, line, column)?;
+        if places.iter().all(|(_, places)| places.is_empty()) {
+            let columns = self.line_columns(file_tpl, line)?;
+            if columns.is_empty() {
+                return Err(NoSuitablePlace);
+            }
+            return Err(ColumnNotFound(column, columns));
+        }
+
+        let brkpts = self.create_breakpoint_at_places(places)?;
+        self.add_breakpoints(brkpts)
+    }
+
+    /// Disable and remove breakpoint at the following file, line and column number.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_tpl`: file name (ex: "main.rs")
+    /// * `line`: line number
+    /// * `column`: column number
+    pub fn remove_breakpoint_at_column(
+        &mut self,
+        file_tpl: &str,
+        line: u64,
+        column: u64,
+    ) -> Result<Vec<BreakpointView<'_>>, Error> {
+        let places = self.search_column(file_tpl, line, column)?;
+        let addresses = self.addresses_for_breakpoints_at_places(&places)?;
+        self.remove_breakpoints_at_addresses(addresses)
+    }
+
     /// Disable and remove breakpoint at the following file and line number.
     ///
     /// # Arguments
@@ -373,88 +1100,187 @@ impl Debugger {
         request: CreateTransparentBreakpointRequest,
     ) -> Result<(), Error> {
         // transparent breakpoint currently may be set only at main object file instructions
-        let debug_info = self.debugee.program_debug_info()?;
-
-        let places: Vec<_> = match &request {
-            CreateTransparentBreakpointRequest::Line(file, line, _) => {
-                self.search_lines_in_file(debug_info, file, *line)?
-            }
+        let debug_info = self.inferior().debugee.program_debug_info()?;
+
+        let addresses: Vec<GlobalAddress> = match &request {
+            CreateTransparentBreakpointRequest::Line(file, line, _) => self
+                .search_lines_in_file(debug_info, file, *line)?
+                .into_iter()
+                .map(|place| place.address)
+                .collect(),
             CreateTransparentBreakpointRequest::Function(tpl, _) => {
                 if debug_info.has_debug_info() && debug_info.tpl_in_pub_names(tpl) != Some(false) {
-                    debug_info.search_places_for_fn_tpl(tpl)?
+                    debug_info
+                        .search_places_for_fn_tpl(tpl)?
+                        .into_iter()
+                        .map(|place| place.address)
+                        .collect()
                 } else {
                     vec![]
                 }
             }
+            CreateTransparentBreakpointRequest::Symbol(regex, _) => debug_info
+                .find_symbols(&Regex::new(regex)?)
+                .into_iter()
+                .map(|symbol| symbol.addr)
+                .collect(),
+            CreateTransparentBreakpointRequest::Address(addr, callback) => {
+                return self.set_transparent_breakpoint_at_addr(*addr, callback.clone());
+            }
         };
 
-        if places.is_empty() {
+        if addresses.is_empty() {
             return Err(NoSuitablePlace);
         }
 
         let callback = request.callback();
-        let breakpoints: Vec<_> = places
+        let breakpoints: Vec<_> = addresses
             .into_iter()
-            .flat_map(|place| {
-                let addr = place
-                    .address
-                    .relocate_to_segment(&self.debugee, debug_info)
+            .flat_map(|addr| {
+                let addr = addr
+                    .relocate_to_segment(&self.inferior().debugee, debug_info)
                     .ok()?;
                 Some(Breakpoint::new_transparent(
                     debug_info.pathname(),
                     addr,
-                    self.process.pid(),
+                    self.inferior().process.pid(),
                     callback.clone(),
                 ))
             })
             .collect();
 
         for brkpt in breakpoints {
-            self.breakpoints.add_and_enable(brkpt)?;
+            self.inferior_mut().breakpoints.add_and_enable(brkpt)?;
+        }
+
+        Ok(())
+    }
+
+    /// Create and enable transparent breakpoint at address. Existing breakpoints
+    /// are never replaced.
+    pub(super) fn set_transparent_breakpoint_at_addr(
+        &mut self,
+        addr: RelocatedAddress,
+        callback: Rc<dyn Fn(&mut Debugger)>,
+    ) -> Result<(), Error> {
+        if self.inferior().breakpoints.get_enabled(addr).is_some() {
+            return Err(NoSuitablePlace);
         }
 
+        let debug_info = self.inferior().debugee.debug_info(addr)?;
+        let brkpt = Breakpoint::new_transparent(
+            debug_info.pathname(),
+            addr,
+            self.inferior().process.pid(),
+            callback,
+        );
+        self.inferior_mut().breakpoints.add_and_enable(brkpt)?;
         Ok(())
     }
 
     /// Return list of breakpoints.
     pub fn breakpoints_snapshot(&self) -> Vec<BreakpointView> {
-        self.breakpoints.snapshot()
+        self.inferior().breakpoints.snapshot()
+    }
+
+    /// Enable a previously disabled breakpoint by it number.
+    ///
+    /// # Arguments
+    ///
+    /// * `number`: breakpoint number
+    pub fn enable_breakpoint(&mut self, number: u32) -> Result<Option<BreakpointView<'_>>, Error> {
+        let inferior = self.inferior_mut();
+        inferior
+            .breakpoints
+            .resume_by_num(number, &inferior.debugee)
+    }
+
+    /// Disable a breakpoint by it number. Disabled breakpoint stays in the breakpoint list
+    /// but never stops a program until it is enabled again.
+    ///
+    /// # Arguments
+    ///
+    /// * `number`: breakpoint number
+    pub fn disable_breakpoint(&mut self, number: u32) -> Result<Option<BreakpointView<'_>>, Error> {
+        self.inferior_mut().breakpoints.suspend_by_num(number)
+    }
+
+    /// Make breakpoints hardware ones. Hardware breakpoint uses a debug register instead of
+    /// a planted INT3 instruction, so it stays in place when debugee overwrites its code
+    /// (JIT or self-modifying code). There are only 4 debug registers (shared with watchpoints),
+    /// if there are no free registers a breakpoint stays a software one.
+    ///
+    /// # Arguments
+    ///
+    /// * `numbers`: breakpoint numbers
+    pub fn make_breakpoints_hardware(
+        &mut self,
+        numbers: &[u32],
+    ) -> Result<Vec<BreakpointView<'_>>, Error> {
+        for &number in numbers {
+            self.inferior_mut().breakpoints.make_hardware(number)?;
+        }
+        Ok(self.inferior().breakpoints.snapshot_of(numbers))
     }
 
     /// Add new deferred breakpoint by address in debugee address space.
     pub fn add_deferred_at_addr(&mut self, addr: RelocatedAddress) {
-        self.breakpoints
+        self.inferior_mut()
+            .breakpoints
             .deferred_breakpoints
             .push(DeferredBreakpoint::at_address(addr));
     }
 
     /// Add new deferred breakpoint by function name.
     pub fn add_deferred_at_function(&mut self, function: &str) {
-        self.breakpoints
+        self.inferior_mut()
+            .breakpoints
             .deferred_breakpoints
             .push(DeferredBreakpoint::at_function(function));
     }
 
     /// Add new deferred breakpoint by file and line.
     pub fn add_deferred_at_line(&mut self, file: &str, line: u64) {
-        self.breakpoints
+        self.inferior_mut()
+            .breakpoints
             .deferred_breakpoints
             .push(DeferredBreakpoint::at_line(file, line));
     }
 
+    /// Add new deferred breakpoint by file, line and column.
+    pub fn add_deferred_at_column(&mut self, file: &str, line: u64, column: u64) {
+        self.inferior_mut()
+            .breakpoints
+            .deferred_breakpoints
+            .push(DeferredBreakpoint::at_column(file, line, column));
+    }
+
+    /// Add new deferred breakpoint on C++ exception handling event.
+    pub fn add_deferred_catchpoint(&mut self, event: CatchEvent) {
+        self.inferior_mut()
+            .breakpoints
+            .deferred_breakpoints
+            .push(DeferredBreakpoint::at_catch(event));
+    }
+
     /// Refresh deferred breakpoints. Trying to set breakpoint if success - remove
     /// breakpoint from a deferred list.
     pub fn refresh_deferred(&mut self) -> Vec<Error> {
         let mut errors = vec![];
 
-        let mut deferred_brkpts = mem::take(&mut self.breakpoints.deferred_breakpoints);
+        let mut deferred_brkpts =
+            mem::take(&mut self.inferior_mut().breakpoints.deferred_breakpoints);
         deferred_brkpts.retain(|brkpt| {
             let mb_error = match &brkpt {
                 DeferredBreakpoint::Address(addr) => self.set_breakpoint_at_addr(*addr).err(),
                 DeferredBreakpoint::Line(file, line) => {
                     self.set_breakpoint_at_line(file, *line).err()
                 }
+                DeferredBreakpoint::Column(file, line, column) => {
+                    self.set_breakpoint_at_column(file, *line, *column).err()
+                }
                 DeferredBreakpoint::Function(function) => self.set_breakpoint_at_fn(function).err(),
+                DeferredBreakpoint::Catch(event) => self.set_catchpoint(*event).err(),
             };
 
             match mb_error {
@@ -466,7 +1292,7 @@ impl Debugger {
                 }
             }
         });
-        self.breakpoints.deferred_breakpoints = deferred_brkpts;
+        self.inferior_mut().breakpoints.deferred_breakpoints = deferred_brkpts;
 
         errors
     }
@@ -490,6 +1316,9 @@ pub enum BrkptType {
     /// Transparent breakpoints are transparent for debugger user and using it by inner mechanisms
     /// like oracles.
     Transparent(Rc<dyn Fn(&mut Debugger)>),
+    /// This breakpoint created as a companion to the on-return breakpoint,
+    /// set at the return address of a tracked function frame.
+    FunctionReturn,
 }
 
 impl Debug for BrkptType {
@@ -501,6 +1330,7 @@ impl Debug for BrkptType {
             BrkptType::LinkerMapFn => f.write_str("linker-map"),
             BrkptType::Transparent(_) => f.write_str("transparent"),
             BrkptType::WatchpointCompanion(_) => f.write_str("watchpoint-companion"),
+            BrkptType::FunctionReturn => f.write_str("function-return"),
         }
     }
 }
@@ -526,6 +1356,9 @@ impl PartialEq for BrkptType {
             BrkptType::WatchpointCompanion(nums) => {
                 matches!(other, BrkptType::WatchpointCompanion(other_nums) if nums == other_nums)
             }
+            BrkptType::FunctionReturn => {
+                matches!(other, BrkptType::FunctionReturn)
+            }
         }
     }
 }
@@ -545,6 +1378,10 @@ pub struct Breakpoint {
     enabled: Cell<bool>,
     r#type: BrkptType,
     pub debug_info_file: PathBuf,
+    /// True if breakpoint uses a debug register instead of a planted INT3 instruction.
+    hardware: bool,
+    /// True if temporary breakpoint stops any thread, not only a thread with `pid`.
+    any_thread: bool,
 }
 
 impl Breakpoint {
@@ -574,6 +1411,8 @@ impl Breakpoint {
             saved_data: Default::default(),
             r#type,
             debug_info_file,
+            hardware: false,
+            any_thread: false,
         }
     }
 
@@ -626,6 +1465,20 @@ impl Breakpoint {
         )
     }
 
+    /// Create a temporary breakpoint that stops any thread that hits it. Used when a step may be
+    /// continued by another thread (for example, an async task polled by another worker).
+    #[inline(always)]
+    pub fn new_temporary_any_thread(
+        debug_info_file: impl Into<PathBuf>,
+        addr: RelocatedAddress,
+        pid: Pid,
+    ) -> Self {
+        Self {
+            any_thread: true,
+            ..Self::new_temporary(debug_info_file, addr, pid)
+        }
+    }
+
     #[inline(always)]
     pub fn new_linker_map(addr: RelocatedAddress, pid: Pid) -> Self {
         Self::new_inner(
@@ -655,6 +1508,22 @@ impl Breakpoint {
         )
     }
 
+    #[inline(always)]
+    pub fn new_function_return(
+        debug_info_file: impl Into<PathBuf>,
+        addr: RelocatedAddress,
+        pid: Pid,
+    ) -> Self {
+        Self::new_inner(
+            addr,
+            pid,
+            0,
+            None,
+            BrkptType::FunctionReturn,
+            debug_info_file.into(),
+        )
+    }
+
     #[inline(always)]
     pub(super) fn new_watchpoint_companion(
         registry: &BreakpointRegistry,
@@ -707,6 +1576,7 @@ impl Breakpoint {
             | BrkptType::Temporary
             | BrkptType::LinkerMapFn
             | BrkptType::WatchpointCompanion(_)
+            | BrkptType::FunctionReturn
             | BrkptType::Transparent(_) => {
                 panic!("only user defined breakpoint has a place attribute")
             }
@@ -731,26 +1601,99 @@ impl Breakpoint {
         matches!(self.r#type, BrkptType::Temporary)
     }
 
+    /// Return true if breakpoint is a temporary one and stops a thread `pid`.
+    #[inline(always)]
+    pub fn is_temporary_for(&self, pid: Pid) -> bool {
+        self.is_temporary() && (self.any_thread || self.pid == pid)
+    }
+
+    /// Return true if breakpoint uses a debug register instead of a planted INT3 instruction.
+    #[inline(always)]
+    pub fn is_hardware(&self) -> bool {
+        self.hardware
+    }
+
     pub fn enable(&self) -> Result<(), Error> {
-        let addr = self.addr.as_usize() as *mut c_void;
-        let data = sys::ptrace::read(self.pid, addr).map_err(Error::Ptrace)?;
+        if self.hardware {
+            return self.enable_hardware();
+        }
+
+        let addr = self.addr.as_usize();
+        let data = sys::read(self.pid, addr)?;
         self.saved_data.set((data & 0xff) as u8);
         let data_with_pb = (data & !0xff) as u64 | Self::INT3;
-        unsafe {
-            sys::ptrace::write(self.pid, addr, data_with_pb as *mut c_void)
-                .map_err(Error::Ptrace)?;
-        }
+        sys::write(self.pid, addr, data_with_pb as i64)?;
         self.enabled.set(true);
 
         Ok(())
     }
 
     pub fn disable(&self) -> Result<(), Error> {
-        let addr = self.addr.as_usize() as *mut c_void;
-        let data = sys::ptrace::read(self.pid, addr).map_err(Error::Ptrace)? as u64;
+        if self.hardware {
+            return self.disable_hardware();
+        }
+
+        let addr = self.addr.as_usize();
+        let data = sys::read(self.pid, addr)? as u64;
         let restored: u64 = (data & !0xff) | self.saved_data.get() as u64;
-        unsafe {
-            sys::ptrace::write(self.pid, addr, restored as *mut c_void).map_err(Error::Ptrace)?;
+        sys::write(self.pid, addr, restored as i64)?;
+        self.enabled.set(false);
+
+        Ok(())
+    }
+
+    /// Check that planted INT3 instruction is still in place and plant it again if debugee
+    /// overwrites it (JIT or self-modifying code). Overwritten byte becomes a new original one.
+    /// Return true if breakpoint is re-planted.
+    pub fn replant(&self) -> Result<bool, Error> {
+        if self.hardware || !self.is_enabled() {
+            return Ok(false);
+        }
+
+        let addr = self.addr.as_usize();
+        let data = sys::read(self.pid, addr)? as u64;
+        if data & 0xff == Self::INT3 {
+            return Ok(false);
+        }
+
+        self.saved_data.set((data & 0xff) as u8);
+        let data_with_pb = (data & !0xff) | Self::INT3;
+        sys::write(self.pid, addr, data_with_pb as i64)?;
+        Ok(true)
+    }
+
+    fn enable_hardware(&self) -> Result<(), Error> {
+        let mut state = HardwareDebugState::current(self.pid)?;
+        let register = match hardware_register(&state, self.addr) {
+            Some(register) => register,
+            None => DEBUG_REGISTERS
+                .into_iter()
+                .find(|&dr| !state.dr7.dr_enabled(dr, false))
+                .ok_or(Error::HardwareBreakpointLimitReached)?,
+        };
+
+        state.address_regs[register as usize] = self.addr.as_usize();
+        state.dr7.configure_instruction_bp(register);
+        state.dr7.set_dr(register, false, true);
+        for tid in process_threads(self.pid) {
+            if let Err(e) = state.sync(tid) {
+                error!("set hardware breakpoint for thread {tid}: {e}")
+            }
+        }
+        self.enabled.set(true);
+
+        Ok(())
+    }
+
+    fn disable_hardware(&self) -> Result<(), Error> {
+        let mut state = HardwareDebugState::current(self.pid)?;
+        if let Some(register) = hardware_register(&state, self.addr) {
+            state.dr7.set_dr(register, false, false);
+            for tid in process_threads(self.pid) {
+                if let Err(e) = state.sync(tid) {
+                    error!("remove hardware breakpoint for thread {tid}: {e}")
+                }
+            }
         }
         self.enabled.set(false);
 
@@ -758,6 +1701,89 @@ impl Breakpoint {
     }
 }
 
+const DEBUG_REGISTERS: [DebugRegisterNumber; 4] = [
+    DebugRegisterNumber::DR0,
+    DebugRegisterNumber::DR1,
+    DebugRegisterNumber::DR2,
+    DebugRegisterNumber::DR3,
+];
+
+/// Return debug register used by a hardware breakpoint at address.
+fn hardware_register(
+    state: &HardwareDebugState,
+    addr: RelocatedAddress,
+) -> Option<DebugRegisterNumber> {
+    DEBUG_REGISTERS.into_iter().find(|&dr| {
+        state.dr7.is_instruction_bp(dr) && state.address_regs[dr as usize] == addr.as_usize()
+    })
+}
+
+/// Return all threads of a process which the thread belongs to.
+fn process_threads(pid: Pid) -> Vec<Pid> {
+    let Ok(tasks) = fs::read_dir(format!("/proc/{pid}/task")) else {
+        return vec![pid];
+    };
+    tasks
+        .filter_map(|task| {
+            let tid = task.ok()?.file_name().to_str()?.parse().ok()?;
+            Some(Pid::from_raw(tid))
+        })
+        .collect()
+}
+
+/// Set hardware breakpoints into debug registers of a new thread, debug registers
+/// are not inherited by threads.
+///
+/// # Arguments
+///
+/// * `breakpoints`: active breakpoints
+/// * `tid`: new thread id
+pub(crate) fn distribute_hardware_breakpoints(
+    breakpoints: &[&Breakpoint],
+    tid: Pid,
+) -> Result<(), Error> {
+    let mut state = HardwareDebugState::current(tid)?;
+    for dr in DEBUG_REGISTERS {
+        if state.dr7.is_instruction_bp(dr) {
+            state.dr7.set_dr(dr, false, false);
+        }
+    }
+
+    for brkpt in breakpoints
+        .iter()
+        .filter(|brkpt| brkpt.hardware && brkpt.is_enabled())
+    {
+        let register = DEBUG_REGISTERS
+            .into_iter()
+            .find(|&dr| !state.dr7.dr_enabled(dr, false))
+            .ok_or(Error::HardwareBreakpointLimitReached)?;
+        state.address_regs[register as usize] = brkpt.addr.as_usize();
+        state.dr7.configure_instruction_bp(register);
+        state.dr7.set_dr(register, false, true);
+    }
+
+    state.sync(tid)
+}
+
+/// Replace planted INT3 bytes in memory read from debugee by original bytes.
+/// Breakpoints outside the `[addr, addr + data.len())` range are ignored.
+pub(crate) fn mask_breakpoints<'a>(
+    breakpoints: impl IntoIterator<Item = &'a Breakpoint>,
+    addr: RelocatedAddress,
+    data: &mut [u8],
+) {
+    let start = addr.as_usize();
+    breakpoints
+        .into_iter()
+        .filter(|brkpt| brkpt.is_enabled() && !brkpt.hardware)
+        .for_each(|brkpt| {
+            let byte_idx = brkpt.addr.as_usize().wrapping_sub(start);
+            if let Some(byte) = data.get_mut(byte_idx) {
+                *byte = brkpt.saved_data.get();
+            }
+        });
+}
+
 /// User defined breakpoint template,
 /// may create if debugee program not running and
 /// there is no, and there is no way to determine the relocated address.
@@ -844,7 +1870,8 @@ impl UninitBreakpoint {
         .ok_or(NoDebugInformation("breakpoint"))?;
 
         let place = if self.r#type == BrkptType::UserDefined {
-            if self.place.is_some() {
+            if self.place.is_some() || !dwarf.has_debug_info() {
+                // breakpoints at symbols without debug information have no place
                 self.place
             } else {
                 Some(
@@ -873,6 +1900,16 @@ pub struct BreakpointView<'a> {
     pub addr: Address,
     pub number: u32,
     pub place: Option<Cow<'a, PlaceDescriptorOwned>>,
+    /// False if breakpoint is disabled by user.
+    pub enabled: bool,
+    /// Number of times the breakpoint stops a program.
+    pub hit_count: u64,
+    /// Return condition if breakpoint stops a program when a function returns.
+    pub on_return: Option<ReturnCondition>,
+    /// Exception handling event if breakpoint is a catchpoint.
+    pub catch: Option<CatchEvent>,
+    /// True if breakpoint uses a debug register instead of a planted INT3 instruction.
+    pub hardware: bool,
 }
 
 impl<'a> From<Breakpoint> for BreakpointView<'a> {
@@ -881,6 +1918,11 @@ impl<'a> From<Breakpoint> for BreakpointView<'a> {
             addr: Address::Relocated(brkpt.addr),
             number: brkpt.number,
             place: brkpt.place.map(Cow::Owned),
+            enabled: true,
+            hit_count: 0,
+            on_return: None,
+            catch: None,
+            hardware: brkpt.hardware,
         }
     }
 }
@@ -891,6 +1933,11 @@ impl<'a> From<&'a Breakpoint> for BreakpointView<'a> {
             addr: Address::Relocated(brkpt.addr),
             number: brkpt.number,
             place: brkpt.place.as_ref().map(Cow::Borrowed),
+            enabled: true,
+            hit_count: 0,
+            on_return: None,
+            catch: None,
+            hardware: brkpt.hardware,
         }
     }
 }
@@ -901,6 +1948,11 @@ impl<'a> From<UninitBreakpoint> for BreakpointView<'a> {
             addr: brkpt.addr,
             number: brkpt.number,
             place: brkpt.place.map(Cow::Owned),
+            enabled: true,
+            hit_count: 0,
+            on_return: None,
+            catch: None,
+            hardware: false,
         }
     }
 }
@@ -911,6 +1963,11 @@ impl<'a> From<&'a UninitBreakpoint> for BreakpointView<'a> {
             addr: brkpt.addr,
             number: brkpt.number,
             place: brkpt.place.as_ref().map(Cow::Borrowed),
+            enabled: true,
+            hit_count: 0,
+            on_return: None,
+            catch: None,
+            hardware: false,
         }
     }
 }
@@ -920,6 +1977,11 @@ pub struct BreakpointViewOwned {
     pub addr: Address,
     pub number: u32,
     pub place: Option<PlaceDescriptorOwned>,
+    pub enabled: bool,
+    pub hit_count: u64,
+    pub on_return: Option<ReturnCondition>,
+    pub catch: Option<CatchEvent>,
+    pub hardware: bool,
 }
 
 impl<'a> BreakpointView<'a> {
@@ -928,6 +1990,25 @@ impl<'a> BreakpointView<'a> {
             addr: self.addr,
             number: self.number,
             place: self.place.clone().map(|p| p.into_owned()),
+            enabled: self.enabled,
+            hit_count: self.hit_count,
+            on_return: self.on_return,
+            catch: self.catch,
+            hardware: self.hardware,
+        }
+    }
+
+    /// Return a view that doesn't borrow a debugger, so it can be sent to another thread.
+    pub fn into_owned(self) -> BreakpointView<'static> {
+        BreakpointView {
+            addr: self.addr,
+            number: self.number,
+            place: self.place.map(|p| Cow::Owned(p.into_owned())),
+            enabled: self.enabled,
+            hit_count: self.hit_count,
+            on_return: self.on_return,
+            catch: self.catch,
+            hardware: self.hardware,
         }
     }
 }
@@ -936,7 +2017,9 @@ impl<'a> BreakpointView<'a> {
 pub enum DeferredBreakpoint {
     Address(RelocatedAddress),
     Line(String, u64),
+    Column(String, u64, u64),
     Function(String),
+    Catch(CatchEvent),
 }
 
 impl DeferredBreakpoint {
@@ -948,9 +2031,70 @@ impl DeferredBreakpoint {
         DeferredBreakpoint::Line(file.to_string(), line)
     }
 
+    pub fn at_column(file: &str, line: u64, column: u64) -> DeferredBreakpoint {
+        DeferredBreakpoint::Column(file.to_string(), line, column)
+    }
+
     pub fn at_function(function: &str) -> DeferredBreakpoint {
         DeferredBreakpoint::Function(function.to_string())
     }
+
+    pub fn at_catch(event: CatchEvent) -> DeferredBreakpoint {
+        DeferredBreakpoint::Catch(event)
+    }
+}
+
+/// Number of lines around a requested line in which lines with code are searched.
+const NEARBY_LINES_RADIUS: u64 = 5;
+
+/// Explanation of why there is no code for a source line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineDiagnostic {
+    /// Lines with code around the requested one (or the closest ones if there are no
+    /// lines with code around).
+    pub nearby_lines: Vec<u64>,
+    /// Function whose code surrounds the requested line,
+    /// `None` if the line is outside of all compiled functions.
+    pub function: Option<String>,
+}
+
+impl Display for LineDiagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.nearby_lines.is_empty() {
+            f.write_str("there are no lines with code in the file")?;
+        } else {
+            let lines: Vec<_> = self.nearby_lines.iter().map(ToString::to_string).collect();
+            write!(f, "closest lines with code: {}", lines.join(", "))?;
+        }
+
+        match &self.function {
+            Some(function) => write!(
+                f,
+                "; the line is inside `{function}`, but it has no executable code \
+                 (like a nested declaration) or its code was removed or merged \
+                 into neighbouring lines by the optimizer"
+            ),
+            None => f.write_str(
+                "; the line is outside of compiled functions, it may be a comment, a declaration \
+                 or a function that is never used (unused and not instantiated generic functions \
+                 are removed at compilation)",
+            ),
+        }
+    }
+}
+
+static GLOBAL_GROUP_COUNTER: AtomicU32 = AtomicU32::new(1);
+
+/// Breakpoints created by a single command (at all functions matching a regular expression
+/// or at all functions matching a name, like monomorphizations of a generic function),
+/// group members may be enabled or disabled together.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BreakpointGroup {
+    pub number: u32,
+    /// Regular expression or function name used for search breakpoint places.
+    pub pattern: String,
+    /// Numbers of breakpoints in a group.
+    pub breakpoints: Vec<u32>,
 }
 
 /// Container for application breakpoints.
@@ -961,27 +2105,139 @@ pub struct BreakpointRegistry {
     breakpoints: HashMap<RelocatedAddress, Breakpoint>,
     /// Non-active breakpoint list.
     disabled_breakpoints: HashMap<Address, UninitBreakpoint>,
+    /// Breakpoints disabled by user, never enabled until user enables it.
+    suspended_breakpoints: HashMap<Address, UninitBreakpoint>,
+    /// Hit counters of user defined breakpoints (breakpoint number to hit count).
+    hits: HashMap<u32, u64>,
     /// List of deferred breakpoints, refresh all time when shared library loading.
     deferred_breakpoints: Vec<DeferredBreakpoint>,
+    /// Addresses of breakpoints temporarily disarmed for stepping over them,
+    /// all of them are re-armed at once before debugee execution continues.
+    disarmed: Vec<RelocatedAddress>,
+    /// Breakpoint groups, a group is removed together with its last breakpoint.
+    groups: Vec<BreakpointGroup>,
+    /// Conditions of on-return breakpoints (breakpoint number to condition).
+    on_return: HashMap<u32, ReturnCondition>,
+    /// Tracked frames of functions with on-return breakpoints.
+    pending_returns: Vec<PendingReturn>,
+    /// Exception handling events of catchpoints (breakpoint number to event).
+    catchpoints: HashMap<u32, CatchEvent>,
+    /// Numbers of breakpoints that use debug registers instead of planted INT3 instructions.
+    hardware: HashSet<u32>,
 }
 
 impl BreakpointRegistry {
     /// Add a new breakpoint to registry and enable it.
-    pub fn add_and_enable(&mut self, brkpt: Breakpoint) -> Result<BreakpointView, Error> {
+    pub fn add_and_enable(&mut self, mut brkpt: Breakpoint) -> Result<BreakpointView, Error> {
         if let Some(existed) = self.breakpoints.get(&brkpt.addr) {
             existed.disable()?;
         }
-        brkpt.enable()?;
+        brkpt.hardware =
+            brkpt.r#type == BrkptType::UserDefined && self.hardware.contains(&brkpt.number);
+        if let Err(e) = brkpt.enable() {
+            if !brkpt.hardware {
+                return Err(e);
+            }
+            warn!(target: "debugger", "breakpoint {} is set as a software one: {e}", brkpt.number);
+            brkpt.hardware = false;
+            brkpt.enable()?;
+        }
 
         let addr = brkpt.addr;
         self.breakpoints.insert(addr, brkpt);
         Ok((&self.breakpoints[&addr]).into())
     }
 
+    /// Make user defined breakpoint a hardware one, hardware breakpoint uses a debug register
+    /// and doesn't modify debugee code, so it survives code overwriting (JIT or self-modifying
+    /// code). Breakpoint is reinstalled immediately if it is active.
+    /// If there are no free debug registers breakpoint stays a software one
+    /// (but a debug register is tried again at debugee restart).
+    ///
+    /// # Arguments
+    ///
+    /// * `number`: breakpoint number
+    pub fn make_hardware(&mut self, number: u32) -> Result<(), Error> {
+        if !self.hardware.insert(number) {
+            return Ok(());
+        }
+
+        let Some(&addr) = self.breakpoints.iter().find_map(|(addr, brkpt)| {
+            (brkpt.number == number && brkpt.r#type == BrkptType::UserDefined).then_some(addr)
+        }) else {
+            return Ok(());
+        };
+
+        let brkpt = self.breakpoints.get_mut(&addr).expect("infallible");
+        let enabled = brkpt.is_enabled();
+        if enabled {
+            brkpt.disable()?;
+        }
+        brkpt.hardware = true;
+        if enabled {
+            if let Err(e) = brkpt.enable() {
+                warn!(target: "debugger", "breakpoint {number} is set as a software one: {e}");
+                brkpt.hardware = false;
+                brkpt.enable()?;
+            }
+        }
+        Ok(())
+    }
+
     pub fn get_enabled(&self, addr: RelocatedAddress) -> Option<&Breakpoint> {
         self.breakpoints.get(&addr)
     }
 
+    /// Disarm breakpoint (restore an original instruction) at address until [`Self::rearm_all`]
+    /// is called. Return false if there is no breakpoint at address.
+    ///
+    /// Unlike the disabling, disarming for a single-step doesn't require an immediate
+    /// re-enable, so consecutive steps over the same breakpoint cost no extra ptrace calls.
+    pub fn disarm(&mut self, addr: RelocatedAddress) -> Result<bool, Error> {
+        let Some(brkpt) = self.breakpoints.get(&addr) else {
+            return Ok(false);
+        };
+        if brkpt.is_enabled() {
+            brkpt.disable()?;
+            self.disarmed.push(addr);
+        }
+        Ok(true)
+    }
+
+    /// Re-arm all breakpoints disarmed by [`Self::disarm`], must be called before
+    /// debugee execution continues.
+    pub fn rearm_all(&mut self) -> Result<(), Error> {
+        for addr in mem::take(&mut self.disarmed) {
+            // breakpoint may be removed or replaced since disarming
+            if let Some(brkpt) = self.breakpoints.get(&addr) {
+                if !brkpt.is_enabled() {
+                    brkpt.enable()?;
+                }
+            }
+        }
+
+        // debugee may overwrite code with planted breakpoints (JIT or self-modifying code)
+        for brkpt in self.breakpoints.values() {
+            if let Some(true) = weak_error!(brkpt.replant()) {
+                warn!(
+                    target: "debugger",
+                    "breakpoint at {} is overwritten by debugee, planted again", brkpt.addr
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Replace planted INT3 bytes in memory read from debugee by original bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr`: address of the first byte in `data`
+    /// * `data`: bytes read from debugee memory
+    pub fn mask_memory(&self, addr: RelocatedAddress, data: &mut [u8]) {
+        mask_breakpoints(self.breakpoints.values(), addr, data)
+    }
+
     pub fn get_disabled(&self, addr: Address) -> Option<&UninitBreakpoint> {
         self.disabled_breakpoints.get(&addr)
     }
@@ -1032,14 +2288,167 @@ impl BreakpointRegistry {
         Ok(())
     }
 
+    /// Increase hit counter of a breakpoint.
+    pub fn record_hit(&mut self, number: u32) {
+        *self.hits.entry(number).or_default() += 1;
+    }
+
+    /// Return condition of an on-return breakpoint, `None` for other breakpoints.
+    pub fn return_condition(&self, number: u32) -> Option<ReturnCondition> {
+        self.on_return.get(&number).copied()
+    }
+
+    /// Return exception handling event of a catchpoint, `None` for other breakpoints.
+    pub fn catch_event(&self, number: u32) -> Option<CatchEvent> {
+        self.catchpoints.get(&number).copied()
+    }
+
+    /// Return number of hits of a breakpoint.
+    pub fn hit_count(&self, number: u32) -> u64 {
+        self.hits.get(&number).copied().unwrap_or_default()
+    }
+
+    /// Disable user defined breakpoint by it number and move it into suspended list.
+    pub fn suspend_by_num(
+        &mut self,
+        number: u32,
+    ) -> Result<Option<BreakpointView<'static>>, Error> {
+        if let Some(&addr) = self
+            .disabled_breakpoints
+            .iter()
+            .find_map(|(addr, brkpt)| (brkpt.number == number).then_some(addr))
+        {
+            let brkpt = self.disabled_breakpoints.remove(&addr).expect("infallible");
+            self.suspended_breakpoints.insert(addr, brkpt);
+            return Ok(Some(self.suspended_view(addr)));
+        }
+
+        if let Some(&addr) = self.breakpoints.iter().find_map(|(addr, brkpt)| {
+            (brkpt.number == number && brkpt.r#type == BrkptType::UserDefined).then_some(addr)
+        }) {
+            let brkpt = self.breakpoints.remove(&addr).expect("infallible");
+            if brkpt.is_enabled() {
+                brkpt.disable()?;
+            }
+            let addr = Address::Relocated(addr);
+            self.suspended_breakpoints.insert(
+                addr,
+                UninitBreakpoint::new_inner(
+                    addr,
+                    brkpt.pid,
+                    brkpt.number,
+                    brkpt.place,
+                    BrkptType::UserDefined,
+                    Some(brkpt.debug_info_file),
+                ),
+            );
+            return Ok(Some(self.suspended_view(addr)));
+        }
+
+        Ok(None)
+    }
+
+    /// Enable breakpoint from suspended list by it number.
+    /// Breakpoint is installed immediately if debugee is running.
+    pub fn resume_by_num(
+        &mut self,
+        number: u32,
+        debugee: &Debugee,
+    ) -> Result<Option<BreakpointView<'_>>, Error> {
+        let Some(&addr) = self
+            .suspended_breakpoints
+            .iter()
+            .find_map(|(addr, brkpt)| (brkpt.number == number).then_some(addr))
+        else {
+            return Ok(None);
+        };
+        let uninit_brkpt = self
+            .suspended_breakpoints
+            .remove(&addr)
+            .expect("infallible");
+        let hit_count = self.hits.get(&number).copied().unwrap_or_default();
+        let on_return = self.on_return.get(&number).copied();
+        let catch = self.catchpoints.get(&number).copied();
+        let hardware = self.hardware.contains(&number);
+
+        let mut view = if debugee.is_in_progress() {
+            let brkpt = uninit_brkpt.try_into_brkpt(debugee)?;
+            self.add_and_enable(brkpt)?
+        } else {
+            let mut view = self.add_uninit(uninit_brkpt);
+            view.hardware = hardware;
+            view
+        };
+        view.hit_count = hit_count;
+        view.on_return = on_return;
+        view.catch = catch;
+        Ok(Some(view))
+    }
+
+    fn suspended_view(&self, addr: Address) -> BreakpointView<'static> {
+        let mut view = BreakpointView::from(self.suspended_breakpoints[&addr].clone());
+        view.enabled = false;
+        view.hit_count = self.hits.get(&view.number).copied().unwrap_or_default();
+        view.on_return = self.on_return.get(&view.number).copied();
+        view.catch = self.catchpoints.get(&view.number).copied();
+        view.hardware = self.hardware.contains(&view.number);
+        view
+    }
+
+    /// Add a new breakpoint group, return group number.
+    fn add_group(&mut self, pattern: &str, breakpoints: Vec<u32>) -> u32 {
+        let number = GLOBAL_GROUP_COUNTER.fetch_add(1, Ordering::Relaxed);
+        self.groups.push(BreakpointGroup {
+            number,
+            pattern: pattern.to_string(),
+            breakpoints,
+        });
+        number
+    }
+
+    /// Return numbers of breakpoints in a group, empty if group not found.
+    fn group_members(&self, group: u32) -> Vec<u32> {
+        self.groups
+            .iter()
+            .find(|g| g.number == group)
+            .map(|g| g.breakpoints.clone())
+            .unwrap_or_default()
+    }
+
+    fn remove_from_groups(&mut self, number: u32) {
+        self.groups.iter_mut().for_each(|group| {
+            group.breakpoints.retain(|&n| n != number);
+        });
+        self.groups.retain(|group| !group.breakpoints.is_empty());
+    }
+
     /// Remove breakpoint or uninit breakpoint from registry.
     pub fn remove_by_addr(
         &mut self,
         addr: Address,
+    ) -> Result<Option<BreakpointView<'static>>, Error> {
+        let view = self.remove_by_addr_inner(addr)?;
+        if let Some(ref view) = view {
+            self.remove_from_groups(view.number);
+            self.on_return.remove(&view.number);
+            self.catchpoints.remove(&view.number);
+            self.hardware.remove(&view.number);
+        }
+        Ok(view)
+    }
+
+    fn remove_by_addr_inner(
+        &mut self,
+        addr: Address,
     ) -> Result<Option<BreakpointView<'static>>, Error> {
         if let Some(brkpt) = self.disabled_breakpoints.remove(&addr) {
             return Ok(Some(brkpt.into()));
         }
+        if let Some(brkpt) = self.suspended_breakpoints.remove(&addr) {
+            let mut view = BreakpointView::from(brkpt);
+            view.enabled = false;
+            return Ok(Some(view));
+        }
         if let Address::Relocated(addr) = addr {
             if let Some(brkpt) = self.breakpoints.remove(&addr) {
                 if brkpt.is_enabled() {
@@ -1053,12 +2462,17 @@ impl BreakpointRegistry {
 
     /// Remove enabled breakpoint from registry by it number.
     pub fn remove_by_num(&mut self, number: u32) -> Result<Option<BreakpointView<'static>>, Error> {
-        if let Some(addr) = self.disabled_breakpoints.iter().find_map(|(addr, brkpt)| {
-            if brkpt.number == number {
-                return Some(addr);
-            }
-            None
-        }) {
+        if let Some(addr) = self
+            .disabled_breakpoints
+            .iter()
+            .chain(self.suspended_breakpoints.iter())
+            .find_map(|(addr, brkpt)| {
+                if brkpt.number == number {
+                    return Some(addr);
+                }
+                None
+            })
+        {
             return self.remove_by_addr(*addr);
         }
 
@@ -1115,6 +2529,8 @@ impl BreakpointRegistry {
     /// Disable currently enabled breakpoints.
     pub fn disable_all_breakpoints(&mut self, debugee: &Debugee) -> Result<Vec<Error>, Error> {
         let mut errors = vec![];
+        self.disarmed.clear();
+        self.pending_returns.clear();
         let mut breakpoints = std::mem::take(&mut self.breakpoints);
         for (_, brkpt) in breakpoints.drain() {
             if let Err(e) = brkpt.disable() {
@@ -1131,19 +2547,34 @@ impl BreakpointRegistry {
                     ));
                 }
                 BrkptType::UserDefined => {
-                    self.add_uninit(UninitBreakpoint::new(
-                        Some(brkpt.debug_info_file),
+                    self.add_uninit(UninitBreakpoint::new_inner(
                         addr,
                         brkpt.pid,
+                        brkpt.number,
                         brkpt.place,
+                        BrkptType::UserDefined,
+                        Some(brkpt.debug_info_file),
                     ));
                 }
                 BrkptType::Temporary
                 | BrkptType::LinkerMapFn
                 | BrkptType::Transparent(_)
-                | BrkptType::WatchpointCompanion(_) => {}
+                | BrkptType::WatchpointCompanion(_)
+                | BrkptType::FunctionReturn => {}
             }
         }
+
+        // relocated addresses of suspended breakpoints are not valid after debugee restart
+        let suspended_breakpoints = mem::take(&mut self.suspended_breakpoints);
+        for (addr, mut brkpt) in suspended_breakpoints {
+            let addr = match addr {
+                Address::Relocated(addr) => Address::Global(addr.into_global(debugee)?),
+                Address::Global(_) => addr,
+            };
+            brkpt.addr = addr;
+            self.suspended_breakpoints.insert(addr, brkpt);
+        }
+
         Ok(errors)
     }
 
@@ -1155,6 +2586,9 @@ impl BreakpointRegistry {
         self.disabled_breakpoints
             .iter_mut()
             .for_each(|(_, brkpt)| brkpt.pid = new_pid);
+        self.suspended_breakpoints
+            .iter_mut()
+            .for_each(|(_, brkpt)| brkpt.pid = new_pid);
     }
 
     /// Return vector of currently enabled breakpoints.
@@ -1162,6 +2596,13 @@ impl BreakpointRegistry {
         self.breakpoints.values().collect()
     }
 
+    /// Return view for user-defined breakpoints with given numbers.
+    fn snapshot_of(&self, numbers: &[u32]) -> Vec<BreakpointView> {
+        let mut snap = self.snapshot();
+        snap.retain(|view| numbers.contains(&view.number));
+        snap
+    }
+
     /// Return view for all user-defined breakpoints.
     pub fn snapshot(&self) -> Vec<BreakpointView> {
         let active_bps = self
@@ -1173,9 +2614,29 @@ impl BreakpointRegistry {
             .disabled_breakpoints
             .values()
             .filter(|&bp| bp.r#type == BrkptType::UserDefined)
-            .map(BreakpointView::from);
+            .map(|bp| {
+                let mut view = BreakpointView::from(bp);
+                view.hardware = self.hardware.contains(&view.number);
+                view
+            });
+
+        let suspended_brkpts = self.suspended_breakpoints.values().map(|bp| {
+            let mut view = BreakpointView::from(bp);
+            view.enabled = false;
+            view.hardware = self.hardware.contains(&view.number);
+            view
+        });
 
-        let mut snap = active_bps.chain(disabled_brkpts).collect::<Vec<_>>();
+        let mut snap = active_bps
+            .chain(disabled_brkpts)
+            .chain(suspended_brkpts)
+            .map(|mut view| {
+                view.hit_count = self.hits.get(&view.number).copied().unwrap_or_default();
+                view.on_return = self.on_return.get(&view.number).copied();
+                view.catch = self.catchpoints.get(&view.number).copied();
+                view
+            })
+            .collect::<Vec<_>>();
         snap.sort_by(|a, b| a.number.cmp(&b.number));
 
         snap
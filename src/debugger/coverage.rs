@@ -0,0 +1,240 @@
+//! Line coverage without program instrumentation. One-shot non-stopping breakpoints are planted
+//! at every statement address (taken from a line table) of selected files. When such breakpoint
+//! is hit, the line is marked as covered and the breakpoint is removed, so a covered line
+//! doesn't slow down the program anymore.
+//! Collected coverage is available as an lcov tracefile (see [`Coverage::lcov`]).
+
+use crate::debugger::address::{Address, RelocatedAddress};
+use crate::debugger::error::Error::NoSuitablePlace;
+use crate::debugger::{Debugger, Error};
+use crate::weak_error;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// Coverage summary of a single file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileCoverage {
+    pub path: PathBuf,
+    /// Number of lines with at least one statement.
+    pub lines_found: usize,
+    /// Number of executed lines.
+    pub lines_hit: usize,
+}
+
+/// Collected line coverage.
+#[derive(Debug, Default, Clone)]
+pub struct Coverage {
+    /// Lines with statements of each file and a hit flag for each line.
+    files: BTreeMap<PathBuf, BTreeMap<u64, bool>>,
+}
+
+impl Coverage {
+    /// Return coverage summary for each file.
+    pub fn summary(&self) -> Vec<FileCoverage> {
+        self.files
+            .iter()
+            .map(|(path, lines)| FileCoverage {
+                path: path.clone(),
+                lines_found: lines.len(),
+                lines_hit: lines.values().filter(|&&hit| hit).count(),
+            })
+            .collect()
+    }
+
+    /// Return `true` if line is executed, `false` if line is not executed yet
+    /// and `None` if there are no statements at line.
+    ///
+    /// # Arguments
+    ///
+    /// * `file`: full file path
+    /// * `line`: line number
+    pub fn is_hit(&self, file: &Path, line: u64) -> Option<bool> {
+        self.files.get(file)?.get(&line).copied()
+    }
+
+    /// Return coverage in lcov tracefile format.
+    pub fn lcov(&self) -> String {
+        let mut out = String::from("TN:\n");
+        for (path, lines) in &self.files {
+            _ = writeln!(out, "SF:{}", path.display());
+            for (line, &hit) in lines {
+                _ = writeln!(out, "DA:{line},{}", u8::from(hit));
+            }
+            _ = writeln!(out, "LF:{}", lines.len());
+            _ = writeln!(out, "LH:{}", lines.values().filter(|&&hit| hit).count());
+            out.push_str("end_of_record\n");
+        }
+        out
+    }
+}
+
+#[derive(Default)]
+pub struct CoverageRegistry {
+    /// File name templates of covered files, `None` if coverage is not collected.
+    files: Option<Vec<String>>,
+    coverage: Coverage,
+    /// Planted one-shot breakpoints and source lines of them.
+    planted: HashMap<RelocatedAddress, (PathBuf, u64)>,
+}
+
+impl Debugger {
+    /// Start collecting line coverage of files. Previously collected coverage is dropped.
+    /// If debugee is not started yet, coverage collecting will be started at debugee start.
+    /// Coverage is collected across debugee restarts until [`Debugger::stop_coverage`] is called.
+    /// Return number of lines with statements in covered files.
+    ///
+    /// # Arguments
+    ///
+    /// * `files`: file name templates (full path or part of a file path)
+    ///
+    /// # Errors
+    ///
+    /// Return [`NoSuitablePlace`] if there are no statements in files.
+    pub fn start_coverage(&mut self, files: Vec<String>) -> Result<usize, Error> {
+        self.stop_coverage()?;
+
+        let mut coverage = Coverage::default();
+        for dwarf in self.inferior().debugee.debug_info_all() {
+            for file in &files {
+                for place in dwarf.find_stmt_places(file) {
+                    coverage
+                        .files
+                        .entry(place.file.to_path_buf())
+                        .or_default()
+                        .insert(place.line_number, false);
+                }
+            }
+        }
+        let lines = coverage.files.values().map(BTreeMap::len).sum();
+        if lines == 0 {
+            return Err(NoSuitablePlace);
+        }
+
+        self.inferior_mut().coverage.coverage = coverage;
+        self.inferior_mut().coverage.files = Some(files);
+        if self.is_in_progress() {
+            self.install_coverage_inner()?;
+        }
+        Ok(lines)
+    }
+
+    /// Stop collecting coverage and remove all planted breakpoints.
+    /// Collected coverage stays available.
+    pub fn stop_coverage(&mut self) -> Result<(), Error> {
+        self.inferior_mut().coverage.files = None;
+        let planted = std::mem::take(&mut self.inferior_mut().coverage.planted);
+        if self.is_in_progress() {
+            for addr in planted.into_keys() {
+                self.inferior_mut()
+                    .breakpoints
+                    .remove_by_addr(Address::Relocated(addr))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Return `true` if coverage is collected now.
+    pub fn is_coverage_active(&self) -> bool {
+        self.inferior().coverage.files.is_some()
+    }
+
+    /// Return collected coverage.
+    pub fn coverage(&self) -> &Coverage {
+        &self.inferior().coverage.coverage
+    }
+
+    /// Plant breakpoints at lines which are not covered yet, called at debugee start.
+    pub(super) fn install_coverage(&mut self) {
+        // breakpoints of a previous debugee run are already removed
+        self.inferior_mut().coverage.planted.clear();
+        if self.inferior().coverage.files.is_some() {
+            weak_error!(self.install_coverage_inner());
+        }
+    }
+
+    /// Plant one-shot breakpoints at statements of not covered lines.
+    /// Statements with an existing breakpoint at the same address are skipped.
+    fn install_coverage_inner(&mut self) -> Result<(), Error> {
+        let files = self.inferior().coverage.files.clone().unwrap_or_default();
+        let mut places = vec![];
+        for dwarf in self.inferior().debugee.debug_info_all() {
+            for file in &files {
+                for place in dwarf.find_stmt_places(file) {
+                    let line = place.line_number;
+                    if self.inferior().coverage.coverage.is_hit(place.file, line) != Some(false) {
+                        continue;
+                    }
+                    let addr = place
+                        .address
+                        .relocate_to_segment(&self.inferior().debugee, dwarf)?;
+                    places.push((addr, place.file.to_path_buf(), line));
+                }
+            }
+        }
+
+        for (addr, file, line) in places {
+            if self.inferior().coverage.planted.contains_key(&addr)
+                || self.inferior().breakpoints.get_enabled(addr).is_some()
+            {
+                continue;
+            }
+            let callback = move |dbg: &mut Debugger| dbg.on_coverage_hit(addr);
+            self.set_transparent_breakpoint_at_addr(addr, Rc::new(callback))?;
+            self.inferior_mut()
+                .coverage
+                .planted
+                .insert(addr, (file, line));
+        }
+        Ok(())
+    }
+
+    /// Mark a line as covered and remove a hit breakpoint.
+    fn on_coverage_hit(&mut self, addr: RelocatedAddress) {
+        let Some((file, line)) = self.inferior_mut().coverage.planted.remove(&addr) else {
+            return;
+        };
+        if let Some(hit) = self
+            .inferior_mut()
+            .coverage
+            .coverage
+            .files
+            .get_mut(&file)
+            .and_then(|lines| lines.get_mut(&line))
+        {
+            *hit = true;
+        }
+        weak_error!(self
+            .inferior_mut()
+            .breakpoints
+            .remove_by_addr(Address::Relocated(addr)));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_lcov() {
+        let mut coverage = Coverage::default();
+        let lines = BTreeMap::from([(3, true), (4, false), (7, true)]);
+        coverage.files.insert(PathBuf::from("/src/main.rs"), lines);
+
+        assert_eq!(
+            coverage.lcov(),
+            "TN:\nSF:/src/main.rs\nDA:3,1\nDA:4,0\nDA:7,1\nLF:3\nLH:2\nend_of_record\n"
+        );
+        assert_eq!(
+            coverage.summary(),
+            vec![FileCoverage {
+                path: PathBuf::from("/src/main.rs"),
+                lines_found: 3,
+                lines_hit: 2,
+            }]
+        );
+        assert_eq!(coverage.is_hit(Path::new("/src/main.rs"), 4), Some(false));
+        assert_eq!(coverage.is_hit(Path::new("/src/main.rs"), 5), None);
+    }
+}
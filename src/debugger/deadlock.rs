@@ -0,0 +1,456 @@
+//! Deadlock detection. Threads blocked in a `lock` call of a mutex (`std::sync::Mutex` or
+//! `parking_lot::Mutex`), parked or still spinning, are waiters. Mutexes don't keep an owner,
+//! so the holder of a mutex is a thread with a live guard of this mutex (a `MutexGuard` local
+//! variable) in one of its frames. Waiters and holders form a wait-for graph, each cycle of
+//! this graph is a deadlock.
+
+use crate::debugger::debugee::dwarf::unwind::FrameSpan;
+use crate::debugger::debugee::tracee::Tracee;
+use crate::debugger::debugee::Location;
+use crate::debugger::variable::render::RenderRepr;
+use crate::debugger::variable::select::{
+    DeclarationState, SelectExpressionEvaluator, VariableSelector, DQE,
+};
+use crate::debugger::variable::VariableIR;
+use crate::debugger::Pid;
+use crate::debugger::{Debugger, Error, ExplorationContext};
+use crate::{disable_when_not_stared, weak_error};
+use indexmap::IndexMap;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+/// Functions that lock a mutex, a frame of this function keeps a mutex reference in `self`.
+const LOCK_FUNCTIONS: &[&str] = &[
+    "std::sync::mutex::Mutex::lock",
+    "std::sync::poison::mutex::Mutex::lock",
+    "lock_api::mutex::Mutex::lock",
+];
+
+/// Functions called by a `lock` function when a mutex is locked by another thread.
+const CONTENDED_FUNCTIONS: &[&str] = &["lock_contended", "lock_slow"];
+
+/// Names of a mutex reference field in `std` and `lock_api` mutex guards.
+const GUARD_MUTEX_FIELDS: &[&str] = &["lock", "mutex"];
+
+/// Place in a program: a function and a source code location.
+#[derive(Debug, Clone)]
+pub struct LockPlace {
+    pub function: Option<String>,
+    /// File and line, `None` if unknown.
+    pub place: Option<(PathBuf, u64)>,
+}
+
+/// Thread that holds a mutex.
+#[derive(Debug, Clone)]
+pub struct MutexHolder {
+    pub thread: Tracee,
+    /// Name of a guard variable.
+    pub guard: String,
+    /// Place where guard is declared.
+    pub place: LockPlace,
+}
+
+/// Thread blocked on a mutex.
+#[derive(Debug, Clone)]
+pub struct MutexWait {
+    pub thread: Tracee,
+    /// Address of a mutex.
+    pub mutex: usize,
+    /// Place of a `lock` call.
+    pub place: LockPlace,
+    /// Thread that holds a mutex, `None` if no live guards of a mutex are found.
+    pub holder: Option<MutexHolder>,
+}
+
+/// Deadlock - threads that wait for each other, each thread waits for a mutex
+/// held by the next one, mutex of the last one is held by the first one.
+#[derive(Debug, Clone)]
+pub struct Deadlock {
+    pub waits: Vec<MutexWait>,
+}
+
+/// Return a function name without generic parameters.
+fn base_name(func_name: &str) -> &str {
+    func_name
+        .split_once('<')
+        .map(|(name, _)| name)
+        .unwrap_or(func_name)
+}
+
+/// Return the number of a frame of a `lock` call if a thread waits in it. A waiter is parked
+/// or still spins in a contended path of a `lock` (`lock_contended`, `lock_slow`), or is stopped
+/// in a `lock` itself, before it enters a contended path. Whether a mutex is really held by
+/// someone is decided later, by a live guard of this mutex.
+fn find_lock_wait(bt: &[FrameSpan]) -> Option<usize> {
+    let is_lock = |frame: &FrameSpan| {
+        frame
+            .func_name
+            .as_deref()
+            .is_some_and(|name| LOCK_FUNCTIONS.contains(&base_name(name)))
+    };
+    let lock_frame = bt.iter().position(is_lock)?;
+    let contended = bt[..lock_frame].iter().any(|frame| {
+        frame.func_name.as_deref().is_some_and(|name| {
+            CONTENDED_FUNCTIONS
+                .iter()
+                .any(|contended| base_name(name).ends_with(contended))
+        })
+    });
+    (lock_frame == 0 || contended).then_some(lock_frame)
+}
+
+/// Return an address of a mutex if variable is a mutex guard.
+fn guarded_mutex(var: &VariableIR) -> Option<usize> {
+    let VariableIR::Struct(guard) = var else {
+        return None;
+    };
+    if !guard
+        .type_name
+        .as_deref()
+        .is_some_and(|name| name.starts_with("MutexGuard<"))
+    {
+        return None;
+    }
+    guard.members.iter().find_map(|member| match member {
+        VariableIR::Pointer(ptr) if GUARD_MUTEX_FIELDS.contains(&member.name().as_str()) => {
+            ptr.value.map(|addr| addr as usize)
+        }
+        _ => None,
+    })
+}
+
+/// Return cycles of a wait-for graph, where each thread waits at most one another thread.
+/// Each cycle starts from a thread met first in the graph.
+fn find_cycles(edges: &IndexMap<Pid, Pid>) -> Vec<Vec<Pid>> {
+    let mut visited = HashSet::new();
+    let mut cycles = vec![];
+    for &start in edges.keys() {
+        let mut path = vec![];
+        let mut current = start;
+        loop {
+            if !visited.insert(current) {
+                // a cycle is found only if a walk returns to a thread from the current path
+                if let Some(cycle_start) = path.iter().position(|&pid| pid == current) {
+                    cycles.push(path[cycle_start..].to_vec());
+                }
+                break;
+            }
+            path.push(current);
+            match edges.get(&current) {
+                Some(&next) => current = next,
+                None => break,
+            }
+        }
+    }
+    cycles
+}
+
+impl Debugger {
+    /// Return evaluation context of a frame.
+    fn frame_context(&self, pid: Pid, num: usize, frame: &FrameSpan) -> Option<ExplorationContext> {
+        let global_pc = frame.ip.into_global(&self.inferior().debugee).ok()?;
+        Some(ExplorationContext::new(
+            Location {
+                pc: frame.ip,
+                global_pc,
+                pid,
+            },
+            num as u32,
+        ))
+    }
+
+    /// Return a function and a source code location of a frame.
+    fn lock_place(&self, frame: Option<&FrameSpan>) -> LockPlace {
+        let place = frame.and_then(|frame| {
+            let dwarf = self.inferior().debugee.debug_info(frame.ip).ok()?;
+            let global_pc = frame.ip.into_global(&self.inferior().debugee).ok()?;
+            dwarf
+                .find_place_from_pc(global_pc)
+                .ok()
+                .flatten()
+                .map(|place| (place.file.to_path_buf(), place.line_number))
+        });
+        LockPlace {
+            function: frame.and_then(|frame| frame.func_name.clone()),
+            place,
+        }
+    }
+
+    /// Find deadlocks between threads blocked on mutexes (`std::sync::Mutex`
+    /// and `parking_lot::Mutex` are supported). Guards of mutexes must be stored in local
+    /// variables, guards stored in structures or collections are not taken into account.
+    pub fn deadlock_check(&self) -> Result<Vec<Deadlock>, Error> {
+        disable_when_not_stared!(self);
+
+        let mut threads: Vec<_> = self
+            .inferior()
+            .debugee
+            .tracee_ctl()
+            .snapshot()
+            .into_iter()
+            .filter_map(|tracee| {
+                let bt = weak_error!(self.inferior().debugee.unwind(tracee.pid))?;
+                Some((tracee, bt))
+            })
+            .collect();
+        threads.sort_by_key(|(tracee, _)| tracee.number);
+
+        let mut waits = IndexMap::new();
+        for (tracee, bt) in &threads {
+            let Some(lock_frame) = find_lock_wait(bt) else {
+                continue;
+            };
+            let Some(ctx) = self.frame_context(tracee.pid, lock_frame, &bt[lock_frame]) else {
+                continue;
+            };
+            let evaluator = SelectExpressionEvaluator::new(
+                self,
+                DQE::Variable(VariableSelector::by_name("self", true)),
+            )
+            .with_context(&ctx);
+            let mutex = evaluator
+                .evaluate_on_arguments()
+                .ok()
+                .and_then(|mut args| args.pop())
+                .and_then(|arg| match arg.variable {
+                    VariableIR::Pointer(ptr) => ptr.value.map(|addr| addr as usize),
+                    _ => None,
+                });
+            let Some(mutex) = mutex else {
+                continue;
+            };
+
+            waits.insert(
+                tracee.pid,
+                MutexWait {
+                    thread: tracee.clone(),
+                    mutex,
+                    place: self.lock_place(bt.get(lock_frame + 1)),
+                    holder: None,
+                },
+            );
+        }
+
+        if waits.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let wanted: HashSet<_> = waits.values().map(|wait| wait.mutex).collect();
+        let mut holders = IndexMap::new();
+        for (tracee, bt) in &threads {
+            for (num, frame) in bt.iter().enumerate() {
+                let Some(ctx) = self.frame_context(tracee.pid, num, frame) else {
+                    continue;
+                };
+                let evaluator =
+                    SelectExpressionEvaluator::new(self, DQE::Variable(VariableSelector::Any))
+                        .with_context(&ctx);
+                let Ok(vars) = evaluator.evaluate() else {
+                    continue;
+                };
+
+                for var in vars {
+                    let Some(mutex) = guarded_mutex(&var.variable) else {
+                        continue;
+                    };
+                    if !wanted.contains(&mutex) || holders.contains_key(&mutex) {
+                        continue;
+                    }
+
+                    let guard = var.variable.name();
+                    let mut place = self.lock_place(Some(frame));
+                    let declarations = SelectExpressionEvaluator::new(
+                        self,
+                        DQE::Variable(VariableSelector::by_name(&guard, true)),
+                    )
+                    .with_context(&ctx)
+                    .evaluate_declarations()
+                    .unwrap_or_default();
+                    if let Some(declared_at) = declarations
+                        .into_iter()
+                        .find(|decl| decl.state == DeclarationState::Live)
+                        .and_then(|decl| decl.place)
+                    {
+                        place.place = Some(declared_at);
+                    }
+
+                    holders.insert(
+                        mutex,
+                        MutexHolder {
+                            thread: tracee.clone(),
+                            guard,
+                            place,
+                        },
+                    );
+                }
+            }
+        }
+
+        let edges: IndexMap<_, _> = waits
+            .iter_mut()
+            .filter_map(|(&pid, wait)| {
+                wait.holder = holders.get(&wait.mutex).cloned();
+                wait.holder.as_ref().map(|holder| (pid, holder.thread.pid))
+            })
+            .collect();
+
+        Ok(find_cycles(&edges)
+            .into_iter()
+            .map(|cycle| Deadlock {
+                waits: cycle.into_iter().map(|pid| waits[&pid].clone()).collect(),
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn graph(edges: &[(i32, i32)]) -> IndexMap<Pid, Pid> {
+        edges
+            .iter()
+            .map(|&(from, to)| (Pid::from_raw(from), Pid::from_raw(to)))
+            .collect()
+    }
+
+    fn backtrace(functions: &[Option<&str>]) -> Vec<FrameSpan> {
+        functions
+            .iter()
+            .map(|name| FrameSpan {
+                func_name: name.map(ToString::to_string),
+                linkage_name: None,
+                fn_start_ip: None,
+                ip: 0_usize.into(),
+                args: None,
+                signal_frame: false,
+            })
+            .collect()
+    }
+
+    fn pids(cycle: &[Pid]) -> Vec<i32> {
+        cycle.iter().map(|pid| pid.as_raw()).collect()
+    }
+
+    #[test]
+    fn test_find_cycles() {
+        struct TestCase {
+            edges: &'static [(i32, i32)],
+            expected: Vec<Vec<i32>>,
+        }
+        let test_cases = vec![
+            TestCase {
+                edges: &[],
+                expected: vec![],
+            },
+            TestCase {
+                edges: &[(1, 2), (2, 3)],
+                expected: vec![],
+            },
+            TestCase {
+                edges: &[(1, 2), (2, 1)],
+                expected: vec![vec![1, 2]],
+            },
+            TestCase {
+                edges: &[(1, 1)],
+                expected: vec![vec![1]],
+            },
+            TestCase {
+                edges: &[(4, 1), (1, 2), (2, 3), (3, 1)],
+                expected: vec![vec![1, 2, 3]],
+            },
+            TestCase {
+                edges: &[(1, 2), (2, 1), (3, 4), (4, 3), (5, 3)],
+                expected: vec![vec![1, 2], vec![3, 4]],
+            },
+        ];
+
+        for tc in test_cases {
+            let cycles: Vec<_> = find_cycles(&graph(tc.edges))
+                .iter()
+                .map(|c| pids(c))
+                .collect();
+            assert_eq!(cycles, tc.expected, "edges: {:?}", tc.edges);
+        }
+    }
+
+    #[test]
+    fn test_base_name() {
+        assert_eq!(
+            base_name("std::sync::poison::mutex::Mutex::lock<i32>"),
+            "std::sync::poison::mutex::Mutex::lock"
+        );
+        assert_eq!(base_name("deadlock::transfer"), "deadlock::transfer");
+    }
+
+    #[test]
+    fn test_find_lock_wait() {
+        struct TestCase {
+            bt: &'static [Option<&'static str>],
+            expected: Option<usize>,
+        }
+        let test_cases = vec![
+            // parked in a futex wait
+            TestCase {
+                bt: &[
+                    None,
+                    Some("std::sys::sync::mutex::futex::Mutex::lock_contended"),
+                    Some("std::sync::poison::mutex::Mutex::lock<i32>"),
+                    Some("deadlock::transfer"),
+                ],
+                expected: Some(2),
+            },
+            // still spins before park
+            TestCase {
+                bt: &[
+                    None,
+                    Some("parking_lot_core::thread_parker::imp::thread_yield"),
+                    Some("parking_lot_core::spinwait::SpinWait::spin"),
+                    Some("parking_lot::raw_mutex::RawMutex::lock_slow"),
+                    Some("parking_lot::raw_mutex::RawMutex::lock"),
+                    Some("lock_api::mutex::Mutex::lock<parking_lot::raw_mutex::RawMutex, i32>"),
+                    Some("deadlock::transfer_pl"),
+                ],
+                expected: Some(5),
+            },
+            TestCase {
+                bt: &[
+                    Some("std::sys::sync::mutex::futex::Mutex::lock_contended"),
+                    Some("std::sync::poison::mutex::Mutex::lock<i32>"),
+                    Some("deadlock::transfer"),
+                ],
+                expected: Some(1),
+            },
+            // stopped in a lock before a contended path
+            TestCase {
+                bt: &[
+                    Some("std::sync::poison::mutex::Mutex::lock<i32>"),
+                    Some("deadlock::transfer"),
+                ],
+                expected: Some(0),
+            },
+            // lock is not contended, thread checks a poison flag
+            TestCase {
+                bt: &[
+                    Some("std::thread::panicking"),
+                    Some("std::sync::poison::mutex::Mutex::lock<i32>"),
+                    Some("deadlock::transfer"),
+                ],
+                expected: None,
+            },
+            TestCase {
+                bt: &[None, Some("deadlock::transfer")],
+                expected: None,
+            },
+        ];
+
+        for tc in test_cases {
+            assert_eq!(
+                find_lock_wait(&backtrace(tc.bt)),
+                tc.expected,
+                "{:?}",
+                tc.bt
+            );
+        }
+    }
+}
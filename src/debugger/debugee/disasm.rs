@@ -1,14 +1,25 @@
 use crate::debugger;
 use crate::debugger::address::{GlobalAddress, RelocatedAddress};
-use crate::debugger::breakpoint::Breakpoint;
+use crate::debugger::breakpoint::{mask_breakpoints, Breakpoint};
 use crate::debugger::debugee::dwarf::{ContextualDieRef, DebugInformation};
 use crate::debugger::debugee::Debugee;
+use crate::debugger::watchpoint::AccessKind;
 use crate::debugger::{Error, FunctionDie};
+use capstone::arch::x86::X86OperandType;
+use capstone::arch::ArchOperand;
 use capstone::prelude::*;
+use capstone::{InsnGroupType, RegAccessType};
 use lru::LruCache;
 use std::cell::RefCell;
 use std::num::NonZeroUsize;
 
+/// Number of bytes disassembled from the program counter if there is no function
+/// information for it.
+const FALLBACK_TEXT_LEN: usize = 128;
+
+/// Maximum length of x86-64 instruction.
+const MAX_INSTRUCTION_LEN: usize = 15;
+
 /// Single assembly instruction.
 #[derive(Clone)]
 pub struct Instruction {
@@ -20,6 +31,26 @@ pub struct Instruction {
     pub operands: Option<String>,
 }
 
+/// Target of a direct call or jump instruction.
+#[derive(Clone, Debug)]
+pub struct BranchTarget {
+    pub address: RelocatedAddress,
+    /// Name of a function (or symbol) that contains the target and offset from its start.
+    pub symbol: Option<(String, usize)>,
+}
+
+/// Single assembly instruction decoded from debugee memory, with its encoding.
+#[derive(Clone, Debug)]
+pub struct DecodedInstruction {
+    pub address: RelocatedAddress,
+    /// Instruction bytes (breakpoints are masked).
+    pub bytes: Vec<u8>,
+    pub mnemonic: Option<String>,
+    pub operands: Option<String>,
+    /// Target of a call or jump, if it's encoded in the instruction.
+    pub target: Option<BranchTarget>,
+}
+
 /// Generate disassembled code of a .text section.
 pub struct Disassembler {
     cs: Capstone,
@@ -34,6 +65,7 @@ impl Disassembler {
                 .x86()
                 .mode(arch::x86::ArchMode::Mode64)
                 .syntax(arch::x86::ArchSyntax::Att)
+                .detail(true)
                 .build()
                 .map_err(Error::DisAsmInit)?,
             cache: RefCell::new(LruCache::new(NonZeroUsize::new(1000).expect("infallible"))),
@@ -69,16 +101,9 @@ impl Disassembler {
                 debugee.tracee_ctl().proc_pid(),
                 fn_reloc_pc_start.into(),
                 text_len,
-            )
-            .map_err(Error::Ptrace)?;
+            )?;
 
-            breakpoints
-                .iter()
-                .filter(|brkpt| brkpt.addr >= fn_reloc_pc_start && brkpt.addr <= fn_reloc_pc_end)
-                .for_each(|brkpt| {
-                    let byte_idx = usize::from(brkpt.addr) - usize::from(fn_reloc_pc_start);
-                    text[byte_idx] = brkpt.saved_data.get();
-                });
+            mask_breakpoints(breakpoints.iter().copied(), fn_reloc_pc_start, &mut text);
 
             let instructions = self
                 .cs
@@ -96,4 +121,174 @@ impl Disassembler {
 
         Ok(instructions.clone())
     }
+
+    /// Return disassembled instructions starting from the program counter.
+    /// Used when there is no debug information about function in focus.
+    ///
+    /// # Arguments
+    ///
+    /// * `debugee`: debugee instance
+    /// * `pc`: current program counter
+    /// * `global_pc`: current program counter in object file
+    /// * `breakpoints`: list of active breakpoints
+    pub fn disasm_from_pc(
+        &self,
+        debugee: &Debugee,
+        pc: RelocatedAddress,
+        global_pc: GlobalAddress,
+        breakpoints: &[&Breakpoint],
+    ) -> Result<Vec<Instruction>, Error> {
+        let mut text = debugger::read_memory_by_pid(
+            debugee.tracee_ctl().proc_pid(),
+            pc.into(),
+            FALLBACK_TEXT_LEN,
+        )?;
+        mask_breakpoints(breakpoints.iter().copied(), pc, &mut text);
+
+        // last instruction may be truncated, capstone stops at the first invalid one
+        let instructions = self
+            .cs
+            .disasm_all(&text, global_pc.into())
+            .map_err(Error::DisAsm)?
+            .iter()
+            .map(|i| Instruction {
+                address: i.address().into(),
+                mnemonic: i.mnemonic().map(ToString::to_string),
+                operands: i.op_str().map(ToString::to_string),
+            })
+            .collect();
+        Ok(instructions)
+    }
+
+    /// Return an instruction located at the program counter.
+    ///
+    /// # Arguments
+    ///
+    /// * `debugee`: debugee instance
+    /// * `pc`: current program counter
+    /// * `breakpoints`: list of active breakpoints
+    pub fn decode_at(
+        &self,
+        debugee: &Debugee,
+        pc: RelocatedAddress,
+        breakpoints: &[&Breakpoint],
+    ) -> Result<Option<DecodedInstruction>, Error> {
+        let mut text = debugger::read_memory_by_pid(
+            debugee.tracee_ctl().proc_pid(),
+            pc.into(),
+            MAX_INSTRUCTION_LEN,
+        )?;
+        mask_breakpoints(breakpoints.iter().copied(), pc, &mut text);
+
+        let instructions = self
+            .cs
+            .disasm_count(&text, pc.into(), 1)
+            .map_err(Error::DisAsm)?;
+        let Some(insn) = instructions.iter().next() else {
+            return Ok(None);
+        };
+
+        let detail = self.cs.insn_detail(insn).map_err(Error::DisAsm)?;
+        let is_branch = detail.groups().iter().any(|group| {
+            let group = group.0 as u32;
+            group == InsnGroupType::CS_GRP_JUMP || group == InsnGroupType::CS_GRP_CALL
+        });
+        let target = is_branch
+            .then(|| {
+                detail
+                    .arch_detail()
+                    .operands()
+                    .into_iter()
+                    .find_map(|op| match op {
+                        ArchOperand::X86Operand(op) => match op.op_type {
+                            X86OperandType::Imm(imm) => Some(BranchTarget {
+                                address: RelocatedAddress::from(imm as usize),
+                                symbol: None,
+                            }),
+                            _ => None,
+                        },
+                        _ => None,
+                    })
+            })
+            .flatten();
+
+        Ok(Some(DecodedInstruction {
+            address: pc,
+            bytes: insn.bytes().to_vec(),
+            mnemonic: insn.mnemonic().map(ToString::to_string),
+            operands: insn.op_str().map(ToString::to_string),
+            target,
+        }))
+    }
+
+    /// Return an address of the instruction that ends right before the program counter and
+    /// a kind of its memory access (`None` if instruction has no explicit memory operand).
+    /// Hardware watchpoints are traps, so when watchpoint is hit, the program counter points
+    /// to the instruction next to one that accesses memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `debugee`: debugee instance
+    /// * `fn_start`: address of a function first instruction, if there is no function
+    ///   information, then instruction boundaries are guessed by scanning backward from the pc
+    /// * `pc`: current program counter
+    /// * `breakpoints`: list of active breakpoints
+    pub fn access_before(
+        &self,
+        debugee: &Debugee,
+        fn_start: Option<RelocatedAddress>,
+        pc: RelocatedAddress,
+        breakpoints: &[&Breakpoint],
+    ) -> Result<Option<(RelocatedAddress, Option<AccessKind>)>, Error> {
+        let starts: Vec<_> = match fn_start {
+            Some(start) if start < pc => vec![start],
+            _ => (1..=MAX_INSTRUCTION_LEN)
+                .rev()
+                .map(|len| pc.offset(-(len as isize)))
+                .collect(),
+        };
+
+        for start in starts {
+            let text_len = usize::from(pc) - usize::from(start);
+            let Ok(mut text) = debugger::read_memory_by_pid(
+                debugee.tracee_ctl().proc_pid(),
+                start.into(),
+                text_len,
+            ) else {
+                continue;
+            };
+            mask_breakpoints(breakpoints.iter().copied(), start, &mut text);
+
+            let instructions = self
+                .cs
+                .disasm_all(&text, start.into())
+                .map_err(Error::DisAsm)?;
+            let Some(last) = instructions.iter().last() else {
+                continue;
+            };
+            if last.address() as usize + last.len() != usize::from(pc) {
+                continue;
+            }
+
+            let detail = self.cs.insn_detail(last).map_err(Error::DisAsm)?;
+            let kind = detail
+                .arch_detail()
+                .operands()
+                .into_iter()
+                .find_map(|op| match op {
+                    ArchOperand::X86Operand(op) if matches!(op.op_type, X86OperandType::Mem(_)) => {
+                        op.access
+                    }
+                    _ => None,
+                })
+                .map(|access| match access {
+                    RegAccessType::ReadOnly => AccessKind::Load,
+                    RegAccessType::WriteOnly => AccessKind::Store,
+                    RegAccessType::ReadWrite => AccessKind::LoadStore,
+                });
+            return Ok(Some((RelocatedAddress::from(last.address()), kind)));
+        }
+
+        Ok(None)
+    }
 }
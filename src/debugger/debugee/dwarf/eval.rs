@@ -7,16 +7,16 @@ use crate::debugger::debugee::Debugee;
 use crate::debugger::error::Error;
 use crate::debugger::error::Error::{
     DieNotFound, EvalOptionRequired, EvalUnsupportedRequire, FunctionNotFound, ImplicitPointer,
-    NoDieType, Ptrace, TypeBinaryRepr, UnwindNoContext,
+    NoDieType, TypeBinaryRepr, UnwindNoContext,
 };
 use crate::debugger::register::{DwarfRegisterMap, RegisterMap};
+use crate::debugger::Pid;
 use crate::debugger::{debugee, ExplorationContext};
 use bytes::{BufMut, Bytes, BytesMut};
 use gimli::{
     DebugAddr, Encoding, EndianSlice, EvaluationResult, Expression, Location, Piece, Register,
     RunTimeEndian, UnitOffset, Value, ValueType,
 };
-use nix::unistd::Pid;
 use object::ReadRef;
 use std::cell::RefCell;
 use std::cmp::min;
@@ -63,10 +63,7 @@ impl<'a> RequirementsResolver<'a> {
         match self.cfa.borrow_mut().entry(ctx.pid_on_focus()) {
             Entry::Occupied(e) => Ok(*e.get()),
             Entry::Vacant(e) => {
-                let cfa = self
-                    .debugee
-                    .debug_info(ctx.location().pc)?
-                    .get_cfa(self.debugee, ctx)?;
+                let cfa = self.debugee.debug_info(ctx.location().pc)?.get_cfa(ctx)?;
                 Ok(*e.insert(cfa))
             }
         }
@@ -78,9 +75,39 @@ impl<'a> RequirementsResolver<'a> {
 
     fn resolve_tls(&self, pid: Pid, offset: u64) -> Result<RelocatedAddress, Error> {
         let lm_addr = self.debugee.rendezvous().link_map_main();
-        self.debugee
+        match self
+            .debugee
             .tracee_ctl()
             .tls_addr(pid, lm_addr, offset as usize)
+        {
+            Ok(addr) => Ok(addr),
+            Err(Error::NoThreadDB | Error::ThreadDB(_)) => Self::resolve_tls_by_dtv(pid, offset),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Resolve TLS address without libthread_db, using a dynamic thread vector (DTV)
+    /// pointed by the thread control block at the FS base (x86_64 glibc layout).
+    /// Only TLS block of the main executable (module with id 1) is used.
+    fn resolve_tls_by_dtv(pid: Pid, offset: u64) -> Result<RelocatedAddress, Error> {
+        const DTV_OFFSET_IN_TCB: usize = mem::size_of::<usize>();
+        const DTV_ENTRY_SIZE: usize = 2 * mem::size_of::<usize>();
+        const MAIN_MODULE_ID: usize = 1;
+
+        let read_usize = |addr: usize| -> Result<usize, Error> {
+            let memory = debugger::read_memory_by_pid(pid, addr, mem::size_of::<usize>())?;
+            Ok(usize::from_ne_bytes(
+                memory.try_into().expect("unexpected memory size"),
+            ))
+        };
+
+        let thread_pointer = RegisterMap::current(pid)?.thread_pointer();
+        let dtv = read_usize(thread_pointer as usize + DTV_OFFSET_IN_TCB)?;
+        let block = read_usize(dtv + DTV_ENTRY_SIZE * MAIN_MODULE_ID)?;
+        if block == 0 || block == usize::MAX {
+            return Err(Error::TlsBlockNotAllocated(pid));
+        }
+        Ok(RelocatedAddress::from(block + offset as usize))
     }
 
     fn debug_addr_section(
@@ -107,7 +134,11 @@ impl<'a> RequirementsResolver<'a> {
             .find(|(_, frame)| frame.fn_start_ip == Some(entry_pc_rel))
             .map(|(num, _)| -> Result<DwarfRegisterMap, Error> {
                 // try to use libunwind if frame determined
-                let mut registers = RegisterMap::current(ctx.pid_on_focus())?.into();
+                let mut registers = self
+                    .debugee
+                    .tracee_ctl()
+                    .registers(ctx.pid_on_focus())?
+                    .into();
                 self.debugee.restore_registers_at_frame(
                     ctx.pid_on_focus(),
                     &mut registers,
@@ -231,7 +262,9 @@ impl<'a> ExpressionEvaluator<'a> {
                             regs.value(register)?
                         } else {
                             let pid = ctx.pid_on_focus();
-                            let mut registers = DwarfRegisterMap::from(RegisterMap::current(pid)?);
+                            let mut registers = DwarfRegisterMap::from(
+                                self.resolver.debugee.tracee_ctl().registers(pid)?,
+                            );
                             // try to use registers for in focus frame
                             self.resolver.debugee.restore_registers_at_frame(
                                 ctx.pid_on_focus(),
@@ -270,8 +303,7 @@ impl<'a> ExpressionEvaluator<'a> {
                         ctx.pid_on_focus(),
                         address as usize,
                         size as usize,
-                    )
-                    .map_err(Ptrace)?;
+                    )?;
 
                     let value_type = self.value_type_from_offset(base_type);
                     let value = match value_type {
@@ -409,8 +441,7 @@ impl<'a> CompletedResult<'a> {
                                     self.ctx.pid_on_focus(),
                                     address as usize,
                                     read_size,
-                                )
-                                .map_err(Ptrace)?;
+                                )?;
                                 data.put(Bytes::from(memory))
                             }
                             AddressKind::Value => {
@@ -476,7 +507,7 @@ fn read_register(
     offset: u64,
 ) -> Result<Bytes, Error> {
     let pid = ctx.pid_on_focus();
-    let mut registers = DwarfRegisterMap::from(RegisterMap::current(pid)?);
+    let mut registers = DwarfRegisterMap::from(debugee.tracee_ctl().registers(pid)?);
     // try to use registers for in focus frame
     debugee.restore_registers_at_frame(ctx.pid_on_focus(), &mut registers, ctx.frame_num())?;
     let register_value = registers.value(reg)?;
@@ -0,0 +1,76 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+
+/// Number of independent interner parts, units are parsed in parallel and
+/// a single lock becomes a bottleneck.
+const SHARD_COUNT: usize = 16;
+
+/// Interned string, all equal strings share a single allocation.
+pub type IStr = Arc<str>;
+
+type Shard = Mutex<HashSet<IStr>>;
+
+/// String interner.
+///
+/// Debug information repeats the same names (types, namespaces, functions, producers)
+/// in many units, interner keeps a single copy of each name for all units of an object file.
+/// Interner is owned by a debug information of an object file, so interned strings
+/// are released together with it.
+pub struct Interner {
+    shards: [Shard; SHARD_COUNT],
+}
+
+impl Default for Interner {
+    fn default() -> Self {
+        Self {
+            shards: std::array::from_fn(|_| Mutex::default()),
+        }
+    }
+}
+
+impl Interner {
+    /// Return a shared copy of a string.
+    pub fn intern(&self, s: &str) -> IStr {
+        let mut hasher = DefaultHasher::new();
+        s.hash(&mut hasher);
+        let shard = &self.shards[hasher.finish() as usize % SHARD_COUNT];
+
+        let mut strings = shard.lock().unwrap();
+        if let Some(interned) = strings.get(s) {
+            return interned.clone();
+        }
+        let interned: IStr = Arc::from(s);
+        strings.insert(interned.clone());
+        interned
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_intern() {
+        let interner = Interner::default();
+        let a = interner.intern("core::option::Option");
+        let b = interner.intern(&String::from("core::option::Option"));
+        assert!(Arc::ptr_eq(&a, &b));
+        assert_eq!(&*a, "core::option::Option");
+        assert!(!Arc::ptr_eq(&a, &interner.intern("core::result::Result")));
+    }
+
+    #[test]
+    fn test_intern_released_with_interner() {
+        let interner = Interner::default();
+        let a = interner.intern("core::option::Option");
+        assert_eq!(Arc::strong_count(&a), 2);
+        drop(interner);
+        assert_eq!(Arc::strong_count(&a), 1);
+
+        // another interner doesn't share strings
+        let interner = Interner::default();
+        assert!(!Arc::ptr_eq(&a, &interner.intern("core::option::Option")));
+    }
+}
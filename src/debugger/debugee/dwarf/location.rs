@@ -3,7 +3,7 @@ use crate::debugger::debugee::dwarf::unit::Unit;
 use crate::debugger::debugee::dwarf::{DebugInformation, EndianArcSlice};
 use crate::weak_error;
 use fallible_iterator::FallibleIterator;
-use gimli::{Attribute, AttributeValue, Expression};
+use gimli::{Attribute, AttributeValue, Expression, LocListIter, Range};
 
 pub(super) struct Location<'a>(pub(super) &'a Attribute<EndianArcSlice>);
 
@@ -26,6 +26,38 @@ impl<'a> Location<'a> {
             return Some(expr);
         }
 
+        let mut iter = self.location_list(dwarf_ctx, unit)?;
+        let pc = u64::from(pc);
+        let entry = iter
+            .find(|list_entry| Ok(list_entry.range.begin <= pc && list_entry.range.end >= pc))
+            .ok()?;
+
+        entry.map(|e| e.data)
+    }
+
+    /// Return address ranges where a location list describes a value location.
+    /// Return `None` if location attribute is not a location list
+    /// (a value location is known at any address).
+    pub(super) fn live_ranges(
+        &self,
+        dwarf_ctx: &DebugInformation<EndianArcSlice>,
+        unit: &Unit,
+    ) -> Option<Vec<Range>> {
+        if self.0.exprloc_value().is_some() {
+            return None;
+        }
+        let iter = self.location_list(dwarf_ctx, unit)?;
+        weak_error!(iter
+            .map(|list_entry| Ok(list_entry.range))
+            .filter(|range| Ok(range.begin < range.end))
+            .collect())
+    }
+
+    fn location_list(
+        &self,
+        dwarf_ctx: &DebugInformation<EndianArcSlice>,
+        unit: &Unit,
+    ) -> Option<LocListIter<EndianArcSlice>> {
         let offset = match self.0.value() {
             AttributeValue::LocationListsRef(offset) => offset,
             AttributeValue::DebugLocListsIndex(index) => weak_error!(dwarf_ctx
@@ -34,19 +66,12 @@ impl<'a> Location<'a> {
             _ => return None,
         };
 
-        let mut iter = weak_error!(dwarf_ctx.locations().locations(
+        weak_error!(dwarf_ctx.locations().locations(
             offset,
             unit.encoding(),
             unit.low_pc(),
             dwarf_ctx.debug_addr(),
             unit.addr_base(),
-        ))?;
-
-        let pc = u64::from(pc);
-        let entry = iter
-            .find(|list_entry| Ok(list_entry.range.begin <= pc && list_entry.range.end >= pc))
-            .ok()?;
-
-        entry.map(|e| e.data)
+        ))
     }
 }
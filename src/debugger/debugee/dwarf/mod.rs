@@ -1,6 +1,8 @@
 pub mod eval;
+pub mod interner;
 mod loader;
 mod location;
+mod prolog;
 mod symbol;
 pub mod r#type;
 pub mod unit;
@@ -11,6 +13,7 @@ pub use self::unwind::DwarfUnwinder;
 
 use crate::debugger::address::{GlobalAddress, RelocatedAddress};
 use crate::debugger::debugee::dwarf::eval::AddressKind;
+use crate::debugger::debugee::dwarf::interner::{IStr, Interner};
 use crate::debugger::debugee::dwarf::location::Location as DwarfLocation;
 use crate::debugger::debugee::dwarf::r#type::ComplexType;
 use crate::debugger::debugee::dwarf::r#type::EvaluationContext;
@@ -23,7 +26,7 @@ use crate::debugger::debugee::dwarf::utils::PathSearchIndex;
 use crate::debugger::debugee::{Debugee, Location};
 use crate::debugger::error::Error;
 use crate::debugger::error::Error::{
-    DebugIDFormat, FBANotAnExpression, FunctionNotFound, NoFBA, NoFunctionRanges, UnitNotFound,
+    DebugIDFormat, FBANotAnExpression, FunctionNotFound, NoFBA, NoFunctionRanges,
 };
 use crate::debugger::register::{DwarfRegisterMap, RegisterMap};
 use crate::debugger::variable::select::ObjectBinaryRepr;
@@ -33,19 +36,26 @@ use fallible_iterator::FallibleIterator;
 use gimli::CfaRule::RegisterAndOffset;
 use gimli::{
     Attribute, BaseAddresses, CfaRule, DebugAddr, DebugInfoOffset, DebugPubTypes, Dwarf, EhFrame,
-    Expression, LocationLists, Range, Reader, RunTimeEndian, Section, UnitOffset, UnwindContext,
-    UnwindSection, UnwindTableRow,
+    Encoding, Expression, FrameDescriptionEntry, LocationLists, Range, Reader, RegisterRule,
+    RunTimeEndian, Section, UnitOffset, UnwindContext, UnwindSection, UnwindTableRow,
 };
 use log::debug;
+use lru::LruCache;
 use memmap2::Mmap;
-use object::{Object, ObjectSection};
+use object::{Object, ObjectSection, SectionKind, SymbolKind};
 use rayon::prelude::*;
 use regex::Regex;
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::num::NonZeroUsize;
 use std::ops::{Add, Deref};
+use std::os::unix::fs::FileExt;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::{fs, path};
 pub use symbol::Symbol;
+pub use symbol::{SymbolMatch, SymbolMatchKind};
 use trie_rs::Trie;
 use unit::PlaceDescriptor;
 use walkdir::WalkDir;
@@ -59,12 +69,34 @@ pub struct DebugInformation<R: gimli::Reader = EndianArcSlice> {
     bases: BaseAddresses,
     units: Option<Vec<Unit>>,
     symbol_table: Option<SymbolTab>,
+    /// Names and address ranges of sections loaded into memory.
+    sections: Vec<(String, std::ops::Range<u64>)>,
+    /// Address ranges and file offsets of sections with executable code.
+    code_sections: Vec<(std::ops::Range<u64>, u64)>,
     pub_names: Option<Trie<u8>>,
     pub_types: HashMap<String, (DebugInfoOffset, UnitOffset)>,
     /// Index for fast search files by full path or part of file path. Contains unit index and
     /// indexes of lines in [`Unit::lines`] vector that belongs to a file, indexes are ordered by
     /// line number, column number and address.
     files_index: PathSearchIndex<(usize, Vec<usize>)>,
+    /// Cache of resolved FDE's by instruction address.
+    fde_cache: Mutex<LruCache<GlobalAddress, FrameDescriptionEntry<R, R::Offset>>>,
+    /// Cache of function lookups by instruction address,
+    /// contains unit index and function entry index in this unit.
+    function_cache: Mutex<LruCache<GlobalAddress, Option<(usize, usize)>>>,
+    /// Interner of names from units of this file, shared with clones.
+    interner: Arc<Interner>,
+}
+
+/// Capacity of FDE and function lookup caches.
+const LOOKUP_CACHE_SIZE: usize = 1024;
+/// Names of a variable that holds a value of a thread local variable in newer rust versions.
+const TLS_VALUE_NAMES: [&str; 2] = ["__RUST_STD_INTERNAL_VAL", "VAL"];
+
+fn lookup_cache<K: Hash + Eq, V>() -> Mutex<LruCache<K, V>> {
+    Mutex::new(LruCache::new(
+        NonZeroUsize::new(LOOKUP_CACHE_SIZE).expect("infallible"),
+    ))
 }
 
 impl Clone for DebugInformation {
@@ -91,10 +123,15 @@ impl Clone for DebugInformation {
             bases: self.bases.clone(),
             units: self.units.clone(),
             symbol_table: self.symbol_table.clone(),
+            sections: self.sections.clone(),
+            code_sections: self.code_sections.clone(),
             // it is ok cause pub_names currently unused, maybe it will be changed in future
             pub_names: None,
             pub_types: self.pub_types.clone(),
             files_index: self.files_index.clone(),
+            fde_cache: lookup_cache(),
+            function_cache: lookup_cache(),
+            interner: self.interner.clone(),
         }
     }
 }
@@ -107,6 +144,16 @@ macro_rules! debug_info_exists {
     };
 }
 
+/// Frame layout described by call frame information.
+pub struct FrameLayout {
+    /// Canonical frame address.
+    pub cfa: RelocatedAddress,
+    /// Registers saved on the stack and their locations.
+    pub saved_registers: Vec<(gimli::Register, RelocatedAddress)>,
+    /// Register that contains the return address.
+    pub return_address_register: gimli::Register,
+}
+
 impl DebugInformation {
     /// Return path to executable file with (possible) debug information.
     /// In case of executable contains debug information in separate file this file may not have
@@ -132,6 +179,28 @@ impl DebugInformation {
         self.units.is_some()
     }
 
+    /// Read instructions from a code section of an object file, result is truncated
+    /// at the end of a section and is empty if address is outside of code sections.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr`: address of the first instruction
+    /// * `len`: maximum number of bytes to read
+    pub fn read_code(&self, addr: GlobalAddress, len: usize) -> Result<Vec<u8>, Error> {
+        let addr = u64::from(addr);
+        let Some((range, offset)) = self
+            .code_sections
+            .iter()
+            .find(|(range, _)| range.contains(&addr))
+        else {
+            return Ok(vec![]);
+        };
+
+        let mut code = vec![0; len.min((range.end - addr) as usize)];
+        fs::File::open(&self.file)?.read_exact_at(&mut code, offset + (addr - range.start))?;
+        Ok(code)
+    }
+
     /// Return unit by its index.
     ///
     /// # Arguments
@@ -176,9 +245,9 @@ impl DebugInformation {
 
     fn evaluate_cfa(
         &self,
-        debugee: &Debugee,
         registers: &DwarfRegisterMap,
         utr: &UnwindTableRow<EndianArcSlice>,
+        encoding: Encoding,
         ctx: &ExplorationContext,
     ) -> Result<RelocatedAddress, Error> {
         let rule = utr.cfa();
@@ -188,36 +257,104 @@ impl DebugInformation {
                 Ok(RelocatedAddress::from(ra as usize).offset(*offset as isize))
             }
             CfaRule::Expression(expr) => {
-                let unit = debug_info_exists!(self.find_unit_by_pc(ctx.location().global_pc))
-                    .ok_or(UnitNotFound(ctx.location().global_pc))?;
-                let evaluator = resolve_unit_call!(&self.inner, unit, evaluator, debugee);
-                let expr_result = evaluator.evaluate(ctx, expr.clone())?;
-
-                Ok((expr_result.into_scalar::<usize>(AddressKind::Value)?).into())
+                let cfa = unwind::evaluate_cfi_expression(
+                    expr.clone(),
+                    encoding,
+                    registers,
+                    ctx.pid_on_focus(),
+                    None,
+                )?;
+                Ok(RelocatedAddress::from(cfa as usize))
             }
         }
     }
 
-    pub fn get_cfa(
+    /// Return FDE for an instruction. FDE's are cached, cause the same FDE's are requested
+    /// many times at one stop (stack unwinding, frame information, variables evaluation).
+    ///
+    /// # Arguments
+    ///
+    /// * `pc`: instruction global address
+    pub fn fde_for_address(
         &self,
-        debugee: &Debugee,
-        expl_ctx: &ExplorationContext,
-    ) -> Result<RelocatedAddress, Error> {
+        pc: GlobalAddress,
+    ) -> gimli::Result<FrameDescriptionEntry<EndianArcSlice, usize>> {
+        let mut cache = self.fde_cache.lock().unwrap();
+        if let Some(fde) = cache.get(&pc) {
+            return Ok(fde.clone());
+        }
+        let fde =
+            self.eh_frame
+                .fde_for_address(&self.bases, pc.into(), EhFrame::cie_from_offset)?;
+        cache.put(pc, fde.clone());
+        Ok(fde)
+    }
+
+    /// Return true if an instruction belongs to a signal trampoline (a function that
+    /// a signal handler returns into, like `__restore_rt`).
+    ///
+    /// # Arguments
+    ///
+    /// * `pc`: instruction global address
+    pub fn is_signal_trampoline(&self, pc: GlobalAddress) -> bool {
+        self.fde_for_address(pc)
+            .map(|fde| fde.cie().is_signal_trampoline())
+            .unwrap_or_default()
+    }
+
+    pub fn get_cfa(&self, expl_ctx: &ExplorationContext) -> Result<RelocatedAddress, Error> {
+        let fde = self.fde_for_address(expl_ctx.location().global_pc)?;
         let mut ctx = Box::new(UnwindContext::new());
-        let row = self.eh_frame.unwind_info_for_address(
+        let row = fde.unwind_info_for_address(
+            &self.eh_frame,
             &self.bases,
             &mut ctx,
             expl_ctx.location().global_pc.into(),
-            EhFrame::cie_from_offset,
         )?;
         self.evaluate_cfa(
-            debugee,
             &DwarfRegisterMap::from(RegisterMap::current(expl_ctx.pid_on_focus())?),
             row,
+            fde.cie().encoding(),
             expl_ctx,
         )
     }
 
+    /// Return CFA and locations of registers saved on the stack for a frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `registers`: register values at the frame
+    /// * `expl_ctx`: exploration context with a frame location
+    pub fn get_frame_layout(
+        &self,
+        registers: &DwarfRegisterMap,
+        expl_ctx: &ExplorationContext,
+    ) -> Result<FrameLayout, Error> {
+        let fde = self.fde_for_address(expl_ctx.location().global_pc)?;
+        let mut ctx = Box::new(UnwindContext::new());
+        let row = fde.unwind_info_for_address(
+            &self.eh_frame,
+            &self.bases,
+            &mut ctx,
+            expl_ctx.location().global_pc.into(),
+        )?;
+        let cfa = self.evaluate_cfa(registers, row, fde.cie().encoding(), expl_ctx)?;
+
+        let saved_registers = row
+            .registers()
+            .filter_map(|(register, rule)| match rule {
+                RegisterRule::Offset(offset) => Some((*register, cfa.offset(*offset as isize))),
+                _ => None,
+            })
+            .collect();
+
+        Ok(FrameLayout {
+            cfa,
+            saved_registers,
+            return_address_register: fde.cie().return_address_register(),
+        })
+    }
+
     pub fn debug_addr(&self) -> &DebugAddr<EndianArcSlice> {
         &self.inner.debug_addr
     }
@@ -227,6 +364,14 @@ impl DebugInformation {
         Ok(self.get_units()?.iter().flat_map(|unit| unit.files()))
     }
 
+    /// Return a source code of a file if it is embedded into debug information.
+    pub fn embedded_source(&self, file: &Path) -> Result<Option<Arc<str>>, Error> {
+        Ok(self
+            .get_units()?
+            .iter()
+            .find_map(|unit| unit.embedded_source(file)))
+    }
+
     /// Searches for a unit by occurrences of PC in its range.
     ///
     /// # Arguments
@@ -273,10 +418,41 @@ impl DebugInformation {
         &self,
         pc: GlobalAddress,
     ) -> Result<Option<ContextualDieRef<FunctionDie>>, Error> {
+        let cached = self.function_cache.lock().unwrap().get(&pc).copied();
+        let location = match cached {
+            Some(location) => location,
+            None => {
+                let location = self.find_function_location_by_pc(pc)?;
+                self.function_cache.lock().unwrap().put(pc, location);
+                location
+            }
+        };
+
+        Ok(location.and_then(|(unit_idx, die_idx)| {
+            let unit = self.unit_ensure(unit_idx);
+            let entry = resolve_unit_call!(self, unit, entry, die_idx);
+            match entry.die {
+                DieVariant::Function(ref func) => Some(ContextualDieRef {
+                    debug_info: self,
+                    node: &entry.node,
+                    unit_idx,
+                    die: func,
+                }),
+                _ => None,
+            }
+        }))
+    }
+
+    /// Return unit index and entry index of a function inside which
+    /// the given instruction is located.
+    fn find_function_location_by_pc(
+        &self,
+        pc: GlobalAddress,
+    ) -> Result<Option<(usize, usize)>, Error> {
         let mb_unit = self.find_unit_by_pc(pc)?;
         Ok(mb_unit.and_then(|unit| {
             let pc = u64::from(pc);
-            let die_ranges = resolve_unit_call!(self.dwarf(), unit, die_ranges);
+            let die_ranges = resolve_unit_call!(self, unit, die_ranges);
             let find_pos = match die_ranges.binary_search_by_key(&pc, |dr| dr.range.begin) {
                 Ok(pos) => {
                     let mut idx = pos + 1;
@@ -289,15 +465,10 @@ impl DebugInformation {
             };
 
             die_ranges[..find_pos].iter().rev().find_map(|dr| {
-                let entry = resolve_unit_call!(&self.inner, unit, entry, dr.die_idx);
-                if let DieVariant::Function(ref func) = entry.die {
+                let entry = resolve_unit_call!(self, unit, entry, dr.die_idx);
+                if let DieVariant::Function(_) = entry.die {
                     if dr.range.begin <= pc && pc < dr.range.end {
-                        return Some(ContextualDieRef {
-                            debug_info: self,
-                            node: &entry.node,
-                            unit_idx: unit.idx(),
-                            die: func,
-                        });
+                        return Some((unit.idx(), dr.die_idx));
                     }
                 };
                 None
@@ -309,16 +480,18 @@ impl DebugInformation {
     ///
     /// # Arguments
     ///
-    /// * `template`: search template (full function path or part of this path).
+    /// * `template`: search template (full function path or part of this path),
+    ///   generic arguments are ignored, so all monomorphizations of a generic function are found.
     pub fn search_functions(
         &self,
         template: &str,
     ) -> Result<Vec<ContextualDieRef<FunctionDie>>, Error> {
+        let template = &path_segments(template).join("::");
         let units = self.get_units()?;
         let result: Vec<_> = units
             .par_iter()
             .flat_map(|unit| {
-                let entries = resolve_unit_call!(self.dwarf(), unit, search_functions, template);
+                let entries = resolve_unit_call!(self, unit, search_functions, template);
                 entries
                     .iter()
                     .map(|entry| {
@@ -337,6 +510,28 @@ impl DebugInformation {
         Ok(result)
     }
 
+    /// Return full paths of all files (with at least one line row) which match a file name template.
+    ///
+    /// # Arguments
+    ///
+    /// * `file`: file name template (full path or part of a file path)
+    pub fn find_files(&self, file_tpl: &str) -> Vec<&Path> {
+        let mut result: Vec<&Path> = vec![];
+        for (unit_idx, file_lines) in self.files_index.get(file_tpl) {
+            let unit = self.unit_ensure(*unit_idx);
+            let Some(&line_idx) = file_lines.first() else {
+                continue;
+            };
+            let Some(file) = unit.files().get(unit.line(line_idx).file_index as usize) else {
+                continue;
+            };
+            if !result.contains(&file.as_path()) {
+                result.push(file);
+            }
+        }
+        result
+    }
+
     /// Return closest [`PlaceDescriptor`] for given file and line.
     /// Closest means that returns descriptor for target line or, if no descriptor for target line,
     /// place for next line after target.
@@ -457,6 +652,161 @@ impl DebugInformation {
         Ok(result)
     }
 
+    /// Return sorted columns of statements at a line of a file, a line with
+    /// more than one column contains several statements (like closures or chained calls).
+    ///
+    /// # Arguments
+    ///
+    /// * `file`: file name template (full path or part of a file path)
+    /// * `line`: line number
+    pub fn find_line_columns(&self, file_tpl: &str, line: u64) -> Vec<u64> {
+        let mut columns = vec![];
+        for (unit_idx, file_lines) in self.files_index.get(file_tpl) {
+            let unit = self.unit_ensure(*unit_idx);
+            for &line_idx in file_lines {
+                let line_row = unit.line(line_idx);
+                if line_row.line == line && line_row.is_stmt() && line_row.column != 0 {
+                    columns.push(line_row.column);
+                }
+            }
+        }
+        columns.sort_unstable();
+        columns.dedup();
+        columns
+    }
+
+    /// Return sorted numbers of all lines of a file which contain at least one statement.
+    ///
+    /// # Arguments
+    ///
+    /// * `file`: file name template (full path or part of a file path)
+    pub fn find_stmt_lines(&self, file_tpl: &str) -> Vec<u64> {
+        let mut lines = vec![];
+        for (unit_idx, file_lines) in self.files_index.get(file_tpl) {
+            let unit = self.unit_ensure(*unit_idx);
+            for &line_idx in file_lines {
+                let line_row = unit.line(line_idx);
+                if line_row.is_stmt() {
+                    lines.push(line_row.line);
+                }
+            }
+        }
+        lines.sort_unstable();
+        lines.dedup();
+        lines
+    }
+
+    /// Return [`PlaceDescriptor`]'s of all statements of files which match a file name template.
+    ///
+    /// # Arguments
+    ///
+    /// * `file`: file name template (full path or part of a file path)
+    pub fn find_stmt_places(&self, file_tpl: &str) -> Vec<PlaceDescriptor<'_>> {
+        let mut places = vec![];
+        for (unit_idx, file_lines) in self.files_index.get(file_tpl) {
+            let unit = self.unit_ensure(*unit_idx);
+            for &line_idx in file_lines {
+                let line_row = unit.line(line_idx);
+                if !line_row.is_stmt() || line_row.end_sequence() || line_row.line == 0 {
+                    continue;
+                }
+                if let Some(place) = unit.find_place_by_idx(line_idx) {
+                    places.push(place);
+                }
+            }
+        }
+        places
+    }
+
+    /// Return line table rows of a statement that contains an instruction and an address
+    /// where a statement ends.
+    ///
+    /// # Arguments
+    ///
+    /// * `pc`: instruction global address
+    pub fn find_statement_by_pc(
+        &self,
+        pc: GlobalAddress,
+    ) -> Result<Option<(Vec<PlaceDescriptor<'_>>, GlobalAddress)>, Error> {
+        let Some(unit) = self.find_unit_by_pc(pc)? else {
+            return Ok(None);
+        };
+        Ok(unit
+            .find_place_by_pc(pc)
+            .and_then(|place| unit.find_statement(place.pos_in_unit)))
+    }
+
+    /// Return line table rows of all statements at given file and line, ordered by address.
+    /// Each statement is returned with an address where it ends.
+    ///
+    /// # Arguments
+    ///
+    /// * `file`: file name template (full path or part of a file path)
+    /// * `line`: line number
+    pub fn find_statements_at_line(
+        &self,
+        file_tpl: &str,
+        line: u64,
+    ) -> Vec<(Vec<PlaceDescriptor<'_>>, GlobalAddress)> {
+        let mut statements = vec![];
+        for (unit_idx, file_lines) in self.files_index.get(file_tpl) {
+            let unit = self.unit_ensure(*unit_idx);
+            let mut last_statement_end = None;
+            for &line_idx in file_lines {
+                let line_row = unit.line(line_idx);
+                if line_row.line != line || Some(line_idx) < last_statement_end {
+                    continue;
+                }
+                if let Some((rows, end)) = unit.find_statement(line_idx) {
+                    last_statement_end = rows.last().map(|row| row.pos_in_unit + 1);
+                    statements.push((rows, end));
+                }
+            }
+        }
+        statements.sort_by_key(|(rows, _)| rows.first().map(|row| row.address));
+        statements
+    }
+
+    /// Return [`PlaceDescriptor`]'s of a statement at given file, line and column,
+    /// only one place for a single unique subprogram is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `file`: file name template (full path or part of a file path)
+    /// * `line`: line number
+    /// * `column`: column number
+    pub fn find_column_place(
+        &self,
+        file_tpl: &str,
+        line: u64,
+        column: u64,
+    ) -> Result<Vec<PlaceDescriptor<'_>>, Error> {
+        let mut unique_subprograms = HashSet::new();
+        let mut result = vec![];
+
+        for (unit_idx, file_lines) in self.files_index.get(file_tpl) {
+            let unit = self.unit_ensure(*unit_idx);
+            for &line_idx in file_lines {
+                let line_row = unit.line(line_idx);
+                if line_row.line != line || line_row.column != column || !line_row.is_stmt() {
+                    continue;
+                }
+                let Some(place) = unit.find_place_by_idx(line_idx) else {
+                    continue;
+                };
+
+                if let Some(func) = self.find_function_by_pc(place.address)? {
+                    if !unique_subprograms.insert(&func.die.base_attributes) {
+                        continue;
+                    }
+                }
+                result.push(place);
+            }
+        }
+
+        Ok(result)
+    }
+
     /// Search all places for functions that relevant to template.
     ///
     /// # Arguments
@@ -469,12 +819,202 @@ impl DebugInformation {
         Ok(self
             .search_functions(template)?
             .into_iter()
-            .filter_map(|fn_die| {
-                weak_error!(fn_die.prolog_end_place()).map(|place| place.to_owned())
+            .filter_map(|fn_die| weak_error!(fn_die.prolog_end_place()))
+            .collect())
+    }
+
+    /// Search places of a program `main` function. Rust `main` is a function in a crate root
+    /// module, C `main` hasn't a namespace at all, a Rust `main` is preferred if both exist
+    /// (C `main` of a Rust program is an entry shim for a runtime).
+    pub fn search_places_for_main(&self) -> Result<Vec<PlaceDescriptorOwned>, Error> {
+        const RUNTIME_CRATES: [&str; 3] = ["std", "core", "alloc"];
+
+        let (rust_main, c_main): (Vec<_>, Vec<_>) = self
+            .search_functions("main")?
+            .into_iter()
+            .filter(|fn_die| fn_die.die.base_attributes.name.as_deref() == Some("main"))
+            .filter(|fn_die| {
+                let ns = &fn_die.die.namespace;
+                ns.is_empty() || (ns.len() == 1 && !RUNTIME_CRATES.contains(&ns[0].as_ref()))
             })
+            .partition(|fn_die| !fn_die.die.namespace.is_empty());
+
+        let mains = if rust_main.is_empty() {
+            c_main
+        } else {
+            rust_main
+        };
+        Ok(mains
+            .into_iter()
+            .filter_map(|fn_die| weak_error!(fn_die.prolog_end_place()))
             .collect())
     }
 
+    /// Return all functions which full names match a regular expression.
+    /// Note: this method requires a full load of all units.
+    ///
+    /// # Arguments
+    ///
+    /// * `regex`: regular expression for function full names
+    pub fn search_functions_by_regex(
+        &self,
+        regex: &Regex,
+    ) -> Result<Vec<ContextualDieRef<FunctionDie>>, Error> {
+        let units = self.get_units()?;
+        let result: Vec<_> = units
+            .par_iter()
+            .flat_map(|unit| {
+                let entries = resolve_unit_call!(self, unit, entries_it);
+                entries
+                    .filter_map(|entry| {
+                        let DieVariant::Function(func) = &entry.die else {
+                            return None;
+                        };
+                        // subprograms without a range are declarations or inlined functions
+                        if func.base_attributes.ranges.is_empty() {
+                            return None;
+                        }
+                        let func = ContextualDieRef {
+                            debug_info: self,
+                            unit_idx: unit.idx(),
+                            node: &entry.node,
+                            die: func,
+                        };
+                        func.full_name()
+                            .is_some_and(|name| regex.is_match(&name))
+                            .then_some(func)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        Ok(result)
+    }
+
+    /// Return places at the end of prolog of all functions which full names match
+    /// a regular expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `regex`: regular expression for function full names
+    pub fn search_places_for_fn_regex(
+        &self,
+        regex: &Regex,
+    ) -> Result<Vec<PlaceDescriptorOwned>, Error> {
+        let mut places: Vec<_> = self
+            .search_functions_by_regex(regex)?
+            .into_iter()
+            .filter_map(|fn_die| weak_error!(fn_die.prolog_end_place()))
+            .collect();
+        places.sort_unstable_by_key(|place| place.address);
+        places.dedup_by_key(|place| place.address);
+        Ok(places)
+    }
+
+    /// Return functions and static variables from debug information and symbols from symbol table
+    /// which names match a regular expression. Symbol table entries are skipped if debug
+    /// information contains a function or variable at the same address.
+    /// Note: this method requires a full load of all units.
+    ///
+    /// # Arguments
+    ///
+    /// * `regex`: regular expression for symbol names
+    pub fn search_symbols(&self, regex: &Regex) -> Vec<SymbolMatch> {
+        let units = self.units.as_deref().unwrap_or_default();
+        let mut result: Vec<SymbolMatch> = units
+            .par_iter()
+            .flat_map(|unit| {
+                let source = |decl_file_line: Option<(u64, u64)>| {
+                    let (file, line) = decl_file_line?;
+                    Some((unit.files().get(file as usize)?.clone(), Some(line)))
+                };
+
+                let entries = resolve_unit_call!(self, unit, entries_it);
+                let functions = entries.filter_map(|entry| {
+                    let DieVariant::Function(func) = &entry.die else {
+                        return None;
+                    };
+                    let begin = func.base_attributes.ranges.iter().map(|r| r.begin).min()?;
+                    let func = ContextualDieRef {
+                        debug_info: self,
+                        unit_idx: unit.idx(),
+                        node: &entry.node,
+                        die: func,
+                    };
+                    let name = func.full_name().filter(|name| regex.is_match(name))?;
+                    Some(SymbolMatch {
+                        name,
+                        kind: SymbolMatchKind::Function,
+                        addr: GlobalAddress::from(begin),
+                        source: source(func.die.decl_file_line),
+                        object: self.file.clone(),
+                    })
+                });
+
+                let variables = resolve_unit_call!(self, unit, variables_it);
+                let variables = variables.filter_map(|(name, namespace, idx)| {
+                    let name = if namespace.is_empty() {
+                        name.to_string()
+                    } else {
+                        format!("{}::{name}", namespace.join("::"))
+                    };
+                    if !regex.is_match(&name) {
+                        return None;
+                    }
+                    let entry = resolve_unit_call!(self, unit, entry, idx);
+                    let DieVariant::Variable(var) = &entry.die else {
+                        return None;
+                    };
+                    if var.fn_block_idx.is_some() || var.lexical_block_idx.is_some() {
+                        return None;
+                    }
+                    let gimli::AttributeValue::Exprloc(expr) = var.location.as_ref()?.value()
+                    else {
+                        return None;
+                    };
+                    let mut ops = expr.operations(unit.encoding());
+                    let Ok(Some(gimli::Operation::Address { address })) = ops.next() else {
+                        return None;
+                    };
+                    Some(SymbolMatch {
+                        name,
+                        kind: SymbolMatchKind::Object,
+                        addr: GlobalAddress::from(address),
+                        source: source(var.decl_file_line),
+                        object: self.file.clone(),
+                    })
+                });
+
+                functions.chain(variables).collect::<Vec<_>>()
+            })
+            .collect();
+
+        let known: HashSet<_> = result.iter().map(|sym| (sym.addr, sym.kind)).collect();
+        result.extend(
+            self.find_symbols(regex)
+                .into_iter()
+                .map(|symbol| SymbolMatch {
+                    name: symbol.name.clone(),
+                    kind: SymbolMatchKind::from(symbol.kind),
+                    addr: symbol.addr,
+                    source: None,
+                    object: self.file.clone(),
+                })
+                .filter(|sym| !known.contains(&(sym.addr, sym.kind))),
+        );
+
+        result
+    }
+
+    /// Return `true` if a symbol with a linkage name presents in a symbol table.
+    /// If there is no linkage name or no symbol table, `true` is returned.
+    fn in_symbol_table(&self, linkage_name: Option<&str>) -> bool {
+        let (Some(linkage_name), Some(table)) = (linkage_name, self.symbol_table.as_ref()) else {
+            return true;
+        };
+        table.contains_key(&rustc_demangle::demangle(linkage_name).to_string())
+    }
+
     pub fn find_symbols(&self, regex: &Regex) -> Vec<&Symbol> {
         let symbols = self
             .symbol_table
@@ -489,6 +1029,60 @@ impl DebugInformation {
         symbols
     }
 
+    /// Return a function symbol defined in this object file by its exact name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name`: symbol name
+    pub fn find_function_symbol(&self, name: &str) -> Option<&Symbol> {
+        self.symbol_table
+            .as_ref()?
+            .get(name)
+            .filter(|symbol| symbol.kind == SymbolKind::Text && usize::from(symbol.addr) != 0)
+    }
+
+    /// Return a function symbol that contains the address.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr`: address in object file
+    pub fn find_symbol_by_addr(&self, addr: GlobalAddress) -> Option<&Symbol> {
+        self.symbol_table.as_ref()?.values().find(|symbol| {
+            let start = usize::from(symbol.addr);
+            symbol.kind == SymbolKind::Text
+                && start <= usize::from(addr)
+                && usize::from(addr) < start + (symbol.size as usize).max(1)
+        })
+    }
+
+    /// Return a data object symbol (static or thread local variable) that contains the address.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr`: address in object file
+    pub fn find_object_symbol_by_addr(&self, addr: GlobalAddress) -> Option<&Symbol> {
+        self.symbol_table.as_ref()?.values().find(|symbol| {
+            let start = usize::from(symbol.addr);
+            matches!(symbol.kind, SymbolKind::Data | SymbolKind::Tls)
+                && start != 0
+                && start <= usize::from(addr)
+                && usize::from(addr) < start + (symbol.size as usize).max(1)
+        })
+    }
+
+    /// Return a name of a section that contains the address.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr`: address in object file
+    pub fn find_section_by_addr(&self, addr: GlobalAddress) -> Option<&str> {
+        let addr = usize::from(addr) as u64;
+        self.sections
+            .iter()
+            .find(|(_, range)| range.contains(&addr))
+            .map(|(name, _)| name.as_str())
+    }
+
     pub fn deref_die<'this>(
         &'this self,
         default_unit: &'this Unit,
@@ -496,18 +1090,39 @@ impl DebugInformation {
     ) -> Option<(&'this Entry, &'this Unit)> {
         match reference {
             DieRef::Unit(offset) => {
-                let entry = resolve_unit_call!(&self.inner, default_unit, find_entry, offset);
+                let entry = resolve_unit_call!(self, default_unit, find_entry, offset);
                 entry.map(|e| (e, default_unit))
             }
             DieRef::Global(offset) => {
                 let unit = self.find_unit(offset)?;
                 let offset = UnitOffset(offset.0 - unit.offset().unwrap_or(DebugInfoOffset(0)).0);
-                let entry = resolve_unit_call!(&self.inner, unit, find_entry, offset);
+                let entry = resolve_unit_call!(self, unit, find_entry, offset);
                 entry.map(|e| (e, unit))
             }
         }
     }
 
+    /// Return sorted and deduplicated names of variables matched by a predicate.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate`: name filter
+    pub fn variable_names(&self, predicate: impl Fn(&str) -> bool) -> Result<Vec<String>, Error> {
+        let mut names = HashSet::new();
+        for unit in self.get_units()? {
+            let unit_names = resolve_unit_call!(self, unit, variable_names);
+            names.extend(
+                unit_names
+                    .into_iter()
+                    .filter(|&name| predicate(name))
+                    .map(ToString::to_string),
+            );
+        }
+        let mut names: Vec<_> = names.into_iter().collect();
+        names.sort_unstable();
+        Ok(names)
+    }
+
     pub fn find_variables(
         &self,
         location: Location,
@@ -517,10 +1132,10 @@ impl DebugInformation {
 
         let mut found = vec![];
         for unit in units {
-            let mb_var_locations = resolve_unit_call!(self.dwarf(), unit, locate_var_die, name);
+            let mb_var_locations = resolve_unit_call!(self, unit, locate_var_die, name);
             if let Some(vars) = mb_var_locations {
                 vars.iter().for_each(|(_, entry_idx)| {
-                    let entry = resolve_unit_call!(&self.inner, unit, entry, *entry_idx);
+                    let entry = resolve_unit_call!(self, unit, entry, *entry_idx);
                     if let DieVariant::Variable(ref var) = entry.die {
                         let variable = ContextualDieRef {
                             debug_info: self,
@@ -542,11 +1157,11 @@ impl DebugInformation {
         // variable with name "__KEY" and namespace like [.., variable_name, __getit]
         let tls_ns_part = &[name, "__getit"];
         for unit in units {
-            let mb_var_locations = resolve_unit_call!(self.dwarf(), unit, locate_var_die, "__KEY");
+            let mb_var_locations = resolve_unit_call!(self, unit, locate_var_die, "__KEY");
             if let Some(vars) = mb_var_locations {
                 vars.iter().for_each(|(namespaces, entry_idx)| {
                     if namespaces.contains(tls_ns_part) {
-                        let entry = resolve_unit_call!(&self.inner, unit, entry, *entry_idx);
+                        let entry = resolve_unit_call!(self, unit, entry, *entry_idx);
                         if let DieVariant::Variable(ref var) = entry.die {
                             found.push(ContextualDieRef {
                                 debug_info: self,
@@ -560,6 +1175,42 @@ impl DebugInformation {
             }
         }
 
+        // in newer rust versions tls variable represents like variable with name
+        // "VAL" (or "__RUST_STD_INTERNAL_VAL") and namespace like
+        // [.., variable_name, {constant#0}, {closure#N}], compiler may keep more than one
+        // such variable (for lazy and eager storages), but only one of them is presented
+        // in a symbol table
+        let is_tls_ns = |namespaces: &NamespaceHierarchy| {
+            namespaces
+                .windows(2)
+                .any(|pair| pair[0].as_ref() == name && pair[1].starts_with('{'))
+        };
+        for unit in units {
+            for val_name in TLS_VALUE_NAMES {
+                let mb_var_locations = resolve_unit_call!(self, unit, locate_var_die, val_name);
+                let Some(vars) = mb_var_locations else {
+                    continue;
+                };
+                vars.iter().for_each(|(namespaces, entry_idx)| {
+                    if !is_tls_ns(namespaces) {
+                        return;
+                    }
+                    let entry = resolve_unit_call!(self, unit, entry, *entry_idx);
+                    if let DieVariant::Variable(ref var) = entry.die {
+                        if !self.in_symbol_table(var.linkage_name.as_deref()) {
+                            return;
+                        }
+                        found.push(ContextualDieRef {
+                            debug_info: self,
+                            unit_idx: unit.idx(),
+                            node: &entry.node,
+                            die: var,
+                        });
+                    }
+                });
+            }
+        }
+
         Ok(found)
     }
 
@@ -571,7 +1222,7 @@ impl DebugInformation {
         if self.pub_types.is_empty() {
             self.get_units().ok()?.iter().find_map(|u| {
                 u.offset().and_then(|u_offset| {
-                    let type_ref_in_unit = resolve_unit_call!(&self.inner, u, locate_type, name)?;
+                    let type_ref_in_unit = resolve_unit_call!(self, u, locate_type, name)?;
                     Some((u_offset, type_ref_in_unit))
                 })
             })
@@ -595,6 +1246,11 @@ impl DebugInformation {
         &self.inner
     }
 
+    /// Return a parser for lazy parts of units of this file.
+    pub(crate) fn unit_parser(&self) -> DwarfUnitParser<'_> {
+        DwarfUnitParser::new(&self.inner, &self.interner)
+    }
+
     /// Return the maximum and minimum address from the collection of unit ranges.
     pub fn range(&self) -> Option<Range> {
         let units = self.get_units().ok()?;
@@ -619,10 +1275,47 @@ impl DebugInformation {
     }
 }
 
+/// Progress of a debug information parsing of a single object file.
+#[derive(Debug, Clone)]
+pub struct ParsingProgress<'a> {
+    /// Path to an object file.
+    pub file: &'a Path,
+    /// Number of already parsed compilation units.
+    pub units_parsed: usize,
+    /// Total number of compilation units.
+    pub units_total: usize,
+    /// Size of already parsed compilation units in bytes.
+    pub bytes_parsed: u64,
+    /// Total size of compilation units in bytes.
+    pub bytes_total: u64,
+}
+
+impl ParsingProgress<'_> {
+    /// Return true if all units are parsed.
+    pub fn is_done(&self) -> bool {
+        self.units_parsed == self.units_total
+    }
+}
+
+/// Callback which called during debug information parsing, may be called from
+/// several threads simultaneously (units and object files are parsed in parallel).
+pub type ProgressCallback = Arc<dyn Fn(&ParsingProgress) + Send + Sync>;
+
 #[derive(Default)]
-pub struct DebugInformationBuilder;
+pub struct DebugInformationBuilder {
+    progress: Option<ProgressCallback>,
+}
 
 impl DebugInformationBuilder {
+    /// Create a new builder.
+    ///
+    /// # Arguments
+    ///
+    /// * `progress`: parsing progress callback
+    pub fn new(progress: Option<ProgressCallback>) -> Self {
+        Self { progress }
+    }
+
     // todo configure this path
     const DEBUG_FILES_DIR: &'static str = "/usr/lib/debug";
 
@@ -703,6 +1396,24 @@ impl DebugInformationBuilder {
                 }
             })
         };
+        let sections = file
+            .sections()
+            .filter(|section| section.address() != 0 && section.size() != 0)
+            .filter_map(|section| {
+                let name = section.name().ok()?.to_string();
+                let start = section.address();
+                Some((name, start..start + section.size()))
+            })
+            .collect();
+        let code_sections = file
+            .sections()
+            .filter(|section| section.kind() == SectionKind::Text && section.address() != 0)
+            .filter_map(|section| {
+                let (offset, size) = section.file_range()?;
+                let start = section.address();
+                Some((start..start + size, offset))
+            })
+            .collect();
         let mut bases = BaseAddresses::default();
         if let Some(got) = section_addr(".got") {
             bases = bases.set_got(got);
@@ -769,7 +1480,8 @@ impl DebugInformationBuilder {
                 .ok()
         });
 
-        let parser = DwarfUnitParser::new(&dwarf);
+        let interner = Arc::new(Interner::default());
+        let parser = DwarfUnitParser::new(&dwarf, &interner);
         let headers = dwarf.units().collect::<Vec<_>>()?;
 
         if headers.is_empty() {
@@ -783,16 +1495,46 @@ impl DebugInformationBuilder {
                 bases,
                 units: None,
                 symbol_table,
+                sections,
+                code_sections,
                 pub_names,
                 pub_types: pub_types.unwrap_or_default(),
                 files_index: PathSearchIndex::new(""),
+                fde_cache: lookup_cache(),
+                function_cache: lookup_cache(),
+                interner,
             });
         }
 
+        let units_total = headers.len();
+        let bytes_total: u64 = headers
+            .iter()
+            .map(|header| header.length_including_self() as u64)
+            .sum();
+        let units_parsed = AtomicUsize::new(0);
+        let bytes_parsed = AtomicU64::new(0);
+        let report_progress = |units_parsed: usize, bytes_parsed: u64| {
+            if let Some(progress) = &self.progress {
+                progress(&ParsingProgress {
+                    file: obj_path,
+                    units_parsed,
+                    units_total,
+                    bytes_parsed,
+                    bytes_total,
+                });
+            }
+        };
+        report_progress(0, 0);
+
         let mut units = headers
             .into_par_iter()
             .map(|header| -> gimli::Result<Unit> {
+                let unit_len = header.length_including_self() as u64;
                 let unit = parser.parse(header)?;
+                report_progress(
+                    units_parsed.fetch_add(1, Ordering::Relaxed) + 1,
+                    bytes_parsed.fetch_add(unit_len, Ordering::Relaxed) + unit_len,
+                );
                 Ok(unit)
             })
             .collect::<gimli::Result<Vec<_>>>()?;
@@ -804,6 +1546,9 @@ impl DebugInformationBuilder {
         units.iter().for_each(|unit| {
             unit.file_path_with_lines_pairs()
                 .for_each(|(file_path, lines)| {
+                    let file_path = file_path
+                        .into_iter()
+                        .map(|part| interner.intern(&part.to_string()));
                     files_index.insert(file_path, (unit.idx(), lines));
                 });
         });
@@ -815,13 +1560,47 @@ impl DebugInformationBuilder {
             bases,
             units: Some(units),
             symbol_table,
+            sections,
+            code_sections,
             pub_names,
             pub_types: pub_types.unwrap_or_default(),
             files_index,
+            fde_cache: lookup_cache(),
+            function_cache: lookup_cache(),
+            interner,
         })
     }
 }
 
+/// Return `true` if `pc` is in a variable scope and a variable is already initialized.
+/// Usually rustc starts a scope of a local variable after the instructions that initialize it,
+/// but sometimes (for example, for variables initialized by constants) a scope starts at the
+/// initializing instruction, in this case the first scope instruction is placed at a variable
+/// declaration line and variable holds stale bytes until this instruction is executed.
+///
+/// # Arguments
+///
+/// * `unit`: compilation unit of a variable
+/// * `pc`: program counter
+/// * `scope`: PC ranges of a variable lexical block
+/// * `decl_line`: line where variable is declared
+pub fn in_variable_scope(
+    unit: &Unit,
+    pc: GlobalAddress,
+    scope: &[Range],
+    decl_line: Option<u64>,
+) -> bool {
+    if !pc.in_ranges(scope) {
+        return false;
+    }
+    let scope_begin = scope.iter().map(|range| range.begin).min();
+    if scope_begin != Some(u64::from(pc)) {
+        return true;
+    }
+    let line = unit.find_place_by_pc(pc).map(|place| place.line_number);
+    decl_line.is_none() || line != decl_line
+}
+
 pub trait AsAllocatedData {
     fn name(&self) -> Option<&str>;
 
@@ -829,6 +1608,11 @@ pub trait AsAllocatedData {
 
     fn location(&self) -> Option<&Attribute<EndianArcSlice>>;
 
+    /// Return a line where data is declared.
+    fn decl_line(&self) -> Option<u64> {
+        None
+    }
+
     fn location_expr(
         &self,
         dwarf_ctx: &DebugInformation<EndianArcSlice>,
@@ -852,6 +1636,10 @@ impl AsAllocatedData for VariableDie {
     fn location(&self) -> Option<&Attribute<EndianArcSlice>> {
         self.location.as_ref()
     }
+
+    fn decl_line(&self) -> Option<u64> {
+        self.decl_file_line.map(|(_, line)| line)
+    }
 }
 
 impl AsAllocatedData for ParameterDie {
@@ -869,10 +1657,10 @@ impl AsAllocatedData for ParameterDie {
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
-pub struct NamespaceHierarchy(Vec<String>);
+pub struct NamespaceHierarchy(Vec<IStr>);
 
 impl Deref for NamespaceHierarchy {
-    type Target = Vec<String>;
+    type Target = Vec<IStr>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
@@ -896,7 +1684,12 @@ impl NamespaceHierarchy {
             Some(parent)
         };
         while let Some(DieVariant::Namespace(ns)) = next_parent().map(|e| &e.die) {
-            ns_chain.push(ns.base_attributes.name.clone().unwrap_or_default());
+            ns_chain.push(
+                ns.base_attributes
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| IStr::from("")),
+            );
         }
         ns_chain.reverse();
 
@@ -909,23 +1702,85 @@ impl NamespaceHierarchy {
     ///
     /// * `needle`: searched part of the namespace
     pub fn contains(&self, needle: &[&str]) -> bool {
-        self.0.windows(needle.len()).any(|slice| slice == needle)
+        self.0.windows(needle.len()).any(|slice| {
+            slice
+                .iter()
+                .zip(needle)
+                .all(|(part, needle_part)| part.as_ref() == *needle_part)
+        })
     }
 
     /// Return (namespace, subroutine name) pair from mangled representation.
     ///
     /// # Arguments
     ///
+    /// * `interner`: interner for namespace parts
     /// * `linkage_name`: mangled subroutine name
     #[inline(always)]
-    pub fn from_mangled(linkage_name: &str) -> (Self, String) {
+    pub fn from_mangled(interner: &Interner, linkage_name: &str) -> (Self, String) {
         let demangled = rustc_demangle::demangle(linkage_name);
         let demangled = format!("{demangled:#}");
-        let mut parts: Vec<_> = demangled.split("::").map(ToString::to_string).collect();
-        debug_assert!(!parts.is_empty());
-        let fn_name = parts.pop().expect("function name must exists");
-        (NamespaceHierarchy(parts), fn_name)
+        let mut parts = path_segments(&demangled);
+        let fn_name = parts.pop().unwrap_or_default();
+        (
+            NamespaceHierarchy(parts.into_iter().map(|p| interner.intern(p)).collect()),
+            fn_name.to_string(),
+        )
+    }
+}
+
+/// Split a string by a delimiter, delimiters inside generic arguments (`<...>`) are ignored.
+fn split_top_level<'a>(s: &'a str, delimiter: &str) -> Vec<&'a str> {
+    let mut parts = vec![];
+    let mut depth = 0_usize;
+    let mut start = 0;
+    let mut prev = None;
+    for (i, c) in s.char_indices() {
+        match c {
+            '<' => depth += 1,
+            // `->` of a function pointer type isn't a closing bracket
+            '>' if prev != Some('-') => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        prev = Some(c);
+        if depth == 0 && i >= start && s[i..].starts_with(delimiter) {
+            parts.push(&s[start..i]);
+            start = i + delimiter.len();
+        }
+    }
+    parts.push(&s[start..]);
+    parts
+}
+
+/// Return a path segment without generic arguments (`Vec<u8>` -> `Vec`).
+fn strip_generic_args(segment: &str) -> &str {
+    match segment.find('<') {
+        Some(idx) if idx > 0 => &segment[..idx],
+        _ => segment,
+    }
+}
+
+/// Return segments of a demangled path (or a path template) without generic arguments,
+/// so all monomorphizations of a generic function have the same path.
+/// A qualified path head (`<Type as Trait>`) is replaced by segments of a type path,
+/// generic arguments of a function (`my_fn::<i32>`) are dropped.
+///
+/// # Arguments
+///
+/// * `path`: demangled path like `<app::Wrapper<u8> as core::fmt::Debug>::fmt`
+pub(crate) fn path_segments(path: &str) -> Vec<&str> {
+    let mut segments = vec![];
+    for (i, segment) in split_top_level(path, "::").into_iter().enumerate() {
+        match segment.strip_prefix('<').and_then(|s| s.strip_suffix('>')) {
+            Some(qualified) if i == 0 => {
+                let self_type = split_top_level(qualified, " as ")[0];
+                segments.extend(path_segments(self_type));
+            }
+            Some(_generic_args) => {}
+            None => segments.push(strip_generic_args(segment)),
+        }
     }
+    segments
 }
 
 pub struct ContextualDieRef<'a, T> {
@@ -938,7 +1793,7 @@ pub struct ContextualDieRef<'a, T> {
 #[macro_export]
 macro_rules! ctx_resolve_unit_call {
     ($self: ident, $fn_name: tt, $($arg: expr),*) => {{
-        $crate::resolve_unit_call!($self.debug_info.dwarf(), $self.unit(), $fn_name, $($arg),*)
+        $crate::resolve_unit_call!($self.debug_info, $self.unit(), $fn_name, $($arg),*)
     }};
 }
 
@@ -961,6 +1816,17 @@ impl<'a, T> ContextualDieRef<'a, T> {
     }
 }
 
+/// Function inlined into another function.
+#[derive(Debug, Clone)]
+pub struct InlineCall {
+    /// Inlined function name.
+    pub name: Option<String>,
+    /// Place where function is inlined (call site).
+    pub call_file: Option<PathBuf>,
+    pub call_line: Option<u64>,
+    pub call_column: Option<u64>,
+}
+
 impl<'ctx> ContextualDieRef<'ctx, FunctionDie> {
     pub fn full_name(&self) -> Option<String> {
         self.die
@@ -970,6 +1836,22 @@ impl<'ctx> ContextualDieRef<'ctx, FunctionDie> {
             .map(|name| format!("{}::{}", self.die.namespace.0.join("::"), name))
     }
 
+    /// Return `true` if function is a resume function of an async function (or an async block)
+    /// state machine, rustc names such functions like `{async_fn#0}` or `{async_block#0}`.
+    pub fn is_async_resume(&self) -> bool {
+        self.die
+            .base_attributes
+            .name
+            .as_deref()
+            .is_some_and(|name| name.starts_with("{async_"))
+    }
+
+    /// Return type of a function result, `None` if function returns nothing.
+    pub fn return_type(&self) -> Option<ComplexType> {
+        let parser = r#type::TypeParser::new();
+        Some(parser.parse(*self, self.die.return_type_ref?))
+    }
+
     pub fn frame_base_addr(
         &self,
         ctx: &ExplorationContext,
@@ -1013,29 +1895,66 @@ impl<'ctx> ContextualDieRef<'ctx, FunctionDie> {
         result
     }
 
+    /// Return a local variable with the given name live at `pc`. If a variable is shadowed
+    /// (several declarations are live at `pc`) the innermost declaration is returned.
     pub fn local_variable<'this>(
         &'this self,
         pc: GlobalAddress,
         needle: &str,
     ) -> Option<ContextualDieRef<'ctx, VariableDie>> {
+        // declarations live at `pc` are nested into each other,
+        // so the last one found in BFS order is the innermost
+        self.local_variables_with_name(needle)
+            .into_iter()
+            .filter(|var| var.valid_at(pc))
+            .last()
+    }
+
+    /// Return all local variables with the given name (including shadowed ones
+    /// and ones whose scopes don't contain the current PC), outer declarations go first.
+    pub fn local_variables_with_name<'this>(
+        &'this self,
+        needle: &str,
+    ) -> Vec<ContextualDieRef<'ctx, VariableDie>> {
+        let mut result = vec![];
         let mut queue = VecDeque::from(self.node.children.clone());
         while let Some(idx) = queue.pop_front() {
             let entry = ctx_resolve_unit_call!(self, entry, idx);
             if let DieVariant::Variable(ref var) = entry.die {
-                let var_ref = ContextualDieRef {
-                    debug_info: self.debug_info,
-                    unit_idx: self.unit_idx,
-                    node: &entry.node,
-                    die: var,
-                };
-
-                if var_ref.die.name() == Some(needle) && var_ref.valid_at(pc) {
-                    return Some(var_ref);
+                if var.base_attributes.name.as_deref() == Some(needle) {
+                    result.push(ContextualDieRef {
+                        debug_info: self.debug_info,
+                        unit_idx: self.unit_idx,
+                        node: &entry.node,
+                        die: var,
+                    });
                 }
             }
             entry.node.children.iter().for_each(|i| queue.push_back(*i));
         }
-        None
+        result
+    }
+
+    /// Return a local variable that is declared in a function, but its scope starts at `pc`
+    /// or after it (so the variable is not initialized yet), the nearest declaration is preferred.
+    ///
+    /// # Arguments
+    ///
+    /// * `pc`: current program counter
+    /// * `needle`: variable name
+    pub fn not_yet_live_local_variable<'this>(
+        &'this self,
+        pc: GlobalAddress,
+        needle: &str,
+    ) -> Option<ContextualDieRef<'ctx, VariableDie>> {
+        self.local_variables_with_name(needle)
+            .into_iter()
+            .filter_map(|var| {
+                let scope_begin = var.ranges()?.iter().map(|range| range.begin).min()?;
+                (scope_begin >= u64::from(pc) && !var.valid_at(pc)).then_some((scope_begin, var))
+            })
+            .min_by_key(|(scope_begin, _)| *scope_begin)
+            .map(|(_, var)| var)
     }
 
     pub fn parameters(&self) -> Vec<ContextualDieRef<'_, ParameterDie>> {
@@ -1084,24 +2003,80 @@ impl<'ctx> ContextualDieRef<'ctx, FunctionDie> {
             .ok_or(FunctionNotFound(low_pc))
     }
 
-    pub fn prolog_end_place(&self) -> Result<PlaceDescriptor, Error> {
-        let mut place = self.prolog_start_place()?;
-        while !place.prolog_end {
-            match place.next() {
-                None => break,
-                Some(next_place) => place = next_place,
+    /// Return a place at the end of function prologue. It is a line table row with
+    /// a `prologue_end` flag, if there is no such row inside a function (functions written
+    /// in assembly, ffi thunks) then prologue is found by scanning of function instructions.
+    pub fn prolog_end_place(&self) -> Result<PlaceDescriptorOwned, Error> {
+        let start = self.start_instruction()?;
+        let end = self.end_instruction()?;
+
+        if let Ok(mut place) = self.prolog_start_place() {
+            while place.address < end {
+                if place.prolog_end {
+                    return Ok(place.to_owned());
+                }
+                match place.next() {
+                    None => break,
+                    Some(next_place) => place = next_place,
+                }
             }
         }
 
-        Ok(place)
+        let scan_len = prolog::PROLOG_SCAN_LEN.min(usize::from(end) - usize::from(start));
+        let code = self.debug_info.read_code(start, scan_len)?;
+        let address = GlobalAddress::from(usize::from(start) + prolog::prolog_len(&code));
+
+        let place = self.debug_info.find_place_from_pc(address)?;
+        Ok(match place {
+            Some(place) if place.address >= start => {
+                // compilers spill arguments to the stack right after a frame setup,
+                // so if the prologue ends at the function opening line
+                // then stop at the first statement of a next line instead
+                let opening_line = self.prolog_start_place().ok().map(|p| p.line_number);
+                if opening_line == Some(place.line_number) {
+                    let mut next = place.next();
+                    while let Some(next_place) = next.filter(|p| p.address < end) {
+                        if next_place.is_stmt && next_place.line_number != place.line_number {
+                            return Ok(PlaceDescriptorOwned {
+                                prolog_end: true,
+                                ..next_place.to_owned()
+                            });
+                        }
+                        next = next_place.next();
+                    }
+                }
+                PlaceDescriptorOwned {
+                    address,
+                    prolog_end: true,
+                    ..place.to_owned()
+                }
+            }
+            // function without line table rows
+            _ => {
+                let (file, line) = self
+                    .die
+                    .decl_file_line
+                    .and_then(|(file, line)| Some((self.unit().files().get(file as usize)?, line)))
+                    .map(|(file, line)| (file.clone(), line))
+                    .unwrap_or_default();
+                PlaceDescriptorOwned {
+                    file,
+                    address,
+                    line_number: line,
+                    pos_in_unit: 0,
+                    is_stmt: true,
+                    column_number: 0,
+                    epilog_begin: false,
+                    prolog_end: true,
+                }
+            }
+        })
     }
 
     pub fn prolog(&self) -> Result<Range, Error> {
-        let start = self.prolog_start_place()?;
-        let end = self.prolog_end_place()?;
         Ok(Range {
-            begin: start.address.into(),
-            end: end.address.into(),
+            begin: self.start_instruction()?.into(),
+            end: self.prolog_end_place()?.address.into(),
         })
     }
 
@@ -1109,6 +2084,55 @@ impl<'ctx> ContextualDieRef<'ctx, FunctionDie> {
         &self.die.base_attributes.ranges
     }
 
+    /// Return functions inlined at the instruction, ordered from the outermost
+    /// to the innermost one.
+    ///
+    /// # Arguments
+    ///
+    /// * `pc`: instruction global address
+    pub fn inline_chain(&self, pc: GlobalAddress) -> Vec<InlineCall> {
+        let mut chain = vec![];
+        let mut queue = VecDeque::from(self.node.children.clone());
+        while let Some(idx) = queue.pop_front() {
+            let entry = ctx_resolve_unit_call!(self, entry, idx);
+            match &entry.die {
+                DieVariant::InlineSubroutine(inline_subroutine) => {
+                    if !pc.in_ranges(&inline_subroutine.base_attributes.ranges) {
+                        continue;
+                    }
+                    let name = inline_subroutine.origin.and_then(|origin| {
+                        let (origin, unit) = self.debug_info.deref_die(self.unit(), origin)?;
+                        let DieVariant::Function(func) = &origin.die else {
+                            return None;
+                        };
+                        ContextualDieRef {
+                            debug_info: self.debug_info,
+                            unit_idx: unit.idx(),
+                            node: &origin.node,
+                            die: func,
+                        }
+                        .full_name()
+                    });
+                    let call_file = inline_subroutine
+                        .call_file
+                        .and_then(|file| self.unit().files().get(file as usize).cloned());
+                    chain.push(InlineCall {
+                        name,
+                        call_file,
+                        call_line: inline_subroutine.call_line,
+                        call_column: inline_subroutine.call_column,
+                    });
+
+                    // search deeper only inside the inlined function
+                    queue.clear();
+                    queue.extend(entry.node.children.iter());
+                }
+                _ => entry.node.children.iter().for_each(|i| queue.push_back(*i)),
+            }
+        }
+        chain
+    }
+
     pub fn inline_ranges(&self) -> Vec<Range> {
         let mut ranges = vec![];
         let mut queue = VecDeque::from(self.node.children.clone());
@@ -1138,12 +2162,21 @@ impl<'ctx> ContextualDieRef<'ctx, VariableDie> {
         }
     }
 
+    /// Return `true` if variable is live at `pc` (see [`in_variable_scope`]),
+    /// variables without a scope (like static ones) are always live.
     pub fn valid_at(&self, pc: GlobalAddress) -> bool {
         self.ranges()
-            .map(|ranges| pc.in_ranges(ranges))
+            .map(|ranges| in_variable_scope(self.unit(), pc, ranges, self.die.decl_line()))
             .unwrap_or(true)
     }
 
+    /// Return a file and a line where variable is declared.
+    pub fn decl_place(&self) -> Option<(PathBuf, u64)> {
+        let (file, line) = self.die.decl_file_line?;
+        let file = self.unit().files().get(file as usize)?;
+        Some((file.clone(), line))
+    }
+
     pub fn assume_parent_function(&self) -> Option<ContextualDieRef<'_, FunctionDie>> {
         let mut mb_parent = self.node.parent;
 
@@ -1195,6 +2228,13 @@ impl<'ctx, D: AsAllocatedData> ContextualDieRef<'ctx, D> {
         Some(parser.parse(*self, self.die.type_ref()?))
     }
 
+    /// Return address ranges where a value location is known (described by a location list),
+    /// `None` if a location is known in the whole variable scope.
+    pub fn location_ranges(&self) -> Option<Vec<Range>> {
+        let location = self.die.location()?;
+        DwarfLocation(location).live_ranges(self.debug_info, self.unit())
+    }
+
     pub fn read_value(
         &self,
         ctx: &ExplorationContext,
@@ -1226,7 +2266,8 @@ impl<'ctx, D: AsAllocatedData> ContextualDieRef<'ctx, D> {
 
 #[cfg(test)]
 mod test {
-    use crate::debugger::debugee::dwarf::NamespaceHierarchy;
+    use crate::debugger::debugee::dwarf::interner::Interner;
+    use crate::debugger::debugee::dwarf::{path_segments, NamespaceHierarchy};
 
     #[test]
     fn test_namespace_from_mangled() {
@@ -1240,11 +2281,11 @@ mod test {
             TestCase {
                 mangled: "_ZN5tokio7runtime4task3raw7RawTask4poll17h7b89afb116da4cf2E",
                 expected_ns: NamespaceHierarchy(vec![
-                    "tokio".to_string(),
-                    "runtime".to_string(),
-                    "task".to_string(),
-                    "raw".to_string(),
-                    "RawTask".to_string(),
+                    "tokio".into(),
+                    "runtime".into(),
+                    "task".into(),
+                    "raw".into(),
+                    "RawTask".into(),
                 ]),
                 expected_fn: "poll",
             },
@@ -1253,12 +2294,49 @@ mod test {
                 expected_ns: NamespaceHierarchy(vec![]),
                 expected_fn: "poll",
             },
+            // g::my_generic_fn::<alloc::vec::Vec<u8>>
+            TestCase {
+                mangled: "_RINvCsltEfNSzeSph_1g13my_generic_fnINtNtCsgFX3jaOk65H_5alloc3vec3VechEEB2_",
+                expected_ns: NamespaceHierarchy(vec!["g".into()]),
+                expected_fn: "my_generic_fn",
+            },
+            // <fizzbuzz::FizzBuzzSolver<fizzbuzz::BrokenPrinter, fizzbuzz::GoodComparator>>::solve
+            TestCase {
+                mangled: "_RNvMs2_CsgwbEWBjc97W_8fizzbuzzINtB5_14FizzBuzzSolverNtB5_13BrokenPrinterNtB5_14GoodComparatorE5solveB5_",
+                expected_ns: NamespaceHierarchy(vec![
+                    "fizzbuzz".into(),
+                    "FizzBuzzSolver".into(),
+                ]),
+                expected_fn: "solve",
+            },
         ];
 
         for tc in test_cases {
-            let (ns, name) = NamespaceHierarchy::from_mangled(tc.mangled);
+            let (ns, name) = NamespaceHierarchy::from_mangled(&Interner::default(), tc.mangled);
             assert_eq!(ns, tc.expected_ns);
             assert_eq!(name, tc.expected_fn);
         }
     }
+
+    #[test]
+    fn test_path_segments() {
+        let test_cases = [
+            ("calc::sum2", vec!["calc", "sum2"]),
+            ("FizzBuzzSolver<P,CMP>::new", vec!["FizzBuzzSolver", "new"]),
+            ("my_generic_fn<i32>", vec!["my_generic_fn"]),
+            (
+                "app::apply::<fn(u8) -> u8, alloc::vec::Vec<u8>>",
+                vec!["app", "apply"],
+            ),
+            (
+                "<app::Wrapper<u8> as core::fmt::Debug>::fmt",
+                vec!["app", "Wrapper", "fmt"],
+            ),
+            ("app::main::{closure#0}", vec!["app", "main", "{closure#0}"]),
+        ];
+
+        for (path, expected) in test_cases {
+            assert_eq!(path_segments(path), expected);
+        }
+    }
 }
@@ -0,0 +1,111 @@
+//! Function prologue analysis by instruction scanning, used when a line table has no
+//! `prologue_end` marker for a function (functions written in assembly, ffi thunks).
+//! Only typical x86-64 prologue instructions are recognized:
+//!
+//! ```text
+//! endbr64
+//! push   %rbp
+//! mov    %rsp,%rbp
+//! push   %rbx | %r12 | %r13 | %r14 | %r15
+//! sub    $imm,%rsp
+//! ```
+
+/// Number of function code bytes scanned for a prologue.
+pub const PROLOG_SCAN_LEN: usize = 64;
+
+const ENDBR64: &[u8] = &[0xF3, 0x0F, 0x1E, 0xFA];
+const PUSH_RBP: &[u8] = &[0x55];
+const MOV_RSP_RBP: [&[u8]; 2] = [&[0x48, 0x89, 0xE5], &[0x48, 0x8B, 0xEC]];
+const PUSH_CALLEE_SAVED: [&[u8]; 5] = [
+    &[0x53],
+    &[0x41, 0x54],
+    &[0x41, 0x55],
+    &[0x41, 0x56],
+    &[0x41, 0x57],
+];
+/// `sub $imm8,%rsp` and `sub $imm32,%rsp` opcodes and sizes of immediate operands.
+const SUB_RSP: [(&[u8], usize); 2] = [(&[0x48, 0x83, 0xEC], 1), (&[0x48, 0x81, 0xEC], 4)];
+
+/// Return a length (in bytes) of a function prologue,
+/// zero if code doesn't start with a known prologue.
+///
+/// # Arguments
+///
+/// * `code`: function code, starts at the first function instruction
+pub fn prolog_len(code: &[u8]) -> usize {
+    let mut pos = 0;
+    let skip = |pos: &mut usize, instruction: &[u8]| -> bool {
+        if code[*pos..].starts_with(instruction) {
+            *pos += instruction.len();
+            return true;
+        }
+        false
+    };
+
+    skip(&mut pos, ENDBR64);
+    if skip(&mut pos, PUSH_RBP) {
+        MOV_RSP_RBP.iter().any(|mov| skip(&mut pos, mov));
+    }
+    while PUSH_CALLEE_SAVED.iter().any(|push| skip(&mut pos, push)) {}
+
+    for (sub, imm_len) in SUB_RSP {
+        if code[pos..].len() >= sub.len() + imm_len && skip(&mut pos, sub) {
+            pos += imm_len;
+            break;
+        }
+    }
+    pos
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_prolog_len() {
+        struct TestCase {
+            code: &'static [u8],
+            expected: usize,
+        }
+        let test_cases = [
+            // push %rbp; mov %rsp,%rbp; sub $0x10,%rsp; mov %edi,-0x4(%rbp)
+            TestCase {
+                code: &[
+                    0x55, 0x48, 0x89, 0xE5, 0x48, 0x83, 0xEC, 0x10, 0x89, 0x7D, 0xFC,
+                ],
+                expected: 8,
+            },
+            // endbr64; push %rbp; mov %rsp,%rbp; push %r15; push %rbx; sub $0x1000,%rsp
+            TestCase {
+                code: &[
+                    0xF3, 0x0F, 0x1E, 0xFA, 0x55, 0x48, 0x89, 0xE5, 0x41, 0x57, 0x53, 0x48, 0x81,
+                    0xEC, 0x00, 0x10, 0x00, 0x00, 0xC3,
+                ],
+                expected: 18,
+            },
+            // push %rbx; sub $0x20,%rsp (frame pointer omitted)
+            TestCase {
+                code: &[0x53, 0x48, 0x83, 0xEC, 0x20, 0xC3],
+                expected: 5,
+            },
+            // mov %rdi,%rax; ret (no prologue)
+            TestCase {
+                code: &[0x48, 0x89, 0xF8, 0xC3],
+                expected: 0,
+            },
+            // truncated sub instruction
+            TestCase {
+                code: &[0x55, 0x48, 0x81, 0xEC, 0x00],
+                expected: 1,
+            },
+            TestCase {
+                code: &[],
+                expected: 0,
+            },
+        ];
+
+        for tc in test_cases {
+            assert_eq!(prolog_len(tc.code), tc.expected);
+        }
+    }
+}
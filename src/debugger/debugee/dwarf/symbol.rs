@@ -1,13 +1,60 @@
 use crate::debugger::address::GlobalAddress;
 use object::{Object, ObjectSymbol, ObjectSymbolTable, SymbolKind};
 use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
 use std::ops::Deref;
+use std::path::PathBuf;
 
 #[derive(Debug, Clone)]
 pub struct Symbol {
     pub name: String,
     pub kind: SymbolKind,
     pub addr: GlobalAddress,
+    pub size: u64,
+}
+
+/// Kind of a symbol found by [`crate::debugger::Debugger::search_symbols`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SymbolMatchKind {
+    /// Executable code.
+    Function,
+    /// Data object (static or thread local variable).
+    Object,
+    /// Other symbol kinds (sections, labels, etc.).
+    Other,
+}
+
+impl From<SymbolKind> for SymbolMatchKind {
+    fn from(kind: SymbolKind) -> Self {
+        match kind {
+            SymbolKind::Text => SymbolMatchKind::Function,
+            SymbolKind::Data | SymbolKind::Tls => SymbolMatchKind::Object,
+            _ => SymbolMatchKind::Other,
+        }
+    }
+}
+
+impl Display for SymbolMatchKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SymbolMatchKind::Function => f.write_str("func"),
+            SymbolMatchKind::Object => f.write_str("object"),
+            SymbolMatchKind::Other => f.write_str("other"),
+        }
+    }
+}
+
+/// Symbol found in debug information (functions and variables) or in a symbol table.
+#[derive(Debug, Clone)]
+pub struct SymbolMatch {
+    pub name: String,
+    pub kind: SymbolMatchKind,
+    pub addr: GlobalAddress,
+    /// Source file where symbol is defined (if symbol found in debug information)
+    /// and declaration line (if known).
+    pub source: Option<(PathBuf, Option<u64>)>,
+    /// Object file (executable or shared library) that contains a symbol.
+    pub object: PathBuf,
 }
 
 #[derive(Debug, Clone)]
@@ -27,7 +74,12 @@ impl SymbolTab {
         'data: 'file,
         OBJ: Object<'data, 'file>,
     {
-        object_file.symbol_table().as_ref().map(|sym_table| {
+        // shared libraries (like libstdc++) are often stripped of `.symtab`,
+        // but still have a dynamic symbol table
+        let sym_table = object_file
+            .symbol_table()
+            .or_else(|| object_file.dynamic_symbol_table());
+        sym_table.as_ref().map(|sym_table| {
             SymbolTab(
                 sym_table
                     .symbols()
@@ -40,6 +92,7 @@ impl SymbolTab {
                                 name,
                                 kind: symbol.kind(),
                                 addr: symbol.address().into(),
+                                size: symbol.size(),
                             },
                         )
                     })
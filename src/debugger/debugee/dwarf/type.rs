@@ -1,20 +1,21 @@
 use crate::debugger::debugee::dwarf::eval::{AddressKind, ExpressionEvaluator};
 use crate::debugger::debugee::dwarf::unit::{
-    ArrayDie, AtomicDie, BaseTypeDie, ConstTypeDie, DieRef, DieVariant, EnumTypeDie, PointerType,
-    RestrictDie, StructTypeDie, SubroutineDie, TypeDefDie, TypeMemberDie, UnionTypeDie,
-    VolatileDie,
+    ArrayDie, AtomicDie, BaseTypeDie, ConstTypeDie, DieRef, DieVariant, DiscrValue, EnumTypeDie,
+    PointerType, RestrictDie, StructTypeDie, SubroutineDie, TypeDefDie, TypeMemberDie,
+    UnionTypeDie, Unit, VolatileDie,
 };
 use crate::debugger::debugee::dwarf::{eval, ContextualDieRef, EndianArcSlice, NamespaceHierarchy};
 use crate::debugger::error::Error;
 use crate::debugger::variable::select::ObjectBinaryRepr;
 use crate::debugger::ExplorationContext;
 use crate::version::Version;
-use crate::{ctx_resolve_unit_call, weak_error};
+use crate::{ctx_resolve_unit_call, resolve_unit_call, weak_error};
 use bytes::Bytes;
-use gimli::{AttributeValue, DwAte, Expression};
+use gimli::{AttributeValue, DW_ATE_signed, DW_ATE_signed_char, DwAte, Expression};
 use log::warn;
 use std::cell::Cell;
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::iter;
 use std::mem;
 use strum_macros::Display;
 use uuid::Uuid;
@@ -58,11 +59,44 @@ pub enum MemberLocation {
     Expr(MemberLocationExpression),
 }
 
+/// Placement of a bit field member.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BitField {
+    /// Offset in bits from the beginning of a containing structure.
+    pub bit_offset: u64,
+    /// Size in bits.
+    pub bit_size: u64,
+}
+
+impl BitField {
+    /// Extract bit field value from structure data. Result has a size of the member type,
+    /// signed values are sign-extended.
+    fn extract(&self, data: &[u8], type_size: usize, signed: bool) -> Option<Vec<u8>> {
+        if self.bit_size == 0 || self.bit_size > 64 || type_size > 16 {
+            return None;
+        }
+        let first_byte = (self.bit_offset / 8) as usize;
+        let last_byte = (self.bit_offset + self.bit_size).div_ceil(8) as usize;
+        let bytes = data.get(first_byte..last_byte)?;
+
+        let mut buf = [0; 16];
+        buf[..bytes.len()].copy_from_slice(bytes);
+        let mask = (1u128 << self.bit_size) - 1;
+        let mut value = (u128::from_le_bytes(buf) >> (self.bit_offset % 8)) & mask;
+        if signed && (value >> (self.bit_size - 1)) & 1 == 1 {
+            value |= !mask;
+        }
+        Some(value.to_le_bytes()[..type_size].to_vec())
+    }
+}
+
 #[derive(Clone)]
 pub struct StructureMember {
     pub in_struct_location: Option<MemberLocation>,
     pub name: Option<String>,
     pub type_ref: Option<TypeIdentity>,
+    /// Bit field placement, `None` if member isn't a bit field.
+    pub bit_field: Option<BitField>,
 }
 
 impl StructureMember {
@@ -81,6 +115,17 @@ impl StructureMember {
     ) -> Option<ObjectBinaryRepr> {
         let type_size = r#type.type_size_in_bytes(eval_ctx, self.type_ref?)? as usize;
 
+        if let Some(bit_field) = self.bit_field {
+            let signed = r#type.is_signed(self.type_ref?);
+            let raw_data = bit_field.extract(&base_data.raw_data, type_size, signed)?;
+            return Some(ObjectBinaryRepr {
+                raw_data: Bytes::from(raw_data),
+                // bit fields are not addressable
+                address: None,
+                size: type_size,
+            });
+        }
+
         let base_entity_addr = base_data.raw_data.as_ptr() as usize;
         let addr = match self.in_struct_location.as_ref()? {
             MemberLocation::Offset(offset) => {
@@ -92,6 +137,10 @@ impl StructureMember {
         }? as *const u8;
 
         let offset = addr as isize - base_entity_addr as isize;
+        // member must be inside a parent data (for example, when member location is incorrect)
+        if offset < 0 || offset as usize + type_size > base_data.raw_data.len() {
+            return None;
+        }
         let new_in_debugee_addr = base_data
             .address
             .map(|addr| (addr as isize + offset) as usize);
@@ -261,7 +310,7 @@ pub enum TypeDeclaration {
         byte_size: Option<u64>,
         discr_type: Option<Box<StructureMember>>,
         /// key `None` is default enumerator
-        enumerators: HashMap<Option<i64>, StructureMember>,
+        enumerators: HashMap<Option<DiscrValue>, StructureMember>,
     },
     Subroutine {
         namespaces: NamespaceHierarchy,
@@ -338,6 +387,21 @@ impl ComplexType {
         }
     }
 
+    /// Returns true if type is a signed scalar type.
+    pub fn is_signed(&self, typ: TypeIdentity) -> bool {
+        #[allow(non_upper_case_globals)]
+        match self.types.get(&typ) {
+            Some(TypeDeclaration::Scalar(ScalarType {
+                encoding: Some(DW_ATE_signed | DW_ATE_signed_char),
+                ..
+            })) => true,
+            Some(TypeDeclaration::ModifiedType {
+                inner: Some(inner), ..
+            }) => self.is_signed(*inner),
+            _ => false,
+        }
+    }
+
     /// Visit type children in bfs order, `start_at` - identity of root type.
     pub fn bfs_iterator(&self, start_at: TypeIdentity) -> BfsIterator {
         BfsIterator {
@@ -426,6 +490,55 @@ impl<'a> Iterator for BfsIterator<'a> {
     }
 }
 
+/// Return index of a closure if type name is a name of closure environment
+/// (like `{closure_env#0}<i32>`).
+fn closure_env_index(type_name: &str) -> Option<u32> {
+    let rest = type_name.strip_prefix("{closure_env#")?;
+    let (idx, _) = rest.split_once('}')?;
+    idx.parse().ok()
+}
+
+/// Return closure type name in format `closure@file:line`. A place is taken from
+/// the closure type or, if there is no such information, from the closure function
+/// (closure functions are declared in the same namespace as their environments,
+/// but possibly in another compilation unit).
+/// If there is no place information at all, the closure namespace is used instead.
+fn closure_name(ctx_die: ContextualDieRef<'_, StructTypeDie>, idx: u32) -> String {
+    let fn_name = format!("{{closure#{idx}}}");
+    let namespaces = ctx_die.namespaces();
+    let render_place = |unit: &Unit, (file, line): (u64, u64)| {
+        let file = unit.files().get(file as usize)?;
+        Some(format!("closure@{}:{line}", file.display()))
+    };
+
+    let place = ctx_die
+        .die
+        .decl_file_line
+        .and_then(|file_line| render_place(ctx_die.unit(), file_line))
+        .or_else(|| {
+            let debug_info = ctx_die.debug_info;
+            let own_unit = ctx_die.unit();
+            let other_units = debug_info.get_units().ok()?.iter();
+            iter::once(own_unit)
+                .chain(other_units.filter(|unit| unit.idx() != own_unit.idx()))
+                .find_map(|unit| {
+                    resolve_unit_call!(debug_info, unit, entries_it).find_map(|entry| match &entry
+                        .die
+                    {
+                        DieVariant::Function(func)
+                            if func.base_attributes.name.as_deref() == Some(&fn_name)
+                                && func.namespace == namespaces =>
+                        {
+                            render_place(unit, func.decl_file_line?)
+                        }
+                        _ => None,
+                    })
+                })
+        });
+
+    place.unwrap_or_else(|| format!("closure@{}", namespaces.join("::")))
+}
+
 /// Dwarf DIE parser.
 pub struct TypeParser {
     known_type_ids: HashSet<TypeIdentity>,
@@ -544,7 +657,12 @@ impl TypeParser {
     }
 
     fn parse_base_type(&mut self, ctx_die: ContextualDieRef<'_, BaseTypeDie>) -> TypeDeclaration {
-        let name = ctx_die.die.base_attributes.name.clone();
+        let name = ctx_die
+            .die
+            .base_attributes
+            .name
+            .as_deref()
+            .map(ToString::to_string);
         TypeDeclaration::Scalar(ScalarType {
             namespaces: ctx_die.namespaces(),
             name,
@@ -638,7 +756,17 @@ impl TypeParser {
         &mut self,
         ctx_die: ContextualDieRef<'_, StructTypeDie>,
     ) -> TypeDeclaration {
-        let name = ctx_die.die.base_attributes.name.clone();
+        let mut name = ctx_die
+            .die
+            .base_attributes
+            .name
+            .as_deref()
+            .map(ToString::to_string);
+        let closure_idx = name.as_deref().and_then(closure_env_index);
+        if let Some(idx) = closure_idx {
+            name = Some(closure_name(ctx_die, idx));
+        }
+
         let members = ctx_die
             .node
             .children
@@ -655,6 +783,15 @@ impl TypeParser {
                 }
                 None
             })
+            .map(|mut member| {
+                // older compilers prefix names of variables captured by reference
+                if closure_idx.is_some() {
+                    member.name = member
+                        .name
+                        .map(|name| name.trim_start_matches("_ref__").to_string());
+                }
+                member
+            })
             .collect::<Vec<_>>();
 
         let type_params = ctx_die
@@ -664,7 +801,7 @@ impl TypeParser {
             .filter_map(|child_idx| {
                 let entry = ctx_resolve_unit_call!(ctx_die, entry, *child_idx);
                 if let DieVariant::TemplateType(param) = &entry.die {
-                    let name = param.base_attributes.name.clone()?;
+                    let name = param.base_attributes.name.as_deref()?.to_string();
                     self.parse_inner(ctx_die, param.type_ref?);
                     return Some((name, param.type_ref));
                 }
@@ -698,10 +835,44 @@ impl TypeParser {
             self.parse_inner(ctx_die, reference);
         }
 
+        let bit_field = ctx_die.die.bit_size.and_then(|bit_size| {
+            let location_bits = match in_struct_location {
+                Some(MemberLocation::Offset(offset)) => offset as u64 * 8,
+                _ => 0,
+            };
+            let bit_offset = match (ctx_die.die.data_bit_offset, ctx_die.die.bit_offset) {
+                (Some(data_bit_offset), _) => location_bits + data_bit_offset,
+                // DWARF 2, 3 bit offset is counted from the most significant bit of
+                // a storage unit, translate it for little-endian
+                (None, Some(bit_offset)) => {
+                    let storage_bits = ctx_die.die.byte_size? * 8;
+                    (location_bits + storage_bits).checked_sub(bit_offset + bit_size)?
+                }
+                (None, None) => location_bits,
+            };
+            Some(BitField {
+                bit_offset,
+                bit_size,
+            })
+        });
+
+        let in_struct_location = match bit_field {
+            Some(bit_field) if in_struct_location.is_none() => {
+                Some(MemberLocation::Offset((bit_field.bit_offset / 8) as i64))
+            }
+            _ => in_struct_location,
+        };
+
         StructureMember {
             in_struct_location,
-            name: ctx_die.die.base_attributes.name.clone(),
+            name: ctx_die
+                .die
+                .base_attributes
+                .name
+                .as_deref()
+                .map(ToString::to_string),
             type_ref: mb_type_ref,
+            bit_field,
         }
     }
 
@@ -709,7 +880,12 @@ impl TypeParser {
         &mut self,
         ctx_die: ContextualDieRef<'_, StructTypeDie>,
     ) -> TypeDeclaration {
-        let name = ctx_die.die.base_attributes.name.clone();
+        let name = ctx_die
+            .die
+            .base_attributes
+            .name
+            .as_deref()
+            .map(ToString::to_string);
 
         let variant_part = ctx_die.node.children.iter().find_map(|c_idx| {
             let entry = ctx_resolve_unit_call!(ctx_die, entry, *c_idx);
@@ -786,7 +962,12 @@ impl TypeParser {
     }
 
     fn parse_enum(&mut self, ctx_die: ContextualDieRef<'_, EnumTypeDie>) -> TypeDeclaration {
-        let name = ctx_die.die.base_attributes.name.clone();
+        let name = ctx_die
+            .die
+            .base_attributes
+            .name
+            .as_deref()
+            .map(ToString::to_string);
 
         let mb_discr_type = ctx_die.die.type_ref;
         if let Some(reference) = mb_discr_type {
@@ -820,7 +1001,12 @@ impl TypeParser {
     }
 
     fn parse_union(&mut self, ctx_die: ContextualDieRef<'_, UnionTypeDie>) -> TypeDeclaration {
-        let name = ctx_die.die.base_attributes.name.clone();
+        let name = ctx_die
+            .die
+            .base_attributes
+            .name
+            .as_deref()
+            .map(ToString::to_string);
         let members = ctx_die
             .node
             .children
@@ -837,6 +1023,13 @@ impl TypeParser {
                 }
                 None
             })
+            .map(|mut member| {
+                // union members may have no location, all of them start at union beginning
+                member
+                    .in_struct_location
+                    .get_or_insert(MemberLocation::Offset(0));
+                member
+            })
             .collect::<Vec<_>>();
 
         TypeDeclaration::Union {
@@ -848,7 +1041,12 @@ impl TypeParser {
     }
 
     fn parse_pointer(&mut self, ctx_die: ContextualDieRef<'_, PointerType>) -> TypeDeclaration {
-        let name = ctx_die.die.base_attributes.name.clone();
+        let name = ctx_die
+            .die
+            .base_attributes
+            .name
+            .as_deref()
+            .map(ToString::to_string);
 
         let mb_type_ref = ctx_die.die.type_ref;
         if let Some(reference) = mb_type_ref {
@@ -866,7 +1064,12 @@ impl TypeParser {
         &mut self,
         ctx_die: ContextualDieRef<'_, SubroutineDie>,
     ) -> TypeDeclaration {
-        let name = ctx_die.die.base_attributes.name.clone();
+        let name = ctx_die
+            .die
+            .base_attributes
+            .name
+            .as_deref()
+            .map(ToString::to_string);
         let mb_ret_type_ref = ctx_die.die.return_type_ref;
         if let Some(reference) = mb_ret_type_ref {
             self.parse_inner(ctx_die, reference);
@@ -883,7 +1086,12 @@ impl TypeParser {
 macro_rules! parse_modifier_fn {
     ($fn_name: ident, $die: ty, $modifier: expr) => {
         fn $fn_name(&mut self, ctx_die: ContextualDieRef<'_, $die>) -> TypeDeclaration {
-            let name = ctx_die.die.base_attributes.name.clone();
+            let name = ctx_die
+                .die
+                .base_attributes
+                .name
+                .as_deref()
+                .map(ToString::to_string);
             let mb_type_ref = ctx_die.die.type_ref;
             if let Some(inner_type) = mb_type_ref {
                 self.parse_inner(ctx_die, inner_type);
@@ -911,3 +1119,38 @@ impl TypeParser {
 /// A cache structure for types.
 /// Every type identifies by its `TypeIdentity` and dwarf unit uuid.
 pub type TypeCache = HashMap<(Uuid, TypeIdentity), ComplexType>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bit_field_extract() {
+        // bits: a: 3 = 0b101, b: 5 = -3 (0b11101), c: 4 = 0b0011 (crosses a byte border)
+        let data = [0b1110_1101, 0b0000_0100, 0xFF];
+        let a = BitField {
+            bit_offset: 0,
+            bit_size: 3,
+        };
+        assert_eq!(a.extract(&data, 1, false), Some(vec![0b101]));
+        let b = BitField {
+            bit_offset: 3,
+            bit_size: 5,
+        };
+        assert_eq!(b.extract(&data, 1, false), Some(vec![0b11101]));
+        assert_eq!(
+            b.extract(&data, 4, true),
+            Some((-3i32).to_le_bytes().to_vec())
+        );
+        let c = BitField {
+            bit_offset: 6,
+            bit_size: 4,
+        };
+        assert_eq!(c.extract(&data, 2, false), Some(vec![0b0011, 0]));
+        let out_of_data = BitField {
+            bit_offset: 20,
+            bit_size: 8,
+        };
+        assert_eq!(out_of_data.extract(&data, 1, false), None);
+    }
+}
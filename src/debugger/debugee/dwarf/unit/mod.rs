@@ -4,6 +4,7 @@ pub use parser::DwarfUnitParser;
 
 use crate::debugger::address::GlobalAddress;
 use crate::debugger::debugee::dwarf::eval::ExpressionEvaluator;
+use crate::debugger::debugee::dwarf::interner::IStr;
 use crate::debugger::debugee::dwarf::utils::PathSearchIndex;
 use crate::debugger::debugee::dwarf::{EndianArcSlice, NamespaceHierarchy};
 use crate::debugger::debugee::Debugee;
@@ -17,7 +18,7 @@ use once_cell::sync::OnceCell;
 use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
 use std::path::{Path, PathBuf};
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
 const IS_STMT: u8 = 1 << 1;
@@ -133,6 +134,11 @@ impl<'a> PlaceDescriptor<'a> {
         self.unit.find_place_by_idx(self.pos_in_unit - 1)
     }
 
+    /// Return a source code of place file if it is embedded into debug information.
+    pub fn embedded_source(&self) -> Option<Arc<str>> {
+        self.unit.embedded_source(self.file)
+    }
+
     pub fn line_eq(&self, other: &PlaceDescriptor) -> bool {
         self.file == other.file && self.line_number == other.line_number
     }
@@ -163,17 +169,18 @@ impl<'a> PartialEq for PlaceDescriptor<'a> {
 
 #[derive(Debug, PartialEq, Clone, Eq, Hash)]
 pub struct DieAttributes {
-    pub name: Option<String>,
+    pub name: Option<IStr>,
     pub ranges: Box<[Range]>,
 }
 
 #[derive(Debug, PartialEq, Clone, Eq)]
 pub struct FunctionDie {
     pub namespace: NamespaceHierarchy,
-    pub linkage_name: Option<String>,
+    pub linkage_name: Option<IStr>,
     pub decl_file_line: Option<(u64, u64)>,
     pub base_attributes: DieAttributes,
     pub fb_addr: Option<Attribute<EndianArcSlice>>,
+    pub return_type_ref: Option<DieRef>,
 }
 
 impl FunctionDie {
@@ -196,6 +203,10 @@ impl FunctionDie {
         if self.decl_file_line.is_none() {
             self.decl_file_line = declaration.decl_file_line;
         }
+
+        if self.return_type_ref.is_none() {
+            self.return_type_ref = declaration.return_type_ref;
+        }
     }
 }
 
@@ -207,10 +218,12 @@ pub struct LexicalBlockDie {
 #[derive(Debug, Clone)]
 pub struct VariableDie {
     pub base_attributes: DieAttributes,
+    pub linkage_name: Option<IStr>,
     pub type_ref: Option<DieRef>,
     pub location: Option<Attribute<EndianArcSlice>>,
     pub lexical_block_idx: Option<usize>,
     pub fn_block_idx: Option<usize>,
+    pub decl_file_line: Option<(u64, u64)>,
 }
 
 #[derive(Debug, Clone)]
@@ -239,15 +252,21 @@ pub struct ArraySubrangeDie {
 pub struct StructTypeDie {
     pub base_attributes: DieAttributes,
     pub byte_size: Option<u64>,
+    pub decl_file_line: Option<(u64, u64)>,
 }
 
 #[derive(Debug, Clone)]
 pub struct TypeMemberDie {
     pub base_attributes: DieAttributes,
-    #[allow(unused)]
     pub byte_size: Option<u64>,
     pub location: Option<Attribute<EndianArcSlice>>,
     pub type_ref: Option<DieRef>,
+    /// Size in bits for bit field members.
+    pub bit_size: Option<u64>,
+    /// Offset in bits from the beginning of a containing entity (DWARF 4+ bit fields).
+    pub data_bit_offset: Option<u64>,
+    /// Offset in bits from the most significant bit of a storage unit (DWARF 2, 3 bit fields).
+    pub bit_offset: Option<u64>,
 }
 
 #[derive(Debug, Clone)]
@@ -273,7 +292,43 @@ pub struct VariantPart {
 #[derive(Debug, Clone)]
 pub struct Variant {
     pub base_attributes: DieAttributes,
-    pub discr_value: Option<i64>,
+    pub discr_value: Option<DiscrValue>,
+}
+
+/// Value of a variant discriminant (`DW_AT_discr_value`). Constant class values
+/// (`DW_FORM_data<n>`) have no signedness, they are interpreted according to
+/// a signedness of a discriminant type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DiscrValue {
+    /// Value bits.
+    pub bits: u64,
+    /// Number of significant bytes, 8 for LEB128 encoded (already extended) values.
+    pub size: u8,
+}
+
+impl DiscrValue {
+    pub fn from_attr(attr: &AttributeValue<EndianArcSlice>) -> Option<Self> {
+        let (bits, size) = match *attr {
+            AttributeValue::Data1(v) => (v as u64, 1),
+            AttributeValue::Data2(v) => (v as u64, 2),
+            AttributeValue::Data4(v) => (v as u64, 4),
+            AttributeValue::Data8(v) => (v, 8),
+            AttributeValue::Sdata(v) => (v as u64, 8),
+            AttributeValue::Udata(v) => (v, 8),
+            _ => return None,
+        };
+        Some(Self { bits, size })
+    }
+
+    /// Return discriminant value extended with respect of discriminant type signedness.
+    pub fn value(&self, signed: bool) -> i128 {
+        let shift = 64 - u32::from(self.size.clamp(1, 8)) * 8;
+        if signed {
+            (((self.bits << shift) as i64) >> shift) as i128
+        } else {
+            ((self.bits << shift) >> shift) as i128
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -318,6 +373,8 @@ pub struct SubroutineDie {
 #[derive(Debug, Clone)]
 pub struct InlineSubroutineDie {
     pub base_attributes: DieAttributes,
+    /// Reference to an abstract instance of inlined function.
+    pub origin: Option<DieRef>,
     pub call_file: Option<u64>,
     pub call_line: Option<u64>,
     pub call_column: Option<u64>,
@@ -461,9 +518,9 @@ struct UnitLazyPart {
     entries: Vec<Entry>,
     die_ranges: Vec<DieRange>,
     // index for variable die position: { variable name -> [namespaces : die position in unit] }
-    variable_index: HashMap<String, Vec<(NamespaceHierarchy, usize)>>,
+    variable_index: HashMap<IStr, Vec<(NamespaceHierarchy, usize)>>,
     // index for type die position: { type name -> offset in unit }
-    type_index: HashMap<String, UnitOffset>,
+    type_index: HashMap<IStr, UnitOffset>,
     // index for variables: offset in unit -> position in unit `entries`
     die_offsets_index: HashMap<UnitOffset, usize>,
     // index for function entries: function -> die position in unit `entries`
@@ -481,16 +538,15 @@ pub enum UnitResult<T> {
 /// then parsing of lazy unit part is happening
 #[macro_export]
 macro_rules! resolve_unit_call {
-    ($dwarf: expr, $unit: expr, $fn_name: tt) => {
-        resolve_unit_call!($dwarf, $unit, $fn_name,)
+    ($debug_info: expr, $unit: expr, $fn_name: tt) => {
+        resolve_unit_call!($debug_info, $unit, $fn_name,)
     };
-    ($dwarf: expr, $unit: expr, $fn_name: tt, $($arg: expr),*) => {{
-        use $crate::debugger::debugee::dwarf::unit::DwarfUnitParser;
+    ($debug_info: expr, $unit: expr, $fn_name: tt, $($arg: expr),*) => {{
         use $crate::debugger::debugee::dwarf::unit::UnitResult;
         match $unit.$fn_name( $($arg,)*) {
             UnitResult::Ok(value) => value,
             UnitResult::Reload => {
-                let parser = DwarfUnitParser::new(&$dwarf);
+                let parser = $debug_info.unit_parser();
                 $unit.reload(parser).expect("unit parsing was fail unexpectedly");
                 $unit.$fn_name(
                         $(
@@ -526,12 +582,15 @@ pub struct Unit {
     idx: usize,
     properties: UnitProperties,
     files: Vec<PathBuf>,
+    /// Source code embedded into debug information (DWARF 5 `DW_LNCT_LLVM_source`),
+    /// key is an index in `files` list.
+    embedded_sources: HashMap<usize, Arc<str>>,
     /// List of program lines, ordered by its address
     lines: Vec<LineRow>,
     ranges: Vec<Range>,
     lazy_part: OnceCell<UnitLazyPart>,
     language: Option<DwLang>,
-    producer: Option<String>,
+    producer: Option<IStr>,
 }
 
 impl Clone for Unit {
@@ -544,6 +603,7 @@ impl Clone for Unit {
             idx: self.idx,
             properties: self.properties.clone(),
             files: self.files.clone(),
+            embedded_sources: self.embedded_sources.clone(),
             lines: self.lines.clone(),
             ranges: self.ranges.clone(),
             lazy_part: self.lazy_part.clone(),
@@ -731,6 +791,44 @@ impl Unit {
         result
     }
 
+    /// Return rows of a statement that contains a row at given index and an address
+    /// where a statement ends. A statement is a contiguous run of rows of the same line.
+    ///
+    /// # Arguments
+    ///
+    /// * `line_pos`: index of a row in unit line vector
+    pub fn find_statement(
+        &self,
+        line_pos: usize,
+    ) -> Option<(Vec<PlaceDescriptor<'_>>, GlobalAddress)> {
+        let row = self.lines.get(line_pos)?;
+        if row.end_sequence() {
+            return None;
+        }
+        let same_line = |other: &LineRow| {
+            !other.end_sequence() && other.file_index == row.file_index && other.line == row.line
+        };
+
+        let mut start = line_pos;
+        while start > 0 && same_line(&self.lines[start - 1]) {
+            start -= 1;
+        }
+        let mut end = line_pos + 1;
+        while end < self.lines.len() && same_line(&self.lines[end]) {
+            end += 1;
+        }
+
+        let rows = (start..end)
+            .filter_map(|pos| self.find_place_by_idx(pos))
+            .collect();
+        let end_address = self
+            .lines
+            .get(end)
+            .map(|row| row.address)
+            .unwrap_or(self.lines[end - 1].address);
+        Some((rows, GlobalAddress::from(end_address)))
+    }
+
     /// Return list on debug entries.
     /// Note: this method requires a full unit.
     pub fn entries(&self) -> UnitResult<&Vec<Entry>> {
@@ -795,6 +893,38 @@ impl Unit {
         }
     }
 
+    /// Return names of all variables in unit.
+    /// Note: this method requires a full unit.
+    pub fn variable_names(&self) -> UnitResult<Vec<&str>> {
+        match self.lazy_part.get() {
+            None => UnitResult::Reload,
+            Some(additional) => UnitResult::Ok(
+                additional
+                    .variable_index
+                    .keys()
+                    .map(AsRef::as_ref)
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Return all variables in unit as (name, namespace, die position in unit) triples.
+    /// Note: this method requires a full unit.
+    pub fn variables_it(
+        &self,
+    ) -> UnitResult<impl Iterator<Item = (&str, &NamespaceHierarchy, usize)>> {
+        match self.lazy_part.get() {
+            None => UnitResult::Reload,
+            Some(additional) => UnitResult::Ok(additional.variable_index.iter().flat_map(
+                |(name, locations)| {
+                    locations
+                        .iter()
+                        .map(move |(namespace, idx)| (name.as_ref(), namespace, *idx))
+                },
+            )),
+        }
+    }
+
     /// Return locations of a type with name equal to `name` parameter.
     /// Note: this method requires a full unit.
     ///
@@ -852,6 +982,15 @@ impl Unit {
         &self.files
     }
 
+    /// Return a source code of unit file if it is embedded into debug information.
+    pub fn embedded_source(&self, file: &Path) -> Option<Arc<str>> {
+        if self.embedded_sources.is_empty() {
+            return None;
+        }
+        let idx = self.files.iter().position(|f| f == file)?;
+        self.embedded_sources.get(&idx).cloned()
+    }
+
     /// Return pairs (file path, indexes of file lines in unit.lines list). This useful for
     /// create searching indexes.
     pub(super) fn file_path_with_lines_pairs(
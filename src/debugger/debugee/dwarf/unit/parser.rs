@@ -1,10 +1,11 @@
+use crate::debugger::debugee::dwarf::interner::{IStr, Interner};
 use crate::debugger::debugee::dwarf::unit::{
     ArrayDie, ArraySubrangeDie, AtomicDie, BaseTypeDie, ConstTypeDie, DieAttributes, DieRange,
-    DieRef, DieVariant, Entry, EnumTypeDie, EnumeratorDie, FunctionDie, InlineSubroutineDie,
-    LexicalBlockDie, LineRow, Namespace, Node, ParameterDie, PointerType, RestrictDie,
-    StructTypeDie, SubroutineDie, TemplateTypeParameter, TypeDefDie, TypeMemberDie, UnionTypeDie,
-    Unit, UnitLazyPart, UnitProperties, VariableDie, Variant, VariantPart, VolatileDie,
-    END_SEQUENCE, EPILOG_BEGIN, IS_STMT, PROLOG_END,
+    DieRef, DieVariant, DiscrValue, Entry, EnumTypeDie, EnumeratorDie, FunctionDie,
+    InlineSubroutineDie, LexicalBlockDie, LineRow, Namespace, Node, ParameterDie, PointerType,
+    RestrictDie, StructTypeDie, SubroutineDie, TemplateTypeParameter, TypeDefDie, TypeMemberDie,
+    UnionTypeDie, Unit, UnitLazyPart, UnitProperties, VariableDie, Variant, VariantPart,
+    VolatileDie, END_SEQUENCE, EPILOG_BEGIN, IS_STMT, PROLOG_END,
 };
 use crate::debugger::debugee::dwarf::utils::PathSearchIndex;
 use crate::debugger::debugee::dwarf::{EndianArcSlice, NamespaceHierarchy};
@@ -13,28 +14,34 @@ use crate::debugger::rust::Environment;
 use crate::weak_error;
 use fallible_iterator::FallibleIterator;
 use gimli::{
-    AttributeValue, DW_AT_address_class, DW_AT_byte_size, DW_AT_call_column, DW_AT_call_file,
-    DW_AT_call_line, DW_AT_const_value, DW_AT_count, DW_AT_data_member_location, DW_AT_decl_file,
+    AttributeValue, DW_AT_abstract_origin, DW_AT_address_class, DW_AT_bit_offset, DW_AT_bit_size,
+    DW_AT_byte_size, DW_AT_call_column, DW_AT_call_file, DW_AT_call_line, DW_AT_const_value,
+    DW_AT_count, DW_AT_data_bit_offset, DW_AT_data_member_location, DW_AT_decl_file,
     DW_AT_decl_line, DW_AT_declaration, DW_AT_discr, DW_AT_discr_value, DW_AT_encoding,
     DW_AT_frame_base, DW_AT_language, DW_AT_linkage_name, DW_AT_location, DW_AT_lower_bound,
-    DW_AT_name, DW_AT_producer, DW_AT_specification, DW_AT_type, DW_AT_upper_bound,
-    DebuggingInformationEntry, DwAt, Range, Reader, UnitHeader, UnitOffset,
+    DW_AT_name, DW_AT_producer, DW_AT_specification, DW_AT_type, DW_AT_upper_bound, DW_FORM_block,
+    DW_FORM_data1, DW_FORM_data16, DW_FORM_data2, DW_FORM_data4, DW_FORM_data8, DW_FORM_line_strp,
+    DW_FORM_string, DW_FORM_strp, DW_FORM_strx, DW_FORM_strx1, DW_FORM_strx2, DW_FORM_strx4,
+    DW_FORM_udata, DebugLineStrOffset, DebugStrOffset, DebugStrOffsetsIndex,
+    DebuggingInformationEntry, DwAt, DwForm, DwLnct, Range, Reader, ReaderOffset, Section,
+    UnitHeader, UnitOffset,
 };
 use log::warn;
 use once_cell::sync::OnceCell;
 use std::collections::HashMap;
 use std::num::NonZeroU64;
 use std::path::PathBuf;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
 pub struct DwarfUnitParser<'a> {
     dwarf: &'a gimli::Dwarf<EndianArcSlice>,
+    interner: &'a Interner,
 }
 
 impl<'a> DwarfUnitParser<'a> {
-    pub fn new(dwarf: &'a gimli::Dwarf<EndianArcSlice>) -> Self {
-        Self { dwarf }
+    pub fn new(dwarf: &'a gimli::Dwarf<EndianArcSlice>, interner: &'a Interner) -> Self {
+        Self { dwarf, interner }
     }
 
     fn attr_to_string(
@@ -42,10 +49,10 @@ impl<'a> DwarfUnitParser<'a> {
         unit: &gimli::Unit<EndianArcSlice, usize>,
         die: &DebuggingInformationEntry<EndianArcSlice, usize>,
         attr: DwAt,
-    ) -> gimli::Result<Option<String>> {
+    ) -> gimli::Result<Option<IStr>> {
         die.attr(attr)?
             .and_then(|attr| self.dwarf.attr_string(unit, attr.value()).ok())
-            .map(|l| l.to_string_lossy().map(|s| s.to_string()))
+            .map(|l| l.to_string_lossy().map(|s| self.interner.intern(&s)))
             .transpose()
     }
 
@@ -58,11 +65,15 @@ impl<'a> DwarfUnitParser<'a> {
             .and_then(|n| n.to_string_lossy().ok().map(|s| s.to_string()));
 
         let mut files = vec![];
+        let mut embedded_sources = HashMap::new();
         let mut lines = vec![];
         if let Some(ref lp) = unit.line_program {
             let mut rows = lp.clone().rows();
             lines = parse_lines(&mut rows)?;
             files = parse_files(self.dwarf, &unit, &rows)?;
+            embedded_sources =
+                weak_error!(parse_embedded_sources(self.dwarf, &unit, rows.header()))
+                    .unwrap_or_default();
         }
         lines.sort_unstable_by_key(|x| x.address);
 
@@ -95,6 +106,7 @@ impl<'a> DwarfUnitParser<'a> {
             id: Uuid::new_v4(),
             name,
             files,
+            embedded_sources,
             lines,
             ranges,
             lazy_part: OnceCell::new(),
@@ -111,8 +123,8 @@ impl<'a> DwarfUnitParser<'a> {
 
         let mut entries: Vec<Entry> = vec![];
         let mut die_ranges: Vec<DieRange> = vec![];
-        let mut variable_index: HashMap<String, Vec<(NamespaceHierarchy, usize)>> = HashMap::new();
-        let mut type_index: HashMap<String, UnitOffset> = HashMap::new();
+        let mut variable_index: HashMap<IStr, Vec<(NamespaceHierarchy, usize)>> = HashMap::new();
+        let mut type_index: HashMap<IStr, UnitOffset> = HashMap::new();
         let mut die_offsets_index: HashMap<UnitOffset, usize> = HashMap::new();
         let mut function_index = PathSearchIndex::new("::");
         let mut fn_declarations = HashMap::new();
@@ -192,8 +204,9 @@ impl<'a> DwarfUnitParser<'a> {
                     let (fn_ns, linkage_name) = match mb_linkage_name {
                         Some(linkage_name) => {
                             let linkage_name = linkage_name.to_string_lossy()?;
-                            let (ns, fn_name) = NamespaceHierarchy::from_mangled(&linkage_name);
-                            (ns, Some(fn_name))
+                            let (ns, fn_name) =
+                                NamespaceHierarchy::from_mangled(self.interner, &linkage_name);
+                            (ns, Some(self.interner.intern(&fn_name)))
                         }
                         None => (
                             NamespaceHierarchy::for_node(&Node::new_leaf(parent_idx), &entries),
@@ -207,6 +220,7 @@ impl<'a> DwarfUnitParser<'a> {
                         fb_addr: die.attr(DW_AT_frame_base)?,
                         decl_file_line,
                         linkage_name,
+                        return_type_ref: die.attr(DW_AT_type)?.and_then(DieRef::from_attr),
                     };
 
                     let specification = die.attr(DW_AT_specification)?.and_then(|attr| {
@@ -219,7 +233,7 @@ impl<'a> DwarfUnitParser<'a> {
                     if let Some(decl_ref) = specification {
                         let declaration_idx = weak_error!(fn_declarations
                             .get(&decl_ref)
-                            .ok_or(Error::InvalidSpecification(decl_ref)));
+                            .ok_or(Error::InvalidSpecification(decl_ref.0)));
                         debug_assert!(declaration_idx.is_some(), "reference to unseen declaration");
 
                         if let Some(&idx) = declaration_idx {
@@ -237,8 +251,8 @@ impl<'a> DwarfUnitParser<'a> {
                         // subprograms without a range are useless for this index
                         if !fn_die.base_attributes.ranges.is_empty() {
                             function_index.insert_w_head(
-                                fn_die.namespace.iter(),
-                                fn_name,
+                                fn_die.namespace.iter().cloned(),
+                                fn_name.clone(),
                                 current_idx,
                             );
                         }
@@ -253,6 +267,7 @@ impl<'a> DwarfUnitParser<'a> {
                 gimli::DW_TAG_inlined_subroutine => {
                     DieVariant::InlineSubroutine(InlineSubroutineDie {
                         base_attributes: base_attrs,
+                        origin: die.attr(DW_AT_abstract_origin)?.and_then(DieRef::from_attr),
                         call_file: die.attr(DW_AT_call_file)?.and_then(|v| match v.value() {
                             AttributeValue::FileIndex(idx) => Some(idx),
                             _ => None,
@@ -302,26 +317,41 @@ impl<'a> DwarfUnitParser<'a> {
                         .attr(DW_AT_linkage_name)?
                         .and_then(|attr| self.dwarf.attr_string(&unit, attr.value()).ok());
 
+                    let mb_linkage_name = mb_linkage_name
+                        .map(|linkage_name| {
+                            linkage_name
+                                .to_string_lossy()
+                                .map(|linkage_name| self.interner.intern(&linkage_name))
+                        })
+                        .transpose()?;
                     let variable_ns = match mb_linkage_name {
-                        Some(linkage_name) => {
-                            let linkage_name = linkage_name.to_string_lossy()?;
-                            let (ns, _) = NamespaceHierarchy::from_mangled(&linkage_name);
+                        Some(ref linkage_name) => {
+                            let (ns, _) =
+                                NamespaceHierarchy::from_mangled(self.interner, linkage_name);
                             ns
                         }
                         None => NamespaceHierarchy::for_node(&Node::new_leaf(parent_idx), &entries),
                     };
 
+                    let mb_file = die
+                        .attr(DW_AT_decl_file)?
+                        .and_then(|attr| attr.udata_value());
+                    let mb_line = die
+                        .attr(DW_AT_decl_line)?
+                        .and_then(|attr| attr.udata_value());
                     let die = VariableDie {
                         base_attributes: base_attrs,
+                        linkage_name: mb_linkage_name,
                         type_ref: die.attr(DW_AT_type)?.and_then(DieRef::from_attr),
                         location: die.attr(DW_AT_location)?,
                         lexical_block_idx,
                         fn_block_idx,
+                        decl_file_line: mb_file.and_then(|file_idx| Some((file_idx, mb_line?))),
                     };
 
                     if let Some(ref name) = die.base_attributes.name {
                         variable_index
-                            .entry(name.to_string())
+                            .entry(name.clone())
                             .or_default()
                             .push((variable_ns, current_idx));
                     }
@@ -338,7 +368,7 @@ impl<'a> DwarfUnitParser<'a> {
                     });
 
                     if let Some(ref name) = base_attrs.name {
-                        type_index.insert(name.to_string(), die.offset());
+                        type_index.insert(name.clone(), die.offset());
                     }
                     DieVariant::BaseType(BaseTypeDie {
                         base_attributes: base_attrs,
@@ -348,11 +378,18 @@ impl<'a> DwarfUnitParser<'a> {
                 }
                 gimli::DW_TAG_structure_type => {
                     if let Some(ref name) = base_attrs.name {
-                        type_index.insert(name.to_string(), die.offset());
+                        type_index.insert(name.clone(), die.offset());
                     }
+                    let mb_file = die
+                        .attr(DW_AT_decl_file)?
+                        .and_then(|attr| attr.udata_value());
+                    let mb_line = die
+                        .attr(DW_AT_decl_line)?
+                        .and_then(|attr| attr.udata_value());
                     DieVariant::StructType(StructTypeDie {
                         base_attributes: base_attrs,
                         byte_size: die.attr(DW_AT_byte_size)?.and_then(|val| val.udata_value()),
+                        decl_file_line: mb_file.and_then(|file_idx| Some((file_idx, mb_line?))),
                     })
                 }
                 gimli::DW_TAG_member => DieVariant::TypeMember(TypeMemberDie {
@@ -360,10 +397,17 @@ impl<'a> DwarfUnitParser<'a> {
                     byte_size: die.attr(DW_AT_byte_size)?.and_then(|val| val.udata_value()),
                     location: die.attr(DW_AT_data_member_location)?,
                     type_ref: die.attr(DW_AT_type)?.and_then(DieRef::from_attr),
+                    bit_size: die.attr(DW_AT_bit_size)?.and_then(|val| val.udata_value()),
+                    data_bit_offset: die
+                        .attr(DW_AT_data_bit_offset)?
+                        .and_then(|val| val.udata_value()),
+                    bit_offset: die
+                        .attr(DW_AT_bit_offset)?
+                        .and_then(|val| val.udata_value()),
                 }),
                 gimli::DW_TAG_union_type => {
                     if let Some(ref name) = base_attrs.name {
-                        type_index.insert(name.to_string(), die.offset());
+                        type_index.insert(name.clone(), die.offset());
                     }
                     DieVariant::UnionTypeDie(UnionTypeDie {
                         base_attributes: base_attrs,
@@ -375,7 +419,7 @@ impl<'a> DwarfUnitParser<'a> {
                 }),
                 gimli::DW_TAG_array_type => {
                     if let Some(ref name) = base_attrs.name {
-                        type_index.insert(name.to_string(), die.offset());
+                        type_index.insert(name.clone(), die.offset());
                     }
                     DieVariant::ArrayType(ArrayDie {
                         base_attributes: base_attrs,
@@ -408,12 +452,13 @@ impl<'a> DwarfUnitParser<'a> {
                 gimli::DW_TAG_variant => DieVariant::Variant(Variant {
                     base_attributes: base_attrs,
                     discr_value: die
-                        .attr(DW_AT_discr_value)?
-                        .and_then(|val| val.sdata_value()),
+                        .attr_value(DW_AT_discr_value)?
+                        .as_ref()
+                        .and_then(DiscrValue::from_attr),
                 }),
                 gimli::DW_TAG_pointer_type => {
                     if let Some(ref name) = base_attrs.name {
-                        type_index.insert(name.to_string(), die.offset());
+                        type_index.insert(name.clone(), die.offset());
                     }
                     DieVariant::PointerType(PointerType {
                         base_attributes: base_attrs,
@@ -539,6 +584,138 @@ where
     Ok(files)
 }
 
+/// LLVM extension of a line table file entry, contains a source code of the file.
+const DW_LNCT_LLVM_SOURCE: DwLnct = DwLnct(0x2001);
+
+/// Parse source code embedded into a DWARF 5 line table (`DW_LNCT_LLVM_source` content of
+/// file entries). Result key is a file index.
+fn parse_embedded_sources<R, Offset>(
+    dwarf: &gimli::Dwarf<R>,
+    unit: &gimli::Unit<R>,
+    header: &gimli::LineProgramHeader<R, Offset>,
+) -> gimli::Result<HashMap<usize, Arc<str>>>
+where
+    R: Reader<Offset = Offset>,
+    Offset: gimli::ReaderOffset,
+{
+    read_embedded_sources(header, dwarf.debug_line.reader(), |value| {
+        dwarf.attr_string(unit, value)
+    })
+}
+
+/// Read embedded sources from a line table header.
+///
+/// `gimli` skips unknown content types, so file entries are read again from the line table
+/// header, starting right after standard opcode lengths.
+///
+/// # Arguments
+///
+/// * `header`: line table header
+/// * `section`: `.debug_line` section data
+/// * `attr_string`: resolves a string value of a file entry
+fn read_embedded_sources<R, Offset>(
+    header: &gimli::LineProgramHeader<R, Offset>,
+    section: &R,
+    attr_string: impl Fn(AttributeValue<R>) -> gimli::Result<R>,
+) -> gimli::Result<HashMap<usize, Arc<str>>>
+where
+    R: Reader<Offset = Offset>,
+    Offset: gimli::ReaderOffset,
+{
+    let has_sources = header
+        .file_name_entry_format()
+        .iter()
+        .any(|format| format.content_type == DW_LNCT_LLVM_SOURCE);
+    if header.version() < 5 || !has_sources {
+        return Ok(HashMap::new());
+    }
+
+    let opcode_lengths = header.standard_opcode_lengths();
+    let mut input = section.clone();
+    input.skip(opcode_lengths.offset_from(section) + opcode_lengths.len())?;
+
+    let encoding = header.encoding();
+    let read_formats = |input: &mut R| -> gimli::Result<Vec<(DwLnct, DwForm)>> {
+        let count = input.read_u8()?;
+        (0..count)
+            .map(|_| {
+                let content_type = input.read_uleb128_u16()?;
+                let form = input.read_uleb128_u16()?;
+                Ok((DwLnct(content_type), DwForm(form)))
+            })
+            .collect()
+    };
+
+    let directory_formats = read_formats(&mut input)?;
+    let directory_count = input.read_uleb128()?;
+    for _ in 0..directory_count {
+        for &(_, form) in &directory_formats {
+            read_line_table_value(&mut input, form, encoding)?;
+        }
+    }
+
+    let file_formats = read_formats(&mut input)?;
+    let file_count = input.read_uleb128()?;
+    let mut sources = HashMap::new();
+    for index in 0..file_count as usize {
+        for &(content_type, form) in &file_formats {
+            let value = read_line_table_value(&mut input, form, encoding)?;
+            if content_type != DW_LNCT_LLVM_SOURCE {
+                continue;
+            }
+            let source = attr_string(value)?;
+            let source = source.to_string_lossy()?;
+            // an empty string means that there is no embedded source for a file
+            if !source.is_empty() {
+                sources.insert(index, Arc::from(source.as_ref()));
+            }
+        }
+    }
+    Ok(sources)
+}
+
+/// Read a value of a line table directory or file entry.
+fn read_line_table_value<R: Reader>(
+    input: &mut R,
+    form: DwForm,
+    encoding: gimli::Encoding,
+) -> gimli::Result<AttributeValue<R>> {
+    #[allow(non_upper_case_globals)]
+    let value = match form {
+        DW_FORM_string => AttributeValue::String(input.read_null_terminated_slice()?),
+        DW_FORM_line_strp => {
+            AttributeValue::DebugLineStrRef(DebugLineStrOffset(input.read_offset(encoding.format)?))
+        }
+        DW_FORM_strp => {
+            AttributeValue::DebugStrRef(DebugStrOffset(input.read_offset(encoding.format)?))
+        }
+        DW_FORM_strx => AttributeValue::DebugStrOffsetsIndex(DebugStrOffsetsIndex(
+            R::Offset::from_u64(input.read_uleb128()?)?,
+        )),
+        DW_FORM_strx1 => AttributeValue::DebugStrOffsetsIndex(DebugStrOffsetsIndex(
+            R::Offset::from_u8(input.read_u8()?),
+        )),
+        DW_FORM_strx2 => AttributeValue::DebugStrOffsetsIndex(DebugStrOffsetsIndex(
+            R::Offset::from_u16(input.read_u16()?),
+        )),
+        DW_FORM_strx4 => AttributeValue::DebugStrOffsetsIndex(DebugStrOffsetsIndex(
+            R::Offset::from_u32(input.read_u32()?),
+        )),
+        DW_FORM_udata => AttributeValue::Udata(input.read_uleb128()?),
+        DW_FORM_data1 => AttributeValue::Data1(input.read_u8()?),
+        DW_FORM_data2 => AttributeValue::Data2(input.read_u16()?),
+        DW_FORM_data4 => AttributeValue::Data4(input.read_u32()?),
+        DW_FORM_data8 => AttributeValue::Data8(input.read_u64()?),
+        DW_FORM_data16 => AttributeValue::Block(input.split(R::Offset::from_u8(16))?),
+        DW_FORM_block => {
+            let len = input.read_uleb128()?;
+            AttributeValue::Block(input.split(R::Offset::from_u64(len)?)?)
+        }
+        _ => return Err(gimli::Error::UnknownForm),
+    };
+    Ok(value)
+}
+
 #[inline(always)]
 fn render_file_path<R: Reader>(
     dw_unit: &gimli::Unit<R>,
@@ -583,3 +760,43 @@ fn render_file_path<R: Reader>(
 
     Ok(path)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gimli::{DebugLine, DebugLineOffset, EndianSlice, LittleEndian};
+
+    #[test]
+    fn test_read_embedded_sources() {
+        let mut header = vec![
+            1, 1, 1, 0xfb, 14, 13, // min_inst_length .. opcode_base
+            0, 1, 1, 1, 1, 0, 0, 0, 1, 0, 0, 1, // standard opcode lengths
+            1, 1, 0x08, // directory format: path (string)
+            1, b'/', b's', b'r', b'c', 0, // directories
+            3, 1, 0x08, 2, 0x0f, 0x81, 0x40, 0x08, // file format: path, dir index, source
+            2,    // file count
+        ];
+        header.extend_from_slice(b"main.rs\0\0fn main() {}\n\0");
+        header.extend_from_slice(b"lib.rs\0\0\0");
+
+        let mut unit = vec![5, 0, 8, 0];
+        unit.extend_from_slice(&(header.len() as u32).to_le_bytes());
+        unit.extend_from_slice(&header);
+        let mut section = (unit.len() as u32).to_le_bytes().to_vec();
+        section.extend_from_slice(&unit);
+
+        let debug_line = DebugLine::new(&section, LittleEndian);
+        let program = debug_line
+            .program(DebugLineOffset(0), 8, None, None)
+            .unwrap();
+        let reader = EndianSlice::new(&section, LittleEndian);
+        let sources = read_embedded_sources(program.header(), &reader, |value| match value {
+            AttributeValue::String(s) => Ok(s),
+            _ => Err(gimli::Error::UnknownForm),
+        })
+        .unwrap();
+
+        assert_eq!(sources.len(), 1);
+        assert_eq!(sources[&0].as_ref(), "fn main() {}\n");
+    }
+}
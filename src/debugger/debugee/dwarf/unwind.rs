@@ -1,17 +1,20 @@
 use crate::debugger::address::RelocatedAddress;
-use crate::debugger::debugee::dwarf::eval::{AddressKind, ExpressionEvaluator};
-use crate::debugger::debugee::dwarf::EndianArcSlice;
+use crate::debugger::debugee::dwarf::unit::FunctionDie;
+use crate::debugger::debugee::dwarf::{ContextualDieRef, EndianArcSlice};
 use crate::debugger::debugee::{Debugee, Location};
 use crate::debugger::error::Error;
 use crate::debugger::error::Error::{
-    TypeBinaryRepr, UnitNotFound, UnwindNoContext, UnwindTooDeepFrame,
+    EvalUnsupportedRequire, NoDebugInformation, TypeBinaryRepr, UnwindNoContext, UnwindTooDeepFrame,
 };
-use crate::debugger::register::{DwarfRegisterMap, RegisterMap};
-use crate::debugger::utils::TryGetOrInsert;
+use crate::debugger::register::DwarfRegisterMap;
+use crate::debugger::variable::VariableIR;
 use crate::debugger::ExplorationContext;
-use crate::{debugger, resolve_unit_call, weak_error};
-use gimli::{EhFrame, FrameDescriptionEntry, RegisterRule, UnwindSection};
-use nix::unistd::Pid;
+use crate::debugger::Pid;
+use crate::{debugger, weak_error};
+use gimli::{
+    Encoding, EvaluationResult, Expression, FrameDescriptionEntry, Location as DwarfLocation,
+    RegisterRule, Value,
+};
 use std::mem;
 
 /// Unique frame identifier. It is just an address of the first instruction in function.
@@ -21,8 +24,16 @@ pub type FrameID = RelocatedAddress;
 #[derive(Debug, Default, Clone)]
 pub struct FrameSpan {
     pub func_name: Option<String>,
+    /// Mangled function name (symbol name), if known.
+    pub linkage_name: Option<String>,
     pub fn_start_ip: Option<RelocatedAddress>,
     pub ip: RelocatedAddress,
+    /// Function arguments, `None` if arguments aren't resolved (unwinder never resolves them,
+    /// see [`debugger::Debugger::resolve_frame_arguments`]).
+    pub args: Option<Vec<VariableIR>>,
+    /// True if frame is a signal trampoline, a frame above it is a signal handler
+    /// and a frame below it is interrupted by a signal.
+    pub signal_frame: bool,
 }
 
 impl FrameSpan {
@@ -30,6 +41,150 @@ impl FrameSpan {
     pub fn id(&self) -> Option<FrameID> {
         self.fn_start_ip
     }
+
+    /// Return function name rendered according to demangle style.
+    ///
+    /// # Arguments
+    ///
+    /// * `style`: demangle style
+    pub fn display_name(&self, style: &DemangleStyle) -> Option<String> {
+        let name = match self.linkage_name {
+            Some(ref linkage_name) if !style.strip_hash => style.demangle(linkage_name),
+            _ => self.func_name.clone()?,
+        };
+        Some(style.shorten(&name))
+    }
+}
+
+/// Rules for demangling and shortening of function names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DemangleStyle {
+    /// Strip crate hash suffixes (like `::h6a9e3c1b2d4f5e6a`).
+    pub strip_hash: bool,
+    /// Replace generic parameters with `<…>`.
+    pub collapse_generics: bool,
+    /// Keep only last N parts of a qualified path.
+    pub max_path_depth: Option<usize>,
+}
+
+impl Default for DemangleStyle {
+    fn default() -> Self {
+        Self {
+            strip_hash: true,
+            collapse_generics: false,
+            max_path_depth: None,
+        }
+    }
+}
+
+impl DemangleStyle {
+    /// Default path depth for a short style.
+    pub const SHORT_PATH_DEPTH: usize = 2;
+
+    /// Demangled names as is, including hash suffixes.
+    pub fn full() -> Self {
+        Self {
+            strip_hash: false,
+            ..Self::default()
+        }
+    }
+
+    /// Names without hash suffixes, with collapsed generic parameters and
+    /// limited qualified-path depth.
+    ///
+    /// # Arguments
+    ///
+    /// * `depth`: maximum number of qualified-path parts
+    pub fn short(depth: usize) -> Self {
+        Self {
+            strip_hash: true,
+            collapse_generics: true,
+            max_path_depth: Some(depth),
+        }
+    }
+
+    /// Demangle a symbol name.
+    ///
+    /// # Arguments
+    ///
+    /// * `symbol`: mangled symbol name
+    pub fn demangle(&self, symbol: &str) -> String {
+        let demangled = rustc_demangle::demangle(symbol);
+        if self.strip_hash {
+            format!("{demangled:#}")
+        } else {
+            format!("{demangled}")
+        }
+    }
+
+    /// Apply generic collapsing and path depth limit to a demangled name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name`: demangled name
+    pub fn shorten(&self, name: &str) -> String {
+        let name = if self.collapse_generics {
+            collapse_generics(name)
+        } else {
+            name.to_string()
+        };
+
+        match self.max_path_depth {
+            Some(depth) => {
+                let parts = split_path(&name);
+                parts[parts.len().saturating_sub(depth.max(1))..].join("::")
+            }
+            None => name,
+        }
+    }
+}
+
+/// Replace generic parameter lists (`Vec<u8>`) with `<…>` (`Vec<…>`),
+/// angle brackets of qualified paths (`<T as Trait>::f`) are kept.
+fn collapse_generics(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut depth = 0;
+    let mut prev = None;
+    for c in name.chars() {
+        match c {
+            '<' if depth > 0 => depth += 1,
+            '<' if prev.is_some_and(|p: char| p.is_alphanumeric() || p == '_') => {
+                depth = 1;
+                result.push_str("<…>");
+            }
+            // skip `->` inside generic parameters
+            '>' if depth > 0 && prev != Some('-') => depth -= 1,
+            _ if depth > 0 => {}
+            _ => result.push(c),
+        }
+        prev = Some(c);
+    }
+    result
+}
+
+/// Split qualified path by `::` delimiters that are not inside angle brackets.
+fn split_path(name: &str) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut depth = 0_usize;
+    let mut start = 0;
+    let bytes = name.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'<' => depth += 1,
+            b'>' if i > 0 && bytes[i - 1] != b'-' => depth = depth.saturating_sub(1),
+            b':' if depth == 0 && bytes.get(i + 1) == Some(&b':') => {
+                parts.push(&name[start..i]);
+                i += 2;
+                start = i;
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    parts.push(&name[start..]);
+    parts
 }
 
 pub type Backtrace = Vec<FrameSpan>;
@@ -92,6 +247,54 @@ pub fn return_addr(debugee: &Debugee, pid: Pid) -> Result<Option<RelocatedAddres
     libunwind::return_addr(pid)
 }
 
+/// Evaluate a call frame information expression. Unlike location expressions, CFI expressions
+/// don't depend on a compilation unit, so frames of code without debug information
+/// (like a signal trampoline) may be unwound too.
+///
+/// # Arguments
+///
+/// * `expr`: DWARF expression
+/// * `encoding`: CIE encoding
+/// * `registers`: register values at the frame
+/// * `pid`: thread id
+/// * `initial_value`: value pushed on the stack before evaluation (CFA for register rules)
+pub(super) fn evaluate_cfi_expression(
+    expr: Expression<EndianArcSlice>,
+    encoding: Encoding,
+    registers: &DwarfRegisterMap,
+    pid: Pid,
+    initial_value: Option<u64>,
+) -> Result<u64, Error> {
+    let mut eval = expr.evaluation(encoding);
+    if let Some(value) = initial_value {
+        eval.set_initial_value(value);
+    }
+
+    let mut result = eval.evaluate()?;
+    while result != EvaluationResult::Complete {
+        match result {
+            EvaluationResult::RequiresRegister { register, .. } => {
+                let value = registers.value(register)?;
+                result = eval.resume_with_register(Value::Generic(value))?;
+            }
+            EvaluationResult::RequiresMemory { address, size, .. } => {
+                let memory = debugger::read_memory_by_pid(pid, address as usize, size as usize)?;
+                let mut bytes = [0; mem::size_of::<u64>()];
+                let len = memory.len().min(bytes.len());
+                bytes[..len].copy_from_slice(&memory[..len]);
+                result = eval.resume_with_memory(Value::Generic(u64::from_ne_bytes(bytes)))?;
+            }
+            _ => return Err(EvalUnsupportedRequire("call frame information")),
+        }
+    }
+
+    match eval.result().first().map(|piece| &piece.location) {
+        Some(DwarfLocation::Address { address }) => Ok(*address),
+        Some(DwarfLocation::Value { value }) => Ok(value.to_u64(u64::MAX)?),
+        _ => Err(EvalUnsupportedRequire("call frame information result")),
+    }
+}
+
 /// UnwindContext contains information for unwinding single frame.  
 pub struct UnwindContext<'a> {
     registers: DwarfRegisterMap,
@@ -110,11 +313,7 @@ impl<'a> UnwindContext<'a> {
         let dwarf = &debugee.debug_info(expl_ctx.location().pc)?;
         let mut next_registers = registers.clone();
         let registers_snap = registers;
-        let fde = match dwarf.eh_frame.fde_for_address(
-            &dwarf.bases,
-            expl_ctx.location().global_pc.into(),
-            EhFrame::cie_from_offset,
-        ) {
+        let fde = match dwarf.fde_for_address(expl_ctx.location().global_pc) {
             Ok(fde) => fde,
             Err(gimli::Error::NoUnwindInfoForAddress) => {
                 return Ok(None);
@@ -129,17 +328,8 @@ impl<'a> UnwindContext<'a> {
             &mut ctx,
             expl_ctx.location().global_pc.into(),
         )?;
-        let cfa = dwarf.evaluate_cfa(debugee, &registers_snap, row, expl_ctx)?;
-
-        let mut lazy_evaluator = None;
-        let evaluator_init_fn = || -> Result<ExpressionEvaluator, Error> {
-            let unit = dwarf
-                .find_unit_by_pc(expl_ctx.location().global_pc)?
-                .ok_or(UnitNotFound(expl_ctx.location().global_pc))?;
-
-            let evaluator = resolve_unit_call!(&dwarf.inner, unit, evaluator, debugee);
-            Ok(evaluator)
-        };
+        let encoding = fde.cie().encoding();
+        let cfa = dwarf.evaluate_cfa(&registers_snap, row, encoding, expl_ctx)?;
 
         row.registers()
             .filter_map(|(register, rule)| {
@@ -147,7 +337,7 @@ impl<'a> UnwindContext<'a> {
                     RegisterRule::Undefined => return None,
                     RegisterRule::SameValue => {
                         let register_map =
-                            weak_error!(RegisterMap::current(expl_ctx.pid_on_focus()))?;
+                            weak_error!(debugee.tracee_ctl().registers(expl_ctx.pid_on_focus()))?;
                         weak_error!(DwarfRegisterMap::from(register_map).value(*register))?
                     }
                     RegisterRule::Offset(offset) => {
@@ -165,27 +355,29 @@ impl<'a> UnwindContext<'a> {
                     RegisterRule::ValOffset(offset) => cfa.offset(*offset as isize).into(),
                     RegisterRule::Register(reg) => weak_error!(registers_snap.value(*reg))?,
                     RegisterRule::Expression(expr) => {
-                        let evaluator =
-                            weak_error!(lazy_evaluator.try_get_or_insert_with(evaluator_init_fn))?;
-                        let expr_result = weak_error!(evaluator.evaluate(expl_ctx, expr.clone()))?;
-                        let addr = weak_error!(
-                            expr_result.into_scalar::<usize>(AddressKind::MemoryAddress)
-                        )?;
+                        let addr = weak_error!(evaluate_cfi_expression(
+                            expr.clone(),
+                            encoding,
+                            &registers_snap,
+                            expl_ctx.pid_on_focus(),
+                            Some(cfa.into()),
+                        ))?;
                         let bytes = weak_error!(debugger::read_memory_by_pid(
                             expl_ctx.pid_on_focus(),
-                            addr,
+                            addr as usize,
                             mem::size_of::<u64>()
                         ))?;
                         u64::from_ne_bytes(weak_error!(bytes.try_into().map_err(
                             |data: Vec<u8>| TypeBinaryRepr("u64", data.into_boxed_slice())
                         ))?)
                     }
-                    RegisterRule::ValExpression(expr) => {
-                        let evaluator =
-                            weak_error!(lazy_evaluator.try_get_or_insert_with(evaluator_init_fn))?;
-                        let expr_result = weak_error!(evaluator.evaluate(expl_ctx, expr.clone()))?;
-                        weak_error!(expr_result.into_scalar::<u64>(AddressKind::MemoryAddress))?
-                    }
+                    RegisterRule::ValExpression(expr) => weak_error!(evaluate_cfi_expression(
+                        expr.clone(),
+                        encoding,
+                        &registers_snap,
+                        expl_ctx.pid_on_focus(),
+                        Some(cfa.into()),
+                    ))?,
                     RegisterRule::Architectural => return None,
                     RegisterRule::Constant(val) => *val,
                     _ => unreachable!(),
@@ -208,11 +400,20 @@ impl<'a> UnwindContext<'a> {
         previous_ctx: UnwindContext<'a>,
         ctx: &ExplorationContext,
     ) -> Result<Option<Self>, Error> {
+        // signal trampoline restores stack pointer of an interrupted frame from a signal context
+        let is_signal_trampoline = previous_ctx.is_signal_trampoline();
         let mut next_frame_registers: DwarfRegisterMap = previous_ctx.registers;
-        next_frame_registers.update(gimli::Register(7), previous_ctx.cfa.into());
+        if !is_signal_trampoline {
+            next_frame_registers.update(gimli::Register(7), previous_ctx.cfa.into());
+        }
         UnwindContext::new(previous_ctx.debugee, next_frame_registers, ctx)
     }
 
+    /// Return true if the frame is a signal trampoline (`__restore_rt` for example).
+    fn is_signal_trampoline(&self) -> bool {
+        self.fde.cie().is_signal_trampoline()
+    }
+
     fn return_address(&self) -> Option<RelocatedAddress> {
         let register = self.fde.cie().return_address_register();
         self.registers
@@ -256,35 +457,28 @@ impl<'a> DwarfUnwinder<'a> {
             .location(self.debugee)?;
 
         let mut ctx = ExplorationContext::new(frame_0_location, 0);
-        let mb_unwind_ctx = UnwindContext::new(
-            self.debugee,
-            DwarfRegisterMap::from(RegisterMap::current(ctx.pid_on_focus())?),
-            &ctx,
-        )?;
-        let Some(mut unwind_ctx) = mb_unwind_ctx else {
-            return Ok(vec![]);
+        let registers =
+            DwarfRegisterMap::from(self.debugee.tracee_ctl().registers(ctx.pid_on_focus())?);
+        let mut bt = vec![];
+        let mut unwind_ctx = match UnwindContext::new(self.debugee, registers.clone(), &ctx)? {
+            Some(unwind_ctx) => {
+                bt.push(self.frame_span(ctx.location(), unwind_ctx.is_signal_trampoline())?);
+                unwind_ctx
+            }
+            None => {
+                // there is no call frame information for a leaf function (like `clone3`
+                // in glibc right after a syscall), expect a return address at the top of the stack
+                let Some((caller_ctx, unwind_ctx)) = self.leaf_caller_context(&ctx, registers)?
+                else {
+                    return Ok(vec![]);
+                };
+                bt.push(self.frame_span(ctx.location(), false)?);
+                ctx = caller_ctx;
+                bt.push(self.frame_span(ctx.location(), unwind_ctx.is_signal_trampoline())?);
+                unwind_ctx
+            }
         };
 
-        let function = self
-            .debugee
-            .debug_info(ctx.location().pc)?
-            .find_function_by_pc(ctx.location().global_pc)?;
-        let fn_start_at = function
-            .and_then(|func| {
-                func.prolog_start_place().ok().map(|prolog| {
-                    prolog
-                        .address
-                        .relocate_to_segment_by_pc(self.debugee, ctx.location().pc)
-                })
-            })
-            .transpose()?;
-
-        let mut bt = vec![FrameSpan {
-            func_name: function.and_then(|func| func.full_name()),
-            fn_start_ip: fn_start_at,
-            ip: ctx.location().pc,
-        }];
-
         // start unwind
         while let Some(return_addr) = unwind_ctx.return_address() {
             let prev_loc = bt.last().expect("backtrace len > 0");
@@ -304,31 +498,100 @@ impl<'a> DwarfUnwinder<'a> {
                 Some(ctx) => ctx,
             };
 
-            let function = self
-                .debugee
-                .debug_info(next_location.pc)?
-                .find_function_by_pc(next_location.global_pc)?;
-            let fn_start_at = function
-                .and_then(|func| {
-                    func.prolog_start_place().ok().map(|prolog| {
-                        prolog
-                            .address
-                            .relocate_to_segment_by_pc(self.debugee, next_location.pc)
-                    })
-                })
-                .transpose()?;
-
-            let span = FrameSpan {
-                func_name: function.and_then(|func| func.full_name()),
-                fn_start_ip: fn_start_at,
-                ip: next_location.pc,
-            };
-            bt.push(span);
+            bt.push(self.frame_span(next_location, unwind_ctx.is_signal_trampoline())?);
         }
 
         Ok(bt)
     }
 
+    /// Return a frame span for a frame location.
+    ///
+    /// # Arguments
+    ///
+    /// * `location`: frame location
+    /// * `signal_frame`: true if frame is a signal trampoline
+    fn frame_span(&self, location: Location, signal_frame: bool) -> Result<FrameSpan, Error> {
+        let function = self.find_function(location)?;
+        let fn_start_at = function
+            .and_then(|func| {
+                func.prolog_start_place().ok().map(|prolog| {
+                    prolog
+                        .address
+                        .relocate_to_segment_by_pc(self.debugee, location.pc)
+                })
+            })
+            .transpose()?;
+
+        Ok(FrameSpan {
+            func_name: function.and_then(|func| func.full_name()),
+            linkage_name: function
+                .and_then(|func| func.die.linkage_name.as_deref().map(String::from)),
+            fn_start_ip: fn_start_at,
+            ip: location.pc,
+            args: None,
+            signal_frame,
+        })
+    }
+
+    /// Return a caller context of a leaf function without call frame information,
+    /// a return address is read from the top of the stack. `None` if there is no
+    /// call frame information for a caller too.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`: exploration context with a leaf function location
+    /// * `registers`: registers at a leaf function
+    fn leaf_caller_context(
+        &self,
+        ctx: &ExplorationContext,
+        mut registers: DwarfRegisterMap,
+    ) -> Result<Option<(ExplorationContext, UnwindContext<'a>)>, Error> {
+        let sp = registers.value(gimli::Register(7))?;
+        let bytes =
+            debugger::read_memory_by_pid(ctx.pid_on_focus(), sp as usize, mem::size_of::<u64>())?;
+        let return_addr = RelocatedAddress::from(u64::from_ne_bytes(
+            bytes
+                .try_into()
+                .map_err(|data: Vec<u8>| TypeBinaryRepr("u64", data.into_boxed_slice()))?,
+        ));
+        let Ok(global_pc) = return_addr.into_global(self.debugee) else {
+            return Ok(None);
+        };
+
+        registers.update(gimli::Register(7), sp + mem::size_of::<u64>() as u64);
+        registers.update(gimli::Register(16), return_addr.into());
+        let caller_ctx = ExplorationContext::new(
+            Location {
+                pc: return_addr,
+                global_pc,
+                pid: ctx.pid_on_focus(),
+            },
+            ctx.frame_num() + 1,
+        );
+        Ok(UnwindContext::new(self.debugee, registers, &caller_ctx)?
+            .map(|unwind_ctx| (caller_ctx, unwind_ctx)))
+    }
+
+    /// Return function at location, `None` if function not found or there is no debug
+    /// information for the object file (like libc frames or signal trampolines).
+    ///
+    /// # Arguments
+    ///
+    /// * `location`: frame location
+    fn find_function(
+        &self,
+        location: Location,
+    ) -> Result<Option<ContextualDieRef<'a, FunctionDie>>, Error> {
+        match self
+            .debugee
+            .debug_info(location.pc)?
+            .find_function_by_pc(location.global_pc)
+        {
+            Err(NoDebugInformation(_)) => Ok(None),
+            result => result,
+        }
+    }
+
     pub fn restore_registers_at_frame(
         &self,
         pid: Pid,
@@ -348,7 +611,7 @@ impl<'a> DwarfUnwinder<'a> {
 
         let mut unwind_ctx = UnwindContext::new(
             self.debugee,
-            DwarfRegisterMap::from(RegisterMap::current(ctx.pid_on_focus())?),
+            DwarfRegisterMap::from(self.debugee.tracee_ctl().registers(ctx.pid_on_focus())?),
             &ctx,
         )?
         .ok_or(UnwindNoContext)?;
@@ -368,9 +631,9 @@ impl<'a> DwarfUnwinder<'a> {
             unwind_ctx = UnwindContext::next(unwind_ctx, &ctx)?.ok_or(UnwindNoContext)?;
         }
 
-        if let Ok(ip) = unwind_ctx.registers().value(gimli::Register(16)) {
-            registers.update(gimli::Register(16), ip);
-        }
+        // unwind context registers are restored for a caller frame,
+        // so a return address is not an instruction pointer of the target frame
+        registers.update(gimli::Register(16), u64::from(ctx.location().pc));
         if let Ok(sp) = unwind_ctx.registers().value(gimli::Register(7)) {
             registers.update(gimli::Register(7), sp);
         }
@@ -393,7 +656,7 @@ impl<'a> DwarfUnwinder<'a> {
 
         let mb_unwind_ctx = UnwindContext::new(
             self.debugee,
-            DwarfRegisterMap::from(RegisterMap::current(ctx.pid_on_focus())?),
+            DwarfRegisterMap::from(self.debugee.tracee_ctl().registers(ctx.pid_on_focus())?),
             &ctx,
         )?;
 
@@ -403,6 +666,25 @@ impl<'a> DwarfUnwinder<'a> {
         Ok(None)
     }
 
+    /// Returns register values at the caller frame: callee-saved registers are restored by
+    /// unwind rules, stack pointer is set to CFA and return address register (DWARF register 16)
+    /// contains a return address.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`: exploration context with a callee frame location.
+    pub fn caller_registers(&self, ctx: &ExplorationContext) -> Result<DwarfRegisterMap, Error> {
+        let unwind_ctx = self.context_for(ctx)?.ok_or(UnwindNoContext)?;
+        let return_addr = unwind_ctx.return_address().ok_or(UnwindNoContext)?;
+
+        let mut registers = unwind_ctx.registers();
+        if !unwind_ctx.is_signal_trampoline() {
+            registers.update(gimli::Register(7), unwind_ctx.cfa.into());
+        }
+        registers.update(gimli::Register(16), return_addr.into());
+        Ok(registers)
+    }
+
     /// Returns unwind context for location.
     ///
     /// # Arguments
@@ -411,7 +693,7 @@ impl<'a> DwarfUnwinder<'a> {
     pub fn context_for(&self, ctx: &ExplorationContext) -> Result<Option<UnwindContext>, Error> {
         UnwindContext::new(
             self.debugee,
-            DwarfRegisterMap::from(RegisterMap::current(ctx.pid_on_focus())?),
+            DwarfRegisterMap::from(self.debugee.tracee_ctl().registers(ctx.pid_on_focus())?),
             ctx,
         )
     }
@@ -419,12 +701,12 @@ impl<'a> DwarfUnwinder<'a> {
 
 #[cfg(feature = "libunwind")]
 mod libunwind {
-    use super::FrameSpan;
+    use super::{DemangleStyle, FrameSpan};
     use crate::debugger::address::RelocatedAddress;
     use crate::debugger::error::Error;
     use crate::debugger::register::DwarfRegisterMap;
     use crate::debugger::unwind::Backtrace;
-    use nix::unistd::Pid;
+    use crate::debugger::Pid;
     use unwind::{Accessors, AddressSpace, Byteorder, Cursor, PTraceState, RegNum};
 
     /// Unwind thread stack and returns backtrace.
@@ -440,21 +722,28 @@ mod libunwind {
 
         loop {
             let ip = cursor.register(RegNum::IP)?;
+            let signal_frame = cursor.is_signal_frame().unwrap_or_default();
             match (cursor.procedure_info(), cursor.procedure_name()) {
                 (Ok(ref info), Ok(ref name)) if ip == info.start_ip() + name.offset() => {
-                    let fn_name = format!("{:#}", rustc_demangle::demangle(name.name()));
+                    let fn_name = DemangleStyle::default().demangle(name.name());
 
                     backtrace.push(FrameSpan {
                         func_name: Some(fn_name),
+                        linkage_name: Some(name.name().to_string()),
                         fn_start_ip: Some(info.start_ip().into()),
                         ip: ip.into(),
+                        args: None,
+                        signal_frame,
                     });
                 }
                 _ => {
                     backtrace.push(FrameSpan {
                         func_name: None,
+                        linkage_name: None,
                         fn_start_ip: None,
                         ip: ip.into(),
+                        args: None,
+                        signal_frame,
                     });
                 }
             }
@@ -513,3 +802,68 @@ mod libunwind {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::DemangleStyle;
+
+    #[test]
+    fn test_demangle_style() {
+        struct TestCase {
+            style: DemangleStyle,
+            input: &'static str,
+            expected: &'static str,
+        }
+
+        let cases = [
+            TestCase {
+                style: DemangleStyle::default(),
+                input: "_ZN11hello_world4main17h6a9e3c1b2d4f5e6aE",
+                expected: "hello_world::main",
+            },
+            TestCase {
+                style: DemangleStyle::full(),
+                input: "_ZN11hello_world4main17h6a9e3c1b2d4f5e6aE",
+                expected: "hello_world::main::h6a9e3c1b2d4f5e6a",
+            },
+            TestCase {
+                style: DemangleStyle::short(2),
+                input: "alloc::vec::Vec<T,A>::push",
+                expected: "Vec<…>::push",
+            },
+            TestCase {
+                style: DemangleStyle::short(2),
+                input: "std::collections::hash::map::HashMap<K,V,S>::insert",
+                expected: "HashMap<…>::insert",
+            },
+            TestCase {
+                style: DemangleStyle::short(1),
+                input: "core::ops::function::FnOnce::call_once<fn() -> u8, ()>",
+                expected: "call_once<…>",
+            },
+            TestCase {
+                style: DemangleStyle::short(2),
+                input: "<alloc::vec::Vec<u8> as core::clone::Clone>::clone",
+                expected: "<alloc::vec::Vec<…> as core::clone::Clone>::clone",
+            },
+            TestCase {
+                style: DemangleStyle {
+                    max_path_depth: Some(3),
+                    ..DemangleStyle::default()
+                },
+                input: "tokio::runtime::task::raw::poll<F, S>",
+                expected: "task::raw::poll<F, S>",
+            },
+            TestCase {
+                style: DemangleStyle::short(5),
+                input: "main",
+                expected: "main",
+            },
+        ];
+
+        for tc in cases {
+            let name = tc.style.demangle(tc.input);
+            assert_eq!(tc.style.shorten(&name), tc.expected);
+        }
+    }
+}
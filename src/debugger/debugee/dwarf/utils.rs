@@ -1,3 +1,4 @@
+use crate::debugger::debugee::dwarf::interner::IStr;
 use std::collections::HashMap;
 use std::iter;
 
@@ -7,11 +8,13 @@ use std::iter;
 /// a pair (an index of tail, id of a head - a nonce) into `heads` hashmap (now heads referenced to one or more tails),
 /// and at last, add (head nonce, tail index) and a value into `data`
 /// hashmap (now combination of head id and tail index referenced to a data).
+/// Path parts are expected to be interned by a caller cause the same namespaces and directories
+/// repeat in many paths.
 #[derive(Clone, Debug)]
 struct PathIndexInner<T> {
     next_nonce: u64,
-    heads: HashMap<String, (Vec<usize>, u64)>,
-    tails: Vec<Vec<String>>,
+    heads: HashMap<IStr, (Vec<usize>, u64)>,
+    tails: Vec<Vec<IStr>>,
     data: HashMap<(u64, usize), T>,
 }
 
@@ -58,13 +61,13 @@ impl<T> PathSearchIndex<T> {
     /// * `path`: an iterator over path parts
     /// * `value`: a value associated with path
     #[allow(unused)]
-    pub fn insert(&mut self, path: impl IntoIterator<Item = impl ToString>, value: T) {
-        let path: Vec<_> = path.into_iter().map(|p| p.to_string()).collect();
-        let Some(head) = path.last() else {
+    pub fn insert(&mut self, path: impl IntoIterator<Item = impl Into<IStr>>, value: T) {
+        let mut path: Vec<IStr> = path.into_iter().map(Into::into).collect();
+        let Some(head) = path.pop() else {
             return;
         };
 
-        self.insert_w_head(path[..path.len() - 1].iter(), head.to_string(), value)
+        self.insert_w_head(path, head, value)
     }
 
     /// Insert a new index value.
@@ -76,14 +79,14 @@ impl<T> PathSearchIndex<T> {
     /// * `value`: a value associated with path
     pub fn insert_w_head(
         &mut self,
-        path: impl IntoIterator<Item = impl ToString>,
-        head: impl ToString,
+        path: impl IntoIterator<Item = impl Into<IStr>>,
+        head: impl Into<IStr>,
         value: T,
     ) {
         let index = &mut self.index;
 
-        let path: Vec<_> = path.into_iter().map(|p| p.to_string()).collect();
-        let head = head.to_string();
+        let path: Vec<IStr> = path.into_iter().map(Into::into).collect();
+        let head = head.into();
 
         let tail = path;
         index.tails.push(tail);
@@ -123,13 +126,17 @@ impl<T> PathSearchIndex<T> {
         };
         let expected_tail = split;
 
-        let Some((tail_indexes, head_nonce)) = self.index.heads.get(&expected_head) else {
+        let Some((tail_indexes, head_nonce)) = self.index.heads.get(expected_head.as_str()) else {
             return vec![];
         };
 
         let tail_indexes = tail_indexes.iter().filter(|&&idx| {
             let tail = &self.index.tails[idx];
-            tail.ends_with(&expected_tail)
+            tail.len() >= expected_tail.len()
+                && tail[tail.len() - expected_tail.len()..]
+                    .iter()
+                    .zip(&expected_tail)
+                    .all(|(part, expected)| part.as_ref() == expected)
         });
 
         tail_indexes
@@ -160,8 +167,8 @@ mod test {
         index.insert_w_head(["ns3", "ns2"], "fn1", 11);
         index.insert_w_head(["ns1"], "fn2", 2);
         index.insert_w_head(["ns1", "ns2"], "fn3", 3);
-        index.insert_w_head(&Vec::<&str>::new(), "fn4", 4);
-        index.insert_w_head(&Vec::<&str>::new(), "fn5", 5);
+        index.insert_w_head(Vec::<&str>::new(), "fn4", 4);
+        index.insert_w_head(Vec::<&str>::new(), "fn5", 5);
         index.insert_w_head(["ns3", "ns2"], "fn3", 6);
         index.insert_w_head(["ns3", "ns2"], "fn6", 7);
         index.insert_w_head(["ns3", "ns2"], "fn7", 8);
@@ -6,6 +6,7 @@ mod rendezvous;
 pub mod tracee;
 pub mod tracer;
 
+pub use disasm::{BranchTarget, DecodedInstruction};
 pub use registry::RegionInfo;
 pub use rendezvous::RendezvousError;
 
@@ -15,7 +16,7 @@ use crate::debugger::debugee::disasm::Disassembler;
 use crate::debugger::debugee::dwarf::unit::PlaceDescriptorOwned;
 use crate::debugger::debugee::dwarf::unwind;
 use crate::debugger::debugee::dwarf::unwind::Backtrace;
-use crate::debugger::debugee::dwarf::DebugInformation;
+use crate::debugger::debugee::dwarf::{DebugInformation, ProgressCallback};
 use crate::debugger::debugee::registry::DwarfRegistry;
 use crate::debugger::debugee::rendezvous::Rendezvous;
 use crate::debugger::debugee::tracee::{Tracee, TraceeCtl};
@@ -23,13 +24,14 @@ use crate::debugger::debugee::tracer::{StopReason, TraceContext, Tracer};
 use crate::debugger::error::Error;
 use crate::debugger::error::Error::{FunctionNotFound, MappingOffsetNotFound, TraceeNotFound};
 use crate::debugger::process::{Child, Installed};
-use crate::debugger::register::DwarfRegisterMap;
+use crate::debugger::register::{DwarfRegisterMap, Register};
 use crate::debugger::unwind::FrameSpan;
+use crate::debugger::watchpoint::AccessKind;
 use crate::debugger::Error::FunctionRangeNotFound;
-use crate::debugger::{ExplorationContext, PlaceDescriptor};
+use crate::debugger::Pid;
+use crate::debugger::{read_memory_by_pid, ExplorationContext, PlaceDescriptor};
 use crate::{muted_error, print_warns, weak_error};
 use log::{info, warn};
-use nix::unistd::Pid;
 use nix::NixPath;
 use object::{Object, ObjectSection};
 use rayon::prelude::*;
@@ -51,6 +53,36 @@ pub struct FrameInfo {
     pub return_addr: Option<RelocatedAddress>,
 }
 
+/// Stack slot annotation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StackSlotAnnotation {
+    /// Callee-saved register, stored by a function prolog.
+    SavedRegister(String),
+    /// Return address of a frame.
+    ReturnAddress,
+    /// Location of a variable.
+    Variable(String),
+}
+
+/// Single stack slot (machine word).
+#[derive(Debug, Clone)]
+pub struct StackSlot {
+    pub addr: RelocatedAddress,
+    pub value: u64,
+    pub annotations: Vec<StackSlotAnnotation>,
+}
+
+/// Stack memory of a frame, between stack pointer and CFA.
+#[derive(Debug, Clone)]
+pub struct FrameMemory {
+    /// Stack pointer value at the frame.
+    pub sp: RelocatedAddress,
+    /// Canonical frame address.
+    pub cfa: RelocatedAddress,
+    /// Stack slots in ascending address order.
+    pub slots: Vec<StackSlot>,
+}
+
 /// Debugee thread description.
 pub struct ThreadSnapshot {
     /// Running thread info - pid, number and status.
@@ -61,8 +93,15 @@ pub struct ThreadSnapshot {
     pub place: Option<PlaceDescriptorOwned>,
     /// On focus frame number (if focus on this thread)
     pub focus_frame: Option<usize>,
+    /// Thread name (from `/proc/{pid}/task/{tid}/comm`)
+    pub name: Option<String>,
+    /// Backtrace of creator thread, captured at the moment of thread creation.
+    /// `None` for the main thread or if thread created before debugger attached.
+    pub origin_bt: Option<Backtrace>,
     /// True if thread in focus, false elsewhere
     pub in_focus: bool,
+    /// Thread role label (like `tokio worker`), provided by installed oracles.
+    pub label: Option<String>,
 }
 
 /// Thread position.
@@ -101,6 +140,27 @@ pub struct FunctionRange<'a> {
     pub end_line: u64,
 }
 
+/// Source location of a function at some address.
+#[derive(Debug, Clone)]
+pub struct ResolvedFrame {
+    /// Function name, `None` if unknown.
+    pub function: Option<String>,
+    /// Source file, line and column, `None` if unknown.
+    pub place: Option<(PathBuf, u64, u64)>,
+}
+
+/// Result of resolution of an address into a module, function and source location.
+#[derive(Debug, Clone, Default)]
+pub struct AddressInfo {
+    /// Object file (executable or shared library) mapped at the address.
+    pub module: Option<PathBuf>,
+    /// Name of a function (or a symbol) that contains the address and offset from its start.
+    pub symbol: Option<(String, usize)>,
+    /// Functions at the address, the innermost inlined function is first,
+    /// the function that contains the address is last.
+    pub frames: Vec<ResolvedFrame>,
+}
+
 /// Debugee - represent static and runtime debugee information.
 pub struct Debugee {
     /// debugee running-status.
@@ -119,6 +179,8 @@ pub struct Debugee {
     disassembly: Disassembler,
     /// Loaded libthread_db.
     libthread_db: Arc<thread_db::Lib>,
+    /// Backtraces of creator threads, captured at the moment of thread creation.
+    thread_origins: HashMap<Pid, Backtrace>,
 }
 
 impl Debugee {
@@ -126,8 +188,9 @@ impl Debugee {
         path: &Path,
         process: &Child<Installed>,
         object: &object::File,
+        progress: Option<ProgressCallback>,
     ) -> Result<Self, Error> {
-        let dwarf_builder = dwarf::DebugInformationBuilder;
+        let dwarf_builder = dwarf::DebugInformationBuilder::new(progress.clone());
         let dwarf = dwarf_builder.build(path, object)?;
         let mut registry = DwarfRegistry::new(process.pid(), path.to_path_buf(), dwarf);
 
@@ -137,7 +200,12 @@ impl Debugee {
             ldd::find_dependencies(path),
             "unsuccessful attempt to use ldd"
         );
-        parse_dependencies_into_registry(&mut registry, deps.unwrap_or_default().into_iter(), true);
+        parse_dependencies_into_registry(
+            &mut registry,
+            deps.unwrap_or_default().into_iter(),
+            true,
+            progress,
+        );
 
         Ok(Self {
             execution_status: ExecutionStatus::Unload,
@@ -151,6 +219,7 @@ impl Debugee {
             dwarf_registry: registry,
             disassembly: Disassembler::new()?,
             libthread_db: Arc::new(thread_db::Lib::try_load()?),
+            thread_origins: HashMap::new(),
         })
     }
 
@@ -158,8 +227,9 @@ impl Debugee {
         path: &Path,
         process: &Child<Installed>,
         object: &object::File,
+        progress: Option<ProgressCallback>,
     ) -> Result<Self, Error> {
-        let dwarf_builder = dwarf::DebugInformationBuilder;
+        let dwarf_builder = dwarf::DebugInformationBuilder::new(progress.clone());
         let dwarf = dwarf_builder.build(path, object)?;
         let mut registry = DwarfRegistry::new(process.pid(), path.to_path_buf(), dwarf);
         registry.update_mappings(false)?;
@@ -194,6 +264,7 @@ impl Debugee {
             dwarf_registry: registry,
             disassembly: Disassembler::new()?,
             libthread_db: Arc::new(thread_db::Lib::try_load()?),
+            thread_origins: HashMap::new(),
         };
 
         debugee.attach_libthread_db();
@@ -217,6 +288,7 @@ impl Debugee {
             dwarf_registry: self.dwarf_registry.extend(proc),
             disassembly: Disassembler::new().expect("infallible"),
             libthread_db: self.libthread_db.clone(),
+            thread_origins: HashMap::new(),
         }
     }
 
@@ -234,6 +306,17 @@ impl Debugee {
         self.execution_status == ExecutionStatus::Exited
     }
 
+    /// Forget all tracees after the debugger detaches from them,
+    /// a detached process is no longer controlled by the debugger and treated as exited.
+    pub fn set_detached(&mut self) {
+        let tracee_ctl = &mut self.tracer.tracee_ctl;
+        let tids: Vec<Pid> = tracee_ctl.tracee_iter().map(|t| t.pid).collect();
+        tids.into_iter().for_each(|tid| {
+            tracee_ctl.remove(tid);
+        });
+        self.execution_status = ExecutionStatus::Exited;
+    }
+
     /// Return rendezvous struct.
     ///
     /// # Panics
@@ -243,6 +326,11 @@ impl Debugee {
         self.rendezvous.as_ref().expect("rendezvous must exists")
     }
 
+    /// Return debugee [`Tracer`]
+    pub fn tracer(&self) -> &Tracer {
+        &self.tracer
+    }
+
     /// Return debugee [`Tracer`]
     pub fn tracer_mut(&mut self) -> &mut Tracer {
         &mut self.tracer
@@ -264,7 +352,16 @@ impl Debugee {
     }
 
     pub fn trace_until_stop(&mut self, ctx: TraceContext) -> Result<StopReason, Error> {
-        let event = self.tracer.resume(ctx)?;
+        let event = loop {
+            match self.tracer.resume(ctx)? {
+                StopReason::ThreadCreated(creator, created) => {
+                    if let Some(bt) = weak_error!(self.unwind(creator)) {
+                        self.thread_origins.insert(created, bt);
+                    }
+                }
+                event => break event,
+            }
+        };
         match event {
             StopReason::DebugeeExit(_) => {
                 self.execution_status = ExecutionStatus::Exited;
@@ -325,6 +422,7 @@ impl Debugee {
             &mut self.dwarf_registry,
             reload_plan.to_add.into_iter(),
             quite,
+            None,
         );
 
         print_warns!(self.dwarf_registry.update_mappings(false)?);
@@ -343,7 +441,7 @@ impl Debugee {
             .ok_or(FunctionNotFound(ctx.location().global_pc))?;
 
         let base_addr = func.frame_base_addr(ctx, self)?;
-        let cfa = dwarf.get_cfa(self, ctx)?;
+        let cfa = dwarf.get_cfa(ctx)?;
         let backtrace = self.unwind(ctx.pid_on_focus())?;
         let (bt_frame_num, frame) = backtrace
             .iter()
@@ -360,6 +458,67 @@ impl Debugee {
         })
     }
 
+    /// Return stack memory of a frame in focus (between stack pointer and CFA).
+    /// Slots that contain saved registers or the return address are annotated.
+    pub fn frame_memory(&self, ctx: &ExplorationContext) -> Result<FrameMemory, Error> {
+        // frame may be too large for printing (for example, when big arrays allocated on stack)
+        const MAX_DUMP_SIZE: usize = 0x1000;
+        const SLOT_SIZE: usize = std::mem::size_of::<u64>();
+
+        let pid = ctx.pid_on_focus();
+        let mut registers = DwarfRegisterMap::from(self.tracee_ctl().registers(pid)?);
+        self.restore_registers_at_frame(pid, &mut registers, ctx.frame_num())?;
+        let sp = RelocatedAddress::from(registers.value(gimli::X86_64::RSP)?);
+
+        let layout = self
+            .debug_info(ctx.location().pc)?
+            .get_frame_layout(&registers, ctx)?;
+        let size = layout
+            .cfa
+            .as_usize()
+            .saturating_sub(sp.as_usize())
+            .min(MAX_DUMP_SIZE);
+        let data = read_memory_by_pid(pid, sp.as_usize(), size)?;
+
+        let slots = data
+            .chunks(SLOT_SIZE)
+            .enumerate()
+            .map(|(i, chunk)| {
+                let addr = sp.offset((i * SLOT_SIZE) as isize);
+                let mut bytes = [0u8; SLOT_SIZE];
+                bytes[..chunk.len()].copy_from_slice(chunk);
+
+                let annotations = layout
+                    .saved_registers
+                    .iter()
+                    .filter(|(_, reg_addr)| *reg_addr == addr)
+                    .map(|(reg, _)| {
+                        if *reg == layout.return_address_register {
+                            StackSlotAnnotation::ReturnAddress
+                        } else {
+                            let name = gimli::X86_64::register_name(*reg)
+                                .map(ToString::to_string)
+                                .unwrap_or_else(|| format!("r{}", reg.0));
+                            StackSlotAnnotation::SavedRegister(name)
+                        }
+                    })
+                    .collect();
+
+                StackSlot {
+                    addr,
+                    value: u64::from_ne_bytes(bytes),
+                    annotations,
+                }
+            })
+            .collect();
+
+        Ok(FrameMemory {
+            sp,
+            cfa: layout.cfa,
+            slots,
+        })
+    }
+
     pub fn thread_state(&self, ctx: &ExplorationContext) -> Result<Vec<ThreadSnapshot>, Error> {
         let threads = self.tracee_ctl().snapshot();
         Ok(threads
@@ -390,12 +549,23 @@ impl Debugee {
                     })
                 });
 
+                let name = fs::read_to_string(format!(
+                    "/proc/{}/task/{}/comm",
+                    self.tracee_ctl().proc_pid(),
+                    tracee.pid
+                ))
+                .ok()
+                .map(|name| name.trim_end().to_string());
+
                 Some(ThreadSnapshot {
+                    name,
+                    origin_bt: self.thread_origins.get(&tracee.pid).cloned(),
                     in_focus: tracee.pid == ctx.pid_on_focus(),
                     thread: tracee,
                     bt: mb_bt,
                     place: place.map(|p| p.to_owned()),
                     focus_frame: frame_num,
+                    label: None,
                 })
             })
             .collect())
@@ -507,6 +677,35 @@ impl Debugee {
         unwind::restore_registers_at_frame(self, pid, registers, frame_num)
     }
 
+    /// Return register value at a frame in focus. Only program counter and stack pointer
+    /// are restored for frames other than the current one, FS and GS bases are the same
+    /// for all frames of a thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`: exploration context
+    /// * `register`: target register
+    pub fn register_value(
+        &self,
+        ctx: &ExplorationContext,
+        register: Register,
+    ) -> Result<u64, Error> {
+        let pid = ctx.pid_on_focus();
+        let registers = self.tracee_ctl().registers(pid)?;
+        if ctx.frame_num() == 0 || matches!(register, Register::FsBase | Register::GsBase) {
+            return Ok(registers.value(register));
+        }
+
+        let dwarf_register = match register {
+            Register::Rip => gimli::Register(16),
+            Register::Rsp => gimli::Register(7),
+            _ => return Err(Error::RegisterNotRestored(register.to_string())),
+        };
+        let mut registers = DwarfRegisterMap::from(registers);
+        self.restore_registers_at_frame(pid, &mut registers, ctx.frame_num())?;
+        registers.value(dwarf_register)
+    }
+
     /// Return return address for thread current program counter.
     ///
     /// # Arguments
@@ -523,15 +722,36 @@ impl Debugee {
     }
 
     /// Return a list of disassembled instruction for a function in focus.
+    /// If there is no debug information for the function in focus, then return instructions
+    /// starting from the current program counter (function name is `None` in this case).
     pub fn disasm(
         &self,
         ctx: &ExplorationContext,
         breakpoints: &[&Breakpoint],
     ) -> Result<FunctionAssembly, Error> {
-        let debug_information = self.debug_info(ctx.location().pc)?;
-        let function = debug_information
-            .find_function_by_pc(ctx.location().global_pc)?
-            .ok_or(FunctionNotFound(ctx.location().global_pc))?;
+        let location = ctx.location();
+        let mb_function = self
+            .debug_info(location.pc)
+            .ok()
+            .and_then(|debug_information| {
+                let function = debug_information
+                    .find_function_by_pc(location.global_pc)
+                    .ok()??;
+                Some((debug_information, function))
+            });
+        let Some((debug_information, function)) = mb_function else {
+            let instructions = self.disassembly.disasm_from_pc(
+                self,
+                location.pc,
+                location.global_pc,
+                breakpoints,
+            )?;
+            return Ok(FunctionAssembly {
+                name: None,
+                addr_in_focus: location.global_pc,
+                instructions,
+            });
+        };
 
         let instructions =
             self.disassembly
@@ -544,6 +764,102 @@ impl Debugee {
         })
     }
 
+    /// Return an address and a memory access kind of the instruction that ends right before
+    /// the program counter (an instruction that activates a hardware watchpoint).
+    ///
+    /// # Arguments
+    ///
+    /// * `pc`: program counter of a thread stopped at watchpoint
+    /// * `breakpoints`: list of active breakpoints
+    pub fn access_before(
+        &self,
+        pc: RelocatedAddress,
+        breakpoints: &[&Breakpoint],
+    ) -> Result<Option<(RelocatedAddress, Option<AccessKind>)>, Error> {
+        let fn_start = self.debug_info(pc).ok().and_then(|debug_information| {
+            let global_pc = pc.into_global(self).ok()?;
+            let function = debug_information.find_function_by_pc(global_pc).ok()??;
+            function
+                .start_instruction()
+                .ok()?
+                .relocate_to_segment(self, debug_information)
+                .ok()
+        });
+        self.disassembly
+            .access_before(self, fn_start, pc, breakpoints)
+    }
+
+    /// Return name of a function (or a symbol from the symbol table) that contains the address
+    /// and offset of the address from the function start.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr`: address in debugee address space
+    pub fn symbolize(&self, addr: RelocatedAddress) -> Option<(String, usize)> {
+        let debug_information = self.debug_info(addr).ok()?;
+        let global_addr = addr.into_global(self).ok()?;
+        symbolize_global(debug_information, global_addr)
+    }
+
+    /// Resolve an address into a module, function, chain of inlined functions and source place.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr`: address in debugee address space
+    pub fn resolve_address(&self, addr: RelocatedAddress) -> Result<AddressInfo, Error> {
+        let Ok(debug_information) = self.debug_info(addr) else {
+            return Ok(AddressInfo::default());
+        };
+        resolve_global_address(debug_information, addr.into_global(self)?)
+    }
+
+    /// Resolve an address in object file into a module, function, chain of inlined functions
+    /// and source place. Unlike [`Debugee::resolve_address`] this method doesn't require
+    /// a running debugee.
+    ///
+    /// # Arguments
+    ///
+    /// * `module`: path to an object file (only file name is taken into account
+    ///   if there is no object file with the same path), `None` for the main executable
+    /// * `addr`: address in object file
+    pub fn resolve_address_in_module(
+        &self,
+        module: Option<&Path>,
+        addr: GlobalAddress,
+    ) -> Result<AddressInfo, Error> {
+        let debug_information = match module {
+            None => self.program_debug_info().ok(),
+            Some(module) => self.debug_info_from_file(module).ok().or_else(|| {
+                self.debug_info_all()
+                    .into_iter()
+                    .find(|dwarf| dwarf.pathname().file_name() == module.file_name())
+            }),
+        };
+        let Some(debug_information) = debug_information else {
+            return Ok(AddressInfo::default());
+        };
+        resolve_global_address(debug_information, addr)
+    }
+
+    /// Return an instruction located at the program counter, target of a call or a jump
+    /// is resolved into a function name.
+    ///
+    /// # Arguments
+    ///
+    /// * `pc`: program counter
+    /// * `breakpoints`: list of active breakpoints
+    pub fn decode_instruction(
+        &self,
+        pc: RelocatedAddress,
+        breakpoints: &[&Breakpoint],
+    ) -> Result<Option<DecodedInstruction>, Error> {
+        let mut instruction = self.disassembly.decode_at(self, pc, breakpoints)?;
+        if let Some(target) = instruction.as_mut().and_then(|i| i.target.as_mut()) {
+            target.symbol = self.symbolize(target.address);
+        }
+        Ok(instruction)
+    }
+
     /// Return two place descriptors, at the start and at the end of the current function.
     pub fn function_range(&self, ctx: &ExplorationContext) -> Result<FunctionRange, Error> {
         let debug_information = self.debug_info(ctx.location().pc)?;
@@ -588,8 +904,93 @@ impl Debugee {
     }
 }
 
+/// Return name of a function (or a symbol from the symbol table) that contains the address
+/// and offset of the address from the function start.
+fn symbolize_global(
+    debug_information: &DebugInformation,
+    global_addr: GlobalAddress,
+) -> Option<(String, usize)> {
+    if let Some(function) = debug_information
+        .find_function_by_pc(global_addr)
+        .ok()
+        .flatten()
+    {
+        if let (Some(name), Ok(start)) = (function.full_name(), function.start_instruction()) {
+            return Some((name, usize::from(global_addr) - usize::from(start)));
+        }
+    }
+    let symbol = debug_information.find_symbol_by_addr(global_addr)?;
+    Some((
+        symbol.name.clone(),
+        usize::from(global_addr) - usize::from(symbol.addr),
+    ))
+}
+
+/// Resolve an address in object file into a function, chain of inlined functions
+/// and source place.
+fn resolve_global_address(
+    debug_information: &DebugInformation,
+    global_addr: GlobalAddress,
+) -> Result<AddressInfo, Error> {
+    let mut info = AddressInfo {
+        module: Some(debug_information.pathname().to_path_buf()),
+        symbol: symbolize_global(debug_information, global_addr),
+        frames: vec![],
+    };
+    if !debug_information.has_debug_info() {
+        if let Some((name, _)) = &info.symbol {
+            info.frames.push(ResolvedFrame {
+                function: Some(name.clone()),
+                place: None,
+            });
+        }
+        return Ok(info);
+    }
+
+    let mut place = debug_information
+        .find_place_from_pc(global_addr)?
+        .map(|place| {
+            (
+                place.file.to_path_buf(),
+                place.line_number,
+                place.column_number,
+            )
+        });
+
+    match debug_information.find_function_by_pc(global_addr)? {
+        Some(function) => {
+            for call in function.inline_chain(global_addr).into_iter().rev() {
+                info.frames.push(ResolvedFrame {
+                    function: call.name,
+                    place,
+                });
+                place = call
+                    .call_file
+                    .zip(call.call_line)
+                    .map(|(file, line)| (file, line, call.call_column.unwrap_or_default()));
+            }
+            info.frames.push(ResolvedFrame {
+                function: function.full_name(),
+                place,
+            });
+        }
+        None if place.is_some() || info.symbol.is_some() => {
+            info.frames.push(ResolvedFrame {
+                function: info.symbol.as_ref().map(|(name, _)| name.clone()),
+                place,
+            });
+        }
+        None => {}
+    }
+
+    Ok(info)
+}
+
 /// Parse dwarf information from new dependency.
-fn parse_dependency(dep_file: impl Into<PathBuf>) -> Result<Option<DebugInformation>, Error> {
+fn parse_dependency(
+    dep_file: impl Into<PathBuf>,
+    progress: Option<ProgressCallback>,
+) -> Result<Option<DebugInformation>, Error> {
     let dep_file = dep_file.into();
 
     // empty string represents a program executable that must already parse
@@ -602,7 +1003,7 @@ fn parse_dependency(dep_file: impl Into<PathBuf>) -> Result<Option<DebugInformat
     let mmap = unsafe { memmap2::Mmap::map(&file)? };
     let object = object::File::parse(&*mmap)?;
 
-    let dwarf_builder = dwarf::DebugInformationBuilder;
+    let dwarf_builder = dwarf::DebugInformationBuilder::new(progress);
     let dwarf = dwarf_builder.build(dep_file.as_path(), &object)?;
     Ok(Some(dwarf))
 }
@@ -612,13 +1013,14 @@ fn parse_dependencies_into_registry(
     registry: &mut DwarfRegistry,
     deps: impl Iterator<Item = impl Into<PathBuf>>,
     quiet: bool,
+    progress: Option<ProgressCallback>,
 ) {
     let dwarfs: Vec<_> = deps
         .map(|dep| dep.into())
         .collect::<Vec<_>>()
         .into_par_iter()
         .filter_map(|dep| {
-            let parse_result = parse_dependency(&dep);
+            let parse_result = parse_dependency(&dep, progress.clone());
             match parse_result {
                 Ok(mb_dep) => mb_dep.map(|dwarf| {
                     if !quiet {
@@ -2,7 +2,7 @@ use crate::debugger::address::RelocatedAddress;
 use crate::debugger::debugee::dwarf::{DebugInformation, EndianArcSlice};
 use crate::debugger::error::Error;
 use crate::debugger::error::Error::MappingNotFound;
-use nix::unistd::Pid;
+use crate::debugger::Pid;
 use proc_maps::MapRange;
 use std::cmp::Ordering;
 use std::collections::HashMap;
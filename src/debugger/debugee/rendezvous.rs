@@ -1,8 +1,8 @@
 #![allow(dead_code)]
 
 use crate::debugger::address::RelocatedAddress;
+use crate::debugger::Pid;
 use nix::libc;
-use nix::unistd::Pid;
 use object::elf::DT_DEBUG;
 use std::collections::HashMap;
 
@@ -95,10 +95,10 @@ impl Rendezvous {
 mod ffi {
     #![allow(non_camel_case_types)]
 
+    use crate::debugger::Pid;
     use nix::libc;
     use nix::sys::uio;
     use nix::sys::uio::RemoteIoVec;
-    use nix::unistd::Pid;
     use std::io::IoSliceMut;
     use std::mem;
 
@@ -141,7 +141,8 @@ mod ffi {
         };
         let local_iov_slice = &mut [local_iov];
 
-        let _reads = uio::process_vm_readv(pid, local_iov_slice.as_mut_slice(), &[remote_iov])?;
+        let _reads =
+            uio::process_vm_readv(pid.into(), local_iov_slice.as_mut_slice(), &[remote_iov])?;
 
         let ptr = local_iov_slice[0].as_ptr();
 
@@ -5,16 +5,20 @@ use crate::debugger::debugee::{Debugee, Location};
 use crate::debugger::error::Error;
 use crate::debugger::error::Error::{MultipleErrors, NoThreadDB, Ptrace, ThreadDB, Waitpid};
 use crate::debugger::register::{Register, RegisterMap};
+use crate::debugger::sys;
+use crate::debugger::sys::WaitStatus;
+use crate::debugger::Pid;
+use crate::debugger::Signal;
 use log::{debug, warn};
 use nix::errno::Errno;
-use nix::sys;
-use nix::sys::signal::Signal;
-use nix::sys::wait::{waitpid, WaitStatus};
-use nix::unistd::Pid;
+use nix::sys::wait::WaitPidFlag;
 use ouroboros::self_referencing;
+use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
 use thread_db;
 
 #[self_referencing]
@@ -38,7 +42,7 @@ pub enum TraceeStatus {
 }
 
 /// Tracee is a thread attached to debugger with ptrace.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug)]
 pub struct Tracee {
     /// Thread number, used for user interaction with tracee
     pub number: u32,
@@ -46,6 +50,15 @@ pub struct Tracee {
     pub pid: Pid,
     /// Tracee current status.
     pub status: TraceeStatus,
+    /// Register values of a stopped tracee. Registers of a stopped thread are changed only
+    /// by debugger, so values are reused until the tracee resumes.
+    registers: RefCell<Option<Box<RegisterMap>>>,
+}
+
+impl PartialEq for Tracee {
+    fn eq(&self, other: &Self) -> bool {
+        self.number == other.number && self.pid == other.pid && self.status == other.status
+    }
 }
 
 impl Tracee {
@@ -55,20 +68,47 @@ impl Tracee {
             number: NEXT_TRACEE_NUM.fetch_add(1, Ordering::Relaxed),
             pid,
             status: Stopped(Interrupt),
+            registers: RefCell::default(),
         }
     }
 
     /// Wait for change of tracee status.
     pub fn wait_one(&self) -> Result<WaitStatus, Error> {
         debug!(target: "tracer", "wait for tracee status, thread {pid}", pid = self.pid);
-        let status = waitpid(self.pid, None).map_err(Waitpid)?;
+        let status = sys::waitpid(self.pid, None).map_err(|e| Waitpid(e.into()))?;
         debug!(target: "tracer", "receive tracee status, thread {pid}, status: {status:?}", pid = self.pid);
         Ok(status)
     }
 
+    /// Wait for change of tracee status, but no longer than `timeout`.
+    /// Return `None` if tracee status isn't changed in time.
+    ///
+    /// There is no `waitpid` with a timeout: pidfd is not readable on a ptrace-stop and
+    /// waiting for SIGCHLD requires blocking this signal in all debugger threads.
+    /// So status is polled with an exponential backoff capped at 10ms, this costs at most
+    /// a hundred wakeups per second and is used only while interrupting threads at group stop.
+    pub fn wait_one_timeout(&self, timeout: Duration) -> Result<Option<WaitStatus>, Error> {
+        let start = Instant::now();
+        let mut delay = Duration::from_micros(100);
+        loop {
+            let status = sys::waitpid(self.pid, Some(WaitPidFlag::WNOHANG))
+                .map_err(|e| Waitpid(e.into()))?;
+            if status != WaitStatus::StillAlive {
+                debug!(target: "tracer", "receive tracee status, thread {pid}, status: {status:?}", pid = self.pid);
+                return Ok(Some(status));
+            }
+            if start.elapsed() >= timeout {
+                return Ok(None);
+            }
+            thread::sleep(delay);
+            delay = (delay * 2).min(Duration::from_millis(10));
+        }
+    }
+
     /// Move the stopped tracee process forward by a single instruction step.
     pub fn step(&self, sig: Option<Signal>) -> Result<(), Error> {
-        sys::ptrace::step(self.pid, sig).map_err(Ptrace)
+        self.invalidate_registers();
+        Ok(sys::step(self.pid, sig)?)
     }
 
     fn update_status(&mut self, status: TraceeStatus) {
@@ -88,12 +128,10 @@ impl Tracee {
             pid = self.pid,
         );
 
-        sys::ptrace::cont(self.pid, sig)
-            .map(|ok| {
-                self.update_status(Running);
-                ok
-            })
-            .map_err(Ptrace)
+        self.invalidate_registers();
+        sys::cont(self.pid, sig)?;
+        self.update_status(Running);
+        Ok(())
     }
 
     /// Set tracee status into stop.
@@ -108,17 +146,44 @@ impl Tracee {
         matches!(self.status, Stopped(_))
     }
 
+    /// Return current register values, values are cached until the tracee resumes.
+    pub fn registers(&self) -> Result<RegisterMap, Error> {
+        if let Some(registers) = self.registers.borrow().as_ref() {
+            return Ok(registers.as_ref().clone());
+        }
+        let registers = RegisterMap::current(self.pid)?;
+        self.registers.replace(Some(Box::new(registers.clone())));
+        Ok(registers)
+    }
+
+    /// Replace tracee registers with values taken from a register map.
+    ///
+    /// # Arguments
+    ///
+    /// * `registers`: new register values
+    pub fn set_registers(&self, registers: RegisterMap) -> Result<(), Error> {
+        self.invalidate_registers();
+        registers.clone().persist(self.pid)?;
+        self.registers.replace(Some(Box::new(registers)));
+        Ok(())
+    }
+
+    /// Drop cached register values, must be called before the tracee resumes.
+    pub(crate) fn invalidate_registers(&self) {
+        self.registers.take();
+    }
+
     /// Get current program counter value.
     pub fn pc(&self) -> Result<RelocatedAddress, Error> {
-        RegisterMap::current(self.pid)
+        self.registers()
             .map(|reg_map| RelocatedAddress::from(reg_map.value(Register::Rip)))
     }
 
     /// Set new program counter value.
     pub fn set_pc(&self, value: u64) -> Result<(), Error> {
-        let mut map = RegisterMap::current(self.pid)?;
+        let mut map = self.registers()?;
         map.update(Register::Rip, value);
-        map.persist(self.pid)
+        self.set_registers(map)
     }
 
     /// Get current tracee location.
@@ -164,6 +229,31 @@ impl TraceeCtl {
         }
     }
 
+    /// Return register values of a thread, values are cached until the thread resumes.
+    ///
+    /// # Arguments
+    ///
+    /// * `pid`: thread id
+    pub fn registers(&self, pid: Pid) -> Result<RegisterMap, Error> {
+        match self.threads_state.get(&pid) {
+            Some(tracee) => tracee.registers(),
+            None => RegisterMap::current(pid),
+        }
+    }
+
+    /// Replace thread registers with values taken from a register map.
+    ///
+    /// # Arguments
+    ///
+    /// * `pid`: thread id
+    /// * `registers`: new register values
+    pub fn set_registers(&self, pid: Pid, registers: RegisterMap) -> Result<(), Error> {
+        match self.threads_state.get(&pid) {
+            Some(tracee) => tracee.set_registers(registers),
+            None => registers.persist(pid),
+        }
+    }
+
     pub(crate) fn tracee(&mut self, pid: Pid) -> Option<&Tracee> {
         self.threads_state.get(&pid)
     }
@@ -210,7 +300,7 @@ impl TraceeCtl {
 
             if let Err(e) = tracee.r#continue(None) {
                 // if no such process - continue, it will be removed later, on PTRACE_EVENT_EXIT event.
-                if matches!(e, Ptrace(err) if err == Errno::ESRCH) {
+                if matches!(e, Ptrace(err) if err.errno() == Errno::ESRCH) {
                     //warn!("thread {} not found, ESRCH", tracee.pid);
                     return;
                 }
@@ -256,7 +346,7 @@ impl TraceeCtl {
 
             if let Err(e) = tracee.r#continue(resume_sign) {
                 // if no such process - continue, it will be removed later, on PTRACE_EVENT_EXIT event.
-                if matches!(e, Ptrace(err) if err == Errno::ESRCH) {
+                if matches!(e, Ptrace(err) if err.errno() == Errno::ESRCH) {
                     warn!("thread {} not found, ESRCH", tracee.pid);
                     return;
                 }
@@ -284,7 +374,7 @@ impl TraceeCtl {
     pub(super) fn attach_thread_db(&mut self, lib: Arc<thread_db::Lib>) -> Result<(), Error> {
         let td_process = ThreadDBProcessTryBuilder {
             lib,
-            process_builder: |lib| lib.attach(self.process_pid),
+            process_builder: |lib| lib.attach(self.process_pid.into()),
         }
         .try_build()?;
         self.thread_db_proc = Some(td_process);
@@ -300,11 +390,41 @@ impl TraceeCtl {
     ) -> Result<RelocatedAddress, Error> {
         let td_proc = self.thread_db_proc.as_ref().ok_or(NoThreadDB)?;
 
-        let thread: thread_db::Thread =
-            td_proc.borrow_process().get_thread(tid).map_err(ThreadDB)?;
+        let thread: thread_db::Thread = td_proc
+            .borrow_process()
+            .get_thread(tid.into())
+            .map_err(ThreadDB)?;
 
         Ok(RelocatedAddress::from(
             thread.tls_addr(link_map_addr.into(), offset)? as usize,
         ))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::process::Command;
+
+    #[test]
+    // child is reaped by a tracee wait
+    #[allow(clippy::zombie_processes)]
+    fn test_wait_one_timeout() {
+        let child = Command::new("sleep").arg("10").spawn().unwrap();
+        let tracee = Tracee::new_stopped(Pid::from_raw(child.id() as i32));
+
+        let timeout = Duration::from_millis(100);
+        let start = Instant::now();
+        assert_eq!(tracee.wait_one_timeout(timeout).unwrap(), None);
+        let elapsed = start.elapsed();
+        assert!(elapsed >= timeout);
+        assert!(elapsed < Duration::from_secs(1));
+
+        sys::kill(tracee.pid, Signal::SIGKILL).unwrap();
+        let status = tracee.wait_one_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(
+            status,
+            Some(WaitStatus::Signaled(tracee.pid, Signal::SIGKILL, false))
+        );
+    }
+}
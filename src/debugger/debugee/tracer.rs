@@ -1,20 +1,21 @@
 use crate::debugger::address::RelocatedAddress;
-use crate::debugger::breakpoint::{Breakpoint, BrkptType};
+use crate::debugger::breakpoint::{distribute_hardware_breakpoints, Breakpoint, BrkptType};
 use crate::debugger::debugee::tracee::{StopType, TraceeCtl, TraceeStatus};
 use crate::debugger::error::Error;
-use crate::debugger::error::Error::{MultipleErrors, ProcessExit, Ptrace, Waitpid};
+use crate::debugger::error::Error::{MultipleErrors, ProcessExit, Waitpid};
 use crate::debugger::register::debug::DebugRegisterNumber;
+use crate::debugger::sys;
+use crate::debugger::sys::WaitStatus;
 use crate::debugger::watchpoint::WatchpointRegistry;
+use crate::debugger::Pid;
+use crate::debugger::Signal;
 use crate::debugger::{code, register};
 use crate::weak_error;
 use log::{debug, warn};
 use nix::errno::Errno;
-use nix::libc::pid_t;
-use nix::sys::signal::{Signal, SIGSTOP};
-use nix::sys::wait::{waitpid, WaitStatus};
-use nix::unistd::Pid;
-use nix::{libc, sys};
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
+use std::mem;
+use std::time::Duration;
 
 /// List of signals that dont interrupt a debugging process and send
 /// to debugee directly on fire.
@@ -31,6 +32,9 @@ static QUIET_SIGNALS: &[Signal] = &[
 /// List of signals that may interrupt a debugging process but debugger will not inject it into.
 static TRANSPARENT_SIGNALS: &[Signal] = &[Signal::SIGINT];
 
+/// How long a group stop waits for a single tracee to stop after PTRACE_INTERRUPT.
+const INTERRUPT_TIMEOUT: Duration = Duration::from_secs(1);
+
 #[derive(Debug, Clone)]
 pub enum WatchpointHitType {
     /// Hit of the underlying hardware breakpoint cause value changed.
@@ -53,6 +57,10 @@ pub enum StopReason {
     SignalStop(Pid, Signal),
     /// Debugee stopped with Errno::ESRCH.
     NoSuchProcess(Pid),
+    /// New thread created (second pid), creator thread (first pid) is stopped.
+    /// Debugee continues execution after handling this event, so it never comes out of the
+    /// [`super::Debugee::trace_until_stop`].
+    ThreadCreated(Pid, Pid),
 }
 
 #[derive(Clone, Copy)]
@@ -79,6 +87,20 @@ pub struct Tracer {
 
     inject_signal_queue: VecDeque<(Pid, Signal)>,
     group_stop_guard: bool,
+    /// Pairs of creator and created threads, creator threads are stopped.
+    created_threads: VecDeque<(Pid, Pid)>,
+    /// Tracees that are single-stepping right now.
+    in_flight_steps: HashSet<Pid>,
+    /// Tracees whose single step `TRAP_TRACE` was received outside of [`Tracer::single_step`].
+    completed_steps: HashSet<Pid>,
+    /// Breakpoint and watchpoint hits of other threads caught during a group stop.
+    pending_stops: VecDeque<StopReason>,
+    /// Statuses of other inferiors processes, `waitpid(-1)` reaps them together with own ones.
+    foreign_statuses: Vec<WaitStatus>,
+    /// Own statuses reaped by tracers of other inferiors, applied at the next resume.
+    deferred_statuses: VecDeque<WaitStatus>,
+    /// Tracees that don't respond to an interrupt at the last group stop.
+    not_stopped: Vec<Pid>,
 }
 
 impl Tracer {
@@ -92,6 +114,13 @@ impl Tracer {
             tracee_ctl: TraceeCtl::new(proc_pid),
             inject_signal_queue: VecDeque::new(),
             group_stop_guard: false,
+            created_threads: VecDeque::new(),
+            in_flight_steps: HashSet::new(),
+            completed_steps: HashSet::new(),
+            pending_stops: VecDeque::new(),
+            foreign_statuses: vec![],
+            deferred_statuses: VecDeque::new(),
+            not_stopped: vec![],
         }
     }
 
@@ -106,37 +135,78 @@ impl Tracer {
             tracee_ctl: TraceeCtl::new_external(proc_pid, threads),
             inject_signal_queue: VecDeque::new(),
             group_stop_guard: false,
+            created_threads: VecDeque::new(),
+            in_flight_steps: HashSet::new(),
+            completed_steps: HashSet::new(),
+            pending_stops: VecDeque::new(),
+            foreign_statuses: vec![],
+            deferred_statuses: VecDeque::new(),
+            not_stopped: vec![],
         }
     }
 
     /// Continue debugee execution until stop happened.
     pub fn resume(&mut self, ctx: TraceContext) -> Result<StopReason, Error> {
         loop {
-            if let Some(req) = self.inject_signal_queue.pop_front() {
-                self.tracee_ctl.cont_stopped_ex(
-                    Some(req),
-                    self.inject_signal_queue
-                        .iter()
-                        .map(|(pid, _)| *pid)
-                        .collect(),
-                )?;
-
-                if let Some((pid, sign)) = self.inject_signal_queue.front().copied() {
-                    // if there are more signals - stop debugee again
-                    self.group_stop_interrupt(ctx, Pid::from_raw(-1))?;
-                    return Ok(StopReason::SignalStop(pid, sign));
+            // threads that hit a breakpoint or a watchpoint during a group stop are still
+            // stopped at it, so report them before resume
+            if let Some(stop) = self.pending_stops.pop_front() {
+                let (StopReason::Breakpoint(pid, _) | StopReason::Watchpoint(pid, _, _)) = stop
+                else {
+                    unreachable!("only breakpoint and watchpoint stops are pending")
+                };
+                if self.tracee_ctl.tracee(pid).is_some() {
+                    debug!(target: "tracer", "debugee stopped, pending reason: {stop:?}");
+                    return Ok(stop);
                 }
-            } else {
-                self.tracee_ctl.cont_stopped().map_err(MultipleErrors)?;
+                continue;
+            }
+
+            // creator threads are still stopped, so give a chance to inspect them before resume
+            if let Some((creator, created)) = self.created_threads.pop_front() {
+                if self.tracee_ctl.tracee(creator).is_some() {
+                    return Ok(StopReason::ThreadCreated(creator, created));
+                }
+                continue;
             }
 
-            debug!(target: "tracer", "resume debugee execution, wait for updates");
-            let status = match waitpid(Pid::from_raw(-1), None) {
-                Ok(status) => status,
-                Err(Errno::ECHILD) => {
-                    return Ok(StopReason::NoSuchProcess(self.tracee_ctl.proc_pid()));
+            let status = if let Some(status) = self.deferred_statuses.pop_front() {
+                debug!(target: "tracer", "apply status reaped by another inferior: {status:?}");
+                status
+            } else {
+                if let Some(req) = self.inject_signal_queue.pop_front() {
+                    self.tracee_ctl.cont_stopped_ex(
+                        Some(req),
+                        self.inject_signal_queue
+                            .iter()
+                            .map(|(pid, _)| *pid)
+                            .collect(),
+                    )?;
+
+                    if let Some((pid, sign)) = self.inject_signal_queue.front().copied() {
+                        // if there are more signals - stop debugee again
+                        self.group_stop_interrupt(ctx, Pid::from_raw(-1))?;
+                        return Ok(StopReason::SignalStop(pid, sign));
+                    }
+                } else {
+                    self.tracee_ctl.cont_stopped().map_err(MultipleErrors)?;
+                }
+
+                debug!(target: "tracer", "resume debugee execution, wait for updates");
+                let status = match sys::waitpid(Pid::from_raw(-1), None) {
+                    Ok(status) => status,
+                    Err(Errno::ECHILD) => {
+                        return Ok(StopReason::NoSuchProcess(self.tracee_ctl.proc_pid()));
+                    }
+                    Err(e) => return Err(Waitpid(e.into())),
+                };
+
+                if !self.is_own(status) {
+                    debug!(target: "tracer", "status of another inferior received: {status:?}");
+                    self.foreign_statuses.push(status);
+                    continue;
                 }
-                Err(e) => return Err(Waitpid(e)),
+                status
             };
 
             debug!(target: "tracer", "received new thread status: {status:?}");
@@ -155,6 +225,40 @@ impl Tracer {
         }
     }
 
+    /// Return true if a status belongs to one of the debugee threads.
+    ///
+    /// # Arguments
+    ///
+    /// * `status`: status returned by `waitpid`
+    fn is_own(&self, status: WaitStatus) -> bool {
+        let Some(pid) = status.pid() else {
+            return true;
+        };
+        let proc_pid = self.tracee_ctl.proc_pid();
+        pid == proc_pid
+            || self.tracee_ctl.tracee_iter().any(|t| t.pid == pid)
+            || sys::thread_exists(proc_pid, pid)
+    }
+
+    /// Take statuses of other inferiors processes reaped by this tracer.
+    pub fn take_foreign_statuses(&mut self) -> Vec<WaitStatus> {
+        mem::take(&mut self.foreign_statuses)
+    }
+
+    /// Accept a status reaped by a tracer of another inferior,
+    /// return it back if status doesn't belong to the debugee.
+    ///
+    /// # Arguments
+    ///
+    /// * `status`: status returned by `waitpid`
+    pub fn defer_status(&mut self, status: WaitStatus) -> Option<WaitStatus> {
+        if !self.is_own(status) {
+            return Some(status);
+        }
+        self.deferred_statuses.push_back(status);
+        None
+    }
+
     fn group_stop_in_progress(&self) -> bool {
         self.group_stop_guard
     }
@@ -174,6 +278,12 @@ impl Tracer {
     ///
     /// If tracee receives signals before interrupt - then tracee in signal-stop and no need to interrupt it.
     ///
+    /// Tracee that doesn't stop in [`INTERRUPT_TIMEOUT`] (for example, in uninterruptible sleep)
+    /// is left running, its stop will be handled later as a regular PTRACE_EVENT_STOP event.
+    ///
+    /// Tracee that hits a breakpoint or a watchpoint before the interrupt stays at it,
+    /// this stop is returned by the next [`Tracer::resume`] call.
+    ///
     /// # Arguments
     ///
     /// * `initiator_pid`: tracee with this thread id already stopped, there is no need to interrupt it.
@@ -182,6 +292,7 @@ impl Tracer {
             return Ok(());
         }
         self.lock_group_stop();
+        self.not_stopped.clear();
 
         debug!(
             target: "tracer",
@@ -204,6 +315,7 @@ impl Tracer {
             return Ok(());
         }
 
+        let mut stuck = HashSet::new();
         // two rounds, cause may be new tracees at first round, they stopped at round 2
         for _ in 0..2 {
             let tracees = self.tracee_ctl.snapshot();
@@ -213,7 +325,7 @@ impl Tracer {
                 let mut tracee = match self.tracee_ctl.tracee(tid) {
                     None => continue,
                     Some(tracee) => {
-                        if tracee.is_stopped() {
+                        if tracee.is_stopped() || stuck.contains(&tid) {
                             continue;
                         } else {
                             tracee.clone()
@@ -221,7 +333,7 @@ impl Tracer {
                     }
                 };
 
-                if let Err(e) = sys::ptrace::interrupt(tracee.pid) {
+                if let Err(e) = sys::interrupt(tracee.pid) {
                     // if no such process - continue, it will be removed later, on PTRACE_EVENT_EXIT event.
                     if Errno::ESRCH == e {
                         warn!("thread {} not found, ESRCH", tracee.pid);
@@ -230,18 +342,25 @@ impl Tracer {
                         }
                         continue;
                     }
-                    return Err(Ptrace(e));
+                    return Err(e.into());
                 }
 
-                let mut wait = tracee.wait_one()?;
+                let Some(mut wait) = tracee.wait_one_timeout(INTERRUPT_TIMEOUT)? else {
+                    stuck.insert(tracee.pid);
+                    continue;
+                };
 
-                while !matches!(wait, WaitStatus::PtraceEvent(_, _, libc::PTRACE_EVENT_STOP)) {
+                while !matches!(wait, WaitStatus::PtraceEvent(_, _, sys::PTRACE_EVENT_STOP)) {
                     let stop = self.apply_new_status(ctx, wait)?;
                     match stop {
                         None => {}
-                        Some(StopReason::Breakpoint(pid, _))
-                        | Some(StopReason::Watchpoint(pid, _, _)) => {
-                            // tracee already stopped cause breakpoint or watchpoint are reached
+                        Some(
+                            stop @ (StopReason::Breakpoint(pid, _)
+                            | StopReason::Watchpoint(pid, _, _)),
+                        ) => {
+                            // tracee already stopped cause breakpoint or watchpoint are reached,
+                            // this stop is reported at next resume
+                            self.pending_stops.push_back(stop);
                             if pid == tracee.pid {
                                 break;
                             }
@@ -258,6 +377,9 @@ impl Tracer {
                             // expect that tracee will be removed later
                             break;
                         }
+                        Some(StopReason::ThreadCreated(_, _)) => {
+                            unreachable!("thread creation events are queued")
+                        }
                     }
 
                     // reload tracee, it states must be changed after handle signal
@@ -271,9 +393,18 @@ impl Tracer {
                         break;
                     }
 
-                    wait = tracee.wait_one()?;
+                    wait = match tracee.wait_one_timeout(INTERRUPT_TIMEOUT)? {
+                        Some(wait) => wait,
+                        None => {
+                            stuck.insert(tracee.pid);
+                            break;
+                        }
+                    };
                 }
 
+                if stuck.contains(&tracee.pid) {
+                    continue;
+                }
                 if let Some(t) = self.tracee_ctl.tracee_mut(tracee.pid) {
                     if !t.is_stopped() {
                         t.set_stop(StopType::Interrupt);
@@ -282,6 +413,7 @@ impl Tracer {
             }
         }
 
+        self.not_stopped = self.check_group_stop_consistency(&stuck);
         self.unlock_group_stop();
 
         debug!(
@@ -293,6 +425,47 @@ impl Tracer {
         Ok(())
     }
 
+    /// Final pass of a group stop. Tracees that exit in the middle of a group stop are removed.
+    /// Return tracees that don't respond to the interrupt and keep running.
+    ///
+    /// # Arguments
+    ///
+    /// * `stuck`: tracees that are not stopped in time
+    fn check_group_stop_consistency(&mut self, stuck: &HashSet<Pid>) -> Vec<Pid> {
+        let proc_pid = self.tracee_ctl.proc_pid();
+        let mut not_stopped = vec![];
+        for tracee in self.tracee_ctl.snapshot() {
+            if tracee.pid != proc_pid && !sys::thread_exists(proc_pid, tracee.pid) {
+                debug!(target: "tracer", "thread {} exited during group stop", tracee.pid);
+                self.tracee_ctl.remove(tracee.pid);
+                continue;
+            }
+
+            if stuck.contains(&tracee.pid) && !tracee.is_stopped() {
+                debug!(
+                    target: "tracer",
+                    "thread {} doesn't respond to interrupt in {INTERRUPT_TIMEOUT:?}",
+                    tracee.pid
+                );
+                not_stopped.push(tracee.pid);
+            }
+        }
+        not_stopped
+    }
+
+    /// Return tracees that don't respond to an interrupt at the last group stop
+    /// (for example, in uninterruptible sleep) and are still running.
+    pub fn not_stopped_tracees(&self) -> Vec<Pid> {
+        let mut tids: Vec<_> = self
+            .tracee_ctl
+            .tracee_iter()
+            .filter(|tracee| self.not_stopped.contains(&tracee.pid) && !tracee.is_stopped())
+            .map(|tracee| tracee.pid)
+            .collect();
+        tids.sort();
+        tids
+    }
+
     /// Handle tracee event fired by `wait` syscall.
     /// After this function ends tracee_ctl must be in consistent state.
     /// If debugee process stop detected - returns a stop reason.
@@ -316,20 +489,19 @@ impl Tracer {
             }
             WaitStatus::PtraceEvent(pid, _signal, code) => {
                 match code {
-                    libc::PTRACE_EVENT_EXEC => {
+                    sys::PTRACE_EVENT_EXEC => {
                         // fire just before debugee start
                         // cause currently `fork()`
                         // in debugee is unsupported we expect this code to call once
                         self.tracee_ctl.add(pid);
                         return Ok(Some(StopReason::DebugeeStart));
                     }
-                    libc::PTRACE_EVENT_CLONE => {
+                    sys::PTRACE_EVENT_CLONE => {
                         // fire just before new thread created
                         self.tracee_ctl
                             .tracee_ensure_mut(pid)
                             .set_stop(StopType::Interrupt);
-                        let new_thread_id =
-                            Pid::from_raw(sys::ptrace::getevent(pid).map_err(Ptrace)? as pid_t);
+                        let new_thread_id = Pid::from_raw(sys::getevent(pid)? as i32);
 
                         // PTRACE_EVENT_STOP may be received first, and new tracee may be already registered at this point
                         if self.tracee_ctl.tracee_mut(new_thread_id).is_none() {
@@ -339,30 +511,44 @@ impl Tracer {
                                 // this situation can occur if the process has already completed
                                 self.tracee_ctl.remove(new_thread_id);
                             } else {
-                                // all watchpoints must be distributed to a new tracee
+                                // all watchpoints and hardware breakpoints must be distributed
+                                // to a new tracee
                                 weak_error!(ctx.watchpoints.distribute_to_tracee(new_tracee));
+                                if ctx.breakpoints.iter().any(|b| b.is_hardware()) {
+                                    weak_error!(distribute_hardware_breakpoints(
+                                        ctx.breakpoints,
+                                        new_thread_id
+                                    ));
+                                }
+                                self.created_threads.push_back((pid, new_thread_id));
 
                                 debug_assert!(
                                     matches!(
                                         new_trace_status,
-                                        WaitStatus::PtraceEvent(tid, _, libc::PTRACE_EVENT_STOP) if tid == new_thread_id
+                                        WaitStatus::PtraceEvent(tid, _, sys::PTRACE_EVENT_STOP) if tid == new_thread_id
                                     ),
                                     "the newly cloned thread must start with PTRACE_EVENT_STOP (cause PTRACE_SEIZE was used), got {new_trace_status:?}"
                                 )
                             }
                         }
                     }
-                    libc::PTRACE_EVENT_STOP => {
+                    sys::PTRACE_EVENT_STOP => {
                         // fire right after new thread started or PTRACE_INTERRUPT called.
                         match self.tracee_ctl.tracee_mut(pid) {
                             Some(tracee) => tracee.set_stop(StopType::Interrupt),
                             None => {
                                 let tracee = self.tracee_ctl.add(pid);
                                 weak_error!(ctx.watchpoints.distribute_to_tracee(tracee));
+                                if ctx.breakpoints.iter().any(|b| b.is_hardware()) {
+                                    weak_error!(distribute_hardware_breakpoints(
+                                        ctx.breakpoints,
+                                        pid
+                                    ));
+                                }
                             }
                         }
                     }
-                    libc::PTRACE_EVENT_EXIT => {
+                    sys::PTRACE_EVENT_EXIT => {
                         // Stop the tracee at exit
                         let tracee = self.tracee_ctl.remove(pid);
                         if let Some(mut tracee) = tracee {
@@ -384,16 +570,30 @@ impl Tracer {
                 Ok(None)
             }
             WaitStatus::Stopped(pid, signal) => {
-                let info = match sys::ptrace::getsiginfo(pid) {
-                    Ok(info) => info,
+                let sigcode = match sys::sigcode(pid) {
+                    Ok(sigcode) => sigcode,
                     Err(Errno::ESRCH) => return Ok(Some(StopReason::NoSuchProcess(pid))),
-                    Err(e) => return Err(Ptrace(e)),
+                    Err(e) => return Err(e.into()),
                 };
 
                 match signal {
-                    Signal::SIGTRAP => match info.si_code {
+                    Signal::SIGTRAP => match sigcode {
                         code::TRAP_TRACE => {
-                            todo!()
+                            self.tracee_ctl
+                                .tracee_ensure_mut(pid)
+                                .set_stop(StopType::Interrupt);
+
+                            if self.in_flight_steps.contains(&pid) {
+                                // step of this tracee is done, but the trap is caught
+                                // outside the step loop, let the step loop know about it
+                                self.completed_steps.insert(pid);
+                            } else {
+                                // trap is raised by a debugee itself (trap flag is set by a program),
+                                // so deliver it to a program at the next resume
+                                debug!(target: "tracer", "TRAP_TRACE in thread {pid} without a step, inject SIGTRAP");
+                                self.inject_signal_queue.push_back((pid, Signal::SIGTRAP));
+                            }
+                            Ok(None)
                         }
                         code::TRAP_BRKPT | code::SI_KERNEL => {
                             let current_pc = {
@@ -402,53 +602,7 @@ impl Tracer {
                                 tracee.pc()?
                             };
 
-                            let mb_hit_brkpt = ctx
-                                .breakpoints
-                                .iter()
-                                .find(|brkpt| brkpt.addr == current_pc);
-                            debug_assert!(
-                                mb_hit_brkpt.is_some(),
-                                "the interrupt caught but the breakpoint was not found"
-                            );
-                            let Some(&brkpt) = mb_hit_brkpt else {
-                                return Ok(None);
-                            };
-
-                            let has_tmp_breakpoints =
-                                ctx.breakpoints.iter().any(|b| b.is_temporary());
-                            if has_tmp_breakpoints {
-                                let temporary_hit = brkpt.is_temporary() && pid == brkpt.pid;
-                                let watchpoint_hit = brkpt.is_wp_companion();
-                                if !temporary_hit && !watchpoint_hit {
-                                    let mut unusual_brkpt = brkpt.clone();
-                                    unusual_brkpt.pid = pid;
-                                    if unusual_brkpt.is_enabled() {
-                                        unusual_brkpt.disable()?;
-                                        while self.single_step(ctx, pid)?.is_some() {}
-                                        unusual_brkpt.enable()?;
-                                    }
-                                    self.tracee_ctl
-                                        .tracee_ensure_mut(pid)
-                                        .set_stop(StopType::Interrupt);
-
-                                    return Ok(None);
-                                }
-                            }
-
-                            self.tracee_ctl
-                                .tracee_ensure_mut(pid)
-                                .set_stop(StopType::Interrupt);
-                            self.group_stop_interrupt(ctx, pid)?;
-
-                            if let BrkptType::WatchpointCompanion(wps) = brkpt.r#type() {
-                                return Ok(Some(StopReason::Watchpoint(
-                                    pid,
-                                    current_pc,
-                                    WatchpointHitType::EndOfScope(wps.clone()),
-                                )));
-                            }
-
-                            Ok(Some(StopReason::Breakpoint(pid, current_pc)))
+                            self.on_breakpoint_hit(ctx, pid, current_pc)
                         }
                         code::TRAP_HWBKPT => {
                             let current_pc = {
@@ -456,14 +610,20 @@ impl Tracer {
                                 tracee.pc()?
                             };
 
+                            let mut state = register::debug::HardwareDebugState::current(pid)?;
+                            let reg = state.dr6.detect_and_flush().expect("should exists");
+                            state.sync(pid)?;
+                            if state.dr7.is_instruction_bp(reg) {
+                                // unlike INT3, a hardware breakpoint stops a thread before
+                                // an instruction is executed, so there is no need to fix the pc
+                                return self.on_breakpoint_hit(ctx, pid, current_pc);
+                            }
+
                             self.tracee_ctl
                                 .tracee_ensure_mut(pid)
                                 .set_stop(StopType::Interrupt);
                             self.group_stop_interrupt(ctx, pid)?;
 
-                            let mut state = register::debug::HardwareDebugState::current(pid)?;
-                            let reg = state.dr6.detect_and_flush().expect("should exists");
-                            state.sync(pid)?;
                             let hit_type = WatchpointHitType::DebugRegister(reg);
                             Ok(Some(StopReason::Watchpoint(pid, current_pc, hit_type)))
                         }
@@ -492,7 +652,14 @@ impl Tracer {
                     }
                 }
             }
-            WaitStatus::Signaled(_, _, _) => Ok(None),
+            WaitStatus::Signaled(pid, signal, _) => {
+                // Thread killed by a signal, exit code of a killed process follows shell convention
+                self.tracee_ctl.remove(pid);
+                if pid == self.tracee_ctl.proc_pid() {
+                    return Ok(Some(StopReason::DebugeeExit(128 + signal.as_raw())));
+                }
+                Ok(None)
+            }
             _ => {
                 warn!("unexpected wait status: {status:?}");
                 Ok(None)
@@ -500,6 +667,74 @@ impl Tracer {
         }
     }
 
+    /// Handle a stop at a breakpoint (software or hardware one).
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`: trace context
+    /// * `pid`: tracee that hits a breakpoint
+    /// * `current_pc`: breakpoint address
+    fn on_breakpoint_hit(
+        &mut self,
+        ctx: TraceContext,
+        pid: Pid,
+        current_pc: RelocatedAddress,
+    ) -> Result<Option<StopReason>, Error> {
+        let mb_hit_brkpt = ctx
+            .breakpoints
+            .iter()
+            .find(|brkpt| brkpt.addr == current_pc);
+        debug_assert!(
+            mb_hit_brkpt.is_some(),
+            "the interrupt caught but the breakpoint was not found"
+        );
+        let Some(&brkpt) = mb_hit_brkpt else {
+            return Ok(None);
+        };
+
+        let has_tmp_breakpoints = ctx.breakpoints.iter().any(|b| b.is_temporary());
+        if has_tmp_breakpoints {
+            let temporary_hit = brkpt.is_temporary_for(pid);
+            let watchpoint_hit = brkpt.is_wp_companion();
+            if !temporary_hit && !watchpoint_hit {
+                let mut unusual_brkpt = brkpt.clone();
+                unusual_brkpt.pid = pid;
+                self.tracee_ctl
+                    .tracee_ensure_mut(pid)
+                    .set_stop(StopType::Interrupt);
+                if unusual_brkpt.is_enabled() {
+                    // other threads must not pass through the breakpoint
+                    // while it is disabled, so stop them for a step time,
+                    // they are continued with this thread at next resume
+                    self.group_stop_interrupt(ctx, pid)?;
+                    unusual_brkpt.disable()?;
+                    while self.single_step(ctx, pid)?.is_some() {}
+                    unusual_brkpt.enable()?;
+                    self.tracee_ctl
+                        .tracee_ensure_mut(pid)
+                        .set_stop(StopType::Interrupt);
+                }
+
+                return Ok(None);
+            }
+        }
+
+        self.tracee_ctl
+            .tracee_ensure_mut(pid)
+            .set_stop(StopType::Interrupt);
+        self.group_stop_interrupt(ctx, pid)?;
+
+        if let BrkptType::WatchpointCompanion(wps) = brkpt.r#type() {
+            return Ok(Some(StopReason::Watchpoint(
+                pid,
+                current_pc,
+                WatchpointHitType::EndOfScope(wps.clone()),
+            )));
+        }
+
+        Ok(Some(StopReason::Breakpoint(pid, current_pc)))
+    }
+
     /// Execute next instruction, then stop with `TRAP_TRACE`.
     ///
     /// # Arguments
@@ -515,23 +750,43 @@ impl Tracer {
         &mut self,
         ctx: TraceContext,
         pid: Pid,
+    ) -> Result<Option<StopReason>, Error> {
+        // steps may be nested (when a breakpoint is stepped over during a step)
+        let outer = self.in_flight_steps.insert(pid);
+        let result = self.single_step_inner(ctx, pid);
+        if outer {
+            self.in_flight_steps.remove(&pid);
+            self.completed_steps.remove(&pid);
+        }
+        result
+    }
+
+    fn single_step_inner(
+        &mut self,
+        ctx: TraceContext,
+        pid: Pid,
     ) -> Result<Option<StopReason>, Error> {
         let tracee = self.tracee_ctl.tracee_ensure(pid);
         let initial_pc = tracee.pc()?;
         tracee.step(None)?;
 
         let reason = loop {
+            let status = if self.completed_steps.remove(&pid) {
+                // trap already caught while handling another event
+                WaitStatus::Stopped(pid, Signal::SIGTRAP)
+            } else {
+                self.tracee_ctl.tracee_ensure(pid).wait_one()?
+            };
             let tracee = self.tracee_ctl.tracee_ensure_mut(pid);
-            let status = tracee.wait_one()?;
-            let info = sys::ptrace::getsiginfo(pid).map_err(Ptrace)?;
+            let sigcode = sys::sigcode(pid)?;
 
             // check that debugee step into an expected trap
             // (breakpoints ignored and are also considered as a trap)
             let in_trap = matches!(status, WaitStatus::Stopped(_, Signal::SIGTRAP))
-                && (info.si_code == code::TRAP_TRACE
-                    || info.si_code == code::TRAP_BRKPT
-                    || info.si_code == code::SI_KERNEL
-                    || info.si_code == code::TRAP_HWBKPT);
+                && (sigcode == code::TRAP_TRACE
+                    || sigcode == code::TRAP_BRKPT
+                    || sigcode == code::SI_KERNEL
+                    || sigcode == code::TRAP_HWBKPT);
             if in_trap {
                 let pc = tracee.pc()?;
                 // check that we aren't on original pc value
@@ -541,7 +796,14 @@ impl Tracer {
                 }
 
                 let mut state = register::debug::HardwareDebugState::current(pid)?;
-                let maybe_dr = state.dr6.detect_and_flush();
+                let mut maybe_dr = None;
+                while let Some(dr) = state.dr6.detect_and_flush() {
+                    // hardware breakpoints are ignored like software ones
+                    if !state.dr7.is_instruction_bp(dr) {
+                        maybe_dr = Some(dr);
+                        break;
+                    }
+                }
                 state.sync(pid)?;
                 if let Some(dr) = maybe_dr {
                     let hit_type = WatchpointHitType::DebugRegister(dr);
@@ -558,10 +820,11 @@ impl Tracer {
             }
 
             let in_trap =
-                matches!(status, WaitStatus::Stopped(_, Signal::SIGTRAP)) && (info.si_code == 5);
+                matches!(status, WaitStatus::Stopped(_, Signal::SIGTRAP)) && (sigcode == 5);
             if in_trap {
                 // if in syscall step to syscall end
-                sys::ptrace::syscall(tracee.pid, None).map_err(Ptrace)?;
+                tracee.invalidate_registers();
+                sys::syscall(tracee.pid, None)?;
                 let syscall_status = tracee.wait_one()?;
                 debug_assert!(matches!(
                     syscall_status,
@@ -576,7 +839,7 @@ impl Tracer {
 
             let is_interrupt = matches!(
                 status,
-                WaitStatus::PtraceEvent(p, SIGSTOP, libc::PTRACE_EVENT_STOP) if pid == p,
+                WaitStatus::PtraceEvent(p, Signal::SIGSTOP, sys::PTRACE_EVENT_STOP) if pid == p,
             );
             if is_interrupt {
                 break None;
@@ -608,8 +871,207 @@ impl Tracer {
                     // expect that tracee will be removed later
                     break None;
                 }
+                Some(StopReason::ThreadCreated(_, _)) => {
+                    unreachable!("thread creation events are queued")
+                }
             }
         };
         Ok(reason)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::debugger::sys::mock::{Mock, Request};
+
+    const PROC_PID: Pid = Pid::from_raw(100);
+    const THREAD_PID: Pid = Pid::from_raw(101);
+
+    #[test]
+    fn test_signal_stop_interrupts_other_threads() {
+        let mock = Mock::new(&[PROC_PID, THREAD_PID]).install();
+        let watchpoints = WatchpointRegistry::default();
+        let ctx = TraceContext::new(&[], &watchpoints);
+        let mut tracer = Tracer::new_external(PROC_PID, &[PROC_PID, THREAD_PID]);
+
+        mock.push_signal(THREAD_PID, Signal::SIGUSR1);
+        let stop = tracer.resume(ctx).unwrap();
+        assert!(matches!(
+            stop,
+            StopReason::SignalStop(THREAD_PID, Signal::SIGUSR1)
+        ));
+        assert!(tracer.tracee_ctl.tracee_iter().all(|t| t.is_stopped()));
+        let requests = mock.requests();
+        assert!(requests.contains(&Request::Interrupt(PROC_PID)));
+        assert!(!requests.contains(&Request::Interrupt(THREAD_PID)));
+
+        // signal is injected at the next resume
+        mock.push_status(WaitStatus::Exited(PROC_PID, 0));
+        let stop = tracer.resume(ctx).unwrap();
+        assert!(matches!(stop, StopReason::DebugeeExit(0)));
+        let requests = mock.requests();
+        assert!(requests.contains(&Request::Cont(THREAD_PID, Some(Signal::SIGUSR1))));
+        assert!(requests.contains(&Request::Cont(PROC_PID, None)));
+    }
+
+    #[test]
+    fn test_quiet_signal_is_injected_without_stop() {
+        let mock = Mock::new(&[PROC_PID]).install();
+        let watchpoints = WatchpointRegistry::default();
+        let ctx = TraceContext::new(&[], &watchpoints);
+        let mut tracer = Tracer::new(PROC_PID);
+
+        mock.push_signal(PROC_PID, Signal::SIGCHLD);
+        mock.push_status(WaitStatus::Exited(PROC_PID, 3));
+        let stop = tracer.resume(ctx).unwrap();
+        assert!(matches!(stop, StopReason::DebugeeExit(3)));
+        assert_eq!(
+            mock.requests(),
+            vec![
+                Request::Cont(PROC_PID, None),
+                Request::Cont(PROC_PID, Some(Signal::SIGCHLD)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_thread_created() {
+        let mock = Mock::new(&[PROC_PID]).install();
+        let watchpoints = WatchpointRegistry::default();
+        let ctx = TraceContext::new(&[], &watchpoints);
+        let mut tracer = Tracer::new(PROC_PID);
+
+        mock.push_event(
+            PROC_PID,
+            sys::PTRACE_EVENT_CLONE,
+            THREAD_PID.as_raw() as i64,
+        );
+        mock.add_thread(THREAD_PID);
+        mock.push_status(WaitStatus::PtraceEvent(
+            THREAD_PID,
+            Signal::SIGSTOP,
+            sys::PTRACE_EVENT_STOP,
+        ));
+        let stop = tracer.resume(ctx).unwrap();
+        assert!(matches!(
+            stop,
+            StopReason::ThreadCreated(PROC_PID, THREAD_PID)
+        ));
+        assert!(tracer.tracee_ctl.tracee_iter().all(|t| t.is_stopped()));
+        assert_eq!(tracer.tracee_ctl.tracee_iter().count(), 2);
+    }
+
+    #[test]
+    fn test_debugee_exit() {
+        let mock = Mock::new(&[PROC_PID]).install();
+        let watchpoints = WatchpointRegistry::default();
+        let ctx = TraceContext::new(&[], &watchpoints);
+
+        let mut tracer = Tracer::new(PROC_PID);
+        mock.push_status(WaitStatus::Signaled(PROC_PID, Signal::SIGKILL, false));
+        let stop = tracer.resume(ctx).unwrap();
+        assert!(matches!(stop, StopReason::DebugeeExit(137)));
+
+        // there is no more children
+        let mut tracer = Tracer::new(PROC_PID);
+        let stop = tracer.resume(ctx).unwrap();
+        assert!(matches!(stop, StopReason::NoSuchProcess(PROC_PID)));
+    }
+
+    #[test]
+    fn test_group_stop_removes_exited_threads() {
+        // thread exits without reporting a status
+        let mock = Mock::new(&[PROC_PID]).install();
+        let watchpoints = WatchpointRegistry::default();
+        let ctx = TraceContext::new(&[], &watchpoints);
+        let mut tracer = Tracer::new_external(PROC_PID, &[PROC_PID, THREAD_PID]);
+
+        mock.push_signal(PROC_PID, Signal::SIGUSR1);
+        let stop = tracer.resume(ctx).unwrap();
+        assert!(matches!(
+            stop,
+            StopReason::SignalStop(PROC_PID, Signal::SIGUSR1)
+        ));
+        assert!(tracer.tracee_ctl.tracee(THREAD_PID).is_none());
+    }
+
+    #[test]
+    fn test_breakpoint_hit_during_group_stop_is_pending() {
+        let mock = Mock::new(&[PROC_PID, THREAD_PID]).install();
+        let brkpt = Breakpoint::new("", RelocatedAddress::from(0x1000_u64), PROC_PID, None);
+        let breakpoints = [&brkpt];
+        let watchpoints = WatchpointRegistry::default();
+        let ctx = TraceContext::new(&breakpoints, &watchpoints);
+        let mut tracer = Tracer::new_external(PROC_PID, &[PROC_PID, THREAD_PID]);
+
+        // second thread hits a breakpoint before it is interrupted
+        mock.set_pc(THREAD_PID, 0x1001);
+        mock.push_signal(PROC_PID, Signal::SIGUSR1);
+        mock.push_trap(THREAD_PID, code::TRAP_BRKPT);
+        let stop = tracer.resume(ctx).unwrap();
+        assert!(matches!(
+            stop,
+            StopReason::SignalStop(PROC_PID, Signal::SIGUSR1)
+        ));
+        assert_eq!(mock.pc(THREAD_PID), 0x1000);
+
+        let requests_before = mock.requests().len();
+        let stop = tracer.resume(ctx).unwrap();
+        assert!(matches!(stop, StopReason::Breakpoint(THREAD_PID, addr) if addr == brkpt.addr));
+        // debugee isn't resumed
+        assert_eq!(mock.requests().len(), requests_before);
+    }
+
+    #[test]
+    fn test_single_step() {
+        let mock = Mock::new(&[PROC_PID]).install();
+        let watchpoints = WatchpointRegistry::default();
+        let ctx = TraceContext::new(&[], &watchpoints);
+        let mut tracer = Tracer::new(PROC_PID);
+
+        mock.set_pc(PROC_PID, 0x1000);
+        let stop = tracer.single_step(ctx, PROC_PID).unwrap();
+        assert!(stop.is_none());
+        assert_eq!(mock.pc(PROC_PID), 0x1001);
+        assert_eq!(mock.requests(), vec![Request::Step(PROC_PID, None)]);
+    }
+
+    #[test]
+    fn test_trace_trap_without_step_is_injected() {
+        let mock = Mock::new(&[PROC_PID]).install();
+        let watchpoints = WatchpointRegistry::default();
+        let ctx = TraceContext::new(&[], &watchpoints);
+        let mut tracer = Tracer::new(PROC_PID);
+
+        mock.push_trap(PROC_PID, code::TRAP_TRACE);
+        mock.push_status(WaitStatus::Exited(PROC_PID, 0));
+        let stop = tracer.resume(ctx).unwrap();
+        assert!(matches!(stop, StopReason::DebugeeExit(0)));
+        assert_eq!(
+            mock.requests(),
+            vec![
+                Request::Cont(PROC_PID, None),
+                Request::Cont(PROC_PID, Some(Signal::SIGTRAP)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_thread_not_responding_to_interrupt() {
+        let mock = Mock::new(&[PROC_PID, THREAD_PID]).install();
+        mock.set_uninterruptible(THREAD_PID);
+        let watchpoints = WatchpointRegistry::default();
+        let ctx = TraceContext::new(&[], &watchpoints);
+        let mut tracer = Tracer::new_external(PROC_PID, &[PROC_PID, THREAD_PID]);
+
+        mock.push_signal(PROC_PID, Signal::SIGUSR1);
+        let stop = tracer.resume(ctx).unwrap();
+        assert!(matches!(
+            stop,
+            StopReason::SignalStop(PROC_PID, Signal::SIGUSR1)
+        ));
+        assert!(mock.requests().contains(&Request::Interrupt(THREAD_PID)));
+        assert_eq!(tracer.not_stopped_tracees(), vec![THREAD_PID]);
+    }
+}
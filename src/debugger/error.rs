@@ -1,9 +1,11 @@
 use crate::debugger::address::GlobalAddress;
+use crate::debugger::breakpoint::LineDiagnostic;
 use crate::debugger::debugee::dwarf::unit::DieRef;
 use crate::debugger::debugee::RendezvousError;
 use crate::debugger::variable::ParsingError;
-use gimli::UnitOffset;
-use nix::unistd::Pid;
+use crate::debugger::Pid;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
 use std::str::Utf8Error;
 use std::string::FromUtf8Error;
 
@@ -24,14 +26,26 @@ pub enum Error {
     // --------------------------------- debugger entity not found----------------------------------
     #[error("no debug information for {0}")]
     NoDebugInformation(&'static str),
-    #[error("unknown register {0:?}")]
-    RegisterNotFound(gimli::Register),
+    #[error("unknown register, DWARF register number: {0}")]
+    RegisterNotFound(u16),
     #[error("unknown register {0:?}")]
     RegisterNameNotFound(String),
+    #[error("register {0} is not supported by the processor")]
+    VectorRegisterUnavailable(String),
+    #[error("value of register {0} is unknown at the selected frame")]
+    RegisterNotRestored(String),
+    #[error("history value ${0} not found")]
+    HistoryValueNotFound(usize),
     #[error("source place not found at address {0}")]
     PlaceNotFound(GlobalAddress),
     #[error("there are no suitable places for this request")]
     NoSuitablePlace,
+    #[error("file `{0}` is ambiguous, candidates are:\n{}", .1.join("\n"))]
+    AmbiguousFile(String, Vec<String>),
+    #[error("there is no statement at column {0}, statements are at columns: {}", .1.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))]
+    ColumnNotFound(u64, Vec<u64>),
+    #[error("no code at line {0}, {1}")]
+    NoCodeAtLine(u64, LineDiagnostic),
     #[error("unit not found at address {0}")]
     UnitNotFound(GlobalAddress),
     #[error("function not found at address {0}")]
@@ -59,11 +73,11 @@ pub enum Error {
 
     // --------------------------------- syscall errors --------------------------------------------
     #[error("waitpid syscall error: {0}")]
-    Waitpid(nix::Error),
+    Waitpid(SyscallError),
     #[error("ptrace syscall error: {0}")]
-    Ptrace(nix::Error),
+    Ptrace(SyscallError),
     #[error("{0} syscall error: {1}")]
-    Syscall(&'static str, nix::Error),
+    Syscall(&'static str, SyscallError),
     #[error("multiple syscall errors {0:?}")]
     MultipleErrors(Vec<Self>),
 
@@ -82,22 +96,58 @@ pub enum Error {
     WatchpointLimitReached,
     #[error("memory location observed by another watchpoint")]
     AddressAlreadyObserved,
+    #[error("there are no free debug registers for a hardware breakpoint (4 registers are shared by hardware breakpoints and watchpoints), try to remove unused")]
+    HardwareBreakpointLimitReached,
     #[error("unknown expression scope")]
     UnknownScope,
     #[error("variable frame is unavailable")]
     VarFrameNotFound,
 
+    // --------------------------------- tracepoint errors -----------------------------------------
+    #[error("invalid format string: {0}")]
+    FormatString(&'static str),
+    #[error("format string requires {0} arguments, but {1} expressions given")]
+    FormatArgumentsMismatch(usize, usize),
+
+    // --------------------------------- jump errors -----------------------------------------------
+    #[error("target line is outside of the current function `{}`", .0.as_deref().unwrap_or("unknown"))]
+    JumpOutsideFunction(Option<String>),
+
+    // --------------------------------- return errors ---------------------------------------------
+    #[error("value of type `{0}` can't be returned from a function")]
+    ReturnTypeUnsupported(String),
+    #[error("value {0} doesn't fit into return type `{1}`")]
+    ReturnValueOutOfRange(String, String),
+
+    // --------------------------------- cast errors -----------------------------------------------
+    #[error("size of type `{0}` is unknown, value can't be reinterpreted")]
+    CastUnknownSize(String),
+    #[error(
+        "value of type `{0}` ({1} bytes) is too small to be reinterpreted as `{2}` ({3} bytes)"
+    )]
+    CastToLargerType(String, usize, String, usize),
+
+    // --------------------------------- memory view errors ----------------------------------------
+    #[error("variable or argument not found")]
+    MemorySubjectNotFound,
+    #[error("expression value is not located in memory")]
+    MemorySubjectNoAddress,
+
+    // --------------------------------- profiler errors -------------------------------------------
+    #[error("profiling is not started")]
+    ProfilingNotStarted,
+
     // --------------------------------- parsing errors --------------------------------------------
     #[error("dwarf file parsing error: {0}")]
-    DwarfParsing(#[from] gimli::Error),
+    DwarfParsing(DwarfError),
     #[error("invalid debug-id note format")]
     DebugIDFormat,
     #[error("object file parsing error: {0}")]
-    ObjParsing(#[from] object::Error),
+    ObjParsing(ObjectError),
     #[error(transparent)]
     VariableParsing(#[from] ParsingError),
-    #[error("function specification ({0:?}) reference to unseen declaration")]
-    InvalidSpecification(UnitOffset),
+    #[error("function specification (unit offset {0:#x}) reference to unseen declaration")]
+    InvalidSpecification(usize),
 
     // --------------------------------- unwind errors ---------------------------------------------
     #[error("unwind: no unwind context")]
@@ -130,6 +180,8 @@ pub enum Error {
     NoThreadDB,
     #[error("libthread_db: {0}")]
     ThreadDB(#[from] thread_db::ThreadDbError),
+    #[error("TLS block is not allocated for thread {0}")]
+    TlsBlockNotAllocated(Pid),
 
     // --------------------------------- linker errors ---------------------------------------------
     #[error(transparent)]
@@ -140,12 +192,16 @@ pub enum Error {
     ProcessExit(i32),
     #[error("program is not being started")]
     ProcessNotStarted,
+    #[error("open {} as a program stdin: {1}", .0.display())]
+    StdinRedirect(PathBuf, std::io::Error),
 
     // --------------------------------- rust toolchain errors -------------------------------------
     #[error("default toolchain not found")]
     DefaultToolchainNotFound,
     #[error("unrecognized rustup output")]
     UnrecognizedRustupOut,
+    #[error("unrecognized rustc output")]
+    UnrecognizedRustcOut,
 
     // --------------------------------- disasm ----------------------------------------------------
     #[error("install disassembler: {0}")]
@@ -163,7 +219,89 @@ pub enum Error {
     #[error("process pid {0} not found")]
     AttachedProcessNotFound(Pid),
     #[error("attach a running process: {0}")]
-    Attach(nix::Error),
+    Attach(SyscallError),
+
+    // --------------------------------- inferior errors -------------------------------------------
+    #[error("inferior number {0} not found")]
+    InferiorNotFound(u32),
+    #[error("inferior switch is not allowed while profiling")]
+    InferiorSwitchWhileProfiling,
+    #[error("add inferior: {0}")]
+    InferiorAttach(Box<Self>),
+}
+
+impl From<gimli::Error> for Error {
+    fn from(e: gimli::Error) -> Self {
+        Error::DwarfParsing(DwarfError(e))
+    }
+}
+
+impl From<object::Error> for Error {
+    fn from(e: object::Error) -> Self {
+        Error::ObjParsing(ObjectError(e))
+    }
+}
+
+/// Errors of ptrace requests (most of the system calls of a debugger).
+impl From<nix::Error> for Error {
+    fn from(e: nix::Error) -> Self {
+        Error::Ptrace(SyscallError(e))
+    }
+}
+
+impl From<SyscallError> for Error {
+    fn from(e: SyscallError) -> Self {
+        Error::Ptrace(e)
+    }
+}
+
+/// Error of a system call (`ptrace`, `waitpid`, etc.).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyscallError(nix::Error);
+
+impl SyscallError {
+    /// Return OS error number (errno).
+    pub fn raw_os_error(&self) -> i32 {
+        self.0 as i32
+    }
+
+    pub(crate) fn errno(&self) -> nix::Error {
+        self.0
+    }
+}
+
+impl From<nix::Error> for SyscallError {
+    fn from(e: nix::Error) -> Self {
+        Self(e)
+    }
+}
+
+impl Display for SyscallError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::error::Error for SyscallError {}
+
+/// Error of a DWARF debug information parsing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DwarfError(gimli::Error);
+
+impl Display for DwarfError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Error of an object (ELF) file parsing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ObjectError(object::Error);
+
+impl Display for ObjectError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
 }
 
 impl Error {
@@ -178,8 +316,14 @@ impl Error {
             Error::NoDebugInformation(_) => false,
             Error::RegisterNotFound(_) => false,
             Error::RegisterNameNotFound(_) => false,
+            Error::VectorRegisterUnavailable(_) => false,
+            Error::RegisterNotRestored(_) => false,
+            Error::HistoryValueNotFound(_) => false,
             Error::PlaceNotFound(_) => false,
             Error::NoSuitablePlace => false,
+            Error::AmbiguousFile(_, _) => false,
+            Error::ColumnNotFound(_, _) => false,
+            Error::NoCodeAtLine(_, _) => false,
             Error::UnitNotFound(_) => false,
             Error::FunctionNotFound(_) => false,
             Error::TypeNotFound => false,
@@ -207,11 +351,14 @@ impl Error {
             Error::NoDieType => false,
             Error::ImplicitPointer => false,
             Error::ThreadDB(_) => false,
+            Error::TlsBlockNotAllocated(_) => false,
             Error::Rendezvous(_) => false,
             Error::ProcessExit(_) => false,
             Error::ProcessNotStarted => false,
+            Error::StdinRedirect(_, _) => false,
             Error::DefaultToolchainNotFound => false,
             Error::UnrecognizedRustupOut => false,
+            Error::UnrecognizedRustcOut => false,
             Error::Hook(_) => false,
             Error::SectionNotFound(_) => false,
             Error::DisAsm(_) => false,
@@ -224,8 +371,22 @@ impl Error {
             Error::WatchpointLimitReached => false,
             Error::WatchSubjectNotFound => false,
             Error::AddressAlreadyObserved => false,
+            Error::HardwareBreakpointLimitReached => false,
             Error::UnknownScope => false,
             Error::VarFrameNotFound => false,
+            Error::FormatString(_) => false,
+            Error::FormatArgumentsMismatch(_, _) => false,
+            Error::JumpOutsideFunction(_) => false,
+            Error::ReturnTypeUnsupported(_) => false,
+            Error::ReturnValueOutOfRange(_, _) => false,
+            Error::CastUnknownSize(_) => false,
+            Error::CastToLargerType(_, _, _, _) => false,
+            Error::MemorySubjectNotFound => false,
+            Error::MemorySubjectNoAddress => false,
+            Error::ProfilingNotStarted => false,
+            Error::InferiorNotFound(_) => false,
+            Error::InferiorSwitchWhileProfiling => false,
+            Error::InferiorAttach(_) => false,
 
             // currently fatal errors
             Error::DwarfParsing(_) => true,
@@ -0,0 +1,368 @@
+//! C++ exceptions support for mixed Rust/C++ programs.
+//!
+//! Catchpoints are breakpoints at C++ runtime functions (`__cxa_throw` and
+//! `__cxa_begin_catch`, see [`Debugger::set_catchpoint`]). When a catchpoint is hit,
+//! a type of exception is read from its `std::type_info`
+//! (see [`crate::debugger::EventHook::on_exception`]).
+
+use crate::debugger::address::Address;
+use crate::debugger::breakpoint::{Breakpoint, CatchEvent};
+use crate::debugger::error::Error::{NoSuitablePlace, ProcessExit, UnknownAddress};
+use crate::debugger::register::{Register, RegisterMap};
+use crate::debugger::Pid;
+use crate::debugger::{sys, Debugger, Error};
+use crate::disable_when_not_stared;
+
+/// Exception class of exceptions thrown by `__cxa_throw` ("GNUCC++\0").
+const GNU_CPP_EXCEPTION_CLASS: u64 = u64::from_be_bytes(*b"GNUCC++\0");
+/// Exception class of rethrown exceptions (`std::rethrow_exception`), such exceptions
+/// refer to a primary exception object.
+const GNU_CPP_DEPENDENT_EXCEPTION_CLASS: u64 = u64::from_be_bytes(*b"GNUCC++\x01");
+/// Offset of `_Unwind_Exception` (unwind header) in `__cxa_exception`,
+/// a pointer to a `std::type_info` is the first field of `__cxa_exception`.
+const UNWIND_HEADER_OFFSET: u64 = 80;
+/// Size of `__cxa_exception`, it is placed right before a thrown object.
+const CXA_EXCEPTION_SIZE: u64 = 112;
+/// Maximum length of a type name read from a `std::type_info`.
+const MAX_TYPE_NAME_LEN: usize = 1024;
+
+fn read_u64(pid: Pid, addr: u64) -> Result<u64, Error> {
+    Ok(sys::read(pid, addr as usize)? as u64)
+}
+
+fn read_c_str(pid: Pid, mut addr: u64) -> Result<String, Error> {
+    let mut buff = vec![];
+    while buff.len() < MAX_TYPE_NAME_LEN {
+        for b in read_u64(pid, addr)?.to_ne_bytes() {
+            if b == 0 {
+                return Ok(String::from_utf8_lossy(&buff).to_string());
+            }
+            buff.push(b);
+        }
+        addr = addr.checked_add(8).ok_or(UnknownAddress)?;
+    }
+    Ok(String::from_utf8_lossy(&buff).to_string())
+}
+
+/// Return an address of `std::type_info` of exception processed by C++ runtime function.
+/// Thread must be stopped at the first instruction of `__cxa_throw` or `__cxa_begin_catch`.
+///
+/// # Arguments
+///
+/// * `pid`: thread id
+/// * `event`: exception handling event
+fn type_info_addr(pid: Pid, event: CatchEvent) -> Result<Option<u64>, Error> {
+    let registers = RegisterMap::current(pid)?;
+    match event {
+        // void __cxa_throw(void* thrown_exception, std::type_info* tinfo, void (*dest)(void*))
+        CatchEvent::Throw => Ok(Some(registers.value(Register::Rsi))),
+        // void* __cxa_begin_catch(void* exception), argument is an unwind header
+        CatchEvent::Catch => {
+            let unwind_header = registers.value(Register::Rdi);
+            let header = unwind_header
+                .checked_sub(UNWIND_HEADER_OFFSET)
+                .ok_or(UnknownAddress)?;
+            match read_u64(pid, unwind_header)? {
+                GNU_CPP_EXCEPTION_CLASS => Ok(Some(read_u64(pid, header)?)),
+                GNU_CPP_DEPENDENT_EXCEPTION_CLASS => {
+                    let primary = read_u64(pid, header)?;
+                    let primary_header = primary
+                        .checked_sub(CXA_EXCEPTION_SIZE)
+                        .ok_or(UnknownAddress)?;
+                    Ok(Some(read_u64(pid, primary_header)?))
+                }
+                // foreign exception (not thrown by C++ code)
+                _ => Ok(None),
+            }
+        }
+    }
+}
+
+/// Return a demangled type name of exception processed by C++ runtime function,
+/// `None` if exception is foreign (not thrown by C++ code).
+///
+/// # Arguments
+///
+/// * `pid`: thread id
+/// * `event`: exception handling event
+pub(super) fn exception_type(pid: Pid, event: CatchEvent) -> Result<Option<String>, Error> {
+    let Some(type_info) = type_info_addr(pid, event)? else {
+        return Ok(None);
+    };
+    // std::type_info is a vtable pointer and a pointer to a mangled name
+    let name_ptr = read_u64(pid, type_info.checked_add(8).ok_or(UnknownAddress)?)?;
+    let name = read_c_str(pid, name_ptr)?;
+    Ok(Some(demangle_type_name(&name)))
+}
+
+/// Demangle a type name stored in `std::type_info` (Itanium C++ ABI mangling without `_Z`
+/// prefix). Only a subset of grammar is supported (builtin types, nested and template names,
+/// pointers, references and cv-qualifiers), name returns as is if it can't be demangled.
+///
+/// # Arguments
+///
+/// * `mangled`: mangled type name
+pub fn demangle_type_name(mangled: &str) -> String {
+    // type_info names of types with internal linkage starts with '*'
+    let name = mangled.strip_prefix('*').unwrap_or(mangled);
+    let mut demangler = TypeNameDemangler {
+        input: name.as_bytes(),
+        pos: 0,
+    };
+    match demangler.parse_type() {
+        Some(demangled) if demangler.pos == name.len() => demangled,
+        _ => name.to_string(),
+    }
+}
+
+struct TypeNameDemangler<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> TypeNameDemangler<'a> {
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).copied()
+    }
+
+    fn consume(&mut self, prefix: &str) -> bool {
+        if self.input[self.pos..].starts_with(prefix.as_bytes()) {
+            self.pos += prefix.len();
+            return true;
+        }
+        false
+    }
+
+    fn parse_type(&mut self) -> Option<String> {
+        if self.consume("P") {
+            return Some(format!("{}*", self.parse_type()?));
+        }
+        if self.consume("R") {
+            return Some(format!("{}&", self.parse_type()?));
+        }
+        if self.consume("O") {
+            return Some(format!("{}&&", self.parse_type()?));
+        }
+        if self.consume("K") {
+            return Some(format!("{} const", self.parse_type()?));
+        }
+        if self.consume("V") {
+            return Some(format!("{} volatile", self.parse_type()?));
+        }
+        if let Some(builtin) = self.parse_builtin() {
+            return Some(builtin.to_string());
+        }
+        self.parse_name()
+    }
+
+    fn parse_builtin(&mut self) -> Option<&'static str> {
+        let builtin = match self.peek()? {
+            b'v' => "void",
+            b'b' => "bool",
+            b'c' => "char",
+            b'a' => "signed char",
+            b'h' => "unsigned char",
+            b's' => "short",
+            b't' => "unsigned short",
+            b'i' => "int",
+            b'j' => "unsigned int",
+            b'l' => "long",
+            b'm' => "unsigned long",
+            b'x' => "long long",
+            b'y' => "unsigned long long",
+            b'n' => "__int128",
+            b'o' => "unsigned __int128",
+            b'f' => "float",
+            b'd' => "double",
+            b'e' => "long double",
+            b'w' => "wchar_t",
+            _ => return None,
+        };
+        self.pos += 1;
+        Some(builtin)
+    }
+
+    fn parse_name(&mut self) -> Option<String> {
+        if self.consume("N") {
+            let mut components = vec![];
+            while !self.consume("E") {
+                if components.is_empty() && self.consume("St") {
+                    components.push("std".to_string());
+                    continue;
+                }
+                let mut component = self.parse_source_name()?;
+                if self.peek() == Some(b'I') {
+                    component += &self.parse_template_args()?;
+                }
+                components.push(component);
+            }
+            return Some(components.join("::"));
+        }
+
+        let mut name = if self.consume("St") {
+            format!("std::{}", self.parse_source_name()?)
+        } else if let Some(abbreviation) = self.parse_std_abbreviation() {
+            abbreviation.to_string()
+        } else {
+            self.parse_source_name()?
+        };
+        if self.peek() == Some(b'I') {
+            name += &self.parse_template_args()?;
+        }
+        Some(name)
+    }
+
+    fn parse_std_abbreviation(&mut self) -> Option<&'static str> {
+        let abbreviations = [
+            ("Sa", "std::allocator"),
+            ("Sb", "std::basic_string"),
+            (
+                "Ss",
+                "std::basic_string<char, std::char_traits<char>, std::allocator<char> >",
+            ),
+            ("Si", "std::istream"),
+            ("So", "std::ostream"),
+            ("Sd", "std::iostream"),
+        ];
+        abbreviations
+            .into_iter()
+            .find_map(|(prefix, name)| self.consume(prefix).then_some(name))
+    }
+
+    fn parse_source_name(&mut self) -> Option<String> {
+        let start = self.pos;
+        while self.peek()?.is_ascii_digit() {
+            self.pos += 1;
+        }
+        let len: usize = std::str::from_utf8(&self.input[start..self.pos])
+            .ok()?
+            .parse()
+            .ok()?;
+        let end = self.pos.checked_add(len)?;
+        let name = self.input.get(self.pos..end)?;
+        self.pos += len;
+        String::from_utf8(name.to_vec()).ok()
+    }
+
+    fn parse_template_args(&mut self) -> Option<String> {
+        self.consume("I");
+        let mut args = vec![];
+        while !self.consume("E") {
+            args.push(self.parse_type()?);
+        }
+        Some(format!("<{}>", args.join(", ")))
+    }
+}
+
+impl Debugger {
+    /// Continue debugee execution until a thrown C++ exception is caught, stop at the start
+    /// of exception handler (`catch` block). Debugee stops early at user defined breakpoints.
+    ///
+    /// # Errors
+    ///
+    /// Return [`NoSuitablePlace`] if C++ runtime is not loaded.
+    pub fn unwind_exception(&mut self) -> Result<(), Error> {
+        disable_when_not_stared!(self);
+        self.expl_ctx_restore_frame()?;
+
+        let pid = self.exploration_ctx().pid_on_focus();
+        let mut catch_addresses = vec![];
+        let mut temporary = vec![];
+        for (dwarf, addr) in self.search_function_symbol(CatchEvent::Catch.function_name()) {
+            let addr = addr.relocate_to_segment(&self.inferior().debugee, dwarf)?;
+            if self.inferior().breakpoints.get_enabled(addr).is_none() {
+                temporary.push(Breakpoint::new_temporary(dwarf.pathname(), addr, pid));
+            }
+            catch_addresses.push(addr);
+        }
+        if catch_addresses.is_empty() {
+            return Err(NoSuitablePlace);
+        }
+
+        let temporary: Vec<_> = temporary
+            .into_iter()
+            .map(|brkpt| {
+                let addr = brkpt.addr;
+                self.inferior_mut()
+                    .breakpoints
+                    .add_and_enable(brkpt)
+                    .map(|_| addr)
+            })
+            .collect::<Result<_, _>>()?;
+        self.continue_execution()?;
+        for addr in temporary {
+            self.remove_breakpoint(Address::Relocated(addr))?;
+        }
+
+        if self.inferior().debugee.is_exited() {
+            // todo add exit code here
+            return Err(ProcessExit(0));
+        }
+        self.expl_ctx_update_location()?;
+
+        // stopped at `__cxa_begin_catch`, a handler is a caller of it
+        if catch_addresses.contains(&self.exploration_ctx().location().pc) {
+            self.step_out_frame()?;
+        }
+
+        self.rebind_watchpoints()?;
+        self.execute_on_step_hook()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_demangle_type_name() {
+        struct TestCase {
+            mangled: &'static str,
+            expected: &'static str,
+        }
+        let test_cases = [
+            TestCase {
+                mangled: "i",
+                expected: "int",
+            },
+            TestCase {
+                mangled: "PKc",
+                expected: "char const*",
+            },
+            TestCase {
+                mangled: "St13runtime_error",
+                expected: "std::runtime_error",
+            },
+            TestCase {
+                mangled: "N3app6errors10ParseErrorE",
+                expected: "app::errors::ParseError",
+            },
+            TestCase {
+                mangled: "NSt7__cxx1112basic_stringIcSt11char_traitsIcESaIcEEE",
+                expected:
+                    "std::__cxx11::basic_string<char, std::char_traits<char>, std::allocator<char>>",
+            },
+            TestCase {
+                mangled: "*N12_GLOBAL__N_15LocalE",
+                expected: "_GLOBAL__N_1::Local",
+            },
+            TestCase {
+                mangled: "N99999999999999999999999E",
+                expected: "N99999999999999999999999E",
+            },
+            TestCase {
+                mangled: "18446744073709551615a",
+                expected: "18446744073709551615a",
+            },
+            TestCase {
+                mangled: "Dv4_f",
+                expected: "Dv4_f",
+            },
+        ];
+
+        for tc in test_cases {
+            assert_eq!(demangle_type_name(tc.mangled), tc.expected);
+        }
+    }
+}
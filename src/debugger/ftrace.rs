@@ -0,0 +1,266 @@
+//! Function tracing. Non-stopping breakpoints are planted at the start (after prolog)
+//! of every function which full name matches a glob pattern. When a traced function is called,
+//! its arguments are reported and a non-stopping breakpoint is planted at the return address,
+//! so the function return value is reported too
+//! (see [`crate::debugger::EventHook::on_ftrace_enter`] and
+//! [`crate::debugger::EventHook::on_ftrace_return`]).
+
+use crate::debugger::address::{Address, RelocatedAddress};
+use crate::debugger::debugee::dwarf::DwarfUnwinder;
+use crate::debugger::debugee::Location;
+use crate::debugger::error::Error::NoSuitablePlace;
+use crate::debugger::register::Register;
+use crate::debugger::variable::select::{VariableSelector, DQE};
+use crate::debugger::Pid;
+use crate::debugger::{Debugger, Error};
+use crate::weak_error;
+use regex::Regex;
+use std::rc::Rc;
+
+/// A call of a traced function that is not returned yet.
+struct TracedCall {
+    function: Rc<str>,
+    /// Location at function start.
+    callee: Location,
+    ret_addr: RelocatedAddress,
+    /// Stack pointer value after return, distinguishes recursive calls.
+    sp: u64,
+}
+
+#[derive(Default)]
+pub struct FunctionTraceRegistry {
+    /// Glob pattern for names of traced functions, `None` if tracing is not active.
+    pattern: Option<String>,
+    /// Addresses of planted breakpoints (function starts and return addresses).
+    planted: Vec<RelocatedAddress>,
+    /// Calls of traced functions that are not returned yet.
+    calls: Vec<TracedCall>,
+}
+
+impl FunctionTraceRegistry {
+    /// Forget calls of a thread which frames are already popped from the stack
+    /// (by unwinding or a long jump), these calls never return.
+    fn drop_popped_calls(&mut self, pid: Pid, sp: u64) {
+        self.calls
+            .retain(|call| call.callee.pid != pid || call.sp > sp);
+    }
+
+    /// Return number of active traced calls in a thread.
+    fn depth(&self, pid: Pid) -> usize {
+        self.calls
+            .iter()
+            .filter(|call| call.callee.pid == pid)
+            .count()
+    }
+}
+
+/// Convert a glob pattern (`*` - any sequence of characters, `?` - any character)
+/// into an anchored regular expression.
+fn glob_to_regex(pattern: &str) -> Result<Regex, Error> {
+    let regex = pattern
+        .split('*')
+        .map(|part| {
+            part.split('?')
+                .map(regex::escape)
+                .collect::<Vec<_>>()
+                .join(".")
+        })
+        .collect::<Vec<_>>()
+        .join(".*");
+    Ok(Regex::new(&format!("^{regex}$"))?)
+}
+
+impl Debugger {
+    /// Start tracing of all functions which full names match a glob pattern.
+    /// Previously started tracing is stopped. If debugee is not started yet,
+    /// tracing will be started at debugee start. Return number of traced functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern`: glob pattern for function full names (like `myapp::parser::*`)
+    ///
+    /// # Errors
+    ///
+    /// Return [`NoSuitablePlace`] if there are no functions matching a pattern.
+    pub fn start_ftrace(&mut self, pattern: &str) -> Result<usize, Error> {
+        let regex = glob_to_regex(pattern)?;
+        self.stop_ftrace()?;
+
+        let count = if self.is_in_progress() {
+            self.install_ftrace_inner(&regex)?
+        } else {
+            let mut functions = vec![];
+            for dwarf in self.inferior().debugee.debug_info_all() {
+                if dwarf.has_debug_info() {
+                    functions.extend(dwarf.search_places_for_fn_regex(&regex)?);
+                }
+            }
+            functions.len()
+        };
+        if count == 0 {
+            return Err(NoSuitablePlace);
+        }
+
+        self.inferior_mut().ftrace.pattern = Some(pattern.to_string());
+        Ok(count)
+    }
+
+    /// Stop function tracing and remove all planted breakpoints.
+    pub fn stop_ftrace(&mut self) -> Result<(), Error> {
+        self.inferior_mut().ftrace.pattern = None;
+        self.inferior_mut().ftrace.calls.clear();
+        let planted = std::mem::take(&mut self.inferior_mut().ftrace.planted);
+        if self.is_in_progress() {
+            for addr in planted {
+                self.inferior_mut()
+                    .breakpoints
+                    .remove_by_addr(Address::Relocated(addr))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Return glob pattern of traced functions, `None` if tracing is not active.
+    pub fn ftrace_pattern(&self) -> Option<&str> {
+        self.inferior().ftrace.pattern.as_deref()
+    }
+
+    /// Plant breakpoints at traced functions, called at debugee start.
+    pub(super) fn install_ftrace(&mut self) {
+        // breakpoints of a previous debugee run are already removed
+        self.inferior_mut().ftrace.planted.clear();
+        self.inferior_mut().ftrace.calls.clear();
+        let Some(pattern) = self.inferior().ftrace.pattern.clone() else {
+            return;
+        };
+        if let Some(regex) = weak_error!(glob_to_regex(&pattern)) {
+            weak_error!(self.install_ftrace_inner(&regex));
+        }
+    }
+
+    /// Plant breakpoints at start of all functions matching a regular expression.
+    /// Functions with an existing breakpoint at the same address are not traced.
+    /// Return number of traced functions.
+    fn install_ftrace_inner(&mut self, regex: &Regex) -> Result<usize, Error> {
+        let mut functions = vec![];
+        for dwarf in self.inferior().debugee.debug_info_all() {
+            if !dwarf.has_debug_info() {
+                continue;
+            }
+            for func in dwarf.search_functions_by_regex(regex)? {
+                let Some(name) = func.full_name() else {
+                    continue;
+                };
+                let Some(place) = weak_error!(func.prolog_end_place()) else {
+                    continue;
+                };
+                let addr = place
+                    .address
+                    .relocate_to_segment(&self.inferior().debugee, dwarf)?;
+                functions.push((Rc::<str>::from(name), addr));
+            }
+        }
+        functions.sort_unstable_by_key(|(_, addr)| *addr);
+        functions.dedup_by_key(|(_, addr)| *addr);
+
+        let mut count = 0;
+        for (name, addr) in functions {
+            if self.inferior().breakpoints.get_enabled(addr).is_some() {
+                continue;
+            }
+            let callback = move |dbg: &mut Debugger| {
+                weak_error!(dbg.on_traced_call(name.clone()));
+            };
+            self.set_transparent_breakpoint_at_addr(addr, Rc::new(callback))?;
+            self.inferior_mut().ftrace.planted.push(addr);
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Report a call of traced function and plant a breakpoint at the return address.
+    fn on_traced_call(&mut self, function: Rc<str>) -> Result<(), Error> {
+        let ctx = self.exploration_ctx();
+        let callee = ctx.location();
+        let caller_registers =
+            DwarfUnwinder::new(&self.inferior().debugee).caller_registers(ctx)?;
+        let ret_addr = RelocatedAddress::from(caller_registers.value(gimli::Register(16))?);
+        let sp = caller_registers.value(gimli::Register(7))?;
+
+        self.inferior_mut().ftrace.drop_popped_calls(callee.pid, sp);
+        let depth = self.inferior().ftrace.depth(callee.pid);
+        let args = self
+            .read_argument(DQE::Variable(VariableSelector::Any))
+            .unwrap_or_default();
+        self.hooks
+            .on_ftrace_enter(callee.pid, depth, &function, &args);
+
+        if !self.inferior().ftrace.planted.contains(&ret_addr) {
+            // return address is occupied by another breakpoint, return is not traced
+            if self.inferior().breakpoints.get_enabled(ret_addr).is_some() {
+                return Ok(());
+            }
+            let callback = |dbg: &mut Debugger| {
+                weak_error!(dbg.on_traced_return());
+            };
+            self.set_transparent_breakpoint_at_addr(ret_addr, Rc::new(callback))?;
+            self.inferior_mut().ftrace.planted.push(ret_addr);
+        }
+
+        self.inferior_mut().ftrace.calls.push(TracedCall {
+            function,
+            callee,
+            ret_addr,
+            sp,
+        });
+        Ok(())
+    }
+
+    /// Report a return from traced function, if current thread returns from it.
+    fn on_traced_return(&mut self) -> Result<(), Error> {
+        let location = self.exploration_ctx().location();
+        let sp = self
+            .inferior()
+            .debugee
+            .tracee_ctl()
+            .registers(location.pid)?
+            .value(Register::Rsp);
+        let Some(idx) = self.inferior().ftrace.calls.iter().position(|call| {
+            call.callee.pid == location.pid && call.ret_addr == location.pc && call.sp == sp
+        }) else {
+            return Ok(());
+        };
+        let call = self.inferior_mut().ftrace.calls.remove(idx);
+
+        self.inferior_mut()
+            .ftrace
+            .drop_popped_calls(location.pid, sp);
+        let depth = self.inferior().ftrace.depth(location.pid);
+        let value = weak_error!(self.read_return_value(call.callee)).flatten();
+        self.hooks
+            .on_ftrace_return(location.pid, depth, &call.function, value.as_ref());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_glob_to_regex() {
+        let regex = glob_to_regex("calc::sum*").unwrap();
+        assert!(regex.is_match("calc::sum2"));
+        assert!(regex.is_match("calc::sum"));
+        assert!(!regex.is_match("calc::float::sum2"));
+        assert!(!regex.is_match("mycalc::sum2"));
+
+        let regex = glob_to_regex("*::sum?").unwrap();
+        assert!(regex.is_match("calc::float::sum2"));
+        assert!(!regex.is_match("calc::sum2_f32"));
+
+        let regex = glob_to_regex("vec<i32>::push").unwrap();
+        assert!(regex.is_match("vec<i32>::push"));
+        assert!(!regex.is_match("vec<i32>xpush"));
+    }
+}
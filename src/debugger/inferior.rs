@@ -0,0 +1,138 @@
+use crate::debugger::address::{Address, GlobalAddress, RelocatedAddress};
+use crate::debugger::breakpoint::{BreakpointRegistry, UninitBreakpoint};
+use crate::debugger::coverage::CoverageRegistry;
+use crate::debugger::debugee::dwarf::r#type::TypeCache;
+use crate::debugger::debugee::dwarf::ProgressCallback;
+use crate::debugger::debugee::{Debugee, ExecutionStatus};
+use crate::debugger::ftrace::FunctionTraceRegistry;
+use crate::debugger::process::{Child, Installed};
+use crate::debugger::step_history::StepHistory;
+use crate::debugger::tracepoint::TracepointRegistry;
+use crate::debugger::watchpoint::WatchpointRegistry;
+use crate::debugger::Pid;
+use crate::debugger::{Error, EventHook, ExplorationContext, StopAt};
+use crate::version;
+use object::Object;
+use std::cell::RefCell;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Debugee process with its own debug information, threads control,
+/// breakpoints and watchpoints.
+pub(super) struct Inferior {
+    /// Inferior number.
+    pub(super) num: u32,
+    pub(super) process: Child<Installed>,
+    pub(super) debugee: Debugee,
+    pub(super) breakpoints: BreakpointRegistry,
+    pub(super) watchpoints: WatchpointRegistry,
+    pub(super) tracepoints: TracepointRegistry,
+    pub(super) ftrace: FunctionTraceRegistry,
+    pub(super) coverage: CoverageRegistry,
+    pub(super) type_cache: RefCell<TypeCache>,
+    pub(super) step_history: RefCell<StepHistory>,
+    pub(super) expl_context: ExplorationContext,
+    pub(super) stop_at: Option<StopAt>,
+    pub(super) main_brkpt: Option<RelocatedAddress>,
+}
+
+impl Inferior {
+    /// Load debug information of a process and create an inferior.
+    ///
+    /// # Arguments
+    ///
+    /// * `num`: inferior number
+    /// * `process`: installed or attached process
+    /// * `hooks`: debugger hooks
+    /// * `progress`: debug information parsing progress callback
+    pub(super) fn new(
+        num: u32,
+        process: Child<Installed>,
+        hooks: &dyn EventHook,
+        progress: Option<ProgressCallback>,
+    ) -> Result<Self, Error> {
+        let program_path = Path::new(process.program());
+
+        let file = fs::File::open(program_path)?;
+        let mmap = unsafe { memmap2::Mmap::map(&file)? };
+        let object = object::File::parse(&*mmap)?;
+
+        let entry_point = GlobalAddress::from(object.entry());
+        let mut breakpoints = BreakpointRegistry::default();
+        breakpoints.add_uninit(UninitBreakpoint::new_entry_point(
+            None::<PathBuf>,
+            Address::Global(entry_point),
+            process.pid(),
+        ));
+
+        let process_id = process.pid();
+        hooks.on_process_install(process_id, version::rustc_version(&object).as_ref());
+
+        let debugee = if process.is_external() {
+            Debugee::new_from_external_process(program_path, &process, &object, progress)?
+        } else {
+            Debugee::new_non_running(program_path, &process, &object, progress)?
+        };
+
+        Ok(Self {
+            num,
+            process,
+            debugee,
+            breakpoints,
+            watchpoints: WatchpointRegistry::default(),
+            tracepoints: TracepointRegistry::default(),
+            ftrace: FunctionTraceRegistry::default(),
+            coverage: CoverageRegistry::default(),
+            type_cache: RefCell::default(),
+            step_history: RefCell::default(),
+            expl_context: ExplorationContext::new_non_running(process_id),
+            stop_at: None,
+            main_brkpt: None,
+        })
+    }
+
+    pub(super) fn info(&self, current: bool) -> InferiorInfo {
+        InferiorInfo {
+            num: self.num,
+            pid: self.process.pid(),
+            program: self.process.program().to_string(),
+            status: self.debugee.execution_status().into(),
+            current,
+        }
+    }
+}
+
+/// Inferior process state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InferiorStatus {
+    /// Process is created but not started yet.
+    NotStarted,
+    /// Process is running (or stopped by the debugger).
+    InProgress,
+    /// Process is exited.
+    Exited,
+}
+
+impl From<ExecutionStatus> for InferiorStatus {
+    fn from(status: ExecutionStatus) -> Self {
+        match status {
+            ExecutionStatus::Unload => InferiorStatus::NotStarted,
+            ExecutionStatus::InProgress => InferiorStatus::InProgress,
+            ExecutionStatus::Exited => InferiorStatus::Exited,
+        }
+    }
+}
+
+/// Inferior information.
+#[derive(Debug, Clone)]
+pub struct InferiorInfo {
+    /// Inferior number.
+    pub num: u32,
+    /// Process id.
+    pub pid: Pid,
+    /// Path to the program.
+    pub program: String,
+    pub status: InferiorStatus,
+    /// True if this is a current inferior.
+    pub current: bool,
+}
@@ -0,0 +1,66 @@
+use crate::debugger::sys;
+use crate::debugger::Pid;
+use crate::debugger::Signal;
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+use std::sync::Arc;
+
+/// Handle for stopping a running debugee. It may be cloned and used from any thread
+/// (for example, from a Ctrl-C handler) while the debugger thread waits for a debugee stop.
+#[derive(Clone, Debug)]
+pub struct Interrupter {
+    requested: Arc<AtomicBool>,
+    /// Set if a requested interrupt is cancelled but its SIGINT is already sent.
+    stale: Arc<AtomicBool>,
+    pid: Arc<AtomicI32>,
+}
+
+impl Interrupter {
+    pub(super) fn new(pid: Pid) -> Self {
+        Self {
+            requested: Arc::default(),
+            stale: Arc::default(),
+            pid: Arc::new(AtomicI32::new(pid.as_raw())),
+        }
+    }
+
+    /// Set a new debugee process (after debugee restart).
+    pub(super) fn set_pid(&self, pid: Pid) {
+        self.stale.store(false, Ordering::Release);
+        self.pid.store(pid.as_raw(), Ordering::Release);
+    }
+
+    /// Interrupt a running debugee. Debugee process receives a SIGINT, then the debugger stops
+    /// all other threads using PTRACE_INTERRUPT and reports an interrupt instead of a signal.
+    /// SIGINT is never injected into the debugee, so a debugee keeps running after
+    /// the next continue.
+    pub fn interrupt(&self) {
+        self.requested.store(true, Ordering::Release);
+        let pid = Pid::from_raw(self.pid.load(Ordering::Acquire));
+        _ = sys::kill(pid, Signal::SIGINT);
+    }
+
+    /// Cancel an interrupt that is requested but not processed yet (for example, debugee
+    /// stops by itself at the same time). The already sent SIGINT is silently ignored
+    /// when the debugee receives it.
+    pub fn cancel(&self) {
+        if self.requested.swap(false, Ordering::AcqRel) {
+            self.stale.store(true, Ordering::Release);
+        }
+    }
+
+    /// Return `true` if a SIGINT of a cancelled interrupt is not received yet,
+    /// flag is reset.
+    pub(super) fn take_stale(&self) -> bool {
+        self.stale.swap(false, Ordering::AcqRel)
+    }
+
+    /// Return `true` if an interrupt was requested and not processed yet.
+    pub(super) fn is_requested(&self) -> bool {
+        self.requested.load(Ordering::Acquire)
+    }
+
+    /// Return `true` if an interrupt was requested, request is reset.
+    pub(super) fn take_request(&self) -> bool {
+        self.requested.swap(false, Ordering::AcqRel)
+    }
+}
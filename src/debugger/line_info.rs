@@ -0,0 +1,233 @@
+//! Line table inspection: address ranges of statements at a source location,
+//! their position in a function (prologue, body or epilogue) and line table rows
+//! that cover them. Helps to understand where and why a breakpoint is set.
+
+use crate::debugger::address::{Address, GlobalAddress, RelocatedAddress};
+use crate::debugger::debugee::dwarf::DebugInformation;
+use crate::debugger::error::Error::{NoCodeAtLine, NoSuitablePlace, PlaceNotFound};
+use crate::debugger::{Debugger, Error, PlaceDescriptor};
+use crate::disable_when_not_stared;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+/// Location which line table information is requested for.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LineInfoLocation {
+    /// A place where current selected frame is stopped.
+    Current,
+    /// Instruction address, an address in the main executable if debugee is not started.
+    Address(usize),
+    Line(String, u64),
+    Function(String),
+}
+
+/// Part of a function that contains a statement.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FunctionPart {
+    Prologue,
+    Body,
+    Epilogue,
+}
+
+impl Display for FunctionPart {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FunctionPart::Prologue => f.write_str("prologue"),
+            FunctionPart::Body => f.write_str("body"),
+            FunctionPart::Epilogue => f.write_str("epilogue"),
+        }
+    }
+}
+
+/// A row of a line number program.
+#[derive(Debug, Clone)]
+pub struct LineTableRow {
+    pub address: Address,
+    pub line: u64,
+    pub column: u64,
+    pub is_stmt: bool,
+    pub prolog_end: bool,
+    pub epilog_begin: bool,
+    /// Row is a place where a breakpoint at the requested location is set
+    /// (or a row that covers the requested address).
+    pub marked: bool,
+}
+
+/// A statement, contiguous run of line table rows of the same source line.
+#[derive(Debug, Clone)]
+pub struct LineInfo {
+    pub file: PathBuf,
+    pub line: u64,
+    pub function: Option<String>,
+    /// `None` if statement isn't inside any function.
+    pub part: Option<FunctionPart>,
+    /// Address of the first statement instruction.
+    pub start: Address,
+    /// Address right after the last statement instruction.
+    pub end: Address,
+    pub rows: Vec<LineTableRow>,
+}
+
+impl Debugger {
+    /// Return statements at the location with their address ranges and line table rows.
+    /// Addresses are relocated into debugee address space if debugee is started.
+    ///
+    /// # Arguments
+    ///
+    /// * `location`: source line, function or instruction address
+    pub fn line_info(&self, location: &LineInfoLocation) -> Result<Vec<LineInfo>, Error> {
+        match location {
+            LineInfoLocation::Current => {
+                disable_when_not_stared!(self);
+                let location = self.exploration_ctx().location();
+                let debug_info = self.inferior().debugee.debug_info(location.pc)?;
+                self.line_info_at_pc(debug_info, location.global_pc)
+            }
+            LineInfoLocation::Address(addr) => {
+                let (debug_info, pc) = if self.inferior().debugee.is_in_progress() {
+                    let addr = RelocatedAddress::from(*addr);
+                    (
+                        self.inferior().debugee.debug_info(addr)?,
+                        addr.into_global(&self.inferior().debugee)?,
+                    )
+                } else {
+                    (
+                        self.inferior().debugee.program_debug_info()?,
+                        GlobalAddress::from(*addr),
+                    )
+                };
+                self.line_info_at_pc(debug_info, pc)
+            }
+            LineInfoLocation::Line(file_tpl, line) => self.line_info_at_line(file_tpl, *line),
+            LineInfoLocation::Function(template) => {
+                let mut result = vec![];
+                for debug_info in self.inferior().debugee.debug_info_all() {
+                    if !debug_info.has_debug_info() {
+                        continue;
+                    }
+                    for place in debug_info.search_places_for_fn_tpl(template)? {
+                        result.extend(self.line_info_at_pc(debug_info, place.address)?);
+                    }
+                }
+                if result.is_empty() {
+                    return Err(NoSuitablePlace);
+                }
+                Ok(result)
+            }
+        }
+    }
+
+    fn line_info_at_pc(
+        &self,
+        debug_info: &DebugInformation,
+        pc: GlobalAddress,
+    ) -> Result<Vec<LineInfo>, Error> {
+        let (rows, end) = debug_info
+            .find_statement_by_pc(pc)?
+            .ok_or(PlaceNotFound(pc))?;
+        let marked = rows
+            .iter()
+            .rev()
+            .find(|row| row.address <= pc)
+            .map(|row| row.address);
+        Ok(vec![self.make_line_info(
+            debug_info,
+            rows,
+            end,
+            &[marked.unwrap_or(pc)],
+        )?])
+    }
+
+    fn line_info_at_line(&self, file_tpl: &str, line: u64) -> Result<Vec<LineInfo>, Error> {
+        let file_tpl = &self.resolve_file_tpl(file_tpl)?;
+
+        let mut result = vec![];
+        for debug_info in self.inferior().debugee.debug_info_all() {
+            if !debug_info.has_debug_info() {
+                continue;
+            }
+            // breakpoints at a line are set at the closest places
+            let breakpoint_places: Vec<_> = debug_info
+                .find_closest_place(file_tpl, line)?
+                .into_iter()
+                .map(|place| place.address)
+                .collect();
+
+            let mut statements = debug_info.find_statements_at_line(file_tpl, line);
+            if statements.is_empty() {
+                // there is no code at the line, show statements where breakpoints are set
+                for &address in &breakpoint_places {
+                    statements.extend(debug_info.find_statement_by_pc(address)?);
+                }
+            }
+
+            for (rows, end) in statements {
+                result.push(self.make_line_info(debug_info, rows, end, &breakpoint_places)?);
+            }
+        }
+
+        if result.is_empty() {
+            return match self.diagnose_line(file_tpl, line)? {
+                Some(diagnostic) => Err(NoCodeAtLine(line, diagnostic)),
+                None => Err(NoSuitablePlace),
+            };
+        }
+        Ok(result)
+    }
+
+    fn make_line_info(
+        &self,
+        debug_info: &DebugInformation,
+        rows: Vec<PlaceDescriptor>,
+        end: GlobalAddress,
+        marked: &[GlobalAddress],
+    ) -> Result<LineInfo, Error> {
+        let first = rows.first().ok_or(NoSuitablePlace)?;
+        let start = first.address;
+
+        let function = debug_info.find_function_by_pc(start)?;
+        let part = function.as_ref().map(|function| {
+            let in_prolog = function
+                .prolog()
+                .map(|prolog| start.in_range(&prolog))
+                .unwrap_or_default();
+            if in_prolog {
+                FunctionPart::Prologue
+            } else if rows.iter().any(|row| row.epilog_begin) {
+                FunctionPart::Epilogue
+            } else {
+                FunctionPart::Body
+            }
+        });
+
+        let relocate = |addr: GlobalAddress| -> Address {
+            if self.inferior().debugee.is_in_progress() {
+                if let Ok(addr) = addr.relocate_to_segment(&self.inferior().debugee, debug_info) {
+                    return Address::Relocated(addr);
+                }
+            }
+            Address::Global(addr)
+        };
+
+        Ok(LineInfo {
+            file: first.file.to_path_buf(),
+            line: first.line_number,
+            function: function.and_then(|function| function.full_name()),
+            part,
+            start: relocate(start),
+            end: relocate(end),
+            rows: rows
+                .iter()
+                .map(|row| LineTableRow {
+                    address: relocate(row.address),
+                    line: row.line_number,
+                    column: row.column_number,
+                    is_stmt: row.is_stmt,
+                    prolog_end: row.prolog_end,
+                    epilog_begin: row.epilog_begin,
+                    marked: marked.contains(&row.address),
+                })
+                .collect(),
+        })
+    }
+}
@@ -1,70 +1,97 @@
 pub mod address;
 mod breakpoint;
 mod code;
+mod coverage;
+mod deadlock;
 mod debugee;
 mod error;
+mod exception;
+mod ftrace;
+mod inferior;
+mod interrupt;
+mod line_info;
 pub mod process;
+mod profile;
 pub mod register;
 pub mod rust;
 mod step;
-mod utils;
+mod step_history;
+mod sys;
+mod tracepoint;
 pub mod variable;
 mod watchpoint;
 
+pub use breakpoint::BreakpointGroup;
 pub use breakpoint::BreakpointView;
 pub use breakpoint::BreakpointViewOwned;
+pub use breakpoint::CatchEvent;
 pub use breakpoint::CreateTransparentBreakpointRequest;
+pub use breakpoint::LineDiagnostic;
+pub use breakpoint::ReturnCondition;
+pub use coverage::{Coverage, FileCoverage};
+pub use deadlock::{Deadlock, LockPlace, MutexHolder, MutexWait};
 pub use debugee::dwarf::r#type::TypeDeclaration;
 pub use debugee::dwarf::unit::FunctionDie;
 pub use debugee::dwarf::unit::PlaceDescriptor;
 pub use debugee::dwarf::unit::PlaceDescriptorOwned;
 pub use debugee::dwarf::unwind;
 pub use debugee::dwarf::Symbol;
-pub use debugee::tracee::Tracee;
+pub use debugee::dwarf::{ParsingProgress, ProgressCallback};
+pub use debugee::dwarf::{SymbolMatch, SymbolMatchKind};
+pub use debugee::tracee::{StopType, Tracee, TraceeStatus};
 pub use debugee::FrameInfo;
+pub use debugee::FrameMemory;
 pub use debugee::FunctionAssembly;
 pub use debugee::FunctionRange;
 pub use debugee::RegionInfo;
 pub use debugee::ThreadSnapshot;
-pub use error::Error;
+pub use debugee::{AddressInfo, ResolvedFrame};
+pub use debugee::{BranchTarget, DecodedInstruction};
+pub use debugee::{StackSlot, StackSlotAnnotation};
+pub use error::{DwarfError, Error, ObjectError, SyscallError};
+pub use exception::demangle_type_name;
+pub use inferior::{InferiorInfo, InferiorStatus};
+pub use interrupt::Interrupter;
+pub use line_info::{FunctionPart, LineInfo, LineInfoLocation, LineTableRow};
+pub use profile::{CallTreeNode, FlatEntry, Profile, DEFAULT_SAMPLE_INTERVAL};
+pub use step::{ReturnValue, StepGranularity};
+pub use step_history::STEP_HISTORY_LIMIT;
+pub use sys::{Pid, Signal, WaitStatus};
+pub use tracepoint::{FormatString, Tracepoint, TracepointHit, TracepointPlace};
 pub use watchpoint::WatchpointView;
 pub use watchpoint::WatchpointViewOwned;
+pub use watchpoint::{AccessKind, MemoryAccess};
 
 use crate::debugger::address::{Address, GlobalAddress, RelocatedAddress};
-use crate::debugger::breakpoint::{Breakpoint, BreakpointRegistry, BrkptType, UninitBreakpoint};
-use crate::debugger::debugee::dwarf::r#type::TypeCache;
+use crate::debugger::breakpoint::{Breakpoint, BrkptType};
 use crate::debugger::debugee::dwarf::unwind::Backtrace;
 use crate::debugger::debugee::dwarf::DwarfUnwinder;
-use crate::debugger::debugee::tracer::{StopReason, TraceContext};
-use crate::debugger::debugee::{Debugee, ExecutionStatus, Location};
+use crate::debugger::debugee::tracer::{StopReason, TraceContext, WatchpointHitType};
+use crate::debugger::debugee::{ExecutionStatus, Location};
 use crate::debugger::error::Error::{
-    FrameNotFound, Hook, ProcessNotStarted, Ptrace, RegisterNameNotFound, UnwindNoContext,
+    FrameNotFound, Hook, ProcessNotStarted, RegisterNameNotFound, UnwindNoContext,
 };
+use crate::debugger::inferior::Inferior;
 use crate::debugger::process::{Child, Installed};
+use crate::debugger::profile::Profiler;
 use crate::debugger::register::debug::BreakCondition;
-use crate::debugger::register::{DwarfRegisterMap, Register, RegisterMap};
+use crate::debugger::register::{DwarfRegisterMap, Register, VectorRegister};
 use crate::debugger::step::StepResult;
-use crate::debugger::variable::select::{VariableSelector, DQE};
+use crate::debugger::variable::render::RenderRepr;
+use crate::debugger::variable::select::{VariableDeclaration, VariableSelector, DQE};
 use crate::debugger::variable::VariableIR;
-use crate::debugger::watchpoint::WatchpointRegistry;
 use crate::debugger::Error::Syscall;
 use crate::oracle::Oracle;
+use crate::version::Version;
 use crate::{print_warns, weak_error};
 use indexmap::IndexMap;
 use log::debug;
-use nix::libc::{c_void, uintptr_t};
-use nix::sys;
-use nix::sys::signal;
-use nix::sys::signal::{Signal, SIGKILL};
-use nix::sys::wait::{waitpid, WaitStatus};
-use nix::unistd::Pid;
-use object::Object;
+use nix::errno::Errno;
 use regex::Regex;
-use std::cell::RefCell;
-use std::ffi::c_long;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 use std::{fs, mem};
 
 /// Trait for the reverse interaction between the debugger and the user interface.
@@ -85,6 +112,36 @@ pub trait EventHook {
         function: Option<&FunctionDie>,
     ) -> anyhow::Result<()>;
 
+    /// Called after [`EventHook::on_breakpoint`] if a program is stopped by an on-return
+    /// breakpoint (at a return address of a function).
+    ///
+    /// # Arguments
+    ///
+    /// * `num`: breakpoint number
+    /// * `value`: value returned from a function, `None` if it can't be read
+    fn on_function_return(&self, num: u32, value: Option<&VariableIR>) -> anyhow::Result<()> {
+        _ = (num, value);
+        Ok(())
+    }
+
+    /// Called after [`EventHook::on_breakpoint`] if a program is stopped by a catchpoint
+    /// (when C++ exception is thrown or caught).
+    ///
+    /// # Arguments
+    ///
+    /// * `num`: breakpoint number
+    /// * `event`: exception handling event
+    /// * `type_name`: type of exception, `None` if it can't be read or exception is foreign
+    fn on_exception(
+        &self,
+        num: u32,
+        event: CatchEvent,
+        type_name: Option<&str>,
+    ) -> anyhow::Result<()> {
+        _ = (num, event, type_name);
+        Ok(())
+    }
+
     /// Called when watchpoint is activated.
     ///
     /// # Arguments
@@ -95,6 +152,8 @@ pub trait EventHook {
     /// * `condition`: reason of a watchpoint activation
     /// * `old_value`: previous expression or mem location value
     /// * `new_value`: current expression or mem location value
+    /// * `access`: instruction that accesses memory and kind of access, `None` if
+    ///   instruction is unknown or watchpoint activated cause end of scope is reached
     /// * `end_of_scope`: true if watchpoint activated cause end of scope is reached
     #[allow(clippy::too_many_arguments)]
     fn on_watchpoint(
@@ -105,9 +164,28 @@ pub trait EventHook {
         condition: BreakCondition,
         old_value: Option<&VariableIR>,
         new_value: Option<&VariableIR>,
+        access: Option<MemoryAccess>,
         end_of_scope: bool,
     ) -> anyhow::Result<()>;
 
+    /// Called when an address of a watchpoint expression is re-resolved at debugee stop.
+    ///
+    /// # Arguments
+    ///
+    /// * `num`: watchpoint number
+    /// * `expr`: watchpoint expression
+    /// * `address`: new address of an observed memory location, `None` if expression
+    ///   can't be resolved anymore and watchpoint is removed
+    fn on_watchpoint_rebind(
+        &self,
+        num: u32,
+        expr: &str,
+        address: Option<RelocatedAddress>,
+    ) -> anyhow::Result<()> {
+        _ = (num, expr, address);
+        Ok(())
+    }
+
     /// Called when one of step commands is done.
     ///
     /// # Arguments
@@ -129,6 +207,26 @@ pub trait EventHook {
     /// * `signal`: received OS signal
     fn on_signal(&self, signal: Signal);
 
+    /// Called after [`EventHook::on_breakpoint`], [`EventHook::on_watchpoint`],
+    /// [`EventHook::on_step`] or [`EventHook::on_signal`] with a summary of a stop.
+    ///
+    /// # Arguments
+    ///
+    /// * `summary`: stop time, stopped thread, stop reason and place
+    fn on_stop(&self, summary: &StopSummary) {
+        _ = summary;
+    }
+
+    /// Called after [`EventHook::on_stop`] if some threads don't respond to an interrupt
+    /// (for example, in uninterruptible sleep) and keep running while debugee is stopped.
+    ///
+    /// # Arguments
+    ///
+    /// * `tids`: running threads
+    fn on_threads_not_stopped(&self, tids: &[Pid]) {
+        _ = tids;
+    }
+
     /// Called right after debugee exit.
     ///
     /// # Arguments
@@ -136,12 +234,95 @@ pub trait EventHook {
     /// * `code`: exit code
     fn on_exit(&self, code: i32);
 
+    /// Called when dprintf tracepoint is hit, debugee continues execution after this call.
+    ///
+    /// # Arguments
+    ///
+    /// * `num`: tracepoint number
+    /// * `format`: message format
+    /// * `values`: values of tracepoint expressions, `None` if expression can't be evaluated
+    fn on_dprintf(&self, num: u32, format: &FormatString, values: &[Option<VariableIR>]) {
+        _ = (num, format, values);
+    }
+
+    /// Called when a traced function (see [`Debugger::start_ftrace`]) is called,
+    /// debugee continues execution after this call.
+    ///
+    /// # Arguments
+    ///
+    /// * `tid`: thread where function is called
+    /// * `depth`: number of active traced calls in a thread
+    /// * `function`: function full name
+    /// * `args`: function arguments
+    fn on_ftrace_enter(&self, tid: Pid, depth: usize, function: &str, args: &[VariableIR]) {
+        _ = (tid, depth, function, args);
+    }
+
+    /// Called when a traced function (see [`Debugger::start_ftrace`]) returns,
+    /// debugee continues execution after this call.
+    ///
+    /// # Arguments
+    ///
+    /// * `tid`: thread where function is called
+    /// * `depth`: number of active traced calls in a thread (same as at function call)
+    /// * `function`: function full name
+    /// * `value`: returned value, `None` if function returns nothing or value can't be read
+    fn on_ftrace_return(&self, tid: Pid, depth: usize, function: &str, value: Option<&VariableIR>) {
+        _ = (tid, depth, function, value);
+    }
+
     /// Called single time for each debugee process (on start or after reinstall).
     ///
     /// # Arguments
     ///
     /// * `pid`: debugee process pid
-    fn on_process_install(&self, pid: Pid, object: Option<&object::File>);
+    /// * `rustc_version`: version of rustc that builds a program, `None` if version is unknown
+    ///   or program is not probed (after reinstall)
+    fn on_process_install(&self, pid: Pid, rustc_version: Option<&Version>);
+}
+
+/// Place where debugee is stopped right after start, even if there are no breakpoints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopAt {
+    /// Program entry point (first program instruction, before any runtime initialization).
+    Entry,
+    /// Start of a program `main` function (user one, not a runtime entry shim).
+    Main,
+}
+
+/// Reason of a debugee stop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopKind {
+    /// User defined breakpoint is hit, `hits` is a total number of hits including this one.
+    Breakpoint { number: u32, hits: u64 },
+    /// Watchpoint is activated or its expression is out of scope.
+    Watchpoint { number: u32, end_of_scope: bool },
+    /// Step command is done.
+    Step,
+    /// OS signal is received.
+    Signal(Signal),
+    /// Debugee is interrupted by user (see [`Interrupter`]).
+    Interrupt,
+    /// Program entry point is reached (see [`StopAt::Entry`]).
+    EntryPoint,
+}
+
+/// Summary of a debugee stop.
+#[derive(Debug, Clone)]
+pub struct StopSummary {
+    /// Time of stop.
+    pub time: SystemTime,
+    /// Stopped thread.
+    pub tid: Pid,
+    /// Stopped thread number.
+    pub thread_num: u32,
+    /// Address where thread stopped, `None` if it can't be read.
+    pub pc: Option<RelocatedAddress>,
+    pub kind: StopKind,
+    /// Function where thread stopped, `None` if there is no debug information for it.
+    pub function: Option<String>,
+    /// Place where thread stopped, `None` if there is no debug information for it.
+    pub place: Option<PlaceDescriptorOwned>,
 }
 
 pub struct NopHook {}
@@ -165,6 +346,7 @@ impl EventHook for NopHook {
         _: BreakCondition,
         _: Option<&VariableIR>,
         _: Option<&VariableIR>,
+        _: Option<MemoryAccess>,
         _: bool,
     ) -> anyhow::Result<()> {
         Ok(())
@@ -183,13 +365,13 @@ impl EventHook for NopHook {
 
     fn on_exit(&self, _: i32) {}
 
-    fn on_process_install(&self, _: Pid, _: Option<&object::File>) {}
+    fn on_process_install(&self, _: Pid, _: Option<&Version>) {}
 }
 
 #[macro_export]
 macro_rules! disable_when_not_stared {
     ($this: expr) => {
-        if !$this.debugee.is_in_progress() {
+        if !$this.inferior().debugee.is_in_progress() {
             return Err($crate::debugger::error::Error::ProcessNotStarted);
         }
     };
@@ -251,6 +433,7 @@ impl ExplorationContext {
 pub struct DebuggerBuilder<H: EventHook + 'static = NopHook> {
     oracles: Vec<Arc<dyn Oracle>>,
     hooks: Option<H>,
+    progress: Option<ProgressCallback>,
 }
 
 impl<H: EventHook + 'static> DebuggerBuilder<H> {
@@ -259,6 +442,7 @@ impl<H: EventHook + 'static> DebuggerBuilder<H> {
         Self {
             oracles: vec![],
             hooks: None,
+            progress: None,
         }
     }
 
@@ -283,6 +467,18 @@ impl<H: EventHook + 'static> DebuggerBuilder<H> {
         }
     }
 
+    /// Add a callback for report debug information parsing progress.
+    ///
+    /// # Arguments
+    ///
+    /// * `progress`: progress callback
+    pub fn with_progress(self, progress: ProgressCallback) -> Self {
+        Self {
+            progress: Some(progress),
+            ..self
+        }
+    }
+
     /// Return all oracles.
     pub fn oracles(&self) -> impl Iterator<Item = &dyn Oracle> {
         self.oracles.iter().map(|oracle| oracle.as_ref())
@@ -295,31 +491,32 @@ impl<H: EventHook + 'static> DebuggerBuilder<H> {
     /// * `process`: debugee process
     pub fn build(self, process: Child<Installed>) -> Result<Debugger, Error> {
         if let Some(hooks) = self.hooks {
-            Debugger::new(process, hooks, self.oracles)
+            Debugger::new(process, hooks, self.oracles, self.progress)
         } else {
-            Debugger::new(process, NopHook {}, self.oracles)
+            Debugger::new(process, NopHook {}, self.oracles, self.progress)
         }
     }
 }
 
 /// Main structure of bug-stalker, control debugee state and provides application functionality.
 pub struct Debugger {
-    /// Child process where debugee is running.
-    process: Child<Installed>,
-    /// Debugee static/runtime state and control flow.
-    debugee: Debugee,
-    /// Active and non-active breakpoints lists.
-    breakpoints: BreakpointRegistry,
-    /// Watchpoints lists.
-    watchpoints: WatchpointRegistry,
-    /// Type declaration cache.
-    type_cache: RefCell<TypeCache>,
+    /// Debugged processes with their own debug information, breakpoints and watchpoints.
+    /// All threads of non-current inferiors are stopped.
+    inferiors: Vec<Inferior>,
+    /// Index of the current inferior in `inferiors`.
+    current: usize,
     /// Debugger interrupt with UI by EventHook trait.
     hooks: Box<dyn EventHook>,
-    /// Current exploration context.
-    expl_context: ExplorationContext,
     /// Map of name -> (oracle, installed flag) pairs.
     oracles: IndexMap<&'static str, (Arc<dyn Oracle>, bool)>,
+    /// Interrupts running debugee from other threads.
+    interrupter: Interrupter,
+    /// Active profiling session.
+    profiler: Option<Profiler>,
+    /// Last collected profile.
+    profile: Option<Profile>,
+    /// Values recorded by user, value index is a history number (`$0`, `$1`, ...).
+    value_history: Vec<VariableIR>,
 }
 
 impl Debugger {
@@ -327,45 +524,38 @@ impl Debugger {
         process: Child<Installed>,
         hooks: impl EventHook + 'static,
         oracles: impl IntoIterator<Item = Arc<dyn Oracle>>,
+        progress: Option<ProgressCallback>,
     ) -> Result<Self, Error> {
-        let program_path = Path::new(process.program());
-
-        let file = fs::File::open(program_path)?;
-        let mmap = unsafe { memmap2::Mmap::map(&file)? };
-        let object = object::File::parse(&*mmap)?;
-
-        let entry_point = GlobalAddress::from(object.entry());
-        let mut breakpoints = BreakpointRegistry::default();
-        breakpoints.add_uninit(UninitBreakpoint::new_entry_point(
-            None::<PathBuf>,
-            Address::Global(entry_point),
-            process.pid(),
-        ));
-
-        let process_id = process.pid();
-        hooks.on_process_install(process_id, Some(&object));
-
-        let debugee = if process.is_external() {
-            Debugee::new_from_external_process(program_path, &process, &object)?
-        } else {
-            Debugee::new_non_running(program_path, &process, &object)?
-        };
+        let inferior = Inferior::new(1, process, &hooks, progress)?;
+        let process_id = inferior.process.pid();
 
         Ok(Self {
-            debugee,
-            process,
-            breakpoints,
-            watchpoints: WatchpointRegistry::default(),
+            inferiors: vec![inferior],
+            current: 0,
             hooks: Box::new(hooks),
-            type_cache: RefCell::default(),
-            expl_context: ExplorationContext::new_non_running(process_id),
             oracles: oracles
                 .into_iter()
                 .map(|oracle| (oracle.name(), (oracle, false)))
                 .collect(),
+            interrupter: Interrupter::new(process_id),
+            profiler: None,
+            profile: None,
+            value_history: vec![],
         })
     }
 
+    /// Return the current inferior.
+    #[inline(always)]
+    fn inferior(&self) -> &Inferior {
+        &self.inferiors[self.current]
+    }
+
+    /// Return the current inferior.
+    #[inline(always)]
+    fn inferior_mut(&mut self) -> &mut Inferior {
+        &mut self.inferiors[self.current]
+    }
+
     /// Return installed oracle, or `None` if oracle not found or not installed.
     ///
     /// # Arguments
@@ -395,7 +585,102 @@ impl Debugger {
     }
 
     pub fn process(&self) -> &Child<Installed> {
-        &self.process
+        &self.inferior().process
+    }
+
+    /// Return a handle for interrupting a running debugee from another thread.
+    pub fn interrupter(&self) -> Interrupter {
+        self.interrupter.clone()
+    }
+
+    /// Return information about all inferiors ordered by inferior number.
+    pub fn inferiors(&self) -> Vec<InferiorInfo> {
+        let mut inferiors: Vec<_> = self
+            .inferiors
+            .iter()
+            .enumerate()
+            .map(|(idx, inferior)| inferior.info(idx == self.current))
+            .collect();
+        inferiors.sort_by_key(|inferior| inferior.num);
+        inferiors
+    }
+
+    /// Make an inferior with an index current.
+    /// Statuses of other inferiors reaped by the current one are passed to their owners.
+    fn set_current_inferior(&mut self, idx: usize) {
+        let statuses = self
+            .inferior_mut()
+            .debugee
+            .tracer_mut()
+            .take_foreign_statuses();
+        for status in statuses {
+            let unclaimed = self
+                .inferiors
+                .iter_mut()
+                .enumerate()
+                .filter(|(other, _)| *other != self.current)
+                .try_fold(status, |status, (_, other)| {
+                    other.debugee.tracer_mut().defer_status(status)
+                });
+            if let Some(status) = unclaimed {
+                debug!(target: "debugger", "drop status of an unknown process: {status:?}");
+            }
+        }
+
+        self.current = idx;
+        let pid = self.inferior().process.pid();
+        self.interrupter.set_pid(pid);
+        self.hooks.on_process_install(pid, None);
+    }
+
+    /// Make an inferior current. The previous current inferior stays stopped
+    /// until it becomes current again.
+    ///
+    /// # Arguments
+    ///
+    /// * `num`: inferior number
+    pub fn switch_inferior(&mut self, num: u32) -> Result<InferiorInfo, Error> {
+        if num != self.inferior().num {
+            if self.profiler.is_some() {
+                return Err(Error::InferiorSwitchWhileProfiling);
+            }
+
+            let idx = self
+                .inferiors
+                .iter()
+                .position(|inferior| inferior.num == num)
+                .ok_or(Error::InferiorNotFound(num))?;
+            self.set_current_inferior(idx);
+        }
+
+        Ok(self.inferior().info(true))
+    }
+
+    /// Attach a running process as a new inferior and make it current.
+    ///
+    /// # Arguments
+    ///
+    /// * `pid`: process id
+    pub fn attach_inferior(&mut self, pid: Pid) -> Result<InferiorInfo, Error> {
+        if self.profiler.is_some() {
+            return Err(Error::InferiorSwitchWhileProfiling);
+        }
+
+        let (stdout, stderr) = self.inferior().process.try_clone_pipes()?;
+        let num = self
+            .inferiors
+            .iter()
+            .map(|inferior| inferior.num)
+            .max()
+            .unwrap_or_default()
+            + 1;
+        let inferior = Child::from_external(pid, stdout, stderr)
+            .and_then(|process| Inferior::new(num, process, self.hooks.as_ref(), None))
+            .map_err(|e| Error::InferiorAttach(Box::new(e)))?;
+
+        self.inferiors.push(inferior);
+        self.set_current_inferior(self.inferiors.len() - 1);
+        Ok(self.inferior().info(true))
     }
 
     pub fn set_hook(&mut self, hooks: impl EventHook + 'static) {
@@ -405,19 +690,20 @@ impl Debugger {
     /// Return last set exploration context.
     #[inline(always)]
     pub fn exploration_ctx(&self) -> &ExplorationContext {
-        &self.expl_context
+        &self.inferior().expl_context
     }
 
     /// Update current program counters for current in focus thread.
     fn expl_ctx_update_location(&mut self) -> Result<&ExplorationContext, Error> {
         let old_ctx = self.exploration_ctx();
-        self.expl_context = ExplorationContext::new(
-            self.debugee
+        self.inferior_mut().expl_context = ExplorationContext::new(
+            self.inferior()
+                .debugee
                 .get_tracee_ensure(old_ctx.pid_on_focus())
-                .location(&self.debugee)?,
+                .location(&self.inferior().debugee)?,
             0,
         );
-        Ok(&self.expl_context)
+        Ok(&self.inferior().expl_context)
     }
 
     /// Restore frame from user defined to real.
@@ -431,28 +717,45 @@ impl Debugger {
     ///
     /// * `pid`: new in focus thread id
     fn expl_ctx_switch_thread(&mut self, pid: Pid) -> Result<&ExplorationContext, Error> {
-        self.expl_context = ExplorationContext::new(
-            self.debugee
+        self.inferior_mut().expl_context = ExplorationContext::new(
+            self.inferior()
+                .debugee
                 .get_tracee_ensure(pid)
-                .location(&self.debugee)?,
+                .location(&self.inferior().debugee)?,
             0,
         );
-        Ok(&self.expl_context)
+        Ok(&self.inferior().expl_context)
     }
 
     /// Continue debugee execution. Step over breakpoint if called at it.
     /// Return if breakpoint is reached or signal occurred or debugee exit.
+    /// Watchpoint addresses are re-resolved at stop.
     ///
     /// **! change exploration context**
     fn continue_execution(&mut self) -> Result<StopReason, Error> {
-        if let Some(sign_or_wp) = self.step_over_breakpoint()? {
+        let stop_reason = self.continue_execution_inner()?;
+        self.rebind_watchpoints()?;
+        Ok(stop_reason)
+    }
+
+    fn continue_execution_inner(&mut self) -> Result<StopReason, Error> {
+        let mut step_result = self.step_over_breakpoint()?;
+        // a step over breakpoint isn't done if it is stopped by an ignored SIGINT
+        while let Some(StopReason::SignalStop(_, Signal::SIGINT)) = step_result {
+            if !self.interrupter.take_stale() {
+                break;
+            }
+            step_result = self.step_over_breakpoint()?;
+        }
+        if let Some(sign_or_wp) = step_result {
             match sign_or_wp {
                 StopReason::Watchpoint(pid, current_pc, ty) => {
-                    self.execute_on_watchpoint_hook(pid, current_pc, &ty)?;
-                    return Ok(StopReason::Watchpoint(pid, current_pc, ty));
+                    if self.execute_on_watchpoint_hook(pid, current_pc, &ty)? {
+                        return Ok(StopReason::Watchpoint(pid, current_pc, ty));
+                    }
                 }
                 StopReason::SignalStop(pid, sign) => {
-                    self.hooks.on_signal(sign);
+                    self.execute_on_signal_hook(pid, sign);
                     return Ok(StopReason::SignalStop(pid, sign));
                 }
                 _ => {
@@ -461,25 +764,36 @@ impl Debugger {
             }
         }
 
+        let _sampling_timer = self.arm_sampling_timer();
         let stop_reason = loop {
-            let event = self.debugee.trace_until_stop(TraceContext::new(
-                &self.breakpoints.active_breakpoints(),
-                &self.watchpoints,
+            let inferior = self.inferior_mut();
+            inferior.breakpoints.rearm_all()?;
+            let event = inferior.debugee.trace_until_stop(TraceContext::new(
+                &inferior.breakpoints.active_breakpoints(),
+                &inferior.watchpoints,
             ))?;
             match event {
                 StopReason::DebugeeExit(code) => {
+                    let inferior = self.inferior_mut();
                     // ignore all possible errors on watchpoints disabling
-                    _ = self.watchpoints.clear_local_disable_global(
-                        self.debugee.tracee_ctl(),
-                        &mut self.breakpoints,
+                    _ = inferior.watchpoints.clear_local_disable_global(
+                        inferior.debugee.tracee_ctl(),
+                        &mut inferior.breakpoints,
                     );
                     // ignore all possible errors on breakpoints disabling
-                    _ = self.breakpoints.disable_all_breakpoints(&self.debugee);
+                    _ = inferior
+                        .breakpoints
+                        .disable_all_breakpoints(&inferior.debugee);
+                    // interrupt requested right before exit is out of date
+                    self.interrupter.take_request();
                     self.hooks.on_exit(code);
                     break event;
                 }
                 StopReason::DebugeeStart => {
-                    self.breakpoints.enable_entry_breakpoint(&self.debugee)?;
+                    let inferior = self.inferior_mut();
+                    inferior
+                        .breakpoints
+                        .enable_entry_breakpoint(&inferior.debugee)?;
                     // no need to update expl context cause next stop been soon, on entry point
                 }
                 StopReason::NoSuchProcess(_) => {
@@ -488,20 +802,28 @@ impl Debugger {
                 StopReason::Breakpoint(pid, current_pc) => {
                     self.expl_ctx_switch_thread(pid)?;
 
-                    if let Some(bp) = self.breakpoints.get_enabled(current_pc) {
+                    if let Some((number, value)) = self.take_function_return(pid, current_pc)? {
+                        self.execute_on_breakpoint_hook(pid, current_pc, number, Some(value))?;
+                        break event;
+                    }
+
+                    if let Some(bp) = self.inferior().breakpoints.get_enabled(current_pc) {
                         match bp.r#type() {
                             BrkptType::EntryPoint => {
-                                print_warns!(self
+                                let inferior = self.inferior_mut();
+                                print_warns!(inferior
                                     .breakpoints
-                                    .enable_all_breakpoints(&self.debugee));
-                                print_warns!(self.watchpoints.refresh(&self.debugee));
+                                    .enable_all_breakpoints(&inferior.debugee));
+                                print_warns!(inferior.watchpoints.refresh(&inferior.debugee));
 
                                 // rendezvous already available at this point
-                                let brk = self.debugee.rendezvous().r_brk();
-                                self.breakpoints.add_and_enable(Breakpoint::new_linker_map(
-                                    brk,
-                                    self.process.pid(),
-                                ))?;
+                                let brk = inferior.debugee.rendezvous().r_brk();
+                                inferior
+                                    .breakpoints
+                                    .add_and_enable(Breakpoint::new_linker_map(
+                                        brk,
+                                        inferior.process.pid(),
+                                    ))?;
 
                                 // check oracles is ready
                                 let oracles = self.oracles.clone();
@@ -523,6 +845,21 @@ impl Debugger {
                                     }
                                 }
 
+                                self.install_tracepoints();
+                                self.install_ftrace();
+                                self.install_coverage();
+
+                                match self.inferior_mut().stop_at.take() {
+                                    Some(StopAt::Entry) => {
+                                        self.execute_on_stop_hook(pid, StopKind::EntryPoint);
+                                        break event;
+                                    }
+                                    Some(StopAt::Main) => {
+                                        weak_error!(self.set_main_breakpoint());
+                                    }
+                                    None => {}
+                                }
+
                                 // ignore possible signals and watchpoints
                                 while self.step_over_breakpoint()?.is_some() {}
                                 continue;
@@ -534,77 +871,132 @@ impl Debugger {
                                 continue;
                             }
                             BrkptType::UserDefined => {
-                                let pc = current_pc.into_global(&self.debugee)?;
-                                let dwarf = self
-                                    .debugee
-                                    .debug_info(self.exploration_ctx().location().pc)?;
-                                let place = weak_error!(dwarf.find_place_from_pc(pc)).flatten();
-                                let func = weak_error!(dwarf.find_function_by_pc(pc))
-                                    .flatten()
-                                    .map(|f| f.die);
-                                self.hooks
-                                    .on_breakpoint(current_pc, bp.number(), place, func)
-                                    .map_err(Hook)?;
+                                let number = bp.number();
+                                if self
+                                    .inferior()
+                                    .breakpoints
+                                    .return_condition(number)
+                                    .is_some()
+                                {
+                                    self.track_function_return(number)?;
+                                    match self.step_over_transparent_breakpoint(current_pc)? {
+                                        Some(stop_reason) => return Ok(stop_reason),
+                                        None => continue,
+                                    }
+                                }
+
+                                self.execute_on_breakpoint_hook(pid, current_pc, number, None)?;
                                 break event;
                             }
                             BrkptType::WatchpointCompanion(_) => {
                                 unreachable!("should not coming from tracer directly");
                             }
                             BrkptType::Temporary => {
+                                if self.inferior().main_brkpt == Some(current_pc) {
+                                    self.inferior_mut().main_brkpt = None;
+                                    self.remove_breakpoint(Address::Relocated(current_pc))?;
+                                    self.execute_on_step_hook()?;
+                                }
                                 break event;
                             }
                             BrkptType::Transparent(callback) => {
                                 callback.clone()(self);
 
-                                match self.step_over_breakpoint()? {
-                                    Some(StopReason::SignalStop(pid, sign)) => {
-                                        self.hooks.on_signal(sign);
-                                        return Ok(StopReason::SignalStop(pid, sign));
-                                    }
-                                    Some(StopReason::Watchpoint(pid, addr, ty)) => {
-                                        self.execute_on_watchpoint_hook(pid, addr, &ty)?;
-                                        return Ok(StopReason::Watchpoint(pid, current_pc, ty));
-                                    }
-                                    _ => continue,
+                                match self.step_over_transparent_breakpoint(current_pc)? {
+                                    Some(stop_reason) => return Ok(stop_reason),
+                                    None => continue,
+                                }
+                            }
+                            BrkptType::FunctionReturn => {
+                                match self.step_over_transparent_breakpoint(current_pc)? {
+                                    Some(stop_reason) => return Ok(stop_reason),
+                                    None => continue,
                                 }
                             }
                         }
                     }
                 }
                 StopReason::SignalStop(pid, sign) => {
-                    if !self.debugee.is_in_progress() {
+                    if !self.inferior().debugee.is_in_progress() {
+                        continue;
+                    }
+
+                    if self.is_sample_stop(sign) {
+                        self.record_samples();
+                        continue;
+                    }
+
+                    if sign == Signal::SIGINT && self.interrupter.take_stale() {
                         continue;
                     }
 
                     self.expl_ctx_switch_thread(pid)?;
-                    self.hooks.on_signal(sign);
+                    self.execute_on_signal_hook(pid, sign);
                     break event;
                 }
                 StopReason::Watchpoint(pid, current_pc, ref ty) => {
                     self.expl_ctx_switch_thread(pid)?;
-                    self.execute_on_watchpoint_hook(pid, current_pc, ty)?;
+                    if !self.execute_on_watchpoint_hook(pid, current_pc, ty)? {
+                        continue;
+                    }
                     break event;
                 }
+                StopReason::ThreadCreated(_, _) => {
+                    unreachable!("handled by debugee")
+                }
             }
         };
 
         Ok(stop_reason)
     }
 
+    /// Step over a breakpoint that doesn't stop a program (like a transparent breakpoint).
+    /// Return a stop reason if a step is interrupted by a signal or a watchpoint
+    /// and debugee must be stopped.
+    fn step_over_transparent_breakpoint(
+        &mut self,
+        current_pc: RelocatedAddress,
+    ) -> Result<Option<StopReason>, Error> {
+        match self.step_over_breakpoint()? {
+            Some(StopReason::SignalStop(_, sign)) if self.is_sample_stop(sign) => {
+                self.record_samples();
+                Ok(None)
+            }
+            Some(StopReason::SignalStop(_, Signal::SIGINT)) if self.interrupter.take_stale() => {
+                Ok(None)
+            }
+            Some(StopReason::SignalStop(pid, sign)) => {
+                self.execute_on_signal_hook(pid, sign);
+                Ok(Some(StopReason::SignalStop(pid, sign)))
+            }
+            Some(StopReason::Watchpoint(pid, addr, ty)) => {
+                if self.execute_on_watchpoint_hook(pid, addr, &ty)? {
+                    return Ok(Some(StopReason::Watchpoint(pid, current_pc, ty)));
+                }
+                Ok(None)
+            }
+            _ => Ok(None),
+        }
+    }
+
     /// Restart debugee by recreating debugee process, save all user-defined breakpoints.
     /// Return when new debugee stopped or ends.
     ///
     /// **! change exploration context**
     pub fn restart_debugee(&mut self) -> Result<Pid, Error> {
-        match self.debugee.execution_status() {
+        match self.inferior().debugee.execution_status() {
             ExecutionStatus::Unload => {
                 // all breakpoints and watchpoints already disabled by default
             }
             ExecutionStatus::InProgress => {
-                print_warns!(self
-                    .watchpoints
-                    .clear_local_disable_global(self.debugee.tracee_ctl(), &mut self.breakpoints));
-                print_warns!(self.breakpoints.disable_all_breakpoints(&self.debugee)?);
+                let inferior = self.inferior_mut();
+                print_warns!(inferior.watchpoints.clear_local_disable_global(
+                    inferior.debugee.tracee_ctl(),
+                    &mut inferior.breakpoints
+                ));
+                print_warns!(inferior
+                    .breakpoints
+                    .disable_all_breakpoints(&inferior.debugee)?);
             }
             ExecutionStatus::Exited => {
                 // all breakpoints and watchpoints
@@ -612,48 +1004,167 @@ impl Debugger {
             }
         }
 
-        if !self.debugee.is_exited() {
-            let proc_pid = self.process.pid();
-            signal::kill(proc_pid, SIGKILL).map_err(|e| Syscall("kill", e))?;
-            _ = self
+        if !self.inferior().debugee.is_exited() {
+            self.kill_process_tree()?;
+        }
+
+        self.reinstall_process()?;
+        self.continue_execution()?;
+        Ok(self.inferior().process.pid())
+    }
+
+    /// Kill the running debugee and all processes spawned by it. Breakpoints and watchpoints
+    /// are kept and will be enabled at the next debugee start.
+    pub fn kill_debugee(&mut self) -> Result<(), Error> {
+        disable_when_not_stared!(self);
+        let inferior = self.inferior_mut();
+        print_warns!(inferior
+            .watchpoints
+            .clear_local_disable_global(inferior.debugee.tracee_ctl(), &mut inferior.breakpoints));
+        print_warns!(inferior
+            .breakpoints
+            .disable_all_breakpoints(&inferior.debugee)?);
+
+        let code = self.kill_process_tree()?;
+        // interrupt requested right before kill is out of date
+        self.interrupter.take_request();
+        if let Some(code) = code {
+            self.hooks.on_exit(code);
+        }
+        Ok(())
+    }
+
+    /// Detach from the running debugee, so it continues execution without a debugger.
+    /// Original instructions under breakpoints are restored before detach,
+    /// breakpoints and watchpoints are kept and will be enabled at the next debugee start.
+    pub fn detach_debugee(&mut self) -> Result<(), Error> {
+        disable_when_not_stared!(self);
+        let inferior = self.inferior_mut();
+        print_warns!(inferior
+            .watchpoints
+            .clear_local_disable_global(inferior.debugee.tracee_ctl(), &mut inferior.breakpoints));
+        print_warns!(inferior
+            .breakpoints
+            .disable_all_breakpoints(&inferior.debugee)?);
+
+        self.detach_tracees();
+        self.inferior_mut().debugee.set_detached();
+        // interrupt requested right before detach is out of date
+        self.interrupter.take_request();
+        Ok(())
+    }
+
+    /// Send SIGKILL to the debugee and its descendant processes, skip pending stops
+    /// of a killed debugee until its exit. Return an exit code if the debugee exit is observed.
+    fn kill_process_tree(&mut self) -> Result<Option<i32>, Error> {
+        let proc_pid = self.inferior().process.pid();
+        let descendants = self.inferior().process.descendants();
+        sys::kill(proc_pid, Signal::SIGKILL).map_err(|e| Syscall("kill", e.into()))?;
+        // descendants may already exit, so ignore errors
+        descendants.into_iter().for_each(|pid| {
+            _ = sys::kill(pid, Signal::SIGKILL);
+        });
+
+        let inferior = self.inferior_mut();
+        loop {
+            match inferior
                 .debugee
-                .tracer_mut()
-                .resume(TraceContext::new(&[], &self.watchpoints));
+                .trace_until_stop(TraceContext::new(&[], &inferior.watchpoints))
+            {
+                Ok(StopReason::DebugeeExit(code)) => return Ok(Some(code)),
+                Ok(StopReason::NoSuchProcess(_)) | Err(_) => return Ok(None),
+                Ok(_) => {}
+            }
         }
+    }
 
-        self.process = self.process.install()?;
+    /// Create a new (not started) debugee process from the current one.
+    fn reinstall_process(&mut self) -> Result<(), Error> {
+        let inferior = self.inferior_mut();
+        inferior.process = inferior.process.install()?;
+        let pid = inferior.process.pid();
 
-        let new_debugee = self.debugee.extend(self.process.pid());
-        _ = mem::replace(&mut self.debugee, new_debugee);
+        let new_debugee = inferior.debugee.extend(pid);
+        _ = mem::replace(&mut inferior.debugee, new_debugee);
 
         // breakpoints will be enabled later, when StopReason::DebugeeStart state is reached
-        self.breakpoints.update_pid(self.process.pid());
+        inferior.breakpoints.update_pid(pid);
+        inferior.expl_context = ExplorationContext::new_non_running(pid);
+        inferior.step_history.borrow_mut().clear();
 
-        self.hooks.on_process_install(self.process.pid(), None);
-        self.expl_context = ExplorationContext::new_non_running(self.process.pid());
-        self.continue_execution()?;
-        Ok(self.process.pid())
+        self.hooks.on_process_install(pid, None);
+        self.interrupter.set_pid(pid);
+        Ok(())
+    }
+
+    /// Set a file used as a standard input of the debugee, if `None` then debugee
+    /// inherits debugger stdin. Takes effect at the next debugee start or restart.
+    ///
+    /// # Arguments
+    ///
+    /// * `stdin`: path to a file
+    pub fn set_debugee_stdin(&mut self, stdin: Option<PathBuf>) -> Result<(), Error> {
+        if self.inferior().process.stdin() == stdin.as_deref() {
+            return Ok(());
+        }
+        if let Some(path) = &stdin {
+            fs::File::open(path).map_err(|e| Error::StdinRedirect(path.clone(), e))?;
+        }
+        self.inferior_mut().process.set_stdin(stdin);
+
+        // not started process is already forked with a previous stdin, so replace it
+        if self.inferior().debugee.execution_status() == ExecutionStatus::Unload {
+            let proc_pid = self.inferior().process.pid();
+            sys::kill(proc_pid, Signal::SIGKILL).map_err(|e| Syscall("kill", e.into()))?;
+            loop {
+                match sys::waitpid(proc_pid, None).map_err(|e| Error::Waitpid(e.into()))? {
+                    WaitStatus::Exited(_, _) | WaitStatus::Signaled(_, _, _) => break,
+                    _ => {
+                        let tracee_ctl = self.inferior().debugee.tracee_ctl();
+                        if let Some(tracee) = tracee_ctl.tracee_iter().find(|t| t.pid == proc_pid) {
+                            tracee.invalidate_registers();
+                        }
+                        _ = sys::cont(proc_pid, None)
+                    }
+                }
+            }
+            self.reinstall_process()?;
+        }
+        Ok(())
     }
 
-    fn start_debugee_inner(&mut self, force: bool, dry_start: bool) -> Result<(), Error> {
+    fn start_debugee_inner(
+        &mut self,
+        force: bool,
+        dry_start: bool,
+        stop_at: Option<StopAt>,
+    ) -> Result<(), Error> {
         if dry_start {
-            if (self.debugee.is_in_progress() || self.debugee.is_exited()) && !force {
+            if (self.inferior().debugee.is_in_progress() || self.inferior().debugee.is_exited())
+                && !force
+            {
                 return Err(Error::AlreadyRun);
             }
             return Ok(());
         }
 
-        match self.debugee.execution_status() {
-            ExecutionStatus::Unload => {
-                self.continue_execution()?;
-            }
+        self.inferior_mut().stop_at = stop_at;
+        let result = match self.inferior().debugee.execution_status() {
+            ExecutionStatus::Unload => self.continue_execution().map(|_| ()),
             ExecutionStatus::InProgress | ExecutionStatus::Exited if force => {
-                self.restart_debugee()?;
+                self.restart_debugee().map(|_| ())
             }
-            ExecutionStatus::InProgress | ExecutionStatus::Exited => return Err(Error::AlreadyRun),
+            ExecutionStatus::InProgress | ExecutionStatus::Exited => Err(Error::AlreadyRun),
         };
+        // stop request is out of date if debugee stops (or exits) before reaching it
+        self.inferior_mut().stop_at = None;
+        if let Some(addr) = self.inferior_mut().main_brkpt.take() {
+            if self.inferior().debugee.is_in_progress() {
+                self.remove_breakpoint(Address::Relocated(addr))?;
+            }
+        }
 
-        Ok(())
+        result
     }
 
     /// Start and execute debugee.
@@ -663,13 +1174,24 @@ impl Debugger {
     ///
     /// Return error if debugee already run or execution fails.
     pub fn start_debugee(&mut self) -> Result<(), Error> {
-        self.start_debugee_inner(false, false)
+        self.start_debugee_inner(false, false, None)
     }
 
     /// Start and execute debugee. Restart if debugee already started.
     /// Return when debugee stopped or ends.
     pub fn start_debugee_force(&mut self) -> Result<(), Error> {
-        self.start_debugee_inner(true, false)
+        self.start_debugee_inner(true, false, None)
+    }
+
+    /// Start (restart if `force` is true and debugee already started) and execute debugee,
+    /// debugee stops at the program entry point or at the `main` function even if there
+    /// are no breakpoints. Return when debugee stopped or ends.
+    ///
+    /// # Errors
+    ///
+    /// Return error if debugee already run (and `force` is false) or execution fails.
+    pub fn start_debugee_and_stop(&mut self, stop_at: StopAt, force: bool) -> Result<(), Error> {
+        self.start_debugee_inner(force, false, Some(stop_at))
     }
 
     /// Dry start debugee. Return immediately.
@@ -678,7 +1200,12 @@ impl Debugger {
     ///
     /// Return error if debugee already runs.
     pub fn dry_start_debugee(&mut self) -> Result<(), Error> {
-        self.start_debugee_inner(false, true)
+        self.start_debugee_inner(false, true, None)
+    }
+
+    /// Return true if debugee process is started and not exited yet.
+    pub fn is_in_progress(&self) -> bool {
+        self.inferior().debugee.is_in_progress()
     }
 
     /// Continue debugee execution.
@@ -697,6 +1224,7 @@ impl Debugger {
         let regex = Regex::new(regex)?;
 
         Ok(self
+            .inferior()
             .debugee
             .debug_info_all()
             .iter()
@@ -704,10 +1232,148 @@ impl Debugger {
             .collect())
     }
 
+    /// Return functions, static variables and symbol table entries matching a query.
+    /// Query is a regular expression, if it's not a valid one - it is used as a substring.
+    /// Result is sorted by symbol name.
+    ///
+    /// # Arguments
+    ///
+    /// * `query`: regular expression or substring
+    pub fn search_symbols(&self, query: &str) -> Result<Vec<SymbolMatch>, Error> {
+        let regex = Regex::new(query).or_else(|_| Regex::new(&regex::escape(query)))?;
+
+        let mut symbols: Vec<_> = self
+            .inferior()
+            .debugee
+            .debug_info_all()
+            .iter()
+            .flat_map(|dwarf| dwarf.search_symbols(&regex))
+            .collect();
+        symbols.sort_by(|s1, s2| s1.name.cmp(&s2.name).then(s1.addr.cmp(&s2.addr)));
+        symbols.dedup_by(|s1, s2| s1.name == s2.name && s1.addr == s2.addr);
+        Ok(symbols)
+    }
+
+    /// Resolve an address into a module, function, chain of inlined functions and source place.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr`: address in debugee address space
+    pub fn resolve_address(&self, addr: RelocatedAddress) -> Result<AddressInfo, Error> {
+        disable_when_not_stared!(self);
+        self.inferior().debugee.resolve_address(addr)
+    }
+
+    /// Resolve an address in object file into a module, function, chain of inlined functions
+    /// and source place. This method doesn't require a running debugee.
+    ///
+    /// # Arguments
+    ///
+    /// * `module`: path to an object file, `None` for the main executable
+    /// * `addr`: address in object file
+    pub fn resolve_address_in_module(
+        &self,
+        module: Option<&Path>,
+        addr: GlobalAddress,
+    ) -> Result<AddressInfo, Error> {
+        self.inferior()
+            .debugee
+            .resolve_address_in_module(module, addr)
+    }
+
+    /// Describe a memory pointed to by an address: a static variable or a function at this
+    /// address (like `→ static FOO`), otherwise an object file and its section
+    /// (like `(libc.so.6 .text)`) or a name of a memory region (like `([heap])`).
+    /// Return `None` for a null pointer and for addresses in anonymous memory mappings.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr`: address in debugee address space
+    pub fn pointer_annotation(&self, addr: RelocatedAddress) -> Option<String> {
+        if usize::from(addr) == 0 || !self.is_in_progress() {
+            return None;
+        }
+
+        if let Ok(dwarf) = self.inferior().debugee.debug_info(addr) {
+            let global_addr = addr.into_global(&self.inferior().debugee).ok()?;
+            if let Some(symbol) = dwarf.find_object_symbol_by_addr(global_addr) {
+                let offset = usize::from(global_addr) - usize::from(symbol.addr);
+                return Some(format_symbol("→ static", &symbol.name, offset));
+            }
+            if let Some((name, offset)) = self.inferior().debugee.symbolize(addr) {
+                return Some(format_symbol("→ fn", &name, offset));
+            }
+
+            let module = dwarf.pathname().file_name()?.to_string_lossy();
+            return match dwarf.find_section_by_addr(global_addr) {
+                Some(section) => Some(format!("({module} {section})")),
+                None => Some(format!("({module})")),
+            };
+        }
+
+        let pid = self.inferior().debugee.tracee_ctl().proc_pid();
+        let maps = proc_maps::get_process_maps(pid.as_raw()).ok()?;
+        let addr = usize::from(addr);
+        let Some(map) = maps
+            .iter()
+            .find(|map| map.start() <= addr && addr < map.start() + map.size())
+        else {
+            return Some("(unmapped)".to_string());
+        };
+        let region = map.filename()?.file_name()?.to_string_lossy();
+        Some(format!("({region})"))
+    }
+
+    /// Fill annotations of pointers in variables (and pointers nested in them),
+    /// see [`Debugger::pointer_annotation`].
+    ///
+    /// # Arguments
+    ///
+    /// * `variables`: variables to annotate
+    pub fn annotate_pointers(&self, variables: &mut [VariableIR]) {
+        for var in variables {
+            var.for_each_pointer_mut(&mut |pointer| {
+                pointer.annotation = pointer
+                    .value
+                    .and_then(|ptr| self.pointer_annotation(RelocatedAddress::from(ptr as usize)));
+            });
+        }
+    }
+
     /// Return in focus frame information.
     pub fn frame_info(&self) -> Result<FrameInfo, Error> {
         disable_when_not_stared!(self);
-        self.debugee.frame_info(self.exploration_ctx())
+        self.inferior().debugee.frame_info(self.exploration_ctx())
+    }
+
+    /// Return stack memory of in focus frame (between stack pointer and CFA).
+    /// Slots are annotated with saved registers, return address and locations
+    /// of local variables and arguments.
+    pub fn frame_memory(&self) -> Result<FrameMemory, Error> {
+        disable_when_not_stared!(self);
+        let mut memory = self
+            .inferior()
+            .debugee
+            .frame_memory(self.exploration_ctx())?;
+
+        let vars = weak_error!(self.read_local_variables()).unwrap_or_default();
+        let args = weak_error!(self.read_argument(DQE::Variable(VariableSelector::Any)))
+            .unwrap_or_default();
+        for var in vars.iter().chain(args.iter()) {
+            let Some(addr) = var.in_memory_location() else {
+                continue;
+            };
+            let slot = memory.slots.iter_mut().find(|slot| {
+                let slot_addr = slot.addr.as_usize();
+                (slot_addr..slot_addr + mem::size_of::<u64>()).contains(&addr)
+            });
+            if let Some(slot) = slot {
+                slot.annotations
+                    .push(StackSlotAnnotation::Variable(var.name()));
+            }
+        }
+
+        Ok(memory)
     }
 
     /// Set new frame into focus.
@@ -718,12 +1384,12 @@ impl Debugger {
     pub fn set_frame_into_focus(&mut self, num: u32) -> Result<u32, Error> {
         disable_when_not_stared!(self);
         let ctx = self.exploration_ctx();
-        let backtrace = self.debugee.unwind(ctx.pid_on_focus())?;
+        let backtrace = self.inferior().debugee.unwind(ctx.pid_on_focus())?;
         let frame = backtrace.get(num as usize).ok_or(FrameNotFound(num))?;
-        self.expl_context = ExplorationContext::new(
+        self.inferior_mut().expl_context = ExplorationContext::new(
             Location {
                 pc: frame.ip,
-                global_pc: frame.ip.into_global(&self.debugee)?,
+                global_pc: frame.ip.into_global(&self.inferior().debugee)?,
                 pid: ctx.pid_on_focus(),
             },
             num,
@@ -732,29 +1398,175 @@ impl Debugger {
     }
 
     /// Execute `on_step` callback with current exploration context
-    fn execute_on_step_hook(&self) -> Result<(), Error> {
+    /// Call [`EventHook::on_step`] with the current location of in focus thread.
+    pub(crate) fn execute_on_step_hook(&self) -> Result<(), Error> {
         let ctx = self.exploration_ctx();
         let pc = ctx.location().pc;
         let global_pc = ctx.location().global_pc;
-        let dwarf = self.debugee.debug_info(pc)?;
+        let dwarf = self.inferior().debugee.debug_info(pc)?;
         let place = weak_error!(dwarf.find_place_from_pc(global_pc)).flatten();
         let func = weak_error!(dwarf.find_function_by_pc(global_pc))
             .flatten()
             .map(|f| f.die);
-        self.hooks.on_step(pc, place, func).map_err(Hook)
+        self.hooks.on_step(pc, place, func).map_err(Hook)?;
+        self.execute_on_stop_hook(ctx.pid_on_focus(), StopKind::Step);
+        Ok(())
+    }
+
+    /// Increase a hit counter of a user defined breakpoint, call [`EventHook::on_breakpoint`],
+    /// [`EventHook::on_function_return`] (for on-return breakpoints),
+    /// [`EventHook::on_exception`] (for catchpoints) and then [`EventHook::on_stop`].
+    ///
+    /// # Arguments
+    ///
+    /// * `tid`: stopped thread id
+    /// * `pc`: breakpoint address
+    /// * `number`: breakpoint number
+    /// * `returned`: value returned from a function if a program is stopped by
+    ///   an on-return breakpoint, the value itself is `None` if it can't be read
+    fn execute_on_breakpoint_hook(
+        &mut self,
+        tid: Pid,
+        pc: RelocatedAddress,
+        number: u32,
+        returned: Option<Option<VariableIR>>,
+    ) -> Result<(), Error> {
+        self.inferior_mut().breakpoints.record_hit(number);
+        let global_pc = pc.into_global(&self.inferior().debugee)?;
+        let dwarf = self
+            .inferior()
+            .debugee
+            .debug_info(self.exploration_ctx().location().pc)?;
+        let place = weak_error!(dwarf.find_place_from_pc(global_pc)).flatten();
+        let func = weak_error!(dwarf.find_function_by_pc(global_pc))
+            .flatten()
+            .map(|f| f.die);
+        self.hooks
+            .on_breakpoint(pc, number, place, func)
+            .map_err(Hook)?;
+        if let Some(value) = returned {
+            self.hooks
+                .on_function_return(number, value.as_ref())
+                .map_err(Hook)?;
+        }
+        if let Some(event) = self.inferior().breakpoints.catch_event(number) {
+            let type_name = weak_error!(exception::exception_type(tid, event)).flatten();
+            self.hooks
+                .on_exception(number, event, type_name.as_deref())
+                .map_err(Hook)?;
+        }
+        let hits = self.inferior().breakpoints.hit_count(number);
+        self.execute_on_stop_hook(tid, StopKind::Breakpoint { number, hits });
+        Ok(())
+    }
+
+    /// Call watchpoint hooks if a step is interrupted by a watchpoint. If watchpoint hit
+    /// is ignored, then step is considered done at the current place.
+    fn execute_on_watchpoint_hook_or_step(
+        &mut self,
+        tid: Pid,
+        pc: RelocatedAddress,
+        ty: &WatchpointHitType,
+    ) -> Result<(), Error> {
+        if !self.execute_on_watchpoint_hook(tid, pc, ty)? {
+            self.execute_on_step_hook()?;
+        }
+        Ok(())
+    }
+
+    /// Call [`EventHook::on_signal`] and then [`EventHook::on_stop`]. A SIGINT caused
+    /// by [`Interrupter::interrupt`] is reported as an interrupt without `on_signal` call.
+    fn execute_on_signal_hook(&self, tid: Pid, signal: Signal) {
+        if signal == Signal::SIGINT && self.interrupter.take_request() {
+            self.execute_on_stop_hook(tid, StopKind::Interrupt);
+            return;
+        }
+        self.hooks.on_signal(signal);
+        self.execute_on_stop_hook(tid, StopKind::Signal(signal));
+    }
+
+    /// Call [`EventHook::on_stop`] with a summary of a stop at the current location of a thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `tid`: stopped thread id
+    /// * `kind`: stop reason
+    pub(crate) fn execute_on_stop_hook(&self, tid: Pid, kind: StopKind) {
+        let tracee = self.inferior().debugee.get_tracee_ensure(tid);
+        let location = weak_error!(tracee.location(&self.inferior().debugee));
+        let (place, function) = location
+            .and_then(|location| {
+                let dwarf = weak_error!(self.inferior().debugee.debug_info(location.pc))?;
+                let place = weak_error!(dwarf.find_place_from_pc(location.global_pc))
+                    .flatten()
+                    .map(|place| place.to_owned());
+                let function = weak_error!(dwarf.find_function_by_pc(location.global_pc))
+                    .flatten()
+                    .and_then(|func| func.full_name());
+                Some((place, function))
+            })
+            .unwrap_or_default();
+        let summary = StopSummary {
+            time: SystemTime::now(),
+            tid,
+            thread_num: tracee.number,
+            pc: location.map(|location| location.pc),
+            kind,
+            function,
+            place,
+        };
+        self.hooks.on_stop(&summary);
+        let not_stopped = self.inferior().debugee.tracer().not_stopped_tracees();
+        if !not_stopped.is_empty() {
+            self.hooks.on_threads_not_stopped(&not_stopped);
+        }
+        self.inferior().step_history.borrow_mut().record(summary);
+    }
+
+    /// Return debugee stops recorded in a thread (or in all threads if `tid` is `None`),
+    /// stops are grouped by a thread and ordered from the oldest one.
+    /// At most [`STEP_HISTORY_LIMIT`] last stops are remembered for each thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `tid`: thread id, `None` for all threads
+    pub fn step_history(&self, tid: Option<Pid>) -> Vec<StopSummary> {
+        self.inferior().step_history.borrow().stops(tid)
     }
 
     /// Do a single step (until debugee reaches a different source line).
     ///
     /// **! change exploration context**
     pub fn step_into(&mut self) -> Result<(), Error> {
+        self.step_into_n(1, StepGranularity::Line)
+    }
+
+    /// Do `count` steps into as a single operation, hooks are called once at the end.
+    /// Steps are stopped early at signals, watchpoints and user defined breakpoints.
+    ///
+    /// # Arguments
+    ///
+    /// * `count`: number of steps
+    /// * `granularity`: stop at a different source line or at a next statement
+    ///
+    /// **! change exploration context**
+    pub fn step_into_n(&mut self, count: u32, granularity: StepGranularity) -> Result<(), Error> {
         disable_when_not_stared!(self);
         self.expl_ctx_restore_frame()?;
 
-        match self.step_in()? {
+        let mut result = StepResult::Done;
+        for _ in 0..count.max(1) {
+            result = self.step_in(granularity)?;
+            if !matches!(result, StepResult::Done) || self.at_user_breakpoint() {
+                break;
+            }
+        }
+        self.rebind_watchpoints()?;
+
+        match result {
             StepResult::Done => self.execute_on_step_hook(),
             StepResult::SignalInterrupt { signal, quiet } if !quiet => {
-                self.hooks.on_signal(signal);
+                self.execute_on_signal_hook(self.exploration_ctx().pid_on_focus(), signal);
                 Ok(())
             }
             StepResult::WatchpointInterrupt {
@@ -762,7 +1574,7 @@ impl Debugger {
                 addr,
                 ref ty,
                 quiet,
-            } if !quiet => self.execute_on_watchpoint_hook(pid, addr, ty),
+            } if !quiet => self.execute_on_watchpoint_hook_or_step(pid, addr, ty),
             _ => Ok(()),
         }
     }
@@ -771,16 +1583,33 @@ impl Debugger {
     ///
     /// **! change exploration context**
     pub fn stepi(&mut self) -> Result<(), Error> {
+        self.stepi_n(1)
+    }
+
+    /// Do `count` instruction steps as a single operation, hooks are called once at the end.
+    /// Steps are stopped early at signals, watchpoints and user defined breakpoints.
+    ///
+    /// **! change exploration context**
+    pub fn stepi_n(&mut self, count: u32) -> Result<(), Error> {
         disable_when_not_stared!(self);
         self.expl_ctx_restore_frame()?;
 
-        match self.single_step_instruction()? {
-            Some(StopReason::SignalStop(_, sign)) => {
-                self.hooks.on_signal(sign);
+        let mut stop_reason = None;
+        for _ in 0..count.max(1) {
+            stop_reason = self.single_step_instruction()?;
+            if stop_reason.is_some() || self.at_user_breakpoint() {
+                break;
+            }
+        }
+        self.rebind_watchpoints()?;
+
+        match stop_reason {
+            Some(StopReason::SignalStop(pid, sign)) => {
+                self.execute_on_signal_hook(pid, sign);
                 Ok(())
             }
             Some(StopReason::Watchpoint(pid, addr, ref ty)) => {
-                self.execute_on_watchpoint_hook(pid, addr, ty)
+                self.execute_on_watchpoint_hook_or_step(pid, addr, ty)
             }
             _ => self.execute_on_step_hook(),
         }
@@ -789,7 +1618,18 @@ impl Debugger {
     /// Return list of currently running debugee threads.
     pub fn thread_state(&self) -> Result<Vec<ThreadSnapshot>, Error> {
         disable_when_not_stared!(self);
-        self.debugee.thread_state(self.exploration_ctx())
+        let mut threads = self
+            .inferior()
+            .debugee
+            .thread_state(self.exploration_ctx())?;
+        for thread in threads.iter_mut() {
+            thread.label = self
+                .oracles
+                .values()
+                .filter(|(_, installed)| *installed)
+                .find_map(|(oracle, _)| oracle.thread_label(thread));
+        }
+        Ok(threads)
     }
 
     /// Sets the thread into focus.
@@ -799,7 +1639,7 @@ impl Debugger {
     /// * `num`: thread number
     pub fn set_thread_into_focus(&mut self, num: u32) -> Result<Tracee, Error> {
         disable_when_not_stared!(self);
-        let tracee = self.debugee.get_tracee_by_num(num)?;
+        let tracee = self.inferior().debugee.get_tracee_by_num(num)?;
         self.expl_ctx_switch_thread(tracee.pid)?;
         Ok(tracee)
     }
@@ -811,7 +1651,41 @@ impl Debugger {
     /// * `pid`: thread id
     pub fn backtrace(&self, pid: Pid) -> Result<Backtrace, Error> {
         disable_when_not_stared!(self);
-        self.debugee.unwind(pid)
+        self.inferior().debugee.unwind(pid)
+    }
+
+    /// Read arguments of each function in a backtrace, arguments are evaluated with
+    /// registers restored at the corresponding frame. Arguments of frames without debug
+    /// information are left unresolved.
+    ///
+    /// # Arguments
+    ///
+    /// * `pid`: thread id
+    /// * `backtrace`: thread backtrace
+    pub fn resolve_frame_arguments(&self, pid: Pid, backtrace: &mut Backtrace) {
+        for (num, frame) in backtrace.iter_mut().enumerate() {
+            let Some(global_pc) = weak_error!(frame.ip.into_global(&self.inferior().debugee))
+            else {
+                continue;
+            };
+            let ctx = ExplorationContext::new(
+                Location {
+                    pc: frame.ip,
+                    global_pc,
+                    pid,
+                },
+                num as u32,
+            );
+            let evaluator = variable::select::SelectExpressionEvaluator::new(
+                self,
+                DQE::Variable(VariableSelector::Any),
+            )
+            .with_context(&ctx);
+            frame.args = evaluator
+                .evaluate_on_arguments()
+                .ok()
+                .map(|args| args.into_iter().map(|res| res.variable).collect());
+        }
     }
 
     /// Read N bytes from a debugee process.
@@ -822,43 +1696,146 @@ impl Debugger {
     /// * `read_n`: read byte count
     pub fn read_memory(&self, addr: usize, read_n: usize) -> Result<Vec<u8>, Error> {
         disable_when_not_stared!(self);
-        read_memory_by_pid(self.debugee.tracee_ctl().proc_pid(), addr, read_n).map_err(Ptrace)
+        let mut data = read_memory_by_pid(
+            self.inferior().debugee.tracee_ctl().proc_pid(),
+            addr,
+            read_n,
+        )?;
+        self.inferior()
+            .breakpoints
+            .mask_memory(RelocatedAddress::from(addr), &mut data);
+        Ok(data)
     }
 
-    /// Write sizeof(uintptr_t) bytes in debugee address space
+    /// Write sizeof(usize) bytes in debugee address space
     ///
     /// # Arguments
     ///
     /// * `addr`: address to write
     /// * `value`: value to write
-    pub fn write_memory(&self, addr: uintptr_t, value: uintptr_t) -> Result<(), Error> {
+    pub fn write_memory(&self, addr: usize, value: usize) -> Result<(), Error> {
         disable_when_not_stared!(self);
-        unsafe {
-            sys::ptrace::write(
-                self.debugee.tracee_ctl().proc_pid(),
-                addr as *mut c_void,
-                value as *mut c_void,
-            )
-            .map_err(Ptrace)
+        Ok(sys::write(
+            self.inferior().debugee.tracee_ctl().proc_pid(),
+            addr,
+            value as i64,
+        )?)
+    }
+
+    /// Write bytes in debugee address space. Memory is written by machine words,
+    /// rest bytes of a partially overwritten word are preserved.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr`: address of the first byte
+    /// * `data`: bytes to write
+    pub fn write_memory_bytes(&self, addr: usize, data: &[u8]) -> Result<(), Error> {
+        disable_when_not_stared!(self);
+        const WORD_SIZE: usize = mem::size_of::<usize>();
+        let pid = self.inferior().debugee.tracee_ctl().proc_pid();
+        for (i, chunk) in data.chunks(WORD_SIZE).enumerate() {
+            let word_addr = addr + i * WORD_SIZE;
+            let mut word = [0; WORD_SIZE];
+            if chunk.len() < WORD_SIZE {
+                let current = read_memory_by_pid(pid, word_addr, WORD_SIZE)?;
+                word.copy_from_slice(&current);
+            }
+            word[..chunk.len()].copy_from_slice(chunk);
+            sys::write(pid, word_addr, i64::from_ne_bytes(word))?;
+        }
+        Ok(())
+    }
+
+    /// Move to higher stack frame. Return a value returned from the function (if it can be read).
+    pub fn step_out(&mut self) -> Result<Option<VariableIR>, Error> {
+        self.step_out_n(1)
+    }
+
+    /// Move `count` stack frames higher as a single operation, hooks are called once at the end.
+    /// Steps are stopped early at user defined breakpoints.
+    /// Return a value returned from the last finished function, `None` if steps are stopped
+    /// early or if the value can't be read.
+    pub fn step_out_n(&mut self, count: u32) -> Result<Option<VariableIR>, Error> {
+        disable_when_not_stared!(self);
+        self.expl_ctx_restore_frame()?;
+        let mut callee = None;
+        for _ in 0..count.max(1) {
+            let location = self.exploration_ctx().location();
+            self.step_out_frame()?;
+            if self.at_user_breakpoint() {
+                callee = None;
+                break;
+            }
+            callee = Some(location);
         }
+        let return_value = callee
+            .and_then(|callee| weak_error!(self.read_return_value(callee)))
+            .flatten();
+        self.rebind_watchpoints()?;
+        self.execute_on_step_hook()?;
+        Ok(return_value)
+    }
+
+    /// Abort execution of the current function and return to the caller.
+    /// Caller registers are restored from the unwind information. Debugee stops
+    /// right after the call instruction in the caller.
+    ///
+    /// # Arguments
+    ///
+    /// * `value`: value returned from a function, `None` if return register must stay untouched
+    pub fn return_early(&mut self, value: Option<ReturnValue>) -> Result<(), Error> {
+        disable_when_not_stared!(self);
+        self.expl_ctx_restore_frame()?;
+        self.pop_frame(value)?;
+        self.rebind_watchpoints()?;
+        self.execute_on_step_hook()
     }
 
-    /// Move to higher stack frame.
-    pub fn step_out(&mut self) -> Result<(), Error> {
+    /// Move program counter to the first instruction of a source line without executing
+    /// instructions between the current and the target places.
+    ///
+    /// # Arguments
+    ///
+    /// * `file`: file name template, current file if `None`
+    /// * `line`: target line number
+    /// * `force`: allow target line outside the current function
+    pub fn jump(&mut self, file: Option<&str>, line: u64, force: bool) -> Result<(), Error> {
         disable_when_not_stared!(self);
         self.expl_ctx_restore_frame()?;
-        self.step_out_frame()?;
+        self.jump_to_line(file, line, force)?;
+        self.rebind_watchpoints()?;
         self.execute_on_step_hook()
     }
 
     /// Do debugee step (over subroutine calls to).
     pub fn step_over(&mut self) -> Result<(), Error> {
+        self.step_over_n(1, StepGranularity::Line)
+    }
+
+    /// Do `count` steps over as a single operation, hooks are called once at the end.
+    /// Steps are stopped early at signals, watchpoints and user defined breakpoints.
+    ///
+    /// # Arguments
+    ///
+    /// * `count`: number of steps
+    /// * `granularity`: stop at a different source line or at a next statement
+    pub fn step_over_n(&mut self, count: u32, granularity: StepGranularity) -> Result<(), Error> {
         disable_when_not_stared!(self);
         self.expl_ctx_restore_frame()?;
-        match self.step_over_any()? {
+
+        let mut result = StepResult::Done;
+        for _ in 0..count.max(1) {
+            result = self.step_over_any(granularity)?;
+            if !matches!(result, StepResult::Done) || self.at_user_breakpoint() {
+                break;
+            }
+        }
+        self.rebind_watchpoints()?;
+
+        match result {
             StepResult::Done => self.execute_on_step_hook(),
             StepResult::SignalInterrupt { signal, quiet } if !quiet => {
-                self.hooks.on_signal(signal);
+                self.execute_on_signal_hook(self.exploration_ctx().pid_on_focus(), signal);
                 Ok(())
             }
             StepResult::WatchpointInterrupt {
@@ -866,7 +1843,7 @@ impl Debugger {
                 addr,
                 ref ty,
                 quiet,
-            } if !quiet => self.execute_on_watchpoint_hook(pid, addr, ty),
+            } if !quiet => self.execute_on_watchpoint_hook_or_step(pid, addr, ty),
             _ => Ok(()),
         }
     }
@@ -896,6 +1873,61 @@ impl Debugger {
         Ok(eval_result.into_iter().map(|res| res.variable).collect())
     }
 
+    /// Return all declarations of a local variable in the current function (a variable may be
+    /// shadowed by a declaration with the same name), declarations are ordered from the outer one.
+    /// A live declaration (the one a variable name refers to) is the innermost one.
+    ///
+    /// # Arguments
+    ///
+    /// * `name`: variable name
+    pub fn variable_declarations(&self, name: &str) -> Result<Vec<VariableDeclaration>, Error> {
+        disable_when_not_stared!(self);
+        let evaluator = variable::select::SelectExpressionEvaluator::new(
+            self,
+            DQE::Variable(VariableSelector::Name {
+                var_name: name.to_string(),
+                only_local: true,
+            }),
+        );
+        evaluator.evaluate_declarations()
+    }
+
+    /// Same as [`Debugger::read_variable`] but pointers in read variables are followed:
+    /// pointed values are read (see [`variable::PointerVariable::target`]),
+    /// pointers to values which contain them are marked as cycles.
+    ///
+    /// # Arguments
+    ///
+    /// * `select_expr`: data query expression
+    /// * `max_depth`: maximum number of pointers followed in a row, `None` for unlimited
+    pub fn read_variable_following_pointers(
+        &self,
+        select_expr: DQE,
+        max_depth: Option<usize>,
+    ) -> Result<Vec<VariableIR>, Error> {
+        disable_when_not_stared!(self);
+        let evaluator = variable::select::SelectExpressionEvaluator::new(self, select_expr)
+            .with_pointer_depth(max_depth);
+        let eval_result = evaluator.evaluate()?;
+        Ok(eval_result.into_iter().map(|res| res.variable).collect())
+    }
+
+    /// Save a value in a value history, return its history number.
+    /// Saved values may be used in data query expressions (like `$1.field`).
+    ///
+    /// # Arguments
+    ///
+    /// * `value`: value to save
+    pub fn record_value(&mut self, value: VariableIR) -> usize {
+        self.value_history.push(value);
+        self.value_history.len() - 1
+    }
+
+    /// Return values from a value history, value index is a history number.
+    pub fn value_history(&self) -> &[VariableIR] {
+        &self.value_history
+    }
+
     ///  Reads any variable from the current thread, uses a select expression to filter variables
     /// and return their names.
     ///
@@ -908,6 +1940,44 @@ impl Debugger {
         evaluator.evaluate_names()
     }
 
+    /// Reads types of any variable from the current thread, uses a select expression to filter
+    /// variables. Return pairs of variable name and rendered type declaration.
+    ///
+    /// # Arguments
+    ///
+    /// * `select_expr`: data query expression
+    pub fn read_variable_types(&self, select_expr: DQE) -> Result<Vec<(String, String)>, Error> {
+        disable_when_not_stared!(self);
+        let evaluator = variable::select::SelectExpressionEvaluator::new(self, select_expr);
+        evaluator.evaluate_types()
+    }
+
+    /// Same as [`Debugger::read_variable_types`] but for arguments of the current function.
+    ///
+    /// # Arguments
+    ///
+    /// * `select_expr`: data query expression
+    pub fn read_argument_types(&self, select_expr: DQE) -> Result<Vec<(String, String)>, Error> {
+        disable_when_not_stared!(self);
+        let evaluator = variable::select::SelectExpressionEvaluator::new(self, select_expr);
+        evaluator.evaluate_on_arguments_types()
+    }
+
+    /// Find a type by name and render its declaration as a rust-like source code
+    /// (with fields offsets and sizes, enum discriminants, etc.).
+    ///
+    /// # Arguments
+    ///
+    /// * `type_name`: type name
+    pub fn read_type(&self, type_name: &str) -> Result<String, Error> {
+        disable_when_not_stared!(self);
+        let evaluator = variable::select::SelectExpressionEvaluator::new(
+            self,
+            DQE::Variable(VariableSelector::Any),
+        );
+        evaluator.evaluate_type_by_name(type_name)
+    }
+
     /// Reads any argument from the current function, uses a select expression to filter variables
     /// and fetch their properties (such as structure fields or array elements).
     ///
@@ -921,6 +1991,25 @@ impl Debugger {
         Ok(eval_result.into_iter().map(|res| res.variable).collect())
     }
 
+    /// Same as [`Debugger::read_argument`] but pointers in read arguments are followed,
+    /// see [`Debugger::read_variable_following_pointers`].
+    ///
+    /// # Arguments
+    ///
+    /// * `select_expr`: data query expression
+    /// * `max_depth`: maximum number of pointers followed in a row, `None` for unlimited
+    pub fn read_argument_following_pointers(
+        &self,
+        select_expr: DQE,
+        max_depth: Option<usize>,
+    ) -> Result<Vec<VariableIR>, Error> {
+        disable_when_not_stared!(self);
+        let evaluator = variable::select::SelectExpressionEvaluator::new(self, select_expr)
+            .with_pointer_depth(max_depth);
+        let eval_result = evaluator.evaluate_on_arguments()?;
+        Ok(eval_result.into_iter().map(|res| res.variable).collect())
+    }
+
     /// Reads any argument from the current function, uses a select expression to filter arguments
     /// and return their names.
     ///
@@ -943,7 +2032,44 @@ impl Debugger {
 
         let r = Register::from_str(register_name)
             .map_err(|_| RegisterNameNotFound(register_name.into()))?;
-        Ok(RegisterMap::current(self.exploration_ctx().pid_on_focus())?.value(r))
+        let pid = self.exploration_ctx().pid_on_focus();
+        Ok(self
+            .inferior()
+            .debugee
+            .tracee_ctl()
+            .registers(pid)?
+            .value(r))
+    }
+
+    /// Return register value at the selected frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `register_name`: x86-64 register name (ex: `rsp`)
+    pub fn get_frame_register_value(&self, register_name: &str) -> Result<u64, Error> {
+        disable_when_not_stared!(self);
+
+        let r = Register::from_str(register_name)
+            .map_err(|_| RegisterNameNotFound(register_name.into()))?;
+        self.inferior()
+            .debugee
+            .register_value(self.exploration_ctx(), r)
+    }
+
+    /// Return vector register value in little-endian byte order.
+    ///
+    /// # Arguments
+    ///
+    /// * `register_name`: SSE or AVX register name (ex: `xmm0`, `ymm1`)
+    pub fn get_vector_register_value(
+        &self,
+        register_name: &str,
+    ) -> Result<(VectorRegister, Vec<u8>), Error> {
+        disable_when_not_stared!(self);
+
+        let r = VectorRegister::from_str(register_name)?;
+        let bytes = register::read_vector_register(self.exploration_ctx().pid_on_focus(), r)?;
+        Ok((r, bytes))
     }
 
     /// Return registers dump for on focus thread at instruction defined by pc.
@@ -956,10 +2082,10 @@ impl Debugger {
         pc: RelocatedAddress,
     ) -> Result<DwarfRegisterMap, Error> {
         disable_when_not_stared!(self);
-        let unwinder = DwarfUnwinder::new(&self.debugee);
+        let unwinder = DwarfUnwinder::new(&self.inferior().debugee);
         let location = Location {
             pc,
-            global_pc: pc.into_global(&self.debugee)?,
+            global_pc: pc.into_global(&self.inferior().debugee)?,
             pid: self.exploration_ctx().pid_on_focus(),
         };
         Ok(unwinder
@@ -981,86 +2107,195 @@ impl Debugger {
         disable_when_not_stared!(self);
 
         let in_focus_pid = self.exploration_ctx().pid_on_focus();
-        let mut map = RegisterMap::current(in_focus_pid)?;
+        let mut map = self
+            .inferior()
+            .debugee
+            .tracee_ctl()
+            .registers(in_focus_pid)?;
         map.update(
             Register::try_from(register_name)
                 .map_err(|_| RegisterNameNotFound(register_name.into()))?,
             val,
         );
-        map.persist(in_focus_pid)
+        self.inferior()
+            .debugee
+            .tracee_ctl()
+            .set_registers(in_focus_pid, map)
     }
 
     /// Return list of known files income from dwarf parser.
     pub fn known_files(&self) -> impl Iterator<Item = &PathBuf> {
-        self.debugee
+        self.inferior()
+            .debugee
             .debug_info_all()
             .into_iter()
             .filter_map(|dwarf| dwarf.known_files().ok())
             .flatten()
     }
 
+    /// Return a source code of a file if it is embedded into debug information
+    /// (DWARF 5 `DW_LNCT_LLVM_source`, see `-gembed-source` clang option).
+    pub fn embedded_source(&self, file: &Path) -> Option<Arc<str>> {
+        self.inferior()
+            .debugee
+            .debug_info_all()
+            .into_iter()
+            .find_map(|dwarf| dwarf.embedded_source(file).ok().flatten())
+    }
+
     /// Return a list of shared libraries.
     pub fn shared_libs(&self) -> Vec<RegionInfo> {
-        self.debugee.dump_mapped_regions()
+        self.inferior().debugee.dump_mapped_regions()
     }
 
     /// Return a list of disassembled instruction for a function in focus.
     pub fn disasm(&self) -> Result<FunctionAssembly, Error> {
         disable_when_not_stared!(self);
-        self.debugee.disasm(
+        self.inferior().debugee.disasm(
             self.exploration_ctx(),
-            &self.breakpoints.active_breakpoints(),
+            &self.inferior().breakpoints.active_breakpoints(),
+        )
+    }
+
+    /// Return an instruction at the program counter of the thread in focus.
+    pub fn current_instruction(&self) -> Result<Option<DecodedInstruction>, Error> {
+        disable_when_not_stared!(self);
+        self.inferior().debugee.decode_instruction(
+            self.exploration_ctx().location().pc,
+            &self.inferior().breakpoints.active_breakpoints(),
         )
     }
 
+    /// Return `count` consecutive instructions starting from the address.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr`: address of a first instruction
+    /// * `count`: number of instructions
+    pub fn decode_instructions(
+        &self,
+        addr: RelocatedAddress,
+        count: usize,
+    ) -> Result<Vec<DecodedInstruction>, Error> {
+        disable_when_not_stared!(self);
+        let breakpoints = self.inferior().breakpoints.active_breakpoints();
+        let mut instructions = Vec::with_capacity(count);
+        let mut addr = addr;
+        for _ in 0..count {
+            let Some(instruction) = self
+                .inferior()
+                .debugee
+                .decode_instruction(addr, &breakpoints)?
+            else {
+                break;
+            };
+            addr = addr.offset(instruction.bytes.len() as isize);
+            instructions.push(instruction);
+        }
+        Ok(instructions)
+    }
+
     /// Return two place descriptors, at the start and at the end of the current function.
     pub fn current_function_range(&self) -> Result<FunctionRange, Error> {
         disable_when_not_stared!(self);
-        self.debugee.function_range(self.exploration_ctx())
+        self.inferior()
+            .debugee
+            .function_range(self.exploration_ctx())
     }
 }
 
 impl Drop for Debugger {
     fn drop(&mut self) {
-        if self.process.is_external() {
-            _ = self.breakpoints.disable_all_breakpoints(&self.debugee);
-            // drain all watchpoints before terminating the process
-            self.watchpoints
-                .clear_all(self.debugee.tracee_ctl(), &mut self.breakpoints);
+        self.release_current_inferior();
+        let current = self.current;
+        for idx in (0..self.inferiors.len())
+            .rev()
+            .filter(|&idx| idx != current)
+        {
+            self.set_current_inferior(idx);
+            self.release_current_inferior();
+        }
+    }
+}
 
-            let current_tids: Vec<Pid> = self
-                .debugee
-                .tracee_ctl()
-                .tracee_iter()
-                .map(|t| t.pid)
-                .collect();
-
-            if !current_tids.is_empty() {
-                current_tids.iter().for_each(|tid| {
-                    sys::ptrace::detach(*tid, None).expect("detach debugee");
-                });
+impl Debugger {
+    /// Detach all tracees of the current inferior and continue the process.
+    fn detach_tracees(&mut self) {
+        let current_tids: Vec<Pid> = self
+            .inferior()
+            .debugee
+            .tracee_ctl()
+            .tracee_iter()
+            .map(|t| t.pid)
+            .collect();
+
+        if !current_tids.is_empty() {
+            // process may be already killed (for example, while the inferior is parked)
+            current_tids.iter().for_each(|tid| {
+                match sys::detach(*tid, None) {
+                    Ok(()) => {}
+                    Err(Errno::ESRCH) => {
+                        // killed tracee isn't in a ptrace-stop yet, it stops at exit soon
+                        let tracee = self.inferior().debugee.get_tracee_ensure(*tid);
+                        if let Ok(Some(WaitStatus::PtraceEvent(..))) =
+                            tracee.wait_one_timeout(Duration::from_millis(100))
+                        {
+                            _ = sys::detach(*tid, None);
+                        }
+                    }
+                    Err(e) => panic!("detach debugee: {e}"),
+                };
+            });
+
+            match sys::kill(
+                self.inferior().debugee.tracee_ctl().proc_pid(),
+                Signal::SIGCONT,
+            ) {
+                Ok(()) | Err(Errno::ESRCH) => {}
+                Err(e) => panic!("kill debugee: {e}"),
+            };
+        }
+    }
 
-                signal::kill(self.debugee.tracee_ctl().proc_pid(), Signal::SIGCONT)
-                    .expect("kill debugee");
-            }
+    /// Detach the current inferior process if it is external, kill it otherwise.
+    fn release_current_inferior(&mut self) {
+        if self.inferior().process.is_external() {
+            let inferior = self.inferior_mut();
+            _ = inferior
+                .breakpoints
+                .disable_all_breakpoints(&inferior.debugee);
+            // drain all watchpoints before terminating the process
+            inferior
+                .watchpoints
+                .clear_all(inferior.debugee.tracee_ctl(), &mut inferior.breakpoints);
 
+            self.detach_tracees();
             return;
         }
 
-        match self.debugee.execution_status() {
+        match self.inferior().debugee.execution_status() {
             ExecutionStatus::Unload => {
-                signal::kill(self.debugee.tracee_ctl().proc_pid(), Signal::SIGKILL)
-                    .expect("kill debugee");
-                waitpid(self.debugee.tracee_ctl().proc_pid(), None).expect("waiting child");
+                sys::kill(
+                    self.inferior().debugee.tracee_ctl().proc_pid(),
+                    Signal::SIGKILL,
+                )
+                .expect("kill debugee");
+                sys::waitpid(self.inferior().debugee.tracee_ctl().proc_pid(), None)
+                    .expect("waiting child");
             }
             ExecutionStatus::InProgress => {
                 // ignore all possible errors on breakpoints disabling
-                _ = self.breakpoints.disable_all_breakpoints(&self.debugee);
+                let inferior = self.inferior_mut();
+                _ = inferior
+                    .breakpoints
+                    .disable_all_breakpoints(&inferior.debugee);
                 // drain all watchpoints before terminating the process
-                self.watchpoints
-                    .clear_all(self.debugee.tracee_ctl(), &mut self.breakpoints);
+                inferior
+                    .watchpoints
+                    .clear_all(inferior.debugee.tracee_ctl(), &mut inferior.breakpoints);
 
                 let current_tids: Vec<Pid> = self
+                    .inferior()
                     .debugee
                     .tracee_ctl()
                     .tracee_iter()
@@ -1071,22 +2306,30 @@ impl Drop for Debugger {
                 // continue all threads with SIGSTOP
                 let prepare_stopped: Vec<_> = current_tids
                     .into_iter()
-                    .filter(|&tid| sys::ptrace::cont(tid, Signal::SIGSTOP).is_ok())
+                    .filter(|&tid| sys::cont(tid, Signal::SIGSTOP).is_ok())
                     .collect();
                 let stopped: Vec<_> = prepare_stopped
                     .into_iter()
-                    .filter(|&tid| waitpid(tid, None).is_ok())
+                    .filter(|&tid| sys::waitpid(tid, None).is_ok())
                     .collect();
                 // detach ptrace
                 stopped.into_iter().for_each(|tid| {
-                    sys::ptrace::detach(tid, None).expect("detach tracee");
+                    sys::detach(tid, None).expect("detach tracee");
+                });
+                // kill debugee process and all processes spawned by it
+                let descendants = self.inferior().process.descendants();
+                sys::kill(
+                    self.inferior().debugee.tracee_ctl().proc_pid(),
+                    Signal::SIGKILL,
+                )
+                .expect("kill debugee");
+                descendants.into_iter().for_each(|pid| {
+                    _ = sys::kill(pid, Signal::SIGKILL);
                 });
-                // kill debugee process
-                signal::kill(self.debugee.tracee_ctl().proc_pid(), Signal::SIGKILL)
-                    .expect("kill debugee");
                 let wait_result = loop {
-                    let wait_result = waitpid(Pid::from_raw(-1), None).expect("waiting debugee");
-                    if wait_result.pid() == Some(self.debugee.tracee_ctl().proc_pid()) {
+                    let wait_result =
+                        sys::waitpid(Pid::from_raw(-1), None).expect("waiting debugee");
+                    if wait_result.pid() == Some(self.inferior().debugee.tracee_ctl().proc_pid()) {
                         break wait_result;
                     }
                 };
@@ -1102,22 +2345,78 @@ impl Drop for Debugger {
 }
 
 /// Read N bytes from `PID` process.
-pub fn read_memory_by_pid(pid: Pid, addr: usize, read_n: usize) -> Result<Vec<u8>, nix::Error> {
+pub fn read_memory_by_pid(pid: Pid, addr: usize, read_n: usize) -> Result<Vec<u8>, SyscallError> {
     let mut read_reminder = read_n as isize;
     let mut result = Vec::with_capacity(read_n);
 
-    let single_read_size = mem::size_of::<c_long>();
+    let single_read_size = mem::size_of::<i64>();
 
-    let mut addr = addr as *mut c_long;
+    let mut addr = addr;
     while read_reminder > 0 {
-        let value = sys::ptrace::read(pid, addr as *mut c_void)?;
+        let value = sys::read(pid, addr)?;
         result.extend(value.to_ne_bytes().into_iter().take(read_reminder as usize));
 
         read_reminder -= single_read_size as isize;
-        addr = unsafe { addr.offset(1) };
+        addr += single_read_size;
     }
 
     debug_assert!(result.len() == read_n);
 
     Ok(result)
 }
+
+/// Render a symbol with a prefix and an offset into a symbol (if not zero),
+/// crate hashes (like `::h6a9e3c1b2d4f5e6a` suffix or `[fa52a15f291d29ae]` disambiguator)
+/// are stripped from a symbol name.
+fn format_symbol(prefix: &str, name: &str, offset: usize) -> String {
+    let is_hash = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_hexdigit());
+    let name = match name.rsplit_once("::h") {
+        Some((path, hash)) if hash.len() == 16 && is_hash(hash) => path,
+        _ => name,
+    };
+
+    let mut stripped = String::with_capacity(name.len());
+    let mut rest = name;
+    while let Some(start) = rest.find('[') {
+        stripped.push_str(&rest[..start]);
+        match rest[start..].find(']') {
+            Some(end) if is_hash(&rest[start + 1..start + end]) => {
+                rest = &rest[start + end + 1..];
+            }
+            _ => {
+                stripped.push('[');
+                rest = &rest[start + 1..];
+            }
+        }
+    }
+    stripped.push_str(rest);
+
+    if offset == 0 {
+        format!("{prefix} {stripped}")
+    } else {
+        format!("{prefix} {stripped}+{offset:#x}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_format_symbol() {
+        assert_eq!(
+            format_symbol("→ static", "vars::GLOB_1::h0123456789abcdef", 0),
+            "→ static vars::GLOB_1"
+        );
+        assert_eq!(
+            format_symbol("→ static", "vars[fa52a15f291d29ae]::GLOB_2", 0),
+            "→ static vars::GLOB_2"
+        );
+        assert_eq!(format_symbol("→ fn", "main", 4), "→ fn main+0x4");
+        assert_eq!(format_symbol("→ fn", "<[u8]>::len", 0), "→ fn <[u8]>::len");
+        assert_eq!(
+            format_symbol("→ fn", "vars::hash::hello", 0),
+            "→ fn vars::hash::hello"
+        );
+    }
+}
@@ -1,19 +1,23 @@
 use crate::debugger::error::Error;
-use crate::debugger::error::Error::{Ptrace, Waitpid};
-use nix::sys;
+use crate::debugger::error::Error::Waitpid;
+use crate::debugger::sys;
+use crate::debugger::sys::WaitStatus::PtraceEvent;
+use crate::debugger::{Pid, Signal};
 use nix::sys::personality::Persona;
-use nix::sys::ptrace::Options;
-use nix::sys::signal::{SIGSTOP, SIGTRAP};
-use nix::sys::wait::WaitStatus::PtraceEvent;
-use nix::sys::wait::{waitpid, WaitPidFlag};
-use nix::unistd::{fork, ForkResult, Pid};
-use os_pipe::PipeWriter;
+use nix::sys::signal::SIGSTOP;
+use nix::sys::wait::WaitPidFlag;
+use nix::sys::{personality, signal};
+use nix::unistd::{fork, ForkResult};
 use std::collections::HashSet;
+use std::fs::File;
 use std::iter;
 use std::marker::PhantomData;
 use std::os::unix::process::CommandExt;
+use std::path::{Path, PathBuf};
 use std::process::Command;
-use sysinfo::{RefreshKind, System};
+use sysinfo::{ProcessRefreshKind, RefreshKind, System};
+
+pub use os_pipe::{pipe, PipeReader, PipeWriter};
 
 /// Process state.
 pub trait State {}
@@ -40,6 +44,8 @@ pub struct Child<S: State> {
     stdout: PipeWriter,
     stderr: PipeWriter,
     args: Vec<String>,
+    /// File used as a standard input, process inherits debugger stdin if `None`.
+    stdin: Option<PathBuf>,
     pid: Option<Pid>,
     external_info: Option<ExternalInfo>,
     _p: PhantomData<S>,
@@ -65,6 +71,7 @@ impl Child<Template> {
             stderr,
             program: program.into(),
             args: args.into_iter().map(Into::into).collect(),
+            stdin: None,
             pid: None,
             external_info: None,
             _p: PhantomData,
@@ -78,6 +85,27 @@ impl Child<Installed> {
         self.pid.unwrap()
     }
 
+    /// Return pids of all processes spawned by the process (children, children of children
+    /// and so on). Threads are not included (only thread group leaders have a task list).
+    pub fn descendants(&self) -> Vec<Pid> {
+        let sys = System::new_with_specifics(
+            RefreshKind::new().with_processes(ProcessRefreshKind::new()),
+        );
+
+        let mut descendants = vec![];
+        let mut parents = vec![sysinfo::Pid::from_u32(self.pid().as_raw() as u32)];
+        while let Some(parent) = parents.pop() {
+            let children = sys.processes().iter().filter(|(_, process)| {
+                process.parent() == Some(parent) && process.tasks().is_some()
+            });
+            for (&pid, _) in children {
+                parents.push(pid);
+                descendants.push(Pid::from_raw(pid.as_u32() as i32));
+            }
+        }
+        descendants
+    }
+
     ///  Create [`Child`] from already running external process.
     ///
     /// # Arguments
@@ -117,23 +145,17 @@ impl Child<Installed> {
                 .collect();
 
             for tid in &threads {
-                sys::ptrace::seize(
-                    *tid,
-                    Options::PTRACE_O_TRACECLONE
-                        .union(Options::PTRACE_O_TRACEEXEC)
-                        .union(Options::PTRACE_O_TRACEEXIT),
-                )
-                .map_err(Error::Attach)?;
+                sys::seize(*tid).map_err(|e| Error::Attach(e.into()))?;
             }
 
             for tid in &threads {
-                sys::ptrace::interrupt(*tid).map_err(Error::Attach)?;
+                sys::interrupt(*tid).map_err(|e| Error::Attach(e.into()))?;
             }
 
             for tid in &threads {
-                let status = waitpid(*tid, None).map_err(Error::Attach)?;
+                let status = sys::waitpid(*tid, None).map_err(|e| Error::Attach(e.into()))?;
                 // currently we assume that attached process not in stop status
-                debug_assert!(matches!(status, PtraceEvent(_, SIGTRAP, _)));
+                debug_assert!(matches!(status, PtraceEvent(_, Signal::SIGTRAP, _)));
             }
 
             interrupted_threads.extend(threads);
@@ -144,6 +166,7 @@ impl Child<Installed> {
             stderr,
             program: program_name,
             args: external_process.cmd()[1..].to_vec(),
+            stdin: None,
             pid: Some(pid),
             external_info: Some(ExternalInfo {
                 threads: interrupted_threads.into_iter().collect(),
@@ -169,6 +192,26 @@ impl<S: State> Child<S> {
         self.external_info.as_ref()
     }
 
+    /// Return a file used as a standard input of the process.
+    pub fn stdin(&self) -> Option<&Path> {
+        self.stdin.as_deref()
+    }
+
+    /// Set a file used as a standard input, it will be used at the next installation.
+    /// If `None` then process inherits debugger stdin.
+    ///
+    /// # Arguments
+    ///
+    /// * `stdin`: path to a file
+    pub fn set_stdin(&mut self, stdin: Option<PathBuf>) {
+        self.stdin = stdin;
+    }
+
+    /// Return copies of stdout and stderr pipes of the process.
+    pub fn try_clone_pipes(&self) -> Result<(PipeWriter, PipeWriter), Error> {
+        Ok((self.stdout.try_clone()?, self.stderr.try_clone()?))
+    }
+
     /// Instantiate process by `fork()` system call with caller as a parent process.
     /// After installation child process stopped by `SIGSTOP` signal.
     pub fn install(&self) -> Result<Child<Installed>, Error> {
@@ -177,37 +220,39 @@ impl<S: State> Child<S> {
             .args(&self.args)
             .stdout(self.stdout.try_clone()?)
             .stderr(self.stderr.try_clone()?);
+        if let Some(stdin) = &self.stdin {
+            let file =
+                File::open(stdin).map_err(|e| Error::StdinRedirect(stdin.to_path_buf(), e))?;
+            debugee_cmd.stdin(file);
+        }
 
         unsafe {
             debugee_cmd.pre_exec(move || {
-                sys::personality::set(Persona::ADDR_NO_RANDOMIZE)?;
+                personality::set(Persona::ADDR_NO_RANDOMIZE)?;
                 Ok(())
             });
         }
 
         match unsafe { fork().expect("fork() error") } {
-            ForkResult::Parent { child: pid } => {
-                waitpid(Pid::from_raw(-1), Some(WaitPidFlag::WSTOPPED)).map_err(Waitpid)?;
-                sys::ptrace::seize(
-                    pid,
-                    Options::PTRACE_O_TRACECLONE
-                        .union(Options::PTRACE_O_TRACEEXEC)
-                        .union(Options::PTRACE_O_TRACEEXIT),
-                )
-                .map_err(Ptrace)?;
+            ForkResult::Parent { child } => {
+                let pid = Pid::from(child);
+                sys::waitpid(Pid::from_raw(-1), Some(WaitPidFlag::WSTOPPED))
+                    .map_err(|e| Waitpid(e.into()))?;
+                sys::seize(pid)?;
 
                 Ok(Child {
                     stdout: self.stdout.try_clone()?,
                     stderr: self.stderr.try_clone()?,
                     program: self.program.clone(),
                     args: self.args.clone(),
+                    stdin: self.stdin.clone(),
                     pid: Some(pid),
                     external_info: None,
                     _p: PhantomData,
                 })
             }
             ForkResult::Child => {
-                sys::signal::raise(SIGSTOP).unwrap();
+                signal::raise(SIGSTOP).unwrap();
                 let err = debugee_cmd.exec();
                 panic!("run debugee fail with: {err}");
             }
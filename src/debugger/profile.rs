@@ -0,0 +1,358 @@
+//! Sampling profiler. While profiling is active, a running debugee is periodically
+//! interrupted by a timer thread, debugger records backtraces of all threads
+//! and immediately continues execution.
+//! Collected samples may be rendered as a flat profile or a call tree
+//! (see [`Profile::flat`] and [`Profile::call_tree`]), or exported in a collapsed
+//! stack format, suitable for flamegraph tools (see [`Profile::collapsed`]).
+
+use crate::debugger::Pid;
+use crate::debugger::Signal;
+use crate::debugger::{sys, Debugger, Error};
+use indexmap::IndexMap;
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{RecvTimeoutError, Sender};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// Default period between two samples.
+pub const DEFAULT_SAMPLE_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Function entry of a flat profile.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlatEntry {
+    pub function: String,
+    /// Number of samples where function is on top of a stack.
+    pub self_samples: u64,
+    /// Number of samples where function is anywhere in a stack.
+    pub total_samples: u64,
+}
+
+/// Node of a call tree, children are sorted by number of samples (descending).
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallTreeNode {
+    pub function: String,
+    pub samples: u64,
+    pub children: Vec<CallTreeNode>,
+}
+
+impl CallTreeNode {
+    fn insert(&mut self, stack: &[String], samples: u64) {
+        self.samples += samples;
+        let Some((function, rest)) = stack.split_first() else {
+            return;
+        };
+        let child = match self.children.iter().position(|c| &c.function == function) {
+            Some(idx) => &mut self.children[idx],
+            None => {
+                self.children.push(CallTreeNode {
+                    function: function.clone(),
+                    samples: 0,
+                    children: vec![],
+                });
+                self.children.last_mut().expect("infallible")
+            }
+        };
+        child.insert(rest, samples);
+    }
+
+    fn sort(&mut self) {
+        self.children.sort_by_key(|c| Reverse(c.samples));
+        self.children.iter_mut().for_each(CallTreeNode::sort);
+    }
+}
+
+/// Samples collected by a profiler. Each sample is a thread stack - a list of function
+/// names from the outermost frame to the innermost one.
+#[derive(Debug, Clone, Default)]
+pub struct Profile {
+    interval: Duration,
+    stacks: IndexMap<Vec<String>, u64>,
+    samples: u64,
+}
+
+impl Profile {
+    fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            ..Default::default()
+        }
+    }
+
+    /// Return period between two samples.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Return number of collected samples (one sample per thread at each interrupt).
+    pub fn samples(&self) -> u64 {
+        self.samples
+    }
+
+    /// Add a sample.
+    ///
+    /// # Arguments
+    ///
+    /// * `stack`: function names from the outermost frame to the innermost one
+    pub fn add_sample(&mut self, stack: Vec<String>) {
+        *self.stacks.entry(stack).or_default() += 1;
+        self.samples += 1;
+    }
+
+    /// Return a flat profile sorted by number of self samples, then by number of total samples.
+    pub fn flat(&self) -> Vec<FlatEntry> {
+        let mut entries: IndexMap<&str, FlatEntry> = IndexMap::new();
+        for (stack, &count) in &self.stacks {
+            // recursive function counted once per stack
+            let mut seen = HashSet::new();
+            for function in stack {
+                if !seen.insert(function.as_str()) {
+                    continue;
+                }
+                let entry = entries
+                    .entry(function.as_str())
+                    .or_insert_with(|| FlatEntry {
+                        function: function.clone(),
+                        self_samples: 0,
+                        total_samples: 0,
+                    });
+                entry.total_samples += count;
+            }
+            if let Some(leaf) = stack.last() {
+                entries[leaf.as_str()].self_samples += count;
+            }
+        }
+
+        let mut entries: Vec<_> = entries.into_values().collect();
+        entries.sort_by(|a, b| {
+            b.self_samples
+                .cmp(&a.self_samples)
+                .then(b.total_samples.cmp(&a.total_samples))
+        });
+        entries
+    }
+
+    /// Return a call tree, root node is a synthetic node with all samples.
+    pub fn call_tree(&self) -> CallTreeNode {
+        let mut root = CallTreeNode {
+            function: String::from("all"),
+            samples: 0,
+            children: vec![],
+        };
+        for (stack, &count) in &self.stacks {
+            root.insert(stack, count);
+        }
+        root.sort();
+        root
+    }
+
+    /// Return samples in a collapsed stack format: a line per unique stack,
+    /// functions are separated by `;`, then a number of samples.
+    pub fn collapsed(&self) -> String {
+        self.stacks
+            .iter()
+            .map(|(stack, count)| format!("{} {count}\n", stack.join(";")))
+            .collect()
+    }
+}
+
+/// Timer thread that periodically interrupts a debugee while it's running.
+/// Timer is stopped when dropped.
+pub(super) struct SamplingTimer {
+    cancel: Option<Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Drop for SamplingTimer {
+    fn drop(&mut self) {
+        drop(self.cancel.take());
+        if let Some(handle) = self.handle.take() {
+            _ = handle.join();
+        }
+    }
+}
+
+/// Active profiling session.
+pub(super) struct Profiler {
+    /// True if SIGINT is sent by sampling timer and not processed yet.
+    requested: Arc<AtomicBool>,
+    profile: Profile,
+}
+
+impl Profiler {
+    /// Start a timer that interrupts a debugee process with SIGINT at each interval.
+    /// Next interrupt is not sent until the previous one is processed.
+    fn arm(&self, pid: Pid) -> SamplingTimer {
+        let (cancel_tx, cancel_rx) = mpsc::channel::<()>();
+        let interval = self.profile.interval;
+        let requested = self.requested.clone();
+        let handle = thread::spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = cancel_rx.recv_timeout(interval) {
+                if !requested.swap(true, Ordering::AcqRel) {
+                    _ = sys::kill(pid, Signal::SIGINT);
+                }
+            }
+        });
+
+        SamplingTimer {
+            cancel: Some(cancel_tx),
+            handle: Some(handle),
+        }
+    }
+}
+
+impl Debugger {
+    /// Start a sampling timer if profiling is active. Timer works until the returned
+    /// guard is dropped.
+    pub(super) fn arm_sampling_timer(&self) -> Option<SamplingTimer> {
+        self.profiler
+            .as_ref()
+            .map(|profiler| profiler.arm(self.inferior().process.pid()))
+    }
+
+    /// Return true if signal stop is caused by the sampling timer, request is reset.
+    /// Interrupt requested by user has higher priority than a sample.
+    pub(super) fn is_sample_stop(&self, signal: Signal) -> bool {
+        signal == Signal::SIGINT
+            && self.profiler.as_ref().is_some_and(|profiler| {
+                profiler.requested.swap(false, Ordering::AcqRel) && !self.interrupter.is_requested()
+            })
+    }
+
+    /// Record a sample for each debugee thread. Threads that can't be unwound are skipped.
+    pub(super) fn record_samples(&mut self) {
+        let stacks: Vec<_> = self
+            .inferior()
+            .debugee
+            .tracee_ctl()
+            .tracee_iter()
+            .filter_map(|tracee| self.inferior().debugee.unwind(tracee.pid).ok())
+            .filter(|bt| !bt.is_empty())
+            .map(|bt| {
+                bt.into_iter()
+                    .rev()
+                    .map(|frame| frame.func_name.unwrap_or_else(|| frame.ip.to_string()))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        if let Some(profiler) = self.profiler.as_mut() {
+            stacks
+                .into_iter()
+                .for_each(|stack| profiler.profile.add_sample(stack));
+        }
+    }
+
+    /// Start profiling, all previously collected samples are dropped.
+    /// Debugee is sampled while running (after `continue` or `run` command).
+    ///
+    /// # Arguments
+    ///
+    /// * `interval`: period between two samples
+    pub fn start_profiling(&mut self, interval: Duration) {
+        self.profile = None;
+        self.profiler = Some(Profiler {
+            requested: Arc::default(),
+            profile: Profile::new(interval),
+        });
+    }
+
+    /// Stop profiling and return collected profile.
+    pub fn stop_profiling(&mut self) -> Result<&Profile, Error> {
+        let profiler = self.profiler.take().ok_or(Error::ProfilingNotStarted)?;
+        Ok(self.profile.insert(profiler.profile))
+    }
+
+    /// Return true if profiling is active.
+    pub fn is_profiling(&self) -> bool {
+        self.profiler.is_some()
+    }
+
+    /// Return profile of an active profiling session or the last collected one.
+    pub fn profile(&self) -> Option<&Profile> {
+        self.profiler
+            .as_ref()
+            .map(|profiler| &profiler.profile)
+            .or(self.profile.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn stack(functions: &[&str]) -> Vec<String> {
+        functions.iter().map(ToString::to_string).collect()
+    }
+
+    fn test_profile() -> Profile {
+        let mut profile = Profile::new(DEFAULT_SAMPLE_INTERVAL);
+        profile.add_sample(stack(&["main", "calc", "sum"]));
+        profile.add_sample(stack(&["main", "calc", "sum"]));
+        profile.add_sample(stack(&["main", "calc"]));
+        profile.add_sample(stack(&["main", "print"]));
+        profile
+    }
+
+    #[test]
+    fn test_flat_profile() {
+        let profile = test_profile();
+        assert_eq!(profile.samples(), 4);
+
+        let flat = profile.flat();
+        let rows: Vec<_> = flat
+            .iter()
+            .map(|e| (e.function.as_str(), e.self_samples, e.total_samples))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                ("sum", 2, 2),
+                ("calc", 1, 3),
+                ("print", 1, 1),
+                ("main", 0, 4)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_flat_profile_recursion() {
+        let mut profile = Profile::new(DEFAULT_SAMPLE_INTERVAL);
+        profile.add_sample(stack(&["main", "fib", "fib", "fib"]));
+
+        let flat = profile.flat();
+        let fib = flat.iter().find(|e| e.function == "fib").unwrap();
+        assert_eq!(fib.self_samples, 1);
+        assert_eq!(fib.total_samples, 1);
+    }
+
+    #[test]
+    fn test_call_tree() {
+        let tree = test_profile().call_tree();
+        assert_eq!(tree.samples, 4);
+        assert_eq!(tree.children.len(), 1);
+
+        let main = &tree.children[0];
+        assert_eq!(main.function, "main");
+        assert_eq!(main.samples, 4);
+        let children: Vec<_> = main
+            .children
+            .iter()
+            .map(|c| (c.function.as_str(), c.samples))
+            .collect();
+        assert_eq!(children, vec![("calc", 3), ("print", 1)]);
+        assert_eq!(main.children[0].children[0].function, "sum");
+        assert_eq!(main.children[0].children[0].samples, 2);
+    }
+
+    #[test]
+    fn test_collapsed() {
+        assert_eq!(
+            test_profile().collapsed(),
+            "main;calc;sum 2\nmain;calc 1\nmain;print 1\n"
+        );
+    }
+}
@@ -1,9 +1,13 @@
 use crate::debugger::error::Error;
-use crate::debugger::error::Error::{Ptrace, RegisterNotFound};
-use nix::libc::user_regs_struct;
-use nix::sys;
-use nix::unistd::Pid;
+use crate::debugger::error::Error::{
+    RegisterNameNotFound, RegisterNotFound, VectorRegisterUnavailable,
+};
+use crate::debugger::sys;
+use crate::debugger::sys::{FpRegisters, GpRegisters, Segment};
+use crate::debugger::Pid;
 use smallvec::{smallvec, SmallVec};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 use strum_macros::Display;
 use strum_macros::EnumString;
 
@@ -78,7 +82,7 @@ impl From<gimli::Register> for Register {
 }
 
 /// x86_64 register values.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct RegisterMap {
     rax: u64,
     rbx: u64,
@@ -109,8 +113,8 @@ pub struct RegisterMap {
     es: u64,
 }
 
-impl From<user_regs_struct> for RegisterMap {
-    fn from(value: user_regs_struct) -> Self {
+impl From<GpRegisters> for RegisterMap {
+    fn from(value: GpRegisters) -> Self {
         Self {
             rax: value.rax,
             rbx: value.rbx,
@@ -143,9 +147,9 @@ impl From<user_regs_struct> for RegisterMap {
     }
 }
 
-impl From<RegisterMap> for user_regs_struct {
-    fn from(reg_map: RegisterMap) -> user_regs_struct {
-        user_regs_struct {
+impl From<RegisterMap> for GpRegisters {
+    fn from(reg_map: RegisterMap) -> GpRegisters {
+        GpRegisters {
             rax: reg_map.rax,
             rbx: reg_map.rbx,
             rcx: reg_map.rcx,
@@ -184,8 +188,15 @@ impl RegisterMap {
     ///
     /// * `pid`: thread id.
     pub fn current(pid: Pid) -> Result<Self, Error> {
-        let regs = sys::ptrace::getregs(pid).map_err(Ptrace)?;
-        Ok(regs.into())
+        let mut map: Self = sys::gp_registers(pid)?.into();
+        // older kernels may not report segment bases in a general purpose register set
+        if map.fs_base == 0 {
+            map.fs_base = sys::segment_base(pid, Segment::Fs).unwrap_or_default();
+        }
+        if map.gs_base == 0 {
+            map.gs_base = sys::segment_base(pid, Segment::Gs).unwrap_or_default();
+        }
+        Ok(map)
     }
 
     /// Return register value.
@@ -226,6 +237,11 @@ impl RegisterMap {
         }
     }
 
+    /// Return thread pointer (a base address of a thread control block).
+    pub fn thread_pointer(&self) -> u64 {
+        self.fs_base
+    }
+
     /// Set new register value.
     ///
     /// # Arguments
@@ -270,10 +286,153 @@ impl RegisterMap {
     ///
     /// * `pid`: target thread.
     pub fn persist(self, pid: Pid) -> Result<(), Error> {
-        sys::ptrace::setregs(pid, self.into()).map_err(Ptrace)
+        Ok(sys::set_gp_registers(pid, self.into())?)
+    }
+}
+
+/// Read floating point registers of a thread.
+fn fp_registers(pid: Pid) -> Result<FpRegisters, Error> {
+    Ok(sys::fp_registers(pid)?)
+}
+
+/// Return XMM0 register value in little-endian byte order.
+///
+/// # Arguments
+///
+/// * `pid`: target thread.
+pub fn read_xmm0(pid: Pid) -> Result<[u8; 16], Error> {
+    let fp_regs = fp_registers(pid)?;
+    let mut bytes = [0; 16];
+    for (chunk, word) in bytes.chunks_mut(4).zip(&fp_regs.xmm_space[..4]) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    Ok(bytes)
+}
+
+/// Return ST0 (top of x87 stack) register value as a 80-bit extended precision float
+/// in little-endian byte order.
+///
+/// # Arguments
+///
+/// * `pid`: target thread.
+pub fn read_st0(pid: Pid) -> Result<[u8; 10], Error> {
+    let fp_regs = fp_registers(pid)?;
+    let mut bytes = [0; 10];
+    for (chunk, word) in bytes.chunks_mut(4).zip(&fp_regs.st_space[..3]) {
+        let len = chunk.len();
+        chunk.copy_from_slice(&word.to_le_bytes()[..len]);
+    }
+    Ok(bytes)
+}
+
+/// Write a value into the low 64 bits of XMM0 register.
+///
+/// # Arguments
+///
+/// * `pid`: target thread.
+/// * `value`: new value.
+pub fn write_xmm0(pid: Pid, value: u64) -> Result<(), Error> {
+    let mut fp_regs = fp_registers(pid)?;
+    fp_regs.xmm_space[0] = value as u32;
+    fp_regs.xmm_space[1] = (value >> 32) as u32;
+    Ok(sys::set_fp_registers(pid, &fp_regs)?)
+}
+
+/// Offset of XSTATE_BV field (bitmap of state components saved in XSAVE area).
+const XSTATE_BV_OFFSET: usize = 512;
+/// Offset of upper halves of YMM registers in a standard format XSAVE area.
+const YMM_HI_OFFSET: usize = 576;
+/// AVX state component bit of XSTATE_BV.
+const XSTATE_AVX: u64 = 1 << 2;
+
+/// SSE (128-bit) and AVX (256-bit) vector registers.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum VectorRegister {
+    Xmm(usize),
+    Ymm(usize),
+}
+
+impl FromStr for VectorRegister {
+    type Err = Error;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let (ctor, num): (fn(usize) -> Self, _) = if let Some(num) = name.strip_prefix("xmm") {
+            (VectorRegister::Xmm, num)
+        } else if let Some(num) = name.strip_prefix("ymm") {
+            (VectorRegister::Ymm, num)
+        } else {
+            return Err(RegisterNameNotFound(name.to_string()));
+        };
+        match num.parse::<usize>() {
+            Ok(n) if n < 16 && n.to_string() == num => Ok(ctor(n)),
+            _ => Err(RegisterNameNotFound(name.to_string())),
+        }
+    }
+}
+
+impl Display for VectorRegister {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VectorRegister::Xmm(n) => write!(f, "xmm{n}"),
+            VectorRegister::Ymm(n) => write!(f, "ymm{n}"),
+        }
+    }
+}
+
+impl VectorRegister {
+    /// Return register size in bytes.
+    pub fn size(&self) -> usize {
+        match self {
+            VectorRegister::Xmm(_) => 16,
+            VectorRegister::Ymm(_) => 32,
+        }
     }
 }
 
+/// Return extended processor state (XSAVE area) of a thread, the area is truncated
+/// to the size needed for YMM registers.
+fn xstate(pid: Pid) -> Result<Vec<u8>, Error> {
+    Ok(sys::xstate(pid, YMM_HI_OFFSET + 16 * 16)?)
+}
+
+/// Return value of a vector register in little-endian byte order.
+/// Lower half of YMM register is a corresponding XMM register.
+///
+/// # Arguments
+///
+/// * `pid`: target thread.
+/// * `register`: vector register.
+pub fn read_vector_register(pid: Pid, register: VectorRegister) -> Result<Vec<u8>, Error> {
+    let (VectorRegister::Xmm(num) | VectorRegister::Ymm(num)) = register;
+
+    let fp_regs = fp_registers(pid)?;
+    let mut bytes: Vec<u8> = fp_regs.xmm_space[num * 4..num * 4 + 4]
+        .iter()
+        .flat_map(|word| word.to_le_bytes())
+        .collect();
+
+    if let VectorRegister::Ymm(_) = register {
+        let area = xstate(pid)?;
+        let hi_offset = YMM_HI_OFFSET + num * 16;
+        if area.len() < hi_offset + 16 {
+            return Err(VectorRegisterUnavailable(register.to_string()));
+        }
+        let xstate_bv = u64::from_le_bytes(
+            area[XSTATE_BV_OFFSET..XSTATE_BV_OFFSET + 8]
+                .try_into()
+                .expect("infallible"),
+        );
+        // upper halves are in initial (zeroed) state if AVX component is absent
+        if xstate_bv & XSTATE_AVX != 0 {
+            bytes.extend_from_slice(&area[hi_offset..hi_offset + 16]);
+        } else {
+            bytes.extend_from_slice(&[0; 16]);
+        }
+    }
+
+    Ok(bytes)
+}
+
 /// x86_64 register values, using DWARF register number as index.
 #[derive(Debug, Clone)]
 pub struct DwarfRegisterMap(SmallVec<[Option<u64>; 0x80]>);
@@ -289,7 +448,7 @@ impl DwarfRegisterMap {
             .get(register.0 as usize)
             .copied()
             .and_then(|v| v)
-            .ok_or(RegisterNotFound(register))
+            .ok_or(RegisterNotFound(register.0))
     }
 
     /// Set new register value.
@@ -339,15 +498,11 @@ impl From<RegisterMap> for DwarfRegisterMap {
 }
 
 pub mod debug {
+    use crate::debugger::sys;
     use crate::debugger::Error;
-    use crate::debugger::Error::Ptrace;
+    use crate::debugger::Pid;
     use bit_field::BitField;
-    use nix::sys;
-    use nix::sys::ptrace::AddressType;
-    use nix::unistd::Pid;
-    use std::ffi::c_void;
     use std::fmt::{Display, Formatter};
-    use std::mem::offset_of;
     use strum_macros::FromRepr;
 
     /// Debug register representation.
@@ -453,12 +608,36 @@ pub mod debug {
             let dr = dr as usize;
             // set condition
             let idx = 16 + (dr * 4);
-            self.0.set_bits(idx..=idx + 1, cond as usize);
+            self.0.set_bits(idx..=idx + 1, cond.bits());
             // set size
             let idx = 18 + (dr * 4);
             self.0.set_bits(idx..=idx + 1, size as usize);
         }
 
+        /// Configures an instruction execution condition for the associated breakpoint.
+        /// Instruction breakpoints always have a 1-byte length.
+        ///
+        /// # Arguments
+        ///
+        /// * `dr`: address debug register number
+        #[inline(always)]
+        pub fn configure_instruction_bp(&mut self, dr: DebugRegisterNumber) {
+            let idx = 16 + (dr as usize * 4);
+            self.0.set_bits(idx..=idx + 3, 0b0000);
+        }
+
+        /// Return true if breakpoint enabled (locally) and configured
+        /// with an instruction execution condition.
+        ///
+        /// # Arguments
+        ///
+        /// * `dr`: address debug register number
+        #[inline(always)]
+        pub fn is_instruction_bp(&self, dr: DebugRegisterNumber) -> bool {
+            let idx = 16 + (dr as usize * 4);
+            self.dr_enabled(dr, false) && self.0.get_bits(idx..=idx + 1) == 0b00
+        }
+
         /// Enable/disable a breakpoint either as global or local.
         ///
         /// # Arguments
@@ -510,11 +689,8 @@ pub mod debug {
         ///
         /// * `pid`: thread id for which state is loaded
         pub fn current(pid: Pid) -> Result<Self, Error> {
-            use nix::libc::user;
-
             fn get_dr(pid: Pid, num: usize) -> Result<usize, Error> {
-                let offset = offset_of!(user, u_debugreg) + num * 8;
-                Ok(sys::ptrace::read_user(pid, offset as AddressType).map_err(Ptrace)? as usize)
+                Ok(sys::debug_register(pid, num)?)
             }
 
             Ok(Self {
@@ -536,12 +712,7 @@ pub mod debug {
         /// * `pid`: thread id into which registers data is saved
         pub fn sync(&self, pid: Pid) -> Result<(), Error> {
             fn set_dr(pid: Pid, num: usize, data: usize) -> Result<(), Error> {
-                let offset = offset_of!(nix::libc::user, u_debugreg);
-                let offset = offset + num * 8;
-                unsafe {
-                    sys::ptrace::write_user(pid, offset as AddressType, data as *mut c_void)
-                        .map_err(Ptrace)
-                }
+                Ok(sys::set_debug_register(pid, num, data)?)
             }
 
             for (reg_num, val) in self.address_regs.iter().enumerate() {
@@ -559,9 +730,22 @@ pub mod debug {
     #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd)]
     pub enum BreakCondition {
         /// 01 — Break on data writes only.
-        DataWrites = 0b01,
+        DataWrites,
         /// 11 — Break on data reads or writes but not instruction fetches.
-        DataReadsWrites = 0b11,
+        DataReadsWrites,
+        /// Break on data reads only. There is no such condition in DR7, so it is configured
+        /// as 11 (reads or writes) and writes are filtered out by the debugger.
+        DataReads,
+    }
+
+    impl BreakCondition {
+        /// Return DR7 condition bits.
+        fn bits(self) -> usize {
+            match self {
+                BreakCondition::DataWrites => 0b01,
+                BreakCondition::DataReadsWrites | BreakCondition::DataReads => 0b11,
+            }
+        }
     }
 
     impl Display for BreakCondition {
@@ -569,6 +753,7 @@ pub mod debug {
             match self {
                 BreakCondition::DataWrites => f.write_str("w"),
                 BreakCondition::DataReadsWrites => f.write_str("rw"),
+                BreakCondition::DataReads => f.write_str("r"),
             }
         }
     }
@@ -1,8 +1,10 @@
 use crate::debugger::error::Error;
-use crate::debugger::error::Error::{DefaultToolchainNotFound, UnrecognizedRustupOut};
+use crate::debugger::error::Error::{
+    DefaultToolchainNotFound, UnrecognizedRustcOut, UnrecognizedRustupOut,
+};
 use log::warn;
 use once_cell::sync::OnceCell;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 static ENVIRONMENT: OnceCell<Environment> = OnceCell::new();
@@ -18,15 +20,25 @@ impl Environment {
         ENVIRONMENT.get().unwrap()
     }
 
+    /// Init rust environment. If a path to rust stdlib sources isn't set, then it is detected
+    /// from a default rustup toolchain or from a sysroot of `rustc` in `PATH`.
     pub fn init(std_lib_path: Option<PathBuf>) {
         let toolchain = default_toolchain();
         if let Err(ref e) = toolchain {
             warn!(target: "debugger", "detect toolchain: {e}")
         }
+        let std_lib_path = std_lib_path
+            .or_else(|| toolchain.as_ref().ok().map(|t| t.std_lib_path()))
+            .or_else(|| match rustc_sysroot() {
+                Ok(sysroot) => Some(sysroot_std_lib_path(&sysroot)),
+                Err(e) => {
+                    warn!(target: "debugger", "detect rustc sysroot: {e}");
+                    None
+                }
+            });
         if ENVIRONMENT
             .set(Environment {
-                std_lib_path: std_lib_path
-                    .or_else(|| toolchain.as_ref().ok().map(|t| t.std_lib_path())),
+                std_lib_path,
                 toolchain: toolchain.ok(),
             })
             .is_err()
@@ -45,10 +57,28 @@ pub struct Toolchain {
 
 impl Toolchain {
     pub fn std_lib_path(&self) -> PathBuf {
-        self.path.clone().join("lib/rustlib/src/rust")
+        sysroot_std_lib_path(&self.path)
     }
 }
 
+/// Return a path to rust stdlib sources (`rust-src` component) in a sysroot.
+pub fn sysroot_std_lib_path(sysroot: &Path) -> PathBuf {
+    sysroot.join("lib/rustlib/src/rust")
+}
+
+/// Return a sysroot of `rustc` found in `PATH`.
+fn rustc_sysroot() -> Result<PathBuf, Error> {
+    let rustc_out = Command::new("rustc")
+        .args(["--print", "sysroot"])
+        .output()?;
+    let sysroot = String::from_utf8(rustc_out.stdout)?;
+    let sysroot = sysroot.trim();
+    if !rustc_out.status.success() || sysroot.is_empty() {
+        return Err(UnrecognizedRustcOut);
+    }
+    Ok(PathBuf::from(sysroot))
+}
+
 pub fn default_toolchain() -> Result<Toolchain, Error> {
     let rustup_out = Command::new("rustup")
         .args(["toolchain", "list", "-v"])
@@ -1,12 +1,193 @@
+use crate::ctx_resolve_unit_call;
 use crate::debugger::address::{Address, GlobalAddress, RelocatedAddress};
-use crate::debugger::breakpoint::Breakpoint;
+use crate::debugger::breakpoint::{Breakpoint, BrkptType};
+use crate::debugger::debugee::dwarf::r#type::{ComplexType, EvaluationContext, TypeDeclaration};
 use crate::debugger::debugee::dwarf::unit::PlaceDescriptorOwned;
+use crate::debugger::debugee::dwarf::unwind::DwarfUnwinder;
 use crate::debugger::debugee::tracer::{StopReason, TraceContext, WatchpointHitType};
+use crate::debugger::debugee::Location;
 use crate::debugger::error::Error;
-use crate::debugger::error::Error::{NoFunctionRanges, PlaceNotFound, ProcessExit};
+use crate::debugger::error::Error::{
+    FunctionNotFound, JumpOutsideFunction, NoCodeAtLine, NoFunctionRanges, NoSuitablePlace,
+    PlaceNotFound, ProcessExit, ReturnTypeUnsupported, ReturnValueOutOfRange,
+};
+use crate::debugger::register::{read_st0, read_xmm0, write_xmm0, Register, RegisterMap};
+use crate::debugger::variable::render::RenderRepr;
+use crate::debugger::variable::select::ObjectBinaryRepr;
+use crate::debugger::variable::{VariableIR, VariableIdentity, VariableParser};
+use crate::debugger::Pid;
+use crate::debugger::Signal;
 use crate::debugger::{Debugger, ExplorationContext};
-use nix::sys::signal::Signal;
-use nix::unistd::Pid;
+use bytes::Bytes;
+use gimli::{
+    DW_ATE_boolean, DW_ATE_float, DW_ATE_signed, DW_ATE_signed_char, DW_ATE_unsigned,
+    DW_ATE_unsigned_char, DW_ATE_UTF,
+};
+use std::fmt::{Display, Formatter};
+use std::mem;
+use std::path::Path;
+
+/// Value returned from a function by [`Debugger::return_early`].
+/// Value is converted into a return type of a function.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReturnValue {
+    /// Integer or boolean value.
+    Int(i64),
+    /// Floating point value.
+    Float(f64),
+}
+
+impl Display for ReturnValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReturnValue::Int(value) => write!(f, "{value}"),
+            ReturnValue::Float(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+/// Registers that hold a value returned from a function according to System V ABI.
+enum ReturnRegisters {
+    /// Integer, boolean, char, pointer or c-style enum in RAX,
+    /// upper half of a 128-bit integer in RDX.
+    Int { rax: u64, rdx: Option<u64> },
+    /// Bits of a `f32` or `f64` value in XMM0.
+    Float(u64),
+}
+
+impl ReturnRegisters {
+    /// Convert a value into a function return type. Aggregates and long doubles
+    /// aren't supported cause they may be returned in memory or in the x87 stack.
+    ///
+    /// # Arguments
+    ///
+    /// * `value`: returned value
+    /// * `r#type`: function return type, `None` if function returns nothing
+    fn new(value: ReturnValue, r#type: Option<&ComplexType>) -> Result<Self, Error> {
+        let Some(r#type) = r#type else {
+            return Err(ReturnTypeUnsupported("()".to_string()));
+        };
+        let type_name = || {
+            r#type
+                .type_name(r#type.root)
+                .unwrap_or("unknown".to_string())
+        };
+        let out_of_range = || ReturnValueOutOfRange(value.to_string(), type_name());
+
+        #[allow(non_upper_case_globals)]
+        let (bits, signed) = match underlying_type(r#type) {
+            Some(TypeDeclaration::Scalar(scalar)) => match (scalar.encoding, scalar.byte_size) {
+                (Some(DW_ATE_float), Some(4)) => {
+                    let float = match value {
+                        ReturnValue::Int(v) => v as f32,
+                        ReturnValue::Float(v) => v as f32,
+                    };
+                    if float.is_infinite()
+                        && !matches!(value, ReturnValue::Float(v) if v.is_infinite())
+                    {
+                        return Err(out_of_range());
+                    }
+                    return Ok(ReturnRegisters::Float(float.to_bits() as u64));
+                }
+                (Some(DW_ATE_float), Some(8)) => {
+                    let float = match value {
+                        ReturnValue::Int(v) => v as f64,
+                        ReturnValue::Float(v) => v,
+                    };
+                    return Ok(ReturnRegisters::Float(float.to_bits()));
+                }
+                (Some(DW_ATE_boolean), _) => (1, false),
+                (Some(DW_ATE_signed | DW_ATE_signed_char), Some(size)) => (size * 8, true),
+                (Some(DW_ATE_unsigned | DW_ATE_unsigned_char | DW_ATE_UTF), Some(size)) => {
+                    (size * 8, false)
+                }
+                _ => return Err(ReturnTypeUnsupported(type_name())),
+            },
+            Some(TypeDeclaration::Pointer { .. }) => (64, false),
+            Some(TypeDeclaration::CStyleEnum {
+                byte_size: Some(size),
+                discr_type,
+                ..
+            }) => {
+                let signed = discr_type.and_then(|t| r#type.types.get(&t)).is_some_and(
+                    |t| matches!(t, TypeDeclaration::Scalar(s) if s.encoding == Some(DW_ATE_signed)),
+                );
+                (size * 8, signed)
+            }
+            _ => return Err(ReturnTypeUnsupported(type_name())),
+        };
+        if bits == 0 || bits > 128 {
+            return Err(ReturnTypeUnsupported(type_name()));
+        }
+
+        let int = match value {
+            ReturnValue::Int(v) => v as i128,
+            // fractional part is dropped like in a `as` conversion
+            ReturnValue::Float(v) if v.is_finite() && v.abs() < 2f64.powi(127) => v as i128,
+            ReturnValue::Float(_) => return Err(out_of_range()),
+        };
+        let fits = match (signed, bits) {
+            (true, 128) => true,
+            (false, 128) => int >= 0,
+            (true, bits) => (-(1 << (bits - 1))..1 << (bits - 1)).contains(&int),
+            (false, bits) => (0..1 << bits).contains(&int),
+        };
+        if !fits {
+            return Err(out_of_range());
+        }
+
+        Ok(ReturnRegisters::Int {
+            rax: int as u64,
+            rdx: (bits > 64).then_some((int >> 64) as u64),
+        })
+    }
+}
+
+/// Return a type declaration without modifiers (like `const` or `volatile`).
+fn underlying_type(r#type: &ComplexType) -> Option<&TypeDeclaration> {
+    let mut type_id = r#type.root;
+    while let Some(TypeDeclaration::ModifiedType {
+        inner: Some(inner), ..
+    }) = r#type.types.get(&type_id)
+    {
+        type_id = *inner;
+    }
+    r#type.types.get(&type_id)
+}
+
+/// Where source level steps (step into and step over) stop.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum StepGranularity {
+    /// Stop when a different source line is reached.
+    #[default]
+    Line,
+    /// Stop at every statement (every `is_stmt` row of a line table),
+    /// even if it belongs to the same source line.
+    Statement,
+}
+
+/// Methods of `Fn`, `FnMut` and `FnOnce` traits.
+const FN_TRAIT_METHODS: [&str; 3] = ["call", "call_mut", "call_once"];
+
+/// Source files of `Fn*` trait call shims (function items called through a trait
+/// and boxed closures), step into passes through such shims.
+const FN_TRAIT_SHIM_FILES: [&str; 2] = ["core/src/ops/function.rs", "alloc/src/boxed.rs"];
+
+/// Registers that callee must preserve according to System V ABI (DWARF numbers: rbx, rbp, r12-r15).
+const CALLEE_SAVED_REGISTERS: [u16; 6] = [3, 6, 12, 13, 14, 15];
+
+/// Names of variants of an async function state machine suspended at await points
+/// start with this prefix (`Suspend0`, `Suspend1`, etc.).
+const SUSPENDED_STATE_PREFIX: &str = "Suspend";
+
+/// State machine of an async function (or an async block) polled in a stack frame.
+#[derive(Clone, Copy, Debug)]
+struct AsyncFrame {
+    /// Address of a state machine (value of `self` argument of a resume function).
+    state_machine: usize,
+    /// `true` if a state machine is suspended at an await point.
+    suspended: bool,
+}
 
 /// Result of a step, if [`SignalInterrupt`] or [`WatchpointInterrupt`] then
 /// a step process interrupted and the user should know about it.
@@ -60,13 +241,49 @@ impl StepResult {
 }
 
 impl Debugger {
-    /// Do a single step (until debugee reaches a different source line).
+    /// Return true if the in focus thread stopped at a user defined breakpoint.
+    /// Repeated steps are stopped at such breakpoints.
+    pub(super) fn at_user_breakpoint(&self) -> bool {
+        let pc = self.exploration_ctx().location().pc;
+        self.inferior()
+            .breakpoints
+            .get_enabled(pc)
+            .is_some_and(|bp| matches!(bp.r#type(), BrkptType::UserDefined))
+    }
+
+    /// Return true if the in focus thread stopped in a `Fn*` trait call shim
+    /// (like `FnOnce::call_once` for a function item passed as a closure).
+    ///
+    /// # Arguments
+    ///
+    /// * `place`: current place
+    fn in_fn_trait_shim(&self, place: &PlaceDescriptorOwned) -> Result<bool, Error> {
+        if !FN_TRAIT_SHIM_FILES
+            .iter()
+            .any(|file| place.file.ends_with(file))
+        {
+            return Ok(false);
+        }
+        let location = self.exploration_ctx().location();
+        let dwarf = self.inferior().debugee.debug_info(location.pc)?;
+        let Some(func) = dwarf.find_function_by_pc(location.global_pc)? else {
+            return Ok(false);
+        };
+        let name = func.die.base_attributes.name.as_deref().unwrap_or_default();
+        let name = name.split('<').next().unwrap_or_default();
+        Ok(FN_TRAIT_METHODS.contains(&name))
+    }
+
+    /// Do a single step (until debugee reaches a different source line or
+    /// a next statement, depending on granularity).
+    /// `Fn*` trait call shims are stepped through, so a step into a closure call
+    /// stops in a closure body.
     ///
     /// Returns [`StepResult::SignalInterrupt`] if the step is interrupted by a signal
     /// or [`StepResult::Done`] if a step is done.
     ///
     /// **! change exploration context**
-    pub(super) fn step_in(&mut self) -> Result<StepResult, Error> {
+    pub(super) fn step_in(&mut self, granularity: StepGranularity) -> Result<StepResult, Error> {
         enum PlaceOrStop {
             Place(PlaceDescriptorOwned),
             Signal(Signal),
@@ -98,7 +315,7 @@ impl Debugger {
                 let mut location = ctx.location();
                 // determine current function, if no debug information for function - step until function found
                 let func = loop {
-                    let dwarf = debugger.debugee.debug_info(location.pc)?;
+                    let dwarf = debugger.inferior().debugee.debug_info(location.pc)?;
                     // step's stop only if there is debug information for PC and current function can be determined
                     if let Ok(Some(func)) = dwarf.find_function_by_pc(location.global_pc) {
                         break func;
@@ -121,6 +338,7 @@ impl Debugger {
 
                 let location = debugger.exploration_ctx().location();
                 if let Some(place) = debugger
+                    .inferior()
                     .debugee
                     .debug_info(location.pc)?
                     .find_exact_place_from_pc(location.global_pc)?
@@ -133,7 +351,7 @@ impl Debugger {
         let mut location = self.exploration_ctx().location();
 
         let start_place = loop {
-            let dwarf = &self.debugee.debug_info(location.pc)?;
+            let dwarf = &self.inferior().debugee.debug_info(location.pc)?;
             if let Ok(Some(place)) = dwarf.find_place_from_pc(location.global_pc) {
                 break place;
             }
@@ -152,9 +370,11 @@ impl Debugger {
         let sp_file = start_place.file.to_path_buf();
         let sp_line = start_place.line_number;
         let start_cfa = self
+            .inferior()
             .debugee
             .debug_info(location.pc)?
-            .get_cfa(&self.debugee, &ExplorationContext::new(location, 0))?;
+            .get_cfa(&ExplorationContext::new(location, 0))?;
+        let async_frame = self.async_frame(location.pc, None)?;
 
         loop {
             let next_place = match step_over_prolog(self)? {
@@ -164,15 +384,19 @@ impl Debugger {
                     return Ok(StepResult::wp_interrupt(pid, addr, dr));
                 }
             };
-            if !next_place.is_stmt {
+            if !next_place.is_stmt || self.in_fn_trait_shim(&next_place)? {
                 continue;
             }
+            if granularity == StepGranularity::Statement {
+                break;
+            }
             let in_same_place = sp_file == next_place.file && sp_line == next_place.line_number;
             let location = self.exploration_ctx().location();
             let next_cfa = self
+                .inferior()
                 .debugee
                 .debug_info(location.pc)?
-                .get_cfa(&self.debugee, &ExplorationContext::new(location, 0))?;
+                .get_cfa(&ExplorationContext::new(location, 0))?;
 
             // step is done if:
             // 1) we may step at same place in code but in another stack frame
@@ -182,21 +406,111 @@ impl Debugger {
             }
         }
 
+        // if an async function returns into an executor cause it's suspended at an await point,
+        // then step until the same state machine is resumed
+        if let Some(frame) = async_frame {
+            let current_location = self.exploration_ctx().location();
+            let current_cfa = self
+                .inferior()
+                .debugee
+                .debug_info(current_location.pc)?
+                .get_cfa(&ExplorationContext::new(current_location, 0))?;
+            let suspended = self
+                .async_frame(location.pc, Some(frame.state_machine))?
+                .is_some_and(|frame| frame.suspended);
+            if current_cfa > start_cfa && suspended {
+                return self.step_into_resumed(frame, location.pc, granularity);
+            }
+        }
+
         self.expl_ctx_update_location()?;
         Ok(StepResult::Done)
     }
 
+    /// Continue debugee until an async function state machine is polled again
+    /// and step to a place where the function is resumed.
+    ///
+    /// **! change exploration context**
+    ///
+    /// # Arguments
+    ///
+    /// * `frame`: suspended state machine
+    /// * `pc`: address in a state machine resume function
+    /// * `granularity`: step granularity
+    fn step_into_resumed(
+        &mut self,
+        frame: AsyncFrame,
+        pc: RelocatedAddress,
+        granularity: StepGranularity,
+    ) -> Result<StepResult, Error> {
+        let pid = self.exploration_ctx().pid_on_focus();
+        let inferior = self.inferior_mut();
+        let debug_info = inferior.debugee.debug_info(pc)?;
+        let func = debug_info
+            .find_function_by_pc(pc.into_global(&inferior.debugee)?)?
+            .ok_or(FunctionNotFound(pc.into_global(&inferior.debugee)?))?;
+        let entry = func
+            .start_instruction()?
+            .relocate_to_segment_by_pc(&inferior.debugee, pc)?;
+        let entry_brkpt_is_set = inferior.breakpoints.get_enabled(entry).is_some();
+        if !entry_brkpt_is_set {
+            inferior
+                .breakpoints
+                .add_and_enable(Breakpoint::new_temporary_any_thread(
+                    debug_info.pathname(),
+                    entry,
+                    pid,
+                ))?;
+        }
+
+        let stop_reason = loop {
+            let stop_reason = self.continue_execution()?;
+            // skip polls of other state machines
+            if matches!(stop_reason, StopReason::Breakpoint(_, addr) if addr == entry) {
+                let polled = self.async_frame(entry, None)?;
+                if polled.is_some_and(|polled| polled.state_machine != frame.state_machine) {
+                    continue;
+                }
+            }
+            break stop_reason;
+        };
+
+        if !entry_brkpt_is_set {
+            self.remove_breakpoint(Address::Relocated(entry))?;
+        }
+
+        // hooks already called at [`Self::continue_execution`], so use `quite` opt
+        match stop_reason {
+            StopReason::SignalStop(_, sign) => Ok(StepResult::signal_interrupt_quiet(sign)),
+            StopReason::Watchpoint(pid, addr, ty) => {
+                Ok(StepResult::wp_interrupt_quite(pid, addr, ty))
+            }
+            StopReason::Breakpoint(_, addr) if addr == entry => self.step_over_any(granularity),
+            _ => {
+                if self.inferior().debugee.is_exited() {
+                    return Err(ProcessExit(0));
+                }
+                self.expl_ctx_update_location()?;
+                Ok(StepResult::Done)
+            }
+        }
+    }
+
     /// Move debugee to next instruction, step over breakpoint if needed.
     /// May return a [`StopReason::SignalStop`] if the step didn't happen cause signal.
     ///
     /// **! change exploration context**
     pub(super) fn single_step_instruction(&mut self) -> Result<Option<StopReason>, Error> {
         let loc = self.exploration_ctx().location();
-        let mb_reason = if self.breakpoints.get_enabled(loc.pc).is_some() {
+        let mb_reason = if self.inferior().breakpoints.get_enabled(loc.pc).is_some() {
             self.step_over_breakpoint()?
         } else {
-            let maybe_reason = self.debugee.tracer_mut().single_step(
-                TraceContext::new(&self.breakpoints.active_breakpoints(), &self.watchpoints),
+            let inferior = self.inferior_mut();
+            let maybe_reason = inferior.debugee.tracer_mut().single_step(
+                TraceContext::new(
+                    &inferior.breakpoints.active_breakpoints(),
+                    &inferior.watchpoints,
+                ),
                 loc.pid,
             )?;
             self.expl_ctx_update_location()?;
@@ -214,21 +528,24 @@ impl Debugger {
     pub(super) fn step_over_breakpoint(&mut self) -> Result<Option<StopReason>, Error> {
         // cannot use debugee::Location mapping offset may be not init yet
         let tracee = self
+            .inferior()
             .debugee
             .get_tracee_ensure(self.exploration_ctx().pid_on_focus());
-        let mb_brkpt = self.breakpoints.get_enabled(tracee.pc()?);
+        let pc = tracee.pc()?;
         let tracee_pid = tracee.pid;
-        if let Some(brkpt) = mb_brkpt {
-            if brkpt.is_enabled() {
-                brkpt.disable()?;
-                let maybe_reason = self.debugee.tracer_mut().single_step(
-                    TraceContext::new(&self.breakpoints.active_breakpoints(), &self.watchpoints),
-                    tracee_pid,
-                )?;
-                brkpt.enable()?;
-                self.expl_ctx_update_location()?;
-                return Ok(maybe_reason);
-            }
+        // breakpoint is re-armed later, before debugee continues,
+        // so stepping in a loop over the same breakpoint doesn't re-arm it every time
+        if self.inferior_mut().breakpoints.disarm(pc)? {
+            let inferior = self.inferior_mut();
+            let maybe_reason = inferior.debugee.tracer_mut().single_step(
+                TraceContext::new(
+                    &inferior.breakpoints.active_breakpoints(),
+                    &inferior.watchpoints,
+                ),
+                tracee_pid,
+            )?;
+            self.expl_ctx_update_location()?;
+            return Ok(maybe_reason);
         }
         Ok(None)
     }
@@ -239,22 +556,22 @@ impl Debugger {
     pub(super) fn step_out_frame(&mut self) -> Result<(), Error> {
         let ctx = self.exploration_ctx();
         let location = ctx.location();
-        let debug_info = self.debugee.debug_info(location.pc)?;
+        let debug_info = self.inferior().debugee.debug_info(location.pc)?;
 
-        if let Some(ret_addr) = self.debugee.return_addr(ctx.pid_on_focus())? {
-            let brkpt_is_set = self.breakpoints.get_enabled(ret_addr).is_some();
+        if let Some(ret_addr) = self.step_out_addr(ctx.pid_on_focus())? {
+            let brkpt_is_set = self.inferior().breakpoints.get_enabled(ret_addr).is_some();
             if brkpt_is_set {
                 self.continue_execution()?;
             } else {
                 let brkpt =
                     Breakpoint::new_temporary(debug_info.pathname(), ret_addr, location.pid);
-                self.breakpoints.add_and_enable(brkpt)?;
+                self.inferior_mut().breakpoints.add_and_enable(brkpt)?;
                 self.continue_execution()?;
                 self.remove_breakpoint(Address::Relocated(ret_addr))?;
             }
         }
 
-        if self.debugee.is_exited() {
+        if self.inferior().debugee.is_exited() {
             // todo add exit code here
             return Err(ProcessExit(0));
         }
@@ -263,18 +580,245 @@ impl Debugger {
         Ok(())
     }
 
+    /// Return an address where the current frame returns. Signal handler returns into
+    /// a signal trampoline, in this case an address of an interrupted instruction is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `pid`: thread id
+    fn step_out_addr(&self, pid: Pid) -> Result<Option<RelocatedAddress>, Error> {
+        let Some(ret_addr) = self.inferior().debugee.return_addr(pid)? else {
+            return Ok(None);
+        };
+        let in_trampoline = self
+            .inferior()
+            .debugee
+            .debug_info(ret_addr)?
+            .is_signal_trampoline(ret_addr.into_global(&self.inferior().debugee)?);
+        if !in_trampoline {
+            return Ok(Some(ret_addr));
+        }
+
+        // frame #1 is a signal trampoline, frame #2 is an interrupted one
+        let backtrace = self.inferior().debugee.unwind(pid)?;
+        Ok(backtrace.get(2).map(|frame| frame.ip).or(Some(ret_addr)))
+    }
+
+    /// Pop the current frame (of in focus thread) without executing the rest of the function.
+    /// Caller registers are restored by unwind information, return value (if exists) placed
+    /// into the return register. Debugee stops at the return address.
+    ///
+    /// **! change exploration context**
+    pub(super) fn pop_frame(&mut self, value: Option<ReturnValue>) -> Result<(), Error> {
+        let ctx = self.exploration_ctx();
+        let pid = ctx.pid_on_focus();
+        let return_registers = match value {
+            Some(value) => {
+                let location = ctx.location();
+                let debug_info = self.inferior().debugee.debug_info(location.pc)?;
+                let func = debug_info
+                    .find_function_by_pc(location.global_pc)?
+                    .ok_or(FunctionNotFound(location.global_pc))?;
+                Some(ReturnRegisters::new(value, func.return_type().as_ref())?)
+            }
+            None => None,
+        };
+        let caller_registers =
+            DwarfUnwinder::new(&self.inferior().debugee).caller_registers(ctx)?;
+
+        let mut registers = self.inferior().debugee.tracee_ctl().registers(pid)?;
+        for reg_num in CALLEE_SAVED_REGISTERS {
+            let reg = gimli::Register(reg_num);
+            if let Ok(value) = caller_registers.value(reg) {
+                registers.update(reg, value);
+            }
+        }
+        registers.update(Register::Rsp, caller_registers.value(gimli::Register(7))?);
+        registers.update(Register::Rip, caller_registers.value(gimli::Register(16))?);
+
+        match return_registers {
+            Some(ReturnRegisters::Int { rax, rdx }) => {
+                registers.update(Register::Rax, rax);
+                if let Some(rdx) = rdx {
+                    registers.update(Register::Rdx, rdx);
+                }
+                self.inferior()
+                    .debugee
+                    .tracee_ctl()
+                    .set_registers(pid, registers)?;
+            }
+            Some(ReturnRegisters::Float(bits)) => {
+                self.inferior()
+                    .debugee
+                    .tracee_ctl()
+                    .set_registers(pid, registers)?;
+                write_xmm0(pid, bits)?;
+            }
+            None => self
+                .inferior()
+                .debugee
+                .tracee_ctl()
+                .set_registers(pid, registers)?,
+        }
+
+        self.expl_ctx_update_location()?;
+        Ok(())
+    }
+
+    /// Read a value returned from a function, must be called right after the function returns.
+    /// Return registers are chosen according to System V ABI, only scalars, pointers and
+    /// c-style enums are supported (values of other types may be returned in memory).
+    ///
+    /// # Arguments
+    ///
+    /// * `callee`: location inside the returned function
+    pub(super) fn read_return_value(&self, callee: Location) -> Result<Option<VariableIR>, Error> {
+        let pid = self.exploration_ctx().pid_on_focus();
+        let debug_info = self.inferior().debugee.debug_info(callee.pc)?;
+        let Some(func) = debug_info.find_function_by_pc(callee.global_pc)? else {
+            return Ok(None);
+        };
+        let Some(r#type) = func.return_type() else {
+            return Ok(None);
+        };
+
+        let raw_data = match underlying_type(&r#type) {
+            Some(TypeDeclaration::Scalar(scalar)) if scalar.encoding == Some(DW_ATE_float) => {
+                match scalar.byte_size {
+                    Some(2 | 4 | 8) => read_xmm0(pid)?.to_vec(),
+                    // long double returned in the x87 stack
+                    _ => {
+                        let mut st0 = read_st0(pid)?.to_vec();
+                        st0.resize(16, 0);
+                        st0
+                    }
+                }
+            }
+            Some(
+                TypeDeclaration::Scalar(_)
+                | TypeDeclaration::Pointer { .. }
+                | TypeDeclaration::CStyleEnum { .. },
+            ) => {
+                // 128-bit integers returned in RAX:RDX pair
+                let registers = self.inferior().debugee.tracee_ctl().registers(pid)?;
+                let mut rax_rdx = registers.value(Register::Rax).to_le_bytes().to_vec();
+                rax_rdx.extend(registers.value(Register::Rdx).to_le_bytes());
+                rax_rdx
+            }
+            _ => return Ok(None),
+        };
+
+        let evaluator = ctx_resolve_unit_call!(func, evaluator, &self.inferior().debugee);
+        let eval_ctx = EvaluationContext {
+            evaluator: &evaluator,
+            expl_ctx: self.exploration_ctx(),
+        };
+        let size = r#type
+            .type_size_in_bytes(&eval_ctx, r#type.root)
+            .unwrap_or_default() as usize;
+        if size == 0 || size > raw_data.len() {
+            return Ok(None);
+        }
+
+        let data = ObjectBinaryRepr {
+            raw_data: Bytes::copy_from_slice(&raw_data[..size]),
+            address: None,
+            size,
+        };
+        Ok(Some(VariableParser::new(&r#type).parse(
+            &eval_ctx,
+            VariableIdentity::no_namespace(None),
+            Some(data),
+        )))
+    }
+
+    /// Set program counter (of in focus thread) to the first instruction of a source line.
+    /// Target line must be in the current function, unless `force` is set.
+    ///
+    /// **! change exploration context**
+    ///
+    /// # Errors
+    ///
+    /// Return [`Error::NoSuitablePlace`] if there is no instructions for target line,
+    /// return [`Error::JumpOutsideFunction`] if target line is outside the current function
+    /// and `force` is not set.
+    pub(super) fn jump_to_line(
+        &mut self,
+        file: Option<&str>,
+        line: u64,
+        force: bool,
+    ) -> Result<(), Error> {
+        let location = self.exploration_ctx().location();
+        let debug_info = self.inferior().debugee.debug_info(location.pc)?;
+        let func = debug_info
+            .find_function_by_pc(location.global_pc)?
+            .ok_or(FunctionNotFound(location.global_pc))?;
+
+        let current_file = match file {
+            Some(file) => file.to_string(),
+            None => debug_info
+                .find_place_from_pc(location.global_pc)?
+                .ok_or(PlaceNotFound(location.global_pc))?
+                .file
+                .to_string_lossy()
+                .to_string(),
+        };
+
+        // jump only to the exact line, a closest line after it may be a part of
+        // a different statement
+        let places: Vec<_> = debug_info
+            .find_closest_place(&current_file, line)?
+            .into_iter()
+            .filter(|place| place.line_number == line)
+            .collect();
+        let place = match places
+            .iter()
+            .find(|place| place.address.in_ranges(func.ranges()))
+        {
+            Some(place) => place,
+            None if places.is_empty() => {
+                return match self.diagnose_line(&current_file, line)? {
+                    Some(diagnostic) => Err(NoCodeAtLine(line, diagnostic)),
+                    None => Err(NoSuitablePlace),
+                };
+            }
+            None if !force => return Err(JumpOutsideFunction(func.full_name())),
+            None => &places[0],
+        };
+
+        let pc = place
+            .address
+            .relocate_to_segment(&self.inferior().debugee, debug_info)?;
+        let mut registers = self
+            .inferior()
+            .debugee
+            .tracee_ctl()
+            .registers(location.pid)?;
+        registers.update(Register::Rip, u64::from(pc));
+        self.inferior()
+            .debugee
+            .tracee_ctl()
+            .set_registers(location.pid, registers)?;
+
+        self.expl_ctx_update_location()?;
+        Ok(())
+    }
+
     /// Do debugee step (over subroutine calls too).
     /// Returns [`StepResult::SignalInterrupt`] if the step is interrupted by a signal
     /// or [`StepResult::Done`] if step done.
     ///
     /// **! change exploration context**
-    pub(super) fn step_over_any(&mut self) -> Result<StepResult, Error> {
+    pub(super) fn step_over_any(
+        &mut self,
+        granularity: StepGranularity,
+    ) -> Result<StepResult, Error> {
         let ctx = self.exploration_ctx();
         let mut current_location = ctx.location();
 
         // determine current function, if no debug information for function - step until function found
         let func = loop {
-            let dwarf = &self.debugee.debug_info(current_location.pc)?;
+            let dwarf = &self.inferior().debugee.debug_info(current_location.pc)?;
             // step's stop only if there is debug information for PC and current function can be determined
             if let Ok(Some(func)) = dwarf.find_function_by_pc(current_location.global_pc) {
                 break func;
@@ -292,8 +836,14 @@ impl Debugger {
         };
 
         let prolog = func.prolog()?;
-        let dwarf = &self.debugee.debug_info(current_location.pc)?;
+        let dwarf = &self.inferior().debugee.debug_info(current_location.pc)?;
         let inline_ranges = func.inline_ranges();
+        let async_frame = self.async_frame(current_location.pc, None)?;
+        // rustc places a state machine dispatch code of async functions
+        // at a function declaration line, a step never stops there
+        let dispatch_line = async_frame
+            .and(func.die.decl_file_line)
+            .map(|(_, line)| line);
 
         let current_place = dwarf
             .find_place_from_pc(current_location.global_pc)?
@@ -322,15 +872,18 @@ impl Debugger {
                 // guard against a step at inlined function body
                 let in_inline_range = place.address.in_ranges(&inline_ranges);
 
+                let other_line = granularity == StepGranularity::Statement
+                    || place.line_number != current_place.line_number;
                 if !in_inline_range
                     && place.is_stmt
+                    && Some(place.line_number) != dispatch_line
                     && place.address != current_place.address
-                    && place.line_number != current_place.line_number
+                    && other_line
                 {
                     let load_addr = place
                         .address
-                        .relocate_to_segment_by_pc(&self.debugee, current_location.pc)?;
-                    if self.breakpoints.get_enabled(load_addr).is_none() {
+                        .relocate_to_segment_by_pc(&self.inferior().debugee, current_location.pc)?;
+                    if self.inferior().breakpoints.get_enabled(load_addr).is_none() {
                         step_over_breakpoints.push(load_addr);
                         to_delete.push(load_addr);
                     }
@@ -343,31 +896,54 @@ impl Debugger {
             }
         }
 
+        let debug_info_file = dwarf.pathname().to_path_buf();
+        let inferior = self.inferior_mut();
+        // an async function may be resumed by another thread
+        let new_step_breakpoint = match async_frame {
+            Some(_) => Breakpoint::new_temporary_any_thread,
+            None => Breakpoint::new_temporary,
+        };
         step_over_breakpoints
             .into_iter()
             .try_for_each(|load_addr| {
-                self.breakpoints
-                    .add_and_enable(Breakpoint::new_temporary(
-                        dwarf.pathname(),
+                inferior
+                    .breakpoints
+                    .add_and_enable(new_step_breakpoint(
+                        &debug_info_file,
                         load_addr,
                         current_location.pid,
                     ))
                     .map(|_| ())
             })?;
 
-        let return_addr = self.debugee.return_addr(current_location.pid)?;
+        let mut return_addr = inferior.debugee.return_addr(current_location.pid)?;
         if let Some(ret_addr) = return_addr {
-            if self.breakpoints.get_enabled(ret_addr).is_none() {
-                self.breakpoints.add_and_enable(Breakpoint::new_temporary(
-                    dwarf.pathname(),
-                    ret_addr,
-                    current_location.pid,
-                ))?;
+            if inferior.breakpoints.get_enabled(ret_addr).is_none() {
+                inferior
+                    .breakpoints
+                    .add_and_enable(Breakpoint::new_temporary(
+                        &debug_info_file,
+                        ret_addr,
+                        current_location.pid,
+                    ))?;
                 to_delete.push(ret_addr);
             }
         }
 
-        let stop_reason = self.continue_execution()?;
+        let stop_reason = match async_frame {
+            Some(frame) => {
+                let entry = GlobalAddress::from(prolog.begin)
+                    .relocate_to_segment_by_pc(&self.inferior().debugee, current_location.pc)?;
+                self.continue_async_step(
+                    frame,
+                    entry,
+                    &debug_info_file,
+                    &mut to_delete,
+                    &mut return_addr,
+                )?
+            }
+            None => self.continue_execution()?,
+        };
 
         to_delete
             .into_iter()
@@ -390,12 +966,13 @@ impl Debugger {
         let new_location = self.exploration_ctx().location();
         if Some(new_location.pc) == return_addr {
             let place = self
+                .inferior()
                 .debugee
                 .debug_info(new_location.pc)?
                 .find_place_from_pc(new_location.global_pc)?
                 .ok_or_else(|| NoFunctionRanges(fn_full_name))?;
             if place.address != new_location.global_pc {
-                match self.step_in()? {
+                match self.step_in(granularity)? {
                     StepResult::SignalInterrupt { signal, .. } => {
                         return Ok(StepResult::signal_interrupt(signal));
                     }
@@ -407,7 +984,7 @@ impl Debugger {
             }
         }
 
-        if self.debugee.is_exited() {
+        if self.inferior().debugee.is_exited() {
             // todo add exit code here
             return Err(ProcessExit(0));
         }
@@ -415,4 +992,290 @@ impl Debugger {
         self.expl_ctx_update_location()?;
         Ok(StepResult::Done)
     }
+
+    /// Return a state machine of an async function (or an async block) polled at `pc`,
+    /// `None` if `pc` isn't in a state machine resume function.
+    ///
+    /// # Arguments
+    ///
+    /// * `pc`: address in a resume function
+    /// * `state_machine`: state machine address, if `None` then it is read from `self` argument
+    ///   of a resume function (`pc` must be the current PC of in focus thread)
+    fn async_frame(
+        &self,
+        pc: RelocatedAddress,
+        state_machine: Option<usize>,
+    ) -> Result<Option<AsyncFrame>, Error> {
+        let debug_info = self.inferior().debugee.debug_info(pc)?;
+        let global_pc = pc.into_global(&self.inferior().debugee)?;
+        let Some(func) = debug_info.find_function_by_pc(global_pc)? else {
+            return Ok(None);
+        };
+        if !func.is_async_resume() {
+            return Ok(None);
+        }
+
+        // `self` argument is a pinned pointer to a state machine
+        let Some(param) = func.parameters().into_iter().next() else {
+            return Ok(None);
+        };
+        let Some(r#type) = param.r#type() else {
+            return Ok(None);
+        };
+        let state_machine = match state_machine {
+            // at the first instruction an argument isn't spilled into a stack yet,
+            // so take it from a register (System V ABI)
+            None if global_pc == func.start_instruction()? => {
+                let registers = RegisterMap::current(self.exploration_ctx().pid_on_focus())?;
+                Some(registers.value(Register::Rdi) as usize)
+            }
+            state_machine => state_machine,
+        };
+        let data = match state_machine {
+            Some(addr) => ObjectBinaryRepr {
+                raw_data: Bytes::copy_from_slice(&addr.to_le_bytes()),
+                address: None,
+                size: mem::size_of::<usize>(),
+            },
+            None => {
+                match param.read_value(self.exploration_ctx(), &self.inferior().debugee, &r#type) {
+                    Some(data) => data,
+                    None => return Ok(None),
+                }
+            }
+        };
+
+        let evaluator = ctx_resolve_unit_call!(func, evaluator, &self.inferior().debugee);
+        let eval_ctx = EvaluationContext {
+            evaluator: &evaluator,
+            expl_ctx: self.exploration_ctx(),
+        };
+        let VariableIR::Struct(pin) = VariableParser::new(&r#type).parse(
+            &eval_ctx,
+            VariableIdentity::no_namespace(None),
+            Some(data),
+        ) else {
+            return Ok(None);
+        };
+        let Some(VariableIR::Pointer(pointer)) = pin.members.first() else {
+            return Ok(None);
+        };
+        let Some(state_machine) = pointer.value else {
+            return Ok(None);
+        };
+
+        // state machine is an enum with `Unresumed`, `Returned`, `Panicked`
+        // and a variant for each await point
+        let suspended = match pointer.deref(&eval_ctx, &VariableParser::new(&r#type)) {
+            Some(VariableIR::RustEnum(state)) => state
+                .value
+                .is_some_and(|variant| variant.r#type().starts_with(SUSPENDED_STATE_PREFIX)),
+            _ => false,
+        };
+
+        Ok(Some(AsyncFrame {
+            state_machine: state_machine as usize,
+            suspended,
+        }))
+    }
+
+    /// Continue a step in an async function. Step breakpoints hit by other instances
+    /// of the function (polled for other state machines) are skipped. If the function returns
+    /// while its state machine is suspended at an await point, then debugee continues until
+    /// the same state machine is polled again (possibly by another thread),
+    /// a poll is detected by a breakpoint at the resume function entry.
+    /// Return a stop reason of the last continue.
+    ///
+    /// **! change exploration context**
+    ///
+    /// # Arguments
+    ///
+    /// * `frame`: state machine polled in the current frame
+    /// * `entry`: the first instruction of the resume function
+    /// * `debug_info_file`: path to an object file with debug information of the function
+    /// * `step_breakpoints`: addresses of breakpoints set by a step, breakpoints set here
+    ///   (at the function entry and at return addresses of new polls) are added into it
+    /// * `return_addr`: return address of the current poll, updated when the state machine
+    ///   is polled again
+    fn continue_async_step(
+        &mut self,
+        frame: AsyncFrame,
+        entry: RelocatedAddress,
+        debug_info_file: &Path,
+        step_breakpoints: &mut Vec<RelocatedAddress>,
+        return_addr: &mut Option<RelocatedAddress>,
+    ) -> Result<StopReason, Error> {
+        let mut poll_tid = self.exploration_ctx().pid_on_focus();
+        if self.inferior().breakpoints.get_enabled(entry).is_none() {
+            self.inferior_mut().breakpoints.add_and_enable(
+                Breakpoint::new_temporary_any_thread(debug_info_file, entry, poll_tid),
+            )?;
+            step_breakpoints.push(entry);
+        }
+
+        loop {
+            let stop_reason = self.continue_execution()?;
+            let StopReason::Breakpoint(pid, pc) = stop_reason else {
+                return Ok(stop_reason);
+            };
+            if !step_breakpoints.contains(&pc) {
+                return Ok(stop_reason);
+            }
+
+            let polled = self.async_frame(pc, None)?;
+            let same_state_machine =
+                polled.is_some_and(|polled| polled.state_machine == frame.state_machine);
+
+            if pc == entry {
+                if !same_state_machine {
+                    continue;
+                }
+                // state machine is polled again, a new poll may return to another place
+                poll_tid = pid;
+                if let Some(ret_addr) = self.inferior().debugee.return_addr(pid)? {
+                    if self.inferior().breakpoints.get_enabled(ret_addr).is_none() {
+                        self.inferior_mut().breakpoints.add_and_enable(
+                            Breakpoint::new_temporary(debug_info_file, ret_addr, pid),
+                        )?;
+                        step_breakpoints.push(ret_addr);
+                    }
+                    *return_addr = Some(ret_addr);
+                }
+                continue;
+            }
+
+            if Some(pc) == *return_addr {
+                let suspended = self
+                    .async_frame(entry, Some(frame.state_machine))?
+                    .is_some_and(|frame| frame.suspended);
+                if pid == poll_tid && !suspended {
+                    return Ok(stop_reason);
+                }
+                continue;
+            }
+
+            if same_state_machine {
+                return Ok(stop_reason);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::debugger::debugee::dwarf::r#type::ScalarType;
+    use crate::debugger::debugee::dwarf::unit::DieRef;
+    use crate::debugger::debugee::dwarf::NamespaceHierarchy;
+    use gimli::{DwAte, UnitOffset};
+    use std::collections::HashMap;
+
+    fn scalar(name: &str, byte_size: u64, encoding: DwAte) -> ComplexType {
+        let root = DieRef::Unit(UnitOffset(0));
+        let decl = TypeDeclaration::Scalar(ScalarType {
+            namespaces: NamespaceHierarchy::default(),
+            name: Some(name.to_string()),
+            byte_size: Some(byte_size),
+            encoding: Some(encoding),
+        });
+        ComplexType {
+            types: HashMap::from([(root, decl)]),
+            root,
+        }
+    }
+
+    fn int(value: ReturnValue, r#type: &ComplexType) -> Result<(u64, Option<u64>), Error> {
+        match ReturnRegisters::new(value, Some(r#type))? {
+            ReturnRegisters::Int { rax, rdx } => Ok((rax, rdx)),
+            ReturnRegisters::Float(_) => panic!("integer expected"),
+        }
+    }
+
+    fn float(value: ReturnValue, r#type: &ComplexType) -> Result<u64, Error> {
+        match ReturnRegisters::new(value, Some(r#type))? {
+            ReturnRegisters::Float(bits) => Ok(bits),
+            ReturnRegisters::Int { .. } => panic!("float expected"),
+        }
+    }
+
+    #[test]
+    fn test_return_integer() {
+        let u8 = scalar("u8", 1, DW_ATE_unsigned);
+        assert_eq!(int(ReturnValue::Int(255), &u8).unwrap(), (255, None));
+        assert!(matches!(
+            int(ReturnValue::Int(256), &u8),
+            Err(ReturnValueOutOfRange(_, _))
+        ));
+        assert!(int(ReturnValue::Int(-1), &u8).is_err());
+
+        let i8 = scalar("i8", 1, DW_ATE_signed);
+        assert_eq!(int(ReturnValue::Int(-1), &i8).unwrap(), (u64::MAX, None));
+        assert!(int(ReturnValue::Int(-129), &i8).is_err());
+
+        let i64 = scalar("i64", 8, DW_ATE_signed);
+        assert_eq!(int(ReturnValue::Float(3.9), &i64).unwrap(), (3, None));
+        assert!(int(ReturnValue::Float(f64::NAN), &i64).is_err());
+
+        let bool = scalar("bool", 1, DW_ATE_boolean);
+        assert_eq!(int(ReturnValue::Int(1), &bool).unwrap(), (1, None));
+        assert!(int(ReturnValue::Int(2), &bool).is_err());
+
+        let i128 = scalar("i128", 16, DW_ATE_signed);
+        assert_eq!(
+            int(ReturnValue::Int(-2), &i128).unwrap(),
+            (u64::MAX - 1, Some(u64::MAX))
+        );
+        let u128 = scalar("u128", 16, DW_ATE_unsigned);
+        assert_eq!(int(ReturnValue::Int(5), &u128).unwrap(), (5, Some(0)));
+        assert!(int(ReturnValue::Int(-1), &u128).is_err());
+    }
+
+    #[test]
+    fn test_return_float() {
+        let f32 = scalar("f32", 4, DW_ATE_float);
+        assert_eq!(
+            float(ReturnValue::Float(1.5), &f32).unwrap(),
+            1.5f32.to_bits() as u64
+        );
+        assert_eq!(
+            float(ReturnValue::Int(3), &f32).unwrap(),
+            3f32.to_bits() as u64
+        );
+        assert!(float(ReturnValue::Float(1e300), &f32).is_err());
+
+        let f64 = scalar("f64", 8, DW_ATE_float);
+        assert_eq!(
+            float(ReturnValue::Int(-7), &f64).unwrap(),
+            (-7f64).to_bits()
+        );
+
+        let f128 = scalar("f128", 16, DW_ATE_float);
+        assert!(matches!(
+            ReturnRegisters::new(ReturnValue::Float(1.0), Some(&f128)),
+            Err(ReturnTypeUnsupported(_))
+        ));
+    }
+
+    #[test]
+    fn test_return_unsupported() {
+        let root = DieRef::Unit(UnitOffset(0));
+        let structure = ComplexType {
+            types: HashMap::from([(
+                root,
+                TypeDeclaration::Structure {
+                    namespaces: NamespaceHierarchy::default(),
+                    name: Some("Point".to_string()),
+                    byte_size: Some(8),
+                    members: vec![],
+                    type_params: HashMap::new(),
+                },
+            )]),
+            root,
+        };
+        let err = ReturnRegisters::new(ReturnValue::Int(1), Some(&structure));
+        assert!(matches!(err, Err(ReturnTypeUnsupported(name)) if name == "Point"));
+
+        let err = ReturnRegisters::new(ReturnValue::Int(1), None);
+        assert!(matches!(err, Err(ReturnTypeUnsupported(name)) if name == "()"));
+    }
 }
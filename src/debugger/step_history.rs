@@ -0,0 +1,89 @@
+//! Stepping history. Each debugee stop (breakpoint or watchpoint hit, finished step,
+//! signal) is recorded with its address, place and reason in a history of a stopped thread,
+//! so a user can review how execution got to the current point.
+
+use crate::debugger::Pid;
+use crate::debugger::StopSummary;
+use indexmap::IndexMap;
+use std::collections::VecDeque;
+
+/// Maximum number of stops remembered for each thread.
+pub const STEP_HISTORY_LIMIT: usize = 256;
+
+/// History of debugee stops (breakpoints, watchpoints, steps, signals) per thread.
+/// History of each thread is bounded, the oldest stops are dropped first.
+#[derive(Default)]
+pub(super) struct StepHistory {
+    threads: IndexMap<Pid, VecDeque<StopSummary>>,
+}
+
+impl StepHistory {
+    /// Add a stop into a history of a stopped thread.
+    pub(super) fn record(&mut self, summary: StopSummary) {
+        let stops = self.threads.entry(summary.tid).or_default();
+        if stops.len() == STEP_HISTORY_LIMIT {
+            stops.pop_front();
+        }
+        stops.push_back(summary);
+    }
+
+    /// Return stops of a thread (or of all threads if `tid` is `None`) grouped by a thread
+    /// and ordered by thread numbers, stops of a thread are ordered from the oldest one.
+    pub(super) fn stops(&self, tid: Option<Pid>) -> Vec<StopSummary> {
+        let mut threads: Vec<_> = self
+            .threads
+            .iter()
+            .filter(|(&thread, _)| tid.is_none() || Some(thread) == tid)
+            .map(|(_, stops)| stops)
+            .collect();
+        threads.sort_by_key(|stops| stops.front().map(|stop| stop.thread_num));
+        threads
+            .into_iter()
+            .flat_map(|stops| stops.iter().cloned())
+            .collect()
+    }
+
+    /// Forget all stops (at debugee restart).
+    pub(super) fn clear(&mut self) {
+        self.threads.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::debugger::StopKind;
+    use std::time::SystemTime;
+
+    fn stop(tid: i32, thread_num: u32) -> StopSummary {
+        StopSummary {
+            time: SystemTime::now(),
+            tid: Pid::from_raw(tid),
+            thread_num,
+            pc: None,
+            kind: StopKind::Step,
+            function: None,
+            place: None,
+        }
+    }
+
+    #[test]
+    fn test_step_history() {
+        let mut history = StepHistory::default();
+        history.record(stop(20, 2));
+        for _ in 0..STEP_HISTORY_LIMIT + 5 {
+            history.record(stop(10, 1));
+        }
+
+        let all = history.stops(None);
+        assert_eq!(all.len(), STEP_HISTORY_LIMIT + 1);
+        assert_eq!(all[0].thread_num, 1);
+        assert_eq!(all[STEP_HISTORY_LIMIT].thread_num, 2);
+
+        let second = history.stops(Some(Pid::from_raw(20)));
+        assert_eq!(second.len(), 1);
+
+        history.clear();
+        assert!(history.stops(None).is_empty());
+    }
+}
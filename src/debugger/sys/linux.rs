@@ -0,0 +1,209 @@
+//! Linux target, requests are executed with `ptrace`, `waitpid` and `kill` syscalls (x86_64 only).
+
+use crate::debugger::sys::{Pid, Segment, Signal, Target, WaitStatus};
+use nix::errno::Errno;
+use nix::libc;
+use nix::sys::ptrace::{AddressType, Options};
+use nix::sys::wait::WaitPidFlag;
+use nix::sys::{ptrace, signal, wait};
+use std::mem::offset_of;
+use std::path::Path;
+use std::{mem, ptr};
+
+pub use libc::{PTRACE_EVENT_CLONE, PTRACE_EVENT_EXEC, PTRACE_EVENT_EXIT, PTRACE_EVENT_STOP};
+
+/// General purpose registers of a thread.
+pub type GpRegisters = libc::user_regs_struct;
+/// Floating point registers of a thread.
+pub type FpRegisters = libc::user_fpregs_struct;
+
+/// `PTRACE_ARCH_PRCTL` request, reads or writes thread segment bases (x86_64 only).
+const PTRACE_ARCH_PRCTL: libc::c_uint = 30;
+/// `arch_prctl` code for reading an FS segment base.
+const ARCH_GET_FS: libc::c_ulong = 0x1003;
+/// `arch_prctl` code for reading a GS segment base.
+const ARCH_GET_GS: libc::c_ulong = 0x1004;
+/// Note type of an extended processor state (XSAVE area) register set.
+const NT_X86_XSTATE: libc::c_int = 0x202;
+
+/// Native linux target.
+pub struct Linux;
+
+impl Linux {
+    /// Return an offset of a debug register in a `user` area.
+    fn debug_register_offset(num: usize) -> AddressType {
+        (offset_of!(libc::user, u_debugreg) + num * 8) as AddressType
+    }
+}
+
+impl Target for Linux {
+    fn waitpid(&self, pid: Pid, options: Option<WaitPidFlag>) -> nix::Result<WaitStatus> {
+        wait::waitpid(nix::unistd::Pid::from(pid), options).map(WaitStatus::from)
+    }
+
+    fn seize(&self, pid: Pid) -> nix::Result<()> {
+        ptrace::seize(
+            pid.into(),
+            Options::PTRACE_O_TRACECLONE
+                .union(Options::PTRACE_O_TRACEEXEC)
+                .union(Options::PTRACE_O_TRACEEXIT),
+        )
+    }
+
+    fn interrupt(&self, pid: Pid) -> nix::Result<()> {
+        ptrace::interrupt(pid.into())
+    }
+
+    fn cont(&self, pid: Pid, sig: Option<Signal>) -> nix::Result<()> {
+        ptrace::cont(pid.into(), sig.map(Into::into))
+    }
+
+    fn step(&self, pid: Pid, sig: Option<Signal>) -> nix::Result<()> {
+        ptrace::step(pid.into(), sig.map(Into::into))
+    }
+
+    fn syscall(&self, pid: Pid, sig: Option<Signal>) -> nix::Result<()> {
+        ptrace::syscall(pid.into(), sig.map(Into::into))
+    }
+
+    fn detach(&self, pid: Pid, sig: Option<Signal>) -> nix::Result<()> {
+        ptrace::detach(pid.into(), sig.map(Into::into))
+    }
+
+    fn kill(&self, pid: Pid, sig: Signal) -> nix::Result<()> {
+        signal::kill(pid.into(), Some(sig.into()))
+    }
+
+    fn getevent(&self, pid: Pid) -> nix::Result<i64> {
+        ptrace::getevent(pid.into())
+    }
+
+    fn sigcode(&self, pid: Pid) -> nix::Result<i32> {
+        ptrace::getsiginfo(pid.into()).map(|info| info.si_code)
+    }
+
+    fn thread_exists(&self, pid: Pid, tid: Pid) -> bool {
+        Path::new(&format!("/proc/{pid}/task/{tid}")).exists()
+    }
+
+    fn read(&self, pid: Pid, addr: usize) -> nix::Result<i64> {
+        ptrace::read(pid.into(), addr as AddressType)
+    }
+
+    fn write(&self, pid: Pid, addr: usize, data: i64) -> nix::Result<()> {
+        // SAFETY: data is a value, not a pointer, ptrace writes it into a tracee memory
+        unsafe { ptrace::write(pid.into(), addr as AddressType, data as *mut libc::c_void) }
+    }
+
+    fn gp_registers(&self, pid: Pid) -> nix::Result<GpRegisters> {
+        let mut regs = mem::MaybeUninit::<GpRegisters>::uninit();
+        let mut iov = libc::iovec {
+            iov_base: regs.as_mut_ptr() as *mut libc::c_void,
+            iov_len: mem::size_of::<GpRegisters>(),
+        };
+        // SAFETY: PTRACE_GETREGSET writes at most `iov_len` bytes into `regs`
+        let res = unsafe {
+            libc::ptrace(
+                libc::PTRACE_GETREGSET,
+                pid.as_raw(),
+                libc::NT_PRSTATUS as usize as *mut libc::c_void,
+                &mut iov as *mut libc::iovec,
+            )
+        };
+        Errno::result(res)?;
+        if iov.iov_len != mem::size_of::<GpRegisters>() {
+            // register set is incomplete, fallback to PTRACE_GETREGS
+            return ptrace::getregs(pid.into());
+        }
+        // SAFETY: structure initialized by the syscall above
+        Ok(unsafe { regs.assume_init() })
+    }
+
+    fn set_gp_registers(&self, pid: Pid, regs: GpRegisters) -> nix::Result<()> {
+        ptrace::setregs(pid.into(), regs)
+    }
+
+    fn segment_base(&self, pid: Pid, segment: Segment) -> nix::Result<u64> {
+        let code = match segment {
+            Segment::Fs => ARCH_GET_FS,
+            Segment::Gs => ARCH_GET_GS,
+        };
+        let mut base: libc::c_ulong = 0;
+        // SAFETY: PTRACE_ARCH_PRCTL writes a segment base into `base`
+        let res = unsafe {
+            libc::ptrace(
+                PTRACE_ARCH_PRCTL,
+                pid.as_raw(),
+                &mut base as *mut libc::c_ulong,
+                code,
+            )
+        };
+        Errno::result(res)?;
+        Ok(base)
+    }
+
+    fn fp_registers(&self, pid: Pid) -> nix::Result<FpRegisters> {
+        let mut fp_regs = mem::MaybeUninit::<FpRegisters>::uninit();
+        // SAFETY: PTRACE_GETFPREGS fills a `user_fpregs_struct` for a stopped thread
+        let res = unsafe {
+            libc::ptrace(
+                libc::PTRACE_GETFPREGS,
+                pid.as_raw(),
+                ptr::null_mut::<libc::c_void>(),
+                fp_regs.as_mut_ptr(),
+            )
+        };
+        Errno::result(res)?;
+        // SAFETY: structure initialized by the syscall above
+        Ok(unsafe { fp_regs.assume_init() })
+    }
+
+    fn set_fp_registers(&self, pid: Pid, regs: &FpRegisters) -> nix::Result<()> {
+        // SAFETY: `regs` is a valid `user_fpregs_struct`
+        let res = unsafe {
+            libc::ptrace(
+                libc::PTRACE_SETFPREGS,
+                pid.as_raw(),
+                ptr::null_mut::<libc::c_void>(),
+                regs as *const FpRegisters,
+            )
+        };
+        Errno::result(res).map(drop)
+    }
+
+    fn xstate(&self, pid: Pid, size: usize) -> nix::Result<Vec<u8>> {
+        let mut area = vec![0u8; size];
+        let mut iov = libc::iovec {
+            iov_base: area.as_mut_ptr() as *mut libc::c_void,
+            iov_len: area.len(),
+        };
+        // SAFETY: PTRACE_GETREGSET writes at most `iov_len` bytes into `area`
+        let res = unsafe {
+            libc::ptrace(
+                libc::PTRACE_GETREGSET,
+                pid.as_raw(),
+                NT_X86_XSTATE as usize as *mut libc::c_void,
+                &mut iov as *mut libc::iovec,
+            )
+        };
+        Errno::result(res)?;
+        // kernel updates `iov_len` with the number of bytes written
+        area.truncate(iov.iov_len);
+        Ok(area)
+    }
+
+    fn debug_register(&self, pid: Pid, num: usize) -> nix::Result<usize> {
+        ptrace::read_user(pid.into(), Self::debug_register_offset(num)).map(|v| v as usize)
+    }
+
+    fn set_debug_register(&self, pid: Pid, num: usize, value: usize) -> nix::Result<()> {
+        // SAFETY: data is a value, not a pointer, ptrace writes it into a `user` area
+        unsafe {
+            ptrace::write_user(
+                pid.into(),
+                Self::debug_register_offset(num),
+                value as *mut libc::c_void,
+            )
+        }
+    }
+}
@@ -0,0 +1,329 @@
+//! Mock target for unit tests. Mock replays scripted thread statuses instead of real ones
+//! and records all resume requests, so tests don't depend on a scheduler and a kernel.
+//!
+//! Mock behaves like a kernel in a few simple cases:
+//! - a thread replies to `interrupt` with a `PTRACE_EVENT_STOP` event, except a thread
+//!   in uninterruptible sleep, it never replies
+//! - a single step moves a program counter by one byte and stops a thread with `TRAP_TRACE`
+//! - requests to threads that don't exist (or already exit) fail with `ESRCH`
+
+use crate::debugger::code;
+use crate::debugger::sys::{
+    FpRegisters, GpRegisters, Pid, Segment, Signal, Target, WaitStatus, PTRACE_EVENT_STOP,
+};
+use nix::errno::Errno;
+use nix::sys::wait::WaitPidFlag;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ops::Deref;
+use std::rc::Rc;
+
+thread_local! {
+    static CURRENT: RefCell<Option<Rc<Mock>>> = const { RefCell::new(None) };
+}
+
+/// Return a mock installed for the current thread.
+pub(super) fn current() -> Option<Rc<Mock>> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+/// Request that changes a thread state.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Request {
+    Seize(Pid),
+    Interrupt(Pid),
+    Cont(Pid, Option<Signal>),
+    Step(Pid, Option<Signal>),
+    Syscall(Pid, Option<Signal>),
+    Detach(Pid, Option<Signal>),
+    Kill(Pid, Signal),
+}
+
+#[derive(Default)]
+struct State {
+    /// Scripted statuses with a signal code (`si_code`) of a stop.
+    statuses: VecDeque<(WaitStatus, i32)>,
+    /// Existing threads.
+    threads: HashSet<Pid>,
+    /// Threads that don't reply to an interrupt.
+    uninterruptible: HashSet<Pid>,
+    /// Signal codes of the last stop of a thread.
+    sigcodes: HashMap<Pid, i32>,
+    /// Messages of the last ptrace event of a thread.
+    events: HashMap<Pid, i64>,
+    registers: HashMap<Pid, GpRegisters>,
+    debug_registers: HashMap<(Pid, usize), usize>,
+    memory: HashMap<usize, i64>,
+    requests: Vec<Request>,
+}
+
+impl State {
+    fn ensure_exists(&self, pid: Pid) -> nix::Result<()> {
+        if self.threads.contains(&pid) {
+            Ok(())
+        } else {
+            Err(Errno::ESRCH)
+        }
+    }
+
+    fn registers_mut(&mut self, pid: Pid) -> &mut GpRegisters {
+        // SAFETY: general purpose registers are plain integers, all zeroes is a valid value
+        self.registers
+            .entry(pid)
+            .or_insert_with(|| unsafe { std::mem::zeroed() })
+    }
+
+    fn resume(&mut self, request: Request, pid: Pid) -> nix::Result<()> {
+        self.ensure_exists(pid)?;
+        self.requests.push(request);
+        Ok(())
+    }
+}
+
+/// Scripted target.
+#[derive(Default)]
+pub struct Mock {
+    state: RefCell<State>,
+}
+
+impl Mock {
+    /// Create a mock with existing threads.
+    pub fn new(threads: &[Pid]) -> Self {
+        let mock = Self::default();
+        mock.state.borrow_mut().threads.extend(threads);
+        mock
+    }
+
+    /// Use mock as a target of the current thread until the guard is dropped.
+    pub fn install(self) -> MockGuard {
+        let mock = Rc::new(self);
+        CURRENT.with(|current| current.replace(Some(mock.clone())));
+        MockGuard(mock)
+    }
+
+    /// Add a new thread (for example, created by a clone).
+    pub fn add_thread(&self, tid: Pid) {
+        self.state.borrow_mut().threads.insert(tid);
+    }
+
+    /// Put a thread into uninterruptible sleep, it doesn't reply to an interrupt.
+    pub fn set_uninterruptible(&self, tid: Pid) {
+        self.state.borrow_mut().uninterruptible.insert(tid);
+    }
+
+    /// Add a status to the end of a status queue.
+    pub fn push_status(&self, status: WaitStatus) {
+        self.state.borrow_mut().statuses.push_back((status, 0));
+    }
+
+    /// Add a signal-stop status to the end of a status queue.
+    pub fn push_signal(&self, pid: Pid, signal: Signal) {
+        self.push_status(WaitStatus::Stopped(pid, signal));
+    }
+
+    /// Add a `SIGTRAP` stop with a signal code (like `TRAP_BRKPT`) to the end of a status queue.
+    pub fn push_trap(&self, pid: Pid, code: i32) {
+        self.state
+            .borrow_mut()
+            .statuses
+            .push_back((WaitStatus::Stopped(pid, Signal::SIGTRAP), code));
+    }
+
+    /// Add a ptrace event with an event message to the end of a status queue.
+    pub fn push_event(&self, pid: Pid, event: i32, message: i64) {
+        let mut state = self.state.borrow_mut();
+        state.events.insert(pid, message);
+        state
+            .statuses
+            .push_back((WaitStatus::PtraceEvent(pid, Signal::SIGTRAP, event), 0));
+    }
+
+    /// Return a program counter of a thread.
+    pub fn pc(&self, pid: Pid) -> u64 {
+        self.state.borrow_mut().registers_mut(pid).rip
+    }
+
+    /// Set a program counter of a thread.
+    pub fn set_pc(&self, pid: Pid, pc: u64) {
+        self.state.borrow_mut().registers_mut(pid).rip = pc;
+    }
+
+    /// Return all requests received by a mock.
+    pub fn requests(&self) -> Vec<Request> {
+        self.state.borrow().requests.clone()
+    }
+}
+
+/// Installed mock, it is uninstalled on drop.
+pub struct MockGuard(Rc<Mock>);
+
+impl Deref for MockGuard {
+    type Target = Mock;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Drop for MockGuard {
+    fn drop(&mut self) {
+        CURRENT.with(|current| current.replace(None));
+    }
+}
+
+impl Target for Mock {
+    fn waitpid(&self, pid: Pid, options: Option<WaitPidFlag>) -> nix::Result<WaitStatus> {
+        let mut state = self.state.borrow_mut();
+        let pos = if pid == Pid::from_raw(-1) {
+            (!state.statuses.is_empty()).then_some(0)
+        } else {
+            state
+                .statuses
+                .iter()
+                .position(|(s, _)| s.pid() == Some(pid))
+        };
+
+        let Some((status, code)) = pos.and_then(|pos| state.statuses.remove(pos)) else {
+            let no_hang = options.is_some_and(|o| o.contains(WaitPidFlag::WNOHANG));
+            return if no_hang && state.threads.contains(&pid) {
+                Ok(WaitStatus::StillAlive)
+            } else {
+                Err(Errno::ECHILD)
+            };
+        };
+
+        if let Some(pid) = status.pid() {
+            match status {
+                WaitStatus::Exited(..) | WaitStatus::Signaled(..) => {
+                    state.threads.remove(&pid);
+                }
+                _ => {
+                    state.sigcodes.insert(pid, code);
+                }
+            }
+        }
+        Ok(status)
+    }
+
+    fn seize(&self, pid: Pid) -> nix::Result<()> {
+        self.state.borrow_mut().resume(Request::Seize(pid), pid)
+    }
+
+    fn interrupt(&self, pid: Pid) -> nix::Result<()> {
+        let mut state = self.state.borrow_mut();
+        state.resume(Request::Interrupt(pid), pid)?;
+        if state.uninterruptible.contains(&pid) {
+            return Ok(());
+        }
+        state.statuses.push_back((
+            WaitStatus::PtraceEvent(pid, Signal::SIGTRAP, PTRACE_EVENT_STOP),
+            0,
+        ));
+        Ok(())
+    }
+
+    fn cont(&self, pid: Pid, sig: Option<Signal>) -> nix::Result<()> {
+        self.state.borrow_mut().resume(Request::Cont(pid, sig), pid)
+    }
+
+    fn step(&self, pid: Pid, sig: Option<Signal>) -> nix::Result<()> {
+        let mut state = self.state.borrow_mut();
+        state.resume(Request::Step(pid, sig), pid)?;
+        state.registers_mut(pid).rip += 1;
+        state
+            .statuses
+            .push_back((WaitStatus::Stopped(pid, Signal::SIGTRAP), code::TRAP_TRACE));
+        Ok(())
+    }
+
+    fn syscall(&self, pid: Pid, sig: Option<Signal>) -> nix::Result<()> {
+        self.state
+            .borrow_mut()
+            .resume(Request::Syscall(pid, sig), pid)
+    }
+
+    fn detach(&self, pid: Pid, sig: Option<Signal>) -> nix::Result<()> {
+        self.state
+            .borrow_mut()
+            .resume(Request::Detach(pid, sig), pid)
+    }
+
+    fn kill(&self, pid: Pid, sig: Signal) -> nix::Result<()> {
+        self.state.borrow_mut().resume(Request::Kill(pid, sig), pid)
+    }
+
+    fn getevent(&self, pid: Pid) -> nix::Result<i64> {
+        let state = self.state.borrow();
+        state.ensure_exists(pid)?;
+        Ok(state.events.get(&pid).copied().unwrap_or_default())
+    }
+
+    fn sigcode(&self, pid: Pid) -> nix::Result<i32> {
+        let state = self.state.borrow();
+        state.ensure_exists(pid)?;
+        Ok(state.sigcodes.get(&pid).copied().unwrap_or_default())
+    }
+
+    fn thread_exists(&self, _pid: Pid, tid: Pid) -> bool {
+        self.state.borrow().threads.contains(&tid)
+    }
+
+    fn read(&self, pid: Pid, addr: usize) -> nix::Result<i64> {
+        let state = self.state.borrow();
+        state.ensure_exists(pid)?;
+        Ok(state.memory.get(&addr).copied().unwrap_or_default())
+    }
+
+    fn write(&self, pid: Pid, addr: usize, data: i64) -> nix::Result<()> {
+        let mut state = self.state.borrow_mut();
+        state.ensure_exists(pid)?;
+        state.memory.insert(addr, data);
+        Ok(())
+    }
+
+    fn gp_registers(&self, pid: Pid) -> nix::Result<GpRegisters> {
+        let mut state = self.state.borrow_mut();
+        state.ensure_exists(pid)?;
+        Ok(*state.registers_mut(pid))
+    }
+
+    fn set_gp_registers(&self, pid: Pid, regs: GpRegisters) -> nix::Result<()> {
+        let mut state = self.state.borrow_mut();
+        state.ensure_exists(pid)?;
+        state.registers.insert(pid, regs);
+        Ok(())
+    }
+
+    fn segment_base(&self, _pid: Pid, _segment: Segment) -> nix::Result<u64> {
+        Err(Errno::ENOSYS)
+    }
+
+    fn fp_registers(&self, _pid: Pid) -> nix::Result<FpRegisters> {
+        Err(Errno::ENOSYS)
+    }
+
+    fn set_fp_registers(&self, _pid: Pid, _regs: &FpRegisters) -> nix::Result<()> {
+        Err(Errno::ENOSYS)
+    }
+
+    fn xstate(&self, _pid: Pid, _size: usize) -> nix::Result<Vec<u8>> {
+        Err(Errno::ENOSYS)
+    }
+
+    fn debug_register(&self, pid: Pid, num: usize) -> nix::Result<usize> {
+        let state = self.state.borrow();
+        state.ensure_exists(pid)?;
+        Ok(state
+            .debug_registers
+            .get(&(pid, num))
+            .copied()
+            .unwrap_or_default())
+    }
+
+    fn set_debug_register(&self, pid: Pid, num: usize, value: usize) -> nix::Result<()> {
+        let mut state = self.state.borrow_mut();
+        state.ensure_exists(pid)?;
+        state.debug_registers.insert((pid, num), value);
+        Ok(())
+    }
+}
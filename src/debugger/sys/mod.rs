@@ -0,0 +1,211 @@
+//! Target system layer. All ptrace, wait and signal requests of a debugger go through this module,
+//! so a tracer doesn't depend on a kernel interface directly:
+//! - [`Target`] is a set of requests that a debugger makes to a kernel
+//! - [`linux::Linux`] implements requests with linux `ptrace` and `waitpid`
+//! - [`mock::Mock`] (only in unit tests) replays scripted thread statuses and records requests,
+//!   so a tracer state machine can be tested without real processes
+//!
+//! Requests are free functions, they are executed by a target of the current thread
+//! (a mock if it is installed, linux otherwise).
+//!
+//! Pids, signals and wait statuses are debugger types (see [`types`]), a target converts them
+//! into a kernel interface ones.
+
+pub mod linux;
+#[cfg(test)]
+pub mod mock;
+pub mod types;
+
+pub use linux::{
+    FpRegisters, GpRegisters, PTRACE_EVENT_CLONE, PTRACE_EVENT_EXEC, PTRACE_EVENT_EXIT,
+    PTRACE_EVENT_STOP,
+};
+use nix::sys::wait::WaitPidFlag;
+pub use types::{Pid, Signal, WaitStatus};
+
+/// Thread segment registers which base addresses are not a part of general purpose registers.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Segment {
+    Fs,
+    Gs,
+}
+
+/// Requests that a debugger makes to a target kernel.
+pub trait Target {
+    /// Wait for a change of thread status, `pid` -1 means any child thread.
+    fn waitpid(&self, pid: Pid, options: Option<WaitPidFlag>) -> nix::Result<WaitStatus>;
+
+    /// Attach to a thread without stopping it, clone, exec and exit events of a thread are traced.
+    fn seize(&self, pid: Pid) -> nix::Result<()>;
+
+    /// Stop a seized thread, thread reports a `PTRACE_EVENT_STOP` event.
+    fn interrupt(&self, pid: Pid) -> nix::Result<()>;
+
+    /// Resume a stopped thread, if signal is some - inject it into a thread.
+    fn cont(&self, pid: Pid, sig: Option<Signal>) -> nix::Result<()>;
+
+    /// Execute a single instruction of a stopped thread.
+    fn step(&self, pid: Pid, sig: Option<Signal>) -> nix::Result<()>;
+
+    /// Resume a stopped thread until the next syscall entry or exit.
+    fn syscall(&self, pid: Pid, sig: Option<Signal>) -> nix::Result<()>;
+
+    /// Detach from a stopped thread.
+    fn detach(&self, pid: Pid, sig: Option<Signal>) -> nix::Result<()>;
+
+    /// Send a signal to a process.
+    fn kill(&self, pid: Pid, sig: Signal) -> nix::Result<()>;
+
+    /// Return a message of the last ptrace event (like a new thread id for a clone event).
+    fn getevent(&self, pid: Pid) -> nix::Result<i64>;
+
+    /// Return a code (`si_code`) of a signal that stops a thread.
+    fn sigcode(&self, pid: Pid) -> nix::Result<i32>;
+
+    /// Return true if thread `tid` belongs to the process `pid`.
+    fn thread_exists(&self, pid: Pid, tid: Pid) -> bool;
+
+    /// Read a machine word from a process memory.
+    fn read(&self, pid: Pid, addr: usize) -> nix::Result<i64>;
+
+    /// Write a machine word into a process memory.
+    fn write(&self, pid: Pid, addr: usize, data: i64) -> nix::Result<()>;
+
+    /// Return general purpose registers of a thread.
+    fn gp_registers(&self, pid: Pid) -> nix::Result<GpRegisters>;
+
+    /// Replace general purpose registers of a thread.
+    fn set_gp_registers(&self, pid: Pid, regs: GpRegisters) -> nix::Result<()>;
+
+    /// Return a base address of a thread segment.
+    fn segment_base(&self, pid: Pid, segment: Segment) -> nix::Result<u64>;
+
+    /// Return floating point registers of a thread.
+    fn fp_registers(&self, pid: Pid) -> nix::Result<FpRegisters>;
+
+    /// Replace floating point registers of a thread.
+    fn set_fp_registers(&self, pid: Pid, regs: &FpRegisters) -> nix::Result<()>;
+
+    /// Return an extended processor state (XSAVE area) of a thread, no longer than `size` bytes.
+    fn xstate(&self, pid: Pid, size: usize) -> nix::Result<Vec<u8>>;
+
+    /// Return a value of a hardware debug register.
+    fn debug_register(&self, pid: Pid, num: usize) -> nix::Result<usize>;
+
+    /// Set a value of a hardware debug register.
+    fn set_debug_register(&self, pid: Pid, num: usize, value: usize) -> nix::Result<()>;
+}
+
+/// Execute a request by a target of the current thread.
+fn with_target<T>(f: impl FnOnce(&dyn Target) -> T) -> T {
+    #[cfg(test)]
+    if let Some(mock) = mock::current() {
+        return f(mock.as_ref());
+    }
+    f(&linux::Linux)
+}
+
+/// See [`Target::waitpid`].
+pub fn waitpid(pid: Pid, options: Option<WaitPidFlag>) -> nix::Result<WaitStatus> {
+    with_target(|t| t.waitpid(pid, options))
+}
+
+/// See [`Target::seize`].
+pub fn seize(pid: Pid) -> nix::Result<()> {
+    with_target(|t| t.seize(pid))
+}
+
+/// See [`Target::interrupt`].
+pub fn interrupt(pid: Pid) -> nix::Result<()> {
+    with_target(|t| t.interrupt(pid))
+}
+
+/// See [`Target::cont`].
+pub fn cont<S: Into<Option<Signal>>>(pid: Pid, sig: S) -> nix::Result<()> {
+    with_target(|t| t.cont(pid, sig.into()))
+}
+
+/// See [`Target::step`].
+pub fn step<S: Into<Option<Signal>>>(pid: Pid, sig: S) -> nix::Result<()> {
+    with_target(|t| t.step(pid, sig.into()))
+}
+
+/// See [`Target::syscall`].
+pub fn syscall<S: Into<Option<Signal>>>(pid: Pid, sig: S) -> nix::Result<()> {
+    with_target(|t| t.syscall(pid, sig.into()))
+}
+
+/// See [`Target::detach`].
+pub fn detach<S: Into<Option<Signal>>>(pid: Pid, sig: S) -> nix::Result<()> {
+    with_target(|t| t.detach(pid, sig.into()))
+}
+
+/// See [`Target::kill`].
+pub fn kill(pid: Pid, sig: Signal) -> nix::Result<()> {
+    with_target(|t| t.kill(pid, sig))
+}
+
+/// See [`Target::getevent`].
+pub fn getevent(pid: Pid) -> nix::Result<i64> {
+    with_target(|t| t.getevent(pid))
+}
+
+/// See [`Target::sigcode`].
+pub fn sigcode(pid: Pid) -> nix::Result<i32> {
+    with_target(|t| t.sigcode(pid))
+}
+
+/// See [`Target::thread_exists`].
+pub fn thread_exists(pid: Pid, tid: Pid) -> bool {
+    with_target(|t| t.thread_exists(pid, tid))
+}
+
+/// See [`Target::read`].
+pub fn read(pid: Pid, addr: usize) -> nix::Result<i64> {
+    with_target(|t| t.read(pid, addr))
+}
+
+/// See [`Target::write`].
+pub fn write(pid: Pid, addr: usize, data: i64) -> nix::Result<()> {
+    with_target(|t| t.write(pid, addr, data))
+}
+
+/// See [`Target::gp_registers`].
+pub fn gp_registers(pid: Pid) -> nix::Result<GpRegisters> {
+    with_target(|t| t.gp_registers(pid))
+}
+
+/// See [`Target::set_gp_registers`].
+pub fn set_gp_registers(pid: Pid, regs: GpRegisters) -> nix::Result<()> {
+    with_target(|t| t.set_gp_registers(pid, regs))
+}
+
+/// See [`Target::segment_base`].
+pub fn segment_base(pid: Pid, segment: Segment) -> nix::Result<u64> {
+    with_target(|t| t.segment_base(pid, segment))
+}
+
+/// See [`Target::fp_registers`].
+pub fn fp_registers(pid: Pid) -> nix::Result<FpRegisters> {
+    with_target(|t| t.fp_registers(pid))
+}
+
+/// See [`Target::set_fp_registers`].
+pub fn set_fp_registers(pid: Pid, regs: &FpRegisters) -> nix::Result<()> {
+    with_target(|t| t.set_fp_registers(pid, regs))
+}
+
+/// See [`Target::xstate`].
+pub fn xstate(pid: Pid, size: usize) -> nix::Result<Vec<u8>> {
+    with_target(|t| t.xstate(pid, size))
+}
+
+/// See [`Target::debug_register`].
+pub fn debug_register(pid: Pid, num: usize) -> nix::Result<usize> {
+    with_target(|t| t.debug_register(pid, num))
+}
+
+/// See [`Target::set_debug_register`].
+pub fn set_debug_register(pid: Pid, num: usize, value: usize) -> nix::Result<()> {
+    with_target(|t| t.set_debug_register(pid, num, value))
+}
@@ -0,0 +1,153 @@
+//! Process identifiers, signals and wait statuses of a debugger. Types are owned by a debugger
+//! (a kernel interface library is an implementation detail), conversions are done by a target.
+
+use nix::sys::signal::Signal as NixSignal;
+use nix::sys::wait::WaitStatus as NixWaitStatus;
+use nix::unistd::Pid as NixPid;
+use std::fmt::{Debug, Display, Formatter};
+
+/// Process or thread identifier.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Pid(NixPid);
+
+impl Pid {
+    /// Create a pid from a raw value.
+    pub const fn from_raw(pid: i32) -> Self {
+        Self(NixPid::from_raw(pid))
+    }
+
+    /// Return a raw value of a pid.
+    pub const fn as_raw(self) -> i32 {
+        self.0.as_raw()
+    }
+
+    /// Return a pid of the current process.
+    pub fn this() -> Self {
+        Self(NixPid::this())
+    }
+}
+
+impl From<NixPid> for Pid {
+    fn from(pid: NixPid) -> Self {
+        Self(pid)
+    }
+}
+
+impl From<Pid> for NixPid {
+    fn from(pid: Pid) -> Self {
+        pid.0
+    }
+}
+
+impl Debug for Pid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+impl Display for Pid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+/// Unix signal.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Signal(NixSignal);
+
+macro_rules! signals {
+    ($($name: ident),* $(,)?) => {
+        impl Signal {
+            $(pub const $name: Signal = Signal(NixSignal::$name);)*
+        }
+    };
+}
+
+signals!(
+    SIGHUP, SIGINT, SIGQUIT, SIGILL, SIGTRAP, SIGABRT, SIGBUS, SIGFPE, SIGKILL, SIGUSR1, SIGSEGV,
+    SIGUSR2, SIGPIPE, SIGALRM, SIGTERM, SIGSTKFLT, SIGCHLD, SIGCONT, SIGSTOP, SIGTSTP, SIGTTIN,
+    SIGTTOU, SIGURG, SIGXCPU, SIGXFSZ, SIGVTALRM, SIGPROF, SIGWINCH, SIGIO, SIGPWR, SIGSYS,
+);
+
+impl Signal {
+    /// Return a signal number.
+    pub fn as_raw(self) -> i32 {
+        self.0 as i32
+    }
+}
+
+impl From<NixSignal> for Signal {
+    fn from(signal: NixSignal) -> Self {
+        Self(signal)
+    }
+}
+
+impl From<Signal> for NixSignal {
+    fn from(signal: Signal) -> Self {
+        signal.0
+    }
+}
+
+impl Debug for Signal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+impl Display for Signal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+/// Change of a thread status, returned by a `waitpid` request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WaitStatus {
+    /// Thread exits with an exit code.
+    Exited(Pid, i32),
+    /// Thread is killed by a signal, last field is true if a core dump is produced.
+    Signaled(Pid, Signal, bool),
+    /// Thread is stopped by a signal.
+    Stopped(Pid, Signal),
+    /// Thread is stopped by a ptrace event (`PTRACE_EVENT_*`).
+    PtraceEvent(Pid, Signal, i32),
+    /// Thread is stopped at a syscall entry or exit.
+    PtraceSyscall(Pid),
+    /// Thread is continued by `SIGCONT`.
+    Continued(Pid),
+    /// There are no status changes (with `WNOHANG` only).
+    StillAlive,
+}
+
+impl WaitStatus {
+    /// Return a pid of a thread which status is changed.
+    pub fn pid(&self) -> Option<Pid> {
+        match *self {
+            WaitStatus::Exited(pid, _)
+            | WaitStatus::Signaled(pid, _, _)
+            | WaitStatus::Stopped(pid, _)
+            | WaitStatus::PtraceEvent(pid, _, _)
+            | WaitStatus::PtraceSyscall(pid)
+            | WaitStatus::Continued(pid) => Some(pid),
+            WaitStatus::StillAlive => None,
+        }
+    }
+}
+
+impl From<NixWaitStatus> for WaitStatus {
+    fn from(status: NixWaitStatus) -> Self {
+        match status {
+            NixWaitStatus::Exited(pid, code) => WaitStatus::Exited(pid.into(), code),
+            NixWaitStatus::Signaled(pid, sig, core) => {
+                WaitStatus::Signaled(pid.into(), sig.into(), core)
+            }
+            NixWaitStatus::Stopped(pid, sig) => WaitStatus::Stopped(pid.into(), sig.into()),
+            NixWaitStatus::PtraceEvent(pid, sig, event) => {
+                WaitStatus::PtraceEvent(pid.into(), sig.into(), event)
+            }
+            NixWaitStatus::PtraceSyscall(pid) => WaitStatus::PtraceSyscall(pid.into()),
+            NixWaitStatus::Continued(pid) => WaitStatus::Continued(pid.into()),
+            NixWaitStatus::StillAlive => WaitStatus::StillAlive,
+        }
+    }
+}
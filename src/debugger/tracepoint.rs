@@ -0,0 +1,316 @@
+//! Tracepoints are breakpoints that never stop a debugee.
+//! When tracepoint is hit, debugger records a hit (time, thread and values of
+//! tracepoint expressions), steps over the breakpoint and continues execution.
+//! Collected hits are available for later analysis (see [`Debugger::tracepoints`]).
+//!
+//! Tracepoint with a format string (dprintf) also prints a message with values
+//! of expressions at every hit (see [`crate::debugger::EventHook::on_dprintf`]).
+
+use crate::debugger::breakpoint::CreateTransparentBreakpointRequest;
+use crate::debugger::variable::select::DQE;
+use crate::debugger::variable::VariableIR;
+use crate::debugger::Pid;
+use crate::debugger::{Debugger, Error};
+use crate::weak_error;
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+use std::time::SystemTime;
+
+/// Maximum number of hits stored for a single tracepoint,
+/// older hits are dropped (but still counted).
+const MAX_STORED_HITS: usize = 10_000;
+
+/// Place in source code where tracepoint is set.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TracepointPlace {
+    Line(String, u64),
+    Function(String),
+}
+
+impl Display for TracepointPlace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TracepointPlace::Line(file, line) => write!(f, "{file}:{line}"),
+            TracepointPlace::Function(name) => f.write_str(name),
+        }
+    }
+}
+
+/// Format string of a dprintf tracepoint. Each `{}` is a placeholder for the next expression
+/// value, `{{` and `}}` are used for literal braces.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormatString {
+    /// Text pieces between placeholders, there is always one more piece than placeholders.
+    pieces: Vec<String>,
+}
+
+impl FormatString {
+    /// Parse a format string.
+    ///
+    /// # Errors
+    ///
+    /// Return error if braces are unbalanced or placeholder is not empty.
+    pub fn parse(format: &str) -> Result<Self, Error> {
+        let mut pieces = vec![String::new()];
+        let mut chars = format.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    pieces.last_mut().expect("infallible").push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    pieces.last_mut().expect("infallible").push('}');
+                }
+                '{' => {
+                    if chars.next() != Some('}') {
+                        return Err(Error::FormatString("expected `}` after `{`"));
+                    }
+                    pieces.push(String::new());
+                }
+                '}' => return Err(Error::FormatString("unmatched `}`")),
+                _ => pieces.last_mut().expect("infallible").push(c),
+            }
+        }
+        Ok(Self { pieces })
+    }
+
+    /// Return number of placeholders.
+    pub fn placeholders(&self) -> usize {
+        self.pieces.len() - 1
+    }
+
+    /// Substitute placeholders with arguments. Missing arguments are substituted
+    /// with an empty string, extra arguments are ignored.
+    pub fn format<T: Display>(&self, args: impl IntoIterator<Item = T>) -> String {
+        let mut args = args.into_iter();
+        let mut out = self.pieces[0].clone();
+        for piece in &self.pieces[1..] {
+            if let Some(arg) = args.next() {
+                out.push_str(&arg.to_string());
+            }
+            out.push_str(piece);
+        }
+        out
+    }
+}
+
+/// A single tracepoint hit.
+#[derive(Debug, Clone)]
+pub struct TracepointHit {
+    /// Time of hit.
+    pub time: SystemTime,
+    /// Thread that hit a tracepoint.
+    pub tid: Pid,
+    /// Values of tracepoint expressions (in the same order as expressions),
+    /// `None` if expression can't be evaluated at hit time.
+    pub values: Vec<Option<VariableIR>>,
+}
+
+/// Tracepoint with collected hits.
+#[derive(Debug)]
+pub struct Tracepoint {
+    pub number: u32,
+    pub place: TracepointPlace,
+    /// Source strings of tracepoint expressions.
+    pub expressions: Vec<String>,
+    /// Message format, if exists then message is printed at each hit.
+    pub format: Option<FormatString>,
+    /// Total number of hits.
+    pub hit_count: u64,
+    /// Last [`MAX_STORED_HITS`] hits.
+    pub hits: VecDeque<TracepointHit>,
+    dqe_list: Vec<DQE>,
+}
+
+impl Tracepoint {
+    fn record(&mut self, hit: TracepointHit) {
+        self.hit_count += 1;
+        if self.hits.len() == MAX_STORED_HITS {
+            self.hits.pop_front();
+        }
+        self.hits.push_back(hit);
+    }
+}
+
+#[derive(Default)]
+pub struct TracepointRegistry {
+    tracepoints: Vec<Tracepoint>,
+    last_number: u32,
+}
+
+impl TracepointRegistry {
+    fn get_mut(&mut self, number: u32) -> Option<&mut Tracepoint> {
+        self.tracepoints.iter_mut().find(|tp| tp.number == number)
+    }
+}
+
+impl Debugger {
+    /// Evaluate tracepoint expressions in current context. Variables are searched first,
+    /// then function arguments.
+    fn eval_tracepoint_expressions(&self, dqe_list: &[DQE]) -> Vec<Option<VariableIR>> {
+        dqe_list
+            .iter()
+            .map(|dqe| {
+                self.read_variable(dqe.clone())
+                    .ok()
+                    .and_then(|vars| vars.into_iter().next())
+                    .or_else(|| {
+                        self.read_argument(dqe.clone())
+                            .ok()
+                            .and_then(|args| args.into_iter().next())
+                    })
+            })
+            .collect()
+    }
+
+    /// Install transparent breakpoints for a tracepoint.
+    fn install_tracepoint(&mut self, number: u32, place: TracepointPlace) -> Result<(), Error> {
+        let callback = move |dbg: &mut Debugger| {
+            let Some(tp) = dbg.inferior_mut().tracepoints.get_mut(number) else {
+                return;
+            };
+            let dqe_list = tp.dqe_list.clone();
+            let format = tp.format.clone();
+
+            let hit = TracepointHit {
+                time: SystemTime::now(),
+                tid: dbg.exploration_ctx().pid_on_focus(),
+                values: dbg.eval_tracepoint_expressions(&dqe_list),
+            };
+            if let Some(format) = format {
+                dbg.hooks.on_dprintf(number, &format, &hit.values);
+            }
+            if let Some(tp) = dbg.inferior_mut().tracepoints.get_mut(number) {
+                tp.record(hit);
+            }
+        };
+
+        let request = match place {
+            TracepointPlace::Line(file, line) => {
+                CreateTransparentBreakpointRequest::line(file, line, callback)
+            }
+            TracepointPlace::Function(name) => {
+                CreateTransparentBreakpointRequest::function(name, callback)
+            }
+        };
+        self.set_transparent_breakpoint(request)
+    }
+
+    /// Create a new tracepoint. If debugee is not started yet, tracepoint will be installed
+    /// at debugee start. Tracepoints are reinstalled after debugee restart.
+    /// Return tracepoint number.
+    ///
+    /// # Arguments
+    ///
+    /// * `place`: tracepoint location
+    /// * `expressions`: list of pairs (source string, data query expression),
+    ///   expressions are evaluated at every hit
+    pub fn set_tracepoint(
+        &mut self,
+        place: TracepointPlace,
+        expressions: Vec<(String, DQE)>,
+    ) -> Result<u32, Error> {
+        self.add_tracepoint(place, None, expressions)
+    }
+
+    /// Create a new dprintf tracepoint. Same as [`Debugger::set_tracepoint`], but in addition
+    /// a message is printed (by [`crate::debugger::EventHook::on_dprintf`]) at every hit.
+    /// Return tracepoint number.
+    ///
+    /// # Arguments
+    ///
+    /// * `place`: tracepoint location
+    /// * `format`: message format, see [`FormatString`]
+    /// * `expressions`: list of pairs (source string, data query expression),
+    ///   values of expressions are substituted into the message
+    ///
+    /// # Errors
+    ///
+    /// Return error if format is invalid or number of placeholders doesn't match
+    /// number of expressions.
+    pub fn set_dprintf(
+        &mut self,
+        place: TracepointPlace,
+        format: &str,
+        expressions: Vec<(String, DQE)>,
+    ) -> Result<u32, Error> {
+        let format = FormatString::parse(format)?;
+        if format.placeholders() != expressions.len() {
+            return Err(Error::FormatArgumentsMismatch(
+                format.placeholders(),
+                expressions.len(),
+            ));
+        }
+        self.add_tracepoint(place, Some(format), expressions)
+    }
+
+    fn add_tracepoint(
+        &mut self,
+        place: TracepointPlace,
+        format: Option<FormatString>,
+        expressions: Vec<(String, DQE)>,
+    ) -> Result<u32, Error> {
+        let number = self.inferior().tracepoints.last_number + 1;
+        if self.is_in_progress() {
+            self.install_tracepoint(number, place.clone())?;
+        }
+
+        let (expressions, dqe_list) = expressions.into_iter().unzip();
+        self.inferior_mut().tracepoints.last_number = number;
+        self.inferior_mut()
+            .tracepoints
+            .tracepoints
+            .push(Tracepoint {
+                number,
+                place,
+                expressions,
+                format,
+                hit_count: 0,
+                hits: VecDeque::new(),
+                dqe_list,
+            });
+        Ok(number)
+    }
+
+    /// Install all registered tracepoints, called at debugee start.
+    pub(super) fn install_tracepoints(&mut self) {
+        let tracepoints = self
+            .inferior()
+            .tracepoints
+            .tracepoints
+            .iter()
+            .map(|tp| (tp.number, tp.place.clone()))
+            .collect::<Vec<_>>();
+        for (number, place) in tracepoints {
+            weak_error!(self.install_tracepoint(number, place));
+        }
+    }
+
+    /// Return all tracepoints with collected hits.
+    pub fn tracepoints(&self) -> &[Tracepoint] {
+        &self.inferior().tracepoints.tracepoints
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_format_string() {
+        let format = FormatString::parse("a = {}, b = {} {{literal}}").unwrap();
+        assert_eq!(format.placeholders(), 2);
+        assert_eq!(format.format([1, 2]), "a = 1, b = 2 {literal}");
+        assert_eq!(format.format([1]), "a = 1, b =  {literal}");
+
+        let format = FormatString::parse("no placeholders").unwrap();
+        assert_eq!(format.placeholders(), 0);
+        assert_eq!(format.format(Vec::<String>::new()), "no placeholders");
+
+        assert!(FormatString::parse("{x}").is_err());
+        assert!(FormatString::parse("{").is_err());
+        assert!(FormatString::parse("}").is_err());
+    }
+}
@@ -0,0 +1,276 @@
+//! Structural comparison of two variable values.
+//! Values are compared as they are rendered (see [`RenderRepr`]), so changes
+//! that aren't visible to a user (like a memory address of a variable) are ignored.
+
+use crate::debugger::variable::render::{RenderRepr, ValueLayout};
+use crate::debugger::variable::VariableIR;
+
+/// Difference between two values of a variable. Path is a relative path to a changed value,
+/// like `.field[1]` or `::Some.0`, an empty path means the root value.
+pub enum Change<'a> {
+    Changed {
+        path: String,
+        old: &'a VariableIR,
+        new: &'a VariableIR,
+    },
+    Added {
+        path: String,
+        new: &'a VariableIR,
+    },
+    Removed {
+        path: String,
+        old: &'a VariableIR,
+    },
+}
+
+/// Return key representation for a map or a set path, keys that haven't
+/// a scalar representation are rendered as a type name.
+fn key_repr(key: &VariableIR) -> String {
+    match key.value() {
+        Some(ValueLayout::PreRendered(s)) => s.to_string(),
+        _ => key.r#type().to_string(),
+    }
+}
+
+/// Return true if values are rendered the same.
+fn same(old: &VariableIR, new: &VariableIR) -> bool {
+    let mut changes = vec![];
+    diff_inner(String::new(), old, new, &mut changes);
+    changes.is_empty()
+}
+
+fn diff_inner<'a>(
+    path: String,
+    old: &'a VariableIR,
+    new: &'a VariableIR,
+    changes: &mut Vec<Change<'a>>,
+) {
+    if old.r#type() != new.r#type() {
+        changes.push(Change::Changed { path, old, new });
+        return;
+    }
+
+    match (old.value(), new.value()) {
+        (None, None) => {}
+        (Some(ValueLayout::PreRendered(a)), Some(ValueLayout::PreRendered(b))) if a == b => {}
+        (
+            Some(ValueLayout::Referential { addr: a, .. }),
+            Some(ValueLayout::Referential { addr: b, .. }),
+        ) if a == b => {}
+        (Some(ValueLayout::Wrapped(a)), Some(ValueLayout::Wrapped(b))) if a.name() == b.name() => {
+            diff_inner(format!("{path}::{}", a.name()), a, b, changes);
+        }
+        (
+            Some(ValueLayout::Structure { members: a }),
+            Some(ValueLayout::Structure { members: b }),
+        ) => {
+            for member in a {
+                let member_path = format!("{path}.{}", member.name());
+                match b.iter().find(|m| m.name() == member.name()) {
+                    Some(new_member) => diff_inner(member_path, member, new_member, changes),
+                    None => changes.push(Change::Removed {
+                        path: member_path,
+                        old: member,
+                    }),
+                }
+            }
+            for member in b {
+                if !a.iter().any(|m| m.name() == member.name()) {
+                    let member_path = format!("{path}.{}", member.name());
+                    changes.push(Change::Added {
+                        path: member_path,
+                        new: member,
+                    });
+                }
+            }
+        }
+        (
+            Some(ValueLayout::List {
+                members: a,
+                indexed: true,
+            }),
+            Some(ValueLayout::List {
+                members: b,
+                indexed: true,
+            }),
+        ) => {
+            for (item_a, item_b) in a.iter().zip(b) {
+                diff_inner(
+                    format!("{path}[{}]", item_a.name()),
+                    item_a,
+                    item_b,
+                    changes,
+                );
+            }
+            for item in &a[a.len().min(b.len())..] {
+                changes.push(Change::Removed {
+                    path: format!("{path}[{}]", item.name()),
+                    old: item,
+                });
+            }
+            for item in &b[a.len().min(b.len())..] {
+                changes.push(Change::Added {
+                    path: format!("{path}[{}]", item.name()),
+                    new: item,
+                });
+            }
+        }
+        (
+            Some(ValueLayout::List {
+                members: a,
+                indexed: false,
+            }),
+            Some(ValueLayout::List {
+                members: b,
+                indexed: false,
+            }),
+        ) => {
+            for item in a.iter().filter(|&item| !b.iter().any(|i| same(item, i))) {
+                changes.push(Change::Removed {
+                    path: format!("{path}[{}]", key_repr(item)),
+                    old: item,
+                });
+            }
+            for item in b.iter().filter(|&item| !a.iter().any(|i| same(item, i))) {
+                changes.push(Change::Added {
+                    path: format!("{path}[{}]", key_repr(item)),
+                    new: item,
+                });
+            }
+        }
+        (Some(ValueLayout::Map(a)), Some(ValueLayout::Map(b))) => {
+            for (key, value) in a {
+                let kv_path = format!("{path}[{}]", key_repr(key));
+                match b.iter().find(|(k, _)| same(key, k)) {
+                    Some((_, new_value)) => diff_inner(kv_path, value, new_value, changes),
+                    None => changes.push(Change::Removed {
+                        path: kv_path,
+                        old: value,
+                    }),
+                }
+            }
+            for (key, value) in b {
+                if !a.iter().any(|(k, _)| same(key, k)) {
+                    let kv_path = format!("{path}[{}]", key_repr(key));
+                    changes.push(Change::Added {
+                        path: kv_path,
+                        new: value,
+                    });
+                }
+            }
+        }
+        _ => changes.push(Change::Changed { path, old, new }),
+    }
+}
+
+/// Compare two values of a variable (for example, values taken at different stops).
+/// Changes are returned in a depth-first order, an empty list means values are the same.
+pub fn diff<'a>(old: &'a VariableIR, new: &'a VariableIR) -> Vec<Change<'a>> {
+    let mut changes = vec![];
+    diff_inner(String::new(), old, new, &mut changes);
+    changes
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::debugger::variable::{
+        ArrayVariable, ScalarVariable, StructVariable, SupportedScalar, VariableIdentity,
+    };
+
+    fn scalar(name: &str, value: i32) -> VariableIR {
+        VariableIR::Scalar(ScalarVariable {
+            identity: VariableIdentity::no_namespace(Some(name.to_string())),
+            value: Some(SupportedScalar::I32(value)),
+            raw_address: None,
+            type_name: Some("i32".to_string()),
+            type_id: None,
+        })
+    }
+
+    fn structure(name: &str, members: Vec<VariableIR>, addr: usize) -> VariableIR {
+        VariableIR::Struct(StructVariable {
+            identity: VariableIdentity::no_namespace(Some(name.to_string())),
+            type_name: Some("Foo".to_string()),
+            members,
+            raw_address: Some(addr),
+            ..Default::default()
+        })
+    }
+
+    fn array(name: &str, items: Vec<VariableIR>) -> VariableIR {
+        VariableIR::Array(ArrayVariable {
+            identity: VariableIdentity::no_namespace(Some(name.to_string())),
+            type_name: Some("[i32]".to_string()),
+            type_id: None,
+            items: Some(items),
+            raw_address: None,
+        })
+    }
+
+    fn paths(changes: Vec<Change>) -> Vec<(char, String)> {
+        changes
+            .into_iter()
+            .map(|change| match change {
+                Change::Changed { path, .. } => ('~', path),
+                Change::Added { path, .. } => ('+', path),
+                Change::Removed { path, .. } => ('-', path),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = structure(
+            "foo",
+            vec![
+                scalar("a", 1),
+                scalar("b", 2),
+                array("arr", vec![scalar("0", 1), scalar("1", 2)]),
+            ],
+            0x100,
+        );
+        // a structure moved to another address, but members are the same
+        let moved = structure(
+            "foo",
+            vec![
+                scalar("a", 1),
+                scalar("b", 2),
+                array("arr", vec![scalar("0", 1), scalar("1", 2)]),
+            ],
+            0x200,
+        );
+        assert!(diff(&old, &moved).is_empty());
+
+        let new = structure(
+            "foo",
+            vec![
+                scalar("a", 1),
+                scalar("b", 3),
+                array("arr", vec![scalar("0", 5), scalar("1", 2), scalar("2", 3)]),
+            ],
+            0x100,
+        );
+        assert_eq!(
+            paths(diff(&old, &new)),
+            vec![
+                ('~', ".b".to_string()),
+                ('~', ".arr[0]".to_string()),
+                ('+', ".arr[2]".to_string()),
+            ]
+        );
+        assert_eq!(
+            paths(diff(&new, &old)),
+            vec![
+                ('~', ".b".to_string()),
+                ('~', ".arr[0]".to_string()),
+                ('-', ".arr[2]".to_string()),
+            ]
+        );
+
+        assert_eq!(
+            paths(diff(&scalar("a", 1), &scalar("a", 2))),
+            vec![('~', String::new())]
+        );
+    }
+}
@@ -0,0 +1,139 @@
+//! JSON representation of variable values.
+//! Unlike a rendered text a JSON document contains a full variable tree (without any render
+//! limits) including type names and memory addresses, so it can be processed by external tools.
+
+use crate::debugger::variable::render::{RenderRepr, ValueLayout};
+use crate::debugger::variable::{PointerTarget, VariableIR};
+use serde_json::{json, Map, Value};
+
+/// Serialize variable into a JSON object. Each object contains `name`, `type` and `address`
+/// fields and one of (depends on a value layout):
+/// - `value` - a string for scalar values, an address for pointers (pointed value is placed
+///   into `target` field, `"cycle"` if it is a back-edge of a cyclic structure)
+///   or an object for wrapped values (like enum variants)
+/// - `members` - an array of objects for structures
+/// - `items` - an array of objects for lists (vectors, arrays, etc.)
+/// - `entries` - an array of `{"key": .., "value": ..}` objects for maps and sets
+pub fn to_json(var: &VariableIR) -> Value {
+    let mut obj = Map::new();
+    obj.insert("name".to_string(), Value::String(var.name()));
+    obj.insert("type".to_string(), Value::String(var.r#type().to_string()));
+    obj.insert(
+        "address".to_string(),
+        var.in_memory_location()
+            .map(|addr| Value::String(format!("{addr:#x}")))
+            .unwrap_or(Value::Null),
+    );
+
+    match var.value() {
+        None => {
+            obj.insert("value".to_string(), Value::Null);
+        }
+        Some(ValueLayout::PreRendered(s)) => {
+            obj.insert("value".to_string(), Value::String(s.to_string()));
+        }
+        Some(ValueLayout::Referential {
+            addr,
+            annotation,
+            target,
+        }) => {
+            obj.insert("value".to_string(), Value::String(format!("{addr:p}")));
+            if let Some(annotation) = annotation {
+                obj.insert(
+                    "annotation".to_string(),
+                    Value::String(annotation.to_string()),
+                );
+            }
+            match target {
+                Some(PointerTarget::Value(target)) => {
+                    obj.insert("target".to_string(), to_json(target));
+                }
+                Some(PointerTarget::Cycle) => {
+                    obj.insert("target".to_string(), json!("cycle"));
+                }
+                None => {}
+            }
+        }
+        Some(ValueLayout::Wrapped(inner)) => {
+            obj.insert("value".to_string(), to_json(inner));
+        }
+        Some(ValueLayout::Structure { members }) => {
+            obj.insert(
+                "members".to_string(),
+                Value::Array(members.iter().map(to_json).collect()),
+            );
+        }
+        Some(ValueLayout::List { members, .. }) => {
+            obj.insert(
+                "items".to_string(),
+                Value::Array(members.iter().map(to_json).collect()),
+            );
+        }
+        Some(ValueLayout::Map(kvs)) => {
+            let entries = kvs
+                .iter()
+                .map(|(k, v)| json!({"key": to_json(k), "value": to_json(v)}))
+                .collect();
+            obj.insert("entries".to_string(), Value::Array(entries));
+        }
+    }
+
+    Value::Object(obj)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::debugger::variable::{
+        PointerVariable, ScalarVariable, StructVariable, SupportedScalar, VariableIdentity,
+    };
+
+    #[test]
+    fn test_struct_to_json() {
+        let var = VariableIR::Struct(StructVariable {
+            identity: VariableIdentity::no_namespace(Some("foo".to_string())),
+            type_name: Some("Foo".to_string()),
+            members: vec![
+                VariableIR::Scalar(ScalarVariable {
+                    identity: VariableIdentity::no_namespace(Some("a".to_string())),
+                    value: Some(SupportedScalar::I32(1)),
+                    raw_address: Some(0x1000),
+                    type_name: Some("i32".to_string()),
+                    type_id: None,
+                }),
+                VariableIR::Pointer(PointerVariable {
+                    identity: VariableIdentity::no_namespace(Some("b".to_string())),
+                    type_name: Some("*const Foo".to_string()),
+                    type_id: None,
+                    value: Some(0x1000 as *const ()),
+                    target_type: None,
+                    target_type_size: None,
+                    raw_address: Some(0x1008),
+                    annotation: None,
+                    target: Some(PointerTarget::Cycle),
+                }),
+            ],
+            raw_address: Some(0x1000),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            to_json(&var),
+            json!({
+                "name": "foo",
+                "type": "Foo",
+                "address": "0x1000",
+                "members": [
+                    {"name": "a", "type": "i32", "address": "0x1000", "value": "1"},
+                    {
+                        "name": "b",
+                        "type": "*const Foo",
+                        "address": "0x1008",
+                        "value": "0x1000",
+                        "target": "cycle",
+                    },
+                ],
+            })
+        );
+    }
+}
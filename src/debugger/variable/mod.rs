@@ -1,13 +1,16 @@
+use crate::debugger::address::RelocatedAddress;
 use crate::debugger::debugee::dwarf::r#type::{
     ArrayType, CModifier, EvaluationContext, ScalarType, StructureMember, TypeIdentity,
 };
 use crate::debugger::debugee::dwarf::r#type::{ComplexType, TypeDeclaration};
+use crate::debugger::debugee::dwarf::unit::DiscrValue;
 use crate::debugger::debugee::dwarf::{AsAllocatedData, ContextualDieRef, NamespaceHierarchy};
 use crate::debugger::variable::render::RenderRepr;
 use crate::debugger::variable::select::{Literal, LiteralOrWildcard, ObjectBinaryRepr};
 use crate::debugger::variable::specialization::{
     HashSetVariable, StrVariable, StringVariable, VariableParserExtension,
 };
+use crate::debugger::SyscallError;
 use crate::{debugger, version_switch, weak_error};
 use bytes::Bytes;
 use gimli::{
@@ -21,6 +24,10 @@ use std::fmt::{Debug, Display, Formatter};
 use std::string::FromUtf8Error;
 use uuid::Uuid;
 
+pub mod diff;
+pub mod json;
+pub mod printer;
+pub mod ptype;
 pub mod render;
 pub mod select;
 mod specialization;
@@ -58,7 +65,7 @@ pub enum ParsingError {
     #[error("unsupported language version")]
     UnsupportedVersion,
     #[error("error while reading from debugee memory: {0}")]
-    ReadDebugeeMemory(#[from] nix::Error),
+    ReadDebugeeMemory(#[from] SyscallError),
 }
 
 /// Identifier of debugee variables.
@@ -149,7 +156,11 @@ impl Display for SupportedScalar {
             SupportedScalar::F32(scalar) => f.write_str(&format!("{scalar}")),
             SupportedScalar::F64(scalar) => f.write_str(&format!("{scalar}")),
             SupportedScalar::Bool(scalar) => f.write_str(&format!("{scalar}")),
-            SupportedScalar::Char(scalar) => f.write_str(&format!("{scalar}")),
+            SupportedScalar::Char(scalar) => match scalar {
+                '\'' | '"' => f.write_str(&format!("{scalar}")),
+                // control and other non-printable characters are escaped
+                _ => f.write_str(&format!("{}", scalar.escape_debug())),
+            },
             SupportedScalar::Empty() => f.write_str("()"),
         }
     }
@@ -190,7 +201,24 @@ pub struct ScalarVariable {
 }
 
 impl ScalarVariable {
-    fn try_as_number(&self) -> Option<i64> {
+    /// Return value of an enum discriminant and a sign flag.
+    fn try_as_discr(&self) -> Option<(i128, bool)> {
+        match self.value {
+            Some(SupportedScalar::I8(num)) => Some((num as i128, true)),
+            Some(SupportedScalar::I16(num)) => Some((num as i128, true)),
+            Some(SupportedScalar::I32(num)) => Some((num as i128, true)),
+            Some(SupportedScalar::I64(num)) => Some((num as i128, true)),
+            Some(SupportedScalar::Isize(num)) => Some((num as i128, true)),
+            Some(SupportedScalar::U8(num)) => Some((num as i128, false)),
+            Some(SupportedScalar::U16(num)) => Some((num as i128, false)),
+            Some(SupportedScalar::U32(num)) => Some((num as i128, false)),
+            Some(SupportedScalar::U64(num)) => Some((num as i128, false)),
+            Some(SupportedScalar::Usize(num)) => Some((num as i128, false)),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn try_as_number(&self) -> Option<i64> {
         match self.value {
             Some(SupportedScalar::I8(num)) => Some(num as i64),
             Some(SupportedScalar::I16(num)) => Some(num as i64),
@@ -282,6 +310,22 @@ pub struct PointerVariable {
     pub target_type: Option<TypeIdentity>,
     pub target_type_size: Option<u64>,
     pub raw_address: Option<usize>,
+    /// Description of a memory pointed to (symbol or memory region),
+    /// see [`crate::debugger::Debugger::annotate_pointers`].
+    pub annotation: Option<String>,
+    /// Pointed value, filled only if pointers are followed when a variable is read
+    /// (see [`crate::debugger::Debugger::read_variable_following_pointers`]).
+    pub target: Option<PointerTarget>,
+}
+
+/// A value pointed to by a pointer.
+#[derive(Clone, PartialEq)]
+pub enum PointerTarget {
+    /// Dereferenced value.
+    Value(Box<VariableIR>),
+    /// Pointer points to a value which contains this pointer (a back-edge of a cyclic structure
+    /// like a cyclic linked list), such value is not dereferenced again.
+    Cycle,
 }
 
 impl PointerVariable {
@@ -399,6 +443,46 @@ pub struct CModifiedVariable {
     pub address: Option<usize>,
 }
 
+/// Reason why a variable value can't be read at the current PC.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Unavailability {
+    /// Variable scope (lexical block) starts at the current PC or after it.
+    NotYetInitialized,
+    /// Variable location list doesn't describe a value location at the current PC.
+    OptimizedOut,
+}
+
+/// Represent a variable that is not live at the current PC, its value is never read
+/// because the bytes at its location are stale.
+#[derive(Clone, PartialEq)]
+pub struct UnavailableVariable {
+    pub identity: VariableIdentity,
+    pub type_name: Option<String>,
+    pub type_id: Option<TypeIdentity>,
+    pub reason: Unavailability,
+    /// PC ranges where the variable is live.
+    pub live_ranges: Vec<std::ops::Range<RelocatedAddress>>,
+}
+
+impl Display for UnavailableVariable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.reason {
+            Unavailability::NotYetInitialized => f.write_str("<not yet initialized>")?,
+            Unavailability::OptimizedOut => f.write_str("<optimized out at this PC>")?,
+        }
+        if !self.live_ranges.is_empty() {
+            let ranges = self
+                .live_ranges
+                .iter()
+                .map(|range| format!("{}-{}", range.start, range.end))
+                .collect::<Vec<_>>()
+                .join(", ");
+            write!(f, " (live at {ranges})")?;
+        }
+        Ok(())
+    }
+}
+
 /// Variable intermediate representation.
 #[derive(Clone, PartialEq)]
 pub enum VariableIR {
@@ -411,6 +495,7 @@ pub enum VariableIR {
     Subroutine(SubroutineVariable),
     Specialized(SpecializedVariableIR),
     CModifiedVariable(CModifiedVariable),
+    Unavailable(UnavailableVariable),
 }
 
 // SAFETY: this enum may contain a raw pointers on memory in a debugee process,
@@ -436,6 +521,7 @@ impl VariableIR {
             VariableIR::Subroutine(s) => s.address,
             VariableIR::Specialized(s) => s.in_memory_location(),
             VariableIR::CModifiedVariable(cmv) => cmv.address,
+            VariableIR::Unavailable(_) => None,
         }
     }
 
@@ -450,6 +536,7 @@ impl VariableIR {
             VariableIR::Subroutine(s) => s.type_id,
             VariableIR::Specialized(s) => s.type_id(),
             VariableIR::CModifiedVariable(cmv) => cmv.type_id,
+            VariableIR::Unavailable(v) => v.type_id,
         }
     }
 
@@ -460,6 +547,130 @@ impl VariableIR {
         }
     }
 
+    /// Call `f` for this variable (if it is a pointer) and for all rendered underline pointers
+    /// (including pointers in dereferenced values of pointers, these pointers are visited first).
+    pub fn for_each_pointer_mut(&mut self, f: &mut impl FnMut(&mut PointerVariable)) {
+        fn visit_struct(s: &mut StructVariable, f: &mut impl FnMut(&mut PointerVariable)) {
+            s.members
+                .iter_mut()
+                .for_each(|member| member.for_each_pointer_mut(f));
+        }
+
+        fn visit_pointer(p: &mut PointerVariable, f: &mut impl FnMut(&mut PointerVariable)) {
+            if let Some(PointerTarget::Value(target)) = p.target.as_mut() {
+                target.for_each_pointer_mut(f);
+            }
+            f(p);
+        }
+
+        match self {
+            VariableIR::Scalar(_)
+            | VariableIR::CEnum(_)
+            | VariableIR::Subroutine(_)
+            | VariableIR::Unavailable(_) => {}
+            VariableIR::Struct(r#struct) => visit_struct(r#struct, f),
+            VariableIR::Array(array) => {
+                if let Some(items) = array.items.as_mut() {
+                    items
+                        .iter_mut()
+                        .for_each(|item| item.for_each_pointer_mut(f));
+                }
+            }
+            VariableIR::RustEnum(r#enum) => {
+                if let Some(value) = r#enum.value.as_mut() {
+                    value.for_each_pointer_mut(f);
+                }
+            }
+            VariableIR::Pointer(pointer) => visit_pointer(pointer, f),
+            VariableIR::CModifiedVariable(v) => {
+                if let Some(value) = v.value.as_mut() {
+                    value.for_each_pointer_mut(f);
+                }
+            }
+            VariableIR::Specialized(spec) => match spec {
+                SpecializedVariableIR::Vector { vec: Some(vec), .. }
+                | SpecializedVariableIR::VecDeque { vec: Some(vec), .. } => {
+                    visit_struct(&mut vec.structure, f);
+                }
+                SpecializedVariableIR::Iterator {
+                    iter: Some(iter), ..
+                }
+                | SpecializedVariableIR::Channel {
+                    channel: Some(iter),
+                    ..
+                } => visit_struct(iter, f),
+                SpecializedVariableIR::HashMap { map: Some(map), .. }
+                | SpecializedVariableIR::BTreeMap { map: Some(map), .. } => {
+                    map.kv_items.iter_mut().for_each(|(k, v)| {
+                        k.for_each_pointer_mut(f);
+                        v.for_each_pointer_mut(f);
+                    });
+                }
+                SpecializedVariableIR::HashSet { set: Some(set), .. }
+                | SpecializedVariableIR::BTreeSet { set: Some(set), .. } => {
+                    set.items
+                        .iter_mut()
+                        .for_each(|item| item.for_each_pointer_mut(f));
+                }
+                SpecializedVariableIR::Tls {
+                    tls_var: Some(tls_var),
+                    ..
+                } => {
+                    if let Some(value) = tls_var.inner_value.as_mut() {
+                        value.for_each_pointer_mut(f);
+                    }
+                }
+                SpecializedVariableIR::Cell {
+                    value: Some(value), ..
+                }
+                | SpecializedVariableIR::RefCell {
+                    value: Some(value), ..
+                } => value.for_each_pointer_mut(f),
+                SpecializedVariableIR::Rc {
+                    value: Some(pointer),
+                    ..
+                }
+                | SpecializedVariableIR::Arc {
+                    value: Some(pointer),
+                    ..
+                } => visit_pointer(pointer, f),
+                SpecializedVariableIR::String {
+                    string: Some(_), ..
+                }
+                | SpecializedVariableIR::Str {
+                    string: Some(_), ..
+                }
+                | SpecializedVariableIR::OsString {
+                    string: Some(_), ..
+                }
+                | SpecializedVariableIR::OsStr {
+                    string: Some(_), ..
+                }
+                | SpecializedVariableIR::Uuid { value: Some(_), .. }
+                | SpecializedVariableIR::Custom { value: Some(_), .. } => {}
+                SpecializedVariableIR::Vector { original, .. }
+                | SpecializedVariableIR::VecDeque { original, .. }
+                | SpecializedVariableIR::HashMap { original, .. }
+                | SpecializedVariableIR::BTreeMap { original, .. }
+                | SpecializedVariableIR::HashSet { original, .. }
+                | SpecializedVariableIR::BTreeSet { original, .. }
+                | SpecializedVariableIR::Tls { original, .. }
+                | SpecializedVariableIR::Cell { original, .. }
+                | SpecializedVariableIR::RefCell { original, .. }
+                | SpecializedVariableIR::Rc { original, .. }
+                | SpecializedVariableIR::Arc { original, .. }
+                | SpecializedVariableIR::String { original, .. }
+                | SpecializedVariableIR::Str { original, .. }
+                | SpecializedVariableIR::OsString { original, .. }
+                | SpecializedVariableIR::OsStr { original, .. }
+                | SpecializedVariableIR::Uuid { original, .. }
+                | SpecializedVariableIR::Custom { original, .. }
+                | SpecializedVariableIR::Iterator { original, .. }
+                | SpecializedVariableIR::Channel { original, .. } => visit_struct(original, f),
+            },
+        }
+    }
+
     /// Returns i64 value representation or error if cast fail.
     fn assume_field_as_scalar_number(&self, field_name: &'static str) -> Result<i64, AssumeError> {
         let ir = self
@@ -536,6 +747,8 @@ impl VariableIR {
                 SpecializedVariableIR::VecDeque { original, .. } => &original.identity,
                 SpecializedVariableIR::String { original, .. } => &original.identity,
                 SpecializedVariableIR::Str { original, .. } => &original.identity,
+                SpecializedVariableIR::OsString { original, .. } => &original.identity,
+                SpecializedVariableIR::OsStr { original, .. } => &original.identity,
                 SpecializedVariableIR::Tls { original, .. } => &original.identity,
                 SpecializedVariableIR::HashMap { original, .. } => &original.identity,
                 SpecializedVariableIR::HashSet { original, .. } => &original.identity,
@@ -546,9 +759,13 @@ impl VariableIR {
                 SpecializedVariableIR::Rc { original, .. } => &original.identity,
                 SpecializedVariableIR::Arc { original, .. } => &original.identity,
                 SpecializedVariableIR::Uuid { original, .. } => &original.identity,
+                SpecializedVariableIR::Custom { original, .. } => &original.identity,
+                SpecializedVariableIR::Iterator { original, .. } => &original.identity,
+                SpecializedVariableIR::Channel { original, .. } => &original.identity,
             },
             VariableIR::Subroutine(s) => &s.identity,
             VariableIR::CModifiedVariable(v) => &v.identity,
+            VariableIR::Unavailable(v) => &v.identity,
         }
     }
 
@@ -565,6 +782,8 @@ impl VariableIR {
                 SpecializedVariableIR::VecDeque { original, .. } => &mut original.identity,
                 SpecializedVariableIR::String { original, .. } => &mut original.identity,
                 SpecializedVariableIR::Str { original, .. } => &mut original.identity,
+                SpecializedVariableIR::OsString { original, .. } => &mut original.identity,
+                SpecializedVariableIR::OsStr { original, .. } => &mut original.identity,
                 SpecializedVariableIR::Tls { original, .. } => &mut original.identity,
                 SpecializedVariableIR::HashMap { original, .. } => &mut original.identity,
                 SpecializedVariableIR::HashSet { original, .. } => &mut original.identity,
@@ -575,9 +794,13 @@ impl VariableIR {
                 SpecializedVariableIR::Rc { original, .. } => &mut original.identity,
                 SpecializedVariableIR::Arc { original, .. } => &mut original.identity,
                 SpecializedVariableIR::Uuid { original, .. } => &mut original.identity,
+                SpecializedVariableIR::Custom { original, .. } => &mut original.identity,
+                SpecializedVariableIR::Iterator { original, .. } => &mut original.identity,
+                SpecializedVariableIR::Channel { original, .. } => &mut original.identity,
             },
             VariableIR::Subroutine(s) => &mut s.identity,
             VariableIR::CModifiedVariable(v) => &mut v.identity,
+            VariableIR::Unavailable(v) => &mut v.identity,
         }
     }
 
@@ -597,12 +820,17 @@ impl VariableIR {
             | SpecializedVariableIR::BTreeSet { original, .. }
             | SpecializedVariableIR::String { original, .. }
             | SpecializedVariableIR::Str { original, .. }
+            | SpecializedVariableIR::OsString { original, .. }
+            | SpecializedVariableIR::OsStr { original, .. }
             | SpecializedVariableIR::Tls { original, .. }
             | SpecializedVariableIR::Cell { original, .. }
             | SpecializedVariableIR::RefCell { original, .. }
             | SpecializedVariableIR::Rc { original, .. }
             | SpecializedVariableIR::Arc { original, .. }
-            | SpecializedVariableIR::Uuid { original, .. } => VariableIR::Struct(original),
+            | SpecializedVariableIR::Uuid { original, .. }
+            | SpecializedVariableIR::Custom { original, .. }
+            | SpecializedVariableIR::Iterator { original, .. }
+            | SpecializedVariableIR::Channel { original, .. } => VariableIR::Struct(original),
         }
     }
 
@@ -644,6 +872,8 @@ impl VariableIR {
                 .and_then(|t| variable_parser.r#type.type_size_in_bytes(eval_ctx, t)),
             raw_address: None,
             type_id: None,
+            annotation: None,
+            target: None,
         }))
     }
 
@@ -683,6 +913,11 @@ impl VariableIR {
                 | SpecializedVariableIR::RefCell { value, .. } => {
                     value.and_then(|var| var.field(field_name))
                 }
+                SpecializedVariableIR::Iterator { iter, original }
+                | SpecializedVariableIR::Channel {
+                    channel: iter,
+                    original,
+                } => VariableIR::Struct(iter.unwrap_or(original)).field(field_name),
                 _ => None,
             },
             _ => None,
@@ -888,10 +1123,18 @@ impl VariableIR {
                 SpecializedVariableIR::String {
                     string: Some(StringVariable { value, .. }),
                     ..
+                }
+                | SpecializedVariableIR::OsString {
+                    string: Some(StringVariable { value, .. }),
+                    ..
                 } => literal.equal_with_string(&value),
                 SpecializedVariableIR::Str {
                     string: Some(StrVariable { value, .. }),
                     ..
+                }
+                | SpecializedVariableIR::OsStr {
+                    string: Some(StrVariable { value, .. }),
+                    ..
                 } => literal.equal_with_string(&value),
                 SpecializedVariableIR::Uuid {
                     value: Some(bytes), ..
@@ -899,6 +1142,9 @@ impl VariableIR {
                     let uuid = Uuid::from_bytes(bytes);
                     literal.equal_with_string(&uuid.to_string())
                 }
+                SpecializedVariableIR::Custom {
+                    value: Some(value), ..
+                } => literal.equal_with_string(&value),
                 SpecializedVariableIR::Cell { mut value, .. }
                 | SpecializedVariableIR::RefCell { mut value, .. } => {
                     let Some(inner) = value.take() else {
@@ -1028,8 +1274,10 @@ impl<'a> VariableParser<'a> {
         type_id: TypeIdentity,
         r#type: &ScalarType,
     ) -> ScalarVariable {
-        fn render_scalar<S: Copy + Display>(data: Option<ObjectBinaryRepr>) -> Option<S> {
-            data.as_ref().map(|v| scalar_from_bytes::<S>(&v.raw_data))
+        fn render_scalar<S: Copy>(data: Option<ObjectBinaryRepr>) -> Option<S> {
+            data.as_ref()
+                .filter(|v| v.raw_data.len() >= std::mem::size_of::<S>())
+                .map(|v| scalar_from_bytes::<S>(&v.raw_data))
         }
         let in_debugee_loc = data.as_ref().and_then(|d| d.address);
         #[allow(non_upper_case_globals)]
@@ -1080,8 +1328,12 @@ impl<'a> VariableParser<'a> {
                 }
             },
             DW_ATE_float => match r#type.byte_size.unwrap_or(0) {
+                2 => render_scalar::<u16>(data).map(|bits| SupportedScalar::F32(f16_to_f32(bits))),
                 4 => render_scalar::<f32>(data).map(SupportedScalar::F32),
                 8 => render_scalar::<f64>(data).map(SupportedScalar::F64),
+                // x87 extended precision (C long double), padded up to 12 or 16 bytes
+                10 | 12 | 16 => render_scalar::<[u8; 10]>(data)
+                    .map(|bytes| SupportedScalar::F64(f80_to_f64(bytes))),
                 _ => {
                     warn!(
                         "parse scalar: unexpected float size: {size:?}",
@@ -1090,19 +1342,30 @@ impl<'a> VariableParser<'a> {
                     None
                 }
             },
-            DW_ATE_boolean => render_scalar::<bool>(data).map(SupportedScalar::Bool),
-            DW_ATE_UTF => render_scalar::<char>(data).map(|char| {
-                // WAITFORFIX: https://github.com/rust-lang/rust/issues/113819
-                // this check is meaningfully here cause in case above there is a random bytes here,
-                // and it may lead to panic in other places
-                // (specially when someone tries to render this char)
-                if String::from_utf8(char.to_string().into_bytes()).is_err() {
-                    SupportedScalar::Char('?')
-                } else {
-                    SupportedScalar::Char(char)
-                }
-            }),
-            DW_ATE_ASCII => render_scalar::<char>(data).map(SupportedScalar::Char),
+            // a boolean may occupy a wider slot than a single byte,
+            // any non-zero bit pattern is true
+            DW_ATE_boolean => data
+                .as_ref()
+                .map(|d| {
+                    let size = r#type.byte_size.unwrap_or(1).max(1) as usize;
+                    d.raw_data.iter().take(size).any(|b| *b != 0)
+                })
+                .map(SupportedScalar::Bool),
+            // a char code point is decoded according to a char size (UTF-8, UTF-16 or UTF-32
+            // code unit), code points that are not a valid unicode scalar are replaced
+            // with U+FFFD, cause they may come from uninitialized memory
+            DW_ATE_UTF | DW_ATE_ASCII => {
+                let code = match r#type.byte_size.unwrap_or(4) {
+                    1 => render_scalar::<u8>(data).map(u32::from),
+                    2 => render_scalar::<u16>(data).map(u32::from),
+                    _ => render_scalar::<u32>(data),
+                };
+                code.map(|code| {
+                    SupportedScalar::Char(
+                        char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER),
+                    )
+                })
+            }
             _ => {
                 warn!("parse scalar: unexpected base type encoding: {encoding}");
                 None
@@ -1268,18 +1531,27 @@ impl<'a> VariableParser<'a> {
         data: Option<ObjectBinaryRepr>,
         type_id: TypeIdentity,
         discr_member: Option<&StructureMember>,
-        enumerators: &HashMap<Option<i64>, StructureMember>,
+        enumerators: &HashMap<Option<DiscrValue>, StructureMember>,
     ) -> RustEnumVariable {
+        // discriminant may be located at any offset (for example, a niche of a dataful variant)
         let discr_value = discr_member.and_then(|member| {
             let discr = self.parse_struct_member(eval_ctx, member, data.as_ref())?;
             if let VariableIR::Scalar(scalar) = discr {
-                return scalar.try_as_number();
+                return scalar.try_as_discr();
             }
             None
         });
 
-        let enumerator =
-            discr_value.and_then(|v| enumerators.get(&Some(v)).or_else(|| enumerators.get(&None)));
+        let enumerator = discr_value.and_then(|(value, signed)| {
+            enumerators
+                .iter()
+                .find_map(|(discr, member)| {
+                    discr
+                        .is_some_and(|discr| discr.value(signed) == value)
+                        .then_some(member)
+                })
+                .or_else(|| enumerators.get(&None))
+        });
 
         let enumerator = enumerator.and_then(|member| {
             Some(Box::new(self.parse_struct_member(
@@ -1322,6 +1594,8 @@ impl<'a> VariableParser<'a> {
             target_type,
             target_type_size: None,
             raw_address: data.and_then(|d| d.address),
+            annotation: None,
+            target: None,
         }
     }
 
@@ -1343,6 +1617,19 @@ impl<'a> VariableParser<'a> {
                 name: struct_name,
                 ..
             } => {
+                let custom_formatter = struct_name.as_deref().and_then(|name| {
+                    let mut full_name = type_ns_h.to_vec();
+                    full_name.push(name.into());
+                    printer::find_formatter(name, &full_name.join("::"))
+                });
+                let custom_value = custom_formatter.map(|formatter| {
+                    let decl = &self.r#type.types[&type_id];
+                    let format_ctx =
+                        printer::FormatContext::new(eval_ctx.expl_ctx.pid_on_focus(), self.r#type);
+                    data.as_ref()
+                        .and_then(|d| formatter(&d.raw_data, decl, &format_ctx))
+                });
+
                 let struct_var = self.parse_struct_variable(
                     eval_ctx,
                     identity,
@@ -1352,11 +1639,20 @@ impl<'a> VariableParser<'a> {
                     members,
                 );
 
+                // user defined pretty-printers take precedence over builtin specializations
+                if let Some(value) = custom_value {
+                    return VariableIR::Specialized(SpecializedVariableIR::Custom {
+                        value,
+                        original: struct_var,
+                    });
+                }
+
                 let parser_ext = VariableParserExtension::new(self);
                 // Reinterpret structure if underline data type is:
                 // - Vector
                 // - String
                 // - &str
+                // - OsString/PathBuf/CString and &OsStr/&Path/&CStr
                 // - tls variable
                 // - hashmaps
                 // - hashset
@@ -1365,6 +1661,8 @@ impl<'a> VariableParser<'a> {
                 // - vecdeque
                 // - cell/refcell
                 // - rc/arc
+                // - iterator adapters and collection iterators
+                // - channel senders and receivers
                 if struct_name.as_deref() == Some("&str") {
                     return VariableIR::Specialized(parser_ext.parse_str(eval_ctx, struct_var));
                 };
@@ -1373,6 +1671,35 @@ impl<'a> VariableParser<'a> {
                     return VariableIR::Specialized(parser_ext.parse_string(eval_ctx, struct_var));
                 };
 
+                match struct_name.as_deref() {
+                    Some("&std::ffi::os_str::OsStr" | "&std::path::Path") => {
+                        return VariableIR::Specialized(
+                            parser_ext.parse_os_str(eval_ctx, struct_var, false),
+                        );
+                    }
+                    Some("&core::ffi::c_str::CStr") => {
+                        return VariableIR::Specialized(
+                            parser_ext.parse_os_str(eval_ctx, struct_var, true),
+                        );
+                    }
+                    Some("OsString") if type_ns_h.contains(&["ffi", "os_str"]) => {
+                        return VariableIR::Specialized(
+                            parser_ext.parse_os_string(eval_ctx, struct_var, false),
+                        );
+                    }
+                    Some("PathBuf") if type_ns_h.contains(&["path"]) => {
+                        return VariableIR::Specialized(
+                            parser_ext.parse_os_string(eval_ctx, struct_var, false),
+                        );
+                    }
+                    Some("CString") if type_ns_h.contains(&["ffi", "c_str"]) => {
+                        return VariableIR::Specialized(
+                            parser_ext.parse_os_string(eval_ctx, struct_var, true),
+                        );
+                    }
+                    _ => {}
+                };
+
                 if struct_name.as_ref().map(|name| name.starts_with("Vec")) == Some(true)
                     && type_ns_h.contains(&["vec"])
                 {
@@ -1388,7 +1715,9 @@ impl<'a> VariableParser<'a> {
                     rust_version,
                     (1, 0, 0) ..= (1, 76, u32::MAX) => type_ns_h.contains(&["std", "sys", "common", "thread_local", "fast_local"]),
                     (1, 77, 0) ..= (1, 77, u32::MAX) => type_ns_h.contains(&["std", "sys", "pal", "common", "thread_local", "fast_local"]),
-                    (1, 78, 0) ..= (1, u32::MAX, u32::MAX) => type_ns_h.contains(&["std", "sys", "thread_local", "fast_local"]),
+                    (1, 78, 0) ..= (1, u32::MAX, u32::MAX) => type_ns_h.contains(&["std", "sys", "thread_local", "fast_local"])
+                        || (type_ns_h.contains(&["std", "sys", "thread_local", "native"])
+                            && struct_name.as_ref().map(|name| name.starts_with("Storage")) == Some(true)),
                 );
                 if is_tls_type == Some(true) {
                     return VariableIR::Specialized(parser_ext.parse_tls(struct_var, type_params));
@@ -1478,6 +1807,90 @@ impl<'a> VariableParser<'a> {
                     return VariableIR::Specialized(parser_ext.parse_uuid(struct_var));
                 };
 
+                const ITER_ADAPTERS: [(&str, &str); 4] = [
+                    ("Map", "map"),
+                    ("Filter", "filter"),
+                    ("Enumerate", "enumerate"),
+                    ("Chain", "chain"),
+                ];
+                if let Some((adapter, _)) = ITER_ADAPTERS.iter().find(|(adapter, ns)| {
+                    struct_name
+                        .as_ref()
+                        .map(|name| name.starts_with(&format!("{adapter}<")))
+                        == Some(true)
+                        && type_ns_h.contains(&["iter", "adapters", ns])
+                }) {
+                    return VariableIR::Specialized(
+                        parser_ext.parse_iter_adapter(struct_var, adapter),
+                    );
+                };
+
+                if struct_name
+                    .as_ref()
+                    .map(|name| name.starts_with("IntoIter<"))
+                    == Some(true)
+                    && type_ns_h.contains(&["vec", "into_iter"])
+                {
+                    return VariableIR::Specialized(parser_ext.parse_contiguous_iter(
+                        eval_ctx,
+                        struct_var,
+                        "vec::IntoIter",
+                        type_params,
+                    ));
+                };
+
+                if let Some(kind) = struct_name.as_ref().and_then(|name| {
+                    if name.starts_with("Iter<") {
+                        Some("slice::Iter")
+                    } else if name.starts_with("IterMut<") {
+                        Some("slice::IterMut")
+                    } else {
+                        None
+                    }
+                }) {
+                    if type_ns_h.contains(&["slice", "iter"]) {
+                        return VariableIR::Specialized(parser_ext.parse_contiguous_iter(
+                            eval_ctx,
+                            struct_var,
+                            kind,
+                            type_params,
+                        ));
+                    }
+                };
+
+                const CHANNEL_HANDLES: [(&[&str], &str, &[&str]); 2] = [
+                    (
+                        &["sync", "mpsc"],
+                        "mpsc",
+                        &["Sender", "SyncSender", "Receiver"],
+                    ),
+                    (
+                        &["crossbeam_channel", "channel"],
+                        "crossbeam_channel",
+                        &["Sender", "Receiver"],
+                    ),
+                ];
+                if let Some(kind) = struct_name.as_ref().and_then(|name| {
+                    CHANNEL_HANDLES
+                        .iter()
+                        .find_map(|(ns, crate_name, handles)| {
+                            if !type_ns_h.contains(ns) {
+                                return None;
+                            }
+                            handles
+                                .iter()
+                                .find(|handle| name.starts_with(&format!("{handle}<")))
+                                .map(|handle| format!("{crate_name}::{handle}"))
+                        })
+                }) {
+                    return VariableIR::Specialized(parser_ext.parse_channel(
+                        eval_ctx,
+                        struct_var,
+                        &kind,
+                        type_params,
+                    ));
+                };
+
                 VariableIR::Struct(struct_var)
             }
             TypeDeclaration::Array(decl) => {
@@ -1608,6 +2021,13 @@ impl<'a> Iterator for BfsIterator<'a> {
                         .iter()
                         .for_each(|member| self.queue.push_back(member));
                 }
+                SpecializedVariableIR::OsString { original, .. }
+                | SpecializedVariableIR::OsStr { original, .. } => {
+                    original
+                        .members
+                        .iter()
+                        .for_each(|member| self.queue.push_back(member));
+                }
                 SpecializedVariableIR::Tls { original, .. } => {
                     original
                         .members
@@ -1629,7 +2049,9 @@ impl<'a> Iterator for BfsIterator<'a> {
                         .for_each(|member| self.queue.push_back(member));
                 }
                 SpecializedVariableIR::Cell { original, .. }
-                | SpecializedVariableIR::RefCell { original, .. } => {
+                | SpecializedVariableIR::RefCell { original, .. }
+                | SpecializedVariableIR::Iterator { original, .. }
+                | SpecializedVariableIR::Channel { original, .. } => {
                     original
                         .members
                         .iter()
@@ -1637,6 +2059,7 @@ impl<'a> Iterator for BfsIterator<'a> {
                 }
                 SpecializedVariableIR::Rc { .. } | SpecializedVariableIR::Arc { .. } => {}
                 SpecializedVariableIR::Uuid { .. } => {}
+                SpecializedVariableIR::Custom { .. } => {}
             },
             _ => {}
         }
@@ -1651,6 +2074,53 @@ fn scalar_from_bytes<T: Copy>(bytes: &Bytes) -> T {
     unsafe { std::ptr::read_unaligned::<T>(ptr as *const T) }
 }
 
+/// Convert IEEE 754 half precision float into `f32`, conversion is lossless.
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exp = ((bits >> 10) & 0x1f) as i32;
+    let mantissa = (bits & 0x3ff) as f32;
+    let value = match exp {
+        0 => mantissa * 2f32.powi(-24),
+        0x1f if mantissa == 0.0 => f32::INFINITY,
+        0x1f => f32::NAN,
+        _ => (1.0 + mantissa / 1024.0) * 2f32.powi(exp - 15),
+    };
+    sign * value
+}
+
+/// Convert x87 80-bit extended precision float into `f64`, precision may be lost.
+fn f80_to_f64(bytes: [u8; 10]) -> f64 {
+    let mut mantissa_bytes = [0; 8];
+    mantissa_bytes.copy_from_slice(&bytes[..8]);
+    // unlike other IEEE 754 formats mantissa contains an explicit integer bit
+    let mantissa = u64::from_le_bytes(mantissa_bytes);
+    let sign_exp = u16::from_le_bytes([bytes[8], bytes[9]]);
+
+    let sign = if sign_exp & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exp = (sign_exp & 0x7fff) as i32;
+    let value = match exp {
+        0x7fff if mantissa << 1 == 0 => f64::INFINITY,
+        0x7fff => f64::NAN,
+        // denormals have the same scale as the minimal normal exponent
+        0 => scale_by_pow2(mantissa as f64, 1 - 16383 - 63),
+        _ => scale_by_pow2(mantissa as f64, exp - 16383 - 63),
+    };
+    sign * value
+}
+
+/// Return `value * 2^exp`, intermediate results don't overflow for any f80 exponent.
+fn scale_by_pow2(mut value: f64, mut exp: i32) -> f64 {
+    while exp > 1000 {
+        value *= 2f64.powi(1000);
+        exp -= 1000;
+    }
+    while exp < -1000 {
+        value *= 2f64.powi(-1000);
+        exp += 1000;
+    }
+    value * 2f64.powi(exp)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1789,6 +2259,8 @@ mod test {
                             target_type: None,
                             target_type_size: None,
                             raw_address: None,
+                            annotation: None,
+                            target: None,
                         }),
                     ],
                     type_params: Default::default(),
@@ -2013,6 +2485,8 @@ mod test {
                     value: Some(123usize as *const ()),
                     raw_address: None,
                     target_type_size: None,
+                    annotation: None,
+                    target: None,
                 }),
                 eq_literal: Literal::Address(123),
                 neq_literals: vec![Literal::Address(124), Literal::Int(123)],
@@ -2026,6 +2500,8 @@ mod test {
                     value: Some(123usize as *const ()),
                     raw_address: None,
                     target_type_size: None,
+                    annotation: None,
+                    target: None,
                 }),
                 eq_literal: Literal::Address(123),
                 neq_literals: vec![Literal::Address(124), Literal::Int(123)],
@@ -2565,4 +3041,70 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_float_conversion() {
+        assert_eq!(f16_to_f32(0x3c00), 1.0);
+        assert_eq!(f16_to_f32(0xc000), -2.0);
+        assert_eq!(f16_to_f32(0x0001), 2f32.powi(-24));
+        assert_eq!(f16_to_f32(0x7c00), f32::INFINITY);
+        assert!(f16_to_f32(0x7e00).is_nan());
+
+        let f80 = |mantissa: u64, sign_exp: u16| {
+            let mut bytes = [0; 10];
+            bytes[..8].copy_from_slice(&mantissa.to_le_bytes());
+            bytes[8..].copy_from_slice(&sign_exp.to_le_bytes());
+            f80_to_f64(bytes)
+        };
+        assert_eq!(f80(0x8000_0000_0000_0000, 0x3fff), 1.0);
+        assert_eq!(f80(0xc000_0000_0000_0000, 0xc000), -3.0);
+        assert_eq!(f80(0, 0), 0.0);
+        assert_eq!(f80(0x8000_0000_0000_0000, 0x7fff), f64::INFINITY);
+        assert!(f80(0xc000_0000_0000_0000, 0x7fff).is_nan());
+    }
+
+    #[test]
+    fn test_char_and_byte_rendering() {
+        use crate::debugger::variable::render::{render_variable_ir, RenderLimits};
+
+        assert_eq!(SupportedScalar::Char('a').to_string(), "a");
+        assert_eq!(SupportedScalar::Char('\'').to_string(), "'");
+        assert_eq!(SupportedScalar::Char('\n').to_string(), "\\n");
+        assert_eq!(SupportedScalar::Char('\0').to_string(), "\\0");
+        assert_eq!(SupportedScalar::Char('\u{7f}').to_string(), "\\u{7f}");
+
+        let bytes = VariableIR::Array(ArrayVariable {
+            identity: VariableIdentity::no_namespace(Some("buf".to_owned())),
+            type_name: Some("[u8]".to_owned()),
+            type_id: None,
+            items: Some(
+                b"ok\r\n\xff"
+                    .iter()
+                    .enumerate()
+                    .map(|(i, b)| {
+                        make_scalar_var_ir(Some(&i.to_string()), "u8", SupportedScalar::U8(*b))
+                    })
+                    .collect(),
+            ),
+            raw_address: None,
+        });
+        let limits = RenderLimits {
+            bytes_as_ascii: true,
+            ..RenderLimits::default()
+        };
+        assert_eq!(
+            render_variable_ir(&bytes, 0, &limits),
+            r#"[u8](b"ok\r\n\xff")"#
+        );
+        let limits = RenderLimits {
+            bytes_as_ascii: true,
+            string_length: Some(2),
+            ..RenderLimits::default()
+        };
+        assert_eq!(
+            render_variable_ir(&bytes, 0, &limits),
+            r#"[u8](b"ok"…(truncated))"#
+        );
+        assert!(render_variable_ir(&bytes, 0, &RenderLimits::default()).starts_with("[u8] {"));
+    }
 }
@@ -0,0 +1,368 @@
+//! Builtin pretty-printers for matrices and vectors of popular math crates.
+//!
+//! Supported types:
+//! - `nalgebra` matrices and vectors with a static (`ArrayStorage`) or a dynamic (`VecStorage`)
+//!   storage
+//! - `ndarray` arrays (`Array`, `ArrayView`, etc.) with a static number of dimensions
+//!
+//! Matrices are rendered in a grid form with shape information, like:
+//! ```text
+//! shape [2, 3]
+//! [[1, 2, 3],
+//!  [4, 5, 6]]
+//! ```
+
+use super::{FormatContext, PrettyPrinter, TypeMatcher};
+use crate::debugger::TypeDeclaration;
+use gimli::{DW_ATE_float, DW_ATE_signed, DW_ATE_unsigned};
+use regex::Regex;
+use std::sync::Arc;
+
+/// Maximum number of rendered elements along any axis, the rest are replaced by `...`.
+const MAX_AXIS_LEN: usize = 16;
+
+pub(super) fn printers() -> Vec<PrettyPrinter> {
+    vec![
+        PrettyPrinter {
+            matcher: TypeMatcher::Regex(
+                Regex::new("^nalgebra::base::matrix::Matrix<.*>$").expect("infallible"),
+            ),
+            formatter: Arc::new(format_nalgebra_matrix),
+        },
+        PrettyPrinter {
+            matcher: TypeMatcher::Regex(
+                Regex::new("^ndarray::ArrayBase<.*>$").expect("infallible"),
+            ),
+            formatter: Arc::new(format_ndarray),
+        },
+    ]
+}
+
+/// Numeric matrix element.
+#[derive(Clone, Copy)]
+enum Element {
+    Float(usize),
+    Signed(usize),
+    Unsigned(usize),
+}
+
+impl Element {
+    fn from_decl(decl: &TypeDeclaration) -> Option<Self> {
+        let TypeDeclaration::Scalar(scalar) = decl else {
+            return None;
+        };
+        let size = scalar.byte_size? as usize;
+        #[allow(non_upper_case_globals)]
+        match scalar.encoding? {
+            DW_ATE_float if matches!(size, 4 | 8) => Some(Element::Float(size)),
+            DW_ATE_signed if matches!(size, 1 | 2 | 4 | 8 | 16) => Some(Element::Signed(size)),
+            DW_ATE_unsigned if matches!(size, 1 | 2 | 4 | 8 | 16) => Some(Element::Unsigned(size)),
+            _ => None,
+        }
+    }
+
+    fn size(self) -> usize {
+        match self {
+            Element::Float(size) | Element::Signed(size) | Element::Unsigned(size) => size,
+        }
+    }
+
+    fn render(self, data: &[u8]) -> Option<String> {
+        let mut buf = [0; 16];
+        buf[..self.size()].copy_from_slice(data.get(..self.size())?);
+        let rendered = match self {
+            Element::Float(4) => f32::from_le_bytes(buf[..4].try_into().ok()?).to_string(),
+            Element::Float(_) => f64::from_le_bytes(buf[..8].try_into().ok()?).to_string(),
+            Element::Signed(size) => {
+                // sign-extend to i128
+                let shift = 128 - size * 8;
+                ((i128::from_le_bytes(buf) << shift) >> shift).to_string()
+            }
+            Element::Unsigned(_) => u128::from_le_bytes(buf).to_string(),
+        };
+        Some(rendered)
+    }
+}
+
+/// Return a `usize` value located at `offset`.
+fn read_usize(data: &[u8], offset: usize) -> Option<usize> {
+    let bytes = data.get(offset..offset + size_of::<usize>())?;
+    Some(usize::from_ne_bytes(bytes.try_into().ok()?))
+}
+
+/// Return an offset of a first member with a `pointer` name (pointer inside
+/// of `NonNull`, `Unique`, `Vec` and so on).
+fn pointer_offset(ctx: &FormatContext, decl: &TypeDeclaration) -> Option<usize> {
+    let TypeDeclaration::Structure { members, .. } = decl else {
+        return None;
+    };
+    if let Some((offset, TypeDeclaration::Pointer { .. })) = ctx.member(decl, "pointer") {
+        return Some(offset);
+    }
+    members.iter().find_map(|member| {
+        let (offset, member_decl) = ctx.member(decl, member.name.as_deref()?)?;
+        Some(offset + pointer_offset(ctx, member_decl)?)
+    })
+}
+
+/// Return values of const generics from a type name like `ArrayStorage<f64, 3, 2>`
+/// or `Dim<[usize; 2]>`.
+fn const_generics(decl: &TypeDeclaration) -> Vec<usize> {
+    let TypeDeclaration::Structure {
+        name: Some(name), ..
+    } = decl
+    else {
+        return vec![];
+    };
+    let Some((_, args)) = name.split_once('<') else {
+        return vec![];
+    };
+    args.split([',', ';', '[', ']', '>', ' '])
+        .filter_map(|arg| arg.parse().ok())
+        .collect()
+}
+
+/// Format `nalgebra::Matrix`, elements of a matrix are stored in column-major order.
+fn format_nalgebra_matrix(
+    data: &[u8],
+    decl: &TypeDeclaration,
+    ctx: &FormatContext,
+) -> Option<String> {
+    let element = Element::from_decl(ctx.type_param(decl, "T")?)?;
+    let (storage_offset, storage) = ctx.member(decl, "data")?;
+    let TypeDeclaration::Structure {
+        name: Some(storage_name),
+        ..
+    } = storage
+    else {
+        return None;
+    };
+
+    let (nrows, ncols, columns) = if storage_name.starts_with("ArrayStorage<") {
+        // dimension types (`Const<R>`, `Const<C>`) are unreachable from a matrix type,
+        // so dimensions are taken from a storage type name
+        let [nrows, ncols] = const_generics(storage)[..] else {
+            return None;
+        };
+        let (array_offset, _) = ctx.member(storage, "__0")?;
+        let start = storage_offset + array_offset;
+        let column_size = nrows * element.size();
+        let columns = (0..ncols.min(MAX_AXIS_LEN))
+            .map(|col| {
+                let column_start = start + col * column_size;
+                Some(data.get(column_start..column_start + column_size)?.to_vec())
+            })
+            .collect::<Option<Vec<_>>>()?;
+        (nrows, ncols, columns)
+    } else if storage_name.starts_with("VecStorage<") {
+        let (nrows_offset, _) = ctx.member(storage, "nrows")?;
+        let (ncols_offset, _) = ctx.member(storage, "ncols")?;
+        let nrows = read_usize(data, storage_offset + nrows_offset)?;
+        let ncols = read_usize(data, storage_offset + ncols_offset)?;
+        let (vec_offset, vec) = ctx.member(storage, "data")?;
+        let ptr = read_usize(
+            data,
+            storage_offset + vec_offset + pointer_offset(ctx, vec)?,
+        )?;
+        // only displayed part of a column is read
+        let column_size = nrows.checked_mul(element.size())?;
+        let columns = (0..ncols.min(MAX_AXIS_LEN))
+            .map(|col| {
+                ctx.read_memory(
+                    ptr.checked_add(col.checked_mul(column_size)?)?,
+                    nrows.min(MAX_AXIS_LEN) * element.size(),
+                )
+            })
+            .collect::<Option<Vec<_>>>()?;
+        (nrows, ncols, columns)
+    } else {
+        return None;
+    };
+
+    render_grid(&[nrows, ncols], |idx| {
+        element.render(columns[idx[1]].get(idx[0] * element.size()..)?)
+    })
+}
+
+/// Format `ndarray::ArrayBase`, location of an element is calculated by array strides.
+fn format_ndarray(data: &[u8], decl: &TypeDeclaration, ctx: &FormatContext) -> Option<String> {
+    let (ptr_offset, non_null) = ctx.member(decl, "ptr")?;
+    let element = Element::from_decl(ctx.type_param(non_null, "T")?)?;
+    let ptr = read_usize(data, ptr_offset + pointer_offset(ctx, non_null)?)?;
+
+    let read_dim = |name: &str| -> Option<Vec<usize>> {
+        let (dim_offset, dim) = ctx.member(decl, name)?;
+        let [ndim] = const_generics(dim)[..] else {
+            return None;
+        };
+        let (index_offset, _) = ctx.member(dim, "index")?;
+        (0..ndim)
+            .map(|i| read_usize(data, dim_offset + index_offset + i * size_of::<usize>()))
+            .collect()
+    };
+    let shape = read_dim("dim")?;
+    let strides = read_dim("strides")?;
+
+    render_grid(&shape, |idx| {
+        // strides are signed, negative strides are stored as a two's complement
+        let offset = idx
+            .iter()
+            .zip(&strides)
+            .map(|(&i, &stride)| i as isize * stride as isize)
+            .sum::<isize>();
+        let addr = ptr.checked_add_signed(offset * element.size() as isize)?;
+        element.render(&ctx.read_memory(addr, element.size())?)
+    })
+}
+
+/// Render N-dimensional array in a grid form, elements in a last dimension are placed in a row.
+///
+/// # Arguments
+///
+/// * `shape`: array shape
+/// * `element`: return rendered element by its index
+fn render_grid(shape: &[usize], element: impl Fn(&[usize]) -> Option<String>) -> Option<String> {
+    let shown: Vec<usize> = shape.iter().map(|&len| len.min(MAX_AXIS_LEN)).collect();
+    let total = shown.iter().product::<usize>();
+
+    let mut items = Vec::with_capacity(total);
+    let mut idx = vec![0; shown.len()];
+    for mut flat in 0..total {
+        for (axis, len) in shown.iter().enumerate().rev() {
+            idx[axis] = flat % len;
+            flat /= len;
+        }
+        items.push(element(&idx)?);
+    }
+    let width = items.iter().map(|item| item.len()).max().unwrap_or(0);
+    let items: Vec<String> = items
+        .into_iter()
+        .map(|item| format!("{item:>width$}"))
+        .collect();
+
+    Some(format!(
+        "shape {shape:?}\n{}",
+        render_block(shape, &shown, &items, 0)
+    ))
+}
+
+fn render_block(shape: &[usize], shown: &[usize], items: &[String], depth: usize) -> String {
+    let Some((&len, inner_shape)) = shape.split_first() else {
+        return items.first().cloned().unwrap_or_default();
+    };
+    let inner_shown = &shown[1..];
+
+    let chunk = inner_shown.iter().product::<usize>();
+    let mut parts: Vec<String> = if chunk == 0 {
+        (0..shown[0])
+            .map(|_| render_block(inner_shape, inner_shown, &[], depth + 1))
+            .collect()
+    } else {
+        items
+            .chunks(chunk)
+            .map(|sub_items| render_block(inner_shape, inner_shown, sub_items, depth + 1))
+            .collect()
+    };
+    if len > shown[0] {
+        parts.push("...".to_string());
+    }
+
+    let separator = if inner_shape.is_empty() {
+        ", ".to_string()
+    } else {
+        // 2d blocks of arrays with 3 and more dimensions are separated by empty lines
+        format!(
+            ",\n{}{}",
+            "\n".repeat(inner_shape.len() - 1),
+            " ".repeat(depth + 1)
+        )
+    };
+    format!("[{}]", parts.join(&separator))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_render_grid() {
+        struct TestCase {
+            shape: Vec<usize>,
+            expected: &'static str,
+        }
+        let test_cases = vec![
+            TestCase {
+                shape: vec![3],
+                expected: "shape [3]\n[0, 1, 2]",
+            },
+            TestCase {
+                shape: vec![2, 3],
+                expected: "shape [2, 3]\n[[0, 1, 2],\n [3, 4, 5]]",
+            },
+            TestCase {
+                shape: vec![2, 1, 2],
+                expected: "shape [2, 1, 2]\n[[[0, 1]],\n\n [[2, 3]]]",
+            },
+            TestCase {
+                shape: vec![3, 0],
+                expected: "shape [3, 0]\n[[],\n [],\n []]",
+            },
+            TestCase {
+                shape: vec![2, 11],
+                expected: "shape [2, 11]\n[[ 0,  1,  2,  3,  4,  5,  6,  7,  8,  9, 10],\n [11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21]]",
+            },
+        ];
+
+        for tc in test_cases {
+            let shape = tc.shape.clone();
+            let rendered = render_grid(&tc.shape, |idx| {
+                let flat = idx
+                    .iter()
+                    .zip(&shape)
+                    .fold(0, |acc, (&i, &len)| acc * len + i);
+                Some(flat.to_string())
+            });
+            assert_eq!(rendered.as_deref(), Some(tc.expected));
+        }
+    }
+
+    #[test]
+    fn test_render_grid_truncation() {
+        let rendered = render_grid(&[1, 20], |idx| Some(idx[1].to_string())).unwrap();
+        assert!(rendered.ends_with("14, 15, ...]]"), "{rendered}");
+    }
+
+    #[test]
+    fn test_const_generics() {
+        let decl = |name: &str| TypeDeclaration::Structure {
+            namespaces: Default::default(),
+            name: Some(name.to_string()),
+            byte_size: None,
+            members: vec![],
+            type_params: Default::default(),
+        };
+        assert_eq!(const_generics(&decl("ArrayStorage<f64, 3, 2>")), vec![3, 2]);
+        assert_eq!(const_generics(&decl("Dim<[usize; 2]>")), vec![2]);
+        assert!(
+            const_generics(&decl("Dim<ndarray::dimension::dynindeximpl::IxDynImpl>")).is_empty()
+        );
+    }
+
+    #[test]
+    fn test_element_render() {
+        assert_eq!(
+            Element::Float(8).render(&1.5f64.to_le_bytes()).unwrap(),
+            "1.5"
+        );
+        assert_eq!(
+            Element::Float(4).render(&(-2f32).to_le_bytes()).unwrap(),
+            "-2"
+        );
+        assert_eq!(Element::Signed(1).render(&[0xFF]).unwrap(), "-1");
+        assert_eq!(
+            Element::Signed(4).render(&(-7i32).to_le_bytes()).unwrap(),
+            "-7"
+        );
+        assert_eq!(Element::Unsigned(2).render(&[0xFF, 0xFF]).unwrap(), "65535");
+    }
+}
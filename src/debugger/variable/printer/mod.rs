@@ -0,0 +1,202 @@
+//! Registry of custom pretty-printers.
+//!
+//! Pretty-printer is a pair of type matcher and formatter. Formatter receives raw bytes of
+//! a variable and it's DWARF type declaration and returns a rendered string. When a structure
+//! type matches one of the registered printers, the variable is rendered by its formatter
+//! instead of the default structure layout. This is useful for domain-specific types like
+//! timestamps, identifiers or matrices. Formatters registered with
+//! [`register_pretty_printer_with_context`] also receive a [`FormatContext`], it gives access
+//! to a debugee memory and to declarations of nested types (for heap allocated data).
+//!
+//! With `math_printers` feature, builtin printers for `nalgebra` and `ndarray` matrices
+//! are installed (see `math` module).
+//!
+//! Example:
+//! ```no_run
+//! use bugstalker::debugger::variable::printer::{register_pretty_printer, TypeMatcher};
+//!
+//! register_pretty_printer(TypeMatcher::Name("Meters".to_string()), |data, _| {
+//!     let value = f64::from_ne_bytes(data.get(..8)?.try_into().ok()?);
+//!     Some(format!("{value}m"))
+//! });
+//! ```
+
+#[cfg(feature = "math_printers")]
+pub mod math;
+
+use crate::debugger;
+use crate::debugger::debugee::dwarf::r#type::{ComplexType, MemberLocation, TypeIdentity};
+use crate::debugger::Pid;
+use crate::debugger::TypeDeclaration;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::sync::{Arc, RwLock};
+
+/// Pretty-printer formatter, return `None` if data can't be rendered.
+pub type Formatter =
+    dyn Fn(&[u8], &TypeDeclaration, &FormatContext) -> Option<String> + Send + Sync;
+
+/// Debugee state available to formatters.
+pub struct FormatContext<'a> {
+    pid: Pid,
+    types: &'a ComplexType,
+}
+
+impl<'a> FormatContext<'a> {
+    pub(super) fn new(pid: Pid, types: &'a ComplexType) -> Self {
+        Self { pid, types }
+    }
+
+    /// Read `len` bytes of debugee memory starting at `addr`.
+    pub fn read_memory(&self, addr: usize, len: usize) -> Option<Vec<u8>> {
+        debugger::read_memory_by_pid(self.pid, addr, len).ok()
+    }
+
+    /// Return a declaration of a type referenced by a formatted type (member types,
+    /// type parameters, etc.).
+    pub fn type_decl(&self, id: TypeIdentity) -> Option<&'a TypeDeclaration> {
+        self.types.types.get(&id)
+    }
+
+    /// Return an offset and a type declaration of a structure member.
+    ///
+    /// # Arguments
+    ///
+    /// * `decl`: structure declaration
+    /// * `name`: member name
+    pub fn member(
+        &self,
+        decl: &TypeDeclaration,
+        name: &str,
+    ) -> Option<(usize, &'a TypeDeclaration)> {
+        let TypeDeclaration::Structure { members, .. } = decl else {
+            return None;
+        };
+        let member = members
+            .iter()
+            .find(|member| member.name.as_deref() == Some(name))?;
+        let MemberLocation::Offset(offset) = member.in_struct_location.as_ref()? else {
+            return None;
+        };
+        Some((
+            usize::try_from(*offset).ok()?,
+            self.type_decl(member.type_ref?)?,
+        ))
+    }
+
+    /// Return a declaration of a structure type parameter.
+    ///
+    /// # Arguments
+    ///
+    /// * `decl`: structure declaration
+    /// * `name`: type parameter name
+    pub fn type_param(&self, decl: &TypeDeclaration, name: &str) -> Option<&'a TypeDeclaration> {
+        let TypeDeclaration::Structure { type_params, .. } = decl else {
+            return None;
+        };
+        self.type_decl((*type_params.get(name)?)?)
+    }
+}
+
+/// Type matching rule.
+pub enum TypeMatcher {
+    /// Match a type name exactly (without namespace), for example `DateTime<Utc>`.
+    Name(String),
+    /// Match a full type name (with namespace), for example `chrono::datetime::DateTime<.*>`.
+    Regex(Regex),
+}
+
+impl TypeMatcher {
+    fn is_match(&self, name: &str, full_name: &str) -> bool {
+        match self {
+            TypeMatcher::Name(n) => n == name,
+            TypeMatcher::Regex(re) => re.is_match(full_name),
+        }
+    }
+}
+
+struct PrettyPrinter {
+    matcher: TypeMatcher,
+    formatter: Arc<Formatter>,
+}
+
+static PRINTERS: Lazy<RwLock<Vec<PrettyPrinter>>> = Lazy::new(|| RwLock::new(builtin_printers()));
+
+#[cfg(feature = "math_printers")]
+fn builtin_printers() -> Vec<PrettyPrinter> {
+    math::printers()
+}
+
+#[cfg(not(feature = "math_printers"))]
+fn builtin_printers() -> Vec<PrettyPrinter> {
+    vec![]
+}
+
+/// Install a new pretty-printer. Printers registered later take precedence over earlier ones.
+///
+/// # Arguments
+///
+/// * `matcher`: type matching rule
+/// * `formatter`: function that render variable from raw data
+pub fn register_pretty_printer(
+    matcher: TypeMatcher,
+    formatter: impl Fn(&[u8], &TypeDeclaration) -> Option<String> + Send + Sync + 'static,
+) {
+    register_pretty_printer_with_context(matcher, move |data, decl, _| formatter(data, decl));
+}
+
+/// Install a new pretty-printer which formatter may read debugee memory
+/// and nested type declarations. Printers registered later take precedence over earlier ones.
+///
+/// # Arguments
+///
+/// * `matcher`: type matching rule
+/// * `formatter`: function that render variable from raw data
+pub fn register_pretty_printer_with_context(
+    matcher: TypeMatcher,
+    formatter: impl Fn(&[u8], &TypeDeclaration, &FormatContext) -> Option<String>
+        + Send
+        + Sync
+        + 'static,
+) {
+    PRINTERS.write().unwrap().push(PrettyPrinter {
+        matcher,
+        formatter: Arc::new(formatter),
+    });
+}
+
+/// Remove all installed pretty-printers (including builtin ones).
+pub fn clear_pretty_printers() {
+    PRINTERS.write().unwrap().clear();
+}
+
+/// Return formatter for a type if one exists.
+///
+/// # Arguments
+///
+/// * `name`: type name
+/// * `full_name`: type name with namespace
+pub(super) fn find_formatter(name: &str, full_name: &str) -> Option<Arc<Formatter>> {
+    let printers = PRINTERS.read().unwrap();
+    printers
+        .iter()
+        .rev()
+        .find(|p| p.matcher.is_match(name, full_name))
+        .map(|p| p.formatter.clone())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_type_matcher() {
+        let name_matcher = TypeMatcher::Name("Uuid".to_string());
+        assert!(name_matcher.is_match("Uuid", "uuid::Uuid"));
+        assert!(!name_matcher.is_match("Uuid2", "uuid::Uuid2"));
+
+        let re_matcher = TypeMatcher::Regex(Regex::new("^chrono::.*::DateTime<.*>$").unwrap());
+        assert!(re_matcher.is_match("DateTime<Utc>", "chrono::datetime::DateTime<Utc>"));
+        assert!(!re_matcher.is_match("DateTime<Utc>", "my::DateTime<Utc>"));
+    }
+}
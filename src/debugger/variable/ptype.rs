@@ -0,0 +1,311 @@
+//! Rendering of type declarations as a rust-like source code.
+//!
+//! Output contains memory layout of a type: offsets and sizes of structure fields,
+//! discriminants of enum variants, element and target types of arrays and pointers.
+//! Only a root type is expanded, fields are rendered by type name.
+//!
+//! Example of output for a structure:
+//! ```text
+//! struct vars::Foo {
+//!     /* offset: 0, size: 4 */ bar: i32,
+//!     /* offset: 4, size: 4 */ baz: char,
+//! } /* size: 8 */
+//! ```
+
+use crate::debugger::debugee::dwarf::r#type::{
+    ComplexType, EvaluationContext, MemberLocation, StructureMember, TypeDeclaration, TypeIdentity,
+};
+use crate::debugger::debugee::dwarf::NamespaceHierarchy;
+use itertools::Itertools;
+use std::fmt::Write;
+
+const UNKNOWN: &str = "?";
+const INDENT: &str = "    ";
+
+/// Type declaration renderer.
+pub struct TypeRenderer<'a> {
+    eval_ctx: &'a EvaluationContext<'a>,
+    r#type: &'a ComplexType,
+}
+
+impl<'a> TypeRenderer<'a> {
+    pub fn new(eval_ctx: &'a EvaluationContext<'a>, r#type: &'a ComplexType) -> Self {
+        Self { eval_ctx, r#type }
+    }
+
+    fn type_name(&self, type_id: Option<TypeIdentity>) -> String {
+        type_id
+            .and_then(|id| self.r#type.type_name(id))
+            .unwrap_or_else(|| UNKNOWN.to_string())
+    }
+
+    fn type_size(&self, type_id: Option<TypeIdentity>) -> Option<u64> {
+        self.r#type.type_size_in_bytes(self.eval_ctx, type_id?)
+    }
+
+    fn full_name(namespaces: &NamespaceHierarchy, name: Option<&String>) -> String {
+        let name = name.map(String::as_str).unwrap_or(UNKNOWN);
+        if namespaces.is_empty() {
+            return name.to_string();
+        }
+        format!("{}::{name}", namespaces.join("::"))
+    }
+
+    fn size_str(size: Option<u64>) -> String {
+        size.map(|s| s.to_string())
+            .unwrap_or_else(|| UNKNOWN.to_string())
+    }
+
+    fn member_offset(member: &StructureMember) -> Option<i64> {
+        match member.in_struct_location.as_ref()? {
+            MemberLocation::Offset(offset) => Some(*offset),
+            MemberLocation::Expr(_) => None,
+        }
+    }
+
+    /// Render structure or union members in memory order, holes between members
+    /// are rendered as a padding.
+    fn render_members(
+        &self,
+        out: &mut String,
+        members: &[StructureMember],
+        byte_size: Option<u64>,
+    ) {
+        let members = members
+            .iter()
+            .map(|m| (Self::member_offset(m), self.type_size(m.type_ref), m))
+            .sorted_by_key(|(offset, _, _)| offset.unwrap_or(i64::MAX))
+            .collect::<Vec<_>>();
+
+        let mut expected_offset = Some(0);
+        for (offset, size, member) in members {
+            if let (Some(expected), Some(offset)) = (expected_offset, offset) {
+                if offset > expected {
+                    _ = writeln!(out, "{INDENT}/* {} bytes padding */", offset - expected);
+                }
+            }
+
+            if let Some(bit_field) = member.bit_field {
+                _ = writeln!(
+                    out,
+                    "{INDENT}/* offset: {}, bit offset: {}, bit size: {} */ {}: {},",
+                    bit_field.bit_offset / 8,
+                    bit_field.bit_offset % 8,
+                    bit_field.bit_size,
+                    member.name.as_deref().unwrap_or(UNKNOWN),
+                    self.type_name(member.type_ref),
+                );
+                let end = (bit_field.bit_offset + bit_field.bit_size).div_ceil(8) as i64;
+                expected_offset = expected_offset.map(|expected| expected.max(end));
+                continue;
+            }
+
+            _ = writeln!(
+                out,
+                "{INDENT}/* offset: {}, size: {} */ {}: {},",
+                offset.map(|o| o.to_string()).unwrap_or(UNKNOWN.to_string()),
+                Self::size_str(size),
+                member.name.as_deref().unwrap_or(UNKNOWN),
+                self.type_name(member.type_ref),
+            );
+
+            expected_offset = match (offset, size) {
+                (Some(offset), Some(size)) => {
+                    Some(expected_offset.unwrap_or(0).max(offset + size as i64))
+                }
+                _ => None,
+            };
+        }
+
+        if let (Some(expected), Some(byte_size)) = (expected_offset, byte_size) {
+            if byte_size as i64 > expected {
+                _ = writeln!(
+                    out,
+                    "{INDENT}/* {} bytes padding */",
+                    byte_size as i64 - expected
+                );
+            }
+        }
+    }
+
+    /// Render enum variant, variant fields are rendered like a tuple or like a structure.
+    fn render_variant(&self, member: &StructureMember) -> String {
+        let name = member.name.as_deref().unwrap_or(UNKNOWN);
+        let Some(TypeDeclaration::Structure { members, .. }) =
+            member.type_ref.and_then(|t| self.r#type.types.get(&t))
+        else {
+            return format!("{name}({})", self.type_name(member.type_ref));
+        };
+
+        if members.is_empty() {
+            return name.to_string();
+        }
+
+        let is_tuple = members
+            .iter()
+            .all(|m| m.name.as_deref().is_some_and(|n| n.starts_with("__")));
+        if is_tuple {
+            let fields = members
+                .iter()
+                .map(|m| self.type_name(m.type_ref))
+                .join(", ");
+            format!("{name}({fields})")
+        } else {
+            let fields = members
+                .iter()
+                .map(|m| {
+                    format!(
+                        "{}: {}",
+                        m.name.as_deref().unwrap_or(UNKNOWN),
+                        self.type_name(m.type_ref)
+                    )
+                })
+                .join(", ");
+            format!("{name} {{ {fields} }}")
+        }
+    }
+
+    /// Render declaration of a type.
+    ///
+    /// # Arguments
+    ///
+    /// * `type_id`: type identity, root of the complex type if `None`
+    pub fn render(&self, type_id: Option<TypeIdentity>) -> String {
+        let type_id = type_id.unwrap_or(self.r#type.root);
+        let Some(decl) = self.r#type.types.get(&type_id) else {
+            return UNKNOWN.to_string();
+        };
+
+        let size = Self::size_str(self.type_size(Some(type_id)));
+        let mut out = String::new();
+        match decl {
+            TypeDeclaration::Scalar(scalar) => {
+                _ = write!(
+                    out,
+                    "{} /* size: {size} */",
+                    Self::full_name(&scalar.namespaces, scalar.name.as_ref())
+                );
+            }
+            TypeDeclaration::Array(arr) => {
+                let len = arr
+                    .bounds(self.eval_ctx)
+                    .map(|(lower, upper)| (upper - lower).to_string())
+                    .unwrap_or_else(|| UNKNOWN.to_string());
+                _ = write!(
+                    out,
+                    "[{}; {len}] /* size: {size}, element size: {} */",
+                    self.type_name(arr.element_type),
+                    Self::size_str(self.type_size(arr.element_type)),
+                );
+            }
+            TypeDeclaration::Pointer {
+                name, target_type, ..
+            } => {
+                _ = write!(
+                    out,
+                    "{} /* size: {size}, target: {}, target size: {} */",
+                    name.as_deref().unwrap_or(UNKNOWN),
+                    self.type_name(*target_type),
+                    Self::size_str(self.type_size(*target_type)),
+                );
+            }
+            TypeDeclaration::Structure {
+                namespaces,
+                name,
+                byte_size,
+                members,
+                ..
+            } => {
+                _ = writeln!(
+                    out,
+                    "struct {} {{",
+                    Self::full_name(namespaces, name.as_ref())
+                );
+                self.render_members(&mut out, members, *byte_size);
+                _ = write!(out, "}} /* size: {size} */");
+            }
+            TypeDeclaration::Union {
+                namespaces,
+                name,
+                byte_size,
+                members,
+            } => {
+                _ = writeln!(
+                    out,
+                    "union {} {{",
+                    Self::full_name(namespaces, name.as_ref())
+                );
+                self.render_members(&mut out, members, *byte_size);
+                _ = write!(out, "}} /* size: {size} */");
+            }
+            TypeDeclaration::CStyleEnum {
+                namespaces,
+                name,
+                discr_type,
+                enumerators,
+                ..
+            } => {
+                _ = writeln!(
+                    out,
+                    "enum {} /* discriminant type: {} */ {{",
+                    Self::full_name(namespaces, name.as_ref()),
+                    self.type_name(*discr_type),
+                );
+                for (discr, variant) in enumerators.iter().sorted_by_key(|(discr, _)| **discr) {
+                    _ = writeln!(out, "{INDENT}{variant} = {discr},");
+                }
+                _ = write!(out, "}} /* size: {size} */");
+            }
+            TypeDeclaration::RustEnum {
+                namespaces,
+                name,
+                discr_type,
+                enumerators,
+                ..
+            } => {
+                let discr_type = discr_type.as_ref().and_then(|d| d.type_ref);
+                let signed = discr_type.is_some_and(|t| self.r#type.is_signed(t));
+                _ = writeln!(
+                    out,
+                    "enum {} /* discriminant type: {} */ {{",
+                    Self::full_name(namespaces, name.as_ref()),
+                    self.type_name(discr_type),
+                );
+                // default variant goes last
+                for (discr, member) in enumerators
+                    .iter()
+                    .sorted_by_key(|(discr, _)| discr.map_or(i128::MAX, |d| d.value(signed)))
+                {
+                    let discr = discr
+                        .map(|d| d.value(signed).to_string())
+                        .unwrap_or_else(|| "default".to_string());
+                    _ = writeln!(
+                        out,
+                        "{INDENT}/* discriminant: {discr} */ {},",
+                        self.render_variant(member)
+                    );
+                }
+                _ = write!(out, "}} /* size: {size} */");
+            }
+            TypeDeclaration::Subroutine {
+                name, return_type, ..
+            } => {
+                _ = write!(
+                    out,
+                    "{} /* returns: {} */",
+                    name.as_deref().unwrap_or("fn"),
+                    self.type_name(*return_type),
+                );
+            }
+            TypeDeclaration::ModifiedType {
+                modifier, inner, ..
+            } => {
+                _ = write!(out, "{modifier} {}", self.type_name(*inner));
+                if let Some(inner) = inner {
+                    _ = write!(out, "\n{}", self.render(Some(*inner)));
+                }
+            }
+        }
+        out
+    }
+}
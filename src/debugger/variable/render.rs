@@ -1,12 +1,79 @@
-use crate::debugger::variable::SpecializedVariableIR;
+use crate::debugger::address::RelocatedAddress;
 use crate::debugger::variable::VariableIR;
+use crate::debugger::variable::{
+    PointerTarget, ScalarVariable, SpecializedVariableIR, SupportedScalar,
+};
 use std::borrow::Cow;
 use std::fmt::{Debug, Formatter};
 
+/// Marker rendered in place of values omitted due to render limits.
+pub const TRUNCATED_MARKER: &str = "…(truncated)";
+
+const TAB: &str = "\t";
+
+/// Limits and options applied to a rendered variable, `None` means no limit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RenderLimits {
+    /// Maximum number of rendered elements of arrays, vectors, sets and maps.
+    pub elements: Option<usize>,
+    /// Maximum nesting level of rendered structures and collections.
+    pub depth: Option<usize>,
+    /// Maximum number of rendered characters of strings.
+    pub string_length: Option<usize>,
+    /// Maximum number of pointers followed in a row when a variable is read,
+    /// pointed values are rendered after a pointer.
+    pub pointer_depth: Option<usize>,
+    /// Render arrays of `u8` as ASCII byte strings (like `b"abc\x00"`) instead of number lists.
+    pub bytes_as_ascii: bool,
+}
+
+impl RenderLimits {
+    pub const DEFAULT_ELEMENTS: usize = 200;
+    pub const DEFAULT_STRING_LENGTH: usize = 200;
+    pub const DEFAULT_POINTER_DEPTH: usize = 0;
+
+    /// Return true if a composite value at a nesting level `depth` must not be expanded.
+    pub fn depth_exceeded(&self, depth: usize) -> bool {
+        self.depth.is_some_and(|max| depth >= max)
+    }
+
+    /// Return a number of elements of a collection that should be rendered.
+    pub fn elements_shown(&self, len: usize) -> usize {
+        self.elements.map_or(len, |max| len.min(max))
+    }
+
+    /// Truncate a string according to a string length limit.
+    pub fn truncate_string<'s>(&self, s: &'s str) -> Cow<'s, str> {
+        match self.string_length {
+            Some(max) if s.chars().count() > max => {
+                let truncated: String = s.chars().take(max).collect();
+                Cow::Owned(format!("{truncated}{TRUNCATED_MARKER}"))
+            }
+            _ => Cow::Borrowed(s),
+        }
+    }
+}
+
+impl Default for RenderLimits {
+    fn default() -> Self {
+        Self {
+            elements: Some(Self::DEFAULT_ELEMENTS),
+            depth: None,
+            string_length: Some(Self::DEFAULT_STRING_LENGTH),
+            pointer_depth: Some(Self::DEFAULT_POINTER_DEPTH),
+            bytes_as_ascii: false,
+        }
+    }
+}
+
 pub enum ValueLayout<'a> {
     PreRendered(Cow<'a, str>),
     Referential {
         addr: *const (),
+        /// Description of a memory pointed to (symbol or memory region).
+        annotation: Option<&'a str>,
+        /// Pointed value if pointer is followed.
+        target: Option<&'a PointerTarget>,
     },
     Wrapped(&'a VariableIR),
     Structure {
@@ -73,6 +140,8 @@ impl RenderRepr for VariableIR {
                 },
                 SpecializedVariableIR::String { .. } => return "String",
                 SpecializedVariableIR::Str { .. } => return "&str",
+                SpecializedVariableIR::OsString { original, .. }
+                | SpecializedVariableIR::OsStr { original, .. } => &original.type_name,
                 SpecializedVariableIR::Tls {
                     tls_var: value,
                     original,
@@ -102,12 +171,22 @@ impl RenderRepr for VariableIR {
                 SpecializedVariableIR::Rc { original, .. }
                 | SpecializedVariableIR::Arc { original, .. } => &original.type_name,
                 SpecializedVariableIR::Uuid { original, .. } => &original.type_name,
+                SpecializedVariableIR::Custom { original, .. } => &original.type_name,
+                SpecializedVariableIR::Iterator { iter, original }
+                | SpecializedVariableIR::Channel {
+                    channel: iter,
+                    original,
+                } => match iter {
+                    None => &original.type_name,
+                    Some(iter) => &iter.type_name,
+                },
             },
             VariableIR::Subroutine(_) => {
                 // currently this line is unreachable cause dereference fn pointer is forbidden
                 &None
             }
             VariableIR::CModifiedVariable(v) => &v.type_name,
+            VariableIR::Unavailable(v) => &v.type_name,
         };
         r#type.as_deref().unwrap_or("unknown")
     }
@@ -128,10 +207,11 @@ impl RenderRepr for VariableIR {
                 ValueLayout::PreRendered(Cow::Borrowed(r#enum.value.as_ref()?))
             }
             VariableIR::RustEnum(r#enum) => ValueLayout::Wrapped(r#enum.value.as_ref()?),
-            VariableIR::Pointer(pointer) => {
-                let ptr = pointer.value?;
-                ValueLayout::Referential { addr: ptr }
-            }
+            VariableIR::Pointer(pointer) => ValueLayout::Referential {
+                addr: pointer.value?,
+                annotation: pointer.annotation.as_deref(),
+                target: pointer.target.as_ref(),
+            },
             VariableIR::Specialized(spec) => match spec {
                 SpecializedVariableIR::Vector { vec, original }
                 | SpecializedVariableIR::VecDeque { vec, original } => match vec {
@@ -155,6 +235,18 @@ impl RenderRepr for VariableIR {
                     },
                     Some(s) => ValueLayout::PreRendered(Cow::Borrowed(&s.value)),
                 },
+                SpecializedVariableIR::OsString { string, original } => match string {
+                    None => ValueLayout::Structure {
+                        members: original.members.as_ref(),
+                    },
+                    Some(s) => ValueLayout::PreRendered(Cow::Borrowed(&s.value)),
+                },
+                SpecializedVariableIR::OsStr { string, original } => match string {
+                    None => ValueLayout::Structure {
+                        members: original.members.as_ref(),
+                    },
+                    Some(s) => ValueLayout::PreRendered(Cow::Borrowed(&s.value)),
+                },
                 SpecializedVariableIR::Tls {
                     tls_var: value,
                     original,
@@ -209,10 +301,11 @@ impl RenderRepr for VariableIR {
                     None => ValueLayout::Structure {
                         members: original.members.as_ref(),
                     },
-                    Some(pointer) => {
-                        let ptr = pointer.value?;
-                        ValueLayout::Referential { addr: ptr }
-                    }
+                    Some(pointer) => ValueLayout::Referential {
+                        addr: pointer.value?,
+                        annotation: pointer.annotation.as_deref(),
+                        target: pointer.target.as_ref(),
+                    },
                 },
                 SpecializedVariableIR::Uuid { value, original } => match value {
                     None => ValueLayout::Structure {
@@ -223,13 +316,206 @@ impl RenderRepr for VariableIR {
                         ValueLayout::PreRendered(Cow::Owned(uuid.to_string()))
                     }
                 },
+                SpecializedVariableIR::Custom { value, original } => match value {
+                    None => ValueLayout::Structure {
+                        members: original.members.as_ref(),
+                    },
+                    Some(rendered) => ValueLayout::PreRendered(Cow::Borrowed(rendered)),
+                },
+                SpecializedVariableIR::Iterator { iter, original }
+                | SpecializedVariableIR::Channel {
+                    channel: iter,
+                    original,
+                } => match iter {
+                    None => ValueLayout::Structure {
+                        members: original.members.as_ref(),
+                    },
+                    Some(iter) => ValueLayout::Structure {
+                        members: iter.members.as_ref(),
+                    },
+                },
             },
             VariableIR::Subroutine(_) => {
                 // currently this line is unreachable a cause dereference fn pointer is forbidden
                 return None;
             }
             VariableIR::CModifiedVariable(v) => ValueLayout::Wrapped(v.value.as_ref()?),
+            VariableIR::Unavailable(v) => ValueLayout::PreRendered(Cow::Owned(v.to_string())),
         };
         Some(value_repr)
     }
 }
+
+/// Render a variable value, composite values nested deeper than `limits.depth` and
+/// collection elements over `limits.elements` are replaced with a truncation marker.
+pub fn render_variable_ir(view: &VariableIR, depth: usize, limits: &RenderLimits) -> String {
+    match view.value() {
+        Some(value) => match value {
+            ValueLayout::PreRendered(rendered_value) => match view {
+                VariableIR::CEnum(_) => format!("{}::{}", view.r#type(), rendered_value),
+                VariableIR::Specialized(
+                    SpecializedVariableIR::String { .. }
+                    | SpecializedVariableIR::Str { .. }
+                    | SpecializedVariableIR::OsString { .. }
+                    | SpecializedVariableIR::OsStr { .. },
+                ) => format!(
+                    "{}({})",
+                    view.r#type(),
+                    limits.truncate_string(&rendered_value)
+                ),
+                VariableIR::Unavailable(_) => format!("{} {}", view.r#type(), rendered_value),
+                _ => format!("{}({})", view.r#type(), rendered_value),
+            },
+            ValueLayout::Referential {
+                addr,
+                annotation,
+                target,
+            } => {
+                let addr = RelocatedAddress::from(addr as usize);
+                let mut render = format!("{} [{addr}]", view.r#type());
+                if let Some(annotation) = annotation {
+                    render = format!("{render} {annotation}");
+                }
+                match target {
+                    None => render,
+                    Some(PointerTarget::Cycle) => format!("{render} <cycle to {addr}>"),
+                    Some(PointerTarget::Value(target)) => {
+                        format!("{render} -> {}", render_variable_ir(target, depth, limits))
+                    }
+                }
+            }
+            ValueLayout::Wrapped(val) => {
+                format!(
+                    "{}::{}",
+                    view.r#type(),
+                    render_variable_ir(val, depth, limits)
+                )
+            }
+            ValueLayout::Structure { .. } | ValueLayout::Map(_) | ValueLayout::List { .. }
+                if limits.depth_exceeded(depth) =>
+            {
+                format!("{} {{{TRUNCATED_MARKER}}}", view.r#type())
+            }
+            ValueLayout::Structure { members } => {
+                let mut render = format!("{} {{", view.r#type());
+
+                let tabs = TAB.repeat(depth + 1);
+
+                for v in members {
+                    render = format!("{render}\n");
+                    render = format!(
+                        "{render}{tabs}{}: {}",
+                        v.name(),
+                        render_variable_ir(v, depth + 1, limits)
+                    );
+                }
+
+                format!("{render}\n{}}}", TAB.repeat(depth))
+            }
+            ValueLayout::Map(kv_children) => {
+                let mut render = format!("{} {{", view.r#type());
+
+                let tabs = TAB.repeat(depth + 1);
+
+                let shown = limits.elements_shown(kv_children.len());
+                for kv in &kv_children[..shown] {
+                    render = format!("{render}\n");
+                    render = format!(
+                        "{render}{tabs}{}: {}",
+                        render_variable_ir(&kv.0, depth + 1, limits),
+                        render_variable_ir(&kv.1, depth + 1, limits)
+                    );
+                }
+                if shown < kv_children.len() {
+                    render = format!("{render}\n{tabs}{TRUNCATED_MARKER}");
+                }
+
+                format!("{render}\n{}}}", TAB.repeat(depth))
+            }
+            ValueLayout::List { members, .. }
+                if limits.bytes_as_ascii && matches!(view, VariableIR::Array(_)) =>
+            {
+                match byte_string(members, limits) {
+                    Some(bytes) => format!("{}({bytes})", view.r#type()),
+                    None => render_list(view, members, true, depth, limits),
+                }
+            }
+            ValueLayout::List { members, indexed } => {
+                render_list(view, members, indexed, depth, limits)
+            }
+        },
+        None => format!("{}(unknown)", view.r#type()),
+    }
+}
+
+/// Render an ASCII byte string (`b"..."`) if all list members are `u8` scalars,
+/// number of rendered bytes is limited by the string length limit.
+fn byte_string(members: &[VariableIR], limits: &RenderLimits) -> Option<String> {
+    let bytes = members
+        .iter()
+        .map(|member| match member {
+            VariableIR::Scalar(ScalarVariable {
+                value: Some(SupportedScalar::U8(b)),
+                ..
+            }) => Some(*b),
+            _ => None,
+        })
+        .collect::<Option<Vec<u8>>>()?;
+
+    let shown = limits
+        .string_length
+        .map_or(bytes.len(), |max| bytes.len().min(max));
+    let escaped: String = bytes[..shown]
+        .iter()
+        .flat_map(|b| std::ascii::escape_default(*b))
+        .map(char::from)
+        .collect();
+    if shown < bytes.len() {
+        Some(format!("b\"{escaped}\"{TRUNCATED_MARKER}"))
+    } else {
+        Some(format!("b\"{escaped}\""))
+    }
+}
+
+fn render_list(
+    view: &VariableIR,
+    members: &[VariableIR],
+    indexed: bool,
+    depth: usize,
+    limits: &RenderLimits,
+) -> String {
+    let mut render = format!("{} {{", view.r#type());
+
+    let tabs = TAB.repeat(depth + 1);
+
+    // vector layout is a list of `buf` and `cap` members,
+    // so a limit is applied to elements of `buf` only
+    let is_vector = matches!(
+        view,
+        VariableIR::Specialized(
+            SpecializedVariableIR::Vector { .. } | SpecializedVariableIR::VecDeque { .. }
+        )
+    );
+    let shown = if is_vector {
+        members.len()
+    } else {
+        limits.elements_shown(members.len())
+    };
+    for v in &members[..shown] {
+        render = format!("{render}\n");
+        if indexed {
+            render = format!(
+                "{render}{tabs}{}: {}",
+                v.name(),
+                render_variable_ir(v, depth + 1, limits)
+            );
+        } else {
+            render = format!("{render}{tabs}{}", render_variable_ir(v, depth + 1, limits));
+        }
+    }
+    if shown < members.len() {
+        render = format!("{render}\n{tabs}{TRUNCATED_MARKER}");
+    }
+
+    format!("{render}\n{}}}", TAB.repeat(depth))
+}
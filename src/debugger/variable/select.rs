@@ -1,19 +1,41 @@
+use crate::debugger::address::GlobalAddress;
 use crate::debugger::debugee::dwarf;
-use crate::debugger::debugee::dwarf::r#type::ComplexType;
-use crate::debugger::debugee::dwarf::unit::{DieRef, Node, VariableDie};
+use crate::debugger::debugee::dwarf::r#type::{ComplexType, TypeDeclaration};
+use crate::debugger::debugee::dwarf::unit::{DieRef, Node, ParameterDie, VariableDie};
 use crate::debugger::debugee::dwarf::{
-    AsAllocatedData, ContextualDieRef, EndianArcSlice, NamespaceHierarchy,
+    in_variable_scope, AsAllocatedData, ContextualDieRef, EndianArcSlice, NamespaceHierarchy,
 };
 use crate::debugger::error::Error;
 use crate::debugger::error::Error::FunctionNotFound;
-use crate::debugger::variable::{AssumeError, ParsingError, VariableIR, VariableIdentity};
-use crate::debugger::Error::TypeNotFound;
-use crate::debugger::{variable, Debugger};
+use crate::debugger::register::Register;
+use crate::debugger::variable::ptype::TypeRenderer;
+use crate::debugger::variable::render::RenderRepr;
+use crate::debugger::variable::{
+    AssumeError, ParsingError, PointerTarget, ScalarVariable, SupportedScalar, Unavailability,
+    UnavailableVariable, VariableIR, VariableIdentity,
+};
+use crate::debugger::Error::{HistoryValueNotFound, RegisterNameNotFound, TypeNotFound};
+use crate::debugger::{self, variable, Debugger, ExplorationContext};
 use crate::{ctx_resolve_unit_call, weak_error};
 use bytes::Bytes;
-use gimli::{Attribute, DebugInfoOffset, Range, UnitOffset};
+use gimli::{
+    Attribute, DW_ATE_address, DW_ATE_float, DW_ATE_signed, DW_ATE_signed_char, DW_ATE_unsigned,
+    DW_ATE_unsigned_char, DebugInfoOffset, Range, UnitOffset,
+};
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Kind of a cast target type, defines how a cast operand is converted.
+#[derive(Clone, Copy)]
+enum CastTarget {
+    Pointer,
+    Integer,
+    Float,
+    Other,
+}
 
 /// This die not exists in debug information.
 /// It may be used to represent variables that are
@@ -46,7 +68,13 @@ impl AsAllocatedData for VirtualVariableDie {
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum VariableSelector {
-    Name { var_name: String, only_local: bool },
+    Name {
+        var_name: String,
+        only_local: bool,
+    },
+    /// Variables with names matched by a glob pattern
+    /// (`*` matches any sequence of characters, `?` matches any single character).
+    Pattern(String),
     Any,
 }
 
@@ -59,6 +87,38 @@ impl VariableSelector {
     }
 }
 
+/// Return true if text is matched by a glob pattern.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    // position of last `*` in pattern and text position matched with it
+    let mut backtrack = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star_p, star_t)) => {
+                    // let `*` match one more character
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    backtrack = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
 /// Literal object. Using it for a searching element by key in key-value containers.
 #[derive(Debug, PartialEq, Clone)]
 pub enum Literal {
@@ -117,6 +177,7 @@ impl Literal {
 }
 
 /// Object binary representation in debugee memory.
+#[derive(Clone)]
 pub struct ObjectBinaryRepr {
     /// Binary representation.
     pub raw_data: Bytes,
@@ -138,6 +199,13 @@ pub struct ObjectBinaryRepr {
 pub enum DQE {
     Variable(VariableSelector),
     PtrCast(usize, String),
+    /// Register value at the selected frame (like `$rsp`).
+    Register(String),
+    /// Value from a value history by its number (like `$1`).
+    History(usize),
+    /// Expression result interpreted as a value of another type: pointers are made
+    /// from numeric values, other types reinterpret the memory of an expression result.
+    Cast(Box<DQE>, String),
     Field(Box<DQE>, String),
     Index(Box<DQE>, Literal),
     Slice(Box<DQE>, Option<usize>, Option<usize>),
@@ -161,10 +229,55 @@ pub struct DqeResult {
     pub scope: Option<Box<[Range]>>,
 }
 
+/// State of a local variable declaration at the current PC.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DeclarationState {
+    /// Declaration is live, variable name refers to it.
+    Live,
+    /// Declaration is live, but shadowed by an inner declaration with the same name.
+    Shadowed,
+    /// Declaration scope starts at the current PC or after it.
+    NotYetInitialized,
+    /// Declaration scope ends before the current PC.
+    OutOfScope,
+}
+
+impl Display for DeclarationState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeclarationState::Live => f.write_str("live"),
+            DeclarationState::Shadowed => f.write_str("shadowed"),
+            DeclarationState::NotYetInitialized => f.write_str("not yet initialized"),
+            DeclarationState::OutOfScope => f.write_str("out of scope"),
+        }
+    }
+}
+
+/// Declaration of a local variable.
+pub struct VariableDeclaration {
+    /// File and line where variable is declared, `None` if unknown.
+    pub place: Option<(PathBuf, u64)>,
+    pub state: DeclarationState,
+    /// Variable value, `None` if declaration is not live or value can't be read.
+    pub value: Option<VariableIR>,
+}
+
+/// Result of a cast subexpression, used as a root of an outer expression.
+struct CastValue {
+    /// Identity of a variable before cast.
+    identity: VariableIdentity,
+    data: ObjectBinaryRepr,
+}
+
 /// Evaluate `Expression` at current breakpoint (for current debugee location).
 pub struct SelectExpressionEvaluator<'a> {
     debugger: &'a Debugger,
     expression: DQE,
+    /// Context for evaluation, debugger exploration context by default.
+    expl_ctx: &'a ExplorationContext,
+    /// Maximum number of pointers followed in a row, `None` for unlimited,
+    /// pointers are not followed by default.
+    pointer_depth: Option<usize>,
 }
 
 macro_rules! type_from_cache {
@@ -187,6 +300,28 @@ impl<'a> SelectExpressionEvaluator<'a> {
         Self {
             debugger,
             expression,
+            expl_ctx: debugger.exploration_ctx(),
+            pointer_depth: Some(0),
+        }
+    }
+
+    /// Evaluate expression in a context other than debugger exploration context
+    /// (for example, at another stack frame).
+    pub fn with_context(self, expl_ctx: &'a ExplorationContext) -> Self {
+        Self { expl_ctx, ..self }
+    }
+
+    /// Follow pointers in evaluation results: pointed values are read and stored
+    /// in [`PointerTarget::Value`], pointers to values that contain them are marked
+    /// as [`PointerTarget::Cycle`].
+    ///
+    /// # Arguments
+    ///
+    /// * `depth`: maximum number of pointers followed in a row, `None` for unlimited
+    pub fn with_pointer_depth(self, depth: Option<usize>) -> Self {
+        Self {
+            pointer_depth: depth,
+            ..self
         }
     }
 
@@ -194,9 +329,9 @@ impl<'a> SelectExpressionEvaluator<'a> {
         &self,
         selector: &VariableSelector,
     ) -> Result<Vec<ContextualDieRef<VariableDie>>, Error> {
-        let ctx = self.debugger.exploration_ctx();
+        let ctx = self.expl_ctx;
 
-        let debugee = &self.debugger.debugee;
+        let debugee = &self.debugger.inferior().debugee;
         let current_func = debugee
             .debug_info(ctx.location().pc)?
             .find_function_by_pc(ctx.location().global_pc)?
@@ -216,12 +351,41 @@ impl<'a> SelectExpressionEvaluator<'a> {
 
                 // local variables is in priority anyway, if there are no local variables and
                 // selector allow non-locals then try to search in a whole object
-                if !local && local_variants.is_empty() {
+                let variants = if !local && local_variants.is_empty() {
                     debugee
                         .debug_info(ctx.location().pc)?
                         .find_variables(ctx.location(), var_name)?
                 } else {
                     local_variants
+                };
+
+                // a local declared later in a function is reported as not initialized
+                if variants.is_empty() {
+                    current_func
+                        .not_yet_live_local_variable(ctx.location().global_pc, var_name)
+                        .into_iter()
+                        .collect()
+                } else {
+                    variants
+                }
+            }
+            VariableSelector::Pattern(pattern) => {
+                let local_variants: Vec<_> = current_func
+                    .local_variables(ctx.location().global_pc)
+                    .into_iter()
+                    .filter(|var| var.die.name().is_some_and(|name| glob_match(pattern, name)))
+                    .collect();
+
+                // same as for names, locals are in priority
+                if local_variants.is_empty() {
+                    let debug_info = debugee.debug_info(ctx.location().pc)?;
+                    let mut vars = vec![];
+                    for name in debug_info.variable_names(|name| glob_match(pattern, name))? {
+                        vars.extend(debug_info.find_variables(ctx.location(), &name)?);
+                    }
+                    vars
+                } else {
+                    local_variants
                 }
             }
             VariableSelector::Any => current_func.local_variables(ctx.location().global_pc),
@@ -230,13 +394,52 @@ impl<'a> SelectExpressionEvaluator<'a> {
         Ok(vars)
     }
 
+    /// Call `f` with function arguments that matched by selector.
+    fn with_arguments_by_selector<T>(
+        &self,
+        selector: &VariableSelector,
+        f: impl FnOnce(Vec<ContextualDieRef<ParameterDie>>) -> T,
+    ) -> Result<T, Error> {
+        let expl_ctx_loc = self.expl_ctx.location();
+        let current_function = self
+            .debugger
+            .inferior()
+            .debugee
+            .debug_info(expl_ctx_loc.pc)?
+            .find_function_by_pc(expl_ctx_loc.global_pc)?
+            .ok_or(FunctionNotFound(expl_ctx_loc.global_pc))?;
+        let params = current_function.parameters();
+
+        let params = match selector {
+            VariableSelector::Name { var_name, .. } => params
+                .into_iter()
+                .filter(|param| {
+                    param.die.base_attributes.name.as_deref() == Some(var_name.as_str())
+                })
+                .collect::<Vec<_>>(),
+            VariableSelector::Pattern(pattern) => params
+                .into_iter()
+                .filter(|param| {
+                    param
+                        .die
+                        .base_attributes
+                        .name
+                        .as_deref()
+                        .is_some_and(|name| glob_match(pattern, name))
+                })
+                .collect::<Vec<_>>(),
+            VariableSelector::Any => params,
+        };
+        Ok(f(params))
+    }
+
     fn fill_virtual_ptr_variable(
         &self,
         vv: &'a mut VirtualVariableDie,
         node: &'a Node,
         type_name: &str,
     ) -> Result<ContextualDieRef<'a, VirtualVariableDie>, Error> {
-        let debugee = &self.debugger.debugee;
+        let debugee = &self.debugger.inferior().debugee;
         let (debug_info, offset_of_unit, offset_of_die) = debugee
             .debug_info_all()
             .iter()
@@ -280,20 +483,166 @@ impl<'a> SelectExpressionEvaluator<'a> {
         }
     }
 
+    /// Evaluate all declarations of a local variable in the current function
+    /// (a variable may be shadowed by a declaration with the same name),
+    /// declarations are ordered from the outer one.
+    /// Only variable name selector supported.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if select expression is not a variable name selector.
+    pub fn evaluate_declarations(&self) -> Result<Vec<VariableDeclaration>, Error> {
+        let DQE::Variable(VariableSelector::Name { var_name, .. }) = &self.expression else {
+            unreachable!("unexpected expression variant")
+        };
+
+        let location = self.expl_ctx.location();
+        let current_func = self
+            .debugger
+            .inferior()
+            .debugee
+            .debug_info(location.pc)?
+            .find_function_by_pc(location.global_pc)?
+            .ok_or(FunctionNotFound(location.global_pc))?;
+        let declarations = current_func.local_variables_with_name(var_name);
+        let live_idx = declarations
+            .iter()
+            .rposition(|var| var.valid_at(location.global_pc));
+
+        let pc = u64::from(location.global_pc);
+        let mut type_cache = self.debugger.inferior().type_cache.borrow_mut();
+        Ok(declarations
+            .iter()
+            .enumerate()
+            .map(|(idx, var)| {
+                let state = if Some(idx) == live_idx {
+                    DeclarationState::Live
+                } else if var.valid_at(location.global_pc) {
+                    DeclarationState::Shadowed
+                } else if var
+                    .ranges()
+                    .is_some_and(|ranges| ranges.iter().all(|range| range.begin >= pc))
+                {
+                    DeclarationState::NotYetInitialized
+                } else {
+                    DeclarationState::OutOfScope
+                };
+
+                let value = match state {
+                    DeclarationState::Live | DeclarationState::Shadowed => {
+                        weak_error!(type_from_cache!(var, type_cache)).and_then(|r#type| {
+                            self.unavailable_variable(var, r#type, var.ranges())
+                                .or_else(|| {
+                                    self.evaluate_single_variable(
+                                        &self.expression,
+                                        var,
+                                        r#type,
+                                        None,
+                                    )
+                                })
+                        })
+                    }
+                    DeclarationState::NotYetInitialized | DeclarationState::OutOfScope => None,
+                };
+
+                VariableDeclaration {
+                    place: var.decl_place(),
+                    state,
+                    value,
+                }
+            })
+            .collect())
+    }
+
+    /// Evaluate variable types, return pairs of variable name and type declaration
+    /// rendered as a rust-like source code.
+    /// Only filter expression supported.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic
+    /// if select expression contains any operators excluding a variable selector.
+    pub fn evaluate_types(&self) -> Result<Vec<(String, String)>, Error> {
+        match &self.expression {
+            DQE::Variable(selector) => {
+                let vars = self.extract_variable_by_selector(selector)?;
+                Ok(self.render_types(&vars))
+            }
+            _ => unreachable!("unexpected expression variant"),
+        }
+    }
+
+    /// Same as [`SelectExpressionEvaluator::evaluate_types`] but for function arguments.
+    pub fn evaluate_on_arguments_types(&self) -> Result<Vec<(String, String)>, Error> {
+        match &self.expression {
+            DQE::Variable(selector) => {
+                self.with_arguments_by_selector(selector, |params| self.render_types(&params))
+            }
+            _ => unreachable!("unexpected expression variant"),
+        }
+    }
+
+    /// Find type by its name and render its declaration as a rust-like source code.
+    ///
+    /// # Arguments
+    ///
+    /// * `type_name`: type name
+    pub fn evaluate_type_by_name(&self, type_name: &str) -> Result<String, Error> {
+        let any_node = Node::new_leaf(None);
+        let mut var_die = VirtualVariableDie::of_unknown_type();
+        let var_die_ref = self.fill_virtual_ptr_variable(&mut var_die, &any_node, type_name)?;
+
+        let mut type_cache = self.debugger.inferior().type_cache.borrow_mut();
+        let r#type = type_from_cache!(var_die_ref, type_cache)?;
+        Ok(self.render_type(&var_die_ref, r#type))
+    }
+
+    fn render_types(
+        &self,
+        vars: &[ContextualDieRef<impl AsAllocatedData>],
+    ) -> Vec<(String, String)> {
+        let mut type_cache = self.debugger.inferior().type_cache.borrow_mut();
+        vars.iter()
+            .filter_map(|var| {
+                let r#type = weak_error!(type_from_cache!(var, type_cache))?;
+                let name = var.die.name()?.to_string();
+                Some((name, self.render_type(var, r#type)))
+            })
+            .collect()
+    }
+
+    fn render_type(
+        &self,
+        variable_die: &ContextualDieRef<impl AsAllocatedData>,
+        r#type: &ComplexType,
+    ) -> String {
+        let evaluator =
+            ctx_resolve_unit_call!(variable_die, evaluator, &self.debugger.inferior().debugee);
+        let evaluation_context = &dwarf::r#type::EvaluationContext {
+            evaluator: &evaluator,
+            expl_ctx: self.expl_ctx,
+        };
+        TypeRenderer::new(evaluation_context, r#type).render(None)
+    }
+
     fn evaluate_inner(&self, expression: &DQE) -> Result<Vec<DqeResult>, Error> {
         // evaluate variable one by one in `evaluate_single_variable` method
         // here just filter variables
         match expression {
             DQE::Variable(selector) => {
                 let vars = self.extract_variable_by_selector(selector)?;
-                let mut type_cache = self.debugger.type_cache.borrow_mut();
+                let mut type_cache = self.debugger.inferior().type_cache.borrow_mut();
 
                 Ok(vars
                     .iter()
                     .filter_map(|var| {
                         let r#type = weak_error!(type_from_cache!(var, type_cache))?;
-                        let var_ir =
-                            self.evaluate_single_variable(&self.expression, var, r#type)?;
+                        let var_ir = match self.unavailable_variable(var, r#type, var.ranges()) {
+                            Some(unavailable) => unavailable,
+                            None => {
+                                self.evaluate_single_variable(&self.expression, var, r#type, None)?
+                            }
+                        };
                         Some(DqeResult {
                             variable: var_ir,
                             scope: var.ranges().map(Box::from),
@@ -311,6 +660,18 @@ impl<'a> SelectExpressionEvaluator<'a> {
                     })
                     .collect())
             }
+            DQE::Register(_) | DQE::History(_) => Ok(self
+                .evaluate_detached(&self.expression)?
+                .map(|var_ir| DqeResult {
+                    variable: var_ir,
+                    scope: None,
+                })
+                .into_iter()
+                .collect()),
+            DQE::Cast(expr, type_name) => {
+                let sources = self.with_expression(expr).evaluate()?;
+                self.evaluate_cast(sources, type_name)
+            }
             DQE::Field(expr, _)
             | DQE::Index(expr, _)
             | DQE::Slice(expr, _, _)
@@ -326,15 +687,214 @@ impl<'a> SelectExpressionEvaluator<'a> {
         let mut var_die = VirtualVariableDie::of_unknown_type();
         let var_die_ref = self.fill_virtual_ptr_variable(&mut var_die, &any_node, type_name)?;
 
-        let mut type_cache = self.debugger.type_cache.borrow_mut();
+        let mut type_cache = self.debugger.inferior().type_cache.borrow_mut();
         let r#type = type_from_cache!(var_die_ref, type_cache)?;
 
-        if let Some(v) = self.evaluate_single_variable(&self.expression, &var_die_ref, r#type) {
+        if let Some(v) = self.evaluate_single_variable(&self.expression, &var_die_ref, r#type, None)
+        {
             return Ok(vec![v]);
         }
         Ok(vec![])
     }
 
+    /// Return an evaluator of a subexpression in the same context.
+    fn with_expression(&self, expression: &DQE) -> Self {
+        Self {
+            debugger: self.debugger,
+            expression: expression.clone(),
+            expl_ctx: self.expl_ctx,
+            // subexpression results are not printed, so pointers in it are not followed
+            pointer_depth: Some(0),
+        }
+    }
+
+    /// Interpret results of a cast subexpression as values of a named type,
+    /// then evaluate expression over these values.
+    ///
+    /// # Arguments
+    ///
+    /// * `sources`: evaluated cast operand
+    /// * `type_name`: cast target type name
+    fn evaluate_cast(
+        &self,
+        sources: Vec<DqeResult>,
+        type_name: &str,
+    ) -> Result<Vec<DqeResult>, Error> {
+        let (size, target) = self.type_layout_by_name(type_name)?;
+        let casts = sources
+            .into_iter()
+            .map(|source| {
+                let cast = CastValue {
+                    identity: source.variable.identity().clone(),
+                    data: self.cast_data(&source.variable, type_name, size, target)?,
+                };
+                Ok((cast, source.scope))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let any_node = Node::new_leaf(None);
+        let mut var_die = VirtualVariableDie::of_unknown_type();
+        let var_die_ref = self.fill_virtual_ptr_variable(&mut var_die, &any_node, type_name)?;
+
+        let mut type_cache = self.debugger.inferior().type_cache.borrow_mut();
+        let r#type = type_from_cache!(var_die_ref, type_cache)?;
+
+        Ok(casts
+            .into_iter()
+            .filter_map(|(cast, scope)| {
+                let variable = self.evaluate_single_variable(
+                    &self.expression,
+                    &var_die_ref,
+                    r#type,
+                    Some(&cast),
+                )?;
+                Some(DqeResult { variable, scope })
+            })
+            .collect())
+    }
+
+    /// Return binary representation of a variable cast to a type of `size` bytes.
+    /// Integers and pointers are converted by value (signed integers are sign-extended),
+    /// values of other types are reinterpreted from the variable location in debugee memory,
+    /// in this case target type can't be larger than the type of the variable.
+    fn cast_data(
+        &self,
+        variable: &VariableIR,
+        type_name: &str,
+        size: usize,
+        target: CastTarget,
+    ) -> Result<ObjectBinaryRepr, Error> {
+        let number = match variable {
+            VariableIR::Pointer(pointer) => pointer.value.map(|ptr| ptr as usize as i128),
+            VariableIR::Scalar(scalar) => match scalar.value {
+                Some(SupportedScalar::U64(num)) => Some(num as i128),
+                Some(SupportedScalar::Usize(num)) => Some(num as i128),
+                _ => scalar.try_as_number().map(i128::from),
+            },
+            _ => None,
+        };
+
+        let raw_data = match (number, target) {
+            (Some(num), CastTarget::Pointer | CastTarget::Integer) => {
+                let mut raw_data = num.to_le_bytes().to_vec();
+                raw_data.resize(size, if num < 0 { 0xFF } else { 0 });
+                Some(raw_data)
+            }
+            (Some(num), CastTarget::Float) if size == 4 => {
+                Some((num as f32).to_le_bytes().to_vec())
+            }
+            (Some(num), CastTarget::Float) if size == 8 => {
+                Some((num as f64).to_le_bytes().to_vec())
+            }
+            _ => None,
+        };
+        if let Some(raw_data) = raw_data {
+            return Ok(ObjectBinaryRepr {
+                raw_data: Bytes::from(raw_data),
+                address: None,
+                size,
+            });
+        }
+
+        let addr = variable
+            .in_memory_location()
+            .ok_or(Error::MemorySubjectNoAddress)?;
+        let operand_type = variable.r#type();
+        let (operand_size, _) = self
+            .type_layout_by_name(operand_type)
+            .map_err(|_| Error::CastUnknownSize(operand_type.to_string()))?;
+        if size > operand_size {
+            return Err(Error::CastToLargerType(
+                operand_type.to_string(),
+                operand_size,
+                type_name.to_string(),
+                size,
+            ));
+        }
+
+        let raw_data = debugger::read_memory_by_pid(self.expl_ctx.pid_on_focus(), addr, size)?;
+        Ok(ObjectBinaryRepr {
+            raw_data: Bytes::from(raw_data),
+            address: Some(addr),
+            size,
+        })
+    }
+
+    /// Find type by its name, return its size in bytes and a kind of a cast into this type.
+    fn type_layout_by_name(&self, type_name: &str) -> Result<(usize, CastTarget), Error> {
+        let any_node = Node::new_leaf(None);
+        let mut var_die = VirtualVariableDie::of_unknown_type();
+        let var_die_ref = self.fill_virtual_ptr_variable(&mut var_die, &any_node, type_name)?;
+
+        let mut type_cache = self.debugger.inferior().type_cache.borrow_mut();
+        let r#type = type_from_cache!(var_die_ref, type_cache)?;
+
+        let evaluator =
+            ctx_resolve_unit_call!(var_die_ref, evaluator, &self.debugger.inferior().debugee);
+        let evaluation_context = &dwarf::r#type::EvaluationContext {
+            evaluator: &evaluator,
+            expl_ctx: self.expl_ctx,
+        };
+        let size = r#type
+            .type_size_in_bytes(evaluation_context, r#type.root)
+            .ok_or(TypeNotFound)? as usize;
+        #[allow(non_upper_case_globals)]
+        let target = match r#type.types.get(&r#type.root) {
+            Some(TypeDeclaration::Pointer { .. }) => CastTarget::Pointer,
+            Some(TypeDeclaration::Scalar(scalar)) => match scalar.encoding {
+                Some(DW_ATE_float) => CastTarget::Float,
+                Some(
+                    DW_ATE_signed | DW_ATE_unsigned | DW_ATE_signed_char | DW_ATE_unsigned_char
+                    | DW_ATE_address,
+                ) => CastTarget::Integer,
+                _ => CastTarget::Other,
+            },
+            _ => CastTarget::Other,
+        };
+        Ok((size, target))
+    }
+
+    /// Evaluate expression over a value without debug information: a register value
+    /// or a history value. Type of these values is unknown,
+    /// so dereference, address and slice operators aren't applicable.
+    fn evaluate_detached(&self, expression: &DQE) -> Result<Option<VariableIR>, Error> {
+        Ok(match expression {
+            DQE::Register(name) => {
+                let register =
+                    Register::from_str(name).map_err(|_| RegisterNameNotFound(name.clone()))?;
+                let value = self
+                    .debugger
+                    .inferior()
+                    .debugee
+                    .register_value(self.expl_ctx, register)?;
+                Some(VariableIR::Scalar(ScalarVariable {
+                    identity: VariableIdentity::no_namespace(Some(format!("${name}"))),
+                    value: Some(SupportedScalar::U64(value)),
+                    raw_address: None,
+                    type_name: Some("u64".to_string()),
+                    type_id: None,
+                }))
+            }
+            DQE::History(num) => {
+                let mut value = self
+                    .debugger
+                    .value_history()
+                    .get(*num)
+                    .ok_or(HistoryValueNotFound(*num))?
+                    .clone();
+                *value.identity_mut() = VariableIdentity::no_namespace(Some(format!("${num}")));
+                Some(value)
+            }
+            DQE::Field(expr, field) => self.evaluate_detached(expr)?.and_then(|v| v.field(field)),
+            DQE::Index(expr, idx) => self.evaluate_detached(expr)?.and_then(|v| v.index(idx)),
+            DQE::Canonic(expr) => self.evaluate_detached(expr)?.map(|v| v.canonic()),
+            DQE::Slice(..) | DQE::Deref(_) | DQE::Address(_) => None,
+            DQE::Variable(_) | DQE::PtrCast(..) | DQE::Cast(..) => {
+                unreachable!("unexpected expression variant")
+            }
+        })
+    }
+
     /// Evaluate a select expression and returns list of matched variables.
     pub fn evaluate(&self) -> Result<Vec<DqeResult>, Error> {
         self.evaluate_inner(&self.expression)
@@ -343,29 +903,12 @@ impl<'a> SelectExpressionEvaluator<'a> {
     /// Same as [`SelectExpressionEvaluator::evaluate_names`] but for function arguments.
     pub fn evaluate_on_arguments_names(&self) -> Result<Vec<String>, Error> {
         match &self.expression {
-            DQE::Variable(selector) => {
-                let expl_ctx_loc = self.debugger.exploration_ctx().location();
-                let current_function = self
-                    .debugger
-                    .debugee
-                    .debug_info(expl_ctx_loc.pc)?
-                    .find_function_by_pc(expl_ctx_loc.global_pc)?
-                    .ok_or(FunctionNotFound(expl_ctx_loc.global_pc))?;
-                let params = current_function.parameters();
-
-                let params = match selector {
-                    VariableSelector::Name { var_name, .. } => params
-                        .into_iter()
-                        .filter(|param| param.die.base_attributes.name.as_ref() == Some(var_name))
-                        .collect::<Vec<_>>(),
-                    VariableSelector::Any => params,
-                };
-
-                Ok(params
+            DQE::Variable(selector) => self.with_arguments_by_selector(selector, |params| {
+                params
                     .into_iter()
                     .filter_map(|die| die.die.name().map(ToOwned::to_owned))
-                    .collect())
-            }
+                    .collect()
+            }),
             _ => unreachable!("unexpected expression variant"),
         }
     }
@@ -378,39 +921,32 @@ impl<'a> SelectExpressionEvaluator<'a> {
     fn evaluate_on_arguments_inner(&self, expression: &DQE) -> Result<Vec<DqeResult>, Error> {
         match expression {
             DQE::Variable(selector) => {
-                let expl_ctx_loc = self.debugger.exploration_ctx().location();
-                let debugee = &self.debugger.debugee;
-                let current_function = debugee
-                    .debug_info(expl_ctx_loc.pc)?
-                    .find_function_by_pc(expl_ctx_loc.global_pc)?
-                    .ok_or(FunctionNotFound(expl_ctx_loc.global_pc))?;
-                let params = current_function.parameters();
-
-                let params = match selector {
-                    VariableSelector::Name { var_name, .. } => params
-                        .into_iter()
-                        .filter(|param| param.die.base_attributes.name.as_ref() == Some(var_name))
-                        .collect::<Vec<_>>(),
-                    VariableSelector::Any => params,
-                };
-
-                let mut type_cache = self.debugger.type_cache.borrow_mut();
+                let mut type_cache = self.debugger.inferior().type_cache.borrow_mut();
 
-                Ok(params
-                    .iter()
-                    .filter_map(|var| {
-                        let r#type = weak_error!(type_from_cache!(var, type_cache))?;
-                        let var_ir =
-                            self.evaluate_single_variable(&self.expression, var, r#type)?;
-                        Some(DqeResult {
-                            variable: var_ir,
-                            scope: var.max_range().map(|r| {
-                                let scope: Box<[Range]> = Box::new([r]);
-                                scope
-                            }),
+                self.with_arguments_by_selector(selector, |params| {
+                    params
+                        .iter()
+                        .filter_map(|var| {
+                            let r#type = weak_error!(type_from_cache!(var, type_cache))?;
+                            let var_ir = match self.unavailable_variable(var, r#type, None) {
+                                Some(unavailable) => unavailable,
+                                None => self.evaluate_single_variable(
+                                    &self.expression,
+                                    var,
+                                    r#type,
+                                    None,
+                                )?,
+                            };
+                            Some(DqeResult {
+                                variable: var_ir,
+                                scope: var.max_range().map(|r| {
+                                    let scope: Box<[Range]> = Box::new([r]);
+                                    scope
+                                }),
+                            })
                         })
-                    })
-                    .collect())
+                        .collect()
+                })
             }
             DQE::PtrCast(_, target_type_name) => {
                 let vars = self.evaluate_from_ptr_cast(target_type_name)?;
@@ -422,6 +958,12 @@ impl<'a> SelectExpressionEvaluator<'a> {
                     })
                     .collect())
             }
+            // registers and history values are not function arguments
+            DQE::Register(_) | DQE::History(_) => Ok(vec![]),
+            DQE::Cast(expr, type_name) => {
+                let sources = self.with_expression(expr).evaluate_on_arguments()?;
+                self.evaluate_cast(sources, type_name)
+            }
             DQE::Field(expr, _)
             | DQE::Index(expr, _)
             | DQE::Slice(expr, _, _)
@@ -431,25 +973,126 @@ impl<'a> SelectExpressionEvaluator<'a> {
         }
     }
 
+    /// Return a stub instead of a variable value if a variable is not live at the current PC:
+    /// its scope starts after the current PC, or its location list doesn't cover the current PC.
+    /// Return `None` if a variable value can be read.
+    ///
+    /// # Arguments
+    ///
+    /// * `variable_die`: variable to check
+    /// * `r#type`: type of `variable_die`
+    /// * `scope`: PC ranges of a variable lexical block, `None` if unknown
+    fn unavailable_variable(
+        &self,
+        variable_die: &ContextualDieRef<impl AsAllocatedData>,
+        r#type: &ComplexType,
+        scope: Option<&[Range]>,
+    ) -> Option<VariableIR> {
+        let location = self.expl_ctx.location();
+        let (reason, mut live_ranges) = match scope {
+            Some(scope)
+                if !in_variable_scope(
+                    variable_die.unit(),
+                    location.global_pc,
+                    scope,
+                    variable_die.die.decl_line(),
+                ) =>
+            {
+                // location of a not yet initialized variable is already allocated,
+                // so its address (for example, to watch an initialization) is valid
+                if takes_address_only(&self.expression) {
+                    return None;
+                }
+                (Unavailability::NotYetInitialized, scope.to_vec())
+            }
+            _ => {
+                let live_ranges = variable_die.location_ranges()?;
+                if location.global_pc.in_ranges(&live_ranges) {
+                    return None;
+                }
+                (Unavailability::OptimizedOut, live_ranges)
+            }
+        };
+        live_ranges.sort_by_key(|range| range.begin);
+
+        let debugee = &self.debugger.inferior().debugee;
+        let relocate = |addr: u64| {
+            weak_error!(GlobalAddress::from(addr).relocate_to_segment_by_pc(debugee, location.pc))
+        };
+        let live_ranges = live_ranges
+            .into_iter()
+            .filter_map(|range| Some(relocate(range.begin)?..relocate(range.end)?))
+            .collect();
+
+        Some(VariableIR::Unavailable(UnavailableVariable {
+            identity: VariableIdentity::from_variable_die(variable_die),
+            type_name: r#type.type_name(r#type.root),
+            type_id: Some(r#type.root),
+            reason,
+            live_ranges,
+        }))
+    }
+
+    /// Evaluate expression over a variable.
+    ///
+    /// # Arguments
+    ///
+    /// * `expression`: expression to evaluate
+    /// * `variable_die`: expression root variable, or a virtual variable of cast result type
+    /// * `r#type`: type of `variable_die`
+    /// * `cast`: a value of cast subexpression if expression root is a cast
     fn evaluate_single_variable(
         &self,
         expression: &DQE,
         variable_die: &ContextualDieRef<impl AsAllocatedData>,
         r#type: &ComplexType,
+        cast: Option<&CastValue>,
+    ) -> Option<VariableIR> {
+        let mut var =
+            self.evaluate_single_variable_inner(expression, variable_die, r#type, cast)?;
+        if self.pointer_depth == Some(0) {
+            return Some(var);
+        }
+
+        let parser = variable::VariableParser::new(r#type);
+        let evaluator =
+            ctx_resolve_unit_call!(variable_die, evaluator, &self.debugger.inferior().debugee);
+        let evaluation_context = &dwarf::r#type::EvaluationContext {
+            evaluator: &evaluator,
+            expl_ctx: self.expl_ctx,
+        };
+        let mut path: Vec<_> = var.in_memory_location().into_iter().collect();
+        follow_pointers(
+            evaluation_context,
+            &parser,
+            &mut var,
+            self.pointer_depth,
+            &mut path,
+        );
+        Some(var)
+    }
+
+    fn evaluate_single_variable_inner(
+        &self,
+        expression: &DQE,
+        variable_die: &ContextualDieRef<impl AsAllocatedData>,
+        r#type: &ComplexType,
+        cast: Option<&CastValue>,
     ) -> Option<VariableIR> {
         let parser = variable::VariableParser::new(r#type);
 
-        let evaluator = ctx_resolve_unit_call!(variable_die, evaluator, &self.debugger.debugee);
+        let evaluator =
+            ctx_resolve_unit_call!(variable_die, evaluator, &self.debugger.inferior().debugee);
         let evaluation_context = &dwarf::r#type::EvaluationContext {
             evaluator: &evaluator,
-            expl_ctx: self.debugger.exploration_ctx(),
+            expl_ctx: self.expl_ctx,
         };
 
         match expression {
             DQE::Variable(_) => {
                 let data = variable_die.read_value(
-                    self.debugger.exploration_ctx(),
-                    &self.debugger.debugee,
+                    self.expl_ctx,
+                    &self.debugger.inferior().debugee,
                     r#type,
                 );
                 Some(parser.parse(
@@ -471,29 +1114,112 @@ impl<'a> SelectExpressionEvaluator<'a> {
                 ))
             }
             DQE::Field(expr, field) => {
-                let var = self.evaluate_single_variable(expr, variable_die, r#type)?;
+                let var = self.evaluate_single_variable_inner(expr, variable_die, r#type, cast)?;
                 var.field(field)
             }
             DQE::Index(expr, idx) => {
-                let var = self.evaluate_single_variable(expr, variable_die, r#type)?;
+                let var = self.evaluate_single_variable_inner(expr, variable_die, r#type, cast)?;
                 var.index(idx)
             }
             DQE::Slice(expr, left, right) => {
-                let var = self.evaluate_single_variable(expr, variable_die, r#type)?;
+                let var = self.evaluate_single_variable_inner(expr, variable_die, r#type, cast)?;
                 var.slice(evaluation_context, &parser, *left, *right)
             }
             DQE::Deref(expr) => {
-                let var = self.evaluate_single_variable(expr, variable_die, r#type)?;
+                let var = self.evaluate_single_variable_inner(expr, variable_die, r#type, cast)?;
                 var.deref(evaluation_context, &parser)
             }
             DQE::Address(expr) => {
-                let var = self.evaluate_single_variable(expr, variable_die, r#type)?;
+                let var = self.evaluate_single_variable_inner(expr, variable_die, r#type, cast)?;
                 var.address(evaluation_context, &parser)
             }
             DQE::Canonic(expr) => {
-                let var = self.evaluate_single_variable(expr, variable_die, r#type)?;
+                let var = self.evaluate_single_variable_inner(expr, variable_die, r#type, cast)?;
                 Some(var.canonic())
             }
+            DQE::Cast(..) => {
+                let cast = cast?;
+                Some(parser.parse(
+                    evaluation_context,
+                    cast.identity.clone(),
+                    Some(cast.data.clone()),
+                ))
+            }
+            DQE::Register(_) | DQE::History(_) => {
+                unreachable!("detached expression evaluated with DIE")
+            }
         }
     }
 }
+
+/// Return true if expression is an address of a variable or of a variable field,
+/// so a variable value isn't used.
+fn takes_address_only(expression: &DQE) -> bool {
+    fn is_place(expression: &DQE) -> bool {
+        match expression {
+            DQE::Variable(_) => true,
+            DQE::Field(expr, _) => is_place(expr),
+            _ => false,
+        }
+    }
+    matches!(expression, DQE::Address(expr) if is_place(expr))
+}
+
+/// Dereference pointers in a variable (and pointers in dereferenced values) up to `depth`
+/// levels of indirection. Pointers to a value in `path` (addresses of values which contain
+/// a pointer) are not dereferenced and marked as [`PointerTarget::Cycle`].
+fn follow_pointers(
+    eval_ctx: &dwarf::r#type::EvaluationContext,
+    parser: &variable::VariableParser,
+    var: &mut VariableIR,
+    depth: Option<usize>,
+    path: &mut Vec<usize>,
+) {
+    if depth == Some(0) {
+        return;
+    }
+
+    var.for_each_pointer_mut(&mut |pointer| {
+        let Some(addr) = pointer.value.map(|ptr| ptr as usize) else {
+            return;
+        };
+        if addr == 0 {
+            return;
+        }
+        if path.contains(&addr) {
+            pointer.target = Some(PointerTarget::Cycle);
+            return;
+        }
+        let Some(mut target) = pointer.deref(eval_ctx, parser) else {
+            return;
+        };
+        path.push(addr);
+        follow_pointers(
+            eval_ctx,
+            parser,
+            &mut target,
+            depth.map(|depth| depth - 1),
+            path,
+        );
+        path.pop();
+        pointer.target = Some(PointerTarget::Value(Box::new(target)));
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*", "anything"));
+        assert!(glob_match("foo*", "foo"));
+        assert!(glob_match("foo*", "foobar"));
+        assert!(!glob_match("foo*", "barfoo"));
+        assert!(glob_match("*_id", "user_id"));
+        assert!(glob_match("a?c*d", "abcxxd"));
+        assert!(!glob_match("a?c", "ac"));
+        assert!(glob_match("*a*b*", "xxaxxbxx"));
+        assert!(!glob_match("*a*b", "xxbxxa"));
+    }
+}
@@ -6,7 +6,7 @@ use crate::debugger::variable::select::ObjectBinaryRepr;
 use crate::debugger::variable::AssumeError::NoType;
 use crate::debugger::variable::ParsingError::ReadDebugeeMemory;
 use crate::debugger::variable::{AssumeError, ParsingError};
-use crate::debugger::TypeDeclaration;
+use crate::debugger::{SyscallError, TypeDeclaration};
 use fallible_iterator::FallibleIterator;
 use std::mem;
 use std::ptr::NonNull;
@@ -355,7 +355,7 @@ impl Handle {
         &self,
         k_size: usize,
         v_size: usize,
-    ) -> nix::Result<(ObjectBinaryRepr, ObjectBinaryRepr)> {
+    ) -> Result<(ObjectBinaryRepr, ObjectBinaryRepr), SyscallError> {
         let leaf = self.node.data.leaf();
         let key_offset = k_size * self.idx;
         let key_raw = leaf.keys_raw[key_offset..k_size * (self.idx + 1)].to_vec();
@@ -0,0 +1,266 @@
+//! Reflection of channels of `std::sync::mpsc` and `crossbeam-channel`. Both crates use the same
+//! implementation (std channels are a port of crossbeam ones): a channel handle points to
+//! a shared counter of senders and receivers, the counter contains a channel of one of flavors:
+//! - array: bounded channel, messages are stored in a ring buffer of slots
+//! - list: unbounded channel, messages are stored in a linked list of blocks of slots
+//! - zero: channel without a buffer, a sender waits for a receiver
+
+use crate::debugger::debugee::dwarf::r#type::{
+    ComplexType, EvaluationContext, MemberLocation, TypeIdentity,
+};
+use crate::debugger::TypeDeclaration;
+
+/// List channel: each block has a place for one extra message, it is never used.
+const LAP: usize = 32;
+/// List channel: number of messages a block can hold.
+const BLOCK_CAP: usize = LAP - 1;
+/// List channel: how many lower bits of an index are reserved for metadata.
+const SHIFT: usize = 1;
+
+/// Return a number of messages in an array channel.
+///
+/// # Arguments
+///
+/// * `head`: head index (a position of the next message to receive)
+/// * `tail`: tail index (a position of the next message to send)
+/// * `cap`: channel capacity
+/// * `mark_bit`: a bit of an index that marks a disconnected channel
+pub(super) fn array_len(head: usize, tail: usize, cap: usize, mark_bit: usize) -> usize {
+    let hix = head & (mark_bit - 1);
+    let tix = tail & (mark_bit - 1);
+
+    if hix < tix {
+        tix - hix
+    } else if hix > tix {
+        cap - hix + tix
+    } else if (tail & !mark_bit) == head {
+        0
+    } else {
+        cap
+    }
+}
+
+/// Return buffer positions of queued messages of an array channel, from the oldest one.
+///
+/// # Arguments
+///
+/// * `head`: head index
+/// * `len`: number of messages
+/// * `cap`: channel capacity
+/// * `mark_bit`: a bit of an index that marks a disconnected channel
+pub(super) fn array_slots(
+    head: usize,
+    len: usize,
+    cap: usize,
+    mark_bit: usize,
+) -> impl Iterator<Item = usize> {
+    let hix = head & (mark_bit - 1);
+    (0..len).map(move |i| (hix + i) % cap)
+}
+
+/// Return a number of messages in a list channel.
+///
+/// # Arguments
+///
+/// * `head`: head index
+/// * `tail`: tail index
+pub(super) fn list_len(head: usize, tail: usize) -> usize {
+    let mut tail = tail & !((1 << SHIFT) - 1);
+    let mut head = head & !((1 << SHIFT) - 1);
+
+    // indices at the block end point to the next block
+    if (tail >> SHIFT) & (LAP - 1) == LAP - 1 {
+        tail = tail.wrapping_add(1 << SHIFT);
+    }
+    if (head >> SHIFT) & (LAP - 1) == LAP - 1 {
+        head = head.wrapping_add(1 << SHIFT);
+    }
+
+    // rotate indices so that head falls into the first block
+    let lap = (head >> SHIFT) / LAP;
+    tail = tail.wrapping_sub((lap * LAP) << SHIFT);
+    head = head.wrapping_sub((lap * LAP) << SHIFT);
+
+    tail >>= SHIFT;
+    head >>= SHIFT;
+
+    // the difference minus the number of unused places at block ends
+    tail - head - tail / LAP
+}
+
+/// Return positions of queued messages of a list channel, from the oldest one.
+/// A position is a pair of a block number (starting from the head block) and a slot
+/// number in a block.
+///
+/// # Arguments
+///
+/// * `head`: head index
+/// * `len`: number of messages
+pub(super) fn list_slots(head: usize, len: usize) -> impl Iterator<Item = (usize, usize)> {
+    let mut block = 0;
+    let mut offset = (head >> SHIFT) % LAP;
+    (0..len).map(move |_| {
+        if offset >= BLOCK_CAP {
+            block += 1;
+            offset = 0;
+        }
+        offset += 1;
+        (block, offset - 1)
+    })
+}
+
+/// Return an offset and a type of a structure member.
+fn member(decl: &TypeDeclaration, name: &str) -> Option<(usize, Option<TypeIdentity>)> {
+    let TypeDeclaration::Structure { members, .. } = decl else {
+        return None;
+    };
+    let member = members
+        .iter()
+        .find(|member| member.name.as_deref() == Some(name))?;
+    let MemberLocation::Offset(offset) = member.in_struct_location.as_ref()? else {
+        return None;
+    };
+    Some((usize::try_from(*offset).ok()?, member.type_ref))
+}
+
+/// Layout of a channel slot (`Slot<T>`), a slot contains a message and a state of a slot.
+pub(super) struct SlotLayout {
+    pub(super) size: usize,
+    /// Offset of a message in a slot.
+    pub(super) msg_offset: usize,
+}
+
+impl SlotLayout {
+    pub(super) fn from_type(
+        eval_ctx: &EvaluationContext,
+        r#type: &ComplexType,
+        slot_type: TypeIdentity,
+    ) -> Option<Self> {
+        let (msg_offset, _) = member(r#type.types.get(&slot_type)?, "msg")?;
+        Some(Self {
+            size: r#type.type_size_in_bytes(eval_ctx, slot_type)? as usize,
+            msg_offset,
+        })
+    }
+}
+
+/// Layout of a list channel block (`Block<T>`).
+pub(super) struct BlockLayout {
+    /// Offset of a pointer to the next block.
+    pub(super) next_offset: usize,
+    /// Offset of a slots array.
+    pub(super) slots_offset: usize,
+    pub(super) slot: SlotLayout,
+}
+
+impl BlockLayout {
+    pub(super) fn from_type(
+        eval_ctx: &EvaluationContext,
+        r#type: &ComplexType,
+        block_type: TypeIdentity,
+    ) -> Option<Self> {
+        let block = r#type.types.get(&block_type)?;
+        let (next_offset, _) = member(block, "next")?;
+        let (slots_offset, slots_type) = member(block, "slots")?;
+        let TypeDeclaration::Array(slots) = r#type.types.get(&slots_type?)? else {
+            return None;
+        };
+        Some(Self {
+            next_offset,
+            slots_offset,
+            slot: SlotLayout::from_type(eval_ctx, r#type, slots.element_type?)?,
+        })
+    }
+
+    /// Return an address of a message in a block.
+    pub(super) fn msg_address(&self, block: usize, slot: usize) -> usize {
+        block + self.slots_offset + slot * self.slot.size + self.slot.msg_offset
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Return a list channel index of a message with a sequence number `n`.
+    fn list_index(n: usize) -> usize {
+        (n + n / BLOCK_CAP) << SHIFT
+    }
+
+    #[test]
+    fn test_array_len() {
+        struct TestCase {
+            head: usize,
+            tail: usize,
+            expected: usize,
+        }
+        // capacity is 4, so a mark bit is 8 and one lap is 16
+        let test_cases = [
+            TestCase {
+                head: 0,
+                tail: 0,
+                expected: 0,
+            },
+            TestCase {
+                head: 1,
+                tail: 3,
+                expected: 2,
+            },
+            TestCase {
+                head: 3,
+                tail: 16 + 1,
+                expected: 2,
+            },
+            TestCase {
+                head: 2,
+                tail: 16 + 2,
+                expected: 4,
+            },
+            TestCase {
+                head: 16 + 2,
+                tail: 16 + 2,
+                expected: 0,
+            },
+            TestCase {
+                head: 1,
+                tail: 3 | 8,
+                expected: 2,
+            },
+        ];
+
+        for tc in test_cases {
+            assert_eq!(
+                array_len(tc.head, tc.tail, 4, 8),
+                tc.expected,
+                "head: {}, tail: {}",
+                tc.head,
+                tc.tail
+            );
+        }
+    }
+
+    #[test]
+    fn test_array_slots() {
+        let slots: Vec<_> = array_slots(3, 3, 4, 8).collect();
+        assert_eq!(slots, vec![3, 0, 1]);
+    }
+
+    #[test]
+    fn test_list_len() {
+        assert_eq!(list_len(0, 0), 0);
+        assert_eq!(list_len(list_index(1), list_index(3)), 2);
+        assert_eq!(list_len(list_index(0), list_index(40)), 40);
+        assert_eq!(list_len(list_index(30), list_index(33)), 3);
+        // disconnected channel
+        assert_eq!(list_len(list_index(5), list_index(7) | 1), 2);
+    }
+
+    #[test]
+    fn test_list_slots() {
+        let slots: Vec<_> = list_slots(list_index(29), 4).collect();
+        assert_eq!(slots, vec![(0, 29), (0, 30), (1, 0), (1, 1)]);
+
+        let slots: Vec<_> = list_slots(list_index(31), 2).collect();
+        assert_eq!(slots, vec![(0, 0), (0, 1)]);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::debugger;
+use crate::debugger::{Pid, SyscallError};
 use fallible_iterator::FallibleIterator;
-use nix::unistd::Pid;
 
 /// A bit mask which contains the result of a Match operation on a Group and allows iterating through them.
 #[derive(Copy, Clone, PartialEq, Debug)]
@@ -42,7 +42,7 @@ impl GroupReflection {
     }
 
     /// Load group of control bytes from debugee process.
-    fn load(pid: Pid, ptr: *const u8) -> Result<Self, nix::Error> {
+    fn load(pid: Pid, ptr: *const u8) -> Result<Self, SyscallError> {
         let mut data: [u8; 16] = Default::default();
         data.copy_from_slice(&debugger::read_memory_by_pid(
             pid,
@@ -90,7 +90,7 @@ impl BucketReflection {
     }
 
     /// Read `T` as raw bytes from a debugee process.
-    pub(super) fn read(&self, pid: Pid) -> nix::Result<Vec<u8>> {
+    pub(super) fn read(&self, pid: Pid) -> Result<Vec<u8>, SyscallError> {
         debugger::read_memory_by_pid(pid, self.location(), self.size)
     }
 
@@ -126,7 +126,7 @@ impl HashmapReflection {
         self.bucket_mask + 1
     }
 
-    pub(super) fn iter(&self, pid: Pid) -> Result<BucketIterator, nix::Error> {
+    pub(super) fn iter(&self, pid: Pid) -> Result<BucketIterator, SyscallError> {
         unsafe {
             let ctrl = self.crtl;
 
@@ -154,7 +154,7 @@ pub(super) struct BucketIterator {
 
 impl FallibleIterator for BucketIterator {
     type Item = BucketReflection;
-    type Error = nix::Error;
+    type Error = SyscallError;
 
     fn next(&mut self) -> Result<Option<Self::Item>, Self::Error> {
         unsafe {
@@ -1,10 +1,12 @@
 mod btree;
+mod channel;
 mod hashbrown;
 
 use crate::debugger::debugee::dwarf::r#type::{EvaluationContext, TypeIdentity};
 use crate::debugger::variable::render::RenderRepr;
 use crate::debugger::variable::select::ObjectBinaryRepr;
 use crate::debugger::variable::specialization::btree::BTreeReflection;
+use crate::debugger::variable::specialization::channel::{BlockLayout, SlotLayout};
 use crate::debugger::variable::specialization::hashbrown::HashmapReflection;
 use crate::debugger::variable::AssumeError::{
     TypeParameterNotFound, TypeParameterTypeNotFound, UnexpectedType,
@@ -139,6 +141,16 @@ pub enum SpecializedVariableIR {
         string: Option<StrVariable>,
         original: StructVariable,
     },
+    /// Platform string owned by `OsString`, `PathBuf` or `CString`.
+    OsString {
+        string: Option<StringVariable>,
+        original: StructVariable,
+    },
+    /// Platform string borrowed by `&OsStr`, `&Path` or `&CStr`.
+    OsStr {
+        string: Option<StrVariable>,
+        original: StructVariable,
+    },
     Tls {
         tls_var: Option<TlsVariable>,
         original: StructVariable,
@@ -163,6 +175,23 @@ pub enum SpecializedVariableIR {
         value: Option<[u8; 16]>,
         original: StructVariable,
     },
+    /// Structure rendered by user defined pretty-printer.
+    Custom {
+        value: Option<String>,
+        original: StructVariable,
+    },
+    /// Iterator adapter (`Map`, `Filter`, `Enumerate`, `Chain`) with its state or an iterator
+    /// over a collection (`vec::IntoIter`, `slice::Iter`) with remaining items.
+    Iterator {
+        iter: Option<StructVariable>,
+        original: StructVariable,
+    },
+    /// Channel sender or receiver (`std::sync::mpsc` or `crossbeam-channel`) with a channel state
+    /// and queued messages.
+    Channel {
+        channel: Option<StructVariable>,
+        original: StructVariable,
+    },
 }
 
 impl SpecializedVariableIR {
@@ -176,12 +205,17 @@ impl SpecializedVariableIR {
             SpecializedVariableIR::BTreeSet { original, .. } => original.raw_address,
             SpecializedVariableIR::String { original, .. } => original.raw_address,
             SpecializedVariableIR::Str { original, .. } => original.raw_address,
+            SpecializedVariableIR::OsString { original, .. } => original.raw_address,
+            SpecializedVariableIR::OsStr { original, .. } => original.raw_address,
             SpecializedVariableIR::Tls { original, .. } => original.raw_address,
             SpecializedVariableIR::Cell { original, .. } => original.raw_address,
             SpecializedVariableIR::RefCell { original, .. } => original.raw_address,
             SpecializedVariableIR::Rc { original, .. } => original.raw_address,
             SpecializedVariableIR::Arc { original, .. } => original.raw_address,
             SpecializedVariableIR::Uuid { original, .. } => original.raw_address,
+            SpecializedVariableIR::Custom { original, .. } => original.raw_address,
+            SpecializedVariableIR::Iterator { original, .. } => original.raw_address,
+            SpecializedVariableIR::Channel { original, .. } => original.raw_address,
         }
     }
 
@@ -195,12 +229,17 @@ impl SpecializedVariableIR {
             SpecializedVariableIR::BTreeSet { original, .. } => original.type_id,
             SpecializedVariableIR::String { original, .. } => original.type_id,
             SpecializedVariableIR::Str { original, .. } => original.type_id,
+            SpecializedVariableIR::OsString { original, .. } => original.type_id,
+            SpecializedVariableIR::OsStr { original, .. } => original.type_id,
             SpecializedVariableIR::Tls { original, .. } => original.type_id,
             SpecializedVariableIR::Cell { original, .. } => original.type_id,
             SpecializedVariableIR::RefCell { original, .. } => original.type_id,
             SpecializedVariableIR::Rc { original, .. } => original.type_id,
             SpecializedVariableIR::Arc { original, .. } => original.type_id,
             SpecializedVariableIR::Uuid { original, .. } => original.type_id,
+            SpecializedVariableIR::Custom { original, .. } => original.type_id,
+            SpecializedVariableIR::Iterator { original, .. } => original.type_id,
+            SpecializedVariableIR::Channel { original, .. } => original.type_id,
         }
     }
 }
@@ -285,6 +324,112 @@ impl<'a> VariableParserExtension<'a> {
         })
     }
 
+    /// Parse an owned platform string (`OsString`, `PathBuf` or `CString`).
+    ///
+    /// # Arguments
+    ///
+    /// * `eval_ctx`: evaluation context
+    /// * `structure`: string structure
+    /// * `nul_terminated`: true for a `CString`, its bytes end with a NUL
+    pub fn parse_os_string(
+        &self,
+        eval_ctx: &EvaluationContext,
+        structure: StructVariable,
+        nul_terminated: bool,
+    ) -> SpecializedVariableIR {
+        SpecializedVariableIR::OsString {
+            string: weak_error!(self
+                .parse_os_string_inner(
+                    eval_ctx,
+                    VariableIR::Struct(structure.clone()),
+                    nul_terminated
+                )
+                .context("OsString interpretation")),
+            original: structure,
+        }
+    }
+
+    fn parse_os_string_inner(
+        &self,
+        eval_ctx: &EvaluationContext,
+        ir: VariableIR,
+        nul_terminated: bool,
+    ) -> Result<StringVariable, ParsingError> {
+        let (data_ptr, len) = if nul_terminated {
+            // `CString` holds a boxed slice of bytes
+            (
+                ir.assume_field_as_pointer("data_ptr")?,
+                ir.assume_field_as_scalar_number("length")?,
+            )
+        } else {
+            // `OsString` and `PathBuf` hold a vector of bytes
+            (
+                ir.assume_field_as_pointer("pointer")?,
+                ir.assume_field_as_scalar_number("len")?,
+            )
+        };
+        let len = guard_len(len);
+
+        let data = debugger::read_memory_by_pid(
+            eval_ctx.expl_ctx.pid_on_focus(),
+            data_ptr as usize,
+            len as usize,
+        )?;
+
+        Ok(StringVariable {
+            identity: ir.identity().clone(),
+            value: decode_platform_string(&data, nul_terminated),
+        })
+    }
+
+    /// Parse a borrowed platform string (`&OsStr`, `&Path` or `&CStr`).
+    ///
+    /// # Arguments
+    ///
+    /// * `eval_ctx`: evaluation context
+    /// * `structure`: string slice structure
+    /// * `nul_terminated`: true for a `&CStr`, its bytes end with a NUL
+    pub fn parse_os_str(
+        &self,
+        eval_ctx: &EvaluationContext,
+        structure: StructVariable,
+        nul_terminated: bool,
+    ) -> SpecializedVariableIR {
+        SpecializedVariableIR::OsStr {
+            string: weak_error!(self
+                .parse_os_str_inner(
+                    eval_ctx,
+                    VariableIR::Struct(structure.clone()),
+                    nul_terminated
+                )
+                .context("&OsStr interpretation")),
+            original: structure,
+        }
+    }
+
+    fn parse_os_str_inner(
+        &self,
+        eval_ctx: &EvaluationContext,
+        ir: VariableIR,
+        nul_terminated: bool,
+    ) -> Result<StrVariable, ParsingError> {
+        let len = ir.assume_field_as_scalar_number("length")?;
+        let len = guard_len(len);
+
+        let data_ptr = ir.assume_field_as_pointer("data_ptr")?;
+
+        let data = debugger::read_memory_by_pid(
+            eval_ctx.expl_ctx.pid_on_focus(),
+            data_ptr as usize,
+            len as usize,
+        )?;
+
+        Ok(StrVariable {
+            identity: ir.identity().clone(),
+            value: decode_platform_string(&data, nul_terminated),
+        })
+    }
+
     pub fn parse_vector(
         &self,
         eval_ctx: &EvaluationContext,
@@ -316,47 +461,7 @@ impl<'a> VariableParserExtension<'a> {
         let cap = guard_cap(cap);
 
         let data_ptr = ir.assume_field_as_pointer("pointer")? as usize;
-
-        let el_type = self.parser.r#type;
-        let el_type_size = el_type
-            .type_size_in_bytes(eval_ctx, inner_type)
-            .ok_or(UnknownSize(
-                el_type.type_name(inner_type).unwrap_or_default(),
-            ))? as usize;
-
-        let raw_data = debugger::read_memory_by_pid(
-            eval_ctx.expl_ctx.pid_on_focus(),
-            data_ptr,
-            len as usize * el_type_size,
-        )
-        .map(Bytes::from)?;
-
-        let (mut bytes_chunks, mut empty_chunks);
-        let raw_items_iter: &mut dyn Iterator<Item = (usize, &[u8])> = if el_type_size != 0 {
-            bytes_chunks = raw_data.chunks(el_type_size).enumerate();
-            &mut bytes_chunks
-        } else {
-            // if an item type is zst
-            let v: Vec<&[u8]> = vec![&[]; len as usize];
-            empty_chunks = v.into_iter().enumerate();
-            &mut empty_chunks
-        };
-
-        let items = raw_items_iter
-            .map(|(i, chunk)| {
-                let data = ObjectBinaryRepr {
-                    raw_data: raw_data.slice_ref(chunk),
-                    address: Some(data_ptr + (i * el_type_size)),
-                    size: el_type_size,
-                };
-                self.parser.parse_inner(
-                    eval_ctx,
-                    VariableIdentity::no_namespace(Some(format!("{}", i as i64))),
-                    Some(data),
-                    inner_type,
-                )
-            })
-            .collect::<Vec<_>>();
+        let items = self.parse_items(eval_ctx, inner_type, data_ptr, len as usize)?;
 
         Ok(VecVariable {
             structure: StructVariable {
@@ -392,6 +497,62 @@ impl<'a> VariableParserExtension<'a> {
         })
     }
 
+    /// Parse `len` consecutive items of the same type.
+    ///
+    /// # Arguments
+    ///
+    /// * `eval_ctx`: evaluation context
+    /// * `el_type`: item type
+    /// * `data_ptr`: address of a first item
+    /// * `len`: items count
+    fn parse_items(
+        &self,
+        eval_ctx: &EvaluationContext,
+        el_type: TypeIdentity,
+        data_ptr: usize,
+        len: usize,
+    ) -> Result<Vec<VariableIR>, ParsingError> {
+        let r#type = self.parser.r#type;
+        let el_type_size = r#type
+            .type_size_in_bytes(eval_ctx, el_type)
+            .ok_or(UnknownSize(r#type.type_name(el_type).unwrap_or_default()))?
+            as usize;
+
+        let raw_data = debugger::read_memory_by_pid(
+            eval_ctx.expl_ctx.pid_on_focus(),
+            data_ptr,
+            len * el_type_size,
+        )
+        .map(Bytes::from)?;
+
+        let (mut bytes_chunks, mut empty_chunks);
+        let raw_items_iter: &mut dyn Iterator<Item = (usize, &[u8])> = if el_type_size != 0 {
+            bytes_chunks = raw_data.chunks(el_type_size).enumerate();
+            &mut bytes_chunks
+        } else {
+            // if an item type is zst
+            let v: Vec<&[u8]> = vec![&[]; len];
+            empty_chunks = v.into_iter().enumerate();
+            &mut empty_chunks
+        };
+
+        Ok(raw_items_iter
+            .map(|(i, chunk)| {
+                let data = ObjectBinaryRepr {
+                    raw_data: raw_data.slice_ref(chunk),
+                    address: Some(data_ptr + (i * el_type_size)),
+                    size: el_type_size,
+                };
+                self.parser.parse_inner(
+                    eval_ctx,
+                    VariableIdentity::no_namespace(Some(format!("{}", i as i64))),
+                    Some(data),
+                    el_type,
+                )
+            })
+            .collect())
+    }
+
     pub fn parse_tls(
         &self,
         structure: StructVariable,
@@ -412,21 +573,71 @@ impl<'a> VariableParserExtension<'a> {
     ) -> Result<TlsVariable, ParsingError> {
         // we assume that tls variable name represents in dwarf
         // as namespace flowed before "__getit" namespace
+        // or before first "{constant#0}"-like namespace (for newer rust versions)
         let namespace = &ir.identity().namespace;
         let name = namespace
             .iter()
-            .find_position(|&ns| ns == "__getit")
-            .map(|(pos, _)| namespace[pos - 1].clone());
+            .find_position(|&ns| ns.as_ref() == "__getit" || ns.starts_with('{'))
+            .and_then(|(pos, _)| pos.checked_sub(1).map(|pos| namespace[pos].clone()));
 
         let inner_type = type_params
             .get("T")
             .ok_or(TypeParameterNotFound("T"))?
             .ok_or(TypeParameterTypeNotFound("T"))?;
 
-        let inner = ir
+        let tls_value = match ir.bfs_iterator().find(|child| child.name() == "state") {
+            // lazy or eager storage (rust 1.79+)
+            Some(state) => Self::parse_tls_storage_value(&ir, state, inner_type)?,
+            None => {
+                let inner = ir
+                    .bfs_iterator()
+                    .find(|child| child.name() == "inner")
+                    .ok_or(FieldNotFound("inner"))?;
+                Self::parse_tls_option_value(inner)?
+            }
+        };
+
+        Ok(TlsVariable {
+            identity: VariableIdentity::no_namespace(name.map(|name| name.to_string())),
+            inner_value: tls_value,
+            inner_type: self.parser.r#type.type_name(inner_type),
+        })
+    }
+
+    /// Parse value of `std::sys::thread_local::native::lazy::Storage`
+    /// or `std::sys::thread_local::native::eager::Storage`.
+    fn parse_tls_storage_value(
+        storage: &VariableIR,
+        state: &VariableIR,
+        inner_type: TypeIdentity,
+    ) -> Result<Option<Box<VariableIR>>, ParsingError> {
+        let is_alive = match state.bfs_iterator().find_map(|child| match child {
+            VariableIR::RustEnum(r_enum) => Some(r_enum.value.as_ref().map(|variant| {
+                variant.r#type() != "Uninitialized" && variant.r#type() != "Destroyed"
+            })),
+            VariableIR::CEnum(c_enum) => Some(Some(c_enum.value.as_deref() != Some("Destroyed"))),
+            _ => None,
+        }) {
+            Some(Some(is_alive)) => is_alive,
+            _ => {
+                return Err(ParsingError::Assume(IncompleteInterp(
+                    "expect TLS storage state as enum",
+                )))
+            }
+        };
+        if !is_alive {
+            return Ok(None);
+        }
+
+        let value = storage
             .bfs_iterator()
-            .find(|child| child.name() == "inner")
-            .ok_or(FieldNotFound("inner"))?;
+            .find(|child| child.type_identity() == Some(inner_type))
+            .ok_or(FieldNotFound("value"))?;
+        Ok(Some(Box::new(value.clone())))
+    }
+
+    /// Parse value of `std::sys::thread_local::fast_local::Key`.
+    fn parse_tls_option_value(inner: &VariableIR) -> Result<Option<Box<VariableIR>>, ParsingError> {
         let inner_option = inner.assume_field_as_rust_enum("value")?;
         let inner_value = inner_option.value.ok_or(IncompleteInterp("value"))?;
 
@@ -443,12 +654,7 @@ impl<'a> VariableParserExtension<'a> {
                         .clone(),
                 ))
             };
-
-            return Ok(TlsVariable {
-                identity: VariableIdentity::no_namespace(name),
-                inner_value: tls_value,
-                inner_type: self.parser.r#type.type_name(inner_type),
-            });
+            return Ok(tls_value);
         }
 
         Err(ParsingError::Assume(IncompleteInterp(
@@ -949,6 +1155,386 @@ impl<'a> VariableParserExtension<'a> {
             .ok_or(IncompleteInterp("Arc"))?)
     }
 
+    /// Parse an iterator adapter, an adapter type name is shortened (like `Map`),
+    /// a not yet consumed part of a `Chain` is unwrapped from `Option`.
+    ///
+    /// # Arguments
+    ///
+    /// * `structure`: adapter structure
+    /// * `adapter`: adapter name
+    pub fn parse_iter_adapter(
+        &self,
+        structure: StructVariable,
+        adapter: &str,
+    ) -> SpecializedVariableIR {
+        let members = structure
+            .members
+            .iter()
+            .map(|member| match member {
+                VariableIR::RustEnum(option)
+                    if option
+                        .type_name
+                        .as_deref()
+                        .is_some_and(|name| name.starts_with("Option")) =>
+                {
+                    match option.value.as_deref() {
+                        Some(VariableIR::Struct(some))
+                            if some.type_name.as_deref() == Some("Some") =>
+                        {
+                            some.members
+                                .first()
+                                .map(|inner| inner.clone_and_rename(member.name().as_str()))
+                                .unwrap_or_else(|| member.clone())
+                        }
+                        _ => member.clone(),
+                    }
+                }
+                _ => member.clone(),
+            })
+            .collect();
+
+        SpecializedVariableIR::Iterator {
+            iter: Some(StructVariable {
+                identity: structure.identity.clone(),
+                type_id: None,
+                type_name: Some(adapter.to_string()),
+                members,
+                type_params: structure.type_params.clone(),
+                // set to `None` because the address operator unavailable for spec vars
+                raw_address: None,
+            }),
+            original: structure,
+        }
+    }
+
+    /// Parse an iterator over a contiguous collection (`vec::IntoIter`, `slice::Iter`
+    /// or `slice::IterMut`), items between the current and the end position are remaining items.
+    ///
+    /// # Arguments
+    ///
+    /// * `eval_ctx`: evaluation context
+    /// * `structure`: iterator structure
+    /// * `kind`: iterator name (like `slice::Iter`)
+    /// * `type_params`: iterator type parameters
+    pub fn parse_contiguous_iter(
+        &self,
+        eval_ctx: &EvaluationContext,
+        structure: StructVariable,
+        kind: &str,
+        type_params: &HashMap<String, Option<TypeIdentity>>,
+    ) -> SpecializedVariableIR {
+        SpecializedVariableIR::Iterator {
+            iter: weak_error!(self
+                .parse_contiguous_iter_inner(eval_ctx, &structure, kind, type_params)
+                .context(format!("{kind}<T> interpretation"))),
+            original: structure,
+        }
+    }
+
+    fn parse_contiguous_iter_inner(
+        &self,
+        eval_ctx: &EvaluationContext,
+        structure: &StructVariable,
+        kind: &str,
+        type_params: &HashMap<String, Option<TypeIdentity>>,
+    ) -> Result<StructVariable, ParsingError> {
+        let inner_type = type_params
+            .get("T")
+            .ok_or(TypeParameterNotFound("T"))?
+            .ok_or(TypeParameterTypeNotFound("T"))?;
+        let r#type = self.parser.r#type;
+        let el_type_size = r#type
+            .type_size_in_bytes(eval_ctx, inner_type)
+            .ok_or_else(|| UnknownSize(r#type.type_name(inner_type).unwrap_or_default()))?
+            as usize;
+
+        let ptr = member_as_address(structure, "ptr")?;
+        // for zst items `end_or_len` of a slice iterator is a number of remaining items,
+        // `end` is an address of the end for a non zst items and `ptr + len` otherwise
+        let len = match member_as_address(structure, "end_or_len") {
+            Ok(len) if el_type_size == 0 => len,
+            Ok(end) => end.saturating_sub(ptr) / el_type_size,
+            Err(_) => {
+                let end = member_as_address(structure, "end")?;
+                end.saturating_sub(ptr) / el_type_size.max(1)
+            }
+        };
+        let len = guard_len(len as i64) as usize;
+
+        let items = self.parse_items(eval_ctx, inner_type, ptr, len)?;
+        let el_type_name = r#type.type_name(inner_type);
+        Ok(StructVariable {
+            identity: structure.identity.clone(),
+            type_id: None,
+            type_name: Some(format!(
+                "{kind}<{}>",
+                el_type_name.as_deref().unwrap_or("unknown")
+            )),
+            members: vec![VariableIR::Array(ArrayVariable {
+                identity: VariableIdentity::no_namespace(Some("remaining".to_owned())),
+                type_id: None,
+                type_name: el_type_name.map(|tp| format!("[{tp}]")),
+                items: Some(items),
+                // set to `None` because the address operator unavailable for spec vars
+                raw_address: None,
+            })],
+            type_params: type_params.clone(),
+            // set to `None` because the address operator unavailable for spec vars
+            raw_address: None,
+        })
+    }
+
+    /// Parse a channel sender or receiver (`std::sync::mpsc` or `crossbeam-channel`).
+    /// A channel state is read from a shared channel counter: a number of queued messages,
+    /// a channel capacity (for bounded channels), whether all senders or all receivers
+    /// are dropped and queued messages.
+    ///
+    /// # Arguments
+    ///
+    /// * `eval_ctx`: evaluation context
+    /// * `structure`: sender or receiver structure
+    /// * `kind`: channel handle name (like `mpsc::Sender`)
+    /// * `type_params`: sender or receiver type parameters
+    pub fn parse_channel(
+        &self,
+        eval_ctx: &EvaluationContext,
+        structure: StructVariable,
+        kind: &str,
+        type_params: &HashMap<String, Option<TypeIdentity>>,
+    ) -> SpecializedVariableIR {
+        SpecializedVariableIR::Channel {
+            channel: weak_error!(self
+                .parse_channel_inner(eval_ctx, &structure, kind, type_params)
+                .context(format!("{kind}<T> interpretation"))),
+            original: structure,
+        }
+    }
+
+    fn parse_channel_inner(
+        &self,
+        eval_ctx: &EvaluationContext,
+        structure: &StructVariable,
+        kind: &str,
+        type_params: &HashMap<String, Option<TypeIdentity>>,
+    ) -> Result<StructVariable, ParsingError> {
+        let inner_type = type_params
+            .get("T")
+            .ok_or(TypeParameterNotFound("T"))?
+            .ok_or(TypeParameterTypeNotFound("T"))?;
+
+        let flavor = VariableIR::Struct(structure.clone()).assume_field_as_rust_enum("flavor")?;
+        let flavor = flavor.value.ok_or(IncompleteInterp("channel flavor"))?;
+        let counter = flavor
+            .bfs_iterator()
+            .find_map(|member| match member {
+                VariableIR::Pointer(ptr) if member.name() == "counter" => Some(ptr),
+                _ => None,
+            })
+            .ok_or(FieldNotFound("counter"))?;
+        let counter = counter
+            .deref(eval_ctx, self.parser)
+            .ok_or(IncompleteInterp("channel counter"))?;
+
+        let senders = inner_number(&counter, "senders")?;
+        let receivers = inner_number(&counter, "receivers")?;
+        let chan = VariableIR::Struct(counter.assume_field_as_struct("chan")?);
+
+        let (len, cap, messages) = match flavor.name().as_str() {
+            "Array" => self.parse_array_channel(eval_ctx, &chan, inner_type)?,
+            "List" => self.parse_list_channel(eval_ctx, &chan, inner_type)?,
+            "Zero" => (0, Some(0), vec![]),
+            _ => return Err(UnexpectedType("channel flavor").into()),
+        };
+
+        let usize_member = |name: &str, value: usize| {
+            VariableIR::Scalar(ScalarVariable {
+                identity: VariableIdentity::no_namespace(Some(name.to_owned())),
+                type_id: None,
+                type_name: Some("usize".to_owned()),
+                value: Some(SupportedScalar::Usize(value)),
+                // set to `None` because the address operator unavailable for spec vars
+                raw_address: None,
+            })
+        };
+        let bool_member = |name: &str, value: bool| {
+            VariableIR::Scalar(ScalarVariable {
+                identity: VariableIdentity::no_namespace(Some(name.to_owned())),
+                type_id: None,
+                type_name: Some("bool".to_owned()),
+                value: Some(SupportedScalar::Bool(value)),
+                // set to `None` because the address operator unavailable for spec vars
+                raw_address: None,
+            })
+        };
+
+        let el_type_name = self.parser.r#type.type_name(inner_type);
+        let mut members = vec![usize_member("len", len)];
+        if let Some(cap) = cap {
+            members.push(usize_member("cap", cap));
+        }
+        members.extend([
+            bool_member("senders_disconnected", senders == 0),
+            bool_member("receivers_disconnected", receivers == 0),
+            VariableIR::Array(ArrayVariable {
+                identity: VariableIdentity::no_namespace(Some("queue".to_owned())),
+                type_id: None,
+                type_name: el_type_name.as_ref().map(|tp| format!("[{tp}]")),
+                items: Some(messages),
+                // set to `None` because the address operator unavailable for spec vars
+                raw_address: None,
+            }),
+        ]);
+
+        Ok(StructVariable {
+            identity: structure.identity.clone(),
+            type_id: None,
+            type_name: Some(format!(
+                "{kind}<{}>",
+                el_type_name.as_deref().unwrap_or("unknown")
+            )),
+            members,
+            type_params: type_params.clone(),
+            // set to `None` because the address operator unavailable for spec vars
+            raw_address: None,
+        })
+    }
+
+    /// Return a number of messages, a capacity and queued messages of a bounded channel.
+    fn parse_array_channel(
+        &self,
+        eval_ctx: &EvaluationContext,
+        chan: &VariableIR,
+        el_type: TypeIdentity,
+    ) -> Result<(usize, Option<usize>, Vec<VariableIR>), ParsingError> {
+        let head = inner_number(chan, "head")?;
+        let tail = inner_number(chan, "tail")?;
+        let cap = chan.assume_field_as_scalar_number("cap")? as usize;
+        let mark_bit = chan.assume_field_as_scalar_number("mark_bit")? as usize;
+        if cap == 0 || !mark_bit.is_power_of_two() {
+            return Err(IncompleteInterp("array channel").into());
+        }
+        let len = channel::array_len(head, tail, cap, mark_bit);
+
+        let buffer = VariableIR::Struct(chan.assume_field_as_struct("buffer")?);
+        let (slots_ptr, slot_type) = buffer
+            .bfs_iterator()
+            .find_map(|member| match member {
+                VariableIR::Pointer(ptr) if member.name() == "data_ptr" => {
+                    Some((ptr.value? as usize, ptr.target_type?))
+                }
+                _ => None,
+            })
+            .ok_or(FieldNotFound("data_ptr"))?;
+        let slot = SlotLayout::from_type(eval_ctx, self.parser.r#type, slot_type)
+            .ok_or(IncompleteInterp("channel slot"))?;
+
+        let messages = channel::array_slots(head, guard_len(len as i64) as usize, cap, mark_bit)
+            .enumerate()
+            .map(|(i, pos)| {
+                let addr = slots_ptr + pos * slot.size + slot.msg_offset;
+                self.parse_item_at(eval_ctx, el_type, addr, i)
+            })
+            .collect();
+
+        Ok((len, Some(cap), messages))
+    }
+
+    /// Return a number of messages and queued messages of an unbounded channel.
+    fn parse_list_channel(
+        &self,
+        eval_ctx: &EvaluationContext,
+        chan: &VariableIR,
+        el_type: TypeIdentity,
+    ) -> Result<(usize, Option<usize>, Vec<VariableIR>), ParsingError> {
+        let head = VariableIR::Struct(chan.assume_field_as_struct("head")?);
+        let head_index = inner_number(&head, "index")?;
+        let tail_index = inner_number(chan, "tail")?;
+        let len = channel::list_len(head_index, tail_index);
+
+        let (head_block, block_type) = head
+            .bfs_iterator()
+            .find_map(|member| match member {
+                VariableIR::Pointer(ptr) => Some((ptr.value.map(|p| p as usize), ptr.target_type?)),
+                _ => None,
+            })
+            .ok_or(FieldNotFound("block"))?;
+        let Some(mut block) = head_block.filter(|&addr| addr != 0) else {
+            // messages are discarded when all receivers are dropped
+            return Ok((len, None, vec![]));
+        };
+        let layout = BlockLayout::from_type(eval_ctx, self.parser.r#type, block_type)
+            .ok_or(IncompleteInterp("channel block"))?;
+
+        let pid = eval_ctx.expl_ctx.pid_on_focus();
+        let mut block_num = 0;
+        let mut messages = vec![];
+        for (i, (msg_block, slot)) in
+            channel::list_slots(head_index, guard_len(len as i64) as usize).enumerate()
+        {
+            if msg_block != block_num {
+                let next = debugger::read_memory_by_pid(
+                    pid,
+                    block + layout.next_offset,
+                    size_of::<usize>(),
+                )?;
+                block = usize::from_ne_bytes(
+                    next.try_into()
+                        .map_err(|_| IncompleteInterp("channel block"))?,
+                );
+                block_num = msg_block;
+                if block == 0 {
+                    break;
+                }
+            }
+            messages.push(self.parse_item_at(
+                eval_ctx,
+                el_type,
+                layout.msg_address(block, slot),
+                i,
+            ));
+        }
+
+        Ok((len, None, messages))
+    }
+
+    /// Parse an item of a collection located at `addr`.
+    ///
+    /// # Arguments
+    ///
+    /// * `eval_ctx`: evaluation context
+    /// * `el_type`: item type
+    /// * `addr`: item address
+    /// * `idx`: item index in a collection
+    fn parse_item_at(
+        &self,
+        eval_ctx: &EvaluationContext,
+        el_type: TypeIdentity,
+        addr: usize,
+        idx: usize,
+    ) -> VariableIR {
+        let r#type = self.parser.r#type;
+        let data = r#type
+            .type_size_in_bytes(eval_ctx, el_type)
+            .and_then(|size| {
+                let raw_data = weak_error!(debugger::read_memory_by_pid(
+                    eval_ctx.expl_ctx.pid_on_focus(),
+                    addr,
+                    size as usize,
+                ))?;
+                Some(ObjectBinaryRepr {
+                    raw_data: Bytes::from(raw_data),
+                    address: Some(addr),
+                    size: size as usize,
+                })
+            });
+        self.parser.parse_inner(
+            eval_ctx,
+            VariableIdentity::no_namespace(Some(format!("{}", idx as i64))),
+            data,
+            el_type,
+        )
+    }
+
     pub fn parse_uuid(&self, structure: StructVariable) -> SpecializedVariableIR {
         SpecializedVariableIR::Uuid {
             value: weak_error!(self
@@ -987,6 +1573,55 @@ impl<'a> VariableParserExtension<'a> {
     }
 }
 
+/// Decode bytes of a platform string. On unix platforms strings are arbitrary bytes
+/// (UTF-8 by convention), invalid sequences are replaced with `U+FFFD`.
+/// For NUL terminated strings bytes after the first NUL are ignored.
+fn decode_platform_string(data: &[u8], nul_terminated: bool) -> String {
+    let data = if nul_terminated {
+        data.split(|&b| b == 0).next().unwrap_or_default()
+    } else {
+        data
+    };
+    String::from_utf8_lossy(data).into_owned()
+}
+
+/// Return a number stored in a structure member, the number may be wrapped into atomics
+/// or other wrappers (like `CachePadded<AtomicUsize>`).
+fn inner_number(ir: &VariableIR, name: &'static str) -> Result<usize, AssumeError> {
+    let member = ir
+        .bfs_iterator()
+        .find(|child| child.name() == name)
+        .ok_or(FieldNotFound(name))?;
+    member
+        .bfs_iterator()
+        .find_map(|child| match child {
+            VariableIR::Scalar(scalar) => scalar.try_as_number(),
+            _ => None,
+        })
+        .map(|num| num as usize)
+        .ok_or(AssumeError::FieldNotANumber(name))
+}
+
+/// Return a value of a raw pointer or a `NonNull` structure member.
+fn member_as_address(structure: &StructVariable, name: &'static str) -> Result<usize, AssumeError> {
+    let member = structure
+        .members
+        .iter()
+        .find(|member| member.name() == name)
+        .ok_or(FieldNotFound(name))?;
+    let pointer = match member {
+        VariableIR::Pointer(pointer) => pointer.value,
+        VariableIR::Struct(non_null) => match non_null.members.first() {
+            Some(VariableIR::Pointer(pointer)) => pointer.value,
+            _ => None,
+        },
+        _ => None,
+    };
+    pointer
+        .map(|ptr| ptr as usize)
+        .ok_or(IncompleteInterp("pointer"))
+}
+
 fn extract_capacity(eval_ctx: &EvaluationContext, ir: &VariableIR) -> Result<usize, ParsingError> {
     let rust_version = eval_ctx
         .rustc_version()
@@ -10,14 +10,44 @@ use crate::debugger::unwind::FrameID;
 use crate::debugger::variable::select::{DqeResult, SelectExpressionEvaluator, DQE};
 use crate::debugger::variable::{ScalarVariable, SupportedScalar, VariableIR, VariableIdentity};
 use crate::debugger::Error::Hook;
-use crate::debugger::{Debugger, Error, ExplorationContext, Tracee};
+use crate::debugger::Pid;
+use crate::debugger::{Debugger, Error, ExplorationContext, StopKind, Tracee};
 use crate::{debugger, disable_when_not_stared, weak_error};
-use log::error;
-use nix::unistd::Pid;
+use log::{debug, error};
 use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
 use std::mem;
 use std::sync::atomic::{AtomicU32, Ordering};
 
+/// Kind of memory access performed by an instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd)]
+pub enum AccessKind {
+    Load,
+    Store,
+    /// Read-modify-write instruction (like `add %eax,(%rdx)`).
+    LoadStore,
+}
+
+impl Display for AccessKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AccessKind::Load => f.write_str("load"),
+            AccessKind::Store => f.write_str("store"),
+            AccessKind::LoadStore => f.write_str("load/store"),
+        }
+    }
+}
+
+/// Memory access that activates a watchpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd)]
+pub struct MemoryAccess {
+    /// Address of an instruction that accesses memory.
+    pub instruction: RelocatedAddress,
+    /// Access kind, `None` if instruction has no explicit memory operand
+    /// (like `push` or `movs`).
+    pub kind: Option<AccessKind>,
+}
+
 #[derive(Debug)]
 struct ExpressionTarget {
     /// Original DQE string.
@@ -43,6 +73,23 @@ impl ExpressionTarget {
         };
         underlying_dqe
     }
+
+    /// Return true if an observed memory location is reached through a pointer
+    /// (expression contains a dereference, index or slice operation),
+    /// so the location address may change during debugee execution.
+    fn indirect(&self) -> bool {
+        fn indirect(dqe: &DQE) -> bool {
+            match dqe {
+                DQE::Variable(_) | DQE::PtrCast(_, _) | DQE::Register(_) | DQE::History(_) => false,
+                DQE::Deref(_) | DQE::Index(_, _) | DQE::Slice(_, _, _) => true,
+                DQE::Field(expr, _)
+                | DQE::Address(expr)
+                | DQE::Canonic(expr)
+                | DQE::Cast(expr, _) => indirect(expr),
+            }
+        }
+        indirect(self.underlying_dqe())
+    }
 }
 
 #[derive(Debug)]
@@ -179,6 +226,13 @@ impl HardwareBreakpoint {
         Ok(state)
     }
 
+    /// Return true if memory access doesn't match a breakpoint condition. Hardware can't
+    /// break on reads only, so stores into a location observed for reads are ignored.
+    fn ignores(&self, access: Option<&MemoryAccess>) -> bool {
+        self.condition == BreakCondition::DataReads
+            && access.is_some_and(|access| access.kind == Some(AccessKind::Store))
+    }
+
     fn address_already_observed(
         tracee_ctl: &TraceeCtl,
         address: RelocatedAddress,
@@ -215,9 +269,9 @@ fn call_with_context<F, T>(debugger: &mut Debugger, ctx: ExplorationContext, f:
 where
     F: FnOnce(&Debugger) -> T,
 {
-    let old_ctx = mem::replace(&mut debugger.expl_context, ctx);
+    let old_ctx = mem::replace(&mut debugger.inferior_mut().expl_context, ctx);
     let result = f(debugger);
-    debugger.expl_context = old_ctx;
+    debugger.inferior_mut().expl_context = old_ctx;
     result
 }
 
@@ -268,7 +322,10 @@ impl Watchpoint {
         };
 
         let address = RelocatedAddress::from(ptr.value.ok_or(Error::WatchpointNoAddress)? as usize);
-        if HardwareBreakpoint::address_already_observed(debugger.debugee.tracee_ctl(), address)? {
+        if HardwareBreakpoint::address_already_observed(
+            debugger.inferior().debugee.tracee_ctl(),
+            address,
+        )? {
             return Err(Error::AddressAlreadyObserved);
         }
 
@@ -291,7 +348,7 @@ impl Watchpoint {
                 .id();
 
             let pc = expl_ctx.location().pc;
-            let dwarf = debugger.debugee.debug_info(pc)?;
+            let dwarf = debugger.inferior().debugee.debug_info(pc)?;
 
             // from all expression ranges take end-address with maximum line number -
             // this will be an address of a companion breakpoint
@@ -360,15 +417,15 @@ impl Watchpoint {
 
             let end_of_scope = best_place
                 .address
-                .relocate_to_segment(&debugger.debugee, dwarf)?;
+                .relocate_to_segment(&debugger.inferior().debugee, dwarf)?;
             let next_wp_num = GLOBAL_WP_COUNTER.load(Ordering::Relaxed);
             let brkpt = Breakpoint::new_watchpoint_companion(
-                &debugger.breakpoints,
+                &debugger.inferior().breakpoints,
                 next_wp_num,
                 end_of_scope,
                 expl_ctx.pid_on_focus(),
             );
-            let brkpt_view = debugger.breakpoints.add_and_enable(brkpt)?;
+            let brkpt_view = debugger.inferior_mut().breakpoints.add_and_enable(brkpt)?;
             end_of_scope_brkpt = Some(brkpt_view.number);
         }
 
@@ -387,7 +444,7 @@ impl Watchpoint {
         target.last_value = var;
 
         let mut hw_brkpt = HardwareBreakpoint::new(address, size, condition);
-        let state = hw_brkpt.enable(debugger.debugee.tracee_ctl())?;
+        let state = hw_brkpt.enable(debugger.inferior().debugee.tracee_ctl())?;
 
         let this = Self {
             number: GLOBAL_WP_COUNTER.fetch_add(1, Ordering::Relaxed),
@@ -411,9 +468,6 @@ impl Watchpoint {
         size: BreakSize,
         condition: BreakCondition,
     ) -> Result<(HardwareDebugState, Self), Error> {
-        debug_assert!(
-            condition == BreakCondition::DataWrites || condition == BreakCondition::DataReadsWrites
-        );
         if HardwareBreakpoint::address_already_observed(tracee_ctl, addr)? {
             return Err(Error::AddressAlreadyObserved);
         }
@@ -730,7 +784,7 @@ impl Debugger {
     ) -> Result<WatchpointView, Error> {
         disable_when_not_stared!(self);
         let (hw_state, wp) = Watchpoint::from_dqe(self, expr_source, dqe, condition)?;
-        Ok(self.watchpoints.add(hw_state, wp))
+        Ok(self.inferior_mut().watchpoints.add(hw_state, wp))
     }
 
     /// Set a new watchpoint on a memory location
@@ -748,8 +802,8 @@ impl Debugger {
     ) -> Result<WatchpointView, Error> {
         disable_when_not_stared!(self);
         let (hw_state, wp) =
-            Watchpoint::from_raw_addr(self.debugee.tracee_ctl(), addr, size, condition)?;
-        Ok(self.watchpoints.add(hw_state, wp))
+            Watchpoint::from_raw_addr(self.inferior().debugee.tracee_ctl(), addr, size, condition)?;
+        Ok(self.inferior_mut().watchpoints.add(hw_state, wp))
     }
 
     /// Remove watchpoint by its number
@@ -761,9 +815,12 @@ impl Debugger {
         &mut self,
         num: u32,
     ) -> Result<Option<WatchpointView>, Error> {
-        let breakpoints = &mut self.breakpoints;
-        self.watchpoints
-            .remove_by_num(self.debugee.tracee_ctl(), breakpoints, num)
+        let inferior = self.inferior_mut();
+        inferior.watchpoints.remove_by_num(
+            inferior.debugee.tracee_ctl(),
+            &mut inferior.breakpoints,
+            num,
+        )
     }
 
     /// Remove watchpoint by observed address in debugee memory.
@@ -775,9 +832,12 @@ impl Debugger {
         &mut self,
         addr: RelocatedAddress,
     ) -> Result<Option<WatchpointView>, Error> {
-        let breakpoints = &mut self.breakpoints;
-        self.watchpoints
-            .remove_by_addr(self.debugee.tracee_ctl(), breakpoints, addr)
+        let inferior = self.inferior_mut();
+        inferior.watchpoints.remove_by_addr(
+            inferior.debugee.tracee_ctl(),
+            &mut inferior.breakpoints,
+            addr,
+        )
     }
 
     /// Remove watchpoint by DQE, which result observed.
@@ -786,25 +846,155 @@ impl Debugger {
     ///
     /// * `dqe`: DQE
     pub fn remove_watchpoint_by_expr(&mut self, dqe: DQE) -> Result<Option<WatchpointView>, Error> {
-        let breakpoints = &mut self.breakpoints;
-        self.watchpoints
-            .remove_by_dqe(self.debugee.tracee_ctl(), breakpoints, dqe)
+        let inferior = self.inferior_mut();
+        inferior.watchpoints.remove_by_dqe(
+            inferior.debugee.tracee_ctl(),
+            &mut inferior.breakpoints,
+            dqe,
+        )
     }
 
     /// Return a list of all watchpoints.
     pub fn watchpoint_list(&self) -> Vec<WatchpointView> {
-        self.watchpoints.all().iter().map(|wp| wp.into()).collect()
+        self.inferior()
+            .watchpoints
+            .all()
+            .iter()
+            .map(|wp| wp.into())
+            .collect()
+    }
+
+    /// Evaluate an expression in a frame where watchpoint was created,
+    /// expressions with a global scope are evaluated in the current context.
+    fn evaluate_in_watch_frame(
+        &mut self,
+        expr_source: &str,
+        dqe: DQE,
+        frame_id: Option<FrameID>,
+        tid: Pid,
+    ) -> Result<DqeResult, Error> {
+        let Some(frame_id) = frame_id else {
+            return Watchpoint::evaluate_dqe(self, expr_source, dqe);
+        };
+
+        let bt = self.backtrace(tid)?;
+        let (num, frame) = bt
+            .iter()
+            .enumerate()
+            .find(|(_, frame)| frame.id() == Some(frame_id))
+            .ok_or(Error::VarFrameNotFound)?;
+        let loc = Location::new(
+            frame.ip,
+            frame.ip.into_global(&self.inferior().debugee)?,
+            tid,
+        );
+        let ctx = ExplorationContext::new(loc, num as u32);
+        call_with_context(self, ctx, |debugger| {
+            Watchpoint::evaluate_dqe(debugger, expr_source, dqe)
+        })
+    }
+
+    /// Re-resolve addresses of watchpoints that observe memory through a pointer.
+    /// If an address of the expression is changed (pointer is reassigned), hardware breakpoint
+    /// moves to a new location. If expression can't be resolved anymore (for example,
+    /// pointer is null), watchpoint is removed. Called at each debugee stop.
+    pub(super) fn rebind_watchpoints(&mut self) -> Result<(), Error> {
+        if !self.inferior().debugee.is_in_progress() {
+            return Ok(());
+        }
+
+        let indirect_watchpoints: Vec<_> = self
+            .inferior()
+            .watchpoints
+            .all()
+            .iter()
+            .filter_map(|wp| match &wp.subject {
+                Subject::Expression(target) if target.indirect() => Some((
+                    wp.number,
+                    wp.hw.address,
+                    target.source_string.clone(),
+                    target.underlying_dqe().clone(),
+                    target.frame_id,
+                    target.tid,
+                )),
+                _ => None,
+            })
+            .collect();
+
+        for (number, address, expr_source, dqe, frame_id, tid) in indirect_watchpoints {
+            let address_dqe = DQE::Address(dqe.clone().boxed());
+            let new_address =
+                match self.evaluate_in_watch_frame(&expr_source, address_dqe, frame_id, tid) {
+                    Ok(result) => {
+                        let VariableIR::Pointer(ptr) = result.variable else {
+                            unreachable!("infallible: address DQE always return a pointer")
+                        };
+                        ptr.value
+                            .filter(|&addr| !addr.is_null())
+                            .map(|addr| RelocatedAddress::from(addr as usize))
+                    }
+                    Err(Error::WatchSubjectNotFound) => None,
+                    Err(e) => {
+                        // expression frame is not reachable from current stop place,
+                        // keep watchpoint as is
+                        debug!(target: "debugger", "watchpoint {number} is not re-resolved: {e}");
+                        continue;
+                    }
+                };
+
+            match new_address {
+                None => {
+                    self.remove_watchpoint_by_number(number)?;
+                    self.hooks
+                        .on_watchpoint_rebind(number, &expr_source, None)
+                        .map_err(Hook)?;
+                }
+                Some(new_address) if new_address != address => {
+                    let new_value = self
+                        .evaluate_in_watch_frame(&expr_source, dqe, frame_id, tid)
+                        .ok()
+                        .map(|result| result.variable);
+
+                    let inferior = self.inferior_mut();
+                    let tracee_ctl = inferior.debugee.tracee_ctl();
+                    let wp = inferior
+                        .watchpoints
+                        .watchpoints
+                        .iter_mut()
+                        .find(|wp| wp.number == number)
+                        .expect("infallible");
+                    wp.hw.disable(tracee_ctl)?;
+                    wp.hw.address = new_address;
+                    let state = wp.hw.enable(tracee_ctl)?;
+                    if let Subject::Expression(ref mut target) = wp.subject {
+                        target.last_value = new_value;
+                    }
+                    self.inferior_mut().watchpoints.last_seen_state = Some(state);
+
+                    self.hooks
+                        .on_watchpoint_rebind(number, &expr_source, Some(new_address))
+                        .map_err(Hook)?;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
     }
 
+    /// Call [`crate::debugger::EventHook::on_watchpoint`] and then
+    /// [`crate::debugger::EventHook::on_stop`]. Return `false` if watchpoint hit is ignored
+    /// and no hooks are called (a store into a location observed for reads).
     pub(super) fn execute_on_watchpoint_hook(
         &mut self,
         tid: Pid,
         pc: RelocatedAddress,
         ty: &WatchpointHitType,
-    ) -> Result<(), Error> {
+    ) -> Result<bool, Error> {
         match ty {
             WatchpointHitType::DebugRegister(reg) => {
                 let maybe_wp = self
+                    .inferior()
                     .watchpoints
                     .all()
                     .iter()
@@ -812,6 +1002,12 @@ impl Debugger {
 
                 if let Some(wp) = maybe_wp {
                     let number = wp.number();
+                    let access = weak_error!(self
+                        .inferior()
+                        .debugee
+                        .access_before(pc, &self.inferior().breakpoints.active_breakpoints()))
+                    .flatten()
+                    .map(|(instruction, kind)| MemoryAccess { instruction, kind });
 
                     match &wp.subject {
                         Subject::Expression(target) => {
@@ -836,7 +1032,7 @@ impl Debugger {
 
                                     let loc = Location::new(
                                         frame.ip,
-                                        frame.ip.into_global(&self.debugee).unwrap(),
+                                        frame.ip.into_global(&self.inferior().debugee).unwrap(),
                                         current_tid,
                                     );
                                     let ctx = ExplorationContext::new(loc, num as u32);
@@ -848,7 +1044,9 @@ impl Debugger {
 
                             let new_value = new_value.ok().map(|expr| expr.variable);
 
-                            let wp_mut = self
+                            // borrow an inferior by a field, hooks are used while it is borrowed
+                            let inferior = &mut self.inferiors[self.current];
+                            let wp_mut = inferior
                                 .watchpoints
                                 .all_mut()
                                 .iter_mut()
@@ -858,10 +1056,13 @@ impl Debugger {
                                 unreachable!()
                             };
                             let old = mem::replace(&mut t.last_value, new_value);
+                            if wp_mut.hw.ignores(access.as_ref()) {
+                                return Ok(false);
+                            }
 
-                            let dwarf = self.debugee.debug_info(pc)?;
+                            let dwarf = inferior.debugee.debug_info(pc)?;
                             let place = weak_error!(
-                                dwarf.find_place_from_pc(pc.into_global(&self.debugee)?)
+                                dwarf.find_place_from_pc(pc.into_global(&inferior.debugee)?)
                             )
                             .flatten();
 
@@ -873,12 +1074,22 @@ impl Debugger {
                                     wp_mut.hw.condition,
                                     old.as_ref(),
                                     t.last_value.as_ref(),
+                                    access,
                                     false,
                                 )
                                 .map_err(Hook)?;
+                            self.execute_on_stop_hook(
+                                tid,
+                                StopKind::Watchpoint {
+                                    number,
+                                    end_of_scope: false,
+                                },
+                            );
                         }
                         Subject::Address(_) => {
-                            let wp_mut = self
+                            // borrow an inferior by a field, hooks are used while it is borrowed
+                            let inferior = &mut self.inferiors[self.current];
+                            let wp_mut = inferior
                                 .watchpoints
                                 .all_mut()
                                 .iter_mut()
@@ -888,10 +1099,13 @@ impl Debugger {
                                 unreachable!()
                             };
                             let old = t.refresh_last_value(tid, &wp_mut.hw);
+                            if wp_mut.hw.ignores(access.as_ref()) {
+                                return Ok(false);
+                            }
 
-                            let dwarf = self.debugee.debug_info(pc)?;
+                            let dwarf = inferior.debugee.debug_info(pc)?;
                             let place = weak_error!(
-                                dwarf.find_place_from_pc(pc.into_global(&self.debugee)?)
+                                dwarf.find_place_from_pc(pc.into_global(&inferior.debugee)?)
                             )
                             .flatten();
                             self.hooks
@@ -902,9 +1116,17 @@ impl Debugger {
                                     wp_mut.hw.condition,
                                     old.as_ref(),
                                     t.last_value.as_ref(),
+                                    access,
                                     false,
                                 )
                                 .map_err(Hook)?;
+                            self.execute_on_stop_hook(
+                                tid,
+                                StopKind::Watchpoint {
+                                    number,
+                                    end_of_scope: false,
+                                },
+                            );
                         }
                     }
                 }
@@ -912,13 +1134,15 @@ impl Debugger {
             WatchpointHitType::EndOfScope(wps) => {
                 let watchpoints = wps
                     .iter()
-                    .filter_map(|&num| self.watchpoints.get(num))
+                    .filter_map(|&num| self.inferior().watchpoints.get(num))
                     .collect::<Vec<_>>();
                 debug_assert_eq!(watchpoints.len(), wps.len());
 
-                let dwarf = self.debugee.debug_info(pc)?;
-                let place =
-                    weak_error!(dwarf.find_place_from_pc(pc.into_global(&self.debugee)?)).flatten();
+                let dwarf = self.inferior().debugee.debug_info(pc)?;
+                let place = weak_error!(
+                    dwarf.find_place_from_pc(pc.into_global(&self.inferior().debugee)?)
+                )
+                .flatten();
 
                 for wp in watchpoints {
                     self.hooks
@@ -929,9 +1153,17 @@ impl Debugger {
                             wp.hw.condition,
                             wp.last_value(),
                             None,
+                            None,
                             true,
                         )
                         .map_err(Hook)?;
+                    self.execute_on_stop_hook(
+                        tid,
+                        StopKind::Watchpoint {
+                            number: wp.number(),
+                            end_of_scope: true,
+                        },
+                    );
                 }
                 for number in wps {
                     self.remove_watchpoint_by_number(*number)?;
@@ -939,6 +1171,6 @@ impl Debugger {
             }
         }
 
-        Ok(())
+        Ok(true)
     }
 }
@@ -1,5 +1,48 @@
+//! BugStalker is a modern and lightweight debugger for rust applications.
+//!
+//! Besides the `bs` binary this crate can be used as a library for programmatic debugging
+//! (test harnesses, fuzzers, custom frontends). The entry point is a [`Debugger`] created
+//! by a [`DebuggerBuilder`] from an already installed debugee process. User interfaces
+//! receive debugger events through the [`EventHook`] trait.
+//!
+//! Types used in public signatures are re-exported by this crate (see [`Pid`] and [`Signal`]),
+//! errors of system and DWARF related crates are wrapped into crate types (see [`SyscallError`]
+//! and [`DwarfError`]), so an embedder doesn't need a direct dependency on these crates.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use bugstalker::debugger::process::{pipe, Child};
+//! use bugstalker::debugger::rust;
+//! use bugstalker::debugger::variable::select::{VariableSelector, DQE};
+//! use bugstalker::{DebuggerBuilder, NopHook};
+//!
+//! let (_reader, writer) = pipe().unwrap();
+//! rust::Environment::init(None);
+//! let process = Child::new("./my_program", Vec::<String>::new(), writer.try_clone().unwrap(), writer)
+//!     .install()
+//!     .unwrap();
+//!
+//! let mut debugger = DebuggerBuilder::<NopHook>::new().build(process).unwrap();
+//! debugger.set_breakpoint_at_line("main.rs", 10).unwrap();
+//! debugger.start_debugee().unwrap();
+//! let vars = debugger
+//!     .read_variable(DQE::Variable(VariableSelector::Any))
+//!     .unwrap();
+//! ```
+
 pub mod debugger;
 pub mod log;
 pub mod oracle;
+pub mod script;
 pub mod ui;
 pub mod version;
+
+pub use debugger::address::{GlobalAddress, RelocatedAddress};
+pub use debugger::variable::VariableIR;
+pub use debugger::{
+    BreakpointGroup, BreakpointView, BreakpointViewOwned, Debugger, DebuggerBuilder, DwarfError,
+    Error, EventHook, Interrupter, NopHook, ObjectError, Pid, PlaceDescriptor,
+    PlaceDescriptorOwned, Signal, StepGranularity, StopAt, StopKind, StopSummary, SyscallError,
+    WatchpointView, WatchpointViewOwned,
+};
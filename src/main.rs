@@ -8,7 +8,7 @@ use bugstalker::ui::supervisor::{DebugeeSource, Interface};
 use clap::error::ErrorKind;
 use clap::{arg, CommandFactory, Parser};
 use std::fmt::Display;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::exit;
 use std::str::FromStr;
 
@@ -31,6 +31,14 @@ pub struct Args {
     #[clap(short, long)]
     std_lib_path: Option<String>,
 
+    /// Path to rust sysroot, stdlib sources are searched in `<sysroot>/lib/rustlib/src/rust`
+    #[clap(long)]
+    sysroot: Option<String>,
+
+    /// Archive (.tar or .tar.gz) with sources of files missing on disk (maybe more than one)
+    #[clap(long)]
+    source_archive: Vec<String>,
+
     /// Discover a specific oracle (maybe more than one)
     #[clap(short, long)]
     oracle: Vec<String>,
@@ -47,8 +55,14 @@ pub struct Args {
     theme: String,
 
     /// Path to TUI keymap file [default: ~/.config/bs/keymap.toml]
+    #[cfg(feature = "tui")]
     #[clap(long, env)]
     keymap_file: Option<String>,
+
+    /// Path to TUI color palette file [default: ~/.config/bs/palette.toml]
+    #[cfg(feature = "tui")]
+    #[clap(long, env)]
+    palette_file: Option<String>,
 }
 
 fn print_fatal_and_exit(kind: ErrorKind, message: impl Display) -> ! {
@@ -75,8 +89,13 @@ impl From<&Args> for UIConfig {
         Self {
             theme: Theme::from_str(&args.theme)
                 .unwrap_or_exit(ErrorKind::InvalidValue, "Not an available theme"),
+            #[cfg(feature = "tui")]
             tui_keymap: ui::tui::config::KeyMap::from_file(args.keymap_file.as_deref())
                 .unwrap_or_default(),
+            #[cfg(feature = "tui")]
+            tui_palette: ui::tui::config::Palette::from_file(args.palette_file.as_deref())
+                .unwrap_or_default(),
+            source_archives: args.source_archive.iter().map(PathBuf::from).collect(),
         }
     }
 }
@@ -89,7 +108,11 @@ fn main() {
     let args = Args::parse();
     ui::config::set(UIConfig::from(&args));
 
-    rust::Environment::init(args.std_lib_path.map(PathBuf::from));
+    let std_lib_path = args.std_lib_path.map(PathBuf::from).or_else(|| {
+        args.sysroot
+            .map(|sysroot| rust::sysroot_std_lib_path(Path::new(&sysroot)))
+    });
+    rust::Environment::init(std_lib_path);
 
     let debugee_src = if let Some(ref debugee) = args.debugee {
         DebugeeSource::File {
@@ -103,7 +126,15 @@ fn main() {
     };
 
     let interface = if args.tui {
-        Interface::TUI
+        #[cfg(feature = "tui")]
+        {
+            Interface::TUI
+        }
+        #[cfg(not(feature = "tui"))]
+        print_fatal_and_exit(
+            ErrorKind::InvalidValue,
+            "Terminal ui is not available, bs is built without `tui` feature",
+        )
     } else {
         Interface::Default
     };
@@ -0,0 +1,664 @@
+use crate::debugger::address::RelocatedAddress;
+use crate::debugger::unwind::Backtrace;
+use crate::debugger::CreateTransparentBreakpointRequest;
+use crate::debugger::Pid;
+use crate::debugger::{Debugger, Error};
+#[cfg(feature = "console")]
+use crate::oracle::ConsolePlugin;
+use crate::oracle::Oracle;
+#[cfg(feature = "tui")]
+use crate::oracle::TuiPlugin;
+#[cfg(feature = "console")]
+use crate::ui::console::print::style::KeywordView;
+#[cfg(feature = "console")]
+use crate::ui::console::print::ExternalPrinter;
+#[cfg(feature = "console")]
+use crate::ui::short::Abbreviator;
+#[cfg(feature = "tui")]
+use crate::ui::tui::app::port::UserEvent;
+#[cfg(feature = "tui")]
+use crate::ui::tui::config::KeyMap;
+#[cfg(feature = "tui")]
+use crate::ui::tui::Msg;
+use indexmap::IndexMap;
+use log::warn;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::mem::size_of;
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "tui")]
+use tuirealm::Component;
+
+const ALLOC_SYMBOL: &str = r"(^|::)__rust_alloc$";
+const ALLOC_ZEROED_SYMBOL: &str = r"(^|::)__rust_alloc_zeroed$";
+const REALLOC_SYMBOL: &str = r"(^|::)__rust_realloc$";
+const DEALLOC_SYMBOL: &str = r"(^|::)__rust_dealloc$";
+
+/// Frames of these functions are skipped when a call site of allocation is determined.
+const INTERNAL_FN_PREFIXES: &[&str] = &[
+    "alloc::", "<alloc::", "core::", "<core::", "std::", "<std::", "__rust", "__rdl",
+];
+
+const UNKNOWN_CALL_SITE: &str = "unknown";
+
+/// Number of frames (starting from a call site) saved for each allocation.
+const BACKTRACE_DEPTH: usize = 4;
+
+/// Return up to [`BACKTRACE_DEPTH`] functions of a backtrace starting from a call site -
+/// first function that is not a part of a standard library.
+fn short_backtrace(bt: &Backtrace) -> Vec<String> {
+    let names: Vec<&str> = bt
+        .iter()
+        .filter_map(|frame| frame.func_name.as_deref())
+        .collect();
+    let call_site = names
+        .iter()
+        .position(|name| {
+            !INTERNAL_FN_PREFIXES
+                .iter()
+                .any(|prefix| name.starts_with(prefix))
+        })
+        .unwrap_or(0);
+
+    let backtrace: Vec<String> = names
+        .into_iter()
+        .skip(call_site)
+        .take(BACKTRACE_DEPTH)
+        .map(ToString::to_string)
+        .collect();
+    if backtrace.is_empty() {
+        return vec![UNKNOWN_CALL_SITE.to_string()];
+    }
+    backtrace
+}
+
+#[derive(Clone, Debug)]
+struct Allocation {
+    size: u64,
+    align: u64,
+    /// Short backtrace of an allocation, first function is a call site.
+    backtrace: Vec<String>,
+}
+
+/// Allocation that waits for an allocator function return.
+struct PendingAllocation {
+    ret_addr: RelocatedAddress,
+    /// Pointer and size of reallocated memory, `None` for new allocations.
+    realloc_from: Option<(u64, u64)>,
+    size: u64,
+    align: u64,
+}
+
+/// Allocations summary.
+///
+/// A reallocation (`__rust_realloc`) is counted as a separate event, not as
+/// a deallocation followed by an allocation, so it changes only live bytes
+/// and a number of allocated bytes (by the size of growth).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct HeapStats {
+    pub allocations: u64,
+    pub allocated_bytes: u64,
+    pub reallocations: u64,
+    pub deallocations: u64,
+    pub live_allocations: u64,
+    pub live_bytes: u64,
+    pub peak_live_bytes: u64,
+}
+
+/// Live allocations made from the same place (allocations with the same short backtrace).
+#[derive(Clone, Debug, PartialEq)]
+pub struct CallSiteLeaks {
+    /// Function that makes allocations, the first function of a backtrace.
+    pub call_site: String,
+    /// Call site followed by its callers.
+    pub backtrace: Vec<String>,
+    pub allocations: u64,
+    pub bytes: u64,
+    /// Maximum alignment of allocations.
+    pub align: u64,
+}
+
+#[derive(Default)]
+struct HeapState {
+    stats: HeapStats,
+    /// Live allocations by pointer.
+    live: HashMap<u64, Allocation>,
+    /// Allocations in progress, by thread.
+    pending: HashMap<Pid, Vec<PendingAllocation>>,
+    /// Addresses of already installed return site watch points.
+    return_sites: HashSet<RelocatedAddress>,
+}
+
+impl HeapState {
+    fn on_alloc(&mut self, ptr: u64, allocation: Allocation) {
+        self.stats.allocations += 1;
+        self.stats.allocated_bytes += allocation.size;
+        self.insert(ptr, allocation);
+    }
+
+    fn on_dealloc(&mut self, ptr: u64) {
+        self.stats.deallocations += 1;
+        self.forget(ptr);
+    }
+
+    /// Memory at `old_ptr` (of `old_size` bytes) is moved or resized into a new allocation.
+    fn on_realloc(&mut self, old_ptr: u64, old_size: u64, ptr: u64, allocation: Allocation) {
+        self.stats.reallocations += 1;
+        self.stats.allocated_bytes += allocation.size.saturating_sub(old_size);
+        self.forget(old_ptr);
+        self.insert(ptr, allocation);
+    }
+
+    /// Add a live allocation.
+    fn insert(&mut self, ptr: u64, allocation: Allocation) {
+        self.stats.live_allocations += 1;
+        self.stats.live_bytes += allocation.size;
+        self.stats.peak_live_bytes = self.stats.peak_live_bytes.max(self.stats.live_bytes);
+        if let Some(old) = self.live.insert(ptr, allocation) {
+            // allocation is lost (freed out of the oracle sight)
+            self.stats.live_allocations -= 1;
+            self.stats.live_bytes -= old.size;
+        }
+    }
+
+    /// Remove a live allocation.
+    fn forget(&mut self, ptr: u64) {
+        if let Some(allocation) = self.live.remove(&ptr) {
+            self.stats.live_allocations -= 1;
+            self.stats.live_bytes -= allocation.size;
+        }
+    }
+
+    /// Return live allocations grouped by a short backtrace, sorted by size (descending).
+    fn leaks(&self) -> Vec<CallSiteLeaks> {
+        let mut by_backtrace: IndexMap<&[String], CallSiteLeaks> = IndexMap::new();
+        for allocation in self.live.values() {
+            let entry = by_backtrace
+                .entry(allocation.backtrace.as_slice())
+                .or_insert_with(|| CallSiteLeaks {
+                    call_site: allocation.backtrace[0].clone(),
+                    backtrace: allocation.backtrace.clone(),
+                    allocations: 0,
+                    bytes: 0,
+                    align: 0,
+                });
+            entry.allocations += 1;
+            entry.bytes += allocation.size;
+            entry.align = entry.align.max(allocation.align);
+        }
+
+        let mut leaks: Vec<_> = by_backtrace.into_values().collect();
+        leaks.sort_by_key(|leak| (Reverse(leak.bytes), Reverse(leak.allocations)));
+        leaks
+    }
+}
+
+/// [`HeapOracle`] tracks heap allocations made by rust global allocator
+/// (`__rust_alloc`, `__rust_realloc` and `__rust_dealloc` functions).
+#[derive(Default)]
+pub struct HeapOracle {
+    state: Mutex<HeapState>,
+}
+
+impl HeapOracle {
+    /// Create a new oracle.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return allocations summary.
+    pub fn stats(&self) -> HeapStats {
+        self.state.lock().unwrap().stats
+    }
+
+    /// Return live allocations grouped by a short backtrace, sorted by size (descending).
+    pub fn leaks(&self) -> Vec<CallSiteLeaks> {
+        self.state.lock().unwrap().leaks()
+    }
+}
+
+impl Oracle for HeapOracle {
+    fn name(&self) -> &'static str {
+        "heap"
+    }
+
+    fn ready_for_install(&self, dbg: &Debugger) -> bool {
+        [ALLOC_SYMBOL, REALLOC_SYMBOL, DEALLOC_SYMBOL]
+            .iter()
+            .all(|symbol| !dbg.get_symbols(symbol).unwrap_or_default().is_empty())
+    }
+
+    fn watch_points(self: Arc<Self>) -> Vec<CreateTransparentBreakpointRequest> {
+        // watch points installed again after debugee restart
+        *self.state.lock().unwrap() = HeapState::default();
+
+        let oracle = self.clone();
+        let alloc_handler = move |dbg: &mut Debugger| {
+            if let Err(e) = oracle.clone().on_alloc(dbg, false) {
+                warn!(target: "heap oracle", "alloc: {e}")
+            }
+        };
+        let alloc_brkpt = CreateTransparentBreakpointRequest::symbol(ALLOC_SYMBOL, alloc_handler);
+
+        let oracle = self.clone();
+        let alloc_zeroed_handler = move |dbg: &mut Debugger| {
+            if let Err(e) = oracle.clone().on_alloc(dbg, false) {
+                warn!(target: "heap oracle", "alloc zeroed: {e}")
+            }
+        };
+        let alloc_zeroed_brkpt =
+            CreateTransparentBreakpointRequest::symbol(ALLOC_ZEROED_SYMBOL, alloc_zeroed_handler);
+
+        let oracle = self.clone();
+        let realloc_handler = move |dbg: &mut Debugger| {
+            if let Err(e) = oracle.clone().on_alloc(dbg, true) {
+                warn!(target: "heap oracle", "realloc: {e}")
+            }
+        };
+        let realloc_brkpt =
+            CreateTransparentBreakpointRequest::symbol(REALLOC_SYMBOL, realloc_handler);
+
+        let oracle = self.clone();
+        let dealloc_handler = move |dbg: &mut Debugger| {
+            if let Err(e) = oracle.on_dealloc(dbg) {
+                warn!(target: "heap oracle", "dealloc: {e}")
+            }
+        };
+        let dealloc_brkpt =
+            CreateTransparentBreakpointRequest::symbol(DEALLOC_SYMBOL, dealloc_handler);
+
+        vec![
+            alloc_brkpt,
+            alloc_zeroed_brkpt,
+            realloc_brkpt,
+            dealloc_brkpt,
+        ]
+    }
+}
+
+impl HeapOracle {
+    /// Called at the first instruction of an allocator function, so arguments are read
+    /// from registers (System V calling convention) and a return address from the stack top.
+    /// Resulting pointer and a call site are determined at a return site
+    /// (allocator shims have no debug information, so they can't be unwound).
+    fn on_alloc(self: Arc<Self>, dbg: &mut Debugger, realloc: bool) -> Result<(), Error> {
+        let pid = dbg.exploration_ctx().pid_on_focus();
+
+        // alloc(size, align), realloc(ptr, old_size, align, new_size)
+        let (realloc_from, size, align) = if realloc {
+            (
+                Some((
+                    dbg.get_register_value("rdi")?,
+                    dbg.get_register_value("rsi")?,
+                )),
+                dbg.get_register_value("rcx")?,
+                dbg.get_register_value("rdx")?,
+            )
+        } else {
+            (
+                None,
+                dbg.get_register_value("rdi")?,
+                dbg.get_register_value("rsi")?,
+            )
+        };
+
+        let rsp = dbg.get_register_value("rsp")?;
+        let ret_addr = dbg.read_memory(rsp as usize, size_of::<u64>())?;
+        let ret_addr =
+            RelocatedAddress::from(u64::from_ne_bytes(ret_addr.try_into().expect("infallible")));
+
+        let install_return_site = {
+            let mut state = self.state.lock().unwrap();
+            state
+                .pending
+                .entry(pid)
+                .or_default()
+                .push(PendingAllocation {
+                    ret_addr,
+                    realloc_from,
+                    size,
+                    align,
+                });
+            state.return_sites.insert(ret_addr)
+        };
+
+        if install_return_site {
+            let oracle = self.clone();
+            let return_handler = move |dbg: &mut Debugger| {
+                if let Err(e) = oracle.on_return(dbg) {
+                    warn!(target: "heap oracle", "allocator return: {e}")
+                }
+            };
+            dbg.set_transparent_breakpoint(CreateTransparentBreakpointRequest::address(
+                ret_addr,
+                return_handler,
+            ))?;
+        }
+
+        Ok(())
+    }
+
+    /// Called at a return site of an allocator function.
+    fn on_return(&self, dbg: &mut Debugger) -> Result<(), Error> {
+        let location = dbg.exploration_ctx().location();
+        let mut state = self.state.lock().unwrap();
+
+        let Some(pending) = state.pending.get_mut(&location.pid) else {
+            return Ok(());
+        };
+        if pending.last().map(|p| p.ret_addr) != Some(location.pc) {
+            return Ok(());
+        }
+        let pending = pending.pop().expect("infallible");
+
+        let ptr = dbg.get_register_value("rax")?;
+        if ptr == 0 {
+            // allocation failed
+            return Ok(());
+        }
+
+        let backtrace = dbg
+            .backtrace(location.pid)
+            .map(|bt| short_backtrace(&bt))
+            .unwrap_or_else(|_| vec![UNKNOWN_CALL_SITE.to_string()]);
+
+        let allocation = Allocation {
+            size: pending.size,
+            align: pending.align,
+            backtrace,
+        };
+        match pending.realloc_from {
+            Some((old_ptr, old_size)) => state.on_realloc(old_ptr, old_size, ptr, allocation),
+            None => state.on_alloc(ptr, allocation),
+        }
+
+        Ok(())
+    }
+
+    fn on_dealloc(&self, dbg: &mut Debugger) -> Result<(), Error> {
+        // dealloc(ptr, size, align)
+        let ptr = dbg.get_register_value("rdi")?;
+        self.state.lock().unwrap().on_dealloc(ptr);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "console")]
+impl ConsolePlugin for HeapOracle {
+    fn print(&self, printer: &ExternalPrinter, subcommand: Option<&str>) {
+        match subcommand {
+            None | Some("stats") => {
+                let stats = self.stats();
+                printer.println(format!(
+                    "{} allocations ({} bytes), {} reallocations, {} deallocations",
+                    KeywordView::from(stats.allocations),
+                    stats.allocated_bytes,
+                    KeywordView::from(stats.reallocations),
+                    KeywordView::from(stats.deallocations),
+                ));
+                printer.println(format!(
+                    "{} live allocations ({} bytes), peak {} bytes",
+                    KeywordView::from(stats.live_allocations),
+                    stats.live_bytes,
+                    stats.peak_live_bytes,
+                ));
+            }
+            Some("leaks") => {
+                let leaks = self.leaks();
+                printer.println(format!(
+                    "{} call sites with live allocations\n",
+                    KeywordView::from(leaks.len())
+                ));
+                if leaks.is_empty() {
+                    return;
+                }
+
+                printer.println(format!(
+                    "{bytes:<12} {count:<8} {align:<6} {call_site}",
+                    bytes = "bytes",
+                    count = "count",
+                    align = "align",
+                    call_site = "call site",
+                ));
+                let abbreviator = Abbreviator::new("::", "", 60);
+                for leak in leaks {
+                    printer.println(format!(
+                        "{bytes:<12} {count:<8} {align:<6} {call_site}",
+                        bytes = leak.bytes,
+                        count = leak.allocations,
+                        align = leak.align,
+                        call_site = abbreviator.apply(&leak.call_site),
+                    ));
+                    for caller in leak.backtrace.iter().skip(1) {
+                        printer.println(format!("{:<28} <- {}", "", abbreviator.apply(caller)));
+                    }
+                }
+            }
+            Some(subcommand) => {
+                printer.println(format!("unknown subcommand: {subcommand}, {}", self.help()));
+            }
+        }
+    }
+
+    fn help(&self) -> &str {
+        "heap - heap allocations tracking, subcommands: stats (default), leaks"
+    }
+}
+
+#[cfg(feature = "tui")]
+impl TuiPlugin for HeapOracle {
+    fn make_tui_component(
+        self: Arc<Self>,
+        _: &'static KeyMap,
+    ) -> Box<dyn Component<Msg, UserEvent>> {
+        Box::new(tui::HeapComponent::new(self))
+    }
+}
+
+#[cfg(feature = "tui")]
+pub mod tui {
+    use crate::oracle::builtin::heap::HeapOracle;
+    use crate::ui::tui::app::port::UserEvent;
+    use crate::ui::tui::Msg;
+    use std::sync::Arc;
+    use tui_realm_stdlib::Paragraph;
+    use tuirealm::props::{
+        Alignment, BorderType, Borders, Color, PropPayload, PropValue, TextSpan,
+    };
+    use tuirealm::{AttrValue, Attribute, Component, Event, MockComponent};
+
+    /// Number of call sites shown in a tui component.
+    const TOP_CALL_SITES: usize = 20;
+
+    #[derive(MockComponent)]
+    pub struct HeapComponent {
+        component: Paragraph,
+        oracle: Arc<HeapOracle>,
+    }
+
+    impl HeapComponent {
+        pub fn new(oracle: Arc<HeapOracle>) -> Self {
+            let mut this = Self {
+                component: Paragraph::default()
+                    .borders(
+                        Borders::default()
+                            .modifiers(BorderType::Rounded)
+                            .color(Color::LightYellow),
+                    )
+                    .title("Heap", Alignment::Center),
+                oracle,
+            };
+            this.refresh();
+            this
+        }
+
+        fn refresh(&mut self) {
+            let stats = self.oracle.stats();
+            let mut lines = vec![
+                TextSpan::from(format!(
+                    "allocations: {} ({} bytes), reallocations: {}, deallocations: {}",
+                    stats.allocations,
+                    stats.allocated_bytes,
+                    stats.reallocations,
+                    stats.deallocations
+                )),
+                TextSpan::from(format!(
+                    "live: {} ({} bytes), peak: {} bytes",
+                    stats.live_allocations, stats.live_bytes, stats.peak_live_bytes
+                )),
+                TextSpan::from(""),
+                TextSpan::from("live allocations by call site:").fg(Color::LightYellow),
+            ];
+            lines.extend(
+                self.oracle
+                    .leaks()
+                    .into_iter()
+                    .take(TOP_CALL_SITES)
+                    .map(|leak| {
+                        TextSpan::from(format!(
+                            "{:<12} {:<8} {}",
+                            leak.bytes,
+                            leak.allocations,
+                            leak.backtrace.join(" <- ")
+                        ))
+                    }),
+            );
+
+            self.component.attr(
+                Attribute::Text,
+                AttrValue::Payload(PropPayload::Vec(
+                    lines.into_iter().map(PropValue::TextSpan).collect(),
+                )),
+            );
+        }
+    }
+
+    impl Component<Msg, UserEvent> for HeapComponent {
+        fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+            if let Event::Tick = ev {
+                self.refresh();
+            }
+            Some(Msg::None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::debugger::unwind::FrameSpan;
+
+    fn allocation(size: u64, backtrace: &[&str]) -> Allocation {
+        Allocation {
+            size,
+            align: 8,
+            backtrace: backtrace.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    #[test]
+    fn test_heap_stats() {
+        let mut state = HeapState::default();
+        state.on_alloc(0x10, allocation(16, &["main"]));
+        state.on_alloc(0x20, allocation(32, &["main"]));
+        state.on_dealloc(0x10);
+        state.on_alloc(0x30, allocation(8, &["foo"]));
+
+        assert_eq!(
+            state.stats,
+            HeapStats {
+                allocations: 3,
+                allocated_bytes: 56,
+                reallocations: 0,
+                deallocations: 1,
+                live_allocations: 2,
+                live_bytes: 40,
+                peak_live_bytes: 48,
+            }
+        );
+    }
+
+    #[test]
+    fn test_heap_realloc_stats() {
+        let mut state = HeapState::default();
+        state.on_alloc(0x10, allocation(16, &["main"]));
+        // grow into a new place, then shrink in place
+        state.on_realloc(0x10, 16, 0x20, allocation(64, &["main"]));
+        state.on_realloc(0x20, 64, 0x20, allocation(32, &["main"]));
+
+        assert_eq!(
+            state.stats,
+            HeapStats {
+                allocations: 1,
+                allocated_bytes: 64,
+                reallocations: 2,
+                deallocations: 0,
+                live_allocations: 1,
+                live_bytes: 32,
+                peak_live_bytes: 64,
+            }
+        );
+    }
+
+    #[test]
+    fn test_heap_leaks() {
+        let mut state = HeapState::default();
+        state.on_alloc(0x10, allocation(16, &["foo", "main"]));
+        state.on_alloc(0x20, allocation(32, &["main"]));
+        state.on_alloc(0x30, allocation(24, &["foo", "main"]));
+        state.on_alloc(0x40, allocation(4, &["bar"]));
+        state.on_alloc(0x50, allocation(8, &["foo", "bar", "main"]));
+        state.on_dealloc(0x40);
+
+        let leaks: Vec<_> = state
+            .leaks()
+            .into_iter()
+            .map(|l| (l.backtrace.join(" <- "), l.allocations, l.bytes))
+            .collect();
+        assert_eq!(
+            leaks,
+            vec![
+                ("foo <- main".to_string(), 2, 40),
+                ("main".to_string(), 1, 32),
+                ("foo <- bar <- main".to_string(), 1, 8),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_short_backtrace() {
+        let frame = |name: Option<&str>| FrameSpan {
+            func_name: name.map(ToString::to_string),
+            linkage_name: None,
+            fn_start_ip: None,
+            ip: RelocatedAddress::from(0x1000_u64),
+            args: None,
+            signal_frame: false,
+        };
+
+        let bt = vec![
+            frame(Some("alloc::raw_vec::RawVec<T,A>::grow_one")),
+            frame(Some("<alloc::string::String as core::clone::Clone>::clone")),
+            frame(Some("calc::print")),
+            frame(None),
+            frame(Some("calc::main")),
+            frame(Some("core::ops::function::FnOnce::call_once")),
+            frame(Some("std::rt::lang_start::{{closure}}")),
+            frame(Some("std::rt::lang_start_internal")),
+        ];
+        assert_eq!(
+            short_backtrace(&bt),
+            vec![
+                "calc::print",
+                "calc::main",
+                "core::ops::function::FnOnce::call_once",
+                "std::rt::lang_start::{{closure}}",
+            ]
+        );
+
+        let bt = vec![frame(None), frame(Some("std::rt::lang_start_internal"))];
+        assert_eq!(short_backtrace(&bt), vec!["std::rt::lang_start_internal"]);
+
+        assert_eq!(short_backtrace(&vec![]), vec![UNKNOWN_CALL_SITE]);
+    }
+}
@@ -1,8 +1,10 @@
+use crate::oracle::builtin::heap::HeapOracle;
 use crate::oracle::builtin::nop::NopOracle;
 use crate::oracle::builtin::tokio::TokioOracle;
 use crate::oracle::Oracle;
 use std::sync::Arc;
 
+pub mod heap;
 pub mod nop;
 pub mod tokio;
 
@@ -14,6 +16,7 @@ pub mod tokio;
 pub fn make_builtin(name: &str) -> Option<Arc<dyn Oracle>> {
     match name {
         "tokio" => Some(Arc::new(TokioOracle::new())),
+        "heap" => Some(Arc::new(HeapOracle::new())),
         "nop" => Some(Arc::new(NopOracle::default())),
         _ => None,
     }
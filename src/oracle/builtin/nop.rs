@@ -1,17 +1,28 @@
 use crate::debugger::{CreateTransparentBreakpointRequest, Debugger};
+#[cfg(feature = "tui")]
 use crate::oracle::builtin::nop::tui::NopComponent;
-use crate::oracle::{ConsolePlugin, Oracle, TuiPlugin};
+#[cfg(feature = "console")]
+use crate::oracle::ConsolePlugin;
+use crate::oracle::Oracle;
+#[cfg(feature = "tui")]
+use crate::oracle::TuiPlugin;
+#[cfg(feature = "console")]
 use crate::ui::console::print::ExternalPrinter;
+#[cfg(feature = "tui")]
 use crate::ui::tui::app::port::UserEvent;
+#[cfg(feature = "tui")]
 use crate::ui::tui::config::KeyMap;
+#[cfg(feature = "tui")]
 use crate::ui::tui::Msg;
 use std::sync::Arc;
+#[cfg(feature = "tui")]
 use tuirealm::Component;
 
 /// Nop-oracle, just for test purposes.
 #[derive(Default)]
 pub struct NopOracle {}
 
+#[cfg(feature = "console")]
 impl ConsolePlugin for NopOracle {
     fn print(&self, printer: &ExternalPrinter, _: Option<&str>) {
         printer.println("nop");
@@ -22,6 +33,7 @@ impl ConsolePlugin for NopOracle {
     }
 }
 
+#[cfg(feature = "tui")]
 impl TuiPlugin for NopOracle {
     fn make_tui_component(
         self: Arc<Self>,
@@ -45,6 +57,7 @@ impl Oracle for NopOracle {
     }
 }
 
+#[cfg(feature = "tui")]
 pub mod tui {
     use crate::ui::tui::app::port::UserEvent;
     use crate::ui::tui::Msg;
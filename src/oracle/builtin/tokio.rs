@@ -2,13 +2,23 @@ use crate::debugger::unwind::{Backtrace, FrameSpan};
 use crate::debugger::variable::select::{VariableSelector, DQE};
 use crate::debugger::variable::{ScalarVariable, StructVariable, SupportedScalar, VariableIR};
 use crate::debugger::CreateTransparentBreakpointRequest;
-use crate::debugger::{Debugger, Error};
-use crate::oracle::{ConsolePlugin, Oracle, TuiPlugin};
+use crate::debugger::{Debugger, Error, ThreadSnapshot};
+#[cfg(feature = "console")]
+use crate::oracle::ConsolePlugin;
+use crate::oracle::Oracle;
+#[cfg(feature = "tui")]
+use crate::oracle::TuiPlugin;
+#[cfg(feature = "console")]
 use crate::ui::console::print::style::KeywordView;
+#[cfg(feature = "console")]
 use crate::ui::console::print::ExternalPrinter;
+#[cfg(feature = "console")]
 use crate::ui::short::Abbreviator;
+#[cfg(feature = "tui")]
 use crate::ui::tui::app::port::UserEvent;
+#[cfg(feature = "tui")]
 use crate::ui::tui::config::KeyMap;
+#[cfg(feature = "tui")]
 use crate::ui::tui::Msg;
 use chrono::Duration;
 use indexmap::IndexMap;
@@ -17,6 +27,7 @@ use std::mem::size_of;
 use std::sync::Arc;
 use std::time::Instant;
 use strum_macros::{Display, EnumString};
+#[cfg(feature = "tui")]
 use tuirealm::Component;
 
 #[derive(Debug, Display, EnumString, Clone, Copy)]
@@ -52,6 +63,48 @@ impl TaskTarget {
     }
 }
 
+#[derive(Debug, Display, EnumString, Clone, Copy, PartialEq)]
+enum ThreadKind {
+    #[strum(serialize = "tokio worker")]
+    Worker,
+    #[strum(serialize = "tokio blocking")]
+    Blocking,
+    #[strum(serialize = "user")]
+    User,
+}
+
+impl ThreadKind {
+    /// Worker threads name prefix (thread name truncated to 15 bytes by kernel).
+    const WORKER_NAME: &'static str = "tokio-runtime-w";
+
+    /// Return a kind of thread using thread backtrace and thread name.
+    /// Note that multi thread scheduler workers are launched as blocking pool tasks,
+    /// so worker frames are looked up first.
+    fn from_thread(thread: &ThreadSnapshot) -> Self {
+        let fn_names = || {
+            thread
+                .bt
+                .iter()
+                .flatten()
+                .filter_map(|frame| frame.func_name.as_deref())
+        };
+
+        if fn_names()
+            .any(|name| name.starts_with("tokio::runtime::scheduler::multi_thread::worker::"))
+        {
+            return Self::Worker;
+        }
+        if fn_names().any(|name| name.starts_with("tokio::runtime::blocking::pool::Inner::run")) {
+            return Self::Blocking;
+        }
+
+        match thread.name.as_deref() {
+            Some(name) if name.starts_with(Self::WORKER_NAME) => Self::Worker,
+            _ => Self::User,
+        }
+    }
+}
+
 #[derive(Debug, Display, EnumString, Clone, Copy)]
 enum State {
     Initial,
@@ -62,6 +115,8 @@ enum State {
     Complete,
 }
 
+// task details are shown by frontends only
+#[cfg_attr(not(feature = "console"), allow(dead_code))]
 #[derive(Clone)]
 struct Task {
     _id: u64,
@@ -125,6 +180,7 @@ impl Task {
         self.dropped_at = Some(Instant::now());
     }
 
+    #[cfg_attr(not(feature = "console"), allow(dead_code))]
     fn task_time(&self) -> Duration {
         if let Some(dropped_at) = self.dropped_at {
             Duration::from_std(dropped_at.duration_since(self.created_at)).expect("infallible")
@@ -159,28 +215,17 @@ impl Oracle for TokioOracle {
     }
 
     fn ready_for_install(&self, dbg: &Debugger) -> bool {
-        let poll_symbols = dbg
-            .get_symbols("tokio::runtime::task::raw::RawTask::poll*")
-            .unwrap_or_default();
-        if poll_symbols.is_empty() {
-            return false;
-        }
-
-        let new_symbols = dbg
-            .get_symbols("tokio::runtime::task::raw::RawTask::new*")
-            .unwrap_or_default();
-        if new_symbols.is_empty() {
-            return false;
-        }
-
-        let shutdown_symbols = dbg
-            .get_symbols("tokio::runtime::task::raw::RawTask::shutdown*")
-            .unwrap_or_default();
-        if shutdown_symbols.is_empty() {
-            return false;
-        }
+        // symbols demangled from a v0 mangling contain crate disambiguators
+        // and wrap a type of inherent method in angle brackets
+        // (like `<tokio[e844bf639985b7a9]::runtime::task::raw::RawTask>::poll`)
+        ["poll", "new", "shutdown"].iter().all(|method| {
+            let regex = format!(r"tokio(\[[0-9a-f]+\])?::runtime::task::raw::RawTask>?::{method}");
+            !dbg.get_symbols(&regex).unwrap_or_default().is_empty()
+        })
+    }
 
-        true
+    fn thread_label(&self, thread: &ThreadSnapshot) -> Option<String> {
+        Some(ThreadKind::from_thread(thread).to_string())
     }
 
     fn watch_points(self: Arc<Self>) -> Vec<CreateTransparentBreakpointRequest> {
@@ -228,6 +273,7 @@ impl Oracle for TokioOracle {
     }
 }
 
+#[cfg(feature = "console")]
 impl ConsolePlugin for TokioOracle {
     fn print(&self, printer: &ExternalPrinter, _: Option<&str>) {
         let tasks = self.tasks.lock().unwrap().clone();
@@ -449,6 +495,7 @@ impl TokioOracle {
     }
 }
 
+#[cfg(feature = "tui")]
 impl TuiPlugin for TokioOracle {
     fn make_tui_component(
         self: Arc<Self>,
@@ -458,6 +505,7 @@ impl TuiPlugin for TokioOracle {
     }
 }
 
+#[cfg(feature = "tui")]
 pub mod tui {
     use crate::oracle::builtin::tokio::{State, TokioOracle};
     use crate::ui::short::Abbreviator;
@@ -509,7 +557,7 @@ pub mod tui {
             for i in 0..SPARKLINE_LEN / SPARKLINE_HIST_EVERY_N_S {
                 let hint = format!("-{}s", (i + 1) * SPARKLINE_HIST_EVERY_N_S);
                 let empty_space = " ".repeat(SPARKLINE_HIST_EVERY_N_S - hint.len());
-                sparkline_hint = sparkline_hint + &empty_space + &hint;
+                sparkline_hint = format!("{sparkline_hint}{empty_space}{hint}");
             }
 
             let container = Container::default()
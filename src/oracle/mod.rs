@@ -2,18 +2,27 @@
 //! Oracle is an optional plugin for debugger. Oracles use `watch points` for analyse
 //! debug information and visualize it. As example - tokio oracle can can keep track of active
 //! tasks. There is builtin and external (created by user) oracles.
+//! Console and tui parts of an oracle exist only if the corresponding frontend
+//! (`console` or `tui` feature) is enabled.
 
 pub mod builtin;
 
 use crate::debugger::CreateTransparentBreakpointRequest;
 use crate::debugger::Debugger;
+use crate::debugger::ThreadSnapshot;
+#[cfg(feature = "console")]
 use crate::ui::console::print::ExternalPrinter;
+#[cfg(feature = "tui")]
 use crate::ui::tui::app::port::UserEvent;
+#[cfg(feature = "tui")]
 use crate::ui::tui::config::KeyMap;
+#[cfg(feature = "tui")]
 use crate::ui::tui::Msg;
 use std::sync::Arc;
+#[cfg(feature = "tui")]
 use tuirealm::Component;
 
+#[cfg(feature = "console")]
 pub trait ConsolePlugin {
     /// Print information into console.
     ///
@@ -27,6 +36,14 @@ pub trait ConsolePlugin {
     fn help(&self) -> &str;
 }
 
+/// Without a console frontend any type is a console plugin.
+#[cfg(not(feature = "console"))]
+pub trait ConsolePlugin {}
+
+#[cfg(not(feature = "console"))]
+impl<T: ?Sized> ConsolePlugin for T {}
+
+#[cfg(feature = "tui")]
 pub trait TuiPlugin: Send + Sync {
     /// Return tui component for visualize oracle information.
     fn make_tui_component(
@@ -35,6 +52,13 @@ pub trait TuiPlugin: Send + Sync {
     ) -> Box<dyn Component<Msg, UserEvent>>;
 }
 
+/// Without a tui frontend any thread safe type is a tui plugin.
+#[cfg(not(feature = "tui"))]
+pub trait TuiPlugin: Send + Sync {}
+
+#[cfg(not(feature = "tui"))]
+impl<T: Send + Sync + ?Sized> TuiPlugin for T {}
+
 pub trait Oracle: ConsolePlugin + TuiPlugin {
     /// Return oracle name.
     fn name(&self) -> &'static str;
@@ -51,4 +75,15 @@ pub trait Oracle: ConsolePlugin + TuiPlugin {
     /// A list of watch_point using by oracle.
     /// In debugger watch point implemented by transparent breakpoints.
     fn watch_points(self: Arc<Self>) -> Vec<CreateTransparentBreakpointRequest>;
+
+    /// Return a label of thread role (like a runtime worker thread), or `None` if oracle
+    /// knows nothing about this thread. Threads with the same label are grouped together
+    /// in thread lists and backtraces.
+    ///
+    /// # Arguments
+    ///
+    /// * `thread`: thread snapshot
+    fn thread_label(&self, _thread: &ThreadSnapshot) -> Option<String> {
+        None
+    }
 }
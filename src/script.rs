@@ -0,0 +1,374 @@
+//! Scripting engine for debugging automation.
+//! Scripts are written in [rhai](https://rhai.rs) language and have access to
+//! breakpoints, steps, memory, registers and variables of a debugee.
+//!
+//! Example of a script that collects some values at breakpoint into CSV file:
+//! ```text
+//! break_at("main.rs", 15);
+//! run();
+//! let i = 0;
+//! while is_running() && i < 1000 {
+//!     append_file("out.csv", variable("a") + "," + variable("b") + "\n");
+//!     cont();
+//!     i += 1;
+//! }
+//! ```
+//!
+//! Full variable trees (with type names and addresses) can be captured as JSON:
+//! ```text
+//! break_at("main.rs", 15);
+//! run();
+//! write_file("state.json", variable_json("*"));
+//! ```
+
+use crate::debugger::variable::json;
+use crate::debugger::variable::render::{render_variable_ir, RenderLimits, RenderRepr};
+use crate::debugger::variable::select::{VariableSelector, DQE};
+use crate::debugger::variable::VariableIR;
+use crate::debugger::{Debugger, Error, StepGranularity};
+use crate::ui::command::parser::expression;
+use chumsky::Parser;
+use rhai::{Array, Blob, Dynamic, Engine, EvalAltResult, INT};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::sync::mpsc;
+use std::thread;
+
+type ScriptResult<T> = Result<T, Box<EvalAltResult>>;
+
+/// Debugger operation requested by a script.
+type DebuggerCall = Box<dyn FnOnce(&mut Debugger) + Send>;
+
+/// Script request executed by a thread that owns a debugger reference.
+enum Request {
+    Call(DebuggerCall),
+    Print(String),
+}
+
+/// Link between engine functions and a debugger.
+///
+/// Engine functions must be `'static` so a debugger reference can't be captured by them.
+/// Instead, a script runs in a separate thread and sends debugger operations to the
+/// thread that owns a debugger reference (this is also a tracer thread, so all ptrace
+/// requests are made by it), see [`ScriptEngine::run`].
+#[derive(Clone)]
+struct DebuggerLink(mpsc::Sender<Request>);
+
+impl DebuggerLink {
+    /// Execute an operation over a debugger and wait for its result.
+    fn call<T: Send + 'static>(
+        &self,
+        f: impl FnOnce(&mut Debugger) -> Result<T, Error> + Send + 'static,
+    ) -> ScriptResult<T> {
+        let (result_tx, result_rx) = mpsc::channel();
+        let call: DebuggerCall = Box::new(move |dbg| {
+            _ = result_tx.send(f(dbg).map_err(|e| e.to_string()));
+        });
+        self.0
+            .send(Request::Call(call))
+            .map_err(|_| "debugger is not available".to_string())?;
+        let result = result_rx
+            .recv()
+            .map_err(|_| "debugger is not available".to_string())?;
+        Ok(result?)
+    }
+}
+
+fn io_err(e: std::io::Error) -> Box<EvalAltResult> {
+    e.to_string().into()
+}
+
+fn render(vars: Vec<VariableIR>) -> String {
+    vars.iter()
+        .map(|var| {
+            format!(
+                "{} = {}",
+                var.name(),
+                render_variable_ir(var, 0, &RenderLimits::default())
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn render_json(vars: Vec<VariableIR>) -> String {
+    let json = serde_json::Value::Array(vars.iter().map(json::to_json).collect());
+    serde_json::to_string_pretty(&json).expect("infallible")
+}
+
+/// Convert a script integer into an unsigned value (count, line number, address, etc.).
+fn to_unsigned<T: TryFrom<INT>>(value: INT, what: &str) -> ScriptResult<T> {
+    T::try_from(value).map_err(|_| format!("invalid {what}: {value}").into())
+}
+
+fn parse_dqe(expr: &str) -> ScriptResult<DQE> {
+    expression::parser()
+        .parse(expr)
+        .into_result()
+        .map_err(|_| format!("malformed expression: {expr}").into())
+}
+
+fn append_to_file(path: &str, data: &str, truncate: bool) -> ScriptResult<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(!truncate)
+        .truncate(truncate)
+        .open(path)
+        .map_err(io_err)?;
+    file.write_all(data.as_bytes()).map_err(io_err)
+}
+
+/// Scripting engine, executes user scripts over a debugger.
+pub struct ScriptEngine<'a> {
+    dbg: &'a mut Debugger,
+}
+
+impl<'a> ScriptEngine<'a> {
+    pub fn new(debugger: &'a mut Debugger) -> Self {
+        Self { dbg: debugger }
+    }
+
+    fn build_engine(dbg: DebuggerLink) -> Engine {
+        let mut engine = Engine::new();
+        let link = dbg.clone();
+        engine.on_print(move |msg| _ = link.0.send(Request::Print(msg.to_string())));
+
+        // ------------------------------ execution control ----------------------------------------
+        let link = dbg.clone();
+        engine.register_fn("run", move || -> ScriptResult<()> {
+            link.call(|dbg| dbg.start_debugee_force())
+        });
+        let link = dbg.clone();
+        engine.register_fn("cont", move || -> ScriptResult<()> {
+            link.call(|dbg| dbg.continue_debugee())
+        });
+        let link = dbg.clone();
+        engine.register_fn("step", move || -> ScriptResult<()> {
+            link.call(|dbg| dbg.step_into())
+        });
+        let link = dbg.clone();
+        engine.register_fn("next", move || -> ScriptResult<()> {
+            link.call(|dbg| dbg.step_over())
+        });
+        let link = dbg.clone();
+        engine.register_fn("stepi", move || -> ScriptResult<()> {
+            link.call(|dbg| dbg.stepi())
+        });
+        let link = dbg.clone();
+        engine.register_fn("finish", move || -> ScriptResult<()> {
+            link.call(|dbg| dbg.step_out().map(drop))
+        });
+        let link = dbg.clone();
+        engine.register_fn("step", move |count: INT| -> ScriptResult<()> {
+            let count = to_unsigned(count, "step count")?;
+            link.call(move |dbg| dbg.step_into_n(count, StepGranularity::Line))
+        });
+        let link = dbg.clone();
+        engine.register_fn("next", move |count: INT| -> ScriptResult<()> {
+            let count = to_unsigned(count, "step count")?;
+            link.call(move |dbg| dbg.step_over_n(count, StepGranularity::Line))
+        });
+        let link = dbg.clone();
+        engine.register_fn("stepi", move |count: INT| -> ScriptResult<()> {
+            let count = to_unsigned(count, "step count")?;
+            link.call(move |dbg| dbg.stepi_n(count))
+        });
+        let link = dbg.clone();
+        engine.register_fn("finish", move |count: INT| -> ScriptResult<()> {
+            let count = to_unsigned(count, "step count")?;
+            link.call(move |dbg| dbg.step_out_n(count).map(drop))
+        });
+        let link = dbg.clone();
+        engine.register_fn("is_running", move || -> ScriptResult<bool> {
+            link.call(|dbg| Ok(dbg.is_in_progress()))
+        });
+
+        // ------------------------------ breakpoints ----------------------------------------------
+        let link = dbg.clone();
+        engine.register_fn(
+            "break_at",
+            move |file: &str, line: INT| -> ScriptResult<Array> {
+                let line = to_unsigned(line, "line number")?;
+                let file = file.to_string();
+                let numbers = link.call(move |dbg| {
+                    let brkpts = dbg.set_breakpoint_at_line(&file, line)?;
+                    Ok(brkpts.into_iter().map(|b| b.number).collect::<Vec<_>>())
+                })?;
+                Ok(numbers
+                    .into_iter()
+                    .map(|number| Dynamic::from_int(number as INT))
+                    .collect())
+            },
+        );
+        let link = dbg.clone();
+        engine.register_fn("break_fn", move |name: &str| -> ScriptResult<Array> {
+            let name = name.to_string();
+            let numbers = link.call(move |dbg| {
+                let brkpts = dbg.set_breakpoint_at_fn(&name)?;
+                Ok(brkpts.into_iter().map(|b| b.number).collect::<Vec<_>>())
+            })?;
+            Ok(numbers
+                .into_iter()
+                .map(|number| Dynamic::from_int(number as INT))
+                .collect())
+        });
+        let link = dbg.clone();
+        engine.register_fn("break_addr", move |addr: INT| -> ScriptResult<INT> {
+            let addr: usize = to_unsigned(addr, "address")?;
+            let number = link.call(move |dbg| {
+                dbg.set_breakpoint_at_addr(addr.into())
+                    .map(|brkpt| brkpt.number)
+            })?;
+            Ok(number as INT)
+        });
+        let link = dbg.clone();
+        engine.register_fn("break_remove", move |num: INT| -> ScriptResult<()> {
+            let num = to_unsigned(num, "breakpoint number")?;
+            link.call(move |dbg| dbg.remove_breakpoint_by_number(num).map(drop))
+        });
+
+        // ------------------------------ data -----------------------------------------------------
+        let link = dbg.clone();
+        engine.register_fn("variable", move |expr: &str| -> ScriptResult<String> {
+            let dqe = parse_dqe(expr)?;
+            link.call(move |dbg| dbg.read_variable(dqe).map(render))
+        });
+        let link = dbg.clone();
+        engine.register_fn("variable_json", move |expr: &str| -> ScriptResult<String> {
+            let dqe = match expr.trim() {
+                "*" | "locals" => DQE::Variable(VariableSelector::Any),
+                _ => parse_dqe(expr)?,
+            };
+            link.call(move |dbg| dbg.read_variable(dqe).map(render_json))
+        });
+        let link = dbg.clone();
+        engine.register_fn("arg", move |expr: &str| -> ScriptResult<String> {
+            let dqe = parse_dqe(expr)?;
+            link.call(move |dbg| dbg.read_argument(dqe).map(render))
+        });
+        let link = dbg.clone();
+        engine.register_fn("read_mem", move |addr: INT, n: INT| -> ScriptResult<Blob> {
+            let addr = to_unsigned(addr, "address")?;
+            let n = to_unsigned(n, "byte count")?;
+            link.call(move |dbg| dbg.read_memory(addr, n))
+        });
+        let link = dbg.clone();
+        engine.register_fn(
+            "write_mem",
+            move |addr: INT, val: INT| -> ScriptResult<()> {
+                let addr = to_unsigned(addr, "address")?;
+                // value is written as is, negative values are allowed
+                let val = val as usize;
+                link.call(move |dbg| dbg.write_memory(addr, val))
+            },
+        );
+        let link = dbg.clone();
+        engine.register_fn("reg", move |name: &str| -> ScriptResult<INT> {
+            let name = name.to_string();
+            // register value is returned as is, values over `INT::MAX` become negative
+            link.call(move |dbg| dbg.get_register_value(&name).map(|v| v as INT))
+        });
+        engine.register_fn("set_reg", move |name: &str, val: INT| -> ScriptResult<()> {
+            let name = name.to_string();
+            dbg.call(move |dbg| dbg.set_register_value(&name, val as u64))
+        });
+
+        // ------------------------------ output ---------------------------------------------------
+        engine.register_fn("write_file", |path: &str, data: &str| {
+            append_to_file(path, data, true)
+        });
+        engine.register_fn("append_file", |path: &str, data: &str| {
+            append_to_file(path, data, false)
+        });
+
+        engine
+    }
+
+    /// Execute a script file. Script runs in a separate thread, debugger operations
+    /// and prints requested by a script are executed in the current thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `path`: path to script file
+    /// * `printer`: callback for the script `print` function
+    pub fn run(self, path: &Path, printer: impl Fn(&str)) -> anyhow::Result<()> {
+        let (request_tx, request_rx) = mpsc::channel();
+        let path = path.to_path_buf();
+        let script = thread::spawn(move || {
+            let engine = Self::build_engine(DebuggerLink(request_tx));
+            engine.run_file(path).map_err(|e| anyhow::anyhow!("{e}"))
+        });
+
+        // channel is closed when the engine (and all registered functions) is dropped
+        for request in request_rx {
+            match request {
+                Request::Call(call) => call(self.dbg),
+                Request::Print(msg) => printer(&msg),
+            }
+        }
+
+        script
+            .join()
+            .map_err(|_| anyhow::anyhow!("script thread panicked"))?
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_to_unsigned() {
+        assert_eq!(to_unsigned::<u32>(5, "count").unwrap(), 5);
+        assert!(to_unsigned::<u32>(-1, "count").is_err());
+        assert!(to_unsigned::<u32>(u32::MAX as INT + 1, "count").is_err());
+        assert_eq!(
+            to_unsigned::<u64>(INT::MAX, "line").unwrap(),
+            INT::MAX as u64
+        );
+        assert!(to_unsigned::<usize>(INT::MIN, "address").is_err());
+    }
+
+    #[test]
+    fn test_invalid_argument_not_sent_to_debugger() {
+        let (request_tx, request_rx) = mpsc::channel();
+        let engine = ScriptEngine::build_engine(DebuggerLink(request_tx));
+
+        let err = engine.run("step(-1)").unwrap_err();
+        assert!(err.to_string().contains("invalid step count: -1"));
+        let err = engine.run("break_at(\"main.rs\", -5)").unwrap_err();
+        assert!(err.to_string().contains("invalid line number: -5"));
+        assert!(request_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_function_names_are_not_reserved() {
+        let (request_tx, _request_rx) = mpsc::channel();
+        let engine = ScriptEngine::build_engine(DebuggerLink(request_tx));
+        let script = r#"
+            break_at("main.rs", 15);
+            run();
+            while is_running() {
+                append_file("out.csv", variable("a") + "," + arg("b") + "\n");
+                cont();
+            }
+        "#;
+        assert!(engine.compile(script).is_ok());
+    }
+
+    #[test]
+    fn test_print_and_closed_link() {
+        let (request_tx, request_rx) = mpsc::channel();
+        let engine = ScriptEngine::build_engine(DebuggerLink(request_tx));
+
+        engine.run("print(\"hello\")").unwrap();
+        assert!(matches!(request_rx.try_recv(), Ok(Request::Print(msg)) if msg == "hello"));
+
+        // debugger side is gone, calls are failed instead of blocking forever
+        drop(request_rx);
+        let err = engine.run("cont()").unwrap_err();
+        assert!(err.to_string().contains("debugger is not available"));
+    }
+}
@@ -3,8 +3,10 @@ use crate::ui::command;
 
 #[derive(Debug, Clone)]
 pub enum Command {
-    CurrentThread,
-    All,
+    /// Backtrace of in focus thread, if `args` is true then frame arguments are resolved.
+    CurrentThread { args: bool },
+    /// Backtraces of all threads, if `args` is true then frame arguments are resolved.
+    All { args: bool },
 }
 
 pub struct Handler<'a> {
@@ -19,14 +21,25 @@ impl<'a> Handler<'a> {
     pub fn handle(&self, cmd: Command) -> command::CommandResult<Vec<ThreadSnapshot>> {
         let mut snap = self.dbg.thread_state()?;
 
-        match cmd {
-            Command::CurrentThread => {
-                Ok(snap.into_iter().filter(|thread| thread.in_focus).collect())
-            }
-            Command::All => {
+        let (mut snap, args) = match cmd {
+            Command::CurrentThread { args } => (
+                snap.into_iter().filter(|thread| thread.in_focus).collect(),
+                args,
+            ),
+            Command::All { args } => {
                 snap.sort_unstable_by(|t1, t2| t1.thread.pid.cmp(&t2.thread.pid));
-                Ok(snap)
+                command::thread::group_by_label(&mut snap);
+                (snap, args)
+            }
+        };
+
+        if args {
+            for thread in snap.iter_mut() {
+                if let Some(bt) = thread.bt.as_mut() {
+                    self.dbg.resolve_frame_arguments(thread.thread.pid, bt);
+                }
             }
         }
+        Ok(snap)
     }
 }
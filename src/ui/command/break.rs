@@ -1,32 +1,59 @@
 use crate::debugger::address::Address;
+use crate::debugger::BreakpointGroup;
 use crate::debugger::BreakpointView;
+use crate::debugger::CatchEvent;
 use crate::debugger::Debugger;
 use crate::debugger::Error;
+use crate::debugger::ReturnCondition;
 
 #[derive(Debug, Clone)]
 pub enum BreakpointIdentity {
     Address(usize),
     Line(String, u64),
+    /// A statement at a line and a column.
+    Column(String, u64, u64),
     Function(String),
     Number(u32),
+    /// C++ exception handling event (catchpoint).
+    Catch(CatchEvent),
 }
 
 #[derive(Debug, Clone)]
 pub enum Command {
     Add(BreakpointIdentity),
+    /// Add breakpoints that use debug registers instead of planted INT3 instructions.
+    AddHardware(BreakpointIdentity),
     Remove(BreakpointIdentity),
     Info,
     AddDeferred(BreakpointIdentity),
+    Enable(u32),
+    Disable(u32),
+    /// Add breakpoints at all functions which names match a regular expression.
+    AddRegex(String),
+    /// Add breakpoints that stop a program when a function returns
+    /// (function name and condition for a returned value).
+    AddOnReturn(String, ReturnCondition),
+    EnableGroup(u32),
+    DisableGroup(u32),
+    /// Attach console commands to a breakpoint, commands are executed each time
+    /// a breakpoint is hit. Empty list detaches commands.
+    SetCommands(u32, Vec<String>),
 }
 
 impl Command {
-    /// Return underline breakpoint identity (if command not an `info`).
+    /// Return underline breakpoint identity (if command not an `info` or a group command).
     pub fn identity(&self) -> Option<BreakpointIdentity> {
         match self {
-            Command::Add(b) => Some(b.clone()),
+            Command::Add(b) | Command::AddHardware(b) => Some(b.clone()),
             Command::Remove(b) => Some(b.clone()),
             Command::Info => None,
             Command::AddDeferred(b) => Some(b.clone()),
+            Command::Enable(n) | Command::Disable(n) => Some(BreakpointIdentity::Number(*n)),
+            Command::AddRegex(_)
+            | Command::AddOnReturn(..)
+            | Command::EnableGroup(_)
+            | Command::DisableGroup(_)
+            | Command::SetCommands(..) => None,
         }
     }
 }
@@ -37,9 +64,36 @@ pub struct Handler<'a> {
 
 pub enum ExecutionResult<'a> {
     New(Vec<BreakpointView<'a>>),
+    /// New breakpoint group with a group number.
+    NewGroup(u32, Vec<BreakpointView<'a>>),
     Removed(Vec<BreakpointView<'a>>),
-    Dump(Vec<BreakpointView<'a>>),
+    Dump(Vec<BreakpointView<'a>>, Vec<BreakpointGroup>),
     AddDeferred,
+    Enabled(Vec<BreakpointView<'a>>),
+    Disabled(Vec<BreakpointView<'a>>),
+    /// Breakpoint (`None` if there is no breakpoint with such number) and commands for it.
+    Commands(Option<BreakpointView<'a>>, Vec<String>),
+}
+
+impl ExecutionResult<'_> {
+    /// Return a result that doesn't borrow a debugger.
+    pub fn into_owned(self) -> ExecutionResult<'static> {
+        let own = |brkpts: Vec<BreakpointView>| {
+            brkpts.into_iter().map(BreakpointView::into_owned).collect()
+        };
+        match self {
+            ExecutionResult::New(brkpts) => ExecutionResult::New(own(brkpts)),
+            ExecutionResult::NewGroup(num, brkpts) => ExecutionResult::NewGroup(num, own(brkpts)),
+            ExecutionResult::Removed(brkpts) => ExecutionResult::Removed(own(brkpts)),
+            ExecutionResult::Dump(brkpts, groups) => ExecutionResult::Dump(own(brkpts), groups),
+            ExecutionResult::AddDeferred => ExecutionResult::AddDeferred,
+            ExecutionResult::Enabled(brkpts) => ExecutionResult::Enabled(own(brkpts)),
+            ExecutionResult::Disabled(brkpts) => ExecutionResult::Disabled(own(brkpts)),
+            ExecutionResult::Commands(brkpt, commands) => {
+                ExecutionResult::Commands(brkpt.map(BreakpointView::into_owned), commands)
+            }
+        }
+    }
 }
 
 impl<'a> Handler<'a> {
@@ -57,15 +111,52 @@ impl<'a> Handler<'a> {
                     BreakpointIdentity::Line(file, line) => {
                         self.dbg.set_breakpoint_at_line(file, *line)?
                     }
+                    BreakpointIdentity::Column(file, line, column) => {
+                        self.dbg.set_breakpoint_at_column(file, *line, *column)?
+                    }
                     BreakpointIdentity::Function(func_name) => {
-                        self.dbg.set_breakpoint_at_fn(func_name)?
+                        let numbers: Vec<_> = self
+                            .dbg
+                            .set_breakpoint_at_fn(func_name)?
+                            .iter()
+                            .map(|brkpt| brkpt.number)
+                            .collect();
+                        // breakpoints at several functions (like monomorphizations of a generic
+                        // function) are united into a group
+                        let brkpts = self
+                            .dbg
+                            .breakpoints_snapshot()
+                            .into_iter()
+                            .filter(|brkpt| numbers.contains(&brkpt.number))
+                            .collect();
+                        return Ok(
+                            match numbers
+                                .first()
+                                .and_then(|&n| self.dbg.breakpoint_group_of(n))
+                            {
+                                Some(group) if numbers.len() > 1 => {
+                                    ExecutionResult::NewGroup(group, brkpts)
+                                }
+                                _ => ExecutionResult::New(brkpts),
+                            },
+                        );
                     }
+                    BreakpointIdentity::Catch(event) => self.dbg.set_catchpoint(*event)?,
                     BreakpointIdentity::Number(_) => {
                         unreachable!()
                     }
                 };
                 ExecutionResult::New(res)
             }
+            Command::AddHardware(brkpt) => {
+                let (ExecutionResult::New(brkpts) | ExecutionResult::NewGroup(_, brkpts)) =
+                    self.handle(&Command::Add(brkpt.clone()))?
+                else {
+                    unreachable!("add command always returns new breakpoints")
+                };
+                let numbers: Vec<_> = brkpts.iter().map(|brkpt| brkpt.number).collect();
+                ExecutionResult::New(self.dbg.make_breakpoints_hardware(&numbers)?)
+            }
             Command::Remove(brkpt) => {
                 let res = match brkpt {
                     BreakpointIdentity::Address(addr) => self
@@ -76,9 +167,13 @@ impl<'a> Handler<'a> {
                     BreakpointIdentity::Line(file, line) => {
                         self.dbg.remove_breakpoint_at_line(file, *line)?
                     }
+                    BreakpointIdentity::Column(file, line, column) => {
+                        self.dbg.remove_breakpoint_at_column(file, *line, *column)?
+                    }
                     BreakpointIdentity::Function(func_name) => {
                         self.dbg.remove_breakpoint_at_fn(func_name)?
                     }
+                    BreakpointIdentity::Catch(event) => self.dbg.remove_catchpoints(*event)?,
                     BreakpointIdentity::Number(number) => self
                         .dbg
                         .remove_breakpoint_by_number(*number)?
@@ -87,7 +182,10 @@ impl<'a> Handler<'a> {
                 };
                 ExecutionResult::Removed(res)
             }
-            Command::Info => ExecutionResult::Dump(self.dbg.breakpoints_snapshot()),
+            Command::Info => ExecutionResult::Dump(
+                self.dbg.breakpoints_snapshot(),
+                self.dbg.breakpoint_groups().to_vec(),
+            ),
             Command::AddDeferred(brkpt) => {
                 match brkpt {
                     BreakpointIdentity::Address(addr) => {
@@ -96,15 +194,52 @@ impl<'a> Handler<'a> {
                     BreakpointIdentity::Line(file, line) => {
                         self.dbg.add_deferred_at_line(file, *line)
                     }
+                    BreakpointIdentity::Column(file, line, column) => {
+                        self.dbg.add_deferred_at_column(file, *line, *column)
+                    }
                     BreakpointIdentity::Function(function) => {
                         self.dbg.add_deferred_at_function(function)
                     }
+                    BreakpointIdentity::Catch(event) => self.dbg.add_deferred_catchpoint(*event),
                     BreakpointIdentity::Number(_) => {
                         unreachable!()
                     }
                 };
                 ExecutionResult::AddDeferred
             }
+            Command::Enable(number) => ExecutionResult::Enabled(
+                self.dbg
+                    .enable_breakpoint(*number)?
+                    .map(|brkpt| vec![brkpt])
+                    .unwrap_or_default(),
+            ),
+            Command::Disable(number) => ExecutionResult::Disabled(
+                self.dbg
+                    .disable_breakpoint(*number)?
+                    .map(|brkpt| vec![brkpt])
+                    .unwrap_or_default(),
+            ),
+            Command::AddRegex(pattern) => {
+                let (group, brkpts) = self.dbg.set_breakpoints_at_fn_regex(pattern)?;
+                ExecutionResult::NewGroup(group, brkpts)
+            }
+            Command::AddOnReturn(func_name, condition) => {
+                ExecutionResult::New(self.dbg.set_breakpoint_on_return(func_name, *condition)?)
+            }
+            Command::EnableGroup(number) => {
+                ExecutionResult::Enabled(self.dbg.enable_breakpoint_group(*number)?)
+            }
+            Command::DisableGroup(number) => {
+                ExecutionResult::Disabled(self.dbg.disable_breakpoint_group(*number)?)
+            }
+            Command::SetCommands(number, commands) => {
+                let brkpt = self
+                    .dbg
+                    .breakpoints_snapshot()
+                    .into_iter()
+                    .find(|brkpt| brkpt.number == *number);
+                ExecutionResult::Commands(brkpt, commands.clone())
+            }
         };
         Ok(result)
     }
@@ -1,5 +1,9 @@
 use crate::debugger::Debugger;
 use crate::ui::command;
+use std::sync::mpsc;
+use std::sync::mpsc::RecvTimeoutError;
+use std::thread;
+use std::time::Duration;
 
 pub struct Handler<'a> {
     dbg: &'a mut Debugger,
@@ -10,8 +14,29 @@ impl<'a> Handler<'a> {
         Self { dbg: debugger }
     }
 
-    pub fn handle(&mut self) -> command::CommandResult<()> {
-        self.dbg.continue_debugee()?;
-        Ok(())
+    /// Continue debugee execution. If `timeout` is set, debugee is interrupted
+    /// after it runs for a given duration without stopping.
+    pub fn handle(&mut self, timeout: Option<Duration>) -> command::CommandResult<()> {
+        let Some(timeout) = timeout else {
+            self.dbg.continue_debugee()?;
+            return Ok(());
+        };
+
+        let interrupter = self.dbg.interrupter();
+        let (cancel_tx, cancel_rx) = mpsc::channel::<()>();
+        let timer = thread::spawn(move || {
+            // sender is dropped when debugee stops earlier, in this case timer is disarmed
+            if let Err(RecvTimeoutError::Timeout) = cancel_rx.recv_timeout(timeout) {
+                interrupter.interrupt();
+            }
+        });
+
+        let result = self.dbg.continue_debugee();
+        drop(cancel_tx);
+        _ = timer.join();
+        // debugee may stop by itself right before the timer fires,
+        // in this case an interrupt is not processed and must be dropped
+        self.dbg.interrupter().cancel();
+        Ok(result?)
     }
 }
@@ -0,0 +1,45 @@
+use crate::debugger::{Coverage, Debugger, Error};
+use std::fs;
+
+#[derive(Debug, Clone)]
+pub enum Command {
+    /// Start collecting coverage of files (file name templates).
+    Start(Vec<String>),
+    Stop,
+    /// Print lcov report or write it into a file.
+    Report(Option<String>),
+}
+
+pub enum ExecutionResult<'a> {
+    /// Number of lines with statements in covered files.
+    Started(usize),
+    Stopped(&'a Coverage),
+    Report(&'a Coverage),
+    /// Report is written into a file.
+    Exported(String),
+}
+
+pub struct Handler<'a> {
+    dbg: &'a mut Debugger,
+}
+
+impl<'a> Handler<'a> {
+    pub fn new(debugger: &'a mut Debugger) -> Self {
+        Self { dbg: debugger }
+    }
+
+    pub fn handle(self, cmd: Command) -> Result<ExecutionResult<'a>, Error> {
+        match cmd {
+            Command::Start(files) => Ok(ExecutionResult::Started(self.dbg.start_coverage(files)?)),
+            Command::Stop => {
+                self.dbg.stop_coverage()?;
+                Ok(ExecutionResult::Stopped(self.dbg.coverage()))
+            }
+            Command::Report(None) => Ok(ExecutionResult::Report(self.dbg.coverage())),
+            Command::Report(Some(path)) => {
+                fs::write(&path, self.dbg.coverage().lcov())?;
+                Ok(ExecutionResult::Exported(path))
+            }
+        }
+    }
+}
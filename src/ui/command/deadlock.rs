@@ -0,0 +1,16 @@
+use crate::debugger::{Deadlock, Debugger};
+use crate::ui::command;
+
+pub struct Handler<'a> {
+    dbg: &'a Debugger,
+}
+
+impl<'a> Handler<'a> {
+    pub fn new(debugger: &'a Debugger) -> Self {
+        Self { dbg: debugger }
+    }
+
+    pub fn handle(&self) -> command::CommandResult<Vec<Deadlock>> {
+        Ok(self.dbg.deadlock_check()?)
+    }
+}
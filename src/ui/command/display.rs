@@ -0,0 +1,106 @@
+use crate::debugger::variable::select::DQE;
+use crate::debugger::variable::VariableIR;
+use crate::debugger::Debugger;
+
+#[derive(Debug, Clone)]
+pub enum Command {
+    /// Register an expression (source string and DQE) to print after each stop.
+    Add(String, DQE),
+    Remove(u32),
+    Info,
+}
+
+/// Expression that is printed automatically after each stop of a debugee.
+#[derive(Debug, Clone)]
+pub struct Display {
+    pub number: u32,
+    pub expr: String,
+    dqe: DQE,
+}
+
+/// List of auto-displayed expressions. Registry is owned by UI, so displays survive
+/// debugee restarts.
+#[derive(Default)]
+pub struct DisplayRegistry {
+    displays: Vec<Display>,
+    last_number: u32,
+}
+
+impl DisplayRegistry {
+    pub fn add(&mut self, expr: String, dqe: DQE) -> &Display {
+        self.last_number += 1;
+        self.displays.push(Display {
+            number: self.last_number,
+            expr,
+            dqe,
+        });
+        self.displays.last().expect("infallible")
+    }
+
+    pub fn remove(&mut self, number: u32) -> Option<Display> {
+        let idx = self.displays.iter().position(|d| d.number == number)?;
+        Some(self.displays.remove(idx))
+    }
+
+    pub fn list(&self) -> &[Display] {
+        &self.displays
+    }
+
+    /// Evaluate all expressions in current debugee state. Expressions that can't be
+    /// evaluated (for example, variables out of scope) are omitted.
+    pub fn evaluate(&self, dbg: &Debugger) -> Vec<(&Display, Vec<VariableIR>)> {
+        if !dbg.is_in_progress() {
+            return vec![];
+        }
+        self.displays
+            .iter()
+            .filter_map(|display| {
+                let vars = eval(dbg, &display.dqe);
+                (!vars.is_empty()).then_some((display, vars))
+            })
+            .collect()
+    }
+}
+
+/// Evaluate an expression, variables are searched first, then function arguments.
+fn eval(dbg: &Debugger, dqe: &DQE) -> Vec<VariableIR> {
+    match dbg.read_variable(dqe.clone()) {
+        Ok(vars) if !vars.is_empty() => vars,
+        _ => dbg.read_argument(dqe.clone()).unwrap_or_default(),
+    }
+}
+
+pub enum ExecutionResult<'a> {
+    New(&'a Display, Vec<VariableIR>),
+    Removed(Option<Display>),
+    Dump(Vec<(&'a Display, Vec<VariableIR>)>),
+}
+
+pub struct Handler<'a> {
+    dbg: &'a Debugger,
+    registry: &'a mut DisplayRegistry,
+}
+
+impl<'a> Handler<'a> {
+    pub fn new(debugger: &'a Debugger, registry: &'a mut DisplayRegistry) -> Self {
+        Self {
+            dbg: debugger,
+            registry,
+        }
+    }
+
+    pub fn handle(self, cmd: Command) -> ExecutionResult<'a> {
+        match cmd {
+            Command::Add(expr, dqe) => {
+                let value = if self.dbg.is_in_progress() {
+                    eval(self.dbg, &dqe)
+                } else {
+                    vec![]
+                };
+                ExecutionResult::New(self.registry.add(expr, dqe), value)
+            }
+            Command::Remove(number) => ExecutionResult::Removed(self.registry.remove(number)),
+            Command::Info => ExecutionResult::Dump(self.registry.evaluate(self.dbg)),
+        }
+    }
+}
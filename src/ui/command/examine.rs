@@ -0,0 +1,254 @@
+use crate::debugger::address::RelocatedAddress;
+use crate::debugger::{Debugger, DecodedInstruction};
+use crate::ui::command;
+use crate::ui::command::memory;
+use crate::ui::command::CommandError;
+
+/// Maximum number of bytes read for a single string.
+const MAX_STRING_LEN: usize = 256;
+/// Maximum number of bytes (or strings and instructions) examined by a single command.
+const MAX_EXAMINE_LEN: usize = 64 * 1024;
+
+/// Output format of an examined memory unit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Hex,
+    Signed,
+    Unsigned,
+    Octal,
+    Binary,
+    Float,
+    Char,
+    /// Null-terminated string, unit size is ignored.
+    String,
+    /// Machine instruction, unit size is ignored.
+    Instruction,
+}
+
+impl Format {
+    pub fn from_char(c: char) -> Option<Self> {
+        Some(match c {
+            'x' => Format::Hex,
+            'd' => Format::Signed,
+            'u' => Format::Unsigned,
+            'o' => Format::Octal,
+            't' => Format::Binary,
+            'f' => Format::Float,
+            'c' => Format::Char,
+            's' => Format::String,
+            'i' => Format::Instruction,
+            _ => return None,
+        })
+    }
+}
+
+/// Size of an examined memory unit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UnitSize {
+    /// Byte.
+    B,
+    /// Halfword, two bytes.
+    H,
+    /// Word, four bytes.
+    W,
+    /// Giant, eight bytes.
+    G,
+}
+
+impl UnitSize {
+    pub fn from_char(c: char) -> Option<Self> {
+        Some(match c {
+            'b' => UnitSize::B,
+            'h' => UnitSize::H,
+            'w' => UnitSize::W,
+            'g' => UnitSize::G,
+            _ => return None,
+        })
+    }
+
+    pub fn bytes(&self) -> usize {
+        match self {
+            UnitSize::B => 1,
+            UnitSize::H => 2,
+            UnitSize::W => 4,
+            UnitSize::G => 8,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Command {
+    pub count: usize,
+    pub format: Format,
+    /// Unit size, if `None` then a size suitable for the format is used.
+    pub size: Option<UnitSize>,
+    pub location: memory::Location,
+}
+
+pub enum ExecutionResult {
+    /// Formatted memory units, grouped in lines, each line starts with an address of
+    /// the first unit.
+    Units(Vec<(usize, Vec<String>)>),
+    /// Strings with their addresses.
+    Strings(Vec<(usize, String)>),
+    /// Decoded instructions and a program counter of the thread in focus.
+    Instructions(Vec<DecodedInstruction>, RelocatedAddress),
+}
+
+/// Render a memory unit (in little-endian byte order).
+fn render_unit(bytes: &[u8], format: Format) -> String {
+    let mut raw = [0u8; 8];
+    raw[..bytes.len()].copy_from_slice(bytes);
+    let unsigned = u64::from_le_bytes(raw);
+    let bits = bytes.len() * 8;
+    // sign extension of a unit less than 8 bytes
+    let signed = ((unsigned << (64 - bits)) as i64) >> (64 - bits);
+
+    match format {
+        Format::Hex => format!("{unsigned:#0width$x}", width = bytes.len() * 2 + 2),
+        Format::Signed => signed.to_string(),
+        Format::Unsigned => unsigned.to_string(),
+        Format::Octal => format!("{unsigned:#o}"),
+        Format::Binary => format!("{unsigned:0bits$b}"),
+        Format::Float if bytes.len() == 4 => format!("{:?}", f32::from_bits(unsigned as u32)),
+        Format::Float => format!("{:?}", f64::from_bits(unsigned)),
+        Format::Char => {
+            let byte = unsigned as u8;
+            if byte.is_ascii() {
+                format!("{signed} {:?}", byte as char)
+            } else {
+                format!("{signed} '\\x{byte:02x}'")
+            }
+        }
+        Format::String | Format::Instruction => unreachable!("not a memory unit format"),
+    }
+}
+
+/// Return unit size used by a format if it isn't set explicitly.
+fn default_size(format: Format) -> UnitSize {
+    match format {
+        Format::Char => UnitSize::B,
+        Format::Float => UnitSize::G,
+        _ => UnitSize::W,
+    }
+}
+
+/// Number of units printed in a single line.
+fn units_per_line(size: UnitSize, format: Format) -> usize {
+    match (format, size) {
+        (_, UnitSize::G) => 2,
+        (_, UnitSize::W) => 4,
+        _ => 8,
+    }
+}
+
+pub struct Handler<'a> {
+    dbg: &'a Debugger,
+}
+
+impl<'a> Handler<'a> {
+    pub fn new(debugger: &'a Debugger) -> Self {
+        Self { dbg: debugger }
+    }
+
+    /// Return a rendered null-terminated string and its length (including a null byte).
+    fn read_string(&self, addr: usize) -> command::CommandResult<(String, usize)> {
+        let mut bytes = vec![];
+        while bytes.len() < MAX_STRING_LEN {
+            let chunk = self.dbg.read_memory(addr + bytes.len(), 8)?;
+            if let Some(nul) = chunk.iter().position(|&b| b == 0) {
+                bytes.extend_from_slice(&chunk[..nul]);
+                let len = bytes.len() + 1;
+                return Ok((format!("{:?}", String::from_utf8_lossy(&bytes)), len));
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        bytes.truncate(MAX_STRING_LEN);
+        let string = format!("{:?}...", String::from_utf8_lossy(&bytes));
+        Ok((string, MAX_STRING_LEN))
+    }
+
+    pub fn handle(&self, cmd: &Command) -> command::CommandResult<ExecutionResult> {
+        let count = cmd.count;
+        if count == 0 {
+            return Err(CommandError::ExamineZeroCount);
+        }
+        if count > MAX_EXAMINE_LEN {
+            return Err(CommandError::ExamineTooLarge(MAX_EXAMINE_LEN));
+        }
+        let addr = memory::Handler::new(self.dbg).resolve(&cmd.location)?;
+
+        match cmd.format {
+            Format::String => {
+                let mut strings = Vec::with_capacity(count);
+                let mut addr = addr;
+                for _ in 0..count {
+                    let (string, len) = self.read_string(addr)?;
+                    strings.push((addr, string));
+                    addr += len;
+                }
+                Ok(ExecutionResult::Strings(strings))
+            }
+            Format::Instruction => {
+                let instructions = self
+                    .dbg
+                    .decode_instructions(RelocatedAddress::from(addr), count)?;
+                let pc = self.dbg.exploration_ctx().location().pc;
+                Ok(ExecutionResult::Instructions(instructions, pc))
+            }
+            format => {
+                let size = cmd.size.unwrap_or(default_size(format));
+                let size = match (format, size) {
+                    (Format::Float, UnitSize::B | UnitSize::H) => UnitSize::G,
+                    _ => size,
+                };
+                let len = count
+                    .checked_mul(size.bytes())
+                    .filter(|&len| len <= MAX_EXAMINE_LEN)
+                    .ok_or(CommandError::ExamineTooLarge(MAX_EXAMINE_LEN))?;
+                let memory = self.dbg.read_memory(addr, len)?;
+                let per_line = units_per_line(size, format);
+                let lines = memory
+                    .chunks(per_line * size.bytes())
+                    .enumerate()
+                    .map(|(i, line)| {
+                        let units = line
+                            .chunks(size.bytes())
+                            .map(|unit| render_unit(unit, format))
+                            .collect();
+                        (addr + i * per_line * size.bytes(), units)
+                    })
+                    .collect();
+                Ok(ExecutionResult::Units(lines))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_render_unit() {
+        assert_eq!(render_unit(&[0xff], Format::Hex), "0xff");
+        assert_eq!(render_unit(&[0x01, 0], Format::Hex), "0x0001");
+        assert_eq!(render_unit(&[0xff], Format::Signed), "-1");
+        assert_eq!(render_unit(&[0xff], Format::Unsigned), "255");
+        assert_eq!(render_unit(&[0xfe, 0xff, 0xff, 0xff], Format::Signed), "-2");
+        assert_eq!(render_unit(&[8, 0], Format::Octal), "0o10");
+        assert_eq!(render_unit(&[5], Format::Binary), "00000101");
+        assert_eq!(render_unit(&1.5f32.to_le_bytes(), Format::Float), "1.5");
+        assert_eq!(
+            render_unit(&(-0.25f64).to_le_bytes(), Format::Float),
+            "-0.25"
+        );
+        assert_eq!(
+            render_unit(&1e-300f64.to_le_bytes(), Format::Float),
+            "1e-300"
+        );
+        assert_eq!(render_unit(b"A", Format::Char), "65 'A'");
+        assert_eq!(render_unit(&[0xe8], Format::Char), "-24 '\\xe8'");
+        assert_eq!(render_unit(&u64::MAX.to_le_bytes(), Format::Signed), "-1");
+    }
+}
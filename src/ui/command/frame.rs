@@ -1,15 +1,17 @@
-use crate::debugger::{Debugger, FrameInfo};
+use crate::debugger::{Debugger, FrameInfo, FrameMemory};
 use crate::ui::command;
 
 #[derive(Debug, Clone)]
 pub enum Command {
     Info,
     Switch(u32),
+    Memory,
 }
 
 pub enum ExecutionResult {
     FrameInfo(FrameInfo),
     BroughtIntoFocus(u32),
+    Memory(FrameMemory),
 }
 
 pub struct Handler<'a> {
@@ -31,6 +33,10 @@ impl<'a> Handler<'a> {
                 self.dbg.set_frame_into_focus(num)?;
                 Ok(ExecutionResult::BroughtIntoFocus(num))
             }
+            Command::Memory => {
+                let memory = self.dbg.frame_memory()?;
+                Ok(ExecutionResult::Memory(memory))
+            }
         }
     }
 }
@@ -0,0 +1,36 @@
+use crate::debugger::{Debugger, Error};
+
+#[derive(Debug, Clone)]
+pub enum Command {
+    /// Start tracing of functions matching a glob pattern.
+    Start(String),
+    Stop,
+}
+
+pub enum ExecutionResult {
+    /// Number of traced functions.
+    Started(usize),
+    Stopped,
+}
+
+pub struct Handler<'a> {
+    dbg: &'a mut Debugger,
+}
+
+impl<'a> Handler<'a> {
+    pub fn new(debugger: &'a mut Debugger) -> Self {
+        Self { dbg: debugger }
+    }
+
+    pub fn handle(self, cmd: Command) -> Result<ExecutionResult, Error> {
+        match cmd {
+            Command::Start(pattern) => {
+                Ok(ExecutionResult::Started(self.dbg.start_ftrace(&pattern)?))
+            }
+            Command::Stop => {
+                self.dbg.stop_ftrace()?;
+                Ok(ExecutionResult::Stopped)
+            }
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use crate::debugger::{Debugger, InferiorInfo, Pid};
+use crate::ui::command;
+
+#[derive(Debug, Clone)]
+pub enum Command {
+    List,
+    Switch(u32),
+    Attach(i32),
+}
+
+pub struct Handler<'a> {
+    dbg: &'a mut Debugger,
+}
+
+pub enum ExecutionResult {
+    List(Vec<InferiorInfo>),
+    Switched(InferiorInfo),
+}
+
+impl<'a> Handler<'a> {
+    pub fn new(debugger: &'a mut Debugger) -> Self {
+        Self { dbg: debugger }
+    }
+
+    pub fn handle(&mut self, cmd: Command) -> command::CommandResult<ExecutionResult> {
+        match cmd {
+            Command::List => Ok(ExecutionResult::List(self.dbg.inferiors())),
+            Command::Switch(num) => {
+                let inferior = self.dbg.switch_inferior(num)?;
+                Ok(ExecutionResult::Switched(inferior))
+            }
+            Command::Attach(pid) => {
+                let inferior = self.dbg.attach_inferior(Pid::from_raw(pid))?;
+                Ok(ExecutionResult::Switched(inferior))
+            }
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use crate::debugger::{Debugger, LineInfo, LineInfoLocation};
+use crate::ui::command;
+
+#[derive(Debug, Clone)]
+pub enum Command {
+    /// Print statements at a location with their address ranges and line table rows.
+    Line(LineInfoLocation),
+}
+
+pub struct Handler<'a> {
+    dbg: &'a Debugger,
+}
+
+impl<'a> Handler<'a> {
+    pub fn new(debugger: &'a Debugger) -> Self {
+        Self { dbg: debugger }
+    }
+
+    pub fn handle(&self, cmd: &Command) -> command::CommandResult<Vec<LineInfo>> {
+        match cmd {
+            Command::Line(location) => Ok(self.dbg.line_info(location)?),
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use crate::debugger::Debugger;
+use crate::ui::command;
+
+/// Jump target: a line in the file (current file if `None`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Location {
+    pub file: Option<String>,
+    pub line: u64,
+}
+
+// Move program counter to another line of the current function
+pub struct Handler<'a> {
+    dbg: &'a mut Debugger,
+}
+
+impl<'a> Handler<'a> {
+    pub fn new(debugger: &'a mut Debugger) -> Self {
+        Self { dbg: debugger }
+    }
+
+    pub fn handle(&mut self, location: &Location, force: bool) -> command::CommandResult<()> {
+        Ok(self
+            .dbg
+            .jump(location.file.as_deref(), location.line, force)?)
+    }
+}
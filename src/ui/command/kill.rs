@@ -0,0 +1,17 @@
+use crate::debugger::Debugger;
+use crate::ui::command;
+
+/// Kill a running debugee together with all processes spawned by it.
+pub struct Handler<'a> {
+    dbg: &'a mut Debugger,
+}
+
+impl<'a> Handler<'a> {
+    pub fn new(debugger: &'a mut Debugger) -> Self {
+        Self { dbg: debugger }
+    }
+
+    pub fn handle(&mut self) -> command::CommandResult<()> {
+        Ok(self.dbg.kill_debugee()?)
+    }
+}
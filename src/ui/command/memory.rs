@@ -1,13 +1,40 @@
+use crate::debugger::variable::select::DQE;
+use crate::debugger::variable::VariableIR;
 use crate::debugger::Debugger;
 use crate::debugger::Error;
 use crate::ui::command;
 use nix::libc::uintptr_t;
-use std::mem;
+use std::{fs, mem};
 
 #[derive(Debug, Clone)]
 pub enum Command {
-    Read(usize),
-    Write(usize, uintptr_t),
+    Read(Location),
+    Write(Location, uintptr_t),
+    /// Save a memory region (start and length) into a file.
+    Save(Location, usize, String),
+    /// Load file content into memory starting from a location.
+    Load(String, Location),
+}
+
+pub enum ExecutionResult {
+    /// Value that is read or written.
+    Value(uintptr_t),
+    /// Number of bytes saved into a file.
+    Saved(usize),
+    /// Number of bytes loaded from a file.
+    Loaded(usize),
+}
+
+/// Start of a memory region.
+#[derive(Debug, Clone)]
+pub enum Location {
+    /// Raw address.
+    Address(usize),
+    /// Address given by an expression: a pointer value, an integer value,
+    /// or a location of any other value in debugee memory.
+    Expression(DQE),
+    /// Address stored in a register (of the thread and frame in focus).
+    Register(String),
 }
 
 pub struct Handler<'a> {
@@ -19,20 +46,82 @@ impl<'a> Handler<'a> {
         Self { dbg: debugger }
     }
 
-    pub fn handle(&self, cmd: Command) -> command::CommandResult<uintptr_t> {
+    pub fn handle(&self, cmd: Command) -> command::CommandResult<ExecutionResult> {
         let result = match &cmd {
-            Command::Read(addr) => {
-                let bytes = self.dbg.read_memory(*addr, mem::size_of::<usize>())?;
-                uintptr_t::from_ne_bytes(bytes.try_into().map_err(|data: Vec<u8>| {
-                    Error::TypeBinaryRepr("uintptr_t", data.into_boxed_slice())
-                })?)
+            Command::Read(location) => {
+                let addr = self.resolve(location)?;
+                let bytes = self.dbg.read_memory(addr, mem::size_of::<usize>())?;
+                ExecutionResult::Value(uintptr_t::from_ne_bytes(bytes.try_into().map_err(
+                    |data: Vec<u8>| Error::TypeBinaryRepr("uintptr_t", data.into_boxed_slice()),
+                )?))
+            }
+            Command::Write(location, ptr) => {
+                let addr = self.resolve(location)?;
+                self.dbg.write_memory(addr, *ptr)?;
+                ExecutionResult::Value(*ptr)
             }
-            Command::Write(addr, ptr) => {
-                self.dbg.write_memory(*addr, *ptr)?;
-                *ptr
+            Command::Save(location, len, path) => {
+                let addr = self.resolve(location)?;
+                let bytes = self.dbg.read_memory(addr, *len)?;
+                fs::write(path, &bytes).map_err(Error::from)?;
+                ExecutionResult::Saved(bytes.len())
+            }
+            Command::Load(path, location) => {
+                let addr = self.resolve(location)?;
+                let bytes = fs::read(path).map_err(Error::from)?;
+                self.dbg.write_memory_bytes(addr, &bytes)?;
+                ExecutionResult::Loaded(bytes.len())
             }
         };
 
         Ok(result)
     }
+
+    /// Return address of a memory location. Expression is searched in variables first,
+    /// then in function arguments. Like in the gdb `x` command, a pointer or an integer
+    /// is an address itself, a value of any other type is examined at its location.
+    pub fn resolve(&self, location: &Location) -> command::CommandResult<usize> {
+        let dqe = match location {
+            Location::Address(addr) => return Ok(*addr),
+            Location::Register(register) => {
+                return Ok(self.dbg.get_frame_register_value(register)? as usize)
+            }
+            Location::Expression(dqe) => dqe,
+        };
+
+        let mut vars = self.dbg.read_variable(dqe.clone())?;
+        if vars.is_empty() {
+            vars = self.dbg.read_argument(dqe.clone())?;
+        }
+        let var = vars
+            .into_iter()
+            .next()
+            .ok_or(Error::MemorySubjectNotFound)?;
+        let addr = match &var {
+            VariableIR::Pointer(pointer) => pointer.value.map(|ptr| ptr as usize),
+            VariableIR::Scalar(scalar) => scalar
+                .try_as_number()
+                .map(|num| num as usize)
+                .or(scalar.raw_address),
+            _ => var.in_memory_location(),
+        };
+        Ok(addr.ok_or(Error::MemorySubjectNoAddress)?)
+    }
+
+    /// Write a single byte in debugee memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr`: address to write
+    /// * `byte`: value to write
+    pub fn write_byte(&self, addr: usize, byte: u8) -> command::CommandResult<()> {
+        let bytes = self.dbg.read_memory(addr, mem::size_of::<usize>())?;
+        let mut word: [u8; mem::size_of::<usize>()] = bytes
+            .try_into()
+            .map_err(|data: Vec<u8>| Error::TypeBinaryRepr("uintptr_t", data.into_boxed_slice()))?;
+        word[0] = byte;
+        Ok(self
+            .dbg
+            .write_memory(addr, uintptr_t::from_ne_bytes(word))?)
+    }
 }
@@ -9,24 +9,47 @@ pub mod arguments;
 pub mod backtrace;
 pub mod r#break;
 pub mod r#continue;
+pub mod coverage;
+pub mod deadlock;
+pub mod display;
+pub mod examine;
 pub mod frame;
+pub mod ftrace;
+pub mod inferior;
+pub mod info;
+pub mod jump;
+pub mod kill;
 pub mod memory;
 pub mod parser;
+pub mod profile;
+pub mod ptype;
+pub mod quit;
 pub mod register;
+pub mod resolve;
+pub mod r#return;
 pub mod run;
+pub mod script;
+pub mod session;
+pub mod set;
 pub mod sharedlib;
+pub mod snapshot;
 pub mod source_code;
 pub mod step_instruction;
 pub mod step_into;
 pub mod step_out;
 pub mod step_over;
 pub mod symbol;
+pub mod symbolize;
 pub mod thread;
+pub mod trace;
+pub mod unwind;
 pub mod variables;
 pub mod watch;
 
 use crate::debugger::variable::select::DQE;
-use crate::debugger::Error;
+use crate::debugger::{Error, ReturnValue, StepGranularity};
+use std::path::PathBuf;
+use std::time::Duration;
 
 #[derive(thiserror::Error, Debug)]
 pub enum CommandError {
@@ -34,6 +57,18 @@ pub enum CommandError {
     Parsing(String),
     #[error("render error: \n{0}")]
     FileRender(anyhow::Error),
+    #[error("script error: {0:#}")]
+    Script(anyhow::Error),
+    #[error("session error: {0:#}")]
+    Session(anyhow::Error),
+    #[error("backtrace import error: {0:#}")]
+    BacktraceImport(anyhow::Error),
+    #[error("no snapshot of `{0}`, use `snapshot {0}` first")]
+    SnapshotNotFound(String),
+    #[error("too much memory to examine, at most {0} bytes (or units) allowed")]
+    ExamineTooLarge(usize),
+    #[error("nothing to examine, count of units must be at least 1")]
+    ExamineZeroCount,
     #[error(transparent)]
     Handle(#[from] Error),
 }
@@ -44,23 +79,63 @@ pub type CommandResult<T> = Result<T, CommandError>;
 #[derive(Debug, Clone)]
 pub enum Command {
     PrintVariables(DQE),
+    /// Print variables as a JSON array (a full variable tree including type names
+    /// and addresses), write it into a file if a path is set.
+    PrintVariablesJson(DQE, Option<PathBuf>),
     PrintArguments(DQE),
     PrintBacktrace(backtrace::Command),
-    Continue,
+    Continue(Option<Duration>),
     Frame(frame::Command),
-    Run,
-    StepInstruction,
-    StepInto,
-    StepOut,
-    StepOver,
+    /// Run (or restart) debugee, optionally stop at a program entry point or at `main`
+    /// and redirect debugee stdin.
+    Run(run::Options),
+    /// Kill debugee and all processes spawned by it.
+    Kill,
+    /// Quit the debugger, kill or detach a running debugee
+    /// (`None` to use an action from settings).
+    Quit(Option<quit::Action>),
+    StepInstruction(u32),
+    StepInto(u32, StepGranularity),
+    StepOut(u32),
+    StepOver(u32, StepGranularity),
+    Return(Option<ReturnValue>),
+    /// Continue until a thrown C++ exception is caught, stop in the handler.
+    UnwindException,
+    Jump(jump::Location),
     PrintSymbol(String),
+    /// Resolve addresses into source places.
+    Resolve(Vec<usize>),
+    /// Resolve addresses from a backtrace stored in a file.
+    Symbolize(String),
+    PrintType(String),
     Breakpoint(r#break::Command),
     Watchpoint(watch::Command),
     Memory(memory::Command),
+    Examine(examine::Command),
     Register(register::Command),
     Thread(thread::Command),
+    Inferior(inferior::Command),
+    /// Inspect line table of a source location.
+    Info(info::Command),
     SharedLib,
+    /// Find threads that wait for each other on mutexes.
+    DeadlockCheck,
+    ValueHistory,
+    /// Print stops (breakpoint hits, steps, signals, etc.) of the thread in focus
+    /// or of all threads.
+    StepHistory {
+        all_threads: bool,
+    },
     SourceCode(source_code::Command),
+    Script(String),
+    Session(session::Command),
+    Display(display::Command),
+    Snapshot(snapshot::Command),
+    Set(set::Command),
+    Trace(trace::Command),
+    Ftrace(ftrace::Command),
+    Coverage(coverage::Command),
+    Profile(profile::Command),
     SkipInput,
     Oracle(String, Option<String>),
     Help {
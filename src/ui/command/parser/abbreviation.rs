@@ -0,0 +1,427 @@
+//! Resolution of abbreviated commands, any unambiguous prefix of a command
+//! (or a subcommand) name is expanded into the full name before parsing,
+//! so `cont` means `continue` and `reg rea rax` means `register read rax`.
+use crate::ui::command::parser::*;
+use itertools::Itertools;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::OnceLock;
+use trie_rs::{Trie, TrieBuilder};
+
+/// A command (or a subcommand) description.
+struct Spec {
+    name: &'static str,
+    aliases: &'static [&'static str],
+    /// Subcommands are resolved only for commands whose first argument is always a subcommand,
+    /// arguments of other commands (like a function name in `break`) are never expanded.
+    subcommands: &'static [Spec],
+}
+
+const fn cmd(name: &'static str) -> Spec {
+    Spec {
+        name,
+        aliases: &[],
+        subcommands: &[],
+    }
+}
+
+const fn cmd_a(name: &'static str, aliases: &'static [&'static str]) -> Spec {
+    Spec {
+        name,
+        aliases,
+        subcommands: &[],
+    }
+}
+
+const fn cmd_s(
+    name: &'static str,
+    aliases: &'static [&'static str],
+    subcommands: &'static [Spec],
+) -> Spec {
+    Spec {
+        name,
+        aliases,
+        subcommands,
+    }
+}
+
+const COMMANDS: &[Spec] = &[
+    cmd(VAR_COMMAND),
+    cmd(ARG_COMMAND),
+    cmd_s(
+        BACKTRACE_COMMAND,
+        &[BACKTRACE_COMMAND_SHORT],
+        &[cmd(BACKTRACE_ALL_SUBCOMMAND)],
+    ),
+    cmd_a(CONTINUE_COMMAND, &[CONTINUE_COMMAND_SHORT]),
+    cmd_s(
+        FRAME_COMMAND,
+        &[FRAME_COMMAND_SHORT],
+        &[
+            cmd(FRAME_COMMAND_INFO_SUBCOMMAND),
+            cmd(FRAME_COMMAND_SWITCH_SUBCOMMAND),
+            cmd(FRAME_COMMAND_MEMORY_SUBCOMMAND),
+        ],
+    ),
+    cmd_a(RUN_COMMAND, &[RUN_COMMAND_SHORT]),
+    cmd(KILL_COMMAND),
+    cmd_a(QUIT_COMMAND, &[QUIT_COMMAND_SHORT]),
+    cmd(STEP_INSTRUCTION_COMMAND),
+    cmd_a(STEP_INTO_COMMAND, &[STEP_INTO_COMMAND_SHORT]),
+    cmd_a(STEP_OUT_COMMAND, &[STEP_OUT_COMMAND_SHORT]),
+    cmd_a(STEP_OVER_COMMAND, &[STEP_OVER_COMMAND_SHORT]),
+    cmd(RETURN_COMMAND),
+    cmd(JUMP_COMMAND),
+    cmd(SYMBOL_COMMAND),
+    cmd(PTYPE_COMMAND),
+    cmd(RESOLVE_COMMAND),
+    cmd(SYMBOLIZE_COMMAND),
+    cmd_a(BREAK_COMMAND, &[BREAK_COMMAND_SHORT]),
+    cmd_s(
+        CATCH_COMMAND,
+        &[],
+        &[
+            cmd(CATCH_THROW_SUBCOMMAND),
+            cmd(CATCH_CATCH_SUBCOMMAND),
+            cmd(CATCH_UNWIND_SUBCOMMAND),
+        ],
+    ),
+    cmd_a(WATCH_COMMAND, &[WATCH_COMMAND_SHORT]),
+    cmd(RWATCH_COMMAND),
+    cmd(AWATCH_COMMAND),
+    cmd_s(
+        MEMORY_COMMAND,
+        &[MEMORY_COMMAND_SHORT],
+        &[
+            cmd(MEMORY_COMMAND_READ_SUBCOMMAND),
+            cmd(MEMORY_COMMAND_WRITE_SUBCOMMAND),
+            cmd(MEMORY_COMMAND_SAVE_SUBCOMMAND),
+            cmd(MEMORY_COMMAND_LOAD_SUBCOMMAND),
+        ],
+    ),
+    cmd(EXAMINE_COMMAND),
+    cmd_s(
+        REGISTER_COMMAND,
+        &[REGISTER_COMMAND_SHORT],
+        &[
+            cmd(REGISTER_COMMAND_READ_SUBCOMMAND),
+            cmd(REGISTER_COMMAND_WRITE_SUBCOMMAND),
+            cmd(REGISTER_COMMAND_INFO_SUBCOMMAND),
+        ],
+    ),
+    cmd_s(
+        THREAD_COMMAND,
+        &[],
+        &[
+            cmd(THREAD_COMMAND_INFO_SUBCOMMAND),
+            cmd(THREAD_COMMAND_SWITCH_SUBCOMMAND),
+            cmd(THREAD_COMMAND_CURRENT_SUBCOMMAND),
+        ],
+    ),
+    cmd_s(
+        INFERIOR_COMMAND,
+        &[],
+        &[
+            cmd(INFERIOR_COMMAND_LIST_SUBCOMMAND),
+            cmd(INFERIOR_COMMAND_SWITCH_SUBCOMMAND),
+            cmd(INFERIOR_COMMAND_ATTACH_SUBCOMMAND),
+        ],
+    ),
+    cmd_s(
+        SHARED_LIB_COMMAND,
+        &[],
+        &[cmd(SHARED_LIB_COMMAND_INFO_SUBCOMMAND)],
+    ),
+    cmd_s(DEADLOCK_COMMAND, &[], &[cmd(DEADLOCK_CHECK_SUBCOMMAND)]),
+    cmd_s(
+        HISTORY_COMMAND,
+        &[],
+        &[
+            cmd(HISTORY_VALUES_SUBCOMMAND),
+            cmd_s(
+                HISTORY_STEPS_SUBCOMMAND,
+                &[],
+                &[cmd(HISTORY_STEPS_ALL_SUBCOMMAND)],
+            ),
+        ],
+    ),
+    cmd_s(INFO_COMMAND, &[], &[cmd(INFO_LINE_SUBCOMMAND)]),
+    cmd_s(
+        SOURCE_COMMAND,
+        &[],
+        &[
+            cmd(SOURCE_COMMAND_DISASM_SUBCOMMAND),
+            cmd(SOURCE_COMMAND_FUNCTION_SUBCOMMAND),
+        ],
+    ),
+    cmd(ORACLE_COMMAND),
+    cmd(SCRIPT_COMMAND),
+    cmd(DISPLAY_COMMAND),
+    cmd(UNDISPLAY_COMMAND),
+    cmd(SNAPSHOT_COMMAND),
+    cmd(DIFF_COMMAND),
+    cmd_s(
+        SESSION_COMMAND,
+        &[],
+        &[cmd(SESSION_SAVE_SUBCOMMAND), cmd(SESSION_LOAD_SUBCOMMAND)],
+    ),
+    cmd_s(
+        SET_COMMAND,
+        &[],
+        &[
+            cmd_s(
+                SET_PRINT_SUBCOMMAND,
+                &[],
+                &[
+                    cmd(SET_DEMANGLE_STYLE_KEY),
+                    cmd(SET_ELEMENTS_KEY),
+                    cmd(SET_DEPTH_KEY),
+                    cmd(SET_STRING_LENGTH_KEY),
+                    cmd(SET_POINTER_DEPTH_KEY),
+                    cmd(SET_BYTES_AS_ASCII_KEY),
+                ],
+            ),
+            cmd(SET_QUIT_ACTION_SUBCOMMAND),
+            cmd(SET_LISTSIZE_SUBCOMMAND),
+        ],
+    ),
+    cmd(TRACE_COMMAND),
+    cmd(DPRINTF_COMMAND),
+    cmd(FTRACE_COMMAND),
+    cmd_s(
+        COVERAGE_COMMAND,
+        &[],
+        &[
+            cmd(COVERAGE_START_SUBCOMMAND),
+            cmd(COVERAGE_STOP_SUBCOMMAND),
+            cmd(COVERAGE_REPORT_SUBCOMMAND),
+        ],
+    ),
+    cmd_s(
+        PROFILE_COMMAND,
+        &[],
+        &[
+            cmd(PROFILE_START_SUBCOMMAND),
+            cmd(PROFILE_STOP_SUBCOMMAND),
+            cmd(PROFILE_REPORT_SUBCOMMAND),
+            cmd(PROFILE_EXPORT_SUBCOMMAND),
+        ],
+    ),
+    cmd_a(HELP_COMMAND, &[HELP_COMMAND_SHORT]),
+];
+
+/// A prefix tree of command names at one level of a command (root commands or
+/// subcommands of some command).
+struct CommandTrie {
+    /// Full names and aliases.
+    names: Trie<u8>,
+    /// Index of a command by its full name or alias.
+    index: HashMap<&'static str, usize>,
+    /// Full names with a subcommand trie.
+    commands: Vec<(&'static str, CommandTrie)>,
+}
+
+impl CommandTrie {
+    fn new(specs: &'static [Spec]) -> Self {
+        let mut builder = TrieBuilder::new();
+        let mut index = HashMap::new();
+        let mut commands = Vec::with_capacity(specs.len());
+        for (i, spec) in specs.iter().enumerate() {
+            for name in std::iter::once(&spec.name).chain(spec.aliases) {
+                builder.push(name);
+                index.insert(*name, i);
+            }
+            commands.push((spec.name, CommandTrie::new(spec.subcommands)));
+        }
+        Self {
+            names: builder.build(),
+            index,
+            commands,
+        }
+    }
+
+    /// Return indexes of all commands which name or alias starts with a prefix.
+    fn candidates(&self, prefix: &str) -> Vec<usize> {
+        let mut candidates: Vec<usize> = self
+            .names
+            .predictive_search(prefix)
+            .iter()
+            .map(|name| {
+                let name = std::str::from_utf8(name).expect("infallible");
+                self.index[name]
+            })
+            .collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+        candidates
+    }
+}
+
+/// Error of an abbreviation resolving.
+pub struct AmbiguousCommand {
+    /// Position of an ambiguous word in the input string.
+    pub span: Range<usize>,
+    pub message: String,
+}
+
+fn root() -> &'static CommandTrie {
+    static ROOT: OnceLock<CommandTrie> = OnceLock::new();
+    ROOT.get_or_init(|| CommandTrie::new(COMMANDS))
+}
+
+/// Return a full name of a command by its name, alias or unambiguous prefix.
+pub fn command_name(word: &str) -> Option<&'static str> {
+    let trie = root();
+    let idx = match trie.index.get(word) {
+        Some(&idx) => idx,
+        None => match trie.candidates(word).as_slice() {
+            [idx] => *idx,
+            _ => return None,
+        },
+    };
+    Some(trie.commands[idx].0)
+}
+
+/// Expand abbreviated command and subcommand names of an input string into full names.
+/// Words that aren't a prefix of any known name are left as is, so they are
+/// reported by the command parser.
+///
+/// # Errors
+///
+/// Return error if a word is a prefix of several commands.
+pub fn resolve(input: &str) -> Result<String, AmbiguousCommand> {
+    let mut trie = root();
+
+    let mut resolved = String::with_capacity(input.len());
+    let mut rest = input;
+    let mut is_root = true;
+    while !trie.commands.is_empty() {
+        let word_start = rest.len() - rest.trim_start().len();
+        let word_len = rest[word_start..]
+            .find(char::is_whitespace)
+            .unwrap_or(rest.len() - word_start);
+        let word = &rest[word_start..word_start + word_len];
+        if word.is_empty() {
+            break;
+        }
+
+        let idx = match trie.index.get(word) {
+            Some(&idx) => idx,
+            None => match trie.candidates(word).as_slice() {
+                [] => break,
+                [idx] => *idx,
+                candidates => {
+                    let offset = input.len() - rest.len() + word_start;
+                    return Err(AmbiguousCommand {
+                        span: offset..offset + word_len,
+                        message: format!(
+                            "ambiguous {} `{word}`, candidates: {}",
+                            if is_root { "command" } else { "subcommand" },
+                            candidates.iter().map(|&i| trie.commands[i].0).join(", ")
+                        ),
+                    });
+                }
+            },
+        };
+
+        let (name, subcommands) = &trie.commands[idx];
+        resolved.push_str(&rest[..word_start]);
+        if trie.index.contains_key(word) {
+            // exact names and aliases are kept as is
+            resolved.push_str(word);
+        } else {
+            resolved.push_str(name);
+        }
+        rest = &rest[word_start + word_len..];
+        trie = subcommands;
+        is_root = false;
+    }
+    resolved.push_str(rest);
+
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_abbreviation_resolve() {
+        struct TestCase {
+            input: &'static str,
+            expected: Result<&'static str, &'static str>,
+        }
+        let cases = [
+            TestCase {
+                input: "cont",
+                expected: Ok("continue"),
+            },
+            TestCase {
+                input: " brea main.rs:5",
+                expected: Ok(" break main.rs:5"),
+            },
+            TestCase {
+                input: "reg rea rax",
+                expected: Ok("reg read rax"),
+            },
+            TestCase {
+                input: "regis wr rax 0x1",
+                expected: Ok("register write rax 0x1"),
+            },
+            TestCase {
+                input: "reg xmm0 as f32x4",
+                expected: Ok("reg xmm0 as f32x4"),
+            },
+            TestCase {
+                input: "set pr elem 10",
+                expected: Ok("set print elements 10"),
+            },
+            TestCase {
+                input: "b r 1",
+                expected: Ok("b r 1"),
+            },
+            TestCase {
+                input: "stepi 2",
+                expected: Ok("stepi 2"),
+            },
+            TestCase {
+                input: "x/4i $rip",
+                expected: Ok("x/4i $rip"),
+            },
+            TestCase {
+                input: "foo bar",
+                expected: Ok("foo bar"),
+            },
+            TestCase {
+                input: "re",
+                expected: Err("ambiguous command `re`, candidates: return, resolve, register"),
+            },
+            TestCase {
+                input: "prof st",
+                expected: Err("ambiguous subcommand `st`, candidates: start, stop"),
+            },
+        ];
+
+        for case in cases {
+            let result = resolve(case.input).map_err(|e| e.message);
+            assert_eq!(
+                result.as_deref(),
+                case.expected.map_err(ToString::to_string).as_deref(),
+                "input: {}",
+                case.input
+            );
+        }
+
+        assert!(matches!(
+            Command::parse("cont"),
+            Ok(Command::Continue(None))
+        ));
+        assert!(matches!(
+            Command::parse("  thr  sw 2"),
+            Ok(Command::Thread(thread::Command::Switch(2)))
+        ));
+        assert!(
+            matches!(Command::parse("s"), Err(CommandError::Parsing(e)) if e.contains("ambiguous command"))
+        );
+    }
+}
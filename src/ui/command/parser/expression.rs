@@ -37,6 +37,74 @@ fn ptr_cast<'a>() -> impl Parser<'a, &'a str, DQE, Err<'a>> + Clone {
         .labelled("pointer cast")
 }
 
+fn history_value<'a>() -> impl Parser<'a, &'a str, DQE, Err<'a>> + Clone {
+    just('$')
+        .ignore_then(text::int(10).from_str().unwrapped())
+        .padded()
+        .map(DQE::History)
+        .labelled("history value")
+}
+
+/// Type name in a cast expression, like `u64`, `*const some::Type<i32>` or `&mut [u8; 4]`.
+fn type_name<'a>() -> impl Parser<'a, &'a str, &'a str, Err<'a>> + Clone {
+    recursive(|ty| {
+        let path = text::ascii::ident()
+            .separated_by(just("::"))
+            .at_least(1)
+            .then(
+                ty.clone()
+                    .separated_by(just(',').padded())
+                    .at_least(1)
+                    .delimited_by(just('<'), just('>'))
+                    .or_not(),
+            );
+
+        let pointer = just('*')
+            .then(text::keyword("const").or(text::keyword("mut")))
+            .then(text::whitespace().at_least(1))
+            .then(ty.clone());
+
+        let reference = just('&')
+            .then(
+                text::keyword("mut")
+                    .then(text::whitespace().at_least(1))
+                    .or_not(),
+            )
+            .then(ty.clone());
+
+        let array = ty
+            .then(just(';').padded().then(text::int(10)).or_not())
+            .padded()
+            .delimited_by(just('['), just(']'));
+
+        choice((
+            pointer.to_slice(),
+            reference.to_slice(),
+            array.to_slice(),
+            path.to_slice(),
+        ))
+    })
+    .labelled("type name")
+}
+
+/// Cast operator, like `as *const SomeType`.
+fn cast_op<'a>() -> impl Parser<'a, &'a str, String, Err<'a>> + Clone {
+    just("as")
+        .then(text::whitespace().at_least(1).labelled("whitespace"))
+        .padded()
+        .ignore_then(type_name())
+        .padded()
+        .map(ToString::to_string)
+}
+
+fn register<'a>() -> impl Parser<'a, &'a str, DQE, Err<'a>> + Clone {
+    just('$')
+        .ignore_then(text::ident())
+        .padded()
+        .map(|name: &str| DQE::Register(name.to_string()))
+        .labelled("register")
+}
+
 fn literal<'a>() -> impl Parser<'a, &'a str, Literal, Err<'a>> + Clone {
     let op = |c| just(c).padded();
 
@@ -123,7 +191,9 @@ fn literal<'a>() -> impl Parser<'a, &'a str, Literal, Err<'a>> + Clone {
     literal
 }
 
-pub fn parser<'a>() -> impl Parser<'a, &'a str, DQE, Err<'a>> {
+/// Data query expression parser that doesn't require an end of input after the expression,
+/// used when expressions are a part of another command.
+pub fn embedded_parser<'a>() -> impl Parser<'a, &'a str, DQE, Err<'a>> + Clone {
     let base_selector = rust_identifier()
         .padded()
         .map(|name: &str| {
@@ -132,9 +202,11 @@ pub fn parser<'a>() -> impl Parser<'a, &'a str, DQE, Err<'a>> {
                 only_local: false,
             })
         })
-        .or(ptr_cast());
+        .or(ptr_cast())
+        .or(register())
+        .or(history_value());
 
-    let expr = recursive(|expr| {
+    recursive(|expr| {
         let op = |c| just(c).padded();
 
         let atom = base_selector
@@ -181,15 +253,22 @@ pub fn parser<'a>() -> impl Parser<'a, &'a str, DQE, Err<'a>> {
             |r, expr_fn| expr_fn(r),
         );
 
-        op('*')
+        let unary = op('*')
             .to(DQE::Deref as fn(_) -> _)
             .or(op('&').to(DQE::Address as fn(_) -> _))
             .or(op('~').to(DQE::Canonic as fn(_) -> _))
             .repeated()
-            .foldr(expr, |op, rhs| op(Box::new(rhs)))
-    });
+            .foldr(expr, |op, rhs| op(Box::new(rhs)));
 
-    expr.then_ignore(end())
+        // like in rust, unary operators take precedence over a cast
+        unary.foldl(cast_op().repeated(), |expr, r#type| {
+            DQE::Cast(Box::new(expr), r#type)
+        })
+    })
+}
+
+pub fn parser<'a>() -> impl Parser<'a, &'a str, DQE, Err<'a>> {
+    embedded_parser().then_ignore(end())
 }
 
 #[cfg(test)]
@@ -694,6 +773,71 @@ mod test {
                     .boxed(),
                 ),
             },
+            TestCase {
+                string: "var1 as *const some::Type<i32, u8>",
+                expr: DQE::Cast(
+                    DQE::Variable(VariableSelector::Name {
+                        var_name: "var1".to_string(),
+                        only_local: false,
+                    })
+                    .boxed(),
+                    "*const some::Type<i32, u8>".to_string(),
+                ),
+            },
+            TestCase {
+                string: "*var1 as u64",
+                expr: DQE::Cast(
+                    DQE::Deref(
+                        DQE::Variable(VariableSelector::Name {
+                            var_name: "var1".to_string(),
+                            only_local: false,
+                        })
+                        .boxed(),
+                    )
+                    .boxed(),
+                    "u64".to_string(),
+                ),
+            },
+            TestCase {
+                string: "*($rdi as &mut [u8; 4])",
+                expr: DQE::Deref(
+                    DQE::Cast(
+                        DQE::Register("rdi".to_string()).boxed(),
+                        "&mut [u8; 4]".to_string(),
+                    )
+                    .boxed(),
+                ),
+            },
+            TestCase {
+                string: "(var1 as *mut Foo).field",
+                expr: DQE::Field(
+                    DQE::Cast(
+                        DQE::Variable(VariableSelector::Name {
+                            var_name: "var1".to_string(),
+                            only_local: false,
+                        })
+                        .boxed(),
+                        "*mut Foo".to_string(),
+                    )
+                    .boxed(),
+                    "field".to_string(),
+                ),
+            },
+            TestCase {
+                string: "$12.field[1]",
+                expr: DQE::Index(
+                    DQE::Field(DQE::History(12).boxed(), "field".to_string()).boxed(),
+                    Literal::Int(1),
+                ),
+            },
+            TestCase {
+                string: "$rsp",
+                expr: DQE::Register("rsp".to_string()),
+            },
+            TestCase {
+                string: "~( $rax )",
+                expr: DQE::Canonic(DQE::Register("rax".to_string()).boxed()),
+            },
             TestCase {
                 string: "&&(*i32)0x123",
                 expr: DQE::Address(
@@ -717,7 +861,7 @@ mod test {
         let test_cases = vec![
             TestCase {
                 string: "var1 var2",
-                err_text: "found 'v' expected '.', '[', or end of input",
+                err_text: "found 'v' expected '.', '[', 'a', or end of input",
             },
             TestCase {
                 string: "var1..",
@@ -733,15 +877,15 @@ mod test {
             },
             TestCase {
                 string: "((var1)",
-                err_text: "found end of input expected '0', '.', '[', or ')'",
+                err_text: "found end of input expected '0', '.', '[', 'a', or ')'",
             },
             TestCase {
                 string: "(var1))",
-                err_text: "found end of input expected '0', '.', '[', or end of input",
+                err_text: "found end of input expected '0', '.', '[', 'a', or end of input",
             },
             TestCase {
                 string: "*",
-                err_text: "found end of input expected '*', '&', '~', ':', or '('",
+                err_text: "found end of input expected '*', '&', '~', ':', '(', or '$'",
             },
         ];
 
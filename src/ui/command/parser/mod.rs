@@ -1,33 +1,63 @@
+mod abbreviation;
 pub mod expression;
 
+pub use abbreviation::command_name;
+
 use super::r#break::BreakpointIdentity;
-use super::{frame, memory, register, source_code, thread, watch, Command, CommandError};
+use super::{
+    coverage, display, examine, frame, ftrace, inferior, info, jump, memory, profile, quit,
+    register, run, session, set, snapshot, source_code, thread, trace, watch, Command,
+    CommandError,
+};
 use super::{r#break, CommandResult};
 use crate::debugger::register::debug::BreakCondition;
+use crate::debugger::unwind::DemangleStyle;
 use crate::debugger::variable::select::{VariableSelector, DQE};
+use crate::debugger::{
+    CatchEvent, LineInfoLocation, ReturnCondition, ReturnValue, StepGranularity, StopAt,
+    TracepointPlace,
+};
+use crate::ui::command::register::LaneFormat;
 use crate::ui::command::watch::WatchpointIdentity;
 use ariadne::{Color, Fmt, Label, Report, ReportKind, Source};
 use chumsky::error::{Rich, RichPattern, RichReason};
-use chumsky::prelude::{any, choice, end, just, one_of};
+use chumsky::prelude::{any, choice, end, just, none_of, one_of};
 use chumsky::text::{whitespace, Char};
-use chumsky::{extra, text, Boxed, Parser};
+use chumsky::{extra, text, Boxed, IterParser, Parser};
 use itertools::Itertools;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
 
 pub const VAR_COMMAND: &str = "var";
 pub const VAR_LOCAL_KEY: &str = "locals";
+pub const VAR_JSON_KEY: &str = "--json";
+pub const VAR_JSON_REDIRECT: &str = ">";
 pub const ARG_COMMAND: &str = "arg";
 pub const ARG_ALL_KEY: &str = "all";
 pub const BACKTRACE_COMMAND: &str = "backtrace";
 pub const BACKTRACE_COMMAND_SHORT: &str = "bt";
 pub const BACKTRACE_ALL_SUBCOMMAND: &str = "all";
+pub const BACKTRACE_ARGS_KEY: &str = "--args";
 pub const CONTINUE_COMMAND: &str = "continue";
 pub const CONTINUE_COMMAND_SHORT: &str = "c";
+pub const CONTINUE_FOR_KEY: &str = "--for";
 pub const FRAME_COMMAND: &str = "frame";
 pub const FRAME_COMMAND_SHORT: &str = "f";
 pub const FRAME_COMMAND_INFO_SUBCOMMAND: &str = "info";
 pub const FRAME_COMMAND_SWITCH_SUBCOMMAND: &str = "switch";
+pub const FRAME_COMMAND_MEMORY_SUBCOMMAND: &str = "memory";
 pub const RUN_COMMAND: &str = "run";
 pub const RUN_COMMAND_SHORT: &str = "r";
+pub const RUN_STOP_AT_ENTRY_KEY: &str = "--stop-at-entry";
+pub const RUN_STOP_AT_MAIN_KEY: &str = "--stop-at-main";
+pub const RUN_STDIN_KEY: &str = "--stdin";
+pub const RUN_STDIN_REDIRECT: &str = "<";
+pub const KILL_COMMAND: &str = "kill";
+pub const QUIT_COMMAND: &str = "quit";
+pub const QUIT_COMMAND_SHORT: &str = "q";
+pub const QUIT_KILL_KEY: &str = "--kill";
+pub const QUIT_DETACH_KEY: &str = "--detach";
 pub const STEP_INSTRUCTION_COMMAND: &str = "stepi";
 pub const STEP_INTO_COMMAND: &str = "stepinto";
 pub const STEP_INTO_COMMAND_SHORT: &str = "step";
@@ -35,36 +65,114 @@ pub const STEP_OUT_COMMAND: &str = "stepout";
 pub const STEP_OUT_COMMAND_SHORT: &str = "finish";
 pub const STEP_OVER_COMMAND: &str = "stepover";
 pub const STEP_OVER_COMMAND_SHORT: &str = "next";
+pub const STEP_STATEMENT_KEY: &str = "stmt";
+pub const RETURN_COMMAND: &str = "return";
+pub const JUMP_COMMAND: &str = "jump";
 pub const SYMBOL_COMMAND: &str = "symbol";
+pub const PTYPE_COMMAND: &str = "ptype";
+pub const RESOLVE_COMMAND: &str = "resolve";
+pub const SYMBOLIZE_COMMAND: &str = "symbolize";
 pub const BREAK_COMMAND: &str = "break";
 pub const BREAK_COMMAND_SHORT: &str = "b";
 pub const BREAK_REMOVE_SUBCOMMAND: &str = "remove";
 pub const BREAK_REMOVE_SUBCOMMAND_SHORT: &str = "r";
 pub const BREAK_INFO_SUBCOMMAND: &str = "info";
+pub const BREAK_ENABLE_SUBCOMMAND: &str = "enable";
+pub const BREAK_DISABLE_SUBCOMMAND: &str = "disable";
+pub const BREAK_REGEX_KEY: &str = "--regex";
+pub const BREAK_GROUP_KEY: &str = "--group";
+pub const BREAK_COMMANDS_SUBCOMMAND: &str = "commands";
+pub const BREAK_ON_RETURN_KEY: &str = "--on-return";
+pub const BREAK_HARDWARE_KEY: &str = "--hardware";
+pub const CATCH_COMMAND: &str = "catch";
+pub const CATCH_THROW_SUBCOMMAND: &str = "throw";
+pub const CATCH_CATCH_SUBCOMMAND: &str = "catch";
+pub const CATCH_UNWIND_SUBCOMMAND: &str = "unwind";
 pub const WATCH_COMMAND: &str = "watch";
 pub const WATCH_COMMAND_SHORT: &str = "w";
 pub const WATCH_REMOVE_SUBCOMMAND: &str = "remove";
 pub const WATCH_REMOVE_SUBCOMMAND_SHORT: &str = "r";
 pub const WATCH_INFO_SUBCOMMAND: &str = "info";
+pub const RWATCH_COMMAND: &str = "rwatch";
+pub const AWATCH_COMMAND: &str = "awatch";
 pub const MEMORY_COMMAND: &str = "memory";
 pub const MEMORY_COMMAND_SHORT: &str = "mem";
 pub const MEMORY_COMMAND_READ_SUBCOMMAND: &str = "read";
 pub const MEMORY_COMMAND_WRITE_SUBCOMMAND: &str = "write";
+pub const MEMORY_COMMAND_SAVE_SUBCOMMAND: &str = "save";
+pub const MEMORY_COMMAND_LOAD_SUBCOMMAND: &str = "load";
+pub const EXAMINE_COMMAND: &str = "x";
 pub const REGISTER_COMMAND: &str = "register";
 pub const REGISTER_COMMAND_SHORT: &str = "reg";
 pub const REGISTER_COMMAND_READ_SUBCOMMAND: &str = "read";
 pub const REGISTER_COMMAND_WRITE_SUBCOMMAND: &str = "write";
 pub const REGISTER_COMMAND_INFO_SUBCOMMAND: &str = "info";
+pub const REGISTER_AS_KEY: &str = "as";
 pub const THREAD_COMMAND: &str = "thread";
 pub const THREAD_COMMAND_INFO_SUBCOMMAND: &str = "info";
 pub const THREAD_COMMAND_SWITCH_SUBCOMMAND: &str = "switch";
 pub const THREAD_COMMAND_CURRENT_SUBCOMMAND: &str = "current";
+pub const INFERIOR_COMMAND: &str = "inferior";
+pub const INFERIOR_COMMAND_LIST_SUBCOMMAND: &str = "list";
+pub const INFERIOR_COMMAND_SWITCH_SUBCOMMAND: &str = "switch";
+pub const INFERIOR_COMMAND_ATTACH_SUBCOMMAND: &str = "attach";
 pub const SHARED_LIB_COMMAND: &str = "sharedlib";
 pub const SHARED_LIB_COMMAND_INFO_SUBCOMMAND: &str = "info";
+pub const INFO_COMMAND: &str = "info";
+pub const INFO_LINE_SUBCOMMAND: &str = "line";
+pub const DEADLOCK_COMMAND: &str = "deadlock";
+pub const DEADLOCK_CHECK_SUBCOMMAND: &str = "check";
+pub const HISTORY_COMMAND: &str = "history";
+pub const HISTORY_VALUES_SUBCOMMAND: &str = "values";
+pub const HISTORY_STEPS_SUBCOMMAND: &str = "steps";
+pub const HISTORY_STEPS_ALL_SUBCOMMAND: &str = "all";
 pub const SOURCE_COMMAND: &str = "source";
 pub const SOURCE_COMMAND_DISASM_SUBCOMMAND: &str = "asm";
 pub const SOURCE_COMMAND_FUNCTION_SUBCOMMAND: &str = "fn";
 pub const ORACLE_COMMAND: &str = "oracle";
+pub const SCRIPT_COMMAND: &str = "script";
+pub const DISPLAY_COMMAND: &str = "display";
+pub const UNDISPLAY_COMMAND: &str = "undisplay";
+pub const SNAPSHOT_COMMAND: &str = "snapshot";
+pub const DIFF_COMMAND: &str = "diff";
+pub const SESSION_COMMAND: &str = "session";
+pub const SESSION_SAVE_SUBCOMMAND: &str = "save";
+pub const SESSION_LOAD_SUBCOMMAND: &str = "load";
+pub const SET_COMMAND: &str = "set";
+pub const SET_PRINT_SUBCOMMAND: &str = "print";
+pub const SET_DEMANGLE_STYLE_KEY: &str = "demangle-style";
+pub const DEMANGLE_STYLE_FULL: &str = "full";
+pub const DEMANGLE_STYLE_DEFAULT: &str = "default";
+pub const DEMANGLE_STYLE_SHORT: &str = "short";
+pub const SET_ELEMENTS_KEY: &str = "elements";
+pub const SET_DEPTH_KEY: &str = "depth";
+pub const SET_STRING_LENGTH_KEY: &str = "string-length";
+pub const SET_POINTER_DEPTH_KEY: &str = "pointer-depth";
+pub const SET_BYTES_AS_ASCII_KEY: &str = "bytes-as-ascii";
+pub const SET_QUIT_ACTION_SUBCOMMAND: &str = "quit-action";
+pub const SET_LISTSIZE_SUBCOMMAND: &str = "listsize";
+pub const QUIT_ACTION_ASK: &str = "ask";
+pub const QUIT_ACTION_KILL: &str = "kill";
+pub const QUIT_ACTION_DETACH: &str = "detach";
+pub const LIMIT_UNLIMITED: &str = "unlimited";
+pub const SWITCH_ON: &str = "on";
+pub const SWITCH_OFF: &str = "off";
+pub const TRACE_COMMAND: &str = "trace";
+pub const TRACE_REPORT_SUBCOMMAND: &str = "report";
+pub const DPRINTF_COMMAND: &str = "dprintf";
+pub const FTRACE_COMMAND: &str = "ftrace";
+pub const FTRACE_STOP_SUBCOMMAND: &str = "stop";
+pub const COVERAGE_COMMAND: &str = "coverage";
+pub const COVERAGE_START_SUBCOMMAND: &str = "start";
+pub const COVERAGE_STOP_SUBCOMMAND: &str = "stop";
+pub const COVERAGE_REPORT_SUBCOMMAND: &str = "report";
+pub const PROFILE_COMMAND: &str = "profile";
+pub const PROFILE_START_SUBCOMMAND: &str = "start";
+pub const PROFILE_STOP_SUBCOMMAND: &str = "stop";
+pub const PROFILE_REPORT_SUBCOMMAND: &str = "report";
+pub const PROFILE_EXPORT_SUBCOMMAND: &str = "export";
+pub const PROFILE_REPORT_FLAT: &str = "flat";
+pub const PROFILE_REPORT_TREE: &str = "tree";
 pub const HELP_COMMAND: &str = "help";
 pub const HELP_COMMAND_SHORT: &str = "h";
 
@@ -93,11 +201,45 @@ pub fn rust_identifier<'a>() -> impl chumsky::Parser<'a, &'a str, &'a str, Err<'
         .labelled("rust identifier")
 }
 
+/// Glob pattern for variable names like `foo*` or `*_id`. Pattern must contain a wildcard
+/// (`*` or `?`) and must not start with `*` followed by an identifier, cause
+/// it is a dereference expression.
+fn name_pattern<'a>() -> impl chumsky::Parser<'a, &'a str, VariableSelector, Err<'a>> + Clone {
+    any()
+        .filter(|c: &char| c.is_ascii_alphanumeric() || matches!(c, '_' | ':' | '*' | '?'))
+        .repeated()
+        .at_least(1)
+        .to_slice()
+        .filter(|pattern: &&str| {
+            pattern.contains(['*', '?']) && (!pattern.starts_with('*') || pattern.ends_with('*'))
+        })
+        .padded()
+        // pattern may be followed by an output redirect (`var --json foo* > file`)
+        .then_ignore(end().or(just(VAR_JSON_REDIRECT).ignored()).rewind())
+        .map(|pattern: &str| VariableSelector::Pattern(pattern.to_string()))
+        .labelled("variable name pattern")
+}
+
 pub fn brkpt_at_addr_parser<'a>() -> impl chumsky::Parser<'a, &'a str, BreakpointIdentity, Err<'a>>
 {
     hex().map(BreakpointIdentity::Address)
 }
 
+/// Parse a raw address or a register name prefixed with `$`.
+pub fn address_location<'a>() -> impl chumsky::Parser<'a, &'a str, memory::Location, Err<'a>> + Clone
+{
+    let register = just('$')
+        .ignore_then(text::ident())
+        .map(|register: &str| memory::Location::Register(register.to_string()));
+    hex().map(memory::Location::Address).or(register).padded()
+}
+
+pub fn memory_location<'a>() -> impl chumsky::Parser<'a, &'a str, memory::Location, Err<'a>> {
+    address_location().or(expression::parser()
+        .padded()
+        .map(memory::Location::Expression))
+}
+
 pub fn brkpt_at_line_parser<'a>() -> impl chumsky::Parser<'a, &'a str, BreakpointIdentity, Err<'a>>
 {
     any()
@@ -106,7 +248,17 @@ pub fn brkpt_at_line_parser<'a>() -> impl chumsky::Parser<'a, &'a str, Breakpoin
         .to_slice()
         .then_ignore(just(':'))
         .then(text::int(10).from_str().unwrapped())
-        .map(|(file, line): (&str, u64)| BreakpointIdentity::Line(file.trim().to_string(), line))
+        .then(
+            just(':')
+                .ignore_then(text::int(10).from_str().unwrapped())
+                .or_not(),
+        )
+        .map(
+            |((file, line), column): ((&str, u64), Option<u64>)| match column {
+                None => BreakpointIdentity::Line(file.trim().to_string(), line),
+                Some(column) => BreakpointIdentity::Column(file.trim().to_string(), line, column),
+            },
+        )
         .padded()
 }
 
@@ -131,6 +283,7 @@ pub fn watchpoint_cond<'a>() -> impl chumsky::Parser<'a, &'a str, BreakCondition
     let op = |sym| whitespace().then(just(sym)).then(ws_req_or_end);
     op("+rw")
         .to(BreakCondition::DataReadsWrites)
+        .or(op("+r").to(BreakCondition::DataReads))
         .or(op("+rw").to(BreakCondition::DataWrites))
         .or(text::whitespace().to(BreakCondition::DataWrites))
 }
@@ -152,6 +305,128 @@ pub fn watchpoint_at_address<'a>() -> impl chumsky::Parser<'a, &'a str, Watchpoi
         })
 }
 
+pub fn return_value<'a>() -> impl chumsky::Parser<'a, &'a str, ReturnValue, Err<'a>> {
+    let sign = just('-').or_not();
+    let float = sign
+        .then(text::int(10))
+        .then(just('.'))
+        .then(text::digits(10))
+        .to_slice()
+        .from_str()
+        .unwrapped()
+        .map(ReturnValue::Float);
+    let int = sign
+        .then(text::int(10))
+        .to_slice()
+        .from_str()
+        .unwrapped()
+        .map(ReturnValue::Int);
+    let bool = just("true")
+        .to(ReturnValue::Int(1))
+        .or(just("false").to(ReturnValue::Int(0)));
+
+    choice((hex().map(|v| ReturnValue::Int(v as i64)), float, int, bool))
+        .padded()
+        .labelled("return value")
+}
+
+pub fn lane_format<'a>() -> impl chumsky::Parser<'a, &'a str, LaneFormat, Err<'a>> {
+    text::ident()
+        .try_map(|fmt: &str, span| LaneFormat::from_str(fmt).map_err(|e| Rich::custom(span, e)))
+        .padded()
+        .labelled("lane format (like f32x4, u8x16 or u64x2)")
+}
+
+pub fn duration<'a>() -> impl chumsky::Parser<'a, &'a str, Duration, Err<'a>> {
+    // unit multipliers in milliseconds
+    let unit = choice((just("ms").to(1), just("s").to(1_000), just("m").to(60_000)));
+    text::int(10)
+        .from_str()
+        .unwrapped()
+        .then(unit)
+        .map(|(value, ms_in_unit): (u64, u64)| Duration::from_millis(value * ms_in_unit))
+        .padded()
+        .labelled("duration (like 500ms, 5s or 1m)")
+}
+
+pub fn jump_location<'a>() -> impl chumsky::Parser<'a, &'a str, jump::Location, Err<'a>> {
+    let line = text::int(10).from_str().unwrapped();
+    let in_file = any()
+        .filter(|c: &char| c.to_char() != ':' && !c.is_whitespace())
+        .repeated()
+        .at_least(1)
+        .to_slice()
+        .then_ignore(just(':'))
+        .then(line)
+        .map(|(file, line): (&str, u64)| jump::Location {
+            file: Some(file.to_string()),
+            line,
+        });
+    let in_current_file = line.map(|line| jump::Location { file: None, line });
+    in_file
+        .or(in_current_file)
+        .padded()
+        .labelled("line or file:line")
+}
+
+pub fn tracepoint_place<'a>() -> impl chumsky::Parser<'a, &'a str, TracepointPlace, Err<'a>> {
+    let at_line = any()
+        .filter(|c: &char| c.to_char() != ':' && !c.is_whitespace())
+        .repeated()
+        .at_least(1)
+        .to_slice()
+        .then_ignore(just(':'))
+        .then(text::int(10).from_str().unwrapped())
+        .map(|(file, line): (&str, u64)| TracepointPlace::Line(file.to_string(), line))
+        .padded();
+    let at_fn = rust_identifier().map(|name| TracepointPlace::Function(name.to_string()));
+    at_line.or(at_fn)
+}
+
+/// Parse a location for `info line` command, an empty location means the current place.
+pub fn line_info_location<'a>() -> impl chumsky::Parser<'a, &'a str, LineInfoLocation, Err<'a>> {
+    let at_line = any()
+        .filter(|c: &char| c.to_char() != ':' && !c.is_whitespace())
+        .repeated()
+        .at_least(1)
+        .to_slice()
+        .then_ignore(just(':'))
+        .then(text::int(10).from_str().unwrapped())
+        .map(|(file, line): (&str, u64)| LineInfoLocation::Line(file.to_string(), line))
+        .padded();
+    let at_fn = rust_identifier().map(|name| LineInfoLocation::Function(name.to_string()));
+    choice((hex().map(LineInfoLocation::Address), at_line, at_fn))
+        .or_not()
+        .map(|location| location.unwrap_or(LineInfoLocation::Current))
+        .padded()
+        .labelled("address, file:line or function name")
+}
+
+pub fn tracepoint_expressions<'a>() -> impl chumsky::Parser<'a, &'a str, Vec<(String, DQE)>, Err<'a>>
+{
+    expression::embedded_parser()
+        .padded()
+        .map_with(|dqe, e| (e.slice().trim().to_string(), dqe))
+        .repeated()
+        .collect()
+}
+
+pub fn format_string<'a>() -> impl chumsky::Parser<'a, &'a str, String, Err<'a>> {
+    let escape = just('\\').ignore_then(choice((
+        just('\\'),
+        just('"'),
+        just('n').to('\n'),
+        just('t').to('\t'),
+    )));
+    none_of("\\\"")
+        .or(escape)
+        .repeated()
+        .collect::<String>()
+        .delimited_by(just('"'), just('"'))
+        .padded()
+        .labelled("format string")
+}
+
 fn command<'a, I>(ctx: &'static str, inner: I) -> Boxed<'a, 'a, &'a str, Command, Err<'a>>
 where
     I: chumsky::Parser<'a, &'a str, Command, Err<'a>> + 'a,
@@ -245,33 +520,148 @@ impl Command {
         let sub_op_w_arg = |sym| just(sym).then(ws_req);
 
         let print_local_vars = op_w_arg(VAR_COMMAND)
-            .then(sub_op(VAR_LOCAL_KEY))
+            .then(sub_op(VAR_LOCAL_KEY).or(sub_op("*")))
             .map(|_| Command::PrintVariables(DQE::Variable(VariableSelector::Any)));
+        let print_vars_by_pattern = op_w_arg(VAR_COMMAND)
+            .ignore_then(name_pattern())
+            .map(|pattern| Command::PrintVariables(DQE::Variable(pattern)));
         let print_var = op_w_arg(VAR_COMMAND)
             .ignore_then(expression::parser())
             .map(Command::PrintVariables);
 
-        let print_variables = choice((print_local_vars, print_var)).boxed();
+        let var_json_output = just(VAR_JSON_REDIRECT)
+            .padded()
+            .ignore_then(
+                any()
+                    .filter(|c: &char| !c.is_whitespace())
+                    .repeated()
+                    .at_least(1)
+                    .to_slice()
+                    .map(PathBuf::from),
+            )
+            .padded();
+        let print_vars_json = op_w_arg(VAR_COMMAND)
+            .ignore_then(sub_op_w_arg(VAR_JSON_KEY))
+            .ignore_then(choice((
+                sub_op(VAR_LOCAL_KEY)
+                    .or(sub_op("*"))
+                    .to(DQE::Variable(VariableSelector::Any)),
+                name_pattern().map(DQE::Variable),
+                expression::embedded_parser(),
+            )))
+            .then(var_json_output.or_not())
+            .map(|(dqe, output)| Command::PrintVariablesJson(dqe, output));
+
+        let print_variables = choice((
+            print_vars_json,
+            print_local_vars,
+            print_vars_by_pattern,
+            print_var,
+        ))
+        .boxed();
 
         let print_all_args = op_w_arg(ARG_COMMAND)
-            .then(sub_op(ARG_ALL_KEY))
+            .then(sub_op(ARG_ALL_KEY).or(sub_op("*")))
             .map(|_| Command::PrintArguments(DQE::Variable(VariableSelector::Any)));
+        let print_args_by_pattern = op_w_arg(ARG_COMMAND)
+            .ignore_then(name_pattern())
+            .map(|pattern| Command::PrintArguments(DQE::Variable(pattern)));
         let print_arg = op_w_arg(ARG_COMMAND)
             .ignore_then(expression::parser())
             .map(Command::PrintArguments);
 
-        let print_arguments = choice((print_all_args, print_arg)).boxed();
+        let print_arguments = choice((print_all_args, print_args_by_pattern, print_arg)).boxed();
 
         let op2 = |full, short| op(full).or(op(short));
         let op2_w_arg = |full, short| op_w_arg(full).or(op_w_arg(short));
         let sub_op2_w_arg = |full, short| sub_op_w_arg(full).or(sub_op_w_arg(short));
 
-        let r#continue = op2(CONTINUE_COMMAND, CONTINUE_COMMAND_SHORT).to(Command::Continue);
-        let run = op2(RUN_COMMAND, RUN_COMMAND_SHORT).to(Command::Run);
-        let stepi = op(STEP_INSTRUCTION_COMMAND).to(Command::StepInstruction);
-        let step_into = op2(STEP_INTO_COMMAND, STEP_INTO_COMMAND_SHORT).to(Command::StepInto);
-        let step_out = op2(STEP_OUT_COMMAND, STEP_OUT_COMMAND_SHORT).to(Command::StepOut);
-        let step_over = op2(STEP_OVER_COMMAND, STEP_OVER_COMMAND_SHORT).to(Command::StepOver);
+        let r#continue = op2(CONTINUE_COMMAND, CONTINUE_COMMAND_SHORT)
+            .ignore_then(
+                sub_op_w_arg(CONTINUE_FOR_KEY)
+                    .ignore_then(duration())
+                    .or_not(),
+            )
+            .map(Command::Continue)
+            .boxed();
+        let run_stdin = choice((
+            just(RUN_STDIN_REDIRECT).then(whitespace()),
+            sub_op_w_arg(RUN_STDIN_KEY),
+        ))
+        .ignore_then(
+            any()
+                .filter(|c: &char| !c.is_whitespace())
+                .repeated()
+                .at_least(1)
+                .to_slice()
+                .map(PathBuf::from),
+        )
+        .padded();
+        let run = op2(RUN_COMMAND, RUN_COMMAND_SHORT)
+            .ignore_then(
+                choice((
+                    sub_op(RUN_STOP_AT_ENTRY_KEY).to(StopAt::Entry),
+                    sub_op(RUN_STOP_AT_MAIN_KEY).to(StopAt::Main),
+                ))
+                .or_not(),
+            )
+            .then(run_stdin.or_not())
+            .map(|(stop_at, stdin)| Command::Run(run::Options { stop_at, stdin }))
+            .boxed();
+        let kill = op(KILL_COMMAND).to(Command::Kill).boxed();
+        let quit = op2(QUIT_COMMAND, QUIT_COMMAND_SHORT)
+            .ignore_then(
+                choice((
+                    sub_op(QUIT_KILL_KEY).to(quit::Action::Kill),
+                    sub_op(QUIT_DETACH_KEY).to(quit::Action::Detach),
+                ))
+                .or_not(),
+            )
+            .map(Command::Quit)
+            .boxed();
+        let step_count = || {
+            text::int(10)
+                .from_str()
+                .unwrapped()
+                .padded()
+                .or_not()
+                .map(|count: Option<u32>| count.unwrap_or(1))
+        };
+        let step_granularity = || {
+            sub_op(STEP_STATEMENT_KEY)
+                .to(StepGranularity::Statement)
+                .or_not()
+                .map(Option::unwrap_or_default)
+        };
+
+        let stepi = op(STEP_INSTRUCTION_COMMAND)
+            .ignore_then(step_count())
+            .map(Command::StepInstruction)
+            .boxed();
+        let step_into = op2(STEP_INTO_COMMAND, STEP_INTO_COMMAND_SHORT)
+            .ignore_then(step_granularity())
+            .then(step_count())
+            .map(|(granularity, count)| Command::StepInto(count, granularity))
+            .boxed();
+        let step_out = op2(STEP_OUT_COMMAND, STEP_OUT_COMMAND_SHORT)
+            .ignore_then(step_count())
+            .map(Command::StepOut)
+            .boxed();
+        let step_over = op2(STEP_OVER_COMMAND, STEP_OVER_COMMAND_SHORT)
+            .ignore_then(step_granularity())
+            .then(step_count())
+            .map(|(granularity, count)| Command::StepOver(count, granularity))
+            .boxed();
+
+        let r#return = op(RETURN_COMMAND)
+            .ignore_then(return_value().or_not())
+            .map(Command::Return)
+            .boxed();
+
+        let jump = op_w_arg(JUMP_COMMAND)
+            .ignore_then(jump_location())
+            .map(Command::Jump)
+            .boxed();
 
         let source_code = op_w_arg(SOURCE_COMMAND)
             .ignore_then(choice((
@@ -298,11 +688,13 @@ impl Command {
 
         let backtrace = op2(BACKTRACE_COMMAND, BACKTRACE_COMMAND_SHORT)
             .ignore_then(sub_op(BACKTRACE_ALL_SUBCOMMAND).or_not())
-            .map(|all| {
+            .then(sub_op(BACKTRACE_ARGS_KEY).or_not())
+            .map(|(all, args)| {
+                let args = args.is_some();
                 if all.is_some() {
-                    Command::PrintBacktrace(super::backtrace::Command::All)
+                    Command::PrintBacktrace(super::backtrace::Command::All { args })
                 } else {
-                    Command::PrintBacktrace(super::backtrace::Command::CurrentThread)
+                    Command::PrintBacktrace(super::backtrace::Command::CurrentThread { args })
                 }
             })
             .boxed();
@@ -312,6 +704,37 @@ impl Command {
             .map(|s| Command::PrintSymbol(s.trim().to_string()))
             .boxed();
 
+        let resolve = op_w_arg(RESOLVE_COMMAND)
+            .ignore_then(any().repeated().at_least(1).to_slice())
+            .try_map(|s: &str, span| {
+                // take all hexadecimal numbers, this allows to paste a raw backtrace
+                let addresses: Vec<_> = s
+                    .split(|c: char| !c.is_ascii_alphanumeric())
+                    .filter_map(|word| {
+                        let digits = word.strip_prefix("0x").or(word.strip_prefix("0X"))?;
+                        usize::from_str_radix(digits, 16).ok()
+                    })
+                    .collect();
+                if addresses.is_empty() {
+                    return Err(Rich::custom(
+                        span,
+                        "expected at least one hexidecimal address",
+                    ));
+                }
+                Ok(Command::Resolve(addresses))
+            })
+            .boxed();
+
+        let symbolize = op_w_arg(SYMBOLIZE_COMMAND)
+            .ignore_then(any().repeated().at_least(1).to_slice())
+            .map(|path: &str| Command::Symbolize(path.trim().to_string()))
+            .boxed();
+
+        let ptype = op_w_arg(PTYPE_COMMAND)
+            .ignore_then(any().repeated().at_least(1).padded().to_slice())
+            .map(|s: &str| Command::PrintType(s.trim().to_string()))
+            .boxed();
+
         let r#break = op2_w_arg(BREAK_COMMAND, BREAK_COMMAND_SHORT)
             .ignore_then(choice((
                 sub_op2_w_arg(BREAK_REMOVE_SUBCOMMAND, BREAK_REMOVE_SUBCOMMAND_SHORT)
@@ -323,6 +746,65 @@ impl Command {
                     )))
                     .map(|brkpt| Command::Breakpoint(r#break::Command::Remove(brkpt))),
                 sub_op(BREAK_INFO_SUBCOMMAND).to(Command::Breakpoint(r#break::Command::Info)),
+                sub_op_w_arg(BREAK_ENABLE_SUBCOMMAND)
+                    .ignore_then(sub_op_w_arg(BREAK_GROUP_KEY))
+                    .ignore_then(text::int(10).from_str().unwrapped().padded())
+                    .map(|number| Command::Breakpoint(r#break::Command::EnableGroup(number))),
+                sub_op_w_arg(BREAK_ENABLE_SUBCOMMAND)
+                    .ignore_then(text::int(10).from_str().unwrapped().padded())
+                    .map(|number| Command::Breakpoint(r#break::Command::Enable(number))),
+                sub_op_w_arg(BREAK_DISABLE_SUBCOMMAND)
+                    .ignore_then(sub_op_w_arg(BREAK_GROUP_KEY))
+                    .ignore_then(text::int(10).from_str().unwrapped().padded())
+                    .map(|number| Command::Breakpoint(r#break::Command::DisableGroup(number))),
+                sub_op_w_arg(BREAK_DISABLE_SUBCOMMAND)
+                    .ignore_then(text::int(10).from_str().unwrapped().padded())
+                    .map(|number| Command::Breakpoint(r#break::Command::Disable(number))),
+                sub_op_w_arg(BREAK_COMMANDS_SUBCOMMAND)
+                    .ignore_then(text::int(10).from_str().unwrapped().padded())
+                    .then(any().repeated().to_slice())
+                    .map(|(number, commands): (u32, &str)| {
+                        let commands = commands
+                            .split(';')
+                            .map(str::trim)
+                            .filter(|cmd| !cmd.is_empty())
+                            .map(ToString::to_string)
+                            .collect();
+                        Command::Breakpoint(r#break::Command::SetCommands(number, commands))
+                    }),
+                sub_op_w_arg(BREAK_ON_RETURN_KEY)
+                    .ignore_then(
+                        any()
+                            .and_is(just("==").not())
+                            .repeated()
+                            .at_least(1)
+                            .to_slice(),
+                    )
+                    .then(just("==").ignore_then(return_value()).or_not())
+                    .map(|(func_name, value): (&str, _)| {
+                        let condition = value.map_or(ReturnCondition::Any, ReturnCondition::Equal);
+                        Command::Breakpoint(r#break::Command::AddOnReturn(
+                            func_name.trim().to_string(),
+                            condition,
+                        ))
+                    }),
+                sub_op_w_arg(BREAK_REGEX_KEY)
+                    .ignore_then(any().repeated().at_least(1).to_slice())
+                    .map(|pattern: &str| {
+                        let pattern = pattern.trim();
+                        let pattern = ['\'', '"']
+                            .into_iter()
+                            .find_map(|q| pattern.strip_prefix(q)?.strip_suffix(q))
+                            .unwrap_or(pattern);
+                        Command::Breakpoint(r#break::Command::AddRegex(pattern.to_string()))
+                    }),
+                sub_op_w_arg(BREAK_HARDWARE_KEY)
+                    .ignore_then(choice((
+                        brkpt_at_addr_parser(),
+                        brkpt_at_line_parser(),
+                        brkpt_at_fn(),
+                    )))
+                    .map(|brkpt| Command::Breakpoint(r#break::Command::AddHardware(brkpt))),
                 choice((
                     brkpt_at_addr_parser(),
                     brkpt_at_line_parser(),
@@ -332,6 +814,18 @@ impl Command {
             )))
             .boxed();
 
+        let catch = op_w_arg(CATCH_COMMAND)
+            .ignore_then(choice((
+                sub_op(CATCH_THROW_SUBCOMMAND).to(Command::Breakpoint(r#break::Command::Add(
+                    BreakpointIdentity::Catch(CatchEvent::Throw),
+                ))),
+                sub_op(CATCH_CATCH_SUBCOMMAND).to(Command::Breakpoint(r#break::Command::Add(
+                    BreakpointIdentity::Catch(CatchEvent::Catch),
+                ))),
+                sub_op(CATCH_UNWIND_SUBCOMMAND).to(Command::UnwindException),
+            )))
+            .boxed();
+
         let watchpoint = op2_w_arg(WATCH_COMMAND, WATCH_COMMAND_SHORT)
             .ignore_then(choice((
                 sub_op2_w_arg(WATCH_REMOVE_SUBCOMMAND, WATCH_REMOVE_SUBCOMMAND_SHORT)
@@ -357,25 +851,93 @@ impl Command {
             )))
             .boxed();
 
+        let read_watchpoint = op_w_arg(RWATCH_COMMAND)
+            .ignore_then(choice((watchpoint_at_address(), watchpoint_at_dqe())))
+            .map(|ident| Command::Watchpoint(watch::Command::Add(ident, BreakCondition::DataReads)))
+            .boxed();
+
+        let access_watchpoint = op_w_arg(AWATCH_COMMAND)
+            .ignore_then(choice((watchpoint_at_address(), watchpoint_at_dqe())))
+            .map(|ident| {
+                Command::Watchpoint(watch::Command::Add(ident, BreakCondition::DataReadsWrites))
+            })
+            .boxed();
+
+        let memory_file = || {
+            any()
+                .filter(|c: &char| !c.is_whitespace())
+                .repeated()
+                .at_least(1)
+                .to_slice()
+                .padded()
+                .map(ToString::to_string)
+        };
         let memory = op2_w_arg(MEMORY_COMMAND, MEMORY_COMMAND_SHORT)
             .ignore_then(choice((
                 sub_op_w_arg(MEMORY_COMMAND_READ_SUBCOMMAND)
-                    .ignore_then(hex())
+                    .ignore_then(address_location())
                     .map(|addr| Command::Memory(memory::Command::Read(addr))),
                 sub_op_w_arg(MEMORY_COMMAND_WRITE_SUBCOMMAND)
-                    .ignore_then(hex().then(hex()))
+                    .ignore_then(address_location().then(hex()))
                     .map(|(addr, val)| Command::Memory(memory::Command::Write(addr, val))),
+                sub_op_w_arg(MEMORY_COMMAND_SAVE_SUBCOMMAND)
+                    .ignore_then(address_location())
+                    .then(hex().or(text::int(10).from_str().unwrapped().padded()))
+                    .then(memory_file())
+                    .map(|((addr, len), path)| {
+                        Command::Memory(memory::Command::Save(addr, len, path))
+                    }),
+                sub_op_w_arg(MEMORY_COMMAND_LOAD_SUBCOMMAND)
+                    .ignore_then(memory_file())
+                    .then(address_location())
+                    .map(|(path, addr)| Command::Memory(memory::Command::Load(path, addr))),
             )))
             .boxed();
 
+        let examine_spec = just('/').ignore_then(
+            text::int(10)
+                .from_str()
+                .unwrapped()
+                .or_not()
+                .then(one_of("xduotfcsibhwg").repeated().collect::<Vec<char>>()),
+        );
+        let examine = whitespace()
+            .then(just(EXAMINE_COMMAND))
+            .ignore_then(examine_spec.or_not())
+            .then_ignore(ws_req)
+            .then(memory_location())
+            .map(|(spec, location)| {
+                let (count, letters) = spec.unwrap_or_default();
+                let format = letters
+                    .iter()
+                    .rev()
+                    .find_map(|&c| examine::Format::from_char(c));
+                let size = letters
+                    .iter()
+                    .rev()
+                    .find_map(|&c| examine::UnitSize::from_char(c));
+                Command::Examine(examine::Command {
+                    count: count.unwrap_or(1),
+                    format: format.unwrap_or(examine::Format::Hex),
+                    size,
+                    location,
+                })
+            })
+            .boxed();
+
         let register = op2_w_arg(REGISTER_COMMAND, REGISTER_COMMAND_SHORT)
             .ignore_then(choice((
                 sub_op(REGISTER_COMMAND_INFO_SUBCOMMAND)
                     .to(Command::Register(register::Command::Info)),
                 sub_op_w_arg(REGISTER_COMMAND_READ_SUBCOMMAND)
                     .ignore_then(text::ident())
-                    .map(|reg_name| {
-                        Command::Register(register::Command::Read(reg_name.to_string()))
+                    .then(
+                        op_w_arg(REGISTER_AS_KEY)
+                            .ignore_then(lane_format())
+                            .or_not(),
+                    )
+                    .map(|(reg_name, format)| {
+                        Command::Register(register::Command::Read(reg_name.to_string(), format))
                     })
                     .padded(),
                 sub_op_w_arg(REGISTER_COMMAND_WRITE_SUBCOMMAND)
@@ -387,6 +949,16 @@ impl Command {
                         ))
                     })
                     .padded(),
+                text::ident()
+                    .then_ignore(op_w_arg(REGISTER_AS_KEY))
+                    .then(lane_format())
+                    .map(|(reg_name, format): (&str, _)| {
+                        Command::Register(register::Command::Read(
+                            reg_name.to_string(),
+                            Some(format),
+                        ))
+                    })
+                    .padded(),
             )))
             .boxed();
 
@@ -404,6 +976,25 @@ impl Command {
             )))
             .boxed();
 
+        let inferior = op_w_arg(INFERIOR_COMMAND)
+            .ignore_then(choice((
+                sub_op(INFERIOR_COMMAND_LIST_SUBCOMMAND)
+                    .to(Command::Inferior(inferior::Command::List)),
+                sub_op_w_arg(INFERIOR_COMMAND_SWITCH_SUBCOMMAND)
+                    .ignore_then(text::int(10))
+                    .from_str()
+                    .unwrapped()
+                    .map(|num| Command::Inferior(inferior::Command::Switch(num)))
+                    .padded(),
+                sub_op_w_arg(INFERIOR_COMMAND_ATTACH_SUBCOMMAND)
+                    .ignore_then(text::int(10))
+                    .from_str()
+                    .unwrapped()
+                    .map(|pid| Command::Inferior(inferior::Command::Attach(pid)))
+                    .padded(),
+            )))
+            .boxed();
+
         let frame = op2_w_arg(FRAME_COMMAND, FRAME_COMMAND_SHORT)
             .ignore_then(choice((
                 sub_op(FRAME_COMMAND_INFO_SUBCOMMAND).to(Command::Frame(frame::Command::Info)),
@@ -411,14 +1002,37 @@ impl Command {
                     .ignore_then(text::int(10).from_str().unwrapped())
                     .map(|num| Command::Frame(frame::Command::Switch(num)))
                     .padded(),
+                sub_op(FRAME_COMMAND_MEMORY_SUBCOMMAND).to(Command::Frame(frame::Command::Memory)),
             )))
             .boxed();
 
+        let info = op_w_arg(INFO_COMMAND)
+            .ignore_then(sub_op(INFO_LINE_SUBCOMMAND))
+            .ignore_then(line_info_location())
+            .map(|location| Command::Info(info::Command::Line(location)))
+            .boxed();
+
         let shared_lib = op_w_arg(SHARED_LIB_COMMAND)
             .then(sub_op(SHARED_LIB_COMMAND_INFO_SUBCOMMAND))
             .to(Command::SharedLib)
             .boxed();
 
+        let deadlock = op_w_arg(DEADLOCK_COMMAND)
+            .then(sub_op(DEADLOCK_CHECK_SUBCOMMAND))
+            .to(Command::DeadlockCheck)
+            .boxed();
+
+        let history = op_w_arg(HISTORY_COMMAND)
+            .ignore_then(choice((
+                sub_op(HISTORY_VALUES_SUBCOMMAND).to(Command::ValueHistory),
+                sub_op(HISTORY_STEPS_SUBCOMMAND)
+                    .ignore_then(sub_op(HISTORY_STEPS_ALL_SUBCOMMAND).or_not())
+                    .map(|all| Command::StepHistory {
+                        all_threads: all.is_some(),
+                    }),
+            )))
+            .boxed();
+
         let oracle = op_w_arg(ORACLE_COMMAND)
             .ignore_then(text::ident().padded().then(text::ident().or_not()))
             .map(|(name, subcmd)| {
@@ -427,36 +1041,278 @@ impl Command {
             .padded()
             .boxed();
 
-        choice((
-            command(VAR_COMMAND, print_variables),
-            command(ARG_COMMAND, print_arguments),
+        let script = op_w_arg(SCRIPT_COMMAND)
+            .ignore_then(any().repeated().at_least(1).to_slice())
+            .map(|path: &str| Command::Script(path.trim().to_string()))
+            .boxed();
+
+        let file_path = || {
+            any()
+                .repeated()
+                .at_least(1)
+                .to_slice()
+                .map(|path: &str| path.trim().to_string())
+        };
+        let session = op_w_arg(SESSION_COMMAND)
+            .ignore_then(choice((
+                sub_op_w_arg(SESSION_SAVE_SUBCOMMAND)
+                    .ignore_then(file_path())
+                    .map(|path| Command::Session(session::Command::Save(path))),
+                sub_op_w_arg(SESSION_LOAD_SUBCOMMAND)
+                    .ignore_then(file_path())
+                    .map(|path| Command::Session(session::Command::Load(path))),
+            )))
+            .boxed();
+
+        let trace = op_w_arg(TRACE_COMMAND)
+            .ignore_then(choice((
+                sub_op(TRACE_REPORT_SUBCOMMAND).to(Command::Trace(trace::Command::Report)),
+                tracepoint_place()
+                    .then(tracepoint_expressions())
+                    .map(|(place, exprs)| Command::Trace(trace::Command::Add(place, exprs))),
+            )))
+            .boxed();
+
+        let profile_report_kind = choice((
+            sub_op(PROFILE_REPORT_FLAT).to(profile::ReportKind::Flat),
+            sub_op(PROFILE_REPORT_TREE).to(profile::ReportKind::Tree),
+        ))
+        .or_not()
+        .map(|kind| kind.unwrap_or(profile::ReportKind::Flat));
+        let profile = op_w_arg(PROFILE_COMMAND)
+            .ignore_then(choice((
+                sub_op(PROFILE_START_SUBCOMMAND)
+                    .ignore_then(duration().or_not())
+                    .map(|interval| Command::Profile(profile::Command::Start(interval))),
+                sub_op(PROFILE_STOP_SUBCOMMAND).to(Command::Profile(profile::Command::Stop)),
+                sub_op(PROFILE_REPORT_SUBCOMMAND)
+                    .ignore_then(profile_report_kind)
+                    .map(|kind| Command::Profile(profile::Command::Report(kind))),
+                sub_op_w_arg(PROFILE_EXPORT_SUBCOMMAND)
+                    .ignore_then(file_path())
+                    .map(|path| Command::Profile(profile::Command::Export(path))),
+            )))
+            .boxed();
+
+        let dprintf = op_w_arg(DPRINTF_COMMAND)
+            .ignore_then(tracepoint_place())
+            .then(format_string())
+            .then(tracepoint_expressions())
+            .map(|((place, format), exprs)| {
+                Command::Trace(trace::Command::AddPrintf(place, format, exprs))
+            })
+            .boxed();
+
+        let ftrace = op_w_arg(FTRACE_COMMAND)
+            .ignore_then(choice((
+                sub_op(FTRACE_STOP_SUBCOMMAND).to(Command::Ftrace(ftrace::Command::Stop)),
+                any()
+                    .filter(|c: &char| !c.is_whitespace())
+                    .repeated()
+                    .at_least(1)
+                    .to_slice()
+                    .padded()
+                    .map(|pattern: &str| {
+                        Command::Ftrace(ftrace::Command::Start(pattern.to_string()))
+                    }),
+            )))
+            .boxed();
+
+        let coverage_file = any()
+            .filter(|c: &char| !c.is_whitespace())
+            .repeated()
+            .at_least(1)
+            .to_slice()
+            .map(ToString::to_string);
+        let coverage = op_w_arg(COVERAGE_COMMAND)
+            .ignore_then(choice((
+                sub_op_w_arg(COVERAGE_START_SUBCOMMAND)
+                    .ignore_then(coverage_file.padded().repeated().at_least(1).collect())
+                    .map(|files| Command::Coverage(coverage::Command::Start(files))),
+                sub_op(COVERAGE_STOP_SUBCOMMAND).to(Command::Coverage(coverage::Command::Stop)),
+                sub_op(COVERAGE_REPORT_SUBCOMMAND)
+                    .ignore_then(file_path().or_not())
+                    .map(|path| Command::Coverage(coverage::Command::Report(path))),
+            )))
+            .boxed();
+
+        let display = choice((
+            op_w_arg(DISPLAY_COMMAND)
+                .ignore_then(any().repeated().to_slice().rewind())
+                .then(expression::parser().padded())
+                .map(|(source, dqe): (&str, _)| {
+                    Command::Display(display::Command::Add(source.trim().to_string(), dqe))
+                }),
+            op(DISPLAY_COMMAND).to(Command::Display(display::Command::Info)),
+        ))
+        .boxed();
+
+        let snapshot = op_w_arg(SNAPSHOT_COMMAND)
+            .ignore_then(any().repeated().to_slice().rewind())
+            .then(expression::parser().padded())
+            .map(|(source, dqe): (&str, _)| {
+                Command::Snapshot(snapshot::Command::Take(source.trim().to_string(), dqe))
+            })
+            .boxed();
+
+        let diff = op_w_arg(DIFF_COMMAND)
+            .ignore_then(any().repeated().to_slice().rewind())
+            .then(expression::parser().padded())
+            .map(|(source, dqe): (&str, _)| {
+                Command::Snapshot(snapshot::Command::Diff(source.trim().to_string(), dqe))
+            })
+            .boxed();
+
+        let undisplay = op_w_arg(UNDISPLAY_COMMAND)
+            .ignore_then(text::int(10).from_str().unwrapped().padded())
+            .map(|number: u32| Command::Display(display::Command::Remove(number)))
+            .boxed();
+
+        let demangle_style = choice((
+            sub_op(DEMANGLE_STYLE_FULL).to(DemangleStyle::full()),
+            sub_op(DEMANGLE_STYLE_DEFAULT).to(DemangleStyle::default()),
+            just(DEMANGLE_STYLE_SHORT)
+                .ignore_then(text::int(10).from_str().unwrapped().padded().or_not())
+                .map(|depth: Option<usize>| {
+                    DemangleStyle::short(depth.unwrap_or(DemangleStyle::SHORT_PATH_DEPTH))
+                }),
+        ))
+        .labelled("demangle style");
+        let limit = choice((
+            sub_op(LIMIT_UNLIMITED).to(None),
+            text::int(10).from_str().unwrapped().padded().map(Some),
+        ))
+        .labelled("limit");
+        let switch = choice((sub_op(SWITCH_ON).to(true), sub_op(SWITCH_OFF).to(false)))
+            .labelled("on or off");
+        let print_setting = choice((
+            sub_op_w_arg(SET_DEMANGLE_STYLE_KEY)
+                .ignore_then(demangle_style)
+                .map(set::Command::PrintDemangleStyle),
+            sub_op_w_arg(SET_ELEMENTS_KEY)
+                .ignore_then(limit)
+                .map(set::Command::PrintElements),
+            sub_op_w_arg(SET_DEPTH_KEY)
+                .ignore_then(limit)
+                .map(set::Command::PrintDepth),
+            sub_op_w_arg(SET_STRING_LENGTH_KEY)
+                .ignore_then(limit)
+                .map(set::Command::PrintStringLength),
+            sub_op_w_arg(SET_POINTER_DEPTH_KEY)
+                .ignore_then(limit)
+                .map(set::Command::PrintPointerDepth),
+            sub_op_w_arg(SET_BYTES_AS_ASCII_KEY)
+                .ignore_then(switch)
+                .map(set::Command::PrintBytesAsAscii),
+        ));
+        let quit_action = choice((
+            sub_op(QUIT_ACTION_ASK).to(quit::Action::Ask),
+            sub_op(QUIT_ACTION_KILL).to(quit::Action::Kill),
+            sub_op(QUIT_ACTION_DETACH).to(quit::Action::Detach),
+        ))
+        .labelled("quit action");
+        let set = op_w_arg(SET_COMMAND)
+            .ignore_then(choice((
+                sub_op_w_arg(SET_PRINT_SUBCOMMAND).ignore_then(print_setting),
+                sub_op_w_arg(SET_QUIT_ACTION_SUBCOMMAND)
+                    .ignore_then(quit_action)
+                    .map(set::Command::QuitAction),
+                sub_op_w_arg(SET_LISTSIZE_SUBCOMMAND)
+                    .ignore_then(
+                        text::int(10)
+                            .from_str()
+                            .unwrapped()
+                            .padded()
+                            .labelled("number of lines"),
+                    )
+                    .map(set::Command::ListSize),
+            )))
+            .map(Command::Set)
+            .boxed();
+
+        // tuple size of `choice` is limited, so commands are split into groups
+        let execution = choice((
             command(CONTINUE_COMMAND, r#continue),
             command(RUN_COMMAND, run),
+            command(KILL_COMMAND, kill),
+            command(QUIT_COMMAND, quit),
             command(STEP_INSTRUCTION_COMMAND, stepi),
             command(STEP_INTO_COMMAND, step_into),
             command(STEP_OUT_COMMAND, step_out),
             command(STEP_OVER_COMMAND, step_over),
+            command(RETURN_COMMAND, r#return),
+            command(JUMP_COMMAND, jump),
+        ));
+
+        choice((
+            execution,
+            command(VAR_COMMAND, print_variables),
+            command(ARG_COMMAND, print_arguments),
             command(SOURCE_COMMAND, source_code),
             command(HELP_COMMAND, help),
             command(BACKTRACE_COMMAND, backtrace),
-            command(SYMBOL_COMMAND, symbol),
-            command(BREAK_COMMAND, r#break),
-            command(MEMORY_COMMAND, memory),
+            choice((
+                command(SYMBOL_COMMAND, symbol),
+                command(RESOLVE_COMMAND, resolve),
+                command(SYMBOLIZE_COMMAND, symbolize),
+                command(PTYPE_COMMAND, ptype),
+            )),
+            choice((
+                command(BREAK_COMMAND, r#break),
+                command(CATCH_COMMAND, catch),
+            )),
+            choice((
+                command(MEMORY_COMMAND, memory),
+                command(EXAMINE_COMMAND, examine),
+            )),
             command(REGISTER_COMMAND, register),
-            command(THREAD_COMMAND, thread),
+            choice((
+                command(THREAD_COMMAND, thread),
+                command(INFERIOR_COMMAND, inferior),
+            )),
             command(FRAME_COMMAND, frame),
-            command(SHARED_LIB_COMMAND, shared_lib),
+            choice((
+                command(SHARED_LIB_COMMAND, shared_lib),
+                command(DEADLOCK_COMMAND, deadlock),
+                command(HISTORY_COMMAND, history),
+                command(INFO_COMMAND, info),
+            )),
             command(ORACLE_COMMAND, oracle),
-            command(WATCH_COMMAND, watchpoint),
+            choice((
+                command(WATCH_COMMAND, watchpoint),
+                command(RWATCH_COMMAND, read_watchpoint),
+                command(AWATCH_COMMAND, access_watchpoint),
+            )),
+            command(SCRIPT_COMMAND, script),
+            command(SESSION_COMMAND, session),
+            choice((
+                command(DISPLAY_COMMAND, display),
+                command(UNDISPLAY_COMMAND, undisplay),
+                command(SNAPSHOT_COMMAND, snapshot),
+                command(DIFF_COMMAND, diff),
+            )),
+            command(SET_COMMAND, set),
+            choice((
+                command(TRACE_COMMAND, trace),
+                command(DPRINTF_COMMAND, dprintf),
+                command(FTRACE_COMMAND, ftrace),
+                command(COVERAGE_COMMAND, coverage),
+            )),
+            command(PROFILE_COMMAND, profile),
         ))
     }
 
-    /// Parse input string into command.
+    /// Parse input string into command, abbreviated command names are expanded before parsing.
     pub fn parse(input: &str) -> CommandResult<Command> {
-        Self::parser()
-            .parse(input)
+        let input = abbreviation::resolve(input).map_err(|e| {
+            let error = Rich::custom(e.span.into(), e.message);
+            CommandError::Parsing(Self::render_errors(input, vec![error]))
+        })?;
+        let command = Self::parser()
+            .parse(&input)
             .into_result()
-            .map_err(|e| CommandError::Parsing(Self::render_errors(input, e)))
+            .map_err(|e| CommandError::Parsing(Self::render_errors(&input, e)));
+        command
     }
 }
 
@@ -491,6 +1347,23 @@ fn test_hex_parser() {
     }
 }
 
+#[test]
+fn test_memory_location_parser() {
+    let location = memory_location().parse(" 0x7FFF10 ").into_result();
+    assert!(matches!(location, Ok(memory::Location::Address(0x7fff10))));
+
+    let location = memory_location().parse("*var.field").into_result();
+    assert!(matches!(
+        location,
+        Ok(memory::Location::Expression(DQE::Deref(_)))
+    ));
+
+    let location = memory_location().parse("$rsp").into_result();
+    assert!(matches!(location, Ok(memory::Location::Register(r)) if r == "rsp"));
+
+    assert!(memory_location().parse("0x").into_result().is_err());
+}
+
 #[test]
 fn test_rust_identifier_parser() {
     struct TestCase {
@@ -565,80 +1438,199 @@ fn test_parser() {
             },
         },
         TestCase {
-            inputs: vec!["var ("],
-            command_matcher: |result| assert!(result.is_err()),
-        },
-        TestCase {
-            inputs: vec!["das"],
-            command_matcher: |result| assert!(result.is_err()),
-        },
-        TestCase {
-            inputs: vec!["voo"],
-            command_matcher: |result| assert!(result.is_err()),
-        },
-        TestCase {
-            inputs: vec!["arg 11"],
-            command_matcher: |result| assert!(result.is_err()),
-        },
-        TestCase {
-            inputs: vec!["br", "arglocals"],
-            command_matcher: |result| assert!(result.is_err()),
-        },
-        TestCase {
-            inputs: vec!["arg all"],
+            inputs: vec!["var *", " var  * "],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
-                    Command::PrintArguments(DQE::Variable(VariableSelector::Any))
+                    Command::PrintVariables(DQE::Variable(VariableSelector::Any))
                 ));
             },
         },
         TestCase {
-            inputs: vec!["arg all_arg"],
+            inputs: vec!["var foo*", "var  foo* "],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
-                    Command::PrintArguments(DQE::Variable(VariableSelector::Name { var_name, .. })) if var_name == "all_arg"
+                    Command::PrintVariables(DQE::Variable(VariableSelector::Pattern(pattern))) if pattern == "foo*"
                 ));
             },
         },
         TestCase {
-            inputs: vec!["bt", "backtrace"],
+            inputs: vec!["var --json *", "var --json locals "],
             command_matcher: |result| {
-                let cmd = result.unwrap();
                 assert!(matches!(
-                    cmd,
-                    Command::PrintBacktrace(super::backtrace::Command::CurrentThread)
+                    result.unwrap(),
+                    Command::PrintVariablesJson(DQE::Variable(VariableSelector::Any), None)
                 ));
             },
         },
         TestCase {
-            inputs: vec!["bt all", "backtrace  all  "],
+            inputs: vec![
+                "var --json * > vars.json",
+                " var --json  locals  >  vars.json ",
+            ],
             command_matcher: |result| {
-                let cmd = result.unwrap();
                 assert!(matches!(
-                    cmd,
-                    Command::PrintBacktrace(super::backtrace::Command::All)
+                    result.unwrap(),
+                    Command::PrintVariablesJson(DQE::Variable(VariableSelector::Any), Some(path)) if path.as_os_str() == "vars.json"
                 ));
             },
         },
         TestCase {
-            inputs: vec!["c", "continue"],
+            inputs: vec!["var --json foo*", "var --json foo* > /tmp/foo.json"],
             command_matcher: |result| {
-                assert!(matches!(result.unwrap(), Command::Continue));
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::PrintVariablesJson(DQE::Variable(VariableSelector::Pattern(pattern)), _) if pattern == "foo*"
+                ));
             },
         },
         TestCase {
-            inputs: vec!["frame info ", "  frame  info"],
+            inputs: vec!["var --json a.b[1]", "var --json *ptr > ptr.json"],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
-                    Command::Frame(frame::Command::Info)
+                    Command::PrintVariablesJson(DQE::Index(..) | DQE::Deref(_), _)
                 ));
             },
         },
         TestCase {
-            inputs: vec!["f info ", "  f  info"],
+            inputs: vec!["var *ptr"],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::PrintVariables(DQE::Deref(_))
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["arg *", "arg a?g*"],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::PrintArguments(DQE::Variable(
+                        VariableSelector::Any | VariableSelector::Pattern(_)
+                    ))
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["var ("],
+            command_matcher: |result| assert!(result.is_err()),
+        },
+        TestCase {
+            inputs: vec!["das"],
+            command_matcher: |result| assert!(result.is_err()),
+        },
+        TestCase {
+            inputs: vec!["voo"],
+            command_matcher: |result| assert!(result.is_err()),
+        },
+        TestCase {
+            inputs: vec!["arg 11"],
+            command_matcher: |result| assert!(result.is_err()),
+        },
+        TestCase {
+            inputs: vec!["br", "arglocals"],
+            command_matcher: |result| assert!(result.is_err()),
+        },
+        TestCase {
+            inputs: vec!["arg all"],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::PrintArguments(DQE::Variable(VariableSelector::Any))
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["arg all_arg"],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::PrintArguments(DQE::Variable(VariableSelector::Name { var_name, .. })) if var_name == "all_arg"
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["bt", "backtrace"],
+            command_matcher: |result| {
+                let cmd = result.unwrap();
+                assert!(matches!(
+                    cmd,
+                    Command::PrintBacktrace(super::backtrace::Command::CurrentThread {
+                        args: false
+                    })
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["bt all", "backtrace  all  "],
+            command_matcher: |result| {
+                let cmd = result.unwrap();
+                assert!(matches!(
+                    cmd,
+                    Command::PrintBacktrace(super::backtrace::Command::All { args: false })
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["bt --args", "bt all --args", "  backtrace  all  --args "],
+            command_matcher: |result| {
+                let cmd = result.unwrap();
+                assert!(matches!(
+                    cmd,
+                    Command::PrintBacktrace(
+                        super::backtrace::Command::CurrentThread { args: true }
+                            | super::backtrace::Command::All { args: true }
+                    )
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["c", "continue"],
+            command_matcher: |result| {
+                assert!(matches!(result.unwrap(), Command::Continue(None)));
+            },
+        },
+        TestCase {
+            inputs: vec!["c --for 5s", "continue  --for  5s "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Continue(Some(d)) if d == Duration::from_secs(5)
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["continue --for 500ms"],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Continue(Some(d)) if d == Duration::from_millis(500)
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["c --for 2m"],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Continue(Some(d)) if d == Duration::from_secs(120)
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["frame info ", "  frame  info"],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Frame(frame::Command::Info)
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["f info ", "  f  info"],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
@@ -647,348 +1639,1520 @@ fn test_parser() {
             },
         },
         TestCase {
-            inputs: vec!["frame switch 1", "  frame  switch   1 "],
+            inputs: vec!["frame memory", "  f  memory "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Frame(frame::Command::Memory)
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["frame switch 1", "  frame  switch   1 "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Frame(frame::Command::Switch(1))
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["r", "run"],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Run(run::Options {
+                        stop_at: None,
+                        stdin: None
+                    })
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["r --stop-at-entry", "  run   --stop-at-entry  "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Run(run::Options {
+                        stop_at: Some(StopAt::Entry),
+                        stdin: None
+                    })
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["r --stop-at-main", "run --stop-at-main"],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Run(run::Options {
+                        stop_at: Some(StopAt::Main),
+                        stdin: None
+                    })
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec![
+                "run < input.txt",
+                "r <input.txt",
+                "run --stdin input.txt",
+                "  run   --stdin   input.txt  ",
+            ],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Run(run::Options { stop_at: None, stdin: Some(path) })
+                        if path.as_os_str() == "input.txt"
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["kill", "  kill  "],
+            command_matcher: |result| {
+                assert!(matches!(result.unwrap(), Command::Kill));
+            },
+        },
+        TestCase {
+            inputs: vec!["quit", "q", "  quit  "],
+            command_matcher: |result| {
+                assert!(matches!(result.unwrap(), Command::Quit(None)));
+            },
+        },
+        TestCase {
+            inputs: vec!["quit --kill", "q --kill"],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Quit(Some(quit::Action::Kill))
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["quit --detach", " q  --detach "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Quit(Some(quit::Action::Detach))
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["quit --force", "quit kill", "quitt"],
+            command_matcher: |result| assert!(result.is_err()),
+        },
+        TestCase {
+            inputs: vec!["kill 1", "killall"],
+            command_matcher: |result| assert!(result.is_err()),
+        },
+        TestCase {
+            inputs: vec![
+                "run --stop-at-main < /tmp/in",
+                "r --stop-at-main --stdin /tmp/in",
+            ],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Run(run::Options { stop_at: Some(StopAt::Main), stdin: Some(path) })
+                        if path.as_os_str() == "/tmp/in"
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec![
+                "run --stop-at",
+                "run main",
+                "run <",
+                "run --stdin",
+                "run < a b",
+            ],
+            command_matcher: |result| assert!(result.is_err()),
+        },
+        TestCase {
+            inputs: vec!["symbol main", " symbol  main "],
+            command_matcher: |result| {
+                assert!(matches!(result.unwrap(), Command::PrintSymbol(s) if s == "main"));
+            },
+        },
+        TestCase {
+            inputs: vec!["resolve 0x100 0X1F", " resolve  0x100,0x1f "],
+            command_matcher: |result| {
+                assert!(
+                    matches!(result.unwrap(), Command::Resolve(addrs) if addrs == vec![0x100, 0x1F])
+                );
+            },
+        },
+        TestCase {
+            inputs: vec!["resolve   3: 0x55d4c5a0f1b3 - std::rt::lang_start::h0c8d"],
+            command_matcher: |result| {
+                assert!(
+                    matches!(result.unwrap(), Command::Resolve(addrs) if addrs == vec![0x55d4c5a0f1b3])
+                );
+            },
+        },
+        TestCase {
+            inputs: vec!["resolve", "resolve main"],
+            command_matcher: |result| assert!(result.is_err()),
+        },
+        TestCase {
+            inputs: vec!["info line", " info  line  "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Info(info::Command::Line(LineInfoLocation::Current))
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["info line main.rs:12", " info line  main.rs:12 "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Info(info::Command::Line(LineInfoLocation::Line(file, 12)))
+                        if file == "main.rs"
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["info line 0x100"],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Info(info::Command::Line(LineInfoLocation::Address(0x100)))
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["info line calc::sum"],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Info(info::Command::Line(LineInfoLocation::Function(name)))
+                        if name == "calc::sum"
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["info", "info lines", "info line main.rs:"],
+            command_matcher: |result| assert!(result.is_err()),
+        },
+        TestCase {
+            inputs: vec!["symbolize /tmp/bt.txt", " symbolize  /tmp/bt.txt "],
+            command_matcher: |result| {
+                assert!(matches!(result.unwrap(), Command::Symbolize(p) if p == "/tmp/bt.txt"));
+            },
+        },
+        TestCase {
+            inputs: vec!["ptype foo", " ptype  foo "],
+            command_matcher: |result| {
+                assert!(matches!(result.unwrap(), Command::PrintType(s) if s == "foo"));
+            },
+        },
+        TestCase {
+            inputs: vec!["ptype alloc::vec::Vec<i32>"],
+            command_matcher: |result| {
+                assert!(
+                    matches!(result.unwrap(), Command::PrintType(s) if s == "alloc::vec::Vec<i32>")
+                );
+            },
+        },
+        TestCase {
+            inputs: vec!["  stepi"],
+            command_matcher: |result| {
+                assert!(matches!(result.unwrap(), Command::StepInstruction(1)));
+            },
+        },
+        TestCase {
+            inputs: vec!["stepi 100", " stepi  100 "],
+            command_matcher: |result| {
+                assert!(matches!(result.unwrap(), Command::StepInstruction(100)));
+            },
+        },
+        TestCase {
+            inputs: vec!["step", "stepinto"],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::StepInto(1, StepGranularity::Line)
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["step 10", "stepinto 10"],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::StepInto(10, StepGranularity::Line)
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["step stmt", "stepinto stmt 1"],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::StepInto(1, StepGranularity::Statement)
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["finish", "stepout"],
+            command_matcher: |result| {
+                assert!(matches!(result.unwrap(), Command::StepOut(1)));
+            },
+        },
+        TestCase {
+            inputs: vec!["finish 2", "stepout 2"],
+            command_matcher: |result| {
+                assert!(matches!(result.unwrap(), Command::StepOut(2)));
+            },
+        },
+        TestCase {
+            inputs: vec!["next", "stepover"],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::StepOver(1, StepGranularity::Line)
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["next stmt 3", "stepover stmt 3"],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::StepOver(3, StepGranularity::Statement)
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["b some_func", "break some_func", "   break some_func   "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Breakpoint(r#break::Command::Add(BreakpointIdentity::Function(f))) if f == "some_func"
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["b rust_fn", "b info_rust"],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Breakpoint(r#break::Command::Add(BreakpointIdentity::Function(f))) if f == "rust_fn" || f == "info_rust"
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["b file:123", "break file:123", "   break file:123   "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Breakpoint(r#break::Command::Add(BreakpointIdentity::Line(f, n))) if f == "file" && n == 123
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["b src/mod.rs:17", "break src/mod.rs:17"],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Breakpoint(r#break::Command::Add(BreakpointIdentity::Line(f, n))) if f == "src/mod.rs" && n == 17
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec![
+                "b main.rs:42:17",
+                "break main.rs:42:17",
+                " break  main.rs:42:17 ",
+            ],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Breakpoint(r#break::Command::Add(BreakpointIdentity::Column(f, l, c))) if f == "main.rs" && l == 42 && c == 17
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["b :42:5"],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Breakpoint(r#break::Command::Add(BreakpointIdentity::Column(f, l, c))) if f.is_empty() && l == 42 && c == 5
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["b :42", "break :42", "  break  :42  "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Breakpoint(r#break::Command::Add(BreakpointIdentity::Line(f, n))) if f.is_empty() && n == 42
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["b 0x123", "break 0x123", "   break 0x0123   "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Breakpoint(r#break::Command::Add(BreakpointIdentity::Address(a))) if a == 0x123
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec![
+                "b r some_func",
+                "break r some_func",
+                "   break r  some_func   ",
+            ],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Breakpoint(r#break::Command::Remove(BreakpointIdentity::Function(f))) if f == "some_func"
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec![
+                "b r ns1::some_func",
+                "break r ns1::some_func",
+                "   break r  ns1::some_func   ",
+            ],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Breakpoint(r#break::Command::Remove(BreakpointIdentity::Function(f))) if f == "ns1::some_func"
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec![
+                "b remove file:123",
+                "break r file:123",
+                "   break  remove file:123   ",
+            ],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Breakpoint(r#break::Command::Remove(BreakpointIdentity::Line(f, n))) if f == "file" && n == 123
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["b remove file:123:9", "break r file:123:9"],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Breakpoint(r#break::Command::Remove(BreakpointIdentity::Column(f, l, c))) if f == "file" && l == 123 && c == 9
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["b remove 0x123", "break r 0x123", "   break r 0x123   "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Breakpoint(r#break::Command::Remove(BreakpointIdentity::Address(a))) if a == 0x123
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["b info", "break info ", "   break   info   "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Breakpoint(r#break::Command::Info)
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["b enable 1", "break enable 1 ", "   break   enable  1  "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Breakpoint(r#break::Command::Enable(1))
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["b disable 2", "break disable 2 ", "   break   disable  2  "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Breakpoint(r#break::Command::Disable(2))
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec![
+                "b enable --group 1",
+                "break enable --group 1 ",
+                "   break   enable  --group  1  ",
+            ],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Breakpoint(r#break::Command::EnableGroup(1))
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["b disable --group 2", "break disable --group 2 "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Breakpoint(r#break::Command::DisableGroup(2))
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec![
+                "break commands 1 var a; bt ;continue",
+                " b  commands  1  var a;bt; continue; ",
+            ],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Breakpoint(r#break::Command::SetCommands(1, commands))
+                        if commands == ["var a", "bt", "continue"]
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["break commands 2", "break commands 2 ;"],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Breakpoint(r#break::Command::SetCommands(2, commands))
+                        if commands.is_empty()
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["catch throw", "  catch   throw  "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Breakpoint(r#break::Command::Add(BreakpointIdentity::Catch(
+                        CatchEvent::Throw
+                    )))
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["catch catch"],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Breakpoint(r#break::Command::Add(BreakpointIdentity::Catch(
+                        CatchEvent::Catch
+                    )))
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["catch unwind", " catch unwind "],
+            command_matcher: |result| {
+                assert!(matches!(result.unwrap(), Command::UnwindException));
+            },
+        },
+        TestCase {
+            inputs: vec!["catch", "catch rethrow", "catch throw 1"],
+            command_matcher: |result| assert!(result.is_err()),
+        },
+        TestCase {
+            inputs: vec!["break --hardware main.rs:15", " b  --hardware  main.rs:15 "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Breakpoint(r#break::Command::AddHardware(BreakpointIdentity::Line(file, 15)))
+                        if file == "main.rs"
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["break --hardware 0x123", "b --hardware calc::sum"],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Breakpoint(r#break::Command::AddHardware(
+                        BreakpointIdentity::Address(0x123) | BreakpointIdentity::Function(_)
+                    ))
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["break --on-return read", " b  --on-return  read "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Breakpoint(r#break::Command::AddOnReturn(f, ReturnCondition::Any))
+                        if f == "read"
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec![
+                "break --on-return std::io::read == -1",
+                "b --on-return std::io::read==-1",
+            ],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Breakpoint(r#break::Command::AddOnReturn(
+                        f,
+                        ReturnCondition::Equal(ReturnValue::Int(-1))
+                    )) if f == "std::io::read"
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["break --on-return sum == 1.5"],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Breakpoint(r#break::Command::AddOnReturn(
+                        f,
+                        ReturnCondition::Equal(ReturnValue::Float(v))
+                    )) if f == "sum" && v == 1.5
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec![
+                "b --regex ^calc::sum.*",
+                "break --regex '^calc::sum.*'",
+                "   break --regex \"^calc::sum.*\"  ",
+            ],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Breakpoint(r#break::Command::AddRegex(p)) if p == "^calc::sum.*"
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["watch var1", "watch var1 ", "   w   var1   "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Watchpoint(watch::Command::Add(
+                        WatchpointIdentity::DQE(source, DQE::Variable(VariableSelector::Name {var_name, ..})), BreakCondition::DataWrites
+                    )) if var_name == "var1" && source == "var1"
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["watch +rw var1", "watch +rw  var1 ", "   w  +rw  var1   "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Watchpoint(watch::Command::Add(
+                        WatchpointIdentity::DQE(source, DQE::Variable(VariableSelector::Name {var_name, ..})), BreakCondition::DataReadsWrites
+                    )) if var_name == "var1" && source == "var1"
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec![
+                "watch ns1::ns2::var1",
+                "watch ns1::ns2::var1 ",
+                "   w   ns1::ns2::var1   ",
+            ],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Watchpoint(watch::Command::Add(
+                        WatchpointIdentity::DQE(source, DQE::Variable(VariableSelector::Name {var_name, ..})), BreakCondition::DataWrites
+                    )) if var_name == "ns1::ns2::var1" && source == "ns1::ns2::var1"
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["watch 0x123:4", "watch 0x123:4 ", "   w   0x123:4   "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Watchpoint(watch::Command::Add(WatchpointIdentity::Address(addr, size), BreakCondition::DataWrites)) if addr == 0x123 && size == 4
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["watch info", "watch info ", "   w   info   "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Watchpoint(watch::Command::Info)
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["watch r var1", "watch remove var1 ", "   w   r var1   "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Watchpoint(watch::Command::Remove(WatchpointIdentity::DQE(source, DQE::Variable(VariableSelector::Name {var_name, ..})))) if var_name == "var1" && source == "var1"
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["watch r 2", "watch remove 2 ", "   w   r 2   "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Watchpoint(watch::Command::Remove(WatchpointIdentity::Number(n))) if n == 2
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["rwatch var1", "rwatch var1 ", "   rwatch   var1   "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Watchpoint(watch::Command::Add(
+                        WatchpointIdentity::DQE(source, DQE::Variable(VariableSelector::Name {var_name, ..})), BreakCondition::DataReads
+                    )) if var_name == "var1" && source == "var1"
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["watch +r var1", "watch +r var1 ", "   w +r  var1   "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Watchpoint(watch::Command::Add(
+                        WatchpointIdentity::DQE(source, DQE::Variable(VariableSelector::Name {var_name, ..})), BreakCondition::DataReads
+                    )) if var_name == "var1" && source == "var1"
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec![
+                "awatch 0x123:4",
+                "awatch 0x123:4 ",
+                "   awatch   0x123:4   ",
+            ],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Watchpoint(watch::Command::Add(WatchpointIdentity::Address(addr, size), BreakCondition::DataReadsWrites)) if addr == 0x123 && size == 4
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["x/8xg $rsp", "  x/8gx   $rsp ", "x/8g $rsp"],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Examine(examine::Command {
+                        count: 8,
+                        format: examine::Format::Hex,
+                        size: Some(examine::UnitSize::G),
+                        location: memory::Location::Register(reg),
+                    }) if reg == "rsp"
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["x/s 0x7fff10", "x/1s 0x7fff10 "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Examine(examine::Command {
+                        count: 1,
+                        format: examine::Format::String,
+                        size: None,
+                        location: memory::Location::Address(0x7fff10),
+                    })
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["x var1", "x/ var1", "  x   var1 "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Examine(examine::Command {
+                        count: 1,
+                        format: examine::Format::Hex,
+                        size: None,
+                        location: memory::Location::Expression(DQE::Variable(VariableSelector::Name {var_name, ..})),
+                    }) if var_name == "var1"
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["x/4i $rip"],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Examine(examine::Command {
+                        count: 4,
+                        format: examine::Format::Instruction,
+                        ..
+                    })
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["x/8z $rsp", "x/8xg", "x8 $rsp"],
+            command_matcher: |result| assert!(result.is_err()),
+        },
+        TestCase {
+            inputs: vec![
+                "mem read 0x123",
+                "memory read 0x123",
+                "   mem read   0x123   ",
+            ],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Memory(memory::Command::Read(memory::Location::Address(a))) if a == 0x123
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["mem read $rsp", "memory read  $rsp "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Memory(memory::Command::Read(memory::Location::Register(r))) if r == "rsp"
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec![
+                "mem write 0x123 0x321",
+                "memory write 0x123 0x321",
+                "   mem write   0x123  0x321 ",
+            ],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Memory(memory::Command::Write(memory::Location::Address(a), v)) if a == 0x123 && v == 0x321
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec![
+                "mem save 0x123 16 /tmp/region.bin",
+                "memory save 0x123 0x10 /tmp/region.bin",
+                "   mem save   0x123  16  /tmp/region.bin ",
+            ],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Memory(memory::Command::Save(memory::Location::Address(a), len, path))
+                        if a == 0x123 && len == 16 && path == "/tmp/region.bin"
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec![
+                "mem load /tmp/region.bin $rsp",
+                "  memory  load  /tmp/region.bin  $rsp ",
+            ],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Memory(memory::Command::Load(path, memory::Location::Register(r)))
+                        if r == "rsp" && path == "/tmp/region.bin"
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["mem save 0x123 /tmp/region.bin", "mem load /tmp/region.bin"],
+            command_matcher: |result| assert!(result.is_err()),
+        },
+        TestCase {
+            inputs: vec!["reg info", "register info", "   reg  info "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Register(register::Command::Info)
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["reg read rip", "register read rip", "   reg  read   rip "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Register(register::Command::Read(r, None)) if r == "rip"
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec![
+                "reg read xmm0 as f32x4",
+                "register xmm0 as f32x4",
+                "   reg  read   xmm0  as   f32x4 ",
+            ],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Register(register::Command::Read(r, Some(LaneFormat { ty: register::LaneType::F32, count: 4 }))) if r == "xmm0"
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec![
+                "reg read xmm0 as f32",
+                "register xmm0 as x4",
+                "register xmm0",
+            ],
+            command_matcher: |result| assert!(result.is_err()),
+        },
+        TestCase {
+            inputs: vec![
+                "reg write rip 0x123",
+                "register write rip 0x123",
+                "   reg  write  rip  0x123 ",
+            ],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Register(register::Command::Write(r, v)) if r == "rip" && v == 0x123
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["thread info", "thread    info  "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Thread(thread::Command::Info)
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["thread current", "thread    current  "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Thread(thread::Command::Current)
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["thread switch 1", " thread  switch 1  "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Thread(thread::Command::Switch(1))
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["inferior list", " inferior    list  "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Inferior(inferior::Command::List)
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["inferior switch 2", " inferior  switch 2  "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Inferior(inferior::Command::Switch(2))
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["inferior attach 1234", " inferior  attach   1234 "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Inferior(inferior::Command::Attach(1234))
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["sharedlib info", " sharedlib     info  "],
+            command_matcher: |result| {
+                assert!(matches!(result.unwrap(), Command::SharedLib));
+            },
+        },
+        TestCase {
+            inputs: vec!["snapshot a.b[1]", " snapshot   a.b[1]  "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Snapshot(snapshot::Command::Take(expr, _)) if expr == "a.b[1]"
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["diff *ptr", " diff  *ptr "],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
-                    Command::Frame(frame::Command::Switch(1))
+                    Command::Snapshot(snapshot::Command::Diff(expr, _)) if expr == "*ptr"
                 ));
             },
         },
         TestCase {
-            inputs: vec!["r", "run"],
+            inputs: vec!["snapshot", "diff"],
+            command_matcher: |result| assert!(result.is_err()),
+        },
+        TestCase {
+            inputs: vec!["deadlock check", " deadlock   check "],
             command_matcher: |result| {
-                assert!(matches!(result.unwrap(), Command::Run));
+                assert!(matches!(result.unwrap(), Command::DeadlockCheck));
             },
         },
         TestCase {
-            inputs: vec!["symbol main", " symbol  main "],
+            inputs: vec!["deadlock"],
+            command_matcher: |result| assert!(result.is_err()),
+        },
+        TestCase {
+            inputs: vec!["history values", " history     values  "],
             command_matcher: |result| {
-                assert!(matches!(result.unwrap(), Command::PrintSymbol(s) if s == "main"));
+                assert!(matches!(result.unwrap(), Command::ValueHistory));
             },
         },
         TestCase {
-            inputs: vec!["  stepi"],
+            inputs: vec!["history steps", " history  steps "],
             command_matcher: |result| {
-                assert!(matches!(result.unwrap(), Command::StepInstruction));
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::StepHistory { all_threads: false }
+                ));
             },
         },
         TestCase {
-            inputs: vec!["step", "stepinto"],
+            inputs: vec!["history steps all", "history steps  all "],
             command_matcher: |result| {
-                assert!(matches!(result.unwrap(), Command::StepInto));
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::StepHistory { all_threads: true }
+                ));
             },
         },
         TestCase {
-            inputs: vec!["finish", "stepout"],
+            inputs: vec!["source asm", " source   asm  "],
             command_matcher: |result| {
-                assert!(matches!(result.unwrap(), Command::StepOut));
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::SourceCode(source_code::Command::Asm)
+                ));
             },
         },
         TestCase {
-            inputs: vec!["next", "stepover"],
+            inputs: vec!["source fn", " source   fn  "],
             command_matcher: |result| {
-                assert!(matches!(result.unwrap(), Command::StepOver));
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::SourceCode(source_code::Command::Function)
+                ));
             },
         },
         TestCase {
-            inputs: vec!["b some_func", "break some_func", "   break some_func   "],
+            inputs: vec!["source 12", " source   12  "],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
-                    Command::Breakpoint(r#break::Command::Add(BreakpointIdentity::Function(f))) if f == "some_func"
+                    Command::SourceCode(source_code::Command::Range(r)) if r == 12
                 ));
             },
         },
         TestCase {
-            inputs: vec!["b rust_fn", "b info_rust"],
+            inputs: vec!["oracle tokio", " oracle  tokio   "],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
-                    Command::Breakpoint(r#break::Command::Add(BreakpointIdentity::Function(f))) if f == "rust_fn" || f == "info_rust"
+                    Command::Oracle(name, None) if name == "tokio"
                 ));
             },
         },
         TestCase {
-            inputs: vec!["b file:123", "break file:123", "   break file:123   "],
+            inputs: vec!["oracle tokio all ", " oracle  tokio   all"],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
-                    Command::Breakpoint(r#break::Command::Add(BreakpointIdentity::Line(f, n))) if f == "file" && n == 123
+                    Command::Oracle(name, Some(subcmd)) if name == "tokio" && subcmd == "all"
                 ));
             },
         },
         TestCase {
-            inputs: vec!["b 0x123", "break 0x123", "   break 0x0123   "],
+            inputs: vec!["script ./auto.rhai", " script   ./auto.rhai  "],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
-                    Command::Breakpoint(r#break::Command::Add(BreakpointIdentity::Address(a))) if a == 0x123
+                    Command::Script(path) if path == "./auto.rhai"
                 ));
             },
         },
         TestCase {
-            inputs: vec![
-                "b r some_func",
-                "break r some_func",
-                "   break r  some_func   ",
-            ],
+            inputs: vec!["jump 15", " jump   15  "],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
-                    Command::Breakpoint(r#break::Command::Remove(BreakpointIdentity::Function(f))) if f == "some_func"
+                    Command::Jump(jump::Location {
+                        file: None,
+                        line: 15
+                    })
                 ));
             },
         },
         TestCase {
-            inputs: vec![
-                "b r ns1::some_func",
-                "break r ns1::some_func",
-                "   break r  ns1::some_func   ",
-            ],
+            inputs: vec!["jump main.rs:15", " jump  main.rs:15 "],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
-                    Command::Breakpoint(r#break::Command::Remove(BreakpointIdentity::Function(f))) if f == "ns1::some_func"
+                    Command::Jump(jump::Location { file: Some(file), line: 15 }) if file == "main.rs"
                 ));
             },
         },
         TestCase {
-            inputs: vec![
-                "b remove file:123",
-                "break r file:123",
-                "   break  remove file:123   ",
-            ],
+            inputs: vec!["jump", "jump main.rs", "jump main.rs:"],
+            command_matcher: |result| assert!(result.is_err()),
+        },
+        TestCase {
+            inputs: vec!["return", "  return  "],
+            command_matcher: |result| {
+                assert!(matches!(result.unwrap(), Command::Return(None)));
+            },
+        },
+        TestCase {
+            inputs: vec!["return 5", " return  5 "],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
-                    Command::Breakpoint(r#break::Command::Remove(BreakpointIdentity::Line(f, n))) if f == "file" && n == 123
+                    Command::Return(Some(ReturnValue::Int(5)))
                 ));
             },
         },
         TestCase {
-            inputs: vec!["b remove 0x123", "break r 0x123", "   break r 0x123   "],
+            inputs: vec!["return -1"],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
-                    Command::Breakpoint(r#break::Command::Remove(BreakpointIdentity::Address(a))) if a == 0x123
+                    Command::Return(Some(ReturnValue::Int(-1)))
                 ));
             },
         },
         TestCase {
-            inputs: vec!["b info", "break info ", "   break   info   "],
+            inputs: vec!["return 0x10"],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
-                    Command::Breakpoint(r#break::Command::Info)
+                    Command::Return(Some(ReturnValue::Int(16)))
                 ));
             },
         },
         TestCase {
-            inputs: vec!["watch var1", "watch var1 ", "   w   var1   "],
+            inputs: vec!["return true"],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
-                    Command::Watchpoint(watch::Command::Add(
-                        WatchpointIdentity::DQE(source, DQE::Variable(VariableSelector::Name {var_name, ..})), BreakCondition::DataWrites
-                    )) if var_name == "var1" && source == "var1"
+                    Command::Return(Some(ReturnValue::Int(1)))
                 ));
             },
         },
         TestCase {
-            inputs: vec!["watch +rw var1", "watch +rw  var1 ", "   w  +rw  var1   "],
+            inputs: vec!["return -1.5"],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
-                    Command::Watchpoint(watch::Command::Add(
-                        WatchpointIdentity::DQE(source, DQE::Variable(VariableSelector::Name {var_name, ..})), BreakCondition::DataReadsWrites
-                    )) if var_name == "var1" && source == "var1"
+                    Command::Return(Some(ReturnValue::Float(f))) if f == -1.5
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["return abc", "returnx"],
+            command_matcher: |result| assert!(result.is_err()),
+        },
+        TestCase {
+            inputs: vec!["trace report", "  trace  report  "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Trace(trace::Command::Report)
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["trace main.rs:15", " trace  main.rs:15  "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Trace(trace::Command::Add(TracepointPlace::Line(file, 15), exprs))
+                        if file == "main.rs" && exprs.is_empty()
                 ));
             },
         },
         TestCase {
             inputs: vec![
-                "watch ns1::ns2::var1",
-                "watch ns1::ns2::var1 ",
-                "   w   ns1::ns2::var1   ",
+                "trace vars::calc a *b.c",
+                "  trace   vars::calc   a   *b.c  ",
+            ],
+            command_matcher: |result| {
+                let Command::Trace(trace::Command::Add(TracepointPlace::Function(name), exprs)) =
+                    result.unwrap()
+                else {
+                    panic!("unexpected command");
+                };
+                assert_eq!(name, "vars::calc");
+                let sources = exprs.into_iter().map(|(src, _)| src).collect::<Vec<_>>();
+                assert_eq!(sources, vec!["a", "*b.c"]);
+            },
+        },
+        TestCase {
+            inputs: vec!["trace main.rs:15 arr[1] (*ptr).field"],
+            command_matcher: |result| {
+                let Command::Trace(trace::Command::Add(TracepointPlace::Line(_, 15), exprs)) =
+                    result.unwrap()
+                else {
+                    panic!("unexpected command");
+                };
+                let sources = exprs.into_iter().map(|(src, _)| src).collect::<Vec<_>>();
+                assert_eq!(sources, vec!["arr[1]", "(*ptr).field"]);
+            },
+        },
+        TestCase {
+            inputs: vec!["trace", "trace main.rs:"],
+            command_matcher: |result| assert!(result.is_err()),
+        },
+        TestCase {
+            inputs: vec![
+                r#"dprintf main.rs:15 "a = {}, b = {}\n" a b.field"#,
+                r#"  dprintf   main.rs:15   "a = {}, b = {}\n"   a   b.field  "#,
+            ],
+            command_matcher: |result| {
+                let Command::Trace(trace::Command::AddPrintf(
+                    TracepointPlace::Line(file, 15),
+                    format,
+                    exprs,
+                )) = result.unwrap()
+                else {
+                    panic!("unexpected command");
+                };
+                assert_eq!(file, "main.rs");
+                assert_eq!(format, "a = {}, b = {}\n");
+                let sources = exprs.into_iter().map(|(src, _)| src).collect::<Vec<_>>();
+                assert_eq!(sources, vec!["a", "b.field"]);
+            },
+        },
+        TestCase {
+            inputs: vec![r#"dprintf vars::calc "quoted \"{}\"" arr[0]"#],
+            command_matcher: |result| {
+                let Command::Trace(trace::Command::AddPrintf(
+                    TracepointPlace::Function(name),
+                    format,
+                    exprs,
+                )) = result.unwrap()
+                else {
+                    panic!("unexpected command");
+                };
+                assert_eq!(name, "vars::calc");
+                assert_eq!(format, "quoted \"{}\"");
+                assert_eq!(exprs.len(), 1);
+            },
+        },
+        TestCase {
+            inputs: vec!["dprintf main.rs:15", "dprintf main.rs:15 \"unclosed"],
+            command_matcher: |result| assert!(result.is_err()),
+        },
+        TestCase {
+            inputs: vec!["ftrace calc::sum*", "  ftrace   calc::sum*  "],
+            command_matcher: |result| {
+                let Command::Ftrace(ftrace::Command::Start(pattern)) = result.unwrap() else {
+                    panic!("not an ftrace command");
+                };
+                assert_eq!(pattern, "calc::sum*");
+            },
+        },
+        TestCase {
+            inputs: vec!["ftrace stop", " ftrace  stop "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Ftrace(ftrace::Command::Stop)
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["ftrace", "ftrace a* b*"],
+            command_matcher: |result| assert!(result.is_err()),
+        },
+        TestCase {
+            inputs: vec![
+                "coverage start main.rs src/lib.rs",
+                "  coverage  start   main.rs  src/lib.rs  ",
             ],
+            command_matcher: |result| {
+                let Command::Coverage(coverage::Command::Start(files)) = result.unwrap() else {
+                    panic!("not a coverage start command");
+                };
+                assert_eq!(files, vec!["main.rs", "src/lib.rs"]);
+            },
+        },
+        TestCase {
+            inputs: vec!["coverage stop"],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
-                    Command::Watchpoint(watch::Command::Add(
-                        WatchpointIdentity::DQE(source, DQE::Variable(VariableSelector::Name {var_name, ..})), BreakCondition::DataWrites
-                    )) if var_name == "ns1::ns2::var1" && source == "ns1::ns2::var1"
+                    Command::Coverage(coverage::Command::Stop)
                 ));
             },
         },
         TestCase {
-            inputs: vec!["watch 0x123:4", "watch 0x123:4 ", "   w   0x123:4   "],
+            inputs: vec!["coverage report", " coverage  report "],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
-                    Command::Watchpoint(watch::Command::Add(WatchpointIdentity::Address(addr, size), BreakCondition::DataWrites)) if addr == 0x123 && size == 4
+                    Command::Coverage(coverage::Command::Report(None))
                 ));
             },
         },
         TestCase {
-            inputs: vec!["watch info", "watch info ", "   w   info   "],
+            inputs: vec!["coverage report lcov.info", "coverage  report  lcov.info "],
+            command_matcher: |result| {
+                let Command::Coverage(coverage::Command::Report(Some(path))) = result.unwrap()
+                else {
+                    panic!("not a coverage report command");
+                };
+                assert_eq!(path, "lcov.info");
+            },
+        },
+        TestCase {
+            inputs: vec!["coverage", "coverage start", "coverage begin main.rs"],
+            command_matcher: |result| assert!(result.is_err()),
+        },
+        TestCase {
+            inputs: vec!["profile start", "  profile  start  "],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
-                    Command::Watchpoint(watch::Command::Info)
+                    Command::Profile(profile::Command::Start(None))
                 ));
             },
         },
         TestCase {
-            inputs: vec!["watch r var1", "watch remove var1 ", "   w   r var1   "],
+            inputs: vec!["profile start 5ms", "profile  start  5ms "],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
-                    Command::Watchpoint(watch::Command::Remove(WatchpointIdentity::DQE(source, DQE::Variable(VariableSelector::Name {var_name, ..})))) if var_name == "var1" && source == "var1"
+                    Command::Profile(profile::Command::Start(Some(d))) if d == Duration::from_millis(5)
                 ));
             },
         },
         TestCase {
-            inputs: vec!["watch r 2", "watch remove 2 ", "   w   r 2   "],
+            inputs: vec!["profile stop"],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
-                    Command::Watchpoint(watch::Command::Remove(WatchpointIdentity::Number(n))) if n == 2
+                    Command::Profile(profile::Command::Stop)
                 ));
             },
         },
         TestCase {
-            inputs: vec![
-                "mem read 0x123",
-                "memory read 0x123",
-                "   mem read   0x123   ",
-            ],
+            inputs: vec!["profile report", "profile report flat"],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
-                    Command::Memory(memory::Command::Read(a)) if a == 0x123
+                    Command::Profile(profile::Command::Report(profile::ReportKind::Flat))
                 ));
             },
         },
         TestCase {
-            inputs: vec![
-                "mem write 0x123 0x321",
-                "memory write 0x123 0x321",
-                "   mem write   0x123  0x321 ",
-            ],
+            inputs: vec!["profile report tree", " profile  report  tree "],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
-                    Command::Memory(memory::Command::Write(a, v)) if a == 0x123 && v == 0x321
+                    Command::Profile(profile::Command::Report(profile::ReportKind::Tree))
                 ));
             },
         },
         TestCase {
-            inputs: vec!["reg info", "register info", "   reg  info "],
+            inputs: vec!["profile export out.folded", "profile  export  out.folded "],
+            command_matcher: |result| {
+                let Command::Profile(profile::Command::Export(path)) = result.unwrap() else {
+                    panic!("unexpected command");
+                };
+                assert_eq!(path, "out.folded");
+            },
+        },
+        TestCase {
+            inputs: vec!["profile", "profile start 5", "profile report graph"],
+            command_matcher: |result| assert!(result.is_err()),
+        },
+        TestCase {
+            inputs: vec![
+                "session save bs.toml",
+                "  session   save  /tmp/my session.toml ",
+            ],
+            command_matcher: |result| {
+                let Command::Session(session::Command::Save(path)) = result.unwrap() else {
+                    panic!("unexpected command");
+                };
+                assert!(path.ends_with(".toml"));
+                assert!(!path.ends_with(' '));
+            },
+        },
+        TestCase {
+            inputs: vec!["session load bs.toml"],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
-                    Command::Register(register::Command::Info)
+                    Command::Session(session::Command::Load(path)) if path == "bs.toml"
                 ));
             },
         },
         TestCase {
-            inputs: vec!["reg read rip", "register read rip", "   reg  read   rip "],
+            inputs: vec!["session", "session save", "session restore bs.toml"],
+            command_matcher: |result| assert!(result.is_err()),
+        },
+        TestCase {
+            inputs: vec!["set print demangle-style full"],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
-                    Command::Register(register::Command::Read(r)) if r == "rip"
+                    Command::Set(set::Command::PrintDemangleStyle(style)) if style == DemangleStyle::full()
                 ));
             },
         },
         TestCase {
             inputs: vec![
-                "reg write rip 0x123",
-                "register write rip 0x123",
-                "   reg  write  rip  0x123 ",
+                "set print demangle-style default",
+                " set  print  demangle-style  default ",
             ],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
-                    Command::Register(register::Command::Write(r, v)) if r == "rip" && v == 0x123
+                    Command::Set(set::Command::PrintDemangleStyle(style)) if style == DemangleStyle::default()
                 ));
             },
         },
         TestCase {
-            inputs: vec!["thread info", "thread    info  "],
+            inputs: vec!["set print demangle-style short"],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
-                    Command::Thread(thread::Command::Info)
+                    Command::Set(set::Command::PrintDemangleStyle(style)) if style == DemangleStyle::short(2)
                 ));
             },
         },
         TestCase {
-            inputs: vec!["thread current", "thread    current  "],
+            inputs: vec!["set print demangle-style short 4"],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
-                    Command::Thread(thread::Command::Current)
+                    Command::Set(set::Command::PrintDemangleStyle(style)) if style == DemangleStyle::short(4)
                 ));
             },
         },
         TestCase {
-            inputs: vec!["thread switch 1", " thread  switch 1  "],
+            inputs: vec![
+                "set",
+                "set print",
+                "set print demangle-style",
+                "set print demangle-style long",
+                "set print demangle-style short x",
+                "set print elements",
+                "set print elements x",
+                "set print depth -1",
+                "set print string-length unlimite",
+                "set print pointer-depth",
+                "set print bytes-as-ascii",
+                "set print bytes-as-ascii yes",
+                "set quit-action",
+                "set quit-action never",
+                "set listsize",
+                "set listsize x",
+            ],
+            command_matcher: |result| assert!(result.is_err()),
+        },
+        TestCase {
+            inputs: vec!["set print elements 10", " set  print  elements  10 "],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
-                    Command::Thread(thread::Command::Switch(1))
+                    Command::Set(set::Command::PrintElements(Some(10)))
                 ));
             },
         },
         TestCase {
-            inputs: vec!["sharedlib info", " sharedlib     info  "],
+            inputs: vec!["set print depth unlimited"],
             command_matcher: |result| {
-                assert!(matches!(result.unwrap(), Command::SharedLib));
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Set(set::Command::PrintDepth(None))
+                ));
             },
         },
         TestCase {
-            inputs: vec!["source asm", " source   asm  "],
+            inputs: vec!["set print string-length 0"],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
-                    Command::SourceCode(source_code::Command::Asm)
+                    Command::Set(set::Command::PrintStringLength(Some(0)))
                 ));
             },
         },
         TestCase {
-            inputs: vec!["source fn", " source   fn  "],
+            inputs: vec![
+                "set print pointer-depth 3",
+                "set print pointer-depth unlimited",
+            ],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
-                    Command::SourceCode(source_code::Command::Function)
+                    Command::Set(set::Command::PrintPointerDepth(Some(3) | None))
                 ));
             },
         },
         TestCase {
-            inputs: vec!["source 12", " source   12  "],
+            inputs: vec![
+                "set print bytes-as-ascii on",
+                " set print bytes-as-ascii  on ",
+            ],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
-                    Command::SourceCode(source_code::Command::Range(r)) if r == 12
+                    Command::Set(set::Command::PrintBytesAsAscii(true))
                 ));
             },
         },
         TestCase {
-            inputs: vec!["oracle tokio", " oracle  tokio   "],
+            inputs: vec!["set print bytes-as-ascii off"],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
-                    Command::Oracle(name, None) if name == "tokio"
+                    Command::Set(set::Command::PrintBytesAsAscii(false))
                 ));
             },
         },
         TestCase {
-            inputs: vec!["oracle tokio all ", " oracle  tokio   all"],
+            inputs: vec!["set quit-action detach", " set  quit-action  detach "],
             command_matcher: |result| {
                 assert!(matches!(
                     result.unwrap(),
-                    Command::Oracle(name, Some(subcmd)) if name == "tokio" && subcmd == "all"
+                    Command::Set(set::Command::QuitAction(quit::Action::Detach))
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["set quit-action ask"],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Set(set::Command::QuitAction(quit::Action::Ask))
+                ));
+            },
+        },
+        TestCase {
+            inputs: vec!["set listsize 7", " set  listsize  7 "],
+            command_matcher: |result| {
+                assert!(matches!(
+                    result.unwrap(),
+                    Command::Set(set::Command::ListSize(7))
                 ));
             },
         },
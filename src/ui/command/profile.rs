@@ -0,0 +1,64 @@
+use crate::debugger::{Debugger, Error, Profile, DEFAULT_SAMPLE_INTERVAL};
+use std::fs;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReportKind {
+    Flat,
+    Tree,
+}
+
+#[derive(Debug, Clone)]
+pub enum Command {
+    /// Start profiling with a sample interval (default interval if `None`).
+    Start(Option<Duration>),
+    Stop,
+    Report(ReportKind),
+    /// Write a collapsed stack file.
+    Export(String),
+}
+
+pub enum ExecutionResult<'a> {
+    Started(Duration),
+    Report(&'a Profile, ReportKind),
+    /// Profile is exported, number of samples and a file path.
+    Exported(u64, String),
+    NoProfile,
+}
+
+pub struct Handler<'a> {
+    dbg: &'a mut Debugger,
+}
+
+impl<'a> Handler<'a> {
+    pub fn new(debugger: &'a mut Debugger) -> Self {
+        Self { dbg: debugger }
+    }
+
+    pub fn handle(self, cmd: Command) -> Result<ExecutionResult<'a>, Error> {
+        match cmd {
+            Command::Start(interval) => {
+                let interval = interval.unwrap_or(DEFAULT_SAMPLE_INTERVAL);
+                self.dbg.start_profiling(interval);
+                Ok(ExecutionResult::Started(interval))
+            }
+            Command::Stop => {
+                let profile = self.dbg.stop_profiling()?;
+                Ok(ExecutionResult::Report(profile, ReportKind::Flat))
+            }
+            Command::Report(kind) => Ok(self
+                .dbg
+                .profile()
+                .map_or(ExecutionResult::NoProfile, |profile| {
+                    ExecutionResult::Report(profile, kind)
+                })),
+            Command::Export(path) => {
+                let Some(profile) = self.dbg.profile() else {
+                    return Ok(ExecutionResult::NoProfile);
+                };
+                fs::write(&path, profile.collapsed())?;
+                Ok(ExecutionResult::Exported(profile.samples(), path))
+            }
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use crate::debugger::variable::select::{VariableSelector, DQE};
+use crate::debugger::Debugger;
+use crate::ui::command;
+
+pub struct Handler<'a> {
+    dbg: &'a Debugger,
+}
+
+impl<'a> Handler<'a> {
+    pub fn new(debugger: &'a Debugger) -> Self {
+        Self { dbg: debugger }
+    }
+
+    /// Return pairs of variable (or type) name and rendered type declaration.
+    /// Variables and arguments are in priority, if there are no such variables
+    /// then argument is considered as a type name.
+    pub fn handle(self, name: &str) -> command::CommandResult<Vec<(String, String)>> {
+        let selector = || DQE::Variable(VariableSelector::by_name(name, false));
+
+        let vars = self.dbg.read_variable_types(selector())?;
+        if !vars.is_empty() {
+            return Ok(vars);
+        }
+        let args = self.dbg.read_argument_types(selector())?;
+        if !args.is_empty() {
+            return Ok(args);
+        }
+
+        let decl = self.dbg.read_type(name)?;
+        Ok(vec![(name.to_string(), decl)])
+    }
+}
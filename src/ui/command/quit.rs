@@ -0,0 +1,39 @@
+use crate::debugger::Debugger;
+use crate::ui::command;
+
+/// What to do with a running debugee when the debugger quits.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Action {
+    /// Ask a user whether to kill or detach the debugee.
+    #[default]
+    Ask,
+    /// Kill the debugee and all processes spawned by it.
+    Kill,
+    /// Detach from the debugee, the debugee continues execution.
+    Detach,
+}
+
+/// Release a running debugee before the debugger quits.
+pub struct Handler<'a> {
+    dbg: &'a mut Debugger,
+}
+
+impl<'a> Handler<'a> {
+    pub fn new(debugger: &'a mut Debugger) -> Self {
+        Self { dbg: debugger }
+    }
+
+    /// Kill or detach the debugee, do nothing if the debugee is not running.
+    /// [`Action::Ask`] must be resolved by a caller.
+    pub fn handle(&mut self, action: Action) -> command::CommandResult<()> {
+        if !self.dbg.is_in_progress() {
+            return Ok(());
+        }
+        match action {
+            Action::Ask => {}
+            Action::Kill => self.dbg.kill_debugee()?,
+            Action::Detach => self.dbg.detach_debugee()?,
+        }
+        Ok(())
+    }
+}
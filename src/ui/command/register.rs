@@ -1,13 +1,106 @@
 use crate::debugger::register::RegisterMap;
-use crate::debugger::{register, Debugger};
+use crate::debugger::{register, Debugger, Error};
 use crate::ui::command;
 use crate::ui::command::CommandError;
 use register::Register as Reg;
+use register::VectorRegister;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use strum_macros::{Display as StrumDisplay, EnumString};
+
+/// Type of a single lane of a register.
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, StrumDisplay)]
+#[strum(serialize_all = "lowercase")]
+pub enum LaneType {
+    I8,
+    I16,
+    I32,
+    I64,
+    U8,
+    U16,
+    U32,
+    U64,
+    F32,
+    F64,
+}
+
+impl LaneType {
+    /// Return lane size in bytes.
+    pub fn size(&self) -> usize {
+        match self {
+            LaneType::I8 | LaneType::U8 => 1,
+            LaneType::I16 | LaneType::U16 => 2,
+            LaneType::I32 | LaneType::U32 | LaneType::F32 => 4,
+            LaneType::I64 | LaneType::U64 | LaneType::F64 => 8,
+        }
+    }
+
+    fn render(&self, bytes: &[u8]) -> String {
+        macro_rules! render {
+            ($t: ty) => {
+                <$t>::from_le_bytes(bytes.try_into().expect("lane size checked")).to_string()
+            };
+        }
+        match self {
+            LaneType::I8 => render!(i8),
+            LaneType::I16 => render!(i16),
+            LaneType::I32 => render!(i32),
+            LaneType::I64 => render!(i64),
+            LaneType::U8 => render!(u8),
+            LaneType::U16 => render!(u16),
+            LaneType::U32 => render!(u32),
+            LaneType::U64 => render!(u64),
+            LaneType::F32 => render!(f32),
+            LaneType::F64 => render!(f64),
+        }
+    }
+}
+
+/// Interpretation of a register value as a vector of lanes (like `f32x4`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LaneFormat {
+    pub ty: LaneType,
+    pub count: usize,
+}
+
+impl Display for LaneFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}x{}", self.ty, self.count)
+    }
+}
+
+impl FromStr for LaneFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || format!("invalid lane format `{s}`");
+        let (ty, count) = s.split_once('x').ok_or_else(err)?;
+        let ty = LaneType::from_str(ty).map_err(|_| err())?;
+        let count = count.parse::<usize>().map_err(|_| err())?;
+        if count == 0 {
+            return Err(err());
+        }
+        Ok(Self { ty, count })
+    }
+}
+
+impl LaneFormat {
+    /// Split register value (in little-endian byte order) into lanes, lowest lane first.
+    /// Lanes that don't fit into a register are not rendered.
+    pub fn render(&self, bytes: &[u8]) -> Vec<String> {
+        bytes
+            .chunks_exact(self.ty.size())
+            .take(self.count)
+            .map(|lane| self.ty.render(lane))
+            .collect()
+    }
+}
 
 #[derive(Debug, Clone)]
 pub enum Command {
     Info,
-    Read(String),
+    /// Read a register, optionally interpret it as a vector of lanes.
+    Read(String, Option<LaneFormat>),
     Write(String, u64),
 }
 
@@ -15,9 +108,17 @@ pub struct Handler<'a> {
     dbg: &'a Debugger,
 }
 
+pub enum Value {
+    Scalar(u64),
+    /// Register value split into lanes, lowest lane first.
+    Lanes(Vec<String>),
+    /// Vector register value split into 64-bit words, lowest word first.
+    Words(Vec<u64>),
+}
+
 pub struct RegisterValue {
     pub register_name: String,
-    pub value: u64,
+    pub value: Value,
 }
 
 pub type ExecutionResult = Vec<RegisterValue>;
@@ -27,6 +128,29 @@ impl<'a> Handler<'a> {
         Self { dbg: debugger }
     }
 
+    fn read(&self, register: &str, format: Option<LaneFormat>) -> Result<Value, Error> {
+        if VectorRegister::from_str(register).is_err() {
+            let value = self.dbg.get_register_value(register)?;
+            return Ok(match format {
+                None => Value::Scalar(value),
+                Some(format) => Value::Lanes(format.render(&value.to_le_bytes())),
+            });
+        }
+
+        let (_, bytes) = self.dbg.get_vector_register_value(register)?;
+        Ok(match format {
+            None => Value::Words(
+                bytes
+                    .as_chunks::<8>()
+                    .0
+                    .iter()
+                    .map(|word| u64::from_le_bytes(*word))
+                    .collect(),
+            ),
+            Some(format) => Value::Lanes(format.render(&bytes)),
+        })
+    }
+
     pub fn handle(self, cmd: &Command) -> command::CommandResult<ExecutionResult> {
         match cmd {
             Command::Info => {
@@ -67,13 +191,13 @@ impl<'a> Handler<'a> {
                     .iter()
                     .map(|&r| RegisterValue {
                         register_name: r.to_string(),
-                        value: register_map.value(r),
+                        value: Value::Scalar(register_map.value(r)),
                     })
                     .collect::<Vec<_>>())
             }
-            Command::Read(register) => Ok(vec![RegisterValue {
+            Command::Read(register, format) => Ok(vec![RegisterValue {
                 register_name: register.to_string(),
-                value: self.dbg.get_register_value(register)?,
+                value: self.read(register, *format)?,
             }]),
             Command::Write(register, value) => {
                 self.dbg.set_register_value(register, *value)?;
@@ -82,3 +206,34 @@ impl<'a> Handler<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_lane_format_render() {
+        let mut bytes = vec![];
+        bytes.extend_from_slice(&1.5f32.to_le_bytes());
+        bytes.extend_from_slice(&(-2f32).to_le_bytes());
+        bytes.extend_from_slice(&0f32.to_le_bytes());
+        bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+
+        let f32x4 = LaneFormat::from_str("f32x4").unwrap();
+        assert_eq!(f32x4.render(&bytes)[..3], ["1.5", "-2", "0"]);
+        let u64x2 = LaneFormat::from_str("u64x2").unwrap();
+        assert_eq!(
+            u64x2.render(&bytes),
+            ["13835058056351711232", "18446744069414584320"]
+        );
+        let i32x8 = LaneFormat::from_str("i32x8").unwrap();
+        assert_eq!(i32x8.render(&bytes).len(), 4);
+        assert_eq!(i32x8.render(&bytes)[3], "-1");
+        let u8x16 = LaneFormat::from_str("u8x16").unwrap();
+        assert_eq!(u8x16.render(&bytes)[..4], ["0", "0", "192", "63"]);
+
+        assert!(LaneFormat::from_str("f16x8").is_err());
+        assert!(LaneFormat::from_str("u8x0").is_err());
+        assert!(LaneFormat::from_str("u8").is_err());
+    }
+}
@@ -0,0 +1,20 @@
+use crate::debugger::address::RelocatedAddress;
+use crate::debugger::{AddressInfo, Debugger};
+use crate::ui::command;
+
+pub struct Handler<'a> {
+    dbg: &'a Debugger,
+}
+
+impl<'a> Handler<'a> {
+    pub fn new(debugger: &'a Debugger) -> Self {
+        Self { dbg: debugger }
+    }
+
+    pub fn handle(self, addresses: &[usize]) -> command::CommandResult<Vec<AddressInfo>> {
+        Ok(addresses
+            .iter()
+            .map(|&addr| self.dbg.resolve_address(RelocatedAddress::from(addr)))
+            .collect::<Result<_, _>>()?)
+    }
+}
@@ -0,0 +1,17 @@
+use crate::debugger::{Debugger, ReturnValue};
+use crate::ui::command;
+
+// Abort current function and return to the caller
+pub struct Handler<'a> {
+    dbg: &'a mut Debugger,
+}
+
+impl<'a> Handler<'a> {
+    pub fn new(debugger: &'a mut Debugger) -> Self {
+        Self { dbg: debugger }
+    }
+
+    pub fn handle(&mut self, value: Option<ReturnValue>) -> command::CommandResult<()> {
+        Ok(self.dbg.return_early(value)?)
+    }
+}
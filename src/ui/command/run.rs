@@ -1,10 +1,22 @@
-use crate::debugger::Debugger;
+use crate::debugger::{Debugger, StopAt};
 use crate::ui::command;
+use std::path::PathBuf;
+
+/// Debugee run options.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// Stop at a program entry point or at `main` function.
+    pub stop_at: Option<StopAt>,
+    /// File used as a standard input of the debugee, debugger stdin is used if `None`.
+    pub stdin: Option<PathBuf>,
+}
 
 pub enum Command {
-    Start,
+    /// Start debugee.
+    Start(Options),
     DryStart,
-    Restart,
+    /// Restart debugee.
+    Restart(Options),
 }
 
 pub struct Handler<'a> {
@@ -20,9 +32,19 @@ impl<'a> Handler<'a> {
     /// Return when debugee stopped or ends.
     pub fn handle(&mut self, cmd: Command) -> command::CommandResult<()> {
         match cmd {
-            Command::Start => Ok(self.dbg.start_debugee()?),
-            Command::Restart => {
-                self.dbg.start_debugee_force()?;
+            Command::Start(options) => {
+                self.dbg.set_debugee_stdin(options.stdin)?;
+                match options.stop_at {
+                    None => Ok(self.dbg.start_debugee()?),
+                    Some(stop_at) => Ok(self.dbg.start_debugee_and_stop(stop_at, false)?),
+                }
+            }
+            Command::Restart(options) => {
+                self.dbg.set_debugee_stdin(options.stdin)?;
+                match options.stop_at {
+                    None => self.dbg.start_debugee_force()?,
+                    Some(stop_at) => self.dbg.start_debugee_and_stop(stop_at, true)?,
+                }
                 Ok(())
             }
             Command::DryStart => Ok(self.dbg.dry_start_debugee()?),
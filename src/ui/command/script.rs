@@ -0,0 +1,25 @@
+use crate::debugger::Debugger;
+use crate::script::ScriptEngine;
+use crate::ui::command;
+use crate::ui::command::CommandError;
+use std::path::Path;
+
+pub struct Handler<'a> {
+    dbg: &'a mut Debugger,
+}
+
+impl<'a> Handler<'a> {
+    pub fn new(debugger: &'a mut Debugger) -> Self {
+        Self { dbg: debugger }
+    }
+
+    pub fn handle(
+        &mut self,
+        path: &str,
+        printer: impl Fn(&str) + 'static,
+    ) -> command::CommandResult<()> {
+        ScriptEngine::new(self.dbg)
+            .run(Path::new(path), printer)
+            .map_err(CommandError::Script)
+    }
+}
@@ -0,0 +1,309 @@
+use crate::debugger::address::Address;
+use crate::debugger::register::debug::{BreakCondition, BreakSize};
+use crate::debugger::{Debugger, Error};
+use crate::ui::command::display::DisplayRegistry;
+use crate::ui::command::parser::{
+    brkpt_at_addr_parser, brkpt_at_fn, brkpt_at_line_parser, expression, watchpoint_at_address,
+    watchpoint_at_dqe,
+};
+use crate::ui::command::{r#break, watch, CommandError, CommandResult};
+use anyhow::anyhow;
+use chumsky::prelude::{choice, end};
+use chumsky::Parser;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+#[derive(Debug, Clone)]
+pub enum Command {
+    Save(String),
+    Load(String),
+}
+
+/// Saved breakpoint. Location is a `break` command argument: `file:line`, function or address.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BreakpointEntry {
+    location: String,
+    #[serde(default = "enabled_by_default")]
+    enabled: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    hardware: bool,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+/// Saved watchpoint. Location is a watch command argument: expression or `addr:size`.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WatchpointEntry {
+    location: String,
+    /// `w` for write-only or `rw` for read-write watchpoints.
+    condition: String,
+}
+
+/// Debugging session, persisted in a TOML file.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Session {
+    #[serde(default)]
+    breakpoints: Vec<BreakpointEntry>,
+    #[serde(default)]
+    watchpoints: Vec<WatchpointEntry>,
+    /// Auto-displayed expressions.
+    #[serde(default)]
+    displays: Vec<String>,
+}
+
+pub enum ExecutionResult {
+    Saved {
+        breakpoints: usize,
+        watchpoints: usize,
+        displays: usize,
+    },
+    /// Session loaded, errors contain descriptions of entries that can't be restored.
+    Loaded {
+        breakpoints: usize,
+        watchpoints: usize,
+        displays: usize,
+        errors: Vec<String>,
+    },
+}
+
+pub struct Handler<'a> {
+    dbg: &'a mut Debugger,
+    displays: &'a mut DisplayRegistry,
+}
+
+impl<'a> Handler<'a> {
+    pub fn new(debugger: &'a mut Debugger, displays: &'a mut DisplayRegistry) -> Self {
+        Self {
+            dbg: debugger,
+            displays,
+        }
+    }
+
+    pub fn handle(&mut self, cmd: Command) -> CommandResult<ExecutionResult> {
+        match cmd {
+            Command::Save(path) => {
+                let session = self.snapshot();
+                let data = toml::to_string_pretty(&session)
+                    .map_err(|e| CommandError::Session(e.into()))?;
+                fs::write(&path, data)
+                    .map_err(|e| CommandError::Session(anyhow!("write {path}: {e}")))?;
+                Ok(ExecutionResult::Saved {
+                    breakpoints: session.breakpoints.len(),
+                    watchpoints: session.watchpoints.len(),
+                    displays: session.displays.len(),
+                })
+            }
+            Command::Load(path) => {
+                let data = fs::read_to_string(&path)
+                    .map_err(|e| CommandError::Session(anyhow!("read {path}: {e}")))?;
+                let session: Session =
+                    toml::from_str(&data).map_err(|e| CommandError::Session(e.into()))?;
+                Ok(self.restore(session))
+            }
+        }
+    }
+
+    fn snapshot(&self) -> Session {
+        let mut breakpoints: Vec<BreakpointEntry> = vec![];
+        for brkpt in self.dbg.breakpoints_snapshot() {
+            let location = match &brkpt.place {
+                Some(place) => format!("{}:{}", place.file.to_string_lossy(), place.line_number),
+                None => {
+                    let addr = match brkpt.addr {
+                        Address::Relocated(addr) => usize::from(addr),
+                        Address::Global(addr) => usize::from(addr),
+                    };
+                    format!("{addr:#x}")
+                }
+            };
+            // a single line may be mapped to several breakpoints, save it once
+            if breakpoints.iter().all(|b| b.location != location) {
+                breakpoints.push(BreakpointEntry {
+                    location,
+                    enabled: brkpt.enabled,
+                    hardware: brkpt.hardware,
+                });
+            }
+        }
+
+        let watchpoints = self
+            .dbg
+            .watchpoint_list()
+            .into_iter()
+            .map(|wp| {
+                let location = match wp.source_dqe {
+                    Some(dqe) => dqe.to_string(),
+                    None => format!("{:#x}:{}", wp.address.as_usize(), size_in_bytes(wp.size)),
+                };
+                WatchpointEntry {
+                    location,
+                    condition: wp.condition.to_string(),
+                }
+            })
+            .collect();
+
+        let displays = self
+            .displays
+            .list()
+            .iter()
+            .map(|display| display.expr.clone())
+            .collect();
+
+        Session {
+            breakpoints,
+            watchpoints,
+            displays,
+        }
+    }
+
+    fn restore(&mut self, session: Session) -> ExecutionResult {
+        let mut errors = vec![];
+        let mut breakpoints = 0;
+        for entry in session.breakpoints {
+            match self.restore_breakpoint(&entry) {
+                Ok(()) => breakpoints += 1,
+                Err(e) => errors.push(format!("breakpoint at {}: {e:#}", entry.location)),
+            }
+        }
+
+        let mut watchpoints = 0;
+        for entry in session.watchpoints {
+            match self.restore_watchpoint(&entry) {
+                Ok(()) => watchpoints += 1,
+                Err(e) => errors.push(format!("watchpoint at {}: {e:#}", entry.location)),
+            }
+        }
+
+        let mut displays = 0;
+        for expr in session.displays {
+            match expression::parser()
+                .padded()
+                .then_ignore(end())
+                .parse(expr.as_str())
+                .into_result()
+            {
+                Ok(dqe) => {
+                    self.displays.add(expr.trim().to_string(), dqe);
+                    displays += 1;
+                }
+                Err(_) => errors.push(format!("display {expr}: malformed expression")),
+            }
+        }
+
+        ExecutionResult::Loaded {
+            breakpoints,
+            watchpoints,
+            displays,
+            errors,
+        }
+    }
+
+    fn restore_breakpoint(&mut self, entry: &BreakpointEntry) -> anyhow::Result<()> {
+        let identity = choice((
+            brkpt_at_addr_parser(),
+            brkpt_at_line_parser(),
+            brkpt_at_fn(),
+        ))
+        .then_ignore(end())
+        .parse(entry.location.as_str())
+        .into_result()
+        .map_err(|_| anyhow!("malformed location"))?;
+
+        let mut handler = r#break::Handler::new(self.dbg);
+        let numbers = match handler.handle(&r#break::Command::Add(identity.clone())) {
+            Ok(
+                r#break::ExecutionResult::New(brkpts)
+                | r#break::ExecutionResult::NewGroup(_, brkpts),
+            ) => brkpts.iter().map(|b| b.number).collect::<Vec<_>>(),
+            Ok(_) => vec![],
+            // place may be in a shared library that isn't loaded yet
+            Err(Error::NoSuitablePlace) => {
+                handler.handle(&r#break::Command::AddDeferred(identity))?;
+                vec![]
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        if entry.hardware {
+            self.dbg.make_breakpoints_hardware(&numbers)?;
+        }
+        if !entry.enabled {
+            for number in numbers {
+                self.dbg.disable_breakpoint(number)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn restore_watchpoint(&mut self, entry: &WatchpointEntry) -> anyhow::Result<()> {
+        let cond = match entry.condition.as_str() {
+            "w" => BreakCondition::DataWrites,
+            "rw" => BreakCondition::DataReadsWrites,
+            "r" => BreakCondition::DataReads,
+            unknown => return Err(anyhow!("unknown condition `{unknown}`")),
+        };
+        let identity = choice((watchpoint_at_address(), watchpoint_at_dqe()))
+            .then_ignore(end())
+            .parse(entry.location.as_str())
+            .into_result()
+            .map_err(|_| anyhow!("malformed location"))?;
+
+        watch::Handler::new(self.dbg).handle(watch::Command::Add(identity, cond))?;
+        Ok(())
+    }
+}
+
+fn size_in_bytes(size: BreakSize) -> u8 {
+    match size {
+        BreakSize::Bytes1 => 1,
+        BreakSize::Bytes2 => 2,
+        BreakSize::Bytes4 => 4,
+        BreakSize::Bytes8 => 8,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_session_serde() {
+        let session = Session {
+            breakpoints: vec![
+                BreakpointEntry {
+                    location: "main.rs:15".to_string(),
+                    enabled: true,
+                    hardware: true,
+                },
+                BreakpointEntry {
+                    location: "0x55555555bd63".to_string(),
+                    enabled: false,
+                    hardware: false,
+                },
+            ],
+            watchpoints: vec![WatchpointEntry {
+                location: "vec1[0]".to_string(),
+                condition: "rw".to_string(),
+            }],
+            displays: vec!["a.b".to_string()],
+        };
+        let data = toml::to_string_pretty(&session).unwrap();
+        assert_eq!(toml::from_str::<Session>(&data).unwrap(), session);
+
+        let session: Session = toml::from_str(
+            r#"
+            [[breakpoints]]
+            location = "vars::calc"
+            "#,
+        )
+        .unwrap();
+        assert!(session.breakpoints[0].enabled);
+        assert!(!session.breakpoints[0].hardware);
+        assert!(session.watchpoints.is_empty());
+    }
+}
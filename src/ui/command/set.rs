@@ -0,0 +1,72 @@
+use crate::debugger::unwind::DemangleStyle;
+use crate::debugger::variable::render::RenderLimits;
+use crate::ui::command::quit;
+
+#[derive(Debug, Clone)]
+pub enum Command {
+    /// Set a demangle style of function names in backtraces.
+    PrintDemangleStyle(DemangleStyle),
+    /// Set a maximum number of printed collection elements, `None` for unlimited.
+    PrintElements(Option<usize>),
+    /// Set a maximum nesting level of printed values, `None` for unlimited.
+    PrintDepth(Option<usize>),
+    /// Set a maximum number of printed string characters, `None` for unlimited.
+    PrintStringLength(Option<usize>),
+    /// Set a maximum number of pointers followed in a row, `None` for unlimited.
+    PrintPointerDepth(Option<usize>),
+    /// Print arrays of `u8` as ASCII byte strings.
+    PrintBytesAsAscii(bool),
+    /// Set an action applied to a running debugee on quit.
+    QuitAction(quit::Action),
+    /// Set a number of source lines printed at a stop place.
+    ListSize(u64),
+}
+
+/// Settings that change debugger output. Settings are owned by UI, so they survive
+/// debugee restarts.
+#[derive(Debug)]
+pub struct Settings {
+    pub demangle_style: DemangleStyle,
+    pub render_limits: RenderLimits,
+    pub quit_action: quit::Action,
+    /// Number of source lines printed at a stop place.
+    pub list_size: u64,
+}
+
+impl Settings {
+    pub const DEFAULT_LIST_SIZE: u64 = 1;
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            demangle_style: DemangleStyle::default(),
+            render_limits: RenderLimits::default(),
+            quit_action: quit::Action::default(),
+            list_size: Self::DEFAULT_LIST_SIZE,
+        }
+    }
+}
+
+pub struct Handler<'a> {
+    settings: &'a mut Settings,
+}
+
+impl<'a> Handler<'a> {
+    pub fn new(settings: &'a mut Settings) -> Self {
+        Self { settings }
+    }
+
+    pub fn handle(self, cmd: Command) {
+        match cmd {
+            Command::PrintDemangleStyle(style) => self.settings.demangle_style = style,
+            Command::PrintElements(limit) => self.settings.render_limits.elements = limit,
+            Command::PrintDepth(limit) => self.settings.render_limits.depth = limit,
+            Command::PrintStringLength(limit) => self.settings.render_limits.string_length = limit,
+            Command::PrintPointerDepth(limit) => self.settings.render_limits.pointer_depth = limit,
+            Command::PrintBytesAsAscii(on) => self.settings.render_limits.bytes_as_ascii = on,
+            Command::QuitAction(action) => self.settings.quit_action = action,
+            Command::ListSize(size) => self.settings.list_size = size,
+        }
+    }
+}
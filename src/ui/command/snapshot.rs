@@ -0,0 +1,88 @@
+use crate::debugger::variable::render::RenderRepr;
+use crate::debugger::variable::select::DQE;
+use crate::debugger::variable::VariableIR;
+use crate::debugger::Debugger;
+use crate::ui::command;
+use crate::ui::command::CommandError;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone)]
+pub enum Command {
+    /// Capture current value of an expression (source string and DQE).
+    Take(String, DQE),
+    /// Compare current value of an expression with a captured one,
+    /// current value replaces the captured one.
+    Diff(String, DQE),
+}
+
+/// Captured expression values, keyed by expression source. Registry is owned by UI,
+/// so snapshots survive debugee restarts.
+#[derive(Default)]
+pub struct SnapshotRegistry {
+    snapshots: HashMap<String, Vec<VariableIR>>,
+}
+
+/// Captured and current values of a variable, a value is `None` if the variable
+/// is missing in a snapshot or in the current state.
+pub struct VariableDiff {
+    pub old: Option<VariableIR>,
+    pub new: Option<VariableIR>,
+}
+
+pub enum ExecutionResult {
+    /// Number of captured variables.
+    Taken(usize),
+    Diff(Vec<VariableDiff>),
+}
+
+pub struct Handler<'a> {
+    dbg: &'a Debugger,
+    registry: &'a mut SnapshotRegistry,
+}
+
+impl<'a> Handler<'a> {
+    pub fn new(debugger: &'a Debugger, registry: &'a mut SnapshotRegistry) -> Self {
+        Self {
+            dbg: debugger,
+            registry,
+        }
+    }
+
+    pub fn handle(self, cmd: Command) -> command::CommandResult<ExecutionResult> {
+        match cmd {
+            Command::Take(expr, dqe) => {
+                let vars = self.dbg.read_variable(dqe)?;
+                let count = vars.len();
+                self.registry.snapshots.insert(expr, vars);
+                Ok(ExecutionResult::Taken(count))
+            }
+            Command::Diff(expr, dqe) => {
+                let old = self
+                    .registry
+                    .snapshots
+                    .get(&expr)
+                    .ok_or_else(|| CommandError::SnapshotNotFound(expr.clone()))?;
+                let new = self.dbg.read_variable(dqe)?;
+
+                let mut diffs: Vec<_> = old
+                    .iter()
+                    .map(|old| VariableDiff {
+                        old: Some(old.clone()),
+                        new: new.iter().find(|v| v.name() == old.name()).cloned(),
+                    })
+                    .collect();
+                for var in &new {
+                    if !old.iter().any(|v| v.name() == var.name()) {
+                        diffs.push(VariableDiff {
+                            old: None,
+                            new: Some(var.clone()),
+                        });
+                    }
+                }
+
+                self.registry.snapshots.insert(expr, new);
+                Ok(ExecutionResult::Diff(diffs))
+            }
+        }
+    }
+}
@@ -11,7 +11,7 @@ impl<'a> Handler<'a> {
         Self { dbg: debugger }
     }
 
-    pub fn handle(&mut self) -> command::CommandResult<()> {
-        Ok(self.dbg.stepi()?)
+    pub fn handle(&mut self, count: u32) -> command::CommandResult<()> {
+        Ok(self.dbg.stepi_n(count)?)
     }
 }
@@ -1,4 +1,4 @@
-use crate::debugger::Debugger;
+use crate::debugger::{Debugger, StepGranularity};
 use crate::ui::command;
 
 /// Step program until it reaches a different source line.
@@ -11,7 +11,11 @@ impl<'a> Handler<'a> {
         Self { dbg: debugger }
     }
 
-    pub fn handle(&mut self) -> command::CommandResult<()> {
-        Ok(self.dbg.step_into()?)
+    pub fn handle(
+        &mut self,
+        count: u32,
+        granularity: StepGranularity,
+    ) -> command::CommandResult<()> {
+        Ok(self.dbg.step_into_n(count, granularity)?)
     }
 }
@@ -1,3 +1,4 @@
+use crate::debugger::variable::VariableIR;
 use crate::debugger::Debugger;
 use crate::ui::command;
 
@@ -11,7 +12,7 @@ impl<'a> Handler<'a> {
         Self { dbg: debugger }
     }
 
-    pub fn handle(&mut self) -> command::CommandResult<()> {
-        Ok(self.dbg.step_out()?)
+    pub fn handle(&mut self, count: u32) -> command::CommandResult<Option<VariableIR>> {
+        Ok(self.dbg.step_out_n(count)?)
     }
 }
@@ -1,4 +1,4 @@
-use crate::debugger::Debugger;
+use crate::debugger::{Debugger, StepGranularity};
 use crate::ui::command;
 
 /// Step program, proceeding through subroutine calls.
@@ -14,7 +14,11 @@ impl<'a> Handler<'a> {
         Self { dbg: debugger }
     }
 
-    pub fn handle(&mut self) -> command::CommandResult<()> {
-        Ok(self.dbg.step_over()?)
+    pub fn handle(
+        &mut self,
+        count: u32,
+        granularity: StepGranularity,
+    ) -> command::CommandResult<()> {
+        Ok(self.dbg.step_over_n(count, granularity)?)
     }
 }
@@ -11,9 +11,7 @@ impl<'a> Handler<'a> {
         Self { dbg: debugger }
     }
 
-    pub fn handle(self, regex: &str) -> command::CommandResult<Vec<&'a debugger::Symbol>> {
-        let mut symbols = self.dbg.get_symbols(regex)?;
-        symbols.sort_by(|s1, s2| s1.name.cmp(&s2.name));
-        Ok(symbols)
+    pub fn handle(self, query: &str) -> command::CommandResult<Vec<debugger::SymbolMatch>> {
+        Ok(self.dbg.search_symbols(query)?)
     }
 }
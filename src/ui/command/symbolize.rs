@@ -0,0 +1,100 @@
+use crate::debugger::address::{GlobalAddress, RelocatedAddress};
+use crate::debugger::{AddressInfo, Debugger};
+use crate::ui::command;
+use crate::ui::command::CommandError;
+use anyhow::anyhow;
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Single frame of an imported backtrace.
+pub struct SymbolizedFrame {
+    /// Frame number (order in a backtrace).
+    pub num: usize,
+    /// Address as it presented in a backtrace.
+    pub addr: usize,
+    /// Module (object file) name if it presented in a backtrace.
+    pub module: Option<String>,
+    pub info: AddressInfo,
+}
+
+/// Frame in ASan style: `#0 0x55d4c5a0f1b3 in foo (/path/to/bin+0x1f1b3)`,
+/// module and offset are captured.
+fn module_offset_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(r"\(([^()\s]+)\+0[xX]([0-9a-fA-F]+)\)").expect("infallible regex")
+    })
+}
+
+fn address_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"\b0[xX]([0-9a-fA-F]+)\b").expect("infallible regex"))
+}
+
+pub struct Handler<'a> {
+    dbg: &'a Debugger,
+}
+
+impl<'a> Handler<'a> {
+    pub fn new(debugger: &'a Debugger) -> Self {
+        Self { dbg: debugger }
+    }
+
+    /// Read a backtrace from file and resolve addresses of its frames.
+    /// Frames with a module and offset (ASan style) are resolved using the debug information
+    /// of this module. Other addresses are treated as addresses in debugee address space
+    /// if debugee is running, or as addresses in the main executable otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `path`: path to a file with backtrace
+    pub fn handle(self, path: &str) -> command::CommandResult<Vec<SymbolizedFrame>> {
+        let backtrace = fs::read_to_string(path)
+            .map_err(|e| CommandError::BacktraceImport(anyhow!("read {path}: {e}")))?;
+
+        let mut frames = vec![];
+        for line in backtrace.lines() {
+            let num = frames.len();
+            let module_offset = module_offset_regex().captures(line).and_then(|caps| {
+                let offset = usize::from_str_radix(&caps[2], 16).ok()?;
+                Some((caps[1].to_string(), offset))
+            });
+            if let Some((module, offset)) = module_offset {
+                let info = self.dbg.resolve_address_in_module(
+                    Some(Path::new(&module)),
+                    GlobalAddress::from(offset),
+                )?;
+                frames.push(SymbolizedFrame {
+                    num,
+                    addr: offset,
+                    module: Some(module),
+                    info,
+                });
+                continue;
+            }
+
+            let Some(caps) = address_regex().captures(line) else {
+                continue;
+            };
+            let Ok(addr) = usize::from_str_radix(&caps[1], 16) else {
+                continue;
+            };
+            let info = if self.dbg.is_in_progress() {
+                self.dbg.resolve_address(RelocatedAddress::from(addr))?
+            } else {
+                self.dbg
+                    .resolve_address_in_module(None, GlobalAddress::from(addr))?
+            };
+            frames.push(SymbolizedFrame {
+                num,
+                addr,
+                module: None,
+                info,
+            });
+        }
+
+        Ok(frames)
+    }
+}
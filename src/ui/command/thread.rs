@@ -42,3 +42,15 @@ impl<'a> Handler<'a> {
         }
     }
 }
+
+/// Group threads with the same label together. Groups are ordered by the first appearance
+/// of a label, the order of threads inside a group is preserved.
+pub fn group_by_label(threads: &mut [ThreadSnapshot]) {
+    let mut labels: Vec<Option<String>> = vec![];
+    for thread in threads.iter() {
+        if !labels.contains(&thread.label) {
+            labels.push(thread.label.clone());
+        }
+    }
+    threads.sort_by_cached_key(|thread| labels.iter().position(|l| l == &thread.label));
+}
@@ -0,0 +1,41 @@
+use crate::debugger::variable::select::DQE;
+use crate::debugger::{Debugger, Error, Tracepoint, TracepointPlace};
+
+#[derive(Debug, Clone)]
+pub enum Command {
+    /// Set a tracepoint at place, list of expressions (source string and DQE)
+    /// evaluated at each hit.
+    Add(TracepointPlace, Vec<(String, DQE)>),
+    /// Same as `Add` but with a message format, message is printed at each hit.
+    AddPrintf(TracepointPlace, String, Vec<(String, DQE)>),
+    Report,
+}
+
+pub enum ExecutionResult<'a> {
+    New(u32),
+    Report(&'a [Tracepoint]),
+}
+
+pub struct Handler<'a> {
+    dbg: &'a mut Debugger,
+}
+
+impl<'a> Handler<'a> {
+    pub fn new(debugger: &'a mut Debugger) -> Self {
+        Self { dbg: debugger }
+    }
+
+    pub fn handle(self, cmd: Command) -> Result<ExecutionResult<'a>, Error> {
+        match cmd {
+            Command::Add(place, expressions) => {
+                let number = self.dbg.set_tracepoint(place, expressions)?;
+                Ok(ExecutionResult::New(number))
+            }
+            Command::AddPrintf(place, format, expressions) => {
+                let number = self.dbg.set_dprintf(place, &format, expressions)?;
+                Ok(ExecutionResult::New(number))
+            }
+            Command::Report => Ok(ExecutionResult::Report(self.dbg.tracepoints())),
+        }
+    }
+}
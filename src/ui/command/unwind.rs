@@ -0,0 +1,17 @@
+use crate::debugger::Debugger;
+use crate::ui::command;
+
+// Execute until a thrown C++ exception is caught
+pub struct Handler<'a> {
+    dbg: &'a mut Debugger,
+}
+
+impl<'a> Handler<'a> {
+    pub fn new(debugger: &'a mut Debugger) -> Self {
+        Self { dbg: debugger }
+    }
+
+    pub fn handle(&mut self) -> command::CommandResult<()> {
+        Ok(self.dbg.unwind_exception()?)
+    }
+}
@@ -5,14 +5,31 @@ use crate::ui::command;
 
 pub struct Handler<'a> {
     dbg: &'a Debugger,
+    /// Maximum number of pointers followed in a row, `None` for unlimited.
+    pointer_depth: Option<usize>,
 }
 
 impl<'a> Handler<'a> {
     pub fn new(debugger: &'a Debugger) -> Self {
-        Self { dbg: debugger }
+        Self {
+            dbg: debugger,
+            pointer_depth: Some(0),
+        }
+    }
+
+    /// Follow pointers in read variables, see [`Debugger::read_variable_following_pointers`].
+    pub fn with_pointer_depth(self, pointer_depth: Option<usize>) -> Self {
+        Self {
+            pointer_depth,
+            ..self
+        }
     }
 
     pub fn handle(self, select_expression: DQE) -> command::CommandResult<Vec<VariableIR>> {
-        Ok(self.dbg.read_variable(select_expression)?)
+        let mut variables = self
+            .dbg
+            .read_variable_following_pointers(select_expression, self.pointer_depth)?;
+        self.dbg.annotate_pointers(&mut variables);
+        Ok(variables)
     }
 }
@@ -0,0 +1,568 @@
+use crate::ui::command::parser::{
+    ARG_ALL_KEY, ARG_COMMAND, AWATCH_COMMAND, BACKTRACE_ALL_SUBCOMMAND, BACKTRACE_ARGS_KEY,
+    BACKTRACE_COMMAND, BACKTRACE_COMMAND_SHORT, BREAK_COMMAND, BREAK_COMMAND_SHORT,
+    CATCH_CATCH_SUBCOMMAND, CATCH_COMMAND, CATCH_THROW_SUBCOMMAND, CATCH_UNWIND_SUBCOMMAND,
+    CONTINUE_COMMAND, CONTINUE_COMMAND_SHORT, CONTINUE_FOR_KEY, COVERAGE_COMMAND,
+    COVERAGE_REPORT_SUBCOMMAND, COVERAGE_START_SUBCOMMAND, COVERAGE_STOP_SUBCOMMAND,
+    DEADLOCK_CHECK_SUBCOMMAND, DEADLOCK_COMMAND, DIFF_COMMAND, DISPLAY_COMMAND, DPRINTF_COMMAND,
+    EXAMINE_COMMAND, FRAME_COMMAND, FRAME_COMMAND_INFO_SUBCOMMAND, FRAME_COMMAND_MEMORY_SUBCOMMAND,
+    FRAME_COMMAND_SWITCH_SUBCOMMAND, FTRACE_COMMAND, FTRACE_STOP_SUBCOMMAND, HELP_COMMAND,
+    HELP_COMMAND_SHORT, HISTORY_COMMAND, HISTORY_STEPS_SUBCOMMAND, HISTORY_VALUES_SUBCOMMAND,
+    INFERIOR_COMMAND, INFERIOR_COMMAND_ATTACH_SUBCOMMAND, INFERIOR_COMMAND_LIST_SUBCOMMAND,
+    INFERIOR_COMMAND_SWITCH_SUBCOMMAND, INFO_COMMAND, INFO_LINE_SUBCOMMAND, JUMP_COMMAND,
+    KILL_COMMAND, MEMORY_COMMAND, MEMORY_COMMAND_LOAD_SUBCOMMAND, MEMORY_COMMAND_READ_SUBCOMMAND,
+    MEMORY_COMMAND_SAVE_SUBCOMMAND, MEMORY_COMMAND_SHORT, MEMORY_COMMAND_WRITE_SUBCOMMAND,
+    ORACLE_COMMAND, PROFILE_COMMAND, PROFILE_EXPORT_SUBCOMMAND, PROFILE_REPORT_SUBCOMMAND,
+    PROFILE_START_SUBCOMMAND, PROFILE_STOP_SUBCOMMAND, PTYPE_COMMAND, QUIT_COMMAND,
+    QUIT_COMMAND_SHORT, QUIT_DETACH_KEY, QUIT_KILL_KEY, REGISTER_COMMAND,
+    REGISTER_COMMAND_INFO_SUBCOMMAND, REGISTER_COMMAND_READ_SUBCOMMAND, REGISTER_COMMAND_SHORT,
+    REGISTER_COMMAND_WRITE_SUBCOMMAND, RESOLVE_COMMAND, RETURN_COMMAND, RUN_COMMAND,
+    RUN_COMMAND_SHORT, RUN_STDIN_KEY, RUN_STOP_AT_ENTRY_KEY, RUN_STOP_AT_MAIN_KEY, RWATCH_COMMAND,
+    SCRIPT_COMMAND, SESSION_COMMAND, SESSION_LOAD_SUBCOMMAND, SESSION_SAVE_SUBCOMMAND, SET_COMMAND,
+    SET_LISTSIZE_SUBCOMMAND, SET_PRINT_SUBCOMMAND, SET_QUIT_ACTION_SUBCOMMAND, SHARED_LIB_COMMAND,
+    SHARED_LIB_COMMAND_INFO_SUBCOMMAND, SNAPSHOT_COMMAND, SOURCE_COMMAND,
+    SOURCE_COMMAND_DISASM_SUBCOMMAND, SOURCE_COMMAND_FUNCTION_SUBCOMMAND, STEP_INSTRUCTION_COMMAND,
+    STEP_INTO_COMMAND, STEP_INTO_COMMAND_SHORT, STEP_OUT_COMMAND, STEP_OUT_COMMAND_SHORT,
+    STEP_OVER_COMMAND, STEP_OVER_COMMAND_SHORT, STEP_STATEMENT_KEY, SYMBOLIZE_COMMAND,
+    SYMBOL_COMMAND, THREAD_COMMAND, THREAD_COMMAND_CURRENT_SUBCOMMAND,
+    THREAD_COMMAND_INFO_SUBCOMMAND, THREAD_COMMAND_SWITCH_SUBCOMMAND, TRACE_COMMAND,
+    TRACE_REPORT_SUBCOMMAND, UNDISPLAY_COMMAND, VAR_COMMAND, VAR_LOCAL_KEY, WATCH_COMMAND,
+    WATCH_COMMAND_SHORT, WATCH_INFO_SUBCOMMAND, WATCH_REMOVE_SUBCOMMAND,
+    WATCH_REMOVE_SUBCOMMAND_SHORT,
+};
+use chumsky::prelude::{any, choice, just};
+use chumsky::text::whitespace;
+use chumsky::{extra, text, Parser};
+use crossterm::style::Stylize;
+use rustyline::completion::{Completer, Pair};
+use rustyline::Context;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use trie_rs::{Trie, TrieBuilder};
+
+struct CommandHint {
+    short: Option<String>,
+    long: String,
+    subcommands: Vec<String>,
+}
+
+impl CommandHint {
+    fn long(&self) -> String {
+        self.long.clone()
+    }
+
+    fn display_with_short(&self) -> String {
+        if let Some(ref short) = self.short {
+            if self.long.starts_with(short) {
+                format!(
+                    "{}{}",
+                    short.clone().bold().underlined(),
+                    &self.long[short.len()..]
+                )
+            } else {
+                format!("{}|{}", &self.long, short.clone().bold().underlined())
+            }
+        } else {
+            self.long()
+        }
+    }
+}
+
+impl From<&str> for CommandHint {
+    fn from(value: &str) -> Self {
+        CommandHint {
+            short: None,
+            long: value.to_string(),
+            subcommands: vec![],
+        }
+    }
+}
+
+impl From<(&str, &str)> for CommandHint {
+    fn from((short, long): (&str, &str)) -> Self {
+        CommandHint {
+            short: Some(short.to_string()),
+            long: long.to_string(),
+            subcommands: vec![],
+        }
+    }
+}
+
+pub struct CommandCompleter {
+    commands: Vec<CommandHint>,
+    subcommand_hints: HashMap<String, Vec<String>>,
+    file_hints: Trie<u8>,
+    var_hints: Trie<u8>,
+    vars: Vec<String>,
+    arg_hints: Trie<u8>,
+    args: Vec<String>,
+}
+
+impl CommandCompleter {
+    /// Create a completer for all debugger commands, `oracles` are completed
+    /// as subcommands of the `oracle` command.
+    pub fn new(oracles: &[&str]) -> Self {
+        let commands = [
+            VAR_COMMAND.into(),
+            ARG_COMMAND.into(),
+            CommandHint {
+                short: Some(CONTINUE_COMMAND_SHORT.to_string()),
+                long: CONTINUE_COMMAND.to_string(),
+                subcommands: vec![CONTINUE_FOR_KEY.to_string()],
+            },
+            CommandHint {
+                short: None,
+                long: FRAME_COMMAND.to_string(),
+                subcommands: vec![
+                    FRAME_COMMAND_INFO_SUBCOMMAND.to_string(),
+                    FRAME_COMMAND_SWITCH_SUBCOMMAND.to_string(),
+                    FRAME_COMMAND_MEMORY_SUBCOMMAND.to_string(),
+                ],
+            },
+            CommandHint {
+                short: Some(RUN_COMMAND_SHORT.to_string()),
+                long: RUN_COMMAND.to_string(),
+                subcommands: vec![
+                    RUN_STOP_AT_ENTRY_KEY.to_string(),
+                    RUN_STOP_AT_MAIN_KEY.to_string(),
+                    RUN_STDIN_KEY.to_string(),
+                ],
+            },
+            KILL_COMMAND.into(),
+            STEP_INSTRUCTION_COMMAND.into(),
+            CommandHint {
+                short: Some(STEP_INTO_COMMAND_SHORT.to_string()),
+                long: STEP_INTO_COMMAND.to_string(),
+                subcommands: vec![STEP_STATEMENT_KEY.to_string()],
+            },
+            (STEP_OUT_COMMAND_SHORT, STEP_OUT_COMMAND).into(),
+            RETURN_COMMAND.into(),
+            JUMP_COMMAND.into(),
+            CommandHint {
+                short: Some(STEP_OVER_COMMAND_SHORT.to_string()),
+                long: STEP_OVER_COMMAND.to_string(),
+                subcommands: vec![STEP_STATEMENT_KEY.to_string()],
+            },
+            SYMBOL_COMMAND.into(),
+            RESOLVE_COMMAND.into(),
+            SYMBOLIZE_COMMAND.into(),
+            PTYPE_COMMAND.into(),
+            (BREAK_COMMAND_SHORT, BREAK_COMMAND).into(),
+            CommandHint {
+                short: None,
+                long: CATCH_COMMAND.to_string(),
+                subcommands: vec![
+                    CATCH_THROW_SUBCOMMAND.to_string(),
+                    CATCH_CATCH_SUBCOMMAND.to_string(),
+                    CATCH_UNWIND_SUBCOMMAND.to_string(),
+                ],
+            },
+            CommandHint {
+                short: Some(WATCH_COMMAND_SHORT.to_string()),
+                long: WATCH_COMMAND.to_string(),
+                subcommands: vec![
+                    WATCH_REMOVE_SUBCOMMAND.to_string(),
+                    WATCH_REMOVE_SUBCOMMAND_SHORT.to_string(),
+                    WATCH_INFO_SUBCOMMAND.to_string(),
+                ],
+            },
+            RWATCH_COMMAND.into(),
+            AWATCH_COMMAND.into(),
+            CommandHint {
+                short: Some(BACKTRACE_COMMAND_SHORT.to_string()),
+                long: BACKTRACE_COMMAND.to_string(),
+                subcommands: vec![
+                    BACKTRACE_ALL_SUBCOMMAND.to_string(),
+                    BACKTRACE_ARGS_KEY.to_string(),
+                ],
+            },
+            CommandHint {
+                short: Some(MEMORY_COMMAND_SHORT.to_string()),
+                long: MEMORY_COMMAND.to_string(),
+                subcommands: vec![
+                    MEMORY_COMMAND_READ_SUBCOMMAND.to_string(),
+                    MEMORY_COMMAND_WRITE_SUBCOMMAND.to_string(),
+                    MEMORY_COMMAND_SAVE_SUBCOMMAND.to_string(),
+                    MEMORY_COMMAND_LOAD_SUBCOMMAND.to_string(),
+                ],
+            },
+            EXAMINE_COMMAND.into(),
+            CommandHint {
+                short: Some(REGISTER_COMMAND_SHORT.to_string()),
+                long: REGISTER_COMMAND.to_string(),
+                subcommands: vec![
+                    REGISTER_COMMAND_READ_SUBCOMMAND.to_string(),
+                    REGISTER_COMMAND_WRITE_SUBCOMMAND.to_string(),
+                    REGISTER_COMMAND_INFO_SUBCOMMAND.to_string(),
+                ],
+            },
+            (HELP_COMMAND_SHORT, HELP_COMMAND).into(),
+            CommandHint {
+                short: None,
+                long: THREAD_COMMAND.to_string(),
+                subcommands: vec![
+                    THREAD_COMMAND_INFO_SUBCOMMAND.to_string(),
+                    THREAD_COMMAND_SWITCH_SUBCOMMAND.to_string(),
+                    THREAD_COMMAND_CURRENT_SUBCOMMAND.to_string(),
+                ],
+            },
+            CommandHint {
+                short: None,
+                long: INFERIOR_COMMAND.to_string(),
+                subcommands: vec![
+                    INFERIOR_COMMAND_LIST_SUBCOMMAND.to_string(),
+                    INFERIOR_COMMAND_SWITCH_SUBCOMMAND.to_string(),
+                    INFERIOR_COMMAND_ATTACH_SUBCOMMAND.to_string(),
+                ],
+            },
+            CommandHint {
+                short: None,
+                long: SHARED_LIB_COMMAND.to_string(),
+                subcommands: vec![SHARED_LIB_COMMAND_INFO_SUBCOMMAND.to_string()],
+            },
+            CommandHint {
+                short: None,
+                long: DEADLOCK_COMMAND.to_string(),
+                subcommands: vec![DEADLOCK_CHECK_SUBCOMMAND.to_string()],
+            },
+            CommandHint {
+                short: None,
+                long: INFO_COMMAND.to_string(),
+                subcommands: vec![INFO_LINE_SUBCOMMAND.to_string()],
+            },
+            CommandHint {
+                short: None,
+                long: HISTORY_COMMAND.to_string(),
+                subcommands: vec![
+                    HISTORY_VALUES_SUBCOMMAND.to_string(),
+                    HISTORY_STEPS_SUBCOMMAND.to_string(),
+                ],
+            },
+            CommandHint {
+                short: None,
+                long: SOURCE_COMMAND.to_string(),
+                subcommands: vec![
+                    SOURCE_COMMAND_DISASM_SUBCOMMAND.to_string(),
+                    SOURCE_COMMAND_FUNCTION_SUBCOMMAND.to_string(),
+                ],
+            },
+            CommandHint {
+                short: None,
+                long: ORACLE_COMMAND.to_string(),
+                subcommands: oracles.iter().map(ToString::to_string).collect(),
+            },
+            SCRIPT_COMMAND.into(),
+            CommandHint {
+                short: None,
+                long: SESSION_COMMAND.to_string(),
+                subcommands: vec![
+                    SESSION_SAVE_SUBCOMMAND.to_string(),
+                    SESSION_LOAD_SUBCOMMAND.to_string(),
+                ],
+            },
+            CommandHint {
+                short: None,
+                long: TRACE_COMMAND.to_string(),
+                subcommands: vec![TRACE_REPORT_SUBCOMMAND.to_string()],
+            },
+            DPRINTF_COMMAND.into(),
+            CommandHint {
+                short: None,
+                long: FTRACE_COMMAND.to_string(),
+                subcommands: vec![FTRACE_STOP_SUBCOMMAND.to_string()],
+            },
+            CommandHint {
+                short: None,
+                long: COVERAGE_COMMAND.to_string(),
+                subcommands: vec![
+                    COVERAGE_START_SUBCOMMAND.to_string(),
+                    COVERAGE_STOP_SUBCOMMAND.to_string(),
+                    COVERAGE_REPORT_SUBCOMMAND.to_string(),
+                ],
+            },
+            CommandHint {
+                short: None,
+                long: PROFILE_COMMAND.to_string(),
+                subcommands: vec![
+                    PROFILE_START_SUBCOMMAND.to_string(),
+                    PROFILE_STOP_SUBCOMMAND.to_string(),
+                    PROFILE_REPORT_SUBCOMMAND.to_string(),
+                    PROFILE_EXPORT_SUBCOMMAND.to_string(),
+                ],
+            },
+            DISPLAY_COMMAND.into(),
+            UNDISPLAY_COMMAND.into(),
+            SNAPSHOT_COMMAND.into(),
+            DIFF_COMMAND.into(),
+            CommandHint {
+                short: None,
+                long: SET_COMMAND.to_string(),
+                subcommands: vec![
+                    SET_PRINT_SUBCOMMAND.to_string(),
+                    SET_QUIT_ACTION_SUBCOMMAND.to_string(),
+                    SET_LISTSIZE_SUBCOMMAND.to_string(),
+                ],
+            },
+            CommandHint {
+                short: Some(QUIT_COMMAND_SHORT.to_string()),
+                long: QUIT_COMMAND.to_string(),
+                subcommands: vec![QUIT_KILL_KEY.to_string(), QUIT_DETACH_KEY.to_string()],
+            },
+        ];
+
+        Self::with_commands(commands)
+    }
+
+    fn with_commands(commands: impl IntoIterator<Item = CommandHint>) -> Self {
+        let commands: Vec<CommandHint> = commands.into_iter().collect();
+        let subcommand_hints = commands
+            .iter()
+            .flat_map(|cmd| {
+                let mut hints = vec![(cmd.long.clone(), cmd.subcommands.clone())];
+                if let Some(ref short) = cmd.short {
+                    hints.push((short.clone(), cmd.subcommands.clone()));
+                }
+                hints
+            })
+            .collect::<HashMap<String, Vec<String>>>();
+
+        Self {
+            commands,
+            subcommand_hints,
+            file_hints: TrieBuilder::new().build(),
+            var_hints: TrieBuilder::new().build(),
+            arg_hints: TrieBuilder::new().build(),
+            args: vec![],
+            vars: vec![],
+        }
+    }
+
+    pub fn replace_file_hints(&mut self, files: impl IntoIterator<Item = PathBuf>) {
+        let mut builder = TrieBuilder::new();
+        files.into_iter().for_each(|path: PathBuf| {
+            let file_name = path
+                .file_name()
+                .and_then(|oss| oss.to_str())
+                .unwrap_or_default()
+                .to_owned();
+            builder.push(file_name);
+        });
+        self.file_hints = builder.build();
+    }
+
+    pub fn replace_local_var_hints(&mut self, variables: impl IntoIterator<Item = String>) {
+        let mut builder = TrieBuilder::new();
+        self.vars = variables.into_iter().collect();
+        self.vars.iter().for_each(|var| {
+            builder.push(var);
+        });
+        self.vars.push(VAR_LOCAL_KEY.to_string());
+        builder.push(VAR_LOCAL_KEY);
+        self.var_hints = builder.build();
+    }
+
+    pub fn replace_arg_hints(&mut self, args: impl IntoIterator<Item = String>) {
+        let mut builder = TrieBuilder::new();
+        self.args = args.into_iter().collect();
+        self.args.iter().for_each(|arg| {
+            builder.push(arg);
+        });
+        self.args.push(ARG_ALL_KEY.to_string());
+        builder.push(ARG_ALL_KEY);
+        self.arg_hints = builder.build();
+    }
+}
+
+#[derive(Debug)]
+enum CompletableCommand<'a> {
+    Breakpoint(&'a str),
+    PrintVariables(&'a str),
+    PrintArguments(&'a str),
+    Unrecognized(&'a str, Option<&'a str>),
+}
+
+impl<'a> CompletableCommand<'a> {
+    fn recognize(line: &'a str) -> Option<CompletableCommand> {
+        let op = just::<_, _, extra::Default>;
+
+        let bp = op(BREAK_COMMAND)
+            .or(op(BREAK_COMMAND_SHORT))
+            .then(whitespace().at_least(1))
+            .ignore_then(any().repeated().to_slice())
+            .map(CompletableCommand::Breakpoint);
+
+        let var = op(VAR_COMMAND)
+            .then(whitespace().at_least(1))
+            .ignore_then(any().repeated().to_slice())
+            .map(CompletableCommand::PrintVariables);
+
+        let arg = op(ARG_COMMAND)
+            .then(whitespace().at_least(1))
+            .ignore_then(any().repeated().to_slice())
+            .map(CompletableCommand::PrintArguments);
+
+        let other = text::ident()
+            .then_ignore(whitespace().at_least(1))
+            .then(text::ident().or_not())
+            .map(|(s1, s2): (&str, Option<&str>)| CompletableCommand::Unrecognized(s1.trim(), s2))
+            .padded();
+
+        let r = choice((bp, var, arg, other)).parse(line);
+        r.into_result().ok()
+    }
+}
+
+impl CommandCompleter {
+    /// Return completion candidates for a command line and a position in the line
+    /// from which candidates replace the rest of the line.
+    pub fn complete_line(&self, line: &str) -> (usize, Vec<Pair>) {
+        fn pairs_from_variants(
+            variants: impl Iterator<Item = impl ToString>,
+            line: &str,
+            tpl: &str,
+            replacement_suffix: &str,
+        ) -> (usize, Vec<Pair>) {
+            let pos = line.len() - tpl.len();
+            let pairs = variants.map(|v| Pair {
+                display: v.to_string(),
+                replacement: v.to_string() + replacement_suffix,
+            });
+            (pos, pairs.collect())
+        }
+
+        /// All variants with an underlined keyword (like `locals` or `all`).
+        fn pairs_with_keyword(variants: &[String], keyword: &str) -> Vec<Pair> {
+            variants
+                .iter()
+                .map(|v| Pair {
+                    display: if v == keyword {
+                        v.as_str().underlined().to_string()
+                    } else {
+                        v.clone()
+                    },
+                    replacement: v.clone(),
+                })
+                .collect()
+        }
+
+        match CompletableCommand::recognize(line) {
+            Some(CompletableCommand::Breakpoint(maybe_file)) => {
+                if maybe_file.trim().is_empty() {
+                    return (0, vec![]);
+                }
+
+                let variants = self.file_hints.predictive_search(maybe_file);
+                if !variants.is_empty() {
+                    let variants_iter = variants.iter().map(|var| {
+                        std::str::from_utf8(var.as_slice()).expect("invalid utf-8 string")
+                    });
+                    return pairs_from_variants(variants_iter, line, maybe_file, ":");
+                }
+            }
+            Some(CompletableCommand::PrintVariables(maybe_var)) => {
+                if maybe_var.trim().is_empty() {
+                    return (
+                        line.len() - maybe_var.len(),
+                        pairs_with_keyword(&self.vars, VAR_LOCAL_KEY),
+                    );
+                }
+
+                let variants = self.var_hints.predictive_search(maybe_var);
+                if !variants.is_empty() {
+                    let variants_iter = variants.iter().map(|var| {
+                        std::str::from_utf8(var.as_slice()).expect("invalid utf-8 string")
+                    });
+                    return pairs_from_variants(variants_iter, line, maybe_var, "");
+                }
+            }
+            Some(CompletableCommand::PrintArguments(maybe_arg)) => {
+                if maybe_arg.trim().is_empty() {
+                    return (
+                        line.len() - maybe_arg.len(),
+                        pairs_with_keyword(&self.args, ARG_ALL_KEY),
+                    );
+                }
+
+                let variants = self.arg_hints.predictive_search(maybe_arg);
+                if !variants.is_empty() {
+                    let variants_iter = variants.iter().map(|var| {
+                        std::str::from_utf8(var.as_slice()).expect("invalid utf-8 string")
+                    });
+                    return pairs_from_variants(variants_iter, line, maybe_arg, "");
+                }
+            }
+            Some(CompletableCommand::Unrecognized(cmd, mb_subcmd_part)) => {
+                if let Some(subcommands) = self.subcommand_hints.get(cmd) {
+                    let pos = cmd.len() + 1;
+                    let subcmd_part = mb_subcmd_part.unwrap_or_default();
+                    let subcommands = subcommands
+                        .iter()
+                        .filter(|&subcmd| subcmd.starts_with(subcmd_part))
+                        .map(|subcmd| Pair {
+                            display: subcmd.to_string(),
+                            replacement: subcmd.to_string(),
+                        })
+                        .collect();
+
+                    return (pos, subcommands);
+                }
+            }
+            _ => {}
+        }
+
+        let pairs = self
+            .commands
+            .iter()
+            .filter(|&cmd| cmd.long.starts_with(line))
+            .map(|cmd| Pair {
+                display: cmd.display_with_short(),
+                replacement: cmd.long(),
+            })
+            .collect();
+        (0, pairs)
+    }
+}
+
+impl Completer for CommandCompleter {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        _pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Self::Candidate>)> {
+        Ok(self.complete_line(line))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_complete_line() {
+        let mut completer = CommandCompleter::new(&["nop"]);
+        completer.replace_file_hints([PathBuf::from("/src/main.rs")]);
+        completer.replace_local_var_hints(["some_var".to_string()]);
+
+        let complete = |line: &str| {
+            let (pos, pairs) = completer.complete_line(line);
+            let replacements = pairs.into_iter().map(|p| p.replacement).collect::<Vec<_>>();
+            (pos, replacements)
+        };
+
+        assert_eq!(complete("cont"), (0, vec!["continue".to_string()]));
+        assert_eq!(
+            complete("reg r"),
+            (4, vec![REGISTER_COMMAND_READ_SUBCOMMAND.to_string()])
+        );
+        assert_eq!(complete("oracle "), (7, vec!["nop".to_string()]));
+        assert_eq!(complete("b mai"), (2, vec!["main.rs:".to_string()]));
+        assert_eq!(complete("var so"), (4, vec!["some_var".to_string()]));
+        assert_eq!(
+            complete("var "),
+            (4, vec!["some_var".to_string(), VAR_LOCAL_KEY.to_string()])
+        );
+    }
+}
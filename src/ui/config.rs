@@ -1,4 +1,6 @@
-use crate::ui::tui::config::KeyMap;
+#[cfg(feature = "tui")]
+use crate::ui::tui::config::{KeyMap, Palette};
+use std::path::PathBuf;
 use std::sync::OnceLock;
 use strum_macros::{Display, EnumString, IntoStaticStr};
 
@@ -43,7 +45,13 @@ pub struct UIConfig {
     /// Theme for visualizing program data and source codes.
     pub theme: Theme,
     /// Keymap for TUI.
+    #[cfg(feature = "tui")]
     pub tui_keymap: KeyMap,
+    /// Color palette for TUI.
+    #[cfg(feature = "tui")]
+    pub tui_palette: Palette,
+    /// Archives with source files, used if a source file doesn't exist.
+    pub source_archives: Vec<PathBuf>,
 }
 
 /// Read-only ui configuration (set only once, at debugger start).
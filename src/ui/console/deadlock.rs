@@ -0,0 +1,54 @@
+use crate::debugger::address::RelocatedAddress;
+use crate::debugger::{Deadlock, LockPlace};
+use crate::ui::console::print::style::{AddressView, FilePathView, FunctionNameView, KeywordView};
+
+fn render_lock_place(place: &LockPlace) -> String {
+    let function = format!(" in {}", FunctionNameView::from(place.function.as_deref()));
+    let location = place
+        .place
+        .as_ref()
+        .map(|(file, line)| format!(" at {}:{line}", FilePathView::from(file.to_string_lossy())))
+        .unwrap_or_default();
+    format!("{function}{location}")
+}
+
+/// Render found deadlocks: threads of each deadlock, a mutex that thread waits for
+/// and a thread that holds this mutex.
+///
+/// # Arguments
+///
+/// * `deadlocks`: deadlocks found by a debugger
+pub fn render_deadlocks(deadlocks: &[Deadlock]) -> Vec<String> {
+    if deadlocks.is_empty() {
+        return vec!["No deadlocks found".to_string()];
+    }
+
+    let mut lines = vec![];
+    for (i, deadlock) in deadlocks.iter().enumerate() {
+        lines.push(format!(
+            "{} #{} ({} threads):",
+            KeywordView::from("Deadlock"),
+            i + 1,
+            deadlock.waits.len()
+        ));
+        for wait in &deadlock.waits {
+            lines.push(format!(
+                "  thread #{} ({}) waits for mutex {}{}",
+                wait.thread.number,
+                wait.thread.pid,
+                AddressView::from(RelocatedAddress::from(wait.mutex)),
+                render_lock_place(&wait.place),
+            ));
+            if let Some(holder) = &wait.holder {
+                lines.push(format!(
+                    "    held by thread #{} ({}), guard `{}`{}",
+                    holder.thread.number,
+                    holder.thread.pid,
+                    holder.guard,
+                    render_lock_place(&holder.place),
+                ));
+            }
+        }
+    }
+    lines
+}
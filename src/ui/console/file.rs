@@ -1,16 +1,30 @@
 use crate::debugger::PlaceDescriptor;
+use crate::ui::command::set::Settings;
+use crate::ui::console::print::style::KeywordView;
+use crate::ui::source;
 use crate::ui::syntax;
 use crate::ui::syntax::StylizedLine;
-use std::cell::RefCell;
 use std::collections::HashMap;
-use std::io::BufRead;
 use std::path::{Path, PathBuf};
-use std::{fs, io};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use syntect::util::as_24_bit_terminal_escaped;
 
-#[derive(Default)]
+/// Source file renderer, shared between a console and a debugger hook (they run in different
+/// threads).
 pub struct FileView {
-    cached_lines: RefCell<HashMap<PathBuf, Box<[String]>>>,
+    cached_lines: Mutex<HashMap<PathBuf, Box<[String]>>>,
+    /// Number of source lines rendered at a stop place.
+    list_size: AtomicU64,
+}
+
+impl Default for FileView {
+    fn default() -> Self {
+        Self {
+            cached_lines: Mutex::default(),
+            list_size: AtomicU64::new(Settings::DEFAULT_LIST_SIZE),
+        }
+    }
 }
 
 impl FileView {
@@ -19,15 +33,27 @@ impl FileView {
         Self::default()
     }
 
-    fn render(&self, file_path: &Path, start: u64, length: u64) -> anyhow::Result<String> {
-        let mut cache = self.cached_lines.borrow_mut();
+    /// Set a number of source lines rendered at a stop place (see [`FileView::render_stop_place`]).
+    pub fn set_list_size(&self, size: u64) {
+        self.list_size.store(size, Ordering::Relaxed);
+    }
+
+    /// Render source lines, lines are numbered from `start + 1`.
+    /// If `stop_line` is set, a gutter with an arrow at this line is rendered.
+    /// `embedded` source is used if a file doesn't exist (see [`source::read_source`]).
+    fn render(
+        &self,
+        file_path: &Path,
+        embedded: Option<Arc<str>>,
+        start: u64,
+        length: u64,
+        stop_line: Option<u64>,
+    ) -> anyhow::Result<String> {
+        let mut cache = self.cached_lines.lock().unwrap();
         let file_lines = match cache.get(file_path) {
             None => {
-                let file = fs::File::open(file_path)?;
-                let lines = io::BufReader::new(file)
-                    .lines()
-                    .map_while(Result::ok)
-                    .collect::<Vec<_>>();
+                let source = source::read_source(file_path, embedded)?;
+                let lines = source.lines().map(ToString::to_string).collect::<Vec<_>>();
                 cache.insert(file_path.to_path_buf(), lines.into_boxed_slice());
                 cache.get(file_path).unwrap()
             }
@@ -46,11 +72,18 @@ impl FileView {
                 let line_number = start + 1 + i;
                 i += 1;
 
+                let gutter = match stop_line {
+                    Some(stop_line) if stop_line == line_number => {
+                        format!("{} {line_number:>4}", KeywordView::from("=>"))
+                    }
+                    Some(_) => format!("   {line_number:>4}"),
+                    None => format!("{line_number:>4}"),
+                };
                 match line_renderer.render_line(line)? {
-                    StylizedLine::NoneStyle(line) => Ok(format!("{acc}{line_number:>4} {line}\n")),
+                    StylizedLine::NoneStyle(line) => Ok(format!("{acc}{gutter} {line}\n")),
                     StylizedLine::Stylized(segments) => {
                         let escaped = as_24_bit_terminal_escaped(&segments, false);
-                        Ok(format!("{acc}{line_number:>4} {escaped}\x1b[0m\n"))
+                        Ok(format!("{acc}{gutter} {escaped}\x1b[0m\n"))
                     }
                 }
             })?;
@@ -71,18 +104,40 @@ impl FileView {
             line_pos - bounds
         };
 
-        self.render(place.file, start, bounds * 2 + 1)
+        self.render(
+            place.file,
+            place.embedded_source(),
+            start,
+            bounds * 2 + 1,
+            Some(line_number),
+        )
+    }
+
+    /// Render source lines around a stop place, a number of lines is set by `set listsize`
+    /// command, a stop line is in the middle of them.
+    pub fn render_stop_place(&self, place: &PlaceDescriptor) -> anyhow::Result<String> {
+        let size = self.list_size.load(Ordering::Relaxed);
+        let line_number = place.line_number.max(1);
+        let start = (line_number - 1).saturating_sub(size.saturating_sub(1) / 2);
+        self.render(
+            place.file,
+            place.embedded_source(),
+            start,
+            size,
+            Some(line_number),
+        )
     }
 
     pub fn render_source_range(
         &self,
         file: &Path,
+        embedded: Option<Arc<str>>,
         from_line: u64,
         to_line: u64,
     ) -> anyhow::Result<String> {
         let start = if from_line == 0 { 0 } else { from_line - 1 };
         let bound = to_line - from_line + 1;
 
-        self.render(file, start, bound)
+        self.render(file, embedded, start, bound, None)
     }
 }
@@ -1,4 +1,3 @@
-use crate::debugger::Debugger;
 use crate::ui::command;
 use command::parser;
 
@@ -7,26 +6,55 @@ Available debugger commands:
 
 var <name or expression>|locals             -- show local and global variables
 arg <name or expression>|all                -- show arguments of current stack frame
-bt, backtrace <>|all                        -- print backtrace of all stack frames in current thread or from all threads
-f, frame info|switch <number>               -- print current stack frame information or change frame
-c, continue                                 -- continue program being debugged, after signal or breakpoint
-r, run                                      -- start or restart debugged programm 
-stepi                                       -- step one instruction
-step, stepinto                              -- step program until it reaches a different source line
-finish, stepout                             -- execute program until selected stack frame returns
-next, stepover                              -- step program, stepping over subroutine calls
+bt, backtrace <>|all [--args]               -- print backtrace of all stack frames in current thread or from all threads
+f, frame info|switch <number>|memory        -- print current stack frame information, change frame or dump frame stack memory
+c, continue <>|--for <duration>             -- continue program being debugged, after signal or breakpoint
+r, run <>|< <path>|--stdin <path>           -- start or restart debugged programm, optionally with stdin from a file
+kill                                        -- kill debugged programm and all processes spawned by it
+stepi <>|<count>                            -- step one instruction
+step, stepinto <>|stmt <>|<count>           -- step program until it reaches a different source line
+finish, stepout <>|<count>                  -- execute program until selected stack frame returns
+next, stepover <>|stmt <>|<count>           -- step program, stepping over subroutine calls
+return <>|<value>                           -- abort current function and return to the caller
+jump <line>|<file:line>                     -- continue execution from another line of the current function
 b, break <addr>|<file:line>|<function>      -- manage breakpoints
+catch throw|catch|unwind                    -- stop when C++ exception is thrown or caught, or continue to its handler
 w, watch +w|+rw| <expression>|<addr:size>   -- manage write or read-write watchpoints
-symbol <name>                               -- print symbol kind and address
-mem, memory read|write <addr>               -- read or write into debugged program memory
+trace <file:line>|<function>|report         -- set a non-stopping tracepoint or show tracepoints report
+dprintf <file:line>|<function> "<format>"   -- print a formatted message at each hit without stopping
+ftrace <glob>|stop                          -- print calls and returns of functions matching a pattern without stopping
+coverage start <file>..|stop|report <>|<path> -- collect line coverage of source files, print or save an lcov report
+profile start|stop|report|export            -- sample backtraces of a running program and show a profile
+display <>|<expression>                     -- print expression after each stop or show all displays
+undisplay <number>                          -- stop displaying an expression
+snapshot <expression>                       -- capture expression value for a later diff
+diff <expression>                           -- print changes of expression value since the snapshot
+symbol <name>|<regex>                       -- print kind, address and defining file of symbols
+resolve <addr> [<addr> ...]                 -- resolve addresses into function, inlined functions and file:line
+symbolize <path>                            -- resolve addresses of a backtrace stored in a file
+ptype <variable>|<type>                     -- print type declaration with memory layout
+mem, memory read|write|save|load <addr>     -- read or write into debugged program memory, save or load a memory region
+x/<count><format><size> <addr>              -- examine debugged program memory
 reg, register read|write|info <addr>        -- read, write, or view debugged program registers
 thread info|current|switch <number>         -- show list of threads or current (in focus) thread or set thread in focus
+inferior list|switch <n>|attach <pid>       -- show list of debugged processes, switch current process or attach a new one
 sharedlib info                              -- show list of shared libraries
+deadlock check                              -- find threads that wait for each other on mutexes
+info line <>|<addr>|<file:line>|<function>  -- show address range and line table rows of a statement
+history values                              -- show values history
 source asm|fn|<bounds>                      -- show source code or assembly instructions for current (in focus) function
 oracle <oracle> <>|<subcommand>             -- execute a specific oracle
+script <path>                               -- execute an automation script
+session save|load <path>                    -- save or load breakpoints, watchpoints and displays
+set print <setting> <value>                 -- change debugger output settings
+set quit-action ask|kill|detach             -- change an action applied to a running program on quit
+set listsize <n>                            -- change a number of source lines printed at a stop place
 h, help <>|<command>                        -- show help
 tui                                         -- change ui mode to tui
-q, quit                                     -- exit the BugStalker 
+q, quit <>|--kill|--detach                  -- exit the BugStalker 
+
+Type `help <command>` for usage and examples of a command.
+Commands and subcommands may be abbreviated to an unambiguous prefix, like `cont` or `reg rea rax`.
 "#;
 
 pub const DQE_DESCRIPTION: &str = "
@@ -43,6 +71,9 @@ Available operators:
 `.` - get field, available for structs, enums and hashmaps (with string keys)
 `(` and `)` - parentheses to prioritize operations
 `({ptr/ref type})` - cast constant address to typed pointer or reference
+`as {type}` - cast operator, numbers and pointers are converted by value (signed integers are sign-extended), a value of any other type is read from the memory of the operand, the target type can't be larger than the operand
+`${register}` - register value at the selected frame (only `$rip` and `$rsp` are known at outer frames)
+`${number}` - value from the values history, results of `var` and `finish` commands are stored there
 `[{literal}]` - index operator, available for arrays, enums, vectors, veqdequeues, hashmaps, hashsets, btreemaps and btreesets. 
 Literal is a json-like object (with wildcards feature), that can be used for matching with real data. 
 See `help dqe literal` for more information
@@ -55,6 +86,10 @@ Examples:
 `&vec1[1]` - print address of second element in vector `vec1`
 `(**var1).field1` - print field `field1` in struct pointed to by the pointer `*var1`
 `*(*const i32)0x1234AA332` - cast memory address to `*const i32` pointer, then dereference it 
+`$rsp` - print stack pointer value
+`$3.field1` - print field `field1` of the third value from the values history
+`*($rdi as *const SomeStruct)` - print structure pointed to by the `rdi` register
+`(*ptr) as u64` - convert a number pointed to by `ptr` to an unsigned integer, or read the first 8 bytes of a larger value as an unsigned integer
 `hashmap[0x1337]` - get value by pointer key 0x1337 from hashmap 
 `hashmap[{\"a\", \"b\"}]` - get value by array key {\"a\", \"b\"} from hashmap
 `*(*(var1.field1)).field2[1][2]` - get `field1` from struct `var1`, dereference it, 
@@ -89,208 +124,1024 @@ Example of usage of literal objects:
 field `field_1` equals to 1, field `field_2` equals to `Option::Some(true)` and field `field_3` equals to any value
 ";
 
-pub const HELP_VAR: &str = "\
-\x1b[32;1mvar\x1b[0m
+pub const HELP_VAR: CommandHelp = CommandHelp {
+    names: &["var"],
+    description: "\
 Show local and global variables, supports data queries expressions over variables (see `help dqe`).
-
-Available subcomands:
-var locals - print current stack frame local variables
+",
+    usage: "\
+var locals, var * - print current stack frame local variables
 var <name or expression> - print local and global variables with selected name
+var <pattern> - print local (or global, if there are no matched locals) variables with names matched by a glob pattern (`*` - any sequence of characters, `?` - any character)
+var --json <locals|*|pattern|expression> [> <path>] - print variables as JSON, or write JSON into a file
+",
+    details: "\
+JSON output contains a full variable tree without render limits: each node has `name`, `type` and `address` fields,
+a scalar or a pointer value is placed in `value` field, nested values are placed in `members` (structures),
+`items` (lists) or `entries` (maps, as `{\"key\": .., \"value\": ..}` objects) fields.
 
-Examples of usage:
+Values of variables that are not live at the current PC are never printed, such variables are reported as
+`<not yet initialized>` (a variable is declared later in a function) or `<optimized out at this PC>`
+(a location list doesn't describe where the variable is) together with PC ranges where the variable is live.
+
+If a local variable is shadowed, `var <name>` prints the innermost live declaration, followed by a list
+of all declarations in the current function with their places, states (live, shadowed, not yet initialized,
+out of scope) and values of shadowed declarations.
+
+Channel senders and receivers (`std::sync::mpsc` and `crossbeam-channel`) are printed with a number of queued
+messages (`len`), a capacity of bounded channels (`cap`), disconnected flags and queued messages (`queue`).
+",
+    examples: "\
 var locals - print current stack frame local variables
 var some_variable - print all variables with given name, variables can be in local or global scope 
+var some_* - print all variables with names starting with `some_`
 var *some_variable - dereference and print value if `some_variable` is a pointer or RC/ARC
 var some_array[0] - print first element if `some_array` is a vector, array, vecdeque or enum
 var some_array[2..5] - print 3 elements, starts from index 2
 var *some_array[0] - print dereferenced value of some_array[0]
 var (*some_array)[0] - print first element of *some_array
-";
+var --json * > vars.json - write all local variables as JSON into `vars.json` file
+",
+};
 
-pub const HELP_ARG: &str = "\
-\x1b[32;1marg\x1b[0m
+pub const HELP_ARG: CommandHelp = CommandHelp {
+    names: &["arg"],
+    description: "\
 Show current stack frame arguments, supports data queries expressions over arguments (see `help dqe`).
-
-Available subcomands:
-arg all - print all arguments
+",
+    usage: "\
+arg all, arg * - print all arguments
 arg <name or expression> - print argument with selected name
-
-Examples of usage:
+arg <pattern> - print arguments with names matched by a glob pattern
+",
+    details: "",
+    examples: "\
 arg all - print current stack frame local variables
 arg some_arg - print argument with name equals to `some_arg`
+arg *_id - print arguments with names ending with `_id`
 arg *some_arg - dereference and print value if `some_arg` is a pointer or RC/ARC
-";
+",
+};
 
-pub const HELP_BACKTRACE: &str = "\
-\x1b[32;1mbt, backtrace\x1b[0m
+pub const HELP_BACKTRACE: CommandHelp = CommandHelp {
+    names: &["bt", "backtrace"],
+    description: "\
 Show backtrace of all stack frames in current thread or from all threads.
-
-Available subcomands:
+",
+    usage: "\
 backtrace all - show backtrace for all running threads
 backtrace - show backtrace of current thread
+",
+    details: "\
+Available flags:
+--args - resolve and show function arguments of each frame (long values are truncated)
 
 Output format:
 thread {id} - {current ip value}
 {current ip value} - {function name} ({function address} + {offset})
 {the address of the instruction in the overlay stack frame} - {function name} ({function address} + {offset})
 ...
-";
+",
+    examples: "",
+};
 
-pub const HELP_FRAME: &str = "\
-\x1b[32;1mf, frame\x1b[0m
+pub const HELP_FRAME: CommandHelp = CommandHelp {
+    names: &["f", "frame"],
+    description: "\
 Show current stack frame info or set frame to focus.
-
-Available subcomands:
+",
+    usage: "\
 frame info - show current stack frame information (see output explanation)
 frame switch <number> - set frame <number> to focus
-
-Output format:
+frame memory - dump stack memory of current stack frame (between stack pointer and cfa)
+",
+    details: "\
+Output format (frame info):
 cfa: {address} -- canonical frame address
 return address: {address} - return address for current stack frame
-";
 
-pub const HELP_CONTINUE: &str = "\
-\x1b[32;1mc, continue\x1b[0m
+Output format (frame memory):
+{address}: {value} <- {annotations} -- stack slot, annotations are saved registers, return address and local variables
+",
+    examples: "",
+};
+
+pub const HELP_CONTINUE: CommandHelp = CommandHelp {
+    names: &["c", "continue"],
+    description: "\
 Continue program being debugged, after signal or breakpoint.
-";
+",
+    usage: "\
+continue - continue until the next stop
+continue --for <duration> - continue and interrupt the program if it is still running after <duration>
+  (like 500ms, 5s or 1m), useful to find out where a program spends its time
+",
+    details: "",
+    examples: "\
+continue --for 5s
+c --for 200ms
+",
+};
 
-pub const HELP_RUN: &str = "\
-\x1b[32;1mr, run\x1b[0m
+pub const HELP_RUN: CommandHelp = CommandHelp {
+    names: &["r", "run"],
+    description: "\
 Start or restart debugged programm.
-";
+",
+    usage: "\
+run - start or restart a program, program runs until a breakpoint, a signal or exit
+run --stop-at-entry - stop at the program entry point, before any runtime initialization
+run --stop-at-main - stop at the start of the program `main` function (not at a runtime entry shim)
+run < <path>, run --stdin <path> - use a file as a standard input of the program (for this run only)
+",
+    details: "\
+Standard input redirection may be combined with stop options, e.g. `run --stop-at-main < input.txt`.
+Without redirection the program shares the terminal stdin with the debugger.
+",
+    examples: "",
+};
 
-pub const HELP_STEPI: &str = "\
-\x1b[32;1mstepi\x1b[0m
+pub const HELP_KILL: CommandHelp = CommandHelp {
+    names: &["kill"],
+    description: "\
+Kill debugged programm and all processes spawned by it (children, children of children and so on).
+Breakpoints and watchpoints are kept, use `run` to start the program again.
+",
+    usage: "\
+kill - kill the program
+",
+    details: "",
+    examples: "",
+};
+
+pub const HELP_STEPI: CommandHelp = CommandHelp {
+    names: &["stepi"],
+    description: "\
 step one instruction.
-";
+After a step, the next instruction is printed with its bytes, a target of a call or a jump
+is shown as a function name with an offset (resolved from debug information or a symbol table).
+",
+    usage: "\
+stepi - step one instruction
+stepi <count> - step <count> instructions, program stops only once (or earlier at a breakpoint or a signal)
+",
+    details: "",
+    examples: "",
+};
 
-pub const HELP_STEPINTO: &str = "\
-\x1b[32;1mstep, stepinto\x1b[0m
+pub const HELP_STEPINTO: CommandHelp = CommandHelp {
+    names: &["step", "stepinto"],
+    description: "\
 Step program until it reaches a different source line.
-";
+`Fn*` trait call shims (like `FnOnce::call_once`) are stepped through,
+so a step into a closure call stops in a closure body.
+",
+    usage: "\
+step - step once
+step <count> - step <count> times, program stops only once (or earlier at a breakpoint or a signal)
+step stmt <>|<count> - step until the next statement, even if it's on the same source line
+",
+    details: "",
+    examples: "",
+};
 
-pub const HELP_STEPOUT: &str = "\
-\x1b[32;1mfinish, stepout\x1b[0m
+pub const HELP_STEPOUT: CommandHelp = CommandHelp {
+    names: &["finish", "stepout"],
+    description: "\
 Execute program until selected stack frame returns.
-";
+Value returned from the function is printed if it's a scalar, a pointer or a c-style enum.
+",
+    usage: "\
+finish - return from the current frame
+finish <count> - return from <count> frames, program stops only once (or earlier at a breakpoint)
+",
+    details: "",
+    examples: "",
+};
+
+pub const HELP_RETURN: CommandHelp = CommandHelp {
+    names: &["return"],
+    description: "\
+Abort execution of the current function (function of the selected thread) and return to the caller.
+Caller registers are restored from the unwind information, program stops after the call instruction.
+Optional value is placed into the return register: integers, booleans and pointers into RAX,
+floating point numbers into XMM0 (as f64).
+",
+    usage: "\
+return - return without a value (return register is untouched)
+return <value> - return a value, value is a number (decimal, hex or floating point) or a boolean
+",
+    details: "",
+    examples: "\
+return 42
+return -1.5
+return false
+",
+};
+
+pub const HELP_JUMP: CommandHelp = CommandHelp {
+    names: &["jump"],
+    description: "\
+Move program counter (of the selected thread) to the first instruction of another line.
+Instructions between current and target places are not executed, the program is not resumed.
+Target line must be in the current function, otherwise a confirmation is required
+(jump outside of the current function usually leads to stack corruption).
+",
+    usage: "\
+jump <line> - jump to the line in the current file
+jump <file:line> - jump to the line in the file
+",
+    details: "",
+    examples: "\
+jump 15
+jump main.rs:15
+",
+};
 
-pub const HELP_STEPOVER: &str = "\
-\x1b[32;1mnext, stepover\x1b[0m
+pub const HELP_STEPOVER: CommandHelp = CommandHelp {
+    names: &["next", "stepover"],
+    description: "\
 Step program, stepping over subroutine calls.
-";
+In an async function a step over `.await` continues until the same task is resumed
+(a state machine of the function is polled again), instead of stopping in an executor.
+",
+    usage: "\
+next - step once
+next <count> - step <count> times, program stops only once (or earlier at a breakpoint or a signal)
+next stmt <>|<count> - step until the next statement, even if it's on the same source line
+",
+    details: "",
+    examples: "",
+};
 
-pub const HELP_BREAK: &str = "\
-\x1b[32;1mb, break\x1b[0m
+pub const HELP_BREAK: CommandHelp = CommandHelp {
+    names: &["b", "break"],
+    description: "\
 Manage breakpoints.
-
-Available subcomands:
+",
+    usage: "\
 break <location> - set breakpoint to location
 break remove <location>|<number> - deactivate and delete selected breakpoint
 break info - show all breakpoints
-
+break enable <number> - enable previously disabled breakpoint
+break disable <number> - disable breakpoint, disabled breakpoint never stops a program
+break --regex <pattern> - set breakpoints at all functions which full names match a regular expression,
+                          created breakpoints are united into a group
+break enable|disable --group <number> - enable or disable all breakpoints of a group
+break --on-return <function> [== <value>] - stop when a function returns (and a returned value
+                                            equals to the given one, ex. `break --on-return read == -1`),
+                                            debugee stops at a return address in a caller
+break --hardware <location> - set breakpoint using a debug register instead of a planted instruction,
+                              such breakpoint stays in place if a program overwrites its code
+                              (JIT or self-modifying code), there are 4 debug registers shared
+                              with watchpoints
+break commands <number> <command>; ... - execute console commands each time the breakpoint is hit,
+                                         `continue` as the last command allows unattended runs,
+                                         no commands removes attached commands
+",
+    details: "\
 Posible location format:
 - at instruction. Example: break 0x55555555BD30
 - at function start. A function can be defined by its full name (with namespace) 
 or by function name (in case of possible collisions, breakpoints will be set in 
-all matching functions). Examples: 
+all matching functions). Generic arguments are ignored, breakpoints are set in all
+monomorphizations of a generic function. Breakpoints at several functions are united
+into a group. Examples: 
     * break fn1 
     * break module1::fn1
-- at code line. Example: break hello_world.rs:15
-- a breakpoint number (only for `remove` subcommand)
-";
+    * break Wrapper<T>::new
+- at code line. A file can be defined by its name or by a suffix of its path
+(if the name matches more than one file, candidates are listed), an empty
+file name means the current file. Examples:
+    * break hello_world.rs:15
+    * break src/utils/mod.rs:17
+    * break :42
+If there is no code at the line, a breakpoint is planted at the next line or,
+if there is no code at the next line too, closest lines with code are printed.
+- at statement on a code line, a line with several statements (closures, chained calls)
+can be narrowed by a column, available columns are printed when such line is requested.
+Example: break hello_world.rs:15:5
+- a breakpoint number (only for `remove`, `enable`, `disable` and `commands` subcommands)
+",
+    examples: "",
+};
+
+pub const HELP_CATCH: CommandHelp = CommandHelp {
+    names: &["catch"],
+    description: "\
+Stop a program on C++ exception handling events (for programs with C++ code).
+Catchpoints are breakpoints at C++ runtime functions (`__cxa_throw` and `__cxa_begin_catch`),
+a type of exception is printed at each hit. Catchpoints are listed, disabled and removed
+by `break info|disable|remove` commands.
+",
+    usage: "\
+catch throw - stop when an exception is thrown
+catch catch - stop when an exception is caught by a handler
+catch unwind - continue until a thrown exception is caught, stop at the start of a handler
+",
+    details: "",
+    examples: "",
+};
 
-pub const HELP_WATCH: &str = "\
-\x1b[32;1mw, watch\x1b[0m
+pub const HELP_WATCH: CommandHelp = CommandHelp {
+    names: &["w", "watch", "rwatch", "awatch"],
+    description: "\
 Manage watchpoints. Note that watchpoints for local variables and watchpoints for global varibales
 or raw memory region have a different lifetimes. Watchpoints for global variables or memory locations
 are lives until BugStalker session is alive. On the contrary, watchpoints for local variables
 are lives until debugee is not restarted, and will be removed automatically.
-
-Available subcomands:
+Address of an expression that goes through a pointer is re-resolved at each debugee stop,
+watchpoint moves to a new location if pointer is reassigned, or removed if pointer is invalid.
+Hardware can't trap on reads only, so read watchpoints observe all accesses and skip the stores
+(the trapping instruction is decoded). Stop message shows whether the access was a load or a store
+and the address of the accessing instruction.
+",
+    usage: "\
 watch +rw|+w| <addr:size> - set write or read-write watchpoint (write by default) to memory location [addr; addr+size], size must be one of [1,2,4,8] bytes
 watch +rw|+w| <expression> - set write or read-write watchpoint (write by default) to DQE result (see `help dqe`), expression result must one of [1,2,4,8] bytes
+watch +r <addr:size>|<expression> - set read watchpoint
+rwatch <addr:size>|<expression> - set read watchpoint, same as `watch +r`
+awatch <addr:size>|<expression> - set access (read or write) watchpoint, same as `watch +rw`
 watch remove <addr:size>|<expression>|<number> - deactivate and delete selected watchpoint
 watch info - show all watchpoints
-
-Examples:
+",
+    details: "",
+    examples: "\
 * watch 0x00000004:4 - set watchpoint to memory region [0x0..04:0x0..07]
 * watch var1 - set watchpoint (write condition) to variable `var1`
 * watch +rw var1 - set watchpoint (read-write condition) to variable `var1`
+* rwatch var1 - stop when variable `var1` is read
+* awatch 0x00000004:4 - stop when memory region [0x0..04:0x0..07] is read or written
 * watch struct1.field1 - set watchpoint to `field1` of variable `struct1`
 * watch arr[2] - set watchpoint to 2nd element of variable `arr`
-";
+* watch (*struct1.ptr).field1 - set watchpoint to `field1` of a structure behind pointer `struct1.ptr`
+",
+};
 
-pub const HELP_SYMBOL: &str = "\
-\x1b[32;1msymbol\x1b[0m
-Print symbols matched by regular expression.
-
-Available subcomands:
+pub const HELP_SYMBOL: CommandHelp = CommandHelp {
+    names: &["symbol"],
+    description: "\
+Print functions, static variables and symbol table entries matched by a regular expression
+(or by a substring if query is not a valid regular expression).
+For each symbol print its kind (func, object or other), address and a file where it is defined
+(source file if symbol is described in debug information, object file otherwise).
+",
+    usage: "\
 symbol <name_regex>
-";
+",
+    details: "",
+    examples: "\
+* symbol main - print all symbols which name contains `main`
+* symbol ^myapp::GLOBAL$ - print static variable `GLOBAL` from `myapp` crate
+",
+};
 
-pub const HELP_MEMORY: &str = "\
-\x1b[32;1mmem, memory\x1b[0m
-Read or write into debugged program memory.
+pub const HELP_RESOLVE: CommandHelp = CommandHelp {
+    names: &["resolve"],
+    description: "\
+Resolve addresses (in debugee address space) into an object file (executable or shared library),
+a function that contains an address, a chain of functions inlined at an address and source places.
+All hexadecimal numbers from command arguments are resolved, so a line of a raw
+backtrace (like a panic backtrace) may be pasted as is.
+",
+    usage: "\
+resolve <addr> [<addr> ...]
+",
+    details: "",
+    examples: "\
+* resolve 0x55555555A1B2 - print function and source place for a single address
+* resolve 0x55555555A1B2 0x55555555A2C0 - resolve several addresses at once
+* resolve   4: 0x55555555A1B2 - std::rt::lang_start - resolve an address from a backtrace line
+",
+};
 
-Available subcomands:
+pub const HELP_SYMBOLIZE: CommandHelp = CommandHelp {
+    names: &["symbolize"],
+    description: "\
+Read a backtrace (list of hexadecimal addresses, like a rust panic or ASan backtrace) from a file
+and resolve each frame into a function, chain of inlined functions and a source place.
+Frames with an object file and an offset, like `(/path/to/program+0x1f1b3)` in ASan backtraces,
+are resolved using debug information of this object file, so a running program is not required.
+Other addresses are treated as addresses in a running program address space, or as addresses
+in the main executable if program is not running.
+",
+    usage: "\
+symbolize <path>
+",
+    details: "",
+    examples: "\
+* symbolize /tmp/backtrace.txt - resolve all frames from `/tmp/backtrace.txt` file
+",
+};
+
+pub const HELP_PTYPE: CommandHelp = CommandHelp {
+    names: &["ptype"],
+    description: "\
+Print type declaration of a variable (or argument) or a type with a given name.
+Declaration is printed as a rust-like source code with memory layout of a type:
+offsets and sizes of structure fields, padding, discriminants of enum variants,
+element and target types of arrays and pointers.
+",
+    usage: "\
+ptype <variable_name> - print type of a variable or argument
+ptype <type_name> - print type with a given name, if there is no variable with this name
+",
+    details: "",
+    examples: "\
+* ptype foo - print type of variable `foo`
+* ptype alloc::string::String - print layout of `String` type
+",
+};
+
+pub const HELP_MEMORY: CommandHelp = CommandHelp {
+    names: &["mem", "memory"],
+    description: "\
+Read or write into debugged program memory.
+",
+    usage: "\
 memory read <address> - print 8-byte block at address in debugee memory
 memory write <address> <value> - writes 8-byte value to address in debugee memory
-";
+memory save <address> <length> <path> - save a memory region into a file (length is a decimal or hexadecimal number)
+memory load <path> <address> - write a file content into debugee memory starting from address
+",
+    details: "\
+Address is a hexadecimal number or a register name prefixed with `$` (like `$rsp`),
+register value is taken at the selected frame.
+",
+    examples: "",
+};
 
-pub const HELP_REGISTER: &str = "\
-\x1b[32;1mreg, register\x1b[0m
-Read, write, or view debugged program registers (x86_64 registers support).
+pub const HELP_EXAMINE: CommandHelp = CommandHelp {
+    names: &["x"],
+    description: "\
+Examine debugged program memory. Count, format and size letters are optional.
+Address may be a hexadecimal number, a register name prefixed with `$`
+or an expression (see `help dqe`): a pointer or an integer value is an address itself,
+memory of a value of any other type is examined at the location of this value
+(use `&var` to examine memory of a pointer or an integer variable).
+",
+    usage: "\
+x/<count><format><size> <address> - print <count> units of memory at address
+x <address> - print a single 4-byte unit as a hexadecimal number
+",
+    details: "\
+Formats:
+x - hexadecimal (default)
+d - signed decimal
+u - unsigned decimal
+o - octal
+t - binary
+f - floating point number (4 or 8 bytes size)
+c - character
+s - null-terminated string, size is ignored
+i - machine instruction, size is ignored
+
+Sizes:
+b - byte
+h - halfword (2 bytes)
+w - word (4 bytes, default)
+g - giant (8 bytes)
+",
+    examples: "\
+x/8xg $rsp - print 8 giant words at the top of the stack
+x/s 0x7ffff7dd1000 - print a string
+x/4i $rip - print 4 instructions starting from the program counter
+x/16cb ptr - print first 16 bytes pointed to by `ptr` as characters
+x/2xg &var - print first 16 bytes of variable `var`
+",
+};
 
-Available subcomands:
+pub const HELP_REGISTER: CommandHelp = CommandHelp {
+    names: &["reg", "register"],
+    description: "\
+Read, write, or view debugged program registers (x86_64 registers support).
+",
+    usage: "\
 register read <reg_name> - print value of register by name (x86_64 register name in lowercase)
+register read <reg_name> as <lanes> - print value of register split into lanes
+register <reg_name> as <lanes> - same as above
 register write <reg_name> <value> - set new value to register by name
 register info - print list of registers with it values
-";
+",
+    details: "\
+Vector registers xmm0-xmm15 and ymm0-ymm15 can be read (ymm registers require AVX support),
+without lanes they are printed as a list of 64-bit words, lowest word first.
+Lanes format is <type>x<count>, type is one of i8, i16, i32, i64, u8, u16, u32, u64, f32, f64.
+Lanes that don't fit into a register are not shown.
+",
+    examples: "\
+register read xmm0
+register xmm0 as f32x4
+register ymm1 as u8x32
+register read rax as u16x4
+",
+};
 
-pub const HELP_THREAD: &str = "\
-\x1b[32;1mthread\x1b[0m
+pub const HELP_THREAD: CommandHelp = CommandHelp {
+    names: &["thread"],
+    description: "\
 Show threads information or set thread to focus.
-
-Available subcomands:
-thread info - print list of thread information
+",
+    usage: "\
+thread info - print list of thread information (thread name and place where thread was created included)
 thread current - prints thread that has focus
 thread switch <number> - set thread <number> to focus
-";
+",
+    details: "\
+Threads are grouped by a role (like user, tokio worker or tokio blocking threads) if an enabled oracle knows it.
+",
+    examples: "",
+};
 
-pub const HELP_SHARED_LIB: &str = "\
-\x1b[32;1msharedlib\x1b[0m
-Show shared libraries information.
+pub const HELP_INFERIOR: CommandHelp = CommandHelp {
+    names: &["inferior"],
+    description: "\
+Show debugged processes (inferiors) or set the current one.
+Only the current inferior runs, other inferiors stay stopped until they become current.
+Breakpoints, watchpoints, threads and frames are independent in each inferior.
+",
+    usage: "\
+inferior list - print list of inferiors (number, pid, state and program)
+inferior switch <n> - set inferior <n> as current
+inferior attach <pid> - attach a running process as a new inferior and set it as current
+",
+    details: "",
+    examples: "",
+};
 
-Available subcomands:
+pub const HELP_SHARED_LIB: CommandHelp = CommandHelp {
+    names: &["sharedlib"],
+    description: "\
+Show shared libraries information.
+",
+    usage: "\
 sharedlib info - print list of loaded shared libraries and their mapping addresses
-";
+",
+    details: "",
+    examples: "",
+};
 
-pub const HELP_SOURCE: &str = "\
-\x1b[32;1msource\x1b[0m
-Show source code or assembly instructions for current (in focus) function.
+pub const HELP_DEADLOCK: CommandHelp = CommandHelp {
+    names: &["deadlock"],
+    description: "\
+Find deadlocks between threads blocked on mutexes (`std::sync::Mutex` and `parking_lot::Mutex`).
+",
+    usage: "\
+deadlock check - print cycles of threads that wait for each other
+",
+    details: "\
+A thread waits for a mutex if it is blocked in a `lock` call. Mutexes don't keep their owner,
+so a thread that holds a mutex is a thread with a live guard (`MutexGuard`) of this mutex
+in one of its local variables. Guards stored in structures or collections are not found.
+For each waiting thread a mutex address, a place of a `lock` call and a holder thread
+with a guard name and a place of a guard declaration are printed.
+",
+    examples: "\
+deadlock check - interrupt a hung program (Ctrl+C) and print threads that wait for each other
+",
+};
+
+pub const HELP_INFO: CommandHelp = CommandHelp {
+    names: &["info"],
+    description: "\
+Show line table information of a source location: address range of each statement at a location,
+a function part that contains it (prologue, body or epilogue) and all line table rows that cover it.
+A row where a breakpoint at the same location is set (or a row of a requested address)
+is marked with `=>`, this explains why a breakpoint landed where it did.
+",
+    usage: "\
+info line - show a statement at the current stop place
+info line <addr> - show a statement that contains an instruction
+info line <file:line> - show all statements of a source line
+info line <function> - show a statement where a function breakpoint is set
+",
+    details: "\
+Rows flags: `stmt` - row is a recommended breakpoint place, `prologue_end` - end of a function
+prologue (function breakpoints are set here), `epilogue_begin` - start of a function epilogue.
+",
+    examples: "\
+* info line main.rs:12 - show address ranges and line table rows of the line 12 of main.rs
+* info line myapp::calc::sum - show where a breakpoint at `sum` function is set
+* info line 0x55555555A1B2 - show a statement that contains an address
+",
+};
 
-Available subcomands:
+pub const HELP_HISTORY: CommandHelp = CommandHelp {
+    names: &["history"],
+    description: "\
+Show values or stepping history. Each variable printed by `var` command and each value returned
+by `finish` command is stored in a history under a number, use `$<number>` in data query
+expressions to refer to it. Each debugee stop (breakpoint or watchpoint hit, finished step,
+signal, interrupt) is stored in a history of a stopped thread.
+",
+    usage: "\
+history values - print all values from the history with their numbers
+history steps - print stops of the thread in focus, from the oldest one
+history steps all - print stops of all threads
+",
+    details: "\
+Stepping history shows how execution got to the current point: a stop time, reason, address,
+function and source place of each stop. Only the last 256 stops of each thread are remembered,
+history is cleared when a program is restarted.
+",
+    examples: "",
+};
+
+pub const HELP_SOURCE: CommandHelp = CommandHelp {
+    names: &["source"],
+    description: "\
+Show source code or assembly instructions for current (in focus) function.
+",
+    usage: "\
 source fn - show code of function in focus 
 source asm - show assembly of function in focus 
 source <bounds> - show line in focus with <bounds> lines up and down of this line
-";
+",
+    details: "",
+    examples: "",
+};
 
-pub const HELP_TUI: &str = "\
-\x1b[32;1mtui\x1b[0m
+pub const HELP_TUI: CommandHelp = CommandHelp {
+    names: &["tui"],
+    description: "\
 Change ui mode to terminal ui.
-";
+",
+    usage: "\
+tui - switch to terminal ui
+",
+    details: "",
+    examples: "",
+};
 
-pub const HELP_ORACLE: &str = "\
-\x1b[32;1moracle\x1b[0m
+pub const HELP_ORACLE: CommandHelp = CommandHelp {
+    names: &["oracle"],
+    description: "\
 Execute a specific oracle.
-
+",
+    usage: "\
+oracle <oracle> - execute an oracle
+oracle <oracle> <subcommand> - execute an oracle subcommand
+",
+    details: "\
 List of available oracles:
-";
+",
+    examples: "",
+};
 
-pub const HELP_QUIT: &str = "\
-\x1b[32;1mq, quit\x1b[0m
-Exit the BugStalker, kill debugee before it.
-";
+pub const HELP_SCRIPT: CommandHelp = CommandHelp {
+    names: &["script"],
+    description: "\
+Execute an automation script written in rhai language (see https://rhai.rs/book).
+",
+    usage: "\
+script <path> - execute script from file
+",
+    details: "\
+Available script functions:
+run(), cont(), step(), next(), stepi(), finish() - control debugee execution,
+    step functions take an optional repeat count, e.g. step(10)
+is_running() - return true if debugee is started and not exited yet
+break_at(file, line), break_fn(name), break_addr(addr) - set breakpoints, return breakpoint numbers
+break_remove(number) - remove breakpoint by its number
+variable(expression), arg(expression) - return rendered variables or arguments (see `help dqe`)
+variable_json(expression) - return variables as a JSON array (full variable trees with types and addresses),
+    `*` selects all local variables
+read_mem(addr, n), write_mem(addr, value) - read n bytes from or write 8-byte value into debugee memory
+reg(name), set_reg(name, value) - read or write register value
+write_file(path, data), append_file(path, data) - write or append a string into file
+",
+    examples: "\
+break_at(\"main.rs\", 15);
+run();
+while is_running() {
+    append_file(\"out.csv\", variable(\"counter\") + \"\\n\");
+    cont();
+}
+",
+};
+
+pub const HELP_SESSION: CommandHelp = CommandHelp {
+    names: &["session"],
+    description: "\
+Save or load a debugging session. Session is a TOML file with breakpoints (location and enabled flag),
+watchpoints (expression or address and condition) and display expressions, so a debugging setup
+survives debugger restarts and can be shared.
+",
+    usage: "\
+session save <path> - save current breakpoints, watchpoints and displays into file
+session load <path> - set breakpoints, watchpoints and displays from file
+",
+    details: "\
+Watchpoints at expressions can be loaded only when program is running.
+",
+    examples: "\
+session save bs_session.toml
+session load bs_session.toml
+",
+};
+
+pub const HELP_TRACE: CommandHelp = CommandHelp {
+    names: &["trace"],
+    description: "\
+Manage tracepoints. Tracepoint is a breakpoint that never stops a program. Each hit is recorded
+(time, thread and values of expressions) and then execution continues.
+",
+    usage: "\
+trace <file:line>|<function> <>|<expression1> <expression2> ... - set a tracepoint, expressions are evaluated at each hit (see `help dqe`)
+trace report - show hit counts, hits per thread and captured values of all tracepoints
+",
+    details: "\
+Tracepoint set before program start is installed when the program starts.
+",
+    examples: "\
+trace main.rs:15 counter
+trace vars::calc a b.field
+",
+};
+
+pub const HELP_DPRINTF: CommandHelp = CommandHelp {
+    names: &["dprintf"],
+    description: "\
+Set a dynamic printf - a tracepoint that prints a formatted message at each hit and continues
+program execution. Format string may contain `{}` placeholders, each of them is replaced
+by the value of the next expression (see `help dqe`). Use `{{` and `}}` for literal braces.
+",
+    usage: "\
+dprintf <file:line>|<function> \"<format>\" <>|<expression1> <expression2> ...
+",
+    details: "\
+dprintf is a tracepoint too, so its hits are shown by `trace report` command.
+",
+    examples: "\
+dprintf main.rs:15 \"counter = {}\" counter
+dprintf vars::calc \"a = {}, b.field = {}\" a b.field
+",
+};
+
+pub const HELP_FTRACE: CommandHelp = CommandHelp {
+    names: &["ftrace"],
+    description: "\
+Trace functions. Non-stopping breakpoints are set at the start of every function which full name
+matches a glob pattern (`*` - any sequence of characters, `?` - any character). Each call of a traced
+function is printed with function arguments, each return is printed with a returned value.
+Nested calls are indented.
+",
+    usage: "\
+ftrace <glob> - start tracing of functions matching a pattern (previous tracing is stopped)
+ftrace stop - stop function tracing
+",
+    details: "\
+Function tracing started before program start is installed when the program starts.
+",
+    examples: "\
+ftrace calc::sum*
+ftrace *::parser::*
+",
+};
+
+pub const HELP_COVERAGE: CommandHelp = CommandHelp {
+    names: &["coverage"],
+    description: "\
+Line coverage without program recompilation. One-shot non-stopping breakpoints are set at every
+statement of selected files (statement addresses are taken from debug information). When a statement
+is executed, its line is marked as covered and the breakpoint is removed.
+",
+    usage: "\
+coverage start <file1> <file2> ... - start collecting coverage of files (full path or part of a file path),
+previously collected coverage is dropped
+coverage stop - stop collecting coverage and show a number of covered lines for each file
+coverage report <>|<path> - print coverage in lcov format or write it into a file
+",
+    details: "\
+Coverage collecting started before program start is installed when the program starts.
+Coverage is accumulated across program restarts until `coverage stop`.
+",
+    examples: "\
+coverage start main.rs src/parser.rs
+coverage report lcov.info
+",
+};
+
+pub const HELP_PROFILE: CommandHelp = CommandHelp {
+    names: &["profile"],
+    description: "\
+Sampling profiler. While profiling is active, a running program is periodically interrupted,
+backtraces of all threads are recorded and the program continues immediately.
+Samples are collected only while the program runs (after `continue`, `run` or `finish` commands).
+",
+    usage: "\
+profile start <>|<interval> - start profiling (drop previously collected samples), default interval is 10ms
+profile stop - stop profiling and show a flat profile
+profile report <>|flat|tree - show a flat profile (share of samples for each function) or a call tree
+profile export <path> - write samples into a file in a collapsed stack format (input for flamegraph tools)
+",
+    details: "",
+    examples: "\
+profile start 5ms
+profile report tree
+profile export bs.folded
+",
+};
+
+pub const HELP_DISPLAY: CommandHelp = CommandHelp {
+    names: &["display", "undisplay"],
+    description: "\
+Print value of an expression (see `help dqe`) automatically each time the program stops.
+Variables are searched first, then function arguments.
+Expressions that can't be evaluated at a stop place (for example, variables out of scope) are skipped.
+Displays are kept when the program is restarted.
+",
+    usage: "\
+display <expression> - add expression to the display list
+display - print all displays
+undisplay <number> - remove expression from the display list
+",
+    details: "",
+    examples: "\
+display counter
+display (~vec1).len
+undisplay 1
+",
+};
+
+pub const HELP_SNAPSHOT: CommandHelp = CommandHelp {
+    names: &["snapshot", "diff"],
+    description: "\
+Capture value of an expression (see `help dqe`) and compare it with a value at a later stop.
+Each comparison replaces the captured value, so repeated `diff` shows changes made since the previous one.
+Changed values are marked with `~`, added fields, elements and entries with `+`, removed ones with `-`.
+",
+    usage: "\
+snapshot <expression> - capture current value of an expression
+diff <expression> - print changes of an expression value since the snapshot
+",
+    details: "",
+    examples: "\
+snapshot my_struct
+diff my_struct
+",
+};
+
+pub const HELP_SET: CommandHelp = CommandHelp {
+    names: &["set"],
+    description: "\
+Change debugger output, source listing and quit settings.
+",
+    usage: "\
+set print demangle-style full - show demangled function names as is, including hash suffixes
+set print demangle-style default - show function names without hash suffixes
+set print demangle-style short <>|<depth> - show function names without hash suffixes, with collapsed
+generic parameters (`Vec<…>`) and only last `depth` parts of a qualified path (2 by default)
+set print elements <n>|unlimited - limit number of printed elements of arrays, vectors, sets and maps (200 by default)
+set print depth <n>|unlimited - limit nesting level of printed structures and collections (unlimited by default)
+set print string-length <n>|unlimited - limit number of printed string characters (200 by default)
+set print pointer-depth <n>|unlimited - follow pointers and print pointed values up to n levels
+of indirection (0 by default), pointers back to an enclosing value are printed as `<cycle to 0x…>`
+set print bytes-as-ascii on|off - print arrays of `u8` as ASCII byte strings (off by default),
+printed bytes are limited by string-length
+set quit-action ask|kill|detach - set an action applied to a running debugee on `quit`,
+ask whether to kill or detach it (default), kill it or detach from it
+set listsize <n> - set a number of source lines printed when a program stops (1 by default),
+a stop line is in the middle of them and is marked with `=>`
+",
+    details: "\
+Demangle style is applied to function names in `backtrace` and `frame info` commands output.
+Print limits are applied to values printed by `var`, `arg`, `display`, `finish` and `history` commands,
+truncated parts are shown as `…(truncated)`.
+",
+    examples: "\
+set print demangle-style short
+set print demangle-style short 3
+set print elements 10
+set print depth unlimited
+set print pointer-depth 5
+set print bytes-as-ascii on
+set quit-action detach
+set listsize 7
+",
+};
+
+pub const HELP_QUIT: CommandHelp = CommandHelp {
+    names: &["q", "quit"],
+    description: "\
+Exit the BugStalker. If the debugee is running, ask whether to kill it, detach from it
+or cancel quit (an answer is one of `k`/`kill`, `d`/`detach` or `c`/`cancel`).
+Detached debugee continues execution, original instructions under breakpoints are restored
+before detach. A default action is changed by `set quit-action` command.
+",
+    usage: "\
+q, quit - kill or detach the running debugee according to `set quit-action` setting
+q, quit --kill - kill the running debugee and all processes spawned by it without asking
+q, quit --detach - detach from the running debugee without asking
+",
+    details: "",
+    examples: "",
+};
+
+/// Help of a single command.
+pub struct CommandHelp {
+    /// Command names and aliases, the first one is the name used in `help` output header.
+    pub names: &'static [&'static str],
+    pub description: &'static str,
+    /// Command syntax, one form per line.
+    pub usage: &'static str,
+    /// Flags, output format and other notes.
+    pub details: &'static str,
+    pub examples: &'static str,
+}
+
+impl CommandHelp {
+    /// Render full command help, used by `help <command>`.
+    pub fn render(&self) -> String {
+        let mut help = format!(
+            "\x1b[32;1m{}\x1b[0m\n{}",
+            self.names.join(", "),
+            self.description
+        );
+        if !self.usage.is_empty() {
+            help = format!("{help}\nUsage:\n{}", self.usage);
+        }
+        if !self.details.is_empty() {
+            help = format!("{help}\n{}", self.details);
+        }
+        if !self.examples.is_empty() {
+            help = format!("{help}\nExamples:\n{}", self.examples);
+        }
+        help
+    }
+
+    /// Render command syntax only, used as a hint after a command parsing error.
+    pub fn render_usage(&self) -> String {
+        format!(
+            "Usage:\n{}See `help {}` for details.",
+            self.usage,
+            self.names.last().expect("at least one name")
+        )
+    }
+}
+
+const COMMANDS_HELP: &[&CommandHelp] = &[
+    &HELP_VAR,
+    &HELP_ARG,
+    &HELP_BACKTRACE,
+    &HELP_FRAME,
+    &HELP_CONTINUE,
+    &HELP_RUN,
+    &HELP_KILL,
+    &HELP_STEPI,
+    &HELP_STEPINTO,
+    &HELP_STEPOUT,
+    &HELP_RETURN,
+    &HELP_JUMP,
+    &HELP_STEPOVER,
+    &HELP_BREAK,
+    &HELP_CATCH,
+    &HELP_WATCH,
+    &HELP_SYMBOL,
+    &HELP_RESOLVE,
+    &HELP_SYMBOLIZE,
+    &HELP_PTYPE,
+    &HELP_MEMORY,
+    &HELP_EXAMINE,
+    &HELP_REGISTER,
+    &HELP_THREAD,
+    &HELP_INFERIOR,
+    &HELP_SHARED_LIB,
+    &HELP_DEADLOCK,
+    &HELP_INFO,
+    &HELP_HISTORY,
+    &HELP_SOURCE,
+    &HELP_TUI,
+    &HELP_ORACLE,
+    &HELP_SCRIPT,
+    &HELP_SESSION,
+    &HELP_TRACE,
+    &HELP_DPRINTF,
+    &HELP_FTRACE,
+    &HELP_COVERAGE,
+    &HELP_PROFILE,
+    &HELP_DISPLAY,
+    &HELP_SNAPSHOT,
+    &HELP_SET,
+    &HELP_QUIT,
+];
+
+/// Find a command help by command name, alias or unambiguous prefix.
+pub fn command_help(command: &str) -> Option<&'static CommandHelp> {
+    let name = parser::command_name(command).unwrap_or(command);
+    COMMANDS_HELP
+        .iter()
+        .find(|help| help.names.contains(&name))
+        .copied()
+}
+
+/// Return a usage block of a command from an input string, used to explain parsing errors.
+pub fn usage_hint(input: &str) -> Option<String> {
+    let word = input.split_whitespace().next()?;
+    // `x/4xb` has no whitespace between a command and its arguments
+    let word = word.split('/').next()?;
+    command_help(word).map(CommandHelp::render_usage)
+}
 
 #[derive(Default)]
 pub struct Helper {
@@ -298,44 +1149,54 @@ pub struct Helper {
 }
 
 impl Helper {
-    pub fn help_for_command(&mut self, debugger: &Debugger, command: Option<&str>) -> &str {
+    /// Return a help for a command, `oracle_help` is a list of installed oracle descriptions.
+    pub fn help_for_command(&mut self, oracle_help: &[String], command: Option<&str>) -> String {
+        let Some(command) = command else {
+            return HELP.to_string();
+        };
         match command {
-            None => HELP,
-            Some("dqe") => DQE_DESCRIPTION,
-            Some("dqe literal") => DQE_LITERAL_DESCRIPTION,
-            Some(parser::VAR_COMMAND) => HELP_VAR,
-            Some(parser::ARG_COMMAND) => HELP_ARG,
-            Some(parser::BACKTRACE_COMMAND) | Some(parser::BACKTRACE_COMMAND_SHORT) => {
-                HELP_BACKTRACE
-            }
-            Some(parser::FRAME_COMMAND) | Some(parser::FRAME_COMMAND_SHORT) => HELP_FRAME,
-            Some(parser::CONTINUE_COMMAND) | Some(parser::CONTINUE_COMMAND_SHORT) => HELP_CONTINUE,
-            Some(parser::RUN_COMMAND) | Some(parser::RUN_COMMAND_SHORT) => HELP_RUN,
-            Some(parser::STEP_INSTRUCTION_COMMAND) => HELP_STEPI,
-            Some(parser::STEP_INTO_COMMAND) | Some(parser::STEP_INTO_COMMAND_SHORT) => {
-                HELP_STEPINTO
-            }
-            Some(parser::STEP_OUT_COMMAND) | Some(parser::STEP_OUT_COMMAND_SHORT) => HELP_STEPOUT,
-            Some(parser::STEP_OVER_COMMAND) | Some(parser::STEP_OVER_COMMAND_SHORT) => {
-                HELP_STEPOVER
+            "dqe" => return DQE_DESCRIPTION.to_string(),
+            "dqe literal" => return DQE_LITERAL_DESCRIPTION.to_string(),
+            _ => {}
+        }
+
+        match command_help(command) {
+            Some(help) if help.names.contains(&parser::ORACLE_COMMAND) => self
+                .oracle_help
+                .get_or_insert_with(|| {
+                    let mut help = help.render();
+                    oracle_help
+                        .iter()
+                        .for_each(|oracle_help| help = format!("{help}{oracle_help}\n"));
+                    help
+                })
+                .clone(),
+            Some(help) => help.render(),
+            None => "unknown command".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_command_help_lookup() {
+        for help in COMMANDS_HELP {
+            assert!(!help.usage.is_empty(), "{:?} has no usage", help.names);
+            for name in help.names {
+                assert_eq!(command_help(name).unwrap().names, help.names);
             }
-            Some(parser::BREAK_COMMAND) | Some(parser::BREAK_COMMAND_SHORT) => HELP_BREAK,
-            Some(parser::WATCH_COMMAND) | Some(parser::WATCH_COMMAND_SHORT) => HELP_WATCH,
-            Some(parser::SYMBOL_COMMAND) => HELP_SYMBOL,
-            Some(parser::MEMORY_COMMAND) | Some(parser::MEMORY_COMMAND_SHORT) => HELP_MEMORY,
-            Some(parser::REGISTER_COMMAND) | Some(parser::REGISTER_COMMAND_SHORT) => HELP_REGISTER,
-            Some(parser::THREAD_COMMAND) => HELP_THREAD,
-            Some(parser::SHARED_LIB_COMMAND) => HELP_SHARED_LIB,
-            Some(parser::SOURCE_COMMAND) => HELP_SOURCE,
-            Some(parser::ORACLE_COMMAND) => self.oracle_help.get_or_insert_with(|| {
-                let mut help = HELP_ORACLE.to_string();
-                let oracles = debugger.all_oracles();
-                oracles.for_each(|oracle| help = format!("{help}{}\n", oracle.help()));
-                help
-            }),
-            Some("tui") => HELP_TUI,
-            Some("q") | Some("quit") => HELP_QUIT,
-            _ => "unknown command",
         }
+
+        assert_eq!(command_help("brea").unwrap().names, HELP_BREAK.names);
+        assert_eq!(command_help("bt").unwrap().names, HELP_BACKTRACE.names);
+        assert!(command_help("unknown").is_none());
+
+        let hint = usage_hint("x/4xb").unwrap();
+        assert!(hint.starts_with("Usage:\nx/<count><format><size> <address>"));
+        assert!(hint.ends_with("See `help x` for details."));
+        assert!(usage_hint("").is_none());
     }
 }
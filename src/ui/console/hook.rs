@@ -1,43 +1,153 @@
 use crate::debugger::address::RelocatedAddress;
 use crate::debugger::register::debug::BreakCondition;
+use crate::debugger::variable::render::RenderLimits;
+use crate::debugger::variable::render::{render_variable_ir, RenderRepr};
 use crate::debugger::variable::VariableIR;
+use crate::debugger::Pid;
 use crate::debugger::PlaceDescriptor;
-use crate::debugger::{EventHook, FunctionDie};
+use crate::debugger::Signal;
+use crate::debugger::{
+    CatchEvent, EventHook, FormatString, FunctionDie, MemoryAccess, StopKind, StopSummary,
+};
 use crate::ui::console::file::FileView;
 use crate::ui::console::print::style::{AddressView, FilePathView, FunctionNameView, KeywordView};
 use crate::ui::console::print::ExternalPrinter;
-use crate::ui::console::variable::render_variable;
+use crate::ui::console::variable::{
+    render_dprintf_message, render_variable, render_variable_inline,
+};
+use crate::ui::proto::{DebuggerEvent, EventPublisher};
 use crate::version;
+use crate::version::Version;
+use chrono::{DateTime, Local};
 use log::warn;
-use nix::sys::signal::Signal;
-use nix::unistd::Pid;
 use std::cell::RefCell;
 use std::ops::Add;
-use std::rc::Rc;
+use std::sync::Arc;
+
+/// Maximum length of argument and return values in a function trace.
+const MAX_FTRACE_VALUE_LEN: usize = 32;
+
+/// Return number with an english ordinal suffix (`1st`, `2nd`, `11th`, ...).
+fn ordinal(n: u64) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{n}{suffix}")
+}
+
+/// Render a one-line stop summary,
+/// like `12:03:45.120 [tid 4211] 0x00555555559A3C breakpoint 2 hit at foo.rs:10 (3rd hit)`.
+pub fn render_stop_summary(summary: &StopSummary) -> String {
+    let time = DateTime::<Local>::from(summary.time).format("%H:%M:%S%.3f");
+    let mut line = format!("{time} [tid {}]", summary.tid);
+    if let Some(pc) = summary.pc {
+        line.push_str(&format!(" {}", AddressView::from(pc)));
+    }
+    line.push_str(&format!(" {}", render_stop_event(summary)));
+    line
+}
+
+/// Render a warning about threads that keep running while debugee is stopped.
+pub fn render_not_stopped_threads(tids: &[Pid]) -> String {
+    let tids = tids
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "Warning: threads [{tids}] don't respond to interrupt \
+        (probably in uninterruptible sleep), they keep running"
+    )
+}
+
+/// Render a stepping history, stops are grouped by a thread,
+/// like `#3 12:03:45.120 0x00555555559A3C step done at foo.rs:10 in foo::bar`.
+pub fn render_step_history(stops: &[StopSummary]) -> Vec<String> {
+    let mut lines = vec![];
+    for thread_stops in stops.chunk_by(|a, b| a.tid == b.tid) {
+        lines.push(format!(
+            "thread #{} [tid {}]:",
+            thread_stops[0].thread_num, thread_stops[0].tid
+        ));
+        for (i, stop) in thread_stops.iter().enumerate() {
+            let time = DateTime::<Local>::from(stop.time).format("%H:%M:%S%.3f");
+            let mut line = format!("  #{i} {time}");
+            if let Some(pc) = stop.pc {
+                line.push_str(&format!(" {}", AddressView::from(pc)));
+            }
+            line.push_str(&format!(" {}", render_stop_event(stop)));
+            if let Some(function) = &stop.function {
+                line.push_str(&format!(" in {}", FunctionNameView::from(function)));
+            }
+            lines.push(line);
+        }
+    }
+    lines
+}
+
+/// Render a stop reason and a stop place, like `breakpoint 2 hit at foo.rs:10 (3rd hit)`.
+pub fn render_stop_event(summary: &StopSummary) -> String {
+    let place = summary
+        .place
+        .as_ref()
+        .map(|place| {
+            let file = place.file.file_name().unwrap_or(place.file.as_os_str());
+            format!("{}:{}", file.to_string_lossy(), place.line_number)
+        })
+        .unwrap_or_else(|| "undefined place".to_string());
+
+    match summary.kind {
+        StopKind::Breakpoint { number, hits } => {
+            format!("breakpoint {number} hit at {place} ({} hit)", ordinal(hits))
+        }
+        StopKind::Watchpoint {
+            number,
+            end_of_scope: false,
+        } => format!("watchpoint {number} hit at {place}"),
+        StopKind::Watchpoint {
+            number,
+            end_of_scope: true,
+        } => format!("watchpoint {number} out of scope at {place}"),
+        StopKind::Step => format!("step done at {place}"),
+        StopKind::Signal(signal) => format!("signal {signal} received at {place}"),
+        StopKind::Interrupt => format!("interrupted at {place}"),
+        StopKind::EntryPoint => "program entry point reached".to_string(),
+    }
+}
 
 #[derive(Default)]
 struct Context {
     prev_func: Option<FunctionDie>,
+    /// Output of a breakpoint hook, printed after a stop summary.
+    stop_details: Vec<String>,
 }
 
 pub struct TerminalHook {
-    file_view: Rc<FileView>,
+    file_view: Arc<FileView>,
     on_install_proc: Box<dyn Fn(Pid)>,
     printer: ExternalPrinter,
     context: RefCell<Context>,
+    /// Debugee stops, exits and process installations are published here for UI.
+    events: EventPublisher,
 }
 
 impl TerminalHook {
     pub fn new(
         printer: ExternalPrinter,
-        fv: Rc<FileView>,
+        fv: Arc<FileView>,
         on_install_proc: impl Fn(Pid) + 'static,
+        events: EventPublisher,
     ) -> Self {
         Self {
             file_view: fv,
             on_install_proc: Box::new(on_install_proc),
             printer,
             context: RefCell::new(Context::default()),
+            events,
         }
     }
 }
@@ -45,25 +155,50 @@ impl TerminalHook {
 impl EventHook for TerminalHook {
     fn on_breakpoint(
         &self,
-        pc: RelocatedAddress,
-        num: u32,
+        _: RelocatedAddress,
+        _: u32,
         mb_place: Option<PlaceDescriptor>,
         mb_func: Option<&FunctionDie>,
     ) -> anyhow::Result<()> {
-        let msg = format!("Hit breakpoint {num} at {}:", AddressView::from(pc));
+        // breakpoint itself is reported by a stop summary, source code is printed after it
+        let mut context = self.context.borrow_mut();
+        context.stop_details.clear();
         if let Some(place) = mb_place {
-            self.printer.println(format!(
-                "{msg} {}:{}",
-                FilePathView::from(place.file.to_string_lossy()),
-                place.line_number
-            ));
-            self.printer.print(self.file_view.render_source(&place, 0)?);
-        } else {
-            self.printer.println(format!("{msg} undefined place"));
+            context
+                .stop_details
+                .push(self.file_view.render_stop_place(&place)?);
         }
+        context.prev_func = mb_func.cloned();
 
-        self.context.borrow_mut().prev_func = mb_func.cloned();
+        Ok(())
+    }
 
+    fn on_function_return(&self, _: u32, value: Option<&VariableIR>) -> anyhow::Result<()> {
+        let value = value
+            .map(|value| render_variable_ir(value, 0, &RenderLimits::default()))
+            .unwrap_or_else(|| "unknown".to_string());
+        self.context
+            .borrow_mut()
+            .stop_details
+            .push(format!("Value returned: {value}\n"));
+        Ok(())
+    }
+
+    fn on_exception(
+        &self,
+        _: u32,
+        event: CatchEvent,
+        type_name: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let type_name = type_name.unwrap_or("unknown (foreign exception)");
+        let details = match event {
+            CatchEvent::Throw => format!(
+                "Exception thrown: {type_name}\n\
+                 Use `catch unwind` to stop at the handler that catches it\n"
+            ),
+            CatchEvent::Catch => format!("Exception caught: {type_name}\n"),
+        };
+        self.context.borrow_mut().stop_details.push(details);
         Ok(())
     }
 
@@ -75,6 +210,7 @@ impl EventHook for TerminalHook {
         cond: BreakCondition,
         old: Option<&VariableIR>,
         new: Option<&VariableIR>,
+        access: Option<MemoryAccess>,
         end_of_scope: bool,
     ) -> anyhow::Result<()> {
         let msg = if end_of_scope {
@@ -99,18 +235,30 @@ impl EventHook for TerminalHook {
             self.printer.println(format!("{msg} undefined place"));
         };
 
-        if cond == BreakCondition::DataReadsWrites && old == new {
+        if let Some(access) = access {
+            let instruction = AddressView::from(access.instruction);
+            match access.kind {
+                Some(kind) => self
+                    .printer
+                    .println(format!("{kind} by instruction at {instruction}")),
+                None => self
+                    .printer
+                    .println(format!("access by instruction at {instruction}")),
+            }
+        }
+
+        if cond != BreakCondition::DataWrites && old == new {
             if let Some(old) = old {
-                let val = render_variable(old)?;
+                let val = render_variable(old, &RenderLimits::default())?;
                 self.printer.println(format!("value: {val}"));
             }
         } else {
             if let Some(old) = old {
-                let old = render_variable(old)?;
+                let old = render_variable(old, &RenderLimits::default())?;
                 self.printer.println(format!("old value: {old}"));
             }
             if let Some(new) = new {
-                let new = render_variable(new)?;
+                let new = render_variable(new, &RenderLimits::default())?;
                 self.printer.println(format!("new value: {new}"));
             }
         }
@@ -118,6 +266,25 @@ impl EventHook for TerminalHook {
         Ok(())
     }
 
+    fn on_watchpoint_rebind(
+        &self,
+        num: u32,
+        expr: &str,
+        address: Option<RelocatedAddress>,
+    ) -> anyhow::Result<()> {
+        let msg = match address {
+            Some(address) => format!(
+                "Watchpoint {num} ({expr}) moved to {}",
+                AddressView::from(address)
+            ),
+            None => format!(
+                "Watchpoint {num} ({expr}) no longer points to a valid location (and it will be removed)"
+            ),
+        };
+        self.printer.println(msg);
+        Ok(())
+    }
+
     fn on_step(
         &self,
         _: RelocatedAddress,
@@ -142,7 +309,8 @@ impl EventHook for TerminalHook {
                     place.line_number,
                 ));
             }
-            self.printer.print(self.file_view.render_source(&place, 0)?);
+            self.printer
+                .print(self.file_view.render_stop_place(&place)?);
         } else {
             self.printer.println("undefined place, go to next");
         }
@@ -157,21 +325,134 @@ impl EventHook for TerminalHook {
         ));
     }
 
+    fn on_stop(&self, summary: &StopSummary) {
+        self.events.publish(DebuggerEvent::Stop(summary.clone()));
+        // step place is already printed by `on_step`
+        if summary.kind != StopKind::Step {
+            self.printer.println(render_stop_summary(summary));
+        }
+        for details in self.context.borrow_mut().stop_details.drain(..) {
+            self.printer.print(details);
+        }
+    }
+
+    fn on_threads_not_stopped(&self, tids: &[Pid]) {
+        self.printer.println(render_not_stopped_threads(tids));
+    }
+
     fn on_exit(&self, code: i32) {
         self.printer.println(format!(
             "Program exit with code: {}",
             KeywordView::from(code)
         ));
+        self.events.publish(DebuggerEvent::Exit(code));
+    }
+
+    fn on_dprintf(&self, _: u32, format: &FormatString, values: &[Option<VariableIR>]) {
+        self.printer.println(render_dprintf_message(format, values));
+    }
+
+    fn on_ftrace_enter(&self, _: Pid, depth: usize, function: &str, args: &[VariableIR]) {
+        let args = args
+            .iter()
+            .map(|arg| {
+                format!(
+                    "{} = {}",
+                    arg.name(),
+                    render_variable_inline(arg, MAX_FTRACE_VALUE_LEN)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        self.printer.println(format!(
+            "{:indent$}-> {}({args})",
+            "",
+            FunctionNameView::from(function),
+            indent = depth * 2
+        ));
     }
 
-    fn on_process_install(&self, pid: Pid, object: Option<&object::File>) {
-        if let Some(obj) = object {
-            if !version::probe_file(obj) {
+    fn on_ftrace_return(&self, _: Pid, depth: usize, function: &str, value: Option<&VariableIR>) {
+        let value = value
+            .map(|value| format!(" = {}", render_variable_inline(value, MAX_FTRACE_VALUE_LEN)))
+            .unwrap_or_default();
+        self.printer.println(format!(
+            "{:indent$}<- {}{value}",
+            "",
+            FunctionNameView::from(function),
+            indent = depth * 2
+        ));
+    }
+
+    fn on_process_install(&self, pid: Pid, rustc_version: Option<&Version>) {
+        if let Some(rustc_version) = rustc_version {
+            if !rustc_version.is_supported() {
                 let supported_versions = version::supported_versions_to_string();
                 warn!(target: "debugger", "Found unsupported rust version, some of program data may not be displayed correctly. \
                 List of supported rustc versions: {supported_versions}.");
             }
         }
-        (self.on_install_proc)(pid)
+        (self.on_install_proc)(pid);
+        self.events.publish(DebuggerEvent::ProcessInstall(pid));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ordinal, render_not_stopped_threads, render_stop_summary};
+    use crate::debugger::address::GlobalAddress;
+    use crate::debugger::{Pid, PlaceDescriptorOwned, StopKind, StopSummary};
+    use std::time::SystemTime;
+
+    #[test]
+    fn test_ordinal() {
+        let cases = [
+            (1, "1st"),
+            (2, "2nd"),
+            (3, "3rd"),
+            (4, "4th"),
+            (11, "11th"),
+            (12, "12th"),
+            (13, "13th"),
+            (21, "21st"),
+            (102, "102nd"),
+            (111, "111th"),
+        ];
+        for (n, expected) in cases {
+            assert_eq!(ordinal(n), expected);
+        }
+    }
+
+    #[test]
+    fn test_render_stop_summary() {
+        let summary = StopSummary {
+            time: SystemTime::now(),
+            tid: Pid::from_raw(4211),
+            thread_num: 1,
+            pc: None,
+            kind: StopKind::Breakpoint { number: 2, hits: 3 },
+            function: Some("foo::bar".to_string()),
+            place: Some(PlaceDescriptorOwned {
+                file: "/src/foo.rs".into(),
+                address: GlobalAddress::from(0x1000_usize),
+                line_number: 10,
+                pos_in_unit: 0,
+                is_stmt: true,
+                column_number: 0,
+                epilog_begin: false,
+                prolog_end: false,
+            }),
+        };
+        assert!(render_stop_summary(&summary)
+            .ends_with("[tid 4211] breakpoint 2 hit at foo.rs:10 (3rd hit)"));
+    }
+
+    #[test]
+    fn test_render_not_stopped_threads() {
+        assert_eq!(
+            render_not_stopped_threads(&[Pid::from_raw(101), Pid::from_raw(102)]),
+            "Warning: threads [101, 102] don't respond to interrupt \
+            (probably in uninterruptible sleep), they keep running"
+        );
     }
 }
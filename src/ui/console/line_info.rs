@@ -0,0 +1,53 @@
+use crate::debugger::LineInfo;
+use crate::ui::console::print::style::{AddressView, FilePathView, FunctionNameView, KeywordView};
+
+/// Render statements of a source location: a statement header with an address range
+/// and a function part, then line table rows, a marked row is prefixed with `=>`.
+///
+/// # Arguments
+///
+/// * `infos`: statements at a location
+pub fn render_line_info(infos: Vec<LineInfo>) -> Vec<String> {
+    let mut lines = vec![];
+    for info in infos {
+        let function = info
+            .function
+            .map(|name| format!(" in {}", FunctionNameView::from(name)))
+            .unwrap_or_default();
+        let part = info
+            .part
+            .map(|part| format!(" ({part})"))
+            .unwrap_or_default();
+        lines.push(format!(
+            "Line {} of {}{function}{part} starts at {} and ends at {}",
+            info.line,
+            FilePathView::from(info.file.to_string_lossy()),
+            AddressView::from(info.start.to_string()),
+            AddressView::from(info.end.to_string()),
+        ));
+
+        for row in info.rows {
+            let marker = if row.marked {
+                KeywordView::from("=>").to_string()
+            } else {
+                "  ".to_string()
+            };
+            let flags = [
+                (row.is_stmt, "stmt"),
+                (row.prolog_end, "prologue_end"),
+                (row.epilog_begin, "epilogue_begin"),
+            ]
+            .into_iter()
+            .filter_map(|(set, flag)| set.then_some(flag))
+            .collect::<Vec<_>>()
+            .join(" ");
+            lines.push(format!(
+                "  {marker} {} {}:{} {flags}",
+                AddressView::from(row.address.to_string()),
+                row.line,
+                row.column,
+            ));
+        }
+    }
+    lines
+}
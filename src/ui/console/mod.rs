@@ -1,48 +1,101 @@
 use crate::debugger;
+use crate::debugger::address::RelocatedAddress;
 use crate::debugger::process::{Child, Installed};
+use crate::debugger::unwind::FrameSpan;
+use crate::debugger::variable::json;
+use crate::debugger::variable::render::{render_variable_ir, RenderLimits, RenderRepr};
 use crate::debugger::variable::select::{VariableSelector, DQE};
-use crate::debugger::{Debugger, DebuggerBuilder};
+use crate::debugger::variable::VariableIR;
+use crate::debugger::Pid;
+use crate::debugger::{
+    BranchTarget, Debugger, DebuggerBuilder, DecodedInstruction, InferiorStatus,
+    StackSlotAnnotation, StopKind,
+};
+use crate::muted_error;
 use crate::ui::command::arguments::Handler as ArgumentsHandler;
 use crate::ui::command::backtrace::Handler as BacktraceHandler;
+use crate::ui::command::coverage::ExecutionResult as CoverageResult;
+use crate::ui::command::coverage::Handler as CoverageHandler;
+use crate::ui::command::deadlock::Handler as DeadlockHandler;
+use crate::ui::command::display::ExecutionResult as DisplayResult;
+use crate::ui::command::display::{Display, DisplayRegistry, Handler as DisplayHandler};
+use crate::ui::command::examine::{ExecutionResult as ExamineResult, Handler as ExamineHandler};
 use crate::ui::command::frame::ExecutionResult as FrameResult;
 use crate::ui::command::frame::Handler as FrameHandler;
+use crate::ui::command::ftrace::ExecutionResult as FtraceResult;
+use crate::ui::command::ftrace::Handler as FtraceHandler;
+use crate::ui::command::inferior::ExecutionResult as InferiorResult;
+use crate::ui::command::info::Handler as InfoHandler;
+use crate::ui::command::jump::Handler as JumpHandler;
+use crate::ui::command::memory::ExecutionResult as MemoryResult;
 use crate::ui::command::memory::Handler as MemoryHandler;
+use crate::ui::command::profile::ExecutionResult as ProfileResult;
+use crate::ui::command::profile::Handler as ProfileHandler;
+use crate::ui::command::profile::ReportKind;
+use crate::ui::command::ptype::Handler as PtypeHandler;
 use crate::ui::command::r#break::ExecutionResult;
 use crate::ui::command::r#break::Handler as BreakpointHandler;
 use crate::ui::command::r#continue::Handler as ContinueHandler;
 use crate::ui::command::register::Handler as RegisterHandler;
+use crate::ui::command::register::Value as RegisterValue;
+use crate::ui::command::resolve::Handler as ResolveHandler;
 use crate::ui::command::run::Handler as RunHandler;
+use crate::ui::command::script::Handler as ScriptHandler;
+use crate::ui::command::session::ExecutionResult as SessionResult;
+use crate::ui::command::session::Handler as SessionHandler;
+use crate::ui::command::set::{Handler as SetHandler, Settings};
 use crate::ui::command::sharedlib::Handler as SharedlibHandler;
+use crate::ui::command::snapshot::ExecutionResult as SnapshotResult;
+use crate::ui::command::snapshot::{Handler as SnapshotHandler, SnapshotRegistry};
 use crate::ui::command::source_code::{DisAsmHandler, FunctionLineRangeHandler};
 use crate::ui::command::symbol::Handler as SymbolHandler;
+use crate::ui::command::symbolize::Handler as SymbolizeHandler;
 use crate::ui::command::thread::ExecutionResult as ThreadResult;
+use crate::ui::command::trace::ExecutionResult as TraceResult;
+use crate::ui::command::trace::Handler as TraceHandler;
 use crate::ui::command::variables::Handler as VariablesHandler;
 use crate::ui::command::watch::ExecutionResult as WatchpointExecutionResult;
 use crate::ui::command::watch::Handler as WatchpointHandler;
 use crate::ui::command::{
-    r#break, source_code, step_instruction, step_into, step_out, step_over, CommandError,
+    kill, quit, r#break, r#return, source_code, step_instruction, step_into, step_out, step_over,
+    unwind, CommandError,
 };
 use crate::ui::command::{run, Command};
-use crate::ui::console::editor::{create_editor, CommandCompleter, RLHelper};
+use crate::ui::completer::CommandCompleter;
+use crate::ui::console::deadlock::render_deadlocks;
+use crate::ui::console::editor::{create_editor, RLHelper};
 use crate::ui::console::file::FileView;
 use crate::ui::console::help::*;
-use crate::ui::console::hook::TerminalHook;
+use crate::ui::console::hook::{render_step_history, TerminalHook};
+use crate::ui::console::line_info::render_line_info;
+use crate::ui::console::pager::Pager;
 use crate::ui::console::print::style::{
     AddressView, AsmInstructionView, AsmOperandsView, ErrorView, FilePathView, FunctionNameView,
-    KeywordView,
+    KeywordView, StatusView,
 };
 use crate::ui::console::print::ExternalPrinter;
-use crate::ui::console::variable::render_variable;
+use crate::ui::console::profile::{render_call_tree, render_flat_profile};
+use crate::ui::console::progress::ProgressBar;
+use crate::ui::console::resolve::render_address_info;
+use crate::ui::console::trace::render_tracepoint_report;
+use crate::ui::console::variable::{render_variable, render_variable_diff, render_variable_inline};
+use crate::ui::proto::{
+    event_stream, exchanger, ClientExchanger, DebuggerEvent, EventStream, ServeExit,
+};
+use crate::ui::status::DebugeeStatus;
 use crate::ui::DebugeeOutReader;
 use crate::ui::{command, supervisor};
 use crossterm::style::{Color, Stylize};
 use debugger::Error;
+use itertools::Itertools;
+use log::error;
 use nix::sys::signal::{kill, Signal};
-use nix::unistd::Pid;
+use r#break::BreakpointIdentity;
 use r#break::Command as BreakpointCommand;
 use rustyline::error::ReadlineError;
 use rustyline::history::MemHistory;
 use rustyline::Editor;
+use std::collections::{HashMap, VecDeque};
 use std::io::{BufRead, BufReader};
 use std::process::exit;
 use std::rc::Rc;
@@ -53,18 +106,30 @@ use std::thread;
 use std::time::Duration;
 use timeout_readwrite::TimeoutReader;
 
+mod deadlock;
 mod editor;
 pub mod file;
 mod help;
 pub mod hook;
+mod line_info;
+mod pager;
 pub mod print;
-mod variable;
+mod profile;
+mod progress;
+mod resolve;
+mod trace;
+pub mod variable;
 
 const WELCOME_TEXT: &str = r#"
 BugStalker greets
 "#;
 const PROMT: &str = "(bs) ";
 const PROMT_YES_NO: &str = "(bs y/n) ";
+const PROMT_QUIT: &str = "(bs k/d/c) ";
+/// Maximum length of an argument value in backtrace, longer values are truncated.
+const MAX_FRAME_ARG_LEN: usize = 32;
+/// Instructions with longer encoding shift mnemonic column to the right.
+const MAX_ENCODING_VIEW_LEN: usize = 8;
 
 type BSEditor = Editor<RLHelper, MemHistory>;
 
@@ -91,11 +156,13 @@ impl AppBuilder {
     ) -> anyhow::Result<TerminalApplication> {
         let (user_cmd_tx, user_cmd_rx) = mpsc::sync_channel::<UserAction>(0);
         let mut editor = create_editor(PROMT, oracles)?;
-        let file_view = Rc::new(FileView::new());
+        let file_view = Arc::new(FileView::new());
+        let (publisher, events) = event_stream();
         let hook = TerminalHook::new(
             ExternalPrinter::new(&mut editor)?,
             file_view.clone(),
             move |pid| DEBUGEE_PID.store(pid.as_raw(), Ordering::Release),
+            publisher,
         );
 
         let debugger = debugger_lazy(hook)?;
@@ -110,6 +177,7 @@ impl AppBuilder {
             debugger,
             editor,
             file_view,
+            events,
             debugee_out: self.debugee_out,
             debugee_err: self.debugee_err,
             user_act_tx: user_cmd_tx,
@@ -130,7 +198,16 @@ impl AppBuilder {
         process: Child<Installed>,
     ) -> anyhow::Result<TerminalApplication> {
         let oracles = dbg_builder.oracles().map(|o| o.name()).collect::<Vec<_>>();
-        let debugger_ctor = |hook| Ok(dbg_builder.with_hooks(hook).build(process)?);
+        let debugger_ctor = |hook| {
+            let progress_bar = Arc::new(ProgressBar::default());
+            let mut dbg_builder = dbg_builder.with_hooks(hook);
+            if let Some(callback) = progress_bar.callback() {
+                dbg_builder = dbg_builder.with_progress(callback);
+            }
+            let debugger = dbg_builder.build(process);
+            progress_bar.finish();
+            Ok(debugger?)
+        };
         self.build_inner(&oracles, debugger_ctor)
     }
 
@@ -165,12 +242,15 @@ enum UserAction {
 enum EditorMode {
     Default,
     YesNo,
+    /// Choose an action applied to a running debugee on quit.
+    Quit,
 }
 
 pub struct TerminalApplication {
     debugger: Debugger,
     editor: BSEditor,
-    file_view: Rc<FileView>,
+    file_view: Arc<FileView>,
+    events: EventStream,
     debugee_out: DebugeeOutReader,
     debugee_err: DebugeeOutReader,
     user_act_tx: SyncSender<UserAction>,
@@ -229,34 +309,53 @@ impl TerminalApplication {
 
         let (ready_to_next_command_tx, ready_to_next_command_rx) = mpsc::channel();
 
-        let app_loop = AppLoop {
-            debugger: self.debugger,
-            file_view: self.file_view,
-            user_input_rx: self.user_act_rx,
-            completer: Arc::clone(
-                &self
-                    .editor
-                    .helper_mut()
-                    .expect("helper must exists")
-                    .completer,
-            ),
-            printer: ExternalPrinter::new(&mut self.editor)?,
-            debugee_out: self.debugee_out.clone(),
-            debugee_err: self.debugee_err.clone(),
-            cancel_output_flag: cancel,
-            ready_to_next_command_tx,
-            helper: Default::default(),
-        };
+        let mut status = DebugeeStatus::default();
+        status.refresh(&self.debugger);
+        let completer = Arc::clone(
+            &self
+                .editor
+                .helper_mut()
+                .expect("helper must exists")
+                .completer,
+        );
+        let printer = ExternalPrinter::new(&mut self.editor)?;
+        let task_printer = Arc::new(Mutex::new(ExternalPrinter::new(&mut self.editor)?));
+        let (srv_exchanger, client_exchanger) = exchanger();
+
+        // console thread, debugger is used through an exchanger
+        let file_view = self.file_view;
+        let events = self.events;
+        let user_input_rx = self.user_act_rx;
+        let console_jh = thread::spawn(move || {
+            let app_loop = AppLoop {
+                exchanger: client_exchanger,
+                file_view,
+                events,
+                last_stop: None,
+                status,
+                printed_status: Default::default(),
+                user_input_rx,
+                completer,
+                printer: Rc::new(printer),
+                task_printer,
+                ready_to_next_command_tx,
+                helper: Default::default(),
+                displays: Default::default(),
+                snapshots: Default::default(),
+                breakpoint_commands: Default::default(),
+                settings: Default::default(),
+                quit: false,
+            };
+            app_loop.run()
+        });
 
+        let interrupter = self.debugger.interrupter();
         static CTRLC_ONCE: Once = Once::new();
         CTRLC_ONCE.call_once(|| {
             // this handler called only if debugee running, otherwise
             // ctrl+c will handle by `readline`
-            ctrlc::set_handler(move || {
-                let pid = Pid::from_raw(DEBUGEE_PID.load(Ordering::Acquire));
-                _ = kill(pid, Signal::SIGINT);
-            })
-            .expect("error setting Ctrl-C handler")
+            ctrlc::set_handler(move || interrupter.interrupt())
+                .expect("error setting Ctrl-C handler")
         });
 
         let error_printer = ExternalPrinter::new(&mut self.editor)?;
@@ -272,6 +371,7 @@ impl TerminalApplication {
                     let promt = match ready_to_next_command_rx.recv() {
                         Ok(EditorMode::Default) => PROMT,
                         Ok(EditorMode::YesNo) => PROMT_YES_NO,
+                        Ok(EditorMode::Quit) => PROMT_QUIT,
                         Err(_) => return,
                     };
 
@@ -282,12 +382,12 @@ impl TerminalApplication {
                     let line = editor.readline(promt);
                     match line {
                         Ok(input) => {
-                            if input == "q" || input == "quit" {
-                                _ = control_tx.send(UserAction::Terminate);
-                                break;
-                            } else if input == "tui" {
+                            if input == "tui" {
                                 _ = control_tx.send(UserAction::ChangeMode);
-                                break;
+                                // without a terminal ui a console keeps reading user input
+                                if cfg!(feature = "tui") {
+                                    break;
+                                }
                             } else {
                                 _ = editor.add_history_entry(&input);
                                 _ = control_tx.send(UserAction::Cmd(input));
@@ -305,7 +405,7 @@ impl TerminalApplication {
                             ReadlineError::Eof => {
                                 if self.user_act_tx.try_send(UserAction::Terminate).is_err() {
                                     let pid = Pid::from_raw(DEBUGEE_PID.load(Ordering::Acquire));
-                                    _ = kill(pid, Signal::SIGINT);
+                                    _ = kill(pid.into(), Signal::SIGINT);
                                     _ = self.user_act_tx.send(UserAction::Terminate);
                                 }
                                 break;
@@ -321,24 +421,82 @@ impl TerminalApplication {
             });
         }
 
-        app_loop.run()
+        // debugger is a tracer and stays in the current thread, it serves
+        // console requests until the console asks for exit or ui switch
+        let exit_type = srv_exchanger.serve(&mut self.debugger);
+        if console_jh.join().is_err() {
+            error!(target: "console", "unexpected: console thread panic");
+        }
+
+        match exit_type {
+            ServeExit::Exit | ServeExit::Shutdown => Ok(supervisor::ControlFlow::Exit),
+            #[cfg(feature = "tui")]
+            ServeExit::SwitchUi => {
+                cancel.store(true, Ordering::SeqCst);
+                let tui_builder =
+                    crate::ui::tui::AppBuilder::new(self.debugee_out, self.debugee_err);
+                let app = tui_builder.extend(self.debugger);
+                Ok(supervisor::ControlFlow::Switch(Box::new(app)))
+            }
+            #[cfg(not(feature = "tui"))]
+            ServeExit::SwitchUi => unreachable!("console doesn't switch ui without `tui` feature"),
+        }
     }
 }
 
 struct AppLoop {
-    debugger: Debugger,
-    file_view: Rc<FileView>,
+    /// Debugger requests are executed in a debugger thread.
+    exchanger: ClientExchanger,
+    file_view: Arc<FileView>,
+    /// Debugee events published by a hook.
+    events: EventStream,
+    /// Reason of the last debugee stop during the current command.
+    last_stop: Option<StopKind>,
+    /// Debugee status, printed before a prompt if changed.
+    status: DebugeeStatus,
+    /// Last printed debugee status.
+    printed_status: DebugeeStatus,
     user_input_rx: Receiver<UserAction>,
-    printer: ExternalPrinter,
+    printer: Rc<ExternalPrinter>,
+    /// Printer for output of debugger tasks (script messages, oracle reports),
+    /// tasks run in a debugger thread.
+    task_printer: Arc<Mutex<ExternalPrinter>>,
     completer: Arc<Mutex<CommandCompleter>>,
-    debugee_out: DebugeeOutReader,
-    debugee_err: DebugeeOutReader,
-    cancel_output_flag: Arc<AtomicBool>,
     helper: Helper,
     ready_to_next_command_tx: mpsc::Sender<EditorMode>,
+    /// Expressions printed after each stop, shared with debugger requests.
+    displays: Arc<Mutex<DisplayRegistry>>,
+    /// Captured expression values for `diff` command, shared with debugger requests.
+    snapshots: Arc<Mutex<SnapshotRegistry>>,
+    /// Console commands executed when a breakpoint (by its number) is hit.
+    breakpoint_commands: HashMap<u32, Vec<String>>,
+    /// Output settings changed by `set` command.
+    settings: Settings,
+    /// Set by `quit` command, application exits after the current command.
+    quit: bool,
 }
 
 impl AppLoop {
+    /// Execute a task in a debugger thread and wait for its result.
+    fn with_debugger<T, F>(&self, f: F) -> T
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Debugger) -> T + Send + 'static,
+    {
+        self.exchanger
+            .request_sync(f)
+            .expect("console never disables messaging")
+    }
+
+    /// Refresh a debugee status in a debugger thread.
+    fn refresh_status(&mut self) {
+        let mut status = std::mem::take(&mut self.status);
+        self.status = self.with_debugger(move |dbg| {
+            status.refresh(dbg);
+            status
+        });
+    }
+
     fn yes(&self, question: &str) -> bool {
         self.printer.println(question);
 
@@ -359,13 +517,89 @@ impl AppLoop {
         }
     }
 
+    /// Ask what to do with a running debugee on quit, return `None` if quit is canceled.
+    fn ask_quit_action(&self) -> Option<quit::Action> {
+        self.printer
+            .println("Debugee is running. Kill it, detach from it or cancel quit?");
+
+        loop {
+            _ = self.ready_to_next_command_tx.send(EditorMode::Quit);
+            let act = self
+                .user_input_rx
+                .recv()
+                .expect("unexpected sender disconnect");
+            return match act {
+                UserAction::Cmd(cmd) => match cmd.trim().to_lowercase().as_str() {
+                    "k" | "kill" => Some(quit::Action::Kill),
+                    "d" | "detach" => Some(quit::Action::Detach),
+                    "c" | "cancel" => None,
+                    _ => continue,
+                },
+                // user input is closed, so quit anyway
+                UserAction::Terminate => Some(quit::Action::Kill),
+                UserAction::ChangeMode | UserAction::Nop => None,
+            };
+        }
+    }
+
+    /// Render all declarations of a shadowed local variable (with a declaration place,
+    /// a state and a value), nothing is rendered if variable declared once.
+    fn render_shadowed_declarations(&self, var_name: &str) -> Vec<String> {
+        let name = var_name.to_string();
+        let declarations = self.with_debugger(move |dbg| dbg.variable_declarations(&name));
+        let Some(declarations) = muted_error!(declarations) else {
+            return vec![];
+        };
+        if declarations.len() < 2 {
+            return vec![];
+        }
+
+        let mut lines = vec![format!(
+            "`{var_name}` is declared {} times in the current function:",
+            declarations.len()
+        )];
+        lines.extend(declarations.into_iter().map(|decl| {
+            let place = decl
+                .place
+                .map(|(file, line)| {
+                    let file = file.file_name().unwrap_or(file.as_os_str()).to_owned();
+                    format!("{}:{line}", file.to_string_lossy())
+                })
+                .unwrap_or_else(|| "unknown place".to_string());
+            let state = decl.state;
+            match decl.value {
+                Some(value) => format!(
+                    "  {place} {state}: {}",
+                    render_variable_ir(&value, 0, &self.settings.render_limits)
+                ),
+                None => format!("  {place} {state}"),
+            }
+        }));
+        lines
+    }
+
+    /// Print command output. Output that doesn't fit into a terminal is shown in the pager.
+    fn print_paged(&self, output: Vec<String>) {
+        let lines: Vec<_> = output
+            .iter()
+            .flat_map(|s| s.lines())
+            .map(ToString::to_string)
+            .collect();
+        if pager::need_pager(lines.len()) {
+            if let Err(e) = Pager::new(lines).run() {
+                self.printer.println(ErrorView::from(e));
+            }
+            return;
+        }
+        output.into_iter().for_each(|s| self.printer.println(s));
+    }
+
     fn update_completer_variables(&self) -> anyhow::Result<()> {
-        let vars = self
-            .debugger
-            .read_variable_names(DQE::Variable(VariableSelector::Any))?;
-        let args = self
-            .debugger
-            .read_argument_names(DQE::Variable(VariableSelector::Any))?;
+        let (vars, args) = self.with_debugger(|dbg| -> Result<_, Error> {
+            let vars = dbg.read_variable_names(DQE::Variable(VariableSelector::Any))?;
+            let args = dbg.read_argument_names(DQE::Variable(VariableSelector::Any))?;
+            Ok((vars, args))
+        })?;
 
         let mut completer = self.completer.lock().unwrap();
         completer.replace_local_var_hints(vars);
@@ -373,39 +607,204 @@ impl AppLoop {
         Ok(())
     }
 
+    fn render_display(
+        display: &Display,
+        vars: &[VariableIR],
+        limits: &RenderLimits,
+    ) -> Vec<String> {
+        vars.iter()
+            .map(|var| {
+                format!(
+                    "{}: {} = {}",
+                    display.number,
+                    display.expr,
+                    render_variable_ir(var, 0, limits)
+                )
+            })
+            .collect()
+    }
+
+    /// Render an instruction with its encoding and a symbolic target of a call or a jump,
+    /// instruction at the program counter is marked with an arrow.
+    fn render_instruction(instruction: &DecodedInstruction, at_pc: bool) -> String {
+        let bytes = instruction
+            .bytes
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .join(" ");
+        let mut line = format!(
+            "{} {} {bytes:<width$} {} {}",
+            if at_pc { "=>" } else { "  " },
+            AddressView::from(instruction.address),
+            AsmInstructionView::from(instruction.mnemonic.as_deref()),
+            AsmOperandsView::from(instruction.operands.as_deref()),
+            width = MAX_ENCODING_VIEW_LEN * 3 - 1,
+        );
+        if let Some(BranchTarget {
+            symbol: Some((name, offset)),
+            ..
+        }) = &instruction.target
+        {
+            line.push_str(&format!(" <{}+{offset:#x}>", FunctionNameView::from(name)));
+        }
+        line
+    }
+
+    /// Update completer and print auto-displayed expressions after debugee stops.
+    fn on_stop(&self) {
+        _ = self.update_completer_variables();
+        let (displays, limits) = (self.displays.clone(), self.settings.render_limits);
+        let lines = self.with_debugger(move |dbg| {
+            let displays = displays.lock().unwrap();
+            displays
+                .evaluate(dbg)
+                .into_iter()
+                .flat_map(|(display, vars)| Self::render_display(display, &vars, &limits))
+                .collect::<Vec<_>>()
+        });
+        lines
+            .into_iter()
+            .for_each(|line| self.printer.println(line));
+    }
+
+    /// Handle debugee events published during a command execution,
+    /// a debugee stop triggers [`AppLoop::on_stop`].
+    fn process_events(&mut self) {
+        let mut stopped = false;
+        for event in self.events.drain() {
+            self.status.apply(&event);
+            if let DebuggerEvent::Stop(summary) = event {
+                self.last_stop = Some(summary.kind);
+                stopped = true;
+            }
+        }
+        if stopped {
+            self.on_stop();
+        }
+    }
+
+    /// Print a status line if debugee status is changed since the last print.
+    fn print_status(&mut self) {
+        if self.status != self.printed_status {
+            self.printer
+                .println(StatusView::from(format!("[{}]", self.status)));
+            self.printed_status = self.status.clone();
+        }
+    }
+
     fn handle_command(&mut self, cmd: &str) -> Result<(), CommandError> {
+        let result = self.execute_command(cmd);
+        self.process_events();
+        result
+    }
+
+    fn execute_command(&mut self, cmd: &str) -> Result<(), CommandError> {
         if cmd.is_empty() {
             return Ok(());
         }
 
-        match Command::parse(cmd)? {
-            Command::PrintVariables(print_var_command) => VariablesHandler::new(&self.debugger)
-                .handle(print_var_command)?
-                .into_iter()
-                .for_each(|var| {
-                    self.printer.println(
-                        render_variable(&var)
-                            .unwrap_or(print::style::UNKNOWN_PLACEHOLDER.to_string()),
-                    );
-                }),
-            Command::PrintArguments(print_arg_command) => ArgumentsHandler::new(&self.debugger)
-                .handle(print_arg_command)?
-                .into_iter()
-                .for_each(|arg| {
-                    self.printer.println(
-                        render_variable(&arg)
-                            .unwrap_or(print::style::UNKNOWN_PLACEHOLDER.to_string()),
-                    );
-                }),
+        let command = Command::parse(cmd).map_err(|e| match e {
+            CommandError::Parsing(error) => match help::usage_hint(cmd) {
+                Some(usage) => CommandError::Parsing(format!("{error}{usage}")),
+                None => CommandError::Parsing(error),
+            },
+            e => e,
+        })?;
+
+        match command {
+            Command::PrintVariables(print_var_command) => {
+                let var_name = match &print_var_command {
+                    DQE::Variable(VariableSelector::Name { var_name, .. }) => {
+                        Some(var_name.clone())
+                    }
+                    _ => None,
+                };
+                let pointer_depth = self.settings.render_limits.pointer_depth;
+                let vars = self.with_debugger(move |dbg| -> Result<_, CommandError> {
+                    let vars = VariablesHandler::new(dbg)
+                        .with_pointer_depth(pointer_depth)
+                        .handle(print_var_command)?;
+                    Ok(vars
+                        .into_iter()
+                        .map(|var| {
+                            // values of not live variables are stale, there is nothing to keep
+                            if matches!(var, VariableIR::Unavailable(_)) {
+                                return (None, var);
+                            }
+                            (Some(dbg.record_value(var.clone())), var)
+                        })
+                        .collect::<Vec<_>>())
+                })?;
+                let mut lines: Vec<_> = vars
+                    .into_iter()
+                    .map(|(num, var)| {
+                        let rendered = render_variable(&var, &self.settings.render_limits)
+                            .unwrap_or(print::style::UNKNOWN_PLACEHOLDER.to_string());
+                        match num {
+                            Some(num) => format!("${num}: {rendered}"),
+                            None => rendered,
+                        }
+                    })
+                    .collect();
+                if let Some(var_name) = var_name {
+                    lines.extend(self.render_shadowed_declarations(&var_name));
+                }
+                self.print_paged(lines);
+            }
+            Command::PrintVariablesJson(print_var_command, output) => {
+                let pointer_depth = self.settings.render_limits.pointer_depth;
+                let vars = self.with_debugger(move |dbg| {
+                    VariablesHandler::new(dbg)
+                        .with_pointer_depth(pointer_depth)
+                        .handle(print_var_command)
+                })?;
+                let json = serde_json::Value::Array(vars.iter().map(json::to_json).collect());
+                let json = serde_json::to_string_pretty(&json).expect("infallible");
+                match output {
+                    None => self.print_paged(json.lines().map(ToString::to_string).collect()),
+                    Some(path) => {
+                        std::fs::write(&path, json).map_err(Error::from)?;
+                        self.printer.println(format!(
+                            "{} variable(s) written into {}",
+                            vars.len(),
+                            path.display()
+                        ));
+                    }
+                }
+            }
+            Command::PrintArguments(print_arg_command) => {
+                let pointer_depth = self.settings.render_limits.pointer_depth;
+                let args = self.with_debugger(move |dbg| {
+                    ArgumentsHandler::new(dbg)
+                        .with_pointer_depth(pointer_depth)
+                        .handle(print_arg_command)
+                })?;
+                self.print_paged(
+                    args.iter()
+                        .map(|arg| {
+                            render_variable(arg, &self.settings.render_limits)
+                                .unwrap_or(print::style::UNKNOWN_PLACEHOLDER.to_string())
+                        })
+                        .collect(),
+                );
+            }
             Command::PrintBacktrace(cmd) => {
-                let bt = BacktraceHandler::new(&self.debugger).handle(cmd)?;
+                let bt = self.with_debugger(move |dbg| BacktraceHandler::new(dbg).handle(cmd))?;
+                let mut output = vec![];
+                let mut group = None;
                 bt.into_iter().for_each(|thread| {
+                    let label = thread.label.as_ref();
+                    if let Some(label) = label.filter(|&l| group.as_ref() != Some(l)) {
+                        output.push(format!("{label} threads:"));
+                        group = Some(label.clone());
+                    }
+
                     let ip = thread
                         .bt
                         .as_ref()
                         .and_then(|bt| bt.first().map(|f| f.ip.to_string()));
 
-                    self.printer.println(format!(
+                    output.push(format!(
                         "thread #{}, {} - {}",
                         thread.thread.number,
                         thread.thread.pid,
@@ -414,6 +813,14 @@ impl AppLoop {
 
                     if let Some(bt) = thread.bt {
                         for (frame_num, frame) in bt.into_iter().enumerate() {
+                            if frame.signal_frame {
+                                output.push(format!(
+                                    "#{frame_num} {} - <signal handler called>",
+                                    AddressView::from(frame.ip),
+                                ));
+                                continue;
+                            }
+
                             let fn_name = frame.func_name.clone().unwrap_or_default();
 
                             let user_bt_end = fn_name == "main"
@@ -422,10 +829,30 @@ impl AppLoop {
 
                             let fn_ip_or_zero = frame.fn_start_ip.unwrap_or_default();
 
+                            let args = frame
+                                .args
+                                .as_ref()
+                                .map(|args| {
+                                    let args = args
+                                        .iter()
+                                        .map(|arg| {
+                                            format!(
+                                                "{} = {}",
+                                                arg.name(),
+                                                render_variable_inline(arg, MAX_FRAME_ARG_LEN)
+                                            )
+                                        })
+                                        .join(", ");
+                                    format!("({args})")
+                                })
+                                .unwrap_or_default();
+
                             let mut frame_info = format!(
-                                "#{frame_num} {} - {} ({} + {:#X})",
+                                "#{frame_num} {} - {}{args} ({} + {:#X})",
                                 AddressView::from(frame.ip),
-                                FunctionNameView::from(frame.func_name),
+                                FunctionNameView::from(
+                                    frame.display_name(&self.settings.demangle_style)
+                                ),
                                 AddressView::from(frame.fn_start_ip),
                                 frame.ip.as_u64().saturating_sub(fn_ip_or_zero.as_u64()),
                             );
@@ -433,26 +860,28 @@ impl AppLoop {
                                 frame_info = frame_info.bold().to_string();
                             }
 
-                            self.printer.println(frame_info);
+                            output.push(frame_info);
                             if user_bt_end {
                                 break;
                             }
                         }
                     }
                 });
+                self.print_paged(output);
             }
-            Command::Continue => {
-                ContinueHandler::new(&mut self.debugger).handle()?;
-                _ = self.update_completer_variables();
+            Command::Continue(timeout) => {
+                self.with_debugger(move |dbg| ContinueHandler::new(dbg).handle(timeout))?;
             }
             Command::Frame(cmd) => {
-                let result = FrameHandler::new(&mut self.debugger).handle(cmd)?;
+                let result = self.with_debugger(move |dbg| FrameHandler::new(dbg).handle(cmd))?;
                 match result {
                     FrameResult::FrameInfo(frame) => {
                         self.printer.println(format!(
                             "frame #{} ({})",
                             frame.num,
-                            FunctionNameView::from(frame.frame.func_name),
+                            FunctionNameView::from(
+                                frame.frame.display_name(&self.settings.demangle_style)
+                            ),
                         ));
                         let cfa = AddressView::from(frame.cfa);
                         self.printer.println(format!("cfa: {cfa}"));
@@ -462,71 +891,267 @@ impl AppLoop {
                     FrameResult::BroughtIntoFocus(num) => {
                         self.printer.println(format!("switch to #{num}"));
                     }
+                    FrameResult::Memory(memory) => {
+                        let sp = AddressView::from(memory.sp);
+                        let cfa = AddressView::from(memory.cfa);
+                        self.printer.println(format!("sp: {sp}, cfa: {cfa}"));
+                        for slot in memory.slots.iter().rev() {
+                            let annotations = slot
+                                .annotations
+                                .iter()
+                                .map(|annotation| match annotation {
+                                    StackSlotAnnotation::SavedRegister(reg) => {
+                                        format!("saved {reg}")
+                                    }
+                                    StackSlotAnnotation::ReturnAddress => {
+                                        "return address".to_string()
+                                    }
+                                    StackSlotAnnotation::Variable(var) => format!("`{var}`"),
+                                })
+                                .collect::<Vec<_>>();
+
+                            let mut line =
+                                format!("{}: {:#018X}", AddressView::from(slot.addr), slot.value);
+                            if !annotations.is_empty() {
+                                line = format!("{line} <- {}", annotations.join(", "));
+                            }
+                            self.printer.println(line);
+                        }
+                    }
                 }
             }
-            Command::Run => match RunHandler::new(&mut self.debugger).handle(run::Command::Start) {
+            Command::Run(options) => match self.with_debugger({
+                let options = options.clone();
+                move |dbg| RunHandler::new(dbg).handle(run::Command::Start(options))
+            }) {
                 Err(CommandError::Handle(Error::AlreadyRun)) => {
                     if self.yes("Restart a program?") {
-                        RunHandler::new(&mut self.debugger).handle(run::Command::Restart)?
+                        self.with_debugger(move |dbg| {
+                            RunHandler::new(dbg).handle(run::Command::Restart(options))
+                        })?;
                     }
                 }
                 Err(e) => return Err(e),
-                _ => {
-                    _ = self.update_completer_variables();
-                }
+                _ => {}
             },
-            Command::StepInstruction => {
-                step_instruction::Handler::new(&mut self.debugger).handle()?;
-                _ = self.update_completer_variables();
+            Command::Kill => {
+                self.with_debugger(|dbg| kill::Handler::new(dbg).handle())?;
             }
-            Command::StepInto => {
-                step_into::Handler::new(&mut self.debugger).handle()?;
-                _ = self.update_completer_variables();
+            Command::Quit(action) => {
+                let action = match action.unwrap_or(self.settings.quit_action) {
+                    quit::Action::Ask if self.with_debugger(|dbg| dbg.is_in_progress()) => {
+                        match self.ask_quit_action() {
+                            Some(action) => action,
+                            None => return Ok(()),
+                        }
+                    }
+                    action => action,
+                };
+                self.with_debugger(move |dbg| quit::Handler::new(dbg).handle(action))?;
+                self.quit = true;
             }
-            Command::StepOut => {
-                step_out::Handler::new(&mut self.debugger).handle()?;
-                _ = self.update_completer_variables();
+            Command::StepInstruction(count) => {
+                let instruction = self.with_debugger(move |dbg| {
+                    step_instruction::Handler::new(dbg).handle(count)?;
+                    Ok::<_, CommandError>(muted_error!(dbg.current_instruction()).flatten())
+                })?;
+                if let Some(instruction) = instruction {
+                    self.printer
+                        .println(Self::render_instruction(&instruction, true));
+                }
             }
-            Command::StepOver => {
-                step_over::Handler::new(&mut self.debugger).handle()?;
-                _ = self.update_completer_variables();
+            Command::StepInto(count, granularity) => {
+                self.with_debugger(move |dbg| {
+                    step_into::Handler::new(dbg).handle(count, granularity)
+                })?;
+            }
+            Command::StepOut(count) => {
+                let returned = self.with_debugger(move |dbg| {
+                    let value = step_out::Handler::new(dbg).handle(count)?;
+                    Ok::<_, CommandError>(
+                        value.map(|value| (dbg.record_value(value.clone()), value)),
+                    )
+                })?;
+                if let Some((num, value)) = returned {
+                    let rendered = render_variable_ir(&value, 0, &self.settings.render_limits);
+                    self.printer
+                        .println(format!("Value returned: ${num} = {rendered}"));
+                }
+            }
+            Command::Return(value) => {
+                self.with_debugger(move |dbg| r#return::Handler::new(dbg).handle(value))?;
+            }
+            Command::UnwindException => {
+                self.with_debugger(|dbg| unwind::Handler::new(dbg).handle())?;
+            }
+            Command::Jump(location) => {
+                let jump = {
+                    let location = location.clone();
+                    self.with_debugger(move |dbg| JumpHandler::new(dbg).handle(&location, false))
+                };
+                match jump {
+                    Err(CommandError::Handle(Error::JumpOutsideFunction(_)))
+                        if self.yes(
+                            "Target line is outside of the current function, jump anyway?",
+                        ) =>
+                    {
+                        self.with_debugger(move |dbg| {
+                            JumpHandler::new(dbg).handle(&location, true)
+                        })?
+                    }
+                    res => res?,
+                }
+            }
+            Command::StepOver(count, granularity) => {
+                self.with_debugger(move |dbg| {
+                    step_over::Handler::new(dbg).handle(count, granularity)
+                })?;
             }
             Command::Breakpoint(mut brkpt_cmd) => {
-                let print_bp = |action: &str, bp: &debugger::BreakpointView| match &bp.place {
-                    None => {
-                        self.printer.println(format!(
-                            "{action} {} at {}",
+                let render_bp = |action: &str, bp: &debugger::BreakpointView| {
+                    let on_return = bp
+                        .on_return
+                        .map(|cond| format!(" (on return{cond})"))
+                        .or_else(|| bp.catch.map(|event| format!(" (catch {event})")))
+                        .unwrap_or_default();
+                    let hardware = if bp.hardware { " (hardware)" } else { "" };
+                    match &bp.place {
+                        None => format!(
+                            "{action} {} at {}{on_return}{hardware}",
                             bp.number,
-                            AddressView::from(bp.addr),
-                        ));
+                            AddressView::from(bp.addr)
+                        ),
+                        Some(place) => {
+                            format!(
+                                "{action} {} at {}: {}:{}{on_return}{hardware} ",
+                                bp.number,
+                                AddressView::from(place.address),
+                                FilePathView::from(place.file.to_string_lossy()),
+                                place.line_number,
+                            )
+                        }
                     }
-                    Some(place) => {
-                        self.printer.println(format!(
-                            "{action} {} at {}: {}:{} ",
-                            bp.number,
-                            AddressView::from(place.address),
-                            FilePathView::from(place.file.to_string_lossy()),
-                            place.line_number,
-                        ));
+                };
+                let print_bp = |action: &str, bp: &debugger::BreakpointView| {
+                    self.printer.println(render_bp(action, bp));
+                };
+
+                // a line with several statements, a user may choose one of them by a column
+                let line_columns = match &brkpt_cmd {
+                    BreakpointCommand::Add(BreakpointIdentity::Line(file, line)) => {
+                        let (file, line) = (file.clone(), *line);
+                        self.with_debugger(move |dbg| {
+                            dbg.line_columns(&file, line).unwrap_or_default()
+                        })
                     }
+                    _ => vec![],
+                };
+                let requested_line = match &brkpt_cmd {
+                    BreakpointCommand::Add(BreakpointIdentity::Line(_, line)) => Some(*line),
+                    _ => None,
                 };
 
                 loop {
-                    match BreakpointHandler::new(&mut self.debugger).handle(&brkpt_cmd) {
+                    let result = {
+                        let brkpt_cmd = brkpt_cmd.clone();
+                        self.with_debugger(move |dbg| {
+                            BreakpointHandler::new(dbg)
+                                .handle(&brkpt_cmd)
+                                .map(ExecutionResult::into_owned)
+                        })
+                    };
+                    match result {
                         Ok(r#break::ExecutionResult::New(brkpts)) => {
                             brkpts
                                 .iter()
                                 .for_each(|brkpt| print_bp("New breakpoint", brkpt));
+                            if let Some(line) = requested_line {
+                                let planted_at = brkpts
+                                    .iter()
+                                    .filter_map(|brkpt| brkpt.place.as_ref())
+                                    .map(|place| place.line_number)
+                                    .find(|&planted| planted != line);
+                                if let Some(planted) = planted_at {
+                                    self.printer.println(format!(
+                                        "There is no code at line {line}, breakpoint is planted at the next line with code: {planted}"
+                                    ));
+                                }
+                            }
+                            if line_columns.len() > 1 {
+                                self.printer.println(format!(
+                                    "Line contains statements at columns: {}, use `break <file>:<line>:<column>` to stop at one of them",
+                                    line_columns.iter().join(", ")
+                                ));
+                            }
                         }
                         Ok(r#break::ExecutionResult::Removed(brkpts)) => {
+                            brkpts.iter().for_each(|brkpt| {
+                                self.breakpoint_commands.remove(&brkpt.number);
+                                print_bp("Removed breakpoint", brkpt)
+                            });
+                        }
+                        Ok(r#break::ExecutionResult::NewGroup(group, brkpts)) => {
                             brkpts
                                 .iter()
-                                .for_each(|brkpt| print_bp("Removed breakpoint", brkpt));
+                                .for_each(|brkpt| print_bp("New breakpoint", brkpt));
+                            self.printer.println(format!(
+                                "{} breakpoints added to group {group}",
+                                brkpts.len()
+                            ));
                         }
-                        Ok(r#break::ExecutionResult::Dump(brkpts)) => brkpts
-                            .iter()
-                            .for_each(|brkpt| print_bp("- Breakpoint", brkpt)),
-                        Err(Error::NoSuitablePlace) => {
+                        Ok(r#break::ExecutionResult::Dump(brkpts, groups)) => {
+                            brkpts.iter().for_each(|brkpt| {
+                                let state = if brkpt.enabled { "" } else { ", disabled" };
+                                self.printer.println(format!(
+                                    "{} (hits: {}{state})",
+                                    render_bp("- Breakpoint", brkpt).trim_end(),
+                                    brkpt.hit_count
+                                ));
+                                if let Some(commands) = self.breakpoint_commands.get(&brkpt.number)
+                                {
+                                    self.printer
+                                        .println(format!("  commands: {}", commands.join("; ")));
+                                }
+                            });
+                            groups.iter().for_each(|group| {
+                                let members = group
+                                    .breakpoints
+                                    .iter()
+                                    .map(ToString::to_string)
+                                    .collect::<Vec<_>>()
+                                    .join(", ");
+                                self.printer.println(format!(
+                                    "- Group {} `{}`: breakpoints {members}",
+                                    group.number, group.pattern
+                                ))
+                            });
+                        }
+                        Ok(r#break::ExecutionResult::Enabled(brkpts)) => {
+                            brkpts
+                                .iter()
+                                .for_each(|brkpt| print_bp("Enabled breakpoint", brkpt));
+                        }
+                        Ok(r#break::ExecutionResult::Disabled(brkpts)) => {
+                            brkpts
+                                .iter()
+                                .for_each(|brkpt| print_bp("Disabled breakpoint", brkpt));
+                        }
+                        Ok(r#break::ExecutionResult::Commands(None, _)) => {
+                            self.printer.println("No breakpoint found")
+                        }
+                        Ok(r#break::ExecutionResult::Commands(Some(brkpt), commands)) => {
+                            for command in &commands {
+                                Command::parse(command)?;
+                            }
+                            if commands.is_empty() {
+                                self.breakpoint_commands.remove(&brkpt.number);
+                                print_bp("Commands removed from breakpoint", &brkpt);
+                            } else {
+                                self.breakpoint_commands.insert(brkpt.number, commands);
+                                print_bp("Commands attached to breakpoint", &brkpt);
+                            }
+                        }
+                        Err(Error::NoSuitablePlace) if brkpt_cmd.identity().is_some() => {
                             if self.yes("Add deferred breakpoint for future shared library load?") {
                                 brkpt_cmd = BreakpointCommand::AddDeferred(
                                     brkpt_cmd
@@ -545,7 +1170,7 @@ impl AppLoop {
                 }
             }
             Command::Watchpoint(cmd) => {
-                let print_wp = |prefix: &str, wp: debugger::WatchpointView| {
+                let render_wp = |prefix: &str, wp: debugger::WatchpointView| {
                     let source_expr = wp
                         .source_dqe
                         .map(|dqe| format!(", expression: {dqe}"))
@@ -554,77 +1179,281 @@ impl AppLoop {
                         AddressView::from(wp.address),
                         KeywordView::from(wp.condition),
                     );
-                    self.printer.println(format!(
+                    format!(
                         "{prefix} {} at {}, condition: {}, watch size: {}{source_expr}",
                         wp.number, addr, cond, wp.size
-                    ))
+                    )
                 };
 
-                let mut handler = WatchpointHandler::new(&mut self.debugger);
-                let res = handler.handle(cmd)?;
-                match res {
-                    WatchpointExecutionResult::New(wp) => print_wp("New watchpoint", wp),
-                    WatchpointExecutionResult::Removed(Some(wp)) => {
-                        print_wp("Removed watchpoint", wp)
-                    }
-                    WatchpointExecutionResult::Removed(_) => {
-                        self.printer.println("No watchpoint found")
-                    }
-                    WatchpointExecutionResult::Dump(wps) => {
-                        self.printer
-                            .println(format!("{}/4 active watchpoints:", wps.len()));
-                        for wp in wps {
-                            print_wp("- Watchpoint", wp)
+                // watchpoint views borrow a debugger, so they are rendered in a debugger thread
+                let lines = self.with_debugger(move |dbg| {
+                    let lines = match WatchpointHandler::new(dbg).handle(cmd)? {
+                        WatchpointExecutionResult::New(wp) => vec![render_wp("New watchpoint", wp)],
+                        WatchpointExecutionResult::Removed(Some(wp)) => {
+                            vec![render_wp("Removed watchpoint", wp)]
                         }
+                        WatchpointExecutionResult::Removed(_) => {
+                            vec!["No watchpoint found".to_string()]
+                        }
+                        WatchpointExecutionResult::Dump(wps) => {
+                            let mut lines = vec![format!("{}/4 active watchpoints:", wps.len())];
+                            lines.extend(wps.into_iter().map(|wp| render_wp("- Watchpoint", wp)));
+                            lines
+                        }
+                    };
+                    Ok::<_, Error>(lines)
+                })?;
+                lines
+                    .into_iter()
+                    .for_each(|line| self.printer.println(line));
+            }
+            Command::Trace(cmd) => {
+                // tracepoints are borrowed from a debugger, so a report is rendered in a
+                // debugger thread
+                let lines = self.with_debugger(move |dbg| {
+                    let lines = match TraceHandler::new(dbg).handle(cmd)? {
+                        TraceResult::New(number) => vec![format!("New tracepoint {number}")],
+                        TraceResult::Report([]) => vec!["No tracepoints".to_string()],
+                        TraceResult::Report(tracepoints) => tracepoints
+                            .iter()
+                            .map(|tp| render_tracepoint_report(tp).trim_end().to_string())
+                            .collect(),
+                    };
+                    Ok::<_, Error>(lines)
+                })?;
+                lines
+                    .into_iter()
+                    .for_each(|line| self.printer.println(line));
+            }
+            Command::Ftrace(cmd) => {
+                match self.with_debugger(move |dbg| FtraceHandler::new(dbg).handle(cmd))? {
+                    FtraceResult::Started(count) => {
+                        self.printer.println(format!("Tracing {count} functions"))
                     }
+                    FtraceResult::Stopped => self.printer.println("Function tracing stopped"),
+                }
+            }
+            Command::Coverage(cmd) => {
+                // coverage is borrowed from a debugger, so it is rendered in a debugger thread
+                let lines = self.with_debugger(move |dbg| {
+                    let lines = match CoverageHandler::new(dbg).handle(cmd)? {
+                        CoverageResult::Started(lines) => {
+                            vec![format!("Coverage collecting started, {lines} lines found")]
+                        }
+                        CoverageResult::Stopped(coverage) => coverage
+                            .summary()
+                            .into_iter()
+                            .map(|file| {
+                                let percent =
+                                    file.lines_hit as f64 * 100.0 / file.lines_found as f64;
+                                format!(
+                                    "{}: {}/{} lines ({percent:.1}%)",
+                                    FilePathView::from(file.path.to_string_lossy()),
+                                    file.lines_hit,
+                                    file.lines_found
+                                )
+                            })
+                            .collect(),
+                        CoverageResult::Report(coverage) => {
+                            vec![coverage.lcov().trim_end().to_string()]
+                        }
+                        CoverageResult::Exported(path) => {
+                            vec![format!("Coverage report is written into {path}")]
+                        }
+                    };
+                    Ok::<_, Error>(lines)
+                })?;
+                lines
+                    .into_iter()
+                    .for_each(|line| self.printer.println(line));
+            }
+            Command::Profile(cmd) => {
+                // profile is borrowed from a debugger, so a report is rendered in a debugger thread
+                let (lines, paged) = self.with_debugger(move |dbg| {
+                    let output = match ProfileHandler::new(dbg).handle(cmd)? {
+                        ProfileResult::Started(interval) => (
+                            vec![format!("Profiling started, sample interval {interval:?}")],
+                            false,
+                        ),
+                        ProfileResult::Report(profile, ReportKind::Flat) => {
+                            (render_flat_profile(profile), true)
+                        }
+                        ProfileResult::Report(profile, ReportKind::Tree) => {
+                            (render_call_tree(profile), true)
+                        }
+                        ProfileResult::Exported(samples, path) => {
+                            (vec![format!("{samples} samples written to {path}")], false)
+                        }
+                        ProfileResult::NoProfile => {
+                            (vec!["No profile collected".to_string()], false)
+                        }
+                    };
+                    Ok::<_, Error>(output)
+                })?;
+                if paged {
+                    self.print_paged(lines);
+                } else {
+                    lines
+                        .into_iter()
+                        .for_each(|line| self.printer.println(line));
                 }
             }
             Command::Memory(mem_cmd) => {
-                let read = MemoryHandler::new(&self.debugger).handle(mem_cmd)?;
-                self.printer.println(format!("{:#016X}", read));
+                match self.with_debugger(move |dbg| MemoryHandler::new(dbg).handle(mem_cmd))? {
+                    MemoryResult::Value(value) => self.printer.println(format!("{:#016X}", value)),
+                    MemoryResult::Saved(len) => self.printer.println(format!("{len} bytes saved")),
+                    MemoryResult::Loaded(len) => {
+                        self.printer.println(format!("{len} bytes loaded"))
+                    }
+                }
+            }
+            Command::Examine(cmd) => {
+                let lines =
+                    match self.with_debugger(move |dbg| ExamineHandler::new(dbg).handle(&cmd))? {
+                        ExamineResult::Units(lines) => lines
+                            .into_iter()
+                            .map(|(addr, units)| {
+                                format!(
+                                    "{}: {}",
+                                    AddressView::from(RelocatedAddress::from(addr)),
+                                    units.join("  ")
+                                )
+                            })
+                            .collect(),
+                        ExamineResult::Strings(strings) => strings
+                            .into_iter()
+                            .map(|(addr, string)| {
+                                format!(
+                                    "{}: {string}",
+                                    AddressView::from(RelocatedAddress::from(addr))
+                                )
+                            })
+                            .collect(),
+                        ExamineResult::Instructions(instructions, pc) => instructions
+                            .iter()
+                            .map(|ins| Self::render_instruction(ins, ins.address == pc))
+                            .collect(),
+                    };
+                self.print_paged(lines);
             }
             Command::Register(reg_cmd) => {
-                let response = RegisterHandler::new(&self.debugger).handle(&reg_cmd)?;
+                let response =
+                    self.with_debugger(move |dbg| RegisterHandler::new(dbg).handle(&reg_cmd))?;
                 response.iter().for_each(|register| {
-                    self.printer.println(format!(
-                        "{:10} {:#016X}",
-                        register.register_name, register.value
-                    ));
+                    let value = match &register.value {
+                        RegisterValue::Scalar(value) => format!("{value:#016X}"),
+                        RegisterValue::Lanes(lanes) => format!("{{{}}}", lanes.join(", ")),
+                        RegisterValue::Words(words) => format!(
+                            "{{{}}}",
+                            words.iter().map(|w| format!("{w:#018X}")).join(", ")
+                        ),
+                    };
+                    self.printer
+                        .println(format!("{:10} {value}", register.register_name));
                 });
             }
             Command::Help { reason, command } => {
                 if let Some(reason) = reason {
                     self.printer.println(reason);
                 }
+                let oracle_help = self.with_debugger(|dbg| {
+                    dbg.all_oracles()
+                        .map(|oracle| oracle.help().to_string())
+                        .collect::<Vec<_>>()
+                });
                 self.printer.println(
                     self.helper
-                        .help_for_command(&self.debugger, command.as_deref()),
+                        .help_for_command(&oracle_help, command.as_deref()),
                 );
             }
             Command::SkipInput => {}
             Command::PrintSymbol(symbol) => {
-                let symbols = SymbolHandler::new(&self.debugger).handle(&symbol)?;
+                let symbols =
+                    self.with_debugger(move |dbg| SymbolHandler::new(dbg).handle(&symbol))?;
                 for symbol in symbols {
+                    let defined_in = match symbol.source {
+                        Some((file, Some(line))) => format!("{}:{line}", file.to_string_lossy()),
+                        Some((file, None)) => file.to_string_lossy().to_string(),
+                        None => symbol.object.to_string_lossy().to_string(),
+                    };
                     self.printer.println(format!(
-                        "{} - {:?} {}",
+                        "{} - {} {} at {}",
                         symbol.name,
                         symbol.kind,
-                        AddressView::from(symbol.addr)
+                        AddressView::from(symbol.addr),
+                        FilePathView::from(defined_in)
                     ));
                 }
             }
+            Command::Resolve(addresses) => {
+                let infos = {
+                    let addresses = addresses.clone();
+                    self.with_debugger(move |dbg| ResolveHandler::new(dbg).handle(&addresses))?
+                };
+                let output = addresses
+                    .into_iter()
+                    .zip(infos)
+                    .flat_map(|(addr, info)| {
+                        let header = AddressView::from(RelocatedAddress::from(addr)).to_string();
+                        render_address_info(header, info)
+                    })
+                    .collect();
+                self.print_paged(output);
+            }
+            Command::Symbolize(path) => {
+                let frames =
+                    self.with_debugger(move |dbg| SymbolizeHandler::new(dbg).handle(&path))?;
+                if frames.is_empty() {
+                    self.printer.println("No addresses found in backtrace");
+                }
+                let output = frames
+                    .into_iter()
+                    .flat_map(|frame| {
+                        let mut header = format!(
+                            "#{} {}",
+                            frame.num,
+                            AddressView::from(RelocatedAddress::from(frame.addr))
+                        );
+                        if let Some(module) = frame.module {
+                            header = format!("{header} ({module})");
+                        }
+                        render_address_info(header, frame.info)
+                    })
+                    .collect();
+                self.print_paged(output);
+            }
+            Command::PrintType(name) => {
+                let types = self.with_debugger(move |dbg| PtypeHandler::new(dbg).handle(&name))?;
+                for (name, decl) in types {
+                    self.printer.println(format!("{name}: {decl}"));
+                }
+            }
             Command::Thread(cmd) => {
-                let result = command::thread::Handler::new(&mut self.debugger).handle(cmd)?;
+                let result =
+                    self.with_debugger(move |dbg| command::thread::Handler::new(dbg).handle(cmd))?;
                 match result {
                     ThreadResult::List(mut list) => {
                         list.sort_by(|t1, t2| t1.thread.number.cmp(&t2.thread.number));
+                        command::thread::group_by_label(&mut list);
+                        let mut group = None;
                         for thread in list {
+                            let label = thread.label.as_ref();
+                            if let Some(label) = label.filter(|&l| group.as_ref() != Some(l)) {
+                                self.printer.println(format!("{label} threads:"));
+                                group = Some(label.clone());
+                            }
+
                             let current_frame = thread.bt.and_then(|mut bt| bt.drain(..).next());
                             let ip = current_frame.as_ref().map(|f| f.ip.to_string());
                             let func = current_frame.and_then(|f| f.func_name);
 
+                            let name = thread
+                                .name
+                                .map(|name| format!(" \"{name}\","))
+                                .unwrap_or_default();
+
                             let view = format!(
-                                "#{} thread id: {}, {} in {}",
+                                "#{} thread id: {},{name} {} in {}",
                                 thread.thread.number,
                                 thread.thread.pid,
                                 AddressView::from(ip),
@@ -636,16 +1465,104 @@ impl AppLoop {
                             } else {
                                 self.printer.println(view)
                             }
+
+                            // skip frames of thread spawning machinery from std library
+                            let origin = thread.origin_bt.and_then(|bt| {
+                                let is_user_frame = |frame: &&FrameSpan| {
+                                    frame.func_name.as_ref().is_some_and(|name| {
+                                        !["std::", "core::", "alloc::"]
+                                            .iter()
+                                            .any(|prefix| name.starts_with(prefix))
+                                    })
+                                };
+                                bt.iter().find(is_user_frame).or(bt.first()).cloned()
+                            });
+                            if let Some(origin) = origin {
+                                self.printer.println(format!(
+                                    "    created at: {} in {}",
+                                    AddressView::from(origin.ip),
+                                    FunctionNameView::from(origin.func_name),
+                                ));
+                            }
                         }
                     }
-                    ThreadResult::BroughtIntoFocus(thread) => self
-                        .printer
-                        .println(format!("Thread #{} brought into focus", thread.number)),
+                    ThreadResult::BroughtIntoFocus(thread) => {
+                        self.printer
+                            .println(format!("Thread #{} brought into focus", thread.number));
+                        self.refresh_status();
+                    }
+                }
+            }
+            Command::Inferior(cmd) => {
+                let result = self
+                    .with_debugger(move |dbg| command::inferior::Handler::new(dbg).handle(cmd))?;
+                match result {
+                    InferiorResult::List(list) => {
+                        for inferior in list {
+                            let status = match inferior.status {
+                                InferiorStatus::NotStarted => "not started",
+                                InferiorStatus::InProgress => "in progress",
+                                InferiorStatus::Exited => "exited",
+                            };
+                            let view = format!(
+                                "#{} pid: {}, {status}, {}",
+                                inferior.num,
+                                inferior.pid,
+                                FilePathView::from(inferior.program),
+                            );
+                            if inferior.current {
+                                self.printer.println(format!("{}", view.bold()))
+                            } else {
+                                self.printer.println(view)
+                            }
+                        }
+                    }
+                    InferiorResult::Switched(inferior) => {
+                        let files = self
+                            .with_debugger(|dbg| dbg.known_files().cloned().collect::<Vec<_>>());
+                        self.completer.lock().unwrap().replace_file_hints(files);
+                        self.printer.println(format!(
+                            "Inferior #{} (pid: {}) brought into focus",
+                            inferior.num, inferior.pid
+                        ))
+                    }
+                }
+            }
+            Command::ValueHistory => {
+                let history = self.with_debugger(|dbg| dbg.value_history().to_vec());
+                let lines = history
+                    .iter()
+                    .enumerate()
+                    .map(|(num, value)| {
+                        let rendered = render_variable(value, &self.settings.render_limits)
+                            .unwrap_or(print::style::UNKNOWN_PLACEHOLDER.to_string());
+                        format!("${num}: {rendered}")
+                    })
+                    .collect();
+                self.print_paged(lines);
+            }
+            Command::StepHistory { all_threads } => {
+                let stops = self.with_debugger(move |dbg| {
+                    let tid = if all_threads {
+                        None
+                    } else {
+                        Some(dbg.exploration_ctx().pid_on_focus())
+                    };
+                    dbg.step_history(tid)
+                });
+                if stops.is_empty() {
+                    self.printer.println("No stops recorded");
+                } else {
+                    self.print_paged(render_step_history(&stops));
                 }
             }
+            Command::Info(cmd) => {
+                let infos = self.with_debugger(move |dbg| InfoHandler::new(dbg).handle(&cmd))?;
+                self.print_paged(render_line_info(infos));
+            }
             Command::SharedLib => {
-                let handler = SharedlibHandler::new(&self.debugger);
-                for lib in handler.handle() {
+                let libs = self.with_debugger(|dbg| SharedlibHandler::new(dbg).handle());
+                for lib in libs {
                     let mb_range = lib
                         .range
                         .map(|range| format!("{} - {}", range.from, range.to));
@@ -658,48 +1575,62 @@ impl AppLoop {
                     ))
                 }
             }
+            Command::DeadlockCheck => {
+                let deadlocks = self.with_debugger(|dbg| DeadlockHandler::new(dbg).handle())?;
+                for line in render_deadlocks(&deadlocks) {
+                    self.printer.println(line);
+                }
+            }
             Command::SourceCode(inner_cmd) => match inner_cmd {
                 source_code::Command::Range(bounds) => {
-                    let handler = FunctionLineRangeHandler::new(&self.debugger);
-                    let range = handler.handle()?;
-
-                    self.printer.println(format!(
-                        "{} at {}:{}",
-                        FunctionNameView::from(range.name),
-                        FilePathView::from(range.stop_place.file.to_string_lossy()),
-                        range.stop_place.line_number,
-                    ));
+                    let file_view = self.file_view.clone();
+                    let (header, source) = self.with_debugger(move |dbg| {
+                        let handler = FunctionLineRangeHandler::new(dbg);
+                        let range = handler.handle()?;
+                        let header = format!(
+                            "{} at {}:{}",
+                            FunctionNameView::from(range.name),
+                            FilePathView::from(range.stop_place.file.to_string_lossy()),
+                            range.stop_place.line_number,
+                        );
+                        let source = file_view.render_source(&range.stop_place, bounds);
+                        Ok::<_, Error>((header, source))
+                    })?;
 
-                    self.printer.print(
-                        self.file_view
-                            .render_source(&range.stop_place, bounds)
-                            .map_err(CommandError::FileRender)?,
-                    );
+                    self.printer.println(header);
+                    self.printer
+                        .print(source.map_err(CommandError::FileRender)?);
                 }
                 source_code::Command::Function => {
-                    let handler = FunctionLineRangeHandler::new(&self.debugger);
-                    let range = handler.handle()?;
-
-                    self.printer.println(format!(
-                        "{} at {}:{}",
-                        FunctionNameView::from(range.name),
-                        FilePathView::from(range.stop_place.file.to_string_lossy()),
-                        range.stop_place.line_number,
-                    ));
+                    let file_view = self.file_view.clone();
+                    let (header, source) = self.with_debugger(move |dbg| {
+                        let handler = FunctionLineRangeHandler::new(dbg);
+                        let range = handler.handle()?;
+                        let header = format!(
+                            "{} at {}:{}",
+                            FunctionNameView::from(range.name),
+                            FilePathView::from(range.stop_place.file.to_string_lossy()),
+                            range.stop_place.line_number,
+                        );
+                        let source = file_view.render_source_range(
+                            range.file,
+                            dbg.embedded_source(range.file),
+                            range.start_line,
+                            range.end_line,
+                        );
+                        Ok::<_, Error>((header, source))
+                    })?;
 
-                    self.printer.print(
-                        self.file_view
-                            .render_source_range(range.file, range.start_line, range.end_line)
-                            .map_err(CommandError::FileRender)?,
-                    );
+                    self.printer.println(header);
+                    self.printer
+                        .print(source.map_err(CommandError::FileRender)?);
                 }
                 source_code::Command::Asm => {
-                    let handler = DisAsmHandler::new(&self.debugger);
-                    let assembly = handler.handle()?;
-                    self.printer.println(format!(
+                    let assembly = self.with_debugger(|dbg| DisAsmHandler::new(dbg).handle())?;
+                    let mut output = vec![format!(
                         "Assembler code for function {}",
                         FunctionNameView::from(assembly.name)
-                    ));
+                    )];
                     for ins in assembly.instructions {
                         let instruction_view = format!(
                             "{} {} {}",
@@ -709,67 +1640,215 @@ impl AppLoop {
                         );
 
                         if ins.address == assembly.addr_in_focus {
-                            self.printer.println(format!("{}", instruction_view.bold()));
+                            output.push(format!("{}", instruction_view.bold()));
                         } else {
-                            self.printer.println(instruction_view);
+                            output.push(instruction_view);
                         }
                     }
+                    self.print_paged(output);
                 }
             },
-            Command::Oracle(name, subcmd) => match self.debugger.get_oracle(&name) {
-                None => self
-                    .printer
-                    .println(ErrorView::from("Oracle not found or not ready")),
-                Some(oracle) => oracle.print(&self.printer, subcmd.as_deref()),
-            },
+            Command::Script(path) => {
+                let printer = self.task_printer.clone();
+                self.with_debugger(move |dbg| {
+                    ScriptHandler::new(dbg)
+                        .handle(&path, move |msg| printer.lock().unwrap().println(msg))
+                })?;
+                _ = self.update_completer_variables();
+            }
+            Command::Display(cmd) => {
+                let (displays, limits) = (self.displays.clone(), self.settings.render_limits);
+                let lines = self.with_debugger(move |dbg| {
+                    let mut displays = displays.lock().unwrap();
+                    match DisplayHandler::new(dbg, &mut displays).handle(cmd) {
+                        DisplayResult::New(display, vars) => {
+                            let mut lines =
+                                vec![format!("New display {}: {}", display.number, display.expr)];
+                            lines.extend(Self::render_display(display, &vars, &limits));
+                            lines
+                        }
+                        DisplayResult::Removed(Some(display)) => {
+                            vec![format!(
+                                "Removed display {}: {}",
+                                display.number, display.expr
+                            )]
+                        }
+                        DisplayResult::Removed(None) => vec!["No display found".to_string()],
+                        DisplayResult::Dump(displays) => displays
+                            .into_iter()
+                            .flat_map(|(display, vars)| {
+                                Self::render_display(display, &vars, &limits)
+                            })
+                            .collect(),
+                    }
+                });
+                lines
+                    .into_iter()
+                    .for_each(|line| self.printer.println(line));
+            }
+            Command::Snapshot(cmd) => {
+                let snapshots = self.snapshots.clone();
+                match self.with_debugger(move |dbg| {
+                    SnapshotHandler::new(dbg, &mut snapshots.lock().unwrap()).handle(cmd)
+                })? {
+                    SnapshotResult::Taken(count) => self
+                        .printer
+                        .println(format!("Snapshot taken, variables captured: {count}")),
+                    SnapshotResult::Diff(diffs) => {
+                        let lines = diffs
+                            .iter()
+                            .flat_map(|d| {
+                                render_variable_diff(
+                                    d.old.as_ref(),
+                                    d.new.as_ref(),
+                                    &self.settings.render_limits,
+                                )
+                            })
+                            .collect();
+                        self.print_paged(lines);
+                    }
+                }
+            }
+            Command::Set(cmd) => {
+                SetHandler::new(&mut self.settings).handle(cmd);
+                self.file_view.set_list_size(self.settings.list_size);
+            }
+            Command::Session(cmd) => {
+                let displays = self.displays.clone();
+                let result = self.with_debugger(move |dbg| {
+                    SessionHandler::new(dbg, &mut displays.lock().unwrap()).handle(cmd)
+                })?;
+                match result {
+                SessionResult::Saved {
+                    breakpoints,
+                    watchpoints,
+                    displays,
+                } => self.printer.println(format!(
+                    "Session saved: {breakpoints} breakpoints, {watchpoints} watchpoints, {displays} displays"
+                )),
+                SessionResult::Loaded {
+                    breakpoints,
+                    watchpoints,
+                    displays,
+                    errors,
+                } => {
+                    for error in errors {
+                        self.printer.println(ErrorView::from(error));
+                    }
+                    self.printer.println(format!(
+                        "Session loaded: {breakpoints} breakpoints, {watchpoints} watchpoints, {displays} displays"
+                    ));
+                }
+                }
+            }
+            Command::Oracle(name, subcmd) => {
+                // oracle state is updated by a debugger, so it is printed in a debugger thread
+                let printer = self.task_printer.clone();
+                let found = self.with_debugger(move |dbg| match dbg.get_oracle(&name) {
+                    None => false,
+                    Some(oracle) => {
+                        oracle.print(&printer.lock().unwrap(), subcmd.as_deref());
+                        true
+                    }
+                });
+                if !found {
+                    self.printer
+                        .println(ErrorView::from("Oracle not found or not ready"));
+                }
+            }
         }
 
         Ok(())
     }
 
-    fn run(mut self) -> anyhow::Result<supervisor::ControlFlow> {
+    fn print_command_error(&self, e: CommandError) {
+        match e {
+            CommandError::Parsing(pretty_error) => {
+                self.printer.println(pretty_error);
+            }
+            CommandError::FileRender(_) => {
+                self.printer
+                    .println(ErrorView::from(format!("Render file error: {e:#}")));
+            }
+            CommandError::Script(_)
+            | CommandError::Session(_)
+            | CommandError::BacktraceImport(_) => {
+                self.printer.println(ErrorView::from(format!("{e:#}")));
+            }
+            CommandError::Handle(ref err) if err.is_fatal() => {
+                self.printer.println(ErrorView::from("Shutdown debugger"));
+                self.printer
+                    .println(ErrorView::from(format!("Fatal error: {e:#}")));
+                exit(1);
+            }
+            CommandError::Handle(_)
+            | CommandError::SnapshotNotFound(_)
+            | CommandError::ExamineTooLarge(_)
+            | CommandError::ExamineZeroCount => {
+                self.printer
+                    .println(ErrorView::from(format!("Error: {e:#}")));
+            }
+        }
+    }
+
+    /// Run a console loop, the loop ends when a console asks a debugger thread for exit
+    /// or ui switch. If the loop ends without a request, a debugger thread shuts down.
+    fn run(mut self) {
         loop {
+            self.print_status();
             _ = self.ready_to_next_command_tx.send(EditorMode::Default);
 
             let Ok(action) = self.user_input_rx.recv() else {
-                return Ok(supervisor::ControlFlow::Exit);
+                return;
             };
 
             match action {
                 UserAction::Cmd(command) => {
-                    if let Err(e) = self.handle_command(&command) {
-                        match e {
-                            CommandError::Parsing(pretty_error) => {
-                                self.printer.println(pretty_error);
-                            }
-                            CommandError::FileRender(_) => {
-                                self.printer
-                                    .println(ErrorView::from(format!("Render file error: {e:#}")));
-                            }
-                            CommandError::Handle(ref err) if err.is_fatal() => {
-                                self.printer.println(ErrorView::from("Shutdown debugger"));
-                                self.printer
-                                    .println(ErrorView::from(format!("Fatal error: {e:#}")));
-                                exit(1);
-                            }
-                            CommandError::Handle(_) => {
-                                self.printer
-                                    .println(ErrorView::from(format!("Error: {e:#}")));
+                    let mut queue = VecDeque::from([command]);
+                    while let Some(command) = queue.pop_front() {
+                        self.last_stop = None;
+                        if let Err(e) = self.handle_command(&command) {
+                            self.print_command_error(e);
+                            break;
+                        }
+                        if self.quit {
+                            self.exchanger.send_exit();
+                            return;
+                        }
+                        // debugee stopped, remaining commands are dropped,
+                        // commands of a hit breakpoint are executed instead
+                        match self.last_stop.take() {
+                            Some(StopKind::Breakpoint { number, .. }) => {
+                                queue = self
+                                    .breakpoint_commands
+                                    .get(&number)
+                                    .cloned()
+                                    .unwrap_or_default()
+                                    .into();
                             }
+                            Some(_) => queue.clear(),
+                            None => {}
                         }
                     }
                 }
                 UserAction::Nop => {}
                 UserAction::Terminate => {
-                    return Ok(supervisor::ControlFlow::Exit);
+                    // there is no user input to ask for a quit action,
+                    // so a debugee is released by default unless an action is set
+                    let action = self.settings.quit_action;
+                    _ = self.with_debugger(move |dbg| quit::Handler::new(dbg).handle(action));
+                    self.exchanger.send_exit();
+                    return;
+                }
+                #[cfg(feature = "tui")]
+                UserAction::ChangeMode => {
+                    self.exchanger.send_switch_ui();
+                    return;
                 }
+                #[cfg(not(feature = "tui"))]
                 UserAction::ChangeMode => {
-                    self.cancel_output_flag.store(true, Ordering::SeqCst);
-                    let tui_builder =
-                        crate::ui::tui::AppBuilder::new(self.debugee_out, self.debugee_err);
-                    let app = tui_builder.extend(self.debugger);
-                    return Ok(supervisor::ControlFlow::Switch(
-                        supervisor::Application::TUI(app),
+                    self.printer.println(ErrorView::from(
+                        "Error: terminal ui is not available, bs is built without `tui` feature",
                     ));
                 }
             }
@@ -0,0 +1,285 @@
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{read, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Print, SetAttribute, Stylize};
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, Clear, ClearType, DisableLineWrap, EnableLineWrap,
+    EnterAlternateScreen, LeaveAlternateScreen,
+};
+use crossterm::{execute, queue, terminal};
+use std::io::{IsTerminal, Write};
+
+/// Return true if output with this number of lines doesn't fit into a terminal and
+/// should be shown in the pager.
+pub fn need_pager(lines: usize) -> bool {
+    if cfg!(feature = "int_test") || !std::io::stdout().is_terminal() {
+        return false;
+    }
+    let Ok((_, rows)) = terminal::size() else {
+        return false;
+    };
+    // last terminal row is used for a pager status line
+    lines >= rows as usize
+}
+
+/// Remove ANSI escape sequences (colors and text styles) from a line.
+fn strip_ansi(line: &str) -> String {
+    let mut result = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // skip CSI sequence: ESC [ parameters final-byte
+            if chars.next() == Some('[') {
+                for c in chars.by_ref() {
+                    if c.is_ascii_alphabetic() {
+                        break;
+                    }
+                }
+            }
+            continue;
+        }
+        result.push(c);
+    }
+    result
+}
+
+/// Return byte ranges of all non-overlapping query occurrences in a text,
+/// search is case-insensitive for ASCII letters.
+fn match_ranges(text: &str, query: &str) -> Vec<(usize, usize)> {
+    if query.is_empty() {
+        return vec![];
+    }
+    // ascii lowercase keeps byte offsets, so ranges are valid for the original text
+    text.to_ascii_lowercase()
+        .match_indices(&query.to_ascii_lowercase())
+        .map(|(start, m)| (start, start + m.len()))
+        .collect()
+}
+
+enum Mode {
+    /// Scroll through text.
+    View,
+    /// Typing a search query.
+    Search,
+}
+
+/// Interactive pager for long command output (like `less`).
+///
+/// Controls: `j`/`k` or arrows scroll by line, `Space`/`b` or `PgDn`/`PgUp` scroll by page,
+/// `g`/`G` go to the begin/end, `/` search a text (matches are highlighted while typing),
+/// `n`/`N` go to the next/previous match, `q` quit.
+pub struct Pager {
+    lines: Vec<String>,
+    /// Lines without escape sequences, used for search.
+    plain_lines: Vec<String>,
+    /// Index of the first visible line.
+    top: usize,
+    query: String,
+    mode: Mode,
+}
+
+impl Pager {
+    pub fn new(lines: Vec<String>) -> Self {
+        let plain_lines = lines.iter().map(|l| strip_ansi(l)).collect();
+        Self {
+            lines,
+            plain_lines,
+            top: 0,
+            query: String::new(),
+            mode: Mode::View,
+        }
+    }
+
+    /// Show pager until user quits it.
+    pub fn run(mut self) -> std::io::Result<()> {
+        let mut stdout = std::io::stdout();
+        enable_raw_mode()?;
+        execute!(stdout, EnterAlternateScreen, DisableLineWrap, Hide)?;
+
+        let result = self.event_loop(&mut stdout);
+
+        execute!(stdout, Show, EnableLineWrap, LeaveAlternateScreen)?;
+        disable_raw_mode()?;
+        result
+    }
+
+    fn page_height() -> usize {
+        let (_, rows) = terminal::size().unwrap_or((80, 24));
+        (rows as usize).saturating_sub(1).max(1)
+    }
+
+    fn max_top(&self) -> usize {
+        self.lines.len().saturating_sub(Self::page_height())
+    }
+
+    fn scroll_down(&mut self, n: usize) {
+        self.top = (self.top + n).min(self.max_top());
+    }
+
+    fn scroll_up(&mut self, n: usize) {
+        self.top = self.top.saturating_sub(n);
+    }
+
+    /// Move to the first line with a match, starting from line `from`.
+    fn find_forward(&mut self, from: usize) {
+        let len = self.plain_lines.len();
+        if let Some(idx) = (0..len)
+            .map(|i| (from + i) % len)
+            .find(|&idx| !match_ranges(&self.plain_lines[idx], &self.query).is_empty())
+        {
+            self.top = idx;
+        }
+    }
+
+    /// Move to the first line with a match before line `from`.
+    fn find_backward(&mut self, from: usize) {
+        let len = self.plain_lines.len();
+        if let Some(idx) = (1..=len)
+            .map(|i| (from + len - i) % len)
+            .find(|&idx| !match_ranges(&self.plain_lines[idx], &self.query).is_empty())
+        {
+            self.top = idx;
+        }
+    }
+
+    fn render_line(&self, idx: usize) -> String {
+        let ranges = match_ranges(&self.plain_lines[idx], &self.query);
+        if ranges.is_empty() {
+            return self.lines[idx].clone();
+        }
+
+        // highlighted lines lose their original colors
+        let plain = &self.plain_lines[idx];
+        let mut result = String::new();
+        let mut pos = 0;
+        for (start, end) in ranges {
+            result.push_str(&plain[pos..start]);
+            result.push_str(&plain[start..end].reverse().to_string());
+            pos = end;
+        }
+        result.push_str(&plain[pos..]);
+        result
+    }
+
+    fn draw(&self, stdout: &mut impl Write) -> std::io::Result<()> {
+        let height = Self::page_height();
+        for row in 0..height {
+            queue!(stdout, MoveTo(0, row as u16), Clear(ClearType::CurrentLine))?;
+            if let Some(idx) = Some(self.top + row).filter(|&idx| idx < self.lines.len()) {
+                queue!(
+                    stdout,
+                    Print(self.render_line(idx)),
+                    SetAttribute(Attribute::Reset)
+                )?;
+            } else {
+                queue!(stdout, Print("~"))?;
+            }
+        }
+
+        let status = match self.mode {
+            Mode::Search => format!("/{}", self.query),
+            Mode::View => {
+                let last = (self.top + height).min(self.lines.len());
+                format!(
+                    "lines {}-{} of {} (q: quit, /: search, n/N: next/previous match)",
+                    self.top + 1,
+                    last,
+                    self.lines.len()
+                )
+            }
+        };
+        queue!(
+            stdout,
+            MoveTo(0, height as u16),
+            Clear(ClearType::CurrentLine),
+            Print(status.reverse())
+        )?;
+        stdout.flush()
+    }
+
+    fn event_loop(&mut self, stdout: &mut impl Write) -> std::io::Result<()> {
+        loop {
+            self.draw(stdout)?;
+
+            let Event::Key(KeyEvent {
+                code,
+                modifiers,
+                kind: KeyEventKind::Press,
+                ..
+            }) = read()?
+            else {
+                continue;
+            };
+
+            if modifiers.contains(KeyModifiers::CONTROL) && code == KeyCode::Char('c') {
+                return Ok(());
+            }
+
+            match self.mode {
+                Mode::Search => match code {
+                    KeyCode::Enter => self.mode = Mode::View,
+                    KeyCode::Esc => {
+                        self.query.clear();
+                        self.mode = Mode::View;
+                    }
+                    KeyCode::Backspace => {
+                        self.query.pop();
+                        self.find_forward(self.top);
+                    }
+                    KeyCode::Char(c) => {
+                        self.query.push(c);
+                        self.find_forward(self.top);
+                    }
+                    _ => {}
+                },
+                Mode::View => {
+                    let page = Self::page_height();
+                    match code {
+                        KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                        KeyCode::Char('j') | KeyCode::Down | KeyCode::Enter => self.scroll_down(1),
+                        KeyCode::Char('k') | KeyCode::Up => self.scroll_up(1),
+                        KeyCode::Char(' ') | KeyCode::Char('f') | KeyCode::PageDown => {
+                            self.scroll_down(page)
+                        }
+                        KeyCode::Char('b') | KeyCode::PageUp => self.scroll_up(page),
+                        KeyCode::Char('g') | KeyCode::Home => self.top = 0,
+                        KeyCode::Char('G') | KeyCode::End => self.top = self.max_top(),
+                        KeyCode::Char('/') => {
+                            self.query.clear();
+                            self.mode = Mode::Search;
+                        }
+                        KeyCode::Char('n') => self.find_forward(self.top + 1),
+                        KeyCode::Char('N') => self.find_backward(self.top),
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_pager_search() {
+        assert_eq!(strip_ansi("\x1b[34m0x100\x1b[0m - main"), "0x100 - main");
+        assert_eq!(match_ranges("Main, main", "MAIN"), vec![(0, 4), (6, 10)]);
+
+        let mut pager = Pager::new(vec![
+            "foo".to_string(),
+            "\x1b[33mbar\x1b[0m".to_string(),
+            "baz".to_string(),
+            "bar".to_string(),
+        ]);
+        pager.query = "bar".to_string();
+        pager.find_forward(0);
+        assert_eq!(pager.top, 1);
+        pager.find_forward(2);
+        assert_eq!(pager.top, 3);
+        pager.find_forward(0);
+        assert_eq!(pager.top, 1);
+        pager.find_backward(1);
+        assert_eq!(pager.top, 3);
+    }
+}
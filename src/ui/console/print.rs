@@ -114,4 +114,8 @@ pub mod style {
     view_struct!(AsmInstructionView, Color::DarkRed);
     view_struct!(AsmOperandsView, Color::DarkGreen);
     view_struct!(ErrorView, Color::DarkRed);
+    view_struct!(AddedView, Color::Green);
+    view_struct!(RemovedView, Color::Red);
+    view_struct!(ChangedView, Color::Yellow);
+    view_struct!(StatusView, Color::DarkGrey);
 }
@@ -0,0 +1,61 @@
+use crate::debugger::{CallTreeNode, Profile};
+use crate::ui::console::print::style::FunctionNameView;
+
+/// Call tree nodes with a smaller share of samples (in percents) are not shown.
+const MIN_TREE_NODE_PERCENT: f64 = 0.5;
+
+fn percent(samples: u64, total: u64) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    samples as f64 * 100.0 / total as f64
+}
+
+fn render_header(profile: &Profile) -> String {
+    format!(
+        "{} samples, interval {:?}",
+        profile.samples(),
+        profile.interval()
+    )
+}
+
+/// Render a flat profile, a line per function with self and total shares of samples.
+pub fn render_flat_profile(profile: &Profile) -> Vec<String> {
+    let total = profile.samples();
+    let mut lines = vec![
+        render_header(profile),
+        format!("{:>8} {:>8}  function", "self", "total"),
+    ];
+    lines.extend(profile.flat().into_iter().map(|entry| {
+        format!(
+            "{:>7.2}% {:>7.2}%  {}",
+            percent(entry.self_samples, total),
+            percent(entry.total_samples, total),
+            FunctionNameView::from(&entry.function),
+        )
+    }));
+    lines
+}
+
+fn render_tree_node(out: &mut Vec<String>, node: &CallTreeNode, total: u64, depth: usize) {
+    for child in &node.children {
+        let share = percent(child.samples, total);
+        if share < MIN_TREE_NODE_PERCENT {
+            continue;
+        }
+        out.push(format!(
+            "{share:>7.2}% {:indent$}{}",
+            "",
+            FunctionNameView::from(&child.function),
+            indent = depth * 2
+        ));
+        render_tree_node(out, child, total, depth + 1);
+    }
+}
+
+/// Render a call tree, each function is shown with a total share of samples.
+pub fn render_call_tree(profile: &Profile) -> Vec<String> {
+    let mut lines = vec![render_header(profile)];
+    render_tree_node(&mut lines, &profile.call_tree(), profile.samples(), 0);
+    lines
+}
@@ -0,0 +1,53 @@
+use crate::debugger::{ParsingProgress, ProgressCallback};
+use crate::ui::progress::{ProgressSummary, ProgressTracker};
+use crossterm::cursor::MoveToColumn;
+use crossterm::queue;
+use crossterm::terminal::{Clear, ClearType};
+use std::io::{IsTerminal, Write};
+use std::sync::Arc;
+
+const BAR_WIDTH: usize = 30;
+
+/// One-line progress bar of debug information parsing, printed into stderr.
+#[derive(Default)]
+pub struct ProgressBar {
+    tracker: ProgressTracker,
+}
+
+impl ProgressBar {
+    /// Return a progress callback that draws this progress bar,
+    /// or `None` if stderr is not a terminal.
+    pub fn callback(self: &Arc<Self>) -> Option<ProgressCallback> {
+        if !std::io::stderr().is_terminal() {
+            return None;
+        }
+        let bar = self.clone();
+        Some(Arc::new(move |progress: &ParsingProgress| {
+            if let Some(summary) = bar.tracker.update(progress) {
+                Self::draw(summary);
+            }
+        }))
+    }
+
+    fn draw(summary: ProgressSummary) {
+        let filled = (summary.ratio() * BAR_WIDTH as f64) as usize;
+        let mut stderr = std::io::stderr().lock();
+        _ = queue!(stderr, MoveToColumn(0), Clear(ClearType::CurrentLine));
+        _ = write!(
+            stderr,
+            "Loading debug information [{}{}] {summary}",
+            "=".repeat(filled),
+            " ".repeat(BAR_WIDTH - filled),
+        );
+        _ = stderr.flush();
+    }
+
+    /// Erase progress bar if it was drawn.
+    pub fn finish(&self) {
+        if self.tracker.is_shown() {
+            let mut stderr = std::io::stderr().lock();
+            _ = queue!(stderr, MoveToColumn(0), Clear(ClearType::CurrentLine));
+            _ = stderr.flush();
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use crate::debugger::AddressInfo;
+use crate::ui::console::print::style::{FilePathView, FunctionNameView};
+
+/// Render result of an address resolution: a module, a function that contains the address
+/// and functions inlined at the address (the innermost first) with source places.
+///
+/// # Arguments
+///
+/// * `header`: address representation, printed at the first line
+/// * `info`: address resolution result
+pub fn render_address_info(header: String, info: AddressInfo) -> Vec<String> {
+    let Some(module) = info.module else {
+        return vec![format!(
+            "{header}: address is not mapped to a known object file"
+        )];
+    };
+    let symbol = info
+        .symbol
+        .map(|(name, offset)| format!(" <{name}+{offset:#x}>"))
+        .unwrap_or_default();
+    let mut lines = vec![format!(
+        "{header}{symbol} in {}",
+        FilePathView::from(module.to_string_lossy())
+    )];
+
+    let last = info.frames.len().saturating_sub(1);
+    for (i, frame) in info.frames.into_iter().enumerate() {
+        let function = frame.function.as_deref().unwrap_or("???");
+        let place = frame
+            .place
+            .map(|(file, line, column)| {
+                let place = format!("{}:{line}:{column}", file.to_string_lossy());
+                format!(" at {}", FilePathView::from(place))
+            })
+            .unwrap_or_default();
+        let inlined = if i < last { " (inlined)" } else { "" };
+        lines.push(format!(
+            "    {}{place}{inlined}",
+            FunctionNameView::from(function)
+        ));
+    }
+    lines
+}
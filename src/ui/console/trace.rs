@@ -0,0 +1,82 @@
+use crate::debugger::variable::render::render_variable_ir;
+use crate::debugger::variable::render::RenderLimits;
+use crate::debugger::Tracepoint;
+use crate::ui::console::print::style::KeywordView;
+use chrono::{DateTime, Local};
+use indexmap::IndexMap;
+use std::fmt::Write;
+use std::time::SystemTime;
+
+/// Maximum number of distinct values shown for a single expression.
+const MAX_VALUES_IN_REPORT: usize = 10;
+
+fn format_time(time: SystemTime) -> String {
+    DateTime::<Local>::from(time)
+        .format("%H:%M:%S%.3f")
+        .to_string()
+}
+
+/// Render tracepoint report: hit counts, hits per thread and captured values of expressions.
+pub fn render_tracepoint_report(tp: &Tracepoint) -> String {
+    let mut out = String::new();
+    let kind = if tp.format.is_some() {
+        "Dprintf"
+    } else {
+        "Tracepoint"
+    };
+    _ = writeln!(
+        out,
+        "{kind} {} at {}, hits: {}",
+        tp.number, tp.place, tp.hit_count
+    );
+
+    let (Some(first), Some(last)) = (tp.hits.front(), tp.hits.back()) else {
+        return out;
+    };
+    if tp.hits.len() as u64 != tp.hit_count {
+        _ = writeln!(out, "  last {} hits are stored", tp.hits.len());
+    }
+    _ = writeln!(
+        out,
+        "  first hit: {}, last hit: {}",
+        format_time(first.time),
+        format_time(last.time)
+    );
+
+    let mut per_thread = IndexMap::<_, u64>::new();
+    tp.hits
+        .iter()
+        .for_each(|hit| *per_thread.entry(hit.tid).or_default() += 1);
+    for (tid, count) in per_thread {
+        _ = writeln!(out, "  thread {tid}: {count} hits");
+    }
+
+    for (idx, expr) in tp.expressions.iter().enumerate() {
+        let mut values = IndexMap::<_, u64>::new();
+        tp.hits.iter().for_each(|hit| {
+            let value = hit
+                .values
+                .get(idx)
+                .and_then(|v| v.as_ref())
+                .map(|v| render_variable_ir(v, 0, &RenderLimits::default()))
+                .unwrap_or_else(|| "<unavailable>".to_string());
+            *values.entry(value).or_default() += 1;
+        });
+        values.sort_by(|_, c1, _, c2| c2.cmp(c1));
+
+        _ = writeln!(
+            out,
+            "  {} ({} distinct values):",
+            KeywordView::from(expr),
+            values.len()
+        );
+        for (value, count) in values.iter().take(MAX_VALUES_IN_REPORT) {
+            _ = writeln!(out, "    {value}: {count} hits");
+        }
+        if values.len() > MAX_VALUES_IN_REPORT {
+            _ = writeln!(out, "    ...");
+        }
+    }
+
+    out
+}
@@ -1,16 +1,16 @@
-use crate::debugger::address::RelocatedAddress;
-use crate::debugger::variable::render::{RenderRepr, ValueLayout};
+use crate::debugger::variable::diff::{diff, Change};
+use crate::debugger::variable::render::{render_variable_ir, RenderLimits, RenderRepr};
 use crate::debugger::variable::VariableIR;
+use crate::debugger::FormatString;
+use crate::ui::console::print::style::{AddedView, ChangedView, RemovedView, UNKNOWN_PLACEHOLDER};
 use crate::ui::syntax;
 use crate::ui::syntax::StylizedLine;
 use syntect::util::as_24_bit_terminal_escaped;
 
-const TAB: &str = "\t";
-
-pub fn render_variable(var: &VariableIR) -> anyhow::Result<String> {
+pub fn render_variable(var: &VariableIR, limits: &RenderLimits) -> anyhow::Result<String> {
     let syntax_renderer = syntax::rust_syntax_renderer();
     let mut line_renderer = syntax_renderer.line_renderer();
-    let var_as_string = format!("{} = {}", var.name(), render_variable_ir(var, 0));
+    let var_as_string = format!("{} = {}", var.name(), render_variable_ir(var, 0, limits));
     Ok(var_as_string
         .lines()
         .map(|l| -> anyhow::Result<String> {
@@ -27,76 +27,81 @@ pub fn render_variable(var: &VariableIR) -> anyhow::Result<String> {
         .join("\n"))
 }
 
-pub fn render_variable_ir(view: &VariableIR, depth: usize) -> String {
-    match view.value() {
-        Some(value) => match value {
-            ValueLayout::PreRendered(rendered_value) => match view {
-                VariableIR::CEnum(_) => format!("{}::{}", view.r#type(), rendered_value),
-                _ => format!("{}({})", view.r#type(), rendered_value),
-            },
-            ValueLayout::Referential { addr } => {
-                format!(
-                    "{} [{}]",
-                    view.r#type(),
-                    RelocatedAddress::from(addr as usize)
-                )
-            }
-            ValueLayout::Wrapped(val) => {
-                format!("{}::{}", view.r#type(), render_variable_ir(val, depth))
-            }
-            ValueLayout::Structure { members } => {
-                let mut render = format!("{} {{", view.r#type());
-
-                let tabs = TAB.repeat(depth + 1);
-
-                for v in members {
-                    render = format!("{render}\n");
-                    render = format!(
-                        "{render}{tabs}{}: {}",
-                        v.name(),
-                        render_variable_ir(v, depth + 1)
-                    );
-                }
-
-                format!("{render}\n{}}}", TAB.repeat(depth))
-            }
-            ValueLayout::Map(kv_children) => {
-                let mut render = format!("{} {{", view.r#type());
-
-                let tabs = TAB.repeat(depth + 1);
-
-                for kv in kv_children {
-                    render = format!("{render}\n");
-                    render = format!(
-                        "{render}{tabs}{}: {}",
-                        render_variable_ir(&kv.0, depth + 1),
-                        render_variable_ir(&kv.1, depth + 1)
-                    );
-                }
-
-                format!("{render}\n{}}}", TAB.repeat(depth))
-            }
-            ValueLayout::List { members, indexed } => {
-                let mut render = format!("{} {{", view.r#type());
-
-                let tabs = TAB.repeat(depth + 1);
+/// Render a dprintf message, values that can't be evaluated are rendered as a placeholder.
+pub fn render_dprintf_message(format: &FormatString, values: &[Option<VariableIR>]) -> String {
+    format.format(values.iter().map(|value| {
+        value
+            .as_ref()
+            .map(|v| render_variable_ir(v, 0, &RenderLimits::default()))
+            .unwrap_or_else(|| UNKNOWN_PLACEHOLDER.to_string())
+    }))
+}
 
-                for v in members {
-                    render = format!("{render}\n");
-                    if indexed {
-                        render = format!(
-                            "{render}{tabs}{}: {}",
-                            v.name(),
-                            render_variable_ir(v, depth + 1)
-                        );
-                    } else {
-                        render = format!("{render}{tabs}{}", render_variable_ir(v, depth + 1));
-                    }
-                }
+/// Render variable value as a single line, values longer than `max_len` characters are truncated.
+pub fn render_variable_inline(var: &VariableIR, max_len: usize) -> String {
+    let rendered = render_variable_ir(var, 0, &RenderLimits::default())
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if rendered.chars().count() <= max_len {
+        return rendered;
+    }
+    let truncated: String = rendered.chars().take(max_len).collect();
+    format!("{truncated}...")
+}
 
-                format!("{render}\n{}}}", TAB.repeat(depth))
+/// Render changes between a captured (`old`) and a current (`new`) value of a variable,
+/// a line per changed, added or removed value.
+pub fn render_variable_diff(
+    old: Option<&VariableIR>,
+    new: Option<&VariableIR>,
+    limits: &RenderLimits,
+) -> Vec<String> {
+    let render = |var| render_variable_ir(var, 0, limits);
+    match (old, new) {
+        (Some(old), Some(new)) => {
+            let name = new.name();
+            let changes = diff(old, new);
+            if changes.is_empty() {
+                return vec![format!("{name}: no changes")];
             }
-        },
-        None => format!("{}(unknown)", view.r#type()),
+            changes
+                .into_iter()
+                .map(|change| match change {
+                    Change::Changed { path, old, new } => format!(
+                        "{} {}: {} -> {}",
+                        ChangedView::from("~"),
+                        ChangedView::from(format!("{name}{path}")),
+                        render(old),
+                        render(new)
+                    ),
+                    Change::Added { path, new } => format!(
+                        "{} {}: {}",
+                        AddedView::from("+"),
+                        AddedView::from(format!("{name}{path}")),
+                        render(new)
+                    ),
+                    Change::Removed { path, old } => format!(
+                        "{} {}: {}",
+                        RemovedView::from("-"),
+                        RemovedView::from(format!("{name}{path}")),
+                        render(old)
+                    ),
+                })
+                .collect()
+        }
+        (None, Some(new)) => vec![format!(
+            "{} {}: {}",
+            AddedView::from("+"),
+            AddedView::from(new.name()),
+            render(new)
+        )],
+        (Some(old), None) => vec![format!(
+            "{} {}: {}",
+            RemovedView::from("-"),
+            RemovedView::from(old.name()),
+            render(old)
+        )],
+        (None, None) => vec![],
     }
 }
@@ -1,9 +1,21 @@
 pub mod command;
+#[cfg(feature = "console")]
+mod completer;
 pub mod config;
+#[cfg(feature = "console")]
 pub mod console;
+#[cfg(feature = "console")]
+mod progress;
+pub mod proto;
 pub mod short;
+#[cfg(feature = "console")]
+mod source;
+pub mod status;
+#[cfg(feature = "console")]
 pub mod supervisor;
+#[cfg(feature = "console")]
 mod syntax;
+#[cfg(feature = "tui")]
 pub mod tui;
 
 use os_pipe::PipeReader;
@@ -0,0 +1,127 @@
+use crate::debugger::ParsingProgress;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Progress is not shown if debug information parsed faster than this.
+const SHOW_DELAY: Duration = Duration::from_millis(300);
+/// Minimal interval between progress redraws.
+const REDRAW_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Summary of debug information parsing progress over all object files.
+#[derive(Clone, Copy, Default)]
+pub struct ProgressSummary {
+    pub units_parsed: usize,
+    pub units_total: usize,
+    pub bytes_parsed: u64,
+    pub bytes_total: u64,
+}
+
+impl ProgressSummary {
+    /// Return a ratio of parsed bytes, in range [0;1].
+    pub fn ratio(&self) -> f64 {
+        if self.bytes_total == 0 {
+            return 1.0;
+        }
+        (self.bytes_parsed as f64 / self.bytes_total as f64).clamp(0.0, 1.0)
+    }
+}
+
+fn human_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} {}", UNITS[0])
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
+
+impl Display for ProgressSummary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:>3}% ({}/{} units, {}/{})",
+            (self.ratio() * 100.0) as u32,
+            self.units_parsed,
+            self.units_total,
+            human_size(self.bytes_parsed),
+            human_size(self.bytes_total),
+        )
+    }
+}
+
+struct State {
+    files: HashMap<PathBuf, ProgressSummary>,
+    last_redraw: Option<Instant>,
+}
+
+/// Collects parsing progress of object files (that are parsed in parallel)
+/// and decides when progress must be redrawn.
+pub struct ProgressTracker {
+    start: Instant,
+    state: Mutex<State>,
+}
+
+impl Default for ProgressTracker {
+    fn default() -> Self {
+        Self {
+            start: Instant::now(),
+            state: Mutex::new(State {
+                files: HashMap::new(),
+                last_redraw: None,
+            }),
+        }
+    }
+}
+
+impl ProgressTracker {
+    /// Update progress of a single object file. Return a summary if progress must be redrawn.
+    pub fn update(&self, progress: &ParsingProgress) -> Option<ProgressSummary> {
+        let mut state = self.state.lock().unwrap();
+        state.files.insert(
+            progress.file.to_path_buf(),
+            ProgressSummary {
+                units_parsed: progress.units_parsed,
+                units_total: progress.units_total,
+                bytes_parsed: progress.bytes_parsed,
+                bytes_total: progress.bytes_total,
+            },
+        );
+
+        let now = Instant::now();
+        if now.duration_since(self.start) < SHOW_DELAY {
+            return None;
+        }
+        if let Some(last_redraw) = state.last_redraw {
+            if now.duration_since(last_redraw) < REDRAW_INTERVAL && !progress.is_done() {
+                return None;
+            }
+        }
+        state.last_redraw = Some(now);
+
+        Some(
+            state
+                .files
+                .values()
+                .fold(ProgressSummary::default(), |acc, file| ProgressSummary {
+                    units_parsed: acc.units_parsed + file.units_parsed,
+                    units_total: acc.units_total + file.units_total,
+                    bytes_parsed: acc.bytes_parsed + file.bytes_parsed,
+                    bytes_total: acc.bytes_total + file.bytes_total,
+                }),
+        )
+    }
+
+    /// Return true if progress was drawn at least once.
+    pub fn is_shown(&self) -> bool {
+        self.state.lock().unwrap().last_redraw.is_some()
+    }
+}
@@ -1,4 +1,13 @@
-use crate::debugger::Debugger;
+//! Protocol between a debugger and a frontend.
+//!
+//! Debugee state changes are published by debugger hooks into an event stream
+//! (see [`event_stream`]), both console and tui track them with the same event types.
+//!
+//! Debugger is a tracer, so it stays in the thread that attached to a debugee and serves
+//! frontend requests with a [`ServerExchanger::serve`] loop. Console and tui run in their own
+//! threads and send requests to the debugger with a [`ClientExchanger`].
+use crate::debugger::Pid;
+use crate::debugger::{Debugger, StopSummary};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{channel, Receiver, Sender};
 
@@ -12,6 +21,16 @@ pub enum Request {
     DebuggerAsyncTask(Box<DebuggerAsyncTask>),
 }
 
+/// Reason of a [`ServerExchanger::serve`] loop end.
+pub enum ServeExit {
+    /// Frontend requests a debugger exit.
+    Exit,
+    /// Frontend requests a switch to another frontend.
+    SwitchUi,
+    /// Frontend is gone (client exchanger dropped).
+    Shutdown,
+}
+
 pub struct ServerExchanger {
     requests: Receiver<Request>,
     responses: Sender<Box<dyn std::any::Any + Send + 'static>>,
@@ -30,6 +49,27 @@ impl ServerExchanger {
     pub fn send_async_response(&self, resp: anyhow::Error) {
         _ = self.async_responses.send(resp);
     }
+
+    /// Execute incoming requests on a debugger until a frontend asks
+    /// for exit or ui switch, or disconnects.
+    pub fn serve(&self, debugger: &mut Debugger) -> ServeExit {
+        loop {
+            match self.next_request() {
+                Some(Request::Exit) => return ServeExit::Exit,
+                Some(Request::SwitchUi) => return ServeExit::SwitchUi,
+                Some(Request::DebuggerSyncTask(task)) => {
+                    let result = task(debugger);
+                    self.send_response(result);
+                }
+                Some(Request::DebuggerAsyncTask(task)) => {
+                    if let Err(e) = task(debugger) {
+                        self.send_async_response(e);
+                    }
+                }
+                None => return ServeExit::Shutdown,
+            }
+        }
+    }
 }
 
 pub struct ClientExchanger {
@@ -116,13 +156,13 @@ impl ClientExchanger {
 }
 
 /// Create an exchanger pair.
-/// Tui use exchanger to communicate with debugger by message passing.
-/// Tui and debugger must be in separate threads,
+/// Frontend use exchanger to communicate with debugger by message passing.
+/// Frontend and debugger must be in separate threads,
 /// because debugger is a tracer and can't be moving between threads.
 ///
 /// [`ServerExchanger`] must be used at tracer (debugger) side and handle
 /// incoming requests.
-/// [`ClientExchanger`] must be used at tui side, send requests and receive responses.
+/// [`ClientExchanger`] must be used at frontend side, send requests and receive responses.
 pub fn exchanger() -> (ServerExchanger, ClientExchanger) {
     let (req_tx, req_rx) = channel::<Request>();
     let (resp_tx, resp_rx) = channel::<Box<dyn std::any::Any + Send + 'static>>();
@@ -141,3 +181,45 @@ pub fn exchanger() -> (ServerExchanger, ClientExchanger) {
         },
     )
 }
+
+/// Debugee state change, published by debugger hooks.
+#[derive(Debug, Clone)]
+pub enum DebuggerEvent {
+    /// Debugee process is installed (on start or restart).
+    ProcessInstall(Pid),
+    /// Debugee is stopped (by a breakpoint, a watchpoint, a step, a signal or an interrupt).
+    Stop(StopSummary),
+    /// Debugee exited with a code.
+    Exit(i32),
+}
+
+/// Publishing side of an event stream, owned by debugger hooks.
+#[derive(Clone)]
+pub struct EventPublisher {
+    events: Sender<DebuggerEvent>,
+}
+
+impl EventPublisher {
+    pub fn publish(&self, event: DebuggerEvent) {
+        _ = self.events.send(event);
+    }
+}
+
+/// Receiving side of an event stream, owned by a frontend.
+pub struct EventStream {
+    events: Receiver<DebuggerEvent>,
+}
+
+impl EventStream {
+    /// Return all events published since the previous call, doesn't block.
+    pub fn drain(&self) -> impl Iterator<Item = DebuggerEvent> + '_ {
+        self.events.try_iter()
+    }
+}
+
+/// Create an event stream. [`EventPublisher`] is given to debugger hooks,
+/// [`EventStream`] is polled by a frontend.
+pub fn event_stream() -> (EventPublisher, EventStream) {
+    let (tx, rx) = channel();
+    (EventPublisher { events: tx }, EventStream { events: rx })
+}
@@ -0,0 +1,166 @@
+//! Source code of debugee files. A file is read from a path found in debug information,
+//! if the file doesn't exist, then its source code is taken from debug information
+//! (embedded sources) or from source archives (see `--source-archive` option).
+
+use crate::ui::config;
+use crate::weak_error;
+use flate2::read::GzDecoder;
+use std::fs;
+use std::io::{self, ErrorKind, Read};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+
+/// Minimal number of trailing path components that must be equal
+/// for a file path and a path of an archive entry.
+const MIN_MATCHED_COMPONENTS: usize = 2;
+
+/// A `.tar` or `.tar.gz` archive with source files.
+struct SourceArchive {
+    path: PathBuf,
+    /// Paths of archive entries, read at first lookup.
+    entries: OnceLock<Vec<PathBuf>>,
+}
+
+impl SourceArchive {
+    fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            entries: OnceLock::new(),
+        }
+    }
+
+    fn open(&self) -> io::Result<tar::Archive<Box<dyn Read>>> {
+        let file = fs::File::open(&self.path)?;
+        let name = self.path.to_string_lossy();
+        let reader: Box<dyn Read> = if name.ends_with(".gz") || name.ends_with(".tgz") {
+            Box::new(GzDecoder::new(file))
+        } else {
+            Box::new(file)
+        };
+        Ok(tar::Archive::new(reader))
+    }
+
+    fn entries(&self) -> &[PathBuf] {
+        self.entries.get_or_init(|| {
+            let read_entries = || -> io::Result<Vec<PathBuf>> {
+                self.open()?
+                    .entries()?
+                    .map(|entry| Ok(entry?.path()?.into_owned()))
+                    .collect()
+            };
+            weak_error!(read_entries(), "read source archive:").unwrap_or_default()
+        })
+    }
+
+    /// Return an archive entry which path has the longest common suffix with a file path.
+    fn find_entry(&self, file: &Path) -> Option<&Path> {
+        self.entries()
+            .iter()
+            .map(|entry| (entry, common_suffix_len(entry, file)))
+            .filter(|&(_, len)| len >= MIN_MATCHED_COMPONENTS)
+            .max_by_key(|&(_, len)| len)
+            .map(|(entry, _)| entry.as_path())
+    }
+
+    fn read(&self, entry_path: &Path) -> io::Result<String> {
+        for entry in self.open()?.entries()? {
+            let mut entry = entry?;
+            if entry.path()? == entry_path {
+                let mut source = String::new();
+                entry.read_to_string(&mut source)?;
+                return Ok(source);
+            }
+        }
+        Err(io::Error::from(ErrorKind::NotFound))
+    }
+}
+
+/// Return a number of equal trailing components of two paths.
+fn common_suffix_len(a: &Path, b: &Path) -> usize {
+    a.components()
+        .rev()
+        .zip(b.components().rev())
+        .take_while(|(a, b)| a == b)
+        .count()
+}
+
+fn archives() -> &'static [SourceArchive] {
+    static ARCHIVES: OnceLock<Vec<SourceArchive>> = OnceLock::new();
+    ARCHIVES.get_or_init(|| {
+        config::current()
+            .source_archives
+            .iter()
+            .cloned()
+            .map(SourceArchive::new)
+            .collect()
+    })
+}
+
+/// Read a source file. If a file doesn't exist, then an embedded source code is returned,
+/// otherwise the file is searched in source archives by the longest matching path suffix.
+///
+/// # Arguments
+///
+/// * `file`: path from debug information
+/// * `embedded`: source code embedded into debug information
+pub fn read_source(file: &Path, embedded: Option<Arc<str>>) -> io::Result<String> {
+    let not_found = match fs::read_to_string(file) {
+        Err(e) if e.kind() == ErrorKind::NotFound => e,
+        result => return result,
+    };
+
+    if let Some(source) = embedded {
+        return Ok(source.to_string());
+    }
+    for archive in archives() {
+        if let Some(entry) = archive.find_entry(file) {
+            return archive.read(entry);
+        }
+    }
+
+    Err(io::Error::new(
+        ErrorKind::NotFound,
+        format!(
+            "{}: {not_found}, use --sysroot or --source-archive options to set a location of sources",
+            file.display()
+        ),
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_source_archive_lookup() {
+        let path = std::env::temp_dir().join(format!("bs-src-{}.tar", std::process::id()));
+        {
+            let mut builder = tar::Builder::new(fs::File::create(&path).unwrap());
+            for (name, data) in [
+                ("rust-src/library/core/src/fmt/mod.rs", "// core fmt"),
+                ("rust-src/library/alloc/src/fmt.rs", "// alloc fmt"),
+                ("rust-src/library/std/src/lib.rs", "// std"),
+            ] {
+                let mut header = tar::Header::new_gnu();
+                header.set_size(data.len() as u64);
+                header.set_cksum();
+                builder
+                    .append_data(&mut header, name, data.as_bytes())
+                    .unwrap();
+            }
+            builder.finish().unwrap();
+        }
+
+        let archive = SourceArchive::new(path.clone());
+        let entry = archive
+            .find_entry(Path::new("/rustc/90b35a/library/core/src/fmt/mod.rs"))
+            .unwrap();
+        assert_eq!(entry, Path::new("rust-src/library/core/src/fmt/mod.rs"));
+        assert_eq!(archive.read(entry).unwrap(), "// core fmt");
+
+        // a file name only isn't enough for a match
+        assert!(archive.find_entry(Path::new("/home/user/lib.rs")).is_none());
+
+        fs::remove_file(path).unwrap();
+    }
+}
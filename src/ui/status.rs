@@ -0,0 +1,203 @@
+//! Debugee status shown by frontends as a one-line status bar: process id, run state,
+//! focused thread, current function and source location.
+
+use crate::debugger::Pid;
+use crate::debugger::{Debugger, PlaceDescriptorOwned, StopKind};
+use crate::ui::proto::DebuggerEvent;
+use std::fmt::{Display, Formatter};
+
+/// Debugee process state.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ProcessState {
+    #[default]
+    NotRunning,
+    Running,
+    /// Process is stopped, stop reason is unknown if process is stopped
+    /// before a frontend is started.
+    Stopped(Option<StopKind>),
+    Exited(i32),
+}
+
+impl Display for ProcessState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProcessState::NotRunning => f.write_str("not running"),
+            ProcessState::Running => f.write_str("running"),
+            ProcessState::Stopped(None) => f.write_str("stopped"),
+            ProcessState::Stopped(Some(kind)) => match kind {
+                StopKind::Breakpoint { number, .. } => write!(f, "stopped at breakpoint {number}"),
+                StopKind::Watchpoint {
+                    number,
+                    end_of_scope: false,
+                } => write!(f, "stopped at watchpoint {number}"),
+                StopKind::Watchpoint {
+                    number,
+                    end_of_scope: true,
+                } => write!(f, "watchpoint {number} out of scope"),
+                StopKind::Step => f.write_str("step done"),
+                StopKind::Signal(signal) => write!(f, "stopped by {signal}"),
+                StopKind::Interrupt => f.write_str("interrupted"),
+                StopKind::EntryPoint => f.write_str("stopped at entry point"),
+            },
+            ProcessState::Exited(code) => write!(f, "exited with code {code}"),
+        }
+    }
+}
+
+/// Context of a debugger prompt, updated from debugee events.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DebugeeStatus {
+    pid: Option<Pid>,
+    state: ProcessState,
+    /// Focused thread number and id.
+    thread: Option<(u32, Pid)>,
+    function: Option<String>,
+    /// File name and line number.
+    place: Option<(String, u64)>,
+}
+
+impl DebugeeStatus {
+    pub fn state(&self) -> ProcessState {
+        self.state
+    }
+
+    /// Update status with a debugee event.
+    pub fn apply(&mut self, event: &DebuggerEvent) {
+        match event {
+            // process is installed (created or restarted) but not started yet, a frontend
+            // marks it as running when it resumes a debugee (possibly before this event)
+            DebuggerEvent::ProcessInstall(pid) => {
+                let state = match self.state {
+                    ProcessState::Running => ProcessState::Running,
+                    _ => ProcessState::NotRunning,
+                };
+                *self = Self {
+                    pid: Some(*pid),
+                    state,
+                    ..Self::default()
+                }
+            }
+            DebuggerEvent::Stop(summary) => {
+                self.state = ProcessState::Stopped(Some(summary.kind));
+                self.thread = Some((summary.thread_num, summary.tid));
+                self.function = summary.function.clone();
+                self.place = summary.place.as_ref().map(place_view);
+            }
+            DebuggerEvent::Exit(code) => {
+                self.state = ProcessState::Exited(*code);
+                self.thread = None;
+                self.function = None;
+                self.place = None;
+            }
+        }
+    }
+
+    /// Mark a debugee as running, used by frontends that resume a debugee asynchronously.
+    pub fn set_running(&mut self) {
+        self.state = ProcessState::Running;
+        self.function = None;
+        self.place = None;
+    }
+
+    /// Read a process id and a focused thread location from the debugger. Used when a frontend
+    /// starts with an already running debugee or when focus changes without a stop.
+    pub fn refresh(&mut self, debugger: &Debugger) {
+        if !debugger.is_in_progress() {
+            return;
+        }
+        self.pid = Some(debugger.process().pid());
+        if !matches!(self.state, ProcessState::Stopped(_)) {
+            self.state = ProcessState::Stopped(None);
+        }
+
+        let Ok(threads) = debugger.thread_state() else {
+            return;
+        };
+        if let Some(snapshot) = threads.into_iter().find(|snapshot| snapshot.in_focus) {
+            self.thread = Some((snapshot.thread.number, snapshot.thread.pid));
+            self.function = snapshot
+                .bt
+                .as_ref()
+                .and_then(|bt| bt.first())
+                .and_then(|frame| frame.func_name.clone());
+            self.place = snapshot.place.as_ref().map(place_view);
+        }
+    }
+}
+
+fn place_view(place: &PlaceDescriptorOwned) -> (String, u64) {
+    let file = place.file.file_name().unwrap_or(place.file.as_os_str());
+    (file.to_string_lossy().to_string(), place.line_number)
+}
+
+impl Display for DebugeeStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Some(pid) = self.pid else {
+            return write!(f, "{}", self.state);
+        };
+        write!(f, "pid {pid} | {}", self.state)?;
+        if let Some((num, tid)) = self.thread {
+            write!(f, " | thread #{num} ({tid})")?;
+        }
+        if let Some(ref function) = self.function {
+            write!(f, " | {function}")?;
+        }
+        if let Some((ref file, line)) = self.place {
+            write!(f, " | {file}:{line}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::debugger::address::GlobalAddress;
+    use crate::debugger::StopSummary;
+    use std::time::SystemTime;
+
+    #[test]
+    fn test_status_from_events() {
+        let mut status = DebugeeStatus::default();
+        assert_eq!(status.to_string(), "not running");
+
+        status.apply(&DebuggerEvent::ProcessInstall(Pid::from_raw(100)));
+        assert_eq!(status.to_string(), "pid 100 | not running");
+        status.set_running();
+        assert_eq!(status.to_string(), "pid 100 | running");
+
+        status.apply(&DebuggerEvent::Stop(StopSummary {
+            time: SystemTime::now(),
+            tid: Pid::from_raw(101),
+            thread_num: 2,
+            pc: None,
+            kind: StopKind::Breakpoint { number: 1, hits: 1 },
+            function: Some("calc::sum".to_string()),
+            place: Some(PlaceDescriptorOwned {
+                file: "/src/calc.rs".into(),
+                address: GlobalAddress::from(0x1000_usize),
+                line_number: 12,
+                pos_in_unit: 0,
+                is_stmt: true,
+                column_number: 0,
+                epilog_begin: false,
+                prolog_end: false,
+            }),
+        }));
+        assert_eq!(
+            status.to_string(),
+            "pid 100 | stopped at breakpoint 1 | thread #2 (101) | calc::sum | calc.rs:12"
+        );
+
+        status.set_running();
+        assert_eq!(status.to_string(), "pid 100 | running | thread #2 (101)");
+
+        status.apply(&DebuggerEvent::Exit(3));
+        assert_eq!(status.to_string(), "pid 100 | exited with code 3");
+
+        // restart requested by a frontend before a new process is installed
+        status.set_running();
+        status.apply(&DebuggerEvent::ProcessInstall(Pid::from_raw(200)));
+        assert_eq!(status.to_string(), "pid 200 | running");
+    }
+}
@@ -2,16 +2,23 @@ use std::path::Path;
 
 use crate::debugger::process::Child;
 use crate::debugger::DebuggerBuilder;
+use crate::debugger::Pid;
 use crate::oracle::builtin;
+use crate::ui::console;
 use crate::ui::console::TerminalApplication;
+#[cfg(feature = "tui")]
+use crate::ui::tui;
+#[cfg(feature = "tui")]
 use crate::ui::tui::TuiApplication;
-use crate::ui::{console, tui};
 use anyhow::Context;
 use log::{info, warn};
-use nix::unistd::Pid;
+use nix::sys::termios;
+use nix::sys::termios::{SetArg, Termios};
+use std::io;
 
 /// Interface type.
 pub enum Interface {
+    #[cfg(feature = "tui")]
     TUI,
     Default,
 }
@@ -24,26 +31,50 @@ pub enum DebugeeSource<'a> {
     Process { pid: i32 },
 }
 
-/// Possible applications.
-pub enum Application {
-    TUI(TuiApplication),
-    Terminal(TerminalApplication),
+/// Debugger frontend (user interface). A frontend owns a debugger and serves user requests
+/// until user exits, or until user requests a switch to another frontend.
+pub trait Frontend {
+    /// Run frontend, return when frontend finished its work.
+    fn run(self: Box<Self>) -> anyhow::Result<ControlFlow>;
 }
 
-impl Application {
-    pub fn run(self) -> anyhow::Result<ControlFlow> {
-        match self {
-            Application::TUI(tui_app) => tui_app.run(),
-            Application::Terminal(term_app) => term_app.run(),
-        }
+impl Frontend for TerminalApplication {
+    fn run(self: Box<Self>) -> anyhow::Result<ControlFlow> {
+        TerminalApplication::run(*self)
+    }
+}
+
+#[cfg(feature = "tui")]
+impl Frontend for TuiApplication {
+    fn run(self: Box<Self>) -> anyhow::Result<ControlFlow> {
+        TuiApplication::run(*self)
     }
 }
 
-/// Result of application execution. Application may request exit at the end of execution, or may
-/// request a switch to another application.
+/// Result of frontend execution. Frontend may request exit at the end of execution, or may
+/// request a switch to another frontend.
 pub enum ControlFlow {
     Exit,
-    Switch(Application),
+    Switch(Box<dyn Frontend>),
+}
+
+/// Terminal attributes captured at debugger start and restored when debugger exits,
+/// so a killed debugee can't leave a terminal in a raw or no-echo mode.
+struct TerminalGuard(Option<Termios>);
+
+impl TerminalGuard {
+    fn capture() -> Self {
+        // stdin may be not a terminal (redirected from file or pipe)
+        Self(termios::tcgetattr(io::stdin()).ok())
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        if let Some(attrs) = self.0.as_ref() {
+            _ = termios::tcsetattr(io::stdin(), SetArg::TCSANOW, attrs);
+        }
+    }
 }
 
 /// Supervisor control application execution process.
@@ -59,6 +90,9 @@ impl Supervisor {
     /// * `ui`: determines what application will be created
     /// * `oracles`: list of oracle names
     pub fn run(src: DebugeeSource, ui: Interface, oracles: &[String]) -> anyhow::Result<()> {
+        // dropped last, after the application (and the debugee) is dropped
+        let _terminal_guard = TerminalGuard::capture();
+
         let (stdout_reader, stdout_writer) = os_pipe::pipe().unwrap();
         let (stderr_reader, stderr_writer) = os_pipe::pipe().unwrap();
 
@@ -93,13 +127,14 @@ impl Supervisor {
             })
             .collect();
 
-        let mut app = match ui {
+        let mut app: Box<dyn Frontend> = match ui {
+            #[cfg(feature = "tui")]
             Interface::TUI => {
                 let app_builder = tui::AppBuilder::new(stdout_reader.into(), stderr_reader.into());
                 let app = app_builder
                     .build(DebuggerBuilder::new().with_oracles(oracles), process)
                     .context("Build debugger")?;
-                Application::TUI(app)
+                Box::new(app)
             }
             Interface::Default => {
                 let app_builder =
@@ -107,7 +142,7 @@ impl Supervisor {
                 let app = app_builder
                     .build(DebuggerBuilder::new().with_oracles(oracles), process)
                     .context("Build debugger")?;
-                Application::Terminal(app)
+                Box::new(app)
             }
         };
 
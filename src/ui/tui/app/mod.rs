@@ -1,18 +1,26 @@
 pub mod port;
 
-use crate::debugger::Error;
+use crate::debugger::variable::select::{VariableSelector, DQE};
+use crate::debugger::{Debugger, Error};
 use crate::ui::command;
 use crate::ui::command::r#break::BreakpointIdentity;
 use crate::ui::command::watch::WatchpointIdentity;
-use crate::ui::command::{r#break, run, watch, CommandError};
+use crate::ui::command::{r#break, run, watch, Command, CommandError};
+use crate::ui::completer::CommandCompleter;
+use crate::ui::proto::{ClientExchanger, EventStream};
+use crate::ui::status::DebugeeStatus;
 use crate::ui::tui::app::port::{
-    AsyncResponsesPort, DebuggerEventQueue, DebuggerEventsPort, LoggerPort, OutputPort, UserEvent,
+    AsyncResponsesPort, DebuggerEventQueue, DebuggerEventsPort, EventStreamPort, InputPort,
+    LoggerPort, OutputPort, UserEvent,
 };
 use crate::ui::tui::components::asm::Asm;
 use crate::ui::tui::components::breakpoint::Breakpoints;
 use crate::ui::tui::components::control::GlobalControl;
+use crate::ui::tui::components::history::History;
 use crate::ui::tui::components::input::{Input, InputStringType};
 use crate::ui::tui::components::logs::Logs;
+use crate::ui::tui::components::memory;
+use crate::ui::tui::components::memory::Memory;
 use crate::ui::tui::components::oracle::make_oracle_tab_window;
 use crate::ui::tui::components::output::Output;
 use crate::ui::tui::components::popup::{Popup, YesNoLabels};
@@ -20,28 +28,33 @@ use crate::ui::tui::components::source::Source;
 use crate::ui::tui::components::status::Status;
 use crate::ui::tui::components::threads::Threads;
 use crate::ui::tui::components::variables::Variables;
-use crate::ui::tui::proto::ClientExchanger;
+use crate::ui::tui::config::LayoutConfig;
 use crate::ui::tui::utils::logger::TuiLogLine;
 use crate::ui::tui::utils::tab;
 use crate::ui::tui::utils::tab::TabWindow;
 use anyhow::anyhow;
 use chumsky::Parser;
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
 use log::warn;
 use std::borrow::Cow;
 use std::str::FromStr;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
-use tuirealm::props::{PropPayload, PropValue, TextSpan};
+use tuirealm::props::{PropPayload, PropValue};
 use tuirealm::terminal::TerminalBridge;
 use tuirealm::tui::layout::Alignment;
-use tuirealm::tui::layout::{Constraint, Direction, Layout};
-use tuirealm::tui::style::Color;
+use tuirealm::tui::layout::{Constraint, Direction, Layout, Rect};
 use tuirealm::{
     props, Application, AttrValue, Attribute, EventListenerCfg, Sub, SubClause, SubEventClause,
 };
 
 use super::{BreakpointsAddType, ConfirmedAction, DebugeeStreamBuffer, Id, Msg};
 
+/// Index of a source code window in the right tabs.
+const RIGHT_TAB_SOURCE: isize = 0;
+/// Index of a disassembly window in the right tabs.
+const RIGHT_TAB_ASM: isize = 2;
+
 pub struct Model {
     /// Application
     pub app: Application<Id, Msg, UserEvent>,
@@ -55,32 +68,78 @@ pub struct Model {
     exchanger: Arc<ClientExchanger>,
     /// Layout of main tabs
     tabs_layout: [Constraint; 2],
+    /// Address of a byte in the memory window that is edited right now
+    memory_write_addr: Option<usize>,
+    /// Persisted proportions of windows
+    layout: LayoutConfig,
+    /// Areas of the left and right tabs at the last render
+    tabs_areas: [Rect; 2],
+    /// True if the user drags a border between left and right tabs
+    resize_in_progress: bool,
+    /// Command completer of the command bar
+    completer: Arc<Mutex<CommandCompleter>>,
+    /// Window that was in focus before the command bar is opened
+    focus_before_command_bar: Option<Id>,
 }
 
 impl Model {
-    const DEFAULT_TABS_LAYOUT: [Constraint; 2] =
-        [Constraint::Percentage(25), Constraint::Percentage(75)];
     const LEFT_TAB_FOCUS_LAYOUT: [Constraint; 2] =
         [Constraint::Percentage(90), Constraint::Percentage(10)];
     const RIGHT_TAB_FOCUS_LAYOUT: [Constraint; 2] =
         [Constraint::Percentage(10), Constraint::Percentage(90)];
 
+    fn default_tabs_layout(layout: &LayoutConfig) -> [Constraint; 2] {
+        [
+            Constraint::Percentage(layout.left_window_width),
+            Constraint::Percentage(100 - layout.left_window_width),
+        ]
+    }
+
     pub fn new(
         output_buf: DebugeeStreamBuffer,
         event_queue: DebuggerEventQueue,
+        events: EventStream,
         client_exchanger: ClientExchanger,
         log_buffer: Arc<Mutex<Vec<TuiLogLine>>>,
     ) -> anyhow::Result<Self> {
         let exchanger = Arc::new(client_exchanger);
+        let layout = LayoutConfig::load();
+        let completer = Arc::new(Mutex::new(Self::init_completer(&exchanger)));
         Ok(Self {
-            app: Self::init_app(output_buf, event_queue, exchanger.clone(), log_buffer)?,
+            app: Self::init_app(
+                output_buf,
+                event_queue,
+                events,
+                exchanger.clone(),
+                log_buffer,
+                completer.clone(),
+            )?,
             quit: false,
             redraw: true,
             terminal: TerminalBridge::new().expect("Cannot initialize terminal"),
             exchanger,
-            tabs_layout: Self::DEFAULT_TABS_LAYOUT,
+            tabs_layout: Self::default_tabs_layout(&layout),
+            memory_write_addr: None,
+            layout,
+            tabs_areas: [Rect::default(); 2],
+            resize_in_progress: false,
+            completer,
+            focus_before_command_bar: None,
         })
     }
+
+    fn init_completer(exchanger: &ClientExchanger) -> CommandCompleter {
+        let (oracles, files) = exchanger
+            .request_sync(|dbg| {
+                let oracles: Vec<_> = dbg.all_oracles().map(|o| o.name()).collect();
+                let files: Vec<_> = dbg.known_files().cloned().collect();
+                (oracles, files)
+            })
+            .expect("messaging enabled at tui start");
+        let mut completer = CommandCompleter::new(&oracles);
+        completer.replace_file_hints(files);
+        completer
+    }
 }
 
 impl Model {
@@ -106,6 +165,7 @@ impl Model {
                 .constraints(self.tabs_layout)
                 .split(tabs_rect);
 
+            self.tabs_areas = [tab_chunks[0], tab_chunks[1]];
             self.app.view(&Id::LeftTabs, f, tab_chunks[0]);
             self.app.view(&Id::RightTabs, f, tab_chunks[1]);
 
@@ -125,12 +185,17 @@ impl Model {
     fn init_app(
         output_buf: DebugeeStreamBuffer,
         event_queue: DebuggerEventQueue,
+        events: EventStream,
         exchanger: Arc<ClientExchanger>,
         log_buffer: Arc<Mutex<Vec<TuiLogLine>>>,
+        completer: Arc<Mutex<CommandCompleter>>,
     ) -> anyhow::Result<Application<Id, Msg, UserEvent>> {
         let mut app: Application<Id, Msg, UserEvent> = Application::init(
             EventListenerCfg::default()
-                .default_input_listener(Duration::from_millis(20))
+                .port(
+                    Box::new(InputPort::new(Duration::from_millis(20))),
+                    Duration::from_millis(10),
+                )
                 .port(
                     Box::new(OutputPort::new(output_buf.data.clone())),
                     Duration::from_millis(10),
@@ -139,6 +204,10 @@ impl Model {
                     Box::new(DebuggerEventsPort::new(event_queue)),
                     Duration::from_millis(10),
                 )
+                .port(
+                    Box::new(EventStreamPort::new(events)),
+                    Duration::from_millis(10),
+                )
                 .port(
                     Box::new(AsyncResponsesPort::new(exchanger.clone())),
                     Duration::from_millis(10),
@@ -151,22 +220,25 @@ impl Model {
                 .tick_interval(Duration::from_millis(200)),
         );
 
-        let pid = exchanger
-            .request_sync(|dbg| dbg.process().pid())
+        let interrupter = exchanger
+            .request_sync(|dbg| dbg.interrupter())
             .expect("messaging enabled at tui start");
         app.mount(
             Id::GlobalControl,
-            Box::new(GlobalControl::new(exchanger.clone(), pid)),
+            Box::new(GlobalControl::new(exchanger.clone(), interrupter)),
             GlobalControl::subscriptions(),
         )?;
 
         app.mount(Id::Popup, Box::<Popup>::default(), vec![])?;
-        app.mount(Id::Input, Box::<Input>::default(), vec![])?;
+        app.mount(Id::Input, Box::new(Input::new(completer)), vec![])?;
 
-        let mb_err = exchanger
-            .request_sync(|dbg| run::Handler::new(dbg).handle(run::Command::DryStart))
+        let status = exchanger
+            .request_sync(|dbg| {
+                let mut status = DebugeeStatus::default();
+                status.refresh(dbg);
+                status
+            })
             .expect("messaging enabled at tui start");
-        let already_run = matches!(mb_err.err(), Some(CommandError::Handle(Error::AlreadyRun)));
 
         let output = output_buf.data.lock().unwrap().clone();
         let oracles: Vec<_> = exchanger
@@ -175,21 +247,23 @@ impl Model {
 
         app.mount(
             Id::Status,
-            Box::new(Status::new(already_run)),
+            Box::new(Status::new(status)),
             Status::subscriptions(),
         )?;
 
         let mut left_tab_sub = Variables::subscriptions();
         left_tab_sub.extend(Threads::subscriptions());
+        left_tab_sub.extend(History::subscriptions());
         left_tab_sub.extend(vec![Sub::new(SubEventClause::Tick, SubClause::Always)]);
 
         let left_tab = TabWindow::new(
             "[1]",
-            &["🔴 Breakpoints", "🧩 Variables", "🧵 Threads"],
+            &["🔴 Breakpoints", "🧩 Variables", "🧵 Threads", "🕘 History"],
             vec![
                 Box::new(Breakpoints::new(exchanger.clone())),
                 Box::new(Variables::new(exchanger.clone())),
                 Box::new(Threads::new(exchanger.clone())),
+                Box::new(History::new(exchanger.clone())),
             ],
             Some(|rewind_direction| match rewind_direction {
                 tuirealm::command::Direction::Left => Msg::RightTabsInFocus {
@@ -208,15 +282,25 @@ impl Model {
         let mut right_tab_sub = Source::subscriptions();
         right_tab_sub.extend(Asm::subscriptions());
         right_tab_sub.extend(Output::subscriptions());
+        right_tab_sub.extend(Memory::subscriptions());
+        right_tab_sub.extend(Logs::subscriptions());
         right_tab_sub.extend(vec![Sub::new(SubEventClause::Tick, SubClause::Always)]);
 
         let right_tab = TabWindow::new(
             "[2]",
-            &["</> Source", "📃 Output", "🤖 Asm", "🔮 Oracles", "💾 Logs"],
+            &[
+                "</> Source",
+                "📃 Output",
+                "🤖 Asm",
+                "🧮 Memory",
+                "🔮 Oracles",
+                "💾 Logs",
+            ],
             vec![
                 Box::new(Source::new(exchanger.clone())?),
                 Box::new(Output::new(&output)),
                 Box::new(Asm::new(exchanger.clone())?),
+                Box::new(Memory::new(exchanger.clone())),
                 Box::new(make_oracle_tab_window(&oracles)),
                 Box::<Logs>::default(),
             ],
@@ -241,12 +325,261 @@ impl Model {
 }
 
 impl Model {
-    fn update_breakpoints(&mut self) -> anyhow::Result<()> {
-        Ok(self.app.attr(
-            &Id::LeftTabs,
-            Attribute::Custom("update_breakpoints"),
+    /// Show input window and lock all subscriptions until input is done.
+    fn open_input(
+        &mut self,
+        title: String,
+        validator: fn(&str) -> bool,
+        input_data_type: InputStringType,
+    ) -> anyhow::Result<()> {
+        self.app.attr(
+            &Id::Input,
+            Attribute::InputType,
+            AttrValue::InputType(props::InputType::Custom(validator, |_, _| -> bool { true })),
+        )?;
+        self.app.attr(
+            &Id::Input,
+            Attribute::Title,
+            AttrValue::Title((title, Alignment::Left)),
+        )?;
+        self.app.attr(
+            &Id::Input,
+            Attribute::Custom("input_data_type"),
+            AttrValue::String(input_data_type.to_string()),
+        )?;
+
+        self.app.active(&Id::Input)?;
+        self.app.lock_subs();
+        Ok(())
+    }
+
+    /// Return type of the data in the opened input window.
+    fn input_data_type(&self) -> anyhow::Result<InputStringType> {
+        let input_data_type = self
+            .app
+            .query(&Id::Input, Attribute::Custom("input_data_type"))?
+            .expect("infallible")
+            .unwrap_string();
+        Ok(InputStringType::from_str(&input_data_type).expect("infallible"))
+    }
+
+    /// Close input window and return focus to the active window of the right tabs
+    /// (memory, output or logs window).
+    fn close_right_tabs_input(&mut self) -> anyhow::Result<()> {
+        self.app.unlock_subs();
+        self.app.blur()?;
+        self.app.active(&Id::RightTabs)?;
+        self.app.attr(
+            &Id::RightTabs,
+            TabWindow::ACTIVATE_TAB,
             AttrValue::Flag(true),
-        )?)
+        )?;
+        Ok(())
+    }
+
+    /// Close the command bar and return focus to the window that was in focus before.
+    fn close_command_bar(&mut self) -> anyhow::Result<()> {
+        self.app.unlock_subs();
+        self.app.blur()?;
+        let id = self.focus_before_command_bar.take().unwrap_or(Id::LeftTabs);
+        self.app.active(&id)?;
+        if matches!(id, Id::LeftTabs | Id::RightTabs) {
+            self.app
+                .attr(&id, TabWindow::ACTIVATE_TAB, AttrValue::Flag(true))?;
+        }
+        Ok(())
+    }
+
+    /// Update variable and argument names known by the command completer.
+    fn update_completer_variables(&self) {
+        let names = self.exchanger.request_sync(|dbg| -> anyhow::Result<_> {
+            let vars = dbg.read_variable_names(DQE::Variable(VariableSelector::Any))?;
+            let args = dbg.read_argument_names(DQE::Variable(VariableSelector::Any))?;
+            Ok((vars, args))
+        });
+        if let Ok(Ok((vars, args))) = names {
+            let mut completer = self.completer.lock().unwrap();
+            completer.replace_local_var_hints(vars);
+            completer.replace_arg_hints(args);
+        }
+    }
+
+    /// Start an async debugger task that resumes a debugee.
+    fn resume<F>(&self, task: F) -> Option<Msg>
+    where
+        F: FnOnce(&mut Debugger) -> anyhow::Result<()> + Send + 'static,
+    {
+        self.exchanger
+            .request_async(task)
+            .expect("messaging enabled");
+        self.exchanger.disable_messaging();
+        Some(Msg::AppRunning)
+    }
+
+    /// Execute a command from the command bar. Only commands that control an execution
+    /// and manage breakpoints are supported, commands that print something are available
+    /// in the console.
+    fn run_command(&mut self, input: &str) -> anyhow::Result<Option<Msg>> {
+        // `:<line>` moves a cursor of the source window to a line
+        if let Ok(line) = input.trim().parse::<u64>() {
+            self.app.attr(
+                &Id::RightTabs,
+                TabWindow::SHOW_TAB,
+                AttrValue::Number(RIGHT_TAB_SOURCE),
+            )?;
+            self.app.attr(
+                &Id::RightTabs,
+                Source::GOTO_LINE_ATTR,
+                AttrValue::Number(line as isize),
+            )?;
+            return Ok(Some(Msg::RightTabsInFocus { reset_to: None }));
+        }
+
+        let command = Command::parse(input).map_err(|_| anyhow!("malformed command `{input}`"))?;
+        let msg = match command {
+            Command::SkipInput => None,
+            Command::Continue(timeout) => {
+                self.resume(move |dbg| Ok(command::r#continue::Handler::new(dbg).handle(timeout)?))
+            }
+            Command::Run(options) => {
+                let mb_err = self
+                    .exchanger
+                    .request_sync(|dbg| run::Handler::new(dbg).handle(run::Command::DryStart))
+                    .expect("messaging enabled");
+                if matches!(mb_err.err(), Some(CommandError::Handle(Error::AlreadyRun))) {
+                    Some(Msg::PopupConfirmDebuggerRestart)
+                } else {
+                    self.resume(move |dbg| {
+                        Ok(run::Handler::new(dbg).handle(run::Command::Start(options))?)
+                    })
+                }
+            }
+            Command::StepInto(count, granularity) => self.resume(move |dbg| {
+                Ok(command::step_into::Handler::new(dbg).handle(count, granularity)?)
+            }),
+            Command::StepOver(count, granularity) => self.resume(move |dbg| {
+                Ok(command::step_over::Handler::new(dbg).handle(count, granularity)?)
+            }),
+            Command::StepOut(count) => self.resume(move |dbg| {
+                Ok(command::step_out::Handler::new(dbg)
+                    .handle(count)
+                    .map(drop)?)
+            }),
+            Command::StepInstruction(count) => self
+                .resume(move |dbg| Ok(command::step_instruction::Handler::new(dbg).handle(count)?)),
+            Command::Breakpoint(cmd) => {
+                self.exchanger
+                    .request_sync(move |dbg| -> anyhow::Result<()> {
+                        command::r#break::Handler::new(dbg).handle(&cmd)?;
+                        Ok(())
+                    })
+                    .expect("messaging enabled")?;
+                self.update_breakpoints()?;
+                None
+            }
+            Command::Watchpoint(cmd) => {
+                self.exchanger
+                    .request_sync(move |dbg| -> anyhow::Result<()> {
+                        command::watch::Handler::new(dbg).handle(cmd)?;
+                        Ok(())
+                    })
+                    .expect("messaging enabled")?;
+                self.update_breakpoints()?;
+                None
+            }
+            Command::Quit(_) => Some(Msg::AppClose),
+            _ => Some(Msg::ShowOkPopup(
+                Some("Command".to_string()),
+                format!("`{input}` is available in the console mode only"),
+            )),
+        };
+        Ok(msg)
+    }
+
+    /// Handle a mouse event: click focuses a window, wheel scrolls a window under the cursor,
+    /// dragging of a border between windows changes their proportions.
+    fn on_mouse(&mut self, event: MouseEvent) -> anyhow::Result<Option<Msg>> {
+        if matches!(self.app.focus(), Some(&Id::Input) | Some(&Id::Popup)) {
+            return Ok(None);
+        }
+
+        let contains = |area: &Rect| {
+            (area.left()..area.right()).contains(&event.column)
+                && (area.top()..area.bottom()).contains(&event.row)
+        };
+        let [left_area, right_area] = self.tabs_areas;
+        let window_under_cursor = if contains(&left_area) {
+            Some(Id::LeftTabs)
+        } else if contains(&right_area) {
+            Some(Id::RightTabs)
+        } else {
+            None
+        };
+
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                // border between windows is a right border of the left window
+                // and a left border of the right window
+                let on_border =
+                    event.column + 1 == right_area.left() || event.column == right_area.left();
+                if on_border && (left_area.top()..left_area.bottom()).contains(&event.row) {
+                    self.resize_in_progress = true;
+                    return Ok(None);
+                }
+
+                return Ok(match window_under_cursor {
+                    Some(Id::LeftTabs) => Some(Msg::LeftTabsInFocus { reset_to: None }),
+                    Some(Id::RightTabs) => Some(Msg::RightTabsInFocus { reset_to: None }),
+                    _ => None,
+                });
+            }
+            MouseEventKind::Drag(MouseButton::Left) if self.resize_in_progress => {
+                let total_width = left_area.width + right_area.width;
+                if total_width == 0 {
+                    return Ok(None);
+                }
+                let left_width =
+                    event.column.saturating_sub(left_area.left()) as u32 * 100 / total_width as u32;
+                self.layout.left_window_width = (left_width as u16).clamp(
+                    LayoutConfig::MIN_WINDOW_WIDTH,
+                    100 - LayoutConfig::MIN_WINDOW_WIDTH,
+                );
+                for id in [&Id::LeftTabs, &Id::RightTabs] {
+                    self.app
+                        .attr(id, TabWindow::VIEW_SIZE_ATTR, tab::ViewSize::Default.into())?;
+                }
+                self.tabs_layout = Self::default_tabs_layout(&self.layout);
+            }
+            MouseEventKind::Up(MouseButton::Left) if self.resize_in_progress => {
+                self.resize_in_progress = false;
+                self.layout.save();
+            }
+            MouseEventKind::ScrollUp | MouseEventKind::ScrollDown => {
+                let direction = if event.kind == MouseEventKind::ScrollUp {
+                    props::Direction::Up
+                } else {
+                    props::Direction::Down
+                };
+                if let Some(id) = window_under_cursor {
+                    self.app
+                        .attr(&id, TabWindow::SCROLL_ATTR, AttrValue::Direction(direction))?;
+                }
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    /// Update breakpoint list and breakpoint markers in the source window.
+    fn update_breakpoints(&mut self) -> anyhow::Result<()> {
+        for id in [&Id::LeftTabs, &Id::RightTabs] {
+            self.app.attr(
+                id,
+                Attribute::Custom("update_breakpoints"),
+                AttrValue::Flag(true),
+            )?;
+        }
+        Ok(())
     }
 
     pub fn update(&mut self, msg: Option<Msg>) -> anyhow::Result<Option<Msg>> {
@@ -260,13 +593,8 @@ impl Model {
                     self.quit = true;
                 }
                 Msg::AppRunning => {
-                    self.app.attr(
-                        &Id::Status,
-                        Attribute::Text,
-                        AttrValue::Payload(PropPayload::Vec(vec![PropValue::TextSpan(
-                            TextSpan::new("running").fg(Color::Red),
-                        )])),
-                    )?;
+                    let (attr, value) = Status::running_attrs();
+                    self.app.attr(&Id::Status, attr, value)?;
                 }
                 Msg::SwitchUI => {
                     self.exchanger.send_switch_ui();
@@ -359,47 +687,80 @@ impl Model {
                         "Add breakpoint".to_string()
                     };
 
-                    self.app.attr(
-                        &Id::Input,
-                        Attribute::InputType,
-                        AttrValue::InputType(props::InputType::Custom(
-                            input_validator,
-                            |_, _| -> bool { true },
-                        )),
+                    self.open_input(title, input_validator, input_data_type)?;
+                }
+                Msg::MemoryGoTo => {
+                    self.open_input(
+                        "Go to address or expression".to_string(),
+                        |s| -> bool {
+                            command::parser::memory_location()
+                                .parse(s)
+                                .into_result()
+                                .is_ok()
+                        },
+                        InputStringType::MemoryLocation,
+                    )?;
+                }
+                Msg::MemoryWrite(addr) => {
+                    self.memory_write_addr = Some(addr);
+                    self.open_input(
+                        format!("Write byte at {addr:#x}"),
+                        |s| -> bool { memory::parse_byte(s).is_some() },
+                        InputStringType::MemoryByte,
                     )?;
+                }
+                Msg::SearchStart => {
                     self.app.attr(
                         &Id::Input,
-                        Attribute::Title,
-                        AttrValue::Title((title, Alignment::Left)),
+                        Attribute::Value,
+                        AttrValue::String(String::new()),
+                    )?;
+                    self.open_input(
+                        "Search".to_string(),
+                        |_| -> bool { true },
+                        InputStringType::Search,
                     )?;
+                }
+                Msg::CommandBarOpen => {
+                    if !self.exchanger.is_messaging_enabled() {
+                        warn!(target: "tui", "trying to open command bar but messaging is disabled");
+                        return Ok(None);
+                    }
+
+                    self.update_completer_variables();
+                    self.focus_before_command_bar = self.app.focus().cloned();
                     self.app.attr(
                         &Id::Input,
-                        Attribute::Custom("input_data_type"),
-                        AttrValue::String(input_data_type.to_string()),
+                        Attribute::Value,
+                        AttrValue::String(String::new()),
                     )?;
-
-                    self.app.active(&Id::Input)?;
-                    self.app.lock_subs();
+                    self.open_input(
+                        Input::COMMAND_TITLE.to_string(),
+                        |_| -> bool { true },
+                        InputStringType::Command,
+                    )?;
+                }
+                Msg::InputChanged(input) => {
+                    if self.input_data_type()? == InputStringType::Search {
+                        self.app.attr(
+                            &Id::RightTabs,
+                            TabWindow::SEARCH_ATTR,
+                            AttrValue::String(input),
+                        )?;
+                    }
                 }
                 Msg::Input(input) => {
-                    let input_data_type = InputStringType::from_str(
-                        &self
-                            .app
-                            .query(&Id::Input, Attribute::Custom("input_data_type"))?
-                            .expect("infallible")
-                            .unwrap_string(),
-                    )
-                    .expect("infallible");
+                    let input_data_type = self.input_data_type()?;
                     return match input_data_type {
                         InputStringType::BreakpointAddAtFunction
                         | InputStringType::BreakpointAddAtLine
                         | InputStringType::BreakpointAddAtAddress => {
                             let identity = match input_data_type {
                                 InputStringType::BreakpointAddAtLine => {
-                                    let file_line: Vec<_> = input.split(':').collect();
-                                    let file = file_line[0];
-                                    let line: u64 = file_line[1].parse().expect("infallible");
-                                    BreakpointIdentity::Line(file.to_string(), line)
+                                    command::parser::brkpt_at_line_parser()
+                                        .parse(&input)
+                                        .into_result()
+                                        .map_err(|_| anyhow!("invalid line format"))?
                                 }
                                 InputStringType::BreakpointAddAtFunction => {
                                     BreakpointIdentity::Function(input.trim().to_string())
@@ -464,15 +825,88 @@ impl Model {
                             )?;
                             Ok(None)
                         }
+                        InputStringType::MemoryLocation => {
+                            let location = command::parser::memory_location()
+                                .parse(&input)
+                                .into_result()
+                                .expect("infallible");
+                            let addr = self
+                                .exchanger
+                                .request_sync(move |dbg| {
+                                    command::memory::Handler::new(dbg).resolve(&location)
+                                })
+                                .expect("messaging enabled")?;
+
+                            self.close_right_tabs_input()?;
+                            self.app.attr(
+                                &Id::RightTabs,
+                                Memory::GOTO_ATTR,
+                                AttrValue::Payload(PropPayload::One(PropValue::Usize(addr))),
+                            )?;
+                            Ok(None)
+                        }
+                        InputStringType::Search => {
+                            self.close_right_tabs_input()?;
+                            self.app.attr(
+                                &Id::RightTabs,
+                                TabWindow::SEARCH_ATTR,
+                                AttrValue::String(input),
+                            )?;
+                            Ok(None)
+                        }
+                        InputStringType::Command => {
+                            self.close_command_bar()?;
+                            self.run_command(&input)
+                        }
+                        InputStringType::MemoryByte => {
+                            let byte = memory::parse_byte(&input).expect("infallible");
+                            if let Some(addr) = self.memory_write_addr.take() {
+                                self.exchanger
+                                    .request_sync(move |dbg| {
+                                        command::memory::Handler::new(dbg).write_byte(addr, byte)
+                                    })
+                                    .expect("messaging enabled")?;
+                            }
+
+                            self.close_right_tabs_input()?;
+                            self.app.attr(
+                                &Id::RightTabs,
+                                Memory::UPDATE_ATTR,
+                                AttrValue::Flag(true),
+                            )?;
+                            Ok(None)
+                        }
                     };
                 }
                 Msg::InputCancel => {
+                    let input_data_type = self.input_data_type()?;
+                    if input_data_type == InputStringType::Command {
+                        self.close_command_bar()?;
+                        return Ok(None);
+                    }
+                    if matches!(
+                        input_data_type,
+                        InputStringType::MemoryLocation | InputStringType::MemoryByte
+                    ) {
+                        self.memory_write_addr = None;
+                        self.close_right_tabs_input()?;
+                        return Ok(None);
+                    }
+                    if input_data_type == InputStringType::Search {
+                        self.close_right_tabs_input()?;
+                        self.app.attr(
+                            &Id::RightTabs,
+                            TabWindow::SEARCH_ATTR,
+                            AttrValue::String(String::new()),
+                        )?;
+                        return Ok(None);
+                    }
+
                     self.app.unlock_subs();
                     self.app.blur()?;
                     self.update_breakpoints()?;
-                    // FIXME: currently input cancel are possible only from breakpoints window,
-                    // that's why breakpoints window come into focus here.
-                    // This behaviour may be changed in future.
+                    // breakpoints window come into focus here, because all other inputs
+                    // are opened from it
                     self.app.active(&Id::LeftTabs)?;
                     self.app.attr(
                         &Id::LeftTabs,
@@ -483,6 +917,20 @@ impl Model {
                 Msg::UpdateBreakpointList => {
                     self.update_breakpoints()?;
                 }
+                Msg::ShowAsm => {
+                    self.app.attr(
+                        &Id::RightTabs,
+                        TabWindow::SHOW_TAB,
+                        AttrValue::Number(RIGHT_TAB_ASM),
+                    )?;
+                }
+                Msg::ShowSource => {
+                    self.app.attr(
+                        &Id::RightTabs,
+                        TabWindow::SHOW_TAB,
+                        AttrValue::Number(RIGHT_TAB_SOURCE),
+                    )?;
+                }
                 Msg::ShowOkPopup(title, text) => {
                     if let Some(title) = title {
                         self.app
@@ -566,7 +1014,8 @@ impl Model {
                     ConfirmedAction::Restart => {
                         self.exchanger
                             .request_async(|dbg| {
-                                Ok(run::Handler::new(dbg).handle(run::Command::Restart)?)
+                                Ok(run::Handler::new(dbg)
+                                    .handle(run::Command::Restart(run::Options::default()))?)
                             })
                             .expect("messaging enabled");
                         self.exchanger.disable_messaging();
@@ -614,7 +1063,7 @@ impl Model {
                     debug_assert!(tab_id == Id::RightTabs || tab_id == Id::LeftTabs);
                     match tab_id {
                         Id::RightTabs
-                            if self.tabs_layout == Self::DEFAULT_TABS_LAYOUT
+                            if self.tabs_layout == Self::default_tabs_layout(&self.layout)
                                 || self.tabs_layout == Self::LEFT_TAB_FOCUS_LAYOUT =>
                         {
                             self.app.attr(
@@ -631,7 +1080,7 @@ impl Model {
                             self.tabs_layout = Self::RIGHT_TAB_FOCUS_LAYOUT;
                         }
                         Id::LeftTabs
-                            if self.tabs_layout == Self::DEFAULT_TABS_LAYOUT
+                            if self.tabs_layout == Self::default_tabs_layout(&self.layout)
                                 || self.tabs_layout == Self::RIGHT_TAB_FOCUS_LAYOUT =>
                         {
                             self.app.attr(
@@ -655,11 +1104,15 @@ impl Model {
                                     tab::ViewSize::Default.into(),
                                 )?;
                             }
-                            self.tabs_layout = Self::DEFAULT_TABS_LAYOUT;
+                            self.tabs_layout = Self::default_tabs_layout(&self.layout);
                         }
                     }
                 }
 
+                Msg::Mouse(event) => {
+                    return self.on_mouse(event);
+                }
+
                 Msg::None => {}
             }
         }
@@ -1,17 +1,25 @@
 use crate::debugger::address::RelocatedAddress;
 use crate::debugger::register::debug::BreakCondition;
 use crate::debugger::variable::VariableIR;
-use crate::debugger::{EventHook, FunctionDie, PlaceDescriptor};
+use crate::debugger::Pid;
+use crate::debugger::Signal;
+use crate::debugger::{
+    CatchEvent, EventHook, FormatString, FunctionDie, MemoryAccess, PlaceDescriptor, StopSummary,
+};
+use crate::ui::console::hook::render_not_stopped_threads;
+use crate::ui::console::variable::{render_dprintf_message, render_variable_inline};
+use crate::ui::proto::{ClientExchanger, DebuggerEvent, EventPublisher, EventStream};
 use crate::ui::tui::output::OutputLine;
-use crate::ui::tui::proto::ClientExchanger;
 use crate::ui::tui::utils::logger::TuiLogLine;
 use crate::version;
-use log::warn;
-use nix::sys::signal::Signal;
-use nix::unistd::Pid;
+use crate::version::Version;
+use crossterm::event as xterm;
+use crossterm::event::MouseEvent;
+use log::{warn, Level};
 use std::cmp::Ordering;
 use std::sync::{Arc, Mutex};
-use tuirealm::listener::{ListenerResult, Poll};
+use std::time::Duration;
+use tuirealm::listener::{ListenerError, ListenerResult, Poll};
 use tuirealm::Event;
 
 impl PartialOrd for VariableIR {
@@ -20,6 +28,18 @@ impl PartialOrd for VariableIR {
     }
 }
 
+impl PartialEq for DebuggerEvent {
+    fn eq(&self, other: &Self) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+impl PartialOrd for DebuggerEvent {
+    fn partial_cmp(&self, _: &Self) -> Option<Ordering> {
+        None
+    }
+}
+
 #[derive(Clone, PartialOrd)]
 pub enum UserEvent {
     GotOutput(Vec<OutputLine>, usize),
@@ -36,8 +56,9 @@ pub enum UserEvent {
         file: Option<String>,
         line: Option<u64>,
         cond: BreakCondition,
-        old_value: Option<VariableIR>,
-        new_value: Option<VariableIR>,
+        old_value: Option<Box<VariableIR>>,
+        new_value: Option<Box<VariableIR>>,
+        access: Option<MemoryAccess>,
         end_of_scope: bool,
     },
     Step {
@@ -47,10 +68,13 @@ pub enum UserEvent {
         function: Option<String>,
     },
     Signal(Signal),
+    /// Debugee state change from an event stream.
+    Debugger(DebuggerEvent),
     Exit(i32),
     AsyncErrorResponse(String),
     Logs(Vec<TuiLogLine>),
     ProcessInstall(Pid),
+    Mouse(MouseEvent),
 }
 
 impl PartialEq for UserEvent {
@@ -64,6 +88,9 @@ impl PartialEq for UserEvent {
             UserEvent::Signal(_) => {
                 matches!(other, UserEvent::Signal(_))
             }
+            UserEvent::Debugger(_) => {
+                matches!(other, UserEvent::Debugger(_))
+            }
             UserEvent::Exit(_) => {
                 matches!(other, UserEvent::Exit(_))
             }
@@ -77,12 +104,42 @@ impl PartialEq for UserEvent {
                 matches!(other, UserEvent::ProcessInstall(_))
             }
             UserEvent::Watchpoint { .. } => matches!(other, UserEvent::Watchpoint { .. }),
+            UserEvent::Mouse(_) => matches!(other, UserEvent::Mouse(_)),
         }
     }
 }
 
 impl Eq for UserEvent {}
 
+/// Terminal input port. Unlike the default tuirealm input listener, mouse events
+/// are not dropped but passed as a [`UserEvent::Mouse`].
+pub struct InputPort {
+    interval: Duration,
+}
+
+impl InputPort {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval: interval / 2,
+        }
+    }
+}
+
+impl Poll<UserEvent> for InputPort {
+    fn poll(&mut self) -> ListenerResult<Option<Event<UserEvent>>> {
+        match xterm::poll(self.interval) {
+            Ok(true) => xterm::read()
+                .map(|event| match event {
+                    xterm::Event::Mouse(mouse) => Some(Event::User(UserEvent::Mouse(mouse))),
+                    event => Some(Event::from(event)),
+                })
+                .map_err(|_| ListenerError::PollFailed),
+            Ok(false) => Ok(None),
+            Err(_) => Err(ListenerError::PollFailed),
+        }
+    }
+}
+
 pub struct OutputPort {
     output_buf: Arc<Mutex<Vec<OutputLine>>>,
     read_line_count: usize,
@@ -113,11 +170,15 @@ pub type DebuggerEventQueue = Arc<Mutex<Vec<UserEvent>>>;
 
 pub struct TuiHook {
     event_queue: DebuggerEventQueue,
+    events: EventPublisher,
 }
 
 impl TuiHook {
-    pub fn new(event_queue: DebuggerEventQueue) -> Self {
-        Self { event_queue }
+    pub fn new(event_queue: DebuggerEventQueue, events: EventPublisher) -> Self {
+        Self {
+            event_queue,
+            events,
+        }
     }
 }
 
@@ -137,11 +198,49 @@ impl EventHook for TuiHook {
                 num,
                 file: place.as_ref().map(|p| p.file.to_string_lossy().to_string()),
                 line: place.as_ref().map(|p| p.line_number),
-                function: function.and_then(|f| f.base_attributes.name.clone()),
+                function: function
+                    .and_then(|f| f.base_attributes.name.as_deref().map(ToString::to_string)),
             });
         Ok(())
     }
 
+    fn on_function_return(&self, num: u32, value: Option<&VariableIR>) -> anyhow::Result<()> {
+        let value = value
+            .map(|value| render_variable_inline(value, 100))
+            .unwrap_or_else(|| "unknown".to_string());
+        self.event_queue
+            .lock()
+            .unwrap()
+            .push(UserEvent::Logs(vec![TuiLogLine::new(
+                Level::Info,
+                "breakpoint",
+                format!("breakpoint {num}: value returned: {value}"),
+            )]));
+        Ok(())
+    }
+
+    fn on_exception(
+        &self,
+        num: u32,
+        event: CatchEvent,
+        type_name: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let action = match event {
+            CatchEvent::Throw => "thrown",
+            CatchEvent::Catch => "caught",
+        };
+        let type_name = type_name.unwrap_or("unknown");
+        self.event_queue
+            .lock()
+            .unwrap()
+            .push(UserEvent::Logs(vec![TuiLogLine::new(
+                Level::Info,
+                "breakpoint",
+                format!("breakpoint {num}: exception {action}: {type_name}"),
+            )]));
+        Ok(())
+    }
+
     fn on_watchpoint(
         &self,
         pc: RelocatedAddress,
@@ -150,6 +249,7 @@ impl EventHook for TuiHook {
         cond: BreakCondition,
         old: Option<&VariableIR>,
         new: Option<&VariableIR>,
+        access: Option<MemoryAccess>,
         end_of_scope: bool,
     ) -> anyhow::Result<()> {
         self.event_queue
@@ -161,13 +261,37 @@ impl EventHook for TuiHook {
                 file: place.as_ref().map(|p| p.file.to_string_lossy().to_string()),
                 line: place.as_ref().map(|p| p.line_number),
                 cond,
-                old_value: old.cloned(),
-                new_value: new.cloned(),
+                old_value: old.cloned().map(Box::new),
+                new_value: new.cloned().map(Box::new),
+                access,
                 end_of_scope,
             });
         Ok(())
     }
 
+    fn on_watchpoint_rebind(
+        &self,
+        num: u32,
+        expr: &str,
+        address: Option<RelocatedAddress>,
+    ) -> anyhow::Result<()> {
+        let msg = match address {
+            Some(address) => format!("watchpoint {num} ({expr}) moved to {address}"),
+            None => {
+                format!("watchpoint {num} ({expr}) no longer points to a valid location, removed")
+            }
+        };
+        self.event_queue
+            .lock()
+            .unwrap()
+            .push(UserEvent::Logs(vec![TuiLogLine::new(
+                Level::Info,
+                "watchpoint",
+                msg,
+            )]));
+        Ok(())
+    }
+
     fn on_step(
         &self,
         pc: RelocatedAddress,
@@ -178,7 +302,8 @@ impl EventHook for TuiHook {
             pc,
             file: place.as_ref().map(|p| p.file.to_string_lossy().to_string()),
             line: place.as_ref().map(|p| p.line_number),
-            function: function.and_then(|f| f.base_attributes.name.clone()),
+            function: function
+                .and_then(|f| f.base_attributes.name.as_deref().map(ToString::to_string)),
         });
         Ok(())
     }
@@ -190,13 +315,71 @@ impl EventHook for TuiHook {
             .push(UserEvent::Signal(signal));
     }
 
+    fn on_stop(&self, summary: &StopSummary) {
+        self.events.publish(DebuggerEvent::Stop(summary.clone()));
+    }
+
+    fn on_threads_not_stopped(&self, tids: &[Pid]) {
+        self.event_queue
+            .lock()
+            .unwrap()
+            .push(UserEvent::Logs(vec![TuiLogLine::new(
+                Level::Warn,
+                "debugger",
+                render_not_stopped_threads(tids),
+            )]));
+    }
+
     fn on_exit(&self, code: i32) {
         self.event_queue.lock().unwrap().push(UserEvent::Exit(code));
+        self.events.publish(DebuggerEvent::Exit(code));
+    }
+
+    fn on_dprintf(&self, _: u32, format: &FormatString, values: &[Option<VariableIR>]) {
+        let msg = render_dprintf_message(format, values);
+        self.event_queue
+            .lock()
+            .unwrap()
+            .push(UserEvent::Logs(vec![TuiLogLine::new(
+                Level::Info,
+                "dprintf",
+                msg,
+            )]));
     }
 
-    fn on_process_install(&self, pid: Pid, object: Option<&object::File>) {
-        if let Some(obj) = object {
-            if !version::probe_file(obj) {
+    fn on_ftrace_enter(&self, _: Pid, depth: usize, function: &str, args: &[VariableIR]) {
+        let args = args
+            .iter()
+            .map(|arg| render_variable_inline(arg, 100))
+            .collect::<Vec<_>>()
+            .join(", ");
+        self.event_queue
+            .lock()
+            .unwrap()
+            .push(UserEvent::Logs(vec![TuiLogLine::new(
+                Level::Info,
+                "ftrace",
+                format!("{:indent$}-> {function}({args})", "", indent = depth * 2),
+            )]));
+    }
+
+    fn on_ftrace_return(&self, _: Pid, depth: usize, function: &str, value: Option<&VariableIR>) {
+        let value = value
+            .map(|value| format!(" = {}", render_variable_inline(value, 100)))
+            .unwrap_or_default();
+        self.event_queue
+            .lock()
+            .unwrap()
+            .push(UserEvent::Logs(vec![TuiLogLine::new(
+                Level::Info,
+                "ftrace",
+                format!("{:indent$}<- {function}{value}", "", indent = depth * 2),
+            )]));
+    }
+
+    fn on_process_install(&self, pid: Pid, rustc_version: Option<&Version>) {
+        if let Some(rustc_version) = rustc_version {
+            if !rustc_version.is_supported() {
                 let supported_versions = version::supported_versions_to_string();
                 warn!(target: "debugger", "Found unsupported rust version, some of program data may not be displayed correctly. \
                 List of supported rustc versions: {supported_versions}.");
@@ -207,6 +390,7 @@ impl EventHook for TuiHook {
             .lock()
             .unwrap()
             .push(UserEvent::ProcessInstall(pid));
+        self.events.publish(DebuggerEvent::ProcessInstall(pid));
     }
 }
 
@@ -229,6 +413,27 @@ impl Poll<UserEvent> for DebuggerEventsPort {
     }
 }
 
+/// Port of a debugger event stream, see [`crate::ui::proto::event_stream`].
+pub struct EventStreamPort {
+    events: EventStream,
+}
+
+impl EventStreamPort {
+    pub fn new(events: EventStream) -> Self {
+        Self { events }
+    }
+}
+
+impl Poll<UserEvent> for EventStreamPort {
+    fn poll(&mut self) -> ListenerResult<Option<Event<UserEvent>>> {
+        Ok(self
+            .events
+            .drain()
+            .next()
+            .map(|event| Event::User(UserEvent::Debugger(event))))
+    }
+}
+
 pub struct AsyncResponsesPort {
     exchanger: Arc<ClientExchanger>,
 }
@@ -2,14 +2,13 @@ use crate::debugger::register::debug::BreakCondition;
 use crate::ui;
 use crate::ui::tui::app::port::UserEvent;
 use crate::ui::tui::config::CommonAction;
-use crate::ui::tui::proto::ClientExchanger;
+use crate::ui::proto::ClientExchanger;
 use crate::ui::tui::utils::mstextarea::MultiSpanTextarea;
 use crate::ui::tui::{Id, Msg};
 use std::sync::Arc;
 use tuirealm::command::{Cmd, Direction, Position};
 use tuirealm::props::{Borders, Style, TextSpan};
 use tuirealm::tui::layout::Alignment;
-use tuirealm::tui::prelude::Color;
 use tuirealm::tui::widgets::BorderType;
 use tuirealm::{
     AttrValue, Attribute, Component, Event, MockComponent, Sub, SubClause, SubEventClause,
@@ -23,13 +22,14 @@ pub struct Asm {
 
 impl Asm {
     pub fn new(exchanger: Arc<ClientExchanger>) -> anyhow::Result<Self> {
+        let palette = &ui::config::current().tui_palette;
         let component = MultiSpanTextarea::default()
             .borders(
                 Borders::default()
                     .modifiers(BorderType::Rounded)
-                    .color(Color::LightYellow),
+                    .color(palette.border),
             )
-            .inactive(Style::default().fg(Color::Gray))
+            .inactive(Style::default().fg(palette.inactive))
             .title("Assembler code for function", Alignment::Center)
             .step(4)
             .highlighted_str("▶");
@@ -45,36 +45,38 @@ impl Asm {
     }
 
     fn update_asm_view(&mut self) {
+        let palette = &ui::config::current().tui_palette;
         let Ok(asm) = self.exchanger.request_sync(|dbg| dbg.disasm()) else {
             return;
         };
 
         if let Ok(asm) = asm {
-            if let Some(ref fn_name) = asm.name {
-                self.component.attr(
-                    Attribute::Title,
-                    AttrValue::Title((
-                        format!("Assembler code for function ({fn_name})"),
-                        Alignment::Center,
-                    )),
-                );
-            }
+            let title = match asm.name {
+                Some(ref fn_name) => format!("Assembler code for function ({fn_name})"),
+                // no debug information, instructions are disassembled from the current pc
+                None => format!("Assembler code at {}", asm.addr_in_focus),
+            };
+            self.component.attr(
+                Attribute::Title,
+                AttrValue::Title((title, Alignment::Center)),
+            );
 
             let mut line_in_focus = None;
             let mut lines = vec![];
             for instr in asm.instructions.into_iter() {
-                let addr_span = TextSpan::new(format!("{} ", instr.address)).fg(Color::Blue);
+                let addr_span = TextSpan::new(format!("{} ", instr.address)).fg(palette.address);
                 let mnemonic_span =
                     TextSpan::new(format!("{} ", instr.mnemonic.as_deref().unwrap_or("???")))
-                        .fg(Color::Red);
+                        .fg(palette.instruction);
                 let operands_span =
-                    TextSpan::new(instr.operands.as_deref().unwrap_or("???")).fg(Color::Green);
+                    TextSpan::new(instr.operands.as_deref().unwrap_or("???")).fg(palette.value);
 
                 let mut line = vec![addr_span, mnemonic_span, operands_span];
 
                 if asm.addr_in_focus == instr.address {
                     line_in_focus = Some(lines.len());
-                    line.iter_mut().for_each(|text| text.fg = Color::LightRed)
+                    line.iter_mut()
+                        .for_each(|text| text.fg = palette.current_line)
                 }
 
                 lines.push(line);
@@ -109,6 +111,7 @@ impl Asm {
                     cond: BreakCondition::DataReadsWrites,
                     old_value: None,
                     new_value: None,
+                    access: None,
                     end_of_scope: false,
                 }),
                 SubClause::Always,
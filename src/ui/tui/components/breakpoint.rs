@@ -2,13 +2,14 @@ use crate::debugger::{BreakpointViewOwned, WatchpointViewOwned};
 use crate::ui;
 use crate::ui::command;
 use crate::ui::command::r#break::Command as BreakpointCommand;
-use crate::ui::command::r#break::ExecutionResult;
+use crate::ui::command::r#break::{BreakpointIdentity, ExecutionResult};
 use crate::ui::command::watch::Command as WatchpointCommand;
 use crate::ui::command::watch::ExecutionResult as WatchpointExecutionResult;
+use crate::ui::command::watch::WatchpointIdentity;
 use crate::ui::short::Abbreviator;
 use crate::ui::tui::app::port::UserEvent;
 use crate::ui::tui::config::CommonAction;
-use crate::ui::tui::proto::ClientExchanger;
+use crate::ui::proto::{ClientExchanger, MessagingDisabled};
 use crate::ui::tui::{BreakpointsAddType, Msg};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -16,7 +17,6 @@ use tui_realm_stdlib::List;
 use tuirealm::command::{Cmd, CmdResult, Direction, Position};
 use tuirealm::props::{BorderType, Borders, Style, TableBuilder, TextSpan};
 use tuirealm::tui::layout::{Alignment, Rect};
-use tuirealm::tui::style::Color;
 use tuirealm::{AttrValue, Attribute, Component, Event, Frame, MockComponent, State, StateValue};
 
 #[derive(PartialEq)]
@@ -69,6 +69,7 @@ impl MockComponent for Breakpoints {
 impl Breakpoints {
     /// Update a breakpoint list. Triggered by custom attribute "update_breakpoints".
     pub fn update_list(&mut self) {
+        let palette = &ui::config::current().tui_palette;
         let skip = if self.state == Some(AddState::SelectType) {
             // skip the first 4 rows because it is an added buttons
             5
@@ -80,7 +81,7 @@ impl Breakpoints {
         let Ok(breakpoints) = self.exchanger.request_sync(|dbg| {
             let mut cmd = command::r#break::Handler::new(dbg);
             let brkpt_result = cmd.handle(&BreakpointCommand::Info).expect("infallible");
-            let ExecutionResult::Dump(breakpoints) = brkpt_result else {
+            let ExecutionResult::Dump(breakpoints, _) = brkpt_result else {
                 unreachable!()
             };
 
@@ -120,7 +121,7 @@ impl Breakpoints {
         let mut table_builder = TableBuilder::default();
         table_builder.add_col(TextSpan::from(" "));
         table_builder.add_col(TextSpan::from(" "));
-        table_builder.add_col(TextSpan::from("NEW").fg(Color::Green).bold());
+        table_builder.add_col(TextSpan::from("NEW").fg(palette.value).bold());
         table_builder.add_row();
 
         if self.state == Some(AddState::SelectType) {
@@ -128,50 +129,61 @@ impl Breakpoints {
             table_builder.add_col(TextSpan::from(" "));
             table_builder.add_col(TextSpan::from(" "));
             table_builder.add_col(TextSpan::from(" "));
-            table_builder.add_col(TextSpan::from("   at file:line").fg(Color::Green).bold());
+            table_builder.add_col(TextSpan::from("   at file:line").fg(palette.value).bold());
             table_builder.add_row();
             table_builder.add_col(TextSpan::from(" "));
             table_builder.add_col(TextSpan::from(" "));
             table_builder.add_col(TextSpan::from(" "));
             table_builder.add_col(TextSpan::from(" "));
-            table_builder.add_col(TextSpan::from("   at function").fg(Color::Green).bold());
+            table_builder.add_col(TextSpan::from("   at function").fg(palette.value).bold());
             table_builder.add_row();
             table_builder.add_col(TextSpan::from(" "));
             table_builder.add_col(TextSpan::from(" "));
             table_builder.add_col(TextSpan::from(" "));
             table_builder.add_col(TextSpan::from(" "));
-            table_builder.add_col(TextSpan::from("   at address").fg(Color::Green).bold());
+            table_builder.add_col(TextSpan::from("   at address").fg(palette.value).bold());
             table_builder.add_row();
             table_builder.add_col(TextSpan::from(" "));
             table_builder.add_col(TextSpan::from(" "));
             table_builder.add_col(TextSpan::from(" "));
             table_builder.add_col(TextSpan::from(" "));
-            table_builder.add_col(TextSpan::from("   watchpoint").fg(Color::Green).bold());
+            table_builder.add_col(TextSpan::from("   watchpoint").fg(palette.value).bold());
             table_builder.add_row();
         }
 
         let abbreviator = Abbreviator::new("/", "/..", 50);
 
         for brkpt in breakpoints.iter() {
-            table_builder.add_col(TextSpan::from(brkpt.number.to_string()).fg(Color::Cyan));
+            table_builder.add_col(TextSpan::from(brkpt.number.to_string()).fg(palette.number));
             table_builder.add_col(TextSpan::from(" "));
-            table_builder.add_col(TextSpan::from("B").fg(Color::LightGreen));
+            if brkpt.enabled {
+                table_builder.add_col(TextSpan::from("B").fg(palette.enabled));
+            } else {
+                table_builder.add_col(TextSpan::from("b").fg(palette.disabled));
+            }
             table_builder.add_col(TextSpan::from(" "));
-            if let Some(ref place) = brkpt.place {
+            let location = if let Some(ref place) = brkpt.place {
                 let breakpoint_path =
                     format!("{}:{}", place.file.to_string_lossy(), place.line_number);
-                let breakpoint_path = abbreviator.apply(&breakpoint_path);
-                table_builder.add_col(TextSpan::from(breakpoint_path));
+                abbreviator.apply(&breakpoint_path).to_string()
             } else {
-                table_builder.add_col(TextSpan::from(format!("{}", brkpt.number)));
+                format!("{}", brkpt.addr)
+            };
+            if brkpt.enabled {
+                table_builder.add_col(TextSpan::from(location));
+            } else {
+                table_builder.add_col(TextSpan::from(location).fg(palette.disabled));
             }
+            table_builder.add_col(
+                TextSpan::from(format!(" hits: {}", brkpt.hit_count)).fg(palette.highlight),
+            );
             table_builder.add_row();
         }
 
         for wp in watchpoints.iter() {
-            table_builder.add_col(TextSpan::from(wp.number.to_string()).fg(Color::Cyan));
+            table_builder.add_col(TextSpan::from(wp.number.to_string()).fg(palette.number));
             table_builder.add_col(TextSpan::from(" "));
-            table_builder.add_col(TextSpan::from("W").fg(Color::LightBlue));
+            table_builder.add_col(TextSpan::from("W").fg(palette.address));
             table_builder.add_col(TextSpan::from(" "));
             if let Some(ref dqe_string) = wp.source_dqe {
                 table_builder.add_col(TextSpan::from(format!("{} ({})", dqe_string, wp.condition)));
@@ -194,17 +206,73 @@ impl Breakpoints {
 }
 
 impl Breakpoints {
+    /// Enable or disable a breakpoint at the selected row.
+    fn toggle_selected(&mut self) -> Msg {
+        let idx = self.component.state().unwrap_one().unwrap_usize();
+        let Some(brkpt) = self.row_to_brkpt_map.get(&idx) else {
+            return Msg::None;
+        };
+        let cmd = if brkpt.enabled {
+            BreakpointCommand::Disable(brkpt.number)
+        } else {
+            BreakpointCommand::Enable(brkpt.number)
+        };
+
+        let result = self
+            .exchanger
+            .request_sync(move |dbg| -> anyhow::Result<()> {
+                command::r#break::Handler::new(dbg).handle(&cmd)?;
+                Ok(())
+            });
+        self.after_change(result)
+    }
+
+    /// Remove a breakpoint or watchpoint at the selected row.
+    fn remove_selected(&mut self) -> Msg {
+        let idx = self.component.state().unwrap_one().unwrap_usize();
+        let result = if let Some(brkpt) = self.row_to_brkpt_map.get(&idx) {
+            let cmd = BreakpointCommand::Remove(BreakpointIdentity::Number(brkpt.number));
+            self.exchanger
+                .request_sync(move |dbg| -> anyhow::Result<()> {
+                    command::r#break::Handler::new(dbg).handle(&cmd)?;
+                    Ok(())
+                })
+        } else if let Some(wp) = self.row_to_watchpoint_map.get(&idx) {
+            let cmd = WatchpointCommand::Remove(WatchpointIdentity::Number(wp.number));
+            self.exchanger
+                .request_sync(move |dbg| -> anyhow::Result<()> {
+                    command::watch::Handler::new(dbg).handle(cmd)?;
+                    Ok(())
+                })
+        } else {
+            return Msg::None;
+        };
+        self.after_change(result)
+    }
+
+    fn after_change(&mut self, result: Result<anyhow::Result<()>, MessagingDisabled>) -> Msg {
+        match result {
+            Ok(Ok(())) => {
+                self.update_list();
+                Msg::None
+            }
+            Ok(Err(e)) => Msg::ShowOkPopup(Some("Error".to_string()), e.to_string()),
+            Err(_) => Msg::None,
+        }
+    }
+
     pub fn new(exchanger: Arc<ClientExchanger>) -> Self {
+        let palette = &ui::config::current().tui_palette;
         let list = List::default()
             .borders(
                 Borders::default()
                     .modifiers(BorderType::Rounded)
-                    .color(Color::LightYellow),
+                    .color(palette.border),
             )
             .title("Breakpoints", Alignment::Center)
             .scroll(true)
-            .inactive(Style::default().fg(Color::Gray))
-            .highlighted_color(Color::LightYellow)
+            .inactive(Style::default().fg(palette.inactive))
+            .highlighted_color(palette.highlight)
             .highlighted_str("▶")
             .rewind(true)
             .step(4);
@@ -249,6 +317,12 @@ impl Component<Msg, UserEvent> for Breakpoints {
                     CommonAction::GotoEnd => {
                         self.perform(Cmd::GoTo(Position::End));
                     }
+                    CommonAction::Toggle if self.state.is_none() => {
+                        return Some(self.toggle_selected());
+                    }
+                    CommonAction::Delete if self.state.is_none() => {
+                        return Some(self.remove_selected());
+                    }
                     CommonAction::Submit => {
                         let idx = self.component.state().unwrap_one().unwrap_usize();
 
@@ -1,16 +1,15 @@
 use crate::debugger::register::debug::BreakCondition;
-use crate::debugger::Error;
+use crate::debugger::Signal;
+use crate::debugger::{Error, Interrupter, StepGranularity};
 use crate::ui;
 use crate::ui::command;
 use crate::ui::command::{run, CommandError};
+use crate::ui::proto::ClientExchanger;
 use crate::ui::tui::app::port::UserEvent;
 use crate::ui::tui::config::SpecialAction;
-use crate::ui::tui::proto::ClientExchanger;
 use crate::ui::tui::{Id, Msg};
+use crossterm::event::{MouseEvent, MouseEventKind};
 use log::warn;
-use nix::sys::signal;
-use nix::sys::signal::Signal;
-use nix::unistd::Pid;
 use std::sync::Arc;
 use tuirealm::event::{Key, KeyEvent, KeyModifiers};
 use tuirealm::{Component, Event, MockComponent, Sub, SubClause, SubEventClause};
@@ -19,15 +18,39 @@ use tuirealm::{Component, Event, MockComponent, Sub, SubClause, SubEventClause};
 pub struct GlobalControl {
     component: tui_realm_stdlib::Phantom,
     exchanger: Arc<ClientExchanger>,
-    last_seen_pid: Pid,
+    interrupter: Interrupter,
+    /// True if last step is a step by instruction.
+    instruction_step: bool,
+    /// True if disassembly window is shown instead of the source code window.
+    asm_shown: bool,
 }
 
 impl GlobalControl {
-    pub fn new(exchanger: Arc<ClientExchanger>, pid: Pid) -> Self {
+    pub fn new(exchanger: Arc<ClientExchanger>, interrupter: Interrupter) -> Self {
         Self {
             component: tui_realm_stdlib::Phantom::default(),
             exchanger,
-            last_seen_pid: pid,
+            interrupter,
+            instruction_step: false,
+            asm_shown: false,
+        }
+    }
+
+    /// Choose a window for the new stop place. Disassembly is shown after step by instruction
+    /// or if there is no source code for stop place,
+    /// source code is shown back when it becomes available.
+    fn switch_code_window(&mut self, has_source: bool) -> Msg {
+        let show_asm = std::mem::take(&mut self.instruction_step) || !has_source;
+        match (show_asm, self.asm_shown) {
+            (true, false) => {
+                self.asm_shown = true;
+                Msg::ShowAsm
+            }
+            (false, true) => {
+                self.asm_shown = false;
+                Msg::ShowSource
+            }
+            _ => Msg::None,
         }
     }
 
@@ -44,6 +67,8 @@ impl GlobalControl {
             SpecialAction::StepOver,
             SpecialAction::StepInto,
             SpecialAction::StepOut,
+            SpecialAction::StepInstruction,
+            SpecialAction::CommandBar,
         ];
         let mut subscriptions = vec![];
 
@@ -69,11 +94,6 @@ impl GlobalControl {
                 SubEventClause::User(UserEvent::AsyncErrorResponse(String::default())),
                 SubClause::Always,
             ),
-            Sub::new(
-                // concrete pid doesn't meter
-                SubEventClause::User(UserEvent::ProcessInstall(Pid::from_raw(0))),
-                SubClause::Always,
-            ),
             Sub::new(
                 // concrete brkpt doesn't meter
                 SubEventClause::User(UserEvent::Breakpoint {
@@ -95,6 +115,7 @@ impl GlobalControl {
                     cond: BreakCondition::DataReadsWrites,
                     old_value: None,
                     new_value: None,
+                    access: None,
                     end_of_scope: false,
                 }),
                 SubClause::Always,
@@ -111,6 +132,16 @@ impl GlobalControl {
             ),
             // concrete code doesn't meter
             Sub::new(SubEventClause::User(UserEvent::Exit(0)), SubClause::Always),
+            Sub::new(
+                // concrete mouse event doesn't meter
+                SubEventClause::User(UserEvent::Mouse(MouseEvent {
+                    kind: MouseEventKind::Moved,
+                    column: 0,
+                    row: 0,
+                    modifiers: crossterm::event::KeyModifiers::NONE,
+                })),
+                SubClause::Always,
+            ),
         ];
 
         subscriptions.extend(user_subs);
@@ -125,7 +156,7 @@ impl Component<Msg, UserEvent> for GlobalControl {
                 code: Key::Char('c'),
                 modifiers: KeyModifiers::CONTROL,
             }) => {
-                _ = signal::kill(self.last_seen_pid, Signal::SIGINT);
+                self.interrupter.interrupt();
                 Msg::None
             }
 
@@ -139,6 +170,7 @@ impl Component<Msg, UserEvent> for GlobalControl {
                         SpecialAction::FocusRightWindow => Msg::RightTabsInFocus { reset_to: None },
                         SpecialAction::SwitchUI => Msg::SwitchUI,
                         SpecialAction::CloseApp => Msg::AppClose,
+                        SpecialAction::CommandBar => Msg::CommandBarOpen,
                         SpecialAction::ContinueDebugee => {
                             if !self.exchanger.is_messaging_enabled() {
                                 warn!(target: "tui", "try continue but messaging disabled");
@@ -147,7 +179,7 @@ impl Component<Msg, UserEvent> for GlobalControl {
 
                             self.exchanger
                                 .request_async(|dbg| {
-                                    Ok(command::r#continue::Handler::new(dbg).handle()?)
+                                    Ok(command::r#continue::Handler::new(dbg).handle(None)?)
                                 })
                                 .expect("messaging enabled");
 
@@ -177,7 +209,8 @@ impl Component<Msg, UserEvent> for GlobalControl {
                             } else {
                                 self.exchanger
                                     .request_async(|dbg| {
-                                        Ok(run::Handler::new(dbg).handle(run::Command::Start)?)
+                                        Ok(run::Handler::new(dbg)
+                                            .handle(run::Command::Start(run::Options::default()))?)
                                     })
                                     .expect("messaging enabled");
                                 self.exchanger.disable_messaging();
@@ -192,7 +225,8 @@ impl Component<Msg, UserEvent> for GlobalControl {
 
                             self.exchanger
                                 .request_async(|dbg| {
-                                    Ok(command::step_over::Handler::new(dbg).handle()?)
+                                    Ok(command::step_over::Handler::new(dbg)
+                                        .handle(1, StepGranularity::Line)?)
                                 })
                                 .expect("messaging enabled");
 
@@ -206,7 +240,8 @@ impl Component<Msg, UserEvent> for GlobalControl {
 
                             self.exchanger
                                 .request_async(|dbg| {
-                                    Ok(command::step_into::Handler::new(dbg).handle()?)
+                                    Ok(command::step_into::Handler::new(dbg)
+                                        .handle(1, StepGranularity::Line)?)
                                 })
                                 .expect("messaging enabled");
 
@@ -220,12 +255,27 @@ impl Component<Msg, UserEvent> for GlobalControl {
 
                             self.exchanger
                                 .request_async(|dbg| {
-                                    Ok(command::step_out::Handler::new(dbg).handle()?)
+                                    Ok(command::step_out::Handler::new(dbg).handle(1).map(drop)?)
                                 })
                                 .expect("messaging enabled");
 
                             Msg::AppRunning
                         }
+                        SpecialAction::StepInstruction => {
+                            if !self.exchanger.is_messaging_enabled() {
+                                warn!(target: "tui", "try step-instruction but messaging disabled");
+                                return None;
+                            }
+
+                            self.exchanger
+                                .request_async(|dbg| {
+                                    Ok(command::step_instruction::Handler::new(dbg).handle(1)?)
+                                })
+                                .expect("messaging enabled");
+
+                            self.instruction_step = true;
+                            Msg::AppRunning
+                        }
                         _ => Msg::None,
                     }
                 } else {
@@ -234,12 +284,10 @@ impl Component<Msg, UserEvent> for GlobalControl {
             }
             Event::User(UserEvent::AsyncErrorResponse(err)) => {
                 self.exchanger.enable_messaging();
+                self.instruction_step = false;
                 Msg::ShowOkPopup(Some("Error".to_string()), err)
             }
-            Event::User(UserEvent::ProcessInstall(pid)) => {
-                self.last_seen_pid = pid;
-                Msg::None
-            }
+            Event::User(UserEvent::Mouse(mouse)) => Msg::Mouse(mouse),
             Event::User(UserEvent::Signal(sig)) => {
                 self.exchanger.enable_messaging();
                 Msg::ShowOkPopup(
@@ -247,11 +295,15 @@ impl Component<Msg, UserEvent> for GlobalControl {
                     format!("Application receive signal: {sig}"),
                 )
             }
-            Event::User(UserEvent::Breakpoint { .. })
-            | Event::User(UserEvent::Exit(_))
-            | Event::User(UserEvent::Step { .. })
-            | Event::User(UserEvent::Watchpoint { .. }) => {
+            Event::User(UserEvent::Breakpoint { file, .. })
+            | Event::User(UserEvent::Step { file, .. })
+            | Event::User(UserEvent::Watchpoint { file, .. }) => {
+                self.exchanger.enable_messaging();
+                self.switch_code_window(file.is_some())
+            }
+            Event::User(UserEvent::Exit(_)) => {
                 self.exchanger.enable_messaging();
+                self.instruction_step = false;
                 Msg::None
             }
             _ => Msg::None,
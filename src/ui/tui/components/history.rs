@@ -0,0 +1,233 @@
+use crate::debugger::register::debug::BreakCondition;
+use crate::debugger::StopSummary;
+use crate::ui;
+use crate::ui::console::hook::render_stop_event;
+use crate::ui::proto::ClientExchanger;
+use crate::ui::tui::app::port::UserEvent;
+use crate::ui::tui::config::CommonAction;
+use crate::ui::tui::utils::mstextarea::MultiSpanTextarea;
+use crate::ui::tui::utils::search::Search;
+use crate::ui::tui::utils::tab::TabWindow;
+use crate::ui::tui::{Id, Msg};
+use chrono::{DateTime, Local};
+use std::sync::Arc;
+use tuirealm::command::{Cmd, CmdResult, Direction, Position};
+use tuirealm::props::{Borders, Style, TextSpan};
+use tuirealm::tui::layout::{Alignment, Rect};
+use tuirealm::tui::widgets::BorderType;
+use tuirealm::{
+    AttrValue, Attribute, Component, Event, Frame, MockComponent, State, Sub, SubClause,
+    SubEventClause,
+};
+
+const TITLE: &str = "Stepping history";
+
+/// Debugee stops of all threads, grouped by a thread and ordered from the oldest one.
+pub struct History {
+    component: MultiSpanTextarea,
+    exchanger: Arc<ClientExchanger>,
+    rows: Vec<Vec<TextSpan>>,
+    search: Search,
+}
+
+/// Render stops into text rows: a header row for each thread and a row for each stop.
+fn render_rows(stops: &[StopSummary]) -> Vec<Vec<TextSpan>> {
+    let palette = &ui::config::current().tui_palette;
+    let mut rows = vec![];
+    for thread_stops in stops.chunk_by(|a, b| a.tid == b.tid) {
+        rows.push(vec![TextSpan::new(format!(
+            "thread #{} [tid {}]",
+            thread_stops[0].thread_num, thread_stops[0].tid
+        ))
+        .bold()]);
+
+        for (i, stop) in thread_stops.iter().enumerate() {
+            let time = DateTime::<Local>::from(stop.time).format("%H:%M:%S%.3f");
+            let mut row = vec![TextSpan::new(format!("  #{i} {time} "))];
+            if let Some(pc) = stop.pc {
+                row.push(TextSpan::new(format!("{pc} ")).fg(palette.address));
+            }
+            row.push(TextSpan::new(render_stop_event(stop)));
+            if let Some(function) = &stop.function {
+                row.push(TextSpan::new(format!(" in {function}")));
+            }
+            rows.push(row);
+        }
+    }
+    rows
+}
+
+impl History {
+    pub fn new(exchanger: Arc<ClientExchanger>) -> Self {
+        let palette = &ui::config::current().tui_palette;
+        let mut this = Self {
+            component: MultiSpanTextarea::default()
+                .borders(
+                    Borders::default()
+                        .modifiers(BorderType::Rounded)
+                        .color(palette.border),
+                )
+                .inactive(Style::default().fg(palette.inactive))
+                .highlighted_str("▶")
+                .title(TITLE, Alignment::Center)
+                .step(4),
+            exchanger,
+            rows: vec![],
+            search: Search::default(),
+        };
+        this.update_history();
+        this
+    }
+
+    pub fn subscriptions() -> Vec<Sub<Id, UserEvent>> {
+        vec![
+            Sub::new(
+                SubEventClause::User(UserEvent::Breakpoint {
+                    pc: Default::default(),
+                    num: 0,
+                    file: None,
+                    line: None,
+                    function: None,
+                }),
+                SubClause::Always,
+            ),
+            Sub::new(
+                SubEventClause::User(UserEvent::Watchpoint {
+                    pc: Default::default(),
+                    num: 0,
+                    file: None,
+                    line: None,
+                    cond: BreakCondition::DataReadsWrites,
+                    old_value: None,
+                    new_value: None,
+                    access: None,
+                    end_of_scope: false,
+                }),
+                SubClause::Always,
+            ),
+            Sub::new(
+                SubEventClause::User(UserEvent::Step {
+                    pc: Default::default(),
+                    file: None,
+                    line: None,
+                    function: None,
+                }),
+                SubClause::Always,
+            ),
+        ]
+    }
+
+    /// Fetch a stepping history of all threads from the debugger,
+    /// the newest stop becomes selected.
+    fn update_history(&mut self) {
+        let Ok(stops) = self.exchanger.request_sync(|dbg| dbg.step_history(None)) else {
+            return;
+        };
+        self.rows = render_rows(&stops);
+        self.search.update(&self.rows);
+        self.component.states.list_index = self.rows.len().saturating_sub(1);
+        self.render();
+    }
+
+    fn render(&mut self) {
+        let list_index = self.component.states.list_index;
+        self.component.text_rows(self.search.highlight(&self.rows));
+        self.component.states.list_index = list_index;
+        self.component.states.fix_list_index();
+        self.component.attr(
+            Attribute::Title,
+            AttrValue::Title((
+                self.search.title(TITLE, self.component.states.list_index),
+                Alignment::Center,
+            )),
+        );
+    }
+
+    fn goto_match(&mut self, row: Option<usize>) {
+        if let Some(row) = row {
+            self.component.states.list_index = row;
+        }
+        self.render();
+    }
+}
+
+impl MockComponent for History {
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        self.component.view(frame, area)
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.component.query(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        match attr {
+            TabWindow::SEARCH_ATTR => {
+                self.search.set_query(&value.unwrap_string(), &self.rows);
+                let current = self.component.states.list_index;
+                self.goto_match(self.search.first_from(current));
+            }
+            _ => self.component.attr(attr, value),
+        }
+    }
+
+    fn state(&self) -> State {
+        self.component.state()
+    }
+
+    fn perform(&mut self, cmd: Cmd) -> CmdResult {
+        let result = self.component.perform(cmd);
+        self.render();
+        result
+    }
+}
+
+impl Component<Msg, UserEvent> for History {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        match ev {
+            Event::Keyboard(key_event) => {
+                let keymap = &ui::config::current().tui_keymap;
+                if let Some(action) = keymap.get_common(&key_event) {
+                    match action {
+                        CommonAction::Up => {
+                            self.perform(Cmd::Move(Direction::Up));
+                        }
+                        CommonAction::Down => {
+                            self.perform(Cmd::Move(Direction::Down));
+                        }
+                        CommonAction::ScrollUp => {
+                            self.perform(Cmd::Scroll(Direction::Up));
+                        }
+                        CommonAction::ScrollDown => {
+                            self.perform(Cmd::Scroll(Direction::Down));
+                        }
+                        CommonAction::GotoBegin => {
+                            self.perform(Cmd::GoTo(Position::Begin));
+                        }
+                        CommonAction::GotoEnd => {
+                            self.perform(Cmd::GoTo(Position::End));
+                        }
+                        CommonAction::Search => return Some(Msg::SearchStart),
+                        CommonAction::SearchNext => {
+                            let current = self.component.states.list_index;
+                            self.goto_match(self.search.next(current));
+                        }
+                        CommonAction::SearchPrev => {
+                            let current = self.component.states.list_index;
+                            self.goto_match(self.search.prev(current));
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Event::User(UserEvent::Breakpoint { .. })
+            | Event::User(UserEvent::Watchpoint { .. })
+            | Event::User(UserEvent::Step { .. }) => {
+                self.exchanger.enable_messaging();
+                self.update_history();
+            }
+            _ => {}
+        };
+        Some(Msg::None)
+    }
+}
@@ -1,43 +1,112 @@
 use crate::ui;
+use crate::ui::completer::CommandCompleter;
 use crate::ui::tui::app::port::UserEvent;
 use crate::ui::tui::config::CommonAction;
 use crate::ui::tui::Msg;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
 use strum_macros::{Display, EnumString};
 use tuirealm::command::{Cmd, CmdResult, Direction, Position};
 use tuirealm::event::{Key, KeyEvent};
-use tuirealm::props::{Borders, InputType};
+use tuirealm::props::{AttrValue, Attribute, Borders, InputType};
 use tuirealm::tui::layout::Alignment;
-use tuirealm::tui::style::{Color, Style};
+use tuirealm::tui::style::Style;
 use tuirealm::tui::widgets::BorderType;
 use tuirealm::{Component, Event, MockComponent, State, StateValue};
 
-#[derive(Debug, Display, EnumString)]
+#[derive(Debug, PartialEq, Display, EnumString)]
 pub enum InputStringType {
     BreakpointAddAtLine,
     BreakpointAddAtFunction,
     BreakpointAddAtAddress,
     Watchpoint,
+    MemoryLocation,
+    MemoryByte,
+    Search,
+    Command,
 }
 
 #[derive(MockComponent)]
 pub struct Input {
     component: tui_realm_stdlib::Input,
+    /// Completer of a command bar input, shared with the console.
+    completer: Arc<Mutex<CommandCompleter>>,
 }
 
-impl Default for Input {
-    fn default() -> Self {
+impl Input {
+    pub const COMMAND_TITLE: &'static str = "Command";
+
+    pub fn new(completer: Arc<Mutex<CommandCompleter>>) -> Self {
+        let palette = &ui::config::current().tui_palette;
         Self {
             component: tui_realm_stdlib::Input::default()
                 .borders(
                     Borders::default()
                         .modifiers(BorderType::Rounded)
-                        .color(Color::LightYellow),
+                        .color(palette.border),
                 )
-                .foreground(Color::LightYellow)
+                .foreground(palette.border)
                 .input_type(InputType::Text)
                 .title("", Alignment::Left)
                 .value("")
-                .invalid_style(Style::default().fg(Color::Red)),
+                .invalid_style(Style::default().fg(palette.error)),
+            completer,
+        }
+    }
+
+    fn is_command_bar(&self) -> bool {
+        self.query(Attribute::Custom("input_data_type"))
+            .and_then(|value| InputStringType::from_str(&value.unwrap_string()).ok())
+            == Some(InputStringType::Command)
+    }
+
+    /// Complete a command in the command bar. A single candidate (or a common prefix
+    /// of all candidates) is inserted into the input, candidates are shown in the title.
+    fn complete(&mut self) {
+        let State::One(StateValue::String(line)) = self.state() else {
+            return;
+        };
+        let (pos, candidates) = self.completer.lock().unwrap().complete_line(&line);
+        let Some(first) = candidates.first() else {
+            return;
+        };
+
+        let common_prefix =
+            candidates
+                .iter()
+                .skip(1)
+                .fold(first.replacement.as_str(), |prefix, candidate| {
+                    let len = prefix
+                        .char_indices()
+                        .zip(candidate.replacement.chars())
+                        .find(|((_, c1), c2)| c1 != c2)
+                        .map(|((i, _), _)| i)
+                        .unwrap_or(prefix.len().min(candidate.replacement.len()));
+                    &prefix[..len]
+                });
+        if line.len() - pos < common_prefix.len() {
+            let completed = format!("{}{common_prefix}", &line[..pos]);
+            self.attr(Attribute::Value, AttrValue::String(completed));
+        }
+
+        let title = if candidates.len() > 1 {
+            let candidates = candidates.iter().map(|c| c.replacement.as_str());
+            format!(
+                "{}: {}",
+                Self::COMMAND_TITLE,
+                candidates.collect::<Vec<_>>().join(" ")
+            )
+        } else {
+            Self::COMMAND_TITLE.to_string()
+        };
+        self.attr(Attribute::Title, AttrValue::Title((title, Alignment::Left)));
+    }
+
+    /// Return a message with the current input value.
+    fn changed(&self) -> Msg {
+        match self.state() {
+            State::One(StateValue::String(input)) => Msg::InputChanged(input),
+            _ => Msg::None,
         }
     }
 }
@@ -45,6 +114,18 @@ impl Default for Input {
 impl Component<Msg, UserEvent> for Input {
     fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
         if let Event::Keyboard(key_event) = ev {
+            if self.is_command_bar() {
+                if key_event.code == Key::Tab {
+                    self.complete();
+                    return Some(Msg::None);
+                }
+                // candidates of a previous completion are no longer actual
+                self.attr(
+                    Attribute::Title,
+                    AttrValue::Title((Self::COMMAND_TITLE.to_string(), Alignment::Left)),
+                );
+            }
+
             let keymap = &ui::config::current().tui_keymap;
             if let Some(action) = keymap.get_common(&key_event) {
                 match action {
@@ -66,11 +147,11 @@ impl Component<Msg, UserEvent> for Input {
                     }
                     CommonAction::Delete => {
                         self.perform(Cmd::Cancel);
-                        return Some(Msg::None);
+                        return Some(self.changed());
                     }
                     CommonAction::Backspace => {
                         self.perform(Cmd::Delete);
-                        return Some(Msg::None);
+                        return Some(self.changed());
                     }
                     CommonAction::Submit => {
                         let state = self.perform(Cmd::Submit);
@@ -90,6 +171,7 @@ impl Component<Msg, UserEvent> for Input {
             } = key_event
             {
                 self.perform(Cmd::Type(ch));
+                return Some(self.changed());
             }
         };
         Some(Msg::None)
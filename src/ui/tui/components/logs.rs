@@ -1,34 +1,52 @@
 use crate::ui;
 use crate::ui::tui::app::port::UserEvent;
 use crate::ui::tui::config::CommonAction;
+use crate::ui::tui::utils::logger::TuiLogLine;
 use crate::ui::tui::utils::mstextarea::MultiSpanTextarea;
+use crate::ui::tui::utils::search::Search;
+use crate::ui::tui::utils::tab::TabWindow;
 use crate::ui::tui::{Id, Msg};
-use tuirealm::command::{Cmd, Direction, Position};
+use log::LevelFilter;
+use tuirealm::command::{Cmd, CmdResult, Direction, Position};
 use tuirealm::props::{Borders, Style, TextSpan};
-use tuirealm::tui::layout::Alignment;
-use tuirealm::tui::prelude::Color;
+use tuirealm::tui::layout::{Alignment, Rect};
 use tuirealm::tui::widgets::BorderType;
-use tuirealm::{Component, Event, MockComponent, Sub, SubClause, SubEventClause};
+use tuirealm::{
+    AttrValue, Attribute, Component, Event, Frame, MockComponent, State, Sub, SubClause,
+    SubEventClause,
+};
+
+const TITLE: &str = "Debugger logs";
+
+/// Maximum number of stored log lines, the oldest lines are dropped first.
+const MAX_LOG_LINES: usize = 10_000;
 
-#[derive(MockComponent)]
 pub struct Logs {
     component: MultiSpanTextarea,
+    logs: Vec<TuiLogLine>,
+    /// Only lines with this or more severe level are shown.
+    level: LevelFilter,
     log_view: Vec<Vec<TextSpan>>,
+    search: Search,
 }
 
 impl Default for Logs {
     fn default() -> Self {
+        let palette = &ui::config::current().tui_palette;
         Self {
+            logs: vec![],
+            level: LevelFilter::Debug,
             log_view: vec![],
+            search: Search::default(),
             component: MultiSpanTextarea::default()
                 .borders(
                     Borders::default()
                         .modifiers(BorderType::Rounded)
-                        .color(Color::LightYellow),
+                        .color(palette.border),
                 )
-                .inactive(Style::default().fg(Color::Gray))
+                .inactive(Style::default().fg(palette.inactive))
                 .highlighted_str("▶")
-                .title("Debugger logs", Alignment::Center)
+                .title(TITLE, Alignment::Center)
                 .step(4),
         }
     }
@@ -41,6 +59,87 @@ impl Logs {
             SubClause::Always,
         )]
     }
+
+    fn render(&mut self) {
+        let list_index = self.component.states.list_index;
+        self.component
+            .text_rows(self.search.highlight(&self.log_view));
+        self.component.states.list_index = list_index;
+        self.component.states.fix_list_index();
+        let title = format!("{TITLE} (level: {})", self.level.as_str().to_lowercase());
+        self.component.attr(
+            Attribute::Title,
+            AttrValue::Title((
+                self.search.title(&title, self.component.states.list_index),
+                Alignment::Center,
+            )),
+        );
+    }
+
+    /// Rebuild visible lines according to a current level filter.
+    fn update_view(&mut self) {
+        self.log_view = self
+            .logs
+            .iter()
+            .filter(|line| line.level() <= self.level)
+            .map(|line| line.to_text_spans())
+            .collect();
+        self.search.update(&self.log_view);
+    }
+
+    /// Switch level filter to the next one (from the most verbose to the least one),
+    /// after the least verbose level the most verbose one is used.
+    fn switch_level(&mut self) {
+        self.level = match self.level {
+            LevelFilter::Trace => LevelFilter::Debug,
+            LevelFilter::Debug => LevelFilter::Info,
+            LevelFilter::Info => LevelFilter::Warn,
+            LevelFilter::Warn => LevelFilter::Error,
+            LevelFilter::Error | LevelFilter::Off => LevelFilter::Trace,
+        };
+        self.update_view();
+        self.component.states.list_index = self.log_view.len();
+        self.render();
+    }
+
+    fn goto_match(&mut self, row: Option<usize>) {
+        if let Some(row) = row {
+            self.component.states.list_index = row;
+        }
+        self.render();
+    }
+}
+
+impl MockComponent for Logs {
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        self.component.view(frame, area)
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.component.query(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        match attr {
+            TabWindow::SEARCH_ATTR => {
+                self.search
+                    .set_query(&value.unwrap_string(), &self.log_view);
+                let current = self.component.states.list_index;
+                self.goto_match(self.search.first_from(current));
+            }
+            _ => self.component.attr(attr, value),
+        }
+    }
+
+    fn state(&self) -> State {
+        self.component.state()
+    }
+
+    fn perform(&mut self, cmd: Cmd) -> CmdResult {
+        let result = self.component.perform(cmd);
+        self.render();
+        result
+    }
 }
 
 impl Component<Msg, UserEvent> for Logs {
@@ -68,15 +167,28 @@ impl Component<Msg, UserEvent> for Logs {
                         CommonAction::GotoEnd => {
                             self.perform(Cmd::GoTo(Position::End));
                         }
+                        CommonAction::Toggle => self.switch_level(),
+                        CommonAction::Search => return Some(Msg::SearchStart),
+                        CommonAction::SearchNext => {
+                            let current = self.component.states.list_index;
+                            self.goto_match(self.search.next(current));
+                        }
+                        CommonAction::SearchPrev => {
+                            let current = self.component.states.list_index;
+                            self.goto_match(self.search.prev(current));
+                        }
                         _ => {}
                     }
                 }
             }
             Event::User(UserEvent::Logs(logs)) => {
-                self.log_view
-                    .extend(logs.into_iter().map(|l| l.to_text_spans()));
-                self.component.text_rows(self.log_view.clone());
-                self.component.states.list_index_at_last();
+                self.logs.extend(logs);
+                if self.logs.len() > MAX_LOG_LINES {
+                    self.logs.drain(..self.logs.len() - MAX_LOG_LINES);
+                }
+                self.update_view();
+                self.component.states.list_index = self.log_view.len();
+                self.render();
             }
             _ => {}
         };
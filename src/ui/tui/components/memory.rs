@@ -0,0 +1,352 @@
+use crate::debugger::register::debug::BreakCondition;
+use crate::ui;
+use crate::ui::tui::app::port::UserEvent;
+use crate::ui::tui::config::CommonAction;
+use crate::ui::proto::ClientExchanger;
+use crate::ui::tui::utils::mstextarea::MultiSpanTextarea;
+use crate::ui::tui::{Id, Msg};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tuirealm::command::{Cmd, CmdResult, Direction};
+use tuirealm::props::{Borders, PropPayload, PropValue, Style, TextSpan};
+use tuirealm::tui::layout::{Alignment, Rect};
+use tuirealm::tui::widgets::BorderType;
+use tuirealm::{
+    AttrValue, Attribute, Component, Event, Frame, MockComponent, State, Sub, SubClause,
+    SubEventClause,
+};
+
+const BYTES_PER_ROW: usize = 16;
+const ROWS_IN_VIEW: usize = 32;
+
+/// Memory hex-view window.
+pub struct Memory {
+    component: MultiSpanTextarea,
+    exchanger: Arc<ClientExchanger>,
+    /// Address of the first byte in view.
+    start: Option<usize>,
+    /// Memory rows, `None` if memory is unreadable.
+    rows: Vec<Option<Vec<u8>>>,
+    /// Addresses of bytes changed since previous update (after a stop or a write).
+    changed: HashSet<usize>,
+    /// Selected byte in a row.
+    column: usize,
+}
+
+impl Memory {
+    pub const GOTO_ATTR: Attribute = Attribute::Custom("memory_goto");
+    pub const UPDATE_ATTR: Attribute = Attribute::Custom("update_memory");
+
+    pub fn new(exchanger: Arc<ClientExchanger>) -> Self {
+        let palette = &ui::config::current().tui_palette;
+        let component = MultiSpanTextarea::default()
+            .borders(
+                Borders::default()
+                    .modifiers(BorderType::Rounded)
+                    .color(palette.border),
+            )
+            .inactive(Style::default().fg(palette.disabled))
+            .title("Memory", Alignment::Center)
+            .highlighted_str("▶");
+
+        let mut this = Self {
+            component,
+            exchanger,
+            start: None,
+            rows: vec![],
+            changed: HashSet::new(),
+            column: 0,
+        };
+        this.render();
+        this
+    }
+
+    /// Read memory rows starting from the current start address.
+    fn read_rows(&self) -> Vec<Option<Vec<u8>>> {
+        let Some(start) = self.start else {
+            return vec![];
+        };
+
+        let Ok(rows) = self.exchanger.request_sync(move |dbg| {
+            (0..ROWS_IN_VIEW)
+                .map(|row| {
+                    dbg.read_memory(start + row * BYTES_PER_ROW, BYTES_PER_ROW)
+                        .ok()
+                })
+                .collect::<Vec<_>>()
+        }) else {
+            return self.rows.clone();
+        };
+        rows
+    }
+
+    /// Reread memory in view. If `track_changes` is true, then bytes that differ
+    /// from the previous read are highlighted.
+    fn update(&mut self, track_changes: bool) {
+        let new_rows = self.read_rows();
+        if track_changes {
+            if let Some(start) = self.start {
+                self.changed = new_rows
+                    .iter()
+                    .zip(self.rows.iter())
+                    .enumerate()
+                    .filter_map(|(row_idx, rows)| match rows {
+                        (Some(new), Some(old)) => Some((row_idx, new, old)),
+                        _ => None,
+                    })
+                    .flat_map(|(row_idx, new, old)| {
+                        new.iter()
+                            .zip(old.iter())
+                            .enumerate()
+                            .filter(|(_, (new, old))| new != old)
+                            .map(move |(idx, _)| start + row_idx * BYTES_PER_ROW + idx)
+                    })
+                    .collect();
+            }
+        }
+        self.rows = new_rows;
+        self.render();
+    }
+
+    /// Move view to the new address.
+    fn goto(&mut self, addr: usize) {
+        self.start = Some(addr);
+        self.changed.clear();
+        self.column = 0;
+        self.rows = self.read_rows();
+        self.component.states.list_index = 1;
+        self.render();
+    }
+
+    /// Shift view by a number of rows.
+    fn shift(&mut self, rows: isize) {
+        let Some(start) = self.start else {
+            return;
+        };
+        let offset = rows * BYTES_PER_ROW as isize;
+        self.start = Some(start.saturating_add_signed(offset));
+        self.rows = self.read_rows();
+        self.render();
+    }
+
+    /// Return address of the selected byte.
+    fn selected_addr(&self) -> Option<usize> {
+        let row = self.component.states.list_index.checked_sub(1)?;
+        Some(self.start? + row * BYTES_PER_ROW + self.column)
+    }
+
+    fn render(&mut self) {
+        let palette = &ui::config::current().tui_palette;
+        let selected_row = self.component.states.list_index;
+        let mut lines = vec![vec![
+            TextSpan::new("go to address or expression...").fg(palette.highlight)
+        ]];
+
+        if let Some(start) = self.start {
+            for (row_idx, row) in self.rows.iter().enumerate() {
+                let row_addr = start + row_idx * BYTES_PER_ROW;
+                let mut line =
+                    vec![TextSpan::new(format!("{row_addr:#018x}  ")).fg(palette.address)];
+
+                let Some(row) = row else {
+                    line.push(TextSpan::new("?? ".repeat(BYTES_PER_ROW)).fg(palette.disabled));
+                    lines.push(line);
+                    continue;
+                };
+
+                for (idx, byte) in row.iter().enumerate() {
+                    let mut span = TextSpan::new(format!("{byte:02x}"));
+                    if self.changed.contains(&(row_addr + idx)) {
+                        span = span.fg(palette.current_line);
+                    }
+                    if selected_row == row_idx + 1 && self.column == idx {
+                        span = span.bg(palette.highlight).fg(palette.selection_text);
+                    }
+                    line.push(span);
+                    line.push(TextSpan::new(" "));
+                }
+
+                let ascii: String = row
+                    .iter()
+                    .map(|b| {
+                        if b.is_ascii_graphic() {
+                            *b as char
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect();
+                line.push(TextSpan::new(format!(" {ascii}")).fg(palette.value));
+                lines.push(line);
+            }
+        }
+
+        self.component.text_rows(lines);
+        self.component.states.list_index = selected_row;
+        self.component.states.fix_list_index();
+    }
+
+    fn move_up(&mut self, rows: usize) {
+        let idx = self.component.states.list_index;
+        if idx == 0 {
+            return;
+        }
+        if idx > rows {
+            self.component.states.list_index = idx - rows;
+        } else {
+            // first row is a "go to" button, scroll memory instead of moving on it
+            self.shift(-(rows as isize - idx as isize + 1));
+            self.component.states.list_index = 1;
+        }
+        self.render();
+    }
+
+    fn move_down(&mut self, rows: usize) {
+        let idx = self.component.states.list_index;
+        if idx + rows <= self.rows.len() {
+            self.component.states.list_index = idx + rows;
+        } else if self.start.is_some() {
+            self.shift((idx + rows - self.rows.len()) as isize);
+            self.component.states.list_index = self.rows.len();
+        }
+        self.render();
+    }
+
+    pub fn subscriptions() -> Vec<Sub<Id, UserEvent>> {
+        vec![
+            Sub::new(
+                SubEventClause::User(UserEvent::Breakpoint {
+                    pc: Default::default(),
+                    num: 0,
+                    file: None,
+                    line: None,
+                    function: None,
+                }),
+                SubClause::Always,
+            ),
+            Sub::new(
+                SubEventClause::User(UserEvent::Watchpoint {
+                    pc: Default::default(),
+                    num: 0,
+                    file: None,
+                    line: None,
+                    cond: BreakCondition::DataReadsWrites,
+                    old_value: None,
+                    new_value: None,
+                    access: None,
+                    end_of_scope: false,
+                }),
+                SubClause::Always,
+            ),
+            Sub::new(
+                SubEventClause::User(UserEvent::Step {
+                    pc: Default::default(),
+                    file: None,
+                    line: None,
+                    function: None,
+                }),
+                SubClause::Always,
+            ),
+        ]
+    }
+}
+
+impl MockComponent for Memory {
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        self.component.view(frame, area)
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.component.query(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        match attr {
+            Self::GOTO_ATTR => {
+                if let AttrValue::Payload(PropPayload::One(PropValue::Usize(addr))) = value {
+                    self.goto(addr);
+                }
+            }
+            Self::UPDATE_ATTR => self.update(true),
+            _ => self.component.attr(attr, value),
+        }
+    }
+
+    fn state(&self) -> State {
+        self.component.state()
+    }
+
+    fn perform(&mut self, cmd: Cmd) -> CmdResult {
+        match cmd {
+            Cmd::Scroll(Direction::Up) => self.move_up(ROWS_IN_VIEW / 2),
+            Cmd::Scroll(Direction::Down) => self.move_down(ROWS_IN_VIEW / 2),
+            _ => return self.component.perform(cmd),
+        }
+        CmdResult::None
+    }
+}
+
+impl Component<Msg, UserEvent> for Memory {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        match ev {
+            Event::Keyboard(key_event) => {
+                let keymap = &ui::config::current().tui_keymap;
+                if let Some(action) = keymap.get_common(&key_event) {
+                    match action {
+                        CommonAction::Up => self.move_up(1),
+                        CommonAction::Down => self.move_down(1),
+                        CommonAction::ScrollUp => {
+                            self.perform(Cmd::Scroll(Direction::Up));
+                        }
+                        CommonAction::ScrollDown => {
+                            self.perform(Cmd::Scroll(Direction::Down));
+                        }
+                        CommonAction::GotoBegin => {
+                            self.component.states.list_index_at_first();
+                            self.render();
+                        }
+                        CommonAction::GotoEnd => {
+                            self.component.states.list_index_at_last();
+                            self.render();
+                        }
+                        CommonAction::Left => {
+                            self.column = self.column.saturating_sub(1);
+                            self.render();
+                        }
+                        CommonAction::Right => {
+                            self.column = (self.column + 1).min(BYTES_PER_ROW - 1);
+                            self.render();
+                        }
+                        CommonAction::Submit => {
+                            if !self.exchanger.is_messaging_enabled() {
+                                return Some(Msg::None);
+                            }
+                            return match self.selected_addr() {
+                                None => Some(Msg::MemoryGoTo),
+                                Some(addr) => Some(Msg::MemoryWrite(addr)),
+                            };
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Event::User(UserEvent::Breakpoint { .. })
+            | Event::User(UserEvent::Step { .. })
+            | Event::User(UserEvent::Watchpoint { .. }) => {
+                self.update(true);
+            }
+            _ => {}
+        }
+        Some(Msg::None)
+    }
+}
+
+/// Parse a byte value in hex format (with or without `0x` prefix).
+pub fn parse_byte(input: &str) -> Option<u8> {
+    let input = input.trim();
+    let hex = input
+        .strip_prefix("0x")
+        .or(input.strip_prefix("0X"))
+        .unwrap_or(input);
+    u8::from_str_radix(hex, 16).ok()
+}
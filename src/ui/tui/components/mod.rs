@@ -1,8 +1,10 @@
 pub mod asm;
 pub mod breakpoint;
 pub mod control;
+pub mod history;
 pub mod input;
 pub mod logs;
+pub mod memory;
 pub mod oracle;
 pub mod output;
 pub mod popup;
@@ -2,45 +2,82 @@ use crate::ui;
 use crate::ui::tui::app::port::UserEvent;
 use crate::ui::tui::config::CommonAction;
 use crate::ui::tui::output::OutputLine;
+use crate::ui::tui::utils::mstextarea::MultiSpanTextarea;
+use crate::ui::tui::utils::search::Search;
+use crate::ui::tui::utils::tab::TabWindow;
 use crate::ui::tui::{Id, Msg};
-use tui_realm_stdlib::Textarea;
 use tuirealm::command::{Cmd, CmdResult, Direction, Position};
-use tuirealm::props::{Borders, PropPayload, PropValue, Style, TextSpan};
-use tuirealm::tui::layout::Alignment;
-use tuirealm::tui::prelude::Color;
+use tuirealm::props::{Borders, Style, TextSpan};
+use tuirealm::tui::layout::{Alignment, Rect};
 use tuirealm::tui::widgets::BorderType;
 use tuirealm::{
-    AttrValue, Attribute, Component, Event, MockComponent, Sub, SubClause, SubEventClause,
+    AttrValue, Attribute, Component, Event, Frame, MockComponent, State, Sub, SubClause,
+    SubEventClause,
 };
 
-#[derive(MockComponent)]
+const TITLE: &str = "Program output";
+
 pub struct Output {
-    component: Textarea,
+    component: MultiSpanTextarea,
+    rows: Vec<Vec<TextSpan>>,
+    search: Search,
 }
 
 impl Output {
     pub fn new(output: &[OutputLine]) -> Self {
-        let rows: Vec<_> = output
+        let palette = &ui::config::current().tui_palette;
+        let component = MultiSpanTextarea::default()
+            .borders(
+                Borders::default()
+                    .modifiers(BorderType::Rounded)
+                    .color(palette.border),
+            )
+            .inactive(Style::default().fg(palette.inactive))
+            .title(TITLE, Alignment::Center)
+            .highlighted_str("▶")
+            .step(4);
+
+        let mut this = Self {
+            component,
+            rows: Self::make_rows(output),
+            search: Search::default(),
+        };
+        this.render();
+        this
+    }
+
+    fn make_rows(output: &[OutputLine]) -> Vec<Vec<TextSpan>> {
+        let palette = &ui::config::current().tui_palette;
+        output
             .iter()
             .map(|line| match line {
-                OutputLine::Out(text) => TextSpan::new(text),
-                OutputLine::Err(err_text) => TextSpan::new(err_text).fg(Color::LightRed),
+                OutputLine::Out(text) => vec![TextSpan::new(text)],
+                OutputLine::Err(err_text) => {
+                    vec![TextSpan::new(err_text).fg(palette.current_line)]
+                }
             })
-            .collect();
-
-        Self {
-            component: Textarea::default()
-                .borders(
-                    Borders::default()
-                        .modifiers(BorderType::Rounded)
-                        .color(Color::LightYellow),
-                )
-                .inactive(Style::default().fg(Color::Gray))
-                .title("Program output", Alignment::Center)
-                .highlighted_str("▶")
-                .step(4)
-                .text_rows(&rows),
+            .collect()
+    }
+
+    fn render(&mut self) {
+        let list_index = self.component.states.list_index;
+        self.component.text_rows(self.search.highlight(&self.rows));
+        self.component.states.list_index = list_index;
+        self.component.states.fix_list_index();
+        self.component.attr(
+            Attribute::Title,
+            AttrValue::Title((
+                self.search.title(TITLE, self.component.states.list_index),
+                Alignment::Center,
+            )),
+        );
+    }
+
+    fn goto_match(&mut self, row: Option<usize>) {
+        if let Some(row) = row {
+            self.component.states.list_index = row;
         }
+        self.render();
     }
 
     pub fn subscriptions() -> Vec<Sub<Id, UserEvent>> {
@@ -51,6 +88,37 @@ impl Output {
     }
 }
 
+impl MockComponent for Output {
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        self.component.view(frame, area)
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.component.query(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        match attr {
+            TabWindow::SEARCH_ATTR => {
+                self.search.set_query(&value.unwrap_string(), &self.rows);
+                let current = self.component.states.list_index;
+                self.goto_match(self.search.first_from(current));
+            }
+            _ => self.component.attr(attr, value),
+        }
+    }
+
+    fn state(&self) -> State {
+        self.component.state()
+    }
+
+    fn perform(&mut self, cmd: Cmd) -> CmdResult {
+        let result = self.component.perform(cmd);
+        self.render();
+        result
+    }
+}
+
 impl Component<Msg, UserEvent> for Output {
     fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
         let _ = match ev {
@@ -76,6 +144,15 @@ impl Component<Msg, UserEvent> for Output {
                         CommonAction::GotoEnd => {
                             self.perform(Cmd::GoTo(Position::End));
                         }
+                        CommonAction::Search => return Some(Msg::SearchStart),
+                        CommonAction::SearchNext => {
+                            let current = self.component.states.list_index;
+                            self.goto_match(self.search.next(current));
+                        }
+                        CommonAction::SearchPrev => {
+                            let current = self.component.states.list_index;
+                            self.goto_match(self.search.prev(current));
+                        }
                         _ => {}
                     }
                 }
@@ -83,21 +160,10 @@ impl Component<Msg, UserEvent> for Output {
             }
 
             Event::User(UserEvent::GotOutput(output, _)) => {
-                let rows: Vec<_> = output
-                    .into_iter()
-                    .map(|line| match line {
-                        OutputLine::Out(text) => TextSpan::new(text),
-                        OutputLine::Err(err_text) => TextSpan::new(err_text).fg(Color::LightRed),
-                    })
-                    .collect();
-
-                self.component.attr(
-                    Attribute::Text,
-                    AttrValue::Payload(PropPayload::Vec(
-                        rows.iter().cloned().map(PropValue::TextSpan).collect(),
-                    )),
-                );
-                self.component.states.list_index_at_last();
+                self.rows = Self::make_rows(&output);
+                self.search.update(&self.rows);
+                self.component.states.list_index = self.rows.len();
+                self.render();
 
                 CmdResult::None
             }
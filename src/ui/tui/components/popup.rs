@@ -8,7 +8,7 @@ use tuirealm::command::{Cmd, CmdResult};
 use tuirealm::props::{BorderSides, Borders, PropPayload, PropValue};
 use tuirealm::tui::layout::{Alignment, Constraint, Direction, Layout, Rect};
 use tuirealm::tui::prelude::Style;
-use tuirealm::tui::style::{Color, Stylize};
+use tuirealm::tui::style::Stylize;
 use tuirealm::tui::widgets;
 use tuirealm::tui::widgets::{Block, BorderType, Clear, Paragraph};
 use tuirealm::{
@@ -29,10 +29,11 @@ pub struct Popup {
 
 impl Default for Popup {
     fn default() -> Self {
+        let palette = &ui::config::current().tui_palette;
         let buttons = Radio::default()
             .borders(Borders::default().sides(BorderSides::NONE))
-            .foreground(Color::LightGreen)
-            .background(Color::Black)
+            .foreground(palette.popup)
+            .background(palette.popup_background)
             .rewind(false);
 
         Self {
@@ -84,10 +85,11 @@ impl Popup {
 
 impl MockComponent for Popup {
     fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let palette = &ui::config::current().tui_palette;
         let mut block = Block::default()
             .borders(widgets::Borders::TOP | widgets::Borders::RIGHT | widgets::Borders::LEFT)
             .border_type(BorderType::Rounded)
-            .border_style(Style::default().fg(Color::LightGreen));
+            .border_style(Style::default().fg(palette.popup));
         if let Some(title) = self.query(Attribute::Title) {
             if self.mode == OpMode::Ok {
                 block = block.title(title.unwrap_string());
@@ -132,15 +134,15 @@ impl MockComponent for Popup {
             .unwrap_string();
 
         let paragraph = Paragraph::new(text)
-            .style(Style::default().bg(Color::Black))
+            .style(Style::default().bg(palette.popup_background))
             .block(block)
             .alignment(Alignment::Center);
 
         let buttons_block = Block::default()
             .borders(widgets::Borders::BOTTOM | widgets::Borders::RIGHT | widgets::Borders::LEFT)
-            .bg(Color::Black)
+            .bg(palette.popup_background)
             .border_type(BorderType::Rounded)
-            .border_style(Style::default().fg(Color::LightGreen));
+            .border_style(Style::default().fg(palette.popup));
 
         let rb_layout = Layout::default()
             .direction(Direction::Horizontal)
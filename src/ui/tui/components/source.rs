@@ -1,29 +1,32 @@
 use crate::debugger::register::debug::BreakCondition;
+use crate::ui::command;
+use crate::ui::command::r#break::{BreakpointIdentity, Command as BreakpointCommand};
+use crate::ui::proto::ClientExchanger;
 use crate::ui::short::Abbreviator;
+use crate::ui::source;
 use crate::ui::syntax;
 use crate::ui::syntax::StylizedLine;
 use crate::ui::tui::app::port::UserEvent;
 use crate::ui::tui::config::CommonAction;
-use crate::ui::tui::proto::ClientExchanger;
 use crate::ui::tui::utils::mstextarea::MultiSpanTextarea;
+use crate::ui::tui::utils::search::Search;
 use crate::ui::tui::utils::syntect::into_text_span;
+use crate::ui::tui::utils::tab::TabWindow;
 use crate::ui::tui::{Id, Msg};
 use crate::{ui, weak_error};
 use log::warn;
 use std::collections::hash_map::Entry;
-use std::collections::HashMap;
-use std::fs;
-use std::io::Read;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use syntect::util::LinesWithEndings;
-use tuirealm::command::{Cmd, Direction, Position};
+use tuirealm::command::{Cmd, CmdResult, Direction, Position};
 use tuirealm::props::{Borders, Style, TextSpan};
-use tuirealm::tui::layout::Alignment;
-use tuirealm::tui::prelude::Color;
+use tuirealm::tui::layout::{Alignment, Rect};
 use tuirealm::tui::widgets::BorderType;
 use tuirealm::{
-    AttrValue, Attribute, Component, Event, MockComponent, Sub, SubClause, SubEventClause,
+    AttrValue, Attribute, Component, Event, Frame, MockComponent, State, Sub, SubClause,
+    SubEventClause,
 };
 
 #[derive(Default)]
@@ -37,17 +40,14 @@ impl FileLinesCache {
         let lines = match self.files.entry(file.to_path_buf()) {
             Entry::Occupied(o) => o.into_mut(),
             Entry::Vacant(v) => {
-                let mut file = match fs::File::open(file) {
-                    Ok(f) => f,
+                let source_code = match source::read_source(file, None) {
+                    Ok(source_code) => source_code,
                     Err(e) => {
                         warn!("error while open {file:?}: {e}");
                         return Ok(&self.empty_file);
                     }
                 };
 
-                let mut source_code = String::new();
-                file.read_to_string(&mut source_code)?;
-
                 let syntax_renderer = syntax::rust_syntax_renderer();
                 let mut line_renderer = syntax_renderer.line_renderer();
 
@@ -76,13 +76,61 @@ impl FileLinesCache {
     }
 }
 
-#[derive(MockComponent)]
+/// Mapping between rows of a source window and breakpoint places of a file.
+/// Row `n` shows a line `n + 1`.
+#[derive(Default)]
+struct BreakpointPlaces {
+    /// Lines where a breakpoint can be set (lines with statements).
+    stmt_lines: BTreeSet<u64>,
+    /// Numbers and enabled flags of breakpoints by their lines.
+    breakpoints: BTreeMap<u64, Vec<(u32, bool)>>,
+}
+
+impl BreakpointPlaces {
+    /// Return a line of a breakpoint place for a row. Like a debugger does when a breakpoint
+    /// is set by a line, a row without statements resolves into the next line.
+    fn line_for_row(&self, row: usize) -> Option<u64> {
+        let line = row as u64 + 1;
+        if self.breakpoints.contains_key(&line) {
+            return Some(line);
+        }
+        self.stmt_lines.range(line..=line + 1).next().copied()
+    }
+
+    /// Return numbers of breakpoints at a line.
+    fn breakpoints_at(&self, line: u64) -> Vec<u32> {
+        self.breakpoints
+            .get(&line)
+            .map(|brkpts| brkpts.iter().map(|(number, _)| *number).collect())
+            .unwrap_or_default()
+    }
+
+    /// Return `Some(true)` if there is an enabled breakpoint at a line, `Some(false)` if all
+    /// breakpoints at a line are disabled and `None` if there are no breakpoints.
+    fn enabled_at(&self, line: u64) -> Option<bool> {
+        self.breakpoints
+            .get(&line)
+            .map(|brkpts| brkpts.iter().any(|(_, enabled)| *enabled))
+    }
+}
+
 pub struct Source {
     component: MultiSpanTextarea,
     file_cache: FileLinesCache,
+    exchanger: Arc<ClientExchanger>,
+    /// File shown in a window.
+    file: Option<PathBuf>,
+    /// Line where a debugee is stopped.
+    current_line: Option<u64>,
+    places: BreakpointPlaces,
+    rows: Vec<Vec<TextSpan>>,
+    search: Search,
 }
 
 impl Source {
+    /// Move a cursor to a line, attribute value is a line number.
+    pub const GOTO_LINE_ATTR: Attribute = Attribute::Custom("source_goto_line");
+
     fn get_title(mb_file: Option<&Path>) -> String {
         if let Some(file) = mb_file {
             let abbreviator = Abbreviator::new("/", "/..", 70);
@@ -97,6 +145,7 @@ impl Source {
     }
 
     pub fn new(exchanger: Arc<ClientExchanger>) -> anyhow::Result<Self> {
+        let palette = &ui::config::current().tui_palette;
         let mb_threads = exchanger
             .request_sync(|dbg| dbg.thread_state())
             .expect("messaging enabled")
@@ -112,16 +161,22 @@ impl Source {
             .borders(
                 Borders::default()
                     .modifiers(BorderType::Rounded)
-                    .color(Color::LightYellow),
+                    .color(palette.border),
             )
             .title("Program source code", Alignment::Center)
             .step(4)
-            .inactive(Style::default().fg(Color::Gray))
+            .inactive(Style::default().fg(palette.inactive))
             .highlighted_str("▶");
 
         let mut this = Self {
             file_cache: cache,
             component,
+            exchanger,
+            file: None,
+            current_line: None,
+            places: BreakpointPlaces::default(),
+            rows: vec![],
+            search: Search::default(),
         };
 
         if let Some(place) = mb_place_in_focus {
@@ -132,30 +187,140 @@ impl Source {
     }
 
     fn update_source_view(&mut self, file: &Path, mb_line_num: Option<u64>) -> anyhow::Result<()> {
-        self.component.attr(
-            Attribute::Title,
-            AttrValue::Title((Self::get_title(Some(file)), Alignment::Center)),
-        );
+        if self.file.as_deref() != Some(file) {
+            self.file = Some(file.to_path_buf());
+            self.update_places();
+        }
+        self.current_line = mb_line_num;
+        self.update_rows()?;
 
-        let lines = self
+        if let Some(line) = mb_line_num {
+            self.component.states.list_index = (line as usize).saturating_sub(1);
+        }
+        self.render();
+        Ok(())
+    }
+
+    /// Read lines with statements and breakpoints of a shown file from a debugger.
+    fn update_places(&mut self) {
+        let Some(file) = self.file.clone() else {
+            return;
+        };
+        let Ok(places) = self.exchanger.request_sync(move |dbg| {
+            let file_tpl = file.to_string_lossy();
+            let mut places = BreakpointPlaces {
+                stmt_lines: dbg
+                    .breakpoint_lines(&file_tpl)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .collect(),
+                ..Default::default()
+            };
+            for brkpt in dbg.breakpoints_snapshot() {
+                if let Some(place) = brkpt.place.filter(|place| place.file == file) {
+                    places
+                        .breakpoints
+                        .entry(place.line_number)
+                        .or_default()
+                        .push((brkpt.number, brkpt.enabled));
+                }
+            }
+            places
+        }) else {
+            return;
+        };
+        self.places = places;
+    }
+
+    /// Build window rows from source lines, breakpoint markers and a current line.
+    fn update_rows(&mut self) -> anyhow::Result<()> {
+        let palette = &ui::config::current().tui_palette;
+        let Some(file) = self.file.as_deref() else {
+            self.rows = vec![];
+            return Ok(());
+        };
+
+        self.rows = self
             .file_cache
             .lines(file)?
             .iter()
-            .cloned()
             .enumerate()
-            .map(|(i, mut line)| {
-                if Some((i + 1) as u64) == mb_line_num {
-                    line.iter_mut().for_each(|text| text.fg = Color::LightRed)
+            .map(|(i, line)| {
+                let mut row = Vec::with_capacity(line.len() + 1);
+                // gutter marker shows breakpoints at a line
+                let marker = match self.places.enabled_at((i + 1) as u64) {
+                    Some(true) => TextSpan::new("●").fg(palette.enabled),
+                    Some(false) => TextSpan::new("○").fg(palette.disabled),
+                    None => TextSpan::new(" "),
+                };
+                row.push(marker);
+                row.extend(line.iter().cloned());
+                if Some((i + 1) as u64) == self.current_line {
+                    row.iter_mut()
+                        .skip(1)
+                        .for_each(|text| text.fg = palette.current_line)
                 }
-                line
+                row
             })
             .collect();
-        self.component.text_rows(lines);
+        self.search.update(&self.rows);
+        Ok(())
+    }
 
-        if let Some(line) = mb_line_num {
-            self.component.states.list_index = (line as usize).saturating_sub(1);
+    fn render(&mut self) {
+        let list_index = self.component.states.list_index;
+        self.component.text_rows(self.search.highlight(&self.rows));
+        self.component.states.list_index = list_index;
+        self.component.states.fix_list_index();
+        let title = Self::get_title(self.file.as_deref());
+        self.component.attr(
+            Attribute::Title,
+            AttrValue::Title((
+                self.search.title(&title, self.component.states.list_index),
+                Alignment::Center,
+            )),
+        );
+    }
+
+    fn goto_row(&mut self, row: Option<usize>) {
+        if let Some(row) = row {
+            self.component.states.list_index = row;
+        }
+        self.render();
+    }
+
+    /// Set a breakpoint at a line under the cursor or remove breakpoints from it.
+    fn toggle_breakpoint(&mut self) -> Msg {
+        let Some(file) = self.file.clone() else {
+            return Msg::None;
+        };
+        let row = self.component.states.list_index;
+        let line = self.places.line_for_row(row).unwrap_or(row as u64 + 1);
+        let numbers = self.places.breakpoints_at(line);
+
+        let result = self
+            .exchanger
+            .request_sync(move |dbg| -> anyhow::Result<()> {
+                let mut handler = command::r#break::Handler::new(dbg);
+                if numbers.is_empty() {
+                    let file = file.to_string_lossy().to_string();
+                    handler.handle(&BreakpointCommand::Add(BreakpointIdentity::Line(
+                        file, line,
+                    )))?;
+                }
+                for number in numbers {
+                    handler.handle(&BreakpointCommand::Remove(BreakpointIdentity::Number(
+                        number,
+                    )))?;
+                }
+                Ok(())
+            });
+        match result {
+            // breakpoint markers are updated together with a breakpoint list
+            Ok(Ok(())) => Msg::UpdateBreakpointList,
+            Ok(Err(e)) => Msg::ShowOkPopup(Some("Error".to_string()), e.to_string()),
+            Err(_) => Msg::None,
         }
-        Ok(())
     }
 
     pub fn subscriptions() -> Vec<Sub<Id, UserEvent>> {
@@ -179,6 +344,7 @@ impl Source {
                     cond: BreakCondition::DataReadsWrites,
                     old_value: None,
                     new_value: None,
+                    access: None,
                     end_of_scope: false,
                 }),
                 SubClause::Always,
@@ -197,6 +363,46 @@ impl Source {
     }
 }
 
+impl MockComponent for Source {
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        self.component.view(frame, area)
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.component.query(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        match attr {
+            TabWindow::SEARCH_ATTR => {
+                self.search.set_query(&value.unwrap_string(), &self.rows);
+                let current = self.component.states.list_index;
+                self.goto_row(self.search.first_from(current));
+            }
+            Self::GOTO_LINE_ATTR => {
+                let line = value.unwrap_number();
+                self.goto_row(Some((line as usize).saturating_sub(1)));
+            }
+            Attribute::Custom("update_breakpoints") => {
+                self.update_places();
+                weak_error!(self.update_rows());
+                self.render();
+            }
+            _ => self.component.attr(attr, value),
+        }
+    }
+
+    fn state(&self) -> State {
+        self.component.state()
+    }
+
+    fn perform(&mut self, cmd: Cmd) -> CmdResult {
+        let result = self.component.perform(cmd);
+        self.render();
+        result
+    }
+}
+
 impl Component<Msg, UserEvent> for Source {
     fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
         match ev {
@@ -222,6 +428,16 @@ impl Component<Msg, UserEvent> for Source {
                         CommonAction::GotoEnd => {
                             self.perform(Cmd::GoTo(Position::End));
                         }
+                        CommonAction::Toggle => return Some(self.toggle_breakpoint()),
+                        CommonAction::Search => return Some(Msg::SearchStart),
+                        CommonAction::SearchNext => {
+                            let current = self.component.states.list_index;
+                            self.goto_row(self.search.next(current));
+                        }
+                        CommonAction::SearchPrev => {
+                            let current = self.component.states.list_index;
+                            self.goto_row(self.search.prev(current));
+                        }
                         _ => {}
                     }
                 }
@@ -234,10 +450,41 @@ impl Component<Msg, UserEvent> for Source {
                 }
             }
             Event::User(UserEvent::Exit { .. }) => {
-                self.component.text_rows(vec![]);
+                self.file = None;
+                self.current_line = None;
+                self.rows = vec![];
+                self.search.update(&self.rows);
+                self.render();
             }
             _ => {}
         };
         Some(Msg::None)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_breakpoint_places() {
+        let places = BreakpointPlaces {
+            stmt_lines: BTreeSet::from([3, 4, 7]),
+            breakpoints: BTreeMap::from([(4, vec![(1, true)]), (9, vec![(2, false)])]),
+        };
+
+        // row 2 is a line 3
+        assert_eq!(places.line_for_row(2), Some(3));
+        // line 6 has no statements, line 7 is used instead
+        assert_eq!(places.line_for_row(5), Some(7));
+        assert_eq!(places.line_for_row(0), None);
+        // a breakpoint line is resolved even if it has no statements (a file is changed)
+        assert_eq!(places.line_for_row(8), Some(9));
+
+        assert_eq!(places.breakpoints_at(4), vec![1]);
+        assert!(places.breakpoints_at(3).is_empty());
+        assert_eq!(places.enabled_at(4), Some(true));
+        assert_eq!(places.enabled_at(9), Some(false));
+        assert_eq!(places.enabled_at(3), None);
+    }
+}
@@ -1,40 +1,41 @@
 use crate::debugger::register::debug::BreakCondition;
+use crate::debugger::Pid;
 use crate::ui;
+use crate::ui::proto::DebuggerEvent;
+use crate::ui::status::DebugeeStatus;
 use crate::ui::tui::app::port::UserEvent;
 use crate::ui::tui::config::{SpecialAction, WrappedKeyEvent};
 use crate::ui::tui::{Id, Msg};
 use itertools::Itertools;
-use nix::sys::signal::Signal;
 use tui_realm_stdlib::Container;
 use tuirealm::command::{Cmd, CmdResult};
 use tuirealm::props::{Borders, Layout, PropPayload, PropValue, TextSpan};
 use tuirealm::tui::layout::{Alignment, Rect};
-use tuirealm::tui::style::Color;
 use tuirealm::tui::widgets::BorderType;
 use tuirealm::{
     AttrValue, Attribute, Component, Event, Frame, MockComponent, State, Sub, SubClause,
     SubEventClause,
 };
 
+/// Attribute that marks a debugee as running, see [`Status::running_attrs`].
+const RUNNING_ATTR: &str = "running";
+
 pub struct Status {
     component: Container,
+    status: DebugeeStatus,
 }
 
 impl Status {
-    pub fn new(app_already_run: bool) -> Self {
-        let initial_state = if app_already_run {
-            TextSpan::new("stopped").fg(Color::Red)
-        } else {
-            TextSpan::new("not running").fg(Color::Red)
-        };
+    pub fn new(status: DebugeeStatus) -> Self {
+        let palette = &ui::config::current().tui_palette;
 
         let app_state = tui_realm_stdlib::Paragraph::default()
-            .text(&[initial_state])
+            .text(&[TextSpan::new(status.to_string()).fg(palette.error)])
             .alignment(Alignment::Center)
             .title("Process", Alignment::Center)
             .borders(
                 Borders::default()
-                    .color(Color::White)
+                    .color(palette.status)
                     .modifiers(BorderType::Rounded),
             );
 
@@ -61,12 +62,12 @@ impl Status {
         );
 
         let help = tui_realm_stdlib::Paragraph::default()
-            .text(&[TextSpan::new(keymap_help).fg(Color::Green).bold()])
+            .text(&[TextSpan::new(keymap_help).fg(palette.value).bold()])
             .alignment(Alignment::Left)
             .title("Help", Alignment::Center)
             .borders(
                 Borders::default()
-                    .color(Color::White)
+                    .color(palette.status)
                     .modifiers(BorderType::Rounded),
             );
 
@@ -77,21 +78,39 @@ impl Status {
                         .direction(tuirealm::tui::layout::Direction::Horizontal)
                         .constraints(
                             [
-                                tuirealm::tui::layout::Constraint::Percentage(80),
-                                tuirealm::tui::layout::Constraint::Percentage(20),
+                                tuirealm::tui::layout::Constraint::Percentage(55),
+                                tuirealm::tui::layout::Constraint::Percentage(45),
                             ]
                             .as_ref(),
                         ),
                 )
                 .children(vec![Box::new(help), Box::new(app_state)]),
+            status,
         }
     }
 
+    /// Attribute and value that mark a debugee as running.
+    pub fn running_attrs() -> (Attribute, AttrValue) {
+        (Attribute::Custom(RUNNING_ATTR), AttrValue::Flag(true))
+    }
+
+    fn render_status(&mut self) {
+        let palette = &ui::config::current().tui_palette;
+        self.component.children[1].attr(
+            Attribute::Text,
+            AttrValue::Payload(PropPayload::Vec(vec![PropValue::TextSpan(
+                TextSpan::new(self.status.to_string()).fg(palette.error),
+            )])),
+        )
+    }
+
     pub fn subscriptions() -> Vec<Sub<Id, UserEvent>> {
         vec![
             Sub::new(
-                // concrete signal doesn't meter
-                SubEventClause::User(UserEvent::Signal(Signal::SIGUSR2)),
+                // concrete event doesn't meter
+                SubEventClause::User(UserEvent::Debugger(DebuggerEvent::ProcessInstall(
+                    Pid::from_raw(0),
+                ))),
                 SubClause::Always,
             ),
             Sub::new(
@@ -113,21 +132,11 @@ impl Status {
                     cond: BreakCondition::DataReadsWrites,
                     old_value: None,
                     new_value: None,
+                    access: None,
                     end_of_scope: false,
                 }),
                 SubClause::Always,
             ),
-            Sub::new(
-                SubEventClause::User(UserEvent::Step {
-                    pc: Default::default(),
-                    file: None,
-                    line: None,
-                    function: None,
-                }),
-                SubClause::Always,
-            ),
-            // concrete code doesn't meter
-            Sub::new(SubEventClause::User(UserEvent::Exit(0)), SubClause::Always),
         ]
     }
 }
@@ -142,6 +151,11 @@ impl MockComponent for Status {
     }
 
     fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        if attr == Attribute::Custom(RUNNING_ATTR) {
+            self.status.set_running();
+            self.render_status();
+            return;
+        }
         self.component.children[1].attr(attr, value)
     }
 
@@ -156,41 +170,18 @@ impl MockComponent for Status {
 
 impl Component<Msg, UserEvent> for Status {
     fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
-        let mut set_text_fn = |text: &str| {
-            self.attr(
-                Attribute::Text,
-                AttrValue::Payload(PropPayload::Vec(vec![PropValue::TextSpan(
-                    TextSpan::new(text).fg(Color::Red),
-                )])),
-            )
-        };
-
         match ev {
             Event::User(user_event) => match user_event {
-                UserEvent::Breakpoint { num, .. } => {
-                    set_text_fn(&format!("stopped at breakpoint #{num}"));
-                    Some(Msg::None)
-                }
-                UserEvent::Watchpoint {
-                    num, end_of_scope, ..
-                } => {
-                    set_text_fn(&format!("stopped at watchpoint #{num}"));
-                    end_of_scope
-                        .then_some(Msg::UpdateBreakpointList)
-                        .or(Some(Msg::None))
-                }
-                UserEvent::Step { .. } => {
-                    set_text_fn("stopped");
-                    Some(Msg::None)
-                }
-                UserEvent::Signal(_) => {
-                    set_text_fn("stopped at signal");
-                    Some(Msg::None)
-                }
-                UserEvent::Exit(_) => {
-                    set_text_fn("finished");
+                UserEvent::Debugger(event) => {
+                    self.status.apply(&event);
+                    self.render_status();
                     Some(Msg::None)
                 }
+                // hit counters are changed
+                UserEvent::Breakpoint { .. } => Some(Msg::UpdateBreakpointList),
+                UserEvent::Watchpoint { end_of_scope, .. } => end_of_scope
+                    .then_some(Msg::UpdateBreakpointList)
+                    .or(Some(Msg::None)),
                 _ => None,
             },
             _ => None,
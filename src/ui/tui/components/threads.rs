@@ -1,30 +1,41 @@
 use crate::debugger::register::debug::BreakCondition;
+use crate::debugger::Signal;
+use crate::debugger::{StopType, TraceeStatus};
 use crate::ui;
 use crate::ui::command::thread::ExecutionResult as ThreadResult;
+use crate::ui::proto::ClientExchanger;
 use crate::ui::syntax::StylizedLine;
 use crate::ui::tui::app::port::UserEvent;
 use crate::ui::tui::config::CommonAction;
-use crate::ui::tui::proto::ClientExchanger;
 use crate::ui::tui::utils::syntect::into_text_span;
 use crate::ui::tui::{Id, Msg};
 use crate::ui::{command, syntax};
-use nix::sys::signal::Signal;
 use std::sync::Arc;
 use tui_realm_treeview::{Node, Tree, TreeView, TREE_CMD_CLOSE, TREE_CMD_OPEN, TREE_INITIAL_NODE};
-use tuirealm::command::{Cmd, Direction, Position};
+use tuirealm::command::{Cmd, CmdResult, Direction, Position};
 use tuirealm::props::{BorderType, Borders, Style, TextSpan};
 use tuirealm::tui::layout::Alignment;
-use tuirealm::tui::style::Color;
 use tuirealm::{
-    AttrValue, Attribute, Component, Event, MockComponent, Sub, SubClause, SubEventClause,
+    AttrValue, Attribute, Component, Event, MockComponent, State, StateValue, Sub, SubClause,
+    SubEventClause,
 };
 
 #[derive(MockComponent)]
 pub struct Threads {
     component: TreeView<Vec<TextSpan>>,
+    /// Thread numbers in order of tree nodes.
+    thread_numbers: Vec<u32>,
     exchanger: Arc<ClientExchanger>,
 }
 
+fn render_status(status: TraceeStatus) -> String {
+    match status {
+        TraceeStatus::Stopped(StopType::Interrupt) => "stopped".to_string(),
+        TraceeStatus::Stopped(StopType::SignalStop(sign)) => format!("stopped by {sign}"),
+        TraceeStatus::Running => "running".to_string(),
+    }
+}
+
 fn render_frame(line: &str) -> anyhow::Result<Vec<TextSpan>> {
     let syntax_renderer = syntax::rust_syntax_renderer();
     let mut line_renderer = syntax_renderer.line_renderer();
@@ -43,7 +54,7 @@ fn render_frame(line: &str) -> anyhow::Result<Vec<TextSpan>> {
 
 impl Threads {
     fn update_threads(&mut self) {
-        let Ok(threads) = self.exchanger.request_sync(|dbg| {
+        let Ok(mut threads) = self.exchanger.request_sync(|dbg| {
             let thread_result = command::thread::Handler::new(dbg)
                 .handle(command::thread::Command::Info)
                 .unwrap_or(ThreadResult::List(vec![]));
@@ -57,9 +68,26 @@ impl Threads {
             return;
         };
 
+        threads.sort_by_key(|t| t.thread.number);
+        command::thread::group_by_label(&mut threads);
+        self.thread_numbers = threads.iter().map(|t| t.thread.number).collect();
+        let focus_idx = threads.iter().position(|t| t.in_focus).unwrap_or_default();
+
         let mut root = Node::new("root".to_string(), vec![TextSpan::new("threads")]);
         for (i, thread_snap) in threads.iter().enumerate() {
             let pid = thread_snap.thread.pid;
+            let number = thread_snap.thread.number;
+            let status = render_status(thread_snap.thread.status);
+            let name = thread_snap
+                .name
+                .as_ref()
+                .map(|name| format!(" \"{name}\""))
+                .unwrap_or_default();
+            let label = thread_snap
+                .label
+                .as_ref()
+                .map(|label| format!(" <{label}>"))
+                .unwrap_or_default();
             let func_name = thread_snap
                 .bt
                 .as_ref()
@@ -72,9 +100,9 @@ impl Threads {
                 .unwrap_or("???".to_string());
 
             let value = if thread_snap.in_focus {
-                format!(" (CURRENT) [{pid}] {func_name}(:{line})")
+                format!(" (CURRENT) #{number} [{pid}]{name}{label} {func_name}(:{line}) {status}")
             } else {
-                format!(" [{pid}] {func_name}(:{line})")
+                format!(" #{number} [{pid}]{name}{label} {func_name}(:{line}) {status}")
             };
 
             let mut thread_node = Node::new(
@@ -86,15 +114,19 @@ impl Threads {
                 for (frame_num, frame) in bt.iter().enumerate() {
                     let fn_ip_or_zero = frame.fn_start_ip.unwrap_or_default();
 
-                    let frame_info = format!(
-                        "#{frame_num} {} ({} + {:#X})",
-                        frame.func_name.as_deref().unwrap_or("???"),
-                        frame
-                            .fn_start_ip
-                            .map(|addr| addr.to_string())
-                            .unwrap_or("???".to_string()),
-                        frame.ip.as_u64().saturating_sub(fn_ip_or_zero.as_u64()),
-                    );
+                    let frame_info = if frame.signal_frame {
+                        format!("#{frame_num} <signal handler called>")
+                    } else {
+                        format!(
+                            "#{frame_num} {} ({} + {:#X})",
+                            frame.func_name.as_deref().unwrap_or("???"),
+                            frame
+                                .fn_start_ip
+                                .map(|addr| addr.to_string())
+                                .unwrap_or("???".to_string()),
+                            frame.ip.as_u64().saturating_sub(fn_ip_or_zero.as_u64()),
+                        )
+                    };
                     thread_node.add_child(Node::new(
                         format!("thread_{i}_frame_{frame_num}"),
                         render_frame(&frame_info).expect("should be rendered"),
@@ -108,10 +140,37 @@ impl Threads {
         self.component.set_tree(Tree::new(root));
         self.component.attr(
             Attribute::Custom(TREE_INITIAL_NODE),
-            AttrValue::String("thread_0".to_string()),
+            AttrValue::String(format!("thread_{focus_idx}")),
         );
     }
 
+    /// Bring a thread into focus, all windows are updated by a step event.
+    fn switch_thread(&mut self, node_id: &str) -> Msg {
+        let Some(idx) = node_id
+            .strip_prefix("thread_")
+            .and_then(|idx| idx.parse::<usize>().ok())
+        else {
+            // not a thread node
+            return Msg::None;
+        };
+        let Some(&number) = self.thread_numbers.get(idx) else {
+            return Msg::None;
+        };
+
+        let result = self
+            .exchanger
+            .request_sync(move |dbg| -> anyhow::Result<()> {
+                command::thread::Handler::new(dbg)
+                    .handle(command::thread::Command::Switch(number))?;
+                dbg.execute_on_step_hook()?;
+                Ok(())
+            });
+        match result {
+            Ok(Err(e)) => Msg::ShowOkPopup(Some("Error".to_string()), e.to_string()),
+            _ => Msg::None,
+        }
+    }
+
     pub fn subscriptions() -> Vec<Sub<Id, UserEvent>> {
         vec![
             Sub::new(
@@ -138,6 +197,7 @@ impl Threads {
                     cond: BreakCondition::DataReadsWrites,
                     old_value: None,
                     new_value: None,
+                    access: None,
                     end_of_scope: false,
                 }),
                 SubClause::Always,
@@ -157,22 +217,24 @@ impl Threads {
     }
 
     pub fn new(exchanger: Arc<ClientExchanger>) -> Self {
+        let palette = &ui::config::current().tui_palette;
         let tree_view = TreeView::default()
             .borders(
                 Borders::default()
-                    .color(Color::LightYellow)
+                    .color(palette.border)
                     .modifiers(BorderType::Rounded),
             )
-            .inactive(Style::default().fg(Color::Gray))
+            .inactive(Style::default().fg(palette.inactive))
             .indent_size(3)
             .scroll_step(6)
             .preserve_state(true)
             .title("Threads", Alignment::Center)
-            .highlighted_color(Color::LightYellow)
+            .highlighted_color(palette.highlight)
             .highlight_symbol("▶");
 
         let mut this = Self {
             component: tree_view,
+            thread_numbers: vec![],
             exchanger,
         };
         this.update_threads();
@@ -212,7 +274,11 @@ impl Component<Msg, UserEvent> for Threads {
                             self.perform(Cmd::GoTo(Position::End));
                         }
                         CommonAction::Submit => {
-                            self.perform(Cmd::Submit);
+                            if let CmdResult::Submit(State::One(StateValue::String(node_id))) =
+                                self.perform(Cmd::Submit)
+                            {
+                                return Some(self.switch_thread(&node_id));
+                            }
                         }
                         _ => {}
                     }
@@ -220,6 +286,7 @@ impl Component<Msg, UserEvent> for Threads {
             }
             Event::User(UserEvent::Breakpoint { .. })
             | Event::User(UserEvent::Watchpoint { .. })
+            | Event::User(UserEvent::Signal(_))
             | Event::User(UserEvent::Exit(_))
             | Event::User(UserEvent::Step { .. }) => {
                 self.exchanger.enable_messaging();
@@ -2,21 +2,21 @@ use crate::debugger::register::debug::BreakCondition;
 use crate::debugger::variable::render::{RenderRepr, ValueLayout};
 use crate::debugger::variable::select::{Literal, VariableSelector, DQE};
 use crate::debugger::variable::{select, VariableIR};
+use crate::debugger::Signal;
 use crate::ui;
+use crate::ui::proto::ClientExchanger;
 use crate::ui::syntax::StylizedLine;
 use crate::ui::tui::app::port::UserEvent;
 use crate::ui::tui::config::CommonAction;
-use crate::ui::tui::proto::ClientExchanger;
 use crate::ui::tui::utils::syntect::into_text_span;
 use crate::ui::tui::{Id, Msg};
 use crate::ui::{command, syntax};
-use nix::sys::signal::Signal;
 use std::sync::Arc;
 use tui_realm_treeview::{Node, Tree, TreeView, TREE_CMD_CLOSE, TREE_CMD_OPEN, TREE_INITIAL_NODE};
 use tuirealm::command::{Cmd, Direction, Position};
 use tuirealm::props::{BorderType, Borders, TextSpan};
 use tuirealm::tui::layout::Alignment;
-use tuirealm::tui::style::{Color, Style};
+use tuirealm::tui::style::Style;
 use tuirealm::{
     AttrValue, Attribute, Component, Event, MockComponent, Sub, SubClause, SubEventClause,
 };
@@ -63,9 +63,17 @@ fn render_var_def(name: &str, typ: &str) -> anyhow::Result<Vec<TextSpan>> {
 }
 
 impl Variables {
+    /// Build a tree node for a variable, pointers are dereferenced into child nodes.
+    ///
+    /// # Arguments
+    ///
+    /// * `recursion`: nesting level of a node
+    /// * `path`: addresses of dereferenced values which contain a variable,
+    ///   pointers to these values are not dereferenced again
     fn node_from_var(
         &self,
         recursion: u32,
+        path: &[usize],
         node_name: &str,
         var: &VariableIR,
         select_path: Option<DQE>,
@@ -98,15 +106,26 @@ impl Variables {
                         render_var(&name, typ, &val).expect("should be rendered"),
                     )
                 }
-                ValueLayout::Referential { addr, .. } => {
-                    let value = format!("{addr:p}");
+                ValueLayout::Referential {
+                    addr, annotation, ..
+                } => {
+                    let value = match annotation {
+                        None => format!("{addr:p}"),
+                        Some(annotation) => format!("{addr:p} {annotation}"),
+                    };
                     let mut node = Node::new(
                         node_name.to_string(),
                         render_var(&name, typ, &value).expect("should be rendered"),
                     );
 
-                    if let Some(path) = select_path {
-                        let deref_expr = DQE::Deref(Box::new(path));
+                    if path.contains(&(addr as usize)) {
+                        let cycle = format!("<cycle to {addr:p}>");
+                        node.add_child(Node::new(
+                            format!("{node_name}_deref"),
+                            vec![TextSpan::new(cycle)],
+                        ));
+                    } else if let Some(select_path) = select_path {
+                        let deref_expr = DQE::Deref(Box::new(select_path));
 
                         let variables = {
                             let deref_expr = deref_expr.clone();
@@ -120,8 +139,10 @@ impl Variables {
 
                         if let Ok(variables) = variables {
                             if let Some(var) = variables.first() {
+                                let deref_path = [path, &[addr as usize]].concat();
                                 let deref_node = self.node_from_var(
                                     recursion + 1,
+                                    &deref_path,
                                     format!("{node_name}_deref").as_str(),
                                     var,
                                     Some(deref_expr),
@@ -140,6 +161,7 @@ impl Variables {
                     );
                     node.add_child(self.node_from_var(
                         recursion + 1,
+                        path,
                         format!("{node_name}_1").as_str(),
                         other,
                         select_path,
@@ -155,6 +177,7 @@ impl Variables {
                         node.add_child(
                             self.node_from_var(
                                 recursion + 1,
+                                path,
                                 format!("{node_name}_{i}").as_str(),
                                 member,
                                 select_path
@@ -178,6 +201,7 @@ impl Variables {
 
                         kv_pair.add_child(self.node_from_var(
                             recursion + 1,
+                            path,
                             format!("{node_name}_kv_{i}_key").as_str(),
                             key,
                             // currently no way to use expressions with keys
@@ -187,6 +211,7 @@ impl Variables {
                         kv_pair.add_child(
                             self.node_from_var(
                                 recursion + 1,
+                                path,
                                 format!("{node_name}_kv_{i}_val").as_str(),
                                 val,
                                 // todo works only if key is a String or &str, need better support of field expr on maps
@@ -217,6 +242,7 @@ impl Variables {
 
                         node.add_child(self.node_from_var(
                             recursion + 1,
+                            path,
                             format!("{node_name}_{i}").as_str(),
                             member,
                             el_path,
@@ -258,6 +284,7 @@ impl Variables {
             let node_name = format!("arg_{i}");
             let var_node = self.node_from_var(
                 0,
+                &arg.in_memory_location().into_iter().collect::<Vec<_>>(),
                 node_name.as_str(),
                 arg,
                 Some(DQE::Variable(VariableSelector::Name {
@@ -274,6 +301,7 @@ impl Variables {
             let node_name = format!("var_{i}");
             let var_node = self.node_from_var(
                 0,
+                &var.in_memory_location().into_iter().collect::<Vec<_>>(),
                 node_name.as_str(),
                 var,
                 Some(DQE::Variable(VariableSelector::Name {
@@ -326,6 +354,7 @@ impl Variables {
                     cond: BreakCondition::DataReadsWrites,
                     old_value: None,
                     new_value: None,
+                    access: None,
                     end_of_scope: false,
                 }),
                 SubClause::Always,
@@ -345,19 +374,20 @@ impl Variables {
     }
 
     pub fn new(exchanger: Arc<ClientExchanger>) -> Self {
+        let palette = &ui::config::current().tui_palette;
         let mut this = Self {
             component: TreeView::default()
                 .borders(
                     Borders::default()
-                        .color(Color::LightYellow)
+                        .color(palette.border)
                         .modifiers(BorderType::Rounded),
                 )
-                .inactive(Style::default().fg(Color::Gray))
+                .inactive(Style::default().fg(palette.inactive))
                 .indent_size(3)
                 .scroll_step(6)
                 .preserve_state(true)
                 .title("Variables", Alignment::Center)
-                .highlighted_color(Color::LightYellow)
+                .highlighted_color(palette.highlight)
                 .highlight_symbol("▶"),
             exchanger,
         };
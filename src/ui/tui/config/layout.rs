@@ -0,0 +1,72 @@
+use crate::{muted_error, weak_error};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::fs::read_to_string;
+use std::path::PathBuf;
+
+/// Proportions of TUI windows, persisted between debugger runs.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LayoutConfig {
+    /// Width of the left window in percent of terminal width.
+    pub left_window_width: u16,
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        Self {
+            left_window_width: 25,
+        }
+    }
+}
+
+impl LayoutConfig {
+    const DEFAULT_PATH: &'static str = ".config/bs/layout.toml";
+    /// Minimal width of any window in percent.
+    pub const MIN_WINDOW_WIDTH: u16 = 10;
+
+    fn path() -> Option<PathBuf> {
+        Some(home::home_dir()?.join(Self::DEFAULT_PATH))
+    }
+
+    /// Load layout from the config file. Return default layout on errors.
+    pub fn load() -> Self {
+        let Some(data) = Self::path().and_then(|path| muted_error!(read_to_string(path))) else {
+            return Self::default();
+        };
+        let Some(mut layout) = weak_error!(toml::de::from_str::<LayoutConfig>(&data)) else {
+            return Self::default();
+        };
+        layout.left_window_width = layout
+            .left_window_width
+            .clamp(Self::MIN_WINDOW_WIDTH, 100 - Self::MIN_WINDOW_WIDTH);
+        layout
+    }
+
+    /// Save layout into the config file, errors are logged and ignored.
+    pub fn save(&self) {
+        let Some(path) = Self::path() else {
+            return;
+        };
+        if let Some(dir) = path.parent() {
+            weak_error!(fs::create_dir_all(dir));
+        }
+        if let Some(data) = weak_error!(toml::ser::to_string(self)) {
+            weak_error!(fs::write(path, data));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_layout_serde() {
+        let layout = LayoutConfig {
+            left_window_width: 40,
+        };
+        let data = toml::ser::to_string(&layout).unwrap();
+        assert_eq!(data.trim(), "left_window_width = 40");
+        assert_eq!(toml::de::from_str::<LayoutConfig>(&data).unwrap(), layout);
+    }
+}
@@ -5,7 +5,11 @@ use std::collections::HashMap;
 use std::fs::read_to_string;
 use tuirealm::event::KeyEvent;
 
+mod layout;
+mod palette;
 mod ser;
+pub use layout::LayoutConfig;
+pub use palette::Palette;
 pub(super) use ser::WrappedKeyEvent;
 
 /// Common control actions (like up/down/scroll up/etc.)
@@ -23,6 +27,10 @@ pub enum CommonAction {
     Delete,
     Backspace,
     Cancel,
+    Toggle,
+    Search,
+    SearchNext,
+    SearchPrev,
 }
 
 /// Specialized debugger actions (like start/quit/steps/etc.)
@@ -40,6 +48,8 @@ pub enum SpecialAction {
     StepOver,
     StepInto,
     StepOut,
+    StepInstruction,
+    CommandBar,
 }
 
 /// Configuration of key bindings for TUI.
@@ -0,0 +1,164 @@
+use crate::{muted_error, weak_error};
+use log::{error, warn};
+use serde::Deserialize;
+use std::fs::read_to_string;
+use tuirealm::tui::style::Color;
+use tuirealm::utils::parser::parse_color;
+
+const DARK_PRESET: &str = include_str!("preset/palette.toml");
+const LIGHT_PRESET: &str = include_str!("preset/palette_light.toml");
+
+macro_rules! palette {
+    ($($(#[$doc: meta])* $name: ident),* $(,)?) => {
+        /// Color palette of TUI.
+        #[derive(Debug, Clone)]
+        pub struct Palette {
+            $($(#[$doc])* pub $name: Color,)*
+        }
+
+        #[derive(Debug, Default, Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct ColorsConfig {
+            $(#[serde(default)] $name: Option<String>,)*
+        }
+
+        impl Palette {
+            fn empty() -> Self {
+                Self {
+                    $($name: Color::Reset,)*
+                }
+            }
+
+            /// Override palette colors by colors from config, invalid colors are ignored.
+            fn apply(&mut self, colors: ColorsConfig) {
+                $(
+                    if let Some(color) = colors.$name {
+                        match parse_color(&color) {
+                            Some(color) => self.$name = color,
+                            None => warn!("Invalid color `{color}` for `{}`", stringify!($name)),
+                        }
+                    }
+                )*
+            }
+        }
+    };
+}
+
+palette!(
+    /// Window borders.
+    border,
+    /// Inactive (not focused) windows.
+    inactive,
+    /// Selected item in lists.
+    highlight,
+    /// Text of selected item if it is highlighted by background.
+    selection_text,
+    /// Tab titles.
+    tabs,
+    /// Tab window.
+    tabs_background,
+    /// Status bar borders.
+    status,
+    /// Popup borders and text.
+    popup,
+    /// Popup background.
+    popup_background,
+    /// Current line in source code and assembler, debugee stderr, changed memory.
+    current_line,
+    /// Addresses in assembler, memory and history windows, watchpoints.
+    address,
+    /// Assembler instruction mnemonics.
+    instruction,
+    /// Assembler operands, memory as text, buttons and hints.
+    value,
+    /// Breakpoint and watchpoint numbers.
+    number,
+    /// Enabled breakpoints.
+    enabled,
+    /// Disabled breakpoints, unknown locations.
+    disabled,
+    /// Debugee state and errors.
+    error,
+);
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PaletteConfig {
+    preset: Option<String>,
+    #[serde(default)]
+    colors: ColorsConfig,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::from_config(toml::de::from_str(DARK_PRESET).expect("should de"))
+    }
+}
+
+impl Palette {
+    const DEFAULT_PATH: &'static str = ".config/bs/palette.toml";
+
+    fn from_config(config: PaletteConfig) -> Self {
+        let preset = match config.preset.as_deref() {
+            None | Some("dark") => DARK_PRESET,
+            Some("light") => LIGHT_PRESET,
+            Some(unknown) => {
+                warn!("Unknown palette preset `{unknown}`, dark preset is used");
+                DARK_PRESET
+            }
+        };
+        let preset: PaletteConfig = toml::de::from_str(preset).expect("should de");
+
+        let mut palette = Self::empty();
+        palette.apply(preset.colors);
+        palette.apply(config.colors);
+        palette
+    }
+
+    /// Load palette from file. Return [`None`] on errors.
+    pub fn from_file(path: Option<&str>) -> Option<Self> {
+        let data = match path {
+            None => {
+                let path = home::home_dir()?;
+                let path = path.join(Self::DEFAULT_PATH);
+                muted_error!(read_to_string(path))?
+            }
+            Some(path) => match read_to_string(path) {
+                Ok(data) => data,
+                Err(err) => {
+                    error!("Error while load palette file: {err}");
+                    return None;
+                }
+            },
+        };
+
+        let config: PaletteConfig = weak_error!(toml::de::from_str(&data))?;
+        Some(Self::from_config(config))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_palette_presets() {
+        let dark = Palette::default();
+        assert_eq!(dark.border, Color::LightYellow);
+        assert_eq!(dark.error, Color::Red);
+
+        let config: PaletteConfig = toml::de::from_str(
+            r##"
+            preset = "light"
+            [colors]
+            border = "LightCyan"
+            error = "#ff0000"
+            "##,
+        )
+        .unwrap();
+        let light = Palette::from_config(config);
+        assert_eq!(light.border, Color::LightCyan);
+        assert_eq!(light.error, Color::Rgb(255, 0, 0));
+        assert_eq!(light.popup_background, Color::White);
+    }
+}
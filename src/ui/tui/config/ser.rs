@@ -166,6 +166,44 @@ struct Common {
     right: Vec<WrappedKeyEvent>,
     input_delete: Vec<WrappedKeyEvent>,
     input_backspace: Vec<WrappedKeyEvent>,
+    #[serde(default = "Common::default_toggle")]
+    toggle: Vec<WrappedKeyEvent>,
+    #[serde(default = "Common::default_search")]
+    search: Vec<WrappedKeyEvent>,
+    #[serde(default = "Common::default_search_next")]
+    search_next: Vec<WrappedKeyEvent>,
+    #[serde(default = "Common::default_search_prev")]
+    search_prev: Vec<WrappedKeyEvent>,
+}
+
+impl Common {
+    fn default_toggle() -> Vec<WrappedKeyEvent> {
+        vec![WrappedKeyEvent(KeyEvent {
+            code: Key::Char(' '),
+            modifiers: KeyModifiers::NONE,
+        })]
+    }
+
+    fn default_search() -> Vec<WrappedKeyEvent> {
+        vec![WrappedKeyEvent(KeyEvent {
+            code: Key::Char('/'),
+            modifiers: KeyModifiers::NONE,
+        })]
+    }
+
+    fn default_search_next() -> Vec<WrappedKeyEvent> {
+        vec![WrappedKeyEvent(KeyEvent {
+            code: Key::Char('n'),
+            modifiers: KeyModifiers::NONE,
+        })]
+    }
+
+    fn default_search_prev() -> Vec<WrappedKeyEvent> {
+        vec![WrappedKeyEvent(KeyEvent {
+            code: Key::Char('N'),
+            modifiers: KeyModifiers::SHIFT,
+        })]
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -182,6 +220,26 @@ struct Special {
     step_over: Vec<WrappedKeyEvent>,
     step_into: Vec<WrappedKeyEvent>,
     step_out: Vec<WrappedKeyEvent>,
+    #[serde(default = "Special::default_step_instruction")]
+    step_instruction: Vec<WrappedKeyEvent>,
+    #[serde(default = "Special::default_command_bar")]
+    command_bar: Vec<WrappedKeyEvent>,
+}
+
+impl Special {
+    fn default_step_instruction() -> Vec<WrappedKeyEvent> {
+        vec![WrappedKeyEvent(KeyEvent {
+            code: Key::Function(5),
+            modifiers: KeyModifiers::NONE,
+        })]
+    }
+
+    fn default_command_bar() -> Vec<WrappedKeyEvent> {
+        vec![WrappedKeyEvent(KeyEvent {
+            code: Key::Char(':'),
+            modifiers: KeyModifiers::NONE,
+        })]
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -221,6 +279,10 @@ impl From<KeyMapConfig> for KeyMap {
         append_key(common_k, cb.input_delete, CommonAction::Delete);
         append_key(common_k, cb.input_backspace, CommonAction::Backspace);
         append_key(common_k, cb.cancel, CommonAction::Cancel);
+        append_key(common_k, cb.toggle, CommonAction::Toggle);
+        append_key(common_k, cb.search, CommonAction::Search);
+        append_key(common_k, cb.search_next, CommonAction::SearchNext);
+        append_key(common_k, cb.search_prev, CommonAction::SearchPrev);
 
         let spec_k = &mut keymap.spec_keys;
         let sb = bindings.special;
@@ -236,6 +298,8 @@ impl From<KeyMapConfig> for KeyMap {
         append_key(spec_k, sb.step_over, SpecialAction::StepOver);
         append_key(spec_k, sb.step_into, SpecialAction::StepInto);
         append_key(spec_k, sb.step_out, SpecialAction::StepOut);
+        append_key(spec_k, sb.step_instruction, SpecialAction::StepInstruction);
+        append_key(spec_k, sb.command_bar, SpecialAction::CommandBar);
 
         keymap
     }
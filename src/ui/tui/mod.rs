@@ -1,20 +1,19 @@
 use crate::debugger::process::{Child, Installed};
 use crate::debugger::{BreakpointViewOwned, Debugger, DebuggerBuilder, WatchpointViewOwned};
+use crate::ui::proto::{event_stream, exchanger, EventStream, ServeExit};
 pub use crate::ui::tui::app::port::TuiHook;
 use crate::ui::tui::app::port::{DebuggerEventQueue, UserEvent};
 use crate::ui::tui::app::Model;
 use crate::ui::tui::components::popup::Popup;
 use crate::ui::tui::output::{OutputLine, OutputStreamProcessor, StreamType};
-use crate::ui::tui::proto::{exchanger, Request};
+use crate::ui::tui::utils::loading::LoadingScreen;
 use crate::ui::{console, supervisor, DebugeeOutReader};
-use crate::weak_error;
 use anyhow::anyhow;
-use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
-use crossterm::execute;
+use crossterm::event::MouseEvent;
 use log::error;
 use std::sync::{Arc, Mutex};
+use std::thread;
 use std::time::Duration;
-use std::{io, thread};
 use strum_macros::{Display, EnumString};
 use timeout_readwrite::TimeoutReader;
 use tuirealm::{props, AttrValue, Attribute, PollStrategy};
@@ -23,7 +22,6 @@ pub mod app;
 pub mod components;
 pub mod config;
 mod output;
-mod proto;
 pub mod utils;
 
 // Component ids for debugger application
@@ -65,6 +63,13 @@ pub enum Msg {
     BreakpointAdd(BreakpointsAddType),
     UpdateBreakpointList,
     ExpandTab(Id),
+    ShowAsm,
+    ShowSource,
+    MemoryGoTo,
+    MemoryWrite(usize),
+    SearchStart,
+    CommandBarOpen,
+    Mouse(MouseEvent),
 
     PopupConfirmDebuggerRestart,
     PopupBreakpoint(BreakpointViewOwned),
@@ -75,6 +80,7 @@ pub enum Msg {
     PopupNo(ConfirmedAction),
 
     Input(String),
+    InputChanged(String),
     InputCancel,
 }
 
@@ -102,27 +108,37 @@ impl AppBuilder {
         process: Child<Installed>,
     ) -> anyhow::Result<TuiApplication> {
         let debugger_event_queue = DebuggerEventQueue::default();
-        let debugger = dbg_builder
-            .with_hooks(TuiHook::new(debugger_event_queue.clone()))
-            .build(process)?;
+        let (publisher, events) = event_stream();
+        let loading_screen = Arc::new(LoadingScreen::default());
+        let mut dbg_builder =
+            dbg_builder.with_hooks(TuiHook::new(debugger_event_queue.clone(), publisher));
+        if let Some(callback) = loading_screen.callback() {
+            dbg_builder = dbg_builder.with_progress(callback);
+        }
+        let debugger = dbg_builder.build(process);
+        loading_screen.finish();
+        let debugger = debugger?;
 
         Ok(TuiApplication::new(
             debugger,
             self.debugee_out,
             self.debugee_err,
             debugger_event_queue,
+            events,
         ))
     }
 
     pub fn extend(self, mut debugger: Debugger) -> TuiApplication {
         let debugger_event_queue = DebuggerEventQueue::default();
-        debugger.set_hook(TuiHook::new(debugger_event_queue.clone()));
+        let (publisher, events) = event_stream();
+        debugger.set_hook(TuiHook::new(debugger_event_queue.clone(), publisher));
 
         TuiApplication::new(
             debugger,
             self.debugee_out,
             self.debugee_err,
             debugger_event_queue,
+            events,
         )
     }
 }
@@ -132,6 +148,7 @@ pub struct TuiApplication {
     debugee_out: DebugeeOutReader,
     debugee_err: DebugeeOutReader,
     debugger_event_queue: Arc<Mutex<Vec<UserEvent>>>,
+    events: EventStream,
 }
 
 impl TuiApplication {
@@ -140,12 +157,14 @@ impl TuiApplication {
         debugee_out: DebugeeOutReader,
         debugee_err: DebugeeOutReader,
         debugger_event_queue: Arc<Mutex<Vec<UserEvent>>>,
+        events: EventStream,
     ) -> Self {
         Self {
             debugger,
             debugee_out,
             debugee_err,
             debugger_event_queue,
+            events,
         }
     }
 
@@ -174,12 +193,12 @@ impl TuiApplication {
             let mut model = Model::new(
                 stream_buf,
                 self.debugger_event_queue,
+                self.events,
                 client_exchanger,
                 log_buffer,
             )?;
             model.terminal.enter_alternate_screen()?;
             model.terminal.enable_raw_mode()?;
-            weak_error!(execute!(io::stdout(), DisableMouseCapture));
 
             while !model.quit {
                 match model.app.tick(PollStrategy::Once) {
@@ -219,7 +238,6 @@ impl TuiApplication {
                 }
             }
 
-            weak_error!(execute!(io::stdout(), EnableMouseCapture));
             model.terminal.leave_alternate_screen()?;
             model.terminal.disable_raw_mode()?;
             model.terminal.clear_screen()?;
@@ -227,45 +245,14 @@ impl TuiApplication {
             Ok(())
         });
 
-        enum ExitType {
-            Exit,
-            Shutdown,
-            SwitchUi,
-        }
-
-        let exit_type;
-        loop {
-            match srv_exchanger.next_request() {
-                Some(Request::Exit) => {
-                    exit_type = ExitType::Exit;
-                    break;
-                }
-                Some(Request::SwitchUi) => {
-                    exit_type = ExitType::SwitchUi;
-                    break;
-                }
-                Some(Request::DebuggerSyncTask(task)) => {
-                    let result = task(&mut self.debugger);
-                    srv_exchanger.send_response(result);
-                }
-                Some(Request::DebuggerAsyncTask(task)) => {
-                    if let Err(e) = task(&mut self.debugger) {
-                        srv_exchanger.send_async_response(e);
-                    }
-                }
-                None => {
-                    exit_type = ExitType::Shutdown;
-                    break;
-                }
-            }
-        }
+        let exit_type = srv_exchanger.serve(&mut self.debugger);
 
         drop(std_out_handle);
         drop(std_err_handle);
 
         match exit_type {
-            ExitType::Exit => Ok(supervisor::ControlFlow::Exit),
-            ExitType::Shutdown => {
+            ServeExit::Exit => Ok(supervisor::ControlFlow::Exit),
+            ServeExit::Shutdown => {
                 let join_result = ui_jh.join();
                 let join_result = join_result
                     .map_err(|_| anyhow!("unexpected: tui thread panic"))
@@ -275,15 +262,13 @@ impl TuiApplication {
                 };
                 Ok(supervisor::ControlFlow::Exit)
             }
-            ExitType::SwitchUi => {
+            ServeExit::SwitchUi => {
                 _ = ui_jh.join();
                 let builder = console::AppBuilder::new(self.debugee_out, self.debugee_err);
                 let app = builder
                     .extend(self.debugger)
                     .expect("build application fail");
-                Ok(supervisor::ControlFlow::Switch(
-                    supervisor::Application::Terminal(app),
-                ))
+                Ok(supervisor::ControlFlow::Switch(Box::new(app)))
             }
         }
     }
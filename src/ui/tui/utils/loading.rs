@@ -0,0 +1,87 @@
+use crate::debugger::{ParsingProgress, ProgressCallback};
+use crate::ui::progress::{ProgressSummary, ProgressTracker};
+use std::io::IsTerminal;
+use std::sync::{Arc, Mutex};
+use tuirealm::terminal::TerminalBridge;
+use tuirealm::tui::layout::{Constraint, Direction, Layout};
+use tuirealm::tui::style::{Color, Style};
+use tuirealm::tui::widgets::{Block, BorderType, Borders, Gauge};
+
+/// Loading screen with debug information parsing progress, shown until a TUI application starts.
+#[derive(Default)]
+pub struct LoadingScreen {
+    tracker: ProgressTracker,
+    /// Terminal, initialized at first redraw.
+    terminal: Mutex<Option<TerminalBridge>>,
+}
+
+impl LoadingScreen {
+    /// Return a progress callback that draws this loading screen,
+    /// or `None` if stdout is not a terminal.
+    pub fn callback(self: &Arc<Self>) -> Option<ProgressCallback> {
+        if !std::io::stdout().is_terminal() {
+            return None;
+        }
+        let screen = self.clone();
+        Some(Arc::new(move |progress: &ParsingProgress| {
+            if let Some(summary) = screen.tracker.update(progress) {
+                screen.draw(summary);
+            }
+        }))
+    }
+
+    fn draw(&self, summary: ProgressSummary) {
+        let mut terminal = self.terminal.lock().unwrap();
+        if terminal.is_none() {
+            let Ok(mut bridge) = TerminalBridge::new() else {
+                return;
+            };
+            if bridge.enter_alternate_screen().is_err() {
+                return;
+            }
+            *terminal = Some(bridge);
+        }
+        let Some(terminal) = terminal.as_mut() else {
+            return;
+        };
+
+        _ = terminal.raw_mut().draw(|f| {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Percentage(45),
+                    Constraint::Length(3),
+                    Constraint::Min(0),
+                ])
+                .split(f.size());
+            let area = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([
+                    Constraint::Percentage(20),
+                    Constraint::Percentage(60),
+                    Constraint::Percentage(20),
+                ])
+                .split(chunks[1])[1];
+
+            let gauge = Gauge::default()
+                .block(
+                    Block::default()
+                        .title("Loading debug information")
+                        .borders(Borders::ALL)
+                        .border_type(BorderType::Rounded),
+                )
+                .gauge_style(Style::default().fg(Color::LightYellow))
+                .ratio(summary.ratio())
+                .label(summary.to_string());
+            f.render_widget(gauge, area);
+        });
+    }
+
+    /// Leave loading screen if it was shown.
+    pub fn finish(&self) {
+        if let Some(mut terminal) = self.terminal.lock().unwrap().take() {
+            _ = terminal.leave_alternate_screen();
+            _ = terminal.clear_screen();
+        }
+    }
+}
@@ -13,6 +13,19 @@ pub struct TuiLogLine {
 }
 
 impl TuiLogLine {
+    pub fn new(level: Level, target: &str, body: String) -> Self {
+        Self {
+            level,
+            time: Local::now().to_rfc3339(),
+            target: target.to_string(),
+            body,
+        }
+    }
+
+    pub fn level(&self) -> Level {
+        self.level
+    }
+
     pub fn to_text_spans(&self) -> Vec<TextSpan> {
         fn fg_for_level(lvl: Level) -> Color {
             match lvl {
@@ -32,6 +45,12 @@ impl TuiLogLine {
     }
 }
 
+/// Target of debugee tracer events (thread stops, group-stops, signals).
+const TRACER_TARGET: &str = "tracer";
+
+/// Logger for a TUI logs window. Besides records allowed by `RUST_LOG`
+/// tracer events (debug level and above) are always captured, so tracer behavior
+/// can be inspected without restarting a debugger.
 pub struct TuiLogger {
     inner: env_logger::Logger,
     buffer: Arc<sync::Mutex<Vec<TuiLogLine>>>,
@@ -39,10 +58,15 @@ pub struct TuiLogger {
 
 impl Log for TuiLogger {
     fn enabled(&self, metadata: &Metadata) -> bool {
-        self.inner.enabled(metadata)
+        (metadata.target() == TRACER_TARGET && metadata.level() <= Level::Debug)
+            || self.inner.enabled(metadata)
     }
 
     fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
         let ts = Local::now();
         let log = TuiLogLine {
             level: record.level(),
@@ -66,6 +90,6 @@ impl TuiLogger {
     }
 
     pub fn filter(&self) -> LevelFilter {
-        self.inner.filter()
+        self.inner.filter().max(LevelFilter::Debug)
     }
 }
@@ -1,5 +1,7 @@
 mod flex_radio;
+pub mod loading;
 pub mod logger;
 pub mod mstextarea;
+pub mod search;
 pub mod syntect;
 pub mod tab;
@@ -0,0 +1,163 @@
+use crate::ui;
+use tuirealm::props::TextSpan;
+
+/// Incremental text search over rows of a window.
+/// Search is case-insensitive for ASCII letters.
+#[derive(Default)]
+pub struct Search {
+    query: String,
+    /// Indexes of rows that contain a query.
+    matches: Vec<usize>,
+}
+
+impl Search {
+    /// Set a new query and find matched rows, empty query resets a search.
+    pub fn set_query(&mut self, query: &str, rows: &[Vec<TextSpan>]) {
+        self.query = query.to_ascii_lowercase();
+        self.update(rows);
+    }
+
+    /// Find matched rows again (after rows are changed).
+    pub fn update(&mut self, rows: &[Vec<TextSpan>]) {
+        self.matches.clear();
+        if self.query.is_empty() {
+            return;
+        }
+        self.matches = rows
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| !match_ranges(&row_text(row), &self.query).is_empty())
+            .map(|(idx, _)| idx)
+            .collect();
+    }
+
+    /// Return index of the first matched row at or after `from`, search wraps around.
+    pub fn first_from(&self, from: usize) -> Option<usize> {
+        self.matches
+            .iter()
+            .find(|&&idx| idx >= from)
+            .or(self.matches.first())
+            .copied()
+    }
+
+    /// Return index of the next matched row after `from`, search wraps around.
+    pub fn next(&self, from: usize) -> Option<usize> {
+        self.first_from(from + 1)
+    }
+
+    /// Return index of the previous matched row before `from`, search wraps around.
+    pub fn prev(&self, from: usize) -> Option<usize> {
+        self.matches
+            .iter()
+            .rev()
+            .find(|&&idx| idx < from)
+            .or(self.matches.last())
+            .copied()
+    }
+
+    /// Return window title extended by a search status.
+    pub fn title(&self, title: &str, current: usize) -> String {
+        if self.query.is_empty() {
+            return title.to_string();
+        }
+        let position = self.matches.iter().position(|&idx| idx == current);
+        match position {
+            Some(pos) => format!(
+                "{title} [/{} {}/{}]",
+                self.query,
+                pos + 1,
+                self.matches.len()
+            ),
+            None => format!("{title} [/{} {}]", self.query, self.matches.len()),
+        }
+    }
+
+    /// Return rows where all query occurrences are highlighted.
+    pub fn highlight(&self, rows: &[Vec<TextSpan>]) -> Vec<Vec<TextSpan>> {
+        if self.matches.is_empty() {
+            return rows.to_vec();
+        }
+
+        let palette = &ui::config::current().tui_palette;
+        let mut result = rows.to_vec();
+        for &idx in &self.matches {
+            let row = &rows[idx];
+            let ranges = match_ranges(&row_text(row), &self.query);
+
+            let mut highlighted = vec![];
+            let mut span_start = 0;
+            for span in row {
+                let span_end = span_start + span.content.len();
+                let mut pos = span_start;
+                for &(start, end) in &ranges {
+                    let start = start.clamp(span_start, span_end);
+                    let end = end.clamp(span_start, span_end);
+                    if start == end {
+                        continue;
+                    }
+                    if pos < start {
+                        highlighted.push(sub_span(span, pos - span_start, start - span_start));
+                    }
+                    let mut matched = sub_span(span, start - span_start, end - span_start);
+                    matched.fg = palette.selection_text;
+                    matched.bg = palette.highlight;
+                    highlighted.push(matched);
+                    pos = end;
+                }
+                if pos < span_end {
+                    highlighted.push(sub_span(span, pos - span_start, span_end - span_start));
+                }
+                span_start = span_end;
+            }
+            result[idx] = highlighted;
+        }
+        result
+    }
+}
+
+fn row_text(row: &[TextSpan]) -> String {
+    row.iter().map(|span| span.content.as_str()).collect()
+}
+
+/// Return byte ranges of all non-overlapping query occurrences in a text.
+fn match_ranges(text: &str, query: &str) -> Vec<(usize, usize)> {
+    // ascii lowercase keeps byte offsets, so ranges are valid for the original text
+    text.to_ascii_lowercase()
+        .match_indices(query)
+        .map(|(start, m)| (start, start + m.len()))
+        .collect()
+}
+
+fn sub_span(span: &TextSpan, start: usize, end: usize) -> TextSpan {
+    let mut sub = span.clone();
+    sub.content = span.content[start..end].to_string();
+    sub
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_search_matches() {
+        let rows = vec![
+            vec![TextSpan::new("fo"), TextSpan::new("obar")],
+            vec![TextSpan::new("baz")],
+            vec![TextSpan::new("Foobar")],
+        ];
+        let mut search = Search::default();
+        search.set_query("OB", &rows);
+        assert_eq!(search.matches, vec![0, 2]);
+        assert_eq!(search.first_from(1), Some(2));
+        assert_eq!(search.next(2), Some(0));
+        assert_eq!(search.prev(0), Some(2));
+        assert_eq!(search.title("Logs", 2), "Logs [/ob 2/2]");
+
+        assert_eq!(match_ranges("foo bar", "o b"), vec![(2, 5)]);
+        assert_eq!(match_ranges("Foobar", "ob"), vec![(2, 4)]);
+
+        search.set_query("", &rows);
+        assert_eq!(search.first_from(0), None);
+        assert_eq!(search.title("Logs", 0), "Logs");
+    }
+}
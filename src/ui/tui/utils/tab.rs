@@ -57,15 +57,16 @@ impl TabWindow {
         windows: Vec<Box<dyn Component<Msg, UserEvent>>>,
         msg_on_rewind: Option<fn(Direction) -> Msg>,
     ) -> Self {
+        let palette = &ui::config::current().tui_palette;
         debug_assert!(tabs.len() == windows.len());
 
         let choices = flex_radio::Radio::default()
             .borders(
                 Borders::default()
                     .modifiers(BorderType::Rounded)
-                    .color(Color::LightGreen),
+                    .color(palette.tabs),
             )
-            .foreground(Color::LightGreen)
+            .foreground(palette.tabs)
             .title(title, Alignment::Center)
             .rewind(msg_on_rewind.is_none())
             .choices(tabs);
@@ -82,8 +83,8 @@ impl TabWindow {
             on_rewind: msg_on_rewind,
         };
 
-        this.background(Color::Yellow)
-            .foreground(Color::Yellow)
+        this.background(palette.tabs_background)
+            .foreground(palette.tabs_background)
             .layout(
                 Layout::default()
                     .direction(tuirealm::tui::layout::Direction::Vertical)
@@ -102,6 +103,12 @@ impl TabWindow {
     pub const VIEW_SIZE_ATTR: Attribute = Attribute::Custom("VIEW_SIZE");
     pub const RESET_CHOICE_ATTR: Attribute = Attribute::Custom("RESET_CHOICE");
     pub const ACTIVATE_TAB: Attribute = Attribute::Custom("ACTIVATE_TAB");
+    /// Select a tab and show related window, attribute value is a tab index.
+    pub const SHOW_TAB: Attribute = Attribute::Custom("SHOW_TAB");
+    /// Scroll a visible window, attribute value is a scroll direction.
+    pub const SCROLL_ATTR: Attribute = Attribute::Custom("SCROLL");
+    /// Search a text in a visible window, attribute value is a search query.
+    pub const SEARCH_ATTR: Attribute = Attribute::Custom("SEARCH");
 
     pub fn foreground(mut self, fg: Color) -> Self {
         self.attr(Attribute::Foreground, AttrValue::Color(fg));
@@ -251,6 +258,29 @@ impl MockComponent for TabWindow {
                     self.set_active_idx(tab_idx);
                 }
             }
+            Self::SCROLL_ATTR => {
+                let direction = match value {
+                    AttrValue::Direction(props::Direction::Up) => Direction::Up,
+                    _ => Direction::Down,
+                };
+                if let Some(window) = self.visible_window_mut() {
+                    window.perform(Cmd::Scroll(direction));
+                }
+            }
+            Self::SEARCH_ATTR => {
+                if let Some(window) = self.visible_window_mut() {
+                    window.attr(attr, value);
+                }
+            }
+            Self::SHOW_TAB => {
+                let tab_idx = value.unwrap_number() as usize;
+                self.choices.states.select(tab_idx);
+                if self.active_idx.is_some() {
+                    self.set_active_idx(tab_idx);
+                } else {
+                    self.visible_idx = tab_idx;
+                }
+            }
             Attribute::Custom(_) => {
                 // all other custom attributes redirect to tab windows
                 for comp in self.windows.iter_mut() {
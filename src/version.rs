@@ -5,7 +5,7 @@ use once_cell::sync;
 use regex::Regex;
 
 /// Compiler SemVer version.
-#[derive(PartialEq, PartialOrd)]
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub struct Version(pub (u32, u32, u32));
 
 impl Version {
@@ -58,6 +58,15 @@ static SUPPORTED_RUSTC: &[(Version, Version)] = &[
     (Version((1, 79, 0)), Version((1, 79, u32::MAX))),
 ];
 
+impl Version {
+    /// Return true if a version is in one of supported diapasons.
+    pub fn is_supported(&self) -> bool {
+        SUPPORTED_RUSTC
+            .iter()
+            .any(|(v_min, v_max)| self >= v_min && self <= v_max)
+    }
+}
+
 pub fn supported_versions_to_string() -> String {
     format!(
         "[{}]",
@@ -68,23 +77,10 @@ pub fn supported_versions_to_string() -> String {
     )
 }
 
-/// Check rustc version, return true if a version supported, false otherwise. False positive.
-pub fn probe_file(obj: &object::File) -> bool {
-    let Some(comment_sect) = obj.section_by_name(".comment") else {
-        return true;
-    };
-    let Ok(data) = comment_sect.data() else {
-        return true;
-    };
-    let Ok(string_data) = std::str::from_utf8(data) else {
-        return true;
-    };
-
-    if let Some(version) = Version::rustc_parse(string_data) {
-        return SUPPORTED_RUSTC
-            .iter()
-            .any(|(v_min, v_max)| version >= *v_min && version <= *v_max);
-    }
-
-    true
+/// Return version of rustc that builds an object file, `None` if version is unknown.
+pub fn rustc_version(obj: &object::File) -> Option<Version> {
+    let comment_sect = obj.section_by_name(".comment")?;
+    let data = comment_sect.data().ok()?;
+    let string_data = std::str::from_utf8(data).ok()?;
+    Version::rustc_parse(string_data)
 }
@@ -1,8 +1,17 @@
 use crate::common::TestHooks;
 use crate::common::TestInfo;
+use crate::variables::assert_scalar;
 use crate::{assert_no_proc, FIZZBUZZ_APP, HW_APP, SHARED_LIB_APP, VARS_APP};
 use crate::{prepare_debugee_process, CALC_APP};
-use bugstalker::debugger::DebuggerBuilder;
+use bugstalker::debugger::address::Address;
+use bugstalker::debugger::variable::render::RenderRepr;
+use bugstalker::debugger::variable::select::{VariableSelector, DQE};
+use bugstalker::debugger::variable::{SupportedScalar, VariableIR};
+use bugstalker::debugger::{
+    read_memory_by_pid, DebuggerBuilder, Error, ReturnCondition, ReturnValue, StopAt, StopKind,
+    TracepointPlace,
+};
+use bugstalker::ui::command::{r#break, Command};
 use serial_test::serial;
 
 #[test]
@@ -128,12 +137,19 @@ fn test_brkpt_on_line_collision() {
     debugger.start_debugee().unwrap();
     assert_eq!(info.line.take(), Some(14));
 
-    // assert that two breakpoints is set at two lib.rs from two shared libraries
-    let brkpts = debugger.set_breakpoint_at_line("lib.rs", 3).unwrap();
-    assert_eq!(brkpts.len(), 2);
-    // assert that two breakpoints is removed
-    let brkpts = debugger.remove_breakpoint_at_line("lib.rs", 3).unwrap();
-    assert_eq!(brkpts.len(), 2);
+    // assert that lib.rs is ambiguous cause there are lib.rs files in two shared libraries
+    let result = debugger.set_breakpoint_at_line("lib.rs", 3);
+    assert!(matches!(result, Err(Error::AmbiguousFile(_, candidates)) if candidates.len() == 2));
+    let result = debugger.remove_breakpoint_at_line("lib.rs", 3);
+    assert!(matches!(result, Err(Error::AmbiguousFile(_, _))));
+
+    // set breakpoint at current file
+    let brkpts = debugger.set_breakpoint_at_line("", 15).unwrap();
+    assert_eq!(brkpts.len(), 1);
+    let brkpts = debugger
+        .remove_breakpoint_at_line("calc_bin/src/main.rs", 15)
+        .unwrap();
+    assert_eq!(brkpts.len(), 1);
 
     // set breakpoint to function in concrete file
     let brkpts = debugger
@@ -172,6 +188,34 @@ fn test_brkpt_on_line() {
     assert_no_proc!(debugee_pid);
 }
 
+#[test]
+#[serial]
+fn test_stop_summary_at_breakpoint() {
+    let process = prepare_debugee_process(HW_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+    let brkpt = debugger
+        .set_breakpoint_at_line("hello_world.rs", 15)
+        .unwrap();
+    let number = brkpt[0].number;
+
+    debugger.start_debugee().unwrap();
+    let summary = info.stop.take().unwrap();
+    assert_eq!(summary.tid, debugee_pid);
+    assert_eq!(summary.kind, StopKind::Breakpoint { number, hits: 1 });
+    assert_eq!(summary.place.unwrap().line_number, 15);
+
+    debugger.continue_debugee().unwrap();
+    let summary = info.stop.take().unwrap();
+    assert_eq!(summary.kind, StopKind::Breakpoint { number, hits: 2 });
+
+    debugger.continue_debugee().unwrap();
+    assert!(info.stop.take().is_none());
+    assert_no_proc!(debugee_pid);
+}
+
 #[test]
 #[serial]
 fn test_brkpt_on_line2() {
@@ -250,6 +294,16 @@ fn test_breakpoint_at_fn_with_monomorphization() {
         .set_breakpoint_at_fn("FizzBuzzSolver<P,CMP>::new")
         .unwrap();
     assert_eq!(brkpts.len(), 3);
+    let new_brkpt_num = brkpts[0].number;
+
+    let groups = debugger.breakpoint_groups();
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0].pattern, "solve");
+    assert_eq!(groups[0].breakpoints.len(), 3);
+    assert_eq!(
+        debugger.breakpoint_group_of(new_brkpt_num),
+        Some(groups[1].number)
+    );
 
     debugger.start_debugee().unwrap();
     assert_eq!(info.line.take(), Some(80));
@@ -290,3 +344,786 @@ fn test_breakpoint_at_line_with_monomorphization() {
     debugger.continue_debugee().unwrap();
     assert_no_proc!(debugee_pid);
 }
+
+#[test]
+#[serial]
+fn test_tracepoint() {
+    let process = prepare_debugee_process(CALC_APP, &["1", "2", "3", "--description", "result"]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    let dqe = |name| DQE::Variable(VariableSelector::by_name(name, false));
+    let number = debugger
+        .set_tracepoint(
+            TracepointPlace::Function("calc::sum2".to_string()),
+            vec![("a".to_string(), dqe("a")), ("b".to_string(), dqe("b"))],
+        )
+        .unwrap();
+    assert_eq!(number, 1);
+
+    // tracepoints never stop a debugee
+    debugger.start_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+    assert_eq!(info.line.take(), None);
+
+    let tracepoints = debugger.tracepoints();
+    assert_eq!(tracepoints.len(), 1);
+    let tp = &tracepoints[0];
+    assert_eq!(tp.hit_count, 2);
+    assert_eq!(tp.expressions, vec!["a", "b"]);
+
+    let expected = [(1, 2), (3, 3)];
+    for (hit, (a, b)) in tp.hits.iter().zip(expected) {
+        assert_eq!(hit.tid, debugee_pid);
+        assert_scalar(
+            hit.values[0].as_ref().unwrap(),
+            "a",
+            "i64",
+            Some(SupportedScalar::I64(a)),
+        );
+        assert_scalar(
+            hit.values[1].as_ref().unwrap(),
+            "b",
+            "i64",
+            Some(SupportedScalar::I64(b)),
+        );
+    }
+}
+
+#[test]
+#[serial]
+fn test_dprintf() {
+    let process = prepare_debugee_process(CALC_APP, &["1", "2", "3", "--description", "result"]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    let dqe = |name| DQE::Variable(VariableSelector::by_name(name, false));
+    let place = TracepointPlace::Function("calc::sum2".to_string());
+    // number of placeholders must match number of expressions
+    assert!(debugger
+        .set_dprintf(
+            place.clone(),
+            "a = {}, b = {}",
+            vec![("a".to_string(), dqe("a"))]
+        )
+        .is_err());
+    assert!(debugger
+        .set_dprintf(place.clone(), "a = {", vec![])
+        .is_err());
+
+    debugger
+        .set_dprintf(place, "a = {}", vec![("a".to_string(), dqe("a"))])
+        .unwrap();
+
+    debugger.start_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+    assert_eq!(info.line.take(), None);
+
+    let tp = &debugger.tracepoints()[0];
+    assert!(tp.format.is_some());
+    assert_eq!(tp.hit_count, 2);
+}
+
+#[test]
+#[serial]
+fn test_ftrace() {
+    let process = prepare_debugee_process(CALC_APP, &["1", "2", "3", "--description", "result"]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    assert!(matches!(
+        debugger.start_ftrace("calc::unknown*"),
+        Err(Error::NoSuitablePlace)
+    ));
+    assert_eq!(debugger.start_ftrace("calc::sum?").unwrap(), 2);
+    assert_eq!(debugger.ftrace_pattern(), Some("calc::sum?"));
+
+    // traced functions never stop a debugee
+    debugger.start_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+    assert_eq!(info.line.take(), None);
+
+    let trace = info.ftrace.take();
+    let expected = [
+        (0, "calc::sum3", false),
+        (1, "calc::sum2", false),
+        (1, "calc::sum2", true),
+        (1, "calc::sum2", false),
+        (1, "calc::sum2", true),
+        (0, "calc::sum3", true),
+    ];
+    assert_eq!(trace.len(), expected.len());
+    for (event, (depth, function, is_return)) in trace.iter().zip(expected) {
+        assert_eq!(event, &(depth, function.to_string(), is_return));
+    }
+
+    let values = info.ftrace_values.take();
+    let expected: [&[(&str, i64)]; 6] = [
+        &[("a", 1), ("b", 2), ("c", 3)],
+        &[("a", 1), ("b", 2)],
+        &[("", 3)],
+        &[("a", 3), ("b", 3)],
+        &[("", 6)],
+        &[("", 6)],
+    ];
+    for (vars, expected) in values.iter().zip(expected) {
+        assert_eq!(vars.len(), expected.len());
+        for (var, (name, value)) in vars.iter().zip(expected) {
+            let VariableIR::Scalar(scalar) = var else {
+                panic!("not a scalar");
+            };
+            if !name.is_empty() {
+                assert_eq!(var.name(), *name);
+            }
+            assert_eq!(scalar.value, Some(SupportedScalar::I64(*value)));
+        }
+    }
+}
+
+#[test]
+#[serial]
+fn test_ftrace_start_and_stop_at_runtime() {
+    let process = prepare_debugee_process(CALC_APP, &["1", "2", "3", "--description", "result"]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_fn("calc::sum3").unwrap();
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(25));
+
+    assert_eq!(debugger.start_ftrace("calc::sum2").unwrap(), 1);
+    debugger.set_breakpoint_at_line("main.rs", 26).unwrap();
+    debugger.continue_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(26));
+    let trace = info.ftrace.take();
+    assert_eq!(
+        trace,
+        vec![
+            (0, "calc::sum2".to_string(), false),
+            (0, "calc::sum2".to_string(), true)
+        ]
+    );
+
+    // no events after tracing is stopped
+    debugger.stop_ftrace().unwrap();
+    assert_eq!(debugger.ftrace_pattern(), None);
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+    assert!(info.ftrace.take().is_empty());
+}
+
+#[test]
+#[serial]
+fn test_coverage() {
+    let process = prepare_debugee_process(HW_APP, &[]);
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    assert!(matches!(
+        debugger.start_coverage(vec!["unknown.rs".to_string()]),
+        Err(Error::NoSuitablePlace)
+    ));
+
+    debugger
+        .set_breakpoint_at_line("hello_world.rs", 7)
+        .unwrap();
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(7));
+
+    assert!(debugger
+        .start_coverage(vec!["hello_world.rs".to_string()])
+        .is_ok());
+    assert!(debugger.is_coverage_active());
+    let summary = debugger.coverage().summary();
+    assert_eq!(summary.len(), 1);
+    assert_eq!(summary[0].lines_hit, 0);
+    let file = summary[0].path.clone();
+
+    let debugee_pid = debugger.process().pid();
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+
+    let coverage = debugger.coverage();
+    assert_eq!(coverage.is_hit(&file, 9), Some(true));
+    assert_eq!(coverage.is_hit(&file, 15), Some(true));
+    // executed before coverage start
+    assert_eq!(coverage.is_hit(&file, 5), Some(false));
+    // no statements
+    assert_eq!(coverage.is_hit(&file, 3), None);
+
+    // coverage is accumulated across restarts
+    debugger.restart_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(7));
+    assert_eq!(debugger.coverage().is_hit(&file, 5), Some(true));
+    assert_eq!(debugger.coverage().is_hit(&file, 9), Some(true));
+
+    debugger.stop_coverage().unwrap();
+    assert!(!debugger.is_coverage_active());
+    let lcov = debugger.coverage().lcov();
+    assert!(lcov.contains(&format!("SF:{}\n", file.display())));
+    assert!(lcov.contains("DA:5,1\n"));
+    assert!(lcov.ends_with("end_of_record\n"));
+
+    let debugee_pid = debugger.process().pid();
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_brkpt_enable_disable() {
+    let process = prepare_debugee_process(HW_APP, &[]);
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    let fn_brkpt_num = debugger.set_breakpoint_at_fn("myprint").unwrap()[0].number;
+    debugger
+        .set_breakpoint_at_line("hello_world.rs", 9)
+        .unwrap();
+
+    // disable before start
+    let disabled = debugger.disable_breakpoint(fn_brkpt_num).unwrap().unwrap();
+    assert!(!disabled.enabled);
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(9));
+
+    let enabled = debugger.enable_breakpoint(fn_brkpt_num).unwrap().unwrap();
+    assert!(enabled.enabled);
+
+    debugger.continue_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(15));
+
+    let snapshot = debugger.breakpoints_snapshot();
+    assert_eq!(snapshot.len(), 2);
+    assert!(snapshot.iter().all(|brkpt| brkpt.enabled));
+    assert!(snapshot.iter().all(|brkpt| brkpt.hit_count == 1));
+
+    // disable at runtime
+    debugger.disable_breakpoint(fn_brkpt_num).unwrap().unwrap();
+    assert!(debugger.breakpoints_snapshot().iter().any(|b| !b.enabled));
+
+    let debugee_pid = debugger.restart_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(9));
+
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_brkpt_group_by_regex() {
+    let process = prepare_debugee_process(CALC_APP, &["1", "2", "3", "--description", "result"]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    let (group, brkpts) = debugger
+        .set_breakpoints_at_fn_regex(r"^calc::sum\d$")
+        .unwrap();
+    assert_eq!(brkpts.len(), 2);
+    let groups = debugger.breakpoint_groups();
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].number, group);
+    assert_eq!(groups[0].breakpoints.len(), 2);
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(25));
+    debugger.continue_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(21));
+
+    let disabled = debugger.disable_breakpoint_group(group).unwrap();
+    assert_eq!(disabled.len(), 2);
+    assert!(disabled.iter().all(|brkpt| !brkpt.enabled));
+
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_brkpt_disarm_rearm() {
+    let process = prepare_debugee_process(CALC_APP, &["1", "2", "3", "--description", "result"]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_fn("calc::sum2").unwrap();
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(21));
+    let addr = info.addr.take().unwrap();
+
+    // INT3 is planted in memory, but masked in memory read by the debugger
+    let raw = read_memory_by_pid(debugee_pid, addr.as_usize(), 8).unwrap();
+    assert_eq!(raw[0], 0xCC);
+    let masked = debugger.read_memory(addr.as_usize(), 8).unwrap();
+    assert_ne!(masked[0], 0xCC);
+    assert_eq!(masked[1..], raw[1..]);
+
+    // breakpoint is disarmed for a step and stays disarmed until debugee continues
+    debugger.stepi().unwrap();
+    let raw = read_memory_by_pid(debugee_pid, addr.as_usize(), 8).unwrap();
+    assert_eq!(raw, masked);
+    assert_eq!(debugger.read_memory(addr.as_usize(), 8).unwrap(), masked);
+
+    // and re-armed before continue, so the second `sum2` call hits it again
+    debugger.continue_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(21));
+    assert_eq!(info.addr.take(), Some(addr));
+    let raw = read_memory_by_pid(debugee_pid, addr.as_usize(), 8).unwrap();
+    assert_eq!(raw[0], 0xCC);
+
+    debugger.remove_breakpoint_at_fn("calc::sum2").unwrap();
+    let raw = read_memory_by_pid(debugee_pid, addr.as_usize(), 8).unwrap();
+    assert_eq!(raw, masked);
+
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_brkpt_replant_overwritten() {
+    let process = prepare_debugee_process(CALC_APP, &["1", "2", "3", "--description", "result"]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_line("main.rs", 25).unwrap();
+    let sum2_brkpt_num = debugger.set_breakpoint_at_fn("calc::sum2").unwrap()[0].number;
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(25));
+
+    let addr = debugger
+        .breakpoints_snapshot()
+        .into_iter()
+        .find(|brkpt| brkpt.number == sum2_brkpt_num)
+        .unwrap()
+        .addr;
+    let Address::Relocated(addr) = addr else {
+        panic!("breakpoint must be relocated");
+    };
+
+    // emulate a program that overwrites own code (like JIT does)
+    let raw = read_memory_by_pid(debugee_pid, addr.as_usize(), 8).unwrap();
+    assert_eq!(raw[0], 0xCC);
+    let original = debugger.read_memory(addr.as_usize(), 8).unwrap();
+    debugger
+        .write_memory(
+            addr.as_usize(),
+            usize::from_ne_bytes(original.try_into().unwrap()),
+        )
+        .unwrap();
+
+    // breakpoint is planted again before continue
+    debugger.continue_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(21));
+    assert_eq!(info.addr.take(), Some(addr));
+    let raw = read_memory_by_pid(debugee_pid, addr.as_usize(), 8).unwrap();
+    assert_eq!(raw[0], 0xCC);
+
+    debugger.remove_breakpoint_at_fn("calc::sum2").unwrap();
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_hardware_brkpt() {
+    let process = prepare_debugee_process(CALC_APP, &["1", "2", "3", "--description", "result"]);
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    let num = debugger.set_breakpoint_at_fn("calc::sum2").unwrap()[0].number;
+    let brkpts = debugger.make_breakpoints_hardware(&[num]).unwrap();
+    assert!(brkpts[0].hardware);
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(21));
+    let addr = info.addr.take().unwrap();
+    assert!(debugger.breakpoints_snapshot()[0].hardware);
+
+    // there is no planted INT3 in code, so code overwriting doesn't affect breakpoint
+    let debugee_pid = debugger.process().pid();
+    let raw = read_memory_by_pid(debugee_pid, addr.as_usize(), 8).unwrap();
+    assert_ne!(raw[0], 0xCC);
+    assert_eq!(debugger.read_memory(addr.as_usize(), 8).unwrap(), raw);
+
+    // second `sum2` call
+    debugger.continue_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(21));
+    assert_eq!(info.addr.take(), Some(addr));
+
+    // hardware breakpoint survives a restart
+    let debugee_pid = debugger.restart_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(21));
+    assert!(debugger.breakpoints_snapshot()[0].hardware);
+    let raw = read_memory_by_pid(debugee_pid, addr.as_usize(), 8).unwrap();
+    assert_ne!(raw[0], 0xCC);
+
+    debugger.remove_breakpoint_at_fn("calc::sum2").unwrap();
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_hardware_brkpt_limit() {
+    let process = prepare_debugee_process(CALC_APP, &["1", "2", "3", "--description", "result"]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_line("main.rs", 4).unwrap();
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(4));
+
+    // there are only 4 debug registers, the last breakpoint stays a software one
+    let numbers: Vec<_> = [10, 15, 21, 25, 30]
+        .into_iter()
+        .map(|line| debugger.set_breakpoint_at_line("main.rs", line).unwrap()[0].number)
+        .collect();
+    let brkpts = debugger.make_breakpoints_hardware(&numbers).unwrap();
+    assert_eq!(brkpts.iter().filter(|brkpt| brkpt.hardware).count(), 4);
+    assert!(!brkpts[4].hardware);
+
+    for line in [10, 25, 21, 21, 15, 30] {
+        debugger.continue_debugee().unwrap();
+        assert_eq!(info.line.take(), Some(line));
+    }
+
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_breakpoint_commands() {
+    let process = prepare_debugee_process(HW_APP, &[]);
+    let debugee_pid = process.pid();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::default());
+    let mut debugger = builder.build(process).unwrap();
+
+    // breakpoint numbers are global for a process, so don't rely on them
+    let number = debugger
+        .set_breakpoint_at_line("hello_world.rs", 15)
+        .unwrap()[0]
+        .number;
+
+    let input = format!("break commands {number} arg s; continue");
+    let Command::Breakpoint(cmd) = Command::parse(&input).unwrap() else {
+        panic!("breakpoint command expected");
+    };
+    let mut handler = r#break::Handler::new(&mut debugger);
+    let result = handler.handle(&cmd).unwrap();
+    let r#break::ExecutionResult::Commands(Some(brkpt), commands) = result else {
+        panic!("breakpoint with commands expected");
+    };
+    assert_eq!(brkpt.number, number);
+    assert_eq!(commands, ["arg s", "continue"]);
+    // attached commands are console commands
+    for command in &commands {
+        Command::parse(command).unwrap();
+    }
+
+    let cmd = r#break::Command::SetCommands(number + 1, vec!["bt".to_string()]);
+    let mut handler = r#break::Handler::new(&mut debugger);
+    let result = handler.handle(&cmd).unwrap();
+    assert!(matches!(
+        result,
+        r#break::ExecutionResult::Commands(None, _)
+    ));
+
+    // `myprint` is called twice
+    debugger.start_debugee().unwrap();
+    debugger.continue_debugee().unwrap();
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+fn take_returned_i64(info: &TestInfo) -> Option<i64> {
+    let returned = info.returned.take().unwrap().unwrap();
+    let VariableIR::Scalar(scalar) = returned else {
+        panic!("not a scalar");
+    };
+    match scalar.value {
+        Some(SupportedScalar::I64(value)) => Some(value),
+        _ => None,
+    }
+}
+
+#[test]
+#[serial]
+fn test_breakpoint_on_return() {
+    let process = prepare_debugee_process(CALC_APP, &["1", "2", "3", "--description", "result"]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    let brkpts = debugger
+        .set_breakpoint_on_return("calc::sum2", ReturnCondition::Any)
+        .unwrap();
+    assert_eq!(brkpts.len(), 1);
+    let number = brkpts[0].number;
+
+    // program stops in a caller, after a function returns
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(25));
+    assert_eq!(take_returned_i64(&info), Some(3));
+    let stop = info.stop.take().unwrap();
+    assert!(matches!(
+        stop.kind,
+        StopKind::Breakpoint { number: n, hits: 1 } if n == number
+    ));
+
+    // return address of a last call in `calc::sum3` is at function epilogue
+    debugger.continue_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(27));
+    assert_eq!(take_returned_i64(&info), Some(6));
+    let stop = info.stop.take().unwrap();
+    assert!(matches!(
+        stop.kind,
+        StopKind::Breakpoint { number: n, hits: 2 } if n == number
+    ));
+
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_breakpoint_on_return_with_condition() {
+    let process = prepare_debugee_process(CALC_APP, &["1", "2", "3", "--description", "result"]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    let number = debugger
+        .set_breakpoint_on_return("calc::sum2", ReturnCondition::Equal(ReturnValue::Int(6)))
+        .unwrap()[0]
+        .number;
+
+    // first return (with value 3) doesn't satisfy a condition
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(27));
+    assert_eq!(take_returned_i64(&info), Some(6));
+    let stop = info.stop.take().unwrap();
+    assert!(matches!(
+        stop.kind,
+        StopKind::Breakpoint { number: n, hits: 1 } if n == number
+    ));
+
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+    assert!(info.returned.take().is_none());
+}
+
+#[test]
+#[serial]
+fn test_stop_at_entry() {
+    let process = prepare_debugee_process(HW_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger
+        .start_debugee_and_stop(StopAt::Entry, false)
+        .unwrap();
+    let stop = info.stop.take().unwrap();
+    assert_eq!(stop.kind, StopKind::EntryPoint);
+    // no user code is executed yet
+    assert!(debugger.breakpoints_snapshot().is_empty());
+
+    debugger.continue_debugee().unwrap();
+    assert_eq!(info.exit_code.take(), Some(0));
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_stop_at_main() {
+    let process = prepare_debugee_process(HW_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger
+        .start_debugee_and_stop(StopAt::Main, false)
+        .unwrap();
+    assert_eq!(info.line.take(), Some(5));
+    // one-shot breakpoint at `main` is not visible to user and removed after stop
+    assert!(debugger.breakpoints_snapshot().is_empty());
+
+    debugger.continue_debugee().unwrap();
+    assert_eq!(info.exit_code.take(), Some(0));
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_stop_at_main_with_user_breakpoint() {
+    let process = prepare_debugee_process(HW_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    let number = debugger.set_breakpoint_at_fn("main").unwrap()[0].number;
+
+    // user breakpoint at `main` is hit only once
+    debugger
+        .start_debugee_and_stop(StopAt::Main, false)
+        .unwrap();
+    assert_eq!(info.line.take(), Some(5));
+    let stop = info.stop.take().unwrap();
+    assert!(matches!(
+        stop.kind,
+        StopKind::Breakpoint { number: n, hits: 1 } if n == number
+    ));
+    assert_eq!(debugger.breakpoints_snapshot().len(), 1);
+
+    debugger.continue_debugee().unwrap();
+    assert_eq!(info.exit_code.take(), Some(0));
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_breakpoint_at_line_file_resolution() {
+    let process = prepare_debugee_process(CALC_APP, &["1", "2", "3", "--description", "result"]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    // there is no current file until debugee starts
+    assert!(matches!(
+        debugger.set_breakpoint_at_line("", 21),
+        Err(Error::ProcessNotStarted)
+    ));
+    // file name matches many std files
+    let result = debugger.set_breakpoint_at_line("mod.rs", 10);
+    assert!(matches!(result, Err(Error::AmbiguousFile(_, candidates)) if candidates.len() > 1));
+
+    // path suffix is enough to set a breakpoint
+    debugger
+        .set_breakpoint_at_line("calc/src/main.rs", 21)
+        .unwrap();
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(21));
+
+    // breakpoint at current file
+    let brkpts = debugger.set_breakpoint_at_line("", 26).unwrap();
+    assert_eq!(brkpts.len(), 1);
+    debugger.remove_breakpoint_at_line("main.rs", 21).unwrap();
+    debugger.continue_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(26));
+
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_breakpoint_at_column() {
+    let process = prepare_debugee_process(VARS_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    // a thread local access and a closure body are statements at the same line
+    assert_eq!(debugger.line_columns("vars.rs", 188).unwrap(), [24, 39]);
+    let result = debugger.set_breakpoint_at_column("vars.rs", 188, 99);
+    assert!(matches!(result, Err(Error::ColumnNotFound(99, columns)) if columns == [24, 39]));
+
+    let brkpts = debugger
+        .set_breakpoint_at_column("vars.rs", 188, 39)
+        .unwrap();
+    assert_eq!(brkpts.len(), 1);
+
+    // debugee stops inside a closure
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(188));
+    let args = debugger
+        .read_argument(DQE::Variable(VariableSelector::Any))
+        .unwrap();
+    assert!(args.iter().any(|arg| arg.name() == "tl1"));
+
+    let brkpts = debugger
+        .remove_breakpoint_at_column("vars.rs", 188, 39)
+        .unwrap();
+    assert_eq!(brkpts.len(), 1);
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_breakpoint_at_line_diagnostic() {
+    let process = prepare_debugee_process(HW_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    // breakpoint is shifted to the next line with code
+    let brkpts = debugger
+        .set_breakpoint_at_line("hello_world.rs", 6)
+        .unwrap();
+    assert_eq!(brkpts.len(), 1);
+    assert_eq!(brkpts[0].place.as_ref().unwrap().line_number, 7);
+
+    let result = debugger.set_breakpoint_at_line("hello_world.rs", 11);
+    let Err(Error::NoCodeAtLine(11, diagnostic)) = result else {
+        panic!("no code at line expected");
+    };
+    assert_eq!(diagnostic.nearby_lines, [7, 9, 10, 14, 15, 16]);
+    assert_eq!(diagnostic.function, None);
+
+    // there are no lines with code around, the closest one is taken
+    let result = debugger.set_breakpoint_at_line("hello_world.rs", 100);
+    let Err(Error::NoCodeAtLine(100, diagnostic)) = result else {
+        panic!("no code at line expected");
+    };
+    assert_eq!(diagnostic.nearby_lines, [16]);
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(7));
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_breakpoint_at_line_inside_function_diagnostic() {
+    let process = prepare_debugee_process(VARS_APP, &[]);
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::default());
+    let mut debugger = builder.build(process).unwrap();
+
+    // struct declaration inside a function has no code
+    let result = debugger.set_breakpoint_at_line("vars.rs", 38);
+    let Err(Error::NoCodeAtLine(38, diagnostic)) = result else {
+        panic!("no code at line expected");
+    };
+    assert_eq!(diagnostic.function.as_deref(), Some("vars::compound_types"));
+}
@@ -1,10 +1,9 @@
 use bugstalker::debugger::address::RelocatedAddress;
 use bugstalker::debugger::register::debug::BreakCondition;
 use bugstalker::debugger::variable::VariableIR;
-use bugstalker::debugger::{EventHook, FunctionDie, PlaceDescriptor};
+use bugstalker::debugger::{EventHook, FunctionDie, MemoryAccess, PlaceDescriptor, StopSummary};
 use bugstalker::version::Version;
-use nix::sys::signal::Signal;
-use nix::unistd::Pid;
+use bugstalker::{Pid, Signal};
 use object::{Object, ObjectSection};
 use std::cell::{Cell, RefCell};
 use std::fs;
@@ -17,6 +16,18 @@ pub struct TestInfo {
     pub file: Arc<Cell<Option<String>>>,
     pub old_value: Arc<RefCell<Option<VariableIR>>>,
     pub new_value: Arc<RefCell<Option<VariableIR>>>,
+    pub stop: Arc<RefCell<Option<StopSummary>>>,
+    /// Last re-resolved watchpoint address, `Some(None)` if watchpoint is invalidated.
+    pub rebind: Arc<RefCell<Option<Option<RelocatedAddress>>>>,
+    pub access: Arc<Cell<Option<MemoryAccess>>>,
+    pub exit_code: Arc<Cell<Option<i32>>>,
+    /// Value returned from a function at last on-return breakpoint hit,
+    /// `Some(None)` if value can't be read.
+    pub returned: Arc<RefCell<Option<Option<VariableIR>>>>,
+    /// Function trace events as (depth, function, is return) triples.
+    pub ftrace: Arc<RefCell<Vec<(usize, String, bool)>>>,
+    /// Arguments and return values of traced functions (in the same order as trace events).
+    pub ftrace_values: Arc<RefCell<Vec<Vec<VariableIR>>>>,
 }
 
 #[derive(Default)]
@@ -45,6 +56,11 @@ impl EventHook for TestHooks {
         Ok(())
     }
 
+    fn on_function_return(&self, _: u32, value: Option<&VariableIR>) -> anyhow::Result<()> {
+        self.info.returned.replace(Some(value.cloned()));
+        Ok(())
+    }
+
     fn on_watchpoint(
         &self,
         pc: RelocatedAddress,
@@ -53,6 +69,7 @@ impl EventHook for TestHooks {
         _: BreakCondition,
         old_value: Option<&VariableIR>,
         new_value: Option<&VariableIR>,
+        access: Option<MemoryAccess>,
         _: bool,
     ) -> anyhow::Result<()> {
         self.info.addr.set(Some(pc));
@@ -61,6 +78,17 @@ impl EventHook for TestHooks {
         self.info.line.set(place.map(|p| p.line_number));
         self.info.old_value.replace(old_value.cloned());
         self.info.new_value.replace(new_value.cloned());
+        self.info.access.set(access);
+        Ok(())
+    }
+
+    fn on_watchpoint_rebind(
+        &self,
+        _: u32,
+        _: &str,
+        address: Option<RelocatedAddress>,
+    ) -> anyhow::Result<()> {
+        self.info.rebind.replace(Some(address));
         Ok(())
     }
 
@@ -77,8 +105,24 @@ impl EventHook for TestHooks {
         Ok(())
     }
     fn on_signal(&self, _: Signal) {}
-    fn on_exit(&self, _code: i32) {}
-    fn on_process_install(&self, _pid: Pid, _: Option<&object::File>) {}
+    fn on_stop(&self, summary: &StopSummary) {
+        self.info.stop.replace(Some(summary.clone()));
+    }
+    fn on_exit(&self, code: i32) {
+        self.info.exit_code.set(Some(code));
+    }
+    fn on_ftrace_enter(&self, _: Pid, depth: usize, function: &str, args: &[VariableIR]) {
+        let event = (depth, function.to_string(), false);
+        self.info.ftrace.borrow_mut().push(event);
+        self.info.ftrace_values.borrow_mut().push(args.to_vec());
+    }
+    fn on_ftrace_return(&self, _: Pid, depth: usize, function: &str, value: Option<&VariableIR>) {
+        let event = (depth, function.to_string(), true);
+        self.info.ftrace.borrow_mut().push(event);
+        let value = value.cloned().into_iter().collect();
+        self.info.ftrace_values.borrow_mut().push(value);
+    }
+    fn on_process_install(&self, _pid: Pid, _: Option<&Version>) {}
 }
 
 #[macro_export]
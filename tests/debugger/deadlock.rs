@@ -0,0 +1,158 @@
+use crate::common::{TestHooks, TestInfo};
+use crate::prepare_debugee_process;
+use crate::{assert_no_proc, DEADLOCK_APP, HW_APP};
+use bugstalker::debugger::{Deadlock, DebuggerBuilder};
+use serial_test::serial;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Run a deadlock example until its threads are blocked and return found deadlocks.
+/// Both threads are stopped just before the second lock, then example is interrupted
+/// until a deadlock is found or a deadline is reached.
+///
+/// # Arguments
+///
+/// * `args`: example arguments
+/// * `lock_line`: line of the second lock
+fn find_deadlocks(args: &[&'static str], lock_line: u64) -> Vec<Deadlock> {
+    let process = prepare_debugee_process(DEADLOCK_APP, args);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger
+        .set_breakpoint_at_line("deadlock/src/main.rs", lock_line)
+        .unwrap();
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(lock_line));
+    debugger.continue_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(lock_line));
+    debugger
+        .remove_breakpoint_at_line("deadlock/src/main.rs", lock_line)
+        .unwrap();
+
+    let deadline = Instant::now() + Duration::from_secs(10);
+    let deadlocks = loop {
+        let interrupter = debugger.interrupter();
+        let ctrl_c = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(100));
+            interrupter.interrupt();
+        });
+        debugger.continue_debugee().unwrap();
+        ctrl_c.join().unwrap();
+
+        let deadlocks = debugger.deadlock_check().unwrap();
+        if !deadlocks.is_empty() || Instant::now() > deadline {
+            break deadlocks;
+        }
+    };
+
+    drop(debugger);
+    assert_no_proc!(debugee_pid);
+    deadlocks
+}
+
+fn assert_deadlock(deadlocks: &[Deadlock], function: &str, lock_line: u64, guard_line: u64) {
+    assert_eq!(deadlocks.len(), 1);
+    let waits = &deadlocks[0].waits;
+    assert_eq!(waits.len(), 2);
+    assert_ne!(waits[0].mutex, waits[1].mutex);
+
+    for (i, wait) in waits.iter().enumerate() {
+        let next = &waits[(i + 1) % waits.len()];
+        assert_eq!(wait.place.function.as_deref(), Some(function));
+        assert_eq!(wait.place.place.as_ref().map(|p| p.1), Some(lock_line));
+
+        let holder = wait.holder.as_ref().unwrap();
+        assert_eq!(holder.thread.pid, next.thread.pid);
+        assert_eq!(holder.guard, "from_guard");
+        assert_eq!(holder.place.place.as_ref().map(|p| p.1), Some(guard_line));
+    }
+}
+
+#[test]
+#[serial]
+fn test_deadlock_check_std_mutex() {
+    let deadlocks = find_deadlocks(&[], 7);
+    assert_deadlock(&deadlocks, "deadlock::transfer", 7, 5);
+}
+
+#[test]
+#[serial]
+fn test_deadlock_check_parking_lot_mutex() {
+    let deadlocks = find_deadlocks(&["parking_lot"], 19);
+    assert_deadlock(&deadlocks, "deadlock::transfer_pl", 19, 17);
+}
+
+#[test]
+#[serial]
+fn test_deadlock_check_spinning_waiters() {
+    struct TestCase {
+        args: &'static [&'static str],
+        lock_line: u64,
+        contended_fn: &'static str,
+    }
+    let test_cases = [
+        TestCase {
+            args: &[],
+            lock_line: 7,
+            contended_fn: "std::sys::sync::mutex::futex::Mutex::lock_contended",
+        },
+        TestCase {
+            args: &["parking_lot"],
+            lock_line: 19,
+            contended_fn: "parking_lot::raw_mutex::RawMutex::lock_slow",
+        },
+    ];
+
+    for tc in test_cases {
+        let process = prepare_debugee_process(DEADLOCK_APP, tc.args);
+        let debugee_pid = process.pid();
+        let builder = DebuggerBuilder::new().with_hooks(TestHooks::default());
+        let mut debugger = builder.build(process).unwrap();
+
+        debugger
+            .set_breakpoint_at_line("deadlock/src/main.rs", tc.lock_line)
+            .unwrap();
+        debugger.start_debugee().unwrap();
+        debugger.continue_debugee().unwrap();
+        debugger
+            .remove_breakpoint_at_line("deadlock/src/main.rs", tc.lock_line)
+            .unwrap();
+
+        // both threads are stopped at the start of a contended path, before they park
+        debugger.set_breakpoint_at_fn(tc.contended_fn).unwrap();
+        debugger.continue_debugee().unwrap();
+        let deadlocks = debugger.deadlock_check().unwrap();
+        let deadlocks = if deadlocks.is_empty() {
+            debugger.continue_debugee().unwrap();
+            debugger.deadlock_check().unwrap()
+        } else {
+            deadlocks
+        };
+        assert_eq!(deadlocks.len(), 1);
+        assert_eq!(deadlocks[0].waits.len(), 2);
+
+        drop(debugger);
+        assert_no_proc!(debugee_pid);
+    }
+}
+
+#[test]
+#[serial]
+fn test_deadlock_check_without_deadlocks() {
+    let process = prepare_debugee_process(HW_APP, &[]);
+    let debugee_pid = process.pid();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::default());
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger
+        .set_breakpoint_at_line("hello_world.rs", 5)
+        .unwrap();
+    debugger.start_debugee().unwrap();
+    assert!(debugger.deadlock_check().unwrap().is_empty());
+
+    drop(debugger);
+    assert_no_proc!(debugee_pid);
+}
@@ -0,0 +1,119 @@
+use crate::common::TestHooks;
+use crate::common::TestInfo;
+use crate::prepare_debugee_process;
+use crate::{assert_no_proc, CALC_APP};
+use bugstalker::debugger::variable::{SupportedScalar, VariableIR};
+use bugstalker::debugger::DebuggerBuilder;
+use bugstalker::ui::command::display::{Command, DisplayRegistry, ExecutionResult, Handler};
+use bugstalker::ui::command::parser::expression;
+use chumsky::Parser;
+use serial_test::serial;
+
+fn add_cmd(expr: &str) -> Command {
+    Command::Add(expr.to_string(), expression::parser().parse(expr).unwrap())
+}
+
+fn scalar_value(vars: &[VariableIR]) -> Option<SupportedScalar> {
+    let [VariableIR::Scalar(scalar)] = vars else {
+        panic!("single scalar expected");
+    };
+    scalar.value.clone()
+}
+
+/// Return expressions and values of all displays evaluated in current debugee state.
+fn dump(
+    registry: &DisplayRegistry,
+    debugger: &bugstalker::debugger::Debugger,
+) -> Vec<(String, Option<SupportedScalar>)> {
+    registry
+        .evaluate(debugger)
+        .into_iter()
+        .map(|(display, vars)| (display.expr.clone(), scalar_value(&vars)))
+        .collect()
+}
+
+#[test]
+#[serial]
+fn test_display_evaluated_at_each_stop() {
+    let process = prepare_debugee_process(CALC_APP, &["1", "2", "3", "--description", "result"]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+    let mut registry = DisplayRegistry::default();
+
+    // display may be added before debugee starts, there is no value yet
+    let ExecutionResult::New(display, value) =
+        Handler::new(&debugger, &mut registry).handle(add_cmd("a"))
+    else {
+        panic!("new display expected");
+    };
+    assert_eq!(display.number, 1);
+    assert!(value.is_empty());
+    assert!(dump(&registry, &debugger).is_empty());
+
+    debugger.set_breakpoint_at_fn("calc::sum2").unwrap();
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(21));
+
+    let ExecutionResult::New(display, value) =
+        Handler::new(&debugger, &mut registry).handle(add_cmd("b"))
+    else {
+        panic!("new display expected");
+    };
+    assert_eq!(display.number, 2);
+    assert_eq!(scalar_value(&value), Some(SupportedScalar::I64(2)));
+    // variable out of scope is omitted
+    Handler::new(&debugger, &mut registry).handle(add_cmd("description"));
+
+    assert_eq!(
+        dump(&registry, &debugger),
+        vec![
+            ("a".to_string(), Some(SupportedScalar::I64(1))),
+            ("b".to_string(), Some(SupportedScalar::I64(2))),
+        ]
+    );
+
+    debugger.continue_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(21));
+    assert_eq!(
+        dump(&registry, &debugger),
+        vec![
+            ("a".to_string(), Some(SupportedScalar::I64(3))),
+            ("b".to_string(), Some(SupportedScalar::I64(3))),
+        ]
+    );
+
+    let ExecutionResult::Removed(Some(removed)) =
+        Handler::new(&debugger, &mut registry).handle(Command::Remove(1))
+    else {
+        panic!("removed display expected");
+    };
+    assert_eq!(removed.expr, "a");
+    let ExecutionResult::Removed(None) =
+        Handler::new(&debugger, &mut registry).handle(Command::Remove(1))
+    else {
+        panic!("display already removed");
+    };
+    assert_eq!(
+        dump(&registry, &debugger),
+        vec![("b".to_string(), Some(SupportedScalar::I64(3)))]
+    );
+
+    // displays survive a debugee restart
+    debugger.restart_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(21));
+    assert_eq!(
+        dump(&registry, &debugger),
+        vec![("b".to_string(), Some(SupportedScalar::I64(2)))]
+    );
+    assert_eq!(
+        registry.list().iter().map(|d| d.number).collect::<Vec<_>>(),
+        vec![2, 3]
+    );
+
+    debugger.remove_breakpoint_at_fn("calc::sum2").unwrap();
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+    assert!(dump(&registry, &debugger).is_empty());
+}
@@ -0,0 +1,89 @@
+use crate::common::TestHooks;
+use crate::common::TestInfo;
+use crate::{assert_no_proc, prepare_debugee_process, HW_APP, SLEEPER_APP};
+use bugstalker::debugger::{DebuggerBuilder, InferiorStatus, Pid};
+use nix::sys::signal;
+use nix::sys::signal::SIGKILL;
+use serial_test::serial;
+use std::os::unix::process::ExitStatusExt;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
+
+#[test]
+#[serial]
+// child is reaped by a tracer wait
+#[allow(clippy::zombie_processes)]
+fn test_parked_inferior_killed() {
+    let process = prepare_debugee_process(HW_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger
+        .set_breakpoint_at_line("hello_world.rs", 5)
+        .unwrap();
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(5));
+
+    let second = Command::new(SLEEPER_APP)
+        .args(["-s", "1"])
+        .stdout(Stdio::null())
+        .spawn()
+        .unwrap();
+    let second_pid = Pid::from_raw(second.id() as i32);
+    thread::sleep(Duration::from_millis(500));
+
+    let second_info = debugger.attach_inferior(second_pid).unwrap();
+    assert_eq!(second_info.num, 2);
+    debugger.switch_inferior(1).unwrap();
+
+    // parked inferior changes state, its events are reaped by the current one
+    signal::kill(second_pid.into(), SIGKILL).unwrap();
+    debugger.continue_debugee().unwrap();
+    assert_eq!(info.exit_code.take(), Some(0));
+    assert_no_proc!(debugee_pid);
+
+    debugger.switch_inferior(2).unwrap();
+    debugger.continue_debugee().unwrap();
+    assert_eq!(info.exit_code.take(), Some(128 + SIGKILL as i32));
+
+    let inferiors = debugger.inferiors();
+    assert_eq!(inferiors[1].status, InferiorStatus::Exited);
+    assert_no_proc!(second_pid);
+}
+
+#[test]
+#[serial]
+fn test_parked_inferior_killed_before_drop() {
+    let process = prepare_debugee_process(HW_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger
+        .set_breakpoint_at_line("hello_world.rs", 5)
+        .unwrap();
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(5));
+
+    let mut second = Command::new(SLEEPER_APP)
+        .args(["-s", "1"])
+        .stdout(Stdio::null())
+        .spawn()
+        .unwrap();
+    let second_pid = Pid::from_raw(second.id() as i32);
+    thread::sleep(Duration::from_millis(500));
+
+    debugger.attach_inferior(second_pid).unwrap();
+    debugger.switch_inferior(1).unwrap();
+
+    // parked inferior is released at drop without a resume
+    signal::kill(second_pid.into(), SIGKILL).unwrap();
+    drop(debugger);
+    assert_no_proc!(debugee_pid);
+    let status = second.wait().unwrap();
+    assert_eq!(status.signal(), Some(SIGKILL as i32), "{status:?}");
+}
@@ -1,12 +1,18 @@
 use crate::common::TestHooks;
 use crate::common::TestInfo;
-use crate::HW_APP;
+use crate::variables::assert_scalar;
 use crate::{assert_no_proc, prepare_debugee_process, CALC_APP};
+use crate::{ECHO_APP, HW_APP, MT_APP};
+use bugstalker::debugger::process::Child;
+use bugstalker::debugger::register::{Register, RegisterMap, VectorRegister};
 use bugstalker::debugger::variable::render::{RenderRepr, ValueLayout};
-use bugstalker::debugger::DebuggerBuilder;
+use bugstalker::debugger::variable::select::DQE;
+use bugstalker::debugger::variable::{SupportedScalar, VariableIR};
+use bugstalker::debugger::{rust, Debugger, DebuggerBuilder, Error};
 use serial_test::serial;
 use std::borrow::Cow;
-use std::mem;
+use std::io::{BufRead, BufReader};
+use std::{fs, mem, thread};
 
 #[test]
 #[serial]
@@ -31,6 +37,130 @@ fn test_read_register_write() {
     assert_no_proc!(debugee_pid);
 }
 
+#[test]
+#[serial]
+fn test_register_cache_freshness() {
+    let process = prepare_debugee_process(HW_APP, &[]);
+    let debugee_pid = process.pid();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::default());
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger
+        .set_breakpoint_at_line("hello_world.rs", 10)
+        .unwrap();
+
+    debugger.start_debugee().unwrap();
+
+    // written value is visible to a debugger and to a debugee
+    let rax = debugger.get_register_value("rax").unwrap();
+    debugger.set_register_value("rax", rax + 1).unwrap();
+    assert_eq!(debugger.get_register_value("rax").unwrap(), rax + 1);
+    let current = RegisterMap::current(debugee_pid).unwrap();
+    assert_eq!(current.value(Register::Rax), rax + 1);
+    debugger.set_register_value("rax", rax).unwrap();
+
+    // registers are reloaded after a debugee moves
+    let rip = debugger.get_register_value("rip").unwrap();
+    debugger.stepi().unwrap();
+    let new_rip = debugger.get_register_value("rip").unwrap();
+    assert_ne!(rip, new_rip);
+    let current = RegisterMap::current(debugee_pid).unwrap();
+    assert_eq!(current.value(Register::Rip), new_rip);
+    assert_eq!(
+        debugger.get_register_value("rsp").unwrap(),
+        current.value(Register::Rsp)
+    );
+
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_read_vector_register() {
+    let process = prepare_debugee_process(HW_APP, &[]);
+    let debugee_pid = process.pid();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::default());
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger
+        .set_breakpoint_at_line("hello_world.rs", 10)
+        .unwrap();
+
+    debugger.start_debugee().unwrap();
+
+    let (xmm, xmm_bytes) = debugger.get_vector_register_value("xmm1").unwrap();
+    assert_eq!(xmm, VectorRegister::Xmm(1));
+    assert_eq!(xmm_bytes.len(), 16);
+
+    // lower half of YMM register is a corresponding XMM register
+    let (ymm, ymm_bytes) = debugger.get_vector_register_value("ymm1").unwrap();
+    assert_eq!(ymm, VectorRegister::Ymm(1));
+    assert_eq!(ymm_bytes.len(), 32);
+    assert_eq!(ymm_bytes[..16], xmm_bytes);
+
+    assert!(debugger.get_vector_register_value("xmm16").is_err());
+    assert!(debugger.get_vector_register_value("zmm0").is_err());
+
+    mem::drop(debugger);
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_read_register_in_expression() {
+    let process = prepare_debugee_process(HW_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger
+        .set_breakpoint_at_line("hello_world.rs", 15)
+        .unwrap();
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(15));
+
+    let rsp = debugger.get_register_value("rsp").unwrap();
+    let vars = debugger
+        .read_variable(DQE::Register("rsp".to_string()))
+        .unwrap();
+    assert_eq!(vars.len(), 1);
+    assert_scalar(&vars[0], "$rsp", "u64", Some(SupportedScalar::U64(rsp)));
+    assert!(debugger
+        .read_variable(DQE::Register("xyz".to_string()))
+        .is_err());
+
+    // program counter and stack pointer are restored at an outer frame
+    let bt = debugger.backtrace(debugee_pid).unwrap();
+    debugger.set_frame_into_focus(1).unwrap();
+    let vars = debugger
+        .read_variable(DQE::Register("rip".to_string()))
+        .unwrap();
+    assert_scalar(
+        &vars[0],
+        "$rip",
+        "u64",
+        Some(SupportedScalar::U64(bt[1].ip.as_u64())),
+    );
+    let vars = debugger
+        .read_variable(DQE::Register("rsp".to_string()))
+        .unwrap();
+    let VariableIR::Scalar(ref outer_rsp) = vars[0] else {
+        panic!("not a scalar");
+    };
+    assert!(matches!(outer_rsp.value, Some(SupportedScalar::U64(v)) if v > rsp));
+    assert!(debugger
+        .read_variable(DQE::Register("rax".to_string()))
+        .is_err());
+
+    debugger.continue_debugee().unwrap();
+    debugger.continue_debugee().unwrap();
+
+    assert_no_proc!(debugee_pid);
+}
+
 #[test]
 #[serial]
 fn test_backtrace() {
@@ -61,6 +191,37 @@ fn test_backtrace() {
     assert_no_proc!(debugee_pid);
 }
 
+#[test]
+#[serial]
+fn test_backtrace_with_args() {
+    let process = prepare_debugee_process(CALC_APP, &["1", "2", "3", "--description", "result"]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+    debugger.set_breakpoint_at_line("main.rs", 21).unwrap();
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(21));
+
+    let mut bt = debugger.backtrace(debugee_pid).unwrap();
+    debugger.resolve_frame_arguments(debugee_pid, &mut bt);
+
+    // frame #0 is `sum2(1, 2)`, frame #1 is `sum3(1, 2, 3)`
+    let expected: [&[(&str, i64)]; 2] = [&[("a", 1), ("b", 2)], &[("a", 1), ("b", 2), ("c", 3)]];
+    for (frame, expected_args) in bt.iter().zip(expected) {
+        let args = frame.args.as_ref().unwrap();
+        assert_eq!(args.len(), expected_args.len());
+        for (arg, (name, value)) in args.iter().zip(expected_args) {
+            assert_scalar(arg, name, "i64", Some(SupportedScalar::I64(*value)));
+        }
+    }
+
+    debugger.remove_breakpoint_at_line("main.rs", 21).unwrap();
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
 #[test]
 #[serial]
 fn test_read_value_u64() {
@@ -89,3 +250,124 @@ fn test_read_value_u64() {
 
     assert_no_proc!(debugee_pid);
 }
+
+#[test]
+#[serial]
+fn test_debugee_stdin_redirect() {
+    let (reader, writer) = os_pipe::pipe().unwrap();
+    let output = thread::spawn(move || {
+        BufReader::new(reader)
+            .lines()
+            .map_while(Result::ok)
+            .collect::<Vec<_>>()
+    });
+    rust::Environment::init(None);
+    let process = Child::new(
+        ECHO_APP,
+        Vec::<&str>::new(),
+        writer.try_clone().unwrap(),
+        writer,
+    )
+    .install()
+    .unwrap();
+
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    let stdin = std::env::temp_dir().join(format!("bs_stdin_{}", std::process::id()));
+    fs::write(&stdin, "first\nsecond\n").unwrap();
+    let missing = stdin.with_extension("missing");
+    assert!(matches!(
+        debugger.set_debugee_stdin(Some(missing.clone())),
+        Err(Error::StdinRedirect(path, _)) if path == missing
+    ));
+    debugger.set_debugee_stdin(Some(stdin.clone())).unwrap();
+
+    debugger.set_breakpoint_at_line("main.rs", 11).unwrap();
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(11));
+
+    // stdin redirection is kept after restart, the file is read from the beginning
+    // (first run is killed before the summary line printed)
+    debugger.restart_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(11));
+    let pid = debugger.process().pid();
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(pid);
+
+    drop(debugger);
+    fs::remove_file(stdin).unwrap();
+    assert_eq!(
+        output.join().unwrap(),
+        vec![
+            "echo: first",
+            "echo: second",
+            "echo: first",
+            "echo: second",
+            "lines read: 2",
+        ]
+    );
+}
+
+#[test]
+#[serial]
+fn test_read_segment_base_registers() {
+    let process = prepare_debugee_process(MT_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    let read_u64_register = |debugger: &Debugger, name: &str| -> u64 {
+        let vars = debugger
+            .read_variable(DQE::Register(name.to_string()))
+            .unwrap();
+        let VariableIR::Scalar(scalar) = &vars[0] else {
+            panic!("scalar expected");
+        };
+        let Some(SupportedScalar::U64(value)) = scalar.value else {
+            panic!("u64 expected");
+        };
+        value
+    };
+
+    debugger.set_breakpoint_at_line("mt.rs", 24).unwrap();
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(24));
+
+    let threads = debugger.thread_state().unwrap();
+    let current_thread = threads.iter().find(|t| t.in_focus).unwrap();
+    let thread_pid = current_thread.thread.pid;
+    let brkpt_thread_num = current_thread.thread.number;
+
+    // FS base is a thread pointer, it always set up for rust programs
+    let fs_base = read_u64_register(&debugger, "fs_base");
+    assert_ne!(fs_base, 0);
+    let registers = RegisterMap::current(thread_pid).unwrap();
+    assert_eq!(registers.value(Register::FsBase), fs_base);
+    assert_eq!(registers.thread_pointer(), fs_base);
+    let gs_base = read_u64_register(&debugger, "gs_base");
+    assert_eq!(registers.value(Register::GsBase), gs_base);
+
+    // segment bases are the same for all frames of a thread
+    debugger.set_frame_into_focus(1).unwrap();
+    assert_eq!(read_u64_register(&debugger, "fs_base"), fs_base);
+    assert_eq!(read_u64_register(&debugger, "gs_base"), gs_base);
+
+    // but differ between threads
+    let main_thread = threads
+        .iter()
+        .find(|t| t.thread.pid == debugee_pid)
+        .unwrap();
+    debugger
+        .set_thread_into_focus(main_thread.thread.number)
+        .unwrap();
+    let main_fs_base = read_u64_register(&debugger, "fs_base");
+    assert_ne!(main_fs_base, 0);
+    assert_ne!(main_fs_base, fs_base);
+
+    debugger.set_thread_into_focus(brkpt_thread_num).unwrap();
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
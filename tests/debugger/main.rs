@@ -1,6 +1,9 @@
 mod common;
 
 mod breakpoints;
+mod deadlock;
+mod display;
+mod inferior;
 mod io;
 mod multithreaded;
 mod signal;
@@ -12,10 +15,14 @@ mod watchpoint;
 use crate::common::{TestHooks, TestInfo};
 use bugstalker::debugger::process::{Child, Installed};
 use bugstalker::debugger::register::{Register, RegisterMap};
-use bugstalker::debugger::{rust, DebuggerBuilder};
+use bugstalker::debugger::{rust, DebuggerBuilder, ParsingProgress, StackSlotAnnotation};
+#[cfg(feature = "tui")]
+use bugstalker::ui::tui::utils::logger::TuiLogger;
 use serial_test::serial;
 use std::io::{BufRead, BufReader};
+use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::Duration;
 
 pub fn prepare_debugee_process(prog: &str, args: &[&'static str]) -> Child<Installed> {
     let (reader, writer) = os_pipe::pipe().unwrap();
@@ -40,13 +47,19 @@ pub fn prepare_debugee_process(prog: &str, args: &[&'static str]) -> Child<Insta
 const HW_APP: &str = "./examples/target/debug/hello_world";
 const CALC_APP: &str = "./examples/target/debug/calc";
 const MT_APP: &str = "./examples/target/debug/mt";
+const MT_WRITERS_APP: &str = "./examples/target/debug/mt_writers";
 const VARS_APP: &str = "./examples/target/debug/vars";
 const RECURSION_APP: &str = "./examples/target/debug/recursion";
 const SIGNALS_APP: &str = "./examples/target/debug/signals";
+const TRAP_FLAG_APP: &str = "./examples/target/debug/trap_flag";
 const SHARED_LIB_APP: &str = "./examples/target/debug/calc_bin";
 const SLEEPER_APP: &str = "./examples/target/debug/sleeper";
 const FIZZBUZZ_APP: &str = "./examples/target/debug/fizzbuzz";
 const CALCULATIONS_APP: &str = "./examples/target/debug/calculations";
+const ECHO_APP: &str = "./examples/target/debug/echo";
+const SPAWNER_APP: &str = "./examples/target/debug/spawner";
+const DEADLOCK_APP: &str = "./examples/target/debug/deadlock";
+const CHANNELS_APP: &str = "./examples/target/debug/channels";
 
 #[test]
 #[serial]
@@ -143,6 +156,44 @@ fn test_registers() {
     assert_no_proc!(debugee_pid);
 }
 
+#[test]
+#[serial]
+fn test_frame_memory() {
+    let process = prepare_debugee_process(HW_APP, &[]);
+    let debugee_pid = process.pid();
+
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+    debugger
+        .set_breakpoint_at_line("hello_world.rs", 5)
+        .unwrap();
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(5));
+
+    let frame = debugger.frame_info().unwrap();
+    let memory = debugger.frame_memory().unwrap();
+    assert_eq!(memory.cfa, frame.cfa);
+    assert!(memory.sp < memory.cfa);
+    assert!(!memory.slots.is_empty());
+
+    // return address is stored right below the cfa
+    let ret_addr_slot = memory
+        .slots
+        .iter()
+        .find(|slot| {
+            slot.annotations
+                .contains(&StackSlotAnnotation::ReturnAddress)
+        })
+        .unwrap();
+    assert_eq!(ret_addr_slot.addr, frame.cfa.offset(-8));
+    assert_eq!(ret_addr_slot.value, u64::from(frame.return_addr.unwrap()));
+
+    drop(debugger);
+    assert_no_proc!(debugee_pid);
+}
+
 #[test]
 #[serial]
 fn test_debugger_disassembler() {
@@ -168,3 +219,222 @@ fn test_debugger_disassembler() {
     drop(debugger);
     assert_no_proc!(pid);
 }
+
+#[test]
+#[serial]
+fn test_debugger_disassembler_instruction_step() {
+    let process = prepare_debugee_process(HW_APP, &[]);
+    let pid = process.pid();
+
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::default());
+    let mut debugger = builder.build(process).unwrap();
+    debugger.set_breakpoint_at_fn("main").unwrap();
+    debugger.start_debugee().unwrap();
+
+    let before_step = debugger.disasm().unwrap();
+    debugger.stepi().unwrap();
+    let after_step = debugger.disasm().unwrap();
+
+    // focus moves to the next instruction of the same function
+    assert_eq!(after_step.name, before_step.name);
+    let focus_idx = before_step
+        .instructions
+        .iter()
+        .position(|i| i.address == before_step.addr_in_focus)
+        .unwrap();
+    assert_eq!(
+        after_step.addr_in_focus,
+        before_step.instructions[focus_idx + 1].address
+    );
+
+    drop(debugger);
+    assert_no_proc!(pid);
+}
+
+#[test]
+#[serial]
+fn test_debugger_disassembler_without_debug_info() {
+    let process = prepare_debugee_process(SLEEPER_APP, &["-s", "2"]);
+    let pid = process.pid();
+
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::default());
+    let mut debugger = builder.build(process).unwrap();
+
+    // interrupt debugee while main thread sleeps in libc code
+    let interrupter = debugger.interrupter();
+    let ctrl_c = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(500));
+        interrupter.interrupt();
+    });
+    debugger.start_debugee().unwrap();
+    ctrl_c.join().unwrap();
+
+    // no function information, so instructions are disassembled from the program counter
+    let assembly = debugger.disasm().unwrap();
+    assert_eq!(assembly.name, None);
+    assert!(!assembly.instructions.is_empty());
+    assert_eq!(assembly.instructions[0].address, assembly.addr_in_focus);
+
+    drop(debugger);
+    assert_no_proc!(pid);
+}
+
+#[cfg(feature = "tui")]
+#[test]
+#[serial]
+fn test_tui_logger_captures_tracer_events() {
+    struct NopLogger;
+    impl log::Log for NopLogger {
+        fn enabled(&self, _: &log::Metadata) -> bool {
+            false
+        }
+        fn log(&self, _: &log::Record) {}
+        fn flush(&self) {}
+    }
+
+    // tracer events must be captured even if nothing is allowed by RUST_LOG
+    std::env::remove_var("RUST_LOG");
+    let buffer = Arc::new(Mutex::new(vec![]));
+    let logger = TuiLogger::new(buffer.clone());
+    let filter = logger.filter();
+    assert_eq!(filter, log::LevelFilter::Debug);
+    bugstalker::log::LOGGER_SWITCHER.switch(logger, filter);
+
+    let process = prepare_debugee_process(HW_APP, &[]);
+    let pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+    debugger
+        .set_breakpoint_at_line("hello_world.rs", 5)
+        .unwrap();
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(5));
+
+    log::debug!(target: "not_a_tracer", "must be filtered");
+
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(pid);
+    bugstalker::log::LOGGER_SWITCHER.switch(NopLogger, log::LevelFilter::Off);
+
+    let lines: Vec<String> = buffer
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|line| {
+            line.to_text_spans()
+                .into_iter()
+                .map(|span| span.content)
+                .collect()
+        })
+        .collect();
+    assert!(lines.iter().any(|l| l.contains(" tracer] debugee stopped")));
+    assert!(lines.iter().all(|l| !l.contains("must be filtered")));
+}
+
+#[test]
+#[serial]
+fn test_debug_info_parsing_progress() {
+    let process = prepare_debugee_process(HW_APP, &[]);
+    let pid = process.pid();
+
+    let reports = Arc::new(Mutex::new(vec![]));
+    let reports_c = reports.clone();
+    let builder = DebuggerBuilder::new()
+        .with_hooks(TestHooks::default())
+        .with_progress(Arc::new(move |progress: &ParsingProgress| {
+            reports_c.lock().unwrap().push((
+                progress.file.to_path_buf(),
+                progress.units_parsed,
+                progress.units_total,
+                progress.bytes_parsed,
+                progress.bytes_total,
+                progress.is_done(),
+            ));
+        }));
+    let mut debugger = builder.build(process).unwrap();
+
+    let reports = reports.lock().unwrap().clone();
+    let program_reports: Vec<_> = reports
+        .iter()
+        .filter(|(file, ..)| file.ends_with("hello_world"))
+        .collect();
+
+    // first report is sent before any unit is parsed, one more report for each parsed unit
+    let (_, units, units_total, bytes, bytes_total, done) = program_reports[0];
+    assert_eq!((*units, *bytes, *done), (0, 0, false));
+    assert!(*units_total > 0 && *bytes_total > 0);
+    assert_eq!(program_reports.len(), units_total + 1);
+    assert!(program_reports
+        .iter()
+        .any(|(_, units, _, bytes, _, done)| *done
+            && *units == *units_total
+            && *bytes == *bytes_total));
+
+    // progress never exceeds totals for every parsed file
+    for (file, units, units_total, bytes, bytes_total, done) in &reports {
+        assert!(units <= units_total && bytes <= bytes_total, "{file:?}");
+        assert_eq!(*done, units == units_total);
+    }
+
+    debugger.start_debugee().unwrap();
+    assert_no_proc!(pid);
+}
+
+/// Return true if process is running (not exited and not a zombie).
+fn is_alive(pid: bugstalker::Pid) -> bool {
+    std::fs::read_to_string(format!("/proc/{pid}/stat"))
+        .map(|stat| {
+            let state = stat.rsplit(')').next().unwrap_or_default().trim_start();
+            !state.starts_with('Z')
+        })
+        .unwrap_or(false)
+}
+
+#[test]
+#[serial]
+fn test_kill_debugee() {
+    let process = prepare_debugee_process(SPAWNER_APP, &[]);
+    let pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    // nothing to kill before start
+    assert!(matches!(
+        debugger.kill_debugee(),
+        Err(bugstalker::debugger::Error::ProcessNotStarted)
+    ));
+
+    debugger.set_breakpoint_at_line("main.rs", 6).unwrap();
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(6));
+
+    let children = debugger.process().descendants();
+    assert_eq!(children.len(), 1);
+    assert!(is_alive(children[0]));
+
+    // debugee is killed together with a spawned process
+    debugger.kill_debugee().unwrap();
+    assert_eq!(info.exit_code.take(), Some(128 + 9));
+    assert_no_proc!(pid);
+    thread::sleep(Duration::from_millis(100));
+    assert!(!is_alive(children[0]));
+    assert!(matches!(
+        debugger.kill_debugee(),
+        Err(bugstalker::debugger::Error::ProcessNotStarted)
+    ));
+
+    // breakpoints are kept for the next run
+    debugger.start_debugee_force().unwrap();
+    assert_eq!(info.line.take(), Some(6));
+    let pid = debugger.process().pid();
+    let children = debugger.process().descendants();
+    assert_eq!(children.len(), 1);
+
+    // spawned processes are killed when debugger exits too
+    drop(debugger);
+    assert_no_proc!(pid);
+    thread::sleep(Duration::from_millis(100));
+    assert!(!is_alive(children[0]));
+}
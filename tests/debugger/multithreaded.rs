@@ -1,11 +1,14 @@
 use crate::common::TestHooks;
 use crate::common::TestInfo;
 use crate::prepare_debugee_process;
-use crate::{assert_no_proc, MT_APP};
+use crate::{assert_no_proc, MT_APP, MT_WRITERS_APP};
+use bugstalker::debugger::register::debug::BreakCondition;
 use bugstalker::debugger::unwind::Backtrace;
-use bugstalker::debugger::DebuggerBuilder;
+use bugstalker::debugger::variable::select::{VariableSelector, DQE};
+use bugstalker::debugger::{DebuggerBuilder, StopKind, StopType, TraceeStatus};
 use itertools::Itertools;
 use serial_test::serial;
+use std::collections::HashMap;
 use std::ffi::OsStr;
 
 #[test]
@@ -51,6 +54,30 @@ fn test_multithreaded_breakpoints() {
     assert_no_proc!(debugee_pid);
 }
 
+#[test]
+#[serial]
+fn test_multithreaded_hardware_breakpoints() {
+    let process = prepare_debugee_process(MT_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    // debug registers are per-thread, breakpoints must be distributed to new threads
+    let numbers =
+        [24, 36].map(|line| debugger.set_breakpoint_at_line("mt.rs", line).unwrap()[0].number);
+    let brkpts = debugger.make_breakpoints_hardware(&numbers).unwrap();
+    assert!(brkpts.iter().all(|brkpt| brkpt.hardware));
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(36));
+    debugger.continue_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(24));
+
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
 fn backtrace_contains_fn(backtrace: &Backtrace, f_name: &str) -> bool {
     backtrace.iter().any(|frame| {
         frame
@@ -90,6 +117,90 @@ fn test_multithreaded_backtrace() {
     assert_no_proc!(debugee_pid);
 }
 
+#[test]
+#[serial]
+fn test_thread_origin() {
+    let process = prepare_debugee_process(MT_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_line("mt.rs", 24).unwrap();
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(24));
+
+    let threads = debugger.thread_state().unwrap();
+    let main_thread = threads
+        .iter()
+        .find(|t| t.thread.pid == debugee_pid)
+        .unwrap();
+    assert!(main_thread.origin_bt.is_none());
+    assert_eq!(main_thread.name.as_deref(), Some("mt"));
+
+    let current_thread = threads.iter().find(|t| t.in_focus).unwrap();
+    let origin_bt = current_thread.origin_bt.as_ref().unwrap();
+    assert!(backtrace_contains_fn(origin_bt, "mt::main"));
+
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_thread_state_and_focus_switch() {
+    let process = prepare_debugee_process(MT_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_line("mt.rs", 24).unwrap();
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(24));
+
+    let threads = debugger.thread_state().unwrap();
+    assert!(threads
+        .iter()
+        .all(|t| t.thread.status == TraceeStatus::Stopped(StopType::Interrupt)));
+    assert_eq!(
+        threads.iter().map(|t| t.thread.number).unique().count(),
+        threads.len()
+    );
+    assert_eq!(threads.iter().filter(|t| t.in_focus).count(), 1);
+    let current_thread = threads.iter().find(|t| t.in_focus).unwrap();
+    assert_ne!(current_thread.thread.pid, debugee_pid);
+    let brkpt_thread_num = current_thread.thread.number;
+
+    // bring main thread into focus
+    let main_thread = threads
+        .iter()
+        .find(|t| t.thread.pid == debugee_pid)
+        .unwrap();
+    let tracee = debugger
+        .set_thread_into_focus(main_thread.thread.number)
+        .unwrap();
+    assert_eq!(tracee.pid, debugee_pid);
+
+    let threads = debugger.thread_state().unwrap();
+    let current_thread = threads.iter().find(|t| t.in_focus).unwrap();
+    assert_eq!(current_thread.thread.pid, debugee_pid);
+    assert!(backtrace_contains_fn(
+        current_thread.bt.as_ref().unwrap(),
+        "mt::main"
+    ));
+
+    // focus on thread that not exists
+    assert!(debugger.set_thread_into_focus(u32::MAX).is_err());
+
+    // return focus to the thread stopped at breakpoint, so continue steps over it
+    debugger.set_thread_into_focus(brkpt_thread_num).unwrap();
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
 #[test]
 #[serial]
 fn test_multithreaded_trace() {
@@ -123,3 +234,45 @@ fn test_multithreaded_trace() {
     debugger.continue_debugee().unwrap();
     assert_no_proc!(debugee_pid);
 }
+
+#[test]
+#[serial]
+fn test_multithreaded_watchpoint_hits_are_not_lost() {
+    let process = prepare_debugee_process(MT_WRITERS_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger
+        .set_breakpoint_at_line("mt_writers.rs", 18)
+        .unwrap();
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(18));
+
+    let dqe = ["v", "value"].into_iter().fold(
+        DQE::Variable(VariableSelector::by_name("COUNTER", false)),
+        |dqe, field| DQE::Field(dqe.boxed(), field.to_string()),
+    );
+    debugger
+        .set_watchpoint_on_expr("COUNTER.v.value", dqe, BreakCondition::DataWrites)
+        .unwrap();
+
+    // writers hit the watchpoint at the same time, a hit caught while other thread
+    // stops must be reported later instead of being lost
+    let mut hits = HashMap::new();
+    loop {
+        info.stop.take();
+        debugger.continue_debugee().unwrap();
+        let Some(summary) = info.stop.take() else {
+            break;
+        };
+        assert!(matches!(summary.kind, StopKind::Watchpoint { .. }));
+        *hits.entry(summary.tid).or_insert(0) += 1;
+    }
+
+    assert_eq!(hits.len(), 2);
+    assert!(hits.values().all(|&count| count == 100));
+
+    assert_no_proc!(debugee_pid);
+}
@@ -1,10 +1,15 @@
 use crate::common::TestHooks;
 use crate::common::TestInfo;
-use crate::{assert_no_proc, prepare_debugee_process, SIGNALS_APP, SLEEPER_APP};
-use bugstalker::debugger::DebuggerBuilder;
+use crate::{assert_no_proc, prepare_debugee_process, SIGNALS_APP, SLEEPER_APP, TRAP_FLAG_APP};
+use bugstalker::debugger::process::Child;
+use bugstalker::debugger::{rust, DebuggerBuilder};
+use bugstalker::ui::command::r#continue;
+use bugstalker::StopKind;
 use nix::sys::signal;
 use nix::sys::signal::{SIGINT, SIGUSR1, SIGUSR2};
 use serial_test::serial;
+use std::io::{BufRead, BufReader};
+use std::sync::mpsc;
 use std::thread;
 use std::time::Duration;
 
@@ -21,7 +26,7 @@ fn test_signal_stop_single_thread() {
 
     thread::spawn(move || {
         thread::sleep(Duration::from_secs(4));
-        signal::kill(debugee_pid, SIGUSR1).unwrap();
+        signal::kill(debugee_pid.into(), SIGUSR1).unwrap();
     });
 
     debugger.start_debugee().unwrap();
@@ -49,7 +54,7 @@ fn test_signal_stop_multi_thread() {
 
     thread::spawn(move || {
         thread::sleep(Duration::from_secs(4));
-        signal::kill(debugee_pid, SIGUSR1).unwrap();
+        signal::kill(debugee_pid.into(), SIGUSR1).unwrap();
     });
 
     debugger.start_debugee().unwrap();
@@ -75,8 +80,8 @@ fn test_signal_stop_multi_thread_multiple_signal() {
 
     thread::spawn(move || {
         thread::sleep(Duration::from_secs(4));
-        signal::kill(debugee_pid, SIGUSR1).unwrap();
-        signal::kill(debugee_pid, SIGUSR2).unwrap();
+        signal::kill(debugee_pid.into(), SIGUSR1).unwrap();
+        signal::kill(debugee_pid.into(), SIGUSR2).unwrap();
     });
 
     debugger.start_debugee().unwrap();
@@ -103,7 +108,7 @@ fn test_transparent_signals() {
 
     thread::spawn(move || {
         thread::sleep(Duration::from_secs(2));
-        signal::kill(debugee_pid, SIGINT).unwrap();
+        signal::kill(debugee_pid.into(), SIGINT).unwrap();
     });
 
     debugger.start_debugee().unwrap();
@@ -114,3 +119,246 @@ fn test_transparent_signals() {
     drop(debugger);
     assert_no_proc!(debugee_pid);
 }
+
+#[test]
+#[serial]
+fn test_continue_for_interrupts_debugee() {
+    let process = prepare_debugee_process(SLEEPER_APP, &["-s", "2"]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_line("sleeper.rs", 12).unwrap();
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(12));
+
+    // all threads sleeping at this moment, so debugee is interrupted by the timer
+    r#continue::Handler::new(&mut debugger)
+        .handle(Some(Duration::from_millis(500)))
+        .unwrap();
+    let summary = info.stop.take().unwrap();
+    assert_eq!(summary.kind, StopKind::Interrupt);
+
+    // SIGINT is not delivered to debugee, so it finishes normally
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_continue_for_cancels_timer_at_stop() {
+    let process = prepare_debugee_process(SLEEPER_APP, &["-s", "1"]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_line("sleeper.rs", 12).unwrap();
+    debugger.set_breakpoint_at_line("sleeper.rs", 24).unwrap();
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(12));
+
+    // debugee stops at the breakpoint before the timer fires
+    r#continue::Handler::new(&mut debugger)
+        .handle(Some(Duration::from_secs(10)))
+        .unwrap();
+    assert_eq!(info.line.take(), Some(24));
+    let summary = info.stop.take().unwrap();
+    assert!(matches!(summary.kind, StopKind::Breakpoint { .. }));
+
+    // timer fires right after debugee stops by itself, an interrupt is cancelled
+    // and the already sent SIGINT doesn't stop the debugee later
+    let interrupter = debugger.interrupter();
+    interrupter.interrupt();
+    interrupter.cancel();
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+    assert!(info.stop.take().is_none());
+}
+
+#[test]
+#[serial]
+fn test_profile_sampling() {
+    let process = prepare_debugee_process(SLEEPER_APP, &["-s", "1"]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_line("sleeper.rs", 12).unwrap();
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(12));
+    assert!(info.stop.take().is_some());
+
+    debugger.start_profiling(Duration::from_millis(20));
+    // sampling never stops a debugee
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+    assert!(info.stop.take().is_none());
+
+    assert!(debugger.is_profiling());
+    let samples = debugger.stop_profiling().unwrap().samples();
+    assert!(!debugger.is_profiling());
+    assert_eq!(debugger.profile().unwrap().samples(), samples);
+    assert!(debugger.stop_profiling().is_err());
+}
+
+#[test]
+#[serial]
+fn test_spurious_single_step_trap() {
+    let (reader, writer) = os_pipe::pipe().unwrap();
+    let (output_tx, output_rx) = mpsc::channel();
+    thread::spawn(move || {
+        for line in BufReader::new(reader).lines().map_while(Result::ok) {
+            _ = output_tx.send(line);
+        }
+    });
+    rust::Environment::init(None);
+    let process = Child::new(
+        TRAP_FLAG_APP,
+        Vec::<&str>::new(),
+        writer.try_clone().unwrap(),
+        writer,
+    )
+    .install()
+    .unwrap();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_line("trap_flag.rs", 25).unwrap();
+    debugger.set_breakpoint_at_line("trap_flag.rs", 27).unwrap();
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(25));
+
+    // traps raised by debugee itself (not by a step) are delivered to debugee
+    debugger.continue_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(27));
+    assert!(matches!(
+        info.stop.take().unwrap().kind,
+        StopKind::Breakpoint { .. }
+    ));
+
+    debugger
+        .remove_breakpoint_at_line("trap_flag.rs", 27)
+        .unwrap();
+    let debugee_pid = debugger.restart_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(25));
+
+    // and a step over them is done as usual
+    debugger.step_over().unwrap();
+    assert_eq!(info.line.take(), Some(27));
+
+    debugger.continue_debugee().unwrap();
+    assert_eq!(info.exit_code.take(), Some(0));
+    // debugee handler is called
+    let output = output_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(output, "trapped: true");
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_interrupt_running_debugee() {
+    let process = prepare_debugee_process(SLEEPER_APP, &["-s", "2"]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_line("sleeper.rs", 12).unwrap();
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(12));
+
+    // interrupt from another thread (like a Ctrl-C handler) while debugger waits for a stop
+    let interrupter = debugger.interrupter();
+    let ctrl_c = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(500));
+        interrupter.interrupt();
+    });
+    debugger.continue_debugee().unwrap();
+    ctrl_c.join().unwrap();
+    let summary = info.stop.take().unwrap();
+    assert_eq!(summary.kind, StopKind::Interrupt);
+    assert!(debugger.thread_state().unwrap().len() > 1);
+
+    // SIGINT is not delivered to debugee, so it finishes normally
+    debugger.continue_debugee().unwrap();
+    assert_eq!(info.exit_code.take(), Some(0));
+    assert_no_proc!(debugee_pid);
+    assert!(info.stop.take().is_none());
+}
+
+#[test]
+#[serial]
+fn test_interrupt_requested_at_stop() {
+    let process = prepare_debugee_process(SLEEPER_APP, &["-s", "1"]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_line("sleeper.rs", 12).unwrap();
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(12));
+
+    // interrupt requested while debugee is stopped, SIGINT is pending until continue,
+    // so debugee is interrupted right at the start of the next continue
+    debugger.interrupter().interrupt();
+    debugger.continue_debugee().unwrap();
+    let summary = info.stop.take().unwrap();
+    assert_eq!(summary.kind, StopKind::Interrupt);
+    assert_eq!(summary.tid, debugee_pid);
+    assert_eq!(summary.place.map(|p| p.line_number), Some(12));
+
+    // interrupt is processed once
+    debugger.continue_debugee().unwrap();
+    assert_eq!(info.exit_code.take(), Some(0));
+    assert_no_proc!(debugee_pid);
+    assert!(info.stop.take().is_none());
+}
+
+#[test]
+#[serial]
+fn test_signal_handler_frames() {
+    let process = prepare_debugee_process(SIGNALS_APP, &["custom_handler"]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_fn("on_sigusr1").unwrap();
+
+    // debugee stops at signal first, the handler is called after continue
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), None);
+    debugger.continue_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(69));
+
+    // backtrace goes through a signal trampoline into the interrupted function
+    let bt = debugger.backtrace(debugee_pid).unwrap();
+    assert_eq!(bt[0].func_name.as_deref(), Some("signals::on_sigusr1"));
+    assert!(!bt[0].signal_frame);
+    let trampoline = bt.iter().position(|frame| frame.signal_frame).unwrap();
+    assert_eq!(trampoline, 1);
+    assert!(bt[trampoline + 1..]
+        .iter()
+        .any(|frame| frame.func_name.as_deref() == Some("signals::custom_handler")));
+    assert!(bt
+        .iter()
+        .any(|frame| frame.func_name.as_deref() == Some("signals::main")));
+
+    // step out of a handler lands at the interrupted code, not into a trampoline
+    debugger.step_out().unwrap();
+    let bt = debugger.backtrace(debugee_pid).unwrap();
+    assert_ne!(bt[0].func_name.as_deref(), Some("signals::on_sigusr1"));
+    assert!(bt.iter().all(|frame| !frame.signal_frame));
+    assert!(bt
+        .iter()
+        .any(|frame| frame.func_name.as_deref() == Some("signals::custom_handler")));
+
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
@@ -2,8 +2,9 @@ use crate::common::TestHooks;
 use crate::common::TestInfo;
 use crate::CALC_APP;
 use crate::{assert_no_proc, prepare_debugee_process, HW_APP, RECURSION_APP, VARS_APP};
+use bugstalker::debugger::variable::select::{VariableSelector, DQE};
 use bugstalker::debugger::variable::{SupportedScalar, VariableIR};
-use bugstalker::debugger::{Debugger, DebuggerBuilder};
+use bugstalker::debugger::{Debugger, DebuggerBuilder, Error, ReturnValue, StepGranularity};
 use bugstalker::ui::command::parser::expression;
 use chumsky::Parser;
 use serial_test::serial;
@@ -49,6 +50,30 @@ fn test_step_into() {
     assert_no_proc!(debugee_pid);
 }
 
+#[test]
+#[serial]
+fn test_step_into_repeated() {
+    let process = prepare_debugee_process(CALC_APP, &["1", "2", "3", "--description", "result"]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_line("main.rs", 10).unwrap();
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(10));
+
+    debugger.step_into_n(3, StepGranularity::Line).unwrap();
+    assert_eq!(info.line.take(), Some(22));
+
+    debugger.step_into_n(4, StepGranularity::Line).unwrap();
+    assert_eq!(info.line.take(), Some(27));
+
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
 #[test]
 #[serial]
 fn test_step_into_recursion() {
@@ -89,6 +114,30 @@ fn test_step_into_recursion() {
     assert_no_proc!(debugee_pid);
 }
 
+#[test]
+#[serial]
+fn test_step_into_fn_trait_shim() {
+    let process = prepare_debugee_process(RECURSION_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_line("recursion.rs", 21).unwrap();
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(21));
+
+    // a function item is called through `FnOnce::call_once` shim,
+    // step stops in a function body instead of the shim
+    debugger.step_into().unwrap();
+    assert_eq!(info.line.take(), Some(2));
+    assert!(info.file.take().unwrap().contains("recursion.rs"));
+
+    mem::drop(debugger);
+    assert_no_proc!(debugee_pid);
+}
+
 #[test]
 #[serial]
 fn test_step_out() {
@@ -116,6 +165,32 @@ fn test_step_out() {
     assert_no_proc!(debugee_pid);
 }
 
+#[test]
+#[serial]
+fn test_step_out_return_value() {
+    let process = prepare_debugee_process(CALC_APP, &["1", "2", "3", "--description", "result"]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_fn("calc::sum2").unwrap();
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(21));
+
+    let value = debugger.step_out().unwrap().unwrap();
+    assert_eq!(info.line.take(), Some(25));
+    let VariableIR::Scalar(scalar) = &value else {
+        panic!("not a scalar");
+    };
+    assert_eq!(scalar.value, Some(SupportedScalar::I64(3)));
+
+    debugger.remove_breakpoint_at_fn("calc::sum2").unwrap();
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
 #[test]
 #[serial]
 fn test_step_over() {
@@ -184,3 +259,213 @@ fn test_step_over_on_fn_decl() {
     debugger.continue_debugee().unwrap();
     assert_no_proc!(debugee_pid);
 }
+
+#[test]
+#[serial]
+fn test_step_instruction_decoded() {
+    let process = prepare_debugee_process(CALC_APP, &["1", "2", "3", "--description", "result"]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_fn("calc::sum3").unwrap();
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(25));
+
+    // step until a call of `sum2`
+    let call = loop {
+        let instruction = debugger.current_instruction().unwrap().unwrap();
+        if instruction.mnemonic.as_deref() == Some("callq") {
+            break instruction;
+        }
+        debugger.stepi().unwrap();
+    };
+    assert!(!call.bytes.is_empty());
+    let target = call.target.unwrap();
+    assert_eq!(target.symbol, Some(("calc::sum2".to_string(), 0)));
+
+    debugger.stepi().unwrap();
+    let instruction = debugger.current_instruction().unwrap().unwrap();
+    assert_eq!(instruction.address, target.address);
+    assert!(instruction.target.is_none());
+
+    debugger.remove_breakpoint_at_fn("calc::sum3").unwrap();
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_return_early() {
+    let process = prepare_debugee_process(CALC_APP, &["1", "2", "3", "--description", "result"]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_fn("calc::sum2").unwrap();
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(21));
+
+    // return from `sum2` to `sum3` with overridden result
+    debugger.return_early(Some(ReturnValue::Int(100))).unwrap();
+    assert_eq!(info.line.take(), Some(25));
+
+    debugger.step_over().unwrap();
+    assert_eq!(info.line.take(), Some(26));
+
+    let vars = debugger
+        .read_variable(DQE::Variable(VariableSelector::by_name("ab", true)))
+        .unwrap();
+    let VariableIR::Scalar(scalar) = &vars[0] else {
+        panic!("not a scalar");
+    };
+    assert_eq!(scalar.value, Some(SupportedScalar::I64(100)));
+
+    debugger.remove_breakpoint_at_fn("calc::sum2").unwrap();
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_return_early_float() {
+    let process = prepare_debugee_process(CALC_APP, &["1", "2", "3", "--description", "result"]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_fn("calc::float::sum2").unwrap();
+    debugger
+        .set_breakpoint_at_fn("calc::float::sum2_f32")
+        .unwrap();
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(38));
+
+    // integer value converted into f64 result
+    debugger.return_early(Some(ReturnValue::Int(7))).unwrap();
+    assert_eq!(info.line.take(), Some(43));
+    debugger.step_over().unwrap();
+    assert_eq!(info.line.take(), Some(44));
+
+    let vars = debugger
+        .read_variable(DQE::Variable(VariableSelector::by_name("ab", true)))
+        .unwrap();
+    let VariableIR::Scalar(scalar) = &vars[0] else {
+        panic!("not a scalar");
+    };
+    assert_eq!(scalar.value, Some(SupportedScalar::F64(7.0)));
+
+    debugger
+        .remove_breakpoint_at_fn("calc::float::sum2")
+        .unwrap();
+    debugger.continue_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(49));
+
+    // f32 result written as a single precision value
+    debugger
+        .return_early(Some(ReturnValue::Float(2.5)))
+        .unwrap();
+    assert_eq!(info.line.take(), Some(54));
+    debugger.step_over().unwrap();
+    assert_eq!(info.line.take(), Some(55));
+
+    let vars = debugger
+        .read_variable(DQE::Variable(VariableSelector::by_name("ab", true)))
+        .unwrap();
+    let VariableIR::Scalar(scalar) = &vars[0] else {
+        panic!("not a scalar");
+    };
+    assert_eq!(scalar.value, Some(SupportedScalar::F32(2.5)));
+
+    debugger
+        .remove_breakpoint_at_fn("calc::float::sum2_f32")
+        .unwrap();
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_return_early_type_check() {
+    let process = prepare_debugee_process(CALC_APP, &["1", "2", "3", "--description", "result"]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_fn("calc::sum2").unwrap();
+    debugger.set_breakpoint_at_fn("calc::print").unwrap();
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(21));
+
+    // float value truncated into i64 result
+    debugger
+        .return_early(Some(ReturnValue::Float(10.9)))
+        .unwrap();
+    assert_eq!(info.line.take(), Some(25));
+    debugger.step_over().unwrap();
+    assert_eq!(info.line.take(), Some(26));
+
+    let vars = debugger
+        .read_variable(DQE::Variable(VariableSelector::by_name("ab", true)))
+        .unwrap();
+    let VariableIR::Scalar(scalar) = &vars[0] else {
+        panic!("not a scalar");
+    };
+    assert_eq!(scalar.value, Some(SupportedScalar::I64(10)));
+
+    debugger.remove_breakpoint_at_fn("calc::sum2").unwrap();
+    debugger.continue_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(30));
+
+    // `print` returns nothing
+    let err = debugger
+        .return_early(Some(ReturnValue::Int(1)))
+        .unwrap_err();
+    assert!(matches!(err, Error::ReturnTypeUnsupported(_)));
+
+    debugger.remove_breakpoint_at_fn("calc::print").unwrap();
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_jump() {
+    let process = prepare_debugee_process(CALC_APP, &["1", "2", "3", "--description", "result"]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_line("main.rs", 26).unwrap();
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(26));
+    debugger.remove_breakpoint_at_line("main.rs", 26).unwrap();
+
+    // repeat `sum2(a, b)` call
+    debugger.jump(None, 25, false).unwrap();
+    assert_eq!(info.line.take(), Some(25));
+
+    debugger.step_over().unwrap();
+    assert_eq!(info.line.take(), Some(26));
+
+    // there is no code at line 23, line 24 must not be used instead
+    let err = debugger.jump(None, 23, false).unwrap_err();
+    assert!(matches!(err, Error::NoCodeAtLine(23, _)));
+
+    // line 15 is outside `sum3`
+    let err = debugger.jump(Some("main.rs"), 15, false).unwrap_err();
+    assert!(matches!(err, Error::JumpOutsideFunction(_)));
+
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
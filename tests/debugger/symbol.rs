@@ -1,9 +1,13 @@
 use crate::common::TestHooks;
+use crate::common::TestInfo;
 use crate::prepare_debugee_process;
-use crate::{assert_no_proc, HW_APP};
-use bugstalker::debugger::DebuggerBuilder;
+use crate::{assert_no_proc, CALC_APP, HW_APP, VARS_APP};
+use bugstalker::debugger::address::RelocatedAddress;
+use bugstalker::debugger::{DebuggerBuilder, Error, SymbolMatchKind};
+use bugstalker::ui::command::{symbolize, CommandError};
 use object::SymbolKind;
 use serial_test::serial;
+use std::fs;
 
 #[test]
 #[serial]
@@ -17,6 +21,202 @@ fn test_symbol() {
     assert_eq!(SymbolKind::Text, main_sym.kind);
     assert_ne!(usize::from(main_sym.addr), 0);
 
+    let symbols = debugger.search_symbols("hello_world::main").unwrap();
+    let main_fn = symbols
+        .iter()
+        .find(|sym| sym.name == "hello_world::main")
+        .unwrap();
+    assert_eq!(main_fn.kind, SymbolMatchKind::Function);
+    let (file, line) = main_fn.source.as_ref().unwrap();
+    assert!(file.ends_with("hello_world.rs"));
+    assert!(line.is_some());
+
+    debugger.start_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_search_symbols() {
+    let process = prepare_debugee_process(VARS_APP, &[]);
+    let debugee_pid = process.pid();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::default());
+    let mut debugger = builder.build(process).unwrap();
+
+    // static variables are found in debug information together with declaration place
+    let symbols = debugger.search_symbols("^vars::GLOB_").unwrap();
+    let names: Vec<_> = symbols.iter().map(|sym| sym.name.as_str()).collect();
+    assert_eq!(names, vec!["vars::GLOB_1", "vars::GLOB_2", "vars::GLOB_3"]);
+    let glob_2 = &symbols[1];
+    assert_eq!(glob_2.kind, SymbolMatchKind::Object);
+    let (file, line) = glob_2.source.as_ref().unwrap();
+    assert!(file.ends_with("vars.rs"));
+    assert_eq!(*line, Some(163));
+    assert!(glob_2.object.ends_with("vars"));
+
+    // symbol table entries at the same address as debug information entries are skipped
+    let symbols = debugger.search_symbols("vars::scalar_types").unwrap();
+    let scalar_types: Vec<_> = symbols
+        .iter()
+        .filter(|sym| sym.kind == SymbolMatchKind::Function)
+        .collect();
+    assert_eq!(scalar_types.len(), 1);
+    assert_eq!(scalar_types[0].name, "vars::scalar_types");
+    assert!(scalar_types[0].source.is_some());
+
+    // symbols without debug information are found in a symbol table
+    let symbols = debugger.search_symbols("^main$").unwrap();
+    let main = symbols.iter().find(|sym| sym.name == "main").unwrap();
+    assert_eq!(main.kind, SymbolMatchKind::Function);
+    assert!(main.source.is_none());
+
+    // invalid regular expression is used as a plain text
+    let symbols = debugger
+        .search_symbols("fn_and_closure::{closure#")
+        .unwrap();
+    assert!(!symbols.is_empty());
+    assert!(symbols
+        .iter()
+        .all(|sym| sym.name.contains("fn_and_closure::{closure#")));
+
+    debugger.start_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_resolve_address() {
+    let process = prepare_debugee_process(CALC_APP, &["1", "2", "3", "--description", "result"]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    // runtime address resolution requires a running debugee
+    assert!(matches!(
+        debugger.resolve_address(RelocatedAddress::from(0x1000_usize)),
+        Err(Error::ProcessNotStarted)
+    ));
+
+    // addresses in object file are resolved without a running debugee
+    let sum2 = debugger
+        .search_symbols("^calc::sum2$")
+        .unwrap()
+        .into_iter()
+        .find(|sym| sym.kind == SymbolMatchKind::Function)
+        .unwrap();
+    let static_info = debugger.resolve_address_in_module(None, sum2.addr).unwrap();
+    assert!(static_info.module.unwrap().ends_with("calc"));
+    let (symbol, offset) = static_info.symbol.unwrap();
+    assert!(symbol.contains("sum2"));
+    assert_eq!(offset, 0);
+    let frame = static_info.frames.last().unwrap();
+    assert_eq!(frame.function.as_deref(), Some("calc::sum2"));
+    let (file, line, _) = frame.place.as_ref().unwrap();
+    assert!(file.ends_with("main.rs"));
+    assert_eq!(*line, 20);
+
+    debugger.set_breakpoint_at_fn("calc::sum2").unwrap();
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(21));
+
+    let location = debugger.exploration_ctx().location();
+    let info_at_pc = debugger.resolve_address(location.pc).unwrap();
+    assert!(info_at_pc.module.unwrap().ends_with("calc"));
+    let (symbol, offset) = info_at_pc.symbol.unwrap();
+    assert!(symbol.contains("sum2"));
+    assert!(offset > 0);
+    let frame = info_at_pc.frames.last().unwrap();
+    assert_eq!(frame.function.as_deref(), Some("calc::sum2"));
+    let (file, line, _) = frame.place.as_ref().unwrap();
+    assert!(file.ends_with("main.rs"));
+    assert_eq!(*line, 21);
+
+    // the same place is resolved by an object file address
+    let static_info = debugger
+        .resolve_address_in_module(None, location.global_pc)
+        .unwrap();
+    let static_frame = static_info.frames.last().unwrap();
+    assert_eq!(static_frame.function, frame.function);
+    assert_eq!(static_frame.place, frame.place);
+
+    // unknown addresses resolve to nothing
+    let unknown = debugger
+        .resolve_address(RelocatedAddress::from(0x10_usize))
+        .unwrap();
+    assert!(unknown.module.is_none() && unknown.symbol.is_none() && unknown.frames.is_empty());
+
+    debugger.remove_breakpoint_at_fn("calc::sum2").unwrap();
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_symbolize_backtrace() {
+    let process = prepare_debugee_process(CALC_APP, &["1", "2", "3", "--description", "result"]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    let sum2 = debugger
+        .search_symbols("^calc::sum2$")
+        .unwrap()
+        .into_iter()
+        .find(|sym| sym.kind == SymbolMatchKind::Function)
+        .unwrap();
+    let sum2_addr = usize::from(sum2.addr);
+
+    let path = std::env::temp_dir().join(format!("bs_backtrace_{}", std::process::id()));
+    let backtrace = format!(
+        "thread 'main' panicked\n\
+         #0 0x7f0000001000 in calc::sum2 (calc+{sum2_addr:#x})\n\
+         frame without address\n\
+         #1 {sum2_addr:#x}\n\
+         #2 0x10 in ?? (unknown_module.so+0x10)\n"
+    );
+    fs::write(&path, backtrace).unwrap();
+    let path = path.to_str().unwrap();
+
+    // without a running debugee all addresses are addresses in object files
+    let frames = symbolize::Handler::new(&debugger).handle(path).unwrap();
+    assert_eq!(frames.len(), 3);
+    assert_eq!(frames[0].num, 0);
+    assert_eq!(frames[0].addr, sum2_addr);
+    assert_eq!(frames[0].module.as_deref(), Some("calc"));
+    assert_eq!(
+        frames[0].info.frames.last().unwrap().function.as_deref(),
+        Some("calc::sum2")
+    );
+    assert_eq!(frames[1].num, 1);
+    assert_eq!(frames[1].module, None);
+    assert_eq!(
+        frames[1].info.frames.last().unwrap().function.as_deref(),
+        Some("calc::sum2")
+    );
+    assert_eq!(frames[2].module.as_deref(), Some("unknown_module.so"));
+    assert!(frames[2].info.frames.is_empty());
+
+    // with a running debugee addresses without a module are runtime addresses
+    debugger.set_breakpoint_at_fn("calc::sum2").unwrap();
     debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(21));
+    let pc = usize::from(debugger.exploration_ctx().location().pc);
+    fs::write(path, format!("#0 {pc:#x}\n")).unwrap();
+    let frames = symbolize::Handler::new(&debugger).handle(path).unwrap();
+    assert_eq!(frames.len(), 1);
+    let frame = frames[0].info.frames.last().unwrap();
+    assert_eq!(frame.function.as_deref(), Some("calc::sum2"));
+    assert_eq!(frame.place.as_ref().unwrap().1, 21);
+
+    fs::remove_file(path).unwrap();
+    assert!(matches!(
+        symbolize::Handler::new(&debugger).handle(path),
+        Err(CommandError::BacktraceImport(_))
+    ));
+
+    debugger.remove_breakpoint_at_fn("calc::sum2").unwrap();
+    debugger.continue_debugee().unwrap();
     assert_no_proc!(debugee_pid);
 }
@@ -1,11 +1,16 @@
 use crate::common::TestHooks;
 use crate::common::{rust_version, TestInfo};
-use crate::VARS_APP;
 use crate::{assert_no_proc, prepare_debugee_process};
-use bugstalker::debugger::variable::render::RenderRepr;
-use bugstalker::debugger::variable::select::{Literal, LiteralOrWildcard, VariableSelector, DQE};
-use bugstalker::debugger::variable::{select, VariableIR};
+use crate::{CHANNELS_APP, VARS_APP};
+use bugstalker::debugger::variable::render::{
+    render_variable_ir, RenderLimits, RenderRepr, TRUNCATED_MARKER,
+};
+use bugstalker::debugger::variable::select::{
+    DeclarationState, Literal, LiteralOrWildcard, VariableSelector, DQE,
+};
+use bugstalker::debugger::variable::{select, Unavailability, VariableIR};
 use bugstalker::debugger::{variable, Debugger, DebuggerBuilder};
+use bugstalker::ui::command::memory;
 use bugstalker::ui::command::parser::expression;
 use bugstalker::{debugger, version_switch};
 use chumsky::Parser;
@@ -43,6 +48,27 @@ fn assert_struct(
     }
 }
 
+fn assert_closure(
+    var: &VariableIR,
+    exp_name: &str,
+    exp_place: &str,
+    for_each_member: impl Fn(usize, &VariableIR),
+) {
+    let VariableIR::Struct(structure) = var else {
+        panic!("not a struct");
+    };
+    assert_eq!(var.name(), exp_name);
+    let r#type = var.r#type();
+    assert!(
+        r#type.starts_with("closure@") && r#type.ends_with(exp_place),
+        "{}",
+        r#type
+    );
+    for (i, member) in structure.members.iter().enumerate() {
+        for_each_member(i, member)
+    }
+}
+
 fn assert_array(
     var: &VariableIR,
     exp_name: &str,
@@ -325,6 +351,50 @@ fn assert_arc(var: &VariableIR, exp_name: &str, exp_type: &str) {
     assert_eq!(var.r#type(), exp_type);
 }
 
+fn assert_os_string(var: &VariableIR, exp_name: &str, exp_type: &str, exp_value: &str) {
+    let VariableIR::Specialized(variable::SpecializedVariableIR::OsString {
+        string: Some(string),
+        ..
+    }) = var
+    else {
+        panic!("not an owned platform string");
+    };
+    assert_eq!(var.name(), exp_name);
+    assert_eq!(var.r#type(), exp_type);
+    assert_eq!(string.value, exp_value);
+}
+
+fn assert_os_str(var: &VariableIR, exp_name: &str, exp_type: &str, exp_value: &str) {
+    let VariableIR::Specialized(variable::SpecializedVariableIR::OsStr {
+        string: Some(str), ..
+    }) = var
+    else {
+        panic!("not a borrowed platform string");
+    };
+    assert_eq!(var.name(), exp_name);
+    assert_eq!(var.r#type(), exp_type);
+    assert_eq!(str.value, exp_value);
+}
+
+fn assert_iterator(
+    var: &VariableIR,
+    exp_name: &str,
+    exp_type: &str,
+    for_each_member: impl Fn(usize, &VariableIR),
+) {
+    let VariableIR::Specialized(variable::SpecializedVariableIR::Iterator {
+        iter: Some(iter), ..
+    }) = var
+    else {
+        panic!("not an iterator");
+    };
+    assert_eq!(var.name(), exp_name);
+    assert_eq!(var.r#type(), exp_type);
+    for (i, member) in iter.members.iter().enumerate() {
+        for_each_member(i, member)
+    }
+}
+
 fn assert_uuid(var: &VariableIR, exp_name: &str, exp_type: &str) {
     let VariableIR::Specialized(variable::SpecializedVariableIR::Uuid { .. }) = var else {
         panic!("not an uuid");
@@ -333,6 +403,66 @@ fn assert_uuid(var: &VariableIR, exp_name: &str, exp_type: &str) {
     assert_eq!(var.r#type(), exp_type);
 }
 
+fn assert_channel(
+    var: &VariableIR,
+    exp_name: &str,
+    exp_type: &str,
+    exp_cap: Option<usize>,
+    exp_disconnected: (bool, bool),
+    for_each_item: impl Fn(usize, &VariableIR),
+) {
+    let VariableIR::Specialized(variable::SpecializedVariableIR::Channel {
+        channel: Some(channel),
+        ..
+    }) = var
+    else {
+        panic!("not a channel");
+    };
+    assert_eq!(var.name(), exp_name);
+    assert_eq!(var.r#type(), exp_type);
+
+    let member = |name: &str| {
+        channel
+            .members
+            .iter()
+            .find(|member| member.name() == name)
+            .cloned()
+    };
+    let cap = member("cap").map(|cap| {
+        let VariableIR::Scalar(cap) = cap else {
+            panic!("cap not a scalar");
+        };
+        cap.value
+    });
+    assert_eq!(cap, exp_cap.map(|cap| Some(SupportedScalar::Usize(cap))));
+    assert_scalar(
+        &member("senders_disconnected").unwrap(),
+        "senders_disconnected",
+        "bool",
+        Some(SupportedScalar::Bool(exp_disconnected.0)),
+    );
+    assert_scalar(
+        &member("receivers_disconnected").unwrap(),
+        "receivers_disconnected",
+        "bool",
+        Some(SupportedScalar::Bool(exp_disconnected.1)),
+    );
+
+    let VariableIR::Array(queue) = member("queue").unwrap() else {
+        panic!("queue not an array");
+    };
+    let items = queue.items.unwrap_or_default();
+    assert_scalar(
+        &member("len").unwrap(),
+        "len",
+        "usize",
+        Some(SupportedScalar::Usize(items.len())),
+    );
+    for (i, item) in items.iter().enumerate() {
+        for_each_item(i, item)
+    }
+}
+
 #[test]
 #[serial]
 fn test_read_scalar_variables() {
@@ -406,10 +536,9 @@ fn test_read_scalar_variables_at_place() {
     assert_eq!(info.line.take(), Some(11));
 
     let vars = debugger.read_local_variables().unwrap();
-    // WAITFORFIX: https://github.com/rust-lang/rust/issues/113819
-    // expected: assert_eq!(vars.len(), 4);
-    // through this bug there is uninitialized variable here
-    assert_eq!(vars.len(), 5);
+    // https://github.com/rust-lang/rust/issues/113819: scope of `int128` starts at
+    // the current PC, but variable is not initialized yet, so it is omitted
+    assert_eq!(vars.len(), 4);
 
     debugger.continue_debugee().unwrap();
     assert_no_proc!(debugee_pid);
@@ -472,6 +601,162 @@ fn test_read_struct() {
     assert_no_proc!(debugee_pid);
 }
 
+#[test]
+#[serial]
+fn test_render_limits() {
+    let process = prepare_debugee_process(VARS_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_line("vars.rs", 149).unwrap();
+    debugger.set_breakpoint_at_line("vars.rs", 159).unwrap();
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(149));
+
+    let vec3 = read_single_var(&debugger, "vec3");
+    let unlimited = RenderLimits {
+        elements: None,
+        depth: None,
+        string_length: None,
+        pointer_depth: None,
+        bytes_as_ascii: false,
+    };
+    let rendered = render_variable_ir(&vec3, 0, &unlimited);
+    assert!(!rendered.contains(TRUNCATED_MARKER));
+    assert_eq!(rendered.matches("i32(3)").count(), 2);
+
+    // only a first element of each vector is shown, vector capacity is not an element
+    let limits = RenderLimits {
+        elements: Some(1),
+        ..unlimited
+    };
+    let rendered = render_variable_ir(&vec3, 0, &limits);
+    assert_eq!(rendered.matches("i32(1)").count(), 1);
+    assert!(!rendered.contains("i32(2)"));
+    assert_eq!(rendered.matches(TRUNCATED_MARKER).count(), 2);
+    assert!(rendered.contains("cap: usize(3)"));
+    assert!(rendered.contains("cap: usize(2)"));
+
+    // nested vectors are not expanded
+    let limits = RenderLimits {
+        depth: Some(2),
+        ..unlimited
+    };
+    let rendered = render_variable_ir(&vec3, 0, &limits);
+    assert!(!rendered.contains("i32("));
+    assert!(rendered.contains(&format!(
+        "0: Vec<i32, alloc::alloc::Global> {{{TRUNCATED_MARKER}}}"
+    )));
+
+    debugger.continue_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(159));
+
+    let s1 = read_single_var(&debugger, "s1");
+    assert_eq!(
+        render_variable_ir(&s1, 0, &unlimited),
+        "String(hello world)"
+    );
+    let limits = RenderLimits {
+        string_length: Some(5),
+        ..unlimited
+    };
+    assert_eq!(
+        render_variable_ir(&s1, 0, &limits),
+        format!("String(hello{TRUNCATED_MARKER})")
+    );
+
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_value_history() {
+    let process = prepare_debugee_process(VARS_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_line("vars.rs", 53).unwrap();
+    debugger.set_breakpoint_at_line("vars.rs", 61).unwrap();
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(53));
+
+    let foo2 = read_single_var(&debugger, "foo2");
+    assert_eq!(debugger.record_value(foo2), 0);
+    let tuple_1 = read_single_var(&debugger, "tuple_1");
+    assert_eq!(debugger.record_value(tuple_1), 1);
+
+    // history values are available when variables are out of scope
+    debugger.continue_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(61));
+    assert!(debugger
+        .read_variable(make_select_plan("foo2"))
+        .unwrap()
+        .is_empty());
+
+    let value = read_single_var(&debugger, "$0");
+    assert_eq!(value.name(), "$0");
+    assert!(value.r#type().contains("Foo2"));
+    let bar = read_single_var(&debugger, "$0.foo.bar");
+    assert_scalar(&bar, "bar", "i32", Some(SupportedScalar::I32(100)));
+    let second = read_single_var(&debugger, "$1.1");
+    assert_scalar(&second, "1", "f64", Some(SupportedScalar::F64(1.1)));
+
+    // history values are not function arguments
+    assert!(debugger
+        .read_argument(make_select_plan("$0"))
+        .unwrap()
+        .is_empty());
+    assert!(matches!(
+        debugger.read_variable(make_select_plan("$2")),
+        Err(debugger::Error::HistoryValueNotFound(2))
+    ));
+    assert_eq!(debugger.value_history().len(), 2);
+
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_read_type_declaration() {
+    let process = prepare_debugee_process(VARS_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_line("vars.rs", 53).unwrap();
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(53));
+
+    let types = debugger
+        .read_variable_types(DQE::Variable(VariableSelector::by_name("foo2", true)))
+        .unwrap();
+    assert_eq!(types.len(), 1);
+    let (name, decl) = &types[0];
+    assert_eq!(name, "foo2");
+    assert!(decl.starts_with("struct "));
+    assert!(decl.contains("Foo2 {"));
+    assert!(decl.contains("/* offset: 0, size: 8 */ foo: Foo,"));
+    assert!(decl.contains("/* offset: 8, size: 1 */ additional: bool,"));
+    assert!(decl.contains("/* 3 bytes padding */"));
+    assert!(decl.ends_with("} /* size: 12 */"));
+
+    let decl = debugger.read_type("i32").unwrap();
+    assert_eq!(decl, "i32 /* size: 4 */");
+
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
 #[test]
 #[serial]
 fn test_read_array() {
@@ -527,6 +812,103 @@ fn test_read_array() {
     assert_no_proc!(debugee_pid);
 }
 
+#[test]
+#[serial]
+fn test_resolve_memory_location() {
+    let process = prepare_debugee_process(VARS_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_line("vars.rs", 119).unwrap();
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(119));
+
+    let vars = debugger.read_local_variables().unwrap();
+    let VariableIR::Pointer(ptr_a) = &vars[2] else {
+        panic!("expect a pointer");
+    };
+    let addr_of_a = ptr_a.value.unwrap() as usize;
+    let addr_of_ptr_a = ptr_a.raw_address.unwrap();
+    let addr_of_f = vars[9].in_memory_location().unwrap();
+
+    let resolve = |expr: DQE| {
+        memory::Handler::new(&debugger)
+            .resolve(&memory::Location::Expression(expr))
+            .unwrap()
+    };
+    let var = |name: &str| DQE::Variable(VariableSelector::by_name(name, true));
+
+    // a pointer is an address itself
+    assert_eq!(resolve(var("ptr_a")), addr_of_a);
+    assert_eq!(resolve(var("ref_a")), addr_of_a);
+    assert_eq!(resolve(DQE::Address(var("ptr_a").boxed())), addr_of_ptr_a);
+    assert_eq!(resolve(var("ref_f")), addr_of_f);
+    // an integer is an address itself
+    assert_eq!(resolve(var("a")), 2);
+    // a value of any other type is examined at its location
+    assert_eq!(resolve(var("f")), addr_of_f);
+
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_save_and_load_memory_region() {
+    let process = prepare_debugee_process(VARS_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_line("vars.rs", 61).unwrap();
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(61));
+
+    let arr_1 =
+        || memory::Location::Expression(DQE::Variable(VariableSelector::by_name("arr_1", true)));
+    let addr = memory::Handler::new(&debugger).resolve(&arr_1()).unwrap();
+    // array size (20 bytes) is not a multiple of a machine word
+    let original = debugger.read_memory(addr, 24).unwrap();
+
+    let path = std::env::temp_dir().join(format!("bs_region_{debugee_pid}.bin"));
+    let path_str = path.to_string_lossy().to_string();
+    let save = memory::Command::Save(arr_1(), 20, path_str.clone());
+    assert!(matches!(
+        memory::Handler::new(&debugger).handle(save).unwrap(),
+        memory::ExecutionResult::Saved(20)
+    ));
+    assert_eq!(std::fs::read(&path).unwrap(), original[..20]);
+
+    debugger.write_memory_bytes(addr, &[0; 20]).unwrap();
+    let zeroed = debugger.read_memory(addr, 24).unwrap();
+    assert_eq!(zeroed[..20], [0; 20]);
+    assert_eq!(zeroed[20..], original[20..]);
+
+    let load = memory::Command::Load(path_str, arr_1());
+    assert!(matches!(
+        memory::Handler::new(&debugger).handle(load).unwrap(),
+        memory::ExecutionResult::Loaded(20)
+    ));
+    assert_eq!(debugger.read_memory(addr, 24).unwrap(), original);
+    std::fs::remove_file(&path).unwrap();
+
+    let vars = debugger.read_local_variables().unwrap();
+    assert_array(&vars[0], "arr_1", "[i32]", |i, item| match i {
+        0 => assert_scalar(item, "0", "i32", Some(SupportedScalar::I32(1))),
+        1 => assert_scalar(item, "1", "i32", Some(SupportedScalar::I32(-1))),
+        2 => assert_scalar(item, "2", "i32", Some(SupportedScalar::I32(2))),
+        3 => assert_scalar(item, "3", "i32", Some(SupportedScalar::I32(-2))),
+        4 => assert_scalar(item, "4", "i32", Some(SupportedScalar::I32(3))),
+        _ => panic!("5 items expected"),
+    });
+
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
 #[test]
 #[serial]
 fn test_read_enum() {
@@ -935,6 +1317,61 @@ fn test_read_static_variables() {
     assert_no_proc!(debugee_pid);
 }
 
+#[test]
+#[serial]
+fn test_pointer_annotations() {
+    let process = prepare_debugee_process(VARS_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_line("vars.rs", 119).unwrap();
+    debugger.set_breakpoint_at_line("vars.rs", 168).unwrap();
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(119));
+
+    let annotation = |var: &VariableIR| {
+        let VariableIR::Pointer(pointer) = var else {
+            panic!("not a pointer");
+        };
+        pointer.annotation.clone()
+    };
+
+    let mut vars = debugger
+        .read_local_variables()
+        .unwrap()
+        .into_iter()
+        .filter(|var| matches!(var.name().as_str(), "ref_a" | "box_d"))
+        .collect::<Vec<_>>();
+    debugger.annotate_pointers(&mut vars);
+    assert_eq!(annotation(&vars[0]).as_deref(), Some("([stack])"));
+    assert_eq!(annotation(&vars[1]).as_deref(), Some("([heap])"));
+
+    debugger.continue_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(168));
+
+    let mut vars = debugger
+        .read_variable(DQE::Address(Box::new(DQE::Variable(
+            VariableSelector::Name {
+                var_name: "GLOB_2".to_string(),
+                only_local: false,
+            },
+        ))))
+        .unwrap();
+    debugger.annotate_pointers(&mut vars);
+    assert_eq!(
+        annotation(&vars[0]).as_deref(),
+        Some("→ static vars::GLOB_2")
+    );
+    assert!(render_variable_ir(&vars[0], 0, &RenderLimits::default())
+        .ends_with("] → static vars::GLOB_2"));
+
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
 #[test]
 #[serial]
 fn test_read_only_local_variables() {
@@ -1070,33 +1507,151 @@ fn test_read_tls_variables() {
 
 #[test]
 #[serial]
-fn test_read_closures() {
+fn test_read_tls_variables_per_thread() {
     let process = prepare_debugee_process(VARS_APP, &[]);
     let debugee_pid = process.pid();
     let info = TestInfo::default();
     let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
     let mut debugger = builder.build(process).unwrap();
 
-    debugger.set_breakpoint_at_line("vars.rs", 223).unwrap();
+    let read_tls = |debugger: &Debugger, name: &str| {
+        debugger
+            .read_variable(DQE::Variable(VariableSelector::Name {
+                var_name: name.to_string(),
+                only_local: false,
+            }))
+            .unwrap()
+    };
 
+    debugger.set_breakpoint_at_line("vars.rs", 194).unwrap();
     debugger.start_debugee().unwrap();
-    assert_eq!(info.line.take(), Some(223));
+    assert_eq!(info.line.take(), Some(194));
 
-    let vars = debugger.read_local_variables().unwrap();
-    assert_struct(&vars[0], "inc", "{closure_env#0}", |_, _| {
-        panic!("no members expected")
+    // values of tls variables are taken from a thread in focus
+    let threads = debugger.thread_state().unwrap();
+    let spawned_thread = threads.iter().find(|t| t.in_focus).unwrap();
+    assert_ne!(spawned_thread.thread.pid, debugee_pid);
+    let spawned_thread_num = spawned_thread.thread.number;
+    let main_thread_num = threads
+        .iter()
+        .find(|t| t.thread.pid == debugee_pid)
+        .unwrap()
+        .thread
+        .number;
+
+    // main thread waits in libc, so focus on a frame with debug information
+    debugger.set_thread_into_focus(main_thread_num).unwrap();
+    let bt = debugger.backtrace(debugee_pid).unwrap();
+    let frame_num = bt
+        .iter()
+        .position(|frame| frame.func_name.as_deref() == Some("vars::thread_local"))
+        .unwrap();
+    debugger.set_frame_into_focus(frame_num as u32).unwrap();
+    let vars = read_tls(&debugger, "THREAD_LOCAL_VAR_1");
+    assert_init_tls(&vars[0], "THREAD_LOCAL_VAR_1", "Cell<i32>", |inner| {
+        assert_cell(inner, "0", "Cell<i32>", |value| {
+            assert_scalar(value, "value", "i32", Some(SupportedScalar::I32(1)))
+        })
     });
-    assert_struct(&vars[1], "inc_mut", "{closure_env#1}", |_, _| {
-        panic!("no members expected")
+    let vars = read_tls(&debugger, "THREAD_LOCAL_VAR_2");
+    assert_init_tls(&vars[0], "THREAD_LOCAL_VAR_2", "Cell<&str>", |inner| {
+        assert_cell(inner, "0", "Cell<&str>", |value| {
+            assert_str(value, "value", "1")
+        })
     });
-    assert_struct(&vars[3], "closure", "{closure_env#2}", |_, member| {
-        assert_string(member, "outer", "outer val")
+
+    debugger.set_thread_into_focus(spawned_thread_num).unwrap();
+    let vars = read_tls(&debugger, "THREAD_LOCAL_VAR_2");
+    assert_init_tls(&vars[0], "THREAD_LOCAL_VAR_2", "Cell<&str>", |inner| {
+        assert_cell(inner, "0", "Cell<&str>", |value| {
+            assert_str(value, "value", "2")
+        })
     });
-    assert_struct(
-        &vars[7],
-        "trait_once",
-        "alloc::boxed::Box<dyn core::ops::function::FnOnce<(), Output=()>, alloc::alloc::Global>",
-        |i, member| match i {
+
+    // all variables are uninitialized in a thread that never touches them
+    debugger.set_breakpoint_at_line("vars.rs", 199).unwrap();
+    debugger.continue_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(199));
+    let vars = read_tls(&debugger, "THREAD_LOCAL_VAR_1");
+    assert_uninit_tls(&vars[0], "THREAD_LOCAL_VAR_1", "Cell<i32>");
+    let vars = read_tls(&debugger, "THREAD_LOCAL_VAR_2");
+    assert_uninit_tls(&vars[0], "THREAD_LOCAL_VAR_2", "Cell<&str>");
+
+    debugger.remove_breakpoint_at_line("vars.rs", 194).unwrap();
+    debugger.remove_breakpoint_at_line("vars.rs", 199).unwrap();
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_closure_type_names() {
+    let process = prepare_debugee_process(VARS_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_line("vars.rs", 223).unwrap();
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(223));
+
+    let closure = read_single_var(&debugger, "closure");
+    assert_closure(
+        &closure,
+        "closure",
+        "vars::fn_and_closure",
+        |i, member| match i {
+            0 => assert_string(member, "outer", "outer val"),
+            _ => panic!("1 member expected"),
+        },
+    );
+
+    // a place is used for a closure that is called
+    debugger.set_breakpoint_at_line("vars.rs", 612).unwrap();
+    debugger.continue_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(612));
+    let inc = read_single_var(&debugger, "inc");
+    assert_closure(&inc, "inc", "vars.rs:609", |_, _| {
+        panic!("no members expected")
+    });
+
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_read_closures() {
+    let process = prepare_debugee_process(VARS_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_line("vars.rs", 223).unwrap();
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(223));
+
+    let vars = debugger.read_local_variables().unwrap();
+    // there is no code generated for a closure that is never called,
+    // so closure namespace is used instead of a place
+    assert_closure(&vars[0], "inc", "vars::fn_and_closure", |_, _| {
+        panic!("no members expected")
+    });
+    assert_closure(&vars[1], "inc_mut", "vars::fn_and_closure", |_, _| {
+        panic!("no members expected")
+    });
+    assert_closure(&vars[3], "closure", "vars::fn_and_closure", |_, member| {
+        assert_string(member, "outer", "outer val")
+    });
+    assert_struct(
+        &vars[7],
+        "trait_once",
+        "alloc::boxed::Box<dyn core::ops::function::FnOnce<(), Output=()>, alloc::alloc::Global>",
+        |i, member| match i {
             0 => {
                 assert_pointer(
                     member,
@@ -1666,6 +2221,62 @@ fn test_circular_ref_types() {
     assert_no_proc!(debugee_pid);
 }
 
+#[test]
+#[serial]
+fn test_follow_pointers_with_cycles() {
+    let process = prepare_debugee_process(VARS_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_line("vars.rs", 334).unwrap();
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(334));
+
+    let a_circ = || {
+        DQE::Variable(VariableSelector::Name {
+            var_name: "a_circ".to_string(),
+            only_local: true,
+        })
+    };
+    let a_addr = match &read_single_var(&debugger, "a_circ") {
+        VariableIR::Specialized(variable::SpecializedVariableIR::Rc {
+            value: Some(ptr), ..
+        }) => ptr.value.unwrap() as usize,
+        _ => panic!("not a rc"),
+    };
+
+    // a_circ -> b_circ -> a_circ, back-edge is not followed
+    let vars = debugger
+        .read_variable_following_pointers(a_circ(), None)
+        .unwrap();
+    let rendered = render_variable_ir(&vars[0], 0, &RenderLimits::default());
+    assert!(rendered.contains("i32(5)"));
+    assert!(rendered.contains("i32(10)"));
+    assert_eq!(rendered.matches(" -> ").count(), 2);
+    assert_eq!(rendered.matches("<cycle to ").count(), 1);
+    assert!(rendered.contains(&format!("<cycle to 0x{a_addr:014X}>")));
+
+    // only a first pointer is followed
+    let vars = debugger
+        .read_variable_following_pointers(a_circ(), Some(1))
+        .unwrap();
+    let rendered = render_variable_ir(&vars[0], 0, &RenderLimits::default());
+    assert!(rendered.contains("i32(5)"));
+    assert!(!rendered.contains("i32(10)"));
+    assert!(!rendered.contains("<cycle to "));
+
+    // pointers are not followed by default
+    let vars = debugger.read_variable(a_circ()).unwrap();
+    let rendered = render_variable_ir(&vars[0], 0, &RenderLimits::default());
+    assert!(!rendered.contains(" -> "));
+
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
 #[test]
 #[serial]
 fn test_lexical_blocks() {
@@ -1680,10 +2291,9 @@ fn test_lexical_blocks() {
     assert_eq!(info.line.take(), Some(340));
 
     let vars = debugger.read_local_variables().unwrap();
-    // WAITFORFIX: https://github.com/rust-lang/rust/issues/113819
-    // expected:     assert_eq!(vars.len(), 1);
-    // through this bug there is uninitialized variable here
-    assert_eq!(vars.len(), 2);
+    // https://github.com/rust-lang/rust/issues/113819: scope of `beta` starts at
+    // the current PC, but variable is not initialized yet, so it is omitted
+    assert_eq!(vars.len(), 1);
     assert_eq!(vars[0].name(), "alpha");
 
     debugger.set_breakpoint_at_line("vars.rs", 342).unwrap();
@@ -2672,6 +3282,143 @@ fn test_cast_pointers() {
     assert_no_proc!(debugee_pid);
 }
 
+#[test]
+#[serial]
+fn test_cast_operator() {
+    let process = prepare_debugee_process(VARS_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_line("vars.rs", 119).unwrap();
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(119));
+
+    let vars = debugger.read_local_variables().unwrap();
+    let VariableIR::Pointer(pointer) = &vars[1] else {
+        panic!("expect a pointer");
+    };
+    let raw_ptr = pointer.value.unwrap();
+
+    let var_a = || {
+        DQE::Variable(VariableSelector::Name {
+            var_name: "a".to_string(),
+            only_local: true,
+        })
+    };
+    let ptr_a = || {
+        DQE::Variable(VariableSelector::Name {
+            var_name: "ptr_a".to_string(),
+            only_local: true,
+        })
+    };
+
+    // reinterpret memory of a variable
+    let vars = debugger
+        .read_variable(DQE::Cast(var_a().boxed(), "u32".to_string()))
+        .unwrap();
+    assert_scalar(&vars[0], "a", "u32", Some(SupportedScalar::U32(2)));
+    let vars = debugger
+        .read_variable(DQE::Cast(ptr_a().boxed(), "u64".to_string()))
+        .unwrap();
+    assert_scalar(
+        &vars[0],
+        "ptr_a",
+        "u64",
+        Some(SupportedScalar::U64(raw_ptr as u64)),
+    );
+
+    // make a pointer from a number, then dereference it
+    let vars = debugger
+        .read_variable(DQE::Deref(
+            DQE::Cast(
+                DQE::Cast(ptr_a().boxed(), "usize".to_string()).boxed(),
+                "*const i32".to_string(),
+            )
+            .boxed(),
+        ))
+        .unwrap();
+    assert_scalar(&vars[0], "*ptr_a", "i32", Some(SupportedScalar::I32(2)));
+
+    assert!(debugger
+        .read_variable(DQE::Cast(var_a().boxed(), "UnknownType".to_string()))
+        .is_err());
+
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_cast_scalar_by_value() {
+    let process = prepare_debugee_process(VARS_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_line("vars.rs", 30).unwrap();
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(30));
+
+    let var = |name: &str| {
+        DQE::Variable(VariableSelector::Name {
+            var_name: name.to_string(),
+            only_local: true,
+        })
+    };
+
+    // widen a narrow integer
+    let vars = debugger
+        .read_variable(DQE::Cast(var("int8").boxed(), "u64".to_string()))
+        .unwrap();
+    assert_scalar(&vars[0], "int8", "u64", Some(SupportedScalar::U64(1)));
+    // negative integers are sign-extended
+    let vars = debugger
+        .read_variable(DQE::Cast(var("int16").boxed(), "i64".to_string()))
+        .unwrap();
+    assert_scalar(&vars[0], "int16", "i64", Some(SupportedScalar::I64(-1)));
+    let vars = debugger
+        .read_variable(DQE::Cast(var("int64").boxed(), "u128".to_string()))
+        .unwrap();
+    assert_scalar(
+        &vars[0],
+        "int64",
+        "u128",
+        Some(SupportedScalar::U128(-2_i128 as u128)),
+    );
+    // narrow an integer
+    let vars = debugger
+        .read_variable(DQE::Cast(var("int16").boxed(), "u8".to_string()))
+        .unwrap();
+    assert_scalar(&vars[0], "int16", "u8", Some(SupportedScalar::U8(u8::MAX)));
+    // integer to float
+    let vars = debugger
+        .read_variable(DQE::Cast(var("int32").boxed(), "f64".to_string()))
+        .unwrap();
+    assert_scalar(&vars[0], "int32", "f64", Some(SupportedScalar::F64(2.0)));
+
+    // memory of a non-integer value can't be read as a larger type
+    assert!(debugger
+        .read_variable(DQE::Cast(var("f32").boxed(), "u64".to_string()))
+        .is_err());
+    let vars = debugger
+        .read_variable(DQE::Cast(var("f32").boxed(), "u32".to_string()))
+        .unwrap();
+    assert_scalar(
+        &vars[0],
+        "f32",
+        "u32",
+        Some(SupportedScalar::U32(1.1_f32.to_bits())),
+    );
+
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
 #[test]
 #[serial]
 fn test_read_uuid() {
@@ -2843,3 +3590,408 @@ fn test_address_operator() {
     debugger.continue_debugee().unwrap();
     assert_no_proc!(debugee_pid);
 }
+
+#[test]
+#[serial]
+fn test_read_enum_with_explicit_discriminants() {
+    let process = prepare_debugee_process(VARS_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_line("vars.rs", 581).unwrap();
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(581));
+
+    let vars = debugger.read_local_variables().unwrap();
+    assert_rust_enum(&vars[0], "high", "HighDiscr", |enum_val| {
+        assert_struct(enum_val, "High", "High", |_, member| {
+            assert_scalar(member, "0", "u32", Some(SupportedScalar::U32(5)));
+        });
+    });
+    assert_rust_enum(&vars[1], "low", "HighDiscr", |enum_val| {
+        assert_struct(enum_val, "Low", "Low", |_, member| {
+            assert_scalar(member, "0", "u32", Some(SupportedScalar::U32(6)));
+        });
+    });
+    assert_rust_enum(&vars[2], "neg", "NegativeDiscr", |enum_val| {
+        assert_struct(enum_val, "Neg", "Neg", |_, member| {
+            assert_scalar(member, "0", "u8", Some(SupportedScalar::U8(7)));
+        });
+    });
+    assert_rust_enum(&vars[3], "pos", "NegativeDiscr", |enum_val| {
+        assert_struct(enum_val, "Pos", "Pos", |_, member| {
+            assert_scalar(member, "0", "u8", Some(SupportedScalar::U8(8)));
+        });
+    });
+    assert_rust_enum(
+        &vars[4],
+        "niche",
+        "Option<vars::enum_discriminants::NegativeDiscr>",
+        |enum_val| {
+            assert_struct(enum_val, "Some", "Some", |_, member| {
+                assert_rust_enum(member, "0", "NegativeDiscr", |enum_val| {
+                    assert_struct(enum_val, "Pos", "Pos", |_, member| {
+                        assert_scalar(member, "0", "u8", Some(SupportedScalar::U8(9)));
+                    });
+                });
+            });
+        },
+    );
+    assert_rust_enum(
+        &vars[5],
+        "none_niche",
+        "Option<vars::enum_discriminants::NegativeDiscr>",
+        |enum_val| {
+            assert_struct(enum_val, "None", "None", |_, _| {
+                panic!("expected empty struct");
+            });
+        },
+    );
+
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_read_iterators() {
+    let process = prepare_debugee_process(VARS_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_line("vars.rs", 594).unwrap();
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(594));
+
+    let vars = debugger.read_local_variables().unwrap();
+    assert_iterator(&vars[2], "map", "Map", |i, member| match i {
+        0 => assert_iterator(member, "iter", "vec::IntoIter<i32>", |_, remaining| {
+            assert_array(remaining, "remaining", "[i32]", |i, item| match i {
+                0 => assert_scalar(item, "0", "i32", Some(SupportedScalar::I32(2))),
+                1 => assert_scalar(item, "1", "i32", Some(SupportedScalar::I32(3))),
+                2 => assert_scalar(item, "2", "i32", Some(SupportedScalar::I32(4))),
+                _ => panic!("3 items expected"),
+            })
+        }),
+        1 => assert_closure(member, "f", "vars.rs:587", |_, _| {
+            panic!("no members expected")
+        }),
+        _ => panic!("2 members expected"),
+    });
+    assert_iterator(&vars[3], "filter", "Filter", |i, member| match i {
+        0 => assert_iterator(member, "iter", "slice::Iter<u8>", |_, remaining| {
+            assert_array(remaining, "remaining", "[u8]", |i, item| match i {
+                0 => assert_scalar(item, "0", "u8", Some(SupportedScalar::U8(10))),
+                1 => assert_scalar(item, "1", "u8", Some(SupportedScalar::U8(20))),
+                2 => assert_scalar(item, "2", "u8", Some(SupportedScalar::U8(30))),
+                _ => panic!("3 items expected"),
+            })
+        }),
+        1 => assert_closure(member, "predicate", "vars::iterators", |_, _| {
+            panic!("no members expected")
+        }),
+        _ => panic!("2 members expected"),
+    });
+    assert_iterator(&vars[4], "enumerate", "Enumerate", |i, member| match i {
+        0 => assert_iterator(member, "iter", "slice::Iter<i32>", |_, remaining| {
+            assert_array(remaining, "remaining", "[i32]", |i, item| match i {
+                0 => assert_scalar(item, "0", "i32", Some(SupportedScalar::I32(2))),
+                1 => assert_scalar(item, "1", "i32", Some(SupportedScalar::I32(3))),
+                2 => assert_scalar(item, "2", "i32", Some(SupportedScalar::I32(4))),
+                _ => panic!("3 items expected"),
+            })
+        }),
+        1 => assert_scalar(member, "count", "usize", Some(SupportedScalar::Usize(1))),
+        _ => panic!("2 members expected"),
+    });
+    assert_iterator(&vars[5], "chain", "Chain", |i, member| match i {
+        0 => assert_struct(member, "a", "Range<i32>", |i, member| match i {
+            0 => assert_scalar(member, "start", "i32", Some(SupportedScalar::I32(0))),
+            1 => assert_scalar(member, "end", "i32", Some(SupportedScalar::I32(3))),
+            _ => panic!("2 members expected"),
+        }),
+        1 => assert_struct(member, "b", "Range<i32>", |i, member| match i {
+            0 => assert_scalar(member, "start", "i32", Some(SupportedScalar::I32(5))),
+            1 => assert_scalar(member, "end", "i32", Some(SupportedScalar::I32(7))),
+            _ => panic!("2 members expected"),
+        }),
+        _ => panic!("2 members expected"),
+    });
+
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_read_platform_strings() {
+    let process = prepare_debugee_process(VARS_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_line("vars.rs", 605).unwrap();
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(605));
+
+    let vars = debugger.read_local_variables().unwrap();
+    assert_os_string(&vars[0], "path", "PathBuf", "/tmp/file.txt");
+    assert_os_string(&vars[1], "os_string", "OsString", "os string");
+    assert_os_string(&vars[2], "c_string", "CString", "c string");
+    assert_os_str(&vars[3], "c_str", "&core::ffi::c_str::CStr", "c string");
+    assert_os_str(&vars[4], "path_ref", "&std::path::Path", "/tmp/file.txt");
+    assert_os_str(&vars[5], "os_str", "&std::ffi::os_str::OsStr", "os string");
+
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_read_not_yet_initialized_variable() {
+    let process = prepare_debugee_process(VARS_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_line("vars.rs", 599).unwrap();
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(599));
+
+    let read_var = |name: &str| {
+        debugger
+            .read_variable(DQE::Variable(VariableSelector::Name {
+                var_name: name.to_string(),
+                only_local: true,
+            }))
+            .unwrap()
+    };
+
+    let path = read_var("path");
+    assert_os_string(&path[0], "path", "PathBuf", "/tmp/file.txt");
+
+    let os_str = read_var("os_str");
+    assert_eq!(os_str.len(), 1);
+    let VariableIR::Unavailable(unavailable) = &os_str[0] else {
+        panic!("not an unavailable variable");
+    };
+    assert_eq!(os_str[0].name(), "os_str");
+    assert_eq!(os_str[0].r#type(), "&std::ffi::os_str::OsStr");
+    assert_eq!(unavailable.reason, Unavailability::NotYetInitialized);
+    assert!(!unavailable.live_ranges.is_empty());
+
+    // not yet initialized variables are not locals
+    let locals = debugger.read_local_variables().unwrap();
+    assert_eq!(locals.len(), 1);
+
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_read_shadowed_variables() {
+    let process = prepare_debugee_process(VARS_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_line("vars.rs", 510).unwrap();
+    debugger.set_breakpoint_at_line("vars.rs", 512).unwrap();
+
+    let read_var1 = |debugger: &Debugger| {
+        debugger
+            .read_variable(DQE::Variable(VariableSelector::Name {
+                var_name: "var1".to_string(),
+                only_local: true,
+            }))
+            .unwrap()
+    };
+    let declaration_states = |debugger: &Debugger| {
+        debugger
+            .variable_declarations("var1")
+            .unwrap()
+            .into_iter()
+            .map(|decl| (decl.place.unwrap().1, decl.state))
+            .collect::<Vec<_>>()
+    };
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(510));
+
+    // the innermost initialized declaration is used
+    let var1 = read_var1(&debugger);
+    assert_eq!(var1.len(), 1);
+    assert_scalar(&var1[0], "var1", "i32", Some(SupportedScalar::I32(1)));
+    assert_eq!(
+        declaration_states(&debugger),
+        vec![
+            (508, DeclarationState::Shadowed),
+            (509, DeclarationState::Live),
+            (510, DeclarationState::NotYetInitialized),
+        ]
+    );
+
+    debugger.continue_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(512));
+
+    let var1 = read_var1(&debugger);
+    assert_eq!(var1.len(), 1);
+    assert_str(&var1[0], "var1", "some str");
+    let declarations = debugger.variable_declarations("var1").unwrap();
+    assert_eq!(declarations[0].state, DeclarationState::Shadowed);
+    assert_scalar(
+        declarations[0].value.as_ref().unwrap(),
+        "var1",
+        "i32",
+        Some(SupportedScalar::I32(0)),
+    );
+    assert_eq!(declarations[2].state, DeclarationState::Live);
+
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_read_channels() {
+    let process = prepare_debugee_process(CHANNELS_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut debugger = builder.build(process).unwrap();
+
+    debugger.set_breakpoint_at_line("main.rs", 33).unwrap();
+
+    debugger.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(33));
+
+    let tx = read_single_var(&debugger, "tx");
+    assert_channel(
+        &tx,
+        "tx",
+        "mpsc::Sender<i32>",
+        None,
+        (false, false),
+        |i, item| match i {
+            0 => assert_scalar(item, "0", "i32", Some(SupportedScalar::I32(2))),
+            1 => assert_scalar(item, "1", "i32", Some(SupportedScalar::I32(3))),
+            _ => panic!("2 items expected"),
+        },
+    );
+    let rx = read_single_var(&debugger, "rx");
+    assert_channel(
+        &rx,
+        "rx",
+        "mpsc::Receiver<i32>",
+        None,
+        (false, false),
+        |i, item| {
+            assert_scalar(
+                item,
+                &i.to_string(),
+                "i32",
+                Some(SupportedScalar::I32(i as i32 + 2)),
+            )
+        },
+    );
+
+    // messages are spread across several blocks of a list channel
+    let long_rx = read_single_var(&debugger, "long_rx");
+    assert_channel(
+        &long_rx,
+        "long_rx",
+        "mpsc::Receiver<u64>",
+        None,
+        (false, false),
+        |i, item| {
+            assert_scalar(
+                item,
+                &i.to_string(),
+                "u64",
+                Some(SupportedScalar::U64(i as u64)),
+            )
+        },
+    );
+    let len = read_single_var(&debugger, "long_rx.len");
+    assert_scalar(&len, "len", "usize", Some(SupportedScalar::Usize(40)));
+    let msg = read_single_var(&debugger, "long_rx.queue[35]");
+    assert_scalar(&msg, "35", "u64", Some(SupportedScalar::U64(35)));
+
+    let sync_tx = read_single_var(&debugger, "sync_tx");
+    assert_channel(
+        &sync_tx,
+        "sync_tx",
+        "mpsc::SyncSender<String>",
+        Some(4),
+        (false, false),
+        |i, item| match i {
+            0 => assert_string(item, "0", "a"),
+            1 => assert_string(item, "1", "b"),
+            _ => panic!("2 items expected"),
+        },
+    );
+
+    let rendezvous_tx = read_single_var(&debugger, "rendezvous_tx");
+    assert_channel(
+        &rendezvous_tx,
+        "rendezvous_tx",
+        "mpsc::SyncSender<u8>",
+        Some(0),
+        (false, true),
+        |_, _| panic!("no items expected"),
+    );
+
+    let closed_rx = read_single_var(&debugger, "closed_rx");
+    assert_channel(
+        &closed_rx,
+        "closed_rx",
+        "mpsc::Receiver<i32>",
+        None,
+        (true, false),
+        |i, item| match i {
+            0 => assert_scalar(item, "0", "i32", Some(SupportedScalar::I32(42))),
+            _ => panic!("1 item expected"),
+        },
+    );
+
+    let cb_tx = read_single_var(&debugger, "cb_tx");
+    assert_channel(
+        &cb_tx,
+        "cb_tx",
+        "crossbeam_channel::Sender<i32>",
+        Some(2),
+        (false, false),
+        |i, item| match i {
+            0 => assert_scalar(item, "0", "i32", Some(SupportedScalar::I32(10))),
+            _ => panic!("1 item expected"),
+        },
+    );
+
+    let cb_list_rx = read_single_var(&debugger, "cb_list_rx");
+    assert_channel(
+        &cb_list_rx,
+        "cb_list_rx",
+        "crossbeam_channel::Receiver<&str>",
+        None,
+        (true, false),
+        |i, item| match i {
+            0 => assert_str(item, "0", "x"),
+            _ => panic!("1 item expected"),
+        },
+    );
+
+    debugger.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
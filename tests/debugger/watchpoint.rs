@@ -6,7 +6,7 @@ use bugstalker::debugger::address::RelocatedAddress;
 use bugstalker::debugger::register::debug::{BreakCondition, BreakSize};
 use bugstalker::debugger::variable::select::{VariableSelector, DQE};
 use bugstalker::debugger::variable::{PointerVariable, SupportedScalar, VariableIR};
-use bugstalker::debugger::{Debugger, DebuggerBuilder};
+use bugstalker::debugger::{AccessKind, Debugger, DebuggerBuilder};
 use serial_test::serial;
 use BreakCondition::DataWrites;
 use BreakSize::Bytes8;
@@ -385,3 +385,106 @@ fn test_watchpoint_argument() {
     assert!(dbg.watchpoint_list().is_empty());
     assert_no_proc!(debugee_pid);
 }
+
+#[test]
+#[serial]
+fn test_watchpoint_through_pointer_rebind() {
+    let process = prepare_debugee_process(CALCULATIONS_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut dbg = builder.build(process).unwrap();
+    dbg.set_breakpoint_at_line("calculations.rs", 140).unwrap();
+    dbg.set_breakpoint_at_line("calculations.rs", 142).unwrap();
+    dbg.set_breakpoint_at_line("calculations.rs", 146).unwrap();
+
+    dbg.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(140));
+
+    let wp_dqe = DQE::Field(
+        DQE::Deref(DQE::Variable(VariableSelector::by_name("ACTIVE_COUNTER", false)).boxed())
+            .boxed(),
+        "value".to_string(),
+    );
+    let first_addr = dbg
+        .set_watchpoint_on_expr("(*ACTIVE_COUNTER).value", wp_dqe, DataWrites)
+        .unwrap()
+        .address;
+
+    dbg.continue_debugee().unwrap();
+    let (old, new) = (SupportedScalar::U32(1), Some(SupportedScalar::U32(2)));
+    assert_old_new(&info, "(*ACTIVE_COUNTER).value", "u32", old, new);
+    assert!(info.rebind.take().is_none());
+
+    // pointer is reassigned, watchpoint moves to a new location
+    dbg.continue_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(142));
+    let second_addr = info.rebind.take().unwrap().unwrap();
+    assert_ne!(first_addr, second_addr);
+    assert_eq!(dbg.watchpoint_list()[0].address, second_addr);
+
+    dbg.continue_debugee().unwrap();
+    let (old, new) = (SupportedScalar::U32(10), Some(SupportedScalar::U32(11)));
+    assert_old_new(&info, "(*ACTIVE_COUNTER).value", "u32", old, new);
+
+    // pointer is null, watchpoint is removed
+    dbg.continue_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(146));
+    assert_eq!(info.rebind.take(), Some(None));
+    assert!(dbg.watchpoint_list().is_empty());
+
+    dbg.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
+
+#[test]
+#[serial]
+fn test_read_and_access_watchpoints() {
+    let process = prepare_debugee_process(CALCULATIONS_APP, &[]);
+    let debugee_pid = process.pid();
+    let info = TestInfo::default();
+    let builder = DebuggerBuilder::new().with_hooks(TestHooks::new(info.clone()));
+    let mut dbg = builder.build(process).unwrap();
+    dbg.set_breakpoint_at_fn("main").unwrap();
+
+    dbg.start_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(108));
+    let wp_dqe = DQE::Variable(VariableSelector::by_name("GLOBAL_1", false));
+    dbg.set_watchpoint_on_expr("GLOBAL_1", wp_dqe, BreakCondition::DataReads)
+        .unwrap();
+
+    // stores are skipped, value is unchanged between loads
+    dbg.continue_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(34));
+    assert_eq!(info.access.take().unwrap().kind, Some(AccessKind::Load));
+    let (old, new) = (SupportedScalar::I64(1), Some(SupportedScalar::I64(1)));
+    assert_old_new(&info, "calculations::GLOBAL_1", "i64", old, new);
+    dbg.continue_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(35));
+    assert_eq!(info.access.take().unwrap().kind, Some(AccessKind::Load));
+    let (old, new) = (SupportedScalar::I64(0), Some(SupportedScalar::I64(0)));
+    assert_old_new(&info, "calculations::GLOBAL_1", "i64", old, new);
+
+    let wp_dqe = DQE::Variable(VariableSelector::by_name("GLOBAL_1", false));
+    dbg.remove_watchpoint_by_expr(wp_dqe.clone()).unwrap();
+    dbg.set_watchpoint_on_expr("GLOBAL_1", wp_dqe, BreakCondition::DataReadsWrites)
+        .unwrap();
+
+    // store at line 35, stop place is the next instruction
+    dbg.continue_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(36));
+    let access = info.access.take().unwrap();
+    assert_eq!(access.kind, Some(AccessKind::Store));
+    let (old, new) = (SupportedScalar::I64(0), Some(SupportedScalar::I64(3)));
+    assert_old_new(&info, "calculations::GLOBAL_1", "i64", old, new);
+    dbg.continue_debugee().unwrap();
+    assert_eq!(info.line.take(), Some(36));
+    let next_access = info.access.take().unwrap();
+    assert_eq!(next_access.kind, Some(AccessKind::Load));
+    assert!(next_access.instruction > access.instruction);
+
+    let wp_dqe = DQE::Variable(VariableSelector::by_name("GLOBAL_1", false));
+    dbg.remove_watchpoint_by_expr(wp_dqe).unwrap();
+    dbg.continue_debugee().unwrap();
+    assert_no_proc!(debugee_pid);
+}
@@ -1,3 +1,5 @@
+import tempfile
+import time
 import unittest
 from helper import Debugger
 
@@ -15,8 +17,8 @@ class CommandTestCase(unittest.TestCase):
         self.debugger.cmd('break main', 'New breakpoint')
         self.debugger.cmd('run', 'myprint("Hello, world!");')
         self.debugger.cmd('break myprint', 'New breakpoint')
-        self.debugger.cmd('continue', 'Hit breakpoint 2')
-        self.debugger.cmd('continue', 'Hello, world!', 'Hit breakpoint 2')
+        self.debugger.cmd('continue', 'breakpoint 2 hit')
+        self.debugger.cmd('continue', 'Hello, world!', 'breakpoint 2 hit')
         self.debugger.cmd('continue', 'bye')
 
     def test_line_breakpoint(self):
@@ -30,8 +32,8 @@ class CommandTestCase(unittest.TestCase):
         """Sets multiple breakpoints at line"""
         self.debugger.cmd('break hello_world.rs:5', 'New breakpoint')
         self.debugger.cmd('break hello_world.rs:9', 'New breakpoint')
-        self.debugger.cmd('run', 'Hit breakpoint 1 at', 'myprint("Hello, world!")')
-        self.debugger.cmd('continue', 'Hello, world!', 'Hit breakpoint 2 at', 'myprint("bye!")')
+        self.debugger.cmd('run', 'breakpoint 1 hit at', 'myprint("Hello, world!")')
+        self.debugger.cmd('continue', 'Hello, world!', 'breakpoint 2 hit at', 'myprint("bye!")')
         self.debugger.cmd('continue', 'bye!')
 
     # maps 555555554000-55555555a000
@@ -40,13 +42,13 @@ class CommandTestCase(unittest.TestCase):
         # determine address first
         self.debugger.cmd('break hello_world.rs:5', 'New breakpoint')
         self.debugger.cmd('run')
-        addr = self.debugger.search_in_output(r'Hit breakpoint 1 at .*0x(.*):')
+        addr = self.debugger.search_in_output(r'0x(\S+) breakpoint 1 hit at')
         addr = "0x" + addr[:14]
-        self.debugger.cmd('q')
+        self.debugger.cmd('q --kill')
         # respawn debugger and test address breakpoint
         self.debugger = Debugger(path='./examples/target/debug/hello_world')
         self.debugger.cmd(f'break {addr}', 'New breakpoint')
-        self.debugger.cmd('run', 'Hit breakpoint 1 at')
+        self.debugger.cmd('run', 'breakpoint 1 hit at')
         self.debugger.cmd('continue', 'Hello, world!', 'bye!')
 
     def test_write_register(self):
@@ -56,14 +58,14 @@ class CommandTestCase(unittest.TestCase):
         self.debugger.cmd('break hello_world.rs:10', 'New breakpoint')
         self.debugger.cmd('run')
 
-        start_addr = self.debugger.search_in_output(r'Hit breakpoint 1 at .*0x(.*):')
+        start_addr = self.debugger.search_in_output(r'0x(\S+) breakpoint 1 hit at')
         start_addr = "0x" + start_addr[:14]
         self.assertNotEqual(start_addr, "")
         self.debugger.cmd('continue')
-        addr = self.debugger.search_in_output(r'Hit breakpoint 2 at .*0x(.*):')
+        addr = self.debugger.search_in_output(r'0x(\S+) breakpoint 2 hit at')
         addr = "0x" + addr[:14]
         self.assertNotEqual(addr, "")
-        self.debugger.cmd('q')
+        self.debugger.cmd('q --kill')
 
         # assume that address of ret instruction at 1 byte offset
         addr_as_integer = int(addr, 16) + 1
@@ -109,12 +111,102 @@ class CommandTestCase(unittest.TestCase):
     def test_step_over_on_fn_decl(self):
         """Stop debugee at function declaration line"""
         self.debugger.cmd('break hello_world.rs:14', 'New breakpoint')
-        self.debugger.cmd('run', 'Hit breakpoint 1 at')
+        self.debugger.cmd('run', 'breakpoint 1 hit at')
         self.debugger.cmd('next', '15     println!("{}", s)')
 
+    @staticmethod
+    def test_step_over_await():
+        """Step over await points stays in the same async task"""
+        debugger = Debugger(path='./examples/target/debug/tokioticker')
+        debugger.cmd('break main.rs:5', 'New breakpoint')
+        debugger.cmd('run', 'breakpoint 1 hit at')
+        debugger.cmd_re('var task_name', r'task_name = &str\((task_\d)\)')
+        task = debugger._process.match.group(1).decode()
+        debugger.cmd('next', '6         println!("task \\"{task_name}\\" tick!");')
+        debugger.cmd('next', '5         tokio::time::sleep(Duration::from_secs(seed)).await;')
+        debugger.cmd('next', '6         println!("task \\"{task_name}\\" tick!");')
+        debugger.cmd('var task_name', f'task_name = &str({task})')
+        debugger.cmd('q --kill')
+
     def test_get_symbol(self):
         """Get debugee symbol"""
-        self.debugger.cmd_re('symbol main', '__libc_start_main', r'main - Text 0x[0-9A-F]{,16}')
+        self.debugger.cmd_re(
+            'symbol main',
+            '__libc_start_main',
+            r'hello_world::main - func 0x[0-9A-F]{,16} at .*hello_world.rs:\d+',
+        )
+
+    def test_resolve_address(self):
+        """Resolve addresses into functions and source places"""
+        self.debugger.cmd('resolve 0x1', 'program is not being started')
+        self.debugger.cmd('break hello_world.rs:15', 'New breakpoint')
+        self.debugger.cmd_re('run', r'(0x[0-9A-F]+) breakpoint 1 hit at')
+        addr = self.debugger._process.match.group(1).decode()
+        self.debugger.cmd_re(
+            f'resolve   0: {addr} - hello_world::myprint 0x10',
+            rf'{addr} <hello_world::myprint\+0x[0-9a-f]+> in .*hello_world',
+            r'hello_world::myprint at \S*hello_world.rs:15:\d+',
+            r'0x00000000000010: address is not mapped to a known object file',
+        )
+
+    def test_run_with_stdin_redirect(self):
+        """Run and restart debugee with a file as a standard input"""
+        self.debugger = Debugger(path='./examples/target/debug/echo')
+        with tempfile.NamedTemporaryFile('w', suffix='.txt') as first, \
+                tempfile.NamedTemporaryFile('w', suffix='.txt') as second:
+            first.write('first\nsecond\n')
+            first.flush()
+            second.write('third\n')
+            second.flush()
+
+            self.debugger.cmd('break main.rs:11', 'New breakpoint 1')
+            self.debugger.cmd(f'run < {first.name}', 'echo: first', 'echo: second', 'breakpoint 1 hit')
+            self.debugger.cmd('var count', 'count = i32(2)')
+            self.debugger.cmd(f'run --stdin {second.name}', 'Restart a program?')
+            self.debugger.cmd('y', 'breakpoint 1 hit')
+            self.debugger.cmd('var count', 'count = i32(1)')
+        self.debugger.cmd('run < /not/exists', 'open /not/exists as a program stdin')
+
+    def test_kill(self):
+        """Kill debugee and run it again"""
+        self.debugger.cmd('kill', 'program is not being started')
+        self.debugger.cmd('break myprint', 'New breakpoint 1')
+        self.debugger.cmd('run', 'breakpoint 1 hit')
+        self.debugger.cmd('kill', 'Program exit with code: 137')
+        self.debugger.cmd('kill', 'program is not being started')
+        # breakpoints are kept for the next run
+        self.debugger.cmd('run', 'Restart a program?')
+        self.debugger.cmd('y', 'breakpoint 1 hit')
+        self.debugger.cmd('continue', 'breakpoint 1 hit')
+
+    def test_symbolize_backtrace(self):
+        """Resolve addresses of a backtrace from file"""
+        self.debugger.cmd_re('break hello_world.rs:15', r'New breakpoint 1 at (0x[0-9A-F]+)')
+        addr = self.debugger._process.match.group(1).decode()
+        offset = hex(int(addr, 16))
+
+        with tempfile.NamedTemporaryFile('w', suffix='.txt') as bt:
+            bt.write('==1==ERROR: AddressSanitizer: heap-use-after-free\n')
+            bt.write(f'    #0 0x55d4c5a0f1b3 in myprint (/another/host/hello_world+{offset})\n')
+            bt.write(f'   1:     {offset} - hello_world::myprint\n')
+            bt.flush()
+
+            self.debugger.cmd_re(
+                f'symbolize {bt.name}',
+                r'#0 0x[0-9A-F]+ \(/another/host/hello_world\) <hello_world::myprint\+0x[0-9a-f]+>',
+                r'hello_world::myprint at \S*hello_world.rs:15:\d+',
+                r'#1 0x[0-9A-F]+ <hello_world::myprint\+0x[0-9a-f]+>',
+                r'hello_world::myprint at \S*hello_world.rs:15:\d+',
+            )
+        self.debugger.cmd('symbolize /not/exists', 'backtrace import error')
+
+    def test_get_symbol_variable(self):
+        """Get debugee static variable symbol"""
+        self.debugger = Debugger(path='./examples/target/debug/vars')
+        self.debugger.cmd_re(
+            'symbol GLOB_1',
+            r'vars::GLOB_1 - object 0x[0-9A-F]{,16} at .*vars.rs:162',
+        )
 
     def test_backtrace(self):
         """Backtrace"""
@@ -136,6 +228,16 @@ class CommandTestCase(unittest.TestCase):
         debugger.cmd('run', '15     print(s, &args[5]);')
         debugger.cmd('var locals', 's = i64(6)')
 
+    @staticmethod
+    def test_value_history():
+        """Reference printed variables by history numbers"""
+        debugger = Debugger(path='./examples/target/debug/calc -- 1 2 3 --description result')
+        debugger.cmd('break main.rs:15', 'New breakpoint')
+        debugger.cmd('run', '15     print(s, &args[5]);')
+        debugger.cmd('var s', '$0: s = i64(6)')
+        debugger.cmd('var $0', '$1: $0 = i64(6)')
+        debugger.cmd('history values', '$0: s = i64(6)', '$1: $0 = i64(6)')
+
     def test_function_breakpoint_remove(self):
         """Remove breakpoint at function by its name"""
         self.debugger.cmd('break main', 'New breakpoint')
@@ -155,6 +257,89 @@ class CommandTestCase(unittest.TestCase):
         self.debugger.cmd('break remove 1', 'Removed breakpoint')
         self.debugger.cmd('run', 'bye!')
 
+    def test_breakpoint_at_line_file_matching(self):
+        """Set breakpoints at lines by a path suffix and in the current file"""
+        self.debugger.cmd('break mod.rs:10', 'is ambiguous, candidates are:')
+        self.debugger.cmd('break src/hello_world.rs:5', 'New breakpoint 1')
+        self.debugger.cmd('run', 'breakpoint 1 hit')
+        self.debugger.cmd('break :15', 'New breakpoint 2', 'hello_world.rs:15')
+        self.debugger.cmd('continue', 'breakpoint 2 hit')
+
+    @staticmethod
+    def test_breakpoint_at_column():
+        """Set breakpoint at a statement on a line with several statements"""
+        debugger = Debugger(path='./examples/target/debug/vars')
+        debugger.cmd(
+            'break vars.rs:188',
+            'New breakpoint 1',
+            'New breakpoint 2',
+            'Line contains statements at columns: 24, 39',
+        )
+        debugger.cmd('break remove vars.rs:188', 'Removed breakpoint 1', 'Removed breakpoint 2')
+        debugger.cmd('break vars.rs:188:99', 'statements are at columns: 24, 39')
+        debugger.cmd('break vars.rs:188:39', 'New breakpoint 3')
+        debugger.cmd('run', 'breakpoint 3 hit', '188     THREAD_LOCAL_VAR_1.with(|tl1| tl1.set(1));')
+        debugger.cmd('arg all', 'tl1 = ')
+
+    def test_breakpoint_resolution_diagnostics(self):
+        """Explain where a breakpoint is planted or why a line has no code"""
+        self.debugger.cmd(
+            'break hello_world.rs:6',
+            'hello_world.rs:7',
+            'There is no code at line 6, breakpoint is planted at the next line with code: 7',
+        )
+        self.debugger.cmd(
+            'break hello_world.rs:11',
+            'no code at line 11, closest lines with code: 7, 9, 10, 14, 15, 16',
+            'the line is outside of compiled functions',
+        )
+        self.debugger.cmd('break hello_world.rs:100', 'closest lines with code: 16')
+        debugger = Debugger(path='./examples/target/debug/vars')
+        debugger.cmd('break vars.rs:38', 'the line is inside `vars::compound_types`')
+
+    def test_breakpoint_commands(self):
+        """Execute commands attached to a breakpoint on each hit"""
+        self.debugger.cmd('break hello_world.rs:15', 'New breakpoint')
+        self.debugger.cmd('break commands 1 arg s; continue', 'Commands attached to breakpoint 1')
+        self.debugger.cmd(
+            'run',
+            'Hello, world!',
+            's = &str(bye!)',
+            'bye!',
+            'Program exit with code: 0',
+        )
+
+    def test_run_stop_at_entry_and_main(self):
+        """Stop at the program entry point or at main function without breakpoints"""
+        self.debugger.cmd('run --stop-at-entry', 'program entry point reached')
+        self.debugger.cmd('run --stop-at-main', 'Restart a program?')
+        self.debugger.cmd(
+            'y',
+            'hello_world::main at',
+            '5     myprint("Hello, world!");',
+        )
+        self.debugger.cmd('continue', 'Hello, world!', 'bye!', 'Program exit with code: 0')
+
+    @staticmethod
+    def test_breakpoint_on_return():
+        """Stop when a function returns a value satisfying a condition"""
+        debugger = Debugger(path='./examples/target/debug/recursion')
+        debugger.cmd('break --on-return fibonacci == 4181', '(on return == 4181)')
+        debugger.cmd(
+            'run',
+            'breakpoint 1 hit at',
+            '15     println!("{}", fibonacci(19));',
+            'Value returned: u64(4181)',
+        )
+        debugger.cmd('break info', '(on return == 4181) (hits: 1)')
+
+    def test_breakpoint_hardware(self):
+        """Set breakpoint using a debug register"""
+        self.debugger.cmd('break --hardware myprint', 'New breakpoint 1', '(hardware)')
+        self.debugger.cmd('run', 'breakpoint 1 hit at')
+        self.debugger.cmd('break info', '(hardware) (hits: 1)')
+        self.debugger.cmd('continue', 'breakpoint 1 hit at')
+
     def test_breakpoint_info(self):
         """View breakpoints list"""
         self.debugger.cmd('break hello_world.rs:9', 'New breakpoint')
@@ -206,18 +391,18 @@ class CommandTestCase(unittest.TestCase):
     def test_debugee_restart_at_end(self):
         """Debugee process restarting after debugee completing"""
         self.debugger.cmd('break hello_world.rs:9', 'New breakpoint')
-        self.debugger.cmd('run', 'Hello, world!', 'Hit breakpoint 1')
+        self.debugger.cmd('run', 'Hello, world!', 'breakpoint 1 hit')
         self.debugger.cmd('continue', 'bye!')
         self.debugger.cmd('run', 'Restart a program?')
-        self.debugger.cmd('y', 'Hello, world!', 'Hit breakpoint 2')
-        self.debugger.cmd('quit')
+        self.debugger.cmd('y', 'Hello, world!', 'breakpoint 2 hit')
+        self.debugger.cmd('quit --kill')
 
     @staticmethod
     def test_frame_switch():
         """Switch stack frame and assert argument values"""
         debugger = Debugger(path='./examples/target/debug/calc -- 1 2 3 --description result')
         debugger.cmd('break main.rs:21', 'New breakpoint 1')
-        debugger.cmd('run', 'Hit breakpoint 1')
+        debugger.cmd('run', 'breakpoint 1 hit')
         debugger.cmd('arg all', 'a = i64(1)', 'b = i64(2)')
         debugger.cmd('frame switch 1')
         debugger.cmd('arg all', 'a = i64(1)', 'b = i64(2)', 'c = i64(3)')
@@ -231,6 +416,30 @@ class CommandTestCase(unittest.TestCase):
         self.debugger.cmd('continue')
         self.debugger.cmd('source asm', 'Assembler code for function hello_world::myprint', 'mov')
 
+    def test_examine_memory(self):
+        """Examine debugee memory with typed reads"""
+        self.debugger.cmd('break myprint', 'New breakpoint')
+        self.debugger.cmd('run')
+        self.debugger.cmd('x/s $rdi', '"Hello, world!"')
+        self.debugger.cmd('x/4cb $rdi', "72 'H'  101 'e'  108 'l'  108 'l'")
+        self.debugger.cmd('x/2i $rip', '=>', 'mov')
+        self.debugger.cmd_re('x/2xg $rsp', r'0x[0-9A-F]{14}: 0x[0-9a-f]{16}  0x[0-9a-f]{16}')
+        self.debugger.cmd('x/0xg $rsp', 'count of units must be at least 1')
+        self.debugger.cmd('x/9000xg $rsp', 'too much memory to examine')
+        self.debugger.cmd('x/2305843009213693952xg $rsp', 'too much memory to examine')
+
+    def test_segment_base_registers(self):
+        """Read FS and GS segment bases in expressions"""
+        self.debugger.cmd('break myprint', 'New breakpoint')
+        self.debugger.cmd('run', 'breakpoint 1 hit')
+        self.debugger.cmd('var $fs_base')
+        fs_base = self.debugger.search_in_output(r'fs_base.*u64\((\d+)\)')
+        self.assertNotIn(fs_base, [None, '0'])
+        self.debugger.cmd('var $gs_base', '$gs_base = u64(')
+        # segment bases are the same for all frames of a thread
+        self.debugger.cmd('frame switch 1')
+        self.debugger.cmd('var $fs_base', f'$fs_base = u64({fs_base})')
+
     def test_source_fn(self):
         """View function source code"""
         self.debugger.cmd('break main', 'New breakpoint')
@@ -248,7 +457,7 @@ class CommandTestCase(unittest.TestCase):
         """Switch stack frame and assert argument values"""
         debugger = Debugger(path='./examples/target/debug/calc -- 1 2 3 --description result')
         debugger.cmd('break main.rs:21', 'New breakpoint 1')
-        debugger.cmd('run', 'Hit breakpoint 1')
+        debugger.cmd('run', 'breakpoint 1 hit')
         debugger.cmd(
             'source fn',
             'fn sum2(a: i64, b: i64) -> i64 {',
@@ -301,3 +510,22 @@ class CommandTestCase(unittest.TestCase):
         debugger.cmd('break rust_panic', 'New breakpoint')
         debugger.cmd('run', 'attempt to divide by zero')
         debugger.cmd('bt', 'rust_panic', 'panic::divided_by_zero')
+
+    @staticmethod
+    def test_deadlock_check():
+        """Find threads that wait for each other on mutexes"""
+        for arg, lock_line, guard_line in [('', 7, 5), (' -- parking_lot', 19, 17)]:
+            debugger = Debugger(path=f'./examples/target/debug/deadlock{arg}')
+            debugger.cmd('run')
+            time.sleep(3)
+            debugger.control('c')
+            debugger.expect_in_output('Signal SIGINT received, debugee stopped')
+            debugger.cmd(
+                'deadlock check',
+                'Deadlock #1 (2 threads):',
+                f'main.rs:{lock_line}',
+                'held by thread',
+                'guard `from_guard`',
+                f'main.rs:{guard_line}',
+            )
+            debugger.cmd('q --kill')
@@ -0,0 +1,36 @@
+import unittest
+from helper import Debugger
+
+
+class CppExceptionsTestCase(unittest.TestCase):
+    def setUp(self):
+        self.debugger = Debugger(path='./examples/target/debug/cpp_exceptions')
+
+    def test_catch_throw(self):
+        """Stop when C++ exception is thrown"""
+        self.debugger.cmd('catch throw', '(catch throw)')
+        self.debugger.cmd('run', 'breakpoint 1 hit', 'Exception thrown: std::runtime_error')
+        self.debugger.cmd('bt', '::parse', '::handle', 'cpp_exceptions::main')
+        self.debugger.cmd('continue', 'Exception thrown: app::ParseError')
+        self.debugger.cmd('break info', '(catch throw) (hits: 2)')
+
+    def test_catch_catch(self):
+        """Stop when C++ exception is caught"""
+        self.debugger.cmd('catch catch', '(catch catch)')
+        self.debugger.cmd('run', 'breakpoint 1 hit', 'Exception caught: std::runtime_error')
+        self.debugger.cmd('continue', 'Exception caught: app::ParseError')
+
+    def test_catch_unwind(self):
+        """Continue from a thrown exception to the handler that catches it"""
+        self.debugger.cmd('catch throw', '(catch throw)')
+        self.debugger.cmd('run', 'Exception thrown: std::runtime_error')
+        self.debugger.cmd('catch unwind', 'parser.cpp:20', 'catch (const std::runtime_error& e)')
+        self.debugger.cmd('continue', 'Exception thrown: app::ParseError')
+        self.debugger.cmd('catch unwind', 'parser.cpp:23', 'catch (const app::ParseError& e)')
+        self.debugger.cmd('continue', 'caught ParseError: 3')
+
+    def test_remove_catchpoint(self):
+        """Catchpoint removed by number never stops a program"""
+        self.debugger.cmd('catch throw', 'New breakpoint 1')
+        self.debugger.cmd('break remove 1', 'Removed breakpoint 1')
+        self.debugger.cmd('run', 'handled: 2')
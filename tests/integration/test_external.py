@@ -19,21 +19,21 @@ class ExternalProcessTestCase(unittest.TestCase):
     def test_external_process_set_breakpoint(self):
         """Set breakpoint in debugee attached by pid"""
         self.debugger.cmd('break sleeper.rs:24', 'New breakpoint')
-        self.debugger.cmd('continue', 'Hit breakpoint 1')
+        self.debugger.cmd('continue', 'breakpoint 1 hit')
         self.debugger.cmd('continue')
 
     def test_external_process_view_variables(self):
         """View variables in debugee attached by pid"""
         self.debugger.cmd('break sleeper.rs:24', 'New breakpoint')
-        self.debugger.cmd('continue', 'Hit breakpoint 1')
+        self.debugger.cmd('continue', 'breakpoint 1 hit')
         self.debugger.cmd('var locals', 'sleep_base_sec = u64(1)')
 
     def test_external_process_restart(self):
         """Restart debugee attached by pid, after restart debugger behaviour should be equivalent to the default one"""
         self.debugger.cmd('break sleeper.rs:24', 'New breakpoint')
-        self.debugger.cmd('continue', 'Hit breakpoint 1')
+        self.debugger.cmd('continue', 'breakpoint 1 hit')
         self.debugger.cmd('run', 'Restart a program?')
-        self.debugger.cmd('y', 'Hit breakpoint 2')
+        self.debugger.cmd('y', 'breakpoint 2 hit')
         self.debugger.cmd('continue')
 
     def test_external_process_resume_process(self):
@@ -41,7 +41,8 @@ class ExternalProcessTestCase(unittest.TestCase):
         debugee = self.debugger.debugee_process()
         self.assertEqual(debugee.status(), psutil.STATUS_TRACING_STOP)
 
-        self.debugger.cmd('quit')
+        self.debugger.cmd('quit', 'Kill it, detach from it or cancel quit?')
+        self.debugger.cmd('d')
         time.sleep(0.1)
 
         debugee = self.debugger.debugee_process()
@@ -49,3 +50,35 @@ class ExternalProcessTestCase(unittest.TestCase):
             debugee.status() == psutil.STATUS_RUNNING or debugee.status() == psutil.STATUS_SLEEPING,
             "unexpected debugee process status: " + debugee.status(),
         )
+
+    def test_external_process_detach_with_breakpoints(self):
+        """Detach from a process stopped at a breakpoint, the process continues without a trap"""
+        process = self.debugger._external_debugee_process
+        self.debugger.cmd('break sleeper.rs:24', 'New breakpoint')
+        self.debugger.cmd('continue', 'breakpoint 1 hit')
+        self.debugger.cmd('quit --detach')
+
+        process.expect(pexpect.EOF, timeout=10)
+        process.close()
+        self.assertEqual(process.exitstatus, 0)
+
+    def test_external_process_second_inferior(self):
+        """Attach second process as a new inferior, inferiors have independent breakpoints"""
+        second = pexpect.spawn('./examples/target/debug/sleeper -s 1')
+        time.sleep(1)
+        first_pid = self.debugger.debugee_process().pid
+
+        self.debugger.cmd(f'inferior attach {second.pid}', f'Inferior #2 (pid: {second.pid}) brought into focus')
+        self.debugger.cmd('inferior list', f'#1 pid: {first_pid}, in progress', f'#2 pid: {second.pid}, in progress')
+        self.debugger.cmd('thread current', f'thread id: {second.pid}')
+
+        self.debugger.cmd('break sleeper.rs:24', 'New breakpoint')
+        self.debugger.cmd('continue', 'breakpoint 1 hit')
+
+        self.debugger.cmd('inferior switch 1', f'Inferior #1 (pid: {first_pid}) brought into focus')
+        self.debugger.cmd('break info')
+        self.debugger.cmd('continue', 'exit with code: 0')
+
+        self.debugger.cmd('inferior switch 2', f'Inferior #2 (pid: {second.pid}) brought into focus')
+        self.debugger.cmd('var locals', 'sleep_base_sec = u64(1)')
+        self.debugger.cmd('continue', 'exit with code: 0')
@@ -30,28 +30,28 @@ class MultithreadTestCase(unittest.TestCase):
         # set breakpoint at program ends
         self.debugger.cmd('break mt.rs:14', 'New breakpoint')
 
-        self.debugger.cmd('run', 'Hit breakpoint 1 at', '6     let jh1 = thread::spawn(sum1);')
+        self.debugger.cmd('run', 'breakpoint 1 hit at', '6     let jh1 = thread::spawn(sum1);')
         self.debugger.cmd(
             'continue',
             'thread 1 spawn',
             'thread 2 spawn',
-            'Hit breakpoint 3 at',
+            'breakpoint 3 hit at',
             '36     let mut sum2 = 0;',
         )
-        self.debugger.cmd('continue', 'Hit breakpoint 2 at', '24     let mut sum = 0;')
-        self.debugger.cmd('continue', 'Hit breakpoint 4 at', '14     println!("total {}", sum1 + sum2);')
+        self.debugger.cmd('continue', 'breakpoint 2 hit at', '24     let mut sum = 0;')
+        self.debugger.cmd('continue', 'breakpoint 4 hit at', '14     println!("total {}", sum1 + sum2);')
         self.debugger.cmd('continue', 'total 249985000')
 
     def test_multithreaded_backtrace(self):
         """Backtrace command for multithread debugee"""
         self.debugger.cmd('break mt.rs:24', 'New breakpoint')
-        self.debugger.cmd('run', 'thread 1 spawn', 'Hit breakpoint 1 at', '24     let mut sum = 0;')
+        self.debugger.cmd('run', 'thread 1 spawn', 'breakpoint 1 hit at', '24     let mut sum = 0;')
         self.debugger.cmd('backtrace', 'mt::sum1', 'new::thread_start')
 
     def test_multithreaded_trace(self):
         """Trace command for multithread debugee"""
         self.debugger.cmd('break mt.rs:36', 'New breakpoint')
-        self.debugger.cmd('run', 'Hit breakpoint 1 at', '36     let mut sum2 = 0;')
+        self.debugger.cmd('run', 'breakpoint 1 hit at', '36     let mut sum2 = 0;')
         self.debugger.cmd(
             'backtrace all',
             'thread',
@@ -65,22 +65,34 @@ class MultithreadTestCase(unittest.TestCase):
     def test_multithreaded_quit(self):
         """Quit command for multithread debugee"""
         self.debugger.cmd('break mt.rs:36', 'New breakpoint')
-        self.debugger.cmd('run', 'Hit breakpoint 1 at', '36     let mut sum2 = 0;')
-        self.debugger.cmd('quit')
+        self.debugger.cmd('run', 'breakpoint 1 hit at', '36     let mut sum2 = 0;')
+        self.debugger.cmd('quit', 'Kill it, detach from it or cancel quit?')
+        self.debugger.cmd('k')
+        time.sleep(2)
+        self.assertFalse(self.debugger.is_alive())
+
+    def test_multithreaded_quit_cancel(self):
+        """Canceled quit command keeps debugger and debugee alive"""
+        self.debugger.cmd('break mt.rs:36', 'New breakpoint')
+        self.debugger.cmd('run', 'breakpoint 1 hit at', '36     let mut sum2 = 0;')
+        self.debugger.cmd('quit', 'Kill it, detach from it or cancel quit?')
+        self.debugger.cmd('c')
+        self.debugger.cmd('thread current', '#1 thread id')
+        self.debugger.cmd('quit --kill')
         time.sleep(2)
         self.assertFalse(self.debugger.is_alive())
 
     def test_thread_info(self):
         """Thread info/current command for multithread debugee"""
         self.debugger.cmd('break mt.rs:40', 'New breakpoint')
-        self.debugger.cmd('run', 'Hit breakpoint 1 at')
+        self.debugger.cmd('run', 'breakpoint 1 hit at')
         self.debugger.cmd('thread info', '#1 thread id', '#2 thread id', '#3 thread id')
         self.debugger.cmd('thread current', '#3 thread id')
 
     def test_thread_switch(self):
         """Trace switch command for multithread debugee"""
         self.debugger.cmd('break mt.rs:40', 'New breakpoint')
-        self.debugger.cmd('run', 'Hit breakpoint 1 at')
+        self.debugger.cmd('run', 'breakpoint 1 hit at')
         self.debugger.cmd('thread current', '#3 thread id')
         # switch to another thread
         self.debugger.cmd('thread switch 2', 'Thread #2 brought into focus')
@@ -106,7 +118,7 @@ class MultithreadTestCase(unittest.TestCase):
     def test_thread_switch_frame_switch(self):
         """Trace switch and frame switch command for multithread debugee"""
         self.debugger.cmd('break mt.rs:40', 'New breakpoint')
-        self.debugger.cmd('run', 'Hit breakpoint 1 at')
+        self.debugger.cmd('run', 'breakpoint 1 hit at')
         self.debugger.cmd('thread current', '#3 thread id')
         # switch to another thread
         self.debugger.cmd('thread switch 2', 'Thread #2 brought into focus')
@@ -16,8 +16,33 @@ class OracleTestCase(unittest.TestCase):
         """Test tokio oracle"""
         self.debugger.cmd('b main.rs:20', 'New breakpoint')
         self.debugger.cmd('b main.rs:32', 'New breakpoint')
-        self.debugger.cmd('run', 'Hit breakpoint 1')
+        self.debugger.cmd('run', 'breakpoint 1 hit')
         self.debugger.cmd_re('oracle tokio', r'[1-9]\d? tasks running')
-        self.debugger.cmd('continue', 'Hit breakpoint 2')
+        self.debugger.cmd('continue', 'breakpoint 2 hit')
         self.debugger.cmd('oracle tokio', '0 tasks running')
-        self.debugger.cmd('q')
+        self.debugger.cmd('q --kill')
+
+    def test_tokio_thread_labels(self):
+        """Test that threads are grouped into user and tokio worker threads"""
+        self.debugger.cmd('b main.rs:6', 'New breakpoint')
+        self.debugger.cmd('run', 'breakpoint 1 hit')
+        self.debugger.cmd('thread info', 'user threads:', '#1 thread id:', 'tokio worker threads:', '"tokio-runtime-w"')
+        self.debugger.cmd('bt all', 'user threads:', 'thread #1', 'tokio worker threads:', 'tokioticker::new_ticker_task')
+        self.debugger.cmd('q --kill')
+
+
+class HeapOracleTestCase(unittest.TestCase):
+    """Test heap oracle"""
+
+    def setUp(self):
+        self.debugger = Debugger(path='./examples/target/debug/calc -- 1 2 3 --description result', oracles=['heap'])
+
+    def test_heap_oracle(self):
+        """Test heap oracle stats and leaks reports"""
+        self.debugger.cmd('b main.rs:10', 'New breakpoint')
+        self.debugger.cmd('run', 'breakpoint 1 hit')
+        self.debugger.cmd_re('oracle heap', r'[1-9]\d* allocations', r'[1-9]\d* live allocations')
+        self.debugger.cmd_re('oracle heap leaks', r'[1-9]\d* call sites with live allocations', 'call site')
+        self.debugger.cmd('continue', 'result: 6')
+        self.debugger.cmd_re('oracle heap stats', r'[1-9]\d* deallocations')
+        self.debugger.cmd('q --kill')
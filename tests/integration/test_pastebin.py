@@ -39,7 +39,7 @@ class PastebinTestCase(unittest.TestCase):
         time.sleep(0.2)
         thread.join()
         self.debugger.control('c')
-        self.debugger.cmd('q')
+        self.debugger.cmd('q --kill')
 
     def test_continue_until_response(self):
         """Runs a pastebin application and set breakpoint at http handler. Makes http request, do `continue` command and wait until http response not returning"""
@@ -66,4 +66,4 @@ class PastebinTestCase(unittest.TestCase):
         self.debugger.cmd('continue')
         thread.join()
         self.debugger.control('c')
-        self.debugger.cmd('q')
+        self.debugger.cmd('q --kill')
@@ -43,7 +43,7 @@ class SignalsTestCase(unittest.TestCase):
         """Send signal to stopped debugee process"""
         self.debugger = Debugger(path='./examples/target/debug/vars')
         self.debugger.cmd('break vars.rs:9', 'New breakpoint')
-        self.debugger.cmd('run', 'Hit breakpoint 1')
+        self.debugger.cmd('run', 'breakpoint 1 hit')
 
         self.debugger.debugee_process().send_signal(signal.SIGWINCH)
         time.sleep(1)
@@ -55,7 +55,7 @@ class SignalsTestCase(unittest.TestCase):
         """Send signal to stop debugee process and do a step"""
         self.debugger = Debugger(path='./examples/target/debug/vars')
         self.debugger.cmd('break vars.rs:9', 'New breakpoint')
-        self.debugger.cmd('run', 'Hit breakpoint 1')
+        self.debugger.cmd('run', 'breakpoint 1 hit')
 
         self.debugger.debugee_process().send_signal(signal.SIGWINCH)
         time.sleep(1)
@@ -67,7 +67,7 @@ class SignalsTestCase(unittest.TestCase):
         """Send signal to stop the debugee process and do a step over"""
         self.debugger = Debugger(path='./examples/target/debug/vars')
         self.debugger.cmd('break vars.rs:9', 'New breakpoint')
-        self.debugger.cmd('run', 'Hit breakpoint 1')
+        self.debugger.cmd('run', 'breakpoint 1 hit')
 
         self.debugger.debugee_process().send_signal(signal.SIGWINCH)
         time.sleep(1)
@@ -85,3 +85,21 @@ class SignalsTestCase(unittest.TestCase):
         self.debugger.control('c')
         self.debugger.expect_in_output('Signal SIGINT received, debugee stopped')
         self.debugger.cmd('bt', 'sleeper::main')
+
+    def test_signal_handler_frames(self):
+        """Backtrace through a signal handler and step out of it"""
+        self.debugger = Debugger(path='./examples/target/debug/signals -- custom_handler')
+        self.debugger.cmd('break on_sigusr1', 'New breakpoint')
+        self.debugger.cmd('run', 'Signal SIGUSR1 received, debugee stopped')
+        self.debugger.cmd('continue', 'breakpoint 1 hit')
+        self.debugger.cmd(
+            'bt',
+            'signals::on_sigusr1',
+            '<signal handler called>',
+            'nix::sys::signal::raise',
+            'signals::custom_handler',
+        )
+        # step out lands at the interrupted instruction, not into a signal trampoline
+        self.debugger.cmd('stepout')
+        self.debugger.cmd_re('bt', r'#0 0x[0-9A-F]+ - (?!signals::on_sigusr1|<signal handler called>)')
+        self.debugger.cmd('continue', 'handled: 1')
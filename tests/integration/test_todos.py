@@ -46,7 +46,7 @@ class TodosTestCase(unittest.TestCase):
             self.debugger.cmd('next', 'next')
             time.sleep(0.05)
 
-        self.debugger.cmd('q')
+        self.debugger.cmd('q --kill')
 
     def test_create_and_get(self):
         """Create an item, then try to get it and check that it exists in debugger (by `var` command)"""
@@ -82,4 +82,4 @@ class TodosTestCase(unittest.TestCase):
             time.sleep(0.1)
         thread.join()
 
-        self.debugger.cmd('q')
+        self.debugger.cmd('q --kill')
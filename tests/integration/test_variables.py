@@ -313,6 +313,48 @@ class VariablesTestCase(unittest.TestCase):
             's3 = &str(hello world)',
         )
 
+    def test_print_limits(self):
+        """Truncate printed collections, nested values and strings"""
+        self.debugger.cmd('break vars.rs:159', 'New breakpoint')
+        self.debugger.cmd('run', '159     let nop: Option<u8> = None;')
+        self.debugger.cmd('set print string-length 5')
+        self.debugger.cmd('var s1', 's1 = String(hello', '(truncated))')
+        self.debugger.cmd('set print string-length unlimited')
+        self.debugger.cmd('var s1', 's1 = String(hello world)')
+
+    def test_snapshot_diff(self):
+        """Compare variable values between stops"""
+        self.debugger.cmd('break vars.rs:254', 'New breakpoint')
+        self.debugger.cmd('run', '254         hm3.insert(i, i);')
+        self.debugger.cmd('diff hm3', 'no snapshot of `hm3`')
+        self.debugger.cmd('snapshot hm3', 'Snapshot taken, variables captured: 1')
+        self.debugger.cmd('continue', '254         hm3.insert(i, i);')
+        self.debugger.cmd('diff hm3', '+ hm3[0]: i32(0)')
+        self.debugger.cmd('diff hm3', 'hm3: no changes')
+
+    def test_print_elements_and_depth(self):
+        """Truncate printed vector elements and nested values"""
+        self.debugger.cmd('break vars.rs:149', 'New breakpoint')
+        self.debugger.cmd('run', '149     let slice2 = &[slice1, slice1];')
+        self.debugger.cmd('set print elements 2')
+        self.debugger.cmd(
+            'var vec1',
+            'vec1 = Vec<i32, alloc::alloc::Global> {',
+            'buf: [i32] {',
+            '0: i32(1)',
+            '1: i32(2)',
+            '(truncated)',
+            '}',
+        )
+        self.debugger.cmd('set print depth 1')
+        self.debugger.cmd(
+            'var vec1',
+            'vec1 = Vec<i32, alloc::alloc::Global> {',
+            'buf: [i32] {',
+            '(truncated)}',
+            'cap: usize(3)',
+        )
+
     def test_read_static_variables(self):
         """Reading rust static's"""
         self.debugger.cmd('break vars.rs:168', 'New breakpoint')
@@ -335,16 +377,16 @@ class VariablesTestCase(unittest.TestCase):
         """Reading rust tls variables"""
         self.debugger.cmd('break vars.rs:194', 'New breakpoint')
         self.debugger.cmd('run', '194         let nop: Option<u8> = None;')
-        self.debugger.cmd('var THREAD_LOCAL_VAR_1', 'vars::THREAD_LOCAL_VAR_1::__getit::__KEY = Cell<i32>(2)')
-        self.debugger.cmd('var THREAD_LOCAL_VAR_2', 'vars::THREAD_LOCAL_VAR_2::__getit::__KEY = Cell<&str>(2)')
+        self.debugger.cmd_re('var THREAD_LOCAL_VAR_1', r'vars::THREAD_LOCAL_VAR_1::\S* = Cell<i32>\(2\)')
+        self.debugger.cmd_re('var THREAD_LOCAL_VAR_2', r'vars::THREAD_LOCAL_VAR_2::\S* = Cell<&str>\(2\)')
         # assert uninit tls variables
         self.debugger.cmd('break vars.rs:199', 'New breakpoint')
         self.debugger.cmd('continue', '199         let nop: Option<u8> = None;')
-        self.debugger.cmd('var THREAD_LOCAL_VAR_1', 'vars::THREAD_LOCAL_VAR_1::__getit::__KEY = Cell<i32>(uninit)')
+        self.debugger.cmd_re('var THREAD_LOCAL_VAR_1', r'vars::THREAD_LOCAL_VAR_1::\S* = Cell<i32>\(uninit\)')
         # assert tls variables changes in another thread
         self.debugger.cmd('break vars.rs:203', 'New breakpoint')
         self.debugger.cmd('continue', '203     let nop: Option<u8> = None;')
-        self.debugger.cmd('var THREAD_LOCAL_VAR_1', 'vars::THREAD_LOCAL_VAR_1::__getit::__KEY = Cell<i32>(1)')
+        self.debugger.cmd_re('var THREAD_LOCAL_VAR_1', r'vars::THREAD_LOCAL_VAR_1::\S* = Cell<i32>\(1\)')
 
     def test_custom_select(self):
         """Reading memory by select expressions"""